@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use portfolio_backend::utils::markdown::{safe_markdown_to_html, sanitize_markdown_content};
+
+fuzz_target!(|data: &str| {
+    let html = safe_markdown_to_html(data);
+    assert!(!html.to_ascii_lowercase().contains("<script"));
+
+    // Sanitizing already-sanitized output must be a no-op
+    assert_eq!(sanitize_markdown_content(&html), html);
+});
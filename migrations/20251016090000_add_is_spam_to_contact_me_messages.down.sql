@@ -0,0 +1,5 @@
+-- Remove the spam flag and export index
+DROP INDEX IF EXISTS idx_contact_me_messages_created_id;
+
+ALTER TABLE contact_me_messages
+DROP COLUMN IF EXISTS is_spam;
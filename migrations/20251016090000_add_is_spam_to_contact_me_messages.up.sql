@@ -0,0 +1,8 @@
+-- Add up migration script here
+
+-- Spam flag used by admin filtering and exports
+ALTER TABLE contact_me_messages
+ADD COLUMN is_spam BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Keyset index for chunked exports ordered by (created_at, id)
+CREATE INDEX idx_contact_me_messages_created_id ON contact_me_messages (created_at, id) WHERE deleted_at IS NULL;
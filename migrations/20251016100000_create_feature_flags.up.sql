@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+-- Feature flags
+-- Runtime switches for experimental subsystems so they can ship dark
+-- and be enabled without a redeploy.
+CREATE TABLE feature_flags (
+    key TEXT PRIMARY KEY,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT feature_flags_key_format CHECK (key ~ '^[a-z0-9_-]{1,64}$')
+);
+
+-- Known experimental subsystems start disabled
+INSERT INTO feature_flags (key, description) VALUES
+    ('comments', 'Visitor comments on blog posts'),
+    ('guestbook', 'Public guestbook'),
+    ('newsletter', 'Newsletter sign-up and delivery')
+ON CONFLICT (key) DO NOTHING;
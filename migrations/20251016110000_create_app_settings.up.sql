@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+-- Runtime settings
+-- Admin-editable values that can change without a redeploy. Values are JSON
+-- so each setting can carry its own shape; the application validates known keys.
+CREATE TABLE app_settings (
+    key TEXT PRIMARY KEY,
+    value JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
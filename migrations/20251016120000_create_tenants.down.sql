@@ -0,0 +1,65 @@
+-- Add down migration script here
+
+-- Content from tenants other than the default cannot be represented without
+-- tenant_id, so it is removed before the columns are dropped.
+DELETE FROM contact_me_messages WHERE tenant_id <> '00000000-0000-0000-0000-000000000001';
+DELETE FROM blog_posts WHERE tenant_id <> '00000000-0000-0000-0000-000000000001';
+DELETE FROM about_me WHERE tenant_id <> '00000000-0000-0000-0000-000000000001';
+DELETE FROM app_settings WHERE tenant_id <> '00000000-0000-0000-0000-000000000001';
+DELETE FROM user_audit WHERE user_id IN (
+    SELECT id FROM users WHERE tenant_id <> '00000000-0000-0000-0000-000000000001'
+);
+DELETE FROM users WHERE tenant_id <> '00000000-0000-0000-0000-000000000001';
+
+CREATE OR REPLACE FUNCTION set_about_me_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Serialize concurrent inserts for the same effective_date
+    PERFORM pg_advisory_xact_lock(
+        'about_me'::regclass::int, 
+        (NEW.effective_date - DATE '2000-01-01')::int
+    );
+
+    -- Calculate next revision for this effective_date
+    NEW.revision := COALESCE((
+        SELECT MAX(revision) + 1
+        FROM about_me
+        WHERE effective_date = NEW.effective_date
+          AND deleted_at IS NULL
+    ), 1);
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX IF EXISTS idx_blog_posts_tenant_published_at;
+
+DROP INDEX idx_contact_me_messages_created_id;
+CREATE INDEX idx_contact_me_messages_created_id ON contact_me_messages (created_at, id) WHERE deleted_at IS NULL;
+
+ALTER TABLE app_settings DROP CONSTRAINT app_settings_pkey;
+ALTER TABLE app_settings ADD PRIMARY KEY (key);
+
+DROP INDEX idx_about_me_active;
+CREATE UNIQUE INDEX idx_about_me_active
+ON about_me (effective_date DESC, revision DESC)
+WHERE deleted_at IS NULL;
+
+DROP INDEX blog_posts_slug_active_idx;
+CREATE UNIQUE INDEX blog_posts_slug_active_idx
+ON blog_posts (LOWER(slug))
+WHERE deleted_at IS NULL;
+
+DROP INDEX unique_active_users_email;
+CREATE UNIQUE INDEX unique_active_users_email
+ON users (LOWER(email))
+WHERE deleted_at IS NULL;
+
+ALTER TABLE app_settings DROP COLUMN tenant_id;
+ALTER TABLE contact_me_messages DROP COLUMN tenant_id;
+ALTER TABLE blog_posts DROP COLUMN tenant_id;
+ALTER TABLE about_me DROP COLUMN tenant_id;
+ALTER TABLE users DROP COLUMN tenant_id;
+
+DROP TABLE IF EXISTS tenant_domains;
+DROP TABLE IF EXISTS tenants;
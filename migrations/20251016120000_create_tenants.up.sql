@@ -0,0 +1,92 @@
+-- Add up migration script here
+
+-- Tenants
+-- Each portfolio served by this instance. Requests are mapped to a tenant by
+-- their Host header through tenant_domains; content, users and settings are
+-- scoped by tenant_id.
+CREATE TABLE tenants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug TEXT NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9][a-z0-9-]{0,62}$'),
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE tenant_domains (
+    hostname TEXT PRIMARY KEY CHECK (hostname = LOWER(hostname)),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tenant_domains_tenant ON tenant_domains (tenant_id);
+
+-- Everything that exists today belongs to the default tenant
+INSERT INTO tenants (id, slug, name)
+VALUES ('00000000-0000-0000-0000-000000000001', 'default', 'Default');
+
+ALTER TABLE users ADD COLUMN tenant_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES tenants(id);
+ALTER TABLE about_me ADD COLUMN tenant_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES tenants(id);
+ALTER TABLE blog_posts ADD COLUMN tenant_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES tenants(id);
+ALTER TABLE contact_me_messages ADD COLUMN tenant_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES tenants(id);
+ALTER TABLE app_settings ADD COLUMN tenant_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES tenants(id);
+
+-- Backfill done; new rows must name their tenant explicitly
+ALTER TABLE users ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE about_me ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE blog_posts ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE contact_me_messages ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE app_settings ALTER COLUMN tenant_id DROP DEFAULT;
+
+-- Uniqueness is now per tenant
+DROP INDEX unique_active_users_email;
+CREATE UNIQUE INDEX unique_active_users_email
+ON users (tenant_id, LOWER(email))
+WHERE deleted_at IS NULL;
+
+DROP INDEX blog_posts_slug_active_idx;
+CREATE UNIQUE INDEX blog_posts_slug_active_idx
+ON blog_posts (tenant_id, LOWER(slug))
+WHERE deleted_at IS NULL;
+
+DROP INDEX idx_about_me_active;
+CREATE UNIQUE INDEX idx_about_me_active
+ON about_me (tenant_id, effective_date DESC, revision DESC)
+WHERE deleted_at IS NULL;
+
+ALTER TABLE app_settings DROP CONSTRAINT app_settings_pkey;
+ALTER TABLE app_settings ADD PRIMARY KEY (tenant_id, key);
+
+DROP INDEX idx_contact_me_messages_created_id;
+CREATE INDEX idx_contact_me_messages_created_id
+ON contact_me_messages (tenant_id, created_at, id)
+WHERE deleted_at IS NULL;
+
+CREATE INDEX idx_blog_posts_tenant_published_at ON blog_posts (tenant_id, published_at DESC);
+
+-- Revisions are numbered per tenant
+CREATE OR REPLACE FUNCTION set_about_me_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Serialize concurrent inserts for the same effective_date
+    PERFORM pg_advisory_xact_lock(
+        'about_me'::regclass::int, 
+        (NEW.effective_date - DATE '2000-01-01')::int
+    );
+
+    NEW.revision := COALESCE((
+        SELECT MAX(revision) + 1
+        FROM about_me
+        WHERE tenant_id = NEW.tenant_id
+          AND effective_date = NEW.effective_date
+          AND deleted_at IS NULL
+    ), 1);
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON TABLE tenants IS 'Portfolios hosted by this instance';
+COMMENT ON TABLE tenant_domains IS 'Host names (lowercase, without port) resolving to a tenant';
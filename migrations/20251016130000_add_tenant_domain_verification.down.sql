@@ -0,0 +1,13 @@
+-- Add down migration script here
+
+-- Unverified domains were never served; drop them rather than start serving them
+DELETE FROM tenant_domains WHERE verified_at IS NULL;
+
+DROP INDEX IF EXISTS idx_tenant_domains_pending;
+
+ALTER TABLE tenant_domains
+    DROP COLUMN verification_token,
+    DROP COLUMN verified_at,
+    DROP COLUMN last_checked_at,
+    DROP COLUMN last_error,
+    DROP COLUMN created_at;
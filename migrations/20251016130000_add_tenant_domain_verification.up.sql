@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+-- Custom domains are registered unverified and only served once the owner
+-- publishes the issued token in a DNS TXT record.
+ALTER TABLE tenant_domains
+    ADD COLUMN verification_token TEXT,
+    ADD COLUMN verified_at TIMESTAMPTZ,
+    ADD COLUMN last_checked_at TIMESTAMPTZ,
+    ADD COLUMN last_error TEXT,
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+-- Domains mapped before verification existed keep serving
+UPDATE tenant_domains
+SET verification_token = md5(random()::text || hostname),
+    verified_at = NOW();
+
+ALTER TABLE tenant_domains ALTER COLUMN verification_token SET NOT NULL;
+
+-- The verification job only scans pending domains
+CREATE INDEX idx_tenant_domains_pending ON tenant_domains (created_at)
+    WHERE verified_at IS NULL;
@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+-- Hire inquiries
+-- Structured "hire me" submissions, kept apart from contact_me_messages so
+-- they can carry budget/timeline and be triaged separately.
+CREATE TABLE hire_inquiries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    company TEXT NOT NULL,
+    role TEXT,
+    budget TEXT NOT NULL,
+    timeline TEXT NOT NULL,
+    message TEXT NOT NULL,
+    is_spam BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_hire_inquiries_tenant_created
+ON hire_inquiries (tenant_id, created_at DESC)
+WHERE deleted_at IS NULL;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+-- Uses page
+-- Gear and software entries, grouped by category on the public page.
+-- `position` orders entries across the whole page; a category appears where
+-- its first entry does.
+CREATE TABLE uses_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    category TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description_markdown TEXT NOT NULL DEFAULT '',
+    link TEXT,
+    image_url TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_uses_entries_tenant_position ON uses_entries (tenant_id, position, created_at);
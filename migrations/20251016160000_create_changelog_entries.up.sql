@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+-- Changelog
+-- "What's new" notes for the portfolio itself, written by hand or created
+-- by the deploy hook. A version is listed once per tenant.
+CREATE TABLE changelog_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    version TEXT NOT NULL,
+    released_on DATE NOT NULL DEFAULT CURRENT_DATE,
+    notes_markdown TEXT NOT NULL,
+    category TEXT NOT NULL CHECK (category IN ('feature', 'improvement', 'fix', 'content', 'deploy')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_changelog_entries_version ON changelog_entries (tenant_id, version);
+CREATE INDEX idx_changelog_entries_released ON changelog_entries (tenant_id, released_on DESC, created_at DESC);
@@ -0,0 +1,16 @@
+-- Add up migration script here
+
+-- Outbound link clicks
+-- One row per redirect through /api/v1/out. Referrers are stored hashed;
+-- source_path is only kept when the click came from one of our own pages.
+CREATE TABLE outbound_clicks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    target_url TEXT NOT NULL,
+    target_host TEXT NOT NULL,
+    source_path TEXT,
+    referrer_hash TEXT,
+    clicked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_outbound_clicks_tenant_clicked ON outbound_clicks (tenant_id, clicked_at DESC);
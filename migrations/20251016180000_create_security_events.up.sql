@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+-- Security events
+-- Append-only log of suspicious traffic. `kind` starts with honeytoken hits
+-- (scanners probing decoy paths like /wp-login.php); ip and user agent are
+-- kept as sent so repeat offenders can be grouped.
+CREATE TABLE security_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    ip TEXT NOT NULL,
+    user_agent TEXT,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_security_events_tenant_kind_occurred ON security_events (tenant_id, kind, occurred_at DESC);
@@ -0,0 +1,22 @@
+-- Add up migration script here
+
+-- Transactional outbox
+-- Rows are written in the same transaction as the domain change that
+-- triggers them, then delivered by the outbox relay. Delivery is
+-- at-least-once: a row is only marked delivered after its subscriber
+-- succeeds, and a claim lease expires if the relay dies mid-delivery.
+CREATE TABLE outbox_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}'::jsonb,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    failed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_outbox_events_due ON outbox_events (next_attempt_at)
+    WHERE delivered_at IS NULL AND failed_at IS NULL;
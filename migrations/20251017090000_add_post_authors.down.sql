@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_blog_posts_author;
+ALTER TABLE blog_posts DROP COLUMN IF EXISTS author_id;
+ALTER TABLE users
+    DROP COLUMN IF EXISTS avatar_url,
+    DROP COLUMN IF EXISTS display_name,
+    DROP COLUMN IF EXISTS is_editor;
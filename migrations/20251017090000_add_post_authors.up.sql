@@ -0,0 +1,25 @@
+-- Add up migration script here
+
+-- Post authorship
+-- Editors write posts and may change their own; admins may change any.
+-- Display name and avatar make up the public author profile shown on posts.
+ALTER TABLE users
+    ADD COLUMN is_editor BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN display_name VARCHAR(100),
+    ADD COLUMN avatar_url TEXT;
+
+-- Purged users leave their posts behind without an author
+ALTER TABLE blog_posts
+    ADD COLUMN author_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Only admins could write posts so far; credit each tenant's first admin
+UPDATE blog_posts p
+SET author_id = (
+    SELECT u.id FROM users u
+    WHERE u.tenant_id = p.tenant_id AND u.is_admin AND u.deleted_at IS NULL
+    ORDER BY u.created_at
+    LIMIT 1
+);
+
+CREATE INDEX idx_blog_posts_author ON blog_posts (tenant_id, author_id, published_at DESC)
+    WHERE deleted_at IS NULL;
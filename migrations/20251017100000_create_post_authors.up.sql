@@ -0,0 +1,22 @@
+-- Add up migration script here
+
+-- Post bylines
+-- Every author credited on a post, lead author first. `blog_posts.author_id`
+-- stays the owner who may edit the post; bylines only decide the credit.
+CREATE TABLE post_authors (
+    post_id UUID NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL CHECK (position >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, user_id),
+    UNIQUE (post_id, position)
+);
+
+CREATE INDEX idx_post_authors_user ON post_authors (tenant_id, user_id);
+
+-- Existing posts are credited to their owner alone
+INSERT INTO post_authors (post_id, user_id, tenant_id, position)
+SELECT id, author_id, tenant_id, 0
+FROM blog_posts
+WHERE author_id IS NOT NULL;
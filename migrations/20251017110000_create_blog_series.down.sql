@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS series_posts;
+DROP TABLE IF EXISTS blog_series;
@@ -0,0 +1,24 @@
+-- Add up migration script here
+
+-- Series
+-- Ordered collections of posts read one after another. A post belongs to at
+-- most one series so its previous/next links are never ambiguous.
+CREATE TABLE blog_series (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    title VARCHAR(120) NOT NULL,
+    slug VARCHAR(80) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, slug)
+);
+
+CREATE TABLE series_posts (
+    series_id UUID NOT NULL REFERENCES blog_series(id) ON DELETE CASCADE,
+    post_id UUID NOT NULL UNIQUE REFERENCES blog_posts(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL CHECK (position >= 0),
+    PRIMARY KEY (series_id, post_id),
+    UNIQUE (series_id, position)
+);
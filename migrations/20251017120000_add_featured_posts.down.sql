@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_blog_posts_featured;
+ALTER TABLE blog_posts
+    DROP COLUMN IF EXISTS featured_order,
+    DROP COLUMN IF EXISTS featured;
@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+-- Featured posts
+-- Curated by admins for the homepage, shown in `featured_order`
+ALTER TABLE blog_posts
+    ADD COLUMN featured BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN featured_order INTEGER;
+
+CREATE INDEX idx_blog_posts_featured ON blog_posts (tenant_id, featured_order)
+    WHERE featured AND deleted_at IS NULL;
@@ -0,0 +1,22 @@
+-- Add up migration script here
+
+-- Link checks
+-- Latest result for each external link found in published content. Rows are
+-- replaced on every run; links no longer in the content are dropped.
+CREATE TABLE link_checks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    source_type VARCHAR(20) NOT NULL CHECK (source_type IN ('post', 'about')),
+    source_id UUID NOT NULL,
+    source_title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    status_code INTEGER,
+    error TEXT,
+    broken BOOLEAN NOT NULL,
+    -- Start of the current failing streak; NULL while the link works
+    broken_since TIMESTAMPTZ,
+    checked_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (tenant_id, source_type, source_id, url)
+);
+
+CREATE INDEX idx_link_checks_broken ON link_checks (tenant_id) WHERE broken;
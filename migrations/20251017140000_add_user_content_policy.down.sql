@@ -0,0 +1,17 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_user_audit_user;
+DELETE FROM user_audit WHERE user_id IS NULL;
+ALTER TABLE user_audit
+    DROP CONSTRAINT user_audit_user_id_fkey,
+    DROP CONSTRAINT user_audit_performed_by_fkey,
+    ADD CONSTRAINT user_audit_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id),
+    ADD CONSTRAINT user_audit_performed_by_fkey FOREIGN KEY (performed_by) REFERENCES users(id),
+    ALTER COLUMN user_id SET NOT NULL,
+    DROP COLUMN IF EXISTS content_posts,
+    DROP COLUMN IF EXISTS content_policy;
+
+-- Posts handed to system users keep them as owner, so the users stay as
+-- ordinary accounts without a usable password
+DROP INDEX IF EXISTS unique_tenant_system_user;
+ALTER TABLE users DROP COLUMN IF EXISTS is_system;
@@ -0,0 +1,25 @@
+-- Add up migration script here
+
+-- Authored content on user deletion
+-- Depending on APP_USER_CONTENT_POLICY, a deleted user's posts move to the
+-- tenant's system user, lose their byline, or block the deletion. The system
+-- user has no usable password and is created on first use.
+ALTER TABLE users ADD COLUMN is_system BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE UNIQUE INDEX unique_tenant_system_user ON users (tenant_id)
+    WHERE is_system AND deleted_at IS NULL;
+
+-- Deletions record the policy applied and how many posts it touched. Audit
+-- rows outlive the purge of the users they mention.
+ALTER TABLE user_audit
+    ADD COLUMN content_policy TEXT,
+    ADD COLUMN content_posts INTEGER,
+    ALTER COLUMN user_id DROP NOT NULL,
+    DROP CONSTRAINT user_audit_user_id_fkey,
+    DROP CONSTRAINT user_audit_performed_by_fkey,
+    ADD CONSTRAINT user_audit_user_id_fkey
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL,
+    ADD CONSTRAINT user_audit_performed_by_fkey
+        FOREIGN KEY (performed_by) REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_user_audit_user ON user_audit (user_id, performed_at DESC);
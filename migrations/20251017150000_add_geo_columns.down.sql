@@ -0,0 +1,13 @@
+-- Add down migration script here
+
+ALTER TABLE contact_me_messages
+    DROP COLUMN IF EXISTS region,
+    DROP COLUMN IF EXISTS country_code;
+
+ALTER TABLE outbound_clicks
+    DROP COLUMN IF EXISTS region,
+    DROP COLUMN IF EXISTS country_code;
+
+ALTER TABLE security_events
+    DROP COLUMN IF EXISTS region,
+    DROP COLUMN IF EXISTS country_code;
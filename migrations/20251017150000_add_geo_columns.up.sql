@@ -0,0 +1,17 @@
+-- Add up migration script here
+
+-- Country and region of the client IP
+-- Filled from the GeoLite2 database when APP_GEOIP_DB_PATH is set, NULL
+-- otherwise. country_code is ISO 3166-1 alpha-2, region the ISO code of the
+-- first subdivision (state, province).
+ALTER TABLE security_events
+    ADD COLUMN country_code TEXT,
+    ADD COLUMN region TEXT;
+
+ALTER TABLE outbound_clicks
+    ADD COLUMN country_code TEXT,
+    ADD COLUMN region TEXT;
+
+ALTER TABLE contact_me_messages
+    ADD COLUMN country_code TEXT,
+    ADD COLUMN region TEXT;
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+ALTER TABLE contact_me_messages DROP COLUMN IF EXISTS anonymized_at;
+
+DROP TABLE IF EXISTS audit_log;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+-- Audit log
+-- Administrative and background actions worth keeping a record of, e.g. how
+-- many rows a retention run purged. `actor` is an admin's user id or
+-- `system` for scheduled jobs.
+CREATE TABLE audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    actor TEXT NOT NULL,
+    action TEXT NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_tenant_created ON audit_log (tenant_id, created_at DESC);
+
+-- Contact messages past their retention period can be anonymized instead of
+-- deleted; anonymized rows keep their dates and country only
+ALTER TABLE contact_me_messages ADD COLUMN anonymized_at TIMESTAMPTZ;
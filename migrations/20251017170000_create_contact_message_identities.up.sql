@@ -0,0 +1,16 @@
+-- Add up migration script here
+
+-- Contact message identities
+-- Name and email of anonymized contact messages, moved here so the
+-- anonymization can be undone until the `anonymized_identities` retention
+-- policy purges them. Deleting the message deletes its identity too.
+CREATE TABLE contact_message_identities (
+    message_id UUID PRIMARY KEY REFERENCES contact_me_messages(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    anonymized_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_contact_message_identities_tenant_anonymized
+    ON contact_message_identities (tenant_id, anonymized_at);
@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+-- Notification preferences
+-- Per-admin choice of channels (email, webhook, sse) for each notification
+-- event type. `channels` maps an event type to its channel list; events left
+-- out use the default. Admins without a row get the default for everything.
+CREATE TABLE notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    webhook_url TEXT,
+    channels JSONB NOT NULL DEFAULT '{}'::jsonb,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notification_preferences_tenant ON notification_preferences (tenant_id);
@@ -0,0 +1,11 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_audit_log_details_search;
+DROP INDEX IF EXISTS idx_audit_log_tenant_entity;
+DROP INDEX IF EXISTS idx_audit_log_tenant_action;
+DROP INDEX IF EXISTS idx_audit_log_tenant_actor;
+
+ALTER TABLE audit_log
+    DROP COLUMN IF EXISTS result,
+    DROP COLUMN IF EXISTS entity_id,
+    DROP COLUMN IF EXISTS entity_type;
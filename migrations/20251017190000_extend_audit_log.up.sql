@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+-- Audit log search
+-- What an entry is about and whether the action succeeded, plus indexes for
+-- the admin audit log filters and full-text search over `details`.
+ALTER TABLE audit_log
+    ADD COLUMN entity_type TEXT,
+    ADD COLUMN entity_id TEXT,
+    ADD COLUMN result TEXT NOT NULL DEFAULT 'success' CHECK (result IN ('success', 'failure'));
+
+CREATE INDEX idx_audit_log_tenant_actor ON audit_log (tenant_id, actor, created_at DESC);
+CREATE INDEX idx_audit_log_tenant_action ON audit_log (tenant_id, action, created_at DESC);
+CREATE INDEX idx_audit_log_tenant_entity ON audit_log (tenant_id, entity_type, entity_id);
+CREATE INDEX idx_audit_log_details_search
+    ON audit_log USING GIN (jsonb_to_tsvector('simple', details, '["string", "numeric"]'));
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TRIGGER IF EXISTS post_revision_trigger ON blog_posts;
+DROP FUNCTION IF EXISTS record_post_revision();
+DROP TABLE IF EXISTS post_revisions;
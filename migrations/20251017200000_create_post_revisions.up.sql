@@ -0,0 +1,68 @@
+-- Add up migration script here
+
+-- Post revisions
+-- A snapshot of a post's content and metadata each time one of them changes,
+-- numbered from 1 per post. Filled by a trigger so every write path is
+-- covered; edits that only touch bookkeeping columns (updated_at, featured,
+-- deleted_at, ...) add no revision.
+CREATE TABLE post_revisions (
+    post_id UUID NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    excerpt TEXT NOT NULL,
+    content_markdown TEXT NOT NULL,
+    cover_image_url TEXT,
+    tags TEXT[],
+    seo_title TEXT,
+    seo_description TEXT,
+    published BOOLEAN NOT NULL,
+    published_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, revision)
+);
+
+CREATE OR REPLACE FUNCTION record_post_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' AND (
+        NEW.title, NEW.slug, NEW.excerpt, NEW.content_markdown, NEW.cover_image_url, NEW.tags,
+        NEW.seo_title, NEW.seo_description, NEW.published, NEW.published_at
+    ) IS NOT DISTINCT FROM (
+        OLD.title, OLD.slug, OLD.excerpt, OLD.content_markdown, OLD.cover_image_url, OLD.tags,
+        OLD.seo_title, OLD.seo_description, OLD.published, OLD.published_at
+    ) THEN
+        RETURN NULL;
+    END IF;
+
+    -- The row lock on the post serializes writers, so MAX + 1 is safe
+    INSERT INTO post_revisions (
+        post_id, revision, tenant_id, title, slug, excerpt, content_markdown, cover_image_url,
+        tags, seo_title, seo_description, published, published_at
+    )
+    SELECT
+        NEW.id, COALESCE(MAX(revision), 0) + 1, NEW.tenant_id, NEW.title, NEW.slug, NEW.excerpt,
+        NEW.content_markdown, NEW.cover_image_url, NEW.tags, NEW.seo_title, NEW.seo_description,
+        NEW.published, NEW.published_at
+    FROM post_revisions
+    WHERE post_id = NEW.id;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER post_revision_trigger
+AFTER INSERT OR UPDATE ON blog_posts
+FOR EACH ROW
+EXECUTE FUNCTION record_post_revision();
+
+-- Existing posts start at revision 1, as they stand now
+INSERT INTO post_revisions (
+    post_id, revision, tenant_id, title, slug, excerpt, content_markdown, cover_image_url,
+    tags, seo_title, seo_description, published, published_at, created_at
+)
+SELECT
+    id, 1, tenant_id, title, slug, excerpt, content_markdown, cover_image_url,
+    tags, seo_title, seo_description, published, published_at, updated_at
+FROM blog_posts;
@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+-- Reading progress
+-- The furthest a reader scrolled into a post, in 10% buckets. One row per
+-- post and browser session; only a hash of the session id is stored. Rows
+-- are purged with the rest of the analytics by the retention policy.
+CREATE TABLE reading_progress (
+    post_id UUID NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    session_hash TEXT NOT NULL,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    max_percent SMALLINT NOT NULL CHECK (max_percent BETWEEN 0 AND 100 AND max_percent % 10 = 0),
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, session_hash)
+);
+
+CREATE INDEX idx_reading_progress_tenant_started ON reading_progress (tenant_id, started_at);
+CREATE INDEX idx_reading_progress_tenant_updated ON reading_progress (tenant_id, updated_at);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS title_variant_stats;
+DROP TABLE IF EXISTS post_title_variants;
@@ -0,0 +1,33 @@
+-- Add up migration script here
+
+-- Title tests
+-- Alternative titles a post is shown with on list endpoints, picked per
+-- response in proportion to `weight`. Variant 0 stands for the post's own
+-- title and has no text of its own; variants 1 and 2 are the alternatives.
+CREATE TABLE post_title_variants (
+    post_id UUID NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    variant SMALLINT NOT NULL CHECK (variant BETWEEN 0 AND 2),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    title TEXT,
+    weight SMALLINT NOT NULL CHECK (weight BETWEEN 0 AND 100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, variant),
+    CHECK ((variant = 0) = (title IS NULL))
+);
+
+CREATE INDEX idx_post_title_variants_tenant ON post_title_variants (tenant_id);
+
+-- Impressions and clicks per variant and UTC day. Dropped with the test;
+-- older days are purged with the rest of the analytics.
+CREATE TABLE title_variant_stats (
+    post_id UUID NOT NULL,
+    variant SMALLINT NOT NULL,
+    day DATE NOT NULL,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    impressions BIGINT NOT NULL DEFAULT 0,
+    clicks BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (post_id, variant, day),
+    FOREIGN KEY (post_id, variant) REFERENCES post_title_variants (post_id, variant) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_title_variant_stats_tenant_day ON title_variant_stats (tenant_id, day);
@@ -0,0 +1,24 @@
+-- Add up migration script here
+
+-- API tokens
+-- Read-only tokens admins issue per site for the token protected public
+-- routes. Only a hash of the token is kept; the prefix identifies it in
+-- reports. Usage counters are flushed from memory periodically.
+CREATE TABLE api_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    name VARCHAR(64) NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    token_prefix VARCHAR(16) NOT NULL,
+    scopes TEXT[] NOT NULL CHECK (cardinality(scopes) > 0),
+    -- NULL uses the configured default
+    rate_limit_per_minute INTEGER CHECK (rate_limit_per_minute > 0),
+    request_count BIGINT NOT NULL DEFAULT 0,
+    rejected_count BIGINT NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_api_tokens_tenant ON api_tokens (tenant_id, created_at);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS country_rollups;
+DROP TABLE IF EXISTS referrer_rollups;
+DROP TABLE IF EXISTS outbound_click_rollups;
+DROP TABLE IF EXISTS post_read_rollups;
@@ -0,0 +1,55 @@
+-- Add up migration script here
+
+-- Analytics rollups
+-- Daily and monthly aggregates of the raw analytics tables, written by the
+-- rollup job. period_start is the UTC day, or the first day of the UTC month
+-- for monthly rows, which are summed from the daily ones. Rollups outlive
+-- the raw rows, so stats for purged periods stay available.
+
+-- Readers per post by furthest scroll depth, from reading_progress.started_at
+CREATE TABLE post_read_rollups (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    period TEXT NOT NULL CHECK (period IN ('day', 'month')),
+    period_start DATE NOT NULL,
+    post_id UUID NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    max_percent SMALLINT NOT NULL,
+    readers BIGINT NOT NULL,
+    PRIMARY KEY (tenant_id, period, period_start, post_id, max_percent)
+);
+
+-- Clicks per outbound link; unique_referrers counts a referrer once per day
+CREATE TABLE outbound_click_rollups (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    period TEXT NOT NULL CHECK (period IN ('day', 'month')),
+    period_start DATE NOT NULL,
+    target_url TEXT NOT NULL,
+    target_host TEXT NOT NULL,
+    clicks BIGINT NOT NULL,
+    unique_referrers BIGINT NOT NULL,
+    last_clicked_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tenant_id, period, period_start, target_url)
+);
+
+-- Outbound clicks per page of our own they were clicked on
+CREATE TABLE referrer_rollups (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    period TEXT NOT NULL CHECK (period IN ('day', 'month')),
+    period_start DATE NOT NULL,
+    source_path TEXT NOT NULL,
+    clicks BIGINT NOT NULL,
+    PRIMARY KEY (tenant_id, period, period_start, source_path)
+);
+
+-- Outbound clicks ('clicks') and honeytoken hits ('attacks') per country;
+-- a NULL country is traffic that could not be located
+CREATE TABLE country_rollups (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    period TEXT NOT NULL CHECK (period IN ('day', 'month')),
+    period_start DATE NOT NULL,
+    source TEXT NOT NULL CHECK (source IN ('clicks', 'attacks')),
+    country_code TEXT,
+    count BIGINT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_country_rollups_key
+    ON country_rollups (tenant_id, period, period_start, source, COALESCE(country_code, ''));
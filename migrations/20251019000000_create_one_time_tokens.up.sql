@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+-- One-time tokens
+-- Email verification and password reset links. Only a hash of the token is
+-- kept; Redis caches valid tokens but this table decides. A token is spent
+-- by setting used_at, and issuing a new one spends the user's earlier
+-- tokens for the same purpose. Expired rows are deleted periodically.
+CREATE TABLE one_time_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose VARCHAR(32) NOT NULL CHECK (purpose IN ('email_verification', 'password_reset')),
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_one_time_tokens_user ON one_time_tokens (user_id, purpose) WHERE used_at IS NULL;
+CREATE INDEX idx_one_time_tokens_expires_at ON one_time_tokens (expires_at);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+DELETE FROM feature_flags WHERE key IN ('captcha_register', 'captcha_contact', 'captcha_guestbook');
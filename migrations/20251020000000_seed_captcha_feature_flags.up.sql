@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+-- Captcha per form
+-- Only checked when CAPTCHA_PROVIDER is configured, so these start enabled and
+-- turning one off exempts that form.
+INSERT INTO feature_flags (key, enabled, description) VALUES
+    ('captcha_register', TRUE, 'Require a captcha on registration'),
+    ('captcha_contact', TRUE, 'Require a captcha on the contact form'),
+    ('captcha_guestbook', TRUE, 'Require a captcha on guestbook entries')
+ON CONFLICT (key) DO NOTHING;
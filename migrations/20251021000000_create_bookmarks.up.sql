@@ -0,0 +1,30 @@
+-- Add up migration script here
+
+-- Bookmarks
+-- Links saved for the public blogroll. The preview columns are filled in by
+-- the background fetcher from the page's <title>, description and OG tags;
+-- a fetch is retried until preview_attempts reaches the limit.
+CREATE TABLE bookmarks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    title TEXT,
+    note TEXT,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    is_public BOOLEAN NOT NULL DEFAULT TRUE,
+    preview_status TEXT NOT NULL DEFAULT 'pending' CHECK (preview_status IN ('pending', 'ready', 'failed')),
+    preview_title TEXT,
+    preview_description TEXT,
+    preview_image_url TEXT,
+    preview_site_name TEXT,
+    preview_error TEXT,
+    preview_attempts INTEGER NOT NULL DEFAULT 0,
+    preview_fetched_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_bookmarks_url ON bookmarks (tenant_id, url);
+CREATE INDEX idx_bookmarks_public ON bookmarks (tenant_id, created_at DESC) WHERE is_public;
+CREATE INDEX idx_bookmarks_tags ON bookmarks USING GIN (tags);
+CREATE INDEX idx_bookmarks_pending ON bookmarks (created_at) WHERE preview_status = 'pending';
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS api_token_usage_daily;
+ALTER TABLE api_tokens DROP COLUMN IF EXISTS monthly_quota;
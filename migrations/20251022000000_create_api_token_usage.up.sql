@@ -0,0 +1,19 @@
+-- Add up migration script here
+
+-- Optional hard limit on a token's requests per calendar month (UTC)
+ALTER TABLE api_tokens ADD COLUMN monthly_quota BIGINT CHECK (monthly_quota > 0);
+
+-- API token usage per day
+-- Counted in memory, aggregated across instances in Redis when configured
+-- and flushed here with the token refresh. With Redis the rows hold the
+-- day's totals from Redis; without it each instance adds its own counts.
+CREATE TABLE api_token_usage_daily (
+    token_id UUID NOT NULL REFERENCES api_tokens(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+    -- Response bodies of known size
+    bytes BIGINT NOT NULL DEFAULT 0,
+    -- Responses with a 4xx or 5xx status
+    errors BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (token_id, day)
+);
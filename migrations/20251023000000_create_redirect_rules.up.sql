@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+-- Redirect rules
+-- Old URLs, e.g. from a previous blog engine, sent to their new location by
+-- the 404 fallback. An exact rule matches one path; a pattern rule is a
+-- regex matched against the whole path whose captures can be used in the
+-- target. Hits are counted in memory and added here periodically.
+CREATE TABLE redirect_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('exact', 'pattern')),
+    source TEXT NOT NULL,
+    target TEXT NOT NULL,
+    status_code INTEGER NOT NULL DEFAULT 301 CHECK (status_code IN (301, 302, 307, 308)),
+    hits BIGINT NOT NULL DEFAULT 0,
+    last_hit_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_redirect_rules_source ON redirect_rules (tenant_id, kind, source);
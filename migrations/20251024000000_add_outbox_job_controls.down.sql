@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS outbox_paused_queues;
+
+DROP INDEX IF EXISTS idx_outbox_events_tenant;
+
+ALTER TABLE outbox_events
+    DROP COLUMN IF EXISTS cancelled_at,
+    DROP COLUMN IF EXISTS leased_until;
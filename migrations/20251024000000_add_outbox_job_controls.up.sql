@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+-- Job admin controls for the outbox
+-- leased_until is set while a relay holds an event, so running events can
+-- be told apart from ones waiting out a retry. Cancelled events are parked
+-- like failed ones (failed_at is set too) and can be retried the same way.
+ALTER TABLE outbox_events
+    ADD COLUMN leased_until TIMESTAMPTZ,
+    ADD COLUMN cancelled_at TIMESTAMPTZ;
+
+CREATE INDEX idx_outbox_events_tenant ON outbox_events (tenant_id, created_at DESC);
+
+-- Event types an admin has paused for their site; the relay leaves their
+-- events queued until the queue is resumed
+CREATE TABLE outbox_paused_queues (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    paused_by TEXT,
+    paused_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tenant_id, event_type)
+);
@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Contact auto-replies
+-- When each sender last got an automatic acknowledgment, so an address (or
+-- a mail loop) gets at most one per throttle window. Only a SHA-256 of the
+-- lowercased address is kept.
+CREATE TABLE contact_auto_replies (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    email_hash TEXT NOT NULL,
+    last_sent_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tenant_id, email_hash)
+);
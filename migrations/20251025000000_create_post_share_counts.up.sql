@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+-- Post share counts
+-- Latest share count per post and network, as reported by the network's own
+-- API. A network that is down or rate limiting us keeps its previous count.
+CREATE TABLE post_share_counts (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    post_id UUID NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    network VARCHAR(20) NOT NULL,
+    shares BIGINT NOT NULL CHECK (shares >= 0),
+    fetched_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (post_id, network)
+);
+
+CREATE INDEX idx_post_share_counts_tenant ON post_share_counts (tenant_id);
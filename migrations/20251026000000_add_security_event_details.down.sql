@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+ALTER TABLE security_events
+    DROP COLUMN IF EXISTS details;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+
+-- What a security event found, when its kind has more to say than the path
+-- (e.g. the content scanner's findings); NULL for honeytoken hits
+ALTER TABLE security_events
+    ADD COLUMN details JSONB;
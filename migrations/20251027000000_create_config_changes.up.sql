@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+-- Config changes
+-- One row per admin change to a runtime setting or feature flag, numbered by
+-- `id` so any point in the history can be rolled back to. Feature flags are
+-- shared by every tenant, so their rows have no tenant.
+CREATE TABLE config_changes (
+    id BIGSERIAL PRIMARY KEY,
+    tenant_id UUID REFERENCES tenants(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('setting', 'feature_flag')),
+    key TEXT NOT NULL,
+    -- NULL when the key was unset before / is removed by the change
+    previous_value JSONB,
+    new_value JSONB,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- Set on changes made by rolling back to that version
+    rollback_to BIGINT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_config_changes_tenant ON config_changes (tenant_id, id DESC);
@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP TRIGGER IF EXISTS blog_posts_search_sync ON blog_posts;
+DROP FUNCTION IF EXISTS blog_post_search_sync();
+DROP FUNCTION IF EXISTS post_reading_minutes(TEXT);
+DROP FUNCTION IF EXISTS post_search_vector(TEXT, TEXT, TEXT);
+DROP TABLE IF EXISTS blog_post_search;
+DROP TABLE IF EXISTS backfills;
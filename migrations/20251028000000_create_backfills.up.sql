@@ -0,0 +1,67 @@
+-- Add up migration script here
+
+-- Backfills
+-- Progress of each background backfill, keyed by the task name declared in
+-- code. `checkpoint` is the last row id done, committed with its batch, so
+-- a restart carries on from there.
+CREATE TABLE backfills (
+    name TEXT PRIMARY KEY,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'completed')),
+    checkpoint UUID,
+    processed BIGINT NOT NULL DEFAULT 0,
+    -- Rows pending when the task started; NULL until then
+    total BIGINT,
+    last_error TEXT,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Blog post search documents
+-- Derived from the post's text: a weighted search vector and the reading
+-- time. Kept current by the trigger below; rows for posts written before
+-- this migration are filled by the `post_search_documents` backfill.
+CREATE TABLE blog_post_search (
+    post_id UUID PRIMARY KEY REFERENCES blog_posts(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    search_vector TSVECTOR NOT NULL,
+    reading_time_minutes INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_blog_post_search_vector ON blog_post_search USING GIN (search_vector);
+
+CREATE FUNCTION post_search_vector(title TEXT, excerpt TEXT, content TEXT) RETURNS TSVECTOR
+LANGUAGE SQL IMMUTABLE AS $$
+    SELECT setweight(to_tsvector('simple', COALESCE(title, '')), 'A')
+        || setweight(to_tsvector('simple', COALESCE(excerpt, '')), 'B')
+        || setweight(to_tsvector('simple', COALESCE(content, '')), 'C')
+$$;
+
+-- 200 words a minute, at least one
+CREATE FUNCTION post_reading_minutes(content TEXT) RETURNS INTEGER
+LANGUAGE SQL IMMUTABLE AS $$
+    SELECT GREATEST(1, CEIL(COALESCE(array_length(regexp_split_to_array(btrim(content), '\s+'), 1), 0) / 200.0))::INTEGER
+$$;
+
+CREATE FUNCTION blog_post_search_sync() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    INSERT INTO blog_post_search (post_id, tenant_id, search_vector, reading_time_minutes)
+    VALUES (
+        NEW.id,
+        NEW.tenant_id,
+        post_search_vector(NEW.title, NEW.excerpt, NEW.content_markdown),
+        post_reading_minutes(NEW.content_markdown)
+    )
+    ON CONFLICT (post_id) DO UPDATE SET
+        search_vector = EXCLUDED.search_vector,
+        reading_time_minutes = EXCLUDED.reading_time_minutes,
+        updated_at = NOW();
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER blog_posts_search_sync
+    AFTER INSERT OR UPDATE OF title, excerpt, content_markdown ON blog_posts
+    FOR EACH ROW EXECUTE FUNCTION blog_post_search_sync();
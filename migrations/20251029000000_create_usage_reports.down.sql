@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS email_send_counts;
+DROP TABLE IF EXISTS usage_reports;
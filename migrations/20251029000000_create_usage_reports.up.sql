@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+-- Usage reports
+-- The last snapshot the usage collector took of each site: storage, row
+-- counts, Redis memory and emails sent. Replaced on every run.
+CREATE TABLE usage_reports (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id) ON DELETE CASCADE,
+    report JSONB NOT NULL,
+    collected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Email sends
+-- Emails handed to the mailer per calendar month, for the whole instance.
+-- Each instance adds what it sent since its last collector run.
+CREATE TABLE email_send_counts (
+    month DATE PRIMARY KEY,
+    sent BIGINT NOT NULL DEFAULT 0,
+    failed BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+-- Add up migration script here
+
+-- Quota alerts
+-- Thresholds of a quota the admins were told about, per period, so each
+-- is raised at most once per calendar month whichever instance collects.
+CREATE TABLE quota_alerts (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    quota TEXT NOT NULL,
+    period DATE NOT NULL,
+    threshold SMALLINT NOT NULL,
+    raised_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tenant_id, quota, period, threshold)
+);
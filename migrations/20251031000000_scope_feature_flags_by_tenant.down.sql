@@ -0,0 +1,15 @@
+-- Add down migration script here
+
+-- Shared flags cannot hold per-tenant values, so the default tenant's are kept
+ALTER TABLE config_changes ALTER COLUMN tenant_id DROP NOT NULL;
+DELETE FROM config_changes
+WHERE kind = 'feature_flag' AND tenant_id <> '00000000-0000-0000-0000-000000000001';
+UPDATE config_changes SET tenant_id = NULL WHERE kind = 'feature_flag';
+
+DROP TRIGGER IF EXISTS tenants_copy_feature_flags ON tenants;
+DROP FUNCTION IF EXISTS copy_default_feature_flags();
+
+DELETE FROM feature_flags WHERE tenant_id <> '00000000-0000-0000-0000-000000000001';
+ALTER TABLE feature_flags DROP CONSTRAINT feature_flags_pkey;
+ALTER TABLE feature_flags ADD PRIMARY KEY (key);
+ALTER TABLE feature_flags DROP COLUMN tenant_id;
@@ -0,0 +1,45 @@
+-- Add up migration script here
+
+-- Feature flags per tenant
+-- Flags were shared by every tenant, so one site's admin could toggle them
+-- for all. Each tenant now has its own copy, starting from the flags as they
+-- are today.
+ALTER TABLE feature_flags ADD COLUMN tenant_id UUID NOT NULL
+    DEFAULT '00000000-0000-0000-0000-000000000001' REFERENCES tenants(id) ON DELETE CASCADE;
+ALTER TABLE feature_flags ALTER COLUMN tenant_id DROP DEFAULT;
+
+ALTER TABLE feature_flags DROP CONSTRAINT feature_flags_pkey;
+ALTER TABLE feature_flags ADD PRIMARY KEY (tenant_id, key);
+
+INSERT INTO feature_flags (tenant_id, key, enabled, description, created_at, updated_at)
+SELECT t.id, f.key, f.enabled, f.description, f.created_at, f.updated_at
+FROM tenants t
+CROSS JOIN feature_flags f
+WHERE t.id <> '00000000-0000-0000-0000-000000000001'
+  AND f.tenant_id = '00000000-0000-0000-0000-000000000001';
+
+-- Tenants added later start from the default tenant's flags, so flags seeded
+-- on (e.g. captcha_contact) are on for them too
+CREATE OR REPLACE FUNCTION copy_default_feature_flags()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO feature_flags (tenant_id, key, enabled, description)
+    SELECT NEW.id, key, enabled, description
+    FROM feature_flags
+    WHERE tenant_id = '00000000-0000-0000-0000-000000000001'
+    ON CONFLICT (tenant_id, key) DO NOTHING;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tenants_copy_feature_flags
+AFTER INSERT ON tenants
+FOR EACH ROW
+EXECUTE FUNCTION copy_default_feature_flags();
+
+-- Flag changes so far were made on the shared flags, which are now the
+-- default tenant's
+UPDATE config_changes SET tenant_id = '00000000-0000-0000-0000-000000000001'
+WHERE tenant_id IS NULL;
+ALTER TABLE config_changes ALTER COLUMN tenant_id SET NOT NULL;
@@ -1,9 +1,23 @@
+use chrono::Utc;
 use tokio::time::{interval, Duration};
 
-use crate::repositories::{sqlx_repo::SqlxUserRepo, user::UserRepository};
+use crate::{
+    cache::redis_pool::SupervisedPool,
+    shared_repos::{
+        DynAboutRepo, DynApiTokenRepo, DynAppSettingsRepo, DynAuditLogRepo, DynBackfillRepo, DynBlogPostRepo, DynBookmarkRepo, DynContactRepo, DynFeatureFlagRepo, DynLinkCheckRepo, DynOneTimeTokenRepo, DynOutboxRepo,
+        DynRedirectRuleRepo, DynRetentionRepo, DynShareCountRepo, DynStatsRollupRepo, DynTenantRepo, DynTitleTestRepo, DynUsageRepo, DynUserRepo,
+    },
+    entities::audit::AUDIT_ACTOR_SYSTEM,
+    reporting::{reporter::report_job_failure, sentry::SentryTransport},
+    use_cases::{
+        api_tokens::ApiTokens, backfills::Backfills, bookmarks::{BookmarkHandler, PREVIEW_BATCH_SIZE}, contact::ContactMeHandler, domains::DomainVerifier, ingest::QueuedContact, feature_flags::FeatureFlags, link_checks::LinkChecker, notifications::ContactNotifier,
+        one_time_tokens::OneTimeTokens, outbox::OutboxRelay, quota_alerts::QuotaAlerts, redirects::RedirectRules, retention::DataRetention, share_counts::ShareCounts, stats_rollups::StatsRollups, tag_suggestions::TagSuggester,
+        tenants::TenantResolver, title_tests::TitleTests, usage::UsageReports,
+    },
+};
 
 pub async fn start_purge_task(
-    repo: SqlxUserRepo,
+    repo: DynUserRepo,
     mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
 ) {
     let mut interval = interval(Duration::from_secs(60 * 60 * 24));
@@ -17,7 +31,10 @@ pub async fn start_purge_task(
             _ = interval.tick() => {
                 match repo.purge_soft_deleted_users().await {
                     Ok(count) => tracing::info!("Purged {} soft-deleted users", count),
-                    Err(e) => tracing::error!("Purge failed: {}", e),
+                    Err(e) => {
+                        tracing::error!("Purge failed: {}", e);
+                        report_job_failure("purge", None, &e);
+                    }
                 }
             }
             _ = shutdown_rx.recv() => {
@@ -26,4 +43,657 @@ pub async fn start_purge_task(
             }
         }
     }
+}
+
+/// Periodically reloads feature flags so toggles made on another instance propagate
+pub async fn start_feature_flag_refresh_task(
+    flags: FeatureFlags<DynFeatureFlagRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(30));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Flags are loaded at startup; skip the immediate first tick
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = flags.refresh().await {
+                    tracing::warn!("Feature flag refresh failed: {}", e);
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Feature flag refresh task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Periodically reloads running title tests so ones started on another instance are served
+pub async fn start_title_test_refresh_task(
+    title_tests: TitleTests<DynTitleTestRepo, DynBlogPostRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(30));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Tests are loaded at startup; skip the immediate first tick
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = title_tests.refresh().await {
+                    tracing::warn!("Title test refresh failed: {}", e);
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Title test refresh task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Periodically flushes API token usage and reloads the issued tokens, so
+/// ones issued or revoked on another instance take effect; flushes once more
+/// on shutdown
+pub async fn start_api_token_refresh_task(
+    api_tokens: ApiTokens<DynApiTokenRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(30));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Tokens are loaded at startup; skip the immediate first tick
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = api_tokens.refresh().await {
+                    tracing::warn!("API token refresh failed: {}", e);
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                if let Err(e) = api_tokens.flush_usage().await {
+                    tracing::warn!("Final API token usage flush failed: {}", e);
+                }
+                tracing::info!("API token refresh task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Periodically saves redirect hits and reloads the rules, so changes made
+/// on another instance take effect; saves hits once more on shutdown
+pub async fn start_redirect_refresh_task(
+    redirects: RedirectRules<DynRedirectRuleRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Rules are loaded at startup; skip the immediate first tick
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = redirects.refresh().await {
+                    tracing::warn!("Redirect rule refresh failed: {}", e);
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                if let Err(e) = redirects.flush_hits().await {
+                    tracing::warn!("Final redirect hit flush failed: {}", e);
+                }
+                tracing::info!("Redirect refresh task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Periodically reloads tenants and their hosts so new domains are picked up without a restart
+pub async fn start_tenant_refresh_task(
+    tenants: TenantResolver<DynTenantRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Tenants are loaded at startup; skip the immediate first tick
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = tenants.refresh().await {
+                    tracing::warn!("Tenant refresh failed: {}", e);
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Tenant refresh task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Re-checks DNS for custom domains that are still pending verification
+pub async fn start_domain_verification_task(
+    domains: DomainVerifier<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match domains.verify_pending().await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Verified {} custom domain(s)", count),
+                    Err(e) => {
+                        tracing::warn!("Domain verification run failed: {}", e);
+                        report_job_failure("domain_verification", None, &e);
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Domain verification task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Refreshes runtime settings and sends each tenant's contact digest when its policy says one is due
+pub async fn start_contact_digest_task(
+    notifier: ContactNotifier<DynContactRepo, DynAppSettingsRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(5 * 60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = notifier.settings.refresh().await {
+                    tracing::warn!("Runtime settings refresh failed: {}", e);
+                }
+
+                for tenant in tenants.all() {
+                    match notifier.send_digest_if_due(&tenant).await {
+                        Ok(Some(count)) => {
+                            tracing::info!(tenant = %tenant.slug, "Contact digest covered {} message(s)", count)
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            tracing::error!(tenant = %tenant.slug, "Contact digest failed: {}", e);
+                            report_job_failure("contact_digest", Some(&tenant.slug), &e);
+                        }
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Contact digest task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Delivers pending outbox events on a timer, or right away when a request nudges the relay
+pub async fn start_outbox_relay_task(
+    relay: OutboxRelay<DynOutboxRepo, DynTenantRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(15));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = relay.woken() => {}
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Outbox relay task shutting down gracefully");
+                break;
+            }
+        }
+
+        match relay.relay_due().await {
+            Ok(outcome) if outcome.delivered + outcome.retrying + outcome.failed > 0 => tracing::info!(
+                delivered = outcome.delivered,
+                retrying = outcome.retrying,
+                failed = outcome.failed,
+                "Outbox relay pass finished"
+            ),
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Outbox relay pass failed: {}", e);
+                report_job_failure("outbox_relay", None, &e);
+            }
+        }
+    }
+}
+
+/// Writes contact messages queued by the contact endpoint, one at a time.
+/// On shutdown the queue is closed and messages already accepted are still written.
+pub async fn start_contact_ingest_task(
+    contacts: ContactMeHandler<DynContactRepo>,
+    relay: OutboxRelay<DynOutboxRepo, DynTenantRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let Some(mut queue) = contacts.queue.take_receiver() else {
+        tracing::warn!("Contact ingest task already running");
+        return;
+    };
+
+    loop {
+        tokio::select! {
+            job = queue.recv() => {
+                let Some(job) = job else { break };
+                // The notification was enqueued with the message; have the relay send it now
+                if write_queued_contact(&contacts, job).await {
+                    relay.nudge();
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                queue.close();
+                let mut drained = 0;
+                while let Some(job) = queue.recv().await {
+                    drained += usize::from(write_queued_contact(&contacts, job).await);
+                }
+                tracing::info!(drained, "Contact ingest task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+async fn write_queued_contact(contacts: &ContactMeHandler<DynContactRepo>, job: QueuedContact) -> bool {
+    let id = job.message.id;
+    match contacts.write_queued(job).await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::error!(message_id = %id, "Queued contact message not stored: {}", e);
+            report_job_failure("contact_ingest", None, &e);
+            false
+        }
+    }
+}
+
+/// Re-checks external links in every tenant's published content
+pub async fn start_link_check_task(
+    checker: LinkChecker<DynLinkCheckRepo, DynBlogPostRepo, DynAboutRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Skip the first tick so restarts do not re-request every link
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for tenant in tenants.all() {
+                    match checker.check_tenant(tenant.id).await {
+                        Ok(run) => tracing::info!(
+                            tenant = %tenant.slug,
+                            checked = run.checked,
+                            broken = run.broken,
+                            "Link check finished"
+                        ),
+                        Err(e) => {
+                            tracing::warn!(tenant = %tenant.slug, "Link check failed: {}", e);
+                            report_job_failure("link_check", Some(&tenant.slug), &e);
+                        }
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Link check task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Refreshes every tenant's post share counts. Does nothing when no network
+/// is configured.
+pub async fn start_share_count_task(
+    share_counts: ShareCounts<DynShareCountRepo, DynBlogPostRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    if !share_counts.enabled() {
+        return;
+    }
+
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Skip the first tick so restarts do not spend the networks' rate limits
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for tenant in tenants.all() {
+                    match share_counts.refresh_tenant(&tenant).await {
+                        Ok(run) => tracing::info!(
+                            tenant = %tenant.slug,
+                            posts = run.posts,
+                            fetched = run.fetched,
+                            skipped = ?run.skipped_networks,
+                            "Share counts refreshed"
+                        ),
+                        Err(e) => {
+                            tracing::warn!(tenant = %tenant.slug, "Share count refresh failed: {}", e);
+                            report_job_failure("share_counts", Some(&tenant.slug), &e);
+                        }
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Share count task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Rebuilds every tenant's tag suggestion index. The first tick runs right
+/// away, as the indexes only live in memory.
+pub async fn start_tag_index_task(
+    suggester: TagSuggester<DynBlogPostRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for tenant in tenants.all() {
+                    match suggester.refresh(tenant.id).await {
+                        Ok(posts) => tracing::debug!(tenant = %tenant.slug, posts, "Tag index rebuilt"),
+                        Err(e) => {
+                            tracing::warn!(tenant = %tenant.slug, "Tag index rebuild failed: {}", e);
+                            report_job_failure("tag_index", Some(&tenant.slug), &e);
+                        }
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Tag index task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Applies every tenant's retention policies
+pub async fn start_retention_task(
+    retention: DataRetention<DynRetentionRepo, DynAuditLogRepo, DynAppSettingsRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Skip the first tick so a restart loop cannot hammer the database with deletes
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for tenant in tenants.all() {
+                    match retention.run(tenant.id, false, AUDIT_ACTOR_SYSTEM).await {
+                        Ok(report) => tracing::info!(
+                            tenant = %tenant.slug,
+                            affected = report.total_affected(),
+                            "Retention run finished"
+                        ),
+                        Err(e) => {
+                            tracing::warn!(tenant = %tenant.slug, "Retention run failed: {}", e);
+                            report_job_failure("retention", Some(&tenant.slug), &e);
+                        }
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Retention task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Re-aggregates the recent days of analytics into the rollup tables
+pub async fn start_stats_rollup_task(
+    rollups: StatsRollups<DynStatsRollupRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // The first tick runs right away, so reports are current after a deploy
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for tenant in tenants.all() {
+                    match rollups.roll_up_recent(tenant.id).await {
+                        Ok(run) => tracing::debug!(tenant = %tenant.slug, rows = run.rows, "Stats rollup finished"),
+                        Err(e) => {
+                            tracing::warn!(tenant = %tenant.slug, "Stats rollup failed: {}", e);
+                            report_job_failure("stats_rollup", Some(&tenant.slug), &e);
+                        }
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Stats rollup task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Measures the usage of every site and stores the reports, then alerts on
+/// quotas nearing their limit. Emails counted since the last run are
+/// stored on shutdown, so a deploy loses none.
+pub async fn start_usage_collector_task(
+    usage: UsageReports<DynUsageRepo>,
+    quota_alerts: QuotaAlerts<DynUsageRepo>,
+    api_tokens: ApiTokens<DynApiTokenRepo>,
+    tenants: TenantResolver<DynTenantRepo>,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let reports = match usage.collect(&tenants.all()).await {
+                    Ok(reports) => {
+                        tracing::debug!(sites = reports.len(), "Usage reports collected");
+                        reports
+                    }
+                    Err(e) => {
+                        tracing::warn!("Usage collection failed: {}", e);
+                        report_job_failure("usage_collector", None, &e);
+                        continue;
+                    }
+                };
+                match quota_alerts.check(&reports, &api_tokens.quota_usage()).await {
+                    Ok(0) => {}
+                    Ok(raised) => tracing::info!(raised, "Quota alerts raised"),
+                    Err(e) => {
+                        tracing::warn!("Quota alert check failed: {}", e);
+                        report_job_failure("quota_alerts", None, &e);
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                if let Err(e) = usage.flush_email_sends().await {
+                    tracing::warn!("Email send counts not stored: {}", e);
+                }
+                tracing::info!("Usage collector task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Runs the declared backfills batch by batch until all are completed, then
+/// exits. Batches are spaced by `pause` to leave the database room for
+/// requests; while another instance holds a task this one checks back less often.
+pub async fn start_backfill_task(
+    backfills: Backfills<DynBackfillRepo>,
+    pause: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    const IDLE: Duration = Duration::from_secs(30);
+
+    loop {
+        let wait = match backfills.run_pass().await {
+            Ok(pass) => {
+                for (task, e) in &pass.failures {
+                    tracing::warn!(task, "Backfill batch failed: {}", e);
+                    report_job_failure("backfill", None, e);
+                }
+                if pass.remaining == 0 {
+                    tracing::info!("All backfills completed");
+                    break;
+                }
+                if pass.batches > pass.failures.len() { pause } else { IDLE }
+            }
+            Err(e) => {
+                tracing::warn!("Backfill progress unreadable: {}", e);
+                report_job_failure("backfill", None, &e);
+                IDLE
+            }
+        };
+
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Backfill task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Deletes expired email verification and password reset tokens
+pub async fn start_one_time_token_cleanup_task(
+    tokens: OneTimeTokens<DynOneTimeTokenRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(60 * 60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Skip the first tick to avoid immediate execution
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match tokens.purge_expired(Utc::now()).await {
+                    Ok(count) => tracing::info!("Deleted {} expired one-time tokens", count),
+                    Err(e) => {
+                        tracing::error!("One-time token cleanup failed: {}", e);
+                        report_job_failure("one_time_token_cleanup", None, &e);
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("One-time token cleanup task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Fetches link previews for new bookmarks and retries failed ones
+pub async fn start_bookmark_preview_task(
+    bookmarks: BookmarkHandler<DynBookmarkRepo>,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(Duration::from_secs(5 * 60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match bookmarks.fetch_pending_previews(PREVIEW_BATCH_SIZE).await {
+                    Ok(run) if run.ready + run.failed > 0 => {
+                        tracing::info!(ready = run.ready, failed = run.failed, "Fetched bookmark previews");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Bookmark preview run failed: {}", e);
+                        report_job_failure("bookmark_previews", None, &e);
+                    }
+                }
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Bookmark preview task shutting down gracefully");
+                break;
+            }
+        }
+    }
+}
+
+/// Delivers queued error reports until shutdown, then flushes the queue
+pub async fn start_error_report_task(
+    transport: SentryTransport,
+    shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    transport.run(shutdown_rx).await;
+    tracing::info!("Error report task shutting down gracefully");
+}
+
+/// Validates pooled Redis connections and rebuilds the pool after an outage
+pub async fn start_redis_supervisor_task(
+    pool: SupervisedPool,
+    every: Duration,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut interval = interval(every);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Skip the first immediate tick; the pool was only just created
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                pool.check().await;
+            }
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Redis supervisor task shutting down gracefully");
+                break;
+            }
+        }
+    }
 }
\ No newline at end of file
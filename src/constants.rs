@@ -1,4 +1,9 @@
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 
-pub static START_TIME: Lazy<DateTime<Utc>> = Lazy::new(Utc::now);
\ No newline at end of file
+pub static START_TIME: Lazy<DateTime<Utc>> = Lazy::new(Utc::now);
+/// Seconds clients are told to wait (`Retry-After`) when we shed load with a 503 or 429.
+pub const RETRY_AFTER_SECS: u64 = 1;
+
+/// Readiness probe, outside `/api/v1` so load balancers need no tenant host or gate token
+pub const READYZ_PATH: &str = "/readyz";
\ No newline at end of file
@@ -0,0 +1,326 @@
+//! Startup self-check.
+//!
+//! `--doctor` runs every check, prints a report and exits non-zero when one
+//! of them fails. Regular startup runs the quick subset, which skips calls to
+//! third-party APIs, and refuses to bind the HTTP port if any of it fails.
+
+use std::{fmt::Write, time::Duration};
+
+use bytes::Bytes;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::jwt::JwtService,
+    cache::redis_pool::SupervisedPool,
+    cdn::purge::CloudflarePurger,
+    db::{migrations::bundled_migrations, postgres::create_probe_pool},
+    entities::schema::SchemaStatus,
+    errors::AppError,
+    mailer::email::RelayMailer,
+    repositories::{schema::SchemaRepository, sqlx_repo::SqlxSchemaRepo},
+    settings::{AppConfig, StorageBackend},
+    storage::object::{storage_from_config, ObjectStorage},
+};
+
+/// Upper bound for any single check
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => " ok ",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub passed: bool,
+    pub checks: Vec<CheckResult>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Depth {
+    /// Local checks only; runs before every start
+    Quick,
+    /// Also logs in to the mail relay, Cloudflare and S3
+    Full,
+}
+
+/// Where the database check gets its connection from
+pub enum Database<'a> {
+    Postgres(&'a PgPool),
+    InMemory,
+    Invalid(String),
+}
+
+impl DoctorReport {
+    fn new(checks: Vec<CheckResult>) -> Self {
+        DoctorReport {
+            passed: checks.iter().all(|c| c.status != CheckStatus::Fail),
+            checks,
+        }
+    }
+
+    /// Report for a configuration that did not even load
+    pub fn config_error(error: impl std::fmt::Display) -> Self {
+        Self::new(vec![check("config", CheckStatus::Fail, error.to_string())])
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn to_text(&self) -> String {
+        let width = self.checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        let mut out = String::from("Portfolio API self-check\n\n");
+
+        for c in &self.checks {
+            let _ = writeln!(out, "  [{}] {:width$}  {}", c.status.label(), c.name, c.detail, width = width);
+        }
+
+        let _ = writeln!(
+            out,
+            "\n{} ({} failed, {} warnings)",
+            if self.passed { "PASSED" } else { "FAILED" },
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Warn),
+        );
+        out
+    }
+
+    /// Emits one log line per check that is not plainly OK
+    pub fn log(&self) {
+        for c in &self.checks {
+            match c.status {
+                CheckStatus::Fail => tracing::error!(check = c.name, "Self-check failed: {}", c.detail),
+                CheckStatus::Warn => tracing::warn!(check = c.name, "Self-check warning: {}", c.detail),
+                CheckStatus::Ok | CheckStatus::Skip => tracing::debug!(check = c.name, "Self-check: {}", c.detail),
+            }
+        }
+    }
+}
+
+/// `--doctor`: opens its own connections so a broken dependency is reported
+/// instead of aborting the run
+pub async fn run_full(config: &AppConfig, use_postgres: bool) -> DoctorReport {
+    let pool = use_postgres.then(|| create_probe_pool(&config.database_url, CHECK_TIMEOUT));
+    let database = match &pool {
+        Some(Ok(pool)) => Database::Postgres(pool),
+        Some(Err(e)) => Database::Invalid(e.to_string()),
+        None => Database::InMemory,
+    };
+    let redis = SupervisedPool::from_config(config);
+    let storage = storage_from_config(config);
+
+    run(config, database, redis.as_ref(), storage.as_deref(), Depth::Full).await
+}
+
+pub async fn run(
+    config: &AppConfig,
+    database: Database<'_>,
+    redis: Option<&SupervisedPool>,
+    storage: Option<&dyn ObjectStorage>,
+    depth: Depth,
+) -> DoctorReport {
+    let mut checks = vec![check_config(config)];
+
+    let db = check_database(&database).await;
+    let db_ok = db.status == CheckStatus::Ok;
+    checks.push(db);
+    checks.push(match &database {
+        Database::Postgres(pool) if db_ok => check_migrations(pool).await,
+        Database::InMemory => check("migrations", CheckStatus::Skip, "in-memory storage"),
+        _ => check("migrations", CheckStatus::Skip, "database unreachable"),
+    });
+
+    checks.push(check_redis(config, redis, depth).await);
+    checks.push(check_jwt(config));
+    checks.push(check_storage(config, storage, depth).await);
+
+    if depth == Depth::Full {
+        checks.push(check_mail_relay(config).await);
+        checks.push(check_cdn(config).await);
+    }
+
+    DoctorReport::new(checks)
+}
+
+fn check(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> CheckResult {
+    CheckResult { name, status, detail: detail.into() }
+}
+
+/// `AppConfig::new` already rejects invalid values; this flags settings that
+/// are valid but leave a feature silently off
+fn check_config(config: &AppConfig) -> CheckResult {
+    let mut gaps = Vec::new();
+
+    if config.redis_url.is_none() {
+        gaps.push("APP_REDIS_URL unset: token revocation, rate limits and the page cache are off");
+    }
+    if config.mail_relay_url.is_none() {
+        gaps.push("APP_MAIL_RELAY_URL unset: emails are only logged");
+    }
+    if config.notification_email.is_none() {
+        gaps.push("APP_NOTIFICATION_EMAIL unset: notifications need a per-tenant recipient setting");
+    }
+    if config.public_base_url.is_none() {
+        gaps.push("APP_PUBLIC_BASE_URL unset: links in emails and feeds are relative");
+    }
+
+    if gaps.is_empty() {
+        check("config", CheckStatus::Ok, "complete")
+    } else if config.is_production() {
+        check("config", CheckStatus::Warn, gaps.join("; "))
+    } else {
+        check("config", CheckStatus::Ok, format!("valid; {}", gaps.join("; ")))
+    }
+}
+
+async fn check_database(database: &Database<'_>) -> CheckResult {
+    let pool = match database {
+        Database::Postgres(pool) => pool,
+        Database::InMemory => return check("database", CheckStatus::Skip, "in-memory storage"),
+        Database::Invalid(e) => return check("database", CheckStatus::Fail, format!("invalid DATABASE_URL: {}", e)),
+    };
+
+    let ping = sqlx::query_scalar::<_, String>("SELECT version()").fetch_one(*pool);
+    match tokio::time::timeout(CHECK_TIMEOUT, ping).await {
+        Ok(Ok(version)) => check("database", CheckStatus::Ok, version),
+        Ok(Err(e)) => check("database", CheckStatus::Fail, e.to_string()),
+        Err(_) => check("database", CheckStatus::Fail, "timed out"),
+    }
+}
+
+/// Compares the migrations compiled into this binary with `_sqlx_migrations`.
+/// Drift only warns here; startup refuses it separately unless
+/// `--allow-drift` is given.
+async fn check_migrations(pool: &PgPool) -> CheckResult {
+    let applied = match SqlxSchemaRepo::new(pool.clone()).applied_migrations().await {
+        Ok(rows) if rows.is_empty() => {
+            return check("migrations", CheckStatus::Fail, "no migrations applied, run `sqlx migrate run`");
+        }
+        Ok(rows) => rows,
+        Err(e) => return check("migrations", CheckStatus::Fail, format!("cannot read _sqlx_migrations: {}", e)),
+    };
+
+    let status = SchemaStatus::compare(&bundled_migrations(), &applied);
+    let outcome = match (status.is_up_to_date(), status.has_drift()) {
+        (false, _) => CheckStatus::Fail,
+        (true, true) => CheckStatus::Warn,
+        (true, false) => CheckStatus::Ok,
+    };
+    check("migrations", outcome, status.summary())
+}
+
+/// Redis is optional at runtime and the pool supervisor reconnects on its
+/// own, so an outage only blocks startup in the full check
+async fn check_redis(config: &AppConfig, redis: Option<&SupervisedPool>, depth: Depth) -> CheckResult {
+    let Some(pool) = redis else {
+        return match config.redis_url {
+            Some(_) => check("redis", CheckStatus::Fail, "pool could not be created from APP_REDIS_URL"),
+            None => check("redis", CheckStatus::Skip, "not configured"),
+        };
+    };
+
+    let health = pool.check().await;
+    match (health.healthy, depth) {
+        (true, _) => check(
+            "redis",
+            CheckStatus::Ok,
+            format!("{}: {}", health.topology, health.detail.unwrap_or_default()),
+        ),
+        (false, Depth::Quick) => check(
+            "redis",
+            CheckStatus::Warn,
+            format!("unreachable, will keep retrying: {}", health.last_error.unwrap_or_default()),
+        ),
+        (false, Depth::Full) => check("redis", CheckStatus::Fail, health.last_error.unwrap_or_default()),
+    }
+}
+
+fn check_jwt(config: &AppConfig) -> CheckResult {
+    if let Err(e) = JwtService::new(config).self_test() {
+        return check("jwt", CheckStatus::Fail, format!("keys cannot sign and verify a token: {}", e));
+    }
+
+    if config.jwt_secret == config.refresh_token_secret {
+        return check("jwt", CheckStatus::Warn, "access and refresh tokens share one secret");
+    }
+
+    check("jwt", CheckStatus::Ok, "access and refresh keys round-trip")
+}
+
+/// Round-trips a small probe object. S3 is a third-party call, so the quick
+/// check leaves it to `--doctor`.
+async fn check_storage(config: &AppConfig, storage: Option<&dyn ObjectStorage>, depth: Depth) -> CheckResult {
+    let Some(storage) = storage else {
+        return check("storage", CheckStatus::Fail, "backend could not be set up from the STORAGE_* settings");
+    };
+
+    if depth == Depth::Quick && config.storage_backend == StorageBackend::S3 {
+        return check("storage", CheckStatus::Skip, format!("{}, checked by --doctor", storage.describe()));
+    }
+
+    let key = format!("doctor-probe-{}.txt", Uuid::new_v4());
+    let probe = async {
+        storage.put(&key, Bytes::from_static(b"ok"), "text/plain").await?;
+        let found = storage.exists(&key).await?;
+        storage.delete(&key).await?;
+        Ok::<_, AppError>(found)
+    };
+
+    match tokio::time::timeout(CHECK_TIMEOUT, probe).await {
+        Ok(Ok(true)) => check("storage", CheckStatus::Ok, format!("{} is writable", storage.describe())),
+        Ok(Ok(false)) => check("storage", CheckStatus::Fail, "probe object was written but cannot be found"),
+        Ok(Err(e)) => check("storage", CheckStatus::Fail, e.to_string()),
+        Err(_) => check("storage", CheckStatus::Fail, "timed out"),
+    }
+}
+
+async fn check_mail_relay(config: &AppConfig) -> CheckResult {
+    let Some(url) = &config.mail_relay_url else {
+        return check("mail_relay", CheckStatus::Skip, "not configured, emails are logged");
+    };
+
+    let relay = RelayMailer::new(url.clone(), config.mail_relay_token.clone());
+    match tokio::time::timeout(CHECK_TIMEOUT, relay.probe()).await {
+        Ok(Ok(())) => check("mail_relay", CheckStatus::Ok, "reachable, token accepted"),
+        Ok(Err(e)) => check("mail_relay", CheckStatus::Fail, e.to_string()),
+        Err(_) => check("mail_relay", CheckStatus::Fail, "timed out"),
+    }
+}
+
+async fn check_cdn(config: &AppConfig) -> CheckResult {
+    let (Some(zone_id), Some(token)) = (&config.cloudflare_zone_id, &config.cloudflare_api_token) else {
+        return check("cdn", CheckStatus::Skip, "Cloudflare purging not configured");
+    };
+
+    let purger = CloudflarePurger::new(zone_id.clone(), token.clone());
+    match tokio::time::timeout(CHECK_TIMEOUT, purger.verify_token()).await {
+        Ok(Ok(())) => check("cdn", CheckStatus::Ok, "Cloudflare API token is active"),
+        Ok(Err(e)) => check("cdn", CheckStatus::Fail, e.to_string()),
+        Err(_) => check("cdn", CheckStatus::Fail, "timed out"),
+    }
+}
\ No newline at end of file
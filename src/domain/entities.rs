@@ -3,4 +3,47 @@ pub mod token;
 pub mod about_me;
 pub mod blog_post;
 pub mod option_fields;
-pub mod contact_me;
\ No newline at end of file
+pub mod contact_me;
+pub mod feature_flag;
+pub mod site_gate;
+pub mod app_setting;
+pub mod tenant;
+pub mod hire;
+pub mod uses;
+pub mod changelog;
+pub mod outbound;
+pub mod security_event;
+pub mod email_template;
+pub mod outbox;
+pub mod content_fixture;
+pub mod presence;
+pub mod export;
+pub mod series;
+pub mod link_check;
+pub mod edit_lock;
+pub mod config_change;
+pub mod backfill;
+pub mod usage;
+pub mod content_scan;
+pub mod share_count;
+pub mod visitor;
+pub mod sync;
+pub mod rate_limit;
+pub mod geo;
+pub mod retention;
+pub mod audit;
+pub mod notification;
+pub mod reading;
+pub mod title_test;
+pub mod api_token;
+pub mod stats_rollup;
+pub mod view;
+pub mod one_time_token;
+pub mod bookmark;
+pub mod site_page;
+pub mod schedule_preview;
+pub mod user_import;
+pub mod redirect_rule;
+pub mod public_api;
+pub mod pii;
+pub mod schema;
\ No newline at end of file
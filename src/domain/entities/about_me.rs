@@ -4,13 +4,14 @@ use uuid::Uuid;
 use validator::{Validate, ValidationError};
 use actix_multipart::form::{json::Json as MpJson, tempfile::TempFile, MultipartForm};
 
-use crate::utils::markdown::safe_markdown_to_html;
+use crate::{entities::option_fields::OptionField, utils::markdown::safe_markdown_to_html};
 
 // ───── Database Models ───────────────────────────────────────────────
 
-#[derive(Debug, sqlx::FromRow)]
+#[derive(Debug, Clone, sqlx::FromRow)]
 pub struct AboutMe {
     pub id: Uuid,
+    pub tenant_id: Uuid,
     pub revision: i32,
     pub content_markdown: String,
     pub effective_date: NaiveDate,
@@ -90,11 +91,18 @@ pub struct AboutMeMetadata {
     pub effective_date: NaiveDate,
 }
 
+/// Fields left out stay as they are. Content and effective date cannot be
+/// cleared, so an explicit `null` for either is rejected.
 #[derive(Debug, Deserialize, Validate)]
 pub struct UpdateAboutMeRequest {
+    #[serde(default)]
     #[validate(length(min = 1, message = "Content cannot be empty"))]
-    pub content_markdown: String,
-    pub effective_date: NaiveDate,
+    pub content_markdown: OptionField<String>,
+
+    #[serde(default)]
+    #[validate(custom(function = "validate_optional_effective_date"))]
+    pub effective_date: OptionField<NaiveDate>,
+
     pub expected_revision: i32,
 }
 
@@ -114,6 +122,13 @@ fn validate_effective_date(date: &NaiveDate) -> Result<(), ValidationError> {
     Ok(())
 }
 
+fn validate_optional_effective_date(date: &OptionField<NaiveDate>) -> Result<(), ValidationError> {
+    if let OptionField::SetToValue(date) = date {
+        validate_effective_date(date)?;
+    }
+    Ok(())
+}
+
 // ───── Insert Preparation Logic ─────────────────────────────────────
 
 impl NewAboutMe {
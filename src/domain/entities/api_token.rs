@@ -0,0 +1,300 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+pub const API_TOKEN_HEADER: &str = "X-Api-Token";
+/// Query parameter alternative to the header, for `navigator.sendBeacon`
+/// which cannot set headers
+pub const API_TOKEN_QUERY_PARAM: &str = "api_token";
+/// Issued tokens start with this, so leaked ones are easy to grep for
+pub const API_TOKEN_PREFIX: &str = "pk_";
+/// Characters of an issued token kept in clear to tell tokens apart
+pub const API_TOKEN_VISIBLE_CHARS: usize = 8;
+
+/// Analytics ingestion: reading progress and title test clicks
+pub const API_SCOPE_ANALYTICS: &str = "analytics";
+/// Every scope a token can be limited to
+pub const API_TOKEN_SCOPES: [&str; 1] = [API_SCOPE_ANALYTICS];
+
+const MAX_TOKEN_NAME_LENGTH: u64 = 64;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub rate_limit_per_minute: Option<i32>,
+    pub monthly_quota: Option<i64>,
+    pub request_count: i64,
+    pub rejected_count: i64,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiTokenInsert {
+    pub name: String,
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub rate_limit_per_minute: Option<i32>,
+    pub monthly_quota: Option<i64>,
+    pub created_by: Option<Uuid>,
+}
+
+/// Requests counted for one token since the last flush
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiTokenUsage {
+    pub requests: u64,
+    pub rejected: u64,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiTokenUsage {
+    pub fn merge(&mut self, other: &ApiTokenUsage) {
+        self.requests += other.requests;
+        self.rejected += other.rejected;
+        self.last_used_at = self.last_used_at.max(other.last_used_at);
+    }
+}
+
+/// Traffic of one token on one day, counted since the last flush
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiTokenTraffic {
+    pub requests: u64,
+    pub bytes: u64,
+    pub errors: u64,
+}
+
+impl ApiTokenTraffic {
+    pub fn merge(&mut self, other: &ApiTokenTraffic) {
+        self.requests += other.requests;
+        self.bytes += other.bytes;
+        self.errors += other.errors;
+    }
+}
+
+/// A row of `api_token_usage_daily`; also what is flushed, as counts to add
+/// or as the day's totals
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ApiTokenDailyUsage {
+    pub token_id: Uuid,
+    pub day: NaiveDate,
+    pub requests: i64,
+    pub bytes: i64,
+    pub errors: i64,
+}
+
+impl ApiTokenDailyUsage {
+    pub fn new(token_id: Uuid, day: NaiveDate, traffic: &ApiTokenTraffic) -> Self {
+        ApiTokenDailyUsage {
+            token_id,
+            day,
+            requests: traffic.requests as i64,
+            bytes: traffic.bytes as i64,
+            errors: traffic.errors as i64,
+        }
+    }
+
+    pub fn traffic(&self) -> ApiTokenTraffic {
+        ApiTokenTraffic {
+            requests: self.requests as u64,
+            bytes: self.bytes as u64,
+            errors: self.errors as u64,
+        }
+    }
+}
+
+/// Only the SHA-256 of a token is stored or compared
+pub fn hash_api_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateApiTokenRequest {
+    #[validate(length(min = 1, max = MAX_TOKEN_NAME_LENGTH))]
+    pub name: String,
+
+    #[validate(length(min = 1), custom(function = "validate_scopes"))]
+    pub scopes: Vec<String>,
+
+    /// Defaults to `api_token_per_minute`
+    #[validate(range(min = 1, max = 100_000))]
+    pub rate_limit_per_minute: Option<u32>,
+
+    /// Requests allowed per calendar month (UTC); unlimited when left out
+    #[validate(range(min = 1, max = 1_000_000_000))]
+    pub monthly_quota: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ApiTokenUsageQuery {
+    /// Months listed, the current one included; defaults to 3
+    #[validate(range(min = 1, max = 24))]
+    pub months: Option<u32>,
+}
+
+// ───── API Response Models ──────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiTokenSource {
+    /// From `public_api_tokens`; valid on every site and scope
+    Config,
+    /// Issued by an admin of the site
+    Issued,
+}
+
+/// A token with its usage, as admins see it; the token itself is never shown
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiTokenSummary {
+    /// `None` for configured tokens
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub source: ApiTokenSource,
+    pub token_prefix: Option<String>,
+    pub scopes: Vec<String>,
+    pub rate_limit_per_minute: u64,
+    pub monthly_quota: Option<u64>,
+    pub request_count: u64,
+    pub rejected_count: u64,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiTokenSummary {
+    /// An issued token with the usage not flushed to the database yet added
+    pub fn issued(token: ApiToken, pending: ApiTokenUsage, default_per_minute: u64) -> Self {
+        ApiTokenSummary {
+            id: Some(token.id),
+            name: token.name,
+            source: ApiTokenSource::Issued,
+            token_prefix: Some(token.token_prefix),
+            scopes: token.scopes,
+            rate_limit_per_minute: token.rate_limit_per_minute.map_or(default_per_minute, |limit| limit as u64),
+            monthly_quota: token.monthly_quota.map(|quota| quota as u64),
+            request_count: token.request_count as u64 + pending.requests,
+            rejected_count: token.rejected_count as u64 + pending.rejected,
+            last_used_at: token.last_used_at.max(pending.last_used_at),
+            created_at: Some(token.created_at),
+            revoked_at: token.revoked_at,
+        }
+    }
+
+    /// A configured token; its usage is counted since this instance started
+    pub fn configured(name: String, usage: ApiTokenUsage, per_minute: u64) -> Self {
+        ApiTokenSummary {
+            id: None,
+            name,
+            source: ApiTokenSource::Config,
+            token_prefix: None,
+            scopes: API_TOKEN_SCOPES.iter().map(|scope| scope.to_string()).collect(),
+            rate_limit_per_minute: per_minute,
+            monthly_quota: None,
+            request_count: usage.requests,
+            rejected_count: usage.rejected,
+            last_used_at: usage.last_used_at,
+            created_at: None,
+            revoked_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiTokenReport {
+    pub total: usize,
+    pub tokens: Vec<ApiTokenSummary>,
+}
+
+/// One day's traffic in a usage report
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiTokenDayUsage {
+    pub day: NaiveDate,
+    pub requests: u64,
+    pub bytes: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiTokenMonthUsage {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub requests: u64,
+    pub bytes: u64,
+    pub errors: u64,
+    /// Requests left under the quota; `None` without one
+    pub quota_remaining: Option<u64>,
+    /// Days with traffic, oldest first
+    pub days: Vec<ApiTokenDayUsage>,
+}
+
+impl ApiTokenMonthUsage {
+    pub fn new<'a>(
+        month: NaiveDate,
+        days: impl Iterator<Item = (&'a NaiveDate, &'a ApiTokenTraffic)>,
+        monthly_quota: Option<u64>,
+    ) -> Self {
+        let mut total = ApiTokenTraffic::default();
+        let days: Vec<ApiTokenDayUsage> = days
+            .map(|(day, traffic)| {
+                total.merge(traffic);
+                ApiTokenDayUsage { day: *day, requests: traffic.requests, bytes: traffic.bytes, errors: traffic.errors }
+            })
+            .collect();
+
+        ApiTokenMonthUsage {
+            month,
+            requests: total.requests,
+            bytes: total.bytes,
+            errors: total.errors,
+            quota_remaining: monthly_quota.map(|quota| quota.saturating_sub(total.requests)),
+            days,
+        }
+    }
+}
+
+/// A token's traffic per month, newest first
+#[derive(Debug, Serialize)]
+pub struct ApiTokenUsageReport {
+    pub id: Uuid,
+    pub name: String,
+    pub monthly_quota: Option<u64>,
+    pub months: Vec<ApiTokenMonthUsage>,
+}
+
+/// Returned once when a token is issued
+#[derive(Debug, Serialize)]
+pub struct IssuedApiToken {
+    pub token: String,
+    #[serde(flatten)]
+    pub summary: ApiTokenSummary,
+}
+
+// ───── Validation Helpers ───────────────────────────────────────────
+
+fn validate_scopes(scopes: &[String]) -> Result<(), ValidationError> {
+    if !scopes.iter().all(|scope| API_TOKEN_SCOPES.contains(&scope.as_str())) {
+        let mut err = ValidationError::new("invalid_scope");
+        err.message = Some(Cow::Owned(format!("Scopes must be among: {}", API_TOKEN_SCOPES.join(", "))));
+        return Err(err);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+
+// ───── Known Keys ────────────────────────────────────────────────────
+pub const CONTACT_NOTIFICATION_POLICY: &str = "contact_notification_policy";
+/// Per-tenant recipient for contact notifications
+pub const CONTACT_NOTIFICATION_EMAIL: &str = "contact_notification_email";
+/// Acknowledgment sent to contact form senders; see `ContactAutoReply`
+pub const CONTACT_AUTO_REPLY: &str = "contact_auto_reply";
+/// Internal bookkeeping for the digest job; not editable through the admin API
+pub const CONTACT_DIGEST_LAST_SENT_AT: &str = "contact_digest_last_sent_at";
+/// Public "hire me" status; see `HireAvailability`
+pub const HIRE_AVAILABILITY: &str = "hire_availability";
+/// Recipient for hire inquiries; falls back to the contact recipient
+pub const HIRE_NOTIFICATION_EMAIL: &str = "hire_notification_email";
+/// Hostnames `/out` may redirect to (subdomains included); empty blocks every redirect
+pub const OUTBOUND_ALLOWED_DOMAINS: &str = "outbound_allowed_domains";
+/// Language for templated emails, e.g. `"fr"`; see `mailer::templates::Locale`
+pub const EMAIL_LOCALE: &str = "email_locale";
+/// Per-entity overrides of the default retention periods; see `RetentionPolicies`
+pub const RETENTION_POLICIES: &str = "retention_policies";
+/// Title and nav of server-rendered pages; see `SitePageSettings`
+pub const SITE_PAGES: &str = "site_pages";
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AppSetting {
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub key: String,
+    pub value: JsonValue,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAppSettingRequest {
+    pub value: JsonValue,
+}
+
+// ───── Typed Values ──────────────────────────────────────────────────
+
+/// How admins are told about new contact messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationPolicy {
+    Immediate,
+    Hourly,
+    Daily,
+}
+
+impl NotificationPolicy {
+    /// Minimum gap between digests; `None` for immediate delivery
+    pub fn digest_interval(&self) -> Option<chrono::Duration> {
+        match self {
+            NotificationPolicy::Immediate => None,
+            NotificationPolicy::Hourly => Some(chrono::Duration::hours(1)),
+            NotificationPolicy::Daily => Some(chrono::Duration::days(1)),
+        }
+    }
+}
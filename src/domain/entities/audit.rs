@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+use validator::Validate;
+
+/// `actor` of entries written by background jobs
+pub const AUDIT_ACTOR_SYSTEM: &str = "system";
+/// A retention run; `details` has the affected row counts per entity
+pub const AUDIT_RETENTION_RUN: &str = "retention.run";
+/// Contact messages anonymized on request; `details` has the threshold and count
+pub const AUDIT_CONTACTS_ANONYMIZED: &str = "contact_messages.anonymize";
+/// An anonymized contact message got its name and email back
+pub const AUDIT_CONTACT_RESTORED: &str = "contact_messages.restore";
+
+/// `entity_type` of entries about contact messages
+pub const AUDIT_ENTITY_CONTACT_MESSAGE: &str = "contact_message";
+
+/// Longest `q` accepted for full-text search
+pub const MAX_AUDIT_SEARCH_LENGTH: u64 = 200;
+
+/// Whether the audited action went through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditResult {
+    #[default]
+    Success,
+    Failure,
+}
+
+impl AuditResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditResult::Success => "success",
+            AuditResult::Failure => "failure",
+        }
+    }
+}
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct AuditEntryInsert {
+    pub actor: String,
+    pub action: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub result: AuditResult,
+    pub details: JsonValue,
+}
+
+impl AuditEntryInsert {
+    /// A successful action about no particular entity
+    pub fn new(actor: &str, action: &str, details: JsonValue) -> Self {
+        AuditEntryInsert {
+            actor: actor.to_string(),
+            action: action.to_string(),
+            entity_type: None,
+            entity_id: None,
+            result: AuditResult::Success,
+            details,
+        }
+    }
+
+    pub fn about(mut self, entity_type: &str, entity_id: Option<String>) -> Self {
+        self.entity_type = Some(entity_type.to_string());
+        self.entity_id = entity_id;
+        self
+    }
+
+    pub fn failed(mut self) -> Self {
+        self.result = AuditResult::Failure;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub actor: String,
+    pub action: String,
+    pub details: JsonValue,
+    pub created_at: DateTime<Utc>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    /// `AuditResult` as stored
+    pub result: String,
+}
+
+/// Keyset position for chunked reads: the last `(created_at, id)` seen
+pub type AuditCursor = (DateTime<Utc>, Uuid);
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+/// Filters shared by the audit log listing and its exports. `from` is
+/// inclusive, `to` is exclusive; `q` searches the words in `details`.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct AuditLogFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub result: Option<AuditResult>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[validate(length(min = 1, max = MAX_AUDIT_SEARCH_LENGTH))]
+    pub q: Option<String>,
+}
+
+/// Paging of `GET /admin/audit-log`, newest first
+#[derive(Debug, Deserialize, Validate)]
+pub struct AuditLogQuery {
+    #[validate(range(min = 1, max = 200))]
+    pub limit: Option<u32>,
+
+    #[validate(range(min = 0))]
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// `GET /admin/audit-log/export`; the filters come from `AuditLogFilter`
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditExportQuery {
+    #[serde(default)]
+    pub format: AuditExportFormat,
+    /// Keep personal data in `details`; redacted by default
+    #[serde(default)]
+    pub include_pii: bool,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditEntry>,
+    /// Entries matching the filters, across all pages
+    pub total: i64,
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Declared but no batch has run yet
+pub const BACKFILL_PENDING: &str = "pending";
+pub const BACKFILL_RUNNING: &str = "running";
+/// Every row that was pending has been filled; new rows are kept current
+/// by whatever writes them
+pub const BACKFILL_COMPLETED: &str = "completed";
+
+// ───── Task Declarations ─────────────────────────────────────────────
+
+/// A derived-data fill over a large table, run in batches in the background
+/// instead of inside a migration, so boot is not held up by it.
+///
+/// Rows are walked in `id` order; a row is pending while `pending` (an SQL
+/// condition on the table aliased `t`) holds for it. `fill` runs once per
+/// batch with the batch's ids as `$1` (`UUID[]`) and must leave those rows
+/// no longer pending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillTask {
+    /// Stable key of the task's progress row; never reuse one
+    pub name: &'static str,
+    pub description: &'static str,
+    pub table: &'static str,
+    pub pending: &'static str,
+    pub fill: &'static str,
+    pub batch_size: i64,
+}
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct BackfillState {
+    pub name: String,
+    pub status: String,
+    /// Last row id done
+    pub checkpoint: Option<Uuid>,
+    pub processed: i64,
+    pub total: Option<i64>,
+    pub last_error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BackfillState {
+    pub fn is_completed(&self) -> bool {
+        self.status == BACKFILL_COMPLETED
+    }
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct BackfillProgress {
+    pub name: String,
+    pub description: String,
+    pub status: String,
+    pub processed: i64,
+    /// Rows pending when the task started; `None` before its first batch
+    pub total: Option<i64>,
+    /// 0-100, rounded down; 100 once completed
+    pub percent: Option<u8>,
+    pub batch_size: i64,
+    pub last_error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackfillsResponse {
+    pub backfills: Vec<BackfillProgress>,
+    /// Whether any task still has rows to fill
+    pub running: bool,
+}
@@ -1,24 +1,31 @@
 use std::borrow::Cow;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::{Validate, ValidationError, ValidationErrors};
-use sqlx::types::Json;
+use sqlx::types::{Json, JsonValue};
 
 use crate::{
-    entities::option_fields::OptionField,
-    utils::markdown::{safe_markdown_to_html, sanitize_markdown_content},
+    entities::{option_fields::OptionField, series::SeriesNavigation, view::admin_only},
+    utils::{
+        markdown::{first_paragraph_excerpt, safe_markdown_to_html, sanitize_markdown_content},
+        tag_index::TagSuggestion,
+        text_diff::{unified_diff, DIFF_CONTEXT_LINES},
+    },
 };
 
 // ───── Constants ──────────────────────────────────────────────────────
-const MIN_TITLE_LENGTH: u64 = 3;
-const MAX_TITLE_LENGTH: u64 = 120;
+pub const MIN_TITLE_LENGTH: u64 = 3;
+pub const MAX_TITLE_LENGTH: u64 = 120;
 const MIN_SLUG_LENGTH: u64 = 3;
 const MAX_SLUG_LENGTH: u64 = 80;
 const MIN_EXCERPT_LENGTH: u64 = 10;
 const MAX_EXCERPT_LENGTH: u64 = 300;
 const MAX_TAGS: u64 = 10;
 const MAX_TAG_LENGTH: u64 = 30;
+/// Largest draft the tag suggester will read, in bytes
+const MAX_SUGGEST_CONTENT_LENGTH: u64 = 200_000;
+const MAX_TAG_SUGGESTIONS: u32 = 20;
 
 
 // ───── Database Models ───────────────────────────────────────────────
@@ -27,6 +34,8 @@ const MAX_TAG_LENGTH: u64 = 30;
 #[derive(Debug, sqlx::FromRow)]
 pub struct BlogPostRow {
     pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub author_id: Option<Uuid>,
     pub title: String,
     pub slug: String,
     pub excerpt: String,
@@ -40,11 +49,17 @@ pub struct BlogPostRow {
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub featured: bool,
+    pub featured_order: Option<i32>,
 }
 
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct BlogPost {
     pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub author_id: Option<Uuid>,
     pub title: String,
     pub slug: String,
     pub excerpt: String,
@@ -57,7 +72,85 @@ pub struct BlogPost {
     pub published_at: Option<DateTime<Utc>>,
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "admin_only")]
     pub deleted_at: Option<DateTime<Utc>>,
+    pub featured: bool,
+    pub featured_order: Option<i32>,
+}
+
+/// A post's content and metadata as they stood after one change, numbered
+/// from 1 per post
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PostRevision {
+    pub post_id: Uuid,
+    pub revision: i32,
+    pub tenant_id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub content_markdown: String,
+    pub cover_image_url: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub seo_title: Option<String>,
+    pub seo_description: Option<String>,
+    pub published: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PostRevision {
+    /// Snapshot of `post` as its next revision
+    pub fn of(post: &BlogPost, revision: i32) -> Self {
+        PostRevision {
+            post_id: post.id,
+            revision,
+            tenant_id: post.tenant_id,
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            excerpt: post.excerpt.clone(),
+            content_markdown: post.content_markdown.clone(),
+            cover_image_url: post.cover_image_url.clone(),
+            tags: post.tags.clone(),
+            seo_title: post.seo_title.clone(),
+            seo_description: post.seo_description.clone(),
+            published: post.published,
+            published_at: post.published_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Same content and metadata, whatever the revision number
+    pub fn same_as(&self, other: &PostRevision) -> bool {
+        self.content_markdown == other.content_markdown && self.metadata() == other.metadata()
+    }
+
+    /// Every tracked field but the markdown, in a fixed order
+    fn metadata(&self) -> [(&'static str, JsonValue); 9] {
+        [
+            ("title", JsonValue::from(self.title.as_str())),
+            ("slug", JsonValue::from(self.slug.as_str())),
+            ("excerpt", JsonValue::from(self.excerpt.as_str())),
+            ("cover_image_url", JsonValue::from(self.cover_image_url.as_deref())),
+            ("tags", JsonValue::from(self.tags.clone())),
+            ("seo_title", JsonValue::from(self.seo_title.as_deref())),
+            ("seo_description", JsonValue::from(self.seo_description.as_deref())),
+            ("published", JsonValue::from(self.published)),
+            ("published_at", JsonValue::from(self.published_at.map(|at| at.to_rfc3339_opts(SecondsFormat::AutoSi, true)))),
+        ]
+    }
+}
+
+/// Filters for the admin search; `query` is matched case-insensitively
+#[derive(Debug, Clone)]
+pub struct BlogSearchFilter {
+    pub query: String,
+    pub status: Option<PostStatus>,
+    /// Also match soft-deleted posts. Implied by `status = deleted`.
+    pub include_deleted: bool,
+    pub tag: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub limit: i64,
 }
 
 #[derive(Debug, Validate)]
@@ -96,9 +189,130 @@ pub struct BlogPostInsert {
     pub published_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    /// Set from the caller's claims, never from the request body
+    pub author_id: Option<Uuid>,
+
+    /// `excerpt` was derived from the content rather than given; not stored
+    pub excerpt_generated: bool,
+}
+
+/// Who is changing a post: editors may only touch their own, admins any
+#[derive(Debug, Clone, Copy)]
+pub struct PostEditor {
+    pub user_id: Uuid,
+    pub admin: bool,
+}
+
+impl PostEditor {
+    pub fn can_modify(&self, post: &BlogPost) -> bool {
+        self.admin || post.author_id == Some(self.user_id)
+    }
+}
+
+/// Credits a user on a post, or moves them if already credited.
+/// `position` is 0-based; left out or past the end, the author goes last.
+#[derive(Debug, Deserialize)]
+pub struct AddPostAuthorRequest {
+    pub user_id: Uuid,
+    pub position: Option<usize>,
 }
 
 // ───── API Response Models ──────────────────────────────────────────
+/// Lifecycle state shown in admin search; deleted wins over published
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    Draft,
+    Published,
+    Deleted,
+}
+
+impl PostStatus {
+    pub fn of(post: &BlogPost) -> Self {
+        match (post.deleted_at, post.published) {
+            (Some(_), _) => PostStatus::Deleted,
+            (None, true) => PostStatus::Published,
+            (None, false) => PostStatus::Draft,
+        }
+    }
+}
+
+/// Character offsets (end exclusive) of one match within `field`
+#[derive(Debug, Serialize)]
+pub struct SearchHighlight {
+    pub field: &'static str,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSearchHit {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub status: PostStatus,
+    pub tags: Option<Vec<String>>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub highlights: Vec<SearchHighlight>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSearchResponse {
+    pub query: String,
+    pub results: Vec<AdminSearchHit>,
+}
+
+/// Public profile of a post's author; `name` is the display name, else the username
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PostAuthor {
+    #[serde(skip_serializing_if = "admin_only")]
+    pub id: Uuid,
+    pub username: Option<String>,
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// The featured posts in homepage order; posts left out stop being featured
+#[derive(Debug, Deserialize)]
+pub struct SetFeaturedPostsRequest {
+    pub post_ids: Vec<Uuid>,
+}
+
+/// One author credited on a post
+#[derive(Debug, Clone)]
+pub struct PostByline {
+    pub post_id: Uuid,
+    pub author: PostAuthor,
+}
+
+/// A post as the API returns it, with its bylines alongside. `author` is the
+/// lead byline, kept for clients that show a single name. Single-post
+/// responses also carry the post's place in its series; list responses carry
+/// the title test variant that was served, for click tracking.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthoredPost {
+    #[serde(flatten)]
+    pub post: BlogPost,
+    pub author: Option<PostAuthor>,
+    pub authors: Vec<PostAuthor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesNavigation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_variant: Option<i16>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorPostsResponse {
+    pub author: PostAuthor,
+    pub page: u32,
+    pub per_page: u32,
+    pub posts: Vec<AuthoredPost>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct BlogPostListResponse {
     pub id: Uuid,
@@ -134,6 +348,140 @@ pub struct BlogPostCreatedResponse {
     pub slug: String,
     pub preview_url: String,
     pub admin_url: String,
+    /// The request left out `excerpt`, so one was taken from the content
+    pub excerpt_generated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlugCheckResponse {
+    /// The slug that was checked: the one supplied, or the title suggestion
+    pub slug: String,
+    pub valid: bool,
+    pub available: bool,
+    /// Normalized slug derived from `title`, when one was given
+    pub suggestion: Option<String>,
+    /// Free numeric-suffix variants, only filled when `slug` is taken or invalid
+    pub alternatives: Vec<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PostRevisionSummary {
+    pub revision: i32,
+    pub title: String,
+    pub published: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostRevisionRef {
+    pub revision: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One metadata field that differs between two revisions
+#[derive(Debug, Serialize)]
+pub struct MetadataChange {
+    pub field: &'static str,
+    pub from: JsonValue,
+    pub to: JsonValue,
+}
+
+/// Unified diff of the markdown; empty when it did not change
+#[derive(Debug, Serialize)]
+pub struct ContentDiff {
+    pub unified: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// What changed going from revision `from` to revision `to`. Either may be
+/// the later one; diffing backwards shows what a revert would do.
+#[derive(Debug, Serialize)]
+pub struct PostRevisionDiff {
+    pub post_id: Uuid,
+    pub from: PostRevisionRef,
+    pub to: PostRevisionRef,
+    pub content: ContentDiff,
+    pub metadata: Vec<MetadataChange>,
+}
+
+impl PostRevisionDiff {
+    pub fn between(from: &PostRevision, to: &PostRevision) -> Self {
+        let diff = unified_diff(
+            &from.content_markdown,
+            &to.content_markdown,
+            &format!("revision {}", from.revision),
+            &format!("revision {}", to.revision),
+            DIFF_CONTEXT_LINES,
+        );
+
+        let metadata = from
+            .metadata()
+            .into_iter()
+            .zip(to.metadata())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((field, old), (_, new))| MetadataChange { field, from: old, to: new })
+            .collect();
+
+        PostRevisionDiff {
+            post_id: to.post_id,
+            from: PostRevisionRef { revision: from.revision, created_at: from.created_at },
+            to: PostRevisionRef { revision: to.revision, created_at: to.created_at },
+            content: ContentDiff { unified: diff.text, additions: diff.additions, deletions: diff.deletions },
+            metadata,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagSuggestionsResponse {
+    pub suggestions: Vec<TagSuggestion>,
+    /// Posts in the index the draft was compared against
+    pub indexed_posts: usize,
+    pub indexed_at: DateTime<Utc>,
+}
+
+/// Where a post stands on the content calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarStatus {
+    Draft,
+    Scheduled,
+    Published,
+}
+
+impl CalendarStatus {
+    /// A future `published_at` counts as scheduled whatever `published` says
+    pub fn of(post: &BlogPost, now: DateTime<Utc>) -> Self {
+        match (post.published_at, post.published) {
+            (Some(at), _) if at > now => CalendarStatus::Scheduled,
+            (_, true) => CalendarStatus::Published,
+            (_, false) => CalendarStatus::Draft,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarEntry {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub status: CalendarStatus,
+    pub published_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Posts filed under one UTC date: by `published_at`, else by last edit
+#[derive(Debug, Serialize)]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    pub posts: Vec<CalendarEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarResponse {
+    pub month: String,
+    pub days: Vec<CalendarDay>,
 }
 
 // ───── Input & Validation Requests ──────────────────────────────────
@@ -152,8 +500,9 @@ pub struct NewBlogPostRequest {
     )]
     pub slug: Option<String>,
 
+    /// Generated from the first paragraph of the content when left out
     #[validate(length(min = MIN_EXCERPT_LENGTH, max = MAX_EXCERPT_LENGTH))]
-    pub excerpt: String,
+    pub excerpt: Option<String>,
 
     pub content_markdown: String,
 
@@ -213,6 +562,71 @@ pub struct UpdateBlogPostRequest {
     pub published_at: OptionField<DateTime<Utc>>,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct SlugCheckQuery {
+    #[validate(length(max = MAX_TITLE_LENGTH))]
+    pub title: Option<String>,
+
+    #[validate(length(max = MAX_SLUG_LENGTH))]
+    pub slug: Option<String>,
+
+    /// Post being edited, so its own slug does not count as taken
+    pub exclude_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AdminSearchQuery {
+    #[validate(length(min = 2, max = 100))]
+    pub q: String,
+
+    pub status: Option<PostStatus>,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+
+    #[validate(length(min = 1, max = MAX_TAG_LENGTH))]
+    pub tag: Option<String>,
+
+    /// Inclusive bounds on `created_at`
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+/// A draft to suggest tags for; the title counts like a line of content
+#[derive(Debug, Deserialize, Validate)]
+pub struct SuggestTagsRequest {
+    #[validate(length(max = MAX_TITLE_LENGTH))]
+    pub title: Option<String>,
+
+    #[validate(length(min = 1, max = MAX_SUGGEST_CONTENT_LENGTH))]
+    pub content_markdown: String,
+
+    #[validate(range(min = 1, max = MAX_TAG_SUGGESTIONS))]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CalendarQuery {
+    /// `YYYY-MM`, in UTC
+    #[validate(custom(function = "validate_calendar_month"))]
+    pub month: String,
+}
+
+impl CalendarQuery {
+    pub fn first_day(&self) -> Option<NaiveDate> {
+        parse_calendar_month(&self.month)
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReschedulePostRequest {
+    #[validate(custom(function = "validate_future_datetime"))]
+    pub published_at: DateTime<Utc>,
+}
+
 // ───── Validation Helpers ───────────────────────────────────────────
 pub fn validate_optional_url(url: &str) -> Result<(), ValidationError> {
     validate_url(url)
@@ -239,6 +653,17 @@ pub fn validate_future_datetime(dt: &DateTime<Utc>) -> Result<(), ValidationErro
     Ok(())
 }
 
+fn parse_calendar_month(month: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d").ok()
+}
+
+pub fn validate_calendar_month(month: &str) -> Result<(), ValidationError> {
+    if parse_calendar_month(month).is_none() {
+        return Err(new_validation_error("month_invalid", "Month must look like 2024-07"));
+    }
+    Ok(())
+}
+
 pub fn validate_slug(slug: &str) -> Result<(), ValidationError> {
     if slug.is_empty() {
         return Err(new_validation_error("slug_empty", "Slug cannot be empty"));
@@ -316,6 +741,45 @@ pub fn validate_title(title: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Derives a slug from a title, returning `None` when the result would be
+/// shorter than the minimum slug length. Any `Some` value passes `validate_slug`.
+pub fn slug_from_title(title: &str) -> Option<String> {
+    let generated = slug::slugify(title);
+    if generated.len() < MIN_SLUG_LENGTH as usize {
+        return None;
+    }
+    Some(generated)
+}
+
+/// Normalizes arbitrary input into slug form, cut to the maximum slug length
+/// on a word boundary where possible.
+pub fn normalize_slug(input: &str) -> Option<String> {
+    let generated = slug_from_title(input)?;
+    Some(truncate_slug(&generated, MAX_SLUG_LENGTH as usize))
+}
+
+/// `base-n`, shortening `base` so the result still fits `MAX_SLUG_LENGTH`
+pub fn slug_with_suffix(base: &str, n: u32) -> String {
+    let suffix = format!("-{}", n);
+    let base = truncate_slug(base, MAX_SLUG_LENGTH as usize - suffix.len());
+    format!("{}{}", base, suffix)
+}
+
+fn truncate_slug(slug: &str, max_len: usize) -> String {
+    if slug.len() <= max_len {
+        return slug.to_string();
+    }
+    let cut = &slug[..max_len];
+    match cut.rfind('-') {
+        Some(idx) if idx >= MIN_SLUG_LENGTH as usize => cut[..idx].to_string(),
+        _ => cut.trim_end_matches('-').to_string(),
+    }
+}
+
+pub fn slug_is_valid(slug: &str) -> bool {
+    (MIN_SLUG_LENGTH as usize..=MAX_SLUG_LENGTH as usize).contains(&slug.len()) && validate_slug(slug).is_ok()
+}
+
 fn new_validation_error(code: &'static str, msg: &'static str) -> ValidationError {
     let mut err = ValidationError::new(code);
     err.message = Some(Cow::Borrowed(msg));
@@ -327,6 +791,8 @@ impl From<BlogPostRow> for BlogPost {
     fn from(row: BlogPostRow) -> Self {
         BlogPost {
             id: row.id,
+            tenant_id: row.tenant_id,
+            author_id: row.author_id,
             title: row.title,
             slug: row.slug,
             excerpt: row.excerpt,
@@ -340,6 +806,8 @@ impl From<BlogPostRow> for BlogPost {
             updated_at: row.updated_at,
             created_at: row.created_at,
             deleted_at: row.deleted_at,
+            featured: row.featured,
+            featured_order: row.featured_order,
         }
     }
 }
@@ -354,23 +822,36 @@ impl TryFrom<NewBlogPostRequest> for BlogPostInsert {
         // Generate slug if not provided
         let slug = match value.slug {
             Some(s) => s,
-            None => {
-                let generated = slug::slugify(&value.title);
-                if generated.len() < MIN_SLUG_LENGTH as usize {
+            None => match slug_from_title(&value.title) {
+                Some(generated) => generated,
+                None => {
                     return Err({
                         let mut errors = ValidationErrors::new();
                         errors.add("slug", new_validation_error("slug_too_short", "Generated slug is too short; please provide a custom slug"));
                         errors
                     });
                 }
-                generated
-            }
+            },
+        };
+
+        let (excerpt, excerpt_generated) = match value.excerpt {
+            Some(excerpt) => (excerpt, false),
+            None => match first_paragraph_excerpt(&value.content_markdown, MAX_EXCERPT_LENGTH as usize) {
+                Some(generated) if generated.chars().count() as u64 >= MIN_EXCERPT_LENGTH => (generated, true),
+                _ => {
+                    return Err({
+                        let mut errors = ValidationErrors::new();
+                        errors.add("excerpt", new_validation_error("excerpt_not_generated", "Content has no opening paragraph long enough for an excerpt; please provide one"));
+                        errors
+                    });
+                }
+            },
         };
 
         let insert = BlogPostInsert {
             title: value.title,
             slug,
-            excerpt: value.excerpt,
+            excerpt,
             content_markdown: sanitized_content,
             cover_image_url: value.cover_image_url,
             tags: value.tags,
@@ -380,6 +861,8 @@ impl TryFrom<NewBlogPostRequest> for BlogPostInsert {
             published_at: value.published_at,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            author_id: None,
+            excerpt_generated,
         };
 
         insert.validate()?;
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::{
+    blog_post::{validate_optional_tags, validate_tags, validate_url},
+    option_fields::{OptionField, PatchString},
+    view::admin_only,
+};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+pub const MAX_URL_LENGTH: u64 = 2048;
+pub const MAX_TITLE_LENGTH: u64 = 200;
+pub const MAX_NOTE_LENGTH: u64 = 2000;
+
+pub const PREVIEW_PENDING: &str = "pending";
+pub const PREVIEW_READY: &str = "ready";
+pub const PREVIEW_FAILED: &str = "failed";
+
+/// Fetches tried before a preview is given up on; an admin can ask again
+pub const MAX_PREVIEW_ATTEMPTS: i32 = 3;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+/// A saved link. The public blogroll leaves out the preview bookkeeping.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Bookmark {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub url: String,
+    /// Shown instead of the page's own title when set
+    pub title: Option<String>,
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub is_public: bool,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub preview_status: String,
+    pub preview_title: Option<String>,
+    pub preview_description: Option<String>,
+    pub preview_image_url: Option<String>,
+    pub preview_site_name: Option<String>,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub preview_error: Option<String>,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub preview_attempts: i32,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub preview_fetched_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct BookmarkInsert {
+    pub url: String,
+    pub title: Option<String>,
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    pub is_public: bool,
+}
+
+/// What the fetcher found on the page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkPreview {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct NewBookmarkRequest {
+    #[validate(length(max = MAX_URL_LENGTH), custom(function = "validate_url"))]
+    pub url: String,
+
+    #[validate(length(min = 1, max = MAX_TITLE_LENGTH))]
+    pub title: Option<String>,
+
+    #[validate(length(max = MAX_NOTE_LENGTH))]
+    pub note: Option<String>,
+
+    #[validate(custom(function = "validate_tags"))]
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Defaults to listed on the blogroll
+    pub is_public: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, Default)]
+#[serde(default)]
+pub struct UpdateBookmarkRequest {
+    /// Changing the URL fetches a new preview
+    #[validate(length(max = MAX_URL_LENGTH), custom(function = "validate_url"))]
+    pub url: Option<String>,
+
+    #[validate(length(min = 1, max = MAX_TITLE_LENGTH))]
+    pub title: PatchString,
+
+    #[validate(length(max = MAX_NOTE_LENGTH))]
+    pub note: PatchString,
+
+    #[validate(custom(function = "validate_optional_tags"))]
+    pub tags: OptionField<Vec<String>>,
+
+    pub is_public: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BookmarkQuery {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<u32>,
+
+    pub tag: Option<String>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct BookmarkListResponse {
+    pub bookmarks: Vec<Bookmark>,
+    pub total: i64,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+// ───── Conversions ───────────────────────────────────────────────────
+
+impl From<NewBookmarkRequest> for BookmarkInsert {
+    fn from(req: NewBookmarkRequest) -> Self {
+        Self {
+            url: req.url.trim().to_string(),
+            title: req.title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+            note: req.note.filter(|n| !n.trim().is_empty()),
+            tags: normalize_tags(req.tags),
+            is_public: req.is_public.unwrap_or(true),
+        }
+    }
+}
+
+/// Lowercased and deduplicated, in first-seen order
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
\ No newline at end of file
@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::utils::markdown::safe_markdown_to_html;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+pub const MAX_VERSION_LENGTH: u64 = 40;
+pub const MAX_NOTES_LENGTH: u64 = 10_000;
+
+// ───── Categories ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogCategory {
+    Feature,
+    Improvement,
+    Fix,
+    Content,
+    Deploy,
+}
+
+impl ChangelogCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangelogCategory::Feature => "feature",
+            ChangelogCategory::Improvement => "improvement",
+            ChangelogCategory::Fix => "fix",
+            ChangelogCategory::Content => "content",
+            ChangelogCategory::Deploy => "deploy",
+        }
+    }
+}
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ChangelogEntry {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub version: String,
+    pub released_on: NaiveDate,
+    pub notes_markdown: String,
+    pub category: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ChangelogEntryInsert {
+    pub version: String,
+    pub released_on: NaiveDate,
+    pub notes_markdown: String,
+    pub category: String,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct NewChangelogEntryRequest {
+    #[validate(length(min = 1, max = MAX_VERSION_LENGTH), custom(function = "validate_version"))]
+    pub version: String,
+
+    /// Defaults to today
+    pub released_on: Option<NaiveDate>,
+
+    #[validate(length(min = 1, max = MAX_NOTES_LENGTH))]
+    pub notes_markdown: String,
+
+    pub category: ChangelogCategory,
+}
+
+#[derive(Debug, Deserialize, Validate, Default)]
+#[serde(default)]
+pub struct UpdateChangelogEntryRequest {
+    #[validate(length(min = 1, max = MAX_VERSION_LENGTH), custom(function = "validate_version"))]
+    pub version: Option<String>,
+
+    pub released_on: Option<NaiveDate>,
+
+    #[validate(length(min = 1, max = MAX_NOTES_LENGTH))]
+    pub notes_markdown: Option<String>,
+
+    pub category: Option<ChangelogCategory>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangelogQuery {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<u32>,
+
+    pub category: Option<ChangelogCategory>,
+}
+
+/// Body sent by CI after a deploy
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeployHookRequest {
+    #[validate(length(min = 1, max = MAX_VERSION_LENGTH), custom(function = "validate_version"))]
+    pub version: String,
+
+    #[validate(length(max = MAX_NOTES_LENGTH))]
+    pub notes: Option<String>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct ChangelogEntryResponse {
+    pub id: Uuid,
+    pub version: String,
+    pub released_on: NaiveDate,
+    pub category: String,
+    pub notes_markdown: String,
+    pub notes_html: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangelogListResponse {
+    pub entries: Vec<ChangelogEntryResponse>,
+    pub total: i64,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+// ───── Conversions ───────────────────────────────────────────────────
+
+impl From<NewChangelogEntryRequest> for ChangelogEntryInsert {
+    fn from(req: NewChangelogEntryRequest) -> Self {
+        Self {
+            version: req.version.trim().to_string(),
+            released_on: req.released_on.unwrap_or_else(|| Utc::now().date_naive()),
+            notes_markdown: req.notes_markdown,
+            category: req.category.as_str().to_string(),
+        }
+    }
+}
+
+impl From<DeployHookRequest> for ChangelogEntryInsert {
+    fn from(req: DeployHookRequest) -> Self {
+        let version = req.version.trim().to_string();
+
+        Self {
+            notes_markdown: req
+                .notes
+                .filter(|n| !n.trim().is_empty())
+                .unwrap_or_else(|| format!("Deployed {}.", version)),
+            version,
+            released_on: Utc::now().date_naive(),
+            category: ChangelogCategory::Deploy.as_str().to_string(),
+        }
+    }
+}
+
+impl From<ChangelogEntry> for ChangelogEntryResponse {
+    fn from(entry: ChangelogEntry) -> Self {
+        Self {
+            notes_html: safe_markdown_to_html(&entry.notes_markdown),
+            id: entry.id,
+            version: entry.version,
+            released_on: entry.released_on,
+            category: entry.category,
+            notes_markdown: entry.notes_markdown,
+            created_at: entry.created_at,
+            updated_at: entry.updated_at,
+        }
+    }
+}
+
+// ───── Validation Helpers ───────────────────────────────────────────
+
+/// Version tags like `v1.4.0`, `2025.10.16` or a short commit SHA
+fn validate_version(version: &str) -> Result<(), ValidationError> {
+    if !version.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+')) {
+        let mut err = ValidationError::new("invalid_version");
+        err.message = Some(Cow::Borrowed("Version may only contain letters, digits and . - _ +"));
+        return Err(err);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+use validator::Validate;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// A tenant's runtime setting; the value is the setting's JSON
+pub const CONFIG_KIND_SETTING: &str = "setting";
+/// A feature flag, shared by every tenant; the value is `enabled`
+pub const CONFIG_KIND_FEATURE_FLAG: &str = "feature_flag";
+
+// ───── Database Models ───────────────────────────────────────────────
+
+/// One versioned change to a setting or feature flag
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct ConfigChange {
+    /// Increases with every change; what a rollback names
+    pub version: i64,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub kind: String,
+    pub key: String,
+    /// `None` when the key was unset before this change
+    pub previous_value: Option<JsonValue>,
+    /// `None` when the change removed the key
+    pub new_value: Option<JsonValue>,
+    pub actor_id: Option<Uuid>,
+    /// The version this change restored, for changes made by a rollback
+    pub rollback_to: Option<i64>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Who made a change, and why
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigChangeSource {
+    pub actor_id: Uuid,
+    pub rollback_to: Option<i64>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct ConfigHistoryQuery {
+    #[validate(range(min = 1, max = 200))]
+    pub limit: Option<u32>,
+    /// Only versions older than this, for paging back
+    pub before: Option<i64>,
+    /// Only changes to this setting or flag
+    pub key: Option<String>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct ConfigHistoryPage {
+    /// Newest first
+    pub changes: Vec<ConfigChange>,
+    /// `before` for the next page; `None` on the last one
+    pub next_before: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigRollbackResponse {
+    pub rolled_back_to: i64,
+    /// The changes the rollback made; empty when nothing had changed since
+    pub changes: Vec<ConfigChange>,
+}
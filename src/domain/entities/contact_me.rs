@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::{Validate, ValidationErrors};
 
+use crate::entities::{pii::pii, view::admin_only};
+
 // ============================== ContactMe Entities ==============================
 
 #[derive(Debug, Deserialize, Validate)]
@@ -20,6 +22,8 @@ pub struct NewContactMeForm {
     pub message: String,
 }
 
+pii!(NewContactMeForm { name, email, message });
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct ContactMeQuery {
     #[validate(range(min = 1, max = 100))]
@@ -31,6 +35,9 @@ pub struct ContactMeQuery {
 
 #[derive(Debug, Validate)]
 pub struct ContactMeFormInsert {
+    /// Assigned up front so the sender gets it back before the row is written
+    pub id: Uuid,
+
     #[validate(length(min = 2, max = 100))]
     pub name: String,
 
@@ -45,27 +52,91 @@ pub struct ContactMeFormInsert {
 
     pub created_at: DateTime<Utc>,
 
+    /// Filled from the sender's IP when geo lookups are on
+    pub country_code: Option<String>,
+    pub region: Option<String>,
+}
 
+pii!(ContactMeFormInsert { name, email, message });
+
+/// Filters for `GET /admin/contact-messages/export.csv`.
+/// `from` is inclusive, `to` is exclusive.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ContactMeExportQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub spam: Option<bool>,
+    /// Keep the sender's name, email and message; redacted by default
+    #[serde(default)]
+    pub include_pii: bool,
 }
 
+/// Keyset position for chunked reads: the last `(created_at, id)` seen.
+pub type ContactMeCursor = (DateTime<Utc>, Uuid);
+
 // ============================= DB Models ==============================
 
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct ContactMeMessage {
     pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
     pub name: String,
     pub email: String,
     pub subject: Option<String>,
     pub message: String,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "admin_only")]
     pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub is_spam: bool,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub country_code: Option<String>,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub region: Option<String>,
+    /// Set once retention replaced the sender's details
+    #[serde(skip_serializing_if = "admin_only")]
+    pub anonymized_at: Option<DateTime<Utc>>,
+}
+
+pii!(ContactMeMessage { name, email, message });
+
+/// The sealed fields of one message as stored, for key rotation.
+/// `identity_email` is the sender's address stashed by anonymization.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ContactSecret {
+    pub message_id: Uuid,
+    pub email: String,
+    pub message: String,
+    pub identity_email: Option<String>,
+}
+
+pii!(ContactSecret { email, message, identity_email });
+
+/// A row re-sealed by key rotation; only written while the row still
+/// holds `before`, so concurrent changes such as anonymization win
+#[derive(Debug, Clone)]
+pub struct ContactSecretRewrite {
+    pub before: ContactSecret,
+    pub after: ContactSecret,
 }
 
 // ======================= Responses =======================
 
+/// Outcome of `--rekey-contact-messages`. `failed` rows could not be opened
+/// with any configured key and were left as they are.
+#[derive(Debug, Default, Serialize)]
+pub struct ContactRekeyReport {
+    pub scanned: u64,
+    pub rewritten: u64,
+    pub failed: Vec<Uuid>,
+}
+
+
 #[derive(Debug, Serialize)]
 pub struct ContactMeResponse {
     pub message: String,
+    #[serde(skip_serializing_if = "admin_only")]
     pub id: Uuid,
 }
 
@@ -75,6 +146,45 @@ pub struct ContactMeListResponse {
     pub total: i64,
 }
 
+// ======================= Auto-Reply =======================
+
+/// Acknowledgment emailed to whoever submits the contact form, kept in the
+/// `contact_auto_reply` runtime setting
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ContactAutoReply {
+    pub enabled: bool,
+
+    /// Completes "I usually reply ...", e.g. "within two business days"
+    #[validate(length(min = 1, max = 100))]
+    pub response_time: String,
+
+    /// Replaces the default subject
+    #[validate(length(min = 1, max = 150))]
+    pub subject: Option<String>,
+
+    /// Replaces the default text; `{name}` and `{response_time}` are filled in
+    #[validate(length(min = 1, max = 2000))]
+    pub message: Option<String>,
+
+    /// At most one auto-reply per address in this many hours
+    #[serde(default = "default_auto_reply_throttle_hours")]
+    #[validate(range(min = 1, max = 720))]
+    pub throttle_hours: u32,
+}
+
+fn default_auto_reply_throttle_hours() -> u32 {
+    24
+}
+
+impl ContactAutoReply {
+    /// The custom message for one sender, if one is set
+    pub fn message_for(&self, name: &str) -> Option<String> {
+        self.message
+            .as_ref()
+            .map(|text| text.replace("{name}", name).replace("{response_time}", &self.response_time))
+    }
+}
+
 // ======================= Conversions =======================
 
 impl TryFrom<NewContactMeForm> for ContactMeFormInsert {
@@ -84,11 +194,14 @@ impl TryFrom<NewContactMeForm> for ContactMeFormInsert {
         form.validate()?;
 
         Ok(Self {
+            id: Uuid::new_v4(),
             name: form.name,
             email: form.email,
             subject: form.subject,
             message: form.message,
             created_at: Utc::now(),
+            country_code: None,
+            region: None,
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,194 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{
+    about_me::{AboutMeInsert, AboutMeResponse},
+    blog_post::{BlogPost, BlogPostInsert, UpdateBlogPostRequest},
+    option_fields::OptionField,
+};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Bumped whenever the file layout changes incompatibly
+pub const CONTENT_FIXTURE_FORMAT: u32 = 1;
+/// Upload limit for `POST /admin/fixtures/load`
+pub const MAX_FIXTURE_BYTES: usize = 20 * 1024 * 1024;
+
+// ───── Fixture File ──────────────────────────────────────────────────
+
+/// Portable snapshot of a tenant's content. Ids and timestamps other than
+/// `published_at` are left out so the same file loads into any environment;
+/// posts are matched by slug and sorted by it to keep diffs between
+/// exports small.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentFixture {
+    pub format: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Tenant the content was exported from; informational only
+    pub source_tenant: String,
+    #[serde(default)]
+    pub about: Option<AboutFixture>,
+    #[serde(default)]
+    pub posts: Vec<PostFixture>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AboutFixture {
+    pub effective_date: NaiveDate,
+    pub content_markdown: String,
+}
+
+/// Optional fields left out of a fixture are left untouched on load
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostFixture {
+    pub slug: String,
+    pub title: String,
+    pub excerpt: String,
+    pub content_markdown: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seo_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seo_description: Option<String>,
+    pub published: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl From<&BlogPost> for PostFixture {
+    fn from(post: &BlogPost) -> Self {
+        PostFixture {
+            slug: post.slug.clone(),
+            title: post.title.clone(),
+            excerpt: post.excerpt.clone(),
+            content_markdown: post.content_markdown.clone(),
+            cover_image_url: post.cover_image_url.clone(),
+            tags: post.tags.clone(),
+            seo_title: post.seo_title.clone(),
+            seo_description: post.seo_description.clone(),
+            published: post.published,
+            published_at: post.published_at,
+        }
+    }
+}
+
+impl From<AboutMeResponse> for AboutFixture {
+    fn from(about: AboutMeResponse) -> Self {
+        AboutFixture {
+            effective_date: about.effective_date,
+            content_markdown: about.content_markdown,
+        }
+    }
+}
+
+impl AboutFixture {
+    pub fn matches(&self, about: &AboutMeResponse) -> bool {
+        self.effective_date == about.effective_date && self.content_markdown == about.content_markdown
+    }
+
+    pub fn to_insert(&self) -> AboutMeInsert {
+        AboutMeInsert {
+            revision: 0,
+            content_markdown: self.content_markdown.clone(),
+            effective_date: self.effective_date,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl PostFixture {
+    pub fn to_insert(&self) -> BlogPostInsert {
+        let now = Utc::now();
+        BlogPostInsert {
+            title: self.title.clone(),
+            slug: self.slug.clone(),
+            excerpt: self.excerpt.clone(),
+            content_markdown: self.content_markdown.clone(),
+            cover_image_url: self.cover_image_url.clone(),
+            tags: self.tags.clone(),
+            seo_title: self.seo_title.clone(),
+            seo_description: self.seo_description.clone(),
+            published: self.published,
+            published_at: self.published_at.or(self.published.then_some(now)),
+            created_at: now,
+            updated_at: now,
+            author_id: None,
+            excerpt_generated: false,
+        }
+    }
+
+    /// True when loading this fixture would leave `post` as it is
+    pub fn matches(&self, post: &BlogPost) -> bool {
+        fn same<T: PartialEq>(wanted: &Option<T>, current: &Option<T>) -> bool {
+            wanted.is_none() || wanted == current
+        }
+
+        self.title == post.title
+            && self.excerpt == post.excerpt
+            && self.content_markdown == post.content_markdown
+            && self.published == post.published
+            && same(&self.cover_image_url, &post.cover_image_url)
+            && same(&self.tags, &post.tags)
+            && same(&self.seo_title, &post.seo_title)
+            && same(&self.seo_description, &post.seo_description)
+            && same(&self.published_at, &post.published_at)
+    }
+
+    pub fn to_update(&self) -> UpdateBlogPostRequest {
+        fn set<T: Clone>(value: &Option<T>) -> OptionField<T> {
+            value.clone().map_or(OptionField::Unchanged, OptionField::SetToValue)
+        }
+
+        UpdateBlogPostRequest {
+            title: OptionField::SetToValue(self.title.clone()),
+            // Pinned so a title change does not regenerate the slug
+            slug: OptionField::SetToValue(self.slug.clone()),
+            excerpt: OptionField::SetToValue(self.excerpt.clone()),
+            content_markdown: OptionField::SetToValue(self.content_markdown.clone()),
+            cover_image_url: set(&self.cover_image_url),
+            tags: set(&self.tags),
+            seo_title: set(&self.seo_title),
+            seo_description: set(&self.seo_description),
+            published: OptionField::SetToValue(self.published),
+            published_at: set(&self.published_at),
+        }
+    }
+}
+
+// ───── Load Reports ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureChange {
+    Created,
+    Unchanged,
+    /// The fixture had no entry for it
+    Absent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureLoadReport {
+    /// Nothing was written; the report shows what a real load would do
+    pub dry_run: bool,
+    pub about: FixtureChange,
+    pub posts_created: Vec<String>,
+    pub posts_updated: Vec<String>,
+    pub posts_unchanged: usize,
+}
+
+impl FixtureLoadReport {
+    /// True when the load created or changed posts
+    pub fn wrote_posts(&self) -> bool {
+        !self.dry_run && (!self.posts_created.is_empty() || !self.posts_updated.is_empty())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FixtureLoadQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
\ No newline at end of file
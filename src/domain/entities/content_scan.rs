@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::content_scan::ContentFinding;
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+/// `POST /blog/admin/posts/scan`: checks a draft without saving it
+#[derive(Debug, Deserialize)]
+pub struct ContentScanRequest {
+    pub content_markdown: String,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+/// A scanner finding in one field of the submitted content
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentWarning {
+    pub field: String,
+    #[serde(flatten)]
+    pub finding: ContentFinding,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContentScanResponse {
+    /// Whether saving this content would be refused
+    pub blocked: bool,
+    pub warnings: Vec<ContentWarning>,
+}
+
+/// A saved post's usual response, plus what the scanner flagged in it
+#[derive(Debug, Serialize)]
+pub struct WithContentWarnings<T> {
+    #[serde(flatten)]
+    pub inner: T,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub content_warnings: Vec<ContentWarning>,
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Deserialize)]
+pub struct EditLockQuery {
+    /// Take the lock even when someone else holds it; admins only
+    #[serde(default)]
+    pub force: bool,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+/// Who is editing a post, until `expires_at` unless they send a heartbeat
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PostEditLock {
+    pub post_id: Uuid,
+    pub holder_id: Uuid,
+    pub holder_email: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EditLockResponse {
+    pub lock: PostEditLock,
+    /// Send the next heartbeat within this many seconds to keep the lock
+    pub next_beat_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EditLockStatus {
+    pub post_id: Uuid,
+    pub lock: Option<PostEditLock>,
+}
+
+/// Body of the 423 returned while someone else holds the lock
+#[derive(Debug, Serialize)]
+pub struct EditLockConflict {
+    pub error: String,
+    pub lock: PostEditLock,
+}
+
+impl EditLockConflict {
+    pub fn new(lock: PostEditLock) -> Self {
+        EditLockConflict {
+            error: format!("{} is editing this post", lock.holder_email),
+            lock,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    /// Subject, text and HTML parts together
+    #[default]
+    Json,
+    Html,
+    Text,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmailPreviewQuery {
+    /// Language tag such as `fr`; defaults to the tenant's `email_locale`
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub format: PreviewFormat,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct EmailTemplateListResponse {
+    pub templates: Vec<&'static str>,
+    pub locales: Vec<&'static str>,
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+/// Query of a signed storage link
+#[derive(Debug, Deserialize)]
+pub struct StorageDownloadQuery {
+    pub token: String,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+/// An export written to object storage, with a link to fetch it
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredExport {
+    pub key: String,
+    pub size: u64,
+    pub download_url: String,
+    pub expires_at: DateTime<Utc>,
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+// ───── Constants ──────────────────────────────────────────────────────
+pub const MAX_FLAG_KEY_LENGTH: usize = 64;
+const MAX_FLAG_DESCRIPTION_LENGTH: u64 = 255;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct FeatureFlag {
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub key: String,
+    pub enabled: bool,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateFeatureFlagRequest {
+    pub enabled: bool,
+
+    #[validate(length(max = MAX_FLAG_DESCRIPTION_LENGTH))]
+    pub description: Option<String>,
+}
+
+/// Flag keys are lowercase ASCII letters, digits, `_` or `-` (mirrors the table CHECK).
+pub fn is_valid_flag_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_FLAG_KEY_LENGTH
+        && key.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-')
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::entities::stats_rollup::{COUNTRY_SOURCE_ATTACKS, COUNTRY_SOURCE_CLICKS};
+
+/// Where a client IP is, as far as the GeoLite2 database knows
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GeoLocation {
+    /// ISO 3166-1 alpha-2, e.g. `NG`
+    pub country_code: Option<String>,
+    /// ISO code of the first subdivision (state, province), e.g. `LA`
+    pub region: Option<String>,
+}
+
+/// Rows of one `source` counted for one country; `None` is traffic that
+/// could not be located
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CountryCount {
+    /// `COUNTRY_SOURCE_CLICKS` or `COUNTRY_SOURCE_ATTACKS`
+    pub source: String,
+    pub country_code: Option<String>,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoDatabaseInfo {
+    pub path: String,
+    pub database_type: String,
+    pub built_at: Option<DateTime<Utc>>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct GeoReportQuery {
+    /// Look-back window; defaults to 30 days
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<u32>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoCountrySummary {
+    pub country_code: Option<String>,
+    /// Outbound link clicks
+    pub clicks: i64,
+    /// Honeytoken hits
+    pub attacks: i64,
+}
+
+impl GeoCountrySummary {
+    /// One entry per country, busiest first and unlocated traffic last
+    pub fn from_counts(counts: Vec<CountryCount>) -> Vec<GeoCountrySummary> {
+        let mut by_country: BTreeMap<Option<String>, GeoCountrySummary> = BTreeMap::new();
+        for count in counts {
+            let summary = by_country
+                .entry(count.country_code.clone())
+                .or_insert_with(|| GeoCountrySummary { country_code: count.country_code, clicks: 0, attacks: 0 });
+            match count.source.as_str() {
+                COUNTRY_SOURCE_CLICKS => summary.clicks += count.count,
+                COUNTRY_SOURCE_ATTACKS => summary.attacks += count.count,
+                _ => {}
+            }
+        }
+
+        let mut countries: Vec<GeoCountrySummary> = by_country.into_values().collect();
+        countries.sort_by(|a, b| {
+            a.country_code
+                .is_none()
+                .cmp(&b.country_code.is_none())
+                .then_with(|| (b.clicks + b.attacks).cmp(&(a.clicks + a.attacks)))
+                .then_with(|| a.country_code.cmp(&b.country_code))
+        });
+
+        countries
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeoReportResponse {
+    pub since: DateTime<Utc>,
+    /// `None` when no database is loaded; only older rows have countries then
+    pub database: Option<GeoDatabaseInfo>,
+    /// Busiest countries first, unlocated traffic last
+    pub countries: Vec<GeoCountrySummary>,
+}
\ No newline at end of file
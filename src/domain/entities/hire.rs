@@ -0,0 +1,244 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::entities::pii::pii;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Inquiries with more links than this are treated as spam
+pub const MAX_INQUIRY_LINKS: usize = 3;
+
+// ───── Availability ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AvailabilityStatus {
+    Open,
+    #[default]
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateUnit {
+    Hourly,
+    Daily,
+    Monthly,
+    Project,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateRange {
+    pub min: u32,
+    pub max: u32,
+    /// ISO 4217 code, e.g. `USD`
+    pub currency: String,
+
+    pub unit: RateUnit,
+}
+
+/// Public "hire me" status, stored per tenant as the `hire_availability`
+/// runtime setting. Tenants that never set it are reported as closed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct HireAvailability {
+    pub status: AvailabilityStatus,
+
+    #[serde(default)]
+    #[validate(length(max = 10), custom(function = "validate_roles"))]
+    pub preferred_roles: Vec<String>,
+
+    #[validate(custom(function = "validate_rate_range"))]
+    pub rate_range: Option<RateRange>,
+
+    #[validate(length(max = 500))]
+    pub note: Option<String>,
+}
+
+impl HireAvailability {
+    pub fn is_open(&self) -> bool {
+        self.status == AvailabilityStatus::Open
+    }
+}
+
+// ───── Inquiry Requests ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BudgetRange {
+    #[serde(rename = "under_5k")]
+    Under5k,
+    #[serde(rename = "5k_15k")]
+    From5kTo15k,
+    #[serde(rename = "15k_50k")]
+    From15kTo50k,
+    #[serde(rename = "over_50k")]
+    Over50k,
+    #[serde(rename = "undisclosed")]
+    Undisclosed,
+}
+
+impl BudgetRange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetRange::Under5k => "under_5k",
+            BudgetRange::From5kTo15k => "5k_15k",
+            BudgetRange::From15kTo50k => "15k_50k",
+            BudgetRange::Over50k => "over_50k",
+            BudgetRange::Undisclosed => "undisclosed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HireTimeline {
+    Asap,
+    WithinMonth,
+    OneToThreeMonths,
+    Flexible,
+}
+
+impl HireTimeline {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HireTimeline::Asap => "asap",
+            HireTimeline::WithinMonth => "within_month",
+            HireTimeline::OneToThreeMonths => "one_to_three_months",
+            HireTimeline::Flexible => "flexible",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct NewHireInquiry {
+    #[validate(length(min = 2, max = 100))]
+    pub name: String,
+
+    #[validate(email)]
+    pub email: String,
+
+    #[validate(length(min = 1, max = 120))]
+    pub company: String,
+
+    #[validate(length(max = 100))]
+    pub role: Option<String>,
+
+    pub budget: BudgetRange,
+
+    pub timeline: HireTimeline,
+
+    #[validate(length(min = 20, max = 5000))]
+    pub message: String,
+
+    /// Honeypot: hidden in the frontend form, so only bots fill it in
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+pii!(NewHireInquiry { name, email, message });
+
+impl NewHireInquiry {
+    /// Honeypot filled in or the message is mostly links
+    pub fn looks_like_spam(&self) -> bool {
+        let honeypot = self.website.as_deref().is_some_and(|w| !w.trim().is_empty());
+        let links = self.message.matches("http://").count() + self.message.matches("https://").count();
+
+        honeypot || links > MAX_INQUIRY_LINKS
+    }
+}
+
+#[derive(Debug)]
+pub struct HireInquiryInsert {
+    pub name: String,
+    pub email: String,
+    pub company: String,
+    pub role: Option<String>,
+    pub budget: String,
+    pub timeline: String,
+    pub message: String,
+    pub is_spam: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+pii!(HireInquiryInsert { name, email, message });
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct HireInquiry {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub company: String,
+    pub role: Option<String>,
+    pub budget: String,
+    pub timeline: String,
+    pub message: String,
+    pub is_spam: bool,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+pii!(HireInquiry { name, email, message });
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct HireInquiryResponse {
+    pub message: String,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HireInquiryListResponse {
+    pub inquiries: Vec<HireInquiry>,
+    pub total: i64,
+}
+
+// ───── Conversions ───────────────────────────────────────────────────
+
+impl From<NewHireInquiry> for HireInquiryInsert {
+    fn from(form: NewHireInquiry) -> Self {
+        let is_spam = form.looks_like_spam();
+
+        Self {
+            name: form.name.trim().to_string(),
+            email: form.email.trim().to_string(),
+            company: form.company.trim().to_string(),
+            role: form.role.map(|r| r.trim().to_string()).filter(|r| !r.is_empty()),
+            budget: form.budget.as_str().to_string(),
+            timeline: form.timeline.as_str().to_string(),
+            message: form.message,
+            is_spam,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// ───── Validation Helpers ───────────────────────────────────────────
+
+fn validate_rate_range(range: &RateRange) -> Result<(), ValidationError> {
+    if range.min > range.max {
+        let mut err = ValidationError::new("invalid_rate_range");
+        err.message = Some("min must not exceed max".into());
+        return Err(err);
+    }
+    if range.currency.len() != 3 || !range.currency.chars().all(|c| c.is_ascii_uppercase()) {
+        let mut err = ValidationError::new("invalid_currency");
+        err.message = Some("currency must be a three-letter ISO 4217 code".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn validate_roles(roles: &[String]) -> Result<(), ValidationError> {
+    if roles.iter().any(|r| r.trim().is_empty() || r.len() > 60) {
+        let mut err = ValidationError::new("invalid_role");
+        err.message = Some("Roles must be between 1 and 60 characters".into());
+        return Err(err);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// `source_id` is a blog post
+pub const LINK_SOURCE_POST: &str = "post";
+/// `source_id` is the current about-me revision
+pub const LINK_SOURCE_ABOUT: &str = "about";
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct LinkCheck {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub source_type: String,
+    pub source_id: Uuid,
+    pub source_title: String,
+    pub url: String,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub broken: bool,
+    pub broken_since: Option<DateTime<Utc>>,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkCheckInsert {
+    pub source_type: &'static str,
+    pub source_id: Uuid,
+    pub source_title: String,
+    pub url: String,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub broken: bool,
+}
+
+// ───── API Response Models ──────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct BrokenLink {
+    pub url: String,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub broken_since: Option<DateTime<Utc>>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// A post, or the about page, with the links in it that failed
+#[derive(Debug, Serialize)]
+pub struct BrokenLinkSource {
+    pub source_type: String,
+    pub source_id: Uuid,
+    pub title: String,
+    pub links: Vec<BrokenLink>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrokenLinksReport {
+    pub total_broken: usize,
+    pub sources: Vec<BrokenLinkSource>,
+}
+
+impl BrokenLinksReport {
+    /// Groups broken checks by source, keeping the order they come in
+    pub fn from_checks(checks: Vec<LinkCheck>) -> Self {
+        let total_broken = checks.len();
+        let mut sources: Vec<BrokenLinkSource> = Vec::new();
+
+        for check in checks {
+            let link = BrokenLink {
+                url: check.url,
+                status_code: check.status_code,
+                error: check.error,
+                broken_since: check.broken_since,
+                checked_at: check.checked_at,
+            };
+
+            match sources.last_mut() {
+                Some(source) if source.source_type == check.source_type && source.source_id == check.source_id => {
+                    source.links.push(link);
+                }
+                _ => sources.push(BrokenLinkSource {
+                    source_type: check.source_type,
+                    source_id: check.source_id,
+                    title: check.source_title,
+                    links: vec![link],
+                }),
+            }
+        }
+
+        BrokenLinksReport { total_broken, sources }
+    }
+}
\ No newline at end of file
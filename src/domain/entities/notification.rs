@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::{blog_post::validate_optional_url_field, option_fields::OptionField, pii::pii};
+
+/// Things an admin can be told about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    ContactReceived,
+    CommentPending,
+    /// A background job gave up, e.g. an outbox event that ran out of attempts
+    JobFailed,
+    BackupCompleted,
+    /// Email sends, storage or an API token reached a share of their quota
+    QuotaWarning,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 5] = [
+        NotificationEvent::ContactReceived,
+        NotificationEvent::CommentPending,
+        NotificationEvent::JobFailed,
+        NotificationEvent::BackupCompleted,
+        NotificationEvent::QuotaWarning,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::ContactReceived => "contact_received",
+            NotificationEvent::CommentPending => "comment_pending",
+            NotificationEvent::JobFailed => "job_failed",
+            NotificationEvent::BackupCompleted => "backup_completed",
+            NotificationEvent::QuotaWarning => "quota_warning",
+        }
+    }
+}
+
+/// Where a notification is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationChannel {
+    /// To the admin's account email
+    Email,
+    /// JSON `POST` to the admin's `webhook_url`
+    Webhook,
+    /// Pushed to the admin's open `/users/me/notifications/stream` connections
+    Sse,
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Email => "email",
+            NotificationChannel::Webhook => "webhook",
+            NotificationChannel::Sse => "sse",
+        }
+    }
+}
+
+/// Channels per event; an empty set silences the event
+pub type EventChannels = BTreeMap<NotificationEvent, BTreeSet<NotificationChannel>>;
+
+/// Channels of events an admin has not chosen any for: only the live stream,
+/// so nobody gets mail they did not ask for
+pub fn default_channels() -> BTreeSet<NotificationChannel> {
+    BTreeSet::from([NotificationChannel::Sse])
+}
+
+/// What gets delivered: the email subject and body, the webhook payload and
+/// the SSE `data`
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub body: String,
+    /// Admin page with the details, when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(event: NotificationEvent, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Notification {
+            event,
+            title: title.into(),
+            body: body.into(),
+            link: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+}
+
+// ───── Database Models ───────────────────────────────────────────────
+
+/// An admin with their stored preferences; `updated_at` is `None` until
+/// they saved some
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct NotificationRecipient {
+    pub user_id: Uuid,
+    pub email: String,
+    pub webhook_url: Option<String>,
+    /// `EventChannels` as stored
+    pub channels: JsonValue,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+pii!(NotificationRecipient { email });
+
+impl NotificationRecipient {
+    /// Stored choices merged over the defaults, one entry per event
+    pub fn event_channels(&self) -> EventChannels {
+        let stored: EventChannels = serde_json::from_value(self.channels.clone()).unwrap_or_default();
+
+        NotificationEvent::ALL
+            .into_iter()
+            .map(|event| (event, stored.get(&event).cloned().unwrap_or_else(default_channels)))
+            .collect()
+    }
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+/// `PATCH /users/me/notifications`. Events left out of `channels` keep
+/// their current channels; `webhook_url: null` removes the webhook.
+#[derive(Debug, Default, Deserialize, Validate)]
+#[serde(default)]
+pub struct UpdateNotificationPreferencesRequest {
+    #[validate(custom(function = "validate_optional_url_field"))]
+    pub webhook_url: OptionField<String>,
+    pub channels: EventChannels,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationPreferencesResponse {
+    pub webhook_url: Option<String>,
+    pub channels: EventChannels,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<NotificationRecipient> for NotificationPreferencesResponse {
+    fn from(recipient: NotificationRecipient) -> Self {
+        NotificationPreferencesResponse {
+            channels: recipient.event_channels(),
+            webhook_url: recipient.webhook_url,
+            updated_at: recipient.updated_at,
+        }
+    }
+}
\ No newline at end of file
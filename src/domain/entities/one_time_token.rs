@@ -0,0 +1,105 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{domain::password::validate_password_strength, entities::pii::pii};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+pub const PURPOSE_EMAIL_VERIFICATION: &str = "email_verification";
+pub const PURPOSE_PASSWORD_RESET: &str = "password_reset";
+
+/// How long a verification link stays valid
+pub const EMAIL_VERIFICATION_TTL_HOURS: u32 = 24;
+/// How long a password reset link stays valid
+pub const PASSWORD_RESET_TTL_MINUTES: u32 = 60;
+
+/// Path of the site's page that reads `?token=` from a verification link
+pub const EMAIL_VERIFICATION_PATH: &str = "/verify-email";
+/// Path of the site's page that reads `?token=` from a password reset link
+pub const PASSWORD_RESET_PATH: &str = "/reset-password";
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OneTimeToken {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: String,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OneTimeToken {
+    pub fn is_valid(&self, purpose: &str, now: DateTime<Utc>) -> bool {
+        self.purpose == purpose && self.used_at.is_none() && self.expires_at > now
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OneTimeTokenInsert {
+    pub user_id: Uuid,
+    pub purpose: &'static str,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl OneTimeTokenInsert {
+    pub fn new(user_id: Uuid, purpose: &'static str, token: &str, ttl: Duration) -> Self {
+        OneTimeTokenInsert {
+            user_id,
+            purpose,
+            token_hash: hash_one_time_token(token),
+            expires_at: Utc::now() + ttl,
+        }
+    }
+}
+
+pub fn hash_one_time_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+/// `POST /auth/password-reset`
+#[derive(Debug, Deserialize, Validate)]
+pub struct PasswordResetRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+pii!(PasswordResetRequest { email });
+
+/// `GET /auth/password-reset?token=`, checked before the form is shown
+#[derive(Debug, Deserialize)]
+pub struct OneTimeTokenQuery {
+    pub token: String,
+}
+
+/// `POST /auth/password-reset/confirm`
+#[derive(Debug, Deserialize, Validate)]
+pub struct PasswordResetConfirmRequest {
+    #[validate(length(min = 1, message = "Token cannot be empty"))]
+    pub token: String,
+
+    #[validate(
+        length(min = 8, message = "Must be at least 8 characters"),
+        custom(
+            function = "validate_password_strength",
+            message = "Must include uppercase, number, and symbol"
+        )
+    )]
+    pub new_password: String,
+}
+
+/// `POST /auth/verify-email/confirm`
+#[derive(Debug, Deserialize, Validate)]
+pub struct EmailVerificationConfirmRequest {
+    #[validate(length(min = 1, message = "Token cannot be empty"))]
+    pub token: String,
+}
\ No newline at end of file
@@ -1,15 +1,21 @@
 use std::borrow::Cow;
 
 use chrono::{DateTime, Utc};
-use serde::{de::{Error, MapAccess, Visitor}, Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use validator::{Validate, ValidateLength, ValidationErrors};
 
 /// Represents optional field semantics in PATCH/UPDATE requests.
 ///
-/// - `Unchanged` → field not touched
-/// - `SetToNull` → explicitly null
-/// - `SetToValue` → set to provided value
-#[derive(Debug, Clone, Serialize, PartialEq)]
+/// - `Unchanged` → field not touched (left out of the body)
+/// - `SetToNull` → explicitly `null`
+/// - `SetToValue` → set to provided value (the plain JSON value)
+///
+/// A missing field can only be told apart from `null` by the containing
+/// struct, so fields must be `#[serde(default)]`; without it a missing field
+/// reads as `SetToNull`. Serializing writes `null` for both `Unchanged` and
+/// `SetToNull`, so skip unchanged fields with
+/// `#[serde(skip_serializing_if = "OptionField::is_unchanged")]`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum OptionField<T> {
     Unchanged,
     SetToNull,
@@ -30,59 +36,26 @@ where
     where
         D: Deserializer<'de>,
     {
-        struct OptionFieldVisitor<T>(std::marker::PhantomData<T>);
-        
-        impl<'de, T> Visitor<'de> for OptionFieldVisitor<T>
-        where
-            T: Deserialize<'de>,
-        {
-            type Value = OptionField<T>;
-
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("Unchanged, SetToNull, {SetToValue: ...}, or plain value")
-            }
-
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-            where
-                E: Error, 
-            {
-                match v {
-                    "Unchanged" => Ok(OptionField::Unchanged),
-                    "SetToNull" => Ok(OptionField::SetToNull),
-                    _ => {
-                        let value = T::deserialize(serde::de::value::StrDeserializer::new(v))?; 
-                        Ok(OptionField::SetToValue(value))
-                    }
-                }
-            }
-
-            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-            where
-                A: MapAccess<'de>, 
-            {
-                if let Some((key, value)) = map.next_entry::<String, T>()? {
-                    match key.as_str() {
-                        "SetToValue" => Ok(OptionField::SetToValue(value)),
-                        "SetToNull" => Ok(OptionField::SetToNull),
-                        "Unchanged" => Ok(OptionField::Unchanged),
-                        _ => Err(Error::unknown_field(&key, &["SetToValue", "SetToNull", "Unchanged"])),
-                    }
-                } else {
-                    Ok(OptionField::Unchanged)
-                }
-            }
-
-            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
-            where
-                D: Deserializer<'de>,
-            {
-                let value = T::deserialize(deserializer)?;
-                Ok(OptionField::SetToValue(value))
-            }
-        }
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(value) => OptionField::SetToValue(value),
+            None => OptionField::SetToNull,
+        })
+    }
+}
 
-        deserializer.deserialize_any(OptionFieldVisitor(std::marker::PhantomData))
-    }   
+impl<T> Serialize for OptionField<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            OptionField::SetToValue(value) => serializer.serialize_some(value),
+            OptionField::Unchanged | OptionField::SetToNull => serializer.serialize_none(),
+        }
+    }
 }
 
 // ---------------------- Validation support ----------------------
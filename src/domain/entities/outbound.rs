@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct OutboundClickInsert {
+    pub target_url: String,
+    pub target_host: String,
+    /// Path of our own page the link was clicked on, when the referrer is same-site
+    pub source_path: Option<String>,
+    /// SHA-256 of the full `Referer` header; the raw value is never stored
+    pub referrer_hash: Option<String>,
+    pub clicked_at: DateTime<Utc>,
+    /// Located from the visitor's IP; the IP itself is never stored
+    pub country_code: Option<String>,
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OutboundClickSummary {
+    pub target_url: String,
+    pub target_host: String,
+    pub clicks: i64,
+    pub unique_referrers: i64,
+    pub last_clicked_at: DateTime<Utc>,
+}
+
+/// Outbound clicks made from one of our own pages
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ReferringPage {
+    pub source_path: String,
+    pub clicks: i64,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct OutboundLinkQuery {
+    pub url: String,
+}
+
+/// How much of a QR code may be damaged or covered and still scan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrErrorCorrection {
+    /// About 7%
+    L,
+    /// About 15%
+    #[default]
+    M,
+    /// About 25%
+    Q,
+    /// About 30%
+    H,
+}
+
+impl QrErrorCorrection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QrErrorCorrection::L => "l",
+            QrErrorCorrection::M => "m",
+            QrErrorCorrection::Q => "q",
+            QrErrorCorrection::H => "h",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct OutboundQrQuery {
+    pub url: String,
+
+    /// Width in pixels; defaults to 512
+    #[validate(range(min = 64, max = 2048))]
+    pub size: Option<u32>,
+
+    #[serde(default)]
+    pub ecc: QrErrorCorrection,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct OutboundReportQuery {
+    /// Look-back window; defaults to 30 days
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<u32>,
+
+    #[validate(range(min = 1, max = 200))]
+    pub limit: Option<u32>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct OutboundReportResponse {
+    pub since: DateTime<Utc>,
+    pub total_clicks: i64,
+    /// `unique_referrers` counts a referrer once per day
+    pub links: Vec<OutboundClickSummary>,
+    /// Our pages the clicks came from, most first
+    pub referring_pages: Vec<ReferringPage>,
+}
\ No newline at end of file
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+use validator::Validate;
+
+// ───── Event Types ───────────────────────────────────────────────────
+
+/// Payload: `AggregateRef` of the new contact message
+pub const CONTACT_MESSAGE_RECEIVED: &str = "contact_message.received";
+/// Payload: `AggregateRef` of the new hire inquiry
+pub const HIRE_INQUIRY_RECEIVED: &str = "hire_inquiry.received";
+
+/// Every event type, each of which is a queue admins can pause
+pub const OUTBOX_EVENT_TYPES: &[&str] = &[CONTACT_MESSAGE_RECEIVED, HIRE_INQUIRY_RECEIVED];
+
+/// Attempts before an event is parked as failed
+pub const OUTBOX_MAX_ATTEMPTS: i32 = 8;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub event_type: String,
+    pub payload: JsonValue,
+    /// Claims so far, including the one currently in flight
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    /// Also set on cancelled events, which are parked the same way
+    pub failed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// End of the current claim; in the past once the relay died mid-delivery
+    pub leased_until: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxEvent {
+    pub fn status(&self, now: DateTime<Utc>) -> JobStatus {
+        if self.cancelled_at.is_some() {
+            JobStatus::Cancelled
+        } else if self.delivered_at.is_some() {
+            JobStatus::Delivered
+        } else if self.failed_at.is_some() {
+            JobStatus::Failed
+        } else if self.leased_until.is_some_and(|until| until > now) {
+            JobStatus::Running
+        } else {
+            JobStatus::Queued
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Due now, or waiting out the backoff after a failed attempt
+    Queued,
+    /// Claimed by a relay whose lease has not run out
+    Running,
+    /// Gave up after `OUTBOX_MAX_ATTEMPTS`
+    Failed,
+    Cancelled,
+    Delivered,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+            JobStatus::Delivered => "delivered",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PausedQueue {
+    pub event_type: String,
+    /// `sub` of the admin who paused it
+    pub paused_by: Option<String>,
+    pub paused_at: DateTime<Utc>,
+}
+
+/// Events of one type: open ones as they stand now, settled ones since the
+/// start of the reporting window
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobTypeCounts {
+    pub event_type: String,
+    pub queued: i64,
+    /// Queued events that already failed at least once
+    pub retrying: i64,
+    pub running: i64,
+    pub delivered: i64,
+    pub failed: i64,
+    pub cancelled: i64,
+    /// Attempts it took to deliver the delivered ones, summed
+    pub delivered_attempts: i64,
+    pub oldest_queued_at: Option<DateTime<Utc>>,
+}
+
+/// Payload of events that only point at the row they are about
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AggregateRef {
+    pub id: Uuid,
+}
+
+impl AggregateRef {
+    pub fn payload(id: Uuid) -> JsonValue {
+        serde_json::json!({ "id": id })
+    }
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct JobListQuery {
+    pub status: Option<JobStatus>,
+
+    pub event_type: Option<String>,
+
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct JobStatsQuery {
+    /// Window for delivered/failed counts; defaults to 7
+    #[validate(range(min = 1, max = 90))]
+    pub days: Option<u32>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+/// An event as the job dashboard lists it; the payload is only summarized,
+/// with personal data redacted
+#[derive(Debug, Clone, Serialize)]
+pub struct JobSummary {
+    pub id: Uuid,
+    pub event_type: String,
+    pub status: JobStatus,
+    pub payload_summary: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// When a queued event is next tried
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub failed_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobListResponse {
+    pub jobs: Vec<JobSummary>,
+    pub total: i64,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobTypeStats {
+    pub event_type: String,
+    pub queued: i64,
+    pub retrying: i64,
+    pub running: i64,
+    pub delivered: i64,
+    pub failed: i64,
+    pub cancelled: i64,
+    /// Delivered over delivered plus failed; `None` before either happened
+    pub success_rate: Option<f64>,
+    pub failure_rate: Option<f64>,
+    pub avg_attempts: Option<f64>,
+    pub oldest_queued_at: Option<DateTime<Utc>>,
+    pub paused: Option<PausedQueue>,
+}
+
+impl JobTypeStats {
+    pub fn new(counts: JobTypeCounts, paused: Option<PausedQueue>) -> Self {
+        let settled = counts.delivered + counts.failed;
+        let success_rate = (settled > 0).then(|| counts.delivered as f64 / settled as f64);
+
+        JobTypeStats {
+            success_rate,
+            failure_rate: success_rate.map(|rate| 1.0 - rate),
+            avg_attempts: (counts.delivered > 0).then(|| counts.delivered_attempts as f64 / counts.delivered as f64),
+            event_type: counts.event_type,
+            queued: counts.queued,
+            retrying: counts.retrying,
+            running: counts.running,
+            delivered: counts.delivered,
+            failed: counts.failed,
+            cancelled: counts.cancelled,
+            oldest_queued_at: counts.oldest_queued_at,
+            paused,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobStatsResponse {
+    pub since: DateTime<Utc>,
+    pub job_types: Vec<JobTypeStats>,
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use crate::entities::{
+    contact_me::{ContactMeFormInsert, ContactMeMessage, ContactSecret, NewContactMeForm},
+    hire::{HireInquiry, HireInquiryInsert, NewHireInquiry},
+    notification::NotificationRecipient,
+    one_time_token::PasswordResetRequest,
+    security_event::SecurityEventInsert,
+    token::Claims,
+    user::{LoginUser, NewUser, PublicUser, User, UserInsert, UserResponse},
+    user_import::{ImportedUser, UserImportIssue, UserImportRow},
+};
+
+/// Replaces every redacted value
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Fields of an entity that hold personal data, tagged with `pii!` next to
+/// the struct. Exports blank them with `redact_pii`; logs and error
+/// reports drop any value under one of their names.
+pub trait Pii {
+    const PII_FIELDS: &'static [&'static str];
+
+    fn redact_pii(&mut self);
+}
+
+/// A field type `pii!` can blank
+pub trait PiiValue {
+    fn redact(&mut self);
+}
+
+impl PiiValue for String {
+    fn redact(&mut self) {
+        *self = REDACTED.to_string();
+    }
+}
+
+impl<T: PiiValue> PiiValue for Option<T> {
+    fn redact(&mut self) {
+        if let Some(value) = self {
+            value.redact();
+        }
+    }
+}
+
+/// Tags fields of an entity as personal data:
+/// `pii!(ContactMeMessage { name, email, message });`
+///
+/// New tagged entities also go in `PII_REGISTRY`.
+macro_rules! pii {
+    ($entity:ty { $($field:ident),+ $(,)? }) => {
+        impl $crate::entities::pii::Pii for $entity {
+            const PII_FIELDS: &'static [&'static str] = &[$(stringify!($field)),+];
+
+            fn redact_pii(&mut self) {
+                $($crate::entities::pii::PiiValue::redact(&mut self.$field);)+
+            }
+        }
+    };
+}
+pub(crate) use pii;
+
+/// Every entity tagged with `pii!`, by type name
+pub const PII_REGISTRY: &[(&str, &[&str])] = &[
+    ("NewContactMeForm", NewContactMeForm::PII_FIELDS),
+    ("ContactMeFormInsert", ContactMeFormInsert::PII_FIELDS),
+    ("ContactMeMessage", ContactMeMessage::PII_FIELDS),
+    ("ContactSecret", ContactSecret::PII_FIELDS),
+    ("NewHireInquiry", NewHireInquiry::PII_FIELDS),
+    ("HireInquiryInsert", HireInquiryInsert::PII_FIELDS),
+    ("HireInquiry", HireInquiry::PII_FIELDS),
+    ("NotificationRecipient", NotificationRecipient::PII_FIELDS),
+    ("PasswordResetRequest", PasswordResetRequest::PII_FIELDS),
+    ("SecurityEventInsert", SecurityEventInsert::PII_FIELDS),
+    ("Claims", Claims::PII_FIELDS),
+    ("User", User::PII_FIELDS),
+    ("UserInsert", UserInsert::PII_FIELDS),
+    ("UserResponse", UserResponse::PII_FIELDS),
+    ("NewUser", NewUser::PII_FIELDS),
+    ("LoginUser", LoginUser::PII_FIELDS),
+    ("PublicUser", PublicUser::PII_FIELDS),
+    ("UserImportRow", UserImportRow::PII_FIELDS),
+    ("ImportedUser", ImportedUser::PII_FIELDS),
+    ("UserImportIssue", UserImportIssue::PII_FIELDS),
+];
+
+/// Names of every tagged field across the registry, sorted and deduplicated
+pub fn pii_field_names() -> Vec<&'static str> {
+    let mut names: Vec<_> = PII_REGISTRY.iter().flat_map(|(_, fields)| fields.iter().copied()).collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
\ No newline at end of file
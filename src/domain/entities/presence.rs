@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+/// Heartbeat sent by the site widget while a page is open
+#[derive(Debug, Deserialize, Validate)]
+pub struct PresenceBeat {
+    /// Random id the browser generates per tab session; only its hash is stored
+    #[validate(length(min = 16, max = 128, message = "session_id must be 16 to 128 characters"))]
+    pub session_id: String,
+
+    #[validate(
+        length(min = 1, max = 512, message = "path must be 1 to 512 characters"),
+        custom(function = "validate_page_path")
+    )]
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PresenceQuery {
+    /// Only count visitors on this page
+    pub path: Option<String>,
+
+    /// Busiest paths to return; defaults to 10
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+/// Drops the query string and fragment and any trailing slash, so
+/// `/blog/post/?ref=x` and `/blog/post` are counted as the same page
+pub fn normalize_page_path(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    match path.trim_end_matches('/') {
+        "" => "/".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct PresenceBeatResponse {
+    /// Send the next heartbeat within this many seconds to stay counted
+    pub next_beat_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PathPresence {
+    pub path: String,
+    pub visitors: u64,
+}
+
+/// Approximate: a visitor who closed the tab is counted until their last
+/// heartbeat is `window_secs` old
+#[derive(Debug, Clone, Serialize)]
+pub struct PresenceSnapshot {
+    pub window_secs: u64,
+    pub total: u64,
+    pub paths: Vec<PathPresence>,
+}
+
+// ───── Validation Helpers ───────────────────────────────────────────
+
+fn validate_page_path(path: &str) -> Result<(), ValidationError> {
+    if !path.starts_with('/') || path.chars().any(char::is_control) {
+        let mut err = ValidationError::new("invalid_path");
+        err.message = Some(Cow::Borrowed("Path must start with '/'"));
+        return Err(err);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{about_me::AboutMeResponse, blog_post::{AuthoredPost, PostAuthor}};
+
+/// Version of the public API DTOs below; bumped only for breaking changes
+pub const PUBLIC_API_VERSION: &str = "1";
+/// Response header carrying `PUBLIC_API_VERSION`. Clients may send it too,
+/// and are turned away when they ask for a version this server lacks.
+pub const API_VERSION_HEADER: &str = "X-API-Version";
+/// Rate limiter route of the public API, sized apart from browser routes
+pub const PUBLIC_API_LIMIT_ROUTE: &str = "public-api";
+pub const DEFAULT_PUBLIC_POSTS_PER_PAGE: u32 = 10;
+pub const MAX_PUBLIC_POSTS_PER_PAGE: u32 = 50;
+
+// The structs below are the public contract. They are mapped from the
+// internal responses field by field, so internal changes never leak out;
+// renaming or dropping a field here needs a new `PUBLIC_API_VERSION`.
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicAuthor {
+    pub name: Option<String>,
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+impl From<PostAuthor> for PublicAuthor {
+    fn from(author: PostAuthor) -> Self {
+        PublicAuthor {
+            name: author.name,
+            username: author.username,
+            avatar_url: author.avatar_url,
+        }
+    }
+}
+
+/// A published post as listed, without its body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicPostSummary {
+    pub slug: String,
+    pub title: String,
+    pub excerpt: String,
+    /// Absolute link to the post on the site
+    pub url: String,
+    pub cover_image_url: Option<String>,
+    pub tags: Vec<String>,
+    pub authors: Vec<PublicAuthor>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PublicPostSummary {
+    pub fn from_post(post: AuthoredPost, base_url: &str) -> Self {
+        PublicPostSummary {
+            url: format!("{}/blog/{}", base_url, post.post.slug),
+            slug: post.post.slug,
+            title: post.post.title,
+            excerpt: post.post.excerpt,
+            cover_image_url: post.post.cover_image_url,
+            tags: post.post.tags.unwrap_or_default(),
+            authors: post.authors.into_iter().map(PublicAuthor::from).collect(),
+            published_at: post.post.published_at,
+            updated_at: post.post.updated_at,
+        }
+    }
+}
+
+/// A published post with its rendered body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicPost {
+    #[serde(flatten)]
+    pub summary: PublicPostSummary,
+    pub content_html: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicPostList {
+    pub page: u32,
+    pub per_page: u32,
+    pub posts: Vec<PublicPostSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicAbout {
+    pub content_html: String,
+    pub effective_date: NaiveDate,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<AboutMeResponse> for PublicAbout {
+    fn from(about: AboutMeResponse) -> Self {
+        PublicAbout {
+            content_html: about.content_html,
+            effective_date: about.effective_date,
+            updated_at: about.updated_at,
+        }
+    }
+}
+
+/// One endpoint in the index served at the API root
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicEndpoint {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub description: &'static str,
+}
+
+/// Self-description of the public API: its version, endpoints and limits
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicApiIndex {
+    pub version: &'static str,
+    pub endpoints: Vec<PublicEndpoint>,
+    pub rate_limit: PublicRateLimit,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicRateLimit {
+    /// Requests a client can make at once
+    pub burst: u64,
+    /// Sustained requests per minute per client
+    pub per_minute: u64,
+}
+
+pub const PUBLIC_ENDPOINTS: [PublicEndpoint; 4] = [
+    PublicEndpoint { method: "GET", path: "/api/v1/public", description: "This index" },
+    PublicEndpoint {
+        method: "GET",
+        path: "/api/v1/public/posts",
+        description: "Published posts, newest first; `page` and `per_page` (at most 50) paginate",
+    },
+    PublicEndpoint { method: "GET", path: "/api/v1/public/posts/{slug}", description: "One published post with its HTML body" },
+    PublicEndpoint { method: "GET", path: "/api/v1/public/about", description: "The current about page as HTML" },
+];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PublicPostsQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+impl PublicPostsQuery {
+    /// Page and page size clamped to what the API serves
+    pub fn pagination(&self) -> (u32, u32) {
+        let page = self.page.unwrap_or(1).max(1);
+        let per_page = self.per_page
+            .unwrap_or(DEFAULT_PUBLIC_POSTS_PER_PAGE)
+            .clamp(1, MAX_PUBLIC_POSTS_PER_PAGE);
+        (page, per_page)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+// ───── API Response Models ──────────────────────────────────────────
+
+/// One client's bucket in the in-process rate limiter
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitEntry {
+    /// `<route>:<client ip>`
+    pub key: String,
+    pub remaining_tokens: f64,
+    pub capacity: f64,
+    /// Requests let through by the sliding window after the bucket ran dry
+    pub window_count: u64,
+    pub previous_window_count: u64,
+    pub window_limit: u64,
+    pub idle_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RateLimitReport {
+    pub total: usize,
+    pub entries: Vec<RateLimitEntry>,
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Progress is stored rounded down to a multiple of this
+pub const READING_BUCKET_PERCENT: u8 = 10;
+/// A reader who got this far counts as having finished the post; the last
+/// screen is usually comments and footer
+pub const COMPLETION_PERCENT: i16 = 90;
+/// Events accepted in one batch
+pub const MAX_PROGRESS_EVENTS: u64 = 50;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadingProgressUpdate {
+    pub post_id: Uuid,
+    /// Multiple of `READING_BUCKET_PERCENT`
+    pub percent: i16,
+}
+
+/// Readers of a post whose furthest point was `max_percent`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReadingDepthCount {
+    pub post_id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub max_percent: i16,
+    pub readers: i64,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReadingProgressEvent {
+    pub post_id: Uuid,
+
+    #[validate(range(max = 100, message = "percent must be 0 to 100"))]
+    pub percent: u8,
+}
+
+/// Scroll depths the site script collected since its last flush
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReadingProgressBatch {
+    /// Random id the browser generates per tab session; only its hash is stored
+    #[validate(length(min = 16, max = 128, message = "session_id must be 16 to 128 characters"))]
+    pub session_id: String,
+
+    #[validate(length(min = 1, max = MAX_PROGRESS_EVENTS, message = "events must hold 1 to 50 entries"), nested)]
+    pub events: Vec<ReadingProgressEvent>,
+}
+
+impl ReadingProgressBatch {
+    /// The furthest bucket reached per post; later events never lower it
+    pub fn furthest_per_post(&self) -> Vec<ReadingProgressUpdate> {
+        let mut furthest: BTreeMap<Uuid, i16> = BTreeMap::new();
+        for event in &self.events {
+            let percent = bucket_percent(event.percent);
+            furthest
+                .entry(event.post_id)
+                .and_modify(|p| *p = (*p).max(percent))
+                .or_insert(percent);
+        }
+
+        furthest
+            .into_iter()
+            .map(|(post_id, percent)| ReadingProgressUpdate { post_id, percent })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReadingStatsQuery {
+    /// Look-back window on when reading started; defaults to 30 days
+    #[validate(range(min = 1, max = 365))]
+    pub days: Option<u32>,
+
+    /// Posts with fewer readers are left out; defaults to 1
+    #[validate(range(min = 1, max = 100000))]
+    pub min_readers: Option<u32>,
+
+    #[validate(range(min = 1, max = 200))]
+    pub limit: Option<u32>,
+}
+
+/// Rounds down to the bucket, so 95% counts as 90%
+pub fn bucket_percent(percent: u8) -> i16 {
+    let percent = percent.min(100);
+    i16::from(percent - percent % READING_BUCKET_PERCENT)
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct ReadingProgressResponse {
+    /// Events kept; ones for unknown or unpublished posts are dropped
+    pub accepted: u64,
+}
+
+/// Readers who scrolled at least `percent` into the post
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingDepthBucket {
+    pub percent: i16,
+    pub readers: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PostReadingStats {
+    pub post_id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub readers: i64,
+    /// Readers who reached `COMPLETION_PERCENT`
+    pub completions: i64,
+    pub completion_rate: f64,
+    /// Mean furthest point, in percent
+    pub average_depth: f64,
+    /// Drop-off curve from 10% to 100%
+    pub depth: Vec<ReadingDepthBucket>,
+}
+
+impl PostReadingStats {
+    /// Folds per-depth counts into one entry per post, in no particular order
+    pub fn from_counts(counts: Vec<ReadingDepthCount>) -> Vec<PostReadingStats> {
+        let mut by_post: BTreeMap<Uuid, (String, String, BTreeMap<i16, i64>)> = BTreeMap::new();
+        for count in counts {
+            let (_, _, depths) = by_post
+                .entry(count.post_id)
+                .or_insert_with(|| (count.title, count.slug, BTreeMap::new()));
+            *depths.entry(count.max_percent).or_default() += count.readers;
+        }
+
+        by_post
+            .into_iter()
+            .map(|(post_id, (title, slug, depths))| {
+                let readers: i64 = depths.values().sum();
+                let reached = |percent: i16| depths.range(percent..).map(|(_, n)| n).sum::<i64>();
+                let completions = reached(COMPLETION_PERCENT);
+                let depth_sum: i64 = depths.iter().map(|(percent, n)| i64::from(*percent) * n).sum();
+
+                PostReadingStats {
+                    post_id,
+                    title,
+                    slug,
+                    readers,
+                    completions,
+                    completion_rate: ratio(completions, readers),
+                    average_depth: ratio(depth_sum, readers),
+                    depth: (1..=100 / READING_BUCKET_PERCENT)
+                        .map(|step| i16::from(step * READING_BUCKET_PERCENT))
+                        .map(|percent| ReadingDepthBucket { percent, readers: reached(percent) })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingStatsResponse {
+    pub since: DateTime<Utc>,
+    pub completion_percent: i16,
+    /// Highest completion rate first, ties broken by readers
+    pub posts: Vec<PostReadingStats>,
+}
+
+fn ratio(part: i64, total: i64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
\ No newline at end of file
@@ -0,0 +1,209 @@
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Matches one path exactly
+pub const REDIRECT_EXACT: &str = "exact";
+/// Regex matched against the whole path; `$1` or `${name}` in the target
+/// are replaced with its captures
+pub const REDIRECT_PATTERN: &str = "pattern";
+
+pub const REDIRECT_STATUS_CODES: [u16; 4] = [301, 302, 307, 308];
+pub const DEFAULT_REDIRECT_STATUS: u16 = 301;
+
+pub const MAX_SOURCE_LENGTH: usize = 1000;
+pub const MAX_TARGET_LENGTH: usize = 2048;
+/// Upload limit for `POST /admin/redirects/import`
+pub const MAX_REDIRECT_IMPORT_BYTES: usize = 5 * 1024 * 1024;
+/// Compiled size limit of a pattern, so one rule cannot eat the memory of
+/// every 404
+const MAX_PATTERN_SIZE: usize = 256 * 1024;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct RedirectRule {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub kind: String,
+    pub source: String,
+    pub target: String,
+    pub status_code: i32,
+    /// Flushed periodically, so recent hits may not show yet
+    pub hits: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RedirectRule {
+    pub fn is_pattern(&self) -> bool {
+        self.kind == REDIRECT_PATTERN
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectRuleInsert {
+    pub kind: String,
+    pub source: String,
+    pub target: String,
+    pub status_code: i32,
+}
+
+impl RedirectRuleInsert {
+    /// Key that has to be unique per tenant
+    pub fn key(&self) -> (String, String) {
+        (self.kind.clone(), self.source.clone())
+    }
+}
+
+/// Where a matched request is sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirect {
+    pub rule_id: Uuid,
+    pub location: String,
+    pub status_code: u16,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewRedirectRuleRequest {
+    /// `exact` (the default) or `pattern`
+    #[serde(default)]
+    pub kind: Option<String>,
+    pub source: String,
+    pub target: String,
+    /// 301 unless given
+    #[serde(default)]
+    pub status_code: Option<u16>,
+}
+
+impl NewRedirectRuleRequest {
+    /// Validates the rule and normalizes its source. Requests reach the
+    /// fallback with the trailing slash trimmed, so sources are stored
+    /// without one.
+    pub fn to_insert(&self) -> Result<RedirectRuleInsert, String> {
+        let kind = self.kind.as_deref().map(str::trim).unwrap_or(REDIRECT_EXACT).to_lowercase();
+        let source = normalize_source(self.source.trim());
+        let target = self.target.trim().to_string();
+        let status_code = self.status_code.unwrap_or(DEFAULT_REDIRECT_STATUS);
+
+        if source.is_empty() || source.len() > MAX_SOURCE_LENGTH {
+            return Err(format!("Source must be 1 to {} characters", MAX_SOURCE_LENGTH));
+        }
+        if target.is_empty() || target.len() > MAX_TARGET_LENGTH {
+            return Err(format!("Target must be 1 to {} characters", MAX_TARGET_LENGTH));
+        }
+        if !REDIRECT_STATUS_CODES.contains(&status_code) {
+            return Err(format!("Status code must be one of {:?}", REDIRECT_STATUS_CODES));
+        }
+
+        match kind.as_str() {
+            REDIRECT_EXACT => {
+                if !is_local_path(&source) {
+                    return Err("Source must be a path starting with '/'".to_string());
+                }
+                if source == target {
+                    return Err("Target is the same as the source".to_string());
+                }
+            }
+            REDIRECT_PATTERN => {
+                compile_pattern(&source)?;
+            }
+            _ => return Err(format!("Kind must be '{}' or '{}'", REDIRECT_EXACT, REDIRECT_PATTERN)),
+        }
+
+        let is_web_url = Url::parse(&target).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+        if !is_local_path(&target) && !is_web_url {
+            return Err("Target must be a path starting with '/' or an http(s) URL".to_string());
+        }
+
+        Ok(RedirectRuleInsert { kind, source, target, status_code: status_code as i32 })
+    }
+}
+
+/// Fields left out keep their current value
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct UpdateRedirectRuleRequest {
+    pub kind: Option<String>,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub status_code: Option<u16>,
+}
+
+impl UpdateRedirectRuleRequest {
+    pub fn apply_to(self, rule: &RedirectRule) -> NewRedirectRuleRequest {
+        NewRedirectRuleRequest {
+            kind: Some(self.kind.unwrap_or_else(|| rule.kind.clone())),
+            source: self.source.unwrap_or_else(|| rule.source.clone()),
+            target: self.target.unwrap_or_else(|| rule.target.clone()),
+            status_code: self.status_code.or(Some(rule.status_code as u16)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedirectImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// ───── Import Reports ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedirectImportIssue {
+    /// 1-based position among the data rows, header not counted
+    pub row: usize,
+    pub source: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedirectImportReport {
+    /// Nothing was written; the report shows what a real import would do
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub created: usize,
+    /// A rule for the same source exists already, here or earlier in the file
+    pub conflicts: Vec<RedirectImportIssue>,
+    /// Rows that are malformed or fail validation
+    pub skipped: Vec<RedirectImportIssue>,
+}
+
+// ───── Matching ──────────────────────────────────────────────────────
+
+/// Compiles a pattern source anchored to the whole path
+pub fn compile_pattern(source: &str) -> Result<Regex, String> {
+    RegexBuilder::new(&format!("^(?:{})$", source))
+        .size_limit(MAX_PATTERN_SIZE)
+        .build()
+        .map_err(|e| format!("Invalid pattern: {}", e))
+}
+
+fn is_local_path(value: &str) -> bool {
+    value.starts_with('/') && !value.starts_with("//")
+}
+
+/// Drops one trailing slash, keeping an escaped `\/` in patterns intact
+fn normalize_source(source: &str) -> String {
+    match source.strip_suffix('/') {
+        Some(rest) if !rest.is_empty() && !rest.ends_with('\\') => rest.to_string(),
+        _ => source.to_string(),
+    }
+}
+
+/// Carries the request's query string over unless the target has its own
+pub fn with_query(location: String, query: &str) -> String {
+    if query.is_empty() || location.contains('?') {
+        location
+    } else {
+        format!("{}?{}", location, query)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Longest retention period a policy can set; 0 days keeps rows forever
+pub const MAX_RETENTION_DAYS: u32 = 3650;
+
+/// Kinds of stored data with a retention period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionEntity {
+    /// Contact form submissions, including soft-deleted ones
+    ContactMessages,
+    /// Names and emails stashed when contact messages were anonymized, counted
+    /// from the anonymization; once purged the anonymization is final
+    AnonymizedIdentities,
+    /// Outbound link clicks, reading progress and title test counters
+    Analytics,
+    /// Honeytoken hits
+    SecurityEvents,
+    /// The audit log itself
+    Audit,
+}
+
+impl RetentionEntity {
+    pub const ALL: [RetentionEntity; 5] = [
+        RetentionEntity::ContactMessages,
+        RetentionEntity::AnonymizedIdentities,
+        RetentionEntity::Analytics,
+        RetentionEntity::SecurityEvents,
+        RetentionEntity::Audit,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionEntity::ContactMessages => "contact_messages",
+            RetentionEntity::AnonymizedIdentities => "anonymized_identities",
+            RetentionEntity::Analytics => "analytics",
+            RetentionEntity::SecurityEvents => "security_events",
+            RetentionEntity::Audit => "audit",
+        }
+    }
+
+    pub fn default_policy(&self) -> RetentionPolicy {
+        let days = match self {
+            RetentionEntity::ContactMessages => 365,
+            RetentionEntity::AnonymizedIdentities => 30,
+            RetentionEntity::Analytics | RetentionEntity::SecurityEvents => 90,
+            RetentionEntity::Audit => 730,
+        };
+
+        RetentionPolicy { days, action: RetentionAction::Purge }
+    }
+
+    /// Only contact messages hold personal data worth keeping in anonymized
+    /// form, and only the audit log is worth archiving
+    pub fn supports(&self, action: RetentionAction) -> bool {
+        match action {
+            RetentionAction::Purge => true,
+            RetentionAction::Anonymize => matches!(self, RetentionEntity::ContactMessages),
+            RetentionAction::Archive => matches!(self, RetentionEntity::Audit),
+        }
+    }
+}
+
+/// What happens to rows past their retention period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    Purge,
+    /// Name and email are replaced by placeholders; subject, message and
+    /// dates stay. The originals are kept until `AnonymizedIdentities` expire.
+    Anonymize,
+    /// Written to object storage as JSON lines under `audit-archive/`, then
+    /// deleted. Skipped while object storage is unavailable.
+    Archive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Age in days after which rows expire; 0 keeps them forever
+    pub days: u32,
+    pub action: RetentionAction,
+}
+
+impl RetentionPolicy {
+    pub fn cutoff(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (self.days > 0).then(|| now - chrono::Duration::days(self.days as i64))
+    }
+
+    /// Why the policy cannot be used for `entity`, if it cannot
+    pub fn problem(&self, entity: RetentionEntity) -> Option<String> {
+        if self.days > MAX_RETENTION_DAYS {
+            return Some(format!("retention for {} must be at most {} days", entity.as_str(), MAX_RETENTION_DAYS));
+        }
+        if !entity.supports(self.action) {
+            return Some(match self.action {
+                RetentionAction::Archive => format!("{} cannot be archived, only the audit log can", entity.as_str()),
+                _ => format!("{} cannot be anonymized, only purged", entity.as_str()),
+            });
+        }
+        None
+    }
+}
+
+/// Value of the `retention_policies` setting; entities left out use their default
+pub type RetentionPolicies = BTreeMap<RetentionEntity, RetentionPolicy>;
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RetentionRunQuery {
+    /// Count what would be affected without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Body of `POST /admin/contact-messages/anonymize`
+#[derive(Debug, Deserialize, Validate)]
+pub struct AnonymizeContactsRequest {
+    /// Messages received more than this many days ago are anonymized
+    #[validate(range(min = 1, max = MAX_RETENTION_DAYS))]
+    pub older_than_days: u32,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionEntityReport {
+    pub entity: RetentionEntity,
+    pub days: u32,
+    pub action: RetentionAction,
+    /// Rows created before this expire; `None` when kept forever
+    pub cutoff: Option<DateTime<Utc>>,
+    /// Rows purged, anonymized or archived, or that would be on a dry run
+    pub affected: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub entities: Vec<RetentionEntityReport>,
+}
+
+impl RetentionReport {
+    pub fn total_affected(&self) -> i64 {
+        self.entities.iter().map(|e| e.affected).sum()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::{
+    about_me::AboutMeResponse,
+    blog_post::{BlogPost, CalendarStatus},
+};
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewAtQuery {
+    /// RFC 3339; must not be in the past
+    pub timestamp: DateTime<Utc>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct PreviewPost {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Where the post stands today
+    pub status_now: CalendarStatus,
+}
+
+impl PreviewPost {
+    pub fn new(post: &BlogPost, now: DateTime<Utc>) -> Self {
+        PreviewPost {
+            id: post.id,
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            published_at: post.published_at,
+            status_now: CalendarStatus::of(post, now),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AboutPreview {
+    #[serde(flatten)]
+    pub about: AboutMeResponse,
+    /// False when it is the revision shown today
+    pub changed: bool,
+}
+
+/// What the public site would show at `at`, given today's content
+#[derive(Debug, Serialize)]
+pub struct SitePreview {
+    pub at: DateTime<Utc>,
+    /// `None` when no revision is in effect by then
+    pub about: Option<AboutPreview>,
+    /// Live posts, most recently published first
+    pub live_posts: Vec<PreviewPost>,
+    /// Live posts that are not live today
+    pub newly_live: usize,
+    /// Posts still waiting for their publish time
+    pub scheduled_posts: Vec<PreviewPost>,
+}
\ No newline at end of file
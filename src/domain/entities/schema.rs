@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// A migration compiled into this binary
+#[derive(Debug, Clone)]
+pub struct BundledMigration {
+    pub version: i64,
+    pub description: String,
+    pub checksum: Vec<u8>,
+}
+
+/// A row of `_sqlx_migrations`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub success: bool,
+    pub checksum: Vec<u8>,
+}
+
+/// How the database schema compares with the migrations this build bundles
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SchemaStatus {
+    /// Latest migration applied successfully, None on an empty database
+    pub version: Option<i64>,
+    /// Latest migration this build bundles
+    pub bundled_version: Option<i64>,
+    /// Bundled but not applied yet
+    pub pending: Vec<i64>,
+    /// Recorded as failed; the schema needs repairing by hand
+    pub failed: Vec<i64>,
+    /// Applied from a file that has changed since
+    pub modified: Vec<i64>,
+    /// Applied but not bundled, e.g. by a newer release during a blue/green
+    /// deploy, or by a branch that was never merged
+    pub unknown: Vec<i64>,
+}
+
+impl SchemaStatus {
+    pub fn compare(bundled: &[BundledMigration], applied: &[AppliedMigration]) -> Self {
+        let bundled: BTreeMap<i64, &BundledMigration> = bundled.iter().map(|m| (m.version, m)).collect();
+        let applied: BTreeMap<i64, &AppliedMigration> = applied.iter().map(|m| (m.version, m)).collect();
+
+        let mut status = SchemaStatus {
+            version: applied.values().filter(|m| m.success).map(|m| m.version).max(),
+            bundled_version: bundled.keys().next_back().copied(),
+            ..Default::default()
+        };
+
+        for (version, migration) in &bundled {
+            match applied.get(version) {
+                None => status.pending.push(*version),
+                Some(row) if row.success && row.checksum != migration.checksum => status.modified.push(*version),
+                Some(_) => {}
+            }
+        }
+        for (version, row) in &applied {
+            if !row.success {
+                status.failed.push(*version);
+            } else if !bundled.contains_key(version) {
+                status.unknown.push(*version);
+            }
+        }
+
+        status
+    }
+
+    /// Every bundled migration has been applied successfully
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty() && self.failed.is_empty()
+    }
+
+    /// The applied history disagrees with this build
+    pub fn has_drift(&self) -> bool {
+        !self.modified.is_empty() || !self.unknown.is_empty()
+    }
+
+    /// One line naming what differs, for logs and the doctor
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.pending.is_empty() {
+            parts.push(format!("{} pending: {:?}", self.pending.len(), self.pending));
+        }
+        if !self.failed.is_empty() {
+            parts.push(format!("failed migration(s) need repair: {:?}", self.failed));
+        }
+        if !self.modified.is_empty() {
+            parts.push(format!("applied migration(s) changed since: {:?}", self.modified));
+        }
+        if !self.unknown.is_empty() {
+            parts.push(format!("database has migration(s) this build does not know: {:?}", self.unknown));
+        }
+
+        match parts.is_empty() {
+            true => format!("up to date at {}", self.version.map_or("nothing".to_string(), |v| v.to_string())),
+            false => parts.join("; "),
+        }
+    }
+}
\ No newline at end of file
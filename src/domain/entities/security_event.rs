@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use validator::Validate;
+
+use crate::entities::pii::pii;
+
+/// `kind` of events recorded when a decoy path is requested
+pub const HONEYTOKEN_HIT: &str = "honeytoken_hit";
+/// `kind` of events recorded when the content scanner finds something in a
+/// post or message that was still saved
+pub const CONTENT_FLAGGED: &str = "content_flagged";
+/// Like `CONTENT_FLAGGED`, for content refused over a high-severity finding
+pub const CONTENT_BLOCKED: &str = "content_blocked";
+
+/// Decoy paths commonly probed by scanners. Nothing legitimate lives here,
+/// so any request to one is recorded and answered with a plain 404.
+pub const HONEYTOKEN_PATHS: &[&str] = &[
+    "/wp-login.php",
+    "/wp-admin",
+    "/xmlrpc.php",
+    "/admin.php",
+    "/administrator",
+    "/phpmyadmin",
+    "/.env",
+    "/.git/config",
+];
+
+pub fn is_honeytoken_path(path: &str) -> bool {
+    HONEYTOKEN_PATHS.contains(&path)
+}
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct SecurityEventInsert {
+    pub kind: String,
+    pub ip: String,
+    pub user_agent: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub occurred_at: DateTime<Utc>,
+    pub country_code: Option<String>,
+    pub region: Option<String>,
+    /// Kind-specific, e.g. the rules a content scan matched
+    pub details: Option<JsonValue>,
+}
+
+pii!(SecurityEventInsert { ip, user_agent });
+
+/// Activity of one source IP within the report window
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SecurityEventIpSummary {
+    pub ip: String,
+    pub hits: i64,
+    /// Distinct paths probed, alphabetical
+    pub paths: Vec<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SecurityEventPathSummary {
+    pub path: String,
+    pub hits: i64,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SecurityReportQuery {
+    /// Look-back window; defaults to 7 days
+    #[validate(range(min = 1, max = 90))]
+    pub days: Option<u32>,
+
+    #[validate(range(min = 1, max = 200))]
+    pub limit: Option<u32>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct HoneytokenReportResponse {
+    pub since: DateTime<Utc>,
+    pub total_hits: i64,
+    pub unique_ips: i64,
+    /// `None` when auto-ban is disabled
+    pub ban_duration_secs: Option<u64>,
+    pub top_ips: Vec<SecurityEventIpSummary>,
+    pub top_paths: Vec<SecurityEventPathSummary>,
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::{
+    blog_post::{validate_optional_slug, validate_slug, AuthoredPost},
+    option_fields::OptionField,
+};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+pub const MAX_SERIES_TITLE_LENGTH: u64 = 120;
+pub const MIN_SERIES_SLUG_LENGTH: u64 = 3;
+pub const MAX_SERIES_SLUG_LENGTH: u64 = 80;
+pub const MAX_SERIES_DESCRIPTION_LENGTH: u64 = 2_000;
+/// Keeps a single reorder request, and every post's navigation lookup, small
+pub const MAX_SERIES_POSTS: u64 = 100;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Series {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct SeriesInsert {
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+}
+
+/// A post's place in its series, enough to link to it
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SeriesPostLink {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    #[serde(skip_serializing)]
+    pub published: bool,
+}
+
+/// The series a post belongs to, with every member in reading order
+#[derive(Debug, Clone)]
+pub struct PostSeries {
+    pub series: Series,
+    pub members: Vec<SeriesPostLink>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct NewSeriesRequest {
+    #[validate(length(min = 1, max = MAX_SERIES_TITLE_LENGTH))]
+    pub title: String,
+
+    /// Derived from the title when left out
+    #[validate(
+        length(min = MIN_SERIES_SLUG_LENGTH, max = MAX_SERIES_SLUG_LENGTH),
+        custom(function = "validate_slug")
+    )]
+    pub slug: Option<String>,
+
+    #[validate(length(max = MAX_SERIES_DESCRIPTION_LENGTH))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, Default)]
+#[serde(default)]
+pub struct UpdateSeriesRequest {
+    #[validate(length(min = 1, max = MAX_SERIES_TITLE_LENGTH))]
+    pub title: OptionField<String>,
+
+    #[validate(
+        length(min = MIN_SERIES_SLUG_LENGTH, max = MAX_SERIES_SLUG_LENGTH),
+        custom(function = "validate_optional_slug")
+    )]
+    pub slug: OptionField<String>,
+
+    #[validate(length(max = MAX_SERIES_DESCRIPTION_LENGTH))]
+    pub description: OptionField<String>,
+}
+
+/// The series' posts in reading order; posts left out leave the series
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetSeriesPostsRequest {
+    #[validate(length(max = MAX_SERIES_POSTS))]
+    pub post_ids: Vec<Uuid>,
+}
+
+// ───── API Response Models ──────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+}
+
+/// Where a post sits in its series. `position` is 1-based and, like `total`,
+/// counts only the posts a reader can see.
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesNavigation {
+    pub series: SeriesSummary,
+    pub position: usize,
+    pub total: usize,
+    pub previous: Option<SeriesPostLink>,
+    pub next: Option<SeriesPostLink>,
+}
+
+impl SeriesNavigation {
+    /// Navigation for `post_id` among the published members. A draft being
+    /// previewed keeps its own place; `None` when the post is not a member.
+    pub fn for_post(post_series: &PostSeries, post_id: Uuid) -> Option<Self> {
+        let visible: Vec<&SeriesPostLink> = post_series
+            .members
+            .iter()
+            .filter(|member| member.published || member.id == post_id)
+            .collect();
+        let index = visible.iter().position(|member| member.id == post_id)?;
+
+        let series = &post_series.series;
+        Some(SeriesNavigation {
+            series: SeriesSummary { id: series.id, title: series.title.clone(), slug: series.slug.clone() },
+            position: index + 1,
+            total: visible.len(),
+            previous: index.checked_sub(1).map(|i| visible[i].clone()),
+            next: visible.get(index + 1).map(|member| (*member).clone()),
+        })
+    }
+}
+
+/// Public view of a series: published posts only, in reading order
+#[derive(Debug, Serialize)]
+pub struct SeriesDetailResponse {
+    #[serde(flatten)]
+    pub series: Series,
+    pub posts: Vec<AuthoredPost>,
+}
\ No newline at end of file
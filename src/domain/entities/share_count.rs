@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Hacker News submissions of the post's URL, via the Algolia search API
+pub const SHARE_NETWORK_HACKERNEWS: &str = "hackernews";
+/// Reddit submissions of the post's URL
+pub const SHARE_NETWORK_REDDIT: &str = "reddit";
+/// Facebook shares, via the Graph API; needs an access token
+pub const SHARE_NETWORK_FACEBOOK: &str = "facebook";
+
+/// Every network share counts can be collected from
+pub const SHARE_NETWORKS: [&str; 3] = [SHARE_NETWORK_HACKERNEWS, SHARE_NETWORK_REDDIT, SHARE_NETWORK_FACEBOOK];
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PostShareCount {
+    #[serde(skip_serializing)]
+    pub tenant_id: Uuid,
+    pub post_id: Uuid,
+    pub network: String,
+    pub shares: i64,
+    pub fetched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareCountInsert {
+    pub post_id: Uuid,
+    pub network: &'static str,
+    pub shares: i64,
+}
+
+// ───── API Response Models ──────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct NetworkShareCount {
+    pub network: String,
+    pub shares: i64,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// A post's latest share counts, summed across networks
+#[derive(Debug, Serialize)]
+pub struct PostShareCounts {
+    pub post_id: Uuid,
+    pub total: i64,
+    pub networks: Vec<NetworkShareCount>,
+}
+
+impl PostShareCounts {
+    pub fn from_counts(post_id: Uuid, counts: Vec<PostShareCount>) -> Self {
+        let networks: Vec<NetworkShareCount> = counts
+            .into_iter()
+            .map(|count| NetworkShareCount { network: count.network, shares: count.shares, fetched_at: count.fetched_at })
+            .collect();
+
+        PostShareCounts {
+            post_id,
+            total: networks.iter().map(|n| n.shares).sum(),
+            networks,
+        }
+    }
+}
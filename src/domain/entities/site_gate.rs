@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SiteGateRequest {
+    #[validate(length(min = 1, max = 256))]
+    pub passphrase: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SiteGateResponse {
+    pub token: String,
+    pub expires_in: i64,
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+use validator::{Validate, ValidationError};
+
+/// Links shown in the nav of server-rendered pages when none are configured
+pub const DEFAULT_NAV: [(&str, &str); 2] = [("About", "/about.html"), ("RSS", "/api/v1/feed/rss.xml")];
+
+const MAX_NAV_LINKS: u64 = 10;
+
+// ───── Typed Values ──────────────────────────────────────────────────
+
+/// Title and nav of the pages the API renders itself (e.g. `/about.html`),
+/// stored per tenant as the `site_pages` runtime setting
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct SitePageSettings {
+    /// Defaults to the tenant's name
+    #[validate(length(min = 1, max = 100))]
+    pub title: Option<String>,
+
+    /// Replaces the default links when set
+    #[serde(default)]
+    #[validate(length(max = MAX_NAV_LINKS), nested)]
+    pub nav: Option<Vec<NavLink>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate)]
+pub struct NavLink {
+    #[validate(length(min = 1, max = 40))]
+    pub label: String,
+
+    /// A path on this site or an absolute http(s) URL
+    #[validate(length(max = 2048), custom(function = "validate_nav_url"))]
+    pub url: String,
+}
+
+impl SitePageSettings {
+    /// The configured links, else the default ones under `base_path`, the
+    /// prefix the API is served at
+    pub fn nav_links(&self, base_path: &str) -> Vec<NavLink> {
+        self.nav.clone().unwrap_or_else(|| {
+            DEFAULT_NAV
+                .iter()
+                .map(|(label, url)| NavLink { label: label.to_string(), url: format!("{}{}", base_path, url) })
+                .collect()
+        })
+    }
+}
+
+// ───── Validation Helpers ───────────────────────────────────────────
+
+fn validate_nav_url(url: &str) -> Result<(), ValidationError> {
+    let is_path = url.starts_with('/') && !url.starts_with("//");
+    let is_web_url = Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+
+    if !is_path && !is_web_url {
+        let mut err = ValidationError::new("invalid_nav_url");
+        err.message = Some("Nav links must be a path starting with '/' or an http(s) URL".into());
+        return Err(err);
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::entities::{geo::GeoCountrySummary, outbound::ReferringPage, reading::PostReadingStats};
+
+pub const ROLLUP_DAY: &str = "day";
+pub const ROLLUP_MONTH: &str = "month";
+
+/// `country_rollups.source` of outbound clicks
+pub const COUNTRY_SOURCE_CLICKS: &str = "clicks";
+/// `country_rollups.source` of honeytoken hits
+pub const COUNTRY_SOURCE_ATTACKS: &str = "attacks";
+
+/// Days `--backfill-stats` rolls up when no count is given
+pub const DEFAULT_BACKFILL_DAYS: u32 = 365;
+/// Posts and referring pages listed per month in the monthly report
+pub const MONTHLY_TOP_ENTRIES: usize = 5;
+
+/// What a stats query reads: the `period` rollups starting in `from..to`,
+/// plus the raw rows from `live_from` on, which no rollup covers yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollupWindow {
+    pub period: &'static str,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub live_from: Option<DateTime<Utc>>,
+}
+
+impl RollupWindow {
+    /// The `days` days before today from daily rollups, and today from the raw rows
+    pub fn last_days(days: u32, now: DateTime<Utc>) -> Self {
+        let today = now.date_naive();
+
+        RollupWindow {
+            period: ROLLUP_DAY,
+            from: today - Days::new(u64::from(days)),
+            to: today,
+            live_from: Some(start_of_day(today)),
+        }
+    }
+
+    /// The month starting on `month`, from its monthly rollups
+    pub fn month(month: NaiveDate) -> Self {
+        RollupWindow {
+            period: ROLLUP_MONTH,
+            from: month,
+            to: month + Months::new(1),
+            live_from: None,
+        }
+    }
+
+    /// Start of the first period covered
+    pub fn since(&self) -> DateTime<Utc> {
+        start_of_day(self.from)
+    }
+}
+
+/// Midnight UTC
+pub fn start_of_day(day: NaiveDate) -> DateTime<Utc> {
+    day.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// First day of the month `day` falls in
+pub fn start_of_month(day: NaiveDate) -> NaiveDate {
+    day.with_day(1).unwrap_or(day)
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StatsRollupRunQuery {
+    /// Days rolled up, today included; defaults to `stats_rollup_days`
+    #[validate(range(min = 1, max = 3650))]
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MonthlyStatsQuery {
+    /// Months listed, the current one included; defaults to 12
+    #[validate(range(min = 1, max = 24))]
+    pub months: Option<u32>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+/// Days rolled up by one run, `from` inclusive and `to` exclusive
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsRollupRun {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Daily and monthly rollup rows written
+    pub rows: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonthlyStats {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub readers: i64,
+    pub completions: i64,
+    pub clicks: i64,
+    pub attacks: i64,
+    /// Most read first
+    pub top_posts: Vec<PostReadingStats>,
+    pub referring_pages: Vec<ReferringPage>,
+    pub countries: Vec<GeoCountrySummary>,
+}
+
+/// Months as of the last rollup run, newest first
+#[derive(Debug, Serialize)]
+pub struct MonthlyStatsResponse {
+    pub months: Vec<MonthlyStats>,
+}
\ No newline at end of file
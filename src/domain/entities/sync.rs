@@ -0,0 +1,68 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::{about_me::AboutMeResponse, blog_post::AuthoredPost};
+
+/// Key of the about page in `SyncResponse::pages`
+pub const SYNC_PAGE_ABOUT: &str = "about";
+
+// ───── API Request Models ───────────────────────────────────────────
+
+/// `GET /sync?since=`; `since` is the `next_since` of the previous sync,
+/// left out for the first one
+#[derive(Debug, Deserialize)]
+pub struct SyncQuery {
+    pub since: Option<DateTime<Utc>>,
+}
+
+// ───── API Response Models ──────────────────────────────────────────
+
+/// What changed in one kind of content. `created` and `updated` carry full
+/// payloads and are upserts either way; `deleted` lists ids that are no
+/// longer public, which may include drafts the client never saw.
+#[derive(Debug, Serialize)]
+pub struct SyncChanges<T, K> {
+    pub created: Vec<T>,
+    pub updated: Vec<T>,
+    pub deleted: Vec<K>,
+}
+
+impl<T, K> Default for SyncChanges<T, K> {
+    fn default() -> Self {
+        SyncChanges { created: Vec::new(), updated: Vec::new(), deleted: Vec::new() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncPage {
+    pub key: &'static str,
+    pub revision: i32,
+    pub content_markdown: String,
+    pub content_html: String,
+    pub effective_date: NaiveDate,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SyncPage {
+    pub fn about(about: AboutMeResponse) -> Self {
+        SyncPage {
+            key: SYNC_PAGE_ABOUT,
+            revision: about.revision,
+            content_markdown: about.content_markdown,
+            content_html: about.content_html,
+            effective_date: about.effective_date,
+            updated_at: about.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncResponse {
+    pub since: Option<DateTime<Utc>>,
+    /// Pass as `since` next time. Slightly behind the server clock, so a
+    /// change committed while this sync ran is picked up by the next one.
+    pub next_since: DateTime<Utc>,
+    pub posts: SyncChanges<AuthoredPost, Uuid>,
+    pub pages: SyncChanges<SyncPage, &'static str>,
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Tenant seeded by the tenants migration; owns all pre-existing content
+/// and serves hosts that no tenant claims unless strict host matching is on.
+pub const DEFAULT_TENANT_ID: Uuid = Uuid::from_u128(1);
+
+/// Label prepended to a custom domain for its verification TXT record
+pub const DOMAIN_VERIFICATION_LABEL: &str = "_portfolio-verification";
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TenantDomain {
+    pub hostname: String,
+    pub tenant_id: Uuid,
+    pub verification_token: String,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TenantDomain {
+    /// DNS name the owner must publish the TXT record under
+    pub fn txt_record_name(&self) -> String {
+        format!("{}.{}", DOMAIN_VERIFICATION_LABEL, self.hostname)
+    }
+
+    /// Exact TXT record value that proves ownership
+    pub fn txt_record_value(&self) -> String {
+        format!("portfolio-verification={}", self.verification_token)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Tenant {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    /// Lowercase host names without port, from `tenant_domains`
+    pub hostnames: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Tenant {
+    /// Namespaces a shared cache key (e.g. Redis) so tenants never collide
+    pub fn cache_key(&self, key: &str) -> String {
+        format!("t:{}:{}", self.slug, key)
+    }
+
+    /// First configured host, used to build absolute links in emails
+    pub fn primary_host(&self) -> Option<&str> {
+        self.hostnames.first().map(String::as_str)
+    }
+}
+
+/// Normalizes a Host header value for lookup: lowercase, port and trailing dot removed
+pub fn normalize_host(host: &str) -> String {
+    let host = host.trim();
+
+    // Bracketed IPv6 literal, e.g. `[::1]:8080`
+    let host = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, port)| {
+            if port.bytes().all(|b| b.is_ascii_digit()) { name } else { host }
+        }),
+    };
+
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct NewTenantDomainRequest {
+    #[validate(length(min = 1, max = 253, message = "Hostname must be 1-253 characters"))]
+    pub hostname: String,
+}
+
+// ───── API Response Models ──────────────────────────────────────────
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainStatus {
+    Pending,
+    Verified,
+}
+
+/// DNS record the onboarding UI asks the owner to create
+#[derive(Debug, Serialize)]
+pub struct DnsRecordInstruction {
+    #[serde(rename = "type")]
+    pub record_type: &'static str,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantDomainResponse {
+    pub hostname: String,
+    pub status: DomainStatus,
+    pub verification_record: DnsRecordInstruction,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// Why the latest check failed, if it did
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TenantDomain> for TenantDomainResponse {
+    fn from(domain: TenantDomain) -> Self {
+        TenantDomainResponse {
+            status: if domain.verified_at.is_some() { DomainStatus::Verified } else { DomainStatus::Pending },
+            verification_record: DnsRecordInstruction {
+                record_type: "TXT",
+                name: domain.txt_record_name(),
+                value: domain.txt_record_value(),
+            },
+            hostname: domain.hostname,
+            verified_at: domain.verified_at,
+            last_checked_at: domain.last_checked_at,
+            last_error: domain.last_error,
+            created_at: domain.created_at,
+        }
+    }
+}
+
+/// Whether `host` (already normalized) is a plausible public DNS name:
+/// at least two labels of letters, digits and inner hyphens
+pub fn is_valid_hostname(host: &str) -> bool {
+    let labels: Vec<&str> = host.split('.').collect();
+
+    host.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            (1..=63).contains(&label.len())
+                && label.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+        })
+        && !labels.last().is_some_and(|tld| tld.bytes().all(|b| b.is_ascii_digit()))
+}
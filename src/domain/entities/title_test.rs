@@ -0,0 +1,189 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::entities::blog_post::{validate_title, MAX_TITLE_LENGTH, MIN_TITLE_LENGTH};
+
+/// Variant number of the post's own title
+pub const ORIGINAL_VARIANT: i16 = 0;
+/// Alternative titles a post can be tested with
+pub const MAX_ALTERNATIVE_TITLES: u64 = 2;
+/// Weight of the post's own title when the request leaves it out
+const DEFAULT_ORIGINAL_WEIGHT: u8 = 50;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+/// One arm of a post's title test. `title` is `None` for variant 0, which
+/// always serves the post's current title.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TitleVariant {
+    pub post_id: Uuid,
+    pub variant: i16,
+    pub tenant_id: Uuid,
+    pub title: Option<String>,
+    pub weight: i16,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TitleVariantInsert {
+    pub variant: i16,
+    pub title: Option<String>,
+    pub weight: i16,
+}
+
+/// A variant shown in a list response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TitleImpression {
+    pub post_id: Uuid,
+    pub variant: i16,
+}
+
+/// Totals for one variant over the life of the test
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TitleVariantStats {
+    pub variant: i16,
+    pub impressions: i64,
+    pub clicks: i64,
+}
+
+/// Day the counters are bucketed by, in UTC
+pub fn stats_day(at: DateTime<Utc>) -> NaiveDate {
+    at.date_naive()
+}
+
+/// The variant `roll` lands on when the weights are laid end to end;
+/// `roll` must be below the weights' sum
+pub fn pick_variant(variants: &[TitleVariant], roll: u32) -> Option<&TitleVariant> {
+    let mut remaining = roll;
+    variants.iter().filter(|v| v.weight > 0).find(|v| {
+        let weight = v.weight as u32;
+        if remaining < weight {
+            return true;
+        }
+        remaining -= weight;
+        false
+    })
+}
+
+pub fn total_weight(variants: &[TitleVariant]) -> u32 {
+    variants.iter().map(|v| v.weight.max(0) as u32).sum()
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct TitleVariantRequest {
+    #[validate(length(min = MIN_TITLE_LENGTH, max = MAX_TITLE_LENGTH), custom(function = "validate_title"))]
+    pub title: String,
+
+    #[validate(range(min = 1, max = 100))]
+    pub weight: u8,
+}
+
+/// Starts a test, replacing any running one for the post and its numbers
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetTitleTestRequest {
+    /// Weight of the post's own title; 0 serves only the alternatives.
+    /// Defaults to 50.
+    #[validate(range(max = 100))]
+    pub original_weight: Option<u8>,
+
+    #[validate(length(min = 1, max = MAX_ALTERNATIVE_TITLES, message = "variants must hold 1 or 2 titles"), nested)]
+    pub variants: Vec<TitleVariantRequest>,
+}
+
+impl SetTitleTestRequest {
+    /// Variant 0 first, then the alternatives numbered from 1
+    pub fn to_inserts(&self) -> Vec<TitleVariantInsert> {
+        let original = TitleVariantInsert {
+            variant: ORIGINAL_VARIANT,
+            title: None,
+            weight: i16::from(self.original_weight.unwrap_or(DEFAULT_ORIGINAL_WEIGHT)),
+        };
+
+        std::iter::once(original)
+            .chain(self.variants.iter().zip(1..).map(|(v, variant)| TitleVariantInsert {
+                variant,
+                title: Some(v.title.clone()),
+                weight: i16::from(v.weight),
+            }))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PromoteTitleRequest {
+    /// 0 keeps the post's own title
+    #[validate(range(min = 0, max = 2))]
+    pub variant: i16,
+}
+
+/// Sent by the site when a reader follows a post link from a list
+#[derive(Debug, Deserialize, Validate)]
+pub struct TitleClickEvent {
+    pub post_id: Uuid,
+
+    #[validate(range(min = 0, max = 2))]
+    pub variant: i16,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TitleVariantReport {
+    pub variant: i16,
+    pub title: String,
+    pub weight: i16,
+    pub impressions: i64,
+    pub clicks: i64,
+    /// Clicks per impression; 0 before the first impression
+    pub ctr: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TitleTestReport {
+    pub post_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub variants: Vec<TitleVariantReport>,
+    /// Highest click-through rate so far, if any variant has been clicked
+    pub leader: Option<i16>,
+}
+
+impl TitleTestReport {
+    /// `current_title` stands in for variant 0
+    pub fn build(current_title: &str, variants: &[TitleVariant], stats: &[TitleVariantStats]) -> Option<TitleTestReport> {
+        let first = variants.first()?;
+
+        let variants: Vec<TitleVariantReport> = variants
+            .iter()
+            .map(|v| {
+                let (impressions, clicks) = stats
+                    .iter()
+                    .find(|s| s.variant == v.variant)
+                    .map_or((0, 0), |s| (s.impressions, s.clicks));
+                TitleVariantReport {
+                    variant: v.variant,
+                    title: v.title.clone().unwrap_or_else(|| current_title.to_string()),
+                    weight: v.weight,
+                    impressions,
+                    clicks,
+                    ctr: if impressions == 0 { 0.0 } else { clicks as f64 / impressions as f64 },
+                }
+            })
+            .collect();
+        let leader = variants
+            .iter()
+            .filter(|v| v.clicks > 0)
+            .max_by(|a, b| a.ctr.total_cmp(&b.ctr).then_with(|| b.variant.cmp(&a.variant)))
+            .map(|v| v.variant);
+
+        Some(TitleTestReport {
+            post_id: first.post_id,
+            started_at: first.created_at,
+            variants,
+            leader,
+        })
+    }
+}
\ No newline at end of file
@@ -1,6 +1,9 @@
 use serde::{ Serialize, Deserialize };
+use uuid::Uuid;
 use validator::Validate;
 
+use crate::entities::{pii::pii, tenant::DEFAULT_TENANT_ID};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AuthResponse {
     pub access_token: String,
@@ -23,15 +26,35 @@ pub struct Claims {
     pub sub: String,
     pub email: String,
     pub admin: bool,
+    /// May write blog posts; absent from tokens issued before editors existed
+    #[serde(default)]
+    pub editor: bool,
     pub verified: bool,
     pub exp: usize,
     pub token_type: TokenType,
     pub iat: usize,
-    // iss: Option<String>,
-    // aud: Option<String>,
+    /// Tenant the user belongs to; tokens are only accepted on that tenant's hosts.
+    /// Tokens issued before tenants existed belong to the default tenant.
+    #[serde(default = "default_tenant_id")]
+    pub tid: Uuid,
+    /// The user's claims version when issued; the token stops being accepted
+    /// once the version is bumped. 0 in tokens issued before versioning.
+    #[serde(default)]
+    pub claims_version: u64,
+    /// Absent from tokens minted before issuers were configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
     // nbf: Option<usize>,
 }
 
+pii!(Claims { email });
+
+fn default_tenant_id() -> Uuid {
+    DEFAULT_TENANT_ID
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum TokenType {
@@ -46,6 +69,12 @@ pub struct RefreshClaims {
     pub iat: usize,
     pub exp: usize,
     pub token_type: TokenType,
+    #[serde(default)]
+    pub claims_version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
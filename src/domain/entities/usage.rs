@@ -0,0 +1,105 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ───── Reports ───────────────────────────────────────────────────────
+
+/// What a site uses of the hosting resources, as the usage collector last
+/// saw it. Parts that could not be measured are `None`, with the reason in
+/// `unavailable`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub tenant_id: Uuid,
+    pub collected_at: DateTime<Utc>,
+    /// `None` when object storage is not configured or could not be listed
+    pub storage: Option<StorageUsage>,
+    pub database: Option<DatabaseUsage>,
+    /// `None` without Redis, or when it could not be scanned
+    pub redis: Option<RedisUsage>,
+    pub email: Option<EmailUsage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageUsage {
+    /// e.g. `s3://bucket`
+    pub backend: String,
+    /// Objects filed under this site: exports, backups, archives, images
+    pub objects: u64,
+    pub bytes: u64,
+    /// Per top-level prefix (`exports`, `qr`, `audit-archive`, ...)
+    pub by_prefix: Vec<PrefixUsage>,
+    /// Everything in the bucket, all sites and unattributed objects included
+    pub total_objects: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrefixUsage {
+    pub prefix: String,
+    pub objects: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseUsage {
+    /// Rows of this site per table, largest first; tables without any are left out
+    pub tables: Vec<TableUsage>,
+    pub rows: i64,
+    /// On-disk size of the whole database, shared by every site
+    pub database_bytes: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TableUsage {
+    pub table: String,
+    pub rows: i64,
+    /// On-disk size of the table, indexes included, for all sites
+    pub table_bytes: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedisUsage {
+    /// Key pattern of the site's namespace, e.g. `t:main:*`
+    pub namespace: String,
+    pub keys: u64,
+    /// Sum of `MEMORY USAGE` over those keys
+    pub bytes: u64,
+    /// `used_memory` of the server, all namespaces and other apps included
+    pub used_memory: Option<u64>,
+    /// The scan stopped at its key limit; `keys` and `bytes` are a lower bound
+    pub truncated: bool,
+}
+
+/// Emails sent this calendar month by the whole instance. The mail relay
+/// quota is per account, not per site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EmailUsage {
+    pub month: NaiveDate,
+    pub sent: i64,
+    pub failed: i64,
+}
+
+// ───── Quotas ────────────────────────────────────────────────────────
+
+/// How much of a quota is used, as the quota alerts see it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    /// Alerts are recorded under this, e.g. `email` or `api_token:<id>`
+    pub quota: String,
+    /// What the alert calls it, e.g. `Emails sent this month`
+    pub label: String,
+    pub used: u64,
+    pub limit: u64,
+}
+
+impl QuotaUsage {
+    /// Share of the quota used, in whole percent rounded down
+    pub fn percent(&self) -> u64 {
+        if self.limit == 0 {
+            return 0;
+        }
+        (self.used as u128 * 100 / self.limit as u128) as u64
+    }
+}
@@ -2,13 +2,18 @@ use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use validator::Validate;
 use uuid::Uuid;
+use std::str::FromStr;
 
-use crate::domain::password::validate_password_strength;
+use crate::{
+    domain::password::validate_password_strength,
+    entities::{blog_post::validate_optional_url_field, option_fields::OptionField, pii::pii, view::admin_only},
+};
 
 
-#[derive(Debug, sqlx::FromRow)]
+#[derive(Debug, Clone, sqlx::FromRow)]
 pub struct User {
     pub id: Uuid,
+    pub tenant_id: Uuid,
     pub email: String,
     pub username: Option<String>,
     pub password_hash: String,
@@ -17,7 +22,46 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
-    pub deleted_by: Option<Uuid>
+    pub deleted_by: Option<Uuid>,
+    /// May write blog posts and change their own
+    pub is_editor: bool,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// Owns the posts of deleted users under the `reassign` policy; cannot sign in
+    pub is_system: bool,
+}
+
+pii!(User { email, username, display_name });
+
+/// Password hash schemes sign-in understands. New hashes are always
+/// Argon2id; bcrypt ones only arrive through user imports and are replaced
+/// on the user's next sign-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordHashAlgorithm {
+    /// PHC string of any Argon2 variant
+    Argon2,
+    /// Modular crypt `$2a$`, `$2b$`, `$2x$` or `$2y$`
+    Bcrypt,
+}
+
+impl PasswordHashAlgorithm {
+    pub fn detect(hashed: &str) -> Option<Self> {
+        if hashed.starts_with("$argon2") {
+            Some(PasswordHashAlgorithm::Argon2)
+        } else if hashed.starts_with("$2") {
+            Some(PasswordHashAlgorithm::Bcrypt)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PasswordHashAlgorithm::Argon2 => "argon2",
+            PasswordHashAlgorithm::Bcrypt => "bcrypt",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -33,6 +77,8 @@ pub struct UserInsert {
     pub deleted_by: Option<Uuid>
 }
 
+pii!(UserInsert { email, username });
+
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct UserResponse {
     pub id: Uuid,
@@ -41,6 +87,8 @@ pub struct UserResponse {
     pub created_at: DateTime<Utc>,
 }
 
+pii!(UserResponse { email, username });
+
 impl From<User> for UserResponse {
     fn from(user: User) -> Self {
         UserResponse {
@@ -75,6 +123,8 @@ pub struct NewUser {
     pub is_verified: bool,
 }
 
+pii!(NewUser { email, username });
+
 /// Returns false, used for serde default.
 fn default_false() -> bool {
     false
@@ -106,6 +156,8 @@ pub struct LoginUser {
     pub password: String,
 }
 
+pii!(LoginUser { email });
+
 
 #[derive(Debug, Serialize)]
 pub struct NewUserResponse {
@@ -113,25 +165,42 @@ pub struct NewUserResponse {
     pub message: String,
 }
 
+/// A user as the API returns it. Users reading their own account get the
+/// public view; admins also see the fields marked `admin_only`.
 #[derive(Serialize)]
 pub struct PublicUser {
     pub id: Uuid,
     pub email: String,
     pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
     pub is_admin: bool,
+    pub is_editor: bool,
     pub is_verified: bool,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "admin_only")]
+    pub updated_at: DateTime<Utc>,
+    /// The per-tenant system user that owns reassigned posts
+    #[serde(skip_serializing_if = "admin_only")]
+    pub is_system: bool,
 }
 
+pii!(PublicUser { email, username, display_name });
+
 impl From<User> for PublicUser {
     fn from(user: User) -> Self {
         PublicUser {
             id: user.id,
             email: user.email,
             username: user.username,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
             is_admin: user.is_admin,
+            is_editor: user.is_editor,
             is_verified: user.is_verified,
             created_at: user.created_at,
+            updated_at: user.updated_at,
+            is_system: user.is_system,
         }
     }
 }
@@ -141,3 +210,77 @@ pub struct LogoutRequest {
     #[validate(length(min = 32, message = "Invalid token format"))]
     pub refresh_token: String,
 }
+
+
+/// `PATCH /users/me`: the public profile shown on the user's posts.
+/// `null` clears a field, leaving it out keeps it.
+#[derive(Debug, Deserialize, Validate, Default)]
+#[serde(default)]
+pub struct UpdateProfileRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub display_name: OptionField<String>,
+
+    #[validate(custom(function = "validate_optional_url_field"))]
+    pub avatar_url: OptionField<String>,
+}
+
+/// `PUT /admin/users/{user_id}/editor`
+#[derive(Debug, Deserialize)]
+pub struct EditorRoleRequest {
+    pub editor: bool,
+}
+
+/// `POST /admin/users/{user_id}/claims-version`
+#[derive(Debug, Serialize)]
+pub struct ClaimsVersionResponse {
+    pub user_id: Uuid,
+    pub claims_version: u64,
+}
+
+/// `user_audit.action` for a completed deletion
+pub const USER_AUDIT_DELETED: &str = "deleted";
+/// `user_audit.action` for a deletion refused by the `block` policy
+pub const USER_AUDIT_DELETE_BLOCKED: &str = "delete_blocked";
+
+/// Username and byline of the per-tenant system user
+pub const SYSTEM_USER_NAME: &str = "system";
+
+/// Address of a tenant's system user; `.invalid` never receives mail
+pub fn system_user_email(tenant_id: &Uuid) -> String {
+    format!("system+{}@users.invalid", tenant_id.simple())
+}
+
+/// What happens to a user's posts and bylines when the user is deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthoredContentPolicy {
+    /// Posts and bylines move to the tenant's system user
+    Reassign,
+    /// Posts lose their owner and the user's byline is removed
+    Anonymize,
+    /// Deletion is refused while the user owns or is credited on any post
+    Block,
+}
+
+impl AuthoredContentPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthoredContentPolicy::Reassign => "reassign",
+            AuthoredContentPolicy::Anonymize => "anonymize",
+            AuthoredContentPolicy::Block => "block",
+        }
+    }
+}
+
+impl FromStr for AuthoredContentPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "reassign" => Ok(AuthoredContentPolicy::Reassign),
+            "anonymize" => Ok(AuthoredContentPolicy::Anonymize),
+            "block" => Ok(AuthoredContentPolicy::Block),
+            _ => Err(format!("Invalid USER_CONTENT_POLICY value: {}, expected 'reassign', 'anonymize' or 'block'", s)),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::{pii::pii, user::PasswordHashAlgorithm};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+/// Upload limit for `POST /admin/users/import`
+pub const MAX_USER_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+/// Separator between roles in the `roles` column of a CSV export
+pub const CSV_ROLE_SEPARATOR: char = ';';
+
+// ───── Import File ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserImportFormat {
+    /// Header row with `email,username,password_hash,hash_algorithm,roles,verified`
+    Csv,
+    /// Array of objects with the same fields, `roles` as an array
+    Json,
+}
+
+impl UserImportFormat {
+    pub fn from_content_type(content_type: &str) -> Self {
+        if content_type.starts_with("text/csv") {
+            UserImportFormat::Csv
+        } else {
+            UserImportFormat::Json
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => UserImportFormat::Csv,
+            _ => UserImportFormat::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    Admin,
+    Editor,
+    /// Plain account; listed so exports that name every role still load
+    User,
+}
+
+/// One account from another backend's export. The hash is stored as is and
+/// replaced with Argon2id the first time the user signs in.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserImportRow {
+    pub email: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    pub password_hash: String,
+    pub hash_algorithm: PasswordHashAlgorithm,
+    #[serde(default)]
+    pub roles: Vec<UserRole>,
+    /// Defaults to true: the address was in use on the old backend
+    #[serde(default)]
+    pub verified: Option<bool>,
+}
+
+pii!(UserImportRow { email, username });
+
+impl UserImportRow {
+    pub fn has_role(&self, role: UserRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+/// CSV shape of `UserImportRow`, with the roles in one column
+#[derive(Debug, Deserialize)]
+pub struct CsvUserImportRow {
+    pub email: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    pub password_hash: String,
+    pub hash_algorithm: PasswordHashAlgorithm,
+    #[serde(default)]
+    pub roles: Option<String>,
+    #[serde(default)]
+    pub verified: Option<bool>,
+}
+
+impl TryFrom<CsvUserImportRow> for UserImportRow {
+    type Error = String;
+
+    fn try_from(row: CsvUserImportRow) -> Result<Self, Self::Error> {
+        let roles = row.roles
+            .as_deref()
+            .unwrap_or_default()
+            .split(CSV_ROLE_SEPARATOR)
+            .map(str::trim)
+            .filter(|role| !role.is_empty())
+            .map(|role| {
+                serde_json::from_value(serde_json::Value::String(role.to_lowercase()))
+                    .map_err(|_| format!("Unknown role '{}'", role))
+            })
+            .collect::<Result<Vec<UserRole>, _>>()?;
+
+        Ok(UserImportRow {
+            email: row.email,
+            username: row.username,
+            password_hash: row.password_hash,
+            hash_algorithm: row.hash_algorithm,
+            roles,
+            verified: row.verified,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// ───── Import Reports ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedUser {
+    /// 1-based position among the data rows, header not counted
+    pub row: usize,
+    pub email: String,
+    /// None on a dry run
+    pub id: Option<Uuid>,
+    pub hash_algorithm: PasswordHashAlgorithm,
+    pub roles: Vec<UserRole>,
+}
+
+pii!(ImportedUser { email });
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserImportIssue {
+    pub row: usize,
+    /// Missing when the row could not be read at all
+    pub email: Option<String>,
+    pub reason: String,
+}
+
+pii!(UserImportIssue { email });
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserImportReport {
+    /// Nothing was written; the report shows what a real import would do
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub imported: Vec<ImportedUser>,
+    /// The address already has an account, here or earlier in the file
+    pub conflicts: Vec<UserImportIssue>,
+    /// Rows that are malformed or fail validation
+    pub skipped: Vec<UserImportIssue>,
+}
\ No newline at end of file
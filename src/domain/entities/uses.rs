@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        blog_post::{validate_optional_url, validate_optional_url_field},
+        option_fields::OptionField,
+    },
+    utils::markdown::safe_markdown_to_html,
+};
+
+// ───── Constants ──────────────────────────────────────────────────────
+
+pub const MAX_CATEGORY_LENGTH: u64 = 60;
+pub const MAX_NAME_LENGTH: u64 = 100;
+pub const MAX_DESCRIPTION_LENGTH: u64 = 5000;
+pub const MAX_USES_ENTRIES: u64 = 500;
+
+// ───── Database Models ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UsesEntry {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub category: String,
+    pub name: String,
+    pub description_markdown: String,
+    pub link: Option<String>,
+    pub image_url: Option<String>,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct UsesEntryInsert {
+    pub category: String,
+    pub name: String,
+    pub description_markdown: String,
+    pub link: Option<String>,
+    pub image_url: Option<String>,
+    /// `None` appends after the current last entry
+    pub position: Option<i32>,
+}
+
+// ───── Requests ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct NewUsesEntryRequest {
+    #[validate(length(min = 1, max = MAX_CATEGORY_LENGTH))]
+    pub category: String,
+
+    #[validate(length(min = 1, max = MAX_NAME_LENGTH))]
+    pub name: String,
+
+    #[validate(length(max = MAX_DESCRIPTION_LENGTH))]
+    pub description_markdown: Option<String>,
+
+    #[validate(custom(function = "validate_optional_url"))]
+    pub link: Option<String>,
+
+    #[validate(custom(function = "validate_optional_url"))]
+    pub image_url: Option<String>,
+
+    pub position: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, Default)]
+#[serde(default)]
+pub struct UpdateUsesEntryRequest {
+    #[validate(length(min = 1, max = MAX_CATEGORY_LENGTH))]
+    pub category: OptionField<String>,
+
+    #[validate(length(min = 1, max = MAX_NAME_LENGTH))]
+    pub name: OptionField<String>,
+
+    #[validate(length(max = MAX_DESCRIPTION_LENGTH))]
+    pub description_markdown: OptionField<String>,
+
+    #[validate(custom(function = "validate_optional_url_field"))]
+    pub link: OptionField<String>,
+
+    #[validate(custom(function = "validate_optional_url_field"))]
+    pub image_url: OptionField<String>,
+
+    pub position: OptionField<i32>,
+}
+
+/// Full page order; every entry id must appear exactly once
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReorderUsesRequest {
+    #[validate(length(min = 1, max = MAX_USES_ENTRIES))]
+    pub ids: Vec<Uuid>,
+}
+
+// ───── Responses ─────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct UsesEntryResponse {
+    pub id: Uuid,
+    pub category: String,
+    pub name: String,
+    pub description_markdown: String,
+    pub description_html: String,
+    pub link: Option<String>,
+    pub image_url: Option<String>,
+    pub position: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsesCategory {
+    pub name: String,
+    pub entries: Vec<UsesEntryResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsesResponse {
+    pub categories: Vec<UsesCategory>,
+}
+
+// ───── Conversions ───────────────────────────────────────────────────
+
+impl From<NewUsesEntryRequest> for UsesEntryInsert {
+    fn from(req: NewUsesEntryRequest) -> Self {
+        Self {
+            category: req.category.trim().to_string(),
+            name: req.name.trim().to_string(),
+            description_markdown: req.description_markdown.unwrap_or_default(),
+            link: req.link,
+            image_url: req.image_url,
+            position: req.position,
+        }
+    }
+}
+
+impl From<UsesEntry> for UsesEntryResponse {
+    fn from(entry: UsesEntry) -> Self {
+        Self {
+            description_html: safe_markdown_to_html(&entry.description_markdown),
+            id: entry.id,
+            category: entry.category,
+            name: entry.name,
+            description_markdown: entry.description_markdown,
+            link: entry.link,
+            image_url: entry.image_url,
+            position: entry.position,
+            updated_at: entry.updated_at,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use std::cell::Cell;
+
+use serde::{Serialize, Serializer};
+
+/// Who a response is rendered for.
+///
+/// Response structs are shared by public and admin endpoints; fields only
+/// admins should see are marked `#[serde(skip_serializing_if = "admin_only")]`
+/// and left out while a `Viewed` value in the `Public` view is serialized.
+/// Anything serialized outside a `Viewed` wrapper, e.g. audit details and
+/// outbox payloads, keeps every field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseView {
+    Public,
+    Admin,
+}
+
+impl ResponseView {
+    pub fn for_admin(is_admin: bool) -> Self {
+        if is_admin { ResponseView::Admin } else { ResponseView::Public }
+    }
+}
+
+thread_local! {
+    // Serialization never yields, so the view cannot leak into another task
+    static CURRENT_VIEW: Cell<ResponseView> = const { Cell::new(ResponseView::Admin) };
+}
+
+/// `skip_serializing_if` for fields only admins see
+pub fn admin_only<T: ?Sized>(_: &T) -> bool {
+    CURRENT_VIEW.with(Cell::get) == ResponseView::Public
+}
+
+/// Serializes `value` as `view` sees it
+#[derive(Debug, Clone)]
+pub struct Viewed<T> {
+    pub view: ResponseView,
+    pub value: T,
+}
+
+impl<T> Viewed<T> {
+    pub fn new(view: ResponseView, value: T) -> Self {
+        Viewed { view, value }
+    }
+
+    pub fn public(value: T) -> Self {
+        Viewed::new(ResponseView::Public, value)
+    }
+
+    pub fn admin(value: T) -> Self {
+        Viewed::new(ResponseView::Admin, value)
+    }
+}
+
+impl<T: Serialize> Serialize for Viewed<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let _scope = ViewScope::enter(self.view);
+        self.value.serialize(serializer)
+    }
+}
+
+/// Restores the enclosing view when dropped, also if serializing panics
+struct ViewScope(ResponseView);
+
+impl ViewScope {
+    fn enter(view: ResponseView) -> Self {
+        ViewScope(CURRENT_VIEW.replace(view))
+    }
+}
+
+impl Drop for ViewScope {
+    fn drop(&mut self) {
+        CURRENT_VIEW.set(self.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct VisitorTokenResponse {
+    pub visitor_id: String,
+    pub token: String,
+    pub expires_in: i64,
+}
\ No newline at end of file
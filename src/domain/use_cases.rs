@@ -2,4 +2,52 @@ pub mod auth;
 pub mod extractors;
 pub mod about;
 pub mod blog;
-pub mod contact;
\ No newline at end of file
+pub mod contact;
+pub mod feature_flags;
+pub mod settings;
+pub mod notifications;
+pub mod tenants;
+pub mod domains;
+pub mod hire;
+pub mod uses;
+pub mod changelog;
+pub mod feed;
+pub mod outbound;
+pub mod honeytoken;
+pub mod prewarm;
+pub mod outbox;
+pub mod fixtures;
+pub mod presence;
+pub mod series;
+pub mod link_checks;
+pub mod edit_locks;
+pub mod config_history;
+pub mod backfills;
+pub mod content_safety;
+pub mod share_counts;
+pub mod rebuild;
+pub mod sync;
+pub mod static_export;
+pub mod geo;
+pub mod ingest;
+pub mod retention;
+pub mod dispatcher;
+pub mod audit;
+pub mod tag_suggestions;
+pub mod reading;
+pub mod title_tests;
+pub mod api_tokens;
+pub mod stats_rollups;
+pub mod usage;
+pub mod quota_alerts;
+pub mod one_time_tokens;
+pub mod account_emails;
+pub mod captcha;
+pub mod bookmarks;
+pub mod pages;
+pub mod schedule_preview;
+pub mod user_import;
+pub mod redirects;
+pub mod public_api;
+pub mod schema;
+pub mod jobs;pub mod post_bundle;
@@ -26,16 +26,17 @@ where
 
     /// Creates the "About Me" content with the provided markdown and effective date
     pub async fn create_about_me(
-        &self, 
+        &self,
+        tenant_id: Uuid,
         request: NewAboutMe
     ) -> Result<AboutMeCreatedResponse, AppError> {
         request.validate()?;
 
         let new_about_me = request.prepare_for_insert();
 
-        let id = self.about_repo.create_about_me(&new_about_me).await?;
+        let id = self.about_repo.create_about_me(&tenant_id, &new_about_me).await?;
 
-        let current_revision = self.about_repo.get_current_revision(new_about_me.effective_date).await?;
+        let current_revision = self.about_repo.get_current_revision(&tenant_id, new_about_me.effective_date).await?;
 
         Ok(AboutMeCreatedResponse {
             id,
@@ -48,50 +49,52 @@ where
     }
 
     /// Retrieves the current "About Me" content
-    pub async fn get_about_me(&self) -> Result<AboutMeResponse, AppError> {
-        self.about_repo.get_current_about_me().await
+    pub async fn get_about_me(&self, tenant_id: Uuid) -> Result<AboutMeResponse, AppError> {
+        self.about_repo.get_current_about_me(&tenant_id).await
             .map_err(|e| match e {
                 AppError::NotFound(_) => AppError::NotFound("About Me content not found".to_string()),
                 _ => e,
             })
     }
 
-    /// Updates the "About Me" content with new markdown and effective date
+    /// Updates the markdown and/or effective date of an "About Me" revision
     pub async fn update_about_me_content(
-        &self, 
+        &self,
+        tenant_id: Uuid,
         id: Uuid, 
         request: UpdateAboutMeRequest
     ) -> Result<AboutMeResponse, AppError> {
         request.validate()?;
 
+        if request.content_markdown.is_set_to_null() || request.effective_date.is_set_to_null() {
+            return Err(AppError::InvalidInput("About me always has content and an effective date".to_string()));
+        }
+
         let valid_id = valid_uuid(&id.to_string())?;
 
-        let current = self.about_repo.get_about_me_by_id(&valid_id).await?;
+        let current = self.about_repo.get_about_me_by_id(&tenant_id, &valid_id).await?;
 
         if current.revision != request.expected_revision {
             return Err(AppError::Conflict("Revision mismatch".to_string()));
         }
 
-        let updated = self.about_repo.update_about_me_content(
-            &valid_id, 
-            &request.content_markdown,
-            &request.effective_date
-        ).await?;
+        let updated = self.about_repo.update_about_me_content(&tenant_id, &valid_id, &request).await?;
 
         Ok(updated.into())
     }
 
     /// Deletes the "About Me" content by ID
     pub async fn delete_about_me(
-        &self, 
+        &self,
+        tenant_id: Uuid,
         id: Uuid,
         hard_delete: bool
     ) -> Result<(), AppError> {
         let valid_id = valid_uuid(&id.to_string())?;
 
         match hard_delete {
-            true => self.about_repo.hard_delete_about_me(valid_id).await,
-            false => self.about_repo.soft_delete_about_me(valid_id).await,
+            true => self.about_repo.hard_delete_about_me(&tenant_id, valid_id).await,
+            false => self.about_repo.soft_delete_about_me(&tenant_id, valid_id).await,
         }.map_err(|e| match e {
             AppError::NotFound(_) => AppError::NotFound("About Me content not found".to_string()),
             _ => e,
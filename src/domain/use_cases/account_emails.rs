@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    auth::password::PasswordHasherPool,
+    cache::claims_version::ClaimsVersionStore,
+    entities::{
+        app_setting::EMAIL_LOCALE,
+        one_time_token::{
+            EmailVerificationConfirmRequest, PasswordResetConfirmRequest, PasswordResetRequest, EMAIL_VERIFICATION_PATH,
+            EMAIL_VERIFICATION_TTL_HOURS, OneTimeToken, PASSWORD_RESET_PATH, PASSWORD_RESET_TTL_MINUTES, PURPOSE_EMAIL_VERIFICATION,
+            PURPOSE_PASSWORD_RESET,
+        },
+        tenant::Tenant,
+        user::User,
+    },
+    errors::AppError,
+    mailer::{
+        email::{EmailMessage, Mailer},
+        templates::{EmailBrand, EmailTemplate, Locale, PasswordResetEmail, VerificationEmail},
+    },
+    repositories::{app_settings::AppSettingsRepository, one_time_token::OneTimeTokenRepository, user::UserRepository},
+    settings::AppConfig,
+    use_cases::{one_time_tokens::OneTimeTokens, settings::RuntimeSettings},
+    utils::public_urls::PublicUrls,
+};
+
+/// Password reset and email verification by emailed link.
+///
+/// A link is spent before the change it allows is made and released again
+/// if that change fails, so a retry with the same link works while a
+/// second, concurrent use does not.
+#[derive(Clone)]
+pub struct AccountEmails<U, R, S>
+where
+    U: UserRepository,
+    R: OneTimeTokenRepository,
+    S: AppSettingsRepository,
+{
+    user_repo: U,
+    pub tokens: OneTimeTokens<R>,
+    settings: RuntimeSettings<S>,
+    password_hasher: PasswordHasherPool,
+    mailer: Arc<dyn Mailer>,
+    /// Bumped after a reset so sessions signed in with the old password end
+    claims_versions: Option<Arc<dyn ClaimsVersionStore>>,
+    from: String,
+    urls: PublicUrls,
+}
+
+impl<U, R, S> AccountEmails<U, R, S>
+where
+    U: UserRepository,
+    R: OneTimeTokenRepository,
+    S: AppSettingsRepository,
+{
+    pub fn new(
+        user_repo: U,
+        tokens: OneTimeTokens<R>,
+        settings: RuntimeSettings<S>,
+        password_hasher: PasswordHasherPool,
+        mailer: Arc<dyn Mailer>,
+        claims_versions: Option<Arc<dyn ClaimsVersionStore>>,
+        config: &AppConfig,
+    ) -> Self {
+        AccountEmails {
+            user_repo,
+            tokens,
+            settings,
+            password_hasher,
+            mailer,
+            claims_versions,
+            from: config.mail_from.clone(),
+            urls: PublicUrls::from_config(config),
+        }
+    }
+
+    /// Emails a reset link when the address belongs to a user who can sign
+    /// in. Succeeds either way, so the endpoint does not reveal accounts.
+    pub async fn request_password_reset(&self, tenant: &Tenant, request: PasswordResetRequest) -> Result<(), AppError> {
+        request.validate()?;
+
+        let Some(user) = self.user_repo.get_user_by_email(&tenant.id, &request.email).await? else {
+            return Ok(());
+        };
+        if user.is_system || user.deleted_at.is_some() {
+            return Ok(());
+        }
+
+        let ttl = Duration::minutes(i64::from(PASSWORD_RESET_TTL_MINUTES));
+        let token = self.tokens.issue(&tenant.id, user.id, PURPOSE_PASSWORD_RESET, ttl).await?;
+
+        let email = PasswordResetEmail {
+            name: display_name(&user),
+            reset_url: self.link(tenant, PASSWORD_RESET_PATH, &token),
+            expires_in_minutes: PASSWORD_RESET_TTL_MINUTES,
+        };
+        self.send(tenant, &user, &email).await
+    }
+
+    /// Whether a reset link can still be used, checked before the form is shown
+    pub async fn check_password_reset(&self, tenant_id: &Uuid, token: &str) -> Result<(), AppError> {
+        self.tokens
+            .peek(tenant_id, PURPOSE_PASSWORD_RESET, token)
+            .await?
+            .map(|_| ())
+            .ok_or_else(invalid_link)
+    }
+
+    pub async fn reset_password(&self, tenant_id: &Uuid, request: PasswordResetConfirmRequest) -> Result<(), AppError> {
+        request.validate()?;
+
+        let token = self.tokens
+            .consume(tenant_id, PURPOSE_PASSWORD_RESET, &request.token)
+            .await?
+            .ok_or_else(invalid_link)?;
+
+        let result = async {
+            let password_hash = self.password_hasher.hash(&request.new_password).await?;
+            self.user_repo.set_password(tenant_id, &token.user_id, &password_hash).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            self.release(&token).await;
+            return Err(e);
+        }
+
+        if let Some(store) = &self.claims_versions {
+            store.bump(&token.user_id).await?;
+        }
+        tracing::info!(user_id = %token.user_id, "Password reset");
+
+        Ok(())
+    }
+
+    /// Emails a verification link to a signed-in user; `Conflict` once verified
+    pub async fn send_verification(&self, tenant: &Tenant, user_id: Uuid) -> Result<(), AppError> {
+        let user = self.user_repo
+            .get_user_by_id(&tenant.id, &user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        if user.is_verified {
+            return Err(AppError::Conflict("Email is already verified".to_string()));
+        }
+
+        let ttl = Duration::hours(i64::from(EMAIL_VERIFICATION_TTL_HOURS));
+        let token = self.tokens.issue(&tenant.id, user.id, PURPOSE_EMAIL_VERIFICATION, ttl).await?;
+
+        let email = VerificationEmail {
+            name: display_name(&user),
+            verify_url: self.link(tenant, EMAIL_VERIFICATION_PATH, &token),
+            expires_in_hours: EMAIL_VERIFICATION_TTL_HOURS,
+        };
+        self.send(tenant, &user, &email).await
+    }
+
+    pub async fn verify_email(&self, tenant_id: &Uuid, request: EmailVerificationConfirmRequest) -> Result<(), AppError> {
+        request.validate()?;
+
+        let token = self.tokens
+            .consume(tenant_id, PURPOSE_EMAIL_VERIFICATION, &request.token)
+            .await?
+            .ok_or_else(invalid_link)?;
+
+        if let Err(e) = self.user_repo.mark_verified(tenant_id, &token.user_id).await {
+            self.release(&token).await;
+            return Err(e);
+        }
+        tracing::info!(user_id = %token.user_id, "Email verified");
+
+        Ok(())
+    }
+
+    async fn release(&self, token: &OneTimeToken) {
+        if let Err(e) = self.tokens.release(token).await {
+            tracing::error!(token_id = %token.id, error = %e, "Failed to release one-time token");
+        }
+    }
+
+    async fn send(&self, tenant: &Tenant, user: &User, email: &impl EmailTemplate) -> Result<(), AppError> {
+        let locale = self.settings.get::<Locale>(&tenant.id, EMAIL_LOCALE).unwrap_or_default();
+        let rendered = email.render(&self.brand(tenant), locale)?;
+
+        self.mailer.send(&EmailMessage {
+            from: self.from.clone(),
+            to: user.email.clone(),
+            subject: rendered.subject,
+            text: rendered.text,
+            html: Some(rendered.html),
+            headers: Default::default(),
+        }).await
+    }
+
+    fn link(&self, tenant: &Tenant, path: &str, token: &str) -> String {
+        format!("{}?token={}", self.urls.site(tenant, path), token)
+    }
+
+    fn brand(&self, tenant: &Tenant) -> EmailBrand {
+        let site_url = self.urls.site_origin(tenant);
+        EmailBrand {
+            name: tenant.name.clone(),
+            site_url: (!site_url.is_empty()).then_some(site_url),
+        }
+    }
+}
+
+fn display_name(user: &User) -> String {
+    user.display_name
+        .clone()
+        .or_else(|| user.username.clone())
+        .unwrap_or_else(|| user.email.clone())
+}
+
+fn invalid_link() -> AppError {
+    AppError::NotFound("This link is invalid or has expired".to_string())
+}
\ No newline at end of file
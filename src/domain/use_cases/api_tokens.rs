@@ -0,0 +1,484 @@
+use std::{collections::{BTreeMap, HashMap}, fmt, sync::Arc};
+
+use chrono::{Months, NaiveDate, Utc};
+use parking_lot::{Mutex, RwLock};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    cache::api_token_usage::ApiTokenUsageCounter,
+    entities::{
+        api_token::{
+            hash_api_token, ApiToken, ApiTokenDailyUsage, ApiTokenInsert, ApiTokenMonthUsage, ApiTokenReport, ApiTokenSummary,
+            ApiTokenTraffic, ApiTokenUsage, ApiTokenUsageQuery, ApiTokenUsageReport, CreateApiTokenRequest, IssuedApiToken,
+            API_TOKEN_PREFIX, API_TOKEN_SCOPES, API_TOKEN_VISIBLE_CHARS,
+        },
+        stats_rollup::{start_of_day, start_of_month},
+        usage::QuotaUsage,
+    },
+    errors::{AppError, AuthError},
+    metrics::METRICS,
+    repositories::api_token::ApiTokenRepository,
+    settings::AppConfig,
+};
+
+/// Months in a usage report when the query does not say
+const DEFAULT_USAGE_MONTHS: u32 = 3;
+
+/// Identifies a token in usage counters and rate limiter keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiTokenKey {
+    Issued(Uuid),
+    Config(String),
+}
+
+impl fmt::Display for ApiTokenKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiTokenKey::Issued(id) => write!(f, "{}", id),
+            ApiTokenKey::Config(name) => write!(f, "config:{}", name),
+        }
+    }
+}
+
+/// A token the middleware can check without the database
+#[derive(Debug, Clone)]
+struct CachedToken {
+    key: ApiTokenKey,
+    name: String,
+    /// `None` for configured tokens, which are valid on every site
+    tenant_id: Option<Uuid>,
+    scopes: Vec<String>,
+    per_minute: u64,
+    monthly_quota: Option<u64>,
+}
+
+impl CachedToken {
+    fn issued(token: &ApiToken, default_per_minute: u64) -> Self {
+        CachedToken {
+            key: ApiTokenKey::Issued(token.id),
+            name: token.name.clone(),
+            tenant_id: Some(token.tenant_id),
+            scopes: token.scopes.clone(),
+            per_minute: token.rate_limit_per_minute.map_or(default_per_minute, |limit| limit as u64),
+            monthly_quota: token.monthly_quota.map(|quota| quota as u64),
+        }
+    }
+}
+
+/// Requests of tokens with a quota this month, as of the last refresh
+#[derive(Debug, Default)]
+struct MonthRequests {
+    month: NaiveDate,
+    requests: HashMap<Uuid, u64>,
+}
+
+/// What an accepted token may do
+#[derive(Debug, Clone)]
+pub struct ApiTokenGrant {
+    pub key: ApiTokenKey,
+    pub per_minute: u64,
+    pub monthly_quota: Option<u64>,
+}
+
+/// Read-only API tokens required on high-volume public routes, so scrapers
+/// need more than the page's URL. Tokens are sent in `X-Api-Token` or the
+/// `api_token` query parameter, neither of which relies on the browser's
+/// origin, and each has its own rate limit bucket.
+///
+/// Configured tokens are valid on every site and scope; admins issue
+/// narrower ones per site. Issued tokens are cached in process and
+/// refreshed periodically; usage is counted in memory and flushed to the
+/// database on each refresh.
+///
+/// Issued tokens' traffic is also counted per day. With Redis the days are
+/// added up there across instances and their totals saved, otherwise each
+/// instance adds its own counts. A token's monthly quota is checked against
+/// the stored month plus what this instance has not flushed yet, so other
+/// instances' requests are seen one refresh late.
+#[derive(Clone)]
+pub struct ApiTokens<R>
+where
+    R: ApiTokenRepository,
+{
+    pub token_repo: R,
+    /// Issued tokens by hash
+    issued: Arc<RwLock<HashMap<String, CachedToken>>>,
+    /// Configured tokens by hash
+    configured: Arc<HashMap<String, CachedToken>>,
+    /// Usage of issued tokens since the last flush, of configured ones since startup
+    usage: Arc<Mutex<HashMap<ApiTokenKey, ApiTokenUsage>>>,
+    /// Issued tokens' traffic per day since the last flush
+    traffic: Arc<Mutex<HashMap<(Uuid, NaiveDate), ApiTokenTraffic>>>,
+    /// Day totals from Redis that Postgres has not taken yet
+    unsaved_totals: Arc<Mutex<HashMap<(Uuid, NaiveDate), ApiTokenDailyUsage>>>,
+    month_requests: Arc<RwLock<MonthRequests>>,
+    usage_counter: Option<Arc<dyn ApiTokenUsageCounter>>,
+    burst: u64,
+    default_per_minute: u64,
+}
+
+impl<R> ApiTokens<R>
+where
+    R: ApiTokenRepository,
+{
+    pub fn new(token_repo: R, usage_counter: Option<Arc<dyn ApiTokenUsageCounter>>, config: &AppConfig) -> Self {
+        let configured = config
+            .public_api_tokens()
+            .into_iter()
+            .map(|(name, token)| {
+                let cached = CachedToken {
+                    key: ApiTokenKey::Config(name.clone()),
+                    name,
+                    tenant_id: None,
+                    scopes: API_TOKEN_SCOPES.iter().map(|scope| scope.to_string()).collect(),
+                    per_minute: config.api_token_per_minute,
+                    monthly_quota: None,
+                };
+                (hash_api_token(&token), cached)
+            })
+            .collect();
+
+        ApiTokens {
+            token_repo,
+            issued: Arc::default(),
+            configured: Arc::new(configured),
+            usage: Arc::default(),
+            traffic: Arc::default(),
+            unsaved_totals: Arc::default(),
+            month_requests: Arc::default(),
+            usage_counter,
+            burst: config.api_token_burst,
+            default_per_minute: config.api_token_per_minute,
+        }
+    }
+
+    /// Flushes counted usage, then reloads the issued tokens and this
+    /// month's requests, returning how many tokens are active. A failed
+    /// flush does not hold back the reload but is still returned.
+    pub async fn refresh(&self) -> Result<usize, AppError> {
+        let flushed = self.flush_usage().await;
+
+        let fresh: HashMap<String, CachedToken> = self.token_repo
+            .list_active_api_tokens()
+            .await?
+            .iter()
+            .map(|token| (token.token_hash.clone(), CachedToken::issued(token, self.default_per_minute)))
+            .collect();
+        let count = fresh.len();
+
+        *self.issued.write() = fresh;
+
+        let month = start_of_month(Utc::now().date_naive());
+        let requests = self.token_repo
+            .count_quota_requests_since(month)
+            .await?
+            .into_iter()
+            .map(|(id, requests)| (id, requests as u64))
+            .collect();
+        *self.month_requests.write() = MonthRequests { month, requests };
+
+        flushed.map(|()| count)
+    }
+
+    /// Adds the issued tokens' usage to their stored totals and flushes
+    /// their daily traffic. On failure the usage is kept for the next flush.
+    pub async fn flush_usage(&self) -> Result<(), AppError> {
+        let totals = self.flush_token_usage().await;
+        let daily = self.flush_daily_usage().await;
+
+        totals.and(daily)
+    }
+
+    async fn flush_token_usage(&self) -> Result<(), AppError> {
+        let pending: Vec<(Uuid, ApiTokenUsage)> = {
+            let mut usage = self.usage.lock();
+            let (issued, configured): (HashMap<_, _>, HashMap<_, _>) = std::mem::take(&mut *usage)
+                .into_iter()
+                .partition(|(key, _)| matches!(key, ApiTokenKey::Issued(_)));
+            *usage = configured;
+
+            issued
+                .into_iter()
+                .filter_map(|(key, counted)| match key {
+                    ApiTokenKey::Issued(id) => Some((id, counted)),
+                    ApiTokenKey::Config(_) => None,
+                })
+                .collect()
+        };
+
+        if let Err(e) = self.token_repo.add_api_token_usage(&pending).await {
+            let mut usage = self.usage.lock();
+            for (id, counted) in &pending {
+                usage.entry(ApiTokenKey::Issued(*id)).or_default().merge(counted);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Through Redis when configured, saving the day totals it returns;
+    /// otherwise the counts are added in Postgres
+    async fn flush_daily_usage(&self) -> Result<(), AppError> {
+        let counted: Vec<ApiTokenDailyUsage> = std::mem::take(&mut *self.traffic.lock())
+            .into_iter()
+            .map(|((id, day), traffic)| ApiTokenDailyUsage::new(id, day, &traffic))
+            .collect();
+        if counted.is_empty() && self.unsaved_totals.lock().is_empty() {
+            return Ok(());
+        }
+
+        let Some(counter) = &self.usage_counter else {
+            if let Err(e) = self.token_repo.add_api_token_daily_usage(&counted).await {
+                self.restore_traffic(&counted);
+                return Err(e);
+            }
+            return Ok(());
+        };
+
+        let fresh = match counter.add_usage(&counted).await {
+            Ok(totals) => totals,
+            Err(e) => {
+                self.restore_traffic(&counted);
+                return Err(e);
+            }
+        };
+
+        // Later totals of a day replace earlier ones that were not saved
+        let mut totals = std::mem::take(&mut *self.unsaved_totals.lock());
+        totals.extend(fresh.into_iter().map(|total| ((total.token_id, total.day), total)));
+        let totals: Vec<ApiTokenDailyUsage> = totals.into_values().collect();
+
+        if let Err(e) = self.token_repo.save_api_token_daily_totals(&totals).await {
+            let mut unsaved = self.unsaved_totals.lock();
+            for total in totals {
+                unsaved.entry((total.token_id, total.day)).or_insert(total);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn restore_traffic(&self, counted: &[ApiTokenDailyUsage]) {
+        let mut traffic = self.traffic.lock();
+        for usage in counted {
+            traffic.entry((usage.token_id, usage.day)).or_default().merge(&usage.traffic());
+        }
+    }
+
+    /// Requests a token can make at once; the sustained rate is per token
+    pub fn burst(&self) -> u64 {
+        self.burst
+    }
+
+    /// Checks a presented token against the site and the route's scope
+    pub fn authorize(&self, tenant_id: &Uuid, presented: &str, scope: &str) -> Result<ApiTokenGrant, AuthError> {
+        let hash = hash_api_token(presented);
+        let token = self.issued
+            .read()
+            .get(&hash)
+            .or_else(|| self.configured.get(&hash))
+            .cloned()
+            .ok_or(AuthError::InvalidToken)?;
+
+        if token.tenant_id.is_some_and(|id| id != *tenant_id) {
+            return Err(AuthError::InvalidToken);
+        }
+        if !token.scopes.iter().any(|s| s == scope) {
+            return Err(AuthError::Forbidden(format!("API token is not valid for '{}'", scope)));
+        }
+
+        Ok(ApiTokenGrant { key: token.key, per_minute: token.per_minute, monthly_quota: token.monthly_quota })
+    }
+
+    /// Seconds until the quota resets when the token has made this month's
+    /// allowance of requests
+    pub fn check_quota(&self, grant: &ApiTokenGrant) -> Result<(), u64> {
+        let (Some(quota), ApiTokenKey::Issued(id)) = (grant.monthly_quota, &grant.key) else {
+            return Ok(());
+        };
+
+        let now = Utc::now();
+        let month = start_of_month(now.date_naive());
+        if self.month_requests(id, month) < quota {
+            return Ok(());
+        }
+
+        METRICS.incr("api_token_quota_exceeded_total");
+        let resets_at = start_of_day(month + Months::new(1));
+        Err((resets_at - now).num_seconds().max(1) as u64)
+    }
+
+    /// This month's requests of every issued token with a quota, by site
+    pub fn quota_usage(&self) -> Vec<(Uuid, QuotaUsage)> {
+        let month = start_of_month(Utc::now().date_naive());
+        let tokens: Vec<CachedToken> = self.issued.read().values().cloned().collect();
+
+        tokens
+            .into_iter()
+            .filter_map(|token| match (token.key, token.tenant_id, token.monthly_quota) {
+                (ApiTokenKey::Issued(id), Some(tenant_id), Some(quota)) => Some((
+                    tenant_id,
+                    QuotaUsage {
+                        quota: format!("api_token:{}", id),
+                        label: format!("Requests of API token '{}' this month", token.name),
+                        used: self.month_requests(&id, month),
+                        limit: quota,
+                    },
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn month_requests(&self, id: &Uuid, month: NaiveDate) -> u64 {
+        let stored = {
+            let months = self.month_requests.read();
+            if months.month == month { months.requests.get(id).copied().unwrap_or(0) } else { 0 }
+        };
+        let pending: u64 = self.traffic
+            .lock()
+            .iter()
+            .filter(|((token, day), _)| token == id && *day >= month)
+            .map(|(_, traffic)| traffic.requests)
+            .sum();
+
+        stored + pending
+    }
+
+    /// Counts one request made with an accepted token
+    pub fn record_use(&self, key: &ApiTokenKey, allowed: bool) {
+        let mut usage = self.usage.lock();
+        let counted = usage.entry(key.clone()).or_default();
+        counted.requests += 1;
+        counted.rejected += u64::from(!allowed);
+        counted.last_used_at = Some(Utc::now());
+
+        METRICS.incr(if allowed { "api_token_requests_total" } else { "api_token_rejected_total" });
+    }
+
+    /// Counts a response to an issued token in its daily traffic; `bytes`
+    /// is the body size when known
+    pub fn record_traffic(&self, key: &ApiTokenKey, bytes: u64, error: bool) {
+        let ApiTokenKey::Issued(id) = key else {
+            return;
+        };
+
+        let mut traffic = self.traffic.lock();
+        let counted = traffic.entry((*id, Utc::now().date_naive())).or_default();
+        counted.requests += 1;
+        counted.bytes += bytes;
+        counted.errors += u64::from(error);
+    }
+
+    /// The token's traffic per month, including what is not flushed yet
+    pub async fn usage(&self, tenant_id: &Uuid, id: Uuid, query: ApiTokenUsageQuery) -> Result<ApiTokenUsageReport, AppError> {
+        query.validate()?;
+
+        let token = self.token_repo
+            .list_api_tokens(tenant_id)
+            .await?
+            .into_iter()
+            .find(|token| token.id == id)
+            .ok_or_else(|| AppError::NotFound("API token not found".to_string()))?;
+
+        let this_month = start_of_month(Utc::now().date_naive());
+        let months = query.months.unwrap_or(DEFAULT_USAGE_MONTHS);
+        let since = this_month - Months::new(months - 1);
+
+        let mut days: BTreeMap<NaiveDate, ApiTokenTraffic> = self.token_repo
+            .list_api_token_daily_usage(&id, since)
+            .await?
+            .iter()
+            .map(|usage| (usage.day, usage.traffic()))
+            .collect();
+        for ((token, day), traffic) in self.traffic.lock().iter() {
+            if *token == id && *day >= since {
+                days.entry(*day).or_default().merge(traffic);
+            }
+        }
+
+        let monthly_quota = token.monthly_quota.map(|quota| quota as u64);
+        let months = (0..months)
+            .map(|back| {
+                let month = this_month - Months::new(back);
+                ApiTokenMonthUsage::new(month, days.range(month..month + Months::new(1)), monthly_quota)
+            })
+            .collect();
+
+        Ok(ApiTokenUsageReport { id, name: token.name, monthly_quota, months })
+    }
+
+    /// The site's issued tokens, then the configured ones, with their usage
+    pub async fn report(&self, tenant_id: &Uuid) -> Result<ApiTokenReport, AppError> {
+        let issued = self.token_repo.list_api_tokens(tenant_id).await?;
+        let usage = self.usage.lock().clone();
+
+        let mut tokens: Vec<ApiTokenSummary> = issued
+            .into_iter()
+            .map(|token| {
+                let pending = usage.get(&ApiTokenKey::Issued(token.id)).copied().unwrap_or_default();
+                ApiTokenSummary::issued(token, pending, self.default_per_minute)
+            })
+            .collect();
+
+        let mut configured: Vec<ApiTokenSummary> = self.configured
+            .values()
+            .filter_map(|token| match &token.key {
+                ApiTokenKey::Config(name) => Some(ApiTokenSummary::configured(
+                    name.clone(),
+                    usage.get(&token.key).copied().unwrap_or_default(),
+                    token.per_minute,
+                )),
+                ApiTokenKey::Issued(_) => None,
+            })
+            .collect();
+        configured.sort_by(|a, b| a.name.cmp(&b.name));
+        tokens.extend(configured);
+
+        Ok(ApiTokenReport { total: tokens.len(), tokens })
+    }
+
+    /// Issues a token for the site; the token is only ever returned here
+    pub async fn issue(&self, tenant_id: Uuid, created_by: Option<Uuid>, request: CreateApiTokenRequest) -> Result<IssuedApiToken, AppError> {
+        request.validate()?;
+
+        let token = format!("{}{:032x}", API_TOKEN_PREFIX, rand::random::<u128>());
+        let mut scopes = request.scopes;
+        scopes.sort_unstable();
+        scopes.dedup();
+
+        let insert = ApiTokenInsert {
+            name: request.name.trim().to_string(),
+            token_hash: hash_api_token(&token),
+            token_prefix: token[..API_TOKEN_PREFIX.len() + API_TOKEN_VISIBLE_CHARS].to_string(),
+            scopes,
+            rate_limit_per_minute: request.rate_limit_per_minute.map(|limit| limit as i32),
+            monthly_quota: request.monthly_quota.map(|quota| quota as i64),
+            created_by,
+        };
+        let created = self.token_repo.create_api_token(&tenant_id, &insert).await?;
+
+        self.issued
+            .write()
+            .insert(created.token_hash.clone(), CachedToken::issued(&created, self.default_per_minute));
+
+        Ok(IssuedApiToken {
+            token,
+            summary: ApiTokenSummary::issued(created, ApiTokenUsage::default(), self.default_per_minute),
+        })
+    }
+
+    /// Revokes at once on this instance; others drop the token on their next refresh
+    pub async fn revoke(&self, tenant_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        if !self.token_repo.revoke_api_token(&tenant_id, &id).await? {
+            return Err(AppError::NotFound("API token not found".to_string()));
+        }
+
+        let key = ApiTokenKey::Issued(id);
+        self.issued.write().retain(|_, token| token.key != key);
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,144 @@
+use actix_web::web::Bytes;
+use futures::{stream, Stream};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::audit::{AuditCursor, AuditEntry, AuditExportFormat, AuditLogFilter, AuditLogPage, AuditLogQuery},
+    errors::AppError,
+    repositories::audit_log::AuditLogRepository,
+    utils::{csv_export::encode_csv_chunk, redact::Redactor},
+};
+
+/// Rows fetched per round trip when streaming an export
+const EXPORT_CHUNK_SIZE: i64 = 500;
+
+const EXPORT_CSV_HEADER: [&str; 8] = [
+    "id", "created_at", "actor", "action", "entity_type", "entity_id", "result", "details",
+];
+
+/// Read side of the audit log for the admin UI: filtered, searchable pages
+/// and streamed CSV/JSON exports. Entries are written by the features they
+/// describe; `DataRetention` purges or archives old ones.
+#[derive(Clone)]
+pub struct AuditTrail<A>
+where
+    A: AuditLogRepository,
+{
+    pub audit_repo: A,
+}
+
+impl<A> AuditTrail<A>
+where
+    A: AuditLogRepository,
+{
+    pub fn new(audit_repo: A) -> Self {
+        AuditTrail { audit_repo }
+    }
+
+    pub async fn search(&self, tenant_id: Uuid, filter: AuditLogFilter, page: AuditLogQuery) -> Result<AuditLogPage, AppError> {
+        validate_filter(&filter)?;
+        page.validate()?;
+
+        let limit = page.limit.unwrap_or(50) as i64;
+        let offset = page.offset.unwrap_or(0) as i64;
+        let entries = self.audit_repo.search_entries(&tenant_id, &filter, limit, offset).await?;
+        let total = self.audit_repo.count_entries(&tenant_id, &filter).await?;
+
+        Ok(AuditLogPage { entries, total })
+    }
+
+    /// Streams every matching entry, oldest first, one chunk per repository
+    /// round trip. Empty exports are still a valid CSV (header only) or JSON (`[]`).
+    ///
+    /// Unless `include_pii` is set, personal data in `details` is redacted.
+    pub fn export(
+        &self,
+        tenant_id: Uuid,
+        filter: AuditLogFilter,
+        format: AuditExportFormat,
+        include_pii: bool,
+    ) -> Result<impl Stream<Item = Result<Bytes, AppError>> + 'static, AppError>
+    where
+        A: Clone + 'static,
+    {
+        validate_filter(&filter)?;
+
+        let repo = self.audit_repo.clone();
+        let redactor = (!include_pii).then(Redactor::with_defaults);
+        // (position, nothing sent yet, last chunk sent)
+        let initial: (Option<AuditCursor>, bool, bool) = (None, true, false);
+
+        Ok(stream::try_unfold(initial, move |(cursor, first, finished)| {
+            let repo = repo.clone();
+            let filter = filter.clone();
+            let redactor = redactor.clone();
+
+            async move {
+                if finished {
+                    return Ok(None);
+                }
+
+                let mut rows = repo.list_entries_chunk(&tenant_id, &filter, cursor, EXPORT_CHUNK_SIZE).await?;
+                if let Some(redactor) = &redactor {
+                    rows.iter_mut().for_each(|entry| redactor.redact_json(&mut entry.details));
+                }
+                let last = (rows.len() as i64) < EXPORT_CHUNK_SIZE;
+                let next_cursor = rows.last().map(|e| (e.created_at, e.id)).or(cursor);
+
+                let chunk = match format {
+                    AuditExportFormat::Csv => encode_csv_chunk(&EXPORT_CSV_HEADER, rows.iter().map(csv_row), first)?,
+                    AuditExportFormat::Json => encode_json_chunk(&rows, first, last)?,
+                };
+                Ok(Some((chunk, (next_cursor, false, last))))
+            }
+        }))
+    }
+}
+
+fn validate_filter(filter: &AuditLogFilter) -> Result<(), AppError> {
+    filter.validate()?;
+
+    if let (Some(from), Some(to)) = (filter.from, filter.to)
+        && from >= to
+    {
+        return Err(AppError::InvalidInput("`from` must be earlier than `to`".to_string()));
+    }
+
+    Ok(())
+}
+
+fn csv_row(entry: &AuditEntry) -> [String; 8] {
+    [
+        entry.id.to_string(),
+        entry.created_at.to_rfc3339(),
+        entry.actor.clone(),
+        entry.action.clone(),
+        entry.entity_type.clone().unwrap_or_default(),
+        entry.entity_id.clone().unwrap_or_default(),
+        entry.result.clone(),
+        entry.details.to_string(),
+    ]
+}
+
+/// Pieces of one JSON array: `[` opens the first chunk, `]` closes the last
+fn encode_json_chunk(rows: &[AuditEntry], first: bool, last: bool) -> Result<Bytes, AppError> {
+    let mut buf = Vec::new();
+    if first {
+        buf.push(b'[');
+    }
+
+    for (i, entry) in rows.iter().enumerate() {
+        if !(first && i == 0) {
+            buf.push(b',');
+        }
+        serde_json::to_writer(&mut buf, entry)
+            .map_err(|e| AppError::InternalError(format!("JSON encoding failed: {}", e)))?;
+    }
+
+    if last {
+        buf.push(b']');
+    }
+
+    Ok(Bytes::from(buf))
+}
\ No newline at end of file
@@ -1,13 +1,18 @@
+use std::sync::Arc;
+
 use actix_web::HttpRequest;
 use chrono::Utc;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::entities::token::{AuthResponse, TokenType};
-use crate::entities::user::{LoginUser, NewUser, NewUserResponse, PublicUser, User};
-use crate::errors::{AppError, AuthError};
+use crate::entities::token::{AuthResponse, Claims, TokenType};
+use crate::entities::user::{
+    AuthoredContentPolicy, ClaimsVersionResponse, LoginUser, NewUser, NewUserResponse, PublicUser, UpdateProfileRequest, User,
+};
+use crate::errors::{AppError, AuthError, PasswordError};
 use crate::interfaces::repositories::user::UserRepository;
-use crate::auth::password::{hash_password, verify_password};
+use crate::auth::password::{needs_rehash, PasswordHasherPool};
+use crate::cache::claims_version::ClaimsVersionStore;
 use crate::repositories::token::TokenServiceRepository;
 use crate::{is_token_invalid, AppState, TokenCheckMode};
 
@@ -18,6 +23,11 @@ where
 {
     pub user_repo: R,
     pub token_service: T,
+    pub password_hasher: PasswordHasherPool,
+    /// Applied to a user's posts when the user is deleted
+    content_policy: AuthoredContentPolicy,
+    /// Per-user claims versions; without Redis every token carries version 0
+    claims_versions: Option<Arc<dyn ClaimsVersionStore>>,
 }
 
 impl<R, T> AuthHandler<R, T>
@@ -25,20 +35,30 @@ where
     R: UserRepository,
     T: TokenServiceRepository,
 {
-    pub fn new(user_repo: R, token_service: T) -> Self {
+    pub fn new(
+        user_repo: R,
+        token_service: T,
+        password_hasher: PasswordHasherPool,
+        content_policy: AuthoredContentPolicy,
+        claims_versions: Option<Arc<dyn ClaimsVersionStore>>,
+    ) -> Self {
         AuthHandler { 
             user_repo, 
-            token_service 
+            token_service,
+            password_hasher,
+            content_policy,
+            claims_versions,
         }
     }
 
-    /// Registers a new user after validation and password hashing
-    pub async fn register(&self, request: NewUser) -> Result<NewUserResponse, AppError> {
+    /// Registers a new user after validation and password hashing.
+    /// The first user of each tenant becomes that tenant's admin.
+    pub async fn register(&self, tenant_id: Uuid, request: NewUser) -> Result<NewUserResponse, AppError> {
         request.validate()?;
 
-        let hashed_password = hash_password(&request.password)?;
+        let hashed_password = self.password_hasher.hash(&request.password).await?;
 
-        let existing_count = self.user_repo.count_users().await?;
+        let existing_count = self.user_repo.count_users(&tenant_id).await?;
         let is_first_user = existing_count == 0;
         
         if !is_first_user && request.is_admin {
@@ -47,7 +67,7 @@ where
 
         let user_insert = request.prepare_for_insert(hashed_password, is_first_user);
 
-        match self.user_repo.create_user(&user_insert).await {
+        match self.user_repo.create_user(&tenant_id, &user_insert).await {
             Ok(user_d) => Ok(NewUserResponse {
                 id: user_d,
                 message: "User created successfully".to_string(),
@@ -58,38 +78,61 @@ where
     }
 
     /// Logs in a user by validating credentials and generating JWTs
-    pub async fn login(&self, request: LoginUser) -> Result<AuthResponse, AuthError> {
+    pub async fn login(&self, tenant_id: Uuid, request: LoginUser) -> Result<AuthResponse, AuthError> {
         request.validate()?;
 
-        let user = self.user_repo.get_user_by_email(&request.email)
+        let user = self.user_repo.get_user_by_email(&tenant_id, &request.email)
             .await
             .map_err(|_e| AuthError::WrongCredentials)?
             .ok_or_else(|| AuthError::WrongCredentials)?;
 
 
-        let is_password_valid = verify_password(&request.password, &user.password_hash)
-            .map_err(|_| {
-                AuthError::WrongCredentials
+        let is_password_valid = self.password_hasher.verify(&request.password, &user.password_hash)
+            .await
+            .map_err(|e| match e {
+                PasswordError::Busy => AuthError::ServiceBusy,
+                _ => AuthError::WrongCredentials,
             })?;
         if !is_password_valid {
             return Err(AuthError::WrongCredentials);
         }
 
-        let response = self.create_auth_response(&user)?;
+        if needs_rehash(&user.password_hash) {
+            self.rehash_password(&user, &request.password).await;
+        }
+
+        let response = self.create_auth_response(&user).await?;
 
         tracing::info!("User logged in successfully");
         Ok(response)
     }
 
+    /// Replaces an imported bcrypt or older Argon2 hash with Argon2id now
+    /// that the password is known. A failure only means trying again on the
+    /// next sign-in, so it never fails the login.
+    async fn rehash_password(&self, user: &User, password: &str) {
+        let result = match self.password_hasher.hash(password).await {
+            Ok(hash) => self.user_repo.set_password(&user.tenant_id, &user.id, &hash).await.map(|_| ()),
+            Err(e) => Err(AppError::from(e)),
+        };
+
+        match result {
+            Ok(()) => tracing::info!(user_id = %user.id, "Password hash upgraded to Argon2id"),
+            Err(e) => tracing::warn!(user_id = %user.id, "Password rehash failed: {}", e),
+        }
+    }
+
     /// Create auth response
-    pub fn create_auth_response(&self, user: &User) -> Result<AuthResponse, AuthError> {
-        let access_token = self.token_service.create_jwt(user)
+    pub async fn create_auth_response(&self, user: &User) -> Result<AuthResponse, AuthError> {
+        let claims_version = self.claims_version(&user.id).await?;
+
+        let access_token = self.token_service.create_jwt(user, claims_version)
             .map_err(|e| {
                 tracing::warn!("Failed to create JWT: {}", e);
                 AuthError::TokenCreation
             })?;
             
-        let refresh_token = self.token_service.create_refresh_jwt(&user.id)
+        let refresh_token = self.token_service.create_refresh_jwt(&user.id, claims_version)
             .map_err(|e| {
                 tracing::warn!("Failed to create refresh JWT: {}", e);
                 AuthError::TokenCreation
@@ -99,7 +142,8 @@ where
 
     /// Refreshes the access token using the refresh token
     pub async fn refresh_token(
-        &self, 
+        &self,
+        tenant_id: Uuid,
         token: &str,
         state: &AppState
     ) -> Result<AuthResponse, AuthError> {
@@ -125,8 +169,13 @@ where
 
         let user_id = Uuid::parse_str(&decoded.claims.sub)
             .map_err(|_| AuthError::InvalidUserId)?;
+
+        if decoded.claims.claims_version < self.claims_version(&user_id).await? {
+            tracing::warn!(user_id = %user_id, "Refresh attempt with a stale claims version");
+            return Err(AuthError::StaleClaims);
+        }
         
-        let user = self.user_repo.get_user_by_id(&user_id)
+        let user = self.user_repo.get_user_by_id(&tenant_id, &user_id)
             .await
             .map_err(|e| {
                 tracing::error!("Database error during refresh: {}", e);
@@ -142,7 +191,43 @@ where
             return Err(AuthError::WrongCredentials);
         }
         
-        self.create_auth_response(&user)
+        self.create_auth_response(&user).await
+    }
+
+    async fn claims_version(&self, user_id: &Uuid) -> Result<u64, AuthError> {
+        match &self.claims_versions {
+            Some(store) => store.current(user_id).await.map_err(|e| AuthError::RedisOperation(e.to_string())),
+            None => Ok(0),
+        }
+    }
+
+    /// Rejects access tokens issued before the user's claims version was bumped
+    pub async fn check_claims_version(&self, claims: &Claims) -> Result<(), AuthError> {
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| AuthError::InvalidUserId)?;
+
+        if claims.claims_version < self.claims_version(&user_id).await? {
+            tracing::warn!(user_id = %user_id, "Access token with a stale claims version");
+            return Err(AuthError::StaleClaims);
+        }
+
+        Ok(())
+    }
+
+    /// Invalidates every access and refresh token the user holds; they have
+    /// to sign in again and get tokens carrying their current roles
+    pub async fn bump_claims_version(&self, tenant_id: Uuid, user_id: Uuid) -> Result<ClaimsVersionResponse, AppError> {
+        let store = self.claims_versions
+            .as_ref()
+            .ok_or_else(|| AppError::ServiceUnavailable("Claims versioning needs Redis".to_string()))?;
+
+        self.user_repo.get_user_by_id(&tenant_id, &user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let claims_version = store.bump(&user_id).await?;
+        tracing::info!(user_id = %user_id, claims_version, "Claims version bumped");
+
+        Ok(ClaimsVersionResponse { user_id, claims_version })
     }
 
     pub async fn get_current_user(
@@ -154,12 +239,13 @@ where
             return Err(AppError::ForbiddenAccess);
         }
 
-        self.user_repo.get_user_by_id(&user_id)
+        self.user_repo.get_user_by_id(&current_user.tenant_id, &user_id)
             .await?
             .map(PublicUser::from)
             .ok_or_else(|| AppError::NotFound("User not found".to_string()))
     }
 
+    /// Soft-deletes a user; the configured policy decides what happens to their posts
     pub async fn delete_user(
         &self,
         user_id: Uuid,
@@ -169,16 +255,45 @@ where
             return Err(AppError::ForbiddenAccess);
         }
 
-        self.user_repo.delete_user(&user_id, &current_user.id).await
+        self.user_repo
+            .delete_user(&current_user.tenant_id, &user_id, &current_user.id, self.content_policy)
+            .await
     }
 
-    pub async fn me(&self, user_id: Uuid) -> Result<PublicUser, AppError> {
-        self.user_repo.get_user_by_id(&user_id)
+    pub async fn me(&self, tenant_id: Uuid, user_id: Uuid) -> Result<PublicUser, AppError> {
+        self.user_repo.get_user_by_id(&tenant_id, &user_id)
             .await?
             .map(PublicUser::from)
             .ok_or_else(|| AppError::NotFound("User not found".to_string()))
     }
 
+    /// Name and avatar shown as the byline on the user's posts
+    pub async fn update_profile(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        request: UpdateProfileRequest,
+    ) -> Result<PublicUser, AppError> {
+        request.validate()?;
+
+        let user = self.user_repo.update_profile(&tenant_id, &user_id, &request).await?;
+        Ok(PublicUser::from(user))
+    }
+
+    /// Editors may write posts and change their own; only admins touch the rest.
+    /// The user's existing tokens stop being accepted, so the change applies
+    /// from their next sign-in.
+    pub async fn set_editor(&self, tenant_id: Uuid, user_id: Uuid, editor: bool) -> Result<PublicUser, AppError> {
+        let user = self.user_repo.set_editor(&tenant_id, &user_id, editor).await?;
+        tracing::info!(user_id = %user_id, editor, "Editor role changed");
+
+        if let Some(store) = &self.claims_versions {
+            store.bump(&user_id).await?;
+        }
+
+        Ok(PublicUser::from(user))
+    }
+
     pub async fn logout(
         &self, 
         refresh_token: &str, 
@@ -0,0 +1,142 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    entities::backfill::{BackfillProgress, BackfillState, BackfillTask, BackfillsResponse, BACKFILL_PENDING},
+    errors::AppError,
+    metrics::METRICS,
+    repositories::backfill::BackfillRepository,
+};
+
+/// What one pass over the declared backfills got done
+#[derive(Debug, Default)]
+pub struct BackfillPass {
+    /// Batches that ran on this instance
+    pub batches: usize,
+    /// Tasks not completed yet, including ones another instance is running
+    pub remaining: usize,
+    /// Tasks whose batch failed, already recorded on the task
+    pub failures: Vec<(&'static str, AppError)>,
+}
+
+/// Runs the declared backfills a batch at a time and reports their progress.
+///
+/// Each batch commits together with its checkpoint, so an interrupted task
+/// resumes where it stopped after a restart. A failing batch is recorded on
+/// the task and retried on the next pass; other tasks keep going.
+#[derive(Clone)]
+pub struct Backfills<R>
+where
+    R: BackfillRepository,
+{
+    pub backfill_repo: R,
+    tasks: Arc<Vec<BackfillTask>>,
+}
+
+impl<R> Backfills<R>
+where
+    R: BackfillRepository,
+{
+    pub fn new(backfill_repo: R, tasks: Vec<BackfillTask>) -> Self {
+        Backfills { backfill_repo, tasks: Arc::new(tasks) }
+    }
+
+    /// Runs one batch of every task that is not completed yet
+    pub async fn run_pass(&self) -> Result<BackfillPass, AppError> {
+        let states = self.states().await?;
+        let mut pass = BackfillPass::default();
+
+        for task in self.tasks.iter() {
+            if states.get(task.name).is_some_and(BackfillState::is_completed) {
+                continue;
+            }
+
+            match self.backfill_repo.run_batch(task).await {
+                Ok(Some(state)) => {
+                    pass.batches += 1;
+                    METRICS.incr("backfill_batches_total");
+                    if state.is_completed() {
+                        tracing::info!(task = task.name, rows = state.processed, "Backfill completed");
+                    } else {
+                        pass.remaining += 1;
+                        tracing::debug!(task = task.name, rows = state.processed, total = ?state.total, "Backfill batch done");
+                    }
+                }
+                // Another instance holds it
+                Ok(None) => pass.remaining += 1,
+                Err(e) => {
+                    pass.remaining += 1;
+                    METRICS.incr("backfill_failures_total");
+                    if let Err(e) = self.backfill_repo.record_failure(task.name, &e.to_string()).await {
+                        tracing::warn!(task = task.name, "Backfill failure not recorded: {}", e);
+                    }
+                    pass.failures.push((task.name, e));
+                }
+            }
+        }
+
+        Ok(pass)
+    }
+
+    pub async fn progress(&self) -> Result<BackfillsResponse, AppError> {
+        let mut states = self.states().await?;
+
+        let backfills: Vec<BackfillProgress> = self.tasks
+            .iter()
+            .map(|task| progress(task, states.remove(task.name)))
+            .collect();
+        let running = backfills.iter().any(|b| b.percent != Some(100));
+
+        Ok(BackfillsResponse { backfills, running })
+    }
+
+    async fn states(&self) -> Result<HashMap<String, BackfillState>, AppError> {
+        Ok(self.backfill_repo
+            .list_states()
+            .await?
+            .into_iter()
+            .map(|state| (state.name.clone(), state))
+            .collect())
+    }
+}
+
+fn progress(task: &BackfillTask, state: Option<BackfillState>) -> BackfillProgress {
+    let Some(state) = state else {
+        return BackfillProgress {
+            name: task.name.to_string(),
+            description: task.description.to_string(),
+            status: BACKFILL_PENDING.to_string(),
+            processed: 0,
+            total: None,
+            percent: None,
+            batch_size: task.batch_size,
+            last_error: None,
+            started_at: None,
+            completed_at: None,
+            updated_at: None,
+        };
+    };
+
+    let percent = if state.is_completed() {
+        Some(100)
+    } else {
+        // Rows added since the start can push `processed` past `total`
+        state.total.map(|total| match total {
+            0 => 99,
+            total => (state.processed * 100 / total).clamp(0, 99) as u8,
+        })
+    };
+
+    BackfillProgress {
+        name: state.name,
+        description: task.description.to_string(),
+        status: state.status,
+        processed: state.processed,
+        total: state.total,
+        percent,
+        batch_size: task.batch_size,
+        last_error: state.last_error,
+        started_at: state.started_at,
+        completed_at: state.completed_at,
+        updated_at: Some(state.updated_at),
+    }
+}
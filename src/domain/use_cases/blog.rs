@@ -1,95 +1,476 @@
-use crate::{entities::blog_post::{BlogPost, BlogPostCreatedResponse, BlogPostInsert, NewBlogPostRequest, UpdateBlogPostRequest}, errors::AppError, repositories::blog_post::BlogPostRepository, utils::valid_uuid::valid_uuid};
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Months, NaiveTime, TimeZone, Utc};
+use uuid::Uuid;
+use crate::{entities::{blog_post::{normalize_slug, AddPostAuthorRequest, AdminSearchHit, AdminSearchQuery, AdminSearchResponse, AuthoredPost, AuthorPostsResponse, BlogSearchFilter, CalendarDay, CalendarEntry, CalendarQuery, CalendarResponse, CalendarStatus, PostAuthor, PostEditor, PostRevisionDiff, PostRevisionSummary, PostStatus, ReschedulePostRequest, SearchHighlight, SetFeaturedPostsRequest, slug_is_valid, slug_with_suffix, BlogPost, BlogPostCreatedResponse, BlogPostInsert, NewBlogPostRequest, SlugCheckQuery, SlugCheckResponse, UpdateBlogPostRequest}, option_fields::OptionField, series::SeriesNavigation, tenant::Tenant}, errors::AppError, repositories::blog_post::BlogPostRepository, settings::AppConfig, utils::{public_urls::PublicUrls, valid_uuid::valid_uuid}};
 use validator::Validate;
 
+/// How many free numeric-suffix slugs the slug check offers
+const SLUG_ALTERNATIVES: usize = 3;
+/// Upper bound on suffixes probed before giving up
+const MAX_SLUG_SUFFIX: u32 = 20;
+/// Highlight offsets returned per field, so long posts stay cheap to render
+const MAX_HIGHLIGHTS_PER_FIELD: usize = 5;
+
 
 pub struct BlogPostHandler<R>
 where
     R: BlogPostRepository,
 {
     pub blog_post_repo: R,
+    featured_posts_max: usize,
+    urls: PublicUrls,
 }
 
 impl<R> BlogPostHandler<R>
 where
     R: BlogPostRepository,
 {
-    pub fn new(blog_post_repo: R) -> Self {
-        BlogPostHandler { blog_post_repo }
+    pub fn new(blog_post_repo: R, config: &AppConfig) -> Self {
+        BlogPostHandler {
+            blog_post_repo,
+            featured_posts_max: config.featured_posts_max,
+            urls: PublicUrls::from_config(config),
+        }
     }
 
-    /// Creates a new blog post with the provided data
-    pub async fn create_blog_post(&self, post: NewBlogPostRequest) -> Result<BlogPostCreatedResponse, AppError> {
-        let insert_post = BlogPostInsert::try_from(post)?;
+    /// Creates a new blog post credited to `editor`. The preview and admin
+    /// links point at the tenant's site.
+    pub async fn create_blog_post(&self, tenant: &Tenant, editor: PostEditor, post: NewBlogPostRequest) -> Result<BlogPostCreatedResponse, AppError> {
+        let mut insert_post = BlogPostInsert::try_from(post)?;
         insert_post.validate()?;
+        insert_post.author_id = Some(editor.user_id);
         
-        let id = self.blog_post_repo.create_blog_post(&insert_post).await?;
+        let id = self.blog_post_repo.create_blog_post(&tenant.id, &insert_post).await?;
         
         let response = BlogPostCreatedResponse {
             id,
             slug: insert_post.slug.clone(),
-            preview_url: format!("/blog/posts/{}", insert_post.slug.clone()),
-            admin_url: format!("/admin/blog/posts/{}", insert_post.slug),
+            preview_url: self.urls.site(tenant, &format!("/blog/posts/{}", insert_post.slug)),
+            admin_url: self.urls.site(tenant, &format!("/admin/blog/posts/{}", insert_post.slug)),
+            excerpt_generated: insert_post.excerpt_generated,
         };
 
         Ok(response)
     }
 
     /// Retrieves a blog post by its ID
-    pub async fn get_blog_post_by_id(&self, post_id: &str) -> Result<BlogPost, AppError> {
+    pub async fn get_blog_post_by_id(&self, tenant_id: Uuid, post_id: &str) -> Result<AuthoredPost, AppError> {
        let valid_id = valid_uuid(post_id)?;
-        self.blog_post_repo.get_blog_post_by_id(&valid_id).await
+        let post = self.blog_post_repo.get_blog_post_by_id(&tenant_id, &valid_id).await?;
+        with_author(&self.blog_post_repo, tenant_id, post).await
     }
 
     /// Retrieves all blog posts
-    pub async fn get_all_blog_posts(&self, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
-        self.blog_post_repo.get_all_blog_posts(published_only, page, per_page).await
+    pub async fn get_all_blog_posts(&self, tenant_id: Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<AuthoredPost>, AppError> {
+        let posts = self.blog_post_repo.get_all_blog_posts(&tenant_id, published_only, page, per_page).await?;
+        with_authors(&self.blog_post_repo, tenant_id, posts).await
     }
 
     /// Retrieves recent blog posts limited by the specified number
     pub async fn get_recent_blog_posts(
         &self,
+        tenant_id: Uuid,
         limit: u32,
         published_only: bool,
-    ) -> Result<Vec<BlogPost>, AppError> {
-        self.blog_post_repo.get_recent_blog_posts(limit, published_only).await
+    ) -> Result<Vec<AuthoredPost>, AppError> {
+        let posts = self.blog_post_repo.get_recent_blog_posts(&tenant_id, limit, published_only).await?;
+        with_authors(&self.blog_post_repo, tenant_id, posts).await
+    }
+
+    /// Featured posts in homepage order; `published_only` hides featured drafts
+    pub async fn get_featured_posts(&self, tenant_id: Uuid, published_only: bool) -> Result<Vec<AuthoredPost>, AppError> {
+        let posts = self.blog_post_repo.get_featured_posts(&tenant_id, published_only).await?;
+        with_authors(&self.blog_post_repo, tenant_id, posts).await
+    }
+
+    /// Replaces the featured list. Returns the admin view and every post that
+    /// was or now is featured, so their cached pages can be refreshed.
+    pub async fn set_featured_posts(
+        &self,
+        tenant_id: Uuid,
+        request: SetFeaturedPostsRequest,
+    ) -> Result<(Vec<AuthoredPost>, Vec<Uuid>), AppError> {
+        if request.post_ids.len() > self.featured_posts_max {
+            return Err(AppError::InvalidInput(format!(
+                "At most {} posts can be featured",
+                self.featured_posts_max
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        if !request.post_ids.iter().all(|post_id| seen.insert(*post_id)) {
+            return Err(AppError::InvalidInput("A post can only be featured once".to_string()));
+        }
+
+        let before = self.blog_post_repo.get_featured_posts(&tenant_id, false).await?;
+        self.blog_post_repo.set_featured_posts(&tenant_id, &request.post_ids).await?;
+        let featured = self.get_featured_posts(tenant_id, false).await?;
+
+        let mut changed = request.post_ids;
+        changed.extend(before.iter().map(|post| post.id).filter(|id| !seen.contains(id)));
+
+        Ok((featured, changed))
+    }
+
+    /// Published posts of the author with `username`, newest first
+    pub async fn get_author_posts(&self, tenant_id: Uuid, username: &str, page: u32, per_page: u32) -> Result<AuthorPostsResponse, AppError> {
+        let author = self.blog_post_repo
+            .get_author_by_username(&tenant_id, username)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Author not found".to_string()))?;
+
+        let posts = self.blog_post_repo
+            .get_blog_posts_by_author(&tenant_id, &author.id, true, page, per_page)
+            .await?;
+        let posts = with_authors(&self.blog_post_repo, tenant_id, posts).await?;
+
+        Ok(AuthorPostsResponse { author, page, per_page, posts })
     }
 
     /// Updates an existing blog post
     pub async fn update_blog_post(
         &self,
+        tenant_id: Uuid,
+        editor: PostEditor,
         id: &str,
         post: &UpdateBlogPostRequest,
-    ) -> Result<BlogPost, AppError> {
+    ) -> Result<AuthoredPost, AppError> {
         post.validate()?;
 
         let valid_id = valid_uuid(id)?;
+        self.ensure_can_modify(tenant_id, editor, &valid_id).await?;
 
-        self.blog_post_repo.update_blog_post(&valid_id, post).await
+        let updated = self.blog_post_repo.update_blog_post(&tenant_id, &valid_id, post).await?;
+        with_author(&self.blog_post_repo, tenant_id, updated).await
     }
 
     /// Publishes a blog post by its ID
     pub async fn publish_blog_post(
-        &self, 
+        &self,
+        tenant_id: Uuid,
+        editor: PostEditor,
         id: &str
-    ) -> Result<BlogPost, AppError> {
+    ) -> Result<AuthoredPost, AppError> {
         let valid_id = valid_uuid(id)?;
-        self.blog_post_repo.publish_blog_post(&valid_id).await
+        self.ensure_can_modify(tenant_id, editor, &valid_id).await?;
+
+        let published = self.blog_post_repo.publish_blog_post(&tenant_id, &valid_id).await?;
+        with_author(&self.blog_post_repo, tenant_id, published).await
     }
 
     /// Deletes a blog post by its ID
     pub async fn delete_blog_post(
-        &self, 
+        &self,
+        tenant_id: Uuid,
+        editor: PostEditor,
         id: &str,
         hard_delete: bool
     ) -> Result<(), AppError> {
         let valid_id = valid_uuid(id)?;
         
-        match hard_delete {
-            true => self.blog_post_repo.hard_delete_blog_post(&valid_id).await,
-            false => self.blog_post_repo.soft_delete_blog_post(&valid_id).await
-        }.map_err(|e| match e {
+        let deleted = async {
+            self.ensure_can_modify(tenant_id, editor, &valid_id).await?;
+            match hard_delete {
+                true => self.blog_post_repo.hard_delete_blog_post(&tenant_id, &valid_id).await,
+                false => self.blog_post_repo.soft_delete_blog_post(&tenant_id, &valid_id).await
+            }
+        };
+
+        deleted.await.map_err(|e| match e {
+            AppError::NotFound(_) => AppError::NotFound("Blog post not found".to_string()),
+            _ => e
+        })
+    }
+
+    /// Editors may only change posts they wrote. Admins skip the lookup, so
+    /// they can still hard-delete a post that is already in the trash.
+    async fn ensure_can_modify(&self, tenant_id: Uuid, editor: PostEditor, id: &Uuid) -> Result<(), AppError> {
+        if editor.admin {
+            return Ok(());
+        }
+
+        let post = self.blog_post_repo.get_blog_post_by_id(&tenant_id, id).await?;
+        if !editor.can_modify(&post) {
+            tracing::warn!(post_id = %id, user_id = %editor.user_id, "Editor tried to change another author's post");
+            return Err(AppError::ForbiddenAccess);
+        }
+
+        Ok(())
+    }
+
+    /// Drafts, scheduled and published posts of one month, grouped by UTC date
+    pub async fn get_calendar(&self, tenant_id: Uuid, query: CalendarQuery) -> Result<CalendarResponse, AppError> {
+        query.validate()?;
+
+        let first_day = query
+            .first_day()
+            .ok_or_else(|| AppError::InvalidInput("Month must look like 2024-07".to_string()))?;
+        let next_month = first_day
+            .checked_add_months(Months::new(1))
+            .ok_or_else(|| AppError::InvalidInput("Month is out of range".to_string()))?;
+        let from = Utc.from_utc_datetime(&first_day.and_time(NaiveTime::MIN));
+        let to = Utc.from_utc_datetime(&next_month.and_time(NaiveTime::MIN));
+
+        let posts = self.blog_post_repo.get_calendar_posts(&tenant_id, from, to).await?;
+
+        let now = Utc::now();
+        let mut days: Vec<CalendarDay> = Vec::new();
+        for post in posts {
+            let date = post.published_at.unwrap_or(post.updated_at).date_naive();
+            let entry = CalendarEntry {
+                status: CalendarStatus::of(&post, now),
+                id: post.id,
+                title: post.title,
+                slug: post.slug,
+                published_at: post.published_at,
+                updated_at: post.updated_at,
+            };
+
+            // Posts arrive in calendar order, so each date is one run
+            match days.last_mut() {
+                Some(day) if day.date == date => day.posts.push(entry),
+                _ => days.push(CalendarDay { date, posts: vec![entry] }),
+            }
+        }
+
+        Ok(CalendarResponse { month: first_day.format("%Y-%m").to_string(), days })
+    }
+
+    /// Moves a post that has not gone live yet to a new future publish time.
+    /// Posts already live stay put; unpublish them first.
+    pub async fn reschedule_post(
+        &self,
+        tenant_id: Uuid,
+        id: &str,
+        request: ReschedulePostRequest,
+    ) -> Result<AuthoredPost, AppError> {
+        request.validate()?;
+
+        let valid_id = valid_uuid(id)?;
+        let post = self.blog_post_repo.get_blog_post_by_id(&tenant_id, &valid_id).await?;
+        if CalendarStatus::of(&post, Utc::now()) == CalendarStatus::Published {
+            return Err(AppError::Conflict("Post is already live and cannot be rescheduled".to_string()));
+        }
+
+        let change = UpdateBlogPostRequest {
+            published_at: OptionField::SetToValue(request.published_at),
+            ..Default::default()
+        };
+        let updated = self.blog_post_repo.update_blog_post(&tenant_id, &valid_id, &change).await?;
+        with_author(&self.blog_post_repo, tenant_id, updated).await
+    }
+
+    /// Credits a user on a post at the requested position, moving them if
+    /// they are already credited
+    pub async fn add_post_author(&self, tenant_id: Uuid, post_id: &str, request: AddPostAuthorRequest) -> Result<AuthoredPost, AppError> {
+        let post = self.post_for_bylines(tenant_id, post_id).await?;
+
+        let known = self.blog_post_repo.get_post_authors(&tenant_id, &[request.user_id]).await?;
+        if known.is_empty() {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+
+        let mut user_ids = self.byline_ids(tenant_id, &post.id).await?;
+        user_ids.retain(|id| *id != request.user_id);
+        let position = request.position.unwrap_or(user_ids.len()).min(user_ids.len());
+        user_ids.insert(position, request.user_id);
+
+        self.blog_post_repo.set_post_authors(&tenant_id, &post.id, &user_ids).await?;
+        with_author(&self.blog_post_repo, tenant_id, post).await
+    }
+
+    /// Drops a user from a post's bylines; the last byline cannot be removed
+    pub async fn remove_post_author(&self, tenant_id: Uuid, post_id: &str, user_id: Uuid) -> Result<AuthoredPost, AppError> {
+        let post = self.post_for_bylines(tenant_id, post_id).await?;
+
+        let mut user_ids = self.byline_ids(tenant_id, &post.id).await?;
+        if !user_ids.contains(&user_id) {
+            return Err(AppError::NotFound("Author not credited on this post".to_string()));
+        }
+        if user_ids.len() == 1 {
+            return Err(AppError::Conflict("A post keeps at least one author".to_string()));
+        }
+        user_ids.retain(|id| *id != user_id);
+
+        self.blog_post_repo.set_post_authors(&tenant_id, &post.id, &user_ids).await?;
+        with_author(&self.blog_post_repo, tenant_id, post).await
+    }
+
+    async fn post_for_bylines(&self, tenant_id: Uuid, post_id: &str) -> Result<BlogPost, AppError> {
+        let valid_id = valid_uuid(post_id)?;
+        self.blog_post_repo.get_blog_post_by_id(&tenant_id, &valid_id).await.map_err(|e| match e {
             AppError::NotFound(_) => AppError::NotFound("Blog post not found".to_string()),
             _ => e
         })
     }
-} 
\ No newline at end of file
+
+    async fn byline_ids(&self, tenant_id: Uuid, post_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
+        Ok(self.blog_post_repo
+            .get_post_bylines(&tenant_id, &[*post_id])
+            .await?
+            .into_iter()
+            .map(|byline| byline.author.id)
+            .collect())
+    }
+
+    /// A post's revisions, newest first
+    pub async fn list_revisions(&self, tenant_id: Uuid, post_id: &str) -> Result<Vec<PostRevisionSummary>, AppError> {
+        let valid_id = valid_uuid(post_id)?;
+        let revisions = self.blog_post_repo.list_post_revisions(&tenant_id, &valid_id).await?;
+        if revisions.is_empty() {
+            return Err(AppError::NotFound("Blog post not found".to_string()));
+        }
+
+        Ok(revisions)
+    }
+
+    /// Markdown and metadata changes going from revision `from` to `to`
+    pub async fn diff_revisions(&self, tenant_id: Uuid, post_id: &str, from: i32, to: i32) -> Result<PostRevisionDiff, AppError> {
+        let valid_id = valid_uuid(post_id)?;
+
+        let mut revisions = Vec::with_capacity(2);
+        for revision in [from, to] {
+            let found = self.blog_post_repo
+                .get_post_revision(&tenant_id, &valid_id, revision)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Revision {} not found", revision)))?;
+            revisions.push(found);
+        }
+
+        Ok(PostRevisionDiff::between(&revisions[0], &revisions[1]))
+    }
+
+    /// Checks a slug before submit and proposes alternatives when it is taken
+    pub async fn check_slug(&self, tenant_id: Uuid, query: SlugCheckQuery) -> Result<SlugCheckResponse, AppError> {
+        query.validate()?;
+
+        let suggestion = query.title.as_deref().and_then(normalize_slug);
+        let slug = match query.slug.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(slug) => slug.to_string(),
+            None => suggestion.clone().ok_or_else(|| {
+                AppError::InvalidInput("Provide a slug or a title long enough to derive one".to_string())
+            })?,
+        };
+
+        let valid = slug_is_valid(&slug);
+        let available = valid
+            && !self.blog_post_repo.blog_post_exists_with_slug(&tenant_id, &slug, query.exclude_id).await?;
+
+        let mut alternatives = Vec::new();
+        if let Some(base) = normalize_slug(&slug).filter(|_| !available) {
+            // An invalid slug's normalized form is itself worth offering
+            let normalized = (!valid && base != slug).then(|| base.clone());
+            let suffixed = (2..=MAX_SLUG_SUFFIX).map(|n| slug_with_suffix(&base, n));
+            for candidate in normalized.into_iter().chain(suffixed) {
+                if !self.blog_post_repo.blog_post_exists_with_slug(&tenant_id, &candidate, query.exclude_id).await? {
+                    alternatives.push(candidate);
+                    if alternatives.len() == SLUG_ALTERNATIVES {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(SlugCheckResponse { slug, valid, available, suggestion, alternatives })
+    }
+
+    /// Admin search across drafts, published and trashed posts, with match offsets
+    pub async fn admin_search(&self, tenant_id: Uuid, query: AdminSearchQuery) -> Result<AdminSearchResponse, AppError> {
+        query.validate()?;
+
+        if matches!((query.from, query.to), (Some(from), Some(to)) if from > to) {
+            return Err(AppError::InvalidInput("`from` must not be after `to`".to_string()));
+        }
+
+        let filter = BlogSearchFilter {
+            query: query.q.trim().to_string(),
+            status: query.status,
+            include_deleted: query.include_deleted,
+            tag: query.tag,
+            created_from: query.from,
+            created_to: query.to,
+            limit: query.limit.unwrap_or(20) as i64,
+        };
+
+        let posts = self.blog_post_repo.admin_search_blog_posts(&tenant_id, &filter).await?;
+
+        let results = posts
+            .into_iter()
+            .map(|post| {
+                let mut highlights = highlight_offsets("title", &post.title, &filter.query);
+                highlights.extend(highlight_offsets("excerpt", &post.excerpt, &filter.query));
+                highlights.extend(highlight_offsets("content_markdown", &post.content_markdown, &filter.query));
+
+                AdminSearchHit {
+                    status: PostStatus::of(&post),
+                    id: post.id,
+                    title: post.title,
+                    slug: post.slug,
+                    excerpt: post.excerpt,
+                    tags: post.tags,
+                    published_at: post.published_at,
+                    created_at: post.created_at,
+                    deleted_at: post.deleted_at,
+                    highlights,
+                }
+            })
+            .collect();
+
+        Ok(AdminSearchResponse { query: filter.query, results })
+    }
+}
+
+/// Pairs each post with its bylines, in one lookup
+pub async fn with_authors<R: BlogPostRepository>(repo: &R, tenant_id: Uuid, posts: Vec<BlogPost>) -> Result<Vec<AuthoredPost>, AppError> {
+    let post_ids: Vec<Uuid> = posts.iter().map(|post| post.id).collect();
+
+    let mut bylines: HashMap<Uuid, Vec<PostAuthor>> = HashMap::new();
+    if !post_ids.is_empty() {
+        for byline in repo.get_post_bylines(&tenant_id, &post_ids).await? {
+            bylines.entry(byline.post_id).or_default().push(byline.author);
+        }
+    }
+
+    Ok(posts
+        .into_iter()
+        .map(|post| {
+            let authors = bylines.remove(&post.id).unwrap_or_default();
+            AuthoredPost { author: authors.first().cloned(), authors, series: None, title_variant: None, post }
+        })
+        .collect())
+}
+
+/// A single post with its bylines and its place in its series
+pub async fn with_author<R: BlogPostRepository>(repo: &R, tenant_id: Uuid, post: BlogPost) -> Result<AuthoredPost, AppError> {
+    let post_series = repo.get_post_series(&tenant_id, &post.id).await?;
+
+    let mut authored = with_authors(repo, tenant_id, vec![post]).await?;
+    let mut authored = authored.pop().ok_or_else(|| AppError::InternalError("Post lost while attaching its authors".to_string()))?;
+    authored.series = post_series.and_then(|post_series| SeriesNavigation::for_post(&post_series, authored.post.id));
+
+    Ok(authored)
+}
+
+/// Case-insensitive, non-overlapping matches of `needle` in `text`, as char offsets
+fn highlight_offsets(field: &'static str, text: &str, needle: &str) -> Vec<SearchHighlight> {
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let haystack: Vec<char> = text.chars().map(fold).collect();
+    let needle: Vec<char> = needle.chars().map(fold).collect();
+
+    let mut highlights = Vec::new();
+    if needle.is_empty() {
+        return highlights;
+    }
+
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() && highlights.len() < MAX_HIGHLIGHTS_PER_FIELD {
+        if haystack[start..start + needle.len()] == needle[..] {
+            highlights.push(SearchHighlight { field, start, end: start + needle.len() });
+            start += needle.len();
+        } else {
+            start += 1;
+        }
+    }
+    highlights
+}
\ No newline at end of file
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use futures::stream::{self, StreamExt};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        bookmark::{
+            normalize_tags, Bookmark, BookmarkInsert, BookmarkListResponse, BookmarkQuery, NewBookmarkRequest,
+            UpdateBookmarkRequest,
+        },
+        option_fields::OptionField,
+    },
+    errors::AppError,
+    links::preview::LinkPreviewFetcher,
+    metrics::METRICS,
+    repositories::bookmark::BookmarkRepository,
+    utils::valid_uuid::valid_uuid,
+};
+
+/// Pending previews picked up per background run
+pub const PREVIEW_BATCH_SIZE: i64 = 50;
+/// Previews fetched at once by a background run
+const PREVIEW_CONCURRENCY: usize = 4;
+
+/// Counts from one background run
+#[derive(Debug, Default)]
+pub struct PreviewRun {
+    pub ready: usize,
+    pub failed: usize,
+}
+
+/// Saved links for the public blogroll.
+///
+/// New bookmarks start with a pending preview; the background task (or the
+/// request that created them) fetches the page and stores its title,
+/// description and image. Failed fetches are retried on later runs up to
+/// `MAX_PREVIEW_ATTEMPTS`.
+#[derive(Clone)]
+pub struct BookmarkHandler<R>
+where
+    R: BookmarkRepository,
+{
+    pub bookmark_repo: R,
+    fetcher: Arc<dyn LinkPreviewFetcher>,
+}
+
+impl<R> BookmarkHandler<R>
+where
+    R: BookmarkRepository,
+{
+    pub fn new(bookmark_repo: R, fetcher: Arc<dyn LinkPreviewFetcher>) -> Self {
+        BookmarkHandler { bookmark_repo, fetcher }
+    }
+
+    /// The blogroll, or with `public_only` off every bookmark for the admin list
+    pub async fn list_bookmarks(
+        &self,
+        tenant_id: Uuid,
+        query: BookmarkQuery,
+        public_only: bool,
+    ) -> Result<BookmarkListResponse, AppError> {
+        query.validate()?;
+
+        let page = query.page.unwrap_or(1);
+        let per_page = query.per_page.unwrap_or(20);
+        let tag = query.tag.map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+        let offset = (page as i64 - 1) * per_page as i64;
+
+        let bookmarks = self.bookmark_repo
+            .list_bookmarks(&tenant_id, public_only, tag.clone(), per_page as i64, offset)
+            .await?;
+        let total = self.bookmark_repo.count_bookmarks(&tenant_id, public_only, tag).await?;
+
+        Ok(BookmarkListResponse { bookmarks, total, page, per_page })
+    }
+
+    pub async fn create_bookmark(&self, tenant_id: Uuid, request: NewBookmarkRequest) -> Result<Bookmark, AppError> {
+        request.validate()?;
+
+        self.bookmark_repo
+            .create_bookmark(&tenant_id, &BookmarkInsert::from(request))
+            .await
+    }
+
+    /// A changed URL discards the old preview and queues a new fetch,
+    /// reported with `true`
+    pub async fn update_bookmark(
+        &self,
+        tenant_id: Uuid,
+        id: &str,
+        request: UpdateBookmarkRequest,
+    ) -> Result<(Bookmark, bool), AppError> {
+        request.validate()?;
+        let valid_id = valid_uuid(id)?;
+
+        let mut bookmark = self.bookmark_repo.get_bookmark(&tenant_id, &valid_id).await?;
+        let mut url_changed = false;
+        if let Some(url) = request.url {
+            let url = url.trim().to_string();
+            url_changed = url != bookmark.url;
+            bookmark.url = url;
+        }
+        if let Some(title) = request.title.into_option() {
+            bookmark.title = title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        }
+        if let Some(note) = request.note.into_option() {
+            bookmark.note = note.filter(|n| !n.trim().is_empty());
+        }
+        match request.tags {
+            OptionField::SetToValue(tags) => bookmark.tags = normalize_tags(tags),
+            OptionField::SetToNull => bookmark.tags.clear(),
+            OptionField::Unchanged => {}
+        }
+        if let Some(is_public) = request.is_public {
+            bookmark.is_public = is_public;
+        }
+
+        let updated = self.bookmark_repo.update_bookmark(&tenant_id, &bookmark).await?;
+        if url_changed {
+            return Ok((self.bookmark_repo.reset_preview(&tenant_id, &updated.id).await?, true));
+        }
+
+        Ok((updated, false))
+    }
+
+    pub async fn delete_bookmark(&self, tenant_id: Uuid, id: &str) -> Result<(), AppError> {
+        let valid_id = valid_uuid(id)?;
+
+        self.bookmark_repo.delete_bookmark(&tenant_id, &valid_id).await
+    }
+
+    /// Clears the preview and resets the attempt count, e.g. after a page was fixed
+    pub async fn reset_preview(&self, tenant_id: Uuid, id: &str) -> Result<Bookmark, AppError> {
+        let valid_id = valid_uuid(id)?;
+
+        self.bookmark_repo.reset_preview(&tenant_id, &valid_id).await
+    }
+
+    /// Fetches and stores one bookmark's preview; `false` when the fetch failed
+    pub async fn fetch_preview(&self, bookmark: &Bookmark) -> Result<bool, AppError> {
+        match self.fetcher.fetch(&bookmark.url).await {
+            Ok(preview) => {
+                self.bookmark_repo.record_preview(&bookmark.id, &preview).await?;
+                METRICS.incr("bookmark_previews_fetched_total");
+                Ok(true)
+            }
+            Err(e) => {
+                tracing::debug!(bookmark = %bookmark.id, "Link preview failed: {}", e);
+                self.bookmark_repo.record_preview_failure(&bookmark.id, &e.to_string()).await?;
+                METRICS.incr("bookmark_previews_failed_total");
+                Ok(false)
+            }
+        }
+    }
+
+    /// Fetches up to `limit` pending previews across tenants
+    pub async fn fetch_pending_previews(&self, limit: i64) -> Result<PreviewRun, AppError>
+    where
+        R: Clone,
+    {
+        let pending = self.bookmark_repo.list_pending_previews(limit).await?;
+
+        // Owned handler clones keep the futures free of borrows, so the run
+        // can be spawned onto the runtime
+        let results: Vec<Result<bool, AppError>> = stream::iter(pending)
+            .map(|bookmark| {
+                let handler = self.clone();
+                async move { handler.fetch_preview(&bookmark).await }
+            })
+            .buffer_unordered(PREVIEW_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut run = PreviewRun::default();
+        for result in results {
+            if result? {
+                run.ready += 1;
+            } else {
+                run.failed += 1;
+            }
+        }
+
+        Ok(run)
+    }
+}
\ No newline at end of file
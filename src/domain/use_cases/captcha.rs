@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use actix_web::HttpRequest;
+use uuid::Uuid;
+
+use crate::{
+    captcha::verifier::CaptchaVerifier,
+    errors::AppError,
+    metrics::METRICS,
+    repositories::feature_flag::FeatureFlagRepository,
+    use_cases::feature_flags::FeatureFlags,
+    utils::get_client_ip::{get_client_ip, TrustedProxies},
+};
+
+/// Header carrying the token the captcha widget produced
+pub const CAPTCHA_TOKEN_HEADER: &str = "x-captcha-token";
+
+/// Public forms a captcha can guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaForm {
+    Register,
+    Contact,
+    Guestbook,
+}
+
+impl CaptchaForm {
+    /// Feature flag that turns the check on for this form, per tenant
+    pub fn flag_key(self) -> &'static str {
+        match self {
+            CaptchaForm::Register => "captcha_register",
+            CaptchaForm::Contact => "captcha_contact",
+            CaptchaForm::Guestbook => "captcha_guestbook",
+        }
+    }
+}
+
+/// Checks captcha tokens on public forms.
+///
+/// A form is guarded when a provider is configured and the form's feature
+/// flag is on. A missing or rejected token is a 400; when the provider
+/// cannot be reached the request is refused rather than let through.
+#[derive(Clone)]
+pub struct CaptchaGuard<F>
+where
+    F: FeatureFlagRepository,
+{
+    verifier: Option<Arc<dyn CaptchaVerifier>>,
+    flags: FeatureFlags<F>,
+}
+
+impl<F> CaptchaGuard<F>
+where
+    F: FeatureFlagRepository,
+{
+    pub fn new(verifier: Option<Arc<dyn CaptchaVerifier>>, flags: FeatureFlags<F>) -> Self {
+        CaptchaGuard { verifier, flags }
+    }
+
+    pub fn is_required(&self, tenant_id: &Uuid, form: CaptchaForm) -> bool {
+        self.verifier.is_some() && self.flags.is_enabled(tenant_id, form.flag_key())
+    }
+
+    pub async fn check(
+        &self,
+        tenant_id: &Uuid,
+        form: CaptchaForm,
+        token: Option<&str>,
+        remote_ip: &str,
+    ) -> Result<(), AppError> {
+        let Some(verifier) = self.verifier.as_ref().filter(|_| self.is_required(tenant_id, form)) else {
+            return Ok(());
+        };
+
+        let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+            METRICS.incr("captcha_missing_total");
+            return Err(AppError::InvalidInput("Captcha is required".to_string()));
+        };
+
+        if verifier.verify(token, remote_ip).await? {
+            METRICS.incr("captcha_passed_total");
+            Ok(())
+        } else {
+            METRICS.incr("captcha_failed_total");
+            Err(AppError::InvalidInput("Captcha verification failed".to_string()))
+        }
+    }
+
+    /// `check` with the token from `X-Captcha-Token` and the client address
+    pub async fn check_request(
+        &self,
+        tenant_id: &Uuid,
+        form: CaptchaForm,
+        req: &HttpRequest,
+        trusted: &TrustedProxies,
+    ) -> Result<(), AppError> {
+        let token = req.headers().get(CAPTCHA_TOKEN_HEADER).and_then(|v| v.to_str().ok());
+        self.check(tenant_id, form, token, &get_client_ip(req, trusted)).await
+    }
+}
\ No newline at end of file
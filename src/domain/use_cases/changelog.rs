@@ -0,0 +1,137 @@
+use uuid::Uuid;
+use validator::Validate;
+use zeroize::Zeroizing;
+
+use crate::{
+    auth::site_gate::constant_time_eq,
+    entities::changelog::{
+        ChangelogEntryInsert, ChangelogEntryResponse, ChangelogListResponse, ChangelogQuery, DeployHookRequest,
+        NewChangelogEntryRequest, UpdateChangelogEntryRequest,
+    },
+    errors::AppError,
+    repositories::changelog::ChangelogRepository,
+    settings::AppConfig,
+    utils::valid_uuid::valid_uuid,
+};
+
+pub struct ChangelogHandler<R>
+where
+    R: ChangelogRepository,
+{
+    pub changelog_repo: R,
+    deploy_hook_token: Option<Zeroizing<String>>,
+}
+
+impl<R> ChangelogHandler<R>
+where
+    R: ChangelogRepository,
+{
+    pub fn new(changelog_repo: R, config: &AppConfig) -> Self {
+        ChangelogHandler {
+            changelog_repo,
+            deploy_hook_token: config.deploy_hook_token.clone().map(Zeroizing::new),
+        }
+    }
+
+    pub async fn list_entries(&self, tenant_id: Uuid, query: ChangelogQuery) -> Result<ChangelogListResponse, AppError> {
+        query.validate()?;
+
+        let page = query.page.unwrap_or(1);
+        let per_page = query.per_page.unwrap_or(20);
+        let category = query.category.map(|c| c.as_str().to_string());
+        let offset = (page as i64 - 1) * per_page as i64;
+
+        let entries = self.changelog_repo
+            .list_entries(&tenant_id, category.clone(), per_page as i64, offset)
+            .await?;
+        let total = self.changelog_repo.count_entries(&tenant_id, category).await?;
+
+        Ok(ChangelogListResponse {
+            entries: entries.into_iter().map(Into::into).collect(),
+            total,
+            page,
+            per_page,
+        })
+    }
+
+    pub async fn create_entry(
+        &self,
+        tenant_id: Uuid,
+        request: NewChangelogEntryRequest,
+    ) -> Result<ChangelogEntryResponse, AppError> {
+        request.validate()?;
+
+        let entry = self.changelog_repo
+            .create_entry(&tenant_id, &ChangelogEntryInsert::from(request))
+            .await?;
+
+        Ok(entry.into())
+    }
+
+    pub async fn update_entry(
+        &self,
+        tenant_id: Uuid,
+        id: &str,
+        request: UpdateChangelogEntryRequest,
+    ) -> Result<ChangelogEntryResponse, AppError> {
+        request.validate()?;
+        let valid_id = valid_uuid(id)?;
+
+        let mut entry = self.changelog_repo.get_entry(&tenant_id, &valid_id).await?;
+        if let Some(version) = request.version {
+            entry.version = version.trim().to_string();
+        }
+        if let Some(released_on) = request.released_on {
+            entry.released_on = released_on;
+        }
+        if let Some(notes) = request.notes_markdown {
+            entry.notes_markdown = notes;
+        }
+        if let Some(category) = request.category {
+            entry.category = category.as_str().to_string();
+        }
+
+        Ok(self.changelog_repo.update_entry(&tenant_id, &entry).await?.into())
+    }
+
+    pub async fn delete_entry(&self, tenant_id: Uuid, id: &str) -> Result<(), AppError> {
+        let valid_id = valid_uuid(id)?;
+
+        self.changelog_repo.delete_entry(&tenant_id, &valid_id).await
+    }
+
+    /// Hook is disabled (404) without a configured token; a wrong token is a 401
+    pub fn verify_deploy_token(&self, presented: Option<&str>) -> Result<(), AppError> {
+        let Some(expected) = &self.deploy_hook_token else {
+            return Err(AppError::NotFound("Not found".to_string()));
+        };
+
+        match presented {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+            _ => Err(AppError::UnauthorizedAccess),
+        }
+    }
+
+    /// Records a deploy as a changelog entry. Redelivered hooks for a version
+    /// that is already listed return the existing entry with `false`.
+    pub async fn record_deploy(
+        &self,
+        tenant_id: Uuid,
+        request: DeployHookRequest,
+    ) -> Result<(ChangelogEntryResponse, bool), AppError> {
+        request.validate()?;
+
+        if let Some(existing) = self.changelog_repo
+            .get_entry_by_version(&tenant_id, request.version.trim())
+            .await?
+        {
+            return Ok((existing.into(), false));
+        }
+
+        let entry = self.changelog_repo
+            .create_entry(&tenant_id, &ChangelogEntryInsert::from(request))
+            .await?;
+
+        Ok((entry.into(), true))
+    }
+}
\ No newline at end of file
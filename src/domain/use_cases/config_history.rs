@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        app_setting::AppSetting,
+        config_change::{
+            ConfigChange, ConfigChangeSource, ConfigHistoryPage, ConfigHistoryQuery, ConfigRollbackResponse,
+            CONFIG_KIND_FEATURE_FLAG, CONFIG_KIND_SETTING,
+        },
+        feature_flag::{FeatureFlag, UpdateFeatureFlagRequest},
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{app_settings::AppSettingsRepository, config_history::ConfigHistoryRepository, feature_flag::FeatureFlagRepository},
+    use_cases::{
+        feature_flags::{check_flag, FeatureFlags},
+        settings::{check_setting, RuntimeSettings},
+    },
+};
+
+/// Admin changes to runtime settings and feature flags, versioned.
+///
+/// Every change is stored with the value it replaced and who made it, so
+/// the history can be read back and the configuration rolled back to any
+/// earlier version in one call. A rollback is itself a set of changes and
+/// can be rolled back in turn. Internal writes (`RuntimeSettings::set`)
+/// are bookkeeping and stay out of the history.
+#[derive(Clone)]
+pub struct ConfigHistory<H, S, F>
+where
+    H: ConfigHistoryRepository,
+    S: AppSettingsRepository,
+    F: FeatureFlagRepository,
+{
+    pub history_repo: H,
+    settings: RuntimeSettings<S>,
+    flags: FeatureFlags<F>,
+}
+
+impl<H, S, F> ConfigHistory<H, S, F>
+where
+    H: ConfigHistoryRepository,
+    S: AppSettingsRepository,
+    F: FeatureFlagRepository,
+{
+    pub fn new(history_repo: H, settings: RuntimeSettings<S>, flags: FeatureFlags<F>) -> Self {
+        ConfigHistory { history_repo, settings, flags }
+    }
+
+    pub async fn update_setting(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Uuid,
+        key: &str,
+        value: JsonValue,
+    ) -> Result<AppSetting, AppError> {
+        check_setting(key, &value)?;
+
+        let source = ConfigChangeSource { actor_id, rollback_to: None };
+        let change = self.history_repo.change_setting(&tenant_id, key, Some(value.clone()), &source).await?;
+
+        self.settings.apply_cached(tenant_id, key, Some(value.clone()));
+        Ok(AppSetting { tenant_id, key: change.key, value, updated_at: change.changed_at })
+    }
+
+    pub async fn set_flag(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Uuid,
+        key: &str,
+        request: UpdateFeatureFlagRequest,
+    ) -> Result<FeatureFlag, AppError> {
+        check_flag(key, &request)?;
+
+        let source = ConfigChangeSource { actor_id, rollback_to: None };
+        let (flag, _) = self.history_repo
+            .change_feature_flag(&tenant_id, key, request.enabled, request.description, &source)
+            .await?;
+
+        self.flags.apply_cached(tenant_id, &flag.key, flag.enabled);
+        Ok(flag)
+    }
+
+    pub async fn history(&self, tenant_id: Uuid, query: ConfigHistoryQuery) -> Result<ConfigHistoryPage, AppError> {
+        query.validate()?;
+
+        let limit = query.limit.unwrap_or(50) as usize;
+        // One extra row tells whether there is another page
+        let mut changes = self.history_repo
+            .list_changes(&tenant_id, query.key, query.before, limit as i64 + 1)
+            .await?;
+
+        let next_before = if changes.len() > limit {
+            changes.truncate(limit);
+            changes.last().map(|c| c.version)
+        } else {
+            None
+        };
+
+        Ok(ConfigHistoryPage { changes, next_before })
+    }
+
+    /// Puts every setting and flag of the tenant changed after `version`
+    /// back to the value it had right after it
+    pub async fn rollback(&self, tenant_id: Uuid, actor_id: Uuid, version: i64) -> Result<ConfigRollbackResponse, AppError> {
+        self.history_repo.get_change(&tenant_id, version).await?;
+
+        let later = self.history_repo.changes_after(&tenant_id, version).await?;
+        let source = ConfigChangeSource { actor_id, rollback_to: Some(version) };
+
+        let mut applied = Vec::new();
+        for (kind, key, target) in rollback_targets(&later) {
+            let change = match kind.as_str() {
+                CONFIG_KIND_SETTING => {
+                    let change = self.history_repo.change_setting(&tenant_id, &key, target, &source).await?;
+                    self.settings.apply_cached(tenant_id, &key, change.new_value.clone());
+                    change
+                }
+                CONFIG_KIND_FEATURE_FLAG => {
+                    // A flag that did not exist yet is unknown, and unknown flags are off
+                    let enabled = target.and_then(|v| v.as_bool()).unwrap_or(false);
+                    let (flag, change) = self.history_repo.change_feature_flag(&tenant_id, &key, enabled, None, &source).await?;
+                    self.flags.apply_cached(tenant_id, &flag.key, flag.enabled);
+                    change
+                }
+                _ => continue,
+            };
+            applied.push(change);
+        }
+
+        METRICS.incr("config_rollbacks_total");
+        tracing::info!(version, actor = %actor_id, changes = applied.len(), "Configuration rolled back");
+
+        Ok(ConfigRollbackResponse { rolled_back_to: version, changes: applied })
+    }
+}
+
+/// For each key changed in `later` (oldest first), the value it had before
+/// the first of those changes, skipping keys that are already back to it
+fn rollback_targets(later: &[ConfigChange]) -> Vec<(String, String, Option<JsonValue>)> {
+    let mut seen = HashSet::new();
+    let mut targets = Vec::new();
+
+    for change in later {
+        if !seen.insert((change.kind.as_str(), change.key.as_str())) {
+            continue;
+        }
+        let current = later
+            .iter()
+            .rev()
+            .find(|c| c.kind == change.kind && c.key == change.key)
+            .and_then(|c| c.new_value.clone());
+        if effective(&change.kind, current) != effective(&change.kind, change.previous_value.clone()) {
+            targets.push((change.kind.clone(), change.key.clone(), change.previous_value.clone()));
+        }
+    }
+
+    targets
+}
+
+/// What a reader sees for the value: a missing flag is an off one
+fn effective(kind: &str, value: Option<JsonValue>) -> Option<JsonValue> {
+    match kind {
+        CONFIG_KIND_FEATURE_FLAG => Some(value.unwrap_or(JsonValue::Bool(false))),
+        _ => value,
+    }
+}
@@ -1,37 +1,76 @@
+use actix_web::web::Bytes;
+use futures::{stream, Stream};
+use uuid::Uuid;
+
 use crate::{
-    entities::contact_me::{ContactMeListResponse, ContactMeMessage, ContactMeResponse, NewContactMeForm}, 
+    crypto::field_cipher::FieldCipher,
+    entities::{
+        contact_me::{
+            ContactMeCursor, ContactMeExportQuery, ContactMeFormInsert, ContactMeListResponse, ContactMeMessage, ContactMeResponse,
+            ContactRekeyReport, ContactSecret, ContactSecretRewrite, NewContactMeForm,
+        },
+        pii::Pii,
+    },
     errors::AppError, 
-    repositories::contact_me::ContactMeRepository, utils::valid_uuid::valid_uuid
+    geo::geoip::GeoLocator,
+    metrics::METRICS,
+    repositories::contact_me::ContactMeRepository, 
+    use_cases::ingest::{ContactIngestQueue, QueuedContact},
+    utils::{csv_export::encode_csv_chunk, valid_uuid::valid_uuid},
 };
 use validator::Validate;
 
+/// Rows fetched per round trip when streaming the CSV export
+const EXPORT_CHUNK_SIZE: i64 = 500;
+
+/// Rows re-sealed per round trip by `--rekey-contact-messages`
+pub const REKEY_BATCH_SIZE: i64 = 200;
+
+/// Shown in place of a sealed field no configured key opens
+pub const UNREADABLE_FIELD: &str = "[unreadable]";
 
+const EXPORT_CSV_HEADER: [&str; 9] = [
+    "id", "created_at", "name", "email", "subject", "message", "is_spam", "country_code", "region",
+];
 
+
+#[derive(Clone)]
 pub struct ContactMeHandler<R>
 where 
     R: ContactMeRepository,
 {
     pub contact_repo: R,
+    pub queue: ContactIngestQueue,
+    geo: GeoLocator,
+    cipher: FieldCipher,
 }
 
 impl<R> ContactMeHandler<R>
 where 
     R: ContactMeRepository,
 {
-    pub fn new(contact_repo: R) -> Self {
-        ContactMeHandler { contact_repo }
+    pub fn new(contact_repo: R, queue: ContactIngestQueue, geo: GeoLocator, cipher: FieldCipher) -> Self {
+        ContactMeHandler { contact_repo, queue, geo, cipher }
     }
 
-    /// Handles the creation of a new contact message
-    pub async fn create_contact_message(
-        &self, 
-        request: NewContactMeForm
+    /// Validates a new contact message and queues it for the writer task.
+    /// `TooManyRequests` when the queue is full.
+    pub fn create_contact_message(
+        &self,
+        tenant_id: Uuid,
+        request: NewContactMeForm,
+        client_ip: &str,
     ) -> Result<ContactMeResponse, AppError> {
         request.validate()?;
 
-        let new_msg = request.try_into()?;
+        let mut new_msg: ContactMeFormInsert = request.try_into()?;
+        if let Some(location) = self.geo.lookup(client_ip) {
+            new_msg.country_code = location.country_code;
+            new_msg.region = location.region;
+        }
 
-        let id = self.contact_repo.create_contact_message(&new_msg).await?;
+        let id = new_msg.id;
+        self.queue.push(tenant_id, new_msg)?;
 
         Ok(ContactMeResponse {
             message: "Your message has been received.".to_string(),
@@ -39,19 +78,40 @@ where
         })
     }
 
+    /// Seals a message taken off the queue and stores it
+    pub async fn write_queued(&self, mut job: QueuedContact) -> Result<Uuid, AppError> {
+        self.queue.record_depth();
+
+        let result = match seal_contact_message(&self.cipher, &mut job.message) {
+            Ok(()) => self.contact_repo.create_contact_message(&job.tenant_id, &job.message).await,
+            Err(e) => Err(e),
+        };
+        match &result {
+            Ok(_) => METRICS.incr("contact_queue_written_total"),
+            Err(_) => METRICS.incr("contact_queue_write_failures_total"),
+        }
+
+        result
+    }
+
     /// Retrieves a contact message by its ID
-    pub async fn get_contact_message_by_id(&self, id: &str) -> Result<ContactMeMessage, AppError> {
+    pub async fn get_contact_message_by_id(&self, tenant_id: Uuid, id: &str) -> Result<ContactMeMessage, AppError> {
         let valid_id = valid_uuid(id)?;
 
-        let msg = self.contact_repo.get_contact_message_by_id(&valid_id).await?;
+        let msg = self.contact_repo.get_contact_message_by_id(&tenant_id, &valid_id).await?;
 
-        Ok(msg)
+        Ok(open_contact_message(&self.cipher, msg))
     }
 
     /// Lists all contact messages
-    pub async fn list_contact_messages(&self) -> Result<ContactMeListResponse, AppError> {
-        let messages = self.contact_repo.list_contact_messages().await?;
-        let total = self.contact_repo.count_contact_messages().await?;
+    pub async fn list_contact_messages(&self, tenant_id: Uuid) -> Result<ContactMeListResponse, AppError> {
+        let messages = self.contact_repo
+            .list_contact_messages(&tenant_id)
+            .await?
+            .into_iter()
+            .map(|msg| open_contact_message(&self.cipher, msg))
+            .collect();
+        let total = self.contact_repo.count_contact_messages(&tenant_id).await?;
 
         Ok(ContactMeListResponse {
             messages,
@@ -61,18 +121,189 @@ where
 
     /// Deletes a contact message by its ID
     pub async fn delete_contact_message(
-        &self, 
+        &self,
+        tenant_id: Uuid,
         id: &str, 
         hard_delete: bool
     ) -> Result<(), AppError> {
         let valid_id = valid_uuid(id)?;
 
         match hard_delete {
-            true => self.contact_repo.hard_delete_contact_message(&valid_id).await,
-            false => self.contact_repo.soft_delete_contact_message(&valid_id).await,
+            true => self.contact_repo.hard_delete_contact_message(&tenant_id, &valid_id).await,
+            false => self.contact_repo.soft_delete_contact_message(&tenant_id, &valid_id).await,
         }.map_err(|e| match e {
             AppError::NotFound(_) => AppError::NotFound("Contact message not found".to_string()),
             _ => e,
         })
     }
-}
\ No newline at end of file
+
+    /// Streams every matching message as CSV, one chunk per repository round trip.
+    ///
+    /// The first chunk carries the header row, so an empty export is still a valid CSV.
+    /// Fields tagged as personal data are redacted unless `include_pii` is set.
+    pub fn export_contact_messages_csv(
+        &self,
+        tenant_id: Uuid,
+        filter: ContactMeExportQuery,
+    ) -> Result<impl Stream<Item = Result<Bytes, AppError>> + 'static, AppError>
+    where
+        R: Clone + 'static,
+    {
+        if let (Some(from), Some(to)) = (filter.from, filter.to)
+            && from >= to
+        {
+            return Err(AppError::InvalidInput("`from` must be earlier than `to`".to_string()));
+        }
+
+        let repo = self.contact_repo.clone();
+        let cipher = self.cipher.clone();
+        let initial: (Option<ContactMeCursor>, bool) = (None, false);
+
+        Ok(stream::try_unfold(initial, move |(cursor, header_sent)| {
+            let repo = repo.clone();
+            let cipher = cipher.clone();
+            let filter = filter.clone();
+
+            async move {
+                // A cursor of None after the header means the previous chunk was short
+                if header_sent && cursor.is_none() {
+                    return Ok(None);
+                }
+
+                let rows: Vec<ContactMeMessage> = repo
+                    .list_contact_messages_chunk(&tenant_id, &filter, cursor, EXPORT_CHUNK_SIZE)
+                    .await?
+                    .into_iter()
+                    .map(|msg| {
+                        let mut msg = open_contact_message(&cipher, msg);
+                        if !filter.include_pii {
+                            msg.redact_pii();
+                        }
+                        msg
+                    })
+                    .collect();
+
+                let next_cursor = match rows.last() {
+                    Some(last) if rows.len() as i64 == EXPORT_CHUNK_SIZE => Some((last.created_at, last.id)),
+                    _ => None,
+                };
+
+                if rows.is_empty() && header_sent {
+                    return Ok(None);
+                }
+
+                let chunk = encode_csv_chunk(&EXPORT_CSV_HEADER, rows.iter().map(csv_row), !header_sent)?;
+                Ok(Some((chunk, (next_cursor, true))))
+            }
+        }))
+    }
+
+    /// Re-seals every stored email and message, anonymized identities
+    /// included, that is in plaintext or sealed with a key other than the
+    /// primary one, `batch_size` rows at a time. Run after adding a key and
+    /// making it primary; the old key can be dropped once `failed` is empty.
+    pub async fn rekey_contact_messages(&self, batch_size: i64) -> Result<ContactRekeyReport, AppError> {
+        if !self.cipher.is_enabled() {
+            return Err(AppError::InvalidInput("No contact encryption key is configured to seal with".to_string()));
+        }
+
+        let mut report = ContactRekeyReport::default();
+        let mut after = None;
+        loop {
+            let batch = self.contact_repo.list_contact_secrets(after, batch_size).await?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after = Some(last.message_id);
+            let full = batch.len() as i64 == batch_size;
+
+            let mut rewrites = Vec::new();
+            for secret in batch {
+                report.scanned += 1;
+                match reseal_contact_secret(&self.cipher, &secret) {
+                    Ok(Some(after)) => rewrites.push(ContactSecretRewrite { before: secret, after }),
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!(message_id = %secret.message_id, "Contact message not re-sealed: {}", e);
+                        report.failed.push(secret.message_id);
+                    }
+                }
+            }
+            if !rewrites.is_empty() {
+                report.rewritten += self.contact_repo.rewrite_contact_secrets(&rewrites).await?;
+            }
+
+            if !full {
+                break;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Associated data binding a sealed field to its message and column
+fn field_context(message_id: &Uuid, field: &str) -> String {
+    format!("contact:{}:{}", message_id, field)
+}
+
+/// Seals the sender's email and message before they are stored
+pub fn seal_contact_message(cipher: &FieldCipher, msg: &mut ContactMeFormInsert) -> Result<(), AppError> {
+    msg.email = cipher.seal(&msg.email, &field_context(&msg.id, "email"))?;
+    msg.message = cipher.seal(&msg.message, &field_context(&msg.id, "message"))?;
+    Ok(())
+}
+
+/// Opens a stored message for reading. A field no configured key opens is
+/// logged and shown as `UNREADABLE_FIELD`, so one bad row never hides the rest.
+pub fn open_contact_message(cipher: &FieldCipher, mut msg: ContactMeMessage) -> ContactMeMessage {
+    let open = |stored: &str, field: &str| {
+        cipher.open(stored, &field_context(&msg.id, field)).unwrap_or_else(|e| {
+            tracing::error!(message_id = %msg.id, field, "Contact message field did not open: {}", e);
+            UNREADABLE_FIELD.to_string()
+        })
+    };
+
+    let email = open(&msg.email, "email");
+    let message = open(&msg.message, "message");
+    msg.email = email;
+    msg.message = message;
+    msg
+}
+
+/// The row sealed with the primary key, or None when it already is
+fn reseal_contact_secret(cipher: &FieldCipher, secret: &ContactSecret) -> Result<Option<ContactSecret>, AppError> {
+    let identity_current = secret.identity_email.as_deref().is_none_or(|email| cipher.is_current(email));
+    if cipher.is_current(&secret.email) && cipher.is_current(&secret.message) && identity_current {
+        return Ok(None);
+    }
+
+    let reseal = |stored: &str, field: &str| -> Result<String, AppError> {
+        if cipher.is_current(stored) {
+            return Ok(stored.to_string());
+        }
+        let context = field_context(&secret.message_id, field);
+        cipher.seal(&cipher.open(stored, &context)?, &context)
+    };
+
+    Ok(Some(ContactSecret {
+        message_id: secret.message_id,
+        email: reseal(&secret.email, "email")?,
+        message: reseal(&secret.message, "message")?,
+        identity_email: secret.identity_email.as_deref().map(|email| reseal(email, "email")).transpose()?,
+    }))
+}
+
+fn csv_row(msg: &ContactMeMessage) -> [String; 9] {
+    [
+        msg.id.to_string(),
+        msg.created_at.to_rfc3339(),
+        msg.name.clone(),
+        msg.email.clone(),
+        msg.subject.clone().unwrap_or_default(),
+        msg.message.clone(),
+        msg.is_spam.to_string(),
+        msg.country_code.clone().unwrap_or_default(),
+        msg.region.clone().unwrap_or_default(),
+    ]
+}
@@ -0,0 +1,142 @@
+use actix_web::{http::header, HttpRequest};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        content_scan::ContentWarning,
+        security_event::{SecurityEventInsert, CONTENT_BLOCKED, CONTENT_FLAGGED},
+    },
+    errors::{AppError, FieldError},
+    geo::geoip::GeoLocator,
+    metrics::METRICS,
+    repositories::security_event::SecurityEventRepository,
+    settings::AppConfig,
+    utils::{content_scan::ContentScanner, get_client_ip::{get_client_ip, TrustedProxies}},
+};
+
+/// The request that submitted scanned content, recorded with its findings
+#[derive(Debug, Clone)]
+pub struct ContentOrigin {
+    pub ip: String,
+    pub user_agent: Option<String>,
+    pub method: String,
+    pub path: String,
+}
+
+impl ContentOrigin {
+    pub fn from_request(req: &HttpRequest, trusted: &TrustedProxies) -> Self {
+        ContentOrigin {
+            ip: get_client_ip(req, trusted),
+            user_agent: req.headers()
+                .get(header::USER_AGENT)
+                .and_then(|v| v.to_str().ok())
+                .map(|ua| ua.chars().take(512).collect()),
+            method: req.method().to_string(),
+            path: req.path().to_string(),
+        }
+    }
+}
+
+/// Scans posts and contact messages before they are saved. Anything found
+/// is logged as a security event; high-severity findings refuse the write,
+/// the rest come back to the caller as warnings.
+#[derive(Clone)]
+pub struct ContentSafety<R>
+where
+    R: SecurityEventRepository,
+{
+    pub event_repo: R,
+    scanner: ContentScanner,
+    geo: GeoLocator,
+}
+
+impl<R> ContentSafety<R>
+where
+    R: SecurityEventRepository,
+{
+    pub fn new(event_repo: R, geo: GeoLocator, config: &AppConfig) -> Self {
+        ContentSafety {
+            event_repo,
+            scanner: ContentScanner::new(config.blocked_content_domains()),
+            geo,
+        }
+    }
+
+    /// Findings in each `(field, text)`, without recording anything
+    pub fn scan(&self, fields: &[(&str, &str)]) -> Vec<ContentWarning> {
+        fields
+            .iter()
+            .flat_map(|(field, text)| {
+                self.scanner.scan(text).into_iter().map(|finding| ContentWarning { field: field.to_string(), finding })
+            })
+            .collect()
+    }
+
+    /// Scans `fields` before a write. Returns the warnings to pass on, or a
+    /// validation error naming each high-severity finding.
+    pub async fn check(
+        &self,
+        tenant_id: Uuid,
+        origin: &ContentOrigin,
+        fields: &[(&str, &str)],
+    ) -> Result<Vec<ContentWarning>, AppError> {
+        let warnings = self.scan(fields);
+        if warnings.is_empty() {
+            return Ok(warnings);
+        }
+
+        let blocked = warnings.iter().any(|warning| warning.finding.severity.blocks());
+        METRICS.incr(if blocked { "content_blocked_total" } else { "content_flagged_total" });
+        tracing::warn!(
+            path = %origin.path,
+            blocked,
+            rules = ?warnings.iter().map(|warning| warning.finding.rule).collect::<Vec<_>>(),
+            "Content scan found suspicious content"
+        );
+
+        let event = self.event(origin, blocked, &warnings);
+        if let Err(e) = self.event_repo.record_event(&tenant_id, &event).await {
+            tracing::warn!("Content scan finding not recorded: {}", e);
+        }
+
+        if blocked {
+            return Err(AppError::ValidationError(
+                warnings
+                    .into_iter()
+                    .filter(|warning| warning.finding.severity.blocks())
+                    .map(|warning| FieldError { field: warning.field, message: warning.finding.message })
+                    .collect(),
+            ));
+        }
+
+        Ok(warnings)
+    }
+
+    /// Excerpts are left out: they quote what was submitted, which may be a
+    /// private message
+    fn event(&self, origin: &ContentOrigin, blocked: bool, warnings: &[ContentWarning]) -> SecurityEventInsert {
+        let location = self.geo.lookup(&origin.ip).unwrap_or_default();
+        let findings: Vec<_> = warnings
+            .iter()
+            .map(|warning| json!({
+                "field": warning.field,
+                "rule": warning.finding.rule,
+                "severity": warning.finding.severity,
+            }))
+            .collect();
+
+        SecurityEventInsert {
+            kind: if blocked { CONTENT_BLOCKED } else { CONTENT_FLAGGED }.to_string(),
+            ip: origin.ip.clone(),
+            user_agent: origin.user_agent.clone(),
+            method: origin.method.clone(),
+            path: origin.path.clone(),
+            occurred_at: Utc::now(),
+            country_code: location.country_code,
+            region: location.region,
+            details: Some(json!({ "findings": findings })),
+        }
+    }
+}
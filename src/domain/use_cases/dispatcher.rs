@@ -0,0 +1,230 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::redirect;
+use tokio::sync::broadcast;
+use url::Url;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        notification::{
+            EventChannels, Notification, NotificationChannel, NotificationPreferencesResponse, NotificationRecipient,
+            UpdateNotificationPreferencesRequest,
+        },
+        option_fields::OptionField,
+    },
+    errors::AppError,
+    links::probe::is_public_http_url,
+    mailer::email::{EmailMessage, Mailer},
+    metrics::METRICS,
+    repositories::notification_preferences::NotificationPreferencesRepository,
+    settings::AppConfig,
+};
+
+/// Notifications buffered for slow stream subscribers before they miss some
+const LIVE_BUFFER: usize = 256;
+const WEBHOOK_TIMEOUT_SECS: u64 = 5;
+
+/// Receives admin notifications from the parts of the app that raise them
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// Delivery failures are logged, never returned: a notification must not
+    /// fail the work that raised it
+    async fn notify(&self, tenant_id: Uuid, notification: Notification);
+}
+
+/// A notification on its way to one admin's open streams
+#[derive(Debug, Clone)]
+pub struct LiveNotification {
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub notification: Notification,
+}
+
+/// Routes admin notifications to the channels each admin picked for the
+/// event type: their account email, their webhook, or the SSE stream.
+///
+/// Admins who never saved preferences get the live stream only.
+#[derive(Clone)]
+pub struct NotificationDispatcher<P>
+where
+    P: NotificationPreferencesRepository,
+{
+    prefs_repo: P,
+    mailer: Arc<dyn Mailer>,
+    from: String,
+    client: reqwest::Client,
+    live: broadcast::Sender<LiveNotification>,
+}
+
+impl<P> NotificationDispatcher<P>
+where
+    P: NotificationPreferencesRepository,
+{
+    pub fn new(prefs_repo: P, mailer: Arc<dyn Mailer>, config: &AppConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .redirect(redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+
+        NotificationDispatcher {
+            prefs_repo,
+            mailer,
+            from: config.mail_from.clone(),
+            client,
+            live: broadcast::channel(LIVE_BUFFER).0,
+        }
+    }
+
+    /// Every notification sent over SSE from now on; filter by tenant and user
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveNotification> {
+        self.live.subscribe()
+    }
+
+    pub async fn preferences(&self, tenant_id: Uuid, user_id: Uuid) -> Result<NotificationPreferencesResponse, AppError> {
+        Ok(self.recipient(tenant_id, user_id).await?.into())
+    }
+
+    /// Merges the request into the stored preferences. Picking the webhook
+    /// channel for any event needs a `webhook_url`.
+    pub async fn update_preferences(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        request: UpdateNotificationPreferencesRequest,
+    ) -> Result<NotificationPreferencesResponse, AppError> {
+        request.validate()?;
+
+        let mut recipient = self.recipient(tenant_id, user_id).await?;
+
+        let mut channels: EventChannels = serde_json::from_value(recipient.channels.clone()).unwrap_or_default();
+        channels.extend(request.channels);
+        recipient.channels = serde_json::to_value(&channels)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode notification channels: {}", e)))?;
+
+        match request.webhook_url {
+            OptionField::Unchanged => {}
+            OptionField::SetToNull => recipient.webhook_url = None,
+            OptionField::SetToValue(url) => {
+                if !Url::parse(&url).is_ok_and(|u| is_public_http_url(&u)) {
+                    return Err(AppError::InvalidInput("webhook_url must be a public http(s) URL".to_string()));
+                }
+                recipient.webhook_url = Some(url);
+            }
+        }
+
+        let uses_webhook = recipient
+            .event_channels()
+            .values()
+            .any(|c| c.contains(&NotificationChannel::Webhook));
+        if uses_webhook && recipient.webhook_url.is_none() {
+            return Err(AppError::InvalidInput("The webhook channel needs a webhook_url".to_string()));
+        }
+
+        let saved = self.prefs_repo
+            .save_preferences(&tenant_id, &user_id, recipient.webhook_url, &recipient.channels)
+            .await?;
+
+        Ok(saved.into())
+    }
+
+    /// Sends `notification` to every admin of the tenant on their channels
+    pub async fn dispatch(&self, tenant_id: Uuid, notification: &Notification) -> Result<(), AppError> {
+        for recipient in self.prefs_repo.list_recipients(&tenant_id).await? {
+            let channels = recipient.event_channels().remove(&notification.event).unwrap_or_default();
+
+            for channel in channels {
+                let result = match channel {
+                    NotificationChannel::Email => self.send_email(&recipient, notification).await,
+                    NotificationChannel::Webhook => self.post_webhook(&recipient, notification).await,
+                    NotificationChannel::Sse => {
+                        // No receivers just means nobody has the stream open
+                        let _ = self.live.send(LiveNotification {
+                            tenant_id,
+                            user_id: recipient.user_id,
+                            notification: notification.clone(),
+                        });
+                        Ok(())
+                    }
+                };
+
+                match result {
+                    Ok(()) => METRICS.incr(&format!("notifications_sent_total{{channel=\"{}\"}}", channel.as_str())),
+                    Err(e) => {
+                        METRICS.incr(&format!("notifications_failed_total{{channel=\"{}\"}}", channel.as_str()));
+                        tracing::warn!(
+                            user_id = %recipient.user_id,
+                            event = notification.event.as_str(),
+                            channel = channel.as_str(),
+                            "Notification not delivered: {}",
+                            e
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn recipient(&self, tenant_id: Uuid, user_id: Uuid) -> Result<NotificationRecipient, AppError> {
+        self.prefs_repo
+            .get_recipient(&tenant_id, &user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    async fn send_email(&self, recipient: &NotificationRecipient, notification: &Notification) -> Result<(), AppError> {
+        let text = match &notification.link {
+            Some(link) => format!("{}\n\n{}", notification.body, link),
+            None => notification.body.clone(),
+        };
+
+        self.mailer.send(&EmailMessage {
+            from: self.from.clone(),
+            to: recipient.email.clone(),
+            subject: notification.title.clone(),
+            text,
+            html: None,
+            headers: Default::default(),
+        }).await
+    }
+
+    async fn post_webhook(&self, recipient: &NotificationRecipient, notification: &Notification) -> Result<(), AppError> {
+        let Some(url) = &recipient.webhook_url else {
+            return Err(AppError::InvalidInput("No webhook_url configured".to_string()));
+        };
+        // Checked again in case the URL was stored before the rules tightened
+        if !Url::parse(url).is_ok_and(|u| is_public_http_url(&u)) {
+            return Err(AppError::InvalidInput("webhook_url is not a public http(s) URL".to_string()));
+        }
+
+        let response = self.client
+            .post(url)
+            .json(notification)
+            .send()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Webhook request failed: {}", e.without_url())))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::InternalError(format!("Webhook answered {}", response.status())));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<P> NotificationSink for NotificationDispatcher<P>
+where
+    P: NotificationPreferencesRepository,
+{
+    async fn notify(&self, tenant_id: Uuid, notification: Notification) {
+        if let Err(e) = self.dispatch(tenant_id, &notification).await {
+            tracing::warn!(event = notification.event.as_str(), "Notification dispatch failed: {}", e);
+        }
+    }
+}
\ No newline at end of file
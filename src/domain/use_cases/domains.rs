@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dns::txt::TxtResolver,
+    entities::tenant::{is_valid_hostname, normalize_host, NewTenantDomainRequest, TenantDomain, TenantDomainResponse},
+    errors::AppError,
+    metrics::METRICS,
+    repositories::tenant::TenantRepository,
+    use_cases::tenants::TenantResolver,
+};
+
+/// Pending domains checked per background run
+const PENDING_BATCH_SIZE: i64 = 100;
+
+/// Custom domain onboarding: registration, DNS TXT ownership checks and removal.
+///
+/// Only verified domains are returned by `list_tenants`, so a domain starts
+/// serving its tenant on the resolver refresh that follows verification.
+#[derive(Clone)]
+pub struct DomainVerifier<R>
+where
+    R: TenantRepository,
+{
+    pub tenants: TenantResolver<R>,
+    dns: Arc<dyn TxtResolver>,
+}
+
+impl<R> DomainVerifier<R>
+where
+    R: TenantRepository,
+{
+    pub fn new(tenants: TenantResolver<R>, dns: Arc<dyn TxtResolver>) -> Self {
+        DomainVerifier { tenants, dns }
+    }
+
+    /// Registers a domain for the tenant and issues its verification token
+    pub async fn add_domain(
+        &self,
+        tenant_id: Uuid,
+        request: NewTenantDomainRequest,
+    ) -> Result<TenantDomainResponse, AppError> {
+        request.validate()?;
+
+        let hostname = normalize_host(&request.hostname);
+        if !is_valid_hostname(&hostname) {
+            return Err(AppError::InvalidInput(
+                "Hostname must be a fully qualified domain name, e.g. blog.example.com".to_string()
+            ));
+        }
+
+        let token = format!("{:032x}", rand::random::<u128>());
+        let domain = self.tenants.tenant_repo.create_domain(&tenant_id, &hostname, &token).await?;
+
+        tracing::info!(%tenant_id, hostname = %domain.hostname, "Custom domain registered");
+        Ok(domain.into())
+    }
+
+    pub async fn list_domains(&self, tenant_id: Uuid) -> Result<Vec<TenantDomainResponse>, AppError> {
+        let domains = self.tenants.tenant_repo.list_domains(&tenant_id).await?;
+
+        Ok(domains.into_iter().map(TenantDomainResponse::from).collect())
+    }
+
+    pub async fn get_domain(&self, tenant_id: Uuid, hostname: &str) -> Result<TenantDomainResponse, AppError> {
+        self.find(&tenant_id, hostname).await.map(TenantDomainResponse::from)
+    }
+
+    /// Checks DNS right away instead of waiting for the background job
+    pub async fn verify_domain(&self, tenant_id: Uuid, hostname: &str) -> Result<TenantDomainResponse, AppError> {
+        let domain = self.find(&tenant_id, hostname).await?;
+        if domain.verified_at.is_some() {
+            return Ok(domain.into());
+        }
+
+        let checked = self.check(&domain).await?;
+        if checked.verified_at.is_some() {
+            self.tenants.refresh().await?;
+        }
+
+        Ok(checked.into())
+    }
+
+    pub async fn remove_domain(&self, tenant_id: Uuid, hostname: &str) -> Result<(), AppError> {
+        self.tenants.tenant_repo.delete_domain(&tenant_id, &normalize_host(hostname)).await?;
+
+        // Stop serving the host now rather than on the next periodic refresh
+        self.tenants.refresh().await?;
+        Ok(())
+    }
+
+    /// Re-checks pending domains, returning how many became verified
+    pub async fn verify_pending(&self) -> Result<usize, AppError> {
+        let pending = self.tenants.tenant_repo.list_pending_domains(PENDING_BATCH_SIZE).await?;
+
+        let mut verified = 0;
+        for domain in &pending {
+            match self.check(domain).await {
+                Ok(checked) if checked.verified_at.is_some() => verified += 1,
+                Ok(_) => {}
+                Err(e) => tracing::warn!(hostname = %domain.hostname, "Recording domain check failed: {}", e),
+            }
+        }
+
+        if verified > 0 {
+            self.tenants.refresh().await?;
+        }
+
+        Ok(verified)
+    }
+
+    async fn find(&self, tenant_id: &Uuid, hostname: &str) -> Result<TenantDomain, AppError> {
+        self.tenants.tenant_repo
+            .get_domain(tenant_id, &normalize_host(hostname))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Domain not found".to_string()))
+    }
+
+    /// Looks up the TXT record and stores the outcome; DNS failures are
+    /// recorded on the domain rather than returned
+    async fn check(&self, domain: &TenantDomain) -> Result<TenantDomain, AppError> {
+        let record_name = domain.txt_record_name();
+        let expected = domain.txt_record_value();
+
+        let error = match self.dns.lookup_txt(&record_name).await {
+            Ok(records) if records.iter().any(|r| r.trim() == expected) => None,
+            Ok(records) if records.is_empty() => Some(format!("No TXT record found at {}", record_name)),
+            Ok(_) => Some(format!("TXT record at {} does not match the verification token", record_name)),
+            Err(e) => Some(format!("DNS lookup failed: {}", e)),
+        };
+
+        let verified = error.is_none();
+        let checked = self.tenants.tenant_repo
+            .record_domain_check(&domain.hostname, verified, error)
+            .await?;
+
+        if verified {
+            METRICS.incr("tenant_domains_verified_total");
+            tracing::info!(hostname = %checked.hostname, tenant_id = %checked.tenant_id, "Custom domain verified");
+        }
+
+        Ok(checked)
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    cache::edit_locks::EditLockStore,
+    entities::{
+        blog_post::PostEditor,
+        edit_lock::{EditLockStatus, PostEditLock},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::blog_post::BlogPostRepository,
+    settings::AppConfig,
+    utils::valid_uuid::valid_uuid,
+};
+
+/// What asking for a lock ended in
+#[derive(Debug)]
+pub enum EditLockOutcome {
+    /// The caller holds the lock, new or extended
+    Acquired(PostEditLock),
+    /// Someone else is editing the post
+    HeldByOther(PostEditLock),
+}
+
+/// Advisory locks that stop two people editing the same post at once.
+///
+/// An editor takes the lock when they open a post and keeps it with
+/// heartbeats; it lapses `ttl_secs` after the last one, so a closed tab
+/// frees the post on its own. Admins can take over a lock. Saving a post
+/// someone else has locked is refused, but only while Redis answers: a
+/// lock that cannot be read never blocks a save.
+#[derive(Clone)]
+pub struct PostEditLocks<B>
+where
+    B: BlogPostRepository,
+{
+    pub blog_post_repo: B,
+    store: Option<Arc<dyn EditLockStore>>,
+    ttl_secs: u64,
+}
+
+impl<B> PostEditLocks<B>
+where
+    B: BlogPostRepository,
+{
+    pub fn new(blog_post_repo: B, store: Option<Arc<dyn EditLockStore>>, config: &AppConfig) -> Self {
+        PostEditLocks { blog_post_repo, store, ttl_secs: config.post_edit_lock_ttl_secs }
+    }
+
+    fn store(&self) -> Result<&Arc<dyn EditLockStore>, AppError> {
+        self.store
+            .as_ref()
+            .ok_or_else(|| AppError::ServiceUnavailable("Edit locks are not enabled".to_string()))
+    }
+
+    /// Half the TTL leaves room for one lost or late heartbeat
+    pub fn next_beat_secs(&self) -> u64 {
+        (self.ttl_secs / 2).max(1)
+    }
+
+    /// Takes the lock, or extends it when the caller holds it already; this
+    /// is also the heartbeat. `force` replaces another holder's lock.
+    pub async fn acquire(
+        &self,
+        tenant: &Tenant,
+        editor: PostEditor,
+        email: &str,
+        post_id: &str,
+        force: bool,
+    ) -> Result<EditLockOutcome, AppError> {
+        if force && !editor.admin {
+            return Err(AppError::ForbiddenAccess);
+        }
+        let store = self.store()?;
+        let valid_id = valid_uuid(post_id)?;
+
+        let post = self.blog_post_repo.get_blog_post_by_id(&tenant.id, &valid_id).await?;
+        if !editor.can_modify(&post) {
+            return Err(AppError::ForbiddenAccess);
+        }
+
+        let now = Utc::now();
+        let wanted = PostEditLock {
+            post_id: valid_id,
+            holder_id: editor.user_id,
+            holder_email: email.to_string(),
+            acquired_at: now,
+            expires_at: now + Duration::seconds(self.ttl_secs as i64),
+        };
+        let lock = store.acquire(&lock_key(tenant, &valid_id), &wanted, self.ttl_secs, force).await?;
+
+        if lock.holder_id != editor.user_id {
+            METRICS.incr("post_edit_lock_conflicts_total");
+            return Ok(EditLockOutcome::HeldByOther(lock));
+        }
+        if force {
+            tracing::info!(post_id = %valid_id, user_id = %editor.user_id, "Post edit lock forced");
+        }
+
+        Ok(EditLockOutcome::Acquired(lock))
+    }
+
+    pub async fn status(&self, tenant: &Tenant, post_id: &str) -> Result<EditLockStatus, AppError> {
+        let store = self.store()?;
+        let valid_id = valid_uuid(post_id)?;
+
+        let lock = store.current(&lock_key(tenant, &valid_id)).await?;
+        Ok(EditLockStatus { post_id: valid_id, lock })
+    }
+
+    /// Frees the post if the caller holds the lock; anyone else's lock stays
+    pub async fn release(&self, tenant: &Tenant, editor: PostEditor, post_id: &str) -> Result<(), AppError> {
+        let store = self.store()?;
+        let valid_id = valid_uuid(post_id)?;
+
+        store.release(&lock_key(tenant, &valid_id), &editor.user_id).await?;
+        Ok(())
+    }
+
+    /// Someone else's live lock on the post, which a save by `editor` would
+    /// overwrite the work of
+    pub async fn held_by_other(&self, tenant: &Tenant, editor: PostEditor, post_id: &Uuid) -> Option<PostEditLock> {
+        let store = self.store.as_ref()?;
+
+        match store.current(&lock_key(tenant, post_id)).await {
+            Ok(lock) => lock.filter(|lock| lock.holder_id != editor.user_id),
+            Err(e) => {
+                tracing::warn!(post_id = %post_id, "Edit lock not checked before saving: {}", e);
+                None
+            }
+        }
+    }
+}
+
+fn lock_key(tenant: &Tenant, post_id: &Uuid) -> String {
+    tenant.cache_key(&format!("edit-lock:{}", post_id))
+}
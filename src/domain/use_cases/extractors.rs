@@ -1,6 +1,7 @@
 use actix_web::{FromRequest, HttpRequest, HttpMessage};
 use futures_util::future::{ready, Ready};
-use crate::{entities::token::Claims, errors::AuthError};
+use uuid::Uuid;
+use crate::{entities::{blog_post::PostEditor, tenant::Tenant, token::Claims}, errors::{AppError, AuthError}};
 
 /// Extractor for authenticated claims, ensuring the user is authenticated.
 /// Returns 401 if the user is not authenticated.
@@ -43,4 +44,62 @@ impl FromRequest for AdminClaims {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Extractor for users who may write blog posts: editors and admins.
+/// Returns 403 for everyone else.
+/// Returns 401 if the user is not authenticated.
+#[derive(Debug)]
+pub struct EditorClaims(pub Claims);
+
+impl EditorClaims {
+    pub fn post_editor(&self) -> Result<PostEditor, AppError> {
+        let user_id = Uuid::parse_str(&self.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+        Ok(PostEditor { user_id, admin: self.0.admin })
+    }
+}
+
+impl FromRequest for EditorClaims {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        match req.extensions().get::<Claims>() {
+            Some(claims) if claims.admin || claims.editor => {
+                ready(Ok(EditorClaims(claims.clone())))
+            }
+            Some(_) => {
+                ready(Err(AuthError::Forbidden("Editor access required".into()).into()))
+            }
+            None => {
+                ready(Err(AuthError::MissingCredentials.into()))
+            }
+        }
+    }
+}
+
+/// Extractor for the tenant resolved from the Host header by `TenantMiddleware`.
+/// Usage: Add `tenant: CurrentTenant` as a parameter to your handler function.
+#[derive(Debug, Clone)]
+pub struct CurrentTenant(pub Tenant);
+
+impl CurrentTenant {
+    pub fn id(&self) -> Uuid {
+        self.0.id
+    }
+}
+
+impl FromRequest for CurrentTenant {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        match req.extensions().get::<Tenant>() {
+            Some(tenant) => ready(Ok(CurrentTenant(tenant.clone()))),
+            None => {
+                tracing::error!("Tenant missing in request extensions; is TenantMiddleware installed?");
+                ready(Err(AppError::InternalError("Tenant not resolved".into()).into()))
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{collections::HashMap, sync::Arc};
+
+use actix_web::{guard::{Guard, GuardContext}, web};
+use parking_lot::RwLock;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        feature_flag::{is_valid_flag_key, FeatureFlag, UpdateFeatureFlagRequest},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    repositories::feature_flag::FeatureFlagRepository,
+    AppState,
+};
+
+/// Feature flags backed by the `feature_flags` table, kept per tenant.
+///
+/// Lookups hit an in-process cache so they are cheap enough for route guards;
+/// the cache is updated on every toggle and refreshed periodically so other
+/// instances pick up changes.
+#[derive(Clone)]
+pub struct FeatureFlags<R>
+where
+    R: FeatureFlagRepository,
+{
+    pub flag_repo: R,
+    cache: Arc<RwLock<HashMap<Uuid, HashMap<String, bool>>>>,
+}
+
+impl<R> FeatureFlags<R>
+where
+    R: FeatureFlagRepository,
+{
+    pub fn new(flag_repo: R) -> Self {
+        FeatureFlags {
+            flag_repo,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Unknown flags are treated as disabled
+    pub fn is_enabled(&self, tenant_id: &Uuid, key: &str) -> bool {
+        self.cache.read().get(tenant_id).and_then(|flags| flags.get(key)).copied().unwrap_or(false)
+    }
+
+    /// Reloads every tenant's flags from the database, returning how many were loaded
+    pub async fn refresh(&self) -> Result<usize, AppError> {
+        let flags = self.flag_repo.list_all_feature_flags().await?;
+        let count = flags.len();
+
+        let mut fresh: HashMap<Uuid, HashMap<String, bool>> = HashMap::new();
+        for flag in flags {
+            fresh.entry(flag.tenant_id).or_default().insert(flag.key, flag.enabled);
+        }
+
+        *self.cache.write() = fresh;
+        Ok(count)
+    }
+
+    /// Lists the tenant's flags straight from the database
+    pub async fn list_flags(&self, tenant_id: Uuid) -> Result<Vec<FeatureFlag>, AppError> {
+        self.flag_repo.list_feature_flags(&tenant_id).await
+    }
+
+    /// Creates or toggles a flag and applies it to the local cache immediately
+    pub async fn set_flag(
+        &self,
+        tenant_id: Uuid,
+        key: &str,
+        request: UpdateFeatureFlagRequest,
+    ) -> Result<FeatureFlag, AppError> {
+        check_flag(key, &request)?;
+
+        let flag = self.flag_repo
+            .upsert_feature_flag(&tenant_id, key, request.enabled, request.description)
+            .await?;
+
+        self.apply_cached(tenant_id, &flag.key, flag.enabled);
+        Ok(flag)
+    }
+
+    /// Applies a change already written to the database to the local cache
+    pub fn apply_cached(&self, tenant_id: Uuid, key: &str, enabled: bool) {
+        self.cache.write().entry(tenant_id).or_default().insert(key.to_string(), enabled);
+    }
+}
+
+/// Whether a flag may be created or toggled with `request`
+pub fn check_flag(key: &str, request: &UpdateFeatureFlagRequest) -> Result<(), AppError> {
+    request.validate()?;
+
+    if !is_valid_flag_key(key) {
+        return Err(AppError::InvalidInput(
+            "Flag key must be 1-64 characters of a-z, 0-9, '_' or '-'".to_string()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Route guard that hides a resource (404) while the named flag is off for
+/// the request's tenant.
+/// Usage: `web::resource("/comments").guard(RequireFeature("comments"))`
+#[derive(Debug, Clone, Copy)]
+pub struct RequireFeature(pub &'static str);
+
+impl Guard for RequireFeature {
+    fn check(&self, ctx: &GuardContext<'_>) -> bool {
+        let Some(tenant_id) = ctx.req_data().get::<Tenant>().map(|tenant| tenant.id) else {
+            return false;
+        };
+        ctx.app_data::<web::Data<AppState>>()
+            .is_some_and(|state| state.feature_flags.is_enabled(&tenant_id, self.0))
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    entities::tenant::Tenant,
+    errors::AppError,
+    repositories::{blog_post::BlogPostRepository, changelog::ChangelogRepository},
+    settings::AppConfig,
+    utils::public_urls::PublicUrls,
+};
+
+/// Items per feed, across blog posts and changelog entries combined
+const FEED_ITEM_LIMIT: usize = 20;
+
+struct FeedItem {
+    title: String,
+    link: String,
+    guid: String,
+    description: String,
+    published: DateTime<Utc>,
+    post_id: Option<Uuid>,
+}
+
+/// Site-wide RSS 2.0 feed: published blog posts and changelog entries,
+/// newest first. Links point at the tenant's site, see `PublicUrls`.
+#[derive(Clone)]
+pub struct FeedHandler<B, C>
+where
+    B: BlogPostRepository,
+    C: ChangelogRepository,
+{
+    pub blog_repo: B,
+    pub changelog_repo: C,
+    urls: PublicUrls,
+}
+
+impl<B, C> FeedHandler<B, C>
+where
+    B: BlogPostRepository,
+    C: ChangelogRepository,
+{
+    pub fn new(blog_repo: B, changelog_repo: C, config: &AppConfig) -> Self {
+        FeedHandler {
+            blog_repo,
+            changelog_repo,
+            urls: PublicUrls::from_config(config),
+        }
+    }
+
+    pub async fn rss(&self, tenant: &Tenant) -> Result<String, AppError> {
+        Ok(self.rss_with_posts(tenant).await?.0)
+    }
+
+    /// The feed and the ids of the blog posts that made it in
+    pub async fn rss_with_posts(&self, tenant: &Tenant) -> Result<(String, Vec<Uuid>), AppError> {
+        let base_url = self.urls.site_origin(tenant);
+
+        let posts = self.blog_repo
+            .get_recent_blog_posts(&tenant.id, FEED_ITEM_LIMIT as u32, true)
+            .await?;
+        let changes = self.changelog_repo
+            .list_entries(&tenant.id, None, FEED_ITEM_LIMIT as i64, 0)
+            .await?;
+
+        let mut items: Vec<FeedItem> = posts
+            .into_iter()
+            .map(|post| FeedItem {
+                link: self.urls.site(tenant, &format!("/blog/{}", post.slug)),
+                guid: format!("post:{}", post.id),
+                title: post.title,
+                description: post.excerpt,
+                published: post.published_at.unwrap_or(post.updated_at),
+                post_id: Some(post.id),
+            })
+            .chain(changes.into_iter().map(|entry| FeedItem {
+                title: format!("What's new in {}", entry.version),
+                link: self.urls.site(tenant, &format!("/changelog#{}", entry.version)),
+                guid: format!("changelog:{}", entry.id),
+                description: entry.notes_markdown,
+                published: entry.released_on.and_time(NaiveTime::MIN).and_utc(),
+                post_id: None,
+            }))
+            .collect();
+
+        items.sort_by_key(|item| std::cmp::Reverse(item.published));
+        items.truncate(FEED_ITEM_LIMIT);
+
+        let post_ids = items.iter().filter_map(|item| item.post_id).collect();
+        Ok((render_rss(&tenant.name, &base_url, &items), post_ids))
+    }
+}
+
+fn render_rss(title: &str, base_url: &str, items: &[FeedItem]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n<channel>\n");
+    let _ = writeln!(out, "<title>{}</title>", escape_xml(title));
+    let _ = writeln!(out, "<link>{}</link>", escape_xml(base_url));
+    let _ = writeln!(out, "<description>{}</description>", escape_xml(&format!("Latest from {}", title)));
+
+    if let Some(latest) = items.first() {
+        let _ = writeln!(out, "<lastBuildDate>{}</lastBuildDate>", latest.published.to_rfc2822());
+    }
+
+    for item in items {
+        let _ = write!(
+            out,
+            "<item>\n<title>{}</title>\n<link>{}</link>\n<guid isPermaLink=\"false\">{}</guid>\n<pubDate>{}</pubDate>\n<description>{}</description>\n</item>\n",
+            escape_xml(&item.title),
+            escape_xml(&item.link),
+            escape_xml(&item.guid),
+            item.published.to_rfc2822(),
+            escape_xml(&item.description),
+        );
+    }
+
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
\ No newline at end of file
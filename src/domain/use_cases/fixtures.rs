@@ -0,0 +1,209 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        content_fixture::{
+            ContentFixture, FixtureChange, FixtureLoadReport, PostFixture, CONTENT_FIXTURE_FORMAT,
+        },
+        export::StoredExport,
+        tenant::Tenant,
+    },
+    errors::AppError,
+    repositories::{about::AboutRepository, blog_post::BlogPostRepository},
+    settings::AppConfig,
+    storage::object::ObjectStorage,
+};
+
+/// Page size used to walk every post during an export
+const EXPORT_PAGE_SIZE: u32 = 100;
+/// How long the link returned for a stored snapshot works
+const SNAPSHOT_LINK_TTL: Duration = Duration::from_secs(3600);
+
+/// Exports a tenant's content to a fixture and loads fixtures back.
+///
+/// Loading upserts posts by slug and adds an about revision only when its
+/// content differs, so loading the same file twice changes nothing the second
+/// time. Every step is idempotent, which also means a load that failed
+/// halfway can simply be re-run. Loading is refused in production.
+#[derive(Clone)]
+pub struct ContentFixtures<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub blog_post_repo: B,
+    pub about_repo: A,
+    storage: Option<Arc<dyn ObjectStorage>>,
+    loading_allowed: bool,
+}
+
+impl<B, A> ContentFixtures<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub fn new(blog_post_repo: B, about_repo: A, storage: Option<Arc<dyn ObjectStorage>>, config: &AppConfig) -> Self {
+        ContentFixtures {
+            blog_post_repo,
+            about_repo,
+            storage,
+            loading_allowed: !config.is_production(),
+        }
+    }
+
+    pub async fn export(&self, tenant: &Tenant) -> Result<ContentFixture, AppError> {
+        let mut posts = Vec::new();
+        for page in 1.. {
+            let batch = self.blog_post_repo
+                .get_all_blog_posts(&tenant.id, false, page, EXPORT_PAGE_SIZE)
+                .await?;
+            let last_page = batch.len() < EXPORT_PAGE_SIZE as usize;
+            posts.extend(batch.iter().map(PostFixture::from));
+            if last_page {
+                break;
+            }
+        }
+        posts.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        let about = match self.about_repo.get_current_about_me(&tenant.id).await {
+            Ok(about) => Some(about.into()),
+            Err(AppError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(ContentFixture {
+            format: CONTENT_FIXTURE_FORMAT,
+            exported_at: Utc::now(),
+            source_tenant: tenant.slug.clone(),
+            about,
+            posts,
+        })
+    }
+
+    /// Exports into object storage under `exports/<tenant>/` and returns a
+    /// link to the file, so the snapshot outlives the request
+    pub async fn snapshot(&self, tenant: &Tenant) -> Result<StoredExport, AppError> {
+        let storage = self.storage.as_ref()
+            .ok_or_else(|| AppError::ServiceUnavailable("Object storage is not available".to_string()))?;
+
+        let fixture = self.export(tenant).await?;
+        let body = serde_json::to_vec_pretty(&fixture)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode content fixture: {}", e)))?;
+
+        let key = format!(
+            "exports/{}/content-{}.json",
+            tenant.slug,
+            fixture.exported_at.format("%Y%m%d-%H%M%S")
+        );
+        let size = body.len() as u64;
+        storage.put(&key, Bytes::from(body), "application/json").await?;
+
+        let download_url = storage.presign(&key, SNAPSHOT_LINK_TTL).await?;
+        tracing::info!(key = %key, size, "Content fixture snapshot stored");
+
+        Ok(StoredExport {
+            key,
+            size,
+            download_url,
+            expires_at: Utc::now() + SNAPSHOT_LINK_TTL,
+        })
+    }
+
+    /// Applies `fixture` to the tenant; with `dry_run` only reports what would change
+    pub async fn load(&self, tenant_id: Uuid, fixture: &ContentFixture, dry_run: bool) -> Result<FixtureLoadReport, AppError> {
+        if !self.loading_allowed {
+            tracing::warn!("Refused to load a content fixture in production");
+            return Err(AppError::ForbiddenAccess);
+        }
+
+        Self::validate(fixture)?;
+
+        let about = match &fixture.about {
+            None => FixtureChange::Absent,
+            Some(about) => {
+                let current = match self.about_repo.get_current_about_me(&tenant_id).await {
+                    Ok(current) => Some(current),
+                    Err(AppError::NotFound(_)) => None,
+                    Err(e) => return Err(e),
+                };
+
+                if current.is_some_and(|current| about.matches(&current)) {
+                    FixtureChange::Unchanged
+                } else {
+                    if !dry_run {
+                        self.about_repo.create_about_me(&tenant_id, &about.to_insert()).await?;
+                    }
+                    FixtureChange::Created
+                }
+            }
+        };
+
+        let mut report = FixtureLoadReport {
+            dry_run,
+            about,
+            posts_created: Vec::new(),
+            posts_updated: Vec::new(),
+            posts_unchanged: 0,
+        };
+
+        for post in &fixture.posts {
+            match self.blog_post_repo.get_blog_post_by_slug(&tenant_id, &post.slug).await {
+                Ok(existing) if post.matches(&existing) => report.posts_unchanged += 1,
+                Ok(existing) => {
+                    if !dry_run {
+                        self.blog_post_repo.update_blog_post(&tenant_id, &existing.id, &post.to_update()).await?;
+                    }
+                    report.posts_updated.push(post.slug.clone());
+                }
+                Err(AppError::NotFound(_)) => {
+                    if !dry_run {
+                        self.blog_post_repo.create_blog_post(&tenant_id, &post.to_insert()).await?;
+                    }
+                    report.posts_created.push(post.slug.clone());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        tracing::info!(
+            dry_run,
+            posts_created = report.posts_created.len(),
+            posts_updated = report.posts_updated.len(),
+            posts_unchanged = report.posts_unchanged,
+            "Content fixture loaded"
+        );
+
+        Ok(report)
+    }
+
+    /// Checks the whole file up front so a bad entry cannot leave a load half done
+    fn validate(fixture: &ContentFixture) -> Result<(), AppError> {
+        if fixture.format != CONTENT_FIXTURE_FORMAT {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported fixture format {}, this build reads format {}",
+                fixture.format, CONTENT_FIXTURE_FORMAT
+            )));
+        }
+
+        if fixture.about.as_ref().is_some_and(|about| about.content_markdown.trim().is_empty()) {
+            return Err(AppError::InvalidInput("About content cannot be empty".to_string()));
+        }
+
+        let mut seen = HashSet::new();
+        for post in &fixture.posts {
+            if !seen.insert(post.slug.to_lowercase()) {
+                return Err(AppError::InvalidInput(format!("Slug '{}' appears more than once", post.slug)));
+            }
+            post.to_insert()
+                .validate()
+                .map_err(|e| AppError::InvalidInput(format!("Post '{}': {}", post.slug, e)))?;
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
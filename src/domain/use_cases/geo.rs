@@ -0,0 +1,52 @@
+use chrono::Utc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        geo::{GeoCountrySummary, GeoDatabaseInfo, GeoReportQuery, GeoReportResponse},
+        stats_rollup::RollupWindow,
+    },
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    repositories::stats_rollup::StatsRollupRepository,
+};
+
+/// Per-country view of the located traffic: outbound clicks from visitors
+/// and honeytoken hits from scanners. Rows stored while no GeoIP database
+/// was loaded are counted under an unknown country. Counts come from the
+/// daily rollups, with today read from the raw rows.
+#[derive(Clone)]
+pub struct GeoInsights<T>
+where
+    T: StatsRollupRepository,
+{
+    rollup_repo: T,
+    pub locator: GeoLocator,
+}
+
+impl<T> GeoInsights<T>
+where
+    T: StatsRollupRepository,
+{
+    pub fn new(rollup_repo: T, locator: GeoLocator) -> Self {
+        GeoInsights { rollup_repo, locator }
+    }
+
+    pub async fn report(&self, tenant_id: Uuid, query: GeoReportQuery) -> Result<GeoReportResponse, AppError> {
+        query.validate()?;
+
+        let window = RollupWindow::last_days(query.days.unwrap_or(30), Utc::now());
+        let counts = self.rollup_repo.country_counts(&tenant_id, &window).await?;
+
+        Ok(GeoReportResponse {
+            since: window.since(),
+            database: self.locator.info(),
+            countries: GeoCountrySummary::from_counts(counts),
+        })
+    }
+
+    pub async fn reload(&self) -> Result<GeoDatabaseInfo, AppError> {
+        self.locator.reload().await
+    }
+}
\ No newline at end of file
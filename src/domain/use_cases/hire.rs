@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        app_setting::{CONTACT_NOTIFICATION_EMAIL, HIRE_AVAILABILITY, HIRE_NOTIFICATION_EMAIL},
+        hire::{HireAvailability, HireInquiryInsert, HireInquiryListResponse, HireInquiryResponse, NewHireInquiry},
+        outbox::{OutboxEvent, HIRE_INQUIRY_RECEIVED},
+        tenant::{Tenant, DEFAULT_TENANT_ID},
+    },
+    errors::AppError,
+    mailer::email::{EmailMessage, Mailer},
+    metrics::METRICS,
+    repositories::{app_settings::AppSettingsRepository, hire::HireInquiryRepository},
+    settings::AppConfig,
+    use_cases::{
+        outbox::{aggregate_ref, OutboxSubscriber},
+        settings::RuntimeSettings,
+    },
+    utils::{public_urls::PublicUrls, valid_uuid::valid_uuid},
+};
+
+/// "Hire me" availability and inquiry intake.
+///
+/// Availability lives in the `hire_availability` runtime setting. Inquiries
+/// are stored apart from contact messages and always notify immediately;
+/// the recipient is `hire_notification_email`, then the contact recipient.
+#[derive(Clone)]
+pub struct HireHandler<R, S>
+where
+    R: HireInquiryRepository,
+    S: AppSettingsRepository,
+{
+    pub hire_repo: R,
+    pub settings: RuntimeSettings<S>,
+    mailer: Arc<dyn Mailer>,
+    recipient: Option<String>,
+    from: String,
+    urls: PublicUrls,
+}
+
+impl<R, S> HireHandler<R, S>
+where
+    R: HireInquiryRepository,
+    S: AppSettingsRepository,
+{
+    pub fn new(
+        hire_repo: R,
+        settings: RuntimeSettings<S>,
+        mailer: Arc<dyn Mailer>,
+        config: &AppConfig,
+    ) -> Self {
+        HireHandler {
+            hire_repo,
+            settings,
+            mailer,
+            recipient: config.notification_email.clone(),
+            from: config.mail_from.clone(),
+            urls: PublicUrls::from_config(config),
+        }
+    }
+
+    /// Current availability; closed until the tenant publishes one
+    pub fn availability(&self, tenant_id: &Uuid) -> HireAvailability {
+        self.settings.get(tenant_id, HIRE_AVAILABILITY).unwrap_or_default()
+    }
+
+    /// Stores an inquiry while availability is open.
+    ///
+    /// Spam is stored flagged rather than rejected so senders get no signal.
+    pub async fn submit_inquiry(
+        &self,
+        tenant_id: Uuid,
+        request: NewHireInquiry,
+    ) -> Result<HireInquiryResponse, AppError> {
+        request.validate()?;
+
+        if !self.availability(&tenant_id).is_open() {
+            return Err(AppError::Conflict("Not accepting new work at the moment".to_string()));
+        }
+
+        let inquiry = HireInquiryInsert::from(request);
+        if inquiry.is_spam {
+            METRICS.incr("hire_inquiries_spam_total");
+        }
+
+        let id = self.hire_repo.create_inquiry(&tenant_id, &inquiry).await?;
+
+        Ok(HireInquiryResponse {
+            message: "Your inquiry has been received.".to_string(),
+            id,
+        })
+    }
+
+    pub async fn list_inquiries(&self, tenant_id: Uuid) -> Result<HireInquiryListResponse, AppError> {
+        let inquiries = self.hire_repo.list_inquiries(&tenant_id).await?;
+        let total = self.hire_repo.count_inquiries(&tenant_id).await?;
+
+        Ok(HireInquiryListResponse { inquiries, total })
+    }
+
+    pub async fn delete_inquiry(&self, tenant_id: Uuid, id: &str) -> Result<(), AppError> {
+        let valid_id = valid_uuid(id)?;
+
+        self.hire_repo.delete_inquiry(&tenant_id, &valid_id).await
+    }
+
+    fn recipient(&self, tenant_id: &Uuid) -> Option<String> {
+        self.settings
+            .get(tenant_id, HIRE_NOTIFICATION_EMAIL)
+            .or_else(|| self.settings.get(tenant_id, CONTACT_NOTIFICATION_EMAIL))
+            .or_else(|| (*tenant_id == DEFAULT_TENANT_ID).then(|| self.recipient.clone()).flatten())
+    }
+
+    /// Emails the owner about a new inquiry; spam is never forwarded
+    pub async fn inquiry_received(&self, tenant: &Tenant, id: Uuid) -> Result<(), AppError> {
+        let Some(recipient) = self.recipient(&tenant.id) else {
+            return Ok(());
+        };
+
+        let inquiry = self.hire_repo.get_inquiry(&tenant.id, &id).await?;
+        if inquiry.is_spam {
+            return Ok(());
+        }
+
+        let text = format!(
+            "{} <{}> from {} is looking to hire.\n\nRole: {}\nBudget: {}\nTimeline: {}\n\n{}\n\nView: {}\n",
+            inquiry.name,
+            inquiry.email,
+            inquiry.company,
+            inquiry.role.as_deref().unwrap_or("(not specified)"),
+            inquiry.budget,
+            inquiry.timeline,
+            inquiry.message,
+            self.urls.site(tenant, &format!("/admin/hire-inquiries/{}", inquiry.id)),
+        );
+
+        self.mailer.send(&EmailMessage {
+            from: self.from.clone(),
+            to: recipient,
+            subject: format!("New hire inquiry from {} ({})", inquiry.name, inquiry.company),
+            text,
+            html: None,
+            headers: Default::default(),
+        }).await
+    }
+}
+
+#[async_trait]
+impl<R, S> OutboxSubscriber for HireHandler<R, S>
+where
+    R: HireInquiryRepository,
+    S: AppSettingsRepository,
+{
+    fn event_types(&self) -> &'static [&'static str] {
+        &[HIRE_INQUIRY_RECEIVED]
+    }
+
+    async fn deliver(&self, tenant: &Tenant, event: &OutboxEvent) -> Result<(), AppError> {
+        let inquiry = aggregate_ref(event)?;
+        match self.inquiry_received(tenant, inquiry.id).await {
+            // Deleted before we got to it; nothing left to announce
+            Err(AppError::NotFound(_)) => Ok(()),
+            result => result,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::security_event::{HoneytokenReportResponse, SecurityEventInsert, SecurityReportQuery, HONEYTOKEN_HIT},
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    limiter::ip_ban::IpBanList,
+    metrics::METRICS,
+    repositories::security_event::SecurityEventRepository,
+};
+
+/// Records scanner hits on decoy paths and, when `honeytoken_ban_secs` is
+/// set, bans the source IP so it gets 429s everywhere for that long.
+#[derive(Clone)]
+pub struct HoneytokenMonitor<R>
+where
+    R: SecurityEventRepository,
+{
+    pub event_repo: R,
+    pub bans: IpBanList,
+    geo: GeoLocator,
+}
+
+impl<R> HoneytokenMonitor<R>
+where
+    R: SecurityEventRepository,
+{
+    pub fn new(event_repo: R, bans: IpBanList, geo: GeoLocator) -> Self {
+        HoneytokenMonitor { event_repo, bans, geo }
+    }
+
+    /// Bans the IP (if enabled) and builds the event to store; returns `(event, banned)`
+    pub fn hit(&self, ip: &str, user_agent: Option<String>, method: &str, path: &str) -> (SecurityEventInsert, bool) {
+        METRICS.incr("honeytoken_hits_total");
+
+        let banned = self.bans.ban(ip);
+        if banned {
+            METRICS.incr("honeytoken_bans_total");
+        }
+
+        let location = self.geo.lookup(ip).unwrap_or_default();
+        let event = SecurityEventInsert {
+            kind: HONEYTOKEN_HIT.to_string(),
+            ip: ip.to_string(),
+            user_agent: user_agent.map(|ua| ua.chars().take(512).collect()),
+            method: method.to_string(),
+            path: path.to_string(),
+            occurred_at: Utc::now(),
+            country_code: location.country_code,
+            region: location.region,
+            details: None,
+        };
+
+        (event, banned)
+    }
+
+    pub async fn record(&self, tenant_id: Uuid, event: &SecurityEventInsert) -> Result<(), AppError> {
+        self.event_repo.record_event(&tenant_id, event).await
+    }
+
+    pub async fn report(&self, tenant_id: Uuid, query: SecurityReportQuery) -> Result<HoneytokenReportResponse, AppError> {
+        query.validate()?;
+
+        let since = Utc::now() - Duration::days(query.days.unwrap_or(7) as i64);
+        let limit = query.limit.unwrap_or(20) as i64;
+        let kind = HONEYTOKEN_HIT.to_string();
+
+        let (total_hits, unique_ips) = self.event_repo.event_totals(&tenant_id, kind.clone(), since).await?;
+        let top_ips = self.event_repo.ip_summary(&tenant_id, kind.clone(), since, limit).await?;
+        let top_paths = self.event_repo.path_summary(&tenant_id, kind, since, limit).await?;
+
+        Ok(HoneytokenReportResponse {
+            since,
+            total_hits,
+            unique_ips,
+            ban_duration_secs: self.bans.is_enabled().then(|| self.bans.duration().as_secs()),
+            top_ips,
+            top_paths,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use uuid::Uuid;
+
+use crate::{
+    constants::RETRY_AFTER_SECS,
+    entities::contact_me::ContactMeFormInsert,
+    errors::AppError,
+    limiter::headers::RateLimitStatus,
+    metrics::METRICS,
+};
+
+/// A contact submission accepted by the endpoint, waiting to be written
+#[derive(Debug)]
+pub struct QueuedContact {
+    pub tenant_id: Uuid,
+    pub message: ContactMeFormInsert,
+}
+
+/// Bounded hand-off between the contact endpoint and the writer task.
+///
+/// Requests never wait for the database: a submission either gets one of
+/// `capacity` slots or is shed straight away, so a spam flood fills the
+/// queue instead of tying up pool connections. The current depth is kept
+/// in the `contact_queue_depth` metric.
+#[derive(Clone)]
+pub struct ContactIngestQueue {
+    tx: Sender<QueuedContact>,
+    rx: Arc<Mutex<Option<Receiver<QueuedContact>>>>,
+}
+
+impl ContactIngestQueue {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        ContactIngestQueue { tx, rx: Arc::new(Mutex::new(Some(rx))) }
+    }
+
+    /// Queues the submission; `TooManyRequests` when every slot is taken
+    pub fn push(&self, tenant_id: Uuid, message: ContactMeFormInsert) -> Result<(), AppError> {
+        let result = self.tx.try_send(QueuedContact { tenant_id, message });
+        self.record_depth();
+
+        match result {
+            Ok(()) => {
+                METRICS.incr("contact_queue_accepted_total");
+                Ok(())
+            }
+            Err(TrySendError::Full(_)) => {
+                METRICS.incr("contact_queue_shed_total");
+                Err(AppError::TooManyRequests("Too many messages right now. Please try again shortly.".to_string()))
+            }
+            Err(TrySendError::Closed(_)) => {
+                Err(AppError::ServiceUnavailable("Contact messages are not being accepted right now".to_string()))
+            }
+        }
+    }
+
+    /// Slots in the queue
+    pub fn capacity(&self) -> usize {
+        self.tx.max_capacity()
+    }
+
+    /// The `RateLimit-*` status a shed submission is answered with: every
+    /// slot taken, worth retrying as soon as the writer frees one
+    pub fn shed_status(&self) -> RateLimitStatus {
+        RateLimitStatus::exhausted(self.capacity() as u64, RETRY_AFTER_SECS)
+    }
+
+    /// Submissions waiting for the writer
+    pub fn depth(&self) -> usize {
+        self.tx.max_capacity() - self.tx.capacity()
+    }
+
+    pub fn record_depth(&self) {
+        METRICS.set("contact_queue_depth", self.depth() as u64);
+    }
+
+    /// Hands the receiving end to the writer task; `None` once taken
+    pub fn take_receiver(&self) -> Option<Receiver<QueuedContact>> {
+        self.rx.lock().take()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::outbox::{
+        JobListQuery, JobListResponse, JobStatsQuery, JobStatsResponse, JobStatus, JobSummary, JobTypeCounts,
+        JobTypeStats, OutboxEvent, PausedQueue, OUTBOX_EVENT_TYPES,
+    },
+    errors::AppError,
+    repositories::outbox::OutboxRepository,
+    utils::{redact::Redactor, valid_uuid::valid_uuid},
+};
+
+const DEFAULT_STATS_DAYS: u32 = 7;
+/// Payload keys shown before the summary is cut short
+const SUMMARY_MAX_KEYS: usize = 6;
+const SUMMARY_MAX_VALUE_CHARS: usize = 40;
+
+/// Admin view of the outbox as a job queue: lists events by state, retries
+/// or cancels single events, pauses delivery of an event type for the site
+/// and reports per-type success rates.
+///
+/// Callers nudge the relay after a retry or resume so the event does not
+/// wait for the next tick.
+#[derive(Clone)]
+pub struct JobDashboard<O>
+where
+    O: OutboxRepository,
+{
+    pub outbox: O,
+    redactor: Redactor,
+}
+
+impl<O> JobDashboard<O>
+where
+    O: OutboxRepository,
+{
+    pub fn new(outbox: O) -> Self {
+        JobDashboard { outbox, redactor: Redactor::with_defaults() }
+    }
+
+    pub async fn list(&self, tenant_id: Uuid, query: JobListQuery) -> Result<JobListResponse, AppError> {
+        query.validate()?;
+
+        let page = query.page.unwrap_or(1);
+        let per_page = query.per_page.unwrap_or(20);
+        let event_type = query.event_type.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        let offset = (page as i64 - 1) * per_page as i64;
+        let now = Utc::now();
+
+        let events = self.outbox
+            .list_jobs(&tenant_id, query.status, event_type.clone(), now, per_page as i64, offset)
+            .await?;
+        let total = self.outbox.count_jobs(&tenant_id, query.status, event_type, now).await?;
+
+        Ok(JobListResponse {
+            jobs: events.into_iter().map(|event| self.summarize(event)).collect(),
+            total,
+            page,
+            per_page,
+        })
+    }
+
+    /// Queues a failed, cancelled or backing-off event to run now
+    pub async fn retry(&self, tenant_id: Uuid, id: &str) -> Result<JobSummary, AppError> {
+        let id = valid_uuid(id)?;
+
+        match self.outbox.retry_job(&tenant_id, &id, Utc::now()).await? {
+            Some(event) => Ok(self.summarize(event)),
+            None => Err(self.refusal(&tenant_id, &id, "retried").await),
+        }
+    }
+
+    /// Parks a queued event; running and settled ones are left alone
+    pub async fn cancel(&self, tenant_id: Uuid, id: &str, admin: &str) -> Result<JobSummary, AppError> {
+        let id = valid_uuid(id)?;
+        let reason = format!("Cancelled by admin {}", admin);
+
+        match self.outbox.cancel_job(&tenant_id, &id, &reason, Utc::now()).await? {
+            Some(event) => Ok(self.summarize(event)),
+            None => Err(self.refusal(&tenant_id, &id, "cancelled").await),
+        }
+    }
+
+    /// Why a retry or cancel did not apply
+    async fn refusal(&self, tenant_id: &Uuid, id: &Uuid, action: &str) -> AppError {
+        match self.outbox.get_job(tenant_id, id).await {
+            Ok(Some(event)) => AppError::Conflict(format!(
+                "A {} job can't be {}",
+                event.status(Utc::now()).as_str(),
+                action
+            )),
+            Ok(None) => AppError::NotFound("Job not found".to_string()),
+            Err(e) => e,
+        }
+    }
+
+    /// Every known event type, plus any other with events in the window
+    pub async fn stats(&self, tenant_id: Uuid, query: JobStatsQuery) -> Result<JobStatsResponse, AppError> {
+        query.validate()?;
+
+        let now = Utc::now();
+        let since = now - Duration::days(query.days.unwrap_or(DEFAULT_STATS_DAYS) as i64);
+        let mut counts = self.outbox.job_counts(&tenant_id, since, now).await?;
+        let mut paused = self.outbox.list_paused_queues(&tenant_id).await?;
+
+        for event_type in OUTBOX_EVENT_TYPES {
+            if !counts.iter().any(|c| c.event_type == *event_type) {
+                counts.push(JobTypeCounts { event_type: event_type.to_string(), ..Default::default() });
+            }
+        }
+        counts.sort_by(|a, b| a.event_type.cmp(&b.event_type));
+
+        let job_types = counts
+            .into_iter()
+            .map(|counts| {
+                let queue = paused
+                    .iter()
+                    .position(|p| p.event_type == counts.event_type)
+                    .map(|i| paused.swap_remove(i));
+                JobTypeStats::new(counts, queue)
+            })
+            .collect();
+
+        Ok(JobStatsResponse { since, job_types })
+    }
+
+    /// Leaves events of `event_type` queued until it is resumed
+    pub async fn pause(&self, tenant_id: Uuid, event_type: &str, admin: &str) -> Result<PausedQueue, AppError> {
+        let event_type = known_event_type(event_type)?;
+        self.outbox.pause_queue(&tenant_id, event_type, admin).await
+    }
+
+    /// `false` when the queue was not paused
+    pub async fn resume(&self, tenant_id: Uuid, event_type: &str) -> Result<bool, AppError> {
+        let event_type = known_event_type(event_type)?;
+        self.outbox.resume_queue(&tenant_id, event_type).await
+    }
+
+    fn summarize(&self, event: OutboxEvent) -> JobSummary {
+        let status = event.status(Utc::now());
+        let mut payload = event.payload;
+        self.redactor.redact_json(&mut payload);
+
+        JobSummary {
+            id: event.id,
+            event_type: event.event_type,
+            status,
+            payload_summary: summarize_payload(&payload),
+            attempts: event.attempts,
+            last_error: event.last_error.map(|e| self.redactor.redact_text(&e)),
+            next_attempt_at: (status == JobStatus::Queued).then_some(event.next_attempt_at),
+            created_at: event.created_at,
+            delivered_at: event.delivered_at,
+            failed_at: event.failed_at.filter(|_| event.cancelled_at.is_none()),
+            cancelled_at: event.cancelled_at,
+        }
+    }
+}
+
+fn known_event_type(event_type: &str) -> Result<&'static str, AppError> {
+    OUTBOX_EVENT_TYPES
+        .iter()
+        .find(|t| **t == event_type)
+        .copied()
+        .ok_or_else(|| AppError::NotFound(format!("Unknown job type '{}'", event_type)))
+}
+
+/// `key=value` pairs of the top-level fields, with nested values reduced to
+/// their shape: `{"id": 7, "tags": ["a", "b"]}` reads `id=7, tags=[2 items]`
+pub fn summarize_payload(payload: &Value) -> String {
+    let Value::Object(fields) = payload else {
+        return summarize_value(payload);
+    };
+
+    let mut parts: Vec<String> = fields
+        .iter()
+        .take(SUMMARY_MAX_KEYS)
+        .map(|(key, value)| format!("{}={}", key, summarize_value(value)))
+        .collect();
+    if fields.len() > SUMMARY_MAX_KEYS {
+        parts.push(format!("… {} more", fields.len() - SUMMARY_MAX_KEYS));
+    }
+    parts.join(", ")
+}
+
+fn summarize_value(value: &Value) -> String {
+    match value {
+        Value::String(text) if text.chars().count() > SUMMARY_MAX_VALUE_CHARS => {
+            format!("{}…", text.chars().take(SUMMARY_MAX_VALUE_CHARS).collect::<String>())
+        }
+        Value::String(text) => text.clone(),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(fields) => format!("{{{} fields}}", fields.len()),
+        other => other.to_string(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    entities::link_check::{BrokenLinksReport, LinkCheckInsert, LINK_SOURCE_ABOUT, LINK_SOURCE_POST},
+    errors::AppError,
+    links::probe::{is_public_http_url, LinkProbe, LinkProber},
+    metrics::METRICS,
+    repositories::{about::AboutRepository, blog_post::BlogPostRepository, link_check::LinkCheckRepository},
+    settings::AppConfig,
+    utils::markdown::external_links,
+};
+
+/// Posts read per page while collecting links
+const POSTS_PAGE_SIZE: u32 = 100;
+const ABOUT_TITLE: &str = "About me";
+
+/// Published content with the external links found in it
+struct LinkSource {
+    source_type: &'static str,
+    id: Uuid,
+    title: String,
+    urls: Vec<String>,
+}
+
+/// Counts from one tenant's run
+#[derive(Debug, Default)]
+pub struct LinkCheckRun {
+    pub checked: usize,
+    pub broken: usize,
+}
+
+/// Periodically requests every external link in published posts and the
+/// about page, keeping the latest result per link for the admin report.
+///
+/// Each distinct URL is requested once per run, at most `concurrency` at a
+/// time. Links pointing at private addresses are never requested.
+#[derive(Clone)]
+pub struct LinkChecker<L, B, A>
+where
+    L: LinkCheckRepository,
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub link_check_repo: L,
+    blog_repo: B,
+    about_repo: A,
+    prober: Arc<dyn LinkProber>,
+    concurrency: usize,
+}
+
+impl<L, B, A> LinkChecker<L, B, A>
+where
+    L: LinkCheckRepository,
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub fn new(link_check_repo: L, blog_repo: B, about_repo: A, prober: Arc<dyn LinkProber>, config: &AppConfig) -> Self {
+        LinkChecker {
+            link_check_repo,
+            blog_repo,
+            about_repo,
+            prober,
+            concurrency: config.link_check_concurrency.max(1),
+        }
+    }
+
+    /// Checks every link in the tenant's published content and replaces its stored results
+    pub async fn check_tenant(&self, tenant_id: Uuid) -> Result<LinkCheckRun, AppError> {
+        let started = Utc::now();
+        let sources = self.collect_sources(tenant_id).await?;
+
+        let mut urls: Vec<String> = sources.iter().flat_map(|s| s.urls.iter().cloned()).collect();
+        urls.sort_unstable();
+        urls.dedup();
+
+        // Owned URLs and prober keep the futures free of borrows, so the
+        // run can be spawned onto the runtime
+        let probes: HashMap<String, LinkProbe> = stream::iter(urls)
+            .map(|url| {
+                let prober = Arc::clone(&self.prober);
+                async move {
+                    let probe = prober.probe(&url).await;
+                    (url, probe)
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut run = LinkCheckRun::default();
+        let mut checks = Vec::new();
+        for source in &sources {
+            for url in &source.urls {
+                let Some(probe) = probes.get(url) else {
+                    continue;
+                };
+                let broken = probe.is_broken();
+                run.checked += 1;
+                run.broken += usize::from(broken);
+
+                checks.push(LinkCheckInsert {
+                    source_type: source.source_type,
+                    source_id: source.id,
+                    source_title: source.title.clone(),
+                    url: url.clone(),
+                    status_code: probe.status_code.map(i32::from),
+                    error: probe.error.clone(),
+                    broken,
+                });
+            }
+        }
+
+        self.link_check_repo.record_link_checks(&tenant_id, &checks, started).await?;
+        METRICS.incr("link_check_runs_total");
+
+        Ok(run)
+    }
+
+    pub async fn broken_links_report(&self, tenant_id: Uuid) -> Result<BrokenLinksReport, AppError> {
+        let broken = self.link_check_repo.list_broken_links(&tenant_id).await?;
+        Ok(BrokenLinksReport::from_checks(broken))
+    }
+
+    async fn collect_sources(&self, tenant_id: Uuid) -> Result<Vec<LinkSource>, AppError> {
+        let mut sources = Vec::new();
+
+        for page in 1.. {
+            let posts = self.blog_repo.get_all_blog_posts(&tenant_id, true, page, POSTS_PAGE_SIZE).await?;
+            let last_page = posts.len() < POSTS_PAGE_SIZE as usize;
+
+            sources.extend(posts.into_iter().map(|post| LinkSource {
+                source_type: LINK_SOURCE_POST,
+                id: post.id,
+                urls: checkable_links(&post.content_markdown),
+                title: post.title,
+            }));
+            if last_page {
+                break;
+            }
+        }
+
+        match self.about_repo.get_current_about_me(&tenant_id).await {
+            Ok(about) => sources.push(LinkSource {
+                source_type: LINK_SOURCE_ABOUT,
+                id: about.id,
+                title: ABOUT_TITLE.to_string(),
+                urls: checkable_links(&about.content_markdown),
+            }),
+            Err(AppError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(sources)
+    }
+}
+
+fn checkable_links(markdown: &str) -> Vec<String> {
+    external_links(markdown)
+        .into_iter()
+        .filter(|link| Url::parse(link).is_ok_and(|url| is_public_http_url(&url)))
+        .collect()
+}
\ No newline at end of file
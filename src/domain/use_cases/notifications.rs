@@ -0,0 +1,344 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use validator::ValidateEmail;
+
+use crate::{
+    crypto::field_cipher::FieldCipher,
+    entities::{
+        app_setting::{
+            NotificationPolicy, CONTACT_AUTO_REPLY, CONTACT_DIGEST_LAST_SENT_AT, CONTACT_NOTIFICATION_EMAIL,
+            CONTACT_NOTIFICATION_POLICY, EMAIL_LOCALE,
+        },
+        contact_me::{ContactAutoReply, ContactMeExportQuery, ContactMeMessage},
+        notification::{Notification, NotificationEvent},
+        outbox::{OutboxEvent, CONTACT_MESSAGE_RECEIVED},
+        tenant::{Tenant, DEFAULT_TENANT_ID},
+    },
+    errors::AppError,
+    mailer::{
+        email::{EmailMessage, Mailer},
+        templates::{ContactAutoReplyEmail, ContactNotificationEmail, EmailBrand, EmailTemplate, Locale},
+    },
+    repositories::{app_settings::AppSettingsRepository, contact_me::ContactMeRepository},
+    settings::AppConfig,
+    use_cases::{
+        contact::open_contact_message,
+        dispatcher::NotificationSink,
+        outbox::{aggregate_ref, OutboxSubscriber},
+        settings::RuntimeSettings,
+    },
+    utils::public_urls::PublicUrls,
+};
+
+const DIGEST_CHUNK_SIZE: i64 = 200;
+/// Messages listed individually in a digest; the rest are summarized as a count
+const DIGEST_MAX_LISTED: usize = 50;
+/// Local parts of mailboxes that are never auto-replied to, so two
+/// responders can't answer each other forever
+const AUTOMATED_MAILBOXES: [&str; 6] = ["noreply", "no-reply", "donotreply", "mailer-daemon", "postmaster", "bounce"];
+
+/// Tells the site owner about new contact messages, either one email per
+/// message or as hourly/daily digests depending on the runtime policy.
+///
+/// Policy and recipient are per-tenant settings. The configured
+/// `notification_email` and `public_base_url` only serve the default tenant.
+/// Every message is also raised as `contact_received` for the admins'
+/// own notification preferences, whatever the policy. When the
+/// `contact_auto_reply` setting is on, the sender gets an acknowledgment.
+#[derive(Clone)]
+pub struct ContactNotifier<R, S>
+where
+    R: ContactMeRepository,
+    S: AppSettingsRepository,
+{
+    pub contact_repo: R,
+    pub settings: RuntimeSettings<S>,
+    mailer: Arc<dyn Mailer>,
+    alerts: Arc<dyn NotificationSink>,
+    cipher: FieldCipher,
+    recipient: Option<String>,
+    from: String,
+    urls: PublicUrls,
+    default_policy: NotificationPolicy,
+}
+
+impl<R, S> ContactNotifier<R, S>
+where
+    R: ContactMeRepository,
+    S: AppSettingsRepository,
+{
+    pub fn new(
+        contact_repo: R,
+        settings: RuntimeSettings<S>,
+        mailer: Arc<dyn Mailer>,
+        alerts: Arc<dyn NotificationSink>,
+        cipher: FieldCipher,
+        config: &AppConfig,
+    ) -> Self {
+        ContactNotifier {
+            contact_repo,
+            settings,
+            mailer,
+            alerts,
+            cipher,
+            recipient: config.notification_email.clone(),
+            from: config.mail_from.clone(),
+            urls: PublicUrls::from_config(config),
+            default_policy: config.contact_notification_policy,
+        }
+    }
+
+    /// Runtime setting wins over the configured default
+    pub fn policy(&self, tenant_id: &Uuid) -> NotificationPolicy {
+        self.settings
+            .get(tenant_id, CONTACT_NOTIFICATION_POLICY)
+            .unwrap_or(self.default_policy)
+    }
+
+    fn recipient(&self, tenant_id: &Uuid) -> Option<String> {
+        self.settings
+            .get(tenant_id, CONTACT_NOTIFICATION_EMAIL)
+            .or_else(|| (*tenant_id == DEFAULT_TENANT_ID).then(|| self.recipient.clone()).flatten())
+    }
+
+    /// Notifies the admins, then sends an immediate notification if that is
+    /// the active policy
+    pub async fn message_received(&self, tenant: &Tenant, id: Uuid) -> Result<(), AppError> {
+        let msg = self.contact_repo.get_contact_message_by_id(&tenant.id, &id).await?;
+        let msg = open_contact_message(&self.cipher, msg);
+
+        let alert = Notification::new(
+            NotificationEvent::ContactReceived,
+            format!("New contact message from {}", msg.name),
+            format!("{} <{}>: {}", msg.name, msg.email, msg.subject.as_deref().unwrap_or("(no subject)")),
+        )
+        .with_link(self.message_link(tenant, &msg.id));
+        self.alerts.notify(tenant.id, alert).await;
+
+        // The throttle slot is taken before sending, so a failed reply is not
+        // retried with the delivery; the owner's notification never waits on it
+        if let Err(e) = self.send_auto_reply(tenant, &msg).await {
+            tracing::warn!(message_id = %msg.id, "Contact auto-reply failed: {}", e);
+        }
+
+        if self.policy(&tenant.id) != NotificationPolicy::Immediate {
+            return Ok(());
+        }
+        let Some(recipient) = self.recipient(&tenant.id) else {
+            return Ok(());
+        };
+
+        let email = ContactNotificationEmail {
+            view_url: self.message_link(tenant, &msg.id),
+            from_name: msg.name,
+            from_email: msg.email,
+            subject: msg.subject,
+            message: msg.message,
+        };
+        let locale = self.settings.get::<Locale>(&tenant.id, EMAIL_LOCALE).unwrap_or_default();
+        let rendered = email.render(&self.brand(tenant), locale)?;
+
+        self.mailer.send(&EmailMessage {
+            from: self.from.clone(),
+            to: recipient,
+            subject: rendered.subject,
+            text: rendered.text,
+            html: Some(rendered.html),
+            headers: Default::default(),
+        }).await
+    }
+
+    /// Acknowledges the sender when the auto-reply is on, at most once per
+    /// address per throttle window. Spam, our own addresses and automated
+    /// mailboxes never get one. Returns whether a reply went out.
+    pub async fn send_auto_reply(&self, tenant: &Tenant, msg: &ContactMeMessage) -> Result<bool, AppError> {
+        let Some(auto_reply) = self.settings
+            .get::<ContactAutoReply>(&tenant.id, CONTACT_AUTO_REPLY)
+            .filter(|auto_reply| auto_reply.enabled)
+        else {
+            return Ok(false);
+        };
+
+        let sender = msg.email.trim();
+        let is_ours = sender.eq_ignore_ascii_case(&self.from)
+            || self.recipient(&tenant.id).is_some_and(|own| sender.eq_ignore_ascii_case(&own));
+        if msg.is_spam || is_ours || !sender.validate_email() || is_automated_address(sender) {
+            return Ok(false);
+        }
+
+        let now = Utc::now();
+        let not_before = now - Duration::hours(auto_reply.throttle_hours as i64);
+        if !self.contact_repo.claim_auto_reply(&tenant.id, &auto_reply_key(&msg.email), now, not_before).await? {
+            tracing::debug!(message_id = %msg.id, "Contact auto-reply throttled");
+            return Ok(false);
+        }
+
+        let email = ContactAutoReplyEmail {
+            name: msg.name.clone(),
+            message: auto_reply.message_for(&msg.name),
+            response_time: auto_reply.response_time,
+            subject: auto_reply.subject,
+            original_subject: msg.subject.clone(),
+        };
+        let locale = self.settings.get::<Locale>(&tenant.id, EMAIL_LOCALE).unwrap_or_default();
+        let rendered = email.render(&self.brand(tenant), locale)?;
+
+        self.mailer.send(&EmailMessage {
+            from: self.from.clone(),
+            to: msg.email.clone(),
+            subject: rendered.subject,
+            text: rendered.text,
+            html: Some(rendered.html),
+            // RFC 3834; well-behaved responders do not answer these
+            headers: BTreeMap::from([
+                ("Auto-Submitted".to_string(), "auto-replied".to_string()),
+                ("X-Auto-Response-Suppress".to_string(), "All".to_string()),
+            ]),
+        }).await?;
+
+        Ok(true)
+    }
+
+    /// Sends a digest when one is due under the current policy.
+    /// Returns the number of messages summarized, or `None` if nothing was due.
+    pub async fn send_digest_if_due(&self, tenant: &Tenant) -> Result<Option<usize>, AppError> {
+        let now = Utc::now();
+
+        let Some(interval) = self.policy(&tenant.id).digest_interval() else {
+            // Keep the marker current so switching to a digest never resends old messages
+            self.settings.set(tenant.id, CONTACT_DIGEST_LAST_SENT_AT, &now).await?;
+            return Ok(None);
+        };
+
+        let Some(last_sent) = self.settings.get::<DateTime<Utc>>(&tenant.id, CONTACT_DIGEST_LAST_SENT_AT) else {
+            self.settings.set(tenant.id, CONTACT_DIGEST_LAST_SENT_AT, &now).await?;
+            return Ok(None);
+        };
+
+        if now - last_sent < interval {
+            return Ok(None);
+        }
+
+        let messages = self.collect_since(&tenant.id, last_sent, now).await?;
+
+        if let Some(recipient) = self.recipient(&tenant.id)
+            && !messages.is_empty()
+        {
+            self.mailer.send(&EmailMessage {
+                from: self.from.clone(),
+                to: recipient,
+                subject: format!("{} new contact message(s)", messages.len()),
+                text: self.digest_body(tenant, &messages, last_sent, now),
+                html: None,
+                headers: Default::default(),
+            }).await?;
+        }
+
+        self.settings.set(tenant.id, CONTACT_DIGEST_LAST_SENT_AT, &now).await?;
+        Ok(Some(messages.len()))
+    }
+
+    async fn collect_since(
+        &self,
+        tenant_id: &Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ContactMeMessage>, AppError> {
+        let filter = ContactMeExportQuery {
+            from: Some(from),
+            to: Some(to),
+            spam: Some(false),
+            include_pii: true,
+        };
+
+        let mut messages = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let chunk = self.contact_repo
+                .list_contact_messages_chunk(tenant_id, &filter, cursor, DIGEST_CHUNK_SIZE)
+                .await?;
+            let done = (chunk.len() as i64) < DIGEST_CHUNK_SIZE;
+            cursor = chunk.last().map(|m| (m.created_at, m.id));
+            messages.extend(chunk.into_iter().map(|msg| open_contact_message(&self.cipher, msg)));
+
+            if done {
+                return Ok(messages);
+            }
+        }
+    }
+
+    fn digest_body(&self, tenant: &Tenant, messages: &[ContactMeMessage], from: DateTime<Utc>, to: DateTime<Utc>) -> String {
+        let mut body = format!(
+            "{} new contact message(s) between {} and {}:\n\n",
+            messages.len(),
+            from.format("%Y-%m-%d %H:%M UTC"),
+            to.format("%Y-%m-%d %H:%M UTC"),
+        );
+
+        for msg in messages.iter().take(DIGEST_MAX_LISTED) {
+            let _ = writeln!(
+                body,
+                "- {} <{}>: {}\n  {}",
+                msg.name,
+                msg.email,
+                msg.subject.as_deref().unwrap_or("(no subject)"),
+                self.message_link(tenant, &msg.id)
+            );
+        }
+
+        if messages.len() > DIGEST_MAX_LISTED {
+            let _ = writeln!(body, "\n...and {} more.", messages.len() - DIGEST_MAX_LISTED);
+        }
+
+        body
+    }
+
+    fn message_link(&self, tenant: &Tenant, id: &Uuid) -> String {
+        self.urls.site(tenant, &format!("/admin/contact-messages/{}", id))
+    }
+
+    fn brand(&self, tenant: &Tenant) -> EmailBrand {
+        let site_url = self.urls.site_origin(tenant);
+        EmailBrand {
+            name: tenant.name.clone(),
+            site_url: (!site_url.is_empty()).then_some(site_url),
+        }
+    }
+}
+
+/// Mailboxes run by software rather than people: `noreply@`, `bounce+x@`, ...
+pub fn is_automated_address(email: &str) -> bool {
+    let local = email.trim().split('@').next().unwrap_or_default().to_ascii_lowercase();
+    let local = local.split('+').next().unwrap_or_default();
+    AUTOMATED_MAILBOXES.contains(&local)
+}
+
+/// Throttle key for an address; the address itself is not stored
+fn auto_reply_key(email: &str) -> String {
+    format!("{:x}", Sha256::digest(email.trim().to_lowercase().as_bytes()))
+}
+
+#[async_trait]
+impl<R, S> OutboxSubscriber for ContactNotifier<R, S>
+where
+    R: ContactMeRepository,
+    S: AppSettingsRepository,
+{
+    fn event_types(&self) -> &'static [&'static str] {
+        &[CONTACT_MESSAGE_RECEIVED]
+    }
+
+    async fn deliver(&self, tenant: &Tenant, event: &OutboxEvent) -> Result<(), AppError> {
+        let message = aggregate_ref(event)?;
+        match self.message_received(tenant, message.id).await {
+            // Deleted before we got to it; nothing left to announce
+            Err(AppError::NotFound(_)) => Ok(()),
+            result => result,
+        }
+    }
+}
\ No newline at end of file
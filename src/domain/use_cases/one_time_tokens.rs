@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    cache::one_time_tokens::OneTimeTokenCache,
+    entities::one_time_token::{hash_one_time_token, OneTimeToken, OneTimeTokenInsert},
+    errors::AppError,
+    metrics::METRICS,
+    repositories::one_time_token::OneTimeTokenRepository,
+};
+
+/// Email verification and password reset tokens.
+///
+/// Only the SHA-256 of a token is stored. Postgres is the source of truth,
+/// so a link keeps working across Redis restarts and a failed change can
+/// release its token for a retry. Redis only answers lookups of unspent
+/// tokens; its errors are logged and the table is asked instead.
+#[derive(Clone)]
+pub struct OneTimeTokens<R>
+where
+    R: OneTimeTokenRepository,
+{
+    pub token_repo: R,
+    cache: Option<Arc<dyn OneTimeTokenCache>>,
+}
+
+impl<R> OneTimeTokens<R>
+where
+    R: OneTimeTokenRepository,
+{
+    pub fn new(token_repo: R, cache: Option<Arc<dyn OneTimeTokenCache>>) -> Self {
+        OneTimeTokens { token_repo, cache }
+    }
+
+    /// Stores a new token for the user and returns it; the user's earlier
+    /// unused tokens for `purpose` stop working
+    pub async fn issue(&self, tenant_id: &Uuid, user_id: Uuid, purpose: &'static str, ttl: Duration) -> Result<String, AppError> {
+        let token = format!("{:032x}{:032x}", rand::random::<u128>(), rand::random::<u128>());
+        let stored = self.token_repo
+            .create_one_time_token(tenant_id, &OneTimeTokenInsert::new(user_id, purpose, &token, ttl))
+            .await?;
+
+        self.cache_put(&stored).await;
+        METRICS.incr("one_time_tokens_issued_total");
+
+        Ok(token)
+    }
+
+    /// The token if it is unspent, unexpired and for `purpose`, without spending it
+    pub async fn peek(&self, tenant_id: &Uuid, purpose: &str, token: &str) -> Result<Option<OneTimeToken>, AppError> {
+        let token_hash = hash_one_time_token(token);
+        let now = Utc::now();
+
+        if let Some(cached) = self.cache_get(&token_hash).await
+            && cached.tenant_id == *tenant_id
+        {
+            return Ok(cached.is_valid(purpose, now).then_some(cached));
+        }
+
+        let Some(stored) = self.token_repo.find_one_time_token(tenant_id, &token_hash).await? else {
+            return Ok(None);
+        };
+        if !stored.is_valid(purpose, now) {
+            return Ok(None);
+        }
+
+        self.cache_put(&stored).await;
+        Ok(Some(stored))
+    }
+
+    /// Spends the token; `None` when it is unknown, expired, already used or
+    /// for another purpose
+    pub async fn consume(&self, tenant_id: &Uuid, purpose: &str, token: &str) -> Result<Option<OneTimeToken>, AppError> {
+        let token_hash = hash_one_time_token(token);
+        let consumed = self.token_repo.consume_one_time_token(tenant_id, purpose, &token_hash).await?;
+
+        if consumed.is_some() {
+            self.cache_remove(&token_hash).await;
+            METRICS.incr("one_time_tokens_consumed_total");
+        }
+
+        Ok(consumed)
+    }
+
+    /// Makes a consumed token usable again after the change it guarded failed
+    pub async fn release(&self, token: &OneTimeToken) -> Result<(), AppError> {
+        self.token_repo.release_one_time_token(&token.tenant_id, &token.id).await?;
+        self.cache_put(&OneTimeToken { used_at: None, ..token.clone() }).await;
+
+        Ok(())
+    }
+
+    /// Deletes tokens that expired before `before`; cached entries expire on their own
+    pub async fn purge_expired(&self, before: DateTime<Utc>) -> Result<u64, AppError> {
+        let deleted = self.token_repo.delete_expired_one_time_tokens(before).await?;
+        METRICS.add("one_time_tokens_purged_total", deleted);
+
+        Ok(deleted)
+    }
+
+    async fn cache_get(&self, token_hash: &str) -> Option<OneTimeToken> {
+        let cache = self.cache.as_ref()?;
+        cache.get_token(token_hash)
+            .await
+            .inspect_err(|e| tracing::warn!(error = %e, "One-time token cache read failed"))
+            .ok()
+            .flatten()
+    }
+
+    async fn cache_put(&self, token: &OneTimeToken) {
+        if let Some(cache) = &self.cache
+            && let Err(e) = cache.put_token(token).await
+        {
+            tracing::warn!(error = %e, "One-time token cache write failed");
+        }
+    }
+
+    async fn cache_remove(&self, token_hash: &str) {
+        if let Some(cache) = &self.cache
+            && let Err(e) = cache.remove_token(token_hash).await
+        {
+            tracing::warn!(error = %e, "One-time token cache delete failed");
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::TryStreamExt;
+use qrcode::EcLevel;
+use sha2::{Digest, Sha256};
+use url::Url;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        app_setting::OUTBOUND_ALLOWED_DOMAINS,
+        outbound::{OutboundClickInsert, OutboundQrQuery, OutboundReportQuery, OutboundReportResponse, QrErrorCorrection},
+        stats_rollup::RollupWindow,
+        tenant::{normalize_host, Tenant},
+    },
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    repositories::{app_settings::AppSettingsRepository, outbound::OutboundClickRepository, stats_rollup::StatsRollupRepository},
+    storage::object::ObjectStorage,
+    use_cases::settings::RuntimeSettings,
+    utils::{public_urls::PublicUrls, qr_code::qr_png},
+};
+
+/// QR code width when the request does not ask for one
+const DEFAULT_QR_SIZE: u32 = 512;
+
+/// Tracked redirects to the tenant's own syndication and social profiles.
+///
+/// Targets must match `outbound_allowed_domains` so `/out` cannot be used as
+/// an open redirect. Clicks keep a hash of the referrer, never the raw header.
+/// Reports come from the daily rollups, with today read from the raw rows.
+/// QR codes encode the tracked link, so printed codes are counted too.
+#[derive(Clone)]
+pub struct OutboundLinks<R, S, T>
+where
+    R: OutboundClickRepository,
+    S: AppSettingsRepository,
+    T: StatsRollupRepository,
+{
+    pub click_repo: R,
+    pub settings: RuntimeSettings<S>,
+    rollup_repo: T,
+    geo: GeoLocator,
+    storage: Option<Arc<dyn ObjectStorage>>,
+}
+
+impl<R, S, T> OutboundLinks<R, S, T>
+where
+    R: OutboundClickRepository,
+    S: AppSettingsRepository,
+    T: StatsRollupRepository,
+{
+    pub fn new(
+        click_repo: R,
+        settings: RuntimeSettings<S>,
+        rollup_repo: T,
+        geo: GeoLocator,
+        storage: Option<Arc<dyn ObjectStorage>>,
+    ) -> Self {
+        OutboundLinks { click_repo, settings, rollup_repo, geo, storage }
+    }
+
+    /// Parses `target` and checks it against the tenant's allowlist
+    pub fn resolve_target(&self, tenant_id: &Uuid, target: &str) -> Result<Url, AppError> {
+        let url = Url::parse(target.trim())
+            .map_err(|_| AppError::InvalidInput("url must be an absolute http(s) URL".to_string()))?;
+
+        if !matches!(url.scheme(), "http" | "https") || !url.username().is_empty() || url.password().is_some() {
+            return Err(AppError::InvalidInput("url must be an absolute http(s) URL".to_string()));
+        }
+
+        let host = url.host_str().map(normalize_host).unwrap_or_default();
+        let allowed: Vec<String> = self.settings.get(tenant_id, OUTBOUND_ALLOWED_DOMAINS).unwrap_or_default();
+
+        if !allowed.iter().any(|domain| host_matches(&host, domain)) {
+            return Err(AppError::InvalidInput("Redirect target is not allowed".to_string()));
+        }
+
+        Ok(url)
+    }
+
+    /// Stores a click. `source_path` is kept only when the referrer is one of
+    /// our own pages on `request_host`; `client_ip` is only used to locate
+    /// the visitor.
+    pub async fn record_click(
+        &self,
+        tenant_id: Uuid,
+        target: &Url,
+        referrer: Option<String>,
+        request_host: &str,
+        client_ip: &str,
+    ) -> Result<(), AppError> {
+        let referrer = referrer.filter(|r| !r.trim().is_empty());
+        let source_path = referrer
+            .as_deref()
+            .and_then(|r| Url::parse(r).ok())
+            .filter(|r| r.host_str().map(normalize_host).is_some_and(|h| h == normalize_host(request_host)))
+            .map(|r| r.path().to_string());
+
+        let location = self.geo.lookup(client_ip).unwrap_or_default();
+        let click = OutboundClickInsert {
+            target_url: target.to_string(),
+            target_host: target.host_str().map(normalize_host).unwrap_or_default(),
+            source_path,
+            referrer_hash: referrer.map(|r| format!("{:x}", Sha256::digest(r.as_bytes()))),
+            clicked_at: Utc::now(),
+            country_code: location.country_code,
+            region: location.region,
+        };
+
+        self.click_repo.record_click(&tenant_id, &click).await
+    }
+
+    /// PNG of the site's `/out` link to `query.url`. Rendered once per link,
+    /// size and error correction, then served from object storage.
+    pub async fn qr_code(&self, tenant: &Tenant, urls: &PublicUrls, query: OutboundQrQuery) -> Result<Bytes, AppError> {
+        query.validate()?;
+
+        let target = self.resolve_target(&tenant.id, &query.url)?;
+        let link = urls.api(tenant, &format!("/api/v1/out?url={}", urlencoding::encode(target.as_str())));
+        let size = query.size.unwrap_or(DEFAULT_QR_SIZE);
+        let digest = Sha256::digest(format!("{}|{}|{}", link, size, query.ecc.as_str()).as_bytes());
+        let key = format!("qr/{}/{:x}.png", tenant.slug, digest);
+
+        if let Some(cached) = self.cached_qr_code(&key).await {
+            return Ok(cached);
+        }
+
+        let level = match query.ecc {
+            QrErrorCorrection::L => EcLevel::L,
+            QrErrorCorrection::M => EcLevel::M,
+            QrErrorCorrection::Q => EcLevel::Q,
+            QrErrorCorrection::H => EcLevel::H,
+        };
+        let png = Bytes::from(qr_png(&link, size, level)?);
+
+        if let Some(storage) = &self.storage
+            && let Err(e) = storage.put(&key, png.clone(), "image/png").await
+        {
+            tracing::warn!(key = %key, "QR code not cached: {}", e);
+        }
+
+        Ok(png)
+    }
+
+    /// The cache is best effort; any storage error renders the code afresh
+    async fn cached_qr_code(&self, key: &str) -> Option<Bytes> {
+        let storage = self.storage.as_ref()?;
+        let read = async {
+            let object = storage.get_stream(key).await?;
+            let chunks: Vec<Bytes> = object.stream.try_collect().await?;
+            Ok::<_, AppError>(Bytes::from(chunks.concat()))
+        };
+
+        match read.await {
+            Ok(png) => Some(png),
+            Err(AppError::NotFound(_)) => None,
+            Err(e) => {
+                tracing::warn!(key = %key, "Cached QR code unreadable: {}", e);
+                None
+            }
+        }
+    }
+
+    pub async fn report(&self, tenant_id: Uuid, query: OutboundReportQuery) -> Result<OutboundReportResponse, AppError> {
+        query.validate()?;
+
+        let window = RollupWindow::last_days(query.days.unwrap_or(30), Utc::now());
+        let limit = query.limit.unwrap_or(50) as i64;
+
+        let links = self.rollup_repo.click_summary(&tenant_id, &window, limit).await?;
+        let total_clicks = self.rollup_repo.count_clicks(&tenant_id, &window).await?;
+        let referring_pages = self.rollup_repo.referring_pages(&tenant_id, &window, limit).await?;
+
+        Ok(OutboundReportResponse { since: window.since(), total_clicks, links, referring_pages })
+    }
+}
+
+/// `host` is `domain` itself or one of its subdomains
+fn host_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|prefix| prefix.ends_with('.'))
+}
\ No newline at end of file
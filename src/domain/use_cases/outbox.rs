@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tokio::sync::Notify;
+
+use crate::{
+    entities::{
+        notification::{Notification, NotificationEvent},
+        outbox::{AggregateRef, OutboxEvent, OUTBOX_MAX_ATTEMPTS},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    repositories::{outbox::OutboxRepository, tenant::TenantRepository},
+    use_cases::{dispatcher::NotificationSink, tenants::TenantResolver},
+};
+
+/// Events claimed per relay pass
+const RELAY_BATCH_SIZE: i64 = 50;
+/// How long a claimed event stays hidden from other relays
+const CLAIM_LEASE_SECS: i64 = 5 * 60;
+const RETRY_BASE_SECS: i64 = 30;
+const RETRY_MAX_SECS: i64 = 60 * 60;
+
+/// Delivers one kind of outbox event (an email, a webhook call, ...).
+///
+/// Delivery is at-least-once, so `deliver` may run more than once for the
+/// same event and should tolerate it.
+#[async_trait]
+pub trait OutboxSubscriber: Send + Sync {
+    fn event_types(&self) -> &'static [&'static str];
+    async fn deliver(&self, tenant: &Tenant, event: &OutboxEvent) -> Result<(), AppError>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RelayOutcome {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
+
+/// Drains `outbox_events` to the registered subscribers.
+///
+/// Events stay pending until their subscriber succeeds. Failures back off
+/// exponentially and are parked as failed after `OUTBOX_MAX_ATTEMPTS`,
+/// which raises `job_failed` on the alerts sink when one is set.
+#[derive(Clone)]
+pub struct OutboxRelay<O, T>
+where
+    O: OutboxRepository,
+    T: TenantRepository,
+{
+    pub outbox: O,
+    tenants: TenantResolver<T>,
+    subscribers: Vec<Arc<dyn OutboxSubscriber>>,
+    alerts: Option<Arc<dyn NotificationSink>>,
+    wake: Arc<Notify>,
+}
+
+impl<O, T> OutboxRelay<O, T>
+where
+    O: OutboxRepository,
+    T: TenantRepository,
+{
+    pub fn new(outbox: O, tenants: TenantResolver<T>) -> Self {
+        OutboxRelay {
+            outbox,
+            tenants,
+            subscribers: Vec::new(),
+            alerts: None,
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn subscribe(mut self, subscriber: Arc<dyn OutboxSubscriber>) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    pub fn with_alerts(mut self, alerts: Arc<dyn NotificationSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Asks the relay task to run now instead of waiting for its next tick
+    pub fn nudge(&self) {
+        self.wake.notify_one();
+    }
+
+    pub async fn woken(&self) {
+        self.wake.notified().await;
+    }
+
+    /// Claims and delivers one batch of due events
+    pub async fn relay_due(&self) -> Result<RelayOutcome, AppError> {
+        let now = Utc::now();
+        let events = self.outbox
+            .claim_due(now, now + Duration::seconds(CLAIM_LEASE_SECS), RELAY_BATCH_SIZE)
+            .await?;
+
+        let mut outcome = RelayOutcome::default();
+        for event in events {
+            match self.deliver(&event).await {
+                Ok(()) => {
+                    // The rest of the batch still goes out; this event is
+                    // delivered again once its lease runs out
+                    if let Err(e) = self.outbox.mark_delivered(&event.id).await {
+                        tracing::error!(event_id = %event.id, "Delivered outbox event not marked: {}", e);
+                    }
+                    outcome.delivered += 1;
+                }
+                Err(e) => {
+                    let retry_at = (event.attempts < OUTBOX_MAX_ATTEMPTS).then(|| Utc::now() + retry_delay(event.attempts));
+                    tracing::warn!(
+                        event_id = %event.id,
+                        event_type = %event.event_type,
+                        attempts = event.attempts,
+                        "Outbox delivery failed{}: {}",
+                        if retry_at.is_some() { "" } else { " permanently" },
+                        e
+                    );
+                    if let Err(mark_error) = self.outbox.mark_failed(&event.id, &e.to_string(), retry_at).await {
+                        tracing::error!(event_id = %event.id, "Failed outbox event not marked: {}", mark_error);
+                    }
+                    match retry_at {
+                        Some(_) => outcome.retrying += 1,
+                        None => {
+                            outcome.failed += 1;
+                            self.alert_failed(&event, &e).await;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    async fn alert_failed(&self, event: &OutboxEvent, error: &AppError) {
+        let Some(alerts) = &self.alerts else {
+            return;
+        };
+
+        let notification = Notification::new(
+            NotificationEvent::JobFailed,
+            format!("Delivery of '{}' failed", event.event_type),
+            format!("Outbox event {} gave up after {} attempts: {}", event.id, event.attempts, error),
+        );
+        alerts.notify(event.tenant_id, notification).await;
+    }
+
+    async fn deliver(&self, event: &OutboxEvent) -> Result<(), AppError> {
+        let tenant = self.tenants
+            .get(&event.tenant_id)
+            .ok_or_else(|| AppError::NotFound(format!("Tenant {} is not loaded", event.tenant_id)))?;
+
+        let subscriber = self.subscribers
+            .iter()
+            .find(|s| s.event_types().contains(&event.event_type.as_str()))
+            .ok_or_else(|| AppError::InternalError(format!("No subscriber for '{}'", event.event_type)))?;
+
+        subscriber.deliver(&tenant, event).await
+    }
+}
+
+/// 30s, 60s, 120s, ... capped at an hour
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    Duration::seconds((RETRY_BASE_SECS << exponent).min(RETRY_MAX_SECS))
+}
+
+/// Reads the `AggregateRef` payload of a `*.received` event
+pub fn aggregate_ref(event: &OutboxEvent) -> Result<AggregateRef, AppError> {
+    serde_json::from_value(event.payload.clone())
+        .map_err(|e| AppError::InternalError(format!("Malformed '{}' payload: {}", event.event_type, e)))
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use askama::Template;
+
+use crate::{
+    entities::{
+        app_setting::SITE_PAGES,
+        site_page::{NavLink, SitePageSettings},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    repositories::{about::AboutRepository, app_settings::AppSettingsRepository},
+    use_cases::settings::RuntimeSettings,
+    utils::public_urls::PublicUrls,
+};
+
+const ABOUT_TITLE: &str = "About";
+
+/// Complete HTML pages the API serves itself, so the content stays readable
+/// for crawlers and without the JS frontend. Title and nav come from the
+/// tenant's `site_pages` setting. Links to the API's own pages go through
+/// the `PublicUrls` of the request, so they carry its path prefix.
+#[derive(Clone)]
+pub struct SitePages<A, S>
+where
+    A: AboutRepository,
+    S: AppSettingsRepository,
+{
+    about_repo: A,
+    settings: RuntimeSettings<S>,
+}
+
+impl<A, S> SitePages<A, S>
+where
+    A: AboutRepository,
+    S: AppSettingsRepository,
+{
+    pub fn new(about_repo: A, settings: RuntimeSettings<S>) -> Self {
+        SitePages { about_repo, settings }
+    }
+
+    /// The current about-me revision as a page; `NotFound` until one is published
+    pub async fn about(&self, tenant: &Tenant, urls: &PublicUrls) -> Result<String, AppError> {
+        let about = self.about_repo.get_current_about_me(&tenant.id).await.map_err(|e| match e {
+            AppError::NotFound(_) => AppError::NotFound("About Me content not found".to_string()),
+            _ => e,
+        })?;
+
+        let page = ContentPage {
+            layout: self.layout(tenant, urls, "/about.html", Some(about.updated_at.format("%Y-%m-%d").to_string())),
+            title: ABOUT_TITLE,
+            content_html: &about.content_html,
+        };
+
+        page.render().map_err(|e| AppError::InternalError(format!("Failed to render page: {}", e)))
+    }
+
+    fn layout(&self, tenant: &Tenant, urls: &PublicUrls, path: &str, updated: Option<String>) -> PageLayout {
+        let settings: SitePageSettings = self.settings.get(&tenant.id, SITE_PAGES).unwrap_or_default();
+
+        PageLayout {
+            nav: settings.nav_links(urls.base_path()),
+            site_title: settings.title.unwrap_or_else(|| tenant.name.clone()),
+            canonical_url: (!urls.site_origin(tenant).is_empty()).then(|| urls.api(tenant, path)),
+            updated,
+        }
+    }
+}
+
+// ───── Rendering ─────────────────────────────────────────────────────
+
+struct PageLayout {
+    site_title: String,
+    nav: Vec<NavLink>,
+    canonical_url: Option<String>,
+    updated: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "pages/content.html")]
+struct ContentPage<'a> {
+    layout: PageLayout,
+    title: &'a str,
+    content_html: &'a str,
+}
\ No newline at end of file
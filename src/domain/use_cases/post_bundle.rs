@@ -0,0 +1,223 @@
+use std::{
+    io::{Cursor, Write},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::Serialize;
+use uuid::Uuid;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    entities::blog_post::PostRevision,
+    errors::AppError,
+    repositories::blog_post::BlogPostRepository,
+    storage::{
+        links::DOWNLOAD_PATH,
+        object::{validate_key, ObjectStorage},
+    },
+    utils::{
+        front_matter::{FrontMatterValue, PostFrontMatter},
+        markdown::image_sources,
+        valid_uuid::valid_uuid,
+    },
+};
+
+/// A post packed into a zip, ready to be sent as a download
+pub struct PostBundle {
+    pub filename: String,
+    pub body: Bytes,
+}
+
+#[derive(Debug, Serialize)]
+struct BundledImage {
+    source: String,
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct BundleManifest<'a> {
+    post_id: Uuid,
+    slug: &'a str,
+    exported_at: DateTime<Utc>,
+    revisions: usize,
+    images: &'a [BundledImage],
+    /// Image references that are not in object storage, left as they were
+    missing_images: &'a [String],
+}
+
+/// Packs one post into a zip so it can be backed up or moved elsewhere:
+///
+/// - `post.md`: the current markdown with its metadata as front matter,
+///   image references pointing into `images/`
+/// - `revisions/<n>.md`: every revision as it was saved
+/// - `images/`: the cover and inline images served from object storage
+/// - `manifest.json`: what went in, and which images could not be found
+///
+/// Images hosted elsewhere are left as links.
+#[derive(Clone)]
+pub struct PostBundler<B>
+where
+    B: BlogPostRepository,
+{
+    pub blog_post_repo: B,
+    storage: Option<Arc<dyn ObjectStorage>>,
+}
+
+impl<B> PostBundler<B>
+where
+    B: BlogPostRepository,
+{
+    pub fn new(blog_post_repo: B, storage: Option<Arc<dyn ObjectStorage>>) -> Self {
+        PostBundler { blog_post_repo, storage }
+    }
+
+    pub async fn bundle(&self, tenant_id: Uuid, post_id: &str) -> Result<PostBundle, AppError> {
+        let valid_id = valid_uuid(post_id)?;
+        let post = self.blog_post_repo.get_blog_post_by_id(&tenant_id, &valid_id).await?;
+
+        let mut revisions = Vec::new();
+        for summary in self.blog_post_repo.list_post_revisions(&tenant_id, &valid_id).await? {
+            if let Some(revision) = self.blog_post_repo.get_post_revision(&tenant_id, &valid_id, summary.revision).await? {
+                revisions.push(revision);
+            }
+        }
+        revisions.sort_by_key(|revision| revision.revision);
+
+        let mut files = Vec::new();
+        let mut images: Vec<BundledImage> = Vec::new();
+        let mut missing_images = Vec::new();
+
+        let sources = post.cover_image_url.iter().cloned().chain(image_sources(&post.content_markdown));
+        for source in sources {
+            if images.iter().any(|image| image.source == source) || missing_images.contains(&source) {
+                continue;
+            }
+            let Some(key) = storage_key(&source) else {
+                continue;
+            };
+            match self.read_object(&key).await? {
+                Some(body) => {
+                    let path = image_path(&key, images.len());
+                    files.push((path.clone(), body, CompressionMethod::Stored));
+                    images.push(BundledImage { source, path });
+                }
+                None => missing_images.push(source),
+            }
+        }
+
+        let mut current = PostRevision::of(&post, 0);
+        for image in &images {
+            current.content_markdown = current.content_markdown.replace(&image.source, &image.path);
+        }
+        current.cover_image_url = current.cover_image_url.map(|cover| {
+            images.iter().find(|image| image.source == cover).map_or(cover, |image| image.path.clone())
+        });
+        let post_md = front_matter_markdown(&current, &[
+            ("id", post.id.to_string()),
+            ("created_at", post.created_at.to_rfc3339()),
+            ("updated_at", post.updated_at.to_rfc3339()),
+        ])?;
+        files.insert(0, ("post.md".to_string(), Bytes::from(post_md), CompressionMethod::Deflated));
+
+        for revision in &revisions {
+            let markdown = front_matter_markdown(revision, &[
+                ("revision", revision.revision.to_string()),
+                ("revised_at", revision.created_at.to_rfc3339()),
+            ])?;
+            files.push((format!("revisions/{:04}.md", revision.revision), Bytes::from(markdown), CompressionMethod::Deflated));
+        }
+
+        let manifest = BundleManifest {
+            post_id: post.id,
+            slug: &post.slug,
+            exported_at: Utc::now(),
+            revisions: revisions.len(),
+            images: &images,
+            missing_images: &missing_images,
+        };
+        let manifest = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode bundle manifest: {}", e)))?;
+        files.push(("manifest.json".to_string(), Bytes::from(manifest), CompressionMethod::Deflated));
+
+        tracing::info!(post_id = %post.id, revisions = revisions.len(), images = images.len(), "Post bundle built");
+
+        Ok(PostBundle {
+            filename: format!("{}.zip", post.slug),
+            body: Bytes::from(zip_files(&files)?),
+        })
+    }
+
+    /// `None` when storage is off or has no object at `key`
+    async fn read_object(&self, key: &str) -> Result<Option<Bytes>, AppError> {
+        let Some(storage) = &self.storage else {
+            return Ok(None);
+        };
+
+        let object = match storage.get_stream(key).await {
+            Ok(object) => object,
+            Err(AppError::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let chunks: Vec<Bytes> = object.stream.try_collect().await?;
+
+        Ok(Some(Bytes::from(chunks.concat())))
+    }
+}
+
+/// Storage key an image reference points at: a (signed) link served under
+/// `/api/v1/storage/`, or a bare relative key
+fn storage_key(source: &str) -> Option<String> {
+    let path = source.split(['?', '#']).next().unwrap_or_default();
+    let prefix = format!("{}/", DOWNLOAD_PATH);
+    let key = match path.find(&prefix) {
+        Some(at) => &path[at + prefix.len()..],
+        None if !path.contains(':') && !path.starts_with('/') => path,
+        None => return None,
+    };
+
+    validate_key(key).ok().map(|_| key.to_string())
+}
+
+/// `images/<file name>`, numbered so two keys with the same file name do not collide
+fn image_path(key: &str, index: usize) -> String {
+    let name = key.rsplit('/').next().unwrap_or(key);
+    format!("images/{:02}-{}", index + 1, name)
+}
+
+/// The revision's markdown with its metadata, then `extra`, as YAML front matter
+fn front_matter_markdown(revision: &PostRevision, extra: &[(&str, String)]) -> Result<String, AppError> {
+    let mut keys = vec![
+        ("published", Some(FrontMatterValue::Plain(revision.published.to_string()))),
+        ("published_at", revision.published_at.map(|at| FrontMatterValue::Plain(at.to_rfc3339()))),
+        ("cover_image", revision.cover_image_url.as_deref().map(FrontMatterValue::Text)),
+        ("seo_title", revision.seo_title.as_deref().map(FrontMatterValue::Text)),
+        ("seo_description", revision.seo_description.as_deref().map(FrontMatterValue::Text)),
+    ];
+    keys.extend(extra.iter().map(|(name, value)| (*name, Some(FrontMatterValue::Plain(value.clone())))));
+
+    PostFrontMatter {
+        title: &revision.title,
+        slug: &revision.slug,
+        excerpt: &revision.excerpt,
+        tags: revision.tags.as_deref(),
+    }
+    .render(&keys, &revision.content_markdown)
+}
+
+/// Text is deflated; images are stored as they are since they are already compressed
+fn zip_files(files: &[(String, Bytes, CompressionMethod)]) -> Result<Vec<u8>, AppError> {
+    let zip_error = |e: zip::result::ZipError| AppError::InternalError(format!("Failed to write post bundle: {}", e));
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, body, method) in files {
+        zip.start_file(path.as_str(), SimpleFileOptions::default().compression_method(*method))
+            .map_err(zip_error)?;
+        zip.write_all(body)
+            .map_err(|e| AppError::InternalError(format!("Failed to write post bundle: {}", e)))?;
+    }
+
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
@@ -0,0 +1,98 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use validator::Validate;
+
+use crate::{
+    cache::presence::PresenceStore,
+    entities::{
+        presence::{
+            normalize_page_path, PathPresence, PresenceBeat, PresenceBeatResponse, PresenceQuery, PresenceSnapshot,
+        },
+        tenant::Tenant,
+    },
+    errors::AppError,
+    metrics::METRICS,
+    settings::AppConfig,
+};
+
+/// Paths returned when `limit` is not given
+const DEFAULT_PATH_LIMIT: usize = 10;
+
+/// "Currently viewing" counter fed by heartbeats from the site widget.
+///
+/// Session ids are hashed with the tenant id before they reach Redis; the
+/// raw ids browsers send are never stored. Counts are approximate by design:
+/// a closed tab is counted until its last heartbeat expires.
+#[derive(Clone)]
+pub struct PresenceTracker {
+    store: Option<Arc<dyn PresenceStore>>,
+    ttl_secs: u64,
+}
+
+impl PresenceTracker {
+    pub fn new(store: Option<Arc<dyn PresenceStore>>, config: &AppConfig) -> Self {
+        PresenceTracker { store, ttl_secs: config.presence_ttl_secs }
+    }
+
+    fn store(&self) -> Result<&Arc<dyn PresenceStore>, AppError> {
+        self.store
+            .as_ref()
+            .ok_or_else(|| AppError::ServiceUnavailable("Presence tracking is not enabled".to_string()))
+    }
+
+    pub async fn beat(&self, tenant: &Tenant, beat: PresenceBeat) -> Result<PresenceBeatResponse, AppError> {
+        beat.validate()?;
+        let store = self.store()?;
+
+        let session = anonymize(tenant, &beat.session_id);
+        let path = normalize_page_path(&beat.path);
+        let expires_at = Utc::now().timestamp() + self.ttl_secs as i64;
+
+        store.beat(&tenant.cache_key("presence"), &session, &path, expires_at).await?;
+        METRICS.incr("presence_beats_total");
+
+        // Half the window leaves room for one lost or late heartbeat
+        Ok(PresenceBeatResponse { next_beat_secs: (self.ttl_secs / 2).max(1) })
+    }
+
+    /// Visitors per path, busiest first. `max_limit` caps `query.limit`.
+    pub async fn snapshot(&self, tenant: &Tenant, query: PresenceQuery, max_limit: usize) -> Result<PresenceSnapshot, AppError> {
+        query.validate()?;
+        let store = self.store()?;
+
+        let live = store.live_paths(&tenant.cache_key("presence"), Utc::now().timestamp()).await?;
+
+        let mut counts: HashMap<String, u64> = HashMap::new();
+        for path in live {
+            *counts.entry(path).or_default() += 1;
+        }
+
+        let only = query.path.as_deref().map(normalize_page_path);
+        let total = match &only {
+            Some(path) => counts.get(path).copied().unwrap_or(0),
+            None => counts.values().sum(),
+        };
+
+        let mut paths: Vec<PathPresence> = counts
+            .into_iter()
+            .filter(|(path, _)| only.as_ref().is_none_or(|only| only == path))
+            .map(|(path, visitors)| PathPresence { path, visitors })
+            .collect();
+        paths.sort_by(|a, b| b.visitors.cmp(&a.visitors).then_with(|| a.path.cmp(&b.path)));
+        paths.truncate(query.limit.map_or(DEFAULT_PATH_LIMIT, |l| l as usize).min(max_limit));
+
+        Ok(PresenceSnapshot { window_secs: self.ttl_secs, total, paths })
+    }
+}
+
+/// Session ids are salted with the tenant, so the same browser cannot be
+/// linked across sites
+pub(crate) fn anonymize(tenant: &Tenant, session_id: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(tenant.id.as_bytes())
+        .chain_update(session_id.as_bytes())
+        .finalize();
+    format!("{:x}", digest)[..32].to_string()
+}
\ No newline at end of file
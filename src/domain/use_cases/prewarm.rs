@@ -0,0 +1,501 @@
+use std::{collections::HashSet, future::Future, sync::Arc};
+
+use uuid::Uuid;
+
+use crate::{
+    cache::{page_cache::{CachedPage, PageStore}, single_flight::SingleFlight},
+    cdn::purge::CdnPurger,
+    entities::{blog_post::{AuthoredPost, BlogPost}, tenant::Tenant, view::Viewed},
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{blog_post::BlogPostRepository, changelog::ChangelogRepository},
+    settings::AppConfig,
+    use_cases::{blog::{with_author, with_authors}, feed::FeedHandler},
+    utils::public_urls::PublicUrls,
+};
+
+/// Page size the public post list uses when `per_page` is not given
+pub const DEFAULT_POSTS_PER_PAGE: u32 = 10;
+/// Leading list pages rendered after a publish
+const WARM_LIST_PAGES: u32 = 2;
+
+const JSON: &str = "application/json";
+const RSS: &str = "application/rss+xml; charset=utf-8";
+
+const POST_DETAIL_PREFIX: &str = "page:blog:post:";
+
+pub fn post_detail_key(tenant: &Tenant, post_id: &Uuid) -> String {
+    tenant.cache_key(&format!("{}{}", POST_DETAIL_PREFIX, post_id))
+}
+
+pub fn post_list_key(tenant: &Tenant, page: u32, per_page: u32) -> String {
+    tenant.cache_key(&format!("page:blog:posts:{}:{}", page, per_page))
+}
+
+pub fn featured_posts_key(tenant: &Tenant) -> String {
+    tenant.cache_key("page:blog:featured")
+}
+
+pub fn rss_feed_key(tenant: &Tenant) -> String {
+    tenant.cache_key("page:feed:rss")
+}
+
+// Tags name what a cached page was rendered from, so a write drops exactly
+// the pages showing what it changed
+
+pub fn post_tag(tenant: &Tenant, post_id: &Uuid) -> String {
+    tenant.cache_key(&format!("tag:post:{}", post_id))
+}
+
+pub fn author_tag(tenant: &Tenant, user_id: &Uuid) -> String {
+    tenant.cache_key(&format!("tag:author:{}", user_id))
+}
+
+/// Pages whose posts change with every publish: the lists and the feed
+pub fn published_posts_tag(tenant: &Tenant) -> String {
+    tenant.cache_key("tag:posts:published")
+}
+
+pub fn featured_posts_tag(tenant: &Tenant) -> String {
+    tenant.cache_key("tag:posts:featured")
+}
+
+pub fn changelog_tag(tenant: &Tenant) -> String {
+    tenant.cache_key("tag:changelog")
+}
+
+/// The shared pages a post can appear on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Listing {
+    Page(u32),
+    Featured,
+    Feed,
+}
+
+impl Listing {
+    /// Everything `render_listings` produces
+    fn all() -> impl Iterator<Item = Listing> {
+        (1..=WARM_LIST_PAGES).map(Listing::Page).chain([Listing::Featured, Listing::Feed])
+    }
+
+    fn key(self, tenant: &Tenant) -> String {
+        match self {
+            Listing::Page(page) => post_list_key(tenant, page, DEFAULT_POSTS_PER_PAGE),
+            Listing::Featured => featured_posts_key(tenant),
+            Listing::Feed => rss_feed_key(tenant),
+        }
+    }
+}
+
+/// A page ready to store, with the tags of what it shows
+struct Rendered {
+    key: String,
+    page: CachedPage,
+    tags: Vec<String>,
+}
+
+/// Renders public responses into Redis right after a publish so the first
+/// readers do not hit cold caches, then asks the CDN to drop its copies.
+///
+/// Pages are only ever written here, never on a read miss. Each is tagged
+/// with the posts, authors and lists it was rendered from; a write drops
+/// the pages under the tags it touches and renders again those that are
+/// still public, leaving the rest of the cache alone. Everything is a
+/// no-op without Redis.
+///
+/// Misses are loaded through `load_*`, which coalesce concurrent loads of
+/// the same page so a cold cache costs Postgres one query per page rather
+/// than one per reader.
+#[derive(Clone)]
+pub struct ContentPrewarmer<B, C>
+where
+    B: BlogPostRepository,
+    C: ChangelogRepository,
+{
+    pub blog_repo: B,
+    feed: FeedHandler<B, C>,
+    pages: Option<Arc<dyn PageStore>>,
+    purger: Option<Arc<dyn CdnPurger>>,
+    ttl_secs: u64,
+    urls: PublicUrls,
+    post_loads: SingleFlight<Result<AuthoredPost, AppError>>,
+    list_loads: SingleFlight<Result<Vec<AuthoredPost>, AppError>>,
+    feed_loads: SingleFlight<Result<String, AppError>>,
+}
+
+impl<B, C> ContentPrewarmer<B, C>
+where
+    B: BlogPostRepository + Clone,
+    C: ChangelogRepository,
+{
+    pub fn new(
+        blog_repo: B,
+        changelog_repo: C,
+        pages: Option<Arc<dyn PageStore>>,
+        purger: Option<Arc<dyn CdnPurger>>,
+        config: &AppConfig,
+    ) -> Self {
+        ContentPrewarmer {
+            feed: FeedHandler::new(blog_repo.clone(), changelog_repo, config),
+            blog_repo,
+            pages,
+            purger,
+            ttl_secs: config.page_cache_ttl_secs,
+            urls: PublicUrls::from_config(config),
+            post_loads: SingleFlight::new(),
+            list_loads: SingleFlight::new(),
+            feed_loads: SingleFlight::new(),
+        }
+    }
+
+    /// Whether pages are stored at all, i.e. Redis is configured
+    pub fn page_cache_enabled(&self) -> bool {
+        self.pages.is_some()
+    }
+
+    /// Pre-rendered page for `key`; cache errors count as a miss
+    pub async fn cached(&self, key: &str) -> Option<CachedPage> {
+        let pages = self.pages.as_ref()?;
+
+        match pages.get_page(key).await {
+            Ok(Some(page)) => {
+                METRICS.incr("page_cache_hits_total");
+                Some(page)
+            }
+            Ok(None) => {
+                METRICS.incr("page_cache_misses_total");
+                None
+            }
+            Err(e) => {
+                tracing::debug!(key, "Page cache read failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Loads a post on a cache miss, shared with concurrent loads of the same `key`
+    pub async fn load_post<F>(&self, key: &str, load: impl FnOnce() -> F) -> Result<AuthoredPost, AppError>
+    where
+        F: Future<Output = Result<AuthoredPost, AppError>>,
+    {
+        self.post_loads.run(key, load).await
+    }
+
+    /// Loads a post list on a cache miss, shared with concurrent loads of the same `key`
+    pub async fn load_posts<F>(&self, key: &str, load: impl FnOnce() -> F) -> Result<Vec<AuthoredPost>, AppError>
+    where
+        F: Future<Output = Result<Vec<AuthoredPost>, AppError>>,
+    {
+        self.list_loads.run(key, load).await
+    }
+
+    /// Renders a feed on a cache miss, shared with concurrent loads of the same `key`
+    pub async fn load_feed<F>(&self, key: &str, load: impl FnOnce() -> F) -> Result<String, AppError>
+    where
+        F: Future<Output = Result<String, AppError>>,
+    {
+        self.feed_loads.run(key, load).await
+    }
+
+    /// Renders a post that was just published along with the leading list
+    /// pages and the RSS feed, which gain it, then purges their URLs from
+    /// the CDN. Returns how many pages were stored.
+    pub async fn warm_post(&self, tenant: &Tenant, post_id: Uuid) -> Result<usize, AppError> {
+        if self.pages.is_none() {
+            return Ok(0);
+        }
+
+        let post = self.blog_repo.get_blog_post_by_id(&tenant.id, &post_id).await?;
+        if !post.published {
+            return Ok(0);
+        }
+
+        let mut fresh = vec![post_detail_key(tenant, &post_id)];
+        fresh.extend(Listing::all().filter(|listing| *listing != Listing::Featured).map(|listing| listing.key(tenant)));
+        let stored = self.refresh(tenant, vec![post_tag(tenant, &post_id), published_posts_tag(tenant)], fresh).await?;
+        METRICS.incr("page_cache_warmups_total");
+
+        self.purge_cdn(tenant, post_id).await;
+
+        Ok(stored)
+    }
+
+    /// Drops every page the post appears on after an edit, unpublish or
+    /// delete, renders again those that are still public and purges the
+    /// post's URLs from the CDN. Returns how many pages were stored.
+    pub async fn refresh_post(&self, tenant: &Tenant, post_id: Uuid) -> Result<usize, AppError> {
+        let stored = self.refresh(tenant, vec![post_tag(tenant, &post_id)], [post_detail_key(tenant, &post_id)]).await?;
+        self.purge_cdn(tenant, post_id).await;
+
+        Ok(stored)
+    }
+
+    /// Bylines carry the author's name and avatar
+    pub async fn refresh_author(&self, tenant: &Tenant, user_id: Uuid) -> Result<usize, AppError> {
+        self.refresh(tenant, vec![author_tag(tenant, &user_id)], []).await
+    }
+
+    /// The feed lists changelog entries next to the posts
+    pub async fn refresh_changelog(&self, tenant: &Tenant) -> Result<usize, AppError> {
+        let stored = self.refresh(tenant, vec![changelog_tag(tenant)], []).await?;
+
+        if let Some(base_url) = self.cdn_base_url(tenant) {
+            self.purge(tenant, vec![format!("{}/api/v1/feed/rss.xml", base_url)]).await;
+        }
+
+        Ok(stored)
+    }
+
+    /// Drops the pages under `tags` and renders again those still public,
+    /// plus `fresh` ones whether they were cached or not
+    async fn refresh(
+        &self,
+        tenant: &Tenant,
+        tags: Vec<String>,
+        fresh: impl IntoIterator<Item = String>,
+    ) -> Result<usize, AppError> {
+        let Some(pages) = &self.pages else {
+            return Ok(0);
+        };
+
+        let mut stale: HashSet<String> = pages.invalidate_tags(tags).await?.into_iter().collect();
+        METRICS.add("page_cache_invalidated_pages_total", stale.len() as u64);
+        stale.extend(fresh);
+
+        self.rewarm(pages.as_ref(), tenant, &stale).await
+    }
+
+    /// Renders the detail pages and listings among `keys` again; posts that
+    /// are gone or unpublished stay uncached
+    async fn rewarm(&self, pages: &dyn PageStore, tenant: &Tenant, keys: &HashSet<String>) -> Result<usize, AppError> {
+        let mut rendered = Vec::new();
+
+        let detail_prefix = tenant.cache_key(POST_DETAIL_PREFIX);
+        let post_ids = keys.iter().filter_map(|key| key.strip_prefix(&detail_prefix)?.parse::<Uuid>().ok());
+        for post_id in post_ids {
+            match self.blog_repo.get_blog_post_by_id(&tenant.id, &post_id).await {
+                Ok(post) if post.published => rendered.push(self.render_post(tenant, post).await?),
+                Ok(_) | Err(AppError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        for listing in Listing::all().filter(|listing| keys.contains(&listing.key(tenant))) {
+            rendered.extend(self.render_listing(tenant, listing).await?);
+        }
+
+        self.store(pages, &rendered).await?;
+        Ok(rendered.len())
+    }
+
+    async fn store(&self, pages: &dyn PageStore, rendered: &[Rendered]) -> Result<(), AppError> {
+        for page in rendered {
+            pages.put_page(&page.key, &page.page, &page.tags, self.ttl_secs).await?;
+        }
+        Ok(())
+    }
+
+    /// Re-renders the detail pages of the published posts given and purges
+    /// them from the CDN; list pages are left to `warm_listings`. Returns how
+    /// many pages were stored.
+    pub async fn warm_posts(&self, tenant: &Tenant, posts: Vec<BlogPost>) -> Result<usize, AppError> {
+        let mut stored = 0;
+        let mut post_ids = Vec::new();
+
+        for post in posts.into_iter().filter(|post| post.published) {
+            post_ids.push(post.id);
+            if let Some(pages) = &self.pages {
+                self.store(pages.as_ref(), &[self.render_post(tenant, post).await?]).await?;
+                stored += 1;
+            }
+        }
+
+        if let Some(base_url) = self.cdn_base_url(tenant).filter(|_| !post_ids.is_empty()) {
+            let urls = post_ids.iter().map(|id| format!("{}/api/v1/blog/posts/{}", base_url, id)).collect();
+            self.purge(tenant, urls).await;
+        }
+
+        Ok(stored)
+    }
+
+    /// Re-renders the leading list pages, the featured list and the RSS feed
+    /// and purges them from the CDN. Returns how many pages were stored.
+    pub async fn warm_listings(&self, tenant: &Tenant) -> Result<usize, AppError> {
+        let mut stored = 0;
+
+        if let Some(pages) = &self.pages {
+            let rendered = self.render_listings(tenant).await?;
+            self.store(pages.as_ref(), &rendered).await?;
+            stored += rendered.len();
+            METRICS.incr("page_cache_warmups_total");
+        }
+
+        if let Some(base_url) = self.cdn_base_url(tenant) {
+            self.purge(tenant, listing_urls(&base_url)).await;
+        }
+
+        Ok(stored)
+    }
+
+    /// Fills the cache on boot with the listings and the pages of the newest
+    /// posts. Nothing is purged: the CDN's copies are still current.
+    /// Returns how many pages were stored.
+    pub async fn warm_startup(&self, tenant: &Tenant) -> Result<usize, AppError> {
+        let Some(pages) = &self.pages else {
+            return Ok(0);
+        };
+
+        let mut rendered = self.render_listings(tenant).await?;
+        let recent = self.blog_repo.get_recent_blog_posts(&tenant.id, DEFAULT_POSTS_PER_PAGE, true).await?;
+        for post in recent {
+            rendered.push(self.render_post(tenant, post).await?);
+        }
+
+        self.store(pages.as_ref(), &rendered).await?;
+        METRICS.incr("page_cache_warmups_total");
+
+        Ok(rendered.len())
+    }
+
+    async fn render_post(&self, tenant: &Tenant, post: BlogPost) -> Result<Rendered, AppError> {
+        let post = with_author(&self.blog_repo, tenant.id, post).await?;
+
+        Ok(Rendered {
+            key: post_detail_key(tenant, &post.post.id),
+            page: CachedPage::json(&post)?,
+            tags: post_tags(tenant, std::slice::from_ref(&post)),
+        })
+    }
+
+    /// The leading list pages, the featured list and the RSS feed
+    async fn render_listings(&self, tenant: &Tenant) -> Result<Vec<Rendered>, AppError> {
+        let mut rendered = Vec::new();
+
+        for listing in Listing::all() {
+            rendered.extend(self.render_listing(tenant, listing).await?);
+        }
+
+        Ok(rendered)
+    }
+
+    /// `None` for list pages past the last post
+    async fn render_listing(&self, tenant: &Tenant, listing: Listing) -> Result<Option<Rendered>, AppError> {
+        let rendered = match listing {
+            Listing::Page(page) => {
+                let posts = self.blog_repo
+                    .get_all_blog_posts(&tenant.id, true, page, DEFAULT_POSTS_PER_PAGE)
+                    .await?;
+                if posts.is_empty() && page > 1 {
+                    return Ok(None);
+                }
+                let posts = with_authors(&self.blog_repo, tenant.id, posts).await?;
+
+                let mut tags = vec![published_posts_tag(tenant)];
+                tags.extend(post_tags(tenant, &posts));
+                Rendered { key: listing.key(tenant), page: CachedPage::json(&posts)?, tags }
+            }
+            Listing::Featured => self.render_featured(tenant).await?,
+            Listing::Feed => {
+                let (body, post_ids) = self.feed.rss_with_posts(tenant).await?;
+
+                let mut tags = vec![published_posts_tag(tenant), changelog_tag(tenant)];
+                tags.extend(post_ids.iter().map(|id| post_tag(tenant, id)));
+                Rendered { key: listing.key(tenant), page: CachedPage { content_type: RSS.to_string(), body }, tags }
+            }
+        };
+
+        Ok(Some(rendered))
+    }
+
+    /// Renders the featured list after it was curated and purges it from the CDN
+    pub async fn warm_featured(&self, tenant: &Tenant) -> Result<(), AppError> {
+        if let Some(pages) = &self.pages {
+            self.store(pages.as_ref(), &[self.render_featured(tenant).await?]).await?;
+            METRICS.incr("page_cache_warmups_total");
+        }
+
+        if let Some(base_url) = self.cdn_base_url(tenant) {
+            self.purge(tenant, vec![format!("{}/api/v1/blog/posts/featured", base_url)]).await;
+        }
+
+        Ok(())
+    }
+
+    async fn render_featured(&self, tenant: &Tenant) -> Result<Rendered, AppError> {
+        let posts = self.blog_repo.get_featured_posts(&tenant.id, true).await?;
+        let posts = with_authors(&self.blog_repo, tenant.id, posts).await?;
+
+        let mut tags = vec![featured_posts_tag(tenant)];
+        tags.extend(post_tags(tenant, &posts));
+        Ok(Rendered { key: featured_posts_key(tenant), page: CachedPage::json(&posts)?, tags })
+    }
+
+    /// Best effort: a failed purge only means the CDN serves its copy until it expires
+    async fn purge_cdn(&self, tenant: &Tenant, post_id: Uuid) {
+        let Some(base_url) = self.cdn_base_url(tenant) else {
+            return;
+        };
+
+        let mut urls = vec![format!("{}/api/v1/blog/posts/{}", base_url, post_id)];
+        urls.extend(listing_urls(&base_url));
+
+        self.purge(tenant, urls).await;
+    }
+
+    /// Origin the CDN caches this tenant under, with the base path the API
+    /// is served at; `None` when there is nothing to purge
+    fn cdn_base_url(&self, tenant: &Tenant) -> Option<String> {
+        self.purger.as_ref()?;
+
+        let origin = self.urls.site_origin(tenant);
+        (!origin.is_empty()).then(|| self.urls.api(tenant, ""))
+    }
+
+    async fn purge(&self, tenant: &Tenant, urls: Vec<String>) {
+        let Some(purger) = &self.purger else {
+            return;
+        };
+
+        if let Err(e) = purger.purge_urls(urls).await {
+            tracing::warn!(tenant = %tenant.slug, "CDN purge failed: {}", e);
+        }
+    }
+}
+
+/// Tags of the posts shown and of everyone on their bylines
+fn post_tags(tenant: &Tenant, posts: &[AuthoredPost]) -> Vec<String> {
+    let mut author_ids: Vec<Uuid> = posts
+        .iter()
+        .flat_map(|post| post.author.iter().chain(&post.authors))
+        .map(|author| author.id)
+        .collect();
+    author_ids.sort();
+    author_ids.dedup();
+
+    posts
+        .iter()
+        .map(|post| post_tag(tenant, &post.post.id))
+        .chain(author_ids.iter().map(|id| author_tag(tenant, id)))
+        .collect()
+}
+
+/// Public URLs of the pages `render_listings` produces
+fn listing_urls(base_url: &str) -> Vec<String> {
+    let mut urls = vec![
+        format!("{}/api/v1/blog/posts", base_url),
+        format!("{}/api/v1/blog/posts/featured", base_url),
+        format!("{}/api/v1/feed/rss.xml", base_url),
+    ];
+    urls.extend((2..=WARM_LIST_PAGES).map(|page| format!("{}/api/v1/blog/posts?page={}", base_url, page)));
+    urls
+}
+
+impl CachedPage {
+    /// Cached pages are served to visitors, so they use the public view
+    fn json<T: serde::Serialize>(value: &T) -> Result<Self, AppError> {
+        let body = serde_json::to_string(&Viewed::public(value))
+            .map_err(|e| AppError::InternalError(format!("Failed to render page: {}", e)))?;
+
+        Ok(CachedPage { content_type: JSON.to_string(), body })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use crate::{
+    entities::{
+        public_api::{PublicAbout, PublicPost, PublicPostList, PublicPostSummary, PublicPostsQuery},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    repositories::{about::AboutRepository, blog_post::BlogPostRepository},
+    settings::AppConfig,
+    use_cases::blog::with_authors,
+    utils::{markdown::safe_markdown_to_html, public_urls::PublicUrls},
+};
+
+/// Read-only view of published content for third parties, in the stable
+/// DTOs of `entities::public_api`. Links point at the tenant's site, like
+/// the RSS feed's.
+#[derive(Clone)]
+pub struct PublicApi<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub blog_repo: B,
+    pub about_repo: A,
+    urls: PublicUrls,
+}
+
+impl<B, A> PublicApi<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub fn new(blog_repo: B, about_repo: A, config: &AppConfig) -> Self {
+        PublicApi {
+            blog_repo,
+            about_repo,
+            urls: PublicUrls::from_config(config),
+        }
+    }
+
+    pub async fn posts(&self, tenant: &Tenant, query: &PublicPostsQuery) -> Result<PublicPostList, AppError> {
+        let (page, per_page) = query.pagination();
+        let posts = self.blog_repo.get_all_blog_posts(&tenant.id, true, page, per_page).await?;
+        let base_url = self.urls.site_origin(tenant);
+
+        let posts = with_authors(&self.blog_repo, tenant.id, posts)
+            .await?
+            .into_iter()
+            .map(|post| PublicPostSummary::from_post(post, &base_url))
+            .collect();
+
+        Ok(PublicPostList { page, per_page, posts })
+    }
+
+    /// Drafts answer like missing posts
+    pub async fn post(&self, tenant: &Tenant, slug: &str) -> Result<PublicPost, AppError> {
+        let not_found = || AppError::NotFound("Post not found".to_string());
+
+        let post = self.blog_repo
+            .get_blog_post_by_slug(&tenant.id, slug)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => not_found(),
+                _ => e,
+            })?;
+        if !post.published {
+            return Err(not_found());
+        }
+
+        let content_html = safe_markdown_to_html(&post.content_markdown);
+        let post = with_authors(&self.blog_repo, tenant.id, vec![post])
+            .await?
+            .pop()
+            .ok_or_else(not_found)?;
+
+        Ok(PublicPost {
+            summary: PublicPostSummary::from_post(post, &self.urls.site_origin(tenant)),
+            content_html,
+        })
+    }
+
+    pub async fn about(&self, tenant: &Tenant) -> Result<PublicAbout, AppError> {
+        self.about_repo.get_current_about_me(&tenant.id).await
+            .map(PublicAbout::from)
+            .map_err(|e| match e {
+                AppError::NotFound(_) => AppError::NotFound("About page not found".to_string()),
+                _ => e,
+            })
+    }
+}
\ No newline at end of file
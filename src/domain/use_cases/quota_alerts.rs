@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        notification::{Notification, NotificationEvent},
+        stats_rollup::start_of_month,
+        tenant::DEFAULT_TENANT_ID,
+        usage::{QuotaUsage, UsageReport},
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::usage::UsageRepository,
+    settings::AppConfig,
+    use_cases::dispatcher::NotificationSink,
+};
+
+/// Raises `quota_warning` when a quota reaches one of the configured
+/// percentages, before it runs out: the mail relay's monthly emails, the
+/// object storage plan, and each API token's monthly quota.
+///
+/// Checked after each usage collector run. Every threshold is raised at
+/// most once per quota and calendar month; one that was skipped over, e.g.
+/// 80% when usage jumped to 97%, is recorded with the higher one and not
+/// raised on its own. Email and storage are shared by the whole instance,
+/// so their alerts go to the default site's admins.
+#[derive(Clone)]
+pub struct QuotaAlerts<R>
+where
+    R: UsageRepository,
+{
+    pub usage_repo: R,
+    alerts: Arc<dyn NotificationSink>,
+    thresholds: Vec<u8>,
+    email_quota: u64,
+    storage_quota: u64,
+}
+
+impl<R> QuotaAlerts<R>
+where
+    R: UsageRepository,
+{
+    pub fn new(usage_repo: R, alerts: Arc<dyn NotificationSink>, config: &AppConfig) -> Self {
+        QuotaAlerts {
+            usage_repo,
+            alerts,
+            thresholds: config.quota_alert_thresholds().unwrap_or_default(),
+            email_quota: config.email_monthly_quota,
+            storage_quota: config.storage_quota_bytes,
+        }
+    }
+
+    /// The instance-wide quotas measured in `reports`, which every site's
+    /// report repeats
+    pub fn instance_quotas(&self, reports: &[UsageReport]) -> Vec<QuotaUsage> {
+        let mut quotas = Vec::new();
+
+        if let Some(email) = reports.iter().find_map(|r| r.email).filter(|_| self.email_quota > 0) {
+            quotas.push(QuotaUsage {
+                quota: "email".to_string(),
+                label: "Emails sent this month".to_string(),
+                used: email.sent.max(0) as u64,
+                limit: self.email_quota,
+            });
+        }
+        if let Some(storage) = reports.iter().find_map(|r| r.storage.as_ref()).filter(|_| self.storage_quota > 0) {
+            quotas.push(QuotaUsage {
+                quota: "storage".to_string(),
+                label: format!("Bytes stored in {}", storage.backend),
+                used: storage.total_bytes,
+                limit: self.storage_quota,
+            });
+        }
+
+        quotas
+    }
+
+    /// Alerts on the instance quotas in `reports` and on `site_quotas`,
+    /// which are per site. Returns how many alerts were raised.
+    pub async fn check(&self, reports: &[UsageReport], site_quotas: &[(Uuid, QuotaUsage)]) -> Result<usize, AppError> {
+        let instance = self.instance_quotas(reports).into_iter().map(|usage| (DEFAULT_TENANT_ID, usage));
+        let period = start_of_month(Utc::now().date_naive());
+
+        let mut raised = 0;
+        for (tenant_id, usage) in instance.chain(site_quotas.iter().cloned()) {
+            let percent = usage.percent();
+            let reached: Vec<i16> = self.thresholds
+                .iter()
+                .filter(|&&threshold| percent >= threshold as u64)
+                .map(|&threshold| threshold as i16)
+                .collect();
+            let Some(&highest) = reached.last() else {
+                continue;
+            };
+
+            let claimed = self.usage_repo.claim_quota_alerts(&tenant_id, &usage.quota, period, &reached).await?;
+            if !claimed.contains(&highest) {
+                continue;
+            }
+
+            let notification = Notification::new(
+                NotificationEvent::QuotaWarning,
+                format!("{} at {}% of the quota", usage.label, percent),
+                format!(
+                    "{}: {} of {} ({}%), past the {}% alert threshold.",
+                    usage.label, usage.used, usage.limit, percent, highest
+                ),
+            );
+            self.alerts.notify(tenant_id, notification).await;
+            METRICS.incr("quota_alerts_total");
+            raised += 1;
+        }
+
+        Ok(raised)
+    }
+}
@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+use chrono::Utc;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        reading::{
+            PostReadingStats, ReadingProgressBatch, ReadingProgressResponse, ReadingStatsQuery, ReadingStatsResponse,
+            COMPLETION_PERCENT,
+        },
+        stats_rollup::RollupWindow,
+        tenant::Tenant,
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{reading_progress::ReadingProgressRepository, stats_rollup::StatsRollupRepository},
+    use_cases::presence::anonymize,
+};
+
+/// Scroll-depth analytics fed by batches from the site script.
+///
+/// Each browser session keeps only its furthest point per post, so the
+/// stats show how far people get rather than how often they scroll. Session
+/// ids are hashed the same way presence heartbeats are. Stats come from the
+/// daily rollups, with today read from the raw rows.
+#[derive(Clone)]
+pub struct ReadingAnalytics<R, S>
+where
+    R: ReadingProgressRepository,
+    S: StatsRollupRepository,
+{
+    pub progress_repo: R,
+    rollup_repo: S,
+}
+
+impl<R, S> ReadingAnalytics<R, S>
+where
+    R: ReadingProgressRepository,
+    S: StatsRollupRepository,
+{
+    pub fn new(progress_repo: R, rollup_repo: S) -> Self {
+        ReadingAnalytics { progress_repo, rollup_repo }
+    }
+
+    pub async fn record(&self, tenant: &Tenant, batch: ReadingProgressBatch) -> Result<ReadingProgressResponse, AppError> {
+        batch.validate()?;
+
+        let session = anonymize(tenant, &batch.session_id);
+        let accepted = self.progress_repo
+            .record_progress(&tenant.id, &session, &batch.furthest_per_post())
+            .await?;
+        METRICS.incr("reading_progress_batches_total");
+
+        Ok(ReadingProgressResponse { accepted })
+    }
+
+    /// Posts by completion rate; the ones people actually finish come first
+    pub async fn stats(&self, tenant: &Tenant, query: ReadingStatsQuery) -> Result<ReadingStatsResponse, AppError> {
+        query.validate()?;
+
+        let window = RollupWindow::last_days(query.days.unwrap_or(30), Utc::now());
+        let min_readers = i64::from(query.min_readers.unwrap_or(1));
+
+        let counts = self.rollup_repo.depth_counts(&tenant.id, &window).await?;
+        let mut posts: Vec<PostReadingStats> = PostReadingStats::from_counts(counts)
+            .into_iter()
+            .filter(|p| p.readers >= min_readers)
+            .collect();
+        posts.sort_by(|a, b| {
+            b.completion_rate
+                .partial_cmp(&a.completion_rate)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| b.readers.cmp(&a.readers))
+                .then_with(|| a.title.cmp(&b.title))
+        });
+        posts.truncate(query.limit.unwrap_or(50) as usize);
+
+        Ok(ReadingStatsResponse { since: window.since(), completion_percent: COMPLETION_PERCENT, posts })
+    }
+}
\ No newline at end of file
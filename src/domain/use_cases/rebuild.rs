@@ -0,0 +1,149 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    entities::tenant::Tenant,
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{blog_post::BlogPostRepository, changelog::ChangelogRepository},
+    use_cases::prewarm::ContentPrewarmer,
+};
+
+/// Published posts re-rendered per batch
+pub const REBUILD_BATCH_SIZE: u32 = 100;
+
+/// Where a tenant's rebuild stands; kept after it finishes until the next run
+#[derive(Debug, Clone, Serialize)]
+pub struct RebuildProgress {
+    pub tenant: String,
+    pub batches: usize,
+    pub posts: usize,
+    pub pages: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+impl RebuildProgress {
+    fn started(tenant: &Tenant) -> Self {
+        RebuildProgress {
+            tenant: tenant.slug.clone(),
+            batches: 0,
+            posts: 0,
+            pages: 0,
+            started_at: Utc::now(),
+            finished_at: None,
+            error: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.finished_at.is_none()
+    }
+}
+
+/// Regenerates everything derived from post content, e.g. after the markdown
+/// sanitizer or highlighting changed: every published post's cached page,
+/// the list pages, the featured list and the RSS feed, with CDN purges.
+///
+/// Posts are read in batches of `REBUILD_BATCH_SIZE`; progress is recorded
+/// after each batch. One run per tenant at a time.
+#[derive(Clone)]
+pub struct ContentRebuilder<B, C>
+where
+    B: BlogPostRepository,
+    C: ChangelogRepository,
+{
+    prewarmer: ContentPrewarmer<B, C>,
+    runs: Arc<RwLock<HashMap<Uuid, RebuildProgress>>>,
+}
+
+impl<B, C> ContentRebuilder<B, C>
+where
+    B: BlogPostRepository + Clone,
+    C: ChangelogRepository,
+{
+    pub fn new(prewarmer: ContentPrewarmer<B, C>) -> Self {
+        ContentRebuilder { prewarmer, runs: Arc::default() }
+    }
+
+    /// The running or last finished rebuild of the tenant
+    pub fn progress(&self, tenant_id: &Uuid) -> Option<RebuildProgress> {
+        self.runs.read().get(tenant_id).cloned()
+    }
+
+    /// Claims the tenant for a new run; `Conflict` while one is in progress
+    pub fn begin(&self, tenant: &Tenant) -> Result<RebuildProgress, AppError> {
+        let mut runs = self.runs.write();
+        if runs.get(&tenant.id).is_some_and(RebuildProgress::is_running) {
+            return Err(AppError::Conflict("A rebuild is already running for this site".to_string()));
+        }
+
+        let progress = RebuildProgress::started(tenant);
+        runs.insert(tenant.id, progress.clone());
+
+        Ok(progress)
+    }
+
+    /// Runs a rebuild claimed with `begin`, calling `on_progress` after each
+    /// batch of posts and once more after the list pages
+    pub async fn run(
+        &self,
+        tenant: &Tenant,
+        on_progress: impl Fn(&RebuildProgress),
+    ) -> Result<RebuildProgress, AppError> {
+        let result = self.rebuild(tenant, &on_progress).await;
+
+        let mut runs = self.runs.write();
+        let progress = runs.entry(tenant.id).or_insert_with(|| RebuildProgress::started(tenant));
+        progress.finished_at = Some(Utc::now());
+        progress.error = result.as_ref().err().map(ToString::to_string);
+        METRICS.incr("content_rebuilds_total");
+
+        result.map(|()| progress.clone())
+    }
+
+    async fn rebuild(&self, tenant: &Tenant, on_progress: &impl Fn(&RebuildProgress)) -> Result<(), AppError> {
+        for page in 1.. {
+            let posts = self.prewarmer
+                .blog_repo
+                .get_all_blog_posts(&tenant.id, true, page, REBUILD_BATCH_SIZE)
+                .await?;
+            let last_batch = posts.len() < REBUILD_BATCH_SIZE as usize;
+            let post_count = posts.len();
+
+            if post_count > 0 {
+                let pages = self.prewarmer.warm_posts(tenant, posts).await?;
+                self.record(tenant, on_progress, |progress| {
+                    progress.batches += 1;
+                    progress.posts += post_count;
+                    progress.pages += pages;
+                });
+            }
+
+            if last_batch {
+                break;
+            }
+        }
+
+        let pages = self.prewarmer.warm_listings(tenant).await?;
+        self.record(tenant, on_progress, |progress| progress.pages += pages);
+
+        Ok(())
+    }
+
+    fn record(&self, tenant: &Tenant, on_progress: &impl Fn(&RebuildProgress), change: impl FnOnce(&mut RebuildProgress)) {
+        let snapshot = {
+            let mut runs = self.runs.write();
+            let progress = runs.entry(tenant.id).or_insert_with(|| RebuildProgress::started(tenant));
+            change(progress);
+            progress.clone()
+        };
+
+        on_progress(&snapshot);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,300 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
+use regex::Regex;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    entities::redirect_rule::{
+        compile_pattern, with_query, NewRedirectRuleRequest, Redirect, RedirectImportIssue, RedirectImportReport,
+        RedirectRule, UpdateRedirectRuleRequest,
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::redirect_rule::RedirectRuleRepository,
+    utils::valid_uuid::valid_uuid,
+};
+
+/// What the fallback needs of a rule
+#[derive(Debug, Clone)]
+struct CachedRule {
+    id: Uuid,
+    target: String,
+    status_code: u16,
+}
+
+/// One tenant's rules, compiled
+#[derive(Debug, Default)]
+struct TenantRedirects {
+    exact: HashMap<String, CachedRule>,
+    /// Tried in order after the exact rules
+    patterns: Vec<(Regex, CachedRule)>,
+}
+
+impl TenantRedirects {
+    fn build(rules: impl IntoIterator<Item = RedirectRule>) -> Self {
+        let mut compiled = TenantRedirects::default();
+        for rule in rules {
+            compiled.add(rule);
+        }
+        compiled
+    }
+
+    fn add(&mut self, rule: RedirectRule) {
+        let cached = CachedRule { id: rule.id, target: rule.target.clone(), status_code: rule.status_code as u16 };
+        if !rule.is_pattern() {
+            self.exact.insert(rule.source, cached);
+            return;
+        }
+
+        // Validated on save; a pattern that no longer compiles is left out
+        match compile_pattern(&rule.source) {
+            Ok(regex) => self.patterns.push((regex, cached)),
+            Err(e) => tracing::warn!(rule = %rule.id, "Skipping redirect rule: {}", e),
+        }
+    }
+
+    fn find(&self, path: &str) -> Option<(&CachedRule, String)> {
+        if let Some(rule) = self.exact.get(path) {
+            return Some((rule, rule.target.clone()));
+        }
+
+        self.patterns.iter().find_map(|(regex, rule)| {
+            let captures = regex.captures(path)?;
+            let mut location = String::new();
+            captures.expand(&rule.target, &mut location);
+            // A pattern that rewrites a path to itself would loop forever
+            (location != path).then_some((rule, location))
+        })
+    }
+}
+
+/// Row of a redirect CSV: `source,target` with optional `status_code` and
+/// `kind` columns
+#[derive(Debug, Deserialize)]
+struct CsvRedirectRow {
+    source: String,
+    target: String,
+    #[serde(default)]
+    status_code: Option<u16>,
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+/// Legacy URL redirects, resolved by the 404 fallback.
+///
+/// Rules are compiled into an in-process cache, rebuilt for the tenant on
+/// every change and refreshed periodically so other instances pick changes
+/// up. Exact rules win over patterns; patterns are tried oldest first.
+/// Hits are counted in memory and added to the rules on each refresh.
+#[derive(Clone)]
+pub struct RedirectRules<R>
+where
+    R: RedirectRuleRepository,
+{
+    pub repo: R,
+    cache: Arc<RwLock<HashMap<Uuid, TenantRedirects>>>,
+    hits: Arc<Mutex<HashMap<Uuid, i64>>>,
+}
+
+impl<R> RedirectRules<R>
+where
+    R: RedirectRuleRepository,
+{
+    pub fn new(repo: R) -> Self {
+        RedirectRules { repo, cache: Arc::default(), hits: Arc::default() }
+    }
+
+    /// Saves counted hits and reloads every tenant's rules, returning how
+    /// many there are. A failed hit flush keeps the counts for the next run.
+    pub async fn refresh(&self) -> Result<usize, AppError> {
+        if let Err(e) = self.flush_hits().await {
+            tracing::warn!("Redirect hit flush failed: {}", e);
+        }
+
+        let mut by_tenant: HashMap<Uuid, Vec<RedirectRule>> = HashMap::new();
+        for rule in self.repo.list_all_redirect_rules().await? {
+            by_tenant.entry(rule.tenant_id).or_default().push(rule);
+        }
+        let count = by_tenant.values().map(Vec::len).sum();
+
+        *self.cache.write() = by_tenant
+            .into_iter()
+            .map(|(tenant_id, rules)| (tenant_id, TenantRedirects::build(rules)))
+            .collect();
+        Ok(count)
+    }
+
+    /// Writes the hits counted since the last flush
+    pub async fn flush_hits(&self) -> Result<usize, AppError> {
+        let hits: Vec<(Uuid, i64)> = std::mem::take(&mut *self.hits.lock()).into_iter().collect();
+        if hits.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(e) = self.repo.record_redirect_hits(&hits, Utc::now()).await {
+            let mut pending = self.hits.lock();
+            for (id, count) in hits {
+                *pending.entry(id).or_default() += count;
+            }
+            return Err(e);
+        }
+        Ok(hits.len())
+    }
+
+    /// Where a request for `path` should go, if a rule matches. The query
+    /// string is carried over unless the target has its own.
+    pub fn resolve(&self, tenant_id: &Uuid, path: &str, query: &str) -> Option<Redirect> {
+        let cache = self.cache.read();
+        let (rule, location) = cache.get(tenant_id)?.find(path)?;
+
+        *self.hits.lock().entry(rule.id).or_default() += 1;
+        METRICS.incr("redirects_served_total");
+
+        Some(Redirect {
+            rule_id: rule.id,
+            location: with_query(location, query),
+            status_code: rule.status_code,
+        })
+    }
+
+    async fn reload_tenant(&self, tenant_id: Uuid) -> Result<(), AppError> {
+        let rules = self.repo.list_redirect_rules(&tenant_id).await?;
+        self.cache.write().insert(tenant_id, TenantRedirects::build(rules));
+        Ok(())
+    }
+
+    pub async fn list(&self, tenant_id: Uuid) -> Result<Vec<RedirectRule>, AppError> {
+        self.repo.list_redirect_rules(&tenant_id).await
+    }
+
+    pub async fn create(&self, tenant_id: Uuid, request: NewRedirectRuleRequest) -> Result<RedirectRule, AppError> {
+        let insert = request.to_insert().map_err(AppError::InvalidInput)?;
+        let rule = self.repo.create_redirect_rule(&tenant_id, &insert).await?;
+        self.reload_tenant(tenant_id).await?;
+        Ok(rule)
+    }
+
+    pub async fn update(&self, tenant_id: Uuid, id: &str, request: UpdateRedirectRuleRequest) -> Result<RedirectRule, AppError> {
+        let id = valid_uuid(id)?;
+        let current = self.repo.get_redirect_rule(&tenant_id, &id).await?;
+        let insert = request.apply_to(&current).to_insert().map_err(AppError::InvalidInput)?;
+
+        let rule = self.repo.update_redirect_rule(&tenant_id, &id, &insert).await?;
+        self.reload_tenant(tenant_id).await?;
+        Ok(rule)
+    }
+
+    pub async fn delete(&self, tenant_id: Uuid, id: &str) -> Result<(), AppError> {
+        let id = valid_uuid(id)?;
+        self.repo.delete_redirect_rule(&tenant_id, &id).await?;
+        self.reload_tenant(tenant_id).await
+    }
+
+    /// Creates a rule per CSV row; with `dry_run` only reports what would be
+    /// created. Sources that already have a rule are reported and left alone,
+    /// so the same file can be imported again after fixing rejected rows.
+    pub async fn import(&self, tenant_id: Uuid, csv: &[u8], dry_run: bool) -> Result<RedirectImportReport, AppError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(csv);
+
+        let headers = reader.headers()
+            .map_err(|e| AppError::InvalidInput(format!("Invalid CSV header: {}", e)))?
+            .clone();
+        for column in ["source", "target"] {
+            if !headers.iter().any(|header| header == column) {
+                return Err(AppError::InvalidInput(format!("CSV header is missing the '{}' column", column)));
+            }
+        }
+        let source_column = headers.iter().position(|header| header == "source");
+
+        let mut existing: HashSet<(String, String)> = self.repo
+            .list_redirect_rules(&tenant_id)
+            .await?
+            .into_iter()
+            .map(|rule| (rule.kind, rule.source))
+            .collect();
+
+        let mut report = RedirectImportReport {
+            dry_run,
+            total_rows: 0,
+            created: 0,
+            conflicts: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        for (index, record) in reader.records().enumerate() {
+            let row = index + 1;
+            report.total_rows = row;
+
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    report.skipped.push(RedirectImportIssue { row, source: None, reason: e.to_string() });
+                    continue;
+                }
+            };
+            let source = source_column
+                .and_then(|column| record.get(column))
+                .filter(|source| !source.is_empty())
+                .map(str::to_owned);
+            let issue = |reason: String| RedirectImportIssue { row, source: source.clone(), reason };
+
+            let insert = match record.deserialize::<CsvRedirectRow>(Some(&headers)) {
+                Ok(parsed) => NewRedirectRuleRequest {
+                    kind: parsed.kind.filter(|kind| !kind.is_empty()),
+                    source: parsed.source,
+                    target: parsed.target,
+                    status_code: parsed.status_code,
+                }
+                .to_insert(),
+                Err(e) => Err(e.to_string()),
+            };
+            let insert = match insert {
+                Ok(insert) => insert,
+                Err(reason) => {
+                    report.skipped.push(issue(reason));
+                    continue;
+                }
+            };
+
+            if !existing.insert(insert.key()) {
+                report.conflicts.push(issue("A redirect rule for this source already exists".to_string()));
+                continue;
+            }
+
+            if !dry_run {
+                match self.repo.create_redirect_rule(&tenant_id, &insert).await {
+                    Ok(_) => {}
+                    Err(AppError::Conflict(reason)) => {
+                        report.conflicts.push(issue(reason));
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            report.created += 1;
+        }
+
+        if !dry_run && report.created > 0 {
+            self.reload_tenant(tenant_id).await?;
+        }
+
+        tracing::info!(
+            tenant_id = %tenant_id,
+            dry_run,
+            created = report.created,
+            conflicts = report.conflicts.len(),
+            skipped = report.skipped.len(),
+            "Redirect import finished"
+        );
+        Ok(report)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use actix_web::web::Bytes;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        app_setting::RETENTION_POLICIES,
+        audit::{
+            AuditEntryInsert, AuditLogFilter, AUDIT_ACTOR_SYSTEM, AUDIT_CONTACTS_ANONYMIZED, AUDIT_CONTACT_RESTORED,
+            AUDIT_ENTITY_CONTACT_MESSAGE, AUDIT_RETENTION_RUN,
+        },
+        retention::{
+            AnonymizeContactsRequest, RetentionAction, RetentionEntity, RetentionEntityReport, RetentionPolicies,
+            RetentionPolicy, RetentionReport,
+        },
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{app_settings::AppSettingsRepository, audit_log::AuditLogRepository, retention::RetentionRepository},
+    storage::object::ObjectStorage,
+    use_cases::settings::RuntimeSettings,
+};
+
+/// Rows purged, anonymized or archived per statement
+pub const RETENTION_BATCH_SIZE: i64 = 500;
+/// Prefix of archived audit entries in object storage
+const AUDIT_ARCHIVE_PREFIX: &str = "audit-archive";
+
+/// Enforces the tenant's retention policies: rows older than an entity's
+/// period are purged or anonymized in batches of `RETENTION_BATCH_SIZE`.
+///
+/// Policies come from the `retention_policies` setting, falling back to each
+/// entity's default. Every run that changed something, and every run an
+/// admin started, leaves an audit entry with the counts; a run that fails
+/// leaves a `failure` entry.
+///
+/// Archived audit entries go to object storage, one JSON lines object per
+/// batch under `audit-archive/<tenant>/`.
+///
+/// Anonymizing a contact message can be undone with `restore_contact` until
+/// the `anonymized_identities` policy purges the stashed name and email.
+#[derive(Clone)]
+pub struct DataRetention<R, A, S>
+where
+    R: RetentionRepository,
+    A: AuditLogRepository,
+    S: AppSettingsRepository,
+{
+    retention_repo: R,
+    audit_repo: A,
+    settings: RuntimeSettings<S>,
+    storage: Option<Arc<dyn ObjectStorage>>,
+}
+
+impl<R, A, S> DataRetention<R, A, S>
+where
+    R: RetentionRepository,
+    A: AuditLogRepository,
+    S: AppSettingsRepository,
+{
+    pub fn new(
+        retention_repo: R,
+        audit_repo: A,
+        settings: RuntimeSettings<S>,
+        storage: Option<Arc<dyn ObjectStorage>>,
+    ) -> Self {
+        DataRetention { retention_repo, audit_repo, settings, storage }
+    }
+
+    /// The policy in force for every entity
+    pub fn policies(&self, tenant_id: &Uuid) -> RetentionPolicies {
+        let overrides: RetentionPolicies = self.settings.get(tenant_id, RETENTION_POLICIES).unwrap_or_default();
+
+        RetentionEntity::ALL
+            .into_iter()
+            .map(|entity| {
+                let policy = match overrides.get(&entity) {
+                    Some(policy) if policy.problem(entity).is_none() => *policy,
+                    Some(_) => {
+                        tracing::warn!(entity = entity.as_str(), "Ignoring unusable retention policy, using the default");
+                        entity.default_policy()
+                    }
+                    None => entity.default_policy(),
+                };
+                (entity, policy)
+            })
+            .collect()
+    }
+
+    /// Applies every policy, or with `dry_run` only counts the rows that would change
+    pub async fn run(&self, tenant_id: Uuid, dry_run: bool, actor: &str) -> Result<RetentionReport, AppError> {
+        let report = match self.enforce(tenant_id, dry_run).await {
+            Ok(report) => report,
+            Err(e) => {
+                if !dry_run {
+                    let entry = AuditEntryInsert::new(actor, AUDIT_RETENTION_RUN, json!({ "error": e.to_string() })).failed();
+                    if let Err(audit_error) = self.audit_repo.record_entry(&tenant_id, &entry).await {
+                        tracing::warn!("Failed to audit the failed retention run: {}", audit_error);
+                    }
+                }
+                return Err(e);
+            }
+        };
+
+        if !dry_run && (report.total_affected() > 0 || actor != AUDIT_ACTOR_SYSTEM) {
+            let details = json!({ "total": report.total_affected(), "entities": report.entities });
+            self.audit_repo.record_entry(&tenant_id, &AuditEntryInsert::new(actor, AUDIT_RETENTION_RUN, details)).await?;
+        }
+
+        Ok(report)
+    }
+
+    async fn enforce(&self, tenant_id: Uuid, dry_run: bool) -> Result<RetentionReport, AppError> {
+        let started_at = Utc::now();
+        let mut entities = Vec::new();
+
+        for (entity, policy) in self.policies(&tenant_id) {
+            let cutoff = policy.cutoff(started_at);
+            let affected = match cutoff {
+                None => 0,
+                Some(cutoff) if dry_run => {
+                    self.retention_repo.count_expired(&tenant_id, entity, policy.action, cutoff).await?
+                }
+                Some(cutoff) if policy.action == RetentionAction::Archive => self.archive_audit(tenant_id, cutoff).await?,
+                Some(cutoff) => self.apply(tenant_id, entity, policy, cutoff).await?,
+            };
+
+            entities.push(RetentionEntityReport {
+                entity,
+                days: policy.days,
+                action: policy.action,
+                cutoff,
+                affected,
+            });
+        }
+
+        Ok(RetentionReport { dry_run, started_at, finished_at: Utc::now(), entities })
+    }
+
+    /// Anonymizes contact messages older than the request's threshold,
+    /// regardless of the `contact_messages` policy
+    pub async fn anonymize_contacts(
+        &self,
+        tenant_id: Uuid,
+        request: AnonymizeContactsRequest,
+        actor: &str,
+    ) -> Result<RetentionReport, AppError> {
+        request.validate()?;
+
+        let started_at = Utc::now();
+        let entity = RetentionEntity::ContactMessages;
+        let policy = RetentionPolicy { days: request.older_than_days, action: RetentionAction::Anonymize };
+        let cutoff = policy.cutoff(started_at);
+
+        let affected = match cutoff {
+            None => 0,
+            Some(cutoff) if request.dry_run => {
+                self.retention_repo.count_expired(&tenant_id, entity, policy.action, cutoff).await?
+            }
+            Some(cutoff) => self.apply(tenant_id, entity, policy, cutoff).await?,
+        };
+
+        if !request.dry_run {
+            let details = json!({ "older_than_days": request.older_than_days, "total": affected });
+            let entry = AuditEntryInsert::new(actor, AUDIT_CONTACTS_ANONYMIZED, details).about(AUDIT_ENTITY_CONTACT_MESSAGE, None);
+            self.audit_repo.record_entry(&tenant_id, &entry).await?;
+        }
+
+        Ok(RetentionReport {
+            dry_run: request.dry_run,
+            started_at,
+            finished_at: Utc::now(),
+            entities: vec![RetentionEntityReport { entity, days: policy.days, action: policy.action, cutoff, affected }],
+        })
+    }
+
+    /// Undoes the anonymization of one contact message
+    pub async fn restore_contact(&self, tenant_id: Uuid, message_id: Uuid, actor: &str) -> Result<(), AppError> {
+        if !self.retention_repo.restore_contact_identity(&tenant_id, &message_id).await? {
+            return Err(AppError::NotFound(
+                "No anonymized contact details are kept for this message".to_string(),
+            ));
+        }
+
+        let entry = AuditEntryInsert::new(actor, AUDIT_CONTACT_RESTORED, json!({ "message_id": message_id }))
+            .about(AUDIT_ENTITY_CONTACT_MESSAGE, Some(message_id.to_string()));
+        self.audit_repo.record_entry(&tenant_id, &entry).await
+    }
+
+    async fn apply(
+        &self,
+        tenant_id: Uuid,
+        entity: RetentionEntity,
+        policy: RetentionPolicy,
+        cutoff: chrono::DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let mut affected = 0;
+
+        loop {
+            let changed = self.retention_repo
+                .apply_expired(&tenant_id, entity, policy.action, cutoff, RETENTION_BATCH_SIZE)
+                .await?;
+            affected += changed;
+
+            if changed < RETENTION_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+
+        METRICS.add(&format!("retention_rows_total{{entity=\"{}\"}}", entity.as_str()), affected);
+
+        Ok(affected as i64)
+    }
+
+    /// Moves audit entries older than `cutoff` to object storage, one batch
+    /// per object; each batch is deleted only after its upload succeeded
+    async fn archive_audit(&self, tenant_id: Uuid, cutoff: DateTime<Utc>) -> Result<i64, AppError> {
+        let Some(storage) = &self.storage else {
+            tracing::warn!(tenant_id = %tenant_id, "Object storage is unavailable; audit entries are not archived");
+            return Ok(0);
+        };
+
+        let expired = AuditLogFilter { to: Some(cutoff), ..Default::default() };
+        let mut archived = 0;
+
+        loop {
+            let entries = self.audit_repo
+                .list_entries_chunk(&tenant_id, &expired, None, RETENTION_BATCH_SIZE)
+                .await?;
+            let Some(last) = entries.last() else {
+                break;
+            };
+
+            let mut body = Vec::new();
+            for entry in &entries {
+                serde_json::to_writer(&mut body, entry)
+                    .map_err(|e| AppError::InternalError(format!("Failed to encode audit entry: {}", e)))?;
+                body.push(b'\n');
+            }
+            let key = format!(
+                "{}/{}/{}-{}.jsonl",
+                AUDIT_ARCHIVE_PREFIX,
+                tenant_id,
+                last.created_at.format("%Y%m%dT%H%M%S"),
+                last.id.simple()
+            );
+            storage.put(&key, Bytes::from(body), "application/x-ndjson").await?;
+
+            let ids: Vec<Uuid> = entries.iter().map(|e| e.id).collect();
+            archived += self.audit_repo.delete_entries(&tenant_id, &ids).await?;
+
+            if entries.len() < RETENTION_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        METRICS.add(&format!("retention_rows_total{{entity=\"{}\"}}", RetentionEntity::Audit.as_str()), archived);
+
+        Ok(archived as i64)
+    }
+}
\ No newline at end of file
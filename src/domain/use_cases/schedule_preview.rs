@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        about_me::AboutMeResponse,
+        blog_post::CalendarStatus,
+        schedule_preview::{AboutPreview, PreviewAtQuery, PreviewPost, SitePreview},
+    },
+    errors::AppError,
+    repositories::{about::AboutRepository, blog_post::BlogPostRepository},
+};
+
+/// Posts read per page while evaluating
+const POSTS_PAGE_SIZE: u32 = 100;
+
+/// Evaluates scheduled content at a future time with the same rules the
+/// public site uses: the about revision by effective date and posts by
+/// `CalendarStatus`. Content as it is stored today is assumed unchanged.
+#[derive(Clone)]
+pub struct SchedulePreview<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    blog_repo: B,
+    about_repo: A,
+}
+
+impl<B, A> SchedulePreview<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub fn new(blog_repo: B, about_repo: A) -> Self {
+        SchedulePreview { blog_repo, about_repo }
+    }
+
+    pub async fn preview_at(&self, tenant_id: Uuid, query: PreviewAtQuery) -> Result<SitePreview, AppError> {
+        let now = Utc::now();
+        let at = query.timestamp;
+        if at < now {
+            return Err(AppError::InvalidInput("timestamp must not be in the past".to_string()));
+        }
+
+        let about_now = self.about_as_of(tenant_id, now).await?;
+        let about = self.about_as_of(tenant_id, at).await?.map(|about| AboutPreview {
+            changed: about_now.as_ref().is_none_or(|current| current.id != about.id),
+            about,
+        });
+
+        let mut live_posts = Vec::new();
+        let mut scheduled_posts = Vec::new();
+        for page in 1.. {
+            let posts = self.blog_repo.get_all_blog_posts(&tenant_id, false, page, POSTS_PAGE_SIZE).await?;
+            let last_page = posts.len() < POSTS_PAGE_SIZE as usize;
+
+            for post in &posts {
+                match CalendarStatus::of(post, at) {
+                    CalendarStatus::Published => live_posts.push(PreviewPost::new(post, now)),
+                    CalendarStatus::Scheduled => scheduled_posts.push(PreviewPost::new(post, now)),
+                    CalendarStatus::Draft => {}
+                }
+            }
+            if last_page {
+                break;
+            }
+        }
+
+        live_posts.sort_by(|a, b| b.published_at.cmp(&a.published_at).then_with(|| a.title.cmp(&b.title)));
+        scheduled_posts.sort_by(|a, b| a.published_at.cmp(&b.published_at).then_with(|| a.title.cmp(&b.title)));
+        let newly_live = live_posts.iter().filter(|post| post.status_now != CalendarStatus::Published).count();
+
+        Ok(SitePreview { at, about, live_posts, newly_live, scheduled_posts })
+    }
+
+    async fn about_as_of(&self, tenant_id: Uuid, at: DateTime<Utc>) -> Result<Option<AboutMeResponse>, AppError> {
+        match self.about_repo.get_about_me_as_of(&tenant_id, at.date_naive()).await {
+            Ok(about) => Ok(Some(about)),
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::{
+    entities::schema::{BundledMigration, SchemaStatus},
+    errors::AppError,
+    repositories::schema::SchemaRepository,
+};
+
+/// How long `--wait-for-migrations` waits when no timeout is given
+pub const DEFAULT_MIGRATION_WAIT: Duration = Duration::from_secs(300);
+/// How often the history is re-read while waiting
+pub const MIGRATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Compares the live schema with the migrations this build bundles
+#[derive(Clone)]
+pub struct SchemaMigrations<S: SchemaRepository> {
+    schema_repo: S,
+    bundled: Arc<Vec<BundledMigration>>,
+}
+
+impl<S: SchemaRepository> SchemaMigrations<S> {
+    pub fn new(schema_repo: S, bundled: Vec<BundledMigration>) -> Self {
+        Self { schema_repo, bundled: Arc::new(bundled) }
+    }
+
+    pub async fn status(&self) -> Result<SchemaStatus, AppError> {
+        let applied = self.schema_repo.applied_migrations().await?;
+        Ok(SchemaStatus::compare(&self.bundled, &applied))
+    }
+
+    /// Polls until another instance (or a `--migrate-only` job) has applied
+    /// every bundled migration. Errors reading the history count as not
+    /// ready yet, since the database may still be starting.
+    pub async fn wait_until_up_to_date(&self, timeout: Duration, poll: Duration) -> Result<SchemaStatus, AppError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let last = match self.status().await {
+                Ok(status) if status.is_up_to_date() => return Ok(status),
+                Ok(status) => status.summary(),
+                Err(e) => e.to_string(),
+            };
+
+            if Instant::now() + poll > deadline {
+                return Err(AppError::ServiceUnavailable(format!(
+                    "Schema not up to date after {}s: {}",
+                    timeout.as_secs(),
+                    last
+                )));
+            }
+            tracing::info!("Waiting for migrations: {}", last);
+            tokio::time::sleep(poll).await;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        blog_post::{normalize_slug, BlogPost},
+        option_fields::OptionField,
+        series::{NewSeriesRequest, Series, SeriesDetailResponse, SeriesInsert, SetSeriesPostsRequest, UpdateSeriesRequest},
+    },
+    errors::AppError,
+    repositories::blog_post::BlogPostRepository,
+    use_cases::blog::with_authors,
+    utils::valid_uuid::valid_uuid,
+};
+
+/// Posts whose series navigation changed, so their cached pages can be refreshed
+#[derive(Debug, Default)]
+pub struct SeriesChange {
+    pub published: Vec<Uuid>,
+    pub unpublished: Vec<Uuid>,
+}
+
+impl<'a> FromIterator<&'a BlogPost> for SeriesChange {
+    fn from_iter<I: IntoIterator<Item = &'a BlogPost>>(posts: I) -> Self {
+        let mut change = SeriesChange::default();
+        for post in posts {
+            match post.published {
+                true => change.published.push(post.id),
+                false => change.unpublished.push(post.id),
+            }
+        }
+        change
+    }
+}
+
+/// Series live alongside posts in the blog repository
+pub struct SeriesHandler<R>
+where
+    R: BlogPostRepository,
+{
+    pub blog_post_repo: R,
+}
+
+impl<R> SeriesHandler<R>
+where
+    R: BlogPostRepository,
+{
+    pub fn new(blog_post_repo: R) -> Self {
+        SeriesHandler { blog_post_repo }
+    }
+
+    pub async fn create_series(&self, tenant_id: Uuid, request: NewSeriesRequest) -> Result<Series, AppError> {
+        request.validate()?;
+
+        let slug = match request.slug {
+            Some(slug) => slug,
+            None => normalize_slug(&request.title).ok_or_else(|| {
+                AppError::InvalidInput("Provide a slug or a title long enough to derive one".to_string())
+            })?,
+        };
+
+        let insert = SeriesInsert {
+            title: request.title.trim().to_string(),
+            slug,
+            description: request.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()),
+        };
+        self.blog_post_repo.create_series(&tenant_id, &insert).await
+    }
+
+    pub async fn update_series(&self, tenant_id: Uuid, id: &str, request: UpdateSeriesRequest) -> Result<Series, AppError> {
+        request.validate()?;
+
+        if matches!(request.title, OptionField::SetToNull) || matches!(request.slug, OptionField::SetToNull) {
+            return Err(AppError::InvalidInput("A series always has a title and a slug".to_string()));
+        }
+
+        let valid_id = valid_uuid(id)?;
+        self.blog_post_repo.update_series(&tenant_id, &valid_id, &request).await
+    }
+
+    /// Returns the posts that were in the series
+    pub async fn delete_series(&self, tenant_id: Uuid, id: &str) -> Result<SeriesChange, AppError> {
+        let valid_id = valid_uuid(id)?;
+        let members = self.blog_post_repo.get_series_posts(&tenant_id, &valid_id, false).await?;
+        self.blog_post_repo.delete_series(&tenant_id, &valid_id).await?;
+
+        Ok(members.iter().collect())
+    }
+
+    pub async fn list_series(&self, tenant_id: Uuid) -> Result<Vec<Series>, AppError> {
+        self.blog_post_repo.list_series(&tenant_id).await
+    }
+
+    /// Public view: published posts in reading order
+    pub async fn get_series(&self, tenant_id: Uuid, slug: &str) -> Result<SeriesDetailResponse, AppError> {
+        let series = self.blog_post_repo
+            .get_series_by_slug(&tenant_id, slug)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Series not found".to_string()))?;
+
+        let posts = self.blog_post_repo.get_series_posts(&tenant_id, &series.id, true).await?;
+        let posts = with_authors(&self.blog_post_repo, tenant_id, posts).await?;
+
+        Ok(SeriesDetailResponse { series, posts })
+    }
+
+    /// Admin view of every member, drafts included
+    pub async fn get_series_posts(&self, tenant_id: Uuid, id: &str) -> Result<SeriesDetailResponse, AppError> {
+        let valid_id = valid_uuid(id)?;
+        let series = self.find_series(tenant_id, valid_id).await?;
+
+        let posts = self.blog_post_repo.get_series_posts(&tenant_id, &valid_id, false).await?;
+        let posts = with_authors(&self.blog_post_repo, tenant_id, posts).await?;
+
+        Ok(SeriesDetailResponse { series, posts })
+    }
+
+    /// Sets the members and their order in one go. Returns the new view and
+    /// every post, old or new member, whose navigation changed.
+    pub async fn set_series_posts(
+        &self,
+        tenant_id: Uuid,
+        id: &str,
+        request: SetSeriesPostsRequest,
+    ) -> Result<(SeriesDetailResponse, SeriesChange), AppError> {
+        request.validate()?;
+
+        let mut seen = HashSet::new();
+        if !request.post_ids.iter().all(|post_id| seen.insert(*post_id)) {
+            return Err(AppError::InvalidInput("A post can only appear once in a series".to_string()));
+        }
+
+        let valid_id = valid_uuid(id)?;
+        let before = self.blog_post_repo.get_series_posts(&tenant_id, &valid_id, false).await?;
+        self.blog_post_repo.set_series_posts(&tenant_id, &valid_id, &request.post_ids).await?;
+        let detail = self.get_series_posts(tenant_id, id).await?;
+
+        let removed = before.iter().filter(|post| !seen.contains(&post.id));
+        let change = removed.chain(detail.posts.iter().map(|authored| &authored.post)).collect();
+
+        Ok((detail, change))
+    }
+
+    async fn find_series(&self, tenant_id: Uuid, id: Uuid) -> Result<Series, AppError> {
+        self.blog_post_repo
+            .get_series_by_id(&tenant_id, &id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Series not found".to_string()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::RwLock;
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+use validator::{Validate, ValidateEmail};
+
+use crate::{
+    entities::{
+        app_setting::{
+            AppSetting, NotificationPolicy, CONTACT_AUTO_REPLY, CONTACT_DIGEST_LAST_SENT_AT, CONTACT_NOTIFICATION_EMAIL,
+            CONTACT_NOTIFICATION_POLICY, EMAIL_LOCALE, HIRE_AVAILABILITY, HIRE_NOTIFICATION_EMAIL,
+            OUTBOUND_ALLOWED_DOMAINS, RETENTION_POLICIES, SITE_PAGES,
+        },
+        contact_me::ContactAutoReply,
+        retention::RetentionPolicies,
+        site_page::SitePageSettings,
+        hire::HireAvailability,
+        tenant::{is_valid_hostname, normalize_host},
+    },
+    errors::AppError,
+    mailer::templates::Locale,
+    repositories::app_settings::AppSettingsRepository,
+};
+
+/// Runtime settings backed by the `app_settings` table, kept per tenant.
+///
+/// Reads come from an in-process cache; writes go to the database first and
+/// then update the cache. Values missing from the table fall back to whatever
+/// default the caller supplies (usually from `AppConfig`).
+#[derive(Clone)]
+pub struct RuntimeSettings<R>
+where
+    R: AppSettingsRepository,
+{
+    pub settings_repo: R,
+    cache: Arc<RwLock<HashMap<Uuid, HashMap<String, JsonValue>>>>,
+}
+
+impl<R> RuntimeSettings<R>
+where
+    R: AppSettingsRepository,
+{
+    pub fn new(settings_repo: R) -> Self {
+        RuntimeSettings {
+            settings_repo,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Typed lookup; `None` if unset or stored in an unexpected shape
+    pub fn get<T: DeserializeOwned>(&self, tenant_id: &Uuid, key: &str) -> Option<T> {
+        let value = self.cache.read().get(tenant_id)?.get(key).cloned()?;
+
+        serde_json::from_value(value)
+            .map_err(|e| tracing::warn!(key, error = %e, "Ignoring malformed runtime setting"))
+            .ok()
+    }
+
+    /// Reloads every tenant's settings from the database, returning how many were loaded
+    pub async fn refresh(&self) -> Result<usize, AppError> {
+        let settings = self.settings_repo.list_all_settings().await?;
+        let count = settings.len();
+
+        let mut fresh: HashMap<Uuid, HashMap<String, JsonValue>> = HashMap::new();
+        for setting in settings {
+            fresh.entry(setting.tenant_id).or_default().insert(setting.key, setting.value);
+        }
+
+        *self.cache.write() = fresh;
+        Ok(count)
+    }
+
+    pub async fn list_settings(&self, tenant_id: Uuid) -> Result<Vec<AppSetting>, AppError> {
+        self.settings_repo.list_settings(&tenant_id).await
+    }
+
+    /// Admin entry point: only known, editable keys with well-formed values are accepted
+    pub async fn update_setting(&self, tenant_id: Uuid, key: &str, value: JsonValue) -> Result<AppSetting, AppError> {
+        check_setting(key, &value)?;
+
+        self.store(tenant_id, key, value).await
+    }
+
+    /// Internal writes (background jobs) skip the admin key whitelist
+    pub async fn set<T: Serialize>(&self, tenant_id: Uuid, key: &str, value: &T) -> Result<AppSetting, AppError> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode setting: {}", e)))?;
+
+        self.store(tenant_id, key, value).await
+    }
+
+    async fn store(&self, tenant_id: Uuid, key: &str, value: JsonValue) -> Result<AppSetting, AppError> {
+        let setting = self.settings_repo.upsert_setting(&tenant_id, key, &value).await?;
+
+        self.cache
+            .write()
+            .entry(tenant_id)
+            .or_default()
+            .insert(setting.key.clone(), setting.value.clone());
+        Ok(setting)
+    }
+
+    /// Applies a change already written to the database to the local cache;
+    /// `None` removes the key
+    pub fn apply_cached(&self, tenant_id: Uuid, key: &str, value: Option<JsonValue>) {
+        let mut cache = self.cache.write();
+        let settings = cache.entry(tenant_id).or_default();
+        match value {
+            Some(value) => settings.insert(key.to_string(), value),
+            None => settings.remove(key),
+        };
+    }
+}
+
+/// Whether `value` may be stored under `key` through the admin API: the key
+/// must be known and editable, the value well-formed
+pub fn check_setting(key: &str, value: &JsonValue) -> Result<(), AppError> {
+    match key {
+        CONTACT_NOTIFICATION_POLICY => {
+            serde_json::from_value::<NotificationPolicy>(value.clone()).map_err(|_| {
+                AppError::InvalidInput(
+                    "contact_notification_policy must be one of: immediate, hourly, daily".to_string()
+                )
+            })?;
+        }
+        CONTACT_NOTIFICATION_EMAIL | HIRE_NOTIFICATION_EMAIL => {
+            if !value.as_str().is_some_and(|email| email.validate_email()) {
+                return Err(AppError::InvalidInput(
+                    format!("{} must be a valid email address", key)
+                ));
+            }
+        }
+        CONTACT_AUTO_REPLY => {
+            serde_json::from_value::<ContactAutoReply>(value.clone())
+                .map_err(|e| AppError::InvalidInput(format!("Invalid contact_auto_reply: {}", e)))?
+                .validate()?;
+        }
+        HIRE_AVAILABILITY => {
+            serde_json::from_value::<HireAvailability>(value.clone())
+                .map_err(|e| AppError::InvalidInput(format!("Invalid hire_availability: {}", e)))?
+                .validate()?;
+        }
+        OUTBOUND_ALLOWED_DOMAINS => {
+            let domains = serde_json::from_value::<Vec<String>>(value.clone()).map_err(|_| {
+                AppError::InvalidInput("outbound_allowed_domains must be an array of hostnames".to_string())
+            })?;
+            if let Some(bad) = domains.iter().find(|d| normalize_host(d) != **d || !is_valid_hostname(d)) {
+                return Err(AppError::InvalidInput(format!("'{}' is not a valid lowercase hostname", bad)));
+            }
+        }
+        EMAIL_LOCALE => {
+            serde_json::from_value::<Locale>(value.clone()).map_err(|_| {
+                let codes: Vec<&str> = Locale::ALL.iter().map(Locale::code).collect();
+                AppError::InvalidInput(format!("email_locale must be one of: {}", codes.join(", ")))
+            })?;
+        }
+        RETENTION_POLICIES => {
+            let policies = serde_json::from_value::<RetentionPolicies>(value.clone())
+                .map_err(|e| AppError::InvalidInput(format!("Invalid retention_policies: {}", e)))?;
+            if let Some(problem) = policies.iter().find_map(|(entity, policy)| policy.problem(*entity)) {
+                return Err(AppError::InvalidInput(problem));
+            }
+        }
+        SITE_PAGES => {
+            serde_json::from_value::<SitePageSettings>(value.clone())
+                .map_err(|e| AppError::InvalidInput(format!("Invalid site_pages: {}", e)))?
+                .validate()?;
+        }
+        CONTACT_DIGEST_LAST_SENT_AT => {
+            return Err(AppError::ForbiddenAccess);
+        }
+        _ => return Err(AppError::NotFound(format!("Unknown setting '{}'", key))),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::future::join_all;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        blog_post::BlogPost,
+        share_count::{PostShareCounts, ShareCountInsert},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    links::shares::{ShareCountProvider, ShareFetchError},
+    metrics::METRICS,
+    repositories::{blog_post::BlogPostRepository, share_count::ShareCountRepository},
+    settings::AppConfig,
+    utils::{public_urls::PublicUrls, valid_uuid::valid_uuid},
+};
+
+/// Posts read per page while collecting URLs
+const POSTS_PAGE_SIZE: u32 = 100;
+/// How long a network is left alone after a 429 that did not say for how long
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// Counts from one tenant's run
+#[derive(Debug, Default)]
+pub struct ShareCountRun {
+    pub posts: usize,
+    pub fetched: usize,
+    /// Networks that were rate limiting or unavailable, so kept their old counts
+    pub skipped_networks: Vec<&'static str>,
+}
+
+/// Periodically asks each configured network how often every published post
+/// was shared, keeping the latest count per post and network for display.
+///
+/// Networks are queried side by side, but requests to one network are
+/// spaced out. A network that rate limits us is paused (for every tenant,
+/// since the limit is on our side) until it said to come back; one that is
+/// unavailable is skipped for the rest of the run. Either way the counts it
+/// gave before stay in place.
+#[derive(Clone)]
+pub struct ShareCounts<S, B>
+where
+    S: ShareCountRepository,
+    B: BlogPostRepository,
+{
+    pub share_count_repo: S,
+    blog_repo: B,
+    providers: Vec<Arc<dyn ShareCountProvider>>,
+    spacing: Duration,
+    urls: PublicUrls,
+    paused_until: Arc<Mutex<HashMap<&'static str, Instant>>>,
+}
+
+impl<S, B> ShareCounts<S, B>
+where
+    S: ShareCountRepository,
+    B: BlogPostRepository,
+{
+    pub fn new(share_count_repo: S, blog_repo: B, providers: Vec<Arc<dyn ShareCountProvider>>, config: &AppConfig) -> Self {
+        ShareCounts {
+            share_count_repo,
+            blog_repo,
+            providers,
+            spacing: Duration::from_millis(config.share_count_request_spacing_ms),
+            urls: PublicUrls::from_config(config),
+            paused_until: Arc::default(),
+        }
+    }
+
+    /// Whether any network is configured
+    pub fn enabled(&self) -> bool {
+        !self.providers.is_empty()
+    }
+
+    /// Fetches fresh counts for every published post of the tenant
+    pub async fn refresh_tenant(&self, tenant: &Tenant) -> Result<ShareCountRun, AppError> {
+        // Posts are shared under the tenant's own host; the default tenant
+        // may only have `public_base_url`
+        if !self.enabled() || self.urls.site_origin(tenant).is_empty() {
+            return Ok(ShareCountRun::default());
+        }
+
+        let posts = self.published_posts(tenant.id).await?;
+        let targets: Vec<(Uuid, String)> = posts
+            .iter()
+            .map(|post| (post.id, self.urls.site(tenant, &format!("/blog/{}", post.slug))))
+            .collect();
+
+        let results = join_all(self.providers.iter().map(|provider| self.fetch_network(provider.as_ref(), &targets))).await;
+
+        let mut run = ShareCountRun { posts: posts.len(), ..Default::default() };
+        let mut counts = Vec::new();
+        for (network, fetched, complete) in results {
+            if !complete {
+                run.skipped_networks.push(network);
+            }
+            counts.extend(fetched);
+        }
+        run.fetched = counts.len();
+
+        self.share_count_repo.record_share_counts(&tenant.id, &counts, Utc::now()).await?;
+        METRICS.incr("share_count_runs_total");
+
+        Ok(run)
+    }
+
+    /// The latest counts for a published post
+    pub async fn post_share_counts(&self, tenant_id: Uuid, post_id: &str) -> Result<PostShareCounts, AppError> {
+        let valid_id = valid_uuid(post_id)?;
+        let post = self.blog_repo.get_blog_post_by_id(&tenant_id, &valid_id).await?;
+        if !post.published {
+            return Err(AppError::NotFound("Blog post not found".to_string()));
+        }
+
+        let counts = self.share_count_repo.get_post_share_counts(&tenant_id, &valid_id).await?;
+        Ok(PostShareCounts::from_counts(valid_id, counts))
+    }
+
+    /// Counts one network gave for `targets`, and whether it answered for all of them
+    async fn fetch_network(
+        &self,
+        provider: &dyn ShareCountProvider,
+        targets: &[(Uuid, String)],
+    ) -> (&'static str, Vec<ShareCountInsert>, bool) {
+        let network = provider.network();
+        let mut counts = Vec::new();
+
+        for (i, (post_id, url)) in targets.iter().enumerate() {
+            if self.is_paused(network) {
+                return (network, counts, false);
+            }
+            if i > 0 && !self.spacing.is_zero() {
+                tokio::time::sleep(self.spacing).await;
+            }
+
+            match provider.share_count(url).await {
+                Ok(shares) => counts.push(ShareCountInsert {
+                    post_id: *post_id,
+                    network,
+                    shares: i64::try_from(shares).unwrap_or(i64::MAX),
+                }),
+                Err(ShareFetchError::RateLimited { retry_after }) => {
+                    let backoff = retry_after.unwrap_or(DEFAULT_BACKOFF);
+                    tracing::warn!(network, backoff_secs = backoff.as_secs(), "Share count network is rate limiting; pausing it");
+                    self.paused_until.lock().insert(network, Instant::now() + backoff);
+                    METRICS.incr(&format!("share_count_rate_limited_total{{network=\"{}\"}}", network));
+                    return (network, counts, false);
+                }
+                Err(ShareFetchError::Unavailable(reason)) => {
+                    tracing::warn!(network, "Share count network unavailable, keeping previous counts: {}", reason);
+                    METRICS.incr(&format!("share_count_unavailable_total{{network=\"{}\"}}", network));
+                    return (network, counts, false);
+                }
+            }
+        }
+
+        (network, counts, true)
+    }
+
+    fn is_paused(&self, network: &'static str) -> bool {
+        let mut paused_until = self.paused_until.lock();
+        match paused_until.get(network) {
+            Some(until) if *until > Instant::now() => true,
+            Some(_) => {
+                paused_until.remove(network);
+                false
+            }
+            None => false,
+        }
+    }
+
+    async fn published_posts(&self, tenant_id: Uuid) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts = Vec::new();
+
+        for page in 1.. {
+            let batch = self.blog_repo.get_all_blog_posts(&tenant_id, true, page, POSTS_PAGE_SIZE).await?;
+            let last_page = batch.len() < POSTS_PAGE_SIZE as usize;
+            posts.extend(batch);
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(posts)
+    }
+}
@@ -0,0 +1,427 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use askama::Template;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    entities::{about_me::AboutMeResponse, blog_post::AuthoredPost, tenant::Tenant, view::Viewed},
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{
+        about::AboutRepository, blog_post::BlogPostRepository, changelog::ChangelogRepository, uses::UsesRepository,
+    },
+    settings::AppConfig,
+    storage::object::ObjectStorage,
+    use_cases::{blog::with_authors, feed::{escape_xml, FeedHandler}, uses::UsesHandler},
+    utils::{
+        front_matter::{FrontMatterValue, PostFrontMatter},
+        markdown::safe_markdown_to_html,
+        public_urls::PublicUrls,
+    },
+};
+
+/// Published posts read per page while exporting
+const EXPORT_PAGE_SIZE: u32 = 100;
+/// How long the manifest link of a finished export works
+const MANIFEST_LINK_TTL: Duration = Duration::from_secs(3600);
+
+const HTML: &str = "text/html; charset=utf-8";
+const MARKDOWN: &str = "text/markdown; charset=utf-8";
+const JSON: &str = "application/json";
+const XML: &str = "application/xml; charset=utf-8";
+const RSS: &str = "application/rss+xml; charset=utf-8";
+
+/// Where a tenant's export stands; kept after it finishes until the next run
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticExportProgress {
+    pub tenant: String,
+    /// Storage prefix every file of this export is written under
+    pub prefix: String,
+    pub files: usize,
+    pub bytes: u64,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    /// Link to `manifest.json` once the export finished
+    pub manifest_url: Option<String>,
+}
+
+impl StaticExportProgress {
+    fn started(tenant: &Tenant) -> Self {
+        let started_at = Utc::now();
+        StaticExportProgress {
+            tenant: tenant.slug.clone(),
+            prefix: format!("exports/{}/site-{}", tenant.slug, started_at.format("%Y%m%d-%H%M%S")),
+            files: 0,
+            bytes: 0,
+            started_at,
+            finished_at: None,
+            error: None,
+            manifest_url: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.finished_at.is_none()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestEntry {
+    path: String,
+    size: u64,
+    content_type: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest<'a> {
+    tenant: &'a str,
+    exported_at: DateTime<Utc>,
+    files: &'a [ManifestEntry],
+}
+
+/// Writes every public page of a tenant as static files into object storage,
+/// so the portfolio can be mirrored (e.g. to GitHub Pages) and served from
+/// there while the API is down.
+///
+/// An export goes under its own `exports/<tenant>/site-<timestamp>/` prefix:
+/// HTML pages for the index, each post and the about page, each post as
+/// Markdown, the RSS feed, a sitemap, the uses page as Markdown, and JSON
+/// copies of the post and about endpoints under `api/v1/`. `manifest.json`
+/// lists every file. One run per tenant at a time.
+#[derive(Clone)]
+pub struct StaticSiteExporter<B, C, A, U>
+where
+    B: BlogPostRepository,
+    C: ChangelogRepository,
+    A: AboutRepository,
+    U: UsesRepository,
+{
+    blog_repo: B,
+    about_repo: A,
+    feed: FeedHandler<B, C>,
+    uses: UsesHandler<U>,
+    storage: Option<Arc<dyn ObjectStorage>>,
+    urls: PublicUrls,
+    runs: Arc<RwLock<HashMap<Uuid, StaticExportProgress>>>,
+}
+
+impl<B, C, A, U> StaticSiteExporter<B, C, A, U>
+where
+    B: BlogPostRepository + Clone,
+    C: ChangelogRepository,
+    A: AboutRepository,
+    U: UsesRepository,
+{
+    pub fn new(
+        blog_repo: B,
+        about_repo: A,
+        feed: FeedHandler<B, C>,
+        uses: UsesHandler<U>,
+        storage: Option<Arc<dyn ObjectStorage>>,
+        config: &AppConfig,
+    ) -> Self {
+        StaticSiteExporter {
+            blog_repo,
+            about_repo,
+            feed,
+            uses,
+            storage,
+            urls: PublicUrls::from_config(config),
+            runs: Arc::default(),
+        }
+    }
+
+    /// The running or last finished export of the tenant
+    pub fn progress(&self, tenant_id: &Uuid) -> Option<StaticExportProgress> {
+        self.runs.read().get(tenant_id).cloned()
+    }
+
+    /// Claims the tenant for a new export; `Conflict` while one is in
+    /// progress, `ServiceUnavailable` without object storage
+    pub fn begin(&self, tenant: &Tenant) -> Result<StaticExportProgress, AppError> {
+        self.storage()?;
+
+        let mut runs = self.runs.write();
+        if runs.get(&tenant.id).is_some_and(StaticExportProgress::is_running) {
+            return Err(AppError::Conflict("A static export is already running for this site".to_string()));
+        }
+
+        let progress = StaticExportProgress::started(tenant);
+        runs.insert(tenant.id, progress.clone());
+
+        Ok(progress)
+    }
+
+    /// Runs an export claimed with `begin`
+    pub async fn run(&self, tenant: &Tenant) -> Result<StaticExportProgress, AppError> {
+        let prefix = self.progress(&tenant.id)
+            .map(|progress| progress.prefix)
+            .unwrap_or_else(|| StaticExportProgress::started(tenant).prefix);
+
+        let result = self.export(tenant, &prefix).await;
+
+        let mut runs = self.runs.write();
+        let progress = runs.entry(tenant.id).or_insert_with(|| StaticExportProgress::started(tenant));
+        progress.finished_at = Some(Utc::now());
+        METRICS.incr("static_exports_total");
+
+        match result {
+            Ok(manifest_url) => {
+                progress.manifest_url = Some(manifest_url);
+                Ok(progress.clone())
+            }
+            Err(e) => {
+                progress.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    fn storage(&self) -> Result<&Arc<dyn ObjectStorage>, AppError> {
+        self.storage
+            .as_ref()
+            .ok_or_else(|| AppError::ServiceUnavailable("Object storage is not available".to_string()))
+    }
+
+    /// Writes every file and returns a link to the manifest
+    async fn export(&self, tenant: &Tenant, prefix: &str) -> Result<String, AppError> {
+        let exported_at = Utc::now();
+        let mut out = ExportWriter { exporter: self, tenant, prefix, files: Vec::new() };
+
+        let posts = self.published_posts(tenant).await?;
+        let about = match self.about_repo.get_current_about_me(&tenant.id).await {
+            Ok(about) => Some(about),
+            Err(AppError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let layout = |root: &'static str| SiteLayout {
+            site_name: &tenant.name,
+            root,
+            canonical_url: None,
+            has_about: about.is_some(),
+            exported_at: exported_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        };
+        let site_url = self.site_url(tenant);
+
+        for authored in &posts {
+            let post = &authored.post;
+            let page = PostPage {
+                layout: SiteLayout {
+                    canonical_url: site_url.as_ref().map(|base| format!("{}/blog/{}", base, post.slug)),
+                    ..layout("../../")
+                },
+                title: &post.title,
+                date: post_date(authored),
+                authors: authored.authors
+                    .iter()
+                    .filter_map(|author| author.name.as_deref().or(author.username.as_deref()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                tags: post.tags.as_deref().unwrap_or_default(),
+                content_html: safe_markdown_to_html(&post.content_markdown),
+            };
+            out.write(&format!("posts/{}/index.html", post.slug), render(&page)?, HTML).await?;
+            out.write(&format!("posts/{}.md", post.slug), post_markdown(authored)?, MARKDOWN).await?;
+            out.write(&format!("api/v1/blog/posts/{}.json", post.id), to_json(authored)?, JSON).await?;
+        }
+
+        let links: Vec<PostLink> = posts
+            .iter()
+            .map(|authored| PostLink {
+                title: authored.post.title.clone(),
+                path: authored.post.slug.clone(),
+                date: post_date(authored),
+                excerpt: authored.post.excerpt.clone(),
+            })
+            .collect();
+        let index = IndexPage { layout: SiteLayout { canonical_url: site_url.clone(), ..layout("") }, posts: &links };
+        out.write("index.html", render(&index)?, HTML).await?;
+        out.write("api/v1/blog/posts.json", to_json(&posts)?, JSON).await?;
+
+        if let Some(about) = &about {
+            let page = ContentPage {
+                layout: SiteLayout {
+                    canonical_url: site_url.as_ref().map(|base| format!("{}/about", base)),
+                    ..layout("../")
+                },
+                title: "About",
+                content_html: &about.content_html,
+            };
+            out.write("about/index.html", render(&page)?, HTML).await?;
+            out.write("api/v1/about-me/introduction.json", to_json(about)?, JSON).await?;
+        }
+
+        if !self.uses.list_grouped(tenant.id).await?.categories.is_empty() {
+            out.write("uses.md", self.uses.export_markdown(tenant.id).await?, MARKDOWN).await?;
+        }
+
+        out.write("feed/rss.xml", self.feed.rss(tenant).await?, RSS).await?;
+        if let Some(base) = &site_url {
+            out.write("sitemap.xml", render_sitemap(base, &posts, about.as_ref()), XML).await?;
+        }
+
+        let manifest = Manifest { tenant: &tenant.slug, exported_at, files: &out.files };
+        let manifest_key = format!("{}/manifest.json", prefix);
+        self.storage()?.put(&manifest_key, Bytes::from(to_json(&manifest)?), JSON).await?;
+
+        self.storage()?.presign(&manifest_key, MANIFEST_LINK_TTL).await
+    }
+
+    async fn published_posts(&self, tenant: &Tenant) -> Result<Vec<AuthoredPost>, AppError> {
+        let mut posts = Vec::new();
+        for page in 1.. {
+            let batch = self.blog_repo.get_all_blog_posts(&tenant.id, true, page, EXPORT_PAGE_SIZE).await?;
+            let last_page = batch.len() < EXPORT_PAGE_SIZE as usize;
+            posts.extend(with_authors(&self.blog_repo, tenant.id, batch).await?);
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(posts)
+    }
+
+    /// Live site the sitemap and canonical links point at, like the feed's
+    fn site_url(&self, tenant: &Tenant) -> Option<String> {
+        let base_url = self.urls.site_origin(tenant);
+        (!base_url.is_empty()).then_some(base_url)
+    }
+
+    fn record(&self, tenant: &Tenant, bytes: u64) {
+        let mut runs = self.runs.write();
+        let progress = runs.entry(tenant.id).or_insert_with(|| StaticExportProgress::started(tenant));
+        progress.files += 1;
+        progress.bytes += bytes;
+    }
+}
+
+/// Puts files under the export's prefix, noting each in the manifest and the progress
+struct ExportWriter<'a, B, C, A, U>
+where
+    B: BlogPostRepository,
+    C: ChangelogRepository,
+    A: AboutRepository,
+    U: UsesRepository,
+{
+    exporter: &'a StaticSiteExporter<B, C, A, U>,
+    tenant: &'a Tenant,
+    prefix: &'a str,
+    files: Vec<ManifestEntry>,
+}
+
+impl<B, C, A, U> ExportWriter<'_, B, C, A, U>
+where
+    B: BlogPostRepository + Clone,
+    C: ChangelogRepository,
+    A: AboutRepository,
+    U: UsesRepository,
+{
+    async fn write(&mut self, path: &str, body: impl Into<Bytes>, content_type: &'static str) -> Result<(), AppError> {
+        let body = body.into();
+        let size = body.len() as u64;
+
+        self.exporter.storage()?.put(&format!("{}/{}", self.prefix, path), body, content_type).await?;
+        self.exporter.record(self.tenant, size);
+        self.files.push(ManifestEntry { path: path.to_string(), size, content_type });
+
+        Ok(())
+    }
+}
+
+// ───── Rendering ─────────────────────────────────────────────────────
+
+struct SiteLayout<'a> {
+    site_name: &'a str,
+    /// Relative path from the page back to the export root
+    root: &'static str,
+    canonical_url: Option<String>,
+    has_about: bool,
+    exported_at: String,
+}
+
+struct PostLink {
+    title: String,
+    path: String,
+    date: String,
+    excerpt: String,
+}
+
+#[derive(Template)]
+#[template(path = "site/index.html")]
+struct IndexPage<'a> {
+    layout: SiteLayout<'a>,
+    posts: &'a [PostLink],
+}
+
+#[derive(Template)]
+#[template(path = "site/post.html")]
+struct PostPage<'a> {
+    layout: SiteLayout<'a>,
+    title: &'a str,
+    date: String,
+    authors: String,
+    tags: &'a [String],
+    content_html: String,
+}
+
+#[derive(Template)]
+#[template(path = "site/page.html")]
+struct ContentPage<'a> {
+    layout: SiteLayout<'a>,
+    title: &'a str,
+    content_html: &'a str,
+}
+
+fn render(template: &impl Template) -> Result<String, AppError> {
+    template.render().map_err(|e| AppError::InternalError(format!("Failed to render static page: {}", e)))
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, AppError> {
+    serde_json::to_vec_pretty(&Viewed::public(value))
+        .map_err(|e| AppError::InternalError(format!("Failed to encode export file: {}", e)))
+}
+
+fn post_date(authored: &AuthoredPost) -> String {
+    authored.post.published_at.unwrap_or(authored.post.updated_at).format("%Y-%m-%d").to_string()
+}
+
+/// The post's Markdown with its metadata as YAML front matter
+fn post_markdown(authored: &AuthoredPost) -> Result<String, AppError> {
+    let post = &authored.post;
+    let date = post.published_at.unwrap_or(post.updated_at).to_rfc3339();
+
+    PostFrontMatter {
+        title: &post.title,
+        slug: &post.slug,
+        excerpt: &post.excerpt,
+        tags: post.tags.as_deref(),
+    }
+    .render(&[("date", Some(FrontMatterValue::Plain(date)))], &post.content_markdown)
+}
+
+fn render_sitemap(base_url: &str, posts: &[AuthoredPost], about: Option<&AboutMeResponse>) -> String {
+    let mut urls = vec![(format!("{}/", base_url), None)];
+    urls.extend(posts.iter().map(|authored| {
+        (format!("{}/blog/{}", base_url, authored.post.slug), Some(authored.post.updated_at))
+    }));
+    if let Some(about) = about {
+        urls.push((format!("{}/about", base_url), Some(about.updated_at)));
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for (loc, lastmod) in urls {
+        out.push_str(&format!("<url><loc>{}</loc>", escape_xml(&loc)));
+        if let Some(lastmod) = lastmod {
+            out.push_str(&format!("<lastmod>{}</lastmod>", lastmod.format("%Y-%m-%d")));
+        }
+        out.push_str("</url>\n");
+    }
+    out.push_str("</urlset>\n");
+    out
+}
\ No newline at end of file
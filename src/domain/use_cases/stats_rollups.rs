@@ -0,0 +1,103 @@
+use chrono::{Days, Months, Utc};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        geo::GeoCountrySummary,
+        reading::PostReadingStats,
+        stats_rollup::{
+            start_of_month, MonthlyStats, MonthlyStatsQuery, MonthlyStatsResponse, RollupWindow, StatsRollupRun,
+            StatsRollupRunQuery, MONTHLY_TOP_ENTRIES,
+        },
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::stats_rollup::StatsRollupRepository,
+    settings::AppConfig,
+};
+
+/// Keeps the daily and monthly analytics rollups current, so the stats
+/// reports read summary rows instead of scanning the raw tables.
+///
+/// Every run re-rolls the last `recent_days` days, today included, so late
+/// scroll updates land in the day reading started. Older days only change
+/// through a backfill. Days with no raw rows left keep their rollups, so a
+/// backfill reaching past the retention period loses nothing.
+#[derive(Clone)]
+pub struct StatsRollups<R>
+where
+    R: StatsRollupRepository,
+{
+    pub rollup_repo: R,
+    recent_days: u32,
+}
+
+impl<R> StatsRollups<R>
+where
+    R: StatsRollupRepository,
+{
+    pub fn new(rollup_repo: R, config: &AppConfig) -> Self {
+        StatsRollups { rollup_repo, recent_days: config.stats_rollup_days.max(1) }
+    }
+
+    /// Rolls up the days the background job covers
+    pub async fn roll_up_recent(&self, tenant_id: Uuid) -> Result<StatsRollupRun, AppError> {
+        self.roll_up_days(tenant_id, self.recent_days).await
+    }
+
+    /// Rolls up the last `days` days, today included
+    pub async fn roll_up_days(&self, tenant_id: Uuid, days: u32) -> Result<StatsRollupRun, AppError> {
+        let today = Utc::now().date_naive();
+        let from = today - Days::new(u64::from(days.max(1)) - 1);
+        let to = today + Days::new(1);
+
+        let rows = self.rollup_repo.roll_up(&tenant_id, from, to).await?;
+        METRICS.incr("stats_rollup_runs_total");
+        METRICS.add("stats_rollup_rows_total", rows);
+
+        Ok(StatsRollupRun { from, to, rows })
+    }
+
+    pub async fn run(&self, tenant_id: Uuid, query: StatsRollupRunQuery) -> Result<StatsRollupRun, AppError> {
+        query.validate()?;
+        self.roll_up_days(tenant_id, query.days.unwrap_or(self.recent_days)).await
+    }
+
+    /// Totals, most read posts, referring pages and countries per month
+    pub async fn monthly(&self, tenant_id: Uuid, query: MonthlyStatsQuery) -> Result<MonthlyStatsResponse, AppError> {
+        query.validate()?;
+
+        let this_month = start_of_month(Utc::now().date_naive());
+        let mut months = Vec::new();
+
+        for back in 0..query.months.unwrap_or(12) {
+            let month = this_month - Months::new(back);
+            let window = RollupWindow::month(month);
+
+            let mut posts = PostReadingStats::from_counts(self.rollup_repo.depth_counts(&tenant_id, &window).await?);
+            let readers = posts.iter().map(|p| p.readers).sum();
+            let completions = posts.iter().map(|p| p.completions).sum();
+            posts.sort_by(|a, b| b.readers.cmp(&a.readers).then_with(|| a.title.cmp(&b.title)));
+            posts.truncate(MONTHLY_TOP_ENTRIES);
+
+            let countries = GeoCountrySummary::from_counts(self.rollup_repo.country_counts(&tenant_id, &window).await?);
+            let referring_pages = self.rollup_repo
+                .referring_pages(&tenant_id, &window, MONTHLY_TOP_ENTRIES as i64)
+                .await?;
+
+            months.push(MonthlyStats {
+                month,
+                readers,
+                completions,
+                clicks: countries.iter().map(|c| c.clicks).sum(),
+                attacks: countries.iter().map(|c| c.attacks).sum(),
+                top_posts: posts,
+                referring_pages,
+                countries,
+            });
+        }
+
+        Ok(MonthlyStatsResponse { months })
+    }
+}
\ No newline at end of file
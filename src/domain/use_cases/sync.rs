@@ -0,0 +1,100 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        about_me::AboutMeResponse,
+        blog_post::BlogPost,
+        sync::{SyncChanges, SyncPage, SyncResponse, SYNC_PAGE_ABOUT},
+    },
+    errors::AppError,
+    repositories::{about::AboutRepository, blog_post::BlogPostRepository},
+    use_cases::blog::with_authors,
+};
+
+/// How far `next_since` trails the server clock. Rows are stamped when their
+/// transaction starts, so one still committing when a sync runs carries an
+/// earlier timestamp than the sync; the overlap makes the next sync see it.
+const SYNC_OVERLAP_SECS: i64 = 5;
+
+/// Incremental changes to published content for static-site and offline
+/// frontends, which would otherwise refetch everything to rebuild
+#[derive(Clone)]
+pub struct ContentSync<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    blog_repo: B,
+    about_repo: A,
+}
+
+impl<B, A> ContentSync<B, A>
+where
+    B: BlogPostRepository,
+    A: AboutRepository,
+{
+    pub fn new(blog_repo: B, about_repo: A) -> Self {
+        ContentSync { blog_repo, about_repo }
+    }
+
+    /// Everything public that changed after `since`; without it, all public content
+    pub async fn changes_since(&self, tenant_id: Uuid, since: Option<DateTime<Utc>>) -> Result<SyncResponse, AppError> {
+        let started = Utc::now();
+
+        let mut posts = SyncChanges::default();
+        let mut live = Vec::new();
+        for post in self.blog_repo.get_posts_changed_since(&tenant_id, since).await? {
+            if post.deleted_at.is_some() || !post.published {
+                posts.deleted.push(post.id);
+            } else {
+                live.push(post);
+            }
+        }
+
+        let (created, updated): (Vec<_>, Vec<_>) = with_authors(&self.blog_repo, tenant_id, live)
+            .await?
+            .into_iter()
+            .partition(|authored| is_new_post(&authored.post, since));
+        posts.created = created;
+        posts.updated = updated;
+
+        let pages = self.page_changes(tenant_id, since).await?;
+
+        let next_since = (started - Duration::seconds(SYNC_OVERLAP_SECS)).max(since.unwrap_or(DateTime::UNIX_EPOCH));
+
+        Ok(SyncResponse { since, next_since, posts, pages })
+    }
+
+    async fn page_changes(&self, tenant_id: Uuid, since: Option<DateTime<Utc>>) -> Result<SyncChanges<SyncPage, &'static str>, AppError> {
+        let mut pages = SyncChanges::default();
+
+        match self.about_repo.get_current_about_me(&tenant_id).await {
+            Ok(about) => match since {
+                None => pages.created.push(SyncPage::about(about)),
+                Some(since) if about_changed_at(&about) > since => pages.updated.push(SyncPage::about(about)),
+                Some(_) => {}
+            },
+            // Whether it ever existed is not recorded, so any delta sync may drop it
+            Err(AppError::NotFound(_)) if since.is_some() => pages.deleted.push(SYNC_PAGE_ABOUT),
+            Err(AppError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(pages)
+    }
+}
+
+/// Posts that became public after `since`, including drafts published since
+fn is_new_post(post: &BlogPost, since: Option<DateTime<Utc>>) -> bool {
+    match since {
+        None => true,
+        Some(since) => post.created_at > since || post.published_at.is_some_and(|at| at > since),
+    }
+}
+
+/// A revision scheduled ahead becomes current on its effective date without
+/// any write, so that date counts as a change too
+fn about_changed_at(about: &AboutMeResponse) -> DateTime<Utc> {
+    about.updated_at.max(about.effective_date.and_time(NaiveTime::MIN).and_utc())
+}
\ No newline at end of file
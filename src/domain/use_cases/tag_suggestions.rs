@@ -0,0 +1,101 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::blog_post::{SuggestTagsRequest, TagSuggestionsResponse},
+    errors::AppError,
+    metrics::METRICS,
+    repositories::blog_post::BlogPostRepository,
+    settings::AppConfig,
+    utils::tag_index::{TagIndex, Tokenizer},
+};
+
+/// Posts read per page while building an index
+const POSTS_PAGE_SIZE: u32 = 100;
+const DEFAULT_SUGGESTIONS: u32 = 8;
+
+struct TenantIndex {
+    index: TagIndex,
+    built_at: DateTime<Utc>,
+}
+
+/// Suggests tags for a draft from a per-tenant TF-IDF index of its posts,
+/// drafts included. Indexes are rebuilt by a background task; a tenant's
+/// first request builds its index on the spot.
+#[derive(Clone)]
+pub struct TagSuggester<B>
+where
+    B: BlogPostRepository,
+{
+    blog_repo: B,
+    tokenizer: Tokenizer,
+    indexes: Arc<RwLock<HashMap<Uuid, Arc<TenantIndex>>>>,
+}
+
+impl<B> TagSuggester<B>
+where
+    B: BlogPostRepository,
+{
+    pub fn new(blog_repo: B, config: &AppConfig) -> Self {
+        TagSuggester {
+            blog_repo,
+            tokenizer: Tokenizer::new(&config.tag_stopwords()),
+            indexes: Arc::default(),
+        }
+    }
+
+    /// Rebuilds the tenant's index from its current posts, returning how many were read
+    pub async fn refresh(&self, tenant_id: Uuid) -> Result<usize, AppError> {
+        Ok(self.rebuild(tenant_id).await?.index.documents())
+    }
+
+    pub async fn suggest(&self, tenant_id: Uuid, request: SuggestTagsRequest) -> Result<TagSuggestionsResponse, AppError> {
+        request.validate()?;
+
+        let cached = self.indexes.read().get(&tenant_id).cloned();
+        let tenant_index = match cached {
+            Some(tenant_index) => tenant_index,
+            None => self.rebuild(tenant_id).await?,
+        };
+
+        let draft = match request.title {
+            Some(title) => format!("{}\n\n{}", title, request.content_markdown),
+            None => request.content_markdown,
+        };
+        let limit = request.limit.unwrap_or(DEFAULT_SUGGESTIONS) as usize;
+
+        Ok(TagSuggestionsResponse {
+            suggestions: tenant_index.index.suggest(&draft, limit),
+            indexed_posts: tenant_index.index.documents(),
+            indexed_at: tenant_index.built_at,
+        })
+    }
+
+    async fn rebuild(&self, tenant_id: Uuid) -> Result<Arc<TenantIndex>, AppError> {
+        let mut posts = Vec::new();
+        for page in 1.. {
+            let batch = self.blog_repo.get_all_blog_posts(&tenant_id, false, page, POSTS_PAGE_SIZE).await?;
+            let last_page = batch.len() < POSTS_PAGE_SIZE as usize;
+
+            posts.extend(batch.into_iter().map(|post| {
+                (format!("{}\n\n{}", post.title, post.content_markdown), post.tags.unwrap_or_default())
+            }));
+            if last_page {
+                break;
+            }
+        }
+
+        let tenant_index = Arc::new(TenantIndex {
+            index: TagIndex::build(self.tokenizer.clone(), &posts),
+            built_at: Utc::now(),
+        });
+        self.indexes.write().insert(tenant_id, Arc::clone(&tenant_index));
+        METRICS.incr("tag_index_rebuilds_total");
+
+        Ok(tenant_index)
+    }
+}
\ No newline at end of file
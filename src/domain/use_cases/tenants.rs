@@ -0,0 +1,82 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::RwLock;
+use uuid::Uuid;
+
+use crate::{
+    entities::tenant::{normalize_host, Tenant, DEFAULT_TENANT_ID},
+    errors::AppError,
+    repositories::tenant::TenantRepository,
+};
+
+#[derive(Default)]
+struct TenantCache {
+    by_host: HashMap<String, Tenant>,
+    by_id: HashMap<Uuid, Tenant>,
+}
+
+/// Maps request hosts to tenants.
+///
+/// Tenants change rarely, so lookups are served from an in-process cache that
+/// is loaded at startup and refreshed periodically. Hosts no tenant claims fall
+/// back to the default tenant unless `strict_hosts` is set.
+#[derive(Clone)]
+pub struct TenantResolver<R>
+where
+    R: TenantRepository,
+{
+    pub tenant_repo: R,
+    cache: Arc<RwLock<TenantCache>>,
+    strict_hosts: bool,
+}
+
+impl<R> TenantResolver<R>
+where
+    R: TenantRepository,
+{
+    pub fn new(tenant_repo: R, strict_hosts: bool) -> Self {
+        TenantResolver {
+            tenant_repo,
+            cache: Arc::new(RwLock::new(TenantCache::default())),
+            strict_hosts,
+        }
+    }
+
+    /// Tenant serving `host` (a raw Host header value), if any
+    pub fn resolve(&self, host: &str) -> Option<Tenant> {
+        let cache = self.cache.read();
+
+        cache.by_host
+            .get(&normalize_host(host))
+            .or_else(|| (!self.strict_hosts).then(|| cache.by_id.get(&DEFAULT_TENANT_ID)).flatten())
+            .cloned()
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<Tenant> {
+        self.cache.read().by_id.get(id).cloned()
+    }
+
+    /// Every known tenant, ordered by slug
+    pub fn all(&self) -> Vec<Tenant> {
+        let mut tenants: Vec<Tenant> = self.cache.read().by_id.values().cloned().collect();
+        tenants.sort_by(|a, b| a.slug.cmp(&b.slug));
+        tenants
+    }
+
+    /// Reloads tenants and their hosts, returning how many tenants were loaded
+    pub async fn refresh(&self) -> Result<usize, AppError> {
+        let tenants = self.tenant_repo.list_tenants().await?;
+
+        let mut fresh = TenantCache::default();
+        for tenant in tenants {
+            for host in &tenant.hostnames {
+                fresh.by_host.insert(normalize_host(host), tenant.clone());
+            }
+            fresh.by_id.insert(tenant.id, tenant);
+        }
+        let count = fresh.by_id.len();
+
+        *self.cache.write() = fresh;
+        Ok(count)
+    }
+}
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::Utc;
+use parking_lot::RwLock;
+use rand::Rng;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        blog_post::{AuthoredPost, UpdateBlogPostRequest},
+        option_fields::OptionField,
+        title_test::{
+            pick_variant, stats_day, total_weight, PromoteTitleRequest, SetTitleTestRequest, TitleClickEvent,
+            TitleImpression, TitleTestReport, TitleVariant,
+        },
+    },
+    errors::AppError,
+    metrics::METRICS,
+    repositories::{blog_post::BlogPostRepository, title_test::TitleTestRepository},
+    use_cases::blog::with_author,
+    utils::valid_uuid::valid_uuid,
+};
+
+/// Running tests per tenant, then per post
+type TestCache = HashMap<Uuid, HashMap<Uuid, Vec<TitleVariant>>>;
+
+/// A/B tests of post titles on the public list endpoints.
+///
+/// Each list response picks one variant per tested post, weighted, and
+/// reports it in `title_variant` so the site can send clicks back. Running
+/// tests are kept in an in-process cache, updated on every change and
+/// refreshed periodically so other instances pick changes up. Tenants with a
+/// running test skip the pre-rendered list pages, which would pin a variant.
+#[derive(Clone)]
+pub struct TitleTests<T, B>
+where
+    T: TitleTestRepository,
+    B: BlogPostRepository,
+{
+    pub title_repo: T,
+    blog_repo: B,
+    cache: Arc<RwLock<TestCache>>,
+}
+
+impl<T, B> TitleTests<T, B>
+where
+    T: TitleTestRepository,
+    B: BlogPostRepository,
+{
+    pub fn new(title_repo: T, blog_repo: B) -> Self {
+        TitleTests { title_repo, blog_repo, cache: Arc::default() }
+    }
+
+    /// Reloads every running test, returning how many posts are being tested
+    pub async fn refresh(&self) -> Result<usize, AppError> {
+        let mut fresh: TestCache = HashMap::new();
+        for variant in self.title_repo.list_all_title_variants().await? {
+            fresh
+                .entry(variant.tenant_id)
+                .or_default()
+                .entry(variant.post_id)
+                .or_default()
+                .push(variant);
+        }
+        let count = fresh.values().map(HashMap::len).sum();
+
+        *self.cache.write() = fresh;
+        Ok(count)
+    }
+
+    pub fn is_testing(&self, tenant_id: &Uuid) -> bool {
+        self.cache.read().get(tenant_id).is_some_and(|tests| !tests.is_empty())
+    }
+
+    /// Puts a weighted pick of each tested post's titles in place and
+    /// returns what was shown
+    pub fn serve(&self, tenant_id: &Uuid, posts: &mut [AuthoredPost]) -> Vec<TitleImpression> {
+        let cache = self.cache.read();
+        let Some(tests) = cache.get(tenant_id) else {
+            return Vec::new();
+        };
+
+        let mut rng = rand::thread_rng();
+        let mut served = Vec::new();
+        for authored in posts.iter_mut() {
+            let Some(variants) = tests.get(&authored.post.id) else {
+                continue;
+            };
+            let total = total_weight(variants);
+            if total == 0 {
+                continue;
+            }
+            let Some(variant) = pick_variant(variants, rng.gen_range(0..total)) else {
+                continue;
+            };
+
+            if let Some(title) = &variant.title {
+                authored.post.title = title.clone();
+            }
+            authored.title_variant = Some(variant.variant);
+            served.push(TitleImpression { post_id: authored.post.id, variant: variant.variant });
+        }
+
+        served
+    }
+
+    pub async fn record_impressions(&self, tenant_id: Uuid, served: &[TitleImpression]) -> Result<(), AppError> {
+        self.title_repo.record_title_impressions(&tenant_id, served, stats_day(Utc::now())).await?;
+        METRICS.add("title_test_impressions_total", served.len() as u64);
+        Ok(())
+    }
+
+    /// Clicks on variants that are not being tested are dropped
+    pub async fn record_click(&self, tenant_id: Uuid, click: TitleClickEvent) -> Result<(), AppError> {
+        click.validate()?;
+
+        let impression = TitleImpression { post_id: click.post_id, variant: click.variant };
+        if self.title_repo.record_title_click(&tenant_id, &impression, stats_day(Utc::now())).await? {
+            METRICS.incr("title_test_clicks_total");
+        }
+        Ok(())
+    }
+
+    pub async fn report(&self, tenant_id: Uuid, post_id: &str) -> Result<TitleTestReport, AppError> {
+        let post_id = valid_uuid(post_id)?;
+        let post = self.blog_repo.get_blog_post_by_id(&tenant_id, &post_id).await?;
+
+        let variants = self.title_repo.get_title_variants(&tenant_id, &post_id).await?;
+        let stats = self.title_repo.title_variant_stats(&tenant_id, &post_id).await?;
+
+        TitleTestReport::build(&post.title, &variants, &stats).ok_or_else(no_test)
+    }
+
+    /// Starts a test for the post, replacing a running one
+    pub async fn start(&self, tenant_id: Uuid, post_id: &str, request: SetTitleTestRequest) -> Result<TitleTestReport, AppError> {
+        request.validate()?;
+
+        let post_id = valid_uuid(post_id)?;
+        let post = self.blog_repo.get_blog_post_by_id(&tenant_id, &post_id).await?;
+
+        let mut titles: Vec<&str> = vec![&post.title];
+        for variant in &request.variants {
+            if titles.iter().any(|t| t.eq_ignore_ascii_case(&variant.title)) {
+                return Err(AppError::InvalidInput(
+                    "Alternative titles must differ from the post title and from each other".to_string(),
+                ));
+            }
+            titles.push(&variant.title);
+        }
+
+        let variants = self.title_repo.replace_title_test(&tenant_id, &post_id, &request.to_inserts()).await?;
+        self.cache
+            .write()
+            .entry(tenant_id)
+            .or_default()
+            .insert(post_id, variants.clone());
+
+        TitleTestReport::build(&post.title, &variants, &[]).ok_or_else(no_test)
+    }
+
+    /// Stops the test; its numbers are dropped with it
+    pub async fn end(&self, tenant_id: Uuid, post_id: &str) -> Result<(), AppError> {
+        let post_id = valid_uuid(post_id)?;
+        let ended = self.title_repo.delete_title_test(&tenant_id, &post_id).await?;
+        if let Some(tests) = self.cache.write().get_mut(&tenant_id) {
+            tests.remove(&post_id);
+        }
+
+        if ended {
+            Ok(())
+        } else {
+            Err(no_test())
+        }
+    }
+
+    /// Makes the variant the post's title, keeping its slug, and ends the test.
+    /// Variant 0 ends it with the title unchanged.
+    pub async fn promote(&self, tenant_id: Uuid, post_id: &str, request: PromoteTitleRequest) -> Result<AuthoredPost, AppError> {
+        request.validate()?;
+
+        let id = valid_uuid(post_id)?;
+        let post = self.blog_repo.get_blog_post_by_id(&tenant_id, &id).await?;
+        let variants = self.title_repo.get_title_variants(&tenant_id, &id).await?;
+        if variants.is_empty() {
+            return Err(no_test());
+        }
+        let winner = variants
+            .iter()
+            .find(|v| v.variant == request.variant)
+            .ok_or_else(|| AppError::NotFound(format!("Variant {} not found", request.variant)))?;
+
+        let post = match &winner.title {
+            Some(title) => {
+                let change = UpdateBlogPostRequest {
+                    title: OptionField::SetToValue(title.clone()),
+                    slug: OptionField::SetToValue(post.slug),
+                    ..Default::default()
+                };
+                self.blog_repo.update_blog_post(&tenant_id, &id, &change).await?
+            }
+            None => post,
+        };
+
+        self.end(tenant_id, post_id).await?;
+        with_author(&self.blog_repo, tenant_id, post).await
+    }
+}
+
+fn no_test() -> AppError {
+    AppError::NotFound("No title test is running for this post".to_string())
+}
\ No newline at end of file
@@ -0,0 +1,194 @@
+use std::{cmp::Reverse, collections::BTreeMap, sync::Arc};
+
+use chrono::Utc;
+
+use crate::{
+    cache::usage::RedisUsageProbe,
+    entities::{
+        stats_rollup::start_of_month,
+        tenant::Tenant,
+        usage::{DatabaseUsage, EmailUsage, PrefixUsage, StorageUsage, UsageReport},
+    },
+    errors::AppError,
+    mailer::email::EmailSendCounter,
+    metrics::METRICS,
+    repositories::usage::UsageRepository,
+    storage::object::{ObjectStorage, StoredObject},
+};
+
+/// Keys scanned per site before the Redis figure is reported as a lower bound
+const MAX_REDIS_KEYS: usize = 100_000;
+
+/// Measures what each site uses of storage, the database, Redis and the
+/// mail relay, to keep an eye on hosting plan limits.
+///
+/// Measuring lists the whole bucket and counts every table, so it is done
+/// by the usage collector job and the result stored; the admin endpoint
+/// reads the stored report. Objects count towards a site when the segment
+/// after their top-level prefix is its slug or id (`exports/<slug>/...`,
+/// `audit-archive/<id>/...`).
+#[derive(Clone)]
+pub struct UsageReports<R>
+where
+    R: UsageRepository,
+{
+    pub usage_repo: R,
+    storage: Option<Arc<dyn ObjectStorage>>,
+    redis: Option<Arc<dyn RedisUsageProbe>>,
+    email_sends: EmailSendCounter,
+}
+
+impl<R> UsageReports<R>
+where
+    R: UsageRepository,
+{
+    pub fn new(
+        usage_repo: R,
+        storage: Option<Arc<dyn ObjectStorage>>,
+        redis: Option<Arc<dyn RedisUsageProbe>>,
+        email_sends: EmailSendCounter,
+    ) -> Self {
+        UsageReports { usage_repo, storage, redis, email_sends }
+    }
+
+    /// The stored report, or a fresh one when the collector has not run yet
+    pub async fn report(&self, tenant: &Tenant) -> Result<UsageReport, AppError> {
+        match self.usage_repo.latest_report(&tenant.id).await? {
+            Some(report) => Ok(report),
+            None => self.refresh(tenant).await,
+        }
+    }
+
+    pub async fn refresh(&self, tenant: &Tenant) -> Result<UsageReport, AppError> {
+        let mut reports = self.collect(std::slice::from_ref(tenant)).await?;
+        reports.pop().ok_or_else(|| AppError::InternalError("Usage report not collected".to_string()))
+    }
+
+    /// Measures every site in `tenants` and stores their reports. A part
+    /// that cannot be measured is left out of the reports, not failed on.
+    pub async fn collect(&self, tenants: &[Tenant]) -> Result<Vec<UsageReport>, AppError> {
+        let mut shared_unavailable = Vec::new();
+
+        let email = match self.email_usage().await {
+            Ok(email) => Some(email),
+            Err(e) => {
+                shared_unavailable.push(format!("email: {}", e));
+                None
+            }
+        };
+        let objects = match self.list_objects().await {
+            Ok(objects) => objects,
+            Err(e) => {
+                shared_unavailable.push(format!("storage: {}", e));
+                None
+            }
+        };
+        let database_bytes = self.usage_repo.database_bytes().await?;
+
+        let mut reports = Vec::with_capacity(tenants.len());
+        for tenant in tenants {
+            let mut unavailable = shared_unavailable.clone();
+
+            let database = match self.usage_repo.table_usage(&tenant.id).await {
+                Ok(mut tables) => {
+                    tables.retain(|t| t.rows > 0);
+                    tables.sort_by(|a, b| b.rows.cmp(&a.rows).then_with(|| a.table.cmp(&b.table)));
+                    let rows = tables.iter().map(|t| t.rows).sum();
+                    Some(DatabaseUsage { tables, rows, database_bytes })
+                }
+                Err(e) => {
+                    unavailable.push(format!("database: {}", e));
+                    None
+                }
+            };
+
+            let redis = match &self.redis {
+                Some(redis) => match redis.namespace_usage(&tenant.cache_key("*"), MAX_REDIS_KEYS).await {
+                    Ok(usage) => Some(usage),
+                    Err(e) => {
+                        unavailable.push(format!("redis: {}", e));
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            let report = UsageReport {
+                tenant_id: tenant.id,
+                collected_at: Utc::now(),
+                storage: objects.as_ref().map(|(backend, objects)| storage_usage(tenant, backend, objects)),
+                database,
+                redis,
+                email,
+                unavailable,
+            };
+            self.usage_repo.save_report(&report).await?;
+            reports.push(report);
+        }
+
+        METRICS.incr("usage_collections_total");
+        Ok(reports)
+    }
+
+    /// Adds the emails counted here since the last call to the month's
+    /// totals. Counts that cannot be stored are kept for the next call.
+    pub async fn flush_email_sends(&self) -> Result<(), AppError> {
+        let (sent, failed) = self.email_sends.take();
+        if sent == 0 && failed == 0 {
+            return Ok(());
+        }
+
+        let month = start_of_month(Utc::now().date_naive());
+        self.usage_repo
+            .add_email_sends(month, sent as i64, failed as i64)
+            .await
+            .inspect_err(|_| self.email_sends.restore(sent, failed))
+    }
+
+    async fn email_usage(&self) -> Result<EmailUsage, AppError> {
+        self.flush_email_sends().await?;
+        self.usage_repo.email_usage(start_of_month(Utc::now().date_naive())).await
+    }
+
+    async fn list_objects(&self) -> Result<Option<(String, Vec<StoredObject>)>, AppError> {
+        let Some(storage) = &self.storage else {
+            return Ok(None);
+        };
+
+        Ok(Some((storage.describe(), storage.list("").await?)))
+    }
+}
+
+/// The site's share of `objects`, grouped by top-level prefix, largest first
+fn storage_usage(tenant: &Tenant, backend: &str, objects: &[StoredObject]) -> StorageUsage {
+    let id = tenant.id.to_string();
+    let mut by_prefix: BTreeMap<&str, PrefixUsage> = BTreeMap::new();
+
+    for object in objects {
+        let mut segments = object.key.split('/');
+        let (Some(prefix), Some(owner)) = (segments.next(), segments.next()) else {
+            continue;
+        };
+        if owner != tenant.slug && owner != id {
+            continue;
+        }
+
+        let usage = by_prefix
+            .entry(prefix)
+            .or_insert_with(|| PrefixUsage { prefix: prefix.to_string(), objects: 0, bytes: 0 });
+        usage.objects += 1;
+        usage.bytes += object.size;
+    }
+
+    let mut by_prefix: Vec<PrefixUsage> = by_prefix.into_values().collect();
+    by_prefix.sort_by_key(|p| Reverse(p.bytes));
+
+    StorageUsage {
+        backend: backend.to_string(),
+        objects: by_prefix.iter().map(|p| p.objects).sum(),
+        bytes: by_prefix.iter().map(|p| p.bytes).sum(),
+        by_prefix,
+        total_objects: objects.len() as u64,
+        total_bytes: objects.iter().map(|o| o.size).sum(),
+    }
+}
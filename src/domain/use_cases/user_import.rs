@@ -0,0 +1,230 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use uuid::Uuid;
+use validator::ValidateEmail;
+
+use crate::{
+    auth::password::check_hash_format,
+    entities::{
+        user::UserInsert,
+        user_import::{
+            CsvUserImportRow, ImportedUser, UserImportFormat, UserImportIssue, UserImportReport, UserImportRow,
+            UserRole,
+        },
+    },
+    errors::AppError,
+    repositories::user::UserRepository,
+};
+
+/// Longest username the `users` table accepts
+const MAX_USERNAME_CHARS: usize = 100;
+
+/// A decoded row, or the reason it could not be decoded
+pub type ParsedUserRow = Result<UserImportRow, UserImportIssue>;
+
+/// Creates accounts from another backend's user export.
+///
+/// Password hashes are kept as exported; sign-in verifies them with their
+/// own algorithm and swaps in an Argon2id hash on the first success. Rows
+/// are independent: an address that already has an account is reported as
+/// a conflict and left alone, so an interrupted import can be re-run.
+#[derive(Clone)]
+pub struct UserImport<R>
+where
+    R: UserRepository,
+{
+    pub user_repo: R,
+}
+
+impl<R> UserImport<R>
+where
+    R: UserRepository,
+{
+    pub fn new(user_repo: R) -> Self {
+        UserImport { user_repo }
+    }
+
+    /// Creates an account per valid row; with `dry_run` only reports what
+    /// would be created
+    pub async fn import(
+        &self,
+        tenant_id: Uuid,
+        rows: Vec<ParsedUserRow>,
+        dry_run: bool,
+    ) -> Result<UserImportReport, AppError> {
+        let mut report = UserImportReport {
+            dry_run,
+            total_rows: rows.len(),
+            imported: Vec::new(),
+            conflicts: Vec::new(),
+            skipped: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+
+        for (index, parsed) in rows.into_iter().enumerate() {
+            let row_number = index + 1;
+            let row = match parsed {
+                Ok(row) => row,
+                Err(issue) => {
+                    report.skipped.push(issue);
+                    continue;
+                }
+            };
+
+            let email = row.email.trim().to_string();
+            let issue = |reason: String| UserImportIssue { row: row_number, email: Some(email.clone()), reason };
+
+            let insert = match Self::prepare(&row, &email) {
+                Ok(insert) => insert,
+                Err(reason) => {
+                    report.skipped.push(issue(reason));
+                    continue;
+                }
+            };
+
+            if !seen.insert(email.to_lowercase()) {
+                report.conflicts.push(issue("Same email as an earlier row".to_string()));
+                continue;
+            }
+
+            if self.user_repo.get_user_by_email(&tenant_id, &email).await?.is_some() {
+                report.conflicts.push(issue("An account with this email already exists".to_string()));
+                continue;
+            }
+
+            let id = if dry_run {
+                None
+            } else {
+                match self.user_repo.create_user(&tenant_id, &insert).await {
+                    Ok(id) => Some(id),
+                    Err(AppError::Conflict(reason)) => {
+                        report.conflicts.push(issue(reason));
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+
+            if let Some(id) = id
+                && row.has_role(UserRole::Editor)
+            {
+                self.user_repo.set_editor(&tenant_id, &id, true).await?;
+            }
+
+            report.imported.push(ImportedUser {
+                row: row_number,
+                email,
+                id,
+                hash_algorithm: row.hash_algorithm,
+                roles: row.roles,
+            });
+        }
+
+        tracing::info!(
+            tenant_id = %tenant_id,
+            dry_run,
+            imported = report.imported.len(),
+            conflicts = report.conflicts.len(),
+            skipped = report.skipped.len(),
+            "User import finished"
+        );
+        Ok(report)
+    }
+
+    /// Validates a row, returning why it is skipped when it is not usable
+    fn prepare(row: &UserImportRow, email: &str) -> Result<UserInsert, String> {
+        if !email.validate_email() {
+            return Err("Invalid email format".to_string());
+        }
+
+        let username = row.username.as_deref().map(str::trim).filter(|name| !name.is_empty());
+        if username.is_some_and(|name| name.chars().count() > MAX_USERNAME_CHARS) {
+            return Err(format!("Username is longer than {} characters", MAX_USERNAME_CHARS));
+        }
+
+        check_hash_format(row.hash_algorithm, &row.password_hash)
+            .map_err(|e| e.to_string())?;
+
+        let now = Utc::now();
+        Ok(UserInsert {
+            email: email.to_string(),
+            username: username.map(str::to_owned),
+            password_hash: row.password_hash.clone(),
+            is_admin: row.has_role(UserRole::Admin),
+            is_verified: row.verified.unwrap_or(true),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            deleted_by: None,
+        })
+    }
+}
+
+/// Decodes an export. Rows that cannot be decoded come back as issues;
+/// only a body that is not CSV or a JSON array at all is an error.
+pub fn parse_user_import(format: UserImportFormat, body: &[u8]) -> Result<Vec<ParsedUserRow>, AppError> {
+    match format {
+        UserImportFormat::Csv => parse_csv(body),
+        UserImportFormat::Json => parse_json(body),
+    }
+}
+
+fn parse_csv(body: &[u8]) -> Result<Vec<ParsedUserRow>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(body);
+
+    let headers = reader.headers()
+        .map_err(|e| AppError::InvalidInput(format!("Invalid CSV header: {}", e)))?
+        .clone();
+    for column in ["email", "password_hash", "hash_algorithm"] {
+        if !headers.iter().any(|header| header == column) {
+            return Err(AppError::InvalidInput(format!("CSV header is missing the '{}' column", column)));
+        }
+    }
+    let email_column = headers.iter().position(|header| header == "email");
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                rows.push(Err(UserImportIssue { row, email: None, reason: e.to_string() }));
+                continue;
+            }
+        };
+
+        let email = email_column
+            .and_then(|column| record.get(column))
+            .filter(|email| !email.is_empty())
+            .map(str::to_owned);
+        let parsed = record.deserialize::<CsvUserImportRow>(Some(&headers))
+            .map_err(|e| e.to_string())
+            .and_then(UserImportRow::try_from)
+            .map_err(|reason| UserImportIssue { row, email, reason });
+        rows.push(parsed);
+    }
+
+    Ok(rows)
+}
+
+fn parse_json(body: &[u8]) -> Result<Vec<ParsedUserRow>, AppError> {
+    let values: Vec<serde_json::Value> = serde_json::from_slice(body)
+        .map_err(|e| AppError::InvalidInput(format!("Expected a JSON array of users: {}", e)))?;
+
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let email = value.get("email").and_then(|email| email.as_str()).map(str::to_owned);
+            serde_json::from_value::<UserImportRow>(value).map_err(|e| UserImportIssue {
+                row: index + 1,
+                email,
+                reason: e.to_string(),
+            })
+        })
+        .collect())
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::{collections::HashSet, fmt::Write};
+
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{
+        option_fields::OptionField,
+        uses::{
+            NewUsesEntryRequest, ReorderUsesRequest, UpdateUsesEntryRequest, UsesCategory, UsesEntry,
+            UsesEntryInsert, UsesEntryResponse, UsesResponse,
+        },
+    },
+    errors::AppError,
+    repositories::uses::UsesRepository,
+    utils::valid_uuid::valid_uuid,
+};
+
+#[derive(Clone)]
+pub struct UsesHandler<R>
+where
+    R: UsesRepository,
+{
+    pub uses_repo: R,
+}
+
+impl<R> UsesHandler<R>
+where
+    R: UsesRepository,
+{
+    pub fn new(uses_repo: R) -> Self {
+        UsesHandler { uses_repo }
+    }
+
+    /// Entries grouped by category; categories appear in the order of their first entry
+    pub async fn list_grouped(&self, tenant_id: Uuid) -> Result<UsesResponse, AppError> {
+        let entries = self.uses_repo.list_entries(&tenant_id).await?;
+
+        Ok(UsesResponse { categories: group_by_category(entries) })
+    }
+
+    /// The whole page as Markdown, suitable for embedding in a README
+    pub async fn export_markdown(&self, tenant_id: Uuid) -> Result<String, AppError> {
+        let entries = self.uses_repo.list_entries(&tenant_id).await?;
+
+        Ok(render_markdown(&group_by_category(entries)))
+    }
+
+    pub async fn create_entry(&self, tenant_id: Uuid, request: NewUsesEntryRequest) -> Result<UsesEntryResponse, AppError> {
+        request.validate()?;
+
+        let entry = self.uses_repo.create_entry(&tenant_id, &UsesEntryInsert::from(request)).await?;
+
+        Ok(entry.into())
+    }
+
+    pub async fn update_entry(
+        &self,
+        tenant_id: Uuid,
+        id: &str,
+        request: UpdateUsesEntryRequest,
+    ) -> Result<UsesEntryResponse, AppError> {
+        request.validate()?;
+        let valid_id = valid_uuid(id)?;
+
+        let current = self.uses_repo.get_entry(&tenant_id, &valid_id).await?;
+        let updated = apply_update(current, request)?;
+
+        Ok(self.uses_repo.update_entry(&tenant_id, &updated).await?.into())
+    }
+
+    pub async fn delete_entry(&self, tenant_id: Uuid, id: &str) -> Result<(), AppError> {
+        let valid_id = valid_uuid(id)?;
+
+        self.uses_repo.delete_entry(&tenant_id, &valid_id).await
+    }
+
+    /// Rewrites positions from a complete, duplicate-free list of entry ids
+    pub async fn reorder_entries(&self, tenant_id: Uuid, request: ReorderUsesRequest) -> Result<UsesResponse, AppError> {
+        request.validate()?;
+
+        let existing: HashSet<Uuid> = self.uses_repo
+            .list_entries(&tenant_id)
+            .await?
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        let requested: HashSet<Uuid> = request.ids.iter().copied().collect();
+
+        if requested.len() != request.ids.len() {
+            return Err(AppError::InvalidInput("ids must not contain duplicates".to_string()));
+        }
+        if requested != existing {
+            return Err(AppError::InvalidInput("ids must list every uses entry exactly once".to_string()));
+        }
+
+        self.uses_repo.reorder_entries(&tenant_id, &request.ids).await?;
+
+        self.list_grouped(tenant_id).await
+    }
+}
+
+fn apply_update(mut entry: UsesEntry, request: UpdateUsesEntryRequest) -> Result<UsesEntry, AppError> {
+    let required = |field: &str| AppError::InvalidInput(format!("{} cannot be null", field));
+
+    match request.category {
+        OptionField::SetToValue(category) => entry.category = category.trim().to_string(),
+        OptionField::SetToNull => return Err(required("category")),
+        OptionField::Unchanged => {}
+    }
+    match request.name {
+        OptionField::SetToValue(name) => entry.name = name.trim().to_string(),
+        OptionField::SetToNull => return Err(required("name")),
+        OptionField::Unchanged => {}
+    }
+    match request.position {
+        OptionField::SetToValue(position) => entry.position = position,
+        OptionField::SetToNull => return Err(required("position")),
+        OptionField::Unchanged => {}
+    }
+    if let Some(description) = request.description_markdown.into_option() {
+        entry.description_markdown = description.unwrap_or_default();
+    }
+    if let Some(link) = request.link.into_option() {
+        entry.link = link;
+    }
+    if let Some(image_url) = request.image_url.into_option() {
+        entry.image_url = image_url;
+    }
+
+    Ok(entry)
+}
+
+fn group_by_category(entries: Vec<UsesEntry>) -> Vec<UsesCategory> {
+    let mut categories: Vec<UsesCategory> = Vec::new();
+
+    for entry in entries {
+        match categories.iter_mut().find(|c| c.name.eq_ignore_ascii_case(&entry.category)) {
+            Some(category) => category.entries.push(entry.into()),
+            None => categories.push(UsesCategory {
+                name: entry.category.clone(),
+                entries: vec![entry.into()],
+            }),
+        }
+    }
+
+    categories
+}
+
+fn render_markdown(categories: &[UsesCategory]) -> String {
+    let mut out = String::from("# Uses\n");
+
+    for category in categories {
+        let _ = write!(out, "\n## {}\n\n", category.name);
+
+        for entry in &category.entries {
+            let title = match &entry.link {
+                Some(link) => format!("[{}]({})", entry.name, link),
+                None => entry.name.clone(),
+            };
+
+            let description = entry.description_markdown.trim();
+            if description.is_empty() {
+                let _ = writeln!(out, "- **{}**", title);
+            } else {
+                // Continuation lines stay inside the list item
+                let _ = writeln!(out, "- **{}**: {}", title, description.replace('\n', "\n  "));
+            }
+        }
+    }
+
+    out
+}
\ No newline at end of file
@@ -3,7 +3,7 @@ use std::fmt;
 
 use actix_web::{
     error::ResponseError,
-    http::{header::ContentType, StatusCode},
+    http::{header::{ContentType, RETRY_AFTER}, StatusCode},
     HttpResponse
 };
 use jsonwebtoken::errors::{ErrorKind, Error as JwtError};
@@ -12,7 +12,10 @@ use serde::Serialize;
 use tracing::{info, warn, error};
 use validator::ValidationErrors;
 
-#[derive(Debug)]
+use crate::constants::RETRY_AFTER_SECS;
+use crate::metrics::METRICS;
+
+#[derive(Debug, Clone)]
 pub enum AppError {
     ValidationError(Vec<FieldError>),
     NotFound(String),
@@ -22,6 +25,8 @@ pub enum AppError {
     InternalError(String),
     InvalidInput(String),
     ServiceUnavailable(String),
+    Timeout(String),
+    TooManyRequests(String),
 }
 
 impl fmt::Display for AppError {
@@ -41,6 +46,8 @@ impl fmt::Display for AppError {
             AppError::InternalError(msg) => write!(f, "{}", msg),
             AppError::InvalidInput(msg) => write!(f, "{}", msg),
             AppError::ServiceUnavailable(msg) => write!(f, "{}", msg),
+            AppError::Timeout(msg) => write!(f, "{}", msg),
+            AppError::TooManyRequests(msg) => write!(f, "{}", msg),
         }
     }
 }
@@ -90,6 +97,20 @@ impl ResponseError for AppError {
                     "External service unavailable"
                 );
             }
+            AppError::Timeout(msg) => {
+                warn!(
+                    error_type = "Timeout",
+                    message = %msg,
+                    "Request exceeded its time budget"
+                );
+            }
+            AppError::TooManyRequests(msg) => {
+                warn!(
+                    error_type = "TooManyRequests",
+                    message = %msg,
+                    "Request shed"
+                );
+            }
             AppError::InternalError(msg) => {
                 error!(
                     error_type = "InternalError",
@@ -110,9 +131,12 @@ impl ResponseError for AppError {
                 serde_json::json!({"error": self.to_string()})
             }
         };
-        HttpResponse::build(self.status_code())
-            .insert_header(ContentType::json())
-            .json(body)
+        let mut response = HttpResponse::build(self.status_code());
+        response.insert_header(ContentType::json());
+        if matches!(self, AppError::ServiceUnavailable(_) | AppError::TooManyRequests(_)) {
+            response.insert_header((RETRY_AFTER, RETRY_AFTER_SECS.to_string()));
+        }
+        response.json(body)
     }
     
     fn status_code(&self) -> StatusCode {
@@ -125,6 +149,8 @@ impl ResponseError for AppError {
             AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
             AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
@@ -165,6 +191,11 @@ impl From<sqlx::Error> for AppError {
             sqlx::Error::Database(e) if e.code() == Some(Cow::Borrowed("23503")) => {
                 AppError::Conflict("Foreign key violation".into())
             }
+            // query_canceled: statement_timeout fired and Postgres aborted the query
+            sqlx::Error::Database(e) if e.code() == Some(Cow::Borrowed("57014")) => {
+                METRICS.incr("db_statement_timeouts_total");
+                AppError::Timeout("Database query timed out".into())
+            }
             sqlx::Error::RowNotFound => {
                 AppError::NotFound("Record not found".into())
             }
@@ -187,7 +218,10 @@ impl From<anyhow::Error> for AppError {
 
 impl From<PasswordError> for AppError {
     fn from(err: PasswordError) -> Self {
-        AppError::InternalError(err.to_string())
+        match err {
+            PasswordError::Busy => AppError::ServiceUnavailable("Server is busy, please retry shortly".into()),
+            _ => AppError::InternalError(err.to_string()),
+        }
     }
 }
 
@@ -249,6 +283,18 @@ pub enum AuthError {
     
     #[display("Redis operation failed: {_0}")]
     RedisOperation(String),
+
+    #[display("Server is busy, please retry shortly")]
+    ServiceBusy,
+
+    #[display("This site is not public yet")]
+    SiteLocked,
+
+    #[display("Account changed since sign-in, please sign in again")]
+    StaleClaims,
+
+    #[display("Missing API token")]
+    MissingApiToken,
 }
 
 impl ResponseError for AuthError {
@@ -257,7 +303,9 @@ impl ResponseError for AuthError {
             // Client errors - expected authentication failures
             AuthError::MissingCredentials
             | AuthError::MissingAuthHeader
-            | AuthError::WrongCredentials => {
+            | AuthError::WrongCredentials
+            | AuthError::MissingApiToken
+            | AuthError::SiteLocked => {
                 info!(
                     error_type = "AuthError",
                     error_kind = "ClientError",
@@ -271,6 +319,7 @@ impl ResponseError for AuthError {
             | AuthError::TokenExpired
             | AuthError::RevokedToken
             | AuthError::TokenRevoked
+            | AuthError::StaleClaims
             | AuthError::InvalidTokenType => {
                 warn!(
                     error_type = "AuthError", 
@@ -325,6 +374,16 @@ impl ResponseError for AuthError {
                 );
             }
             
+            // Capacity limits - client should back off and retry
+            AuthError::ServiceBusy => {
+                warn!(
+                    error_type = "AuthError",
+                    error_kind = "Overloaded",
+                    error = %self,
+                    "Password hashing pool saturated"
+                );
+            }
+
             // Internal processing errors
             AuthError::TokenCreation
             | AuthError::PasswordError(_) => {
@@ -338,8 +397,11 @@ impl ResponseError for AuthError {
         }
         
         let msg = self.to_string();
-        HttpResponse::build(self.status_code())
-            .json(serde_json::json!({ "error": msg }))
+        let mut response = HttpResponse::build(self.status_code());
+        if matches!(self, AuthError::ServiceBusy) {
+            response.insert_header((RETRY_AFTER, RETRY_AFTER_SECS.to_string()));
+        }
+        response.json(serde_json::json!({ "error": msg }))
     }
     fn status_code(&self) -> StatusCode {
         match *self {
@@ -348,7 +410,10 @@ impl ResponseError for AuthError {
             | AuthError::RevokedToken
             | AuthError::WrongCredentials
             | AuthError::AuthenticationFailed
-            | AuthError::TokenRevoked => StatusCode::UNAUTHORIZED,
+            | AuthError::TokenRevoked
+            | AuthError::StaleClaims
+            | AuthError::MissingApiToken
+            | AuthError::SiteLocked => StatusCode::UNAUTHORIZED,
 
             AuthError::MissingCredentials
             | AuthError::MissingAuthHeader
@@ -365,6 +430,8 @@ impl ResponseError for AuthError {
             | AuthError::MissingAppState
             | AuthError::PasswordError(_)
             | AuthError::RedisConnection(_)=> StatusCode::INTERNAL_SERVER_ERROR,
+
+            AuthError::ServiceBusy => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 }
@@ -386,8 +453,10 @@ impl From<argon2::password_hash::Error> for AuthError {
 
 impl From<PasswordError> for AuthError {
     fn from(err: PasswordError) -> Self {
-        AuthError::PasswordError(err.to_string());
-        AuthError::AuthenticationFailed
+        match err {
+            PasswordError::Busy => AuthError::ServiceBusy,
+            _ => AuthError::AuthenticationFailed,
+        }
     }
 }
 
@@ -430,10 +499,13 @@ pub enum PasswordError {
     EvaluationFailed,
 
     #[display("Password is too weak: {_0}")]
-    WeakWithFeedback(String)
+    WeakWithFeedback(String),
+
+    #[display("Password hashing capacity exhausted")]
+    Busy,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FieldError {
     pub field: String,
     pub message: String,
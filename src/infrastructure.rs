@@ -1,4 +1,15 @@
 pub mod auth;
 pub mod db;
 pub mod utils;
-pub mod limiter;
\ No newline at end of file
+pub mod limiter;
+pub mod mailer;
+pub mod metrics;
+pub mod dns;
+pub mod cache;
+pub mod cdn;
+pub mod storage;
+pub mod links;
+pub mod geo;
+pub mod captcha;
+pub mod reporting;
+pub mod crypto;
\ No newline at end of file
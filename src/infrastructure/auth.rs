@@ -1,2 +1,4 @@
 pub mod jwt;
-pub mod password;
\ No newline at end of file
+pub mod password;
+pub mod site_gate;
+pub mod visitor;
\ No newline at end of file
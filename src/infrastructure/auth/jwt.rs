@@ -19,6 +19,9 @@ pub struct JwtService {
     keys: JwtKeys,
     access_expiration: Duration,
     refresh_expiration: Duration,
+    issuer: Option<String>,
+    audience: Option<String>,
+    enforce_issuer_audience: bool,
 }
 impl JwtService {
     pub fn new(config: &AppConfig) -> Self {
@@ -26,10 +29,39 @@ impl JwtService {
             keys: JwtKeys::from(config), 
             access_expiration: Duration::minutes(config.jwt_expiration_minutes), 
             refresh_expiration: Duration::days(config.refresh_token_exp_days),
+            issuer: config.jwt_issuer.clone(),
+            audience: config.jwt_audience.clone(),
+            enforce_issuer_audience: config.jwt_enforce_issuer_audience,
         }
     }
 
-    pub fn create_jwt(&self, user: &User) -> Result<String, AuthError> {
+    /// Checks `iss`/`aud` against the configured values. A token carrying a
+    /// different value is always rejected; one without them (minted before
+    /// they were configured) only when enforcement is on.
+    fn validation(&self) -> Validation {
+        let mut validation = Validation::new(JWT_ALGORITHM);
+
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+            if self.enforce_issuer_audience {
+                validation.required_spec_claims.insert("iss".to_string());
+            }
+        }
+
+        match &self.audience {
+            Some(audience) => {
+                validation.set_audience(&[audience]);
+                if self.enforce_issuer_audience {
+                    validation.required_spec_claims.insert("aud".to_string());
+                }
+            }
+            None => validation.validate_aud = false,
+        }
+
+        validation
+    }
+
+    pub fn create_jwt(&self, user: &User, claims_version: u64) -> Result<String, AuthError> {
         let now = Utc::now();
         let exp = (now + self.access_expiration).timestamp() as usize;
 
@@ -37,16 +69,21 @@ impl JwtService {
             sub: user.id.to_string(),
             email: user.email.clone(),
             admin: user.is_admin,
+            editor: user.is_editor,
             verified: user.is_verified,
             exp,
             token_type: TokenType::Access,
             iat: now.timestamp() as usize,
+            tid: user.tenant_id,
+            claims_version,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
         };
 
         encode(&Header::new(JWT_ALGORITHM), &claims, &self.keys.encoding).map_err(AuthError::from)
     }
 
-    pub fn create_refresh_jwt(&self, user_id: &Uuid) -> Result<String, AuthError> {
+    pub fn create_refresh_jwt(&self, user_id: &Uuid, claims_version: u64) -> Result<String, AuthError> {
         let now = Utc::now();
         let exp = (now + self.refresh_expiration).timestamp() as usize;
 
@@ -55,14 +92,17 @@ impl JwtService {
             exp,
             token_type: TokenType::Refresh,
             iat: now.timestamp() as usize,
+            claims_version,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
         };
 
         encode(&Header::new(JWT_ALGORITHM), &claims, &self.keys.refresh_encoding).map_err(AuthError::from)
     }
 
     pub fn decode_jwt(&self, token: &str) -> Result<TokenData<Claims>, AuthError> {
-        let mut validation = Validation::new(JWT_ALGORITHM);
-        validation.required_spec_claims = ["exp".to_string(), "sub".to_string()].iter().cloned().collect();
+        let mut validation = self.validation();
+        validation.required_spec_claims.insert("sub".to_string());
         validation.leeway = 0;
 
         decode::<Claims>(
@@ -74,7 +114,7 @@ impl JwtService {
     }
 
     pub fn decode_refresh_jwt(&self, token: &str) -> Result<TokenData<RefreshClaims>, AuthError> {
-        let mut validation = Validation::new(JWT_ALGORITHM);
+        let mut validation = self.validation();
         validation.validate_exp = true;
     
         decode::<RefreshClaims>(
@@ -84,17 +124,45 @@ impl JwtService {
         )
         .map_err(AuthError::from)
     }
+
+    /// Signs and verifies a throwaway access and refresh token, so keys that
+    /// cannot round-trip are caught at startup instead of at the first login
+    pub fn self_test(&self) -> Result<(), AuthError> {
+        let now = Utc::now();
+        let claims = Claims {
+            sub: Uuid::nil().to_string(),
+            email: String::new(),
+            admin: false,
+            editor: false,
+            verified: false,
+            exp: (now + Duration::minutes(1)).timestamp() as usize,
+            token_type: TokenType::Access,
+            iat: now.timestamp() as usize,
+            tid: Uuid::nil(),
+            claims_version: 0,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+        };
+
+        let access = encode(&Header::new(JWT_ALGORITHM), &claims, &self.keys.encoding)?;
+        self.decode_jwt(&access)?;
+
+        let refresh = self.create_refresh_jwt(&Uuid::nil(), 0)?;
+        self.decode_refresh_jwt(&refresh)?;
+
+        Ok(())
+    }
 }
 
 
 #[async_trait]
 impl TokenServiceRepository for JwtService {
-    fn create_jwt(&self, user: &User) -> Result<String, AuthError> {
-        self.create_jwt(user)
+    fn create_jwt(&self, user: &User, claims_version: u64) -> Result<String, AuthError> {
+        self.create_jwt(user, claims_version)
     }
 
-    fn create_refresh_jwt(&self, user_id: &Uuid) -> Result<String, AuthError> {
-        self.create_refresh_jwt(user_id)
+    fn create_refresh_jwt(&self, user_id: &Uuid, claims_version: u64) -> Result<String, AuthError> {
+        self.create_refresh_jwt(user_id, claims_version)
     }
 
     fn decode_jwt(&self, token: &str) -> Result<TokenData<Claims>, AuthError> {
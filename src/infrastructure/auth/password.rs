@@ -6,7 +6,59 @@ use argon2::{
     Argon2, Algorithm, Params, Version
 };
 
-use crate::errors::PasswordError;
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use tokio::sync::Semaphore;
+
+use crate::{entities::user::PasswordHashAlgorithm, errors::PasswordError};
+
+/// Runs Argon2 on tokio's blocking pool with at most `max_concurrency` jobs in flight.
+///
+/// Callers wait up to `queue_timeout` for a slot; past that they get
+/// `PasswordError::Busy`, which surfaces as 503 with `Retry-After`.
+#[derive(Clone)]
+pub struct PasswordHasherPool {
+    permits: Arc<Semaphore>,
+    queue_timeout: Duration,
+}
+
+impl PasswordHasherPool {
+    pub fn new(max_concurrency: usize, queue_timeout: Duration) -> Self {
+        PasswordHasherPool {
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+            queue_timeout,
+        }
+    }
+
+    pub async fn hash(&self, password: &str) -> Result<String, PasswordError> {
+        let password = password.to_owned();
+        self.run(move || hash_password(&password)).await
+    }
+
+    pub async fn verify(&self, password: &str, hashed: &str) -> Result<bool, PasswordError> {
+        let password = password.to_owned();
+        let hashed = hashed.to_owned();
+        self.run(move || verify_password(&password, &hashed)).await
+    }
+
+    async fn run<F, T>(&self, job: F) -> Result<T, PasswordError>
+    where
+        F: FnOnce() -> Result<T, PasswordError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = tokio::time::timeout(self.queue_timeout, self.permits.clone().acquire_owned())
+            .await
+            .map_err(|_| PasswordError::Busy)?
+            .map_err(|_| PasswordError::Busy)?;
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|e| PasswordError::HashingError(e.to_string()))?
+    }
+}
 
 pub fn hash_password(password: &str) -> Result<String, PasswordError> {
     let salt = SaltString::generate(&mut OsRng);
@@ -23,10 +75,37 @@ pub fn hash_password(password: &str) -> Result<String, PasswordError> {
         .map(|hash| hash.to_string())
 }
 
+/// Checks that `hashed` is a well-formed hash of `algorithm`
+pub fn check_hash_format(algorithm: PasswordHashAlgorithm, hashed: &str) -> Result<(), PasswordError> {
+    if PasswordHashAlgorithm::detect(hashed) != Some(algorithm) {
+        return Err(PasswordError::InvalidHashFormat(format!("Not a {} hash", algorithm.as_str())));
+    }
+
+    match algorithm {
+        PasswordHashAlgorithm::Argon2 => PasswordHash::new(hashed)
+            .map(|_| ())
+            .map_err(|e| PasswordError::InvalidHashFormat(e.to_string())),
+        PasswordHashAlgorithm::Bcrypt => bcrypt::HashParts::from_str(hashed)
+            .map(|_| ())
+            .map_err(|e| PasswordError::InvalidHashFormat(e.to_string())),
+    }
+}
+
+/// True when `hashed` should be replaced by a fresh Argon2id hash once the
+/// password is known
+pub fn needs_rehash(hashed: &str) -> bool {
+    !hashed.starts_with("$argon2id$")
+}
+
 pub fn verify_password(
     password: &str,
     hashed: &str,
 ) -> Result<bool, PasswordError> {
+    if PasswordHashAlgorithm::detect(hashed) == Some(PasswordHashAlgorithm::Bcrypt) {
+        return bcrypt::verify(password, hashed)
+            .map_err(|e| PasswordError::VerificationError(e.to_string()));
+    }
+
     let parsed_hash = PasswordHash::new(hashed)
         .map_err(|e| PasswordError::InvalidHashFormat(e.to_string()))?;
     
@@ -0,0 +1,90 @@
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{errors::AuthError, settings::AppConfig};
+
+pub const SITE_GATE_COOKIE: &str = "site_gate";
+pub const SITE_GATE_HEADER: &str = "X-Site-Gate";
+
+const GATE_ALGORITHM: Algorithm = Algorithm::HS512;
+// Audience keeps gate tokens and access tokens from being accepted in place of each other
+const GATE_AUDIENCE: &str = "site-gate";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GateClaims {
+    aud: String,
+    exp: usize,
+    iat: usize,
+}
+
+/// Site-wide passphrase gate used while the portfolio is in soft launch.
+///
+/// A correct passphrase is exchanged for a short-lived signed token that the
+/// `SiteGateMiddleware` accepts from the `site_gate` cookie or `X-Site-Gate` header.
+#[derive(Clone)]
+pub struct SiteGate {
+    passphrase: Zeroizing<String>,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+    secure_cookie: bool,
+}
+
+impl SiteGate {
+    /// Returns `None` when no passphrase is configured, i.e. the site is public
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let passphrase = config.site_gate_passphrase.as_ref()?;
+        let secret = Zeroizing::new(config.jwt_secret.clone());
+
+        Some(SiteGate {
+            passphrase: Zeroizing::new(passphrase.clone()),
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            ttl: Duration::hours(config.site_gate_ttl_hours),
+            secure_cookie: config.is_production(),
+        })
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Gate cookies are only marked `Secure` in production so local HTTP still works
+    pub fn secure_cookie(&self) -> bool {
+        self.secure_cookie
+    }
+
+    /// Exchanges the shared passphrase for a gate token
+    pub fn unlock(&self, passphrase: &str) -> Result<String, AuthError> {
+        if !constant_time_eq(passphrase.as_bytes(), self.passphrase.as_bytes()) {
+            return Err(AuthError::WrongCredentials);
+        }
+
+        let now = Utc::now();
+        let claims = GateClaims {
+            aud: GATE_AUDIENCE.to_string(),
+            exp: (now + self.ttl).timestamp() as usize,
+            iat: now.timestamp() as usize,
+        };
+
+        encode(&Header::new(GATE_ALGORITHM), &claims, &self.encoding).map_err(AuthError::from)
+    }
+
+    pub fn verify(&self, token: &str) -> bool {
+        let mut validation = Validation::new(GATE_ALGORITHM);
+        validation.set_audience(&[GATE_AUDIENCE]);
+        validation.leeway = 0;
+
+        decode::<GateClaims>(token, &self.decoding, &validation).is_ok()
+    }
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,138 @@
+use actix_web::HttpRequest;
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use crate::{errors::AuthError, settings::AppConfig, utils::get_client_ip::{get_client_ip, TrustedProxies}};
+
+pub const VISITOR_COOKIE: &str = "visitor";
+pub const VISITOR_HEADER: &str = "X-Visitor-Token";
+
+const VISITOR_ALGORITHM: Algorithm = Algorithm::HS512;
+// A key of their own keeps visitor tokens and access or gate tokens from
+// being accepted for one another; the required audience is a second check
+const VISITOR_AUDIENCE: &str = "visitor";
+const VISITOR_KEY_LABEL: &[u8] = b"visitor";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VisitorClaims {
+    sub: String,
+    tid: Uuid,
+    aud: String,
+    exp: usize,
+    iat: usize,
+}
+
+/// Anonymous identity for visitors who never sign in, so limits and
+/// attribution can tell them apart more fairly than by IP alone.
+///
+/// The id is a keyed hash of the tenant, client IP and user agent; neither
+/// is kept. Visitors without a token get the id their request hashes to, and
+/// a signed token from `GET /visitor-token` keeps it when their IP changes.
+#[derive(Clone)]
+pub struct VisitorTokens {
+    hash_key: Zeroizing<String>,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+    secure_cookie: bool,
+    trusted_proxies: TrustedProxies,
+}
+
+impl VisitorTokens {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let secret = Zeroizing::new(config.jwt_secret.clone());
+        let signing_key = signing_key(&secret);
+
+        VisitorTokens {
+            hash_key: secret,
+            encoding: EncodingKey::from_secret(&signing_key),
+            decoding: DecodingKey::from_secret(&signing_key),
+            ttl: Duration::days(config.visitor_token_ttl_days),
+            secure_cookie: config.is_production(),
+            trusted_proxies: TrustedProxies::from_config(config),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Visitor cookies are only marked `Secure` in production so local HTTP still works
+    pub fn secure_cookie(&self) -> bool {
+        self.secure_cookie
+    }
+
+    /// The visitor id of a valid token presented with the request, otherwise
+    /// the one derived from the request itself
+    pub fn identify(&self, tenant_id: Uuid, req: &HttpRequest) -> String {
+        presented_token(req)
+            .and_then(|token| self.verify(tenant_id, &token))
+            .unwrap_or_else(|| self.derive_id(tenant_id, req))
+    }
+
+    /// Signs `visitor_id` for the tenant, valid for the configured TTL
+    pub fn issue(&self, tenant_id: Uuid, visitor_id: &str) -> Result<String, AuthError> {
+        let now = Utc::now();
+        let claims = VisitorClaims {
+            sub: visitor_id.to_string(),
+            tid: tenant_id,
+            aud: VISITOR_AUDIENCE.to_string(),
+            exp: (now + self.ttl).timestamp() as usize,
+            iat: now.timestamp() as usize,
+        };
+
+        encode(&Header::new(VISITOR_ALGORITHM), &claims, &self.encoding).map_err(AuthError::from)
+    }
+
+    /// The visitor id in `token`, if it is valid and was issued for the tenant
+    pub fn verify(&self, tenant_id: Uuid, token: &str) -> Option<String> {
+        let mut validation = Validation::new(VISITOR_ALGORITHM);
+        validation.set_audience(&[VISITOR_AUDIENCE]);
+        // jsonwebtoken only checks `aud` when the token has one
+        validation.required_spec_claims.insert("aud".into());
+        validation.leeway = 0;
+
+        decode::<VisitorClaims>(token, &self.decoding, &validation)
+            .ok()
+            .filter(|data| data.claims.tid == tenant_id)
+            .map(|data| data.claims.sub)
+    }
+
+    fn derive_id(&self, tenant_id: Uuid, req: &HttpRequest) -> String {
+        let user_agent = req.headers()
+            .get("User-Agent")
+            .and_then(|ua| ua.to_str().ok())
+            .unwrap_or_default();
+
+        let digest = Sha256::new()
+            .chain_update(self.hash_key.as_bytes())
+            .chain_update(tenant_id.as_bytes())
+            .chain_update(get_client_ip(req, &self.trusted_proxies).as_bytes())
+            .chain_update([0])
+            .chain_update(user_agent.as_bytes())
+            .finalize();
+        format!("{:x}", digest)[..32].to_string()
+    }
+}
+
+/// HMAC(jwt_secret, "visitor"), so visitor tokens are not signed with the
+/// key access tokens are
+fn signing_key(secret: &str) -> Zeroizing<Vec<u8>> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(VISITOR_KEY_LABEL);
+    Zeroizing::new(mac.finalize().into_bytes().to_vec())
+}
+
+fn presented_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(VISITOR_HEADER)
+        .and_then(|header| header.to_str().ok())
+        .map(|token| token.trim().to_string())
+        .or_else(|| req.cookie(VISITOR_COOKIE).map(|cookie| cookie.value().to_string()))
+        .filter(|token| !token.is_empty())
+}
\ No newline at end of file
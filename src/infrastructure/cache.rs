@@ -0,0 +1,10 @@
+pub mod page_cache;
+pub mod redis_pool;
+pub mod presence;
+pub mod claims_version;
+pub mod rate_limit_state;
+pub mod single_flight;
+pub mod one_time_tokens;
+pub mod api_token_usage;
+pub mod edit_locks;
+pub mod usage;
\ No newline at end of file
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::{cache::redis_pool::{RedisConnection, SupervisedPool}, entities::api_token::ApiTokenDailyUsage, errors::AppError};
+
+const KEY_PREFIX: &str = "api_usage";
+/// Days are kept past the longest month so a late flush still finds them
+const DAY_TTL_SECS: i64 = 40 * 24 * 60 * 60;
+
+/// API token traffic per day shared by every instance, one hash per token
+/// and day. Each instance adds what it counted and gets the day's totals
+/// back, which are then saved to Postgres as they are.
+#[automock]
+#[async_trait]
+pub trait ApiTokenUsageCounter: Send + Sync {
+    /// Adds the counts and returns the resulting totals of the same days
+    async fn add_usage(&self, usage: &[ApiTokenDailyUsage]) -> Result<Vec<ApiTokenDailyUsage>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct RedisApiTokenUsageCounter {
+    pool: SupervisedPool,
+}
+
+impl RedisApiTokenUsageCounter {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisApiTokenUsageCounter { pool }
+    }
+
+    fn key(usage: &ApiTokenDailyUsage) -> String {
+        format!("{}:{}:{}", KEY_PREFIX, usage.token_id, usage.day)
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl ApiTokenUsageCounter for RedisApiTokenUsageCounter {
+    async fn add_usage(&self, usage: &[ApiTokenDailyUsage]) -> Result<Vec<ApiTokenDailyUsage>, AppError> {
+        if usage.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for counted in usage {
+            let key = Self::key(counted);
+            pipe.hincr(&key, "requests", counted.requests)
+                .hincr(&key, "bytes", counted.bytes)
+                .hincr(&key, "errors", counted.errors)
+                .expire(&key, DAY_TTL_SECS)
+                .ignore();
+        }
+
+        let mut conn = self.connection().await?;
+        let totals: Vec<i64> = pipe.query_async(&mut conn).await.map_err(|e| self.redis_error(e))?;
+
+        Ok(usage
+            .iter()
+            .zip(totals.chunks_exact(3))
+            .map(|(counted, total)| ApiTokenDailyUsage {
+                token_id: counted.token_id,
+                day: counted.day,
+                requests: total[0],
+                bytes: total[1],
+                errors: total[2],
+            })
+            .collect())
+    }
+}
+
+/// Without Redis each instance adds its counts to Postgres directly
+pub fn api_token_usage_counter_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn ApiTokenUsageCounter>> {
+    pool.map(|pool| Arc::new(RedisApiTokenUsageCounter::new(pool.clone())) as Arc<dyn ApiTokenUsageCounter>)
+}
\ No newline at end of file
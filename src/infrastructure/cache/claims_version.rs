@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{cache::redis_pool::{RedisConnection, SupervisedPool}, errors::AppError};
+
+const KEY_PREFIX: &str = "claims_version";
+
+/// Per-user counter stamped into every token the user is issued. Bumping it
+/// invalidates all of the user's outstanding tokens at once.
+#[automock]
+#[async_trait]
+pub trait ClaimsVersionStore: Send + Sync {
+    /// 0 for users whose version was never bumped
+    async fn current(&self, user_id: &Uuid) -> Result<u64, AppError>;
+
+    /// Increments the user's version and returns the new one
+    async fn bump(&self, user_id: &Uuid) -> Result<u64, AppError>;
+}
+
+/// Keys never expire: a counter that lapsed would restart below versions
+/// already handed out, and those tokens would pass the check again.
+#[derive(Clone)]
+pub struct RedisClaimsVersionStore {
+    pool: SupervisedPool,
+}
+
+impl RedisClaimsVersionStore {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisClaimsVersionStore { pool }
+    }
+
+    fn key(user_id: &Uuid) -> String {
+        format!("{}:{}", KEY_PREFIX, user_id)
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl ClaimsVersionStore for RedisClaimsVersionStore {
+    async fn current(&self, user_id: &Uuid) -> Result<u64, AppError> {
+        let mut conn = self.connection().await?;
+
+        let version: Option<u64> = conn.get(Self::key(user_id)).await.map_err(|e| self.redis_error(e))?;
+        Ok(version.unwrap_or(0))
+    }
+
+    async fn bump(&self, user_id: &Uuid) -> Result<u64, AppError> {
+        let mut conn = self.connection().await?;
+
+        conn.incr(Self::key(user_id), 1u64).await.map_err(|e| self.redis_error(e))
+    }
+}
+
+/// Versioning needs Redis; without it every token carries version 0 and
+/// bumping answers 503
+pub fn claims_version_store_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn ClaimsVersionStore>> {
+    pool.map(|pool| Arc::new(RedisClaimsVersionStore::new(pool.clone())) as Arc<dyn ClaimsVersionStore>)
+}
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    cache::redis_pool::{RedisConnection, SupervisedPool},
+    entities::edit_lock::PostEditLock,
+    errors::AppError,
+};
+
+/// Takes or extends the lock. Another holder's lock is returned untouched
+/// unless forced; a new holder gets a fresh `acquired_at`.
+const ACQUIRE_SCRIPT: &str = r#"
+    local holder = redis.call("HGET", KEYS[1], "holder_id")
+    if holder and holder ~= ARGV[1] and ARGV[2] ~= "1" then
+        return redis.call("HGETALL", KEYS[1])
+    end
+    if holder ~= ARGV[1] then
+        redis.call("DEL", KEYS[1])
+        redis.call("HSET", KEYS[1], "post_id", ARGV[4], "holder_id", ARGV[1], "holder_email", ARGV[5], "acquired_at", ARGV[6])
+    end
+    redis.call("HSET", KEYS[1], "expires_at", ARGV[7])
+    redis.call("EXPIRE", KEYS[1], ARGV[3])
+    return redis.call("HGETALL", KEYS[1])
+"#;
+
+/// Deletes the lock only if ARGV[1] still holds it
+const RELEASE_SCRIPT: &str = r#"
+    if redis.call("HGET", KEYS[1], "holder_id") == ARGV[1] then
+        return redis.call("DEL", KEYS[1])
+    end
+    return 0
+"#;
+
+#[automock]
+#[async_trait]
+pub trait EditLockStore: Send + Sync {
+    /// Gives `lock` to its holder for `ttl_secs`, or extends it when they
+    /// hold it already. Someone else's lock is only replaced with `force`.
+    /// Returns the lock in place afterwards, whoever holds it.
+    async fn acquire(&self, key: &str, lock: &PostEditLock, ttl_secs: u64, force: bool) -> Result<PostEditLock, AppError>;
+
+    async fn current(&self, key: &str) -> Result<Option<PostEditLock>, AppError>;
+
+    /// Drops the lock if `holder_id` holds it; false when they did not
+    async fn release(&self, key: &str, holder_id: &Uuid) -> Result<bool, AppError>;
+}
+
+#[derive(Clone)]
+pub struct RedisEditLockStore {
+    pool: SupervisedPool,
+}
+
+impl RedisEditLockStore {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisEditLockStore { pool }
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl EditLockStore for RedisEditLockStore {
+    async fn acquire(&self, key: &str, lock: &PostEditLock, ttl_secs: u64, force: bool) -> Result<PostEditLock, AppError> {
+        let mut conn = self.connection().await?;
+
+        let fields: HashMap<String, String> = redis::Script::new(ACQUIRE_SCRIPT)
+            .key(key)
+            .arg(lock.holder_id.to_string())
+            .arg(if force { "1" } else { "0" })
+            .arg(ttl_secs)
+            .arg(lock.post_id.to_string())
+            .arg(&lock.holder_email)
+            .arg(lock.acquired_at.to_rfc3339())
+            .arg(lock.expires_at.to_rfc3339())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| self.redis_error(e))?;
+
+        from_fields(&fields).ok_or_else(|| AppError::InternalError("Malformed edit lock in Redis".to_string()))
+    }
+
+    async fn current(&self, key: &str) -> Result<Option<PostEditLock>, AppError> {
+        let mut conn = self.connection().await?;
+        let fields: HashMap<String, String> = conn.hgetall(key).await.map_err(|e| self.redis_error(e))?;
+
+        // A malformed entry is treated as no lock
+        Ok(from_fields(&fields))
+    }
+
+    async fn release(&self, key: &str, holder_id: &Uuid) -> Result<bool, AppError> {
+        let mut conn = self.connection().await?;
+        let deleted: i64 = redis::Script::new(RELEASE_SCRIPT)
+            .key(key)
+            .arg(holder_id.to_string())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| self.redis_error(e))?;
+
+        Ok(deleted > 0)
+    }
+}
+
+fn from_fields(fields: &HashMap<String, String>) -> Option<PostEditLock> {
+    let time = |name: &str| {
+        fields.get(name)
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&Utc))
+    };
+
+    Some(PostEditLock {
+        post_id: fields.get("post_id")?.parse().ok()?,
+        holder_id: fields.get("holder_id")?.parse().ok()?,
+        holder_email: fields.get("holder_email")?.clone(),
+        acquired_at: time("acquired_at")?,
+        expires_at: time("expires_at")?,
+    })
+}
+
+/// Edit locks need Redis; without it the lock endpoints answer 503
+pub fn edit_lock_store_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn EditLockStore>> {
+    pool.map(|pool| Arc::new(RedisEditLockStore::new(pool.clone())) as Arc<dyn EditLockStore>)
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use mockall::automock;
+use redis::AsyncCommands;
+
+use crate::{cache::redis_pool::{RedisConnection, SupervisedPool}, entities::one_time_token::OneTimeToken, errors::AppError};
+
+const KEY_PREFIX: &str = "ott";
+
+/// Unspent one-time tokens by hash, in front of the `one_time_tokens`
+/// table. Postgres decides whether a token is valid; a missing entry only
+/// means the table is asked.
+#[automock]
+#[async_trait]
+pub trait OneTimeTokenCache: Send + Sync {
+    async fn get_token(&self, token_hash: &str) -> Result<Option<OneTimeToken>, AppError>;
+    /// Kept until the token expires
+    async fn put_token(&self, token: &OneTimeToken) -> Result<(), AppError>;
+    async fn remove_token(&self, token_hash: &str) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct RedisOneTimeTokenCache {
+    pool: SupervisedPool,
+}
+
+impl RedisOneTimeTokenCache {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisOneTimeTokenCache { pool }
+    }
+
+    fn key(token_hash: &str) -> String {
+        format!("{}:{}", KEY_PREFIX, token_hash)
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl OneTimeTokenCache for RedisOneTimeTokenCache {
+    async fn get_token(&self, token_hash: &str) -> Result<Option<OneTimeToken>, AppError> {
+        let mut conn = self.connection().await?;
+        let raw: Option<String> = conn.get(Self::key(token_hash)).await.map_err(|e| self.redis_error(e))?;
+
+        // A malformed entry is treated as a miss
+        Ok(raw.and_then(|raw| serde_json::from_str(&raw).ok()))
+    }
+
+    async fn put_token(&self, token: &OneTimeToken) -> Result<(), AppError> {
+        let ttl_secs = (token.expires_at - Utc::now()).num_seconds();
+        if ttl_secs <= 0 {
+            return Ok(());
+        }
+
+        let raw = serde_json::to_string(token)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode one-time token: {}", e)))?;
+
+        let mut conn = self.connection().await?;
+        conn.set_ex::<_, _, ()>(Self::key(&token.token_hash), raw, ttl_secs as u64)
+            .await
+            .map_err(|e| self.redis_error(e))
+    }
+
+    async fn remove_token(&self, token_hash: &str) -> Result<(), AppError> {
+        let mut conn = self.connection().await?;
+        conn.del::<_, ()>(Self::key(token_hash)).await.map_err(|e| self.redis_error(e))
+    }
+}
+
+/// Without Redis every lookup goes to the database
+pub fn one_time_token_cache_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn OneTimeTokenCache>> {
+    pool.map(|pool| Arc::new(RedisOneTimeTokenCache::new(pool.clone())) as Arc<dyn OneTimeTokenCache>)
+}
\ No newline at end of file
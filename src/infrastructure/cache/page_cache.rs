@@ -0,0 +1,105 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use async_trait::async_trait;
+use mockall::automock;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+use crate::{cache::redis_pool::{RedisConnection, SupervisedPool}, errors::AppError};
+
+/// A fully rendered public response, ready to be served as-is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedPage {
+    pub content_type: String,
+    pub body: String,
+}
+
+#[automock]
+#[async_trait]
+pub trait PageStore: Send + Sync {
+    async fn get_page(&self, key: &str) -> Result<Option<CachedPage>, AppError>;
+
+    /// Stores the page and records it under each of `tags`, the keys of the
+    /// entities it was rendered from
+    async fn put_page(&self, key: &str, page: &CachedPage, tags: &[String], ttl_secs: u64) -> Result<(), AppError>;
+
+    /// Drops every page recorded under any of `tags`; returns their keys
+    async fn invalidate_tags(&self, tags: Vec<String>) -> Result<Vec<String>, AppError>;
+}
+
+/// Stores rendered pages as JSON strings with a TTL. Each tag is a set of
+/// page keys that lives as long as the newest page added to it; keys of
+/// pages that expired on their own are left in it and deleted harmlessly.
+#[derive(Clone)]
+pub struct RedisPageStore {
+    pool: SupervisedPool,
+}
+
+impl RedisPageStore {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisPageStore { pool }
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl PageStore for RedisPageStore {
+    async fn get_page(&self, key: &str) -> Result<Option<CachedPage>, AppError> {
+        let mut conn = self.connection().await?;
+        let raw: Option<String> = conn.get(key).await.map_err(|e| self.redis_error(e))?;
+
+        // A malformed entry is treated as a miss and overwritten by the next warm-up
+        Ok(raw.and_then(|raw| serde_json::from_str(&raw).ok()))
+    }
+
+    async fn put_page(&self, key: &str, page: &CachedPage, tags: &[String], ttl_secs: u64) -> Result<(), AppError> {
+        let raw = serde_json::to_string(page)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode cached page: {}", e)))?;
+        let ttl_secs = ttl_secs.max(1);
+
+        let mut pipe = redis::pipe();
+        pipe.set_ex(key, raw, ttl_secs).ignore();
+        for tag in tags {
+            pipe.sadd(tag, key).ignore().expire(tag, ttl_secs as i64).ignore();
+        }
+
+        let mut conn = self.connection().await?;
+        pipe.query_async::<()>(&mut conn).await.map_err(|e| self.redis_error(e))
+    }
+
+    async fn invalidate_tags(&self, tags: Vec<String>) -> Result<Vec<String>, AppError> {
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.connection().await?;
+        let mut pipe = redis::pipe();
+        for tag in &tags {
+            pipe.smembers(tag);
+        }
+        let members: Vec<Vec<String>> = pipe.query_async(&mut conn).await.map_err(|e| self.redis_error(e))?;
+        let keys: Vec<String> = members.into_iter().flatten().collect::<BTreeSet<_>>().into_iter().collect();
+
+        let mut doomed = keys.clone();
+        doomed.extend(tags);
+        conn.del::<_, ()>(doomed).await.map_err(|e| self.redis_error(e))?;
+
+        Ok(keys)
+    }
+}
+
+/// Page caching needs Redis; without it every public read goes to the database
+pub fn page_store_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn PageStore>> {
+    pool.map(|pool| Arc::new(RedisPageStore::new(pool.clone())) as Arc<dyn PageStore>)
+}
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::{cache::redis_pool::{RedisConnection, SupervisedPool}, errors::AppError};
+
+/// Records a heartbeat: the session's expiry goes into a sorted set and its
+/// current path into a hash, so a visitor who moves to another page is
+/// counted once, on the new page. Both keys expire when the tenant goes quiet.
+const BEAT_SCRIPT: &str = r#"
+    redis.call("ZADD", KEYS[1], ARGV[1], ARGV[3])
+    redis.call("HSET", KEYS[2], ARGV[3], ARGV[4])
+    redis.call("EXPIRE", KEYS[1], ARGV[2])
+    redis.call("EXPIRE", KEYS[2], ARGV[2])
+    return 1
+"#;
+
+/// Drops expired sessions and returns the path of every live one
+const LIVE_PATHS_SCRIPT: &str = r#"
+    local expired = redis.call("ZRANGEBYSCORE", KEYS[1], "-inf", ARGV[1])
+    for i = 1, #expired, 500 do
+        redis.call("HDEL", KEYS[2], unpack(expired, i, math.min(i + 499, #expired)))
+    end
+    redis.call("ZREMRANGEBYSCORE", KEYS[1], "-inf", ARGV[1])
+
+    local live = redis.call("ZRANGE", KEYS[1], 0, -1)
+    local paths = {}
+    for i = 1, #live, 500 do
+        local chunk = redis.call("HMGET", KEYS[2], unpack(live, i, math.min(i + 499, #live)))
+        for _, path in ipairs(chunk) do
+            if path then
+                table.insert(paths, path)
+            end
+        end
+    end
+    return paths
+"#;
+
+#[automock]
+#[async_trait]
+pub trait PresenceStore: Send + Sync {
+    /// Marks `session` as viewing `path` until `expires_at` (unix seconds).
+    /// `scope` namespaces the keys, one scope per tenant.
+    async fn beat(&self, scope: &str, session: &str, path: &str, expires_at: i64) -> Result<(), AppError>;
+
+    /// Paths of the sessions still live at `now`, one entry per session
+    async fn live_paths(&self, scope: &str, now: i64) -> Result<Vec<String>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct RedisPresenceStore {
+    pool: SupervisedPool,
+    /// Idle keys are dropped after this long
+    key_ttl_secs: u64,
+}
+
+impl RedisPresenceStore {
+    pub fn new(pool: SupervisedPool, ttl_secs: u64) -> Self {
+        RedisPresenceStore { pool, key_ttl_secs: ttl_secs * 2 }
+    }
+
+    fn keys(scope: &str) -> [String; 2] {
+        [format!("{}:expiry", scope), format!("{}:path", scope)]
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl PresenceStore for RedisPresenceStore {
+    async fn beat(&self, scope: &str, session: &str, path: &str, expires_at: i64) -> Result<(), AppError> {
+        let [expiry_key, path_key] = Self::keys(scope);
+        let mut conn = self.connection().await?;
+
+        redis::Script::new(BEAT_SCRIPT)
+            .key(expiry_key)
+            .key(path_key)
+            .arg(expires_at)
+            .arg(self.key_ttl_secs)
+            .arg(session)
+            .arg(path)
+            .invoke_async::<()>(&mut conn)
+            .await
+            .map_err(|e| self.redis_error(e))
+    }
+
+    async fn live_paths(&self, scope: &str, now: i64) -> Result<Vec<String>, AppError> {
+        let [expiry_key, path_key] = Self::keys(scope);
+        let mut conn = self.connection().await?;
+
+        redis::Script::new(LIVE_PATHS_SCRIPT)
+            .key(expiry_key)
+            .key(path_key)
+            .arg(now)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| self.redis_error(e))
+    }
+}
+
+/// Presence needs Redis; without it the endpoints answer 503
+pub fn presence_store_from_pool(pool: Option<&SupervisedPool>, ttl_secs: u64) -> Option<Arc<dyn PresenceStore>> {
+    pool.map(|pool| Arc::new(RedisPresenceStore::new(pool.clone(), ttl_secs)) as Arc<dyn PresenceStore>)
+}
\ No newline at end of file
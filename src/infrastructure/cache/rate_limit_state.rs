@@ -0,0 +1,109 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use mockall::automock;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+use crate::{cache::redis_pool::{RedisConnection, SupervisedPool}, errors::AppError};
+
+/// One hash holds every saved bucket, field per limiter key
+const STATE_KEY: &str = "rate_limits";
+
+/// A limiter bucket as saved in Redis. Ages are relative to `saved_at_ms`
+/// because `Instant`s do not survive a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedBucket {
+    /// `<route>:<client ip>`
+    pub key: String,
+    pub tokens: f64,
+    pub window_count: u64,
+    pub previous_window_count: u64,
+    pub window_age_ms: u64,
+    pub idle_ms: u64,
+    /// Unix milliseconds
+    pub saved_at_ms: i64,
+}
+
+#[automock]
+#[async_trait]
+pub trait RateLimitStateStore: Send + Sync {
+    /// Every saved bucket; entries that fail to parse are skipped
+    async fn load(&self) -> Result<Vec<PersistedBucket>, AppError>;
+
+    /// Saves the buckets, replacing earlier saves of the same keys. The whole
+    /// set expires `ttl_secs` after the last save.
+    async fn save(&self, buckets: &[PersistedBucket], ttl_secs: u64) -> Result<(), AppError>;
+
+    /// Drops saved buckets, e.g. ones too old to restore
+    async fn remove(&self, keys: &[String]) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct RedisRateLimitStateStore {
+    pool: SupervisedPool,
+}
+
+impl RedisRateLimitStateStore {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisRateLimitStateStore { pool }
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl RateLimitStateStore for RedisRateLimitStateStore {
+    async fn load(&self) -> Result<Vec<PersistedBucket>, AppError> {
+        let mut conn = self.connection().await?;
+
+        let raw: HashMap<String, String> = conn.hgetall(STATE_KEY).await.map_err(|e| self.redis_error(e))?;
+        Ok(raw.values().filter_map(|value| serde_json::from_str(value).ok()).collect())
+    }
+
+    async fn save(&self, buckets: &[PersistedBucket], ttl_secs: u64) -> Result<(), AppError> {
+        if buckets.is_empty() {
+            return Ok(());
+        }
+
+        let fields: Vec<(&str, String)> = buckets
+            .iter()
+            .filter_map(|bucket| serde_json::to_string(bucket).ok().map(|raw| (bucket.key.as_str(), raw)))
+            .collect();
+        let mut conn = self.connection().await?;
+
+        redis::pipe()
+            .atomic()
+            .hset_multiple(STATE_KEY, &fields)
+            .ignore()
+            .expire(STATE_KEY, ttl_secs as i64)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| self.redis_error(e))
+    }
+
+    async fn remove(&self, keys: &[String]) -> Result<(), AppError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.connection().await?;
+        conn.hdel::<_, _, ()>(STATE_KEY, keys).await.map_err(|e| self.redis_error(e))
+    }
+}
+
+/// Without Redis, limiter buckets always start empty after a restart
+pub fn rate_limit_state_store_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn RateLimitStateStore>> {
+    pool.map(|pool| Arc::new(RedisRateLimitStateStore::new(pool.clone())) as Arc<dyn RateLimitStateStore>)
+}
\ No newline at end of file
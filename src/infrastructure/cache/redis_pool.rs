@@ -0,0 +1,521 @@
+//! Redis connection pool with a health supervisor.
+//!
+//! deadpool only finds out about a dead connection when a request trips over
+//! it, so after a Redis restart requests keep failing until traffic churns
+//! the pool. `SupervisedPool::check` runs on a timer instead: it pings the
+//! idle connections, drops the broken ones and rebuilds the whole pool after
+//! sustained failure. Errors seen by requests are only counted; the
+//! supervisor logs them once per check rather than once per request.
+//!
+//! The pool fronts a single server, a Sentinel-managed master or a Cluster.
+//! Sentinel pools ask the sentinels for the master on every new connection,
+//! so after a failover the check drops connections still open to the
+//! demoted master; Cluster connections follow slot moves on their own.
+//! `run` retries a command that a failover made fail on a fresh connection.
+
+use std::{fmt::Display, sync::Arc, time::Duration};
+
+use deadpool_redis::{
+    cluster,
+    sentinel::{self, SentinelServerType},
+    Config, Connection, Pool, PoolError, Runtime,
+};
+use parking_lot::{Mutex, RwLock};
+use redis::{aio::ConnectionLike, AsyncCommands, Cmd, ErrorKind, Pipeline, RedisError, RedisFuture, RedisResult, Value};
+use serde::Serialize;
+
+use crate::{metrics::METRICS, settings::{AppConfig, RedisTopology}};
+
+/// Upper bound for one health check, connection attempts included
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+/// Pause before the first failover retry; doubles for each one after
+const FAILOVER_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedisHealth {
+    pub topology: &'static str,
+    pub healthy: bool,
+    /// What the last passing check saw: the master's replicas, or the
+    /// cluster state
+    pub detail: Option<String>,
+    pub consecutive_failures: u32,
+    /// Times the pool was rebuilt since startup
+    pub pool_rebuilds: u64,
+    /// Request errors since the last health check
+    pub suppressed_errors: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    consecutive_failures: u32,
+    detail: Option<String>,
+    pool_rebuilds: u64,
+    suppressed_errors: u64,
+    last_error: Option<String>,
+}
+
+/// Where connections come from
+#[derive(Debug, Clone)]
+pub enum RedisEndpoints {
+    Standalone(String),
+    /// Sentinel URLs and the name of the master they monitor
+    Sentinel { urls: Vec<String>, master: String },
+    /// Seed nodes; the rest of the cluster is discovered from them
+    Cluster(Vec<String>),
+}
+
+impl RedisEndpoints {
+    /// None when no Redis URL is configured
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let mut urls = config.redis_urls();
+        if urls.is_empty() {
+            return None;
+        }
+
+        Some(match config.redis_topology {
+            RedisTopology::Standalone => RedisEndpoints::Standalone(urls.swap_remove(0)),
+            RedisTopology::Sentinel => RedisEndpoints::Sentinel {
+                urls,
+                master: config.redis_sentinel_master.clone().unwrap_or_default(),
+            },
+            RedisTopology::Cluster => RedisEndpoints::Cluster(urls),
+        })
+    }
+
+    pub fn topology(&self) -> RedisTopology {
+        match self {
+            RedisEndpoints::Standalone(_) => RedisTopology::Standalone,
+            RedisEndpoints::Sentinel { .. } => RedisTopology::Sentinel,
+            RedisEndpoints::Cluster(_) => RedisTopology::Cluster,
+        }
+    }
+
+    fn create_pool(&self) -> Result<RedisPool, String> {
+        let runtime = Some(Runtime::Tokio1);
+        match self {
+            RedisEndpoints::Standalone(url) => {
+                Config::from_url(url.as_str()).create_pool(runtime).map(RedisPool::Standalone).map_err(|e| e.to_string())
+            }
+            RedisEndpoints::Sentinel { urls, master } => {
+                sentinel::Config::from_urls(urls.clone(), master.clone(), SentinelServerType::Master)
+                    .create_pool(runtime)
+                    .map(RedisPool::Sentinel)
+                    .map_err(|e| e.to_string())
+            }
+            RedisEndpoints::Cluster(urls) => {
+                cluster::Config::from_urls(urls.clone()).create_pool(runtime).map(RedisPool::Cluster).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// deadpool has a pool type per topology
+#[derive(Clone)]
+enum RedisPool {
+    Standalone(Pool),
+    Sentinel(sentinel::Pool),
+    Cluster(cluster::Pool),
+}
+
+impl RedisPool {
+    async fn get(&self) -> Result<RedisConnection, PoolError> {
+        match self {
+            RedisPool::Standalone(pool) => pool.get().await.map(RedisConnection::Standalone),
+            RedisPool::Sentinel(pool) => pool.get().await.map(RedisConnection::Sentinel),
+            RedisPool::Cluster(pool) => pool.get().await.map(RedisConnection::Cluster),
+        }
+    }
+
+    fn available(&self) -> usize {
+        match self {
+            RedisPool::Standalone(pool) => pool.status().available,
+            RedisPool::Sentinel(pool) => pool.status().available,
+            RedisPool::Cluster(pool) => pool.status().available,
+        }
+    }
+
+    /// Closes every idle connection; new ones are made on demand
+    fn evict_idle(&self) {
+        match self {
+            RedisPool::Standalone(pool) => drop(pool.retain(|_, _| false)),
+            RedisPool::Sentinel(pool) => drop(pool.retain(|_, _| false)),
+            RedisPool::Cluster(pool) => drop(pool.retain(|_, _| false)),
+        }
+    }
+
+    fn close(&self) {
+        match self {
+            RedisPool::Standalone(pool) => pool.close(),
+            RedisPool::Sentinel(pool) => pool.close(),
+            RedisPool::Cluster(pool) => pool.close(),
+        }
+    }
+}
+
+/// A pooled connection to whichever topology is configured
+pub enum RedisConnection {
+    Standalone(Connection),
+    Sentinel(sentinel::Connection),
+    Cluster(cluster::Connection),
+}
+
+impl RedisConnection {
+    fn topology(&self) -> RedisTopology {
+        match self {
+            RedisConnection::Standalone(_) => RedisTopology::Standalone,
+            RedisConnection::Sentinel(_) => RedisTopology::Sentinel,
+            RedisConnection::Cluster(_) => RedisTopology::Cluster,
+        }
+    }
+
+    /// Takes the connection out of the pool so it is closed rather than
+    /// handed out again
+    fn detach(self) {
+        match self {
+            RedisConnection::Standalone(conn) => drop(Connection::take(conn)),
+            RedisConnection::Sentinel(conn) => drop(sentinel::Connection::take(conn)),
+            RedisConnection::Cluster(conn) => drop(cluster::Connection::take(conn)),
+        }
+    }
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            RedisConnection::Standalone(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Sentinel(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(&'a mut self, cmd: &'a Pipeline, offset: usize, count: usize) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            RedisConnection::Standalone(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Sentinel(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            RedisConnection::Standalone(conn) => conn.get_db(),
+            RedisConnection::Sentinel(conn) => conn.get_db(),
+            RedisConnection::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
+/// Errors another connection may not hit: the socket broke, the node was
+/// demoted to a replica, or the cluster is moving slots
+pub fn is_failover_error(e: &RedisError) -> bool {
+    e.is_io_error()
+        || e.is_connection_dropped()
+        || e.is_connection_refusal()
+        || matches!(
+            e.kind(),
+            ErrorKind::ReadOnly | ErrorKind::TryAgain | ErrorKind::ClusterDown | ErrorKind::MasterDown | ErrorKind::BusyLoadingError
+        )
+}
+
+/// Why `SupervisedPool::run` gave up
+#[derive(Debug)]
+pub enum RedisRunError {
+    /// No connection could be checked out
+    Connection(PoolError),
+    /// The command failed, after any failover retries
+    Command(RedisError),
+}
+
+impl RedisRunError {
+    fn is_failover(&self) -> bool {
+        match self {
+            RedisRunError::Connection(PoolError::Backend(e)) | RedisRunError::Command(e) => is_failover_error(e),
+            RedisRunError::Connection(_) => false,
+        }
+    }
+}
+
+impl Display for RedisRunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RedisRunError::Connection(e) => write!(f, "{}", e),
+            RedisRunError::Command(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+struct Inner {
+    endpoints: RedisEndpoints,
+    pool: RwLock<RedisPool>,
+    rebuild_after: u32,
+    failover_retries: u32,
+    state: Mutex<HealthState>,
+}
+
+/// Cheap to clone; every clone sees the same (swappable) pool
+#[derive(Clone)]
+pub struct SupervisedPool {
+    inner: Arc<Inner>,
+}
+
+impl SupervisedPool {
+    /// `rebuild_after` is the number of failed checks in a row that
+    /// triggers a pool rebuild; `failover_retries` bounds the retries of `run`
+    pub fn new(endpoints: RedisEndpoints, rebuild_after: u32, failover_retries: u32) -> Result<Self, String> {
+        let pool = endpoints.create_pool()?;
+
+        Ok(SupervisedPool {
+            inner: Arc::new(Inner {
+                endpoints,
+                pool: RwLock::new(pool),
+                rebuild_after: rebuild_after.max(1),
+                failover_retries,
+                state: Mutex::new(HealthState::default()),
+            }),
+        })
+    }
+
+    /// Redis is optional; a pool that cannot be built is logged and skipped
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let endpoints = RedisEndpoints::from_config(config)?;
+
+        Self::new(endpoints, config.redis_rebuild_after_failures, config.redis_failover_retries)
+            .map_err(|e| tracing::error!("Redis pool creation error: {}", e))
+            .ok()
+    }
+
+    pub fn topology(&self) -> RedisTopology {
+        self.inner.endpoints.topology()
+    }
+
+    pub async fn get(&self) -> Result<RedisConnection, PoolError> {
+        let pool = self.inner.pool.read().clone();
+        pool.get().await.inspect_err(|e| self.note_failure(e))
+    }
+
+    /// Runs `op` on a pooled connection. When a failover explains the
+    /// failure, the idle connections are dropped, since after a Sentinel
+    /// failover all of them still point at the demoted master, and `op` runs
+    /// again on a fresh one, up to `failover_retries` times. No retries while
+    /// the supervisor considers Redis unreachable, so an outage does not slow
+    /// every request down.
+    pub async fn run<F, Fut, T>(&self, op: F) -> Result<T, RedisRunError>
+    where
+        F: Fn(RedisConnection) -> Fut,
+        Fut: Future<Output = RedisResult<T>>,
+    {
+        let mut retries = 0;
+        loop {
+            let error = match self.get().await {
+                Ok(conn) => match op(conn).await {
+                    Ok(value) => return Ok(value),
+                    Err(e) => {
+                        self.note_failure(&e);
+                        RedisRunError::Command(e)
+                    }
+                },
+                Err(e) => RedisRunError::Connection(e),
+            };
+
+            let unreachable = self.inner.state.lock().consecutive_failures > 0;
+            if retries >= self.inner.failover_retries || unreachable || !error.is_failover() {
+                return Err(error);
+            }
+
+            METRICS.incr("redis_failover_retries_total");
+            let pool = self.inner.pool.read().clone();
+            pool.evict_idle();
+            tokio::time::sleep(FAILOVER_BACKOFF * 2u32.pow(retries)).await;
+            retries += 1;
+        }
+    }
+
+    /// Records a request-path Redis error. It is logged at debug level only;
+    /// the next health check reports all of them in one line.
+    pub fn note_failure(&self, error: &dyn Display) {
+        METRICS.incr("redis_request_errors_total");
+        tracing::debug!("Redis request failed: {}", error);
+
+        let mut state = self.inner.state.lock();
+        state.suppressed_errors += 1;
+        state.last_error = Some(error.to_string());
+    }
+
+    pub fn health(&self) -> RedisHealth {
+        let state = self.inner.state.lock();
+        RedisHealth {
+            topology: self.topology().as_str(),
+            healthy: state.consecutive_failures == 0,
+            detail: state.detail.clone(),
+            consecutive_failures: state.consecutive_failures,
+            pool_rebuilds: state.pool_rebuilds,
+            suppressed_errors: state.suppressed_errors,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    /// Validates the idle connections and updates the health state.
+    ///
+    /// An outage is logged once when the first check fails and once when
+    /// Redis answers again; every `rebuild_after` failed checks in a row the
+    /// pool is replaced so no connection from before the outage survives.
+    pub async fn check(&self) -> RedisHealth {
+        let pool = self.inner.pool.read().clone();
+        let result = tokio::time::timeout(CHECK_TIMEOUT, validate_idle(&pool))
+            .await
+            .unwrap_or_else(|_| Err("health check timed out".to_string()));
+
+        match result {
+            Ok((dropped, detail)) => self.check_passed(dropped, detail),
+            Err(error) => self.check_failed(error),
+        }
+
+        self.health()
+    }
+
+    fn check_passed(&self, dropped: usize, detail: String) {
+        METRICS.add("redis_connections_dropped_total", dropped as u64);
+
+        let mut state = self.inner.state.lock();
+        if state.consecutive_failures > 0 {
+            METRICS.incr("redis_reconnects_total");
+            tracing::info!(
+                failed_checks = state.consecutive_failures,
+                suppressed_errors = state.suppressed_errors,
+                "Redis is reachable again"
+            );
+        } else if state.suppressed_errors > 0 {
+            tracing::warn!(
+                errors = state.suppressed_errors,
+                last_error = state.last_error.as_deref().unwrap_or_default(),
+                "Redis request errors since the last health check"
+            );
+        }
+
+        state.consecutive_failures = 0;
+        state.suppressed_errors = 0;
+        state.detail = Some(detail);
+    }
+
+    fn check_failed(&self, error: String) {
+        METRICS.incr("redis_health_check_failures_total");
+
+        let mut state = self.inner.state.lock();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures == 1 {
+            tracing::warn!(
+                error = %error,
+                "Redis is unreachable; request errors are suppressed until it recovers"
+            );
+        }
+        state.last_error = Some(error);
+
+        if state.consecutive_failures.is_multiple_of(self.inner.rebuild_after) {
+            drop(state);
+            self.rebuild();
+        }
+    }
+
+    fn rebuild(&self) {
+        let pool = match self.inner.endpoints.create_pool() {
+            Ok(pool) => pool,
+            Err(e) => {
+                tracing::debug!("Redis pool rebuild failed: {}", e);
+                return;
+            }
+        };
+
+        let old = std::mem::replace(&mut *self.inner.pool.write(), pool);
+        // Connections still checked out by requests are discarded when returned
+        old.close();
+
+        METRICS.incr("redis_pool_rebuilds_total");
+        let mut state = self.inner.state.lock();
+        state.pool_rebuilds += 1;
+        tracing::debug!(rebuilds = state.pool_rebuilds, "Redis pool rebuilt");
+    }
+}
+
+/// Probes every idle connection, detaching the ones that fail so they are
+/// not handed out again. Returns how many were dropped and what the last
+/// healthy connection saw, or an error when none passed.
+async fn validate_idle(pool: &RedisPool) -> Result<(usize, String), String> {
+    let idle = pool.available().max(1);
+
+    // Connections stay checked out until the end so each `get` returns a
+    // different one
+    let mut checked = Vec::with_capacity(idle);
+    let mut dropped = 0;
+    let mut detail = None;
+    let mut last_error = None;
+
+    for attempt in 0..=idle {
+        // One extra, fresh connection only when every idle one was stale,
+        // e.g. all still open to a master that has since failed over
+        if attempt == idle && !(checked.is_empty() && dropped > 0) {
+            break;
+        }
+
+        match pool.get().await {
+            Ok(mut conn) => match probe(&mut conn).await {
+                Ok(seen) => {
+                    detail = Some(seen);
+                    checked.push(conn);
+                }
+                Err(e) => {
+                    conn.detach();
+                    dropped += 1;
+                    last_error = Some(e);
+                }
+            },
+            Err(e) => {
+                last_error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+
+    match (detail, last_error) {
+        (Some(detail), _) => Ok((dropped, detail)),
+        (None, Some(error)) => Err(error),
+        (None, None) => Err("no connection answered PING".to_string()),
+    }
+}
+
+/// Checks a connection against its topology: a standalone server must
+/// answer PING, a Sentinel connection must still reach the master and a
+/// Cluster must report itself ok. Returns what it saw, or why the
+/// connection should be dropped.
+async fn probe(conn: &mut RedisConnection) -> Result<String, String> {
+    match conn.topology() {
+        RedisTopology::Standalone => conn.ping::<String>().await.map_err(|e| e.to_string()),
+        RedisTopology::Sentinel => {
+            let info: String = redis::cmd("INFO").arg("replication").query_async(conn).await.map_err(|e| e.to_string())?;
+            match info_field(&info, "role") {
+                Some("master") => Ok(format!(
+                    "master with {} replica(s)",
+                    info_field(&info, "connected_slaves").unwrap_or("0")
+                )),
+                role => Err(format!("connected to a {} rather than the master", role.unwrap_or("node"))),
+            }
+        }
+        RedisTopology::Cluster => {
+            let info: String = redis::cmd("CLUSTER").arg("INFO").query_async(conn).await.map_err(|e| e.to_string())?;
+            match info_field(&info, "cluster_state") {
+                Some("ok") => Ok(format!(
+                    "cluster_state:ok, {} known node(s)",
+                    info_field(&info, "cluster_known_nodes").unwrap_or("?")
+                )),
+                state => Err(format!("cluster_state:{}", state.unwrap_or("unknown"))),
+            }
+        }
+    }
+}
+
+/// Reads `name:value` from an INFO reply
+pub(crate) fn info_field<'a>(info: &'a str, name: &str) -> Option<&'a str> {
+    info.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+        .map(str::trim)
+}
\ No newline at end of file
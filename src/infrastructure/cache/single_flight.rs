@@ -0,0 +1,68 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use parking_lot::Mutex;
+use tokio::sync::OnceCell;
+
+use crate::metrics::METRICS;
+
+/// Lets concurrent callers asking for the same key share one computation:
+/// the first caller runs it, everyone arriving before it finishes awaits
+/// that result instead of running their own. Nothing is kept afterwards,
+/// so this only collapses requests that overlap in time.
+///
+/// If the caller running the computation goes away (e.g. the client
+/// disconnected), one of the waiting callers takes over.
+#[derive(Clone)]
+pub struct SingleFlight<T> {
+    calls: Arc<Mutex<HashMap<String, Arc<OnceCell<T>>>>>,
+}
+
+impl<T> Default for SingleFlight<T> {
+    fn default() -> Self {
+        SingleFlight { calls: Arc::default() }
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Result of `compute` for `key`, shared with any concurrent call for
+    /// the same key. Errors are shared too; the next call after they are
+    /// handed out computes again.
+    pub async fn run<F, Fut>(&self, key: &str, compute: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let call = Arc::clone(self.calls.lock().entry(key.to_string()).or_default());
+
+        let mut computed = false;
+        let value = call
+            .get_or_init(|| {
+                computed = true;
+                compute()
+            })
+            .await
+            .clone();
+
+        if computed {
+            METRICS.incr("cache_coalesced_computations_total");
+        } else {
+            METRICS.incr("cache_coalesced_requests_total");
+        }
+
+        let mut calls = self.calls.lock();
+        if calls.get(key).is_some_and(|current| Arc::ptr_eq(current, &call)) {
+            calls.remove(key);
+        }
+
+        value
+    }
+
+    /// Keys with a computation currently running
+    pub fn in_flight(&self) -> usize {
+        self.calls.lock().len()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::{
+    cache::redis_pool::{info_field, RedisConnection, SupervisedPool},
+    entities::usage::RedisUsage,
+    errors::AppError,
+    settings::RedisTopology,
+};
+
+/// Keys asked for per SCAN round trip
+const SCAN_COUNT: usize = 500;
+
+#[automock]
+#[async_trait]
+pub trait RedisUsageProbe: Send + Sync {
+    /// Keys matching `pattern` and the memory they take, stopping after
+    /// `max_keys` keys
+    async fn namespace_usage(&self, pattern: &str, max_keys: usize) -> Result<RedisUsage, AppError>;
+}
+
+/// Walks the namespace with SCAN and sums `MEMORY USAGE` of each key, a
+/// batch per pipeline. SCAN does not block the server, but may return a
+/// key twice while it is rehashing; the sum is an estimate either way.
+#[derive(Clone)]
+pub struct RedisNamespaceProbe {
+    pool: SupervisedPool,
+}
+
+impl RedisNamespaceProbe {
+    pub fn new(pool: SupervisedPool) -> Self {
+        RedisNamespaceProbe { pool }
+    }
+
+    async fn connection(&self) -> Result<RedisConnection, AppError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Redis unavailable: {}", e)))
+    }
+
+    fn redis_error(&self, e: redis::RedisError) -> AppError {
+        self.pool.note_failure(&e);
+        AppError::ServiceUnavailable(format!("Redis operation failed: {}", e))
+    }
+}
+
+#[async_trait]
+impl RedisUsageProbe for RedisNamespaceProbe {
+    async fn namespace_usage(&self, pattern: &str, max_keys: usize) -> Result<RedisUsage, AppError> {
+        // A cluster SCAN only covers the node it lands on
+        if self.pool.topology() == RedisTopology::Cluster {
+            return Err(AppError::ServiceUnavailable("Namespace usage is not measured on a Redis Cluster".to_string()));
+        }
+
+        let mut conn = self.connection().await?;
+        let (mut keys, mut bytes, mut cursor) = (0u64, 0u64, 0u64);
+
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(SCAN_COUNT)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| self.redis_error(e))?;
+
+            if !batch.is_empty() {
+                let mut pipe = redis::pipe();
+                for key in &batch {
+                    pipe.cmd("MEMORY").arg("USAGE").arg(key);
+                }
+                // Keys that expired since the SCAN come back as nil
+                let sizes: Vec<Option<u64>> = pipe.query_async(&mut conn).await.map_err(|e| self.redis_error(e))?;
+                keys += sizes.iter().flatten().count() as u64;
+                bytes += sizes.into_iter().flatten().sum::<u64>();
+            }
+
+            cursor = next;
+            if cursor == 0 || keys as usize >= max_keys {
+                break;
+            }
+        }
+
+        let info: String = redis::cmd("INFO")
+            .arg("memory")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.redis_error(e))?;
+
+        Ok(RedisUsage {
+            namespace: pattern.to_string(),
+            keys,
+            bytes,
+            used_memory: info_field(&info, "used_memory").and_then(|v| v.parse().ok()),
+            truncated: cursor != 0,
+        })
+    }
+}
+
+pub fn redis_usage_probe_from_pool(pool: Option<&SupervisedPool>) -> Option<Arc<dyn RedisUsageProbe>> {
+    pool.map(|pool| Arc::new(RedisNamespaceProbe::new(pool.clone())) as Arc<dyn RedisUsageProbe>)
+}
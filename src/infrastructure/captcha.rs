@@ -0,0 +1 @@
+pub mod verifier;
\ No newline at end of file
@@ -0,0 +1,100 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use mockall::automock;
+use zeroize::Zeroizing;
+
+use crate::{errors::AppError, settings::AppConfig};
+
+const CLOUDFLARE_API: &str = "https://api.cloudflare.com/client/v4";
+/// Cloudflare accepts at most this many URLs per purge request
+const CLOUDFLARE_PURGE_BATCH: usize = 30;
+
+#[automock]
+#[async_trait]
+pub trait CdnPurger: Send + Sync {
+    /// Evicts the given absolute URLs from the edge cache
+    async fn purge_urls(&self, urls: Vec<String>) -> Result<(), AppError>;
+}
+
+/// Purges single files through Cloudflare's zone `purge_cache` API
+#[derive(Clone)]
+pub struct CloudflarePurger {
+    client: reqwest::Client,
+    zone_id: String,
+    api_token: Zeroizing<String>,
+}
+
+impl CloudflarePurger {
+    pub fn new(zone_id: String, api_token: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        CloudflarePurger {
+            client,
+            zone_id,
+            api_token: Zeroizing::new(api_token),
+        }
+    }
+
+    /// Asks Cloudflare whether the API token is valid and active
+    pub async fn verify_token(&self) -> Result<(), AppError> {
+        let response = self.client
+            .get(format!("{}/user/tokens/verify", CLOUDFLARE_API))
+            .bearer_auth(self.api_token.as_str())
+            .send()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Cloudflare unreachable: {}", e)))?;
+
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let active = body["success"].as_bool() == Some(true) && body["result"]["status"] == "active";
+
+        if !active {
+            return Err(AppError::ServiceUnavailable(format!(
+                "Cloudflare API token is not active (status {})",
+                status
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CdnPurger for CloudflarePurger {
+    async fn purge_urls(&self, urls: Vec<String>) -> Result<(), AppError> {
+        let endpoint = format!("{}/zones/{}/purge_cache", CLOUDFLARE_API, self.zone_id);
+
+        for batch in urls.chunks(CLOUDFLARE_PURGE_BATCH) {
+            let response = self.client
+                .post(&endpoint)
+                .bearer_auth(self.api_token.as_str())
+                .json(&serde_json::json!({ "files": batch }))
+                .send()
+                .await
+                .map_err(|e| AppError::ServiceUnavailable(format!("Cloudflare unreachable: {}", e)))?;
+
+            if !response.status().is_success() {
+                return Err(AppError::ServiceUnavailable(format!(
+                    "Cloudflare purge failed with status {}",
+                    response.status()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `None` unless both the Cloudflare zone id and API token are configured
+pub fn cdn_purger_from_config(config: &AppConfig) -> Option<Arc<dyn CdnPurger>> {
+    match (&config.cloudflare_zone_id, &config.cloudflare_api_token) {
+        (Some(zone_id), Some(token)) => {
+            Some(Arc::new(CloudflarePurger::new(zone_id.clone(), token.clone())) as Arc<dyn CdnPurger>)
+        }
+        _ => None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+pub mod keys;
+pub mod field_cipher;
\ No newline at end of file
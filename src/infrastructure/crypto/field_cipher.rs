@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+
+use crate::{
+    crypto::keys::{KeyProvider, StaticKeyProvider},
+    errors::AppError,
+};
+
+/// Marks a sealed value: `enc:v1:<key id>:<base64url of nonce || ciphertext>`
+const SEALED_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+
+/// Seals single text fields with AES-256-GCM before they are stored.
+///
+/// Sealed values keep their column and name their key, so rows sealed
+/// before a rotation still open while the old key is served. Values without
+/// the prefix, written before encryption was turned on, open as they are.
+/// `context` is bound into each value as associated data, so a sealed
+/// field copied onto another row or column no longer opens.
+#[derive(Clone)]
+pub struct FieldCipher {
+    provider: Arc<dyn KeyProvider>,
+}
+
+impl FieldCipher {
+    pub fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        FieldCipher { provider }
+    }
+
+    /// Stores everything in plaintext
+    pub fn disabled() -> Self {
+        FieldCipher::new(Arc::new(StaticKeyProvider::empty()))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.provider.primary().is_some()
+    }
+
+    /// Seals `plaintext` with the primary key; passed through when there is none
+    pub fn seal(&self, plaintext: &str, context: &str) -> Result<String, AppError> {
+        let Some(key) = self.provider.primary() else {
+            return Ok(plaintext.to_string());
+        };
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.material.as_slice()));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: plaintext.as_bytes(), aad: context.as_bytes() })
+            .map_err(|_| AppError::InternalError("Sealing a field failed".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}:{}", SEALED_PREFIX, key.id, BASE64_URL_SAFE_NO_PAD.encode(sealed)))
+    }
+
+    /// Opens a stored value with whichever key sealed it
+    pub fn open(&self, stored: &str, context: &str) -> Result<String, AppError> {
+        let Some((key_id, encoded)) = parse_sealed(stored) else {
+            return Ok(stored.to_string());
+        };
+
+        let key = self.provider.key(key_id).ok_or_else(|| {
+            AppError::InternalError(format!("Field sealed with unknown key '{}'", key_id))
+        })?;
+        let sealed = BASE64_URL_SAFE_NO_PAD
+            .decode(encoded)
+            .ok()
+            .filter(|sealed| sealed.len() > NONCE_LEN)
+            .ok_or_else(|| AppError::InternalError("Malformed sealed field".to_string()))?;
+
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.material.as_slice()));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: context.as_bytes() })
+            .map_err(|_| AppError::InternalError(format!("Field did not open with key '{}'", key_id)))?;
+
+        String::from_utf8(plaintext).map_err(|_| AppError::InternalError("Sealed field is not UTF-8".to_string()))
+    }
+
+    /// Whether `stored` is sealed with the primary key, or needs no sealing
+    /// because there is none
+    pub fn is_current(&self, stored: &str) -> bool {
+        match self.provider.primary() {
+            Some(primary) => parse_sealed(stored).is_some_and(|(key_id, _)| key_id == primary.id),
+            None => parse_sealed(stored).is_none(),
+        }
+    }
+}
+
+/// Key id and payload of a sealed value
+fn parse_sealed(stored: &str) -> Option<(&str, &str)> {
+    stored.strip_prefix(SEALED_PREFIX)?.split_once(':')
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use zeroize::Zeroizing;
+
+use crate::settings::AppConfig;
+
+/// A 256-bit data key and the id stored next to everything it sealed
+pub struct DataKey {
+    pub id: String,
+    pub material: Zeroizing<[u8; 32]>,
+}
+
+/// Where the keys sealing fields at rest come from. The primary key seals
+/// new values and every key still served opens old ones, so a retired key
+/// stays listed until rows sealed with it have been re-sealed.
+///
+/// Lookups are synchronous: a KMS-backed provider unwraps its data keys
+/// once at startup and serves them from memory, like `StaticKeyProvider`.
+pub trait KeyProvider: Send + Sync {
+    /// Key new values are sealed with; None leaves them in plaintext
+    fn primary(&self) -> Option<&DataKey>;
+    fn key(&self, id: &str) -> Option<&DataKey>;
+}
+
+/// Keys held in memory, e.g. from `APP_CONTACT_ENCRYPTION_KEYS`
+pub struct StaticKeyProvider {
+    keys: Vec<DataKey>,
+    primary: Option<usize>,
+}
+
+impl StaticKeyProvider {
+    /// `primary_id` must be one of `keys` to seal anything
+    pub fn new(keys: Vec<DataKey>, primary_id: Option<&str>) -> Self {
+        let primary = primary_id.and_then(|id| keys.iter().position(|key| key.id == id));
+        StaticKeyProvider { keys, primary }
+    }
+
+    pub fn empty() -> Self {
+        StaticKeyProvider { keys: Vec::new(), primary: None }
+    }
+}
+
+impl KeyProvider for StaticKeyProvider {
+    fn primary(&self) -> Option<&DataKey> {
+        self.primary.map(|index| &self.keys[index])
+    }
+
+    fn key(&self, id: &str) -> Option<&DataKey> {
+        self.keys.iter().find(|key| key.id == id)
+    }
+}
+
+/// Contact message keys from config; without any, messages are stored as sent
+pub fn contact_key_provider_from_config(config: &AppConfig) -> Arc<dyn KeyProvider> {
+    // Malformed keys are refused when the config is loaded
+    let keys = config.contact_encryption_keys().unwrap_or_default();
+    let primary = config.contact_encryption_key_id();
+
+    let keys = keys.into_iter().map(|(id, material)| DataKey { id, material }).collect();
+    Arc::new(StaticKeyProvider::new(keys, primary.as_deref()))
+}
\ No newline at end of file
@@ -1 +1,3 @@
-pub mod postgres;
\ No newline at end of file
+pub mod postgres;
+pub mod migrations;
+pub mod backfills;
\ No newline at end of file
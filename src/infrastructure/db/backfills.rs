@@ -0,0 +1,24 @@
+use crate::entities::backfill::BackfillTask;
+
+/// Fills `blog_post_search` for posts written before it existed; the
+/// table's trigger covers every write since
+const POST_SEARCH_DOCUMENTS: BackfillTask = BackfillTask {
+    name: "post_search_documents",
+    description: "Search vectors and reading times of existing blog posts",
+    table: "blog_posts",
+    pending: "NOT EXISTS (SELECT 1 FROM blog_post_search s WHERE s.post_id = t.id)",
+    fill: r#"
+        INSERT INTO blog_post_search (post_id, tenant_id, search_vector, reading_time_minutes)
+        SELECT id, tenant_id, post_search_vector(title, excerpt, content_markdown), post_reading_minutes(content_markdown)
+        FROM blog_posts
+        WHERE id = ANY($1)
+        ON CONFLICT (post_id) DO NOTHING
+    "#,
+    batch_size: 500,
+};
+
+/// The backfills this build ships. Add new ones at the end, with the
+/// migration that creates their columns.
+pub fn bundled_backfills() -> Vec<BackfillTask> {
+    vec![POST_SEARCH_DOCUMENTS]
+}
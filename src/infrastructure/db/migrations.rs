@@ -0,0 +1,39 @@
+use sqlx::{
+    migrate::{MigrateError, Migrator},
+    PgPool,
+};
+
+use crate::entities::schema::BundledMigration;
+
+/// Migrations compiled into the binary from `migrations/`
+static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// The up migrations this build ships, oldest first
+pub fn bundled_migrations() -> Vec<BundledMigration> {
+    MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| BundledMigration {
+            version: m.version,
+            description: m.description.to_string(),
+            checksum: m.checksum.to_vec(),
+        })
+        .collect()
+}
+
+/// Applies every pending migration. sqlx holds a Postgres advisory lock
+/// while it runs, so replicas that race here still apply each migration
+/// once. With `allow_unknown`, migrations applied by a newer release are
+/// left alone instead of failing the run.
+///
+/// Runs on a connection of its own with no `statement_timeout`: neither a
+/// slow migration nor a replica queued on the lock may be cancelled. The
+/// connection is closed afterwards rather than returned to the pool.
+pub async fn run_migrations(pool: &PgPool, allow_unknown: bool) -> Result<(), MigrateError> {
+    let mut migrator = sqlx::migrate!();
+    migrator.set_ignore_missing(allow_unknown);
+
+    let mut conn = pool.acquire().await?.detach();
+    sqlx::query("SET statement_timeout = 0").execute(&mut conn).await?;
+    migrator.run(&mut conn).await
+}
\ No newline at end of file
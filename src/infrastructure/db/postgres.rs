@@ -1,16 +1,57 @@
-use sqlx::postgres::{PgPool, PgPoolOptions};
+use futures::future::BoxFuture;
+use sqlx::postgres::{PgConnectOptions, PgConnection, PgPool, PgPoolOptions};
 use tracing::info;
-use std::time::Duration;
+use std::{future::Future, str::FromStr, time::Duration};
 
-pub async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+tokio::task_local! {
+    static WITHOUT_STATEMENT_TIMEOUT: ();
+}
+
+/// Runs `work` with no `statement_timeout` on the connections it checks out.
+/// For background jobs and one-off commands, whose queries may legitimately
+/// outlast a request's budget.
+pub async fn without_statement_timeout<F: Future>(work: F) -> F::Output {
+    WITHOUT_STATEMENT_TIMEOUT.scope((), work).await
+}
+
+/// Sets the session's `statement_timeout` for whoever is checking it out.
+/// Doubles as the liveness check sqlx would otherwise ping for.
+fn apply_statement_timeout(conn: &mut PgConnection, statement_timeout: Duration) -> BoxFuture<'_, Result<(), sqlx::Error>> {
+    let millis = match WITHOUT_STATEMENT_TIMEOUT.try_with(|_| ()) {
+        Ok(()) => 0,
+        Err(_) => statement_timeout.as_millis(),
+    };
+
+    Box::pin(async move {
+        sqlx::query("SELECT set_config('statement_timeout', $1, false)")
+            .bind(millis.to_string())
+            .execute(conn)
+            .await
+            .map(|_| ())
+    })
+}
+
+/// Connections checked out by a request get `statement_timeout`, so a query
+/// abandoned by a timed out request is cancelled server-side instead of
+/// running to completion. Work wrapped in [`without_statement_timeout`] runs
+/// unbounded on the same pool.
+pub async fn create_pool(database_url: &str, statement_timeout: Duration) -> Result<PgPool, sqlx::Error> {
     let max_retries = 5;
     let mut retry_count = 0;
     let mut wait_seconds = 2;
 
+    let connect_options = PgConnectOptions::from_str(database_url)?;
+
     loop {
         match PgPoolOptions::new()
             .max_connections(20)
-            .connect(database_url)
+            .test_before_acquire(false)
+            .after_connect(move |conn, _| apply_statement_timeout(conn, statement_timeout))
+            .before_acquire(move |conn, _| {
+                let applied = apply_statement_timeout(conn, statement_timeout);
+                Box::pin(async move { applied.await.map(|_| true) })
+            })
+            .connect_with(connect_options.clone())
             .await
         {
             Ok(pool) => {
@@ -30,4 +71,15 @@ pub async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
             Err(e) => return Err(e),
         }
     }
+}
+
+/// Single-connection pool for diagnostics: fails fast instead of retrying,
+/// and connects on first use so an unreachable server is reported, not fatal.
+pub fn create_probe_pool(database_url: &str, acquire_timeout: Duration) -> Result<PgPool, sqlx::Error> {
+    let connect_options = PgConnectOptions::from_str(database_url)?;
+
+    Ok(PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(acquire_timeout)
+        .connect_lazy_with(connect_options))
 }
\ No newline at end of file
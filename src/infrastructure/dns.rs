@@ -0,0 +1 @@
+pub mod txt;
\ No newline at end of file
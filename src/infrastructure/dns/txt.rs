@@ -0,0 +1,113 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use mockall::automock;
+use serde::Deserialize;
+
+use crate::{errors::AppError, settings::AppConfig};
+
+/// DNS response code for a name that does not exist
+const NXDOMAIN: u32 = 3;
+const TXT_RECORD_TYPE: u16 = 16;
+
+#[automock]
+#[async_trait]
+pub trait TxtResolver: Send + Sync {
+    /// TXT strings published at `name`; empty when the name does not exist
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, AppError>;
+}
+
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    data: String,
+}
+
+/// Looks up TXT records through a DNS-over-HTTPS resolver's JSON API
+/// (Cloudflare and Google both serve `application/dns-json`).
+#[derive(Clone)]
+pub struct DohTxtResolver {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl DohTxtResolver {
+    pub fn new(endpoint: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap_or_default();
+
+        DohTxtResolver { client, endpoint }
+    }
+}
+
+#[async_trait]
+impl TxtResolver for DohTxtResolver {
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, AppError> {
+        let response = self.client
+            .get(&self.endpoint)
+            .query(&[("name", name), ("type", "TXT")])
+            .header(reqwest::header::ACCEPT, "application/dns-json")
+            .send()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("DNS resolver unreachable: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ServiceUnavailable(format!(
+                "DNS resolver answered with status {}",
+                response.status()
+            )));
+        }
+
+        let body: DohResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Malformed DNS resolver response: {}", e)))?;
+
+        match body.status {
+            0 => Ok(body.answer
+                .iter()
+                .filter(|a| a.record_type == TXT_RECORD_TYPE)
+                .map(|a| unquote_txt(&a.data))
+                .collect()),
+            NXDOMAIN => Ok(Vec::new()),
+            code => Err(AppError::ServiceUnavailable(format!("DNS lookup failed with rcode {}", code))),
+        }
+    }
+}
+
+/// Joins the quoted character-strings of a TXT record (`"abc" "def"` -> `abcdef`)
+fn unquote_txt(data: &str) -> String {
+    if !data.contains('"') {
+        return data.to_string();
+    }
+
+    let mut value = String::with_capacity(data.len());
+    let mut in_quotes = false;
+    let mut chars = data.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '\\' if in_quotes => value.extend(chars.next()),
+            _ if in_quotes => value.push(c),
+            _ => {}
+        }
+    }
+
+    value
+}
+
+pub fn txt_resolver_from_config(config: &AppConfig) -> Arc<dyn TxtResolver> {
+    Arc::new(DohTxtResolver::new(config.dns_over_https_url.clone()))
+}
@@ -0,0 +1,112 @@
+use std::{net::IpAddr, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use maxminddb::{geoip2, MaxMindDBError, Reader};
+use parking_lot::RwLock;
+
+use crate::{
+    entities::geo::{GeoDatabaseInfo, GeoLocation},
+    errors::AppError,
+    settings::AppConfig,
+};
+
+/// An in-memory GeoLite2 City database
+type GeoDatabase = Reader<Vec<u8>>;
+
+/// Country/region lookups against a MaxMind GeoLite2 City database.
+///
+/// The database is read into memory once and swapped as a whole by
+/// `reload`, so lookups never see a half-written file. Without
+/// `geoip_db_path`, or while the file cannot be read, every lookup
+/// returns `None`.
+#[derive(Clone, Default)]
+pub struct GeoLocator {
+    path: Option<String>,
+    reader: Arc<RwLock<Option<Arc<GeoDatabase>>>>,
+}
+
+impl GeoLocator {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let locator = GeoLocator {
+            path: config.geoip_db_path.clone(),
+            reader: Arc::default(),
+        };
+
+        if let Some(path) = &locator.path {
+            match Reader::open_readfile(path) {
+                Ok(reader) => *locator.reader.write() = Some(Arc::new(reader)),
+                Err(e) => tracing::warn!(path = %path, "GeoIP database not loaded: {}", e),
+            }
+        }
+
+        locator
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Details of the loaded database, `None` when nothing is loaded
+    pub fn info(&self) -> Option<GeoDatabaseInfo> {
+        let reader = self.reader.read().clone()?;
+        Some(database_info(self.path.as_deref().unwrap_or_default(), &reader))
+    }
+
+    /// Locates `ip`; `None` when lookups are off, the address is not a
+    /// valid IP or the database has no entry for it (private ranges)
+    pub fn lookup(&self, ip: &str) -> Option<GeoLocation> {
+        let reader = self.reader.read().clone()?;
+        let ip: IpAddr = ip.trim().parse().ok()?;
+
+        let city: geoip2::City = match reader.lookup(ip) {
+            Ok(city) => city,
+            Err(MaxMindDBError::AddressNotFoundError(_)) => return None,
+            Err(e) => {
+                tracing::debug!(ip = %ip, "GeoIP lookup failed: {}", e);
+                return None;
+            }
+        };
+
+        let location = GeoLocation {
+            country_code: city.country.and_then(|c| c.iso_code).map(str::to_string),
+            region: city.subdivisions
+                .and_then(|s| s.into_iter().next())
+                .and_then(|s| s.iso_code)
+                .map(str::to_string),
+        };
+
+        (location != GeoLocation::default()).then_some(location)
+    }
+
+    /// Re-reads the database file, e.g. after a new GeoLite2 release was
+    /// dropped in place. The old database stays in use if the new one
+    /// cannot be read.
+    pub async fn reload(&self) -> Result<GeoDatabaseInfo, AppError> {
+        let Some(path) = self.path.clone() else {
+            return Err(AppError::ServiceUnavailable("GeoIP lookups are not configured".to_string()));
+        };
+
+        let reader = tokio::task::spawn_blocking({
+            let path = path.clone();
+            move || Reader::open_readfile(path)
+        })
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .map_err(|e| AppError::InternalError(format!("GeoIP database could not be read: {}", e)))?;
+
+        let info = database_info(&path, &reader);
+        *self.reader.write() = Some(Arc::new(reader));
+
+        Ok(info)
+    }
+}
+
+fn database_info(path: &str, reader: &GeoDatabase) -> GeoDatabaseInfo {
+    GeoDatabaseInfo {
+        path: path.to_string(),
+        database_type: reader.metadata.database_type.clone(),
+        built_at: i64::try_from(reader.metadata.build_epoch)
+            .ok()
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0)),
+    }
+}
\ No newline at end of file
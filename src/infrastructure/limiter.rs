@@ -1 +1,4 @@
-pub mod rate_limiter;
\ No newline at end of file
+pub mod rate_limiter;
+pub mod load_shedder;
+pub mod ip_ban;
+pub mod headers;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use actix_web::{
+    http::{
+        header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
+        StatusCode,
+    },
+    HttpResponse,
+};
+use serde::Serialize;
+
+/// Header fields of the IETF RateLimit header fields draft
+pub const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+pub const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+pub const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Where a client stands against one limit, as sent in the `RateLimit-*` headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Requests the limit allows
+    pub limit: u64,
+    /// Requests left before it refuses
+    pub remaining: u64,
+    /// Seconds until `remaining` is back to `limit`; on a refusal, until
+    /// the next request is accepted
+    pub reset_secs: u64,
+}
+
+impl RateLimitStatus {
+    /// Nothing left for `reset_secs` seconds
+    pub fn exhausted(limit: u64, reset_secs: u64) -> Self {
+        RateLimitStatus { limit, remaining: 0, reset_secs: reset_secs.max(1) }
+    }
+
+    /// A counter allowing `limit` hits per window, after `count` hits, with
+    /// the window ending in `reset_secs`
+    pub fn fixed_window(limit: u32, count: u32, reset_secs: u64) -> Self {
+        RateLimitStatus {
+            limit: limit as u64,
+            remaining: limit.saturating_sub(count) as u64,
+            reset_secs,
+        }
+    }
+
+    /// Adds the `RateLimit-*` headers, e.g. to a response that got through
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        for (name, value) in [
+            (RATELIMIT_LIMIT, self.limit),
+            (RATELIMIT_REMAINING, self.remaining),
+            (RATELIMIT_RESET, self.reset_secs),
+        ] {
+            headers.insert(name, HeaderValue::from(value));
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RateLimitedBody<'a> {
+    code: u16,
+    error: &'static str,
+    message: &'a str,
+    /// Which limit refused, e.g. `route:auth` or `contact:email`
+    scope: &'a str,
+    limit: u64,
+    remaining: u64,
+    reset: u64,
+    retry_after: u64,
+}
+
+/// The 429 of every limiting layer: `RateLimit-*` and `Retry-After`
+/// headers, and a JSON body naming the limit that refused
+pub fn too_many_requests(scope: &str, status: RateLimitStatus, message: &str) -> HttpResponse {
+    let retry_after = status.reset_secs.max(1);
+    let mut response = HttpResponse::build(StatusCode::TOO_MANY_REQUESTS)
+        .insert_header((RETRY_AFTER, retry_after))
+        .json(RateLimitedBody {
+            code: StatusCode::TOO_MANY_REQUESTS.as_u16(),
+            error: "Too many requests",
+            message,
+            scope,
+            limit: status.limit,
+            remaining: 0,
+            reset: retry_after,
+            retry_after,
+        });
+    RateLimitStatus { remaining: 0, reset_secs: retry_after, ..status }.apply(response.headers_mut());
+    response
+}
@@ -0,0 +1,55 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use dashmap::DashMap;
+
+/// Temporary per-IP bans, enforced for every route by `IpBanMiddleware`.
+/// Bans are held in process memory and expire lazily on lookup. A zero
+/// duration disables banning entirely.
+#[derive(Clone, Debug)]
+pub struct IpBanList {
+    bans: Arc<DashMap<String, Instant>>,
+    duration: Duration,
+}
+
+impl IpBanList {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            bans: Arc::new(DashMap::new()),
+            duration,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.duration.is_zero()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Bans `ip` for the configured duration, extending any current ban.
+    /// Returns false when banning is disabled.
+    pub fn ban(&self, ip: &str) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        self.bans.insert(ip.to_string(), Instant::now() + self.duration);
+        true
+    }
+
+    /// Time left on the ban for `ip`, if any
+    pub fn remaining(&self, ip: &str) -> Option<Duration> {
+        let until = *self.bans.get(ip)?;
+        let remaining = until.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            self.bans.remove_if(ip, |_, until| *until <= Instant::now());
+            return None;
+        }
+
+        Some(remaining)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Per-scope concurrency budgets. Each route scope gets its own semaphore so a
+/// burst on one scope (e.g. `/blog`) cannot starve the others (e.g. `/auth`).
+#[derive(Clone, Debug)]
+pub struct LoadShedder {
+    scopes: Arc<HashMap<String, Arc<Semaphore>>>,
+    queue_timeout: Duration,
+}
+
+impl LoadShedder {
+    pub fn new(limits: HashMap<String, usize>, queue_timeout: Duration) -> Self {
+        let scopes = limits
+            .into_iter()
+            .map(|(scope, limit)| (scope, Arc::new(Semaphore::new(limit.max(1)))))
+            .collect();
+
+        Self {
+            scopes: Arc::new(scopes),
+            queue_timeout,
+        }
+    }
+
+    /// Waits up to the queue timeout for a slot in `scope`.
+    /// `Ok(None)` means the scope has no budget configured and is not limited.
+    pub async fn acquire(&self, scope: &str) -> Result<Option<OwnedSemaphorePermit>, Saturated> {
+        let Some(semaphore) = self.scopes.get(scope) else {
+            return Ok(None);
+        };
+
+        match tokio::time::timeout(self.queue_timeout, Arc::clone(semaphore).acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(Some(permit)),
+            Ok(Err(_)) | Err(_) => Err(Saturated),
+        }
+    }
+
+    pub fn available(&self, scope: &str) -> Option<usize> {
+        self.scopes.get(scope).map(|s| s.available_permits())
+    }
+}
+
+/// The scope stayed at its concurrency limit for the whole queue timeout
+#[derive(Debug, Clone, Copy)]
+pub struct Saturated;
@@ -1,11 +1,26 @@
 use std::{
+    collections::HashMap,
     sync::Arc,
     time::{Duration, Instant},
 };
+use chrono::Utc;
 use dashmap::DashMap;
 use parking_lot::Mutex;
 use tokio::time::sleep;
 
+use crate::{
+    cache::rate_limit_state::{PersistedBucket, RateLimitStateStore},
+    entities::{public_api::PUBLIC_API_LIMIT_ROUTE, rate_limit::RateLimitEntry},
+    limiter::headers::RateLimitStatus,
+    metrics::METRICS,
+    settings::{AppConfig, RateLimitPersistence},
+};
+
+/// Buckets idle for this long are dropped by the eviction task
+const BUCKET_TTL: Duration = Duration::from_secs(10 * 60);
+/// Span of the sliding window that takes over once a bucket is empty
+const WINDOW_SIZE: Duration = Duration::from_secs(60);
+
 /// A token bucket which allows fractional tokens for precise refill
 #[derive(Debug)]
 pub struct TokenBucket {
@@ -52,6 +67,12 @@ impl TokenBucket {
     fn remaining(&self) -> f64 {
         self.tokens
     }
+
+    /// Tokens available right now, without consuming or refilling
+    fn peek(&self) -> f64 {
+        let elapsed = self.last_refill.elapsed().as_secs_f64();
+        (self.tokens + elapsed * self.refill_per_sec).min(self.capacity)
+    }
 }
 
 /// Sliding window
@@ -96,6 +117,29 @@ impl SlidingWindow {
             (false, effective)
         }
     }
+
+    /// (current, previous) window counts as of now, without rolling the window
+    fn counts(&self) -> (u64, u64) {
+        let elapsed = self.current_window_start.elapsed();
+        if elapsed >= self.window_size * 2 {
+            (0, 0)
+        } else if elapsed >= self.window_size {
+            (0, self.current_count)
+        } else {
+            (self.current_count, self.prev_count)
+        }
+    }
+
+    /// Requests the window would still let through right now
+    fn headroom(&self) -> u64 {
+        let elapsed = self.current_window_start.elapsed();
+        let into_window = if elapsed >= self.window_size { elapsed - self.window_size } else { elapsed };
+        let weight = (into_window.as_secs_f64() / self.window_size.as_secs_f64()).min(1.0);
+
+        let (current, previous) = self.counts();
+        let effective = (previous as f64) * (1.0 - weight) + (current as f64);
+        (self.limit as f64 - effective).max(0.0).floor() as u64
+    }
 }
 
 #[derive(Debug)]
@@ -142,6 +186,60 @@ impl RateHybridLimiter {
     fn remaining_limit(&self) -> u64 {
         self.per_second_limit
     }
+
+    /// The burst plus the window as the limit, and the time until the
+    /// bucket is full again as the reset. The window headroom makes
+    /// `remaining` an estimate.
+    fn status(&self) -> RateLimitStatus {
+        let tokens = self.bucket.peek();
+        let refill_secs = (self.bucket.capacity - tokens) / self.bucket.refill_per_sec;
+        RateLimitStatus {
+            limit: self.bucket.capacity as u64 + self.window.limit,
+            remaining: tokens.floor() as u64 + self.window.headroom(),
+            reset_secs: if refill_secs.is_finite() { refill_secs.ceil().max(0.0) as u64 } else { 0 },
+        }
+    }
+
+    /// Rebuilds a saved bucket, ageing it by the time it spent in Redis
+    fn restore(saved: &PersistedBucket, capacity: f64, refill_per_sec: f64, window_size: Duration, limit: u64) -> Self {
+        let now = Instant::now();
+        let downtime = Duration::from_millis(Utc::now().timestamp_millis().saturating_sub(saved.saved_at_ms).max(0) as u64);
+        let ago = |age_ms: u64| now.checked_sub(downtime + Duration::from_millis(age_ms)).unwrap_or(now);
+
+        let mut limiter = Self::new(capacity, refill_per_sec, window_size, limit);
+        limiter.bucket.tokens = saved.tokens.clamp(0.0, capacity);
+        limiter.bucket.last_refill = ago(0);
+        limiter.window.current_count = saved.window_count;
+        limiter.window.prev_count = saved.previous_window_count;
+        limiter.window.current_window_start = ago(saved.window_age_ms);
+        limiter.last_seen = ago(saved.idle_ms);
+        limiter
+    }
+
+    fn persist(&self, key: &str) -> PersistedBucket {
+        PersistedBucket {
+            key: key.to_string(),
+            tokens: self.bucket.tokens,
+            window_count: self.window.current_count,
+            previous_window_count: self.window.prev_count,
+            window_age_ms: self.window.current_window_start.elapsed().as_millis() as u64,
+            idle_ms: self.last_seen.elapsed().as_millis() as u64,
+            saved_at_ms: Utc::now().timestamp_millis(),
+        }
+    }
+
+    fn entry(&self, key: &str) -> RateLimitEntry {
+        let (window_count, previous_window_count) = self.window.counts();
+        RateLimitEntry {
+            key: key.to_string(),
+            remaining_tokens: self.bucket.peek(),
+            capacity: self.bucket.capacity,
+            window_count,
+            previous_window_count,
+            window_limit: self.window.limit,
+            idle_secs: self.last_seen.elapsed().as_secs(),
+        }
+    }
 }
 
 /// --- Rate limiter store & eviction ---
@@ -154,6 +252,11 @@ pub struct RateHybridLimiterStore {
     default_window_size: Duration,
     default_limit: u64,
     bucket_ttl: Duration,
+    /// Routes whose buckets are kept in Redis across restarts
+    persistence: Arc<HashMap<String, RateLimitPersistence>>,
+    /// Routes sized apart from the defaults, as `(burst, per_minute)`
+    route_sizes: Arc<HashMap<String, (u64, u64)>>,
+    state_store: Option<Arc<dyn RateLimitStateStore>>,
 }
 
 impl RateHybridLimiterStore {
@@ -171,6 +274,9 @@ impl RateHybridLimiterStore {
             default_window_size: window_size,
             default_limit: limit,
             bucket_ttl,
+            persistence: Arc::default(),
+            route_sizes: Arc::default(),
+            state_store: None,
         };
 
         // spawn eviction task
@@ -205,23 +311,142 @@ impl RateHybridLimiterStore {
         store
     }
 
-    fn get_bucket(&self, key: &str) -> Arc<Mutex<RateHybridLimiter>> {
-        if let Some(existing) = self.map.get(key) {
-            existing.clone()
-        } else {
-            let limiter = Arc::new(Mutex::new(RateHybridLimiter::new(
+    /// `APP_RATE_LIMIT_BURST` requests at once, then `APP_RATE_LIMIT_PER_MINUTE`.
+    /// The public API has its own `APP_PUBLIC_API_BURST` and
+    /// `APP_PUBLIC_API_PER_MINUTE`, so third parties and browsers never
+    /// share a budget. Routes in `APP_RATE_LIMIT_PERSISTENCE` are saved to `state_store`.
+    pub fn from_config(config: &AppConfig, state_store: Option<Arc<dyn RateLimitStateStore>>) -> Self {
+        let mut store = Self::new(
+            config.rate_limit_burst as f64,
+            config.rate_limit_per_minute as f64 / 60.0,
+            WINDOW_SIZE,
+            config.rate_limit_per_minute,
+            BUCKET_TTL,
+        );
+        store.persistence = Arc::new(config.rate_limit_persistence());
+        store.route_sizes = Arc::new(HashMap::from([(
+            PUBLIC_API_LIMIT_ROUTE.to_string(),
+            (config.public_api_burst, config.public_api_per_minute),
+        )]));
+        store.state_store = state_store;
+        store
+    }
+
+    fn persistence(&self, key: &str) -> RateLimitPersistence {
+        let route = key.split_once(':').map_or(key, |(route, _)| route);
+        self.persistence.get(route).copied().unwrap_or(RateLimitPersistence::Off)
+    }
+
+    /// Loads saved buckets of persisted routes, e.g. at startup; buckets
+    /// that would have been evicted by now are dropped from Redis instead.
+    /// Returns how many were restored.
+    pub async fn restore(&self) -> usize {
+        let Some(state_store) = &self.state_store else {
+            return 0;
+        };
+
+        let saved = match state_store.load().await {
+            Ok(saved) => saved,
+            Err(e) => {
+                tracing::warn!("Could not restore rate limit buckets: {}", e);
+                return 0;
+            }
+        };
+
+        let now_ms = Utc::now().timestamp_millis();
+        let ttl_ms = self.bucket_ttl.as_millis() as i64;
+        let (live, stale): (Vec<_>, Vec<_>) = saved.into_iter().partition(|bucket| {
+            self.persistence(&bucket.key) != RateLimitPersistence::Off
+                && now_ms - bucket.saved_at_ms + (bucket.idle_ms as i64) < ttl_ms
+        });
+
+        for bucket in &live {
+            let limiter = RateHybridLimiter::restore(
+                bucket,
                 self.default_capacity,
                 self.default_refill_per_sec,
                 self.default_window_size,
                 self.default_limit,
-            )));
-            match self.map.entry(key.to_string()) {
-                dashmap::mapref::entry::Entry::Occupied(entry) => entry.get().clone(),
-                dashmap::mapref::entry::Entry::Vacant(entry) => {
-                    entry.insert(limiter.clone());
-                    limiter
+            );
+            self.map.insert(bucket.key.clone(), Arc::new(Mutex::new(limiter)));
+        }
+
+        let stale: Vec<String> = stale.into_iter().map(|bucket| bucket.key).collect();
+        if let Err(e) = state_store.remove(&stale).await {
+            tracing::warn!("Could not drop stale rate limit buckets: {}", e);
+        }
+
+        live.len()
+    }
+
+    /// Saves the buckets of persisted routes, e.g. on shutdown.
+    /// Returns how many were saved.
+    pub async fn persist(&self) -> usize {
+        let Some(state_store) = &self.state_store else {
+            return 0;
+        };
+
+        let buckets: Vec<PersistedBucket> = self.map
+            .iter()
+            .filter(|entry| self.persistence(entry.key()) != RateLimitPersistence::Off)
+            .map(|entry| entry.value().lock().persist(entry.key()))
+            .collect();
+
+        match state_store.save(&buckets, self.bucket_ttl.as_secs()).await {
+            Ok(()) => buckets.len(),
+            Err(e) => {
+                tracing::warn!("Could not save rate limit buckets: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Saves one bucket in the background for write-through routes
+    fn write_through(&self, key: &str) {
+        let Some(state_store) = self.state_store.clone() else {
+            return;
+        };
+        let Some(bucket) = self.map.get(key).map(|entry| entry.value().lock().persist(key)) else {
+            return;
+        };
+
+        let ttl_secs = self.bucket_ttl.as_secs();
+        tokio::spawn(async move {
+            if let Err(e) = state_store.save(&[bucket], ttl_secs).await {
+                tracing::debug!("Rate limit write-through failed: {}", e);
+            }
+        });
+    }
+
+    fn get_bucket(&self, key: &str) -> Arc<Mutex<RateHybridLimiter>> {
+        self.get_sized_bucket(key, self.default_capacity, self.default_refill_per_sec, self.default_limit)
+    }
+
+    /// The key's bucket, replaced by a full one when its size changed
+    fn get_sized_bucket(&self, key: &str, capacity: f64, refill_per_sec: f64, limit: u64) -> Arc<Mutex<RateHybridLimiter>> {
+        let fits = |bucket: &Arc<Mutex<RateHybridLimiter>>| {
+            let bucket = bucket.lock();
+            bucket.bucket.capacity == capacity && bucket.window.limit == limit
+        };
+
+        if let Some(existing) = self.map.get(key).filter(|existing| fits(existing)) {
+            return existing.clone();
+        }
+
+        let fresh = || Arc::new(Mutex::new(RateHybridLimiter::new(
+            capacity,
+            refill_per_sec,
+            self.default_window_size,
+            limit,
+        )));
+        match self.map.entry(key.to_string()) {
+            dashmap::mapref::entry::Entry::Occupied(mut entry) => {
+                if !fits(entry.get()) {
+                    entry.insert(fresh());
                 }
+                entry.get().clone()
             }
+            dashmap::mapref::entry::Entry::Vacant(entry) => entry.insert(fresh()).clone(),
         }
     }
 
@@ -231,4 +456,75 @@ impl RateHybridLimiterStore {
         let (allowed, remaining, retry_after) = b.is_allowed();
         (allowed, remaining, retry_after, b.remaining_limit())
     }
+
+    /// Checks one request of `client` against `route`'s bucket and counts
+    /// the outcome per route. Either way returns where the client stands,
+    /// with the `Retry-After` seconds as the reset when rejected.
+    pub fn check(&self, route: &str, client: &str) -> Result<RateLimitStatus, RateLimitStatus> {
+        if let Some(&(burst, per_minute)) = self.route_sizes.get(route) {
+            return self.check_sized(route, client, burst, per_minute);
+        }
+
+        let key = format!("{}:{}", route, client);
+        let bucket = self.get_bucket(&key);
+        let (allowed, status) = Self::take(&bucket);
+        self.finish_check(route, &key, allowed, status)
+    }
+
+    /// `(burst, per_minute)` each client gets on `route`
+    pub fn route_size(&self, route: &str) -> (u64, u64) {
+        self.route_sizes
+            .get(route)
+            .copied()
+            .unwrap_or((self.default_capacity as u64, self.default_limit))
+    }
+
+    /// Like `check`, with the client's bucket sized by `burst` and
+    /// `per_minute` instead of the store defaults, e.g. per API token
+    pub fn check_sized(&self, route: &str, client: &str, burst: u64, per_minute: u64) -> Result<RateLimitStatus, RateLimitStatus> {
+        let key = format!("{}:{}", route, client);
+        let bucket = self.get_sized_bucket(&key, burst as f64, per_minute as f64 / 60.0, per_minute);
+        let (allowed, status) = Self::take(&bucket);
+        self.finish_check(route, &key, allowed, status)
+    }
+
+    fn take(bucket: &Mutex<RateHybridLimiter>) -> (bool, RateLimitStatus) {
+        let mut bucket = bucket.lock();
+        let (allowed, _, retry_after) = bucket.is_allowed();
+        let status = bucket.status();
+        if allowed {
+            (true, status)
+        } else {
+            (false, RateLimitStatus::exhausted(status.limit, retry_after.unwrap_or(1)))
+        }
+    }
+
+    fn finish_check(&self, route: &str, key: &str, allowed: bool, status: RateLimitStatus) -> Result<RateLimitStatus, RateLimitStatus> {
+        if self.persistence(key) == RateLimitPersistence::WriteThrough {
+            self.write_through(key);
+        }
+
+        if allowed {
+            METRICS.incr(&format!("rate_limit_allowed_total{{route=\"{route}\"}}"));
+            Ok(status)
+        } else {
+            METRICS.incr(&format!("rate_limit_rejected_total{{route=\"{route}\"}}"));
+            Err(status)
+        }
+    }
+
+    /// Every live bucket, sorted by key
+    pub fn snapshot(&self) -> Vec<RateLimitEntry> {
+        let mut entries: Vec<RateLimitEntry> = self.map
+            .iter()
+            .map(|entry| entry.value().lock().entry(entry.key()))
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        entries
+    }
+
+    /// Drops a client's bucket so its next request starts full; false if there was none
+    pub fn reset(&self, key: &str) -> bool {
+        self.map.remove(key).is_some()
+    }
 }
@@ -0,0 +1,3 @@
+pub mod probe;
+pub mod preview;
+pub mod shares;
\ No newline at end of file
@@ -0,0 +1,267 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use mockall::automock;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::{header, redirect};
+use url::{Host, Url};
+
+use crate::{
+    entities::bookmark::LinkPreview,
+    errors::AppError,
+    links::probe::{is_public_http_url, is_public_ip},
+    settings::AppConfig,
+};
+
+/// Redirect hops followed before the fetch gives up
+const MAX_REDIRECTS: usize = 5;
+/// Bytes of the page read at most; the tags we want live in `<head>`
+pub const MAX_PREVIEW_BYTES: usize = 512 * 1024;
+
+const MAX_TITLE_CHARS: usize = 300;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+const MAX_SITE_NAME_CHARS: usize = 100;
+const MAX_IMAGE_URL_LENGTH: usize = 2048;
+
+static TITLE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+static META_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<meta\s[^>]*>").unwrap());
+static ATTR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)([a-z][a-z0-9:_-]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap()
+});
+
+#[automock]
+#[async_trait]
+pub trait LinkPreviewFetcher: Send + Sync {
+    async fn fetch(&self, url: &str) -> Result<LinkPreview, AppError>;
+}
+
+/// Reads a page's title, description and Open Graph image.
+///
+/// Every hop is checked before it is requested: the host must resolve to
+/// public addresses only, and the connection is pinned to the addresses
+/// that were checked so a second DNS answer cannot point it elsewhere.
+/// Only HTML is read, and at most `MAX_PREVIEW_BYTES` of it.
+#[derive(Clone)]
+pub struct HttpLinkPreviewFetcher {
+    timeout: Duration,
+}
+
+impl HttpLinkPreviewFetcher {
+    pub fn new(timeout: Duration) -> Self {
+        HttpLinkPreviewFetcher { timeout }
+    }
+
+    /// A client that can only reach the addresses `url`'s host was checked against
+    async fn pinned_client(&self, url: &Url) -> Result<reqwest::Client, AppError> {
+        if !is_public_http_url(url) {
+            return Err(AppError::InvalidInput("Only public http(s) addresses can be previewed".to_string()));
+        }
+
+        let mut builder = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .redirect(redirect::Policy::none())
+            .no_proxy()
+            .user_agent(concat!("portfolio-link-preview/", env!("CARGO_PKG_VERSION")));
+
+        if let Some(Host::Domain(domain)) = url.host() {
+            let port = url.port_or_known_default().unwrap_or(443);
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((domain, port))
+                .await
+                .map_err(|_| AppError::ServiceUnavailable("Host could not be resolved".to_string()))?
+                .collect();
+
+            if addrs.is_empty() {
+                return Err(AppError::ServiceUnavailable("Host could not be resolved".to_string()));
+            }
+            if addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+                return Err(AppError::InvalidInput("Host resolves to a private address".to_string()));
+            }
+
+            builder = builder.resolve_to_addrs(domain, &addrs);
+        }
+
+        builder
+            .build()
+            .map_err(|e| AppError::InternalError(format!("Failed to build preview client: {}", e)))
+    }
+}
+
+#[async_trait]
+impl LinkPreviewFetcher for HttpLinkPreviewFetcher {
+    async fn fetch(&self, url: &str) -> Result<LinkPreview, AppError> {
+        let mut url = Url::parse(url).map_err(|_| AppError::InvalidInput("Invalid URL".to_string()))?;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let client = self.pinned_client(&url).await?;
+            let mut response = client
+                .get(url.clone())
+                .header(header::ACCEPT, "text/html,application/xhtml+xml")
+                .send()
+                .await
+                .map_err(|e| AppError::ServiceUnavailable(describe_error(&e)))?;
+
+            if response.status().is_redirection() {
+                let location = response.headers()
+                    .get(header::LOCATION)
+                    .and_then(|l| l.to_str().ok())
+                    .ok_or_else(|| AppError::ServiceUnavailable("Redirect without a location".to_string()))?;
+                url = url.join(location).map_err(|_| AppError::ServiceUnavailable("Invalid redirect".to_string()))?;
+                continue;
+            }
+
+            if !response.status().is_success() {
+                return Err(AppError::ServiceUnavailable(format!("Page answered with status {}", response.status().as_u16())));
+            }
+
+            let is_html = response.headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|c| c.to_str().ok())
+                .is_some_and(|c| {
+                    let c = c.to_ascii_lowercase();
+                    c.starts_with("text/html") || c.starts_with("application/xhtml+xml")
+                });
+            if !is_html {
+                return Err(AppError::InvalidInput("Not an HTML page".to_string()));
+            }
+
+            let mut body = Vec::new();
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| AppError::ServiceUnavailable(describe_error(&e)))?
+            {
+                body.extend_from_slice(&chunk);
+                if body.len() >= MAX_PREVIEW_BYTES || contains_head_end(&body) {
+                    break;
+                }
+            }
+            body.truncate(MAX_PREVIEW_BYTES);
+
+            return Ok(parse_link_preview(&String::from_utf8_lossy(&body), &url));
+        }
+
+        Err(AppError::ServiceUnavailable("Too many redirects".to_string()))
+    }
+}
+
+/// Short, stable reasons; reqwest's own messages embed the full URL
+fn describe_error(e: &reqwest::Error) -> String {
+    if e.is_timeout() {
+        "Timed out".to_string()
+    } else if e.is_connect() {
+        "Connection failed".to_string()
+    } else {
+        "Request failed".to_string()
+    }
+}
+
+fn contains_head_end(body: &[u8]) -> bool {
+    body.windows(7).any(|w| w.eq_ignore_ascii_case(b"</head>"))
+}
+
+/// Open Graph tags first, then Twitter cards, then plain `<title>` and
+/// `description`. Relative image URLs are resolved against `base`; images
+/// on private addresses are dropped.
+pub fn parse_link_preview(html: &str, base: &Url) -> LinkPreview {
+    let mut meta: Vec<(String, String)> = Vec::new();
+    for tag in META_RE.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for attr in ATTR_RE.captures_iter(tag.as_str()) {
+            let value = attr.get(2).or(attr.get(3)).or(attr.get(4)).map_or("", |m| m.as_str());
+            match attr[1].to_ascii_lowercase().as_str() {
+                "property" | "name" => key = Some(value.to_ascii_lowercase()),
+                "content" => content = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        if let (Some(key), Some(content)) = (key, content) {
+            meta.push((key, content));
+        }
+    }
+
+    let find = |keys: &[&str], max_chars: usize| {
+        keys.iter().find_map(|key| {
+            meta.iter()
+                .find(|(k, _)| k == key)
+                .and_then(|(_, v)| clean_text(v, max_chars))
+        })
+    };
+
+    let title = find(&["og:title", "twitter:title"], MAX_TITLE_CHARS).or_else(|| {
+        TITLE_RE.captures(html).and_then(|c| clean_text(&c[1], MAX_TITLE_CHARS))
+    });
+    let image_url = ["og:image:secure_url", "og:image", "og:image:url", "twitter:image"]
+        .iter()
+        .find_map(|key| meta.iter().find(|(k, _)| k == key))
+        .and_then(|(_, v)| base.join(decode_entities(v.trim()).as_str()).ok())
+        .filter(is_public_http_url)
+        .map(String::from)
+        .filter(|u| u.len() <= MAX_IMAGE_URL_LENGTH);
+
+    LinkPreview {
+        title,
+        description: find(&["og:description", "twitter:description", "description"], MAX_DESCRIPTION_CHARS),
+        image_url,
+        site_name: find(&["og:site_name"], MAX_SITE_NAME_CHARS),
+    }
+}
+
+/// Decodes entities, collapses whitespace and cuts to `max_chars`
+fn clean_text(raw: &str, max_chars: usize) -> Option<String> {
+    let text = decode_entities(raw).split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => Some(format!("{}…", text[..cut].trim_end())),
+        None => Some(text),
+    }
+}
+
+/// The named entities pages commonly use in titles, plus numeric references
+fn decode_entities(raw: &str) -> String {
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..].find(';').filter(|&end| end <= 10).map(|end| &rest[1..=end]);
+        let replacement = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        });
+
+        match (entity, replacement) {
+            (Some(entity), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+pub fn link_preview_fetcher_from_config(config: &AppConfig) -> Arc<dyn LinkPreviewFetcher> {
+    Arc::new(HttpLinkPreviewFetcher::new(Duration::from_secs(config.link_check_timeout_secs)))
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use std::{
+    net::{IpAddr, Ipv6Addr},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use mockall::automock;
+use reqwest::{redirect, StatusCode};
+use url::{Host, Url};
+
+use crate::settings::AppConfig;
+
+/// Redirect hops followed before a link counts as broken
+const MAX_REDIRECTS: usize = 5;
+
+/// Result of requesting one link. `status_code` is the final response's
+/// status; `error` is set when no response came back at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkProbe {
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl LinkProbe {
+    /// 429 only says the site is rate limiting us, not that the link is gone
+    pub fn is_broken(&self) -> bool {
+        match self.status_code {
+            Some(status) => status >= 400 && status != 429,
+            None => true,
+        }
+    }
+}
+
+#[automock]
+#[async_trait]
+pub trait LinkProber: Send + Sync {
+    async fn probe(&self, url: &str) -> LinkProbe;
+}
+
+/// Probes with `HEAD`, falling back to `GET` for servers that refuse it.
+/// Redirects into private networks are not followed.
+#[derive(Clone)]
+pub struct HttpLinkProber {
+    client: reqwest::Client,
+}
+
+impl HttpLinkProber {
+    pub fn new(timeout: Duration) -> Self {
+        let policy = redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if !is_public_http_url(attempt.url()) {
+                attempt.error("redirected to a private address")
+            } else {
+                attempt.follow()
+            }
+        });
+
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(policy)
+            .user_agent(concat!("portfolio-link-checker/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+
+        HttpLinkProber { client }
+    }
+
+    async fn request(&self, method: reqwest::Method, url: &str) -> Result<StatusCode, reqwest::Error> {
+        self.client.request(method, url).send().await.map(|response| response.status())
+    }
+}
+
+#[async_trait]
+impl LinkProber for HttpLinkProber {
+    async fn probe(&self, url: &str) -> LinkProbe {
+        let result = match self.request(reqwest::Method::HEAD, url).await {
+            Ok(StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED) => {
+                self.request(reqwest::Method::GET, url).await
+            }
+            other => other,
+        };
+
+        match result {
+            Ok(status) => LinkProbe { status_code: Some(status.as_u16()), error: None },
+            Err(e) => LinkProbe { status_code: None, error: Some(describe_error(&e)) },
+        }
+    }
+}
+
+/// Short, stable reasons; reqwest's own messages embed the full URL
+pub(crate) fn describe_error(e: &reqwest::Error) -> String {
+    if e.is_timeout() {
+        "Timed out".to_string()
+    } else if e.is_redirect() {
+        "Redirect not followed".to_string()
+    } else if e.is_connect() {
+        "Connection failed".to_string()
+    } else {
+        "Request failed".to_string()
+    }
+}
+
+/// An http(s) URL whose host is not a loopback, private or link-local
+/// address. Hostnames are not resolved, so this only stops the obvious cases.
+pub fn is_public_http_url(url: &Url) -> bool {
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+
+    match url.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost"
+                && !domain.ends_with(".localhost")
+                && !domain.ends_with(".local")
+                && !domain.ends_with(".internal")
+        }
+        Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        None => false,
+    }
+}
+
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() || ip.is_broadcast())
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ip(IpAddr::V4(mapped)),
+            None => !(ip.is_loopback() || ip.is_unspecified() || is_unique_local(&ip) || is_unicast_link_local(&ip)),
+        },
+    }
+}
+
+/// fc00::/7
+fn is_unique_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xfe00) == 0xfc00
+}
+
+/// fe80::/10
+fn is_unicast_link_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+pub fn link_prober_from_config(config: &AppConfig) -> Arc<dyn LinkProber> {
+    Arc::new(HttpLinkProber::new(Duration::from_secs(config.link_check_timeout_secs)))
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use mockall::automock;
+use reqwest::{header::RETRY_AFTER, StatusCode};
+use serde_json::Value;
+
+use crate::{
+    entities::share_count::{SHARE_NETWORK_FACEBOOK, SHARE_NETWORK_HACKERNEWS, SHARE_NETWORK_REDDIT},
+    links::probe::describe_error,
+    settings::AppConfig,
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const HACKERNEWS_SEARCH_URL: &str = "https://hn.algolia.com/api/v1/search";
+const REDDIT_INFO_URL: &str = "https://www.reddit.com/api/info.json";
+const FACEBOOK_GRAPH_URL: &str = "https://graph.facebook.com/v19.0/";
+/// Graph API error codes for app, user and page level throttling
+const FACEBOOK_THROTTLE_CODES: [i64; 4] = [4, 17, 32, 613];
+
+/// Why a network gave no count this time. Either way the previous count is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareFetchError {
+    /// The network asked us to slow down, for `retry_after` when it said
+    RateLimited { retry_after: Option<Duration> },
+    /// No usable answer: the network is down, refused the request, or changed its API
+    Unavailable(String),
+}
+
+#[automock]
+#[async_trait]
+pub trait ShareCountProvider: Send + Sync {
+    /// One of `SHARE_NETWORKS`
+    fn network(&self) -> &'static str;
+    async fn share_count(&self, url: &str) -> Result<u64, ShareFetchError>;
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("portfolio-share-counts/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Sends `request` and reads the JSON body, sorting failures into rate
+/// limiting and everything else
+async fn fetch_json(request: reqwest::RequestBuilder) -> Result<Value, ShareFetchError> {
+    let response = request.send().await.map_err(|e| ShareFetchError::Unavailable(describe_error(&e)))?;
+
+    let status = response.status();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        return Err(ShareFetchError::RateLimited { retry_after });
+    }
+
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|_| ShareFetchError::Unavailable(format!("Unreadable response ({})", status.as_u16())))?;
+    if !status.is_success() && body.get("error").is_none() {
+        return Err(ShareFetchError::Unavailable(format!("HTTP {}", status.as_u16())));
+    }
+
+    Ok(body)
+}
+
+fn missing_field(field: &str) -> ShareFetchError {
+    ShareFetchError::Unavailable(format!("Response has no {}", field))
+}
+
+/// Stories on Hacker News linking to the URL
+pub struct HackerNewsShares {
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl ShareCountProvider for HackerNewsShares {
+    fn network(&self) -> &'static str {
+        SHARE_NETWORK_HACKERNEWS
+    }
+
+    async fn share_count(&self, url: &str) -> Result<u64, ShareFetchError> {
+        let request = self.client.get(HACKERNEWS_SEARCH_URL).query(&[
+            ("query", url),
+            ("restrictSearchableAttributes", "url"),
+            ("tags", "story"),
+            ("hitsPerPage", "0"),
+        ]);
+        let body = fetch_json(request).await?;
+
+        body["nbHits"].as_u64().ok_or_else(|| missing_field("nbHits"))
+    }
+}
+
+/// Reddit posts linking to the URL, across all subreddits
+pub struct RedditShares {
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl ShareCountProvider for RedditShares {
+    fn network(&self) -> &'static str {
+        SHARE_NETWORK_REDDIT
+    }
+
+    async fn share_count(&self, url: &str) -> Result<u64, ShareFetchError> {
+        let request = self.client.get(REDDIT_INFO_URL).query(&[("url", url), ("limit", "100")]);
+        let body = fetch_json(request).await?;
+
+        body["data"]["children"]
+            .as_array()
+            .map(|children| children.len() as u64)
+            .ok_or_else(|| missing_field("data.children"))
+    }
+}
+
+/// Shares reported by the Graph API's URL engagement
+pub struct FacebookShares {
+    client: reqwest::Client,
+    access_token: String,
+}
+
+#[async_trait]
+impl ShareCountProvider for FacebookShares {
+    fn network(&self) -> &'static str {
+        SHARE_NETWORK_FACEBOOK
+    }
+
+    async fn share_count(&self, url: &str) -> Result<u64, ShareFetchError> {
+        let request = self.client.get(FACEBOOK_GRAPH_URL).query(&[
+            ("id", url),
+            ("fields", "engagement"),
+            ("access_token", &self.access_token),
+        ]);
+        let body = fetch_json(request).await?;
+
+        if let Some(error) = body.get("error") {
+            let code = error["code"].as_i64().unwrap_or_default();
+            if FACEBOOK_THROTTLE_CODES.contains(&code) {
+                return Err(ShareFetchError::RateLimited { retry_after: None });
+            }
+            return Err(ShareFetchError::Unavailable(format!("Graph API error {}", code)));
+        }
+
+        body["engagement"]["share_count"].as_u64().ok_or_else(|| missing_field("engagement.share_count"))
+    }
+}
+
+/// One provider per network in `share_count_networks`, in the order listed
+pub fn share_providers_from_config(config: &AppConfig) -> Vec<Arc<dyn ShareCountProvider>> {
+    let client = http_client();
+
+    config
+        .share_count_networks()
+        .into_iter()
+        .filter_map(|network| -> Option<Arc<dyn ShareCountProvider>> {
+            match network.as_str() {
+                SHARE_NETWORK_HACKERNEWS => Some(Arc::new(HackerNewsShares { client: client.clone() })),
+                SHARE_NETWORK_REDDIT => Some(Arc::new(RedditShares { client: client.clone() })),
+                SHARE_NETWORK_FACEBOOK => config.share_count_facebook_token.clone().map(|access_token| {
+                    Arc::new(FacebookShares { client: client.clone(), access_token }) as Arc<dyn ShareCountProvider>
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,2 @@
+pub mod email;
+pub mod templates;
\ No newline at end of file
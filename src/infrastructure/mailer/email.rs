@@ -0,0 +1,166 @@
+use std::{
+    collections::BTreeMap,
+    sync::{atomic::{AtomicU64, Ordering}, Arc},
+};
+
+use async_trait::async_trait;
+use mockall::automock;
+use serde::Serialize;
+
+use crate::{errors::AppError, metrics::METRICS, settings::AppConfig};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailMessage {
+    pub from: String,
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    /// Optional HTML alternative to `text`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    /// Extra headers for the relay to set, e.g. `Auto-Submitted`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+#[automock]
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError>;
+}
+
+/// Writes emails to the log instead of delivering them (development default)
+#[derive(Clone, Default)]
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        tracing::info!(
+            to = %message.to,
+            subject = %message.subject,
+            body = %message.text,
+            "Email (log mailer)"
+        );
+        Ok(())
+    }
+}
+
+/// Delivers emails by POSTing JSON to an HTTP mail relay (transactional email API)
+#[derive(Clone)]
+pub struct RelayMailer {
+    client: reqwest::Client,
+    endpoint: String,
+    token: Option<String>,
+}
+
+impl RelayMailer {
+    pub fn new(endpoint: String, token: Option<String>) -> Self {
+        RelayMailer {
+            client: reqwest::Client::new(),
+            endpoint,
+            token,
+        }
+    }
+
+    /// Checks that the relay answers and accepts the token, without sending anything
+    pub async fn probe(&self) -> Result<(), AppError> {
+        let mut request = self.client.head(&self.endpoint);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Mail relay unreachable: {}", e)))?;
+
+        let status = response.status();
+        if matches!(status, reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN) {
+            return Err(AppError::ServiceUnavailable(format!("Mail relay rejected the token with status {}", status)));
+        }
+        if status.is_server_error() {
+            return Err(AppError::ServiceUnavailable(format!("Mail relay answered with status {}", status)));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Mailer for RelayMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        let mut request = self.client.post(&self.endpoint).json(message);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Mail relay unreachable: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ServiceUnavailable(format!(
+                "Mail relay rejected message with status {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Emails sent and failed since the usage collector last took the counts
+#[derive(Clone, Default)]
+pub struct EmailSendCounter {
+    sent: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+}
+
+impl EmailSendCounter {
+    /// Returns `(sent, failed)` and starts counting from zero again
+    pub fn take(&self) -> (u64, u64) {
+        (self.sent.swap(0, Ordering::Relaxed), self.failed.swap(0, Ordering::Relaxed))
+    }
+
+    /// Puts counts back that could not be stored, to be taken with the next ones
+    pub fn restore(&self, sent: u64, failed: u64) {
+        self.sent.fetch_add(sent, Ordering::Relaxed);
+        self.failed.fetch_add(failed, Ordering::Relaxed);
+    }
+}
+
+/// Counts what goes through the wrapped mailer, for the usage report
+pub struct CountingMailer {
+    inner: Arc<dyn Mailer>,
+    counter: EmailSendCounter,
+}
+
+impl CountingMailer {
+    pub fn wrap(inner: Arc<dyn Mailer>, counter: EmailSendCounter) -> Arc<dyn Mailer> {
+        Arc::new(CountingMailer { inner, counter })
+    }
+}
+
+#[async_trait]
+impl Mailer for CountingMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        let result = self.inner.send(message).await;
+        let (counter, metric) = match result {
+            Ok(()) => (&self.counter.sent, "emails_sent_total"),
+            Err(_) => (&self.counter.failed, "emails_failed_total"),
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        METRICS.incr(metric);
+        result
+    }
+}
+
+/// Picks the relay when `mail_relay_url` is configured, otherwise logs emails
+pub fn mailer_from_config(config: &AppConfig) -> Arc<dyn Mailer> {
+    match &config.mail_relay_url {
+        Some(url) => Arc::new(RelayMailer::new(url.clone(), config.mail_relay_token.clone())),
+        None => Arc::new(LogMailer),
+    }
+}
@@ -0,0 +1,339 @@
+//! Transactional email templates.
+//!
+//! Every message renders an HTML part and a plaintext part from the askama
+//! templates under `templates/email/`, both wrapped in a shared layout.
+//! User-facing copy comes from a per-locale string catalog (`Strings`), so
+//! adding a language means adding a catalog, not touching the templates.
+
+use askama::Template;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entities::one_time_token::{EMAIL_VERIFICATION_PATH, PASSWORD_RESET_PATH},
+    errors::AppError,
+};
+
+// ───── Localization ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Fr];
+
+    /// Accepts a language tag such as `fr` or `fr-CA`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let lang = tag.split(['-', '_']).next().unwrap_or_default();
+        Self::ALL.into_iter().find(|locale| locale.code().eq_ignore_ascii_case(lang))
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Fr => FR,
+        }
+    }
+}
+
+/// Looks up template copy for one locale. Keys missing from a catalog fall
+/// back to English, and unknown keys render as the key itself.
+#[derive(Debug, Clone, Copy)]
+pub struct Strings(Locale);
+
+impl Strings {
+    pub fn new(locale: Locale) -> Self {
+        Strings(locale)
+    }
+
+    pub fn get<'a>(&self, key: &'a str) -> &'a str {
+        lookup(self.0.catalog(), key)
+            .or_else(|| lookup(EN, key))
+            .unwrap_or(key)
+    }
+}
+
+fn lookup(catalog: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+const EN: &[(&str, &str)] = &[
+    ("greeting", "Hi"),
+    ("footer.sent_by", "Sent by"),
+    ("action.fallback", "If the button does not work, copy this link into your browser:"),
+    ("link.expires_in", "This link expires in"),
+    ("unit.hours", "hour(s)"),
+    ("unit.minutes", "minute(s)"),
+    ("verification.subject", "Confirm your email address"),
+    ("verification.intro", "Please confirm your email address by following the link below."),
+    ("verification.action", "Confirm email"),
+    ("verification.ignore", "If you did not create an account, you can ignore this email."),
+    ("password_reset.subject", "Reset your password"),
+    ("password_reset.intro", "We received a request to reset your password."),
+    ("password_reset.action", "Reset password"),
+    ("password_reset.ignore", "If you did not request this, you can ignore this email and your password will stay the same."),
+    ("contact.subject_prefix", "New contact message from"),
+    ("contact.heading", "You have a new contact message"),
+    ("contact.from", "From"),
+    ("contact.subject", "Subject"),
+    ("contact.action", "View message"),
+    ("auto_reply.subject", "Thanks for getting in touch"),
+    ("auto_reply.intro", "Thanks for your message. This is an automatic reply to let you know it arrived."),
+    ("auto_reply.response_time", "I usually reply"),
+    ("auto_reply.your_message", "Your message"),
+    ("auto_reply.no_reply", "There is no need to answer this email; I will get back to you personally."),
+    ("newsletter.read_more", "Read more"),
+    ("newsletter.reason", "You are receiving this because you subscribed to updates from"),
+    ("newsletter.unsubscribe", "Unsubscribe"),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("greeting", "Bonjour"),
+    ("footer.sent_by", "Envoyé par"),
+    ("action.fallback", "Si le bouton ne fonctionne pas, copiez ce lien dans votre navigateur :"),
+    ("link.expires_in", "Ce lien expire dans"),
+    ("unit.hours", "heure(s)"),
+    ("unit.minutes", "minute(s)"),
+    ("verification.subject", "Confirmez votre adresse e-mail"),
+    ("verification.intro", "Veuillez confirmer votre adresse e-mail en suivant le lien ci-dessous."),
+    ("verification.action", "Confirmer l'adresse"),
+    ("verification.ignore", "Si vous n'avez pas créé de compte, vous pouvez ignorer cet e-mail."),
+    ("password_reset.subject", "Réinitialisez votre mot de passe"),
+    ("password_reset.intro", "Nous avons reçu une demande de réinitialisation de votre mot de passe."),
+    ("password_reset.action", "Réinitialiser le mot de passe"),
+    ("password_reset.ignore", "Si vous n'êtes pas à l'origine de cette demande, ignorez cet e-mail ; votre mot de passe ne changera pas."),
+    ("contact.subject_prefix", "Nouveau message de"),
+    ("contact.heading", "Vous avez reçu un nouveau message"),
+    ("contact.from", "De"),
+    ("contact.subject", "Objet"),
+    ("contact.action", "Voir le message"),
+    ("auto_reply.subject", "Merci pour votre message"),
+    ("auto_reply.intro", "Merci pour votre message. Cette réponse automatique vous confirme qu'il est bien arrivé."),
+    ("auto_reply.response_time", "Je réponds généralement"),
+    ("auto_reply.your_message", "Votre message"),
+    ("auto_reply.no_reply", "Inutile de répondre à cet e-mail ; je reviendrai vers vous personnellement."),
+    ("newsletter.read_more", "Lire la suite"),
+    ("newsletter.reason", "Vous recevez cet e-mail car vous êtes abonné(e) aux nouvelles de"),
+    ("newsletter.unsubscribe", "Se désabonner"),
+];
+
+// ───── Messages ──────────────────────────────────────────────────────
+
+/// Sender identity shown in the layout header and footer
+#[derive(Debug, Clone)]
+pub struct EmailBrand {
+    pub name: String,
+    pub site_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+pub trait EmailTemplate {
+    fn render(&self, brand: &EmailBrand, locale: Locale) -> Result<RenderedEmail, AppError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct VerificationEmail {
+    pub name: String,
+    pub verify_url: String,
+    pub expires_in_hours: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordResetEmail {
+    pub name: String,
+    pub reset_url: String,
+    pub expires_in_minutes: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContactNotificationEmail {
+    pub from_name: String,
+    pub from_email: String,
+    pub subject: Option<String>,
+    pub message: String,
+    pub view_url: String,
+}
+
+/// Acknowledgment to a contact form sender
+#[derive(Debug, Clone)]
+pub struct ContactAutoReplyEmail {
+    pub name: String,
+    pub response_time: String,
+    /// The admin's own text, replacing the default intro
+    pub message: Option<String>,
+    pub subject: Option<String>,
+    /// What the sender wrote, quoted back to them
+    pub original_subject: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewsletterEmail {
+    pub title: String,
+    pub intro: String,
+    pub posts: Vec<NewsletterItem>,
+    pub unsubscribe_url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewsletterItem {
+    pub title: String,
+    pub url: String,
+    pub excerpt: String,
+}
+
+// ───── Template Bindings ─────────────────────────────────────────────
+
+struct Layout<'a> {
+    lang: &'static str,
+    subject: &'a str,
+    brand: &'a str,
+    site_url: Option<&'a str>,
+}
+
+macro_rules! template_pair {
+    ($message:ty, $html:ident => $html_path:literal, $text:ident => $text_path:literal) => {
+        #[derive(Template)]
+        #[template(path = $html_path)]
+        struct $html<'a> {
+            layout: &'a Layout<'a>,
+            t: Strings,
+            m: &'a $message,
+        }
+
+        #[derive(Template)]
+        #[template(path = $text_path)]
+        struct $text<'a> {
+            layout: &'a Layout<'a>,
+            t: Strings,
+            m: &'a $message,
+        }
+    };
+}
+
+template_pair!(VerificationEmail, VerificationHtml => "email/verification.html", VerificationText => "email/verification.txt");
+template_pair!(PasswordResetEmail, PasswordResetHtml => "email/password_reset.html", PasswordResetText => "email/password_reset.txt");
+template_pair!(ContactNotificationEmail, ContactHtml => "email/contact_notification.html", ContactText => "email/contact_notification.txt");
+template_pair!(ContactAutoReplyEmail, AutoReplyHtml => "email/contact_auto_reply.html", AutoReplyText => "email/contact_auto_reply.txt");
+template_pair!(NewsletterEmail, NewsletterHtml => "email/newsletter.html", NewsletterText => "email/newsletter.txt");
+
+macro_rules! impl_email_template {
+    ($message:ty, $html:ident, $text:ident, |$m:ident, $t:ident| $subject:expr) => {
+        impl EmailTemplate for $message {
+            fn render(&self, brand: &EmailBrand, locale: Locale) -> Result<RenderedEmail, AppError> {
+                let ($m, $t) = (self, Strings::new(locale));
+                let subject: String = $subject;
+                let layout = Layout {
+                    lang: locale.code(),
+                    subject: &subject,
+                    brand: &brand.name,
+                    site_url: brand.site_url.as_deref(),
+                };
+
+                let html = $html { layout: &layout, t: $t, m: $m }.render().map_err(render_error)?;
+                let text = $text { layout: &layout, t: $t, m: $m }.render().map_err(render_error)?;
+
+                Ok(RenderedEmail { subject, text, html })
+            }
+        }
+    };
+}
+
+impl_email_template!(VerificationEmail, VerificationHtml, VerificationText, |_m, t| {
+    t.get("verification.subject").to_string()
+});
+impl_email_template!(PasswordResetEmail, PasswordResetHtml, PasswordResetText, |_m, t| {
+    t.get("password_reset.subject").to_string()
+});
+impl_email_template!(ContactNotificationEmail, ContactHtml, ContactText, |m, t| {
+    format!(
+        "{} {}{}",
+        t.get("contact.subject_prefix"),
+        m.from_name,
+        m.subject.as_deref().map(|s| format!(": {}", s)).unwrap_or_default()
+    )
+});
+impl_email_template!(ContactAutoReplyEmail, AutoReplyHtml, AutoReplyText, |m, t| {
+    m.subject.clone().unwrap_or_else(|| t.get("auto_reply.subject").to_string())
+});
+impl_email_template!(NewsletterEmail, NewsletterHtml, NewsletterText, |m, _t| m.title.clone());
+
+fn render_error(e: askama::Error) -> AppError {
+    AppError::InternalError(format!("Email template failed to render: {}", e))
+}
+
+// ───── Previews ──────────────────────────────────────────────────────
+
+/// Template names accepted by `render_sample`
+pub const TEMPLATE_NAMES: [&str; 5] = ["verification", "password_reset", "contact_notification", "contact_auto_reply", "newsletter"];
+
+/// Renders a template with placeholder data for the admin preview endpoint
+pub fn render_sample(name: &str, brand: &EmailBrand, locale: Locale) -> Result<RenderedEmail, AppError> {
+    let site = brand.site_url.clone().unwrap_or_else(|| "https://example.com".to_string());
+
+    match name {
+        "verification" => VerificationEmail {
+            name: "Ada".to_string(),
+            verify_url: format!("{}{}?token=sample", site, EMAIL_VERIFICATION_PATH),
+            expires_in_hours: 24,
+        }
+        .render(brand, locale),
+        "password_reset" => PasswordResetEmail {
+            name: "Ada".to_string(),
+            reset_url: format!("{}{}?token=sample", site, PASSWORD_RESET_PATH),
+            expires_in_minutes: 30,
+        }
+        .render(brand, locale),
+        "contact_notification" => ContactNotificationEmail {
+            from_name: "Ada Lovelace".to_string(),
+            from_email: "ada@example.com".to_string(),
+            subject: Some("Project inquiry".to_string()),
+            message: "Hello! I'd love to talk about a project.\nAre you available next month?".to_string(),
+            view_url: format!("{}/admin/contact-messages/sample", site),
+        }
+        .render(brand, locale),
+        "contact_auto_reply" => ContactAutoReplyEmail {
+            name: "Ada Lovelace".to_string(),
+            response_time: "within two business days".to_string(),
+            message: None,
+            subject: None,
+            original_subject: Some("Project inquiry".to_string()),
+        }
+        .render(brand, locale),
+        "newsletter" => NewsletterEmail {
+            title: "What's new this month".to_string(),
+            intro: "A few things I wrote recently.".to_string(),
+            posts: vec![
+                NewsletterItem {
+                    title: "Shipping a Rust API".to_string(),
+                    url: format!("{}/blog/shipping-a-rust-api", site),
+                    excerpt: "Notes from taking an actix-web service to production.".to_string(),
+                },
+                NewsletterItem {
+                    title: "Postgres tips".to_string(),
+                    url: format!("{}/blog/postgres-tips", site),
+                    excerpt: "Indexes, partial indexes and when to reach for them.".to_string(),
+                },
+            ],
+            unsubscribe_url: format!("{}/newsletter/unsubscribe?token=sample", site),
+        }
+        .render(brand, locale),
+        _ => Err(AppError::NotFound(format!("Unknown email template '{}'", name))),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Process-wide counters (and a few gauges), exposed to admins via `/api/v1/admin/metrics`
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    counters: DashMap<String, AtomicU64>,
+}
+
+impl Metrics {
+    /// Increments a counter, creating it on first use.
+    /// Labels are part of the key, e.g. `http_requests_shed_total{scope="blog"}`
+    pub fn incr(&self, key: &str) {
+        self.add(key, 1);
+    }
+
+    /// Adds `n` to a counter; a zero leaves a missing counter uncreated
+    pub fn add(&self, key: &str, n: u64) {
+        if n == 0 {
+            return;
+        }
+
+        if let Some(counter) = self.counters.get(key) {
+            counter.fetch_add(n, Ordering::Relaxed);
+            return;
+        }
+
+        self.counters
+            .entry(key.to_string())
+            .or_default()
+            .fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Overwrites a value that goes up and down, e.g. a queue depth
+    pub fn set(&self, key: &str, n: u64) {
+        if let Some(counter) = self.counters.get(key) {
+            counter.store(n, Ordering::Relaxed);
+            return;
+        }
+
+        self.counters
+            .entry(key.to_string())
+            .or_default()
+            .store(n, Ordering::Relaxed);
+    }
+
+    pub fn get(&self, key: &str) -> u64 {
+        self.counters
+            .get(key)
+            .map(|counter| counter.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Sorted copy of all counters
+    pub fn snapshot(&self) -> BTreeMap<String, u64> {
+        self.counters
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+            .collect()
+    }
+}
@@ -0,0 +1,2 @@
+pub mod reporter;
+pub mod sentry;
\ No newline at end of file
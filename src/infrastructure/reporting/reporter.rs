@@ -0,0 +1,115 @@
+use std::{fmt, panic, sync::Arc};
+
+use mockall::automock;
+use once_cell::sync::OnceCell;
+
+/// What went wrong, which decides the sample rate applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSource {
+    Panic,
+    Request,
+    Job,
+}
+
+impl ErrorSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorSource::Panic => "panic",
+            ErrorSource::Request => "request",
+            ErrorSource::Job => "job",
+        }
+    }
+}
+
+/// The request an error response was sent for. Only the route pattern is
+/// kept, never the query string, headers or body.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub method: String,
+    pub route: String,
+    pub request_id: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// One error as handed to a reporter, before sampling and scrubbing
+#[derive(Debug, Clone)]
+pub struct ErrorEvent {
+    pub source: ErrorSource,
+    /// Short type name, e.g. `InternalError` or `panic`
+    pub error_type: String,
+    pub message: String,
+    pub tenant: Option<String>,
+    pub request: Option<RequestContext>,
+    /// Background job name for `ErrorSource::Job`
+    pub job: Option<String>,
+    /// `file:line` of a panic
+    pub location: Option<String>,
+}
+
+impl ErrorEvent {
+    pub fn new(source: ErrorSource, error_type: impl Into<String>, message: impl Into<String>) -> Self {
+        ErrorEvent {
+            source,
+            error_type: error_type.into(),
+            message: message.into(),
+            tenant: None,
+            request: None,
+            job: None,
+            location: None,
+        }
+    }
+}
+
+/// Sends errors to an external service. `report` must not block: it runs
+/// inside request handlers and the panic hook.
+#[automock]
+pub trait ErrorReporter: Send + Sync {
+    fn report(&self, event: ErrorEvent);
+}
+
+static REPORTER: OnceCell<Arc<dyn ErrorReporter>> = OnceCell::new();
+
+/// Sets the process-wide reporter; later calls are ignored
+pub fn install_error_reporter(reporter: Arc<dyn ErrorReporter>) {
+    if REPORTER.set(reporter).is_err() {
+        tracing::warn!("Error reporter already installed");
+    }
+}
+
+/// Hands `event` to the installed reporter, if any
+pub fn report_error(event: ErrorEvent) {
+    if let Some(reporter) = REPORTER.get() {
+        reporter.report(event);
+    }
+}
+
+/// Reports a failed background job run. Cache refreshes that simply retry on
+/// the next tick are only logged.
+pub fn report_job_failure(job: &str, tenant: Option<&str>, error: &dyn fmt::Display) {
+    report_error(ErrorEvent {
+        job: Some(job.to_string()),
+        tenant: tenant.map(str::to_string),
+        ..ErrorEvent::new(ErrorSource::Job, "JobFailed", error.to_string())
+    });
+}
+
+/// Reports panics before the default hook prints them. Worker panics kill
+/// only the worker, so without this they leave nothing but a log line.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        report_error(ErrorEvent {
+            location: info.location().map(|l| format!("{}:{}", l.file(), l.line())),
+            ..ErrorEvent::new(ErrorSource::Panic, "panic", message)
+        });
+
+        default_hook(info);
+    }));
+}
\ No newline at end of file
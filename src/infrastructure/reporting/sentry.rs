@@ -0,0 +1,228 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use tokio::sync::{broadcast, mpsc};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    metrics::METRICS,
+    reporting::reporter::{ErrorEvent, ErrorReporter, ErrorSource},
+    settings::AppConfig,
+    utils::redact::Redactor,
+};
+
+/// Events waiting to be sent; more are dropped rather than queued
+const QUEUE_CAPACITY: usize = 100;
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+const SENTRY_CLIENT: &str = concat!("portfolio-backend/", env!("CARGO_PKG_VERSION"));
+
+/// `https://<public key>@<host>/<project id>` from the Sentry project settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentryDsn {
+    pub public_key: String,
+    /// Where events are posted: `<scheme>://<host>/api/<project id>/store/`
+    pub store_url: Url,
+}
+
+impl FromStr for SentryDsn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dsn = Url::parse(s.trim()).map_err(|_| "ERROR_REPORTING_DSN is not a URL".to_string())?;
+        if !matches!(dsn.scheme(), "http" | "https") || dsn.host_str().is_none() {
+            return Err("ERROR_REPORTING_DSN must be an http(s) URL".to_string());
+        }
+        if dsn.username().is_empty() {
+            return Err("ERROR_REPORTING_DSN is missing the public key".to_string());
+        }
+
+        let path = dsn.path().trim_end_matches('/');
+        let (prefix, project_id) = path.rsplit_once('/').unwrap_or(("", path));
+        if project_id.is_empty() || !project_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err("ERROR_REPORTING_DSN is missing the project id".to_string());
+        }
+
+        let mut store_url = dsn.clone();
+        store_url.set_username("").ok();
+        store_url.set_password(None).ok();
+        store_url.set_query(None);
+        store_url.set_fragment(None);
+        store_url.set_path(&format!("{}/api/{}/store/", prefix, project_id));
+
+        Ok(SentryDsn { public_key: dsn.username().to_string(), store_url })
+    }
+}
+
+/// What is attached to every event and how events are sampled and scrubbed
+#[derive(Debug, Clone)]
+pub struct SentryOptions {
+    pub environment: String,
+    pub release: String,
+    /// Share of error responses sent
+    pub sample_rate: f64,
+    /// Share of failed background job runs sent; panics are always sent
+    pub job_sample_rate: f64,
+    pub redactor: Redactor,
+}
+
+impl SentryOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let settings = &config.body_logging;
+        // Patterns are checked by config validation, as for body logging
+        let redactor = Redactor::new(&settings.redact_fields, &settings.redact_patterns)
+            .unwrap_or_else(|_| Redactor::new(&settings.redact_fields, &[]).expect("no patterns to compile"));
+
+        SentryOptions {
+            environment: config.env.to_string(),
+            release: format!("portfolio_backend@{}", env!("CARGO_PKG_VERSION")),
+            sample_rate: config.error_reporting_sample_rate,
+            job_sample_rate: config.error_reporting_job_sample_rate,
+            redactor,
+        }
+    }
+
+    fn rate_for(&self, source: ErrorSource) -> f64 {
+        match source {
+            ErrorSource::Panic => 1.0,
+            ErrorSource::Request => self.sample_rate,
+            ErrorSource::Job => self.job_sample_rate,
+        }
+    }
+}
+
+/// Builds the Sentry event for `event`. Messages go through the same
+/// redaction as logged bodies, so emails, tokens and the configured
+/// patterns never leave the process.
+pub fn sentry_event(event: &ErrorEvent, options: &SentryOptions) -> Value {
+    let message = options.redactor.redact_text(&event.message);
+
+    let mut tags = Map::new();
+    tags.insert("source".to_string(), json!(event.source.as_str()));
+    if let Some(tenant) = &event.tenant {
+        tags.insert("tenant".to_string(), json!(tenant));
+    }
+    if let Some(job) = &event.job {
+        tags.insert("job".to_string(), json!(job));
+    }
+
+    let mut payload = json!({
+        "event_id": Uuid::new_v4().simple().to_string(),
+        "timestamp": Utc::now().to_rfc3339(),
+        "platform": "other",
+        "logger": "portfolio_backend",
+        "level": if event.source == ErrorSource::Panic { "fatal" } else { "error" },
+        "release": options.release,
+        "environment": options.environment,
+        "message": { "formatted": message },
+        "exception": { "values": [{ "type": event.error_type, "value": message }] },
+    });
+
+    if let Some(request) = &event.request {
+        let route = options.redactor.redact_text(&request.route);
+        tags.insert("method".to_string(), json!(request.method));
+        if let Some(request_id) = &request.request_id {
+            tags.insert("request_id".to_string(), json!(request_id));
+        }
+        payload["transaction"] = json!(format!("{} {}", request.method, route));
+        payload["request"] = json!({ "method": request.method, "url": route });
+        if let Some(user_id) = &request.user_id {
+            payload["user"] = json!({ "id": user_id });
+        }
+    } else if let Some(job) = &event.job {
+        payload["transaction"] = json!(job);
+    }
+    if let Some(location) = &event.location {
+        payload["extra"] = json!({ "location": location });
+    }
+    payload["tags"] = Value::Object(tags);
+
+    payload
+}
+
+/// Samples, scrubs and queues events for [`SentryTransport`]
+pub struct SentryReporter {
+    options: SentryOptions,
+    queue: mpsc::Sender<Value>,
+}
+
+impl ErrorReporter for SentryReporter {
+    fn report(&self, event: ErrorEvent) {
+        let rate = self.options.rate_for(event.source);
+        if rate < 1.0 && rand::random::<f64>() >= rate {
+            METRICS.incr("error_reports_sampled_out_total");
+            return;
+        }
+
+        if self.queue.try_send(sentry_event(&event, &self.options)).is_err() {
+            METRICS.incr("error_reports_dropped_total");
+        }
+    }
+}
+
+/// Posts queued events to Sentry; run by the error report task
+pub struct SentryTransport {
+    dsn: SentryDsn,
+    client: reqwest::Client,
+    queue: mpsc::Receiver<Value>,
+}
+
+impl SentryTransport {
+    /// Sends events until shutdown, then whatever is still queued
+    pub async fn run(mut self, mut shutdown_rx: broadcast::Receiver<()>) {
+        loop {
+            tokio::select! {
+                event = self.queue.recv() => match event {
+                    Some(event) => self.send(&event).await,
+                    None => return,
+                },
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+
+        while let Ok(event) = self.queue.try_recv() {
+            self.send(&event).await;
+        }
+    }
+
+    async fn send(&self, event: &Value) {
+        let auth = format!(
+            "Sentry sentry_version=7, sentry_client={}, sentry_key={}",
+            SENTRY_CLIENT, self.dsn.public_key
+        );
+
+        let result = self.client
+            .post(self.dsn.store_url.clone())
+            .header("X-Sentry-Auth", auth)
+            .json(event)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match result {
+            Ok(_) => METRICS.incr("error_reports_sent_total"),
+            Err(e) => {
+                METRICS.incr("error_reports_failed_total");
+                // Not `error!`: a reporting layer on top must not loop on its own failures
+                tracing::warn!("Error report not delivered: {}", e.without_url());
+            }
+        }
+    }
+}
+
+/// Reporter and its transport when `error_reporting_dsn` is set. The DSN is
+/// checked by config validation.
+pub fn error_reporter_from_config(config: &AppConfig) -> Option<(Arc<dyn ErrorReporter>, SentryTransport)> {
+    let dsn: SentryDsn = config.error_reporting_dsn.as_deref()?.parse().ok()?;
+    let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+
+    let client = reqwest::Client::builder()
+        .timeout(SEND_TIMEOUT)
+        .user_agent(SENTRY_CLIENT)
+        .build()
+        .ok()?;
+
+    let reporter = SentryReporter { options: SentryOptions::from_config(config), queue: sender };
+    Some((Arc::new(reporter), SentryTransport { dsn, client, queue: receiver }))
+}
\ No newline at end of file
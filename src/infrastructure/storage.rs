@@ -0,0 +1,4 @@
+pub mod object;
+pub mod links;
+pub mod local;
+pub mod s3;
\ No newline at end of file
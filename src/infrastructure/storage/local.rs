@@ -0,0 +1,150 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use tokio::{fs, io::AsyncReadExt};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    storage::{
+        links::StorageLinks,
+        object::{validate_key, ObjectBody, ObjectStorage, StoredObject},
+    },
+};
+
+/// Read size when streaming a file back
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Keeps objects as files under `root`, one file per key. Writes go to a
+/// temporary file that is renamed into place, so readers never see half an
+/// object. Presigned URLs point at our own download route.
+#[derive(Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    links: StorageLinks,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>, links: StorageLinks) -> Self {
+        LocalStorage { root: root.into(), links }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> AppError {
+    match e.kind() {
+        ErrorKind::NotFound => AppError::NotFound("Object not found".to_string()),
+        _ => AppError::InternalError(format!("Storage I/O error on {}: {}", path.display(), e)),
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, body: Bytes, _content_type: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await.map_err(|e| io_error(dir, e))?;
+        }
+
+        let tmp = path.with_file_name(format!(".{}.tmp", Uuid::new_v4()));
+        if let Err(e) = fs::write(&tmp, &body).await {
+            let _ = fs::remove_file(&tmp).await;
+            return Err(io_error(&tmp, e));
+        }
+
+        fs::rename(&tmp, &path).await.map_err(|e| io_error(&path, e))
+    }
+
+    async fn get_stream(&self, key: &str) -> Result<ObjectBody, AppError> {
+        let path = self.path(key)?;
+        let file = fs::File::open(&path).await.map_err(|e| io_error(&path, e))?;
+        let size = file.metadata().await.map_err(|e| io_error(&path, e))?.len();
+
+        let stream = futures::stream::unfold(Some((file, path)), |state| async move {
+            let (mut file, path) = state?;
+            let mut buf = BytesMut::with_capacity(CHUNK_SIZE);
+            match file.read_buf(&mut buf).await {
+                Ok(0) => None,
+                Ok(_) => Some((Ok(buf.freeze()), Some((file, path)))),
+                Err(e) => Some((Err(io_error(&path, e)), None)),
+            }
+        });
+
+        Ok(ObjectBody { size, stream: stream.boxed() })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        match fs::remove_file(&path).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error(&path, e)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        let path = self.path(key)?;
+        match fs::metadata(&path).await {
+            Ok(meta) => Ok(meta.is_file()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(io_error(&path, e)),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>, AppError> {
+        let start = match prefix {
+            "" => self.root.clone(),
+            prefix => self.path(prefix)?,
+        };
+
+        let mut objects = Vec::new();
+        let mut dirs = vec![start];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(io_error(&dir, e)),
+            };
+
+            while let Some(entry) = entries.next_entry().await.map_err(|e| io_error(&dir, e))? {
+                let path = entry.path();
+                let meta = entry.metadata().await.map_err(|e| io_error(&path, e))?;
+                if meta.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                // Half-written objects are hidden temporary files
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                if let Ok(relative) = path.strip_prefix(&self.root) {
+                    let key = relative.components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    objects.push(StoredObject { key, size: meta.len() });
+                }
+            }
+        }
+
+        Ok(objects)
+    }
+
+    async fn presign(&self, key: &str, ttl: Duration) -> Result<String, AppError> {
+        validate_key(key)?;
+        self.links.sign(key, ttl)
+    }
+
+    fn describe(&self) -> String {
+        format!("local filesystem at {}", self.root.display())
+    }
+}
\ No newline at end of file
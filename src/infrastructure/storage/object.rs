@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use mockall::automock;
+
+use crate::{
+    errors::AppError,
+    settings::{AppConfig, StorageBackend},
+    storage::{links::StorageLinks, local::LocalStorage, s3::S3Storage},
+};
+
+/// Longest key any backend accepts
+pub const MAX_KEY_LENGTH: usize = 512;
+
+/// Body of a stored object, read in chunks
+pub struct ObjectBody {
+    pub size: u64,
+    pub stream: BoxStream<'static, Result<Bytes, AppError>>,
+}
+
+/// Key and size of a stored object, as listed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredObject {
+    pub key: String,
+    pub size: u64,
+}
+
+/// Flat key/value blob store shared by everything that writes files.
+///
+/// Keys are relative, `/`-separated paths such as
+/// `exports/<tenant>/content-20250101.json`; see [`validate_key`].
+#[automock]
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Creates or replaces the object at `key`
+    async fn put(&self, key: &str, body: Bytes, content_type: &str) -> Result<(), AppError>;
+
+    /// `AppError::NotFound` when there is no object at `key`
+    async fn get_stream(&self, key: &str) -> Result<ObjectBody, AppError>;
+
+    /// Deleting a missing object is not an error
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError>;
+
+    /// Every object whose key starts with `prefix` (a whole segment; empty
+    /// for all of them), in no particular order
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>, AppError>;
+
+    /// URL anyone can `GET` the object from until `ttl` runs out
+    async fn presign(&self, key: &str, ttl: Duration) -> Result<String, AppError>;
+
+    /// Short description for logs and the self-check, e.g. `s3://bucket`
+    fn describe(&self) -> String;
+}
+
+/// Keys are limited to characters that need no escaping in a file path, an
+/// S3 key or a URL, and may not climb out of their root
+pub fn validate_key(key: &str) -> Result<(), AppError> {
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LENGTH
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!("Invalid storage key '{}'", key)))
+    }
+}
+
+/// Content type to serve an object with, from its extension
+pub fn content_type_for(key: &str) -> &'static str {
+    match key.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()).as_deref() {
+        Some("json") => "application/json",
+        Some("csv") => "text/csv; charset=utf-8",
+        Some("md") => "text/markdown; charset=utf-8",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("zip") => "application/zip",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Builds the configured backend. Misconfiguration is logged and leaves
+/// storage off; the startup self-check then refuses to start.
+pub fn storage_from_config(config: &AppConfig) -> Option<Arc<dyn ObjectStorage>> {
+    let storage: Result<Arc<dyn ObjectStorage>, AppError> = match config.storage_backend {
+        StorageBackend::Local => Ok(Arc::new(LocalStorage::new(&config.storage_local_root, StorageLinks::new(config)))),
+        StorageBackend::S3 => S3Storage::from_config(config).map(|s3| Arc::new(s3) as Arc<dyn ObjectStorage>),
+    };
+
+    storage
+        .inspect(|storage| tracing::info!("Object storage: {}", storage.describe()))
+        .map_err(|e| tracing::error!("Object storage could not be set up: {}", e))
+        .ok()
+}
\ No newline at end of file
@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::{
+    aws::{AmazonS3, AmazonS3Builder},
+    path::Path,
+    signer::Signer,
+    Attribute, Attributes, ObjectStore, PutOptions, PutPayload, RetryConfig,
+};
+use reqwest::Method;
+
+use crate::{
+    errors::AppError,
+    settings::AppConfig,
+    storage::object::{validate_key, ObjectBody, ObjectStorage, StoredObject},
+};
+
+/// Retries stop well before the admin request timeout; the library default
+/// keeps trying for three minutes
+const RETRY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Amazon S3 or any S3-compatible service (MinIO, R2, ...)
+#[derive(Debug)]
+pub struct S3Storage {
+    store: AmazonS3,
+    bucket: String,
+}
+
+impl S3Storage {
+    /// Credentials not given in the config are taken from the standard
+    /// `AWS_*` variables or the instance role
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        let bucket = config.storage_s3_bucket.clone()
+            .ok_or_else(|| AppError::InternalError("STORAGE_S3_BUCKET is not set".to_string()))?;
+
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(&bucket)
+            .with_region(&config.storage_s3_region)
+            .with_retry(RetryConfig {
+                max_retries: 3,
+                retry_timeout: RETRY_TIMEOUT,
+                ..RetryConfig::default()
+            });
+
+        if let Some(endpoint) = &config.storage_s3_endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"))
+                .with_virtual_hosted_style_request(false);
+        }
+        if let (Some(key_id), Some(secret)) = (&config.storage_s3_access_key_id, &config.storage_s3_secret_access_key) {
+            builder = builder.with_access_key_id(key_id).with_secret_access_key(secret);
+        }
+
+        let store = builder
+            .build()
+            .map_err(|e| AppError::InternalError(format!("Invalid S3 storage configuration: {}", e)))?;
+
+        Ok(S3Storage { store, bucket })
+    }
+
+    fn path(key: &str) -> Result<Path, AppError> {
+        validate_key(key)?;
+        Ok(Path::from(key))
+    }
+}
+
+fn s3_error(e: object_store::Error) -> AppError {
+    match e {
+        object_store::Error::NotFound { .. } => AppError::NotFound("Object not found".to_string()),
+        e => AppError::ServiceUnavailable(format!("Object storage unavailable: {}", e)),
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for S3Storage {
+    async fn put(&self, key: &str, body: Bytes, content_type: &str) -> Result<(), AppError> {
+        let path = Self::path(key)?;
+        let attributes = Attributes::from_iter([(Attribute::ContentType, content_type.to_string())]);
+
+        self.store
+            .put_opts(&path, PutPayload::from(body), PutOptions::from(attributes))
+            .await
+            .map(|_| ())
+            .map_err(s3_error)
+    }
+
+    async fn get_stream(&self, key: &str) -> Result<ObjectBody, AppError> {
+        let path = Self::path(key)?;
+        let result = self.store.get(&path).await.map_err(s3_error)?;
+
+        Ok(ObjectBody {
+            size: result.meta.size,
+            stream: result.into_stream().map_err(s3_error).boxed(),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = Self::path(key)?;
+        match self.store.delete(&path).await {
+            Err(e) if !matches!(e, object_store::Error::NotFound { .. }) => Err(s3_error(e)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, AppError> {
+        let path = Self::path(key)?;
+        match self.store.head(&path).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(s3_error(e)),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<StoredObject>, AppError> {
+        let prefix = match prefix {
+            "" => None,
+            prefix => Some(Self::path(prefix)?),
+        };
+
+        self.store
+            .list(prefix.as_ref())
+            .map_ok(|meta| StoredObject { key: meta.location.to_string(), size: meta.size })
+            .try_collect()
+            .await
+            .map_err(s3_error)
+    }
+
+    async fn presign(&self, key: &str, ttl: Duration) -> Result<String, AppError> {
+        let path = Self::path(key)?;
+        self.store
+            .signed_url(Method::GET, &path, ttl)
+            .await
+            .map(String::from)
+            .map_err(s3_error)
+    }
+
+    fn describe(&self) -> String {
+        format!("s3://{}", self.bucket)
+    }
+}
\ No newline at end of file
@@ -1,3 +1,11 @@
 pub mod markdown;
 pub mod valid_uuid;
-pub mod get_client_ip;
\ No newline at end of file
+pub mod get_client_ip;
+pub mod redact;
+pub mod text_diff;
+pub mod tag_index;pub mod qr_code;
+pub mod content_scan;
+pub mod public_urls;
+pub mod route_pattern;
+pub mod csv_export;
+pub mod front_matter;
\ No newline at end of file
@@ -0,0 +1,150 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use url::Url;
+
+/// More `<script>`/`<iframe>` tags than this in one text is treated as an
+/// injection attempt rather than a pasted embed
+pub const MAX_EMBEDS: usize = 3;
+
+/// Longest excerpt of the offending text kept in a finding
+const EXCERPT_CHARS: usize = 80;
+
+static DATA_URI: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bdata:([a-z]+/[a-z0-9.+-]+)?(;[a-z0-9=-]+)*,").expect("data URI pattern compiles")
+});
+static JAVASCRIPT_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:java|vb)script\s*:").expect("javascript URL pattern compiles")
+});
+static EMBED_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)<\s*(script|iframe|object|embed)\b[^>]*>?").expect("embed tag pattern compiles")
+});
+static EVENT_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)<[a-z][^>]*\s(on[a-z]+)\s*=\s*["']?[^>]*>?"#).expect("event handler pattern compiles")
+});
+static LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)(?:https?:)?//[^\s<>"'()\[\]]+"#).expect("link pattern compiles")
+});
+
+/// `high` findings block the write; the others are returned as warnings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+impl ScanSeverity {
+    /// Whether a finding this severe refuses the content
+    pub fn blocks(self) -> bool {
+        self == ScanSeverity::High
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContentFinding {
+    /// Stable identifier of the check that matched, e.g. `data_uri`
+    pub rule: &'static str,
+    pub severity: ScanSeverity,
+    pub message: String,
+    /// The offending text, shortened
+    pub excerpt: String,
+}
+
+/// Flags content that is unsafe or suspicious once rendered: embedded data
+/// URIs, script URLs, script and frame tags, inline event handlers and links
+/// to blocked domains. Markdown is sanitized on render anyway; this catches
+/// the attempts so they can be refused or reviewed.
+#[derive(Debug, Clone, Default)]
+pub struct ContentScanner {
+    /// Lowercased; subdomains are blocked too
+    blocked_domains: Vec<String>,
+}
+
+impl ContentScanner {
+    pub fn new(blocked_domains: Vec<String>) -> Self {
+        ContentScanner { blocked_domains }
+    }
+
+    /// Findings in the order they appear, most severe rule first on ties
+    pub fn scan(&self, text: &str) -> Vec<ContentFinding> {
+        let mut findings: Vec<(usize, ContentFinding)> = Vec::new();
+
+        for found in DATA_URI.captures_iter(text) {
+            let whole = found.get(0).expect("match has a whole group");
+            let mime = found.get(1).map(|m| m.as_str().to_lowercase());
+            let (severity, message) = match mime.as_deref() {
+                Some("image/svg+xml") => (ScanSeverity::High, "SVG data URIs can carry scripts".to_string()),
+                Some(mime) if mime.starts_with("image/") => {
+                    (ScanSeverity::Low, "Embedded image; upload it and link to it instead".to_string())
+                }
+                Some(mime) if mime.starts_with("text/html") || mime.starts_with("application/") => {
+                    (ScanSeverity::High, format!("Data URI with executable content ({})", mime))
+                }
+                _ => (ScanSeverity::Medium, "Embedded data URI".to_string()),
+            };
+            findings.push((whole.start(), finding("data_uri", severity, message, text, whole.start())));
+        }
+
+        for found in JAVASCRIPT_URL.find_iter(text) {
+            findings.push((
+                found.start(),
+                finding("script_url", ScanSeverity::High, "Script URL".to_string(), text, found.start()),
+            ));
+        }
+
+        let embeds: Vec<_> = EMBED_TAG.captures_iter(text).collect();
+        let excessive = embeds.len() > MAX_EMBEDS;
+        for found in &embeds {
+            let whole = found.get(0).expect("match has a whole group");
+            let tag = found[1].to_lowercase();
+            let (severity, message) = if excessive {
+                (ScanSeverity::High, format!("<{}> tag, one of {} in this text", tag, embeds.len()))
+            } else {
+                (ScanSeverity::Medium, format!("<{}> tag; it is removed when rendered", tag))
+            };
+            findings.push((whole.start(), finding("embed_tag", severity, message, text, whole.start())));
+        }
+
+        for found in EVENT_HANDLER.captures_iter(text) {
+            let whole = found.get(0).expect("match has a whole group");
+            let message = format!("Inline `{}` handler", found[1].to_lowercase());
+            findings.push((whole.start(), finding("event_handler", ScanSeverity::Medium, message, text, whole.start())));
+        }
+
+        if !self.blocked_domains.is_empty() {
+            for found in LINK.find_iter(text) {
+                let Some(host) = link_host(found.as_str()) else {
+                    continue;
+                };
+                if let Some(domain) = self.blocked_domain(&host) {
+                    let message = format!("Link to blocked domain {}", domain);
+                    findings.push((found.start(), finding("blocked_domain", ScanSeverity::High, message, text, found.start())));
+                }
+            }
+        }
+
+        findings.sort_by(|(a_at, a), (b_at, b)| a_at.cmp(b_at).then(b.severity.cmp(&a.severity)));
+        findings.into_iter().map(|(_, finding)| finding).collect()
+    }
+
+    fn blocked_domain(&self, host: &str) -> Option<&str> {
+        self.blocked_domains
+            .iter()
+            .find(|domain| host == domain.as_str() || host.ends_with(&format!(".{}", domain)))
+            .map(String::as_str)
+    }
+}
+
+fn finding(rule: &'static str, severity: ScanSeverity, message: String, text: &str, at: usize) -> ContentFinding {
+    let excerpt: String = text[at..].chars().take(EXCERPT_CHARS).collect();
+    ContentFinding { rule, severity, message, excerpt: excerpt.lines().next().unwrap_or_default().to_string() }
+}
+
+/// Lowercased host of an absolute or protocol-relative link
+fn link_host(link: &str) -> Option<String> {
+    let absolute = if link.starts_with("//") { format!("https:{}", link) } else { link.to_string() };
+    let host = Url::parse(&absolute).ok()?.host_str()?.trim_end_matches('.').to_lowercase();
+    (!host.is_empty()).then_some(host)
+}
@@ -0,0 +1,43 @@
+use std::borrow::Cow;
+
+use actix_web::web::Bytes;
+
+use crate::errors::AppError;
+
+/// Leading characters that make Excel or Sheets read a cell as a formula
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// `value` with a `'` in front when a spreadsheet would run it as a formula,
+/// so visitor-supplied text in an admin export opens as plain text
+pub fn escape_formula(value: &str) -> Cow<'_, str> {
+    if value.starts_with(FORMULA_PREFIXES) {
+        Cow::Owned(format!("'{value}"))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// One chunk of a streamed CSV export: the header row when `with_header`,
+/// then `rows`, every cell passed through [`escape_formula`]
+pub fn encode_csv_chunk<const N: usize>(
+    header: &[&str; N],
+    rows: impl IntoIterator<Item = [String; N]>,
+    with_header: bool,
+) -> Result<Bytes, AppError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let csv_err = |e: csv::Error| AppError::InternalError(format!("CSV encoding failed: {}", e));
+
+    if with_header {
+        writer.write_record(header).map_err(csv_err)?;
+    }
+
+    for row in rows {
+        writer.write_record(row.iter().map(|cell| escape_formula(cell).into_owned())).map_err(csv_err)?;
+    }
+
+    let buf = writer
+        .into_inner()
+        .map_err(|e| AppError::InternalError(format!("CSV encoding failed: {}", e)))?;
+
+    Ok(Bytes::from(buf))
+}
@@ -0,0 +1,45 @@
+use crate::errors::AppError;
+
+/// A value in YAML front matter
+pub enum FrontMatterValue<'a> {
+    /// Free text, written as a JSON string so quotes, colons and values
+    /// YAML would read as numbers or booleans come back as the same text
+    Text(&'a str),
+    /// Written as is: booleans, numbers, timestamps
+    Plain(String),
+}
+
+/// The metadata every Markdown export of a post starts with
+pub struct PostFrontMatter<'a> {
+    pub title: &'a str,
+    pub slug: &'a str,
+    pub excerpt: &'a str,
+    pub tags: Option<&'a [String]>,
+}
+
+impl PostFrontMatter<'_> {
+    /// `body` under YAML front matter: title, slug, excerpt and tags, then
+    /// the `extra` keys in order, leaving out those without a value
+    pub fn render(&self, extra: &[(&str, Option<FrontMatterValue>)], body: &str) -> Result<String, AppError> {
+        let quoted = |value: &str| serde_json::to_string(value)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode front matter: {}", e)));
+
+        let mut lines = vec![
+            format!("title: {}", quoted(self.title)?),
+            format!("slug: {}", quoted(self.slug)?),
+            format!("excerpt: {}", quoted(self.excerpt)?),
+        ];
+        if let Some(tags) = self.tags.filter(|tags| !tags.is_empty()) {
+            lines.push(format!("tags: {}", serde_json::to_string(tags).unwrap_or_default()));
+        }
+        for (name, value) in extra {
+            match value {
+                Some(FrontMatterValue::Text(text)) => lines.push(format!("{}: {}", name, quoted(text)?)),
+                Some(FrontMatterValue::Plain(value)) => lines.push(format!("{}: {}", name, value)),
+                None => {}
+            }
+        }
+
+        Ok(format!("---\n{}\n---\n\n{}\n", lines.join("\n"), body.trim_end()))
+    }
+}
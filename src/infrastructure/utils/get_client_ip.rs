@@ -1,16 +1,63 @@
+use std::net::IpAddr;
+
 use actix_web::HttpRequest;
+use ipnet::IpNet;
+
+use crate::settings::AppConfig;
+
+/// The reverse proxies whose `X-Forwarded-For` is believed; empty by default,
+/// so clients cannot pick the address they are rate limited or banned under
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies(Vec<IpNet>);
+
+impl TrustedProxies {
+    pub fn new(ranges: Vec<IpNet>) -> Self {
+        TrustedProxies(ranges)
+    }
+
+    /// The validated `trusted_proxies` setting
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(config.trusted_proxies().unwrap_or_default())
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+}
+
+/// Extract the client's IP address from the request.
+///
+/// `X-Forwarded-For` is only read when the peer is a trusted proxy. It is
+/// walked from the right, skipping the proxies' own hops, so the address is
+/// the one the outermost trusted proxy saw rather than whatever the client
+/// put at the front.
+pub fn get_client_ip(req: &HttpRequest, trusted: &TrustedProxies) -> String {
+    let Some(peer) = req.peer_addr().map(|addr| addr.ip()) else {
+        return "unknown".to_string();
+    };
+    if !trusted.contains(&peer) {
+        return peer.to_string();
+    }
+
+    let forwarded = req.headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect::<Vec<_>>();
 
-/// Extract the client's IP address from the request, considering X-Forwarded-For if trusted
-/// `trust_x_forwarded_for`: whether to trust the X-Forwarded-For header
-pub fn get_client_ip(req: &HttpRequest, trust_x_forwarded_for: bool) -> String {
-    if trust_x_forwarded_for {
-        if let Some(forwarded) = req.headers().get("x-forwarded-for") {
-            if let Ok(s) = forwarded.to_str() {
-                return s.split(',').next().unwrap_or("").trim().to_string();
+    let mut client = peer;
+    for hop in forwarded.into_iter().rev() {
+        match hop.parse::<IpAddr>() {
+            Ok(ip) => {
+                client = ip;
+                if !trusted.contains(&ip) {
+                    break;
+                }
             }
+            // A hop that is not an address cannot be traced further back
+            Err(_) => break,
         }
     }
-    req.peer_addr()
-        .map(|addr| addr.ip().to_string())
-        .unwrap_or_else(|| "unknown".to_string())
-}
\ No newline at end of file
+    client.to_string()
+}
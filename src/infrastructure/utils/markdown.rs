@@ -1,7 +1,7 @@
 use std::{path::Path, io};
 use tokio::fs;
 
-use pulldown_cmark::{html, Options, Parser};
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
 use ammonia::{Builder, UrlRelative};
 use derive_more::Display;
 use infer::{self, Infer};
@@ -27,6 +27,125 @@ pub fn sanitize_markdown_content(content: &str) -> String {
         .to_string()
 }
 
+/// Plain text of the first paragraph with formatting and inline HTML
+/// dropped, cut on a word boundary to at most `max_chars` characters.
+/// `None` when the content has no paragraph text.
+pub fn first_paragraph_excerpt(markdown: &str, max_chars: usize) -> Option<String> {
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    let mut image_depth = 0usize;
+
+    for event in Parser::new_ext(markdown, Options::all()) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(TagEnd::Paragraph) => {
+                if !text.trim().is_empty() {
+                    break;
+                }
+                in_paragraph = false;
+            }
+            // Alt text is not prose
+            Event::Start(Tag::Image { .. }) => image_depth += 1,
+            Event::End(TagEnd::Image) => image_depth = image_depth.saturating_sub(1),
+            Event::Text(t) | Event::Code(t) if in_paragraph && image_depth == 0 => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() || max_chars == 0 {
+        return None;
+    }
+
+    let full = words.join(" ");
+    if full.chars().count() <= max_chars {
+        return Some(full);
+    }
+
+    // Leaves room for the ellipsis
+    let budget = max_chars - 1;
+    let mut excerpt = String::new();
+    let mut len = 0;
+    for word in words {
+        let word_len = word.chars().count();
+        let needed = if excerpt.is_empty() { word_len } else { word_len + 1 };
+        if len + needed > budget {
+            break;
+        }
+        if !excerpt.is_empty() {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+        len += needed;
+    }
+
+    // A single word longer than the budget is cut mid-word
+    if excerpt.is_empty() {
+        excerpt = full.chars().take(budget).collect();
+    }
+    excerpt.push('…');
+
+    Some(excerpt)
+}
+
+/// Prose of the whole document: text, inline code and alt text, with code
+/// blocks, link targets and inline HTML dropped. Every element ends in a
+/// newline so words never run together; the layout is not preserved.
+pub fn plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(markdown, Options::all()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(t) | Event::Code(t) if !in_code_block => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::End(_) => text.push('\n'),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Absolute http(s) link and image targets in document order, without duplicates.
+pub fn external_links(markdown: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+
+    for event in Parser::new_ext(markdown, Options::all()) {
+        let dest_url = match event {
+            Event::Start(Tag::Link { dest_url, .. }) | Event::Start(Tag::Image { dest_url, .. }) => dest_url,
+            _ => continue,
+        };
+
+        let url = dest_url.trim();
+        let lower = url.to_ascii_lowercase();
+        if (lower.starts_with("http://") || lower.starts_with("https://")) && !links.iter().any(|l| l == url) {
+            links.push(url.to_string());
+        }
+    }
+
+    links
+}
+
+/// Image targets in document order, without duplicates, whatever their scheme.
+pub fn image_sources(markdown: &str) -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+
+    for event in Parser::new_ext(markdown, Options::all()) {
+        if let Event::Start(Tag::Image { dest_url, .. }) = event {
+            let url = dest_url.trim();
+            if !url.is_empty() && !sources.iter().any(|s| s == url) {
+                sources.push(url.to_string());
+            }
+        }
+    }
+
+    sources
+}
+
 /// Checks whether a given Markdown string is structurally valid.
 pub fn is_valid_markdown(content: &str) -> bool {
     let parser = Parser::new_ext(content, Options::all());
@@ -0,0 +1,108 @@
+use actix_web::{HttpMessage, HttpRequest};
+
+use crate::{entities::tenant::Tenant, settings::AppConfig};
+
+/// Header a reverse proxy names the path it serves the API under with
+pub const FORWARDED_PREFIX_HEADER: &str = "x-forwarded-prefix";
+
+/// The path prefix a request reached the API under, as recorded by the
+/// `ForwardedPrefix` middleware; empty when served at the root
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestBasePath(pub String);
+
+/// Builds the links the API hands out, so they stay right behind a
+/// reverse proxy.
+///
+/// Two kinds of URL are built. Site URLs (`/blog/<slug>`, `/admin/...`)
+/// point at the site's own pages and start at its origin: the tenant's
+/// primary host, else `public_base_url`. API URLs (`/api/v1/...`,
+/// `/about.html`) point back at this server, which the proxy may serve
+/// under a prefix, so they also get `base_path`. Without any origin both
+/// are relative.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicUrls {
+    base_url: String,
+    base_path: String,
+}
+
+impl PublicUrls {
+    pub fn new(base_url: &str, base_path: &str) -> Self {
+        PublicUrls {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            base_path: normalize_base_path(base_path).unwrap_or_default(),
+        }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(
+            config.public_base_url.as_deref().unwrap_or_default(),
+            config.base_path.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// These URLs with the base path the request came in under, e.g. from
+    /// `X-Forwarded-Prefix`
+    pub fn for_request(&self, req: &HttpRequest) -> Self {
+        match req.extensions().get::<RequestBasePath>() {
+            Some(RequestBasePath(base_path)) => PublicUrls { base_path: base_path.clone(), ..self.clone() },
+            None => self.clone(),
+        }
+    }
+
+    /// These URLs starting at `base_url` when `public_base_url` is not
+    /// configured, for links that must be absolute (QR codes)
+    pub fn or_base_url(&self, base_url: &str) -> Self {
+        if !self.base_url.is_empty() {
+            return self.clone();
+        }
+        PublicUrls { base_url: base_url.trim_end_matches('/').to_string(), ..self.clone() }
+    }
+
+    /// `""` at the root, otherwise e.g. `/api`
+    pub fn base_path(&self) -> &str {
+        &self.base_path
+    }
+
+    /// Where the tenant's site lives, e.g. `https://example.com`; empty when
+    /// neither a host nor `public_base_url` is known
+    pub fn site_origin(&self, tenant: &Tenant) -> String {
+        match tenant.primary_host() {
+            Some(host) => format!("https://{}", host),
+            None => self.base_url.clone(),
+        }
+    }
+
+    /// `path` on the tenant's site
+    pub fn site(&self, tenant: &Tenant, path: &str) -> String {
+        format!("{}{}", self.site_origin(tenant), path)
+    }
+
+    /// `path` of this API as clients reach it through the proxy
+    pub fn api(&self, tenant: &Tenant, path: &str) -> String {
+        format!("{}{}{}", self.site_origin(tenant), self.base_path, path)
+    }
+
+    /// Like `api`, for links not tied to one site; starts at `public_base_url`
+    pub fn api_root(&self, path: &str) -> String {
+        format!("{}{}{}", self.base_url, self.base_path, path)
+    }
+}
+
+/// `path` as a base path: a leading `/`, no trailing one, `""` for the
+/// root. `None` when it is not a plain path, so a header cannot turn links
+/// into another origin (`//evil.example`) or add a query.
+pub fn normalize_base_path(path: &str) -> Option<String> {
+    let path = path.trim().trim_end_matches('/');
+    if path.is_empty() {
+        return Some(String::new());
+    }
+
+    let valid = path.starts_with('/')
+        && path[1..].split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'))
+        });
+    valid.then(|| path.to_string())
+}
@@ -0,0 +1,41 @@
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::errors::AppError;
+
+/// Light modules scanners expect around the code, on each side
+const QUIET_ZONE: usize = 4;
+
+/// Renders `data` as a black-on-white grayscale PNG at most `size` pixels
+/// wide. Modules are whole pixels so the code stays sharp when printed,
+/// which can make the image a little smaller than asked for.
+pub fn qr_png(data: &str, size: u32, level: EcLevel) -> Result<Vec<u8>, AppError> {
+    let code = QrCode::with_error_correction_level(data.as_bytes(), level)
+        .map_err(|e| AppError::InvalidInput(format!("Cannot encode a QR code: {}", e)))?;
+
+    let modules = code.width() + 2 * QUIET_ZONE;
+    let scale = (size as usize / modules).max(1);
+    let side = modules * scale;
+
+    let colors = code.to_colors();
+    let mut pixels = vec![u8::MAX; side * side];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let (x, y) = ((i % code.width() + QUIET_ZONE) * scale, (i / code.width() + QUIET_ZONE) * scale);
+        for row in y..y + scale {
+            pixels[row * side + x..row * side + x + scale].fill(0);
+        }
+    }
+
+    let encode_error = |e: png::EncodingError| AppError::InternalError(format!("Failed to encode QR code: {}", e));
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    writer.write_image_data(&pixels).map_err(encode_error)?;
+    writer.finish().map_err(encode_error)?;
+
+    Ok(png_bytes)
+}
@@ -0,0 +1,246 @@
+use std::io::{self, Write};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value};
+use tracing_subscriber::fmt::MakeWriter;
+
+pub use crate::entities::pii::REDACTED;
+use crate::{entities::pii::pii_field_names, settings::BodyLoggingConfig};
+
+/// Loose on purpose: a false positive only hides a little more of a debug log
+static EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}").expect("email pattern compiles")
+});
+
+static PII_FIELDS: Lazy<Vec<&'static str>> = Lazy::new(pii_field_names);
+
+/// Start of a `field=` value as tracing formats event fields, with or
+/// without ANSI styling around the `=`, for every field tagged with `pii!`
+static PII_FIELD_KEY: Lazy<Regex> = Lazy::new(|| {
+    let ansi = r"(?:\x1b\[[0-9;]*m)*";
+    Regex::new(&format!(
+        r"(?i)(?:^|[^a-z0-9_]|\x1b\[[0-9;]*m)(?:{}){ansi}={ansi}(?:Some\()?",
+        PII_FIELDS.join("|"),
+    ))
+    .expect("PII field pattern compiles")
+});
+
+/// The next ` field=`, which ends an unquoted value: `%` fields are written
+/// as is, spaces and all
+static NEXT_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\s(?:\x1b\[[0-9;]*m)*[a-z_][a-z0-9_.]*(?:\x1b\[[0-9;]*m)*=|[\r\n]")
+        .expect("field pattern compiles")
+});
+
+static QUOTED: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^"(?:[^"\\]|\\.)*""#).expect("quoted pattern compiles"));
+
+/// `field: "value"` and `"field":"value"` as `Debug` and JSON write them.
+/// Only quoted values match, so prose like `message: connection refused` stays.
+static PII_QUOTED_PAIR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r#"(?i)(^|[^a-z0-9_])({})("?\s*:\s*(?:Some\()?)("(?:[^"\\]|\\.)*")"#,
+        PII_FIELDS.join("|"),
+    ))
+    .expect("PII field pattern compiles")
+});
+
+/// Strips secrets and personal data from request and response bodies
+/// before they are logged.
+///
+/// Values under a key that contains one of `fields` (case-insensitive, so
+/// `password` also covers `new_password`) or that is exactly a field tagged
+/// with `pii!` are dropped entirely; every other string has email
+/// addresses, tagged `field=value` pairs and the extra `patterns` masked.
+#[derive(Clone, Debug)]
+pub struct Redactor {
+    fields: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    pub fn new(fields: &[String], patterns: &[String]) -> Result<Self, regex::Error> {
+        Ok(Redactor {
+            fields: fields.iter().map(|f| f.trim().to_ascii_lowercase()).filter(|f| !f.is_empty()).collect(),
+            patterns: patterns.iter().map(|p| Regex::new(p)).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// The default secret fields and no extra patterns, e.g. for log
+    /// output set up before the config is loaded
+    pub fn with_defaults() -> Self {
+        Redactor::new(&BodyLoggingConfig::default().redact_fields, &[]).expect("no patterns to compile")
+    }
+
+    fn is_sensitive_key(&self, key: &str) -> bool {
+        let key = key.to_ascii_lowercase();
+        self.fields.iter().any(|field| key.contains(field.as_str())) || PII_FIELDS.contains(&key.as_str())
+    }
+
+    /// Masks emails, tagged fields and the configured patterns in free text
+    pub fn redact_text(&self, text: &str) -> String {
+        let masked = EMAIL.replace_all(text, REDACTED);
+        let masked = mask_field_values(&masked);
+        let masked = PII_QUOTED_PAIR.replace_all(&masked, format!("${{1}}${{2}}${{3}}\"{}\"", REDACTED)).into_owned();
+        self.patterns
+            .iter()
+            .fold(masked, |text, pattern| pattern.replace_all(&text, REDACTED).into_owned())
+    }
+
+    pub fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.is_sensitive_key(key) {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            Value::String(text) => *text = self.redact_text(text),
+            _ => {}
+        }
+    }
+
+    /// Redacts `body` according to its content type. JSON and form bodies are
+    /// redacted field by field; anything else is treated as text. Bodies that
+    /// claim to be JSON but do not parse fall back to text masking.
+    pub fn redact_body(&self, content_type: &str, body: &[u8]) -> String {
+        let text = String::from_utf8_lossy(body);
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+
+        if mime == "application/json" || mime.ends_with("+json") {
+            if let Ok(mut value) = serde_json::from_slice::<Value>(body) {
+                self.redact_json(&mut value);
+                return value.to_string();
+            }
+        } else if mime == "application/x-www-form-urlencoded" {
+            return text
+                .split('&')
+                .map(|pair| match pair.split_once('=') {
+                    Some((key, _)) if self.is_sensitive_key(&urlencoding::decode(key).unwrap_or_default()) => {
+                        format!("{}={}", key, REDACTED)
+                    }
+                    _ => self.redact_text(&urlencoding::decode(pair).unwrap_or_default()),
+                })
+                .collect::<Vec<_>>()
+                .join("&");
+        }
+
+        self.redact_text(&text)
+    }
+
+    /// Redacts one formatted log line. JSON lines are redacted key by key,
+    /// except the event's own `message`, which is masked as text.
+    pub fn redact_log_line(&self, line: &str) -> String {
+        if line.starts_with('{')
+            && let Ok(Value::Object(mut event)) = serde_json::from_str::<Value>(line)
+        {
+            self.redact_log_fields(&mut event);
+            return Value::Object(event).to_string();
+        }
+
+        self.redact_text(line)
+    }
+
+    fn redact_log_fields(&self, event: &mut Map<String, Value>) {
+        for (key, value) in event.iter_mut() {
+            match value {
+                Value::String(text) if key == "message" => *text = self.redact_text(text),
+                Value::Object(fields) if key == "fields" => self.redact_log_fields(fields),
+                _ if self.is_sensitive_key(key) => *value = Value::String(REDACTED.to_string()),
+                _ => self.redact_json(value),
+            }
+        }
+    }
+}
+
+/// Masks the value of every tagged `field=value` pair in `text`
+fn mask_field_values(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut rest = 0;
+
+    while let Some(key) = PII_FIELD_KEY.find_at(text, rest) {
+        masked.push_str(&text[rest..key.end()]);
+        let value = &text[key.end()..];
+        let len = match QUOTED.find(value) {
+            Some(quoted) => quoted.end(),
+            None => NEXT_FIELD.find(value).map_or(value.len(), |next| next.start()),
+        };
+        if len > 0 {
+            masked.push_str(REDACTED);
+        }
+        rest = key.end() + len;
+    }
+
+    masked.push_str(&text[rest..]);
+    masked
+}
+
+/// Wraps a tracing writer so every formatted event goes through a
+/// `Redactor` before it is written
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+    redactor: Redactor,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M, redactor: Redactor) -> Self {
+        RedactingMakeWriter { inner, redactor }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactedEvent<'a, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactedEvent { inner: self.inner.make_writer(), redactor: &self.redactor, buf: Vec::new() }
+    }
+}
+
+/// One event's output, buffered until it is complete so fields split
+/// across writes are still redacted
+pub struct RedactedEvent<'a, W: Write> {
+    inner: W,
+    redactor: &'a Redactor,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> RedactedEvent<'_, W> {
+    fn write_out(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let buf = std::mem::take(&mut self.buf);
+        let text = String::from_utf8_lossy(&buf);
+        let redacted: String = text
+            .split_inclusive('\n')
+            .map(|line| {
+                let body = line.trim_end_matches(['\r', '\n']);
+                format!("{}{}", self.redactor.redact_log_line(body), &line[body.len()..])
+            })
+            .collect();
+        self.inner.write_all(redacted.as_bytes())?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for RedactedEvent<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_out()
+    }
+}
+
+impl<W: Write> Drop for RedactedEvent<'_, W> {
+    fn drop(&mut self) {
+        let _ = self.write_out();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+/// A path pattern of the config tables (`[cache_policy]`, `[route_policy]`),
+/// matched segment by segment: `*` or `{name}` stands for one segment, `**`
+/// for any number of them, including none. `/api/v1/blog/posts/{post_id}`
+/// matches `/api/v1/blog/posts/42`; `/api/v1/admin/**` matches everything
+/// under `/api/v1/admin` and that path itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePattern {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    One,
+    Any,
+}
+
+impl RoutePattern {
+    /// `None` unless `pattern` starts with `/` and every `{` closes its segment
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let pattern = pattern.trim();
+        if !pattern.starts_with('/') {
+            return None;
+        }
+
+        let segments = split_path(pattern)
+            .map(|segment| match segment {
+                "**" => Some(Segment::Any),
+                "*" => Some(Segment::One),
+                param if param.starts_with('{') => {
+                    let name = param.strip_prefix('{')?.strip_suffix('}')?;
+                    (!name.is_empty() && !name.contains(['{', '}'])).then_some(Segment::One)
+                }
+                literal if literal.contains(['{', '}']) => None,
+                literal => Some(Segment::Literal(literal.to_string())),
+            })
+            .collect::<Option<_>>()?;
+
+        Some(RoutePattern { segments })
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let path: Vec<&str> = split_path(path).collect();
+        matches_segments(&self.segments, &path)
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+fn matches_segments(pattern: &[Segment], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((Segment::Any, rest)) => (0..=path.len()).any(|skip| matches_segments(rest, &path[skip..])),
+        Some((head, rest)) => path.split_first().is_some_and(|(segment, tail)| {
+            let matched = match head {
+                Segment::Literal(literal) => literal == segment,
+                _ => true,
+            };
+            matched && matches_segments(rest, tail)
+        }),
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::utils::markdown::plain_text;
+
+/// Shorter words are too vague to tag a post with
+const MIN_TERM_LENGTH: usize = 3;
+/// Longest tag the post validation accepts
+const MAX_TERM_LENGTH: usize = 30;
+/// New terms rank below an existing tag of equal weight, so tags get reused
+const NEW_TAG_WEIGHT: f64 = 0.5;
+/// Added when the draft names an existing tag outright
+const MENTION_BONUS: f64 = 0.25;
+/// Weaker matches are noise
+const MIN_SCORE: f64 = 0.05;
+
+/// Common English words, never suggested
+const STOPWORDS: &[&str] = &[
+    "about", "above", "after", "again", "against", "all", "also", "and", "any", "are", "because", "been",
+    "before", "being", "below", "between", "both", "but", "can", "could", "did", "does", "doing", "down",
+    "during", "each", "even", "every", "few", "for", "from", "further", "get", "gets", "got", "had", "has",
+    "have", "having", "her", "here", "hers", "herself", "him", "himself", "his", "how", "however", "into",
+    "its", "itself", "just", "let", "like", "made", "make", "makes", "many", "may", "might", "more", "most",
+    "much", "must", "myself", "need", "new", "not", "now", "off", "once", "one", "only", "other", "our",
+    "ours", "ourselves", "out", "over", "own", "really", "said", "same", "see", "she", "should", "since",
+    "some", "still", "such", "take", "than", "that", "the", "their", "theirs", "them", "themselves", "then",
+    "there", "these", "they", "thing", "things", "this", "those", "through", "too", "two", "under", "until",
+    "use", "used", "uses", "using", "very", "want", "was", "way", "well", "were", "what", "when", "where",
+    "which", "while", "who", "whom", "why", "will", "with", "without", "would", "yet", "you", "your",
+    "yours", "yourself", "yourselves",
+];
+
+/// Splits markdown prose into lowercase terms, dropping stopwords, numbers
+/// and words outside the tag length limits
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+    stopwords: HashSet<String>,
+}
+
+impl Tokenizer {
+    /// The built-in stopwords plus `extra`
+    pub fn new(extra: &[String]) -> Self {
+        let stopwords = STOPWORDS
+            .iter()
+            .map(|word| word.to_string())
+            .chain(extra.iter().map(|word| word.to_lowercase()))
+            .collect();
+        Tokenizer { stopwords }
+    }
+
+    pub fn terms(&self, markdown: &str) -> Vec<String> {
+        plain_text(markdown)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| (MIN_TERM_LENGTH..=MAX_TERM_LENGTH).contains(&word.chars().count()))
+            .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+            .map(str::to_lowercase)
+            .filter(|word| !self.stopwords.contains(word))
+            .collect()
+    }
+}
+
+/// A ranked suggestion; `uses` is how many indexed posts already carry the
+/// tag, 0 for a new one
+#[derive(Debug, Clone, Serialize)]
+pub struct TagSuggestion {
+    pub tag: String,
+    pub score: f64,
+    pub existing: bool,
+    pub uses: usize,
+}
+
+/// Posts carrying one tag: its spelling as first seen, and the sum of their
+/// unit TF-IDF vectors with that sum's length
+#[derive(Debug, Clone)]
+struct TagProfile {
+    tag: String,
+    uses: usize,
+    centroid: HashMap<String, f64>,
+    norm: f64,
+}
+
+/// TF-IDF statistics over a tenant's posts. A draft is weighted against the
+/// corpus; existing tags are scored by cosine similarity between the draft
+/// and the posts carrying them, and the draft's own strongest terms fill in
+/// as new tags.
+#[derive(Debug, Clone)]
+pub struct TagIndex {
+    tokenizer: Tokenizer,
+    documents: usize,
+    document_frequency: HashMap<String, usize>,
+    /// Keyed by lowercased tag
+    tags: HashMap<String, TagProfile>,
+}
+
+impl TagIndex {
+    /// Indexes `(markdown, tags)` pairs, one per post
+    pub fn build(tokenizer: Tokenizer, posts: &[(String, Vec<String>)]) -> Self {
+        let terms: Vec<Vec<String>> = posts.iter().map(|(markdown, _)| tokenizer.terms(markdown)).collect();
+
+        let mut document_frequency: HashMap<String, usize> = HashMap::new();
+        for doc in &terms {
+            for term in doc.iter().collect::<HashSet<_>>() {
+                *document_frequency.entry(term.clone()).or_default() += 1;
+            }
+        }
+
+        let mut index = TagIndex { tokenizer, documents: posts.len(), document_frequency, tags: HashMap::new() };
+
+        for (doc, (_, tags)) in terms.iter().zip(posts) {
+            let vector = index.weigh(doc);
+            let mut seen = HashSet::new();
+            for tag in tags {
+                let key = tag.to_lowercase();
+                if !seen.insert(key.clone()) {
+                    continue;
+                }
+                let profile = index.tags.entry(key).or_insert_with(|| TagProfile {
+                    tag: tag.clone(),
+                    uses: 0,
+                    centroid: HashMap::new(),
+                    norm: 0.0,
+                });
+                profile.uses += 1;
+                for (term, weight) in &vector {
+                    *profile.centroid.entry(term.clone()).or_default() += weight;
+                }
+            }
+        }
+
+        for profile in index.tags.values_mut() {
+            profile.norm = profile.centroid.values().map(|w| w * w).sum::<f64>().sqrt();
+        }
+
+        index
+    }
+
+    /// Posts the index was built from
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+
+    /// Up to `limit` tags for `markdown`, best first
+    pub fn suggest(&self, markdown: &str, limit: usize) -> Vec<TagSuggestion> {
+        let terms = self.tokenizer.terms(markdown);
+        let vector = self.weigh(&terms);
+        let mentioned: HashSet<&str> = terms.iter().map(String::as_str).collect();
+
+        let mut suggestions: Vec<TagSuggestion> = self
+            .tags
+            .iter()
+            .map(|(key, profile)| {
+                let similarity = match profile.norm {
+                    norm if norm > 0.0 => {
+                        vector.iter().map(|(term, w)| w * profile.centroid.get(term).unwrap_or(&0.0)).sum::<f64>() / norm
+                    }
+                    _ => 0.0,
+                };
+                let named = key.split('-').filter(|part| !part.is_empty()).all(|part| mentioned.contains(part));
+                TagSuggestion {
+                    tag: profile.tag.clone(),
+                    score: similarity + if named { MENTION_BONUS } else { 0.0 },
+                    existing: true,
+                    uses: profile.uses,
+                }
+            })
+            .collect();
+
+        suggestions.extend(
+            vector
+                .iter()
+                .filter(|(term, _)| !self.tags.contains_key(*term))
+                .map(|(term, weight)| TagSuggestion {
+                    tag: term.clone(),
+                    score: weight * NEW_TAG_WEIGHT,
+                    existing: false,
+                    uses: 0,
+                }),
+        );
+
+        suggestions.retain(|s| s.score >= MIN_SCORE);
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.tag.cmp(&b.tag)));
+        suggestions.truncate(limit);
+        for suggestion in &mut suggestions {
+            suggestion.score = (suggestion.score * 1000.0).round() / 1000.0;
+        }
+
+        suggestions
+    }
+
+    /// Unit-length TF-IDF vector: sublinear term frequency times smoothed
+    /// inverse document frequency, so terms unseen in the corpus count most
+    fn weigh(&self, terms: &[String]) -> HashMap<String, f64> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for term in terms {
+            *counts.entry(term.as_str()).or_default() += 1;
+        }
+
+        let mut vector: HashMap<String, f64> = counts
+            .into_iter()
+            .map(|(term, count)| {
+                let df = self.document_frequency.get(term).copied().unwrap_or(0);
+                let idf = ((1 + self.documents) as f64 / (1 + df) as f64).ln() + 1.0;
+                (term.to_string(), (1.0 + (count as f64).ln()) * idf)
+            })
+            .collect();
+
+        let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            vector.values_mut().for_each(|w| *w /= norm);
+        }
+
+        vector
+    }
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+/// Unchanged lines kept around each change in a hunk
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A unified diff and how many lines it adds and removes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnifiedDiff {
+    pub text: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Line-based unified diff of `old` against `new`, with `context` unchanged
+/// lines around each hunk. Identical inputs give an empty diff without
+/// headers. There is no "\ No newline at end of file" marker.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str, context: usize) -> UnifiedDiff {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut diff = UnifiedDiff::default();
+    let hunks = hunk_ranges(&ops, context);
+    if hunks.is_empty() {
+        return diff;
+    }
+
+    diff.text.push_str(&format!("--- {}\n+++ {}\n", old_label, new_label));
+
+    // Lines of each side consumed before every op
+    let mut old_before = Vec::with_capacity(ops.len());
+    let mut new_before = Vec::with_capacity(ops.len());
+    let (mut old_at, mut new_at) = (0, 0);
+    for (op, _) in &ops {
+        old_before.push(old_at);
+        new_before.push(new_at);
+        old_at += usize::from(*op != LineOp::Insert);
+        new_at += usize::from(*op != LineOp::Delete);
+    }
+
+    for (start, end) in hunks {
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|(op, _)| *op != LineOp::Insert).count();
+        let new_len = hunk.iter().filter(|(op, _)| *op != LineOp::Delete).count();
+        // An empty side points at the line before the hunk, as diff(1) does
+        let old_start = old_before[start] + usize::from(old_len > 0);
+        let new_start = new_before[start] + usize::from(new_len > 0);
+        diff.text.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_len, new_start, new_len));
+
+        for (op, line) in hunk {
+            let prefix = match op {
+                LineOp::Equal => ' ',
+                LineOp::Delete => {
+                    diff.deletions += 1;
+                    '-'
+                }
+                LineOp::Insert => {
+                    diff.additions += 1;
+                    '+'
+                }
+            };
+            diff.text.push(prefix);
+            diff.text.push_str(line);
+            diff.text.push('\n');
+        }
+    }
+
+    diff
+}
+
+/// `[start, end)` op ranges of each hunk; changes closer than twice the
+/// context share one hunk
+fn hunk_ranges(ops: &[(LineOp, &str)], context: usize) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+
+    for (i, _) in ops.iter().enumerate().filter(|(_, (op, _))| *op != LineOp::Equal) {
+        let start = i.saturating_sub(context);
+        let end = (i + 1).saturating_add(context).min(ops.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    hunks
+}
+
+/// Shortest edit script between two line lists (Myers, O((N+M)·D)).
+/// The common prefix and suffix are split off first, as most edits to a
+/// long document touch a small part of it.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(LineOp, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops: Vec<(LineOp, &str)> = old[..prefix].iter().map(|line| (LineOp::Equal, *line)).collect();
+    ops.extend(myers(&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]));
+    ops.extend(old[old.len() - suffix..].iter().map(|line| (LineOp::Equal, *line)));
+    ops
+}
+
+fn myers<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(LineOp, &'a str)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    if max == 0 {
+        return Vec::new();
+    }
+
+    // Furthest x reached on each diagonal k = x - y, indexed from k = -max
+    let index = |k: isize| (k + max) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 1];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk the snapshots back from the end to recover the path
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) { k + 1 } else { k - 1 };
+        let prev_x = v[index(prev_k)];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push((LineOp::Equal, old[x as usize]));
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                ops.push((LineOp::Insert, new[y as usize]));
+            } else {
+                x -= 1;
+                ops.push((LineOp::Delete, old[x as usize]));
+            }
+        }
+    }
+
+    ops.reverse();
+    ops
+}
\ No newline at end of file
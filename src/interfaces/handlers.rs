@@ -5,4 +5,39 @@ pub mod json_error;
 pub mod home;
 pub mod about_me;
 pub mod blog_posts;
-pub mod contact_me;
\ No newline at end of file
+pub mod contact_me;
+pub mod feature_flags;
+pub mod gate;
+pub mod settings;
+pub mod fallback;
+pub mod domains;
+pub mod hire;
+pub mod uses;
+pub mod changelog;
+pub mod feed;
+pub mod outbound;
+pub mod honeytoken;
+pub mod email_templates;
+pub mod fixtures;
+pub mod presence;
+pub mod storage;
+pub mod series;
+pub mod link_checks;
+pub mod rebuild;
+pub mod visitor;
+pub mod sync;
+pub mod static_export;
+pub mod rate_limits;
+pub mod geo;
+pub mod retention;
+pub mod audit;
+pub mod reading;
+pub mod api_tokens;
+pub mod stats;
+pub mod bookmarks;
+pub mod pages;
+pub mod schedule_preview;
+pub mod redirects;
+pub mod public_api;
+pub mod jobs;
+pub mod usage;
\ No newline at end of file
@@ -11,13 +11,14 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
-    entities::about_me::{AboutMeUpload, DeleteAboutMeQuery, NewAboutMe}, handlers::json_error::handle_handler_error, use_cases::extractors::AdminClaims, utils::markdown::read_markdown_file, AppState
+    entities::about_me::{AboutMeUpload, DeleteAboutMeQuery, NewAboutMe, UpdateAboutMeRequest}, handlers::json_error::handle_handler_error, use_cases::extractors::{AdminClaims, CurrentTenant}, utils::markdown::read_markdown_file, AppState
 };
 
 
 
 pub async fn create_about_me(
     _claims: AdminClaims,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     data_input: Result<Either<MultipartForm<AboutMeUpload>, web::Json<NewAboutMe>>, actix_web::Error>
 ) -> impl Responder {
@@ -113,7 +114,7 @@ pub async fn create_about_me(
                 effective_date: form.metadata.0.effective_date,
             };
             
-            match state.about_handler.create_about_me(new_about_me).await {
+            match state.about_handler.create_about_me(tenant.id(), new_about_me).await {
                 Ok(response) => HttpResponse::Created().json(response),
                 Err(e) => handle_handler_error(e),
             }
@@ -127,7 +128,7 @@ pub async fn create_about_me(
                 );
             }
             
-            match state.about_handler.create_about_me(text_data).await {
+            match state.about_handler.create_about_me(tenant.id(), text_data).await {
                 Ok(response) => HttpResponse::Created().json(response),
                 Err(e) => handle_handler_error(e),
             }   
@@ -136,21 +137,33 @@ pub async fn create_about_me(
 }
 
 pub async fn get_about_me(
+    tenant: CurrentTenant,
     state: web::Data<AppState>
 ) -> impl Responder {
-    match state.about_handler.get_about_me().await {
+    match state.about_handler.get_about_me(tenant.id()).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => handle_handler_error(e)
     }
 }
 
-// pub async fn update_about_me(
-// ) -> impl Responder {
-//     todo!("Implement update_about_me handler");
-// }
+pub async fn update_about_me(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    path: web::Path<Uuid>,
+    request: web::Json<UpdateAboutMeRequest>,
+    state: web::Data<AppState>
+) -> impl Responder {
+    let id = path.into_inner();
+
+    match state.about_handler.update_about_me_content(tenant.id(), id, request.into_inner()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => handle_handler_error(e)
+    }
+}
 
 pub async fn delete_about_me(
     _claims: AdminClaims,
+    tenant: CurrentTenant,
     path: web::Path<Uuid>,
     query: web::Query<DeleteAboutMeQuery>,
     state: web::Data<AppState>
@@ -158,7 +171,7 @@ pub async fn delete_about_me(
     let id = path.into_inner();
     let hard_delete = query.hard_delete.unwrap_or(false);
 
-    match state.about_handler.delete_about_me(id, hard_delete).await {
+    match state.about_handler.delete_about_me(tenant.id(), id, hard_delete).await {
         Ok(()) => HttpResponse::NoContent().finish(),
         Err(e) => handle_handler_error(e)
     }
@@ -0,0 +1,74 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    entities::api_token::{ApiTokenUsageQuery, CreateApiTokenRequest},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    utils::valid_uuid::valid_uuid,
+    AppState,
+};
+
+/// The site's issued tokens and the configured ones, with usage counters
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_api_tokens(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let report = state.api_tokens.report(&tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// The response is the only time the token itself is shown
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn issue_api_token(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<CreateApiTokenRequest>,
+) -> Result<impl Responder, AppError> {
+    let created_by = Uuid::parse_str(&claims.0.sub).ok();
+    let issued = state.api_tokens.issue(tenant.id(), created_by, data.into_inner()).await?;
+
+    info!(
+        name = %issued.summary.name,
+        scopes = ?issued.summary.scopes,
+        admin = %claims.0.sub,
+        "API token issued"
+    );
+
+    Ok(HttpResponse::Created().json(issued))
+}
+
+/// Daily traffic per month, with what is left of the monthly quota
+#[instrument(skip(_claims, tenant, state))]
+pub async fn api_token_usage(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    query: web::Query<ApiTokenUsageQuery>,
+) -> Result<impl Responder, AppError> {
+    let id = valid_uuid(&id)?;
+    let report = state.api_tokens.usage(&tenant.id(), id, query.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn revoke_api_token(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let id = valid_uuid(&id)?;
+    state.api_tokens.revoke(tenant.id(), id).await?;
+
+    info!(token_id = %id, admin = %claims.0.sub, "API token revoked");
+
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use actix_web::{http::header::ContentDisposition, web, HttpResponse, Responder};
+use chrono::Utc;
+use futures::TryStreamExt;
+use tracing::instrument;
+
+use crate::{
+    entities::audit::{AuditExportFormat, AuditExportQuery, AuditLogFilter, AuditLogQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// One page of entries matching the filters, newest first
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_audit_log(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    filter: web::Query<AuditLogFilter>,
+    page: web::Query<AuditLogQuery>,
+) -> Result<impl Responder, AppError> {
+    let page = state.audit.search(tenant.id(), filter.into_inner(), page.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Streams every entry matching the filters as a CSV or JSON attachment
+#[instrument(skip(_claims, tenant, state))]
+pub async fn export_audit_log(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    filter: web::Query<AuditLogFilter>,
+    query: web::Query<AuditExportQuery>,
+) -> Result<HttpResponse, AppError> {
+    let body = state.audit
+        .export(tenant.id(), filter.into_inner(), query.format, query.include_pii)?
+        .map_err(|e| {
+            tracing::error!(error = %e, "Audit log export aborted mid-stream");
+            actix_web::error::ErrorInternalServerError(e.to_string())
+        });
+
+    let (extension, content_type) = match query.format {
+        AuditExportFormat::Csv => ("csv", "text/csv; charset=utf-8"),
+        AuditExportFormat::Json => ("json", "application/json"),
+    };
+    let filename = format!("audit-log-{}.{}", Utc::now().format("%Y%m%d-%H%M%S"), extension);
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition::attachment(filename))
+        .streaming(body))
+}
\ No newline at end of file
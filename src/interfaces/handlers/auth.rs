@@ -1,18 +1,30 @@
 use actix_web::http::StatusCode;
 use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder};
 use crate::domain::entities::user::LoginUser;
+use uuid::Uuid;
+use crate::entities::one_time_token::{
+    EmailVerificationConfirmRequest, OneTimeTokenQuery, PasswordResetConfirmRequest, PasswordResetRequest,
+};
 use crate::entities::token::{AuthResponse, RefreshTokenRequest};
 use crate::entities::user::{LogoutRequest, NewUser};
+use crate::errors::AppError;
 use crate::handlers::json_error::{handle_auth_handler_error, json_error};
-use crate::use_cases::extractors::AdminClaims;
+use crate::use_cases::captcha::CaptchaForm;
+use crate::use_cases::extractors::{AdminClaims, AuthClaims, CurrentTenant};
 use crate::AppState;
 
 #[post("/register")]
 pub async fn register(
+    req: HttpRequest,
     state: web::Data<AppState>,
+    tenant: CurrentTenant,
     user: web::Json<NewUser>
 ) -> impl Responder {
-    match state.auth_handler.register(user.into_inner()).await {
+    if let Err(e) = state.captcha.check_request(&tenant.id(), CaptchaForm::Register, &req, &state.trusted_proxies).await {
+        return e.to_http_response();
+    }
+
+    match state.auth_handler.register(tenant.id(), user.into_inner()).await {
         Ok(response) => HttpResponse::Created().json(response),
         Err(e) => e.to_http_response(),
     }
@@ -21,9 +33,10 @@ pub async fn register(
 #[post("/login")]
 pub async fn login(
     state: web::Data<AppState>,
+    tenant: CurrentTenant,
     user: web::Json<LoginUser>
 ) -> impl Responder {
-    match state.auth_handler.login(user.into_inner()).await {
+    match state.auth_handler.login(tenant.id(), user.into_inner()).await {
         Ok(auth_response) => HttpResponse::Ok().json(auth_response), 
         Err(e) => handle_auth_handler_error(e),
     }
@@ -32,9 +45,10 @@ pub async fn login(
 #[post("/refresh-token")]
 pub async fn refresh_token(
     state: web::Data<AppState>,
+    tenant: CurrentTenant,
     request: web::Json<RefreshTokenRequest>,
 ) -> impl Responder {
-    match state.auth_handler.refresh_token(&request.refresh_token, &state).await {
+    match state.auth_handler.refresh_token(tenant.id(), &request.refresh_token, &state).await {
         Ok(auth_response) => HttpResponse::Ok().json(AuthResponse {
             access_token: auth_response.access_token,
             refresh_token: auth_response.refresh_token,
@@ -78,3 +92,57 @@ pub async fn admin_dashboard(
         "message": format!("Welcome, admin {}", admin.0.sub)
     }))
 }
+
+
+/// Always 202, so the response does not tell whether the email has an account
+#[post("/password-reset")]
+pub async fn request_password_reset(
+    state: web::Data<AppState>,
+    tenant: CurrentTenant,
+    request: web::Json<PasswordResetRequest>,
+) -> Result<HttpResponse, AppError> {
+    state.account_emails.request_password_reset(&tenant.0, request.into_inner()).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+/// 204 while the reset link can be used, 404 otherwise
+#[get("/password-reset")]
+pub async fn check_password_reset(
+    state: web::Data<AppState>,
+    tenant: CurrentTenant,
+    query: web::Query<OneTimeTokenQuery>,
+) -> Result<HttpResponse, AppError> {
+    state.account_emails.check_password_reset(&tenant.id(), &query.token).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/password-reset/confirm")]
+pub async fn confirm_password_reset(
+    state: web::Data<AppState>,
+    tenant: CurrentTenant,
+    request: web::Json<PasswordResetConfirmRequest>,
+) -> Result<HttpResponse, AppError> {
+    state.account_emails.reset_password(&tenant.id(), request.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/verify-email")]
+pub async fn send_verification_email(
+    state: web::Data<AppState>,
+    tenant: CurrentTenant,
+    claims: AuthClaims,
+) -> Result<HttpResponse, AppError> {
+    let user_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+    state.account_emails.send_verification(&tenant.0, user_id).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[post("/verify-email/confirm")]
+pub async fn confirm_email_verification(
+    state: web::Data<AppState>,
+    tenant: CurrentTenant,
+    request: web::Json<EmailVerificationConfirmRequest>,
+) -> Result<HttpResponse, AppError> {
+    state.account_emails.verify_email(&tenant.id(), request.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
@@ -1,18 +1,49 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{http::{header::ContentDisposition, StatusCode}, web, HttpRequest, HttpResponse, Responder};
 use tracing::{info, instrument};
 
-use crate::{entities::blog_post::{NewBlogPostRequest, UpdateBlogPostRequest}, errors::AppError, use_cases::extractors::AdminClaims, AppState};
+use uuid::Uuid;
 
-#[instrument(skip(_claims, state, data))]
+use crate::{
+    entities::{
+        blog_post::{AddPostAuthorRequest, AdminSearchQuery, AuthoredPost, CalendarQuery, NewBlogPostRequest, ReschedulePostRequest, SetFeaturedPostsRequest, SlugCheckQuery, SuggestTagsRequest, UpdateBlogPostRequest},
+        content_scan::{ContentScanRequest, ContentScanResponse, WithContentWarnings},
+        option_fields::OptionField,
+        edit_lock::{EditLockConflict, EditLockQuery, EditLockResponse, PostEditLock},
+        tenant::Tenant,
+        title_test::{PromoteTitleRequest, SetTitleTestRequest},
+        view::Viewed,
+    },
+    errors::AppError,
+    use_cases::{
+        content_safety::ContentOrigin,
+        edit_locks::EditLockOutcome,
+        extractors::{AdminClaims, CurrentTenant, EditorClaims},
+        prewarm::{featured_posts_key, post_detail_key, post_list_key, DEFAULT_POSTS_PER_PAGE},
+    },
+    AppState,
+};
+
+#[instrument(skip(req, claims, tenant, state, data))]
 pub async fn create_blog_post(
-    _claims: AdminClaims,
+    req: HttpRequest,
+    claims: EditorClaims,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     data: web::Json<NewBlogPostRequest>
 ) -> Result<impl Responder, AppError> {
+    let fields = [
+        ("title", data.title.as_str()),
+        ("excerpt", data.excerpt.as_deref().unwrap_or_default()),
+        ("content_markdown", data.content_markdown.as_str()),
+    ];
+    let content_warnings = state.content_safety
+        .check(tenant.id(), &ContentOrigin::from_request(&req, &state.trusted_proxies), &fields)
+        .await?;
+
     let blog_post_handler = &state.blog_handler;
 
     let response = blog_post_handler
-        .create_blog_post(data.into_inner())
+        .create_blog_post(&tenant.0, claims.post_editor()?, data.into_inner())
         .await?;
 
     info!(
@@ -21,11 +52,12 @@ pub async fn create_blog_post(
         "✅ Blog post created successfully"
     );
 
-    Ok(HttpResponse::Created().json(response))
+    Ok(HttpResponse::Created().json(WithContentWarnings { inner: response, content_warnings }))
 }
 
-#[instrument(skip(state, query))]
+#[instrument(skip(tenant, state, query))]
 pub async fn get_all_blog_posts(
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> Result<impl Responder, AppError> {
@@ -34,18 +66,31 @@ pub async fn get_all_blog_posts(
     let page = query.get("page").and_then(|v| v.parse::<u32>().ok()).unwrap_or(1);
     let per_page = query.get("per_page")
         .and_then(|v| v.parse::<u32>().ok())
-        .unwrap_or(10)
+        .unwrap_or(DEFAULT_POSTS_PER_PAGE)
         .min(100);
 
-    let posts = blog_post_handler
-        .get_all_blog_posts(true, page, per_page)
+    // Pre-rendered pages would pin one title variant
+    let key = post_list_key(&tenant.0, page, per_page);
+    let cached = if state.title_tests.is_testing(&tenant.id()) {
+        None
+    } else {
+        state.prewarmer.cached(&key).await
+    };
+    if let Some(cached) = cached {
+        return Ok(HttpResponse::Ok().content_type(cached.content_type).body(cached.body));
+    }
+
+    let mut posts = state.prewarmer
+        .load_posts(&key, || blog_post_handler.get_all_blog_posts(tenant.id(), true, page, per_page))
         .await?;
+    serve_title_variants(&state, tenant.id(), &mut posts);
 
-    Ok(HttpResponse::Ok().json(posts))
+    Ok(HttpResponse::Ok().json(Viewed::public(posts)))
 }
 
-#[instrument(skip(state, query))]
+#[instrument(skip(tenant, state, query))]
 pub async fn get_recent_blog_posts(
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> Result<impl Responder, AppError> {
@@ -57,69 +102,185 @@ pub async fn get_recent_blog_posts(
         .min(50);
 
 
-    let posts = blog_post_handler.get_recent_blog_posts(limit, true).await?;
+    let mut posts = blog_post_handler.get_recent_blog_posts(tenant.id(), limit, true).await?;
+    serve_title_variants(&state, tenant.id(), &mut posts);
 
-    Ok(HttpResponse::Ok().json(posts))
+    Ok(HttpResponse::Ok().json(Viewed::public(posts)))
 }
 
-#[instrument(skip(post_id, state))]
+#[instrument(skip(tenant, state))]
+pub async fn get_featured_posts(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let key = featured_posts_key(&tenant.0);
+    let cached = if state.title_tests.is_testing(&tenant.id()) {
+        None
+    } else {
+        state.prewarmer.cached(&key).await
+    };
+    if let Some(cached) = cached {
+        return Ok(HttpResponse::Ok().content_type(cached.content_type).body(cached.body));
+    }
+
+    let mut posts = state.prewarmer
+        .load_posts(&key, || state.blog_handler.get_featured_posts(tenant.id(), true))
+        .await?;
+    serve_title_variants(&state, tenant.id(), &mut posts);
+
+    Ok(HttpResponse::Ok().json(Viewed::public(posts)))
+}
+
+#[instrument(skip(username, tenant, state, query))]
+pub async fn get_author_posts(
+    username: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> Result<impl Responder, AppError> {
+    let page = query.get("page").and_then(|v| v.parse::<u32>().ok()).unwrap_or(1).max(1);
+    let per_page = query.get("per_page")
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_POSTS_PER_PAGE)
+        .clamp(1, 100);
+
+    let mut posts = state.blog_handler.get_author_posts(tenant.id(), &username, page, per_page).await?;
+    serve_title_variants(&state, tenant.id(), &mut posts.posts);
+
+    Ok(HttpResponse::Ok().json(Viewed::public(posts)))
+}
+
+#[instrument(skip(post_id, tenant, state))]
 pub async fn get_blog_post_by_id(
     post_id: web::Path<String>,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
 ) -> Result<impl Responder, AppError> {
     let blog_post_handler = &state.blog_handler;
 
-    let post = blog_post_handler.get_blog_post_by_id(&post_id).await?;
-    Ok(HttpResponse::Ok().json(post))
+    // Malformed ids are rejected by the handler without touching the database
+    let Ok(id) = post_id.parse::<Uuid>() else {
+        let post = blog_post_handler.get_blog_post_by_id(tenant.id(), &post_id).await?;
+        return Ok(HttpResponse::Ok().json(Viewed::public(post)));
+    };
+
+    let key = post_detail_key(&tenant.0, &id);
+    if let Some(cached) = state.prewarmer.cached(&key).await {
+        return Ok(HttpResponse::Ok().content_type(cached.content_type).body(cached.body));
+    }
+
+    let post = state.prewarmer
+        .load_post(&key, || blog_post_handler.get_blog_post_by_id(tenant.id(), &post_id))
+        .await?;
+    Ok(HttpResponse::Ok().json(Viewed::public(post)))
+}
+
+/// Latest share counts per network, refreshed in the background
+#[instrument(skip(tenant, state))]
+pub async fn get_post_share_counts(
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let counts = state.share_counts.post_share_counts(tenant.id(), &post_id).await?;
+    Ok(HttpResponse::Ok().json(counts))
 }
 
-#[instrument(skip(_claims, post_id, state, data))]
+#[instrument(skip(req, claims, post_id, tenant, state, data))]
 pub async fn update_blog_post(
-    _claims: AdminClaims,
+    req: HttpRequest,
+    claims: EditorClaims,
     post_id: web::Path<String>,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     data: web::Json<UpdateBlogPostRequest>,
 ) -> Result<impl Responder, AppError> {
+    let editor = claims.post_editor()?;
+    if let Ok(id) = post_id.parse::<Uuid>()
+        && let Some(lock) = state.edit_locks.held_by_other(&tenant.0, editor, &id).await
+    {
+        return Ok(edit_locked(lock));
+    }
+
+    // Only what the request changes; the rest was scanned when it was saved
+    let fields: Vec<(&str, &str)> = [("title", &data.title), ("excerpt", &data.excerpt), ("content_markdown", &data.content_markdown)]
+        .into_iter()
+        .filter_map(|(field, value)| match value {
+            OptionField::SetToValue(text) => Some((field, text.as_str())),
+            _ => None,
+        })
+        .collect();
+    let content_warnings = state.content_safety
+        .check(tenant.id(), &ContentOrigin::from_request(&req, &state.trusted_proxies), &fields)
+        .await?;
+
     let blog_post_handler = &state.blog_handler;
-    let updated_post = blog_post_handler.update_blog_post(&post_id, &data.into_inner()).await?;
+    let updated_post = blog_post_handler
+        .update_blog_post(tenant.id(), editor, &post_id, &data.into_inner())
+        .await?;
+
+    refresh_post_pages(&state, tenant.0, &updated_post);
     
     info!(
-        id = %updated_post.id,
-        slug = %updated_post.slug,
+        id = %updated_post.post.id,
+        slug = %updated_post.post.slug,
         "📝 Blog post updated successfully"
     );
 
-    Ok(HttpResponse::Ok().json(updated_post))
+    Ok(HttpResponse::Ok().json(WithContentWarnings { inner: updated_post, content_warnings }))
 }
 
-#[instrument(skip(_claims, post_id, state))]
+#[instrument(skip(claims, post_id, tenant, state))]
 pub async fn publish_blog_post(
-    _claims: AdminClaims,
+    claims: EditorClaims,
     post_id: web::Path<String>,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
 ) -> Result<impl Responder, AppError> {
     let blog_post_handler = &state.blog_handler;
-    let published_post = blog_post_handler.publish_blog_post(&post_id).await?;
+    let published_post = blog_post_handler.publish_blog_post(tenant.id(), claims.post_editor()?, &post_id).await?;
+
+    // Warm caches off the request path so launch traffic finds them hot
+    let prewarmer = state.prewarmer.clone();
+    let id = published_post.post.id;
+    actix_web::rt::spawn(async move {
+        match prewarmer.warm_post(&tenant.0, id).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(post_id = %id, "Pre-warmed {} page(s)", count),
+            Err(e) => tracing::warn!(post_id = %id, "Page cache pre-warm failed: {}", e),
+        }
+    });
 
     info!(
-        id = %published_post.id,
-        slug = %published_post.slug,
+        id = %published_post.post.id,
+        slug = %published_post.post.slug,
         "🚀 Blog post published successfully"
     );
 
     Ok(HttpResponse::Ok().json(published_post))
 }
 
-#[instrument(skip(_claims, post_id, state, query))]
+#[instrument(skip(claims, post_id, tenant, state, query))]
 pub async fn delete_blog_post(
-    _claims: AdminClaims,
+    claims: EditorClaims,
     post_id: web::Path<String>,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> Result<impl Responder, AppError> {
     let blog_post_handler = &state.blog_handler;
     let hard_delete = query.get("hard_delete").map_or(false, |v| v == "true");
-    blog_post_handler.delete_blog_post(&post_id, hard_delete).await?;
+    blog_post_handler.delete_blog_post(tenant.id(), claims.post_editor()?, &post_id, hard_delete).await?;
+
+    if let Ok(id) = post_id.parse::<Uuid>() {
+        let prewarmer = state.prewarmer.clone();
+        let tenant = tenant.0.clone();
+        actix_web::rt::spawn(async move {
+            if let Err(e) = prewarmer.refresh_post(&tenant, id).await {
+                tracing::warn!(post_id = %id, "Page cache invalidation failed: {}", e);
+            }
+        });
+    }
 
     info!(
         post_id = %post_id,
@@ -132,9 +293,10 @@ pub async fn delete_blog_post(
 
 // Additional handlers for the admin interface can be added here
 // such as listing all posts including unpublished ones, etc.
-#[instrument(skip(_claims, state, query))]
+#[instrument(skip(_claims, tenant, state, query))]
 pub async fn admin_get_all_blog_posts(
     _claims: AdminClaims,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> Result<impl Responder, AppError> {
@@ -147,15 +309,16 @@ pub async fn admin_get_all_blog_posts(
         .min(100);
 
     let posts = blog_post_handler
-        .get_all_blog_posts(false, page, per_page)
+        .get_all_blog_posts(tenant.id(), false, page, per_page)
         .await?;
 
     Ok(HttpResponse::Ok().json(posts))
 }
 
-#[instrument(skip(_claims, state, query))]
+#[instrument(skip(_claims, tenant, state, query))]
 pub async fn admin_get_recent_blog_posts(
     _claims: AdminClaims,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> Result<impl Responder, AppError> {
@@ -166,6 +329,313 @@ pub async fn admin_get_recent_blog_posts(
         .unwrap_or(5)
         .min(50);
     
-    let posts = blog_post_handler.get_recent_blog_posts(limit, false).await?;
+    let posts = blog_post_handler.get_recent_blog_posts(tenant.id(), limit, false).await?;
     Ok(HttpResponse::Ok().json(posts))
+}
+
+#[instrument(skip(_claims, tenant, state, query))]
+pub async fn admin_check_slug(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<SlugCheckQuery>,
+) -> Result<impl Responder, AppError> {
+    let result = state.blog_handler.check_slug(tenant.id(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn admin_suggest_tags(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<SuggestTagsRequest>,
+) -> Result<impl Responder, AppError> {
+    let suggestions = state.tag_suggester.suggest(tenant.id(), data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+/// Runs the content scanner over a draft without saving or recording anything
+#[instrument(skip(_claims, state, data))]
+pub async fn admin_scan_content(
+    _claims: EditorClaims,
+    state: web::Data<AppState>,
+    data: web::Json<ContentScanRequest>,
+) -> Result<impl Responder, AppError> {
+    let warnings = state.content_safety.scan(&[("content_markdown", &data.content_markdown)]);
+    let blocked = warnings.iter().any(|warning| warning.finding.severity.blocks());
+    Ok(HttpResponse::Ok().json(ContentScanResponse { blocked, warnings }))
+}
+
+#[instrument(skip(_claims, tenant, state, query))]
+pub async fn admin_search_blog_posts(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<AdminSearchQuery>,
+) -> Result<impl Responder, AppError> {
+    let results = state.blog_handler.admin_search(tenant.id(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[instrument(skip(_claims, post_id, tenant, state, data))]
+pub async fn admin_add_post_author(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<AddPostAuthorRequest>,
+) -> Result<impl Responder, AppError> {
+    let post = state.blog_handler.add_post_author(tenant.id(), &post_id, data.into_inner()).await?;
+    refresh_post_pages(&state, tenant.0, &post);
+
+    info!(id = %post.post.id, authors = post.authors.len(), "✍️ Post bylines updated");
+    Ok(HttpResponse::Ok().json(post))
+}
+
+#[instrument(skip(_claims, path, tenant, state))]
+pub async fn admin_remove_post_author(
+    _claims: AdminClaims,
+    path: web::Path<(String, Uuid)>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let (post_id, user_id) = path.into_inner();
+    let post = state.blog_handler.remove_post_author(tenant.id(), &post_id, user_id).await?;
+    refresh_post_pages(&state, tenant.0, &post);
+
+    info!(id = %post.post.id, authors = post.authors.len(), "✍️ Post bylines updated");
+    Ok(HttpResponse::Ok().json(post))
+}
+
+#[instrument(skip(_claims, post_id, tenant, state))]
+pub async fn admin_list_post_revisions(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let revisions = state.blog_handler.list_revisions(tenant.id(), &post_id).await?;
+    Ok(HttpResponse::Ok().json(revisions))
+}
+
+#[instrument(skip(_claims, path, tenant, state))]
+pub async fn admin_diff_post_revisions(
+    _claims: AdminClaims,
+    path: web::Path<(String, i32, i32)>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let (post_id, from, to) = path.into_inner();
+    let diff = state.blog_handler.diff_revisions(tenant.id(), &post_id, from, to).await?;
+    Ok(HttpResponse::Ok().json(diff))
+}
+
+/// Takes the post's edit lock, or extends it as a heartbeat. 423 with the
+/// holder when someone else is editing; admins can pass `force=true`.
+#[instrument(skip(claims, tenant, state))]
+pub async fn admin_acquire_edit_lock(
+    claims: EditorClaims,
+    post_id: web::Path<String>,
+    query: web::Query<EditLockQuery>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let outcome = state.edit_locks
+        .acquire(&tenant.0, claims.post_editor()?, &claims.0.email, &post_id, query.force)
+        .await?;
+
+    Ok(match outcome {
+        EditLockOutcome::Acquired(lock) => HttpResponse::Ok().json(EditLockResponse {
+            lock,
+            next_beat_secs: state.edit_locks.next_beat_secs(),
+        }),
+        EditLockOutcome::HeldByOther(lock) => edit_locked(lock),
+    })
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn admin_get_edit_lock(
+    _claims: EditorClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let status = state.edit_locks.status(&tenant.0, &post_id).await?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn admin_release_edit_lock(
+    claims: EditorClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    state.edit_locks.release(&tenant.0, claims.post_editor()?, &post_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn edit_locked(lock: PostEditLock) -> HttpResponse {
+    HttpResponse::build(StatusCode::LOCKED).json(EditLockConflict::new(lock))
+}
+
+/// The post's markdown, revisions and stored images as a zip
+#[instrument(skip(_claims, tenant, state))]
+pub async fn admin_download_post_bundle(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let bundle = state.post_bundler.bundle(tenant.id(), &post_id).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header(ContentDisposition::attachment(bundle.filename))
+        .body(bundle.body))
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn admin_get_featured_posts(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let posts = state.blog_handler.get_featured_posts(tenant.id(), false).await?;
+    Ok(HttpResponse::Ok().json(posts))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn admin_set_featured_posts(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<SetFeaturedPostsRequest>,
+) -> Result<impl Responder, AppError> {
+    let (posts, changed) = state.blog_handler
+        .set_featured_posts(tenant.id(), data.into_inner())
+        .await?;
+
+    // Detail pages carry the featured flag, so changed posts are re-rendered too
+    let prewarmer = state.prewarmer.clone();
+    let tenant = tenant.0;
+    actix_web::rt::spawn(async move {
+        if let Err(e) = prewarmer.warm_featured(&tenant).await {
+            tracing::warn!("Featured page refresh failed: {}", e);
+        }
+        for id in changed {
+            if let Err(e) = prewarmer.refresh_post(&tenant, id).await {
+                tracing::warn!(post_id = %id, "Page cache refresh failed: {}", e);
+            }
+        }
+    });
+
+    info!(featured = posts.len(), "⭐ Featured posts updated");
+    Ok(HttpResponse::Ok().json(posts))
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn admin_get_calendar(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<CalendarQuery>,
+) -> Result<impl Responder, AppError> {
+    let calendar = state.blog_handler.get_calendar(tenant.id(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(calendar))
+}
+
+#[instrument(skip(_claims, post_id, tenant, state, data))]
+pub async fn admin_reschedule_post(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<ReschedulePostRequest>,
+) -> Result<impl Responder, AppError> {
+    let post = state.blog_handler.reschedule_post(tenant.id(), &post_id, data.into_inner()).await?;
+    refresh_post_pages(&state, tenant.0, &post);
+
+    info!(id = %post.post.id, published_at = ?post.post.published_at, "📅 Post rescheduled");
+    Ok(HttpResponse::Ok().json(post))
+}
+
+#[instrument(skip(_claims, post_id, tenant, state))]
+pub async fn admin_get_title_test(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let report = state.title_tests.report(tenant.id(), &post_id).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[instrument(skip(_claims, post_id, tenant, state, data))]
+pub async fn admin_set_title_test(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<SetTitleTestRequest>,
+) -> Result<impl Responder, AppError> {
+    let report = state.title_tests.start(tenant.id(), &post_id, data.into_inner()).await?;
+
+    info!(id = %report.post_id, variants = report.variants.len(), "🧪 Title test started");
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[instrument(skip(_claims, post_id, tenant, state))]
+pub async fn admin_end_title_test(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    state.title_tests.end(tenant.id(), &post_id).await?;
+
+    info!(post_id = %post_id, "🧪 Title test ended");
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[instrument(skip(_claims, post_id, tenant, state, data))]
+pub async fn admin_promote_title(
+    _claims: AdminClaims,
+    post_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<PromoteTitleRequest>,
+) -> Result<impl Responder, AppError> {
+    let variant = data.variant;
+    let post = state.title_tests.promote(tenant.id(), &post_id, data.into_inner()).await?;
+    refresh_post_pages(&state, tenant.0, &post);
+
+    info!(id = %post.post.id, variant, title = %post.post.title, "🏆 Title variant promoted");
+    Ok(HttpResponse::Ok().json(post))
+}
+
+/// Swaps in title test variants and counts the impressions off the request path
+fn serve_title_variants(state: &AppState, tenant_id: Uuid, posts: &mut [AuthoredPost]) {
+    let served = state.title_tests.serve(&tenant_id, posts);
+    if served.is_empty() {
+        return;
+    }
+
+    let title_tests = state.title_tests.clone();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = title_tests.record_impressions(tenant_id, &served).await {
+            tracing::warn!("Title test impressions not recorded: {}", e);
+        }
+    });
+}
+
+/// Only the pages showing the post are dropped and, if it is public, rendered again
+fn refresh_post_pages(state: &AppState, tenant: Tenant, post: &AuthoredPost) {
+    let prewarmer = state.prewarmer.clone();
+    let id = post.post.id;
+    actix_web::rt::spawn(async move {
+        if let Err(e) = prewarmer.refresh_post(&tenant, id).await {
+            tracing::warn!(post_id = %id, "Page cache refresh failed: {}", e);
+        }
+    });
 }
\ No newline at end of file
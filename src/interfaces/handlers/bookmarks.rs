@@ -0,0 +1,122 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::{
+        bookmark::{Bookmark, BookmarkQuery, NewBookmarkRequest, UpdateBookmarkRequest},
+        view::Viewed,
+    },
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// The public blogroll
+#[instrument(skip(tenant, state))]
+pub async fn list_bookmarks(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<BookmarkQuery>,
+) -> Result<impl Responder, AppError> {
+    let bookmarks = state.bookmark_handler
+        .list_bookmarks(tenant.id(), query.into_inner(), true)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(Viewed::public(bookmarks)))
+}
+
+/// Every bookmark, hidden ones and preview status included
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_all_bookmarks(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<BookmarkQuery>,
+) -> Result<impl Responder, AppError> {
+    let bookmarks = state.bookmark_handler
+        .list_bookmarks(tenant.id(), query.into_inner(), false)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(Viewed::admin(bookmarks)))
+}
+
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn create_bookmark(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<NewBookmarkRequest>,
+) -> Result<impl Responder, AppError> {
+    let bookmark = state.bookmark_handler
+        .create_bookmark(tenant.id(), data.into_inner())
+        .await?;
+
+    info!(
+        url = %bookmark.url,
+        admin = %claims.0.sub,
+        "Bookmark created"
+    );
+
+    spawn_preview_fetch(&state, bookmark.clone());
+    Ok(HttpResponse::Created().json(Viewed::admin(bookmark)))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn update_bookmark(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    data: web::Json<UpdateBookmarkRequest>,
+) -> Result<impl Responder, AppError> {
+    let (bookmark, url_changed) = state.bookmark_handler
+        .update_bookmark(tenant.id(), &id, data.into_inner())
+        .await?;
+
+    if url_changed {
+        spawn_preview_fetch(&state, bookmark.clone());
+    }
+    Ok(HttpResponse::Ok().json(Viewed::admin(bookmark)))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn delete_bookmark(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    state.bookmark_handler.delete_bookmark(tenant.id(), &id).await?;
+
+    info!(
+        id = %id,
+        admin = %claims.0.sub,
+        "Bookmark deleted"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Discards the stored preview and fetches it again
+#[instrument(skip(_claims, tenant, state))]
+pub async fn refresh_bookmark_preview(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let bookmark = state.bookmark_handler.reset_preview(tenant.id(), &id).await?;
+
+    spawn_preview_fetch(&state, bookmark.clone());
+    Ok(HttpResponse::Accepted().json(Viewed::admin(bookmark)))
+}
+
+/// Fetches right away so the admin does not wait for the background run
+fn spawn_preview_fetch(state: &AppState, bookmark: Bookmark) {
+    let handler = state.bookmark_handler.clone();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = handler.fetch_preview(&bookmark).await {
+            tracing::warn!(bookmark = %bookmark.id, "Failed to store link preview: {}", e);
+        }
+    });
+}
\ No newline at end of file
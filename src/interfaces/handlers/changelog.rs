@@ -0,0 +1,119 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::changelog::{
+        ChangelogQuery, DeployHookRequest, NewChangelogEntryRequest, UpdateChangelogEntryRequest,
+    },
+    entities::tenant::Tenant,
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+pub const DEPLOY_TOKEN_HEADER: &str = "X-Deploy-Token";
+
+#[instrument(skip(tenant, state))]
+pub async fn list_changelog(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<ChangelogQuery>,
+) -> Result<impl Responder, AppError> {
+    let entries = state.changelog_handler
+        .list_entries(tenant.id(), query.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn create_changelog_entry(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<NewChangelogEntryRequest>,
+) -> Result<impl Responder, AppError> {
+    let entry = state.changelog_handler
+        .create_entry(tenant.id(), data.into_inner())
+        .await?;
+    refresh_feed(&state, tenant.0);
+
+    info!(
+        version = %entry.version,
+        admin = %claims.0.sub,
+        "Changelog entry created"
+    );
+
+    Ok(HttpResponse::Created().json(entry))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn update_changelog_entry(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    data: web::Json<UpdateChangelogEntryRequest>,
+) -> Result<impl Responder, AppError> {
+    let entry = state.changelog_handler
+        .update_entry(tenant.id(), &id, data.into_inner())
+        .await?;
+    refresh_feed(&state, tenant.0);
+
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn delete_changelog_entry(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    state.changelog_handler.delete_entry(tenant.id(), &id).await?;
+    refresh_feed(&state, tenant.0);
+
+    info!(
+        id = %id,
+        admin = %claims.0.sub,
+        "Changelog entry deleted"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Called by CI after a deploy; authenticated by `X-Deploy-Token` rather than a JWT
+#[instrument(skip(req, tenant, state, data))]
+pub async fn deploy_hook(
+    req: HttpRequest,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<DeployHookRequest>,
+) -> Result<impl Responder, AppError> {
+    let token = req.headers()
+        .get(DEPLOY_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok());
+    state.changelog_handler.verify_deploy_token(token)?;
+
+    let (entry, created) = state.changelog_handler
+        .record_deploy(tenant.id(), data.into_inner())
+        .await?;
+
+    if created {
+        refresh_feed(&state, tenant.0);
+        info!(version = %entry.version, "Deploy recorded in changelog");
+        Ok(HttpResponse::Created().json(entry))
+    } else {
+        Ok(HttpResponse::Ok().json(entry))
+    }
+}
+
+/// The RSS feed lists changelog entries; pages without them are left alone
+fn refresh_feed(state: &AppState, tenant: Tenant) {
+    let prewarmer = state.prewarmer.clone();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = prewarmer.refresh_changelog(&tenant).await {
+            tracing::warn!("Feed cache refresh failed: {}", e);
+        }
+    });
+}
@@ -1,31 +1,95 @@
-use actix_web::{web, Error, HttpResponse, Responder};
+use actix_web::{http::header::ContentDisposition, web, Error, HttpRequest, HttpResponse, Responder};
+use chrono::Utc;
+use futures::TryStreamExt;
 
-use crate::{entities::contact_me::NewContactMeForm, AppState};
+use crate::{
+    entities::{contact_me::{ContactMeExportQuery, NewContactMeForm}, view::Viewed},
+    errors::AppError,
+    limiter::headers::{too_many_requests, RateLimitStatus},
+    use_cases::{captcha::CaptchaForm, content_safety::ContentOrigin, extractors::{AdminClaims, CurrentTenant}},
+    utils::get_client_ip::get_client_ip,
+    AppState,
+};
 
 
 const EMAIL_LIMIT: u32 = 2;
 const EMAIL_WINDOW_SECS: usize = 3600;
+/// Per visitor rather than per IP, so one busy office or carrier NAT does not
+/// use up everyone's allowance
+const VISITOR_LIMIT: u32 = 5;
+const VISITOR_WINDOW_SECS: usize = 3600;
 
 pub async fn create_contact_me(
+    req: HttpRequest,
+    tenant: CurrentTenant,
     state: web::Data<AppState>,
     form: web::Json<NewContactMeForm>,
 ) -> Result<impl Responder, Error> {
+    state.captcha.check_request(&tenant.id(), CaptchaForm::Contact, &req, &state.trusted_proxies).await?;
+
+    let visitor = state.visitor_tokens.identify(tenant.id(), &req);
+    let visitor_key = tenant.0.cache_key(&format!("rl:contact:{}", visitor));
+    let (visitor_cnt, visitor_reset) = state.redis_incr_with_ttl(&visitor_key, VISITOR_WINDOW_SECS).await?;
+
+    if visitor_cnt > VISITOR_LIMIT {
+        return Ok(too_many_requests(
+            "contact:visitor",
+            RateLimitStatus::fixed_window(VISITOR_LIMIT, visitor_cnt, visitor_reset),
+            "Too many messages. Please try again later.",
+        ));
+    }
 
     // Normalize to lower_case and URL-encode to keep the Redis key safe
     let email_norm = form.email.trim().to_lowercase();
     let email_enc = urlencoding::encode(&email_norm);
 
-    let email_key = format!("rl:email:{}", email_enc);
-    let email_cnt = state.redis_incr_with_ttl(&email_key, EMAIL_WINDOW_SECS).await?;
+    let email_key = tenant.0.cache_key(&format!("rl:email:{}", email_enc));
+    let (email_cnt, email_reset) = state.redis_incr_with_ttl(&email_key, EMAIL_WINDOW_SECS).await?;
     
     if email_cnt > EMAIL_LIMIT {
-        return Ok(HttpResponse::TooManyRequests().json(serde_json::json!({
-            "error": "Too many messages from this email address. Please try again later."
-        })));
+        return Ok(too_many_requests(
+            "contact:email",
+            RateLimitStatus::fixed_window(EMAIL_LIMIT, email_cnt, email_reset),
+            "Too many messages from this email address. Please try again later.",
+        ));
     }
 
-    let response = state.contact_handler
-        .create_contact_message(form.into_inner()).await?;
+    // Flagged messages still go through; the findings are in security_events
+    let fields = [("subject", form.subject.as_deref().unwrap_or_default()), ("message", form.message.as_str())];
+    state.content_safety.check(tenant.id(), &ContentOrigin::from_request(&req, &state.trusted_proxies), &fields).await?;
+
+    // Written by the contact ingest task, which also nudges the outbox relay
+    let queued = state.contact_handler
+        .create_contact_message(tenant.id(), form.into_inner(), &get_client_ip(&req, &state.trusted_proxies));
+    let response = match queued {
+        Ok(response) => response,
+        Err(AppError::TooManyRequests(message)) => {
+            return Ok(too_many_requests("contact:queue", state.contact_handler.queue.shed_status(), &message));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(HttpResponse::Accepted().json(Viewed::public(response)))
+}
+
+/// Streams contact messages as a CSV attachment without buffering the full result set
+pub async fn export_contact_messages_csv(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<ContactMeExportQuery>,
+) -> Result<HttpResponse, AppError> {
+    let body = state.contact_handler
+        .export_contact_messages_csv(tenant.id(), query.into_inner())?
+        .map_err(|e| {
+            tracing::error!(error = %e, "Contact message export aborted mid-stream");
+            actix_web::error::ErrorInternalServerError(e.to_string())
+        });
+
+    let filename = format!("contact-messages-{}.csv", Utc::now().format("%Y%m%d-%H%M%S"));
 
-    Ok(HttpResponse::Created().json(response))
-}
\ No newline at end of file
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(ContentDisposition::attachment(filename))
+        .streaming(body))
+}
@@ -0,0 +1,82 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::tenant::NewTenantDomainRequest,
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_domains(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let domains = state.domains.list_domains(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(domains))
+}
+
+/// Registers a custom domain; the response carries the TXT record to publish
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn add_domain(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<NewTenantDomainRequest>,
+) -> Result<impl Responder, AppError> {
+    let domain = state.domains.add_domain(tenant.id(), data.into_inner()).await?;
+
+    info!(
+        hostname = %domain.hostname,
+        admin = %claims.0.sub,
+        "Custom domain added"
+    );
+
+    Ok(HttpResponse::Created().json(domain))
+}
+
+/// Current verification status, polled by the onboarding UI
+#[instrument(skip(_claims, tenant, state))]
+pub async fn get_domain(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    hostname: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let domain = state.domains.get_domain(tenant.id(), &hostname).await?;
+
+    Ok(HttpResponse::Ok().json(domain))
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn verify_domain(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    hostname: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let domain = state.domains.verify_domain(tenant.id(), &hostname).await?;
+
+    Ok(HttpResponse::Ok().json(domain))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn delete_domain(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    hostname: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    state.domains.remove_domain(tenant.id(), &hostname).await?;
+
+    info!(
+        hostname = %hostname,
+        admin = %claims.0.sub,
+        "Custom domain removed"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
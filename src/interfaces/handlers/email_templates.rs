@@ -0,0 +1,51 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{
+    entities::{
+        app_setting::EMAIL_LOCALE,
+        email_template::{EmailPreviewQuery, EmailTemplateListResponse, PreviewFormat},
+    },
+    errors::AppError,
+    mailer::templates::{render_sample, EmailBrand, Locale, TEMPLATE_NAMES},
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+#[instrument(skip(_claims))]
+pub async fn list_email_templates(_claims: AdminClaims) -> Result<impl Responder, AppError> {
+    Ok(HttpResponse::Ok().json(EmailTemplateListResponse {
+        templates: TEMPLATE_NAMES.to_vec(),
+        locales: Locale::ALL.iter().map(Locale::code).collect(),
+    }))
+}
+
+/// Renders a template with sample data so admins can check layout and copy
+#[instrument(skip(_claims, tenant, state, query))]
+pub async fn preview_email_template(
+    _claims: AdminClaims,
+    name: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<EmailPreviewQuery>,
+) -> Result<impl Responder, AppError> {
+    let locale = match query.locale.as_deref() {
+        Some(tag) => Locale::from_tag(tag)
+            .ok_or_else(|| AppError::InvalidInput(format!("Unsupported locale '{}'", tag)))?,
+        None => state.settings.get(&tenant.id(), EMAIL_LOCALE).unwrap_or_default(),
+    };
+
+    // Like the emails sent for real, see `AccountEmails`
+    let site_url = state.public_urls.site_origin(&tenant.0);
+    let brand = EmailBrand {
+        name: tenant.0.name.clone(),
+        site_url: (!site_url.is_empty()).then_some(site_url),
+    };
+    let rendered = render_sample(&name, &brand, locale)?;
+
+    Ok(match query.format {
+        PreviewFormat::Json => HttpResponse::Ok().json(rendered),
+        PreviewFormat::Html => HttpResponse::Ok().content_type("text/html; charset=utf-8").body(rendered.html),
+        PreviewFormat::Text => HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(rendered.text),
+    })
+}
\ No newline at end of file
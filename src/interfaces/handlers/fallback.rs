@@ -0,0 +1,101 @@
+use actix_web::{
+    body::MessageBody,
+    dev::ServiceResponse,
+    http::{header, Method, StatusCode},
+    middleware::ErrorHandlerResponse,
+    web, HttpMessage, HttpRequest, HttpResponse,
+};
+use serde::Serialize;
+use tracing_actix_web::RequestId;
+
+use crate::{entities::tenant::Tenant, AppState};
+
+/// Body shared by the 404/405 fallbacks; mirrors `json_error` plus the request id
+#[derive(Serialize)]
+struct FallbackError {
+    code: u16,
+    error: &'static str,
+    message: String,
+    request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_methods: Option<Vec<String>>,
+}
+
+fn request_id(req: &HttpRequest) -> Option<String> {
+    req.extensions().get::<RequestId>().map(|id| id.to_string())
+}
+
+/// Default service for paths that match no route. GET and HEAD requests
+/// matching one of the tenant's redirect rules are sent on instead.
+pub async fn not_found(req: HttpRequest) -> HttpResponse {
+    if let Some(response) = legacy_redirect(&req) {
+        return response;
+    }
+
+    HttpResponse::NotFound().json(FallbackError {
+        code: StatusCode::NOT_FOUND.as_u16(),
+        error: "Not Found",
+        message: format!("No route for {} {}", req.method(), req.path()),
+        request_id: request_id(&req),
+        allowed_methods: None,
+    })
+}
+
+fn legacy_redirect(req: &HttpRequest) -> Option<HttpResponse> {
+    if !matches!(*req.method(), Method::GET | Method::HEAD) {
+        return None;
+    }
+
+    let state = req.app_data::<web::Data<AppState>>()?;
+    let tenant_id = req.extensions().get::<Tenant>()?.id;
+    let redirect = state.redirects.resolve(&tenant_id, req.path(), req.query_string())?;
+    let status = StatusCode::from_u16(redirect.status_code).ok()?;
+
+    Some(
+        HttpResponse::build(status)
+            .insert_header((header::LOCATION, redirect.location))
+            .finish()
+    )
+}
+
+/// Rewrites Actix's empty 405 into JSON listing the resource's allowed methods.
+/// Responses that already carry a body (Content-Type set) are left untouched.
+pub fn method_not_allowed<B>(res: ServiceResponse<B>) -> actix_web::Result<ErrorHandlerResponse<B>>
+where
+    B: MessageBody + 'static,
+{
+    if res.headers().contains_key(header::CONTENT_TYPE) {
+        return Ok(ErrorHandlerResponse::Response(res.map_into_left_body()));
+    }
+
+    let allow = res.headers().get(header::ALLOW).cloned();
+    let allowed_methods = allow
+        .as_ref()
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .split(',')
+                .map(|method| method.trim().to_string())
+                .filter(|method| !method.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let (req, _) = res.into_parts();
+
+    let mut builder = HttpResponse::MethodNotAllowed();
+    if let Some(allow) = allow {
+        builder.insert_header((header::ALLOW, allow));
+    }
+    let response = builder.json(FallbackError {
+        code: StatusCode::METHOD_NOT_ALLOWED.as_u16(),
+        error: "Method Not Allowed",
+        message: format!("{} is not supported for {}", req.method(), req.path()),
+        request_id: request_id(&req),
+        allowed_methods: Some(allowed_methods),
+    });
+
+    Ok(ErrorHandlerResponse::Response(
+        ServiceResponse::new(req, response).map_into_right_body()
+    ))
+}
@@ -0,0 +1,55 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    entities::feature_flag::UpdateFeatureFlagRequest,
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+#[instrument(skip(_claims, state, tenant))]
+pub async fn list_feature_flags(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let flags = state.feature_flags.list_flags(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(flags))
+}
+
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn update_feature_flag(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    key: web::Path<String>,
+    data: web::Json<UpdateFeatureFlagRequest>,
+) -> Result<impl Responder, AppError> {
+    let actor_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+    let flag = state.config_history
+        .set_flag(tenant.id(), actor_id, &key.into_inner(), data.into_inner())
+        .await?;
+
+    info!(
+        key = %flag.key,
+        tenant = %tenant.0.slug,
+        enabled = flag.enabled,
+        admin = %claims.0.sub,
+        "Feature flag updated"
+    );
+
+    Ok(HttpResponse::Ok().json(flag))
+}
+
+#[instrument(skip(_claims, state))]
+pub async fn refresh_feature_flags(
+    _claims: AdminClaims,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let loaded = state.feature_flags.refresh().await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "loaded": loaded })))
+}
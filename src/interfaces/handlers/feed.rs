@@ -0,0 +1,25 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{
+    errors::AppError,
+    use_cases::{extractors::CurrentTenant, prewarm::rss_feed_key},
+    AppState,
+};
+
+#[instrument(skip(tenant, state))]
+pub async fn rss_feed(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let key = rss_feed_key(&tenant.0);
+    if let Some(cached) = state.prewarmer.cached(&key).await {
+        return Ok(HttpResponse::Ok().content_type(cached.content_type).body(cached.body));
+    }
+
+    let rss = state.prewarmer.load_feed(&key, || state.feed_handler.rss(&tenant.0)).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/rss+xml; charset=utf-8")
+        .body(rss))
+}
\ No newline at end of file
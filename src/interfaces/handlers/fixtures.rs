@@ -0,0 +1,65 @@
+use actix_web::{http::header::ContentDisposition, web, HttpResponse};
+use chrono::Utc;
+use tracing::instrument;
+
+use crate::{
+    entities::content_fixture::{ContentFixture, FixtureLoadQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Downloads the tenant's posts and about page as a content fixture file
+#[instrument(skip(_claims, tenant, state))]
+pub async fn export_content_fixture(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, AppError> {
+    let fixture = state.fixtures.export(&tenant.0).await?;
+    let filename = format!("content-{}-{}.json", tenant.0.slug, Utc::now().format("%Y%m%d-%H%M%S"));
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentDisposition::attachment(filename))
+        .json(fixture))
+}
+
+/// Stores a content fixture in object storage and returns a download link
+#[instrument(skip(_claims, tenant, state))]
+pub async fn snapshot_content_fixture(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, AppError> {
+    let export = state.fixtures.snapshot(&tenant.0).await?;
+    Ok(HttpResponse::Created().json(export))
+}
+
+/// Loads a fixture file into the current tenant; `?dry_run=true` only reports the changes.
+/// A load that wrote posts rebuilds the tag suggestion index in the background.
+#[instrument(skip(_claims, tenant, state, body))]
+pub async fn load_content_fixture(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<FixtureLoadQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    let fixture: ContentFixture = serde_json::from_slice(&body)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid content fixture: {}", e)))?;
+
+    let report = state.fixtures.load(tenant.id(), &fixture, query.dry_run).await?;
+
+    if report.wrote_posts() {
+        let suggester = state.tag_suggester.clone();
+        let tenant = tenant.0;
+        actix_web::rt::spawn(async move {
+            match suggester.refresh(tenant.id).await {
+                Ok(posts) => tracing::info!(tenant = %tenant.slug, posts, "Tag index rebuilt after fixture load"),
+                Err(e) => tracing::warn!(tenant = %tenant.slug, "Tag index rebuild after fixture load failed: {}", e),
+            }
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(report))
+}
\ No newline at end of file
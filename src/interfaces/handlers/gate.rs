@@ -0,0 +1,68 @@
+use actix_web::{
+    cookie::{time::Duration as CookieDuration, Cookie, SameSite},
+    http::StatusCode,
+    post, web, HttpRequest, HttpResponse, Responder, ResponseError,
+};
+use validator::Validate;
+
+use crate::{
+    auth::site_gate::SITE_GATE_COOKIE,
+    entities::site_gate::{SiteGateRequest, SiteGateResponse},
+    errors::AuthError,
+    handlers::json_error::json_error,
+    limiter::headers::{too_many_requests, RateLimitStatus},
+    utils::get_client_ip::get_client_ip,
+    AppState,
+};
+
+const GATE_ATTEMPT_LIMIT: u32 = 5;
+const GATE_WINDOW_SECS: usize = 900;
+
+/// Exchanges the soft launch passphrase for a gate cookie (and token for non-browser clients)
+#[post("/gate")]
+pub async fn unlock_site(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    data: web::Json<SiteGateRequest>,
+) -> impl Responder {
+    let Some(gate) = state.site_gate.as_ref() else {
+        return json_error(StatusCode::NOT_FOUND, "Not found", "Site gate is not enabled");
+    };
+
+    if data.validate().is_err() {
+        return json_error(StatusCode::BAD_REQUEST, "Invalid input", "Passphrase is required");
+    }
+
+    // Best effort brute-force protection; the gate still works without Redis
+    let ip_key = format!("rl:gate:{}", get_client_ip(&req, &state.trusted_proxies));
+    match state.redis_incr_with_ttl(&ip_key, GATE_WINDOW_SECS).await {
+        Ok((attempts, reset)) if attempts > GATE_ATTEMPT_LIMIT => {
+            return too_many_requests(
+                "gate:passphrase",
+                RateLimitStatus::fixed_window(GATE_ATTEMPT_LIMIT, attempts, reset),
+                "Too many passphrase attempts. Please try again later."
+            );
+        }
+        Ok(_) | Err(AuthError::RedisNotConfigured) => {}
+        Err(e) => tracing::warn!("Gate attempt counter unavailable: {}", e),
+    }
+
+    let token = match gate.unlock(&data.passphrase) {
+        Ok(token) => token,
+        Err(e) => return e.error_response(),
+    };
+
+    let max_age = gate.ttl().num_seconds();
+    let cookie = Cookie::build(SITE_GATE_COOKIE, token.clone())
+        .path("/")
+        .http_only(true)
+        .secure(gate.secure_cookie())
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(max_age))
+        .finish();
+
+    HttpResponse::Ok().cookie(cookie).json(SiteGateResponse {
+        token,
+        expires_in: max_age,
+    })
+}
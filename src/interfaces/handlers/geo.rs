@@ -0,0 +1,46 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::geo::GeoReportQuery,
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Outbound clicks and honeytoken hits per country
+#[instrument(skip(claims, tenant, state))]
+pub async fn geo_report(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<GeoReportQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = state.geo.report(tenant.id(), query.into_inner()).await?;
+
+    info!(
+        admin = %claims.0.sub,
+        countries = report.countries.len(),
+        "Geo report generated"
+    );
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Re-reads the GeoLite2 database from `geoip_db_path`. The database is
+/// shared by every site on this server.
+#[instrument(skip(claims, state))]
+pub async fn reload_geo_database(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let database = state.geo.reload().await?;
+
+    info!(
+        admin = %claims.0.sub,
+        database_type = %database.database_type,
+        "🌍 GeoIP database reloaded"
+    );
+
+    Ok(HttpResponse::Ok().json(database))
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use actix_web::{web, Error, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::hire::NewHireInquiry,
+    errors::AppError,
+    limiter::headers::{too_many_requests, RateLimitStatus},
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+
+const EMAIL_LIMIT: u32 = 3;
+const EMAIL_WINDOW_SECS: usize = 86_400;
+
+/// Public availability card: open/closed, preferred roles and rate range
+pub async fn get_availability(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> impl Responder {
+    HttpResponse::Ok().json(state.hire_handler.availability(&tenant.id()))
+}
+
+pub async fn create_hire_inquiry(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    form: web::Json<NewHireInquiry>,
+) -> Result<impl Responder, Error> {
+    let email_norm = form.email.trim().to_lowercase();
+    let email_enc = urlencoding::encode(&email_norm);
+
+    let email_key = tenant.0.cache_key(&format!("rl:hire:email:{}", email_enc));
+    let (email_cnt, email_reset) = state.redis_incr_with_ttl(&email_key, EMAIL_WINDOW_SECS).await?;
+
+    if email_cnt > EMAIL_LIMIT {
+        return Ok(too_many_requests(
+            "hire:email",
+            RateLimitStatus::fixed_window(EMAIL_LIMIT, email_cnt, email_reset),
+            "Too many inquiries from this email address. Please try again later.",
+        ));
+    }
+
+    let response = state.hire_handler
+        .submit_inquiry(tenant.id(), form.into_inner()).await?;
+
+    // The notification was queued with the inquiry; have the relay send it now
+    state.outbox_relay.nudge();
+
+    Ok(HttpResponse::Created().json(response))
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_hire_inquiries(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let inquiries = state.hire_handler.list_inquiries(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(inquiries))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn delete_hire_inquiry(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    state.hire_handler.delete_inquiry(tenant.id(), &id).await?;
+
+    info!(
+        inquiry_id = %id,
+        admin = %claims.0.sub,
+        "Hire inquiry deleted"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
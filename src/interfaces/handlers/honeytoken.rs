@@ -0,0 +1,56 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use tracing::{info, instrument, warn};
+
+use crate::{
+    entities::security_event::SecurityReportQuery,
+    errors::AppError,
+    handlers::fallback,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    utils::get_client_ip::get_client_ip,
+    AppState,
+};
+
+/// Decoy for scanner paths: records the hit and answers exactly like an
+/// unknown route so nothing hints that it was noticed
+pub async fn decoy(
+    req: HttpRequest,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> HttpResponse {
+    let ip = get_client_ip(&req, &state.trusted_proxies);
+    let user_agent = req.headers()
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let (event, banned) = state.honeytokens.hit(&ip, user_agent, req.method().as_str(), req.path());
+    warn!(ip = %event.ip, path = %event.path, banned, "Honeytoken path requested");
+
+    let honeytokens = state.honeytokens.clone();
+    let tenant_id = tenant.id();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = honeytokens.record(tenant_id, &event).await {
+            warn!("Honeytoken hit not recorded: {}", e);
+        }
+    });
+
+    fallback::not_found(req).await
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn honeytoken_report(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<SecurityReportQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = state.honeytokens.report(tenant.id(), query.into_inner()).await?;
+
+    info!(
+        admin = %claims.0.sub,
+        total_hits = report.total_hits,
+        "Honeytoken report generated"
+    );
+
+    Ok(HttpResponse::Ok().json(report))
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::outbox::{JobListQuery, JobStatsQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Outbox events of the site, newest first, filtered by `status` and
+/// `event_type`
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_jobs(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<JobListQuery>,
+) -> Result<impl Responder, AppError> {
+    let jobs = state.jobs.list(tenant.id(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(jobs))
+}
+
+/// Per event type: open jobs now, success and failure rates over `?days=`
+#[instrument(skip(_claims, tenant, state))]
+pub async fn job_stats(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<JobStatsQuery>,
+) -> Result<impl Responder, AppError> {
+    let stats = state.jobs.stats(tenant.id(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn retry_job(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let job = state.jobs.retry(tenant.id(), &id).await?;
+    state.outbox_relay.nudge();
+
+    info!(
+        id = %job.id,
+        event_type = %job.event_type,
+        admin = %claims.0.sub,
+        "Job queued for retry"
+    );
+
+    Ok(HttpResponse::Ok().json(job))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn cancel_job(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let job = state.jobs.cancel(tenant.id(), &id, &claims.0.sub).await?;
+
+    info!(
+        id = %job.id,
+        event_type = %job.event_type,
+        admin = %claims.0.sub,
+        "Job cancelled"
+    );
+
+    Ok(HttpResponse::Ok().json(job))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn pause_job_queue(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    event_type: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let queue = state.jobs.pause(tenant.id(), &event_type, &claims.0.sub).await?;
+
+    info!(
+        event_type = %queue.event_type,
+        admin = %claims.0.sub,
+        "Job queue paused"
+    );
+
+    Ok(HttpResponse::Ok().json(queue))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn resume_job_queue(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    event_type: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    if state.jobs.resume(tenant.id(), &event_type).await? {
+        state.outbox_relay.nudge();
+
+        info!(
+            event_type = %event_type,
+            admin = %claims.0.sub,
+            "Job queue resumed"
+        );
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Progress of the derived-data backfills. They are shared by every site,
+/// so this is the same for all tenants.
+#[instrument(skip(_claims, state))]
+pub async fn backfill_progress(
+    _claims: AdminClaims,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let progress = state.backfills.progress().await?;
+    Ok(HttpResponse::Ok().json(progress))
+}
@@ -57,6 +57,16 @@ pub fn handle_handler_error(e: AppError) -> HttpResponse {
             "Service unavailable", 
             &msg
         ),
+        AppError::Timeout(msg) => json_error(
+            StatusCode::GATEWAY_TIMEOUT,
+            "Gateway timeout",
+            &msg
+        ),
+        AppError::TooManyRequests(msg) => json_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many requests",
+            &msg
+        ),
         _ => json_error(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Internal server error",
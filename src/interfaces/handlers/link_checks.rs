@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Broken links per post from the latest check run
+#[instrument(skip(_claims, tenant, state))]
+pub async fn link_check_report(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let report = state.link_checker.broken_links_report(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Starts a check run now instead of waiting for the background job
+#[instrument(skip(claims, tenant, state))]
+pub async fn run_link_check(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let checker = state.link_checker.clone();
+    let tenant = tenant.0;
+    actix_web::rt::spawn(async move {
+        match checker.check_tenant(tenant.id).await {
+            Ok(run) => info!(tenant = %tenant.slug, checked = run.checked, broken = run.broken, "🔗 Link check finished"),
+            Err(e) => tracing::warn!(tenant = %tenant.slug, "Link check failed: {}", e),
+        }
+    });
+
+    info!(admin = %claims.0.sub, "🔗 Link check started");
+    Ok(HttpResponse::Accepted().finish())
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::outbound::{OutboundLinkQuery, OutboundQrQuery, OutboundReportQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    utils::get_client_ip::get_client_ip,
+    AppState,
+};
+
+/// Records the click and 302s to an allowlisted syndication/social URL
+#[instrument(skip(req, tenant, state))]
+pub async fn redirect_outbound(
+    req: HttpRequest,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<OutboundLinkQuery>,
+) -> Result<impl Responder, AppError> {
+    let target = state.outbound.resolve_target(&tenant.id(), &query.url)?;
+
+    let referrer = req.headers()
+        .get(header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let request_host = req.connection_info().host().to_string();
+    let client_ip = get_client_ip(&req, &state.trusted_proxies);
+
+    // Record off the request path; a failed insert must not break the redirect
+    let outbound = state.outbound.clone();
+    let tenant_id = tenant.id();
+    let location = target.to_string();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = outbound.record_click(tenant_id, &target, referrer, &request_host, &client_ip).await {
+            tracing::warn!("Outbound click not recorded: {}", e);
+        }
+    });
+
+    Ok(HttpResponse::Found()
+        .insert_header((header::LOCATION, location))
+        .finish())
+}
+
+/// QR code of the tracked link for the owner to print; scans are recorded as clicks
+#[instrument(skip(_claims, req, tenant, state))]
+pub async fn outbound_qr_code(
+    _claims: AdminClaims,
+    req: HttpRequest,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<OutboundQrQuery>,
+) -> Result<impl Responder, AppError> {
+    let origin = {
+        let info = req.connection_info();
+        format!("{}://{}", info.scheme(), info.host())
+    };
+    let urls = state.public_urls.for_request(&req).or_base_url(&origin);
+    let png = state.outbound.qr_code(&tenant.0, &urls, query.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().content_type("image/png").body(png))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn outbound_click_report(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<OutboundReportQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = state.outbound.report(tenant.id(), query.into_inner()).await?;
+
+    info!(
+        admin = %claims.0.sub,
+        total_clicks = report.total_clicks,
+        "Outbound click report generated"
+    );
+
+    Ok(HttpResponse::Ok().json(report))
+}
\ No newline at end of file
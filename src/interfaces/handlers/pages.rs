@@ -0,0 +1,18 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{errors::AppError, use_cases::extractors::CurrentTenant, AppState};
+
+/// The about-me content as a standalone HTML page
+#[instrument(skip(req, tenant, state))]
+pub async fn about_page(
+    req: HttpRequest,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let html = state.site_pages.about(&tenant.0, &state.public_urls.for_request(&req)).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(html))
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{
+    entities::presence::{PresenceBeat, PresenceQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Paths the public widget endpoint returns at most
+const PUBLIC_PATH_LIMIT: usize = 20;
+/// Paths the admin view returns at most
+const ADMIN_PATH_LIMIT: usize = 100;
+
+#[instrument(skip(tenant, state, beat))]
+pub async fn presence_beat(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    beat: web::Json<PresenceBeat>,
+) -> Result<impl Responder, AppError> {
+    let response = state.presence.beat(&tenant.0, beat.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[instrument(skip(tenant, state))]
+pub async fn current_presence(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<PresenceQuery>,
+) -> Result<impl Responder, AppError> {
+    let snapshot = state.presence.snapshot(&tenant.0, query.into_inner(), PUBLIC_PATH_LIMIT).await?;
+    Ok(HttpResponse::Ok().json(snapshot))
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn admin_presence(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<PresenceQuery>,
+) -> Result<impl Responder, AppError> {
+    let snapshot = state.presence.snapshot(&tenant.0, query.into_inner(), ADMIN_PATH_LIMIT).await?;
+    Ok(HttpResponse::Ok().json(snapshot))
+}
\ No newline at end of file
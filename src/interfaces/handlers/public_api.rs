@@ -0,0 +1,49 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{
+    entities::public_api::{PublicApiIndex, PublicPostsQuery, PublicRateLimit, PUBLIC_API_LIMIT_ROUTE, PUBLIC_API_VERSION, PUBLIC_ENDPOINTS},
+    errors::AppError,
+    use_cases::extractors::CurrentTenant,
+    AppState,
+};
+
+/// The API's version, endpoints and the limits each client gets
+pub async fn public_api_index(state: web::Data<AppState>) -> impl Responder {
+    let (burst, per_minute) = state.rate_limiter.route_size(PUBLIC_API_LIMIT_ROUTE);
+
+    HttpResponse::Ok().json(PublicApiIndex {
+        version: PUBLIC_API_VERSION,
+        endpoints: PUBLIC_ENDPOINTS.to_vec(),
+        rate_limit: PublicRateLimit { burst, per_minute },
+    })
+}
+
+#[instrument(skip(tenant, state, query))]
+pub async fn public_posts(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<PublicPostsQuery>,
+) -> Result<impl Responder, AppError> {
+    let posts = state.public_api.posts(&tenant.0, &query).await?;
+    Ok(HttpResponse::Ok().json(posts))
+}
+
+#[instrument(skip(slug, tenant, state))]
+pub async fn public_post(
+    slug: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let post = state.public_api.post(&tenant.0, &slug).await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+#[instrument(skip(tenant, state))]
+pub async fn public_about(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let about = state.public_api.about(&tenant.0).await?;
+    Ok(HttpResponse::Ok().json(about))
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::rate_limit::RateLimitReport,
+    errors::AppError,
+    use_cases::extractors::AdminClaims,
+    AppState,
+};
+
+/// Clients currently tracked by the in-process rate limiter. Like the
+/// metrics, this covers the whole server rather than one site.
+#[instrument(skip(_claims, state))]
+pub async fn list_rate_limits(
+    _claims: AdminClaims,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let entries = state.rate_limiter.snapshot();
+    Ok(HttpResponse::Ok().json(RateLimitReport { total: entries.len(), entries }))
+}
+
+/// Forgets a client's bucket, e.g. after locking themselves out
+#[instrument(skip(claims, state))]
+pub async fn reset_rate_limit(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let key = path.into_inner();
+    if !state.rate_limiter.reset(&key) {
+        return Err(AppError::NotFound("No rate limit is tracked for this key".to_string()));
+    }
+
+    info!(admin = %claims.0.sub, key = %key, "🚦 Rate limit reset");
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
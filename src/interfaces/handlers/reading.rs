@@ -0,0 +1,51 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::{
+        reading::{ReadingProgressBatch, ReadingStatsQuery},
+        title_test::TitleClickEvent,
+    },
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+#[instrument(skip(tenant, state, batch))]
+pub async fn record_reading_progress(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    batch: web::Json<ReadingProgressBatch>,
+) -> Result<impl Responder, AppError> {
+    let response = state.reading.record(&tenant.0, batch.into_inner()).await?;
+    Ok(HttpResponse::Accepted().json(response))
+}
+
+/// Click on a post link whose title came from a title test
+#[instrument(skip(tenant, state, click))]
+pub async fn record_title_click(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    click: web::Json<TitleClickEvent>,
+) -> Result<impl Responder, AppError> {
+    state.title_tests.record_click(tenant.id(), click.into_inner()).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn reading_stats_report(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<ReadingStatsQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = state.reading.stats(&tenant.0, query.into_inner()).await?;
+
+    info!(
+        admin = %claims.0.sub,
+        posts = report.posts.len(),
+        "Reading stats report generated"
+    );
+
+    Ok(HttpResponse::Ok().json(report))
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Progress of the running rebuild, or the outcome of the last one
+#[instrument(skip(_claims, tenant, state))]
+pub async fn rebuild_status(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let progress = state.rebuilder
+        .progress(&tenant.id())
+        .ok_or_else(|| AppError::NotFound("No rebuild has run since the server started".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(progress))
+}
+
+/// Regenerates cached pages and feeds in the background; poll `GET` for progress
+#[instrument(skip(claims, tenant, state))]
+pub async fn start_rebuild(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let progress = state.rebuilder.begin(&tenant.0)?;
+
+    let rebuilder = state.rebuilder.clone();
+    let tenant = tenant.0;
+    actix_web::rt::spawn(async move {
+        match rebuilder.run(&tenant, |_| {}).await {
+            Ok(done) => info!(tenant = %tenant.slug, posts = done.posts, pages = done.pages, "🧱 Rebuild finished"),
+            Err(e) => tracing::warn!(tenant = %tenant.slug, "Rebuild failed: {}", e),
+        }
+    });
+
+    info!(admin = %claims.0.sub, "🧱 Rebuild started");
+    Ok(HttpResponse::Accepted().json(progress))
+}
\ No newline at end of file
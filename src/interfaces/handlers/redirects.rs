@@ -0,0 +1,83 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::redirect_rule::{NewRedirectRuleRequest, RedirectImportQuery, UpdateRedirectRuleRequest},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Every rule of the tenant with its hit count, oldest first
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_redirect_rules(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let rules = state.redirects.list(tenant.id()).await?;
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn create_redirect_rule(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<NewRedirectRuleRequest>,
+) -> Result<impl Responder, AppError> {
+    let rule = state.redirects.create(tenant.id(), data.into_inner()).await?;
+
+    info!(
+        source = %rule.source,
+        target = %rule.target,
+        admin = %claims.0.sub,
+        "Redirect rule created"
+    );
+
+    Ok(HttpResponse::Created().json(rule))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn update_redirect_rule(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    data: web::Json<UpdateRedirectRuleRequest>,
+) -> Result<impl Responder, AppError> {
+    let rule = state.redirects.update(tenant.id(), &id, data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn delete_redirect_rule(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    state.redirects.delete(tenant.id(), &id).await?;
+
+    info!(
+        id = %id,
+        admin = %claims.0.sub,
+        "Redirect rule deleted"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Creates rules from a CSV of `source,target[,status_code][,kind]`;
+/// `?dry_run=true` only reports what would be created
+#[instrument(skip(_claims, tenant, state, body))]
+pub async fn import_redirect_rules(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<RedirectImportQuery>,
+    body: web::Bytes,
+) -> Result<impl Responder, AppError> {
+    let report = state.redirects.import(tenant.id(), &body, query.dry_run).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    entities::retention::{AnonymizeContactsRequest, RetentionRunQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Policies in force and how many rows each would affect right now
+#[instrument(skip(claims, tenant, state))]
+pub async fn retention_report(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let report = state.retention.run(tenant.id(), true, &claims.0.sub).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Applies the policies now instead of waiting for the background job
+#[instrument(skip(claims, tenant, state))]
+pub async fn run_retention(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<RetentionRunQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = state.retention.run(tenant.id(), query.dry_run, &claims.0.sub).await?;
+
+    info!(
+        admin = %claims.0.sub,
+        dry_run = report.dry_run,
+        affected = report.total_affected(),
+        "🧹 Retention run finished"
+    );
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Anonymizes contact messages older than a threshold, outside the policy
+#[instrument(skip(claims, tenant, state))]
+pub async fn anonymize_contact_messages(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    body: web::Json<AnonymizeContactsRequest>,
+) -> Result<impl Responder, AppError> {
+    let report = state.retention.anonymize_contacts(tenant.id(), body.into_inner(), &claims.0.sub).await?;
+
+    info!(
+        admin = %claims.0.sub,
+        dry_run = report.dry_run,
+        affected = report.total_affected(),
+        "Contact messages anonymized"
+    );
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Gives an anonymized contact message its name and email back
+#[instrument(skip(claims, tenant, state))]
+pub async fn restore_contact_message(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    state.retention.restore_contact(tenant.id(), *id, &claims.0.sub).await?;
+
+    info!(
+        message_id = %id,
+        admin = %claims.0.sub,
+        "Contact message identity restored"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{
+    entities::schedule_preview::PreviewAtQuery,
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// The about revision and posts that would be live at `timestamp`
+#[instrument(skip(_claims, tenant, state))]
+pub async fn preview_at(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<PreviewAtQuery>,
+) -> Result<impl Responder, AppError> {
+    let preview = state.schedule_preview.preview_at(tenant.id(), query.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(preview))
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::{
+        series::{NewSeriesRequest, SetSeriesPostsRequest, UpdateSeriesRequest},
+        tenant::Tenant,
+        view::Viewed,
+    },
+    errors::AppError,
+    use_cases::{
+        extractors::{AdminClaims, CurrentTenant},
+        series::SeriesChange,
+    },
+    AppState,
+};
+
+#[instrument(skip(slug, tenant, state))]
+pub async fn get_series(
+    slug: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let series = state.series_handler.get_series(tenant.id(), &slug).await?;
+    Ok(HttpResponse::Ok().json(Viewed::public(series)))
+}
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn admin_list_series(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let series = state.series_handler.list_series(tenant.id()).await?;
+    Ok(HttpResponse::Ok().json(series))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn admin_create_series(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<NewSeriesRequest>,
+) -> Result<impl Responder, AppError> {
+    let series = state.series_handler.create_series(tenant.id(), data.into_inner()).await?;
+
+    info!(id = %series.id, slug = %series.slug, "📚 Series created");
+    Ok(HttpResponse::Created().json(series))
+}
+
+#[instrument(skip(_claims, series_id, tenant, state, data))]
+pub async fn admin_update_series(
+    _claims: AdminClaims,
+    series_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<UpdateSeriesRequest>,
+) -> Result<impl Responder, AppError> {
+    let series = state.series_handler.update_series(tenant.id(), &series_id, data.into_inner()).await?;
+
+    // Member pages show the series title and slug
+    let members = state.series_handler.get_series_posts(tenant.id(), &series_id).await?;
+    refresh_member_pages(&state, tenant.0, members.posts.iter().map(|authored| &authored.post).collect());
+
+    Ok(HttpResponse::Ok().json(series))
+}
+
+#[instrument(skip(_claims, series_id, tenant, state))]
+pub async fn admin_delete_series(
+    _claims: AdminClaims,
+    series_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let change = state.series_handler.delete_series(tenant.id(), &series_id).await?;
+    refresh_member_pages(&state, tenant.0, change);
+
+    info!(series_id = %series_id, "🗑️ Series deleted");
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[instrument(skip(_claims, series_id, tenant, state))]
+pub async fn admin_get_series_posts(
+    _claims: AdminClaims,
+    series_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let series = state.series_handler.get_series_posts(tenant.id(), &series_id).await?;
+    Ok(HttpResponse::Ok().json(series))
+}
+
+#[instrument(skip(_claims, series_id, tenant, state, data))]
+pub async fn admin_set_series_posts(
+    _claims: AdminClaims,
+    series_id: web::Path<String>,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<SetSeriesPostsRequest>,
+) -> Result<impl Responder, AppError> {
+    let (series, change) = state.series_handler
+        .set_series_posts(tenant.id(), &series_id, data.into_inner())
+        .await?;
+    refresh_member_pages(&state, tenant.0, change);
+
+    info!(series_id = %series.series.id, posts = series.posts.len(), "📚 Series posts updated");
+    Ok(HttpResponse::Ok().json(series))
+}
+
+/// Previous/next links live in each member's cached detail page
+fn refresh_member_pages(state: &AppState, tenant: Tenant, change: SeriesChange) {
+    if change.published.is_empty() && change.unpublished.is_empty() {
+        return;
+    }
+
+    let prewarmer = state.prewarmer.clone();
+    actix_web::rt::spawn(async move {
+        for id in change.published.into_iter().chain(change.unpublished) {
+            if let Err(e) = prewarmer.refresh_post(&tenant, id).await {
+                tracing::warn!(post_id = %id, "Page cache refresh failed: {}", e);
+            }
+        }
+    });
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    entities::{app_setting::UpdateAppSettingRequest, config_change::ConfigHistoryQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+#[instrument(skip(_claims, tenant, state))]
+pub async fn list_settings(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let settings = state.settings.list_settings(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn update_setting(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    key: web::Path<String>,
+    data: web::Json<UpdateAppSettingRequest>,
+) -> Result<impl Responder, AppError> {
+    let actor_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+    let setting = state.config_history
+        .update_setting(tenant.id(), actor_id, &key.into_inner(), data.into_inner().value)
+        .await?;
+
+    info!(
+        key = %setting.key,
+        admin = %claims.0.sub,
+        "Runtime setting updated"
+    );
+
+    Ok(HttpResponse::Ok().json(setting))
+}
+
+#[instrument(skip(_claims, tenant, state, query))]
+pub async fn settings_history(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<ConfigHistoryQuery>,
+) -> Result<impl Responder, AppError> {
+    let page = state.config_history.history(tenant.id(), query.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn rollback_settings(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    version: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let actor_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+    let rollback = state.config_history
+        .rollback(tenant.id(), actor_id, version.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(rollback))
+}
@@ -0,0 +1,45 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Progress of the running static export, or the outcome of the last one
+#[instrument(skip(_claims, tenant, state))]
+pub async fn static_export_status(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let progress = state.static_exporter
+        .progress(&tenant.id())
+        .ok_or_else(|| AppError::NotFound("No static export has run since the server started".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(progress))
+}
+
+/// Writes the public site as static files to object storage in the
+/// background; poll `GET` for progress and the manifest link
+#[instrument(skip(claims, tenant, state))]
+pub async fn start_static_export(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let progress = state.static_exporter.begin(&tenant.0)?;
+
+    let exporter = state.static_exporter.clone();
+    let tenant = tenant.0;
+    actix_web::rt::spawn(async move {
+        match exporter.run(&tenant).await {
+            Ok(done) => info!(tenant = %tenant.slug, files = done.files, prefix = %done.prefix, "📦 Static export finished"),
+            Err(e) => tracing::warn!(tenant = %tenant.slug, "Static export failed: {}", e),
+        }
+    });
+
+    info!(admin = %claims.0.sub, prefix = %progress.prefix, "📦 Static export started");
+    Ok(HttpResponse::Accepted().json(progress))
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::stats_rollup::{MonthlyStatsQuery, StatsRollupRunQuery},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Readers, clicks, attacks, top posts, referring pages and countries per month
+#[instrument(skip(_claims, tenant, state))]
+pub async fn monthly_stats(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<MonthlyStatsQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = state.stats_rollups.monthly(tenant.id(), query.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Refreshes the rollups of the last `days` days now instead of waiting for
+/// the background job
+#[instrument(skip(claims, tenant, state))]
+pub async fn run_stats_rollup(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<StatsRollupRunQuery>,
+) -> Result<impl Responder, AppError> {
+    let run = state.stats_rollups.run(tenant.id(), query.into_inner()).await?;
+
+    info!(admin = %claims.0.sub, from = %run.from, rows = run.rows, "📊 Stats rollup finished");
+    Ok(HttpResponse::Ok().json(run))
+}
\ No newline at end of file
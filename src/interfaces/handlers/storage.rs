@@ -0,0 +1,42 @@
+use actix_web::{
+    http::header::{self, ContentDisposition},
+    web, HttpResponse,
+};
+use futures::TryStreamExt;
+use tracing::instrument;
+
+use crate::{
+    entities::export::StorageDownloadQuery,
+    errors::AppError,
+    storage::object::content_type_for,
+    AppState,
+};
+
+/// Streams an object for a signed link. Expired, tampered or mismatched
+/// tokens all answer 404 so keys cannot be probed.
+#[instrument(skip(state, query))]
+pub async fn download_object(
+    state: web::Data<AppState>,
+    key: web::Path<String>,
+    query: web::Query<StorageDownloadQuery>,
+) -> Result<HttpResponse, AppError> {
+    let not_found = || AppError::NotFound("Object not found".to_string());
+
+    if !state.storage_links.verify(&key, &query.token) {
+        return Err(not_found());
+    }
+    let storage = state.storage.as_ref().ok_or_else(not_found)?;
+    let object = storage.get_stream(&key).await?;
+
+    let body = object.stream.map_err(|e| {
+        tracing::error!(error = %e, "Object download aborted mid-stream");
+        actix_web::error::ErrorInternalServerError(e.to_string())
+    });
+    let filename = key.rsplit('/').next().unwrap_or(&key).to_string();
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type_for(&key))
+        .insert_header((header::CONTENT_LENGTH, object.size))
+        .insert_header(ContentDisposition::attachment(filename))
+        .streaming(body))
+}
\ No newline at end of file
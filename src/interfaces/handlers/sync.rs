@@ -0,0 +1,19 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::instrument;
+
+use crate::{
+    entities::{sync::SyncQuery, view::Viewed},
+    errors::AppError,
+    use_cases::extractors::CurrentTenant,
+    AppState,
+};
+
+#[instrument(skip(tenant, state))]
+pub async fn sync_changes(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    query: web::Query<SyncQuery>,
+) -> Result<impl Responder, AppError> {
+    let changes = state.content_sync.changes_since(tenant.id(), query.since).await?;
+    Ok(HttpResponse::Ok().json(Viewed::public(changes)))
+}
\ No newline at end of file
@@ -8,7 +8,10 @@ use std::{
 };
 use sysinfo::System;
 use serde::Serialize;
-use crate::{constants::START_TIME, repositories::user::UserRepository, use_cases::extractors::AdminClaims, AppState};
+use crate::{
+    cache::redis_pool::RedisHealth, constants::START_TIME, metrics::METRICS, repositories::user::UserRepository,
+    use_cases::extractors::AdminClaims, AppState,
+};
 
 #[derive(Serialize, Clone, Default)]
 struct SystemInfo {
@@ -28,6 +31,8 @@ struct HealthCheckResponse {
     today_date: String,
     database: String,
     redis_status: String,
+    /// Topology and what the last supervisor check saw
+    redis: Option<RedisHealth>,
     version: String,
     memory_usage: String,
     system: SystemInfo,
@@ -75,6 +80,7 @@ async fn build_health_response(state: &web::Data<AppState>) -> HealthCheckRespon
         memory_usage,
         database: db_status.to_string(),
         redis_status: redis_status.to_string(),
+        redis: state.redis_pool.as_ref().map(|pool| pool.health()),
         system: system_info,
     }
 }
@@ -107,3 +113,54 @@ async fn admin_health_check(
         }
     }
 }
+
+/// Process counters, including requests admitted and shed per route scope
+#[get("/metrics")]
+async fn admin_metrics(_admin: AdminClaims) -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "counters": METRICS.snapshot(),
+        "timestamp": Utc::now().to_rfc3339(),
+    }))
+}
+
+
+#[derive(Serialize)]
+struct ReadinessResponse {
+    status: &'static str,
+    schema_version: Option<i64>,
+    bundled_version: Option<i64>,
+    drift: bool,
+    pending: Vec<i64>,
+    failed: Vec<i64>,
+}
+
+/// Ready once every migration this build bundles is applied. Drift does not
+/// fail the probe: during a blue/green deploy the old release keeps serving
+/// while the new one has migrated ahead of it.
+pub async fn readiness(state: web::Data<AppState>) -> HttpResponse {
+    let status = match state.schema.status().await {
+        Ok(status) => status,
+        Err(e) => {
+            tracing::warn!("Readiness check cannot read the schema: {}", e);
+            return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+                "status": "unavailable",
+                "error": "Database unavailable",
+            }));
+        }
+    };
+
+    let ready = status.is_up_to_date();
+    let body = ReadinessResponse {
+        status: if ready { "ready" } else { "migrating" },
+        schema_version: status.version,
+        bundled_version: status.bundled_version,
+        drift: status.has_drift(),
+        pending: status.pending,
+        failed: status.failed,
+    };
+
+    match ready {
+        true => HttpResponse::Ok().json(body),
+        false => HttpResponse::ServiceUnavailable().json(body),
+    }
+}
\ No newline at end of file
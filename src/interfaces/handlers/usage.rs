@@ -0,0 +1,32 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+/// Storage, database rows, Redis memory and emails sent, as last collected
+#[instrument(skip(_claims, tenant, state))]
+pub async fn usage_report(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let report = state.usage.report(&tenant.0).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Measures the site's usage now instead of waiting for the collector job
+#[instrument(skip(claims, tenant, state))]
+pub async fn refresh_usage(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let report = state.usage.refresh(&tenant.0).await?;
+
+    info!(admin = %claims.0.sub, tenant = %tenant.0.slug, "Usage report refreshed");
+    Ok(HttpResponse::Ok().json(report))
+}
@@ -1,10 +1,21 @@
-use actix_web::{http::StatusCode, web, HttpResponse, Responder};
+use std::{convert::Infallible, time::Duration};
+
+use actix_web::{http::StatusCode, web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use bytes::Bytes;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
 use uuid::Uuid;
 
 use crate::{ 
+    entities::{
+        notification::UpdateNotificationPreferencesRequest,
+        user::{EditorRoleRequest, UpdateProfileRequest},
+        user_import::{UserImportFormat, UserImportQuery},
+        view::{ResponseView, Viewed},
+    },
+    errors::AppError,
     handlers::json_error::{handle_handler_error, json_error}, 
     repositories::user::UserRepository, 
-    use_cases::extractors::AuthClaims, 
+    use_cases::{dispatcher::LiveNotification, extractors::{AdminClaims, AuthClaims, CurrentTenant}, user_import::parse_user_import},
     AppState
 };
 
@@ -23,8 +34,8 @@ pub async fn me(
         )}
     };
 
-    match state.auth_handler.me(user_id).await {
-        Ok(user) => HttpResponse::Ok().json(user),
+    match state.auth_handler.me(claims.0.tid, user_id).await {
+        Ok(user) => HttpResponse::Ok().json(Viewed::new(ResponseView::for_admin(claims.0.admin), user)),
         Err(e) => {
             tracing::warn!("User not found for ID: {}", user_id);
             handle_handler_error(e)
@@ -32,6 +43,139 @@ pub async fn me(
     }
 }
 
+pub async fn update_profile(
+    claims: AuthClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<UpdateProfileRequest>,
+) -> Result<impl Responder, AppError> {
+    let user_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+
+    let user = state.auth_handler.update_profile(claims.0.tid, user_id, data.into_inner()).await?;
+
+    // Cached posts show the author's name and avatar in their bylines
+    let prewarmer = state.prewarmer.clone();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = prewarmer.refresh_author(&tenant.0, user_id).await {
+            tracing::warn!(user_id = %user_id, "Byline cache refresh failed: {}", e);
+        }
+    });
+
+    Ok(HttpResponse::Ok().json(Viewed::new(ResponseView::for_admin(claims.0.admin), user)))
+}
+
+/// Comment lines sent on idle streams so proxies keep them open and closed
+/// connections are noticed
+const STREAM_KEEPALIVE_SECS: u64 = 15;
+
+pub async fn notification_preferences(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let user_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+
+    let preferences = state.notifications.preferences(claims.0.tid, user_id).await?;
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+pub async fn update_notification_preferences(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+    data: web::Json<UpdateNotificationPreferencesRequest>,
+) -> Result<impl Responder, AppError> {
+    let user_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+
+    let preferences = state.notifications.update_preferences(claims.0.tid, user_id, data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// Server-sent events for the admin's notifications on the `sse` channel.
+/// Each event is named after the notification's event type.
+pub async fn notification_stream(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let user_id = Uuid::parse_str(&claims.0.sub).map_err(|_| AppError::UnauthorizedAccess)?;
+    let tenant_id = claims.0.tid;
+
+    let keepalive = tokio::time::interval(Duration::from_secs(STREAM_KEEPALIVE_SECS));
+    let stream = futures::stream::unfold(
+        (state.notifications.subscribe(), keepalive),
+        move |(mut live, mut keepalive)| async move {
+            let frame = next_frame(&mut live, &mut keepalive, tenant_id, user_id).await?;
+            Some((Ok::<_, Infallible>(frame), (live, keepalive)))
+        },
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream))
+}
+
+/// The next SSE frame for the user: a notification or a keepalive comment.
+/// `None` ends the stream.
+async fn next_frame(
+    live: &mut Receiver<LiveNotification>,
+    keepalive: &mut tokio::time::Interval,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> Option<Bytes> {
+    loop {
+        tokio::select! {
+            _ = keepalive.tick() => return Some(Bytes::from_static(b": keepalive\n\n")),
+            received = live.recv() => match received {
+                Ok(item) if item.tenant_id == tenant_id && item.user_id == user_id => {
+                    let data = serde_json::to_string(&item.notification).ok()?;
+                    return Some(Bytes::from(format!("event: {}\ndata: {}\n\n", item.notification.event.as_str(), data)));
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!(user_id = %user_id, missed, "Notification stream fell behind");
+                    continue;
+                }
+                Err(RecvError::Closed) => return None,
+            },
+        }
+    }
+}
+
+pub async fn set_editor(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+    user_id: web::Path<Uuid>,
+    data: web::Json<EditorRoleRequest>,
+) -> Result<impl Responder, AppError> {
+    let user = state.auth_handler.set_editor(claims.0.tid, user_id.into_inner(), data.editor).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// Forces the user to sign in again, e.g. after a role or password change
+/// made outside the API
+pub async fn bump_claims_version(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+    user_id: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let version = state.auth_handler.bump_claims_version(claims.0.tid, user_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(version))
+}
+
+/// Imports accounts exported from another backend: CSV when the body is
+/// `text/csv`, a JSON array otherwise. `?dry_run=true` only reports the outcome.
+pub async fn import_users(
+    claims: AdminClaims,
+    state: web::Data<AppState>,
+    query: web::Query<UserImportQuery>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> Result<impl Responder, AppError> {
+    let format = UserImportFormat::from_content_type(req.content_type());
+    let rows = parse_user_import(format, &body)?;
+    let report = state.user_import.import(claims.0.tid, rows, query.dry_run).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
 pub async fn get_user(
     state: web::Data<AppState>,
     user_id: web::Path<Uuid>,
@@ -48,14 +192,14 @@ pub async fn get_user(
         }
     };
 
-    let current_user = match state.auth_handler.user_repo.get_user_by_id(&user_uuid).await {
+    let current_user = match state.auth_handler.user_repo.get_user_by_id(&claims.0.tid, &user_uuid).await {
         Ok(Some(user)) => user,
         Ok(None) => return HttpResponse::NotFound().json("User not found"),
         Err(e) => return handle_handler_error(e)
     };
 
     match state.auth_handler.get_current_user(user_id.into_inner(), &current_user).await {
-        Ok(user) => HttpResponse::Ok().json(user),
+        Ok(user) => HttpResponse::Ok().json(Viewed::new(ResponseView::for_admin(current_user.is_admin), user)),
         Err(e) => {
             tracing::warn!("User not found for ID: {}", user_uuid);
             handle_handler_error(e)
@@ -79,7 +223,7 @@ pub async fn delete_user(
         }
     };
 
-    let current_user = match state.auth_handler.user_repo.get_user_by_id(&user_uuid).await {
+    let current_user = match state.auth_handler.user_repo.get_user_by_id(&claims.0.tid, &user_uuid).await {
         Ok(Some(user)) => user,
         Ok(None) => return HttpResponse::NotFound().json("User not found"),
         Err(e) => return handle_handler_error(e)
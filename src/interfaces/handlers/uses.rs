@@ -0,0 +1,98 @@
+use actix_web::{web, HttpResponse, Responder};
+use tracing::{info, instrument};
+
+use crate::{
+    entities::uses::{NewUsesEntryRequest, ReorderUsesRequest, UpdateUsesEntryRequest},
+    errors::AppError,
+    use_cases::extractors::{AdminClaims, CurrentTenant},
+    AppState,
+};
+
+#[instrument(skip(tenant, state))]
+pub async fn get_uses(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let uses = state.uses_handler.list_grouped(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok().json(uses))
+}
+
+/// Same content as `get_uses`, rendered as Markdown for README embedding
+#[instrument(skip(tenant, state))]
+pub async fn export_uses_markdown(
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let markdown = state.uses_handler.export_markdown(tenant.id()).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/markdown; charset=utf-8")
+        .body(markdown))
+}
+
+#[instrument(skip(claims, tenant, state, data))]
+pub async fn create_uses_entry(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<NewUsesEntryRequest>,
+) -> Result<impl Responder, AppError> {
+    let entry = state.uses_handler.create_entry(tenant.id(), data.into_inner()).await?;
+
+    info!(
+        id = %entry.id,
+        category = %entry.category,
+        admin = %claims.0.sub,
+        "Uses entry created"
+    );
+
+    Ok(HttpResponse::Created().json(entry))
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn update_uses_entry(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    data: web::Json<UpdateUsesEntryRequest>,
+) -> Result<impl Responder, AppError> {
+    let entry = state.uses_handler
+        .update_entry(tenant.id(), &id, data.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+#[instrument(skip(claims, tenant, state))]
+pub async fn delete_uses_entry(
+    claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    state.uses_handler.delete_entry(tenant.id(), &id).await?;
+
+    info!(
+        id = %id,
+        admin = %claims.0.sub,
+        "Uses entry deleted"
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[instrument(skip(_claims, tenant, state, data))]
+pub async fn reorder_uses_entries(
+    _claims: AdminClaims,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+    data: web::Json<ReorderUsesRequest>,
+) -> Result<impl Responder, AppError> {
+    let uses = state.uses_handler
+        .reorder_entries(tenant.id(), data.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(uses))
+}
\ No newline at end of file
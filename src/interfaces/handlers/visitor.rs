@@ -0,0 +1,41 @@
+use actix_web::{
+    cookie::{time::Duration as CookieDuration, Cookie, SameSite},
+    web, Error, HttpRequest, HttpResponse, Responder,
+};
+use tracing::instrument;
+
+use crate::{
+    auth::visitor::VISITOR_COOKIE,
+    entities::visitor::VisitorTokenResponse,
+    use_cases::extractors::CurrentTenant,
+    AppState,
+};
+
+/// Issues (or renews) the anonymous visitor cookie, with the token for
+/// clients that send it as `X-Visitor-Token` instead. A visitor who already
+/// holds a valid token keeps their id.
+#[instrument(skip(req, tenant, state))]
+pub async fn visitor_token(
+    req: HttpRequest,
+    tenant: CurrentTenant,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, Error> {
+    let visitors = &state.visitor_tokens;
+    let visitor_id = visitors.identify(tenant.id(), &req);
+    let token = visitors.issue(tenant.id(), &visitor_id)?;
+
+    let max_age = visitors.ttl().num_seconds();
+    let cookie = Cookie::build(VISITOR_COOKIE, token.clone())
+        .path("/")
+        .http_only(true)
+        .secure(visitors.secure_cookie())
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(max_age))
+        .finish();
+
+    Ok(HttpResponse::Ok().cookie(cookie).json(VisitorTokenResponse {
+        visitor_id,
+        token,
+        expires_in: max_age,
+    }))
+}
\ No newline at end of file
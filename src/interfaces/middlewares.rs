@@ -1,3 +1,15 @@
 pub mod auth;
 pub mod logger;
-pub mod validator;
\ No newline at end of file
+pub mod validator;
+pub mod site_gate;
+pub mod load_shed;
+pub mod timeout;
+pub mod cache_control;
+pub mod tenant;
+pub mod ip_ban;
+pub mod body_log;
+pub mod rate_limit;
+pub mod api_token;
+pub mod error_report;
+pub mod api_version;
+pub mod forwarded_prefix;
\ No newline at end of file
@@ -0,0 +1,155 @@
+use actix_web::{
+    body::{BodySize, BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error, HttpMessage,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+use url::form_urlencoded;
+
+use crate::{
+    entities::{api_token::{API_TOKEN_HEADER, API_TOKEN_QUERY_PARAM}, tenant::Tenant},
+    errors::{AppError, AuthError},
+    limiter::headers::{too_many_requests, RateLimitStatus},
+    AppState,
+};
+
+/// Rate limiter route API token buckets are kept under
+const API_TOKEN_LIMIT_ROUTE: &str = "api-token";
+
+/// Requires an API token valid for `scope` on the scope's routes, then
+/// holds the token to its monthly quota and limits it with its own bucket.
+/// Usage is counted per token, and each response in its daily traffic.
+pub struct RequireApiToken {
+    scope: &'static str,
+}
+
+impl RequireApiToken {
+    pub fn scope(scope: &'static str) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequireApiToken
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequireApiTokenService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequireApiTokenService {
+            service: Rc::new(service),
+            scope: self.scope,
+        })
+    }
+}
+
+pub struct RequireApiTokenService<S> {
+    service: Rc<S>,
+    scope: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireApiTokenService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let scope = self.scope;
+
+        Box::pin(async move {
+            if req.method() == actix_web::http::Method::OPTIONS {
+                let downstream_res = service.call(req).await?;
+                return Ok(downstream_res.map_into_boxed_body());
+            }
+
+            let state = req.app_data::<web::Data<AppState>>()
+                .cloned()
+                .ok_or(AuthError::MissingAppState)?;
+            let Some(tenant_id) = req.extensions().get::<Tenant>().map(|tenant| tenant.id) else {
+                tracing::error!("Tenant missing in request extensions; is TenantMiddleware installed?");
+                let err = AppError::InternalError("Tenant not resolved".into());
+                return Ok(req.error_response(err).map_into_boxed_body());
+            };
+            let Some(presented) = extract_api_token(&req) else {
+                return Ok(req.error_response(AuthError::MissingApiToken).map_into_boxed_body());
+            };
+
+            let grant = match state.api_tokens.authorize(&tenant_id, &presented, scope) {
+                Ok(grant) => grant,
+                Err(e) => {
+                    tracing::warn!(scope, "API token rejected: {}", e);
+                    return Ok(req.error_response(e).map_into_boxed_body());
+                }
+            };
+
+            if let Err(retry_after) = state.api_tokens.check_quota(&grant) {
+                state.api_tokens.record_use(&grant.key, false);
+                let status = RateLimitStatus::exhausted(grant.monthly_quota.unwrap_or_default(), retry_after);
+                let response = too_many_requests("api_token:monthly_quota", status, "Monthly API quota exceeded");
+                return Ok(req.into_response(response));
+            }
+
+            let verdict = state.rate_limiter.check_sized(
+                API_TOKEN_LIMIT_ROUTE,
+                &grant.key.to_string(),
+                state.api_tokens.burst(),
+                grant.per_minute,
+            );
+            state.api_tokens.record_use(&grant.key, verdict.is_ok());
+
+            let status = match verdict {
+                Ok(status) => status,
+                Err(status) => {
+                    let response = too_many_requests("api_token:rate", status, "Too many requests");
+                    let res = req.into_response(response);
+                    state.api_tokens.record_traffic(&grant.key, body_bytes(&res), true);
+                    return Ok(res);
+                }
+            };
+
+            let mut downstream_res = service.call(req).await?;
+            status.apply(downstream_res.headers_mut());
+            let error = downstream_res.status().is_client_error() || downstream_res.status().is_server_error();
+            state.api_tokens.record_traffic(&grant.key, body_bytes(&downstream_res), error);
+
+            Ok(downstream_res.map_into_boxed_body())
+        })
+    }
+}
+
+/// The header wins over the query parameter
+fn extract_api_token(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(API_TOKEN_HEADER)
+        .and_then(|header| header.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .or_else(|| {
+            form_urlencoded::parse(req.query_string().as_bytes())
+                .find(|(key, _)| key == API_TOKEN_QUERY_PARAM)
+                .map(|(_, value)| value.trim().to_string())
+        })
+        .filter(|token| !token.is_empty())
+}
+
+/// Streamed bodies of unknown size count as zero
+fn body_bytes<B: MessageBody>(res: &ServiceResponse<B>) -> u64 {
+    match res.response().body().size() {
+        BodySize::Sized(bytes) => bytes,
+        BodySize::None | BodySize::Stream => 0,
+    }
+}
\ No newline at end of file
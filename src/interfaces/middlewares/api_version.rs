@@ -0,0 +1,90 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue},
+    Error, HttpResponse,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::entities::public_api::API_VERSION_HEADER;
+
+/// Stamps every response of a versioned scope with `X-API-Version`, rate
+/// limit and error responses included. A client pinning another version
+/// through the same request header gets 400 instead of a silently
+/// different payload.
+pub struct ApiVersion {
+    version: &'static str,
+}
+
+impl ApiVersion {
+    pub fn new(version: &'static str) -> Self {
+        Self { version }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiVersion
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ApiVersionService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ApiVersionService {
+            service: Rc::new(service),
+            version: self.version,
+        })
+    }
+}
+
+pub struct ApiVersionService<S> {
+    service: Rc<S>,
+    version: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiVersionService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let version = self.version;
+
+        Box::pin(async move {
+            let requested = req.headers()
+                .get(API_VERSION_HEADER)
+                .map(|value| value.to_str().unwrap_or_default().trim().to_string());
+
+            let mut res = match requested {
+                Some(requested) if requested != version => {
+                    let response = HttpResponse::BadRequest().json(serde_json::json!({
+                        "error": format!("Unsupported API version '{}'", requested),
+                        "supported": [version],
+                    }));
+                    req.into_response(response)
+                }
+                _ => service.call(req).await?.map_into_boxed_body(),
+            };
+
+            res.headers_mut().insert(
+                HeaderName::from_static("x-api-version"),
+                HeaderValue::from_static(version),
+            );
+            Ok(res)
+        })
+    }
+}
\ No newline at end of file
@@ -4,12 +4,15 @@ use actix_web::{
     web, Error, HttpMessage
 };
 use futures_util::future::{ok, Ready, LocalBoxFuture};
-use std::{rc::Rc, task::{Context, Poll}};
+use std::{rc::Rc, sync::Arc, task::{Context, Poll}};
 
 use crate::{
-    entities::token::Claims, 
+    cache::redis_pool::SupervisedPool,
+    entities::{security_event::is_honeytoken_path, tenant::Tenant, token::Claims}, 
     errors::AuthError, 
     is_token_invalid, 
+    settings::{AppConfig, RouteAccess},
+    utils::route_pattern::RoutePattern,
     AppState, 
     TokenCheckMode
 };
@@ -62,17 +65,18 @@ where
             let path = req.path();
             let method = req.method().as_str();
 
-            if is_public_route(path, method) {
-                let downstream_res = service.call(req).await?;
-                return Ok(downstream_res.map_into_boxed_body());
-            }
-
             let state = req.app_data::<web::Data<AppState>>()
                 .ok_or_else(|| {
                     tracing::error!("AppState missing in middleware");
                     AuthError::MissingAppState
                 })?;
 
+            let access = state.route_policy.access(path, method);
+            if access == RouteAccess::Public {
+                let downstream_res = service.call(req).await?;
+                return Ok(downstream_res.map_into_boxed_body());
+            }
+
             let token = match extract_token(&req) {
                 Some(token) => token,
                 None => {
@@ -90,22 +94,39 @@ where
                 }
             };
             
+            // A token issued for one tenant is never valid on another tenant's hosts
+            let tenant_mismatch = req.extensions()
+                .get::<Tenant>()
+                .is_some_and(|tenant| tenant.id != claims.tid);
+            if tenant_mismatch {
+                tracing::warn!(user_id = %claims.sub, "Token presented on another tenant's host");
+                return Ok(req.error_response(AuthError::InvalidToken).map_into_boxed_body());
+            }
+
             if let Some(redis_pool) = &state.redis_pool {
                 if let Err(e) = check_token_blacklist(redis_pool, &token).await {
                     return Ok(req.error_response(e).map_into_boxed_body());
                 }
             }
 
-            if !is_authorized(path, &claims) {
+            if let Err(e) = state.auth_handler.check_claims_version(&claims).await {
+                return Ok(req.error_response(e).map_into_boxed_body());
+            }
+
+            if !is_authorized(access, &claims) {
+                let role = if access == RouteAccess::Admin { "Admin" } else { "Editor" };
                 let error = AuthError::Forbidden(format!(
-                    "Admin access required. User {} is not an admin",
-                    claims.sub
+                    "{} access required. User {} is not an {}",
+                    role,
+                    claims.sub,
+                    role.to_ascii_lowercase()
                 ));
                 tracing::warn!(
-                    "Access denied - Path: {}, User ID: {}, Admin: {}",
+                    "Access denied - Path: {}, User ID: {}, Admin: {}, Editor: {}",
                     path,
                     claims.sub,
-                    claims.admin
+                    claims.admin,
+                    claims.editor
                 );
                 return Ok(req.error_response(error).map_into_boxed_body());
             }
@@ -117,53 +138,118 @@ where
     }
 }
 
-/// Maps AuthError variants to appropriate
+/// Built-in `[route_policy]` rules, checked after the configured ones.
+/// Everything else under `/api` needs a signed-in user; reads outside it
+/// are public pages and the 404 fallback, which resolves legacy redirects.
+const DEFAULT_ROUTE_RULES: &[(&str, &[&str], RouteAccess)] = &[
+    ("/api/v1/auth/register", &["POST"], RouteAccess::Public),
+    ("/api/v1/auth/login", &["POST"], RouteAccess::Public),
+    ("/api/v1/auth/refresh-token", &["POST"], RouteAccess::Public),
+    ("/api/v1/auth/password-reset", &["GET", "POST"], RouteAccess::Public),
+    ("/api/v1/auth/password-reset/confirm", &["POST"], RouteAccess::Public),
+    ("/api/v1/auth/verify-email/confirm", &["POST"], RouteAccess::Public),
+    ("/api/v1/gate", &["POST"], RouteAccess::Public),
+    ("/api/v1/contact-me", &["POST"], RouteAccess::Public),
+    ("/api/v1/hire", &["GET"], RouteAccess::Public),
+    ("/api/v1/hire/inquiries", &["POST"], RouteAccess::Public),
+    ("/api/v1/uses", &["GET"], RouteAccess::Public),
+    ("/api/v1/uses/export.md", &["GET"], RouteAccess::Public),
+    ("/api/v1/changelog", &["GET"], RouteAccess::Public),
+    ("/api/v1/bookmarks", &["GET"], RouteAccess::Public),
+    ("/api/v1/feed/rss.xml", &["GET"], RouteAccess::Public),
+    ("/api/v1/out", &["GET"], RouteAccess::Public),
+    // Renders and stores a PNG per distinct link, so only the owner prints them
+    ("/api/v1/out/qr.png", &["GET"], RouteAccess::Admin),
+    ("/api/v1/presence", &["GET"], RouteAccess::Public),
+    ("/api/v1/presence/beat", &["POST"], RouteAccess::Public),
+    ("/api/v1/reading/progress", &["POST"], RouteAccess::Public),
+    ("/api/v1/reading/title-click", &["POST"], RouteAccess::Public),
+    ("/api/v1/visitor-token", &["GET"], RouteAccess::Public),
+    ("/api/v1/sync", &["GET"], RouteAccess::Public),
+    ("/api/v1/about-me/introduction", &["GET"], RouteAccess::Public),
+    ("/api/v1/blog/posts/**", &["GET"], RouteAccess::Public),
+    ("/api/v1/blog/authors/{username}/posts", &["GET"], RouteAccess::Public),
+    ("/api/v1/blog/series/{slug}", &["GET"], RouteAccess::Public),
+    // Editors take edit locks and scan drafts; the rest of the blog admin is admins only
+    ("/api/v1/blog/admin/posts/{post_id}/lock", &[], RouteAccess::Editor),
+    ("/api/v1/blog/admin/posts/scan", &["POST"], RouteAccess::Editor),
+    ("/api/v1/blog/admin/**", &[], RouteAccess::Admin),
+    // The third-party read API is rate limited instead
+    ("/api/v1/public/**", &["GET"], RouteAccess::Public),
+    // Signed links carry their own token, checked by the handler
+    ("/api/v1/storage/**", &["GET"], RouteAccess::Public),
+    // Authenticated by X-Deploy-Token in the handler
+    ("/api/v1/admin/deploy-hook", &["POST"], RouteAccess::Public),
+    ("/api/v1/admin/**", &[], RouteAccess::Admin),
+    ("/api/**", &[], RouteAccess::User),
+    ("/**", &["GET", "HEAD"], RouteAccess::Public),
+];
 
-fn is_public_route(path: &str, method: &str) -> bool {
-    if method.eq_ignore_ascii_case("OPTIONS") {
-        return true;
-    }
+#[derive(Debug)]
+struct CompiledRouteRule {
+    pattern: RoutePattern,
+    /// Upper case; empty for all
+    methods: Vec<String>,
+    access: RouteAccess,
+}
 
-    let method = method.to_ascii_uppercase();
-
-    let exact_public_routes = [
-        ("/", "GET"),
-        ("/api/v1/auth/refresh", "POST"),
-        ("/api/v1/auth/login", "POST"),
-        ("/api/v1/auth/register", "POST"),
-        ("/api/v1/about-me/introduction", "GET"),
-        ("/api/v1/blog/posts", "GET"),
-        ("/api/v1/blog/posts/recent", "GET"),
-        ("/api/v1/tags", "GET")
-    ];
-
-    if exact_public_routes
-        .iter()
-        .any(|(p, m)| p == &path && m == &method)
-    {
-        return true;
-    }
-    
-    if method == "GET" 
-        && (path == "/api/v1/blog/posts" ||
-            path.starts_with("/api/v1/blog/posts/")) 
-    {
-        return true;
+/// The `[route_policy]` rules followed by the built-in ones, compiled once
+#[derive(Clone, Debug)]
+pub struct RoutePolicy {
+    rules: Arc<Vec<CompiledRouteRule>>,
+}
+
+impl RoutePolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let configured = config.route_policy.rules.iter().filter_map(|rule| {
+            let pattern = RoutePattern::parse(&rule.pattern);
+            if pattern.is_none() {
+                tracing::warn!(pattern = %rule.pattern, "Ignoring route rule with invalid pattern");
+            }
+            Some(CompiledRouteRule {
+                pattern: pattern?,
+                methods: rule.methods.iter().map(|m| m.to_ascii_uppercase()).collect(),
+                access: rule.access,
+            })
+        });
+        let built_in = DEFAULT_ROUTE_RULES.iter().map(|(pattern, methods, access)| CompiledRouteRule {
+            pattern: RoutePattern::parse(pattern).expect("built-in route pattern"),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            access: *access,
+        });
+
+        Self { rules: Arc::new(configured.chain(built_in).collect()) }
     }
 
-    false
-}
+    /// What `method` on `path` requires; the first matching rule wins and
+    /// paths no rule covers need a signed-in user
+    pub fn access(&self, path: &str, method: &str) -> RouteAccess {
+        // Preflights carry no credentials, and decoys must answer like any
+        // unknown path, whatever the method
+        if method.eq_ignore_ascii_case("OPTIONS") || is_honeytoken_path(path) {
+            return RouteAccess::Public;
+        }
 
-pub fn is_authorized(path: &str, claims: &Claims) -> bool {
-    if is_admin_route(path) {
-        return claims.admin;
+        self.rules
+            .iter()
+            .find(|rule| {
+                (rule.methods.is_empty() || rule.methods.iter().any(|m| m.eq_ignore_ascii_case(method)))
+                    && rule.pattern.matches(path)
+            })
+            .map_or(RouteAccess::User, |rule| rule.access)
     }
 
-    true
+    pub fn is_public(&self, path: &str, method: &str) -> bool {
+        self.access(path, method) == RouteAccess::Public
+    }
 }
 
-fn is_admin_route(path: &str) -> bool {
-    path.starts_with("/admin/") || path == "/admin"
+pub fn is_authorized(access: RouteAccess, claims: &Claims) -> bool {
+    match access {
+        RouteAccess::Public | RouteAccess::User => true,
+        RouteAccess::Editor => claims.editor || claims.admin,
+        RouteAccess::Admin => claims.admin,
+    }
 }
 
 fn extract_token(req: &ServiceRequest) -> Option<String> {
@@ -180,6 +266,15 @@ fn extract_token(req: &ServiceRequest) -> Option<String> {
         })
 }
 
+/// Claims of a well-formed bearer token on any route, public ones included.
+/// Quiet and without the revocation check, so only fit for opting into
+/// debug behaviour, never for granting access.
+pub(crate) fn bearer_claims(req: &ServiceRequest) -> Option<Claims> {
+    let state = req.app_data::<web::Data<AppState>>()?;
+    let token = extract_token(req)?;
+    state.auth_handler.token_service.decode_jwt(&token).ok().map(|data| data.claims)
+}
+
 fn get_valid_claims(
     req: &ServiceRequest,
 ) -> Result<Claims, AuthError> {
@@ -204,7 +299,7 @@ fn get_valid_claims(
 
 
 async  fn check_token_blacklist(
-    redis_pool: &deadpool_redis::Pool,
+    redis_pool: &SupervisedPool,
     token: &str
 ) -> Result<(), AuthError> {
     let blacklist_key = format!("access_deny:{}", token);
@@ -214,9 +309,7 @@ async  fn check_token_blacklist(
             Err(AuthError::TokenExpired)
         }
         Ok(false) => Ok(()),
-        Err(e) => {
-            tracing::error!("Redis error checking token blacklist: {}", e);
-            Err(AuthError::RedisOperation(e.to_string()))
-        }
+        // Already counted by the pool; its health check reports the outage
+        Err(e) => Err(AuthError::RedisOperation(e.to_string())),
     }
 }
\ No newline at end of file
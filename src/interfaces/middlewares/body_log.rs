@@ -0,0 +1,231 @@
+use actix_web::{
+    body::{self, BodySize, BoxBody, MessageBody},
+    dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE},
+    web::{self, Bytes, BytesMut},
+    Error, HttpMessage,
+};
+use futures_util::{future::{ok, Ready, LocalBoxFuture}, StreamExt};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::{
+    entities::{tenant::Tenant, token::Claims},
+    middlewares::auth::bearer_claims,
+    settings::AppConfig,
+    utils::redact::Redactor,
+    AppState,
+};
+
+/// Sent by an admin to have the bodies of a single request logged
+pub const DEBUG_BODY_LOG_HEADER: &str = "X-Debug-Body-Log";
+
+/// What to log and how to redact it, from the `[body_logging]` config table
+#[derive(Clone, Debug)]
+pub struct BodyLogPolicy {
+    always: bool,
+    max_body_bytes: usize,
+    redactor: Redactor,
+}
+
+impl BodyLogPolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let settings = &config.body_logging;
+        // Patterns are checked by config validation; this only guards callers
+        // that build a config by hand
+        let redactor = Redactor::new(&settings.redact_fields, &settings.redact_patterns)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Ignoring body log redact_patterns: {}", e);
+                Redactor::new(&settings.redact_fields, &[]).expect("no patterns to compile")
+            });
+
+        Self {
+            always: settings.enabled && !config.is_production(),
+            max_body_bytes: settings.max_body_bytes,
+            redactor,
+        }
+    }
+
+    /// Logged for every request, or for admins who send the debug header
+    fn applies_to(&self, req: &ServiceRequest) -> bool {
+        if self.always {
+            return true;
+        }
+        if !req.headers().contains_key(DEBUG_BODY_LOG_HEADER) {
+            return false;
+        }
+
+        // Authenticated routes already carry checked claims; public ones do not
+        let claims = req.extensions().get::<Claims>().cloned().or_else(|| bearer_claims(req));
+        let tenant_id = req.extensions().get::<Tenant>().map(|tenant| tenant.id);
+
+        claims.is_some_and(|claims| claims.admin && tenant_id.is_none_or(|id| id == claims.tid))
+    }
+
+    /// Bodies of unknown length are only buffered for requests, which arrive
+    /// chunked now and then; streamed responses are never held back
+    fn fits(&self, length: Option<u64>) -> bool {
+        length.is_none_or(|length| length <= self.max_body_bytes as u64)
+    }
+
+    /// Redacted and cut to `max_body_bytes`
+    fn render(&self, headers: &HeaderMap, body: &[u8]) -> String {
+        if body.is_empty() {
+            return String::new();
+        }
+
+        let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or_default();
+        let mut text = self.redactor.redact_body(content_type, body);
+        if text.len() > self.max_body_bytes {
+            let cut = (0..=self.max_body_bytes).rev().find(|&i| text.is_char_boundary(i)).unwrap_or(0);
+            text.truncate(cut);
+            text.push_str("…[truncated]");
+        }
+        text
+    }
+}
+
+/// Only bodies a person can read are buffered; uploads and files are not
+fn is_textual(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || matches!(
+            mime.as_str(),
+            "application/json" | "application/x-www-form-urlencoded" | "application/xml"
+        )
+}
+
+fn skipped(headers: &HeaderMap, size: Option<u64>) -> String {
+    let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("none");
+    match size {
+        Some(size) => format!("[not captured: {}, {} bytes]", content_type, size),
+        None => format!("[not captured: {}, streamed]", content_type),
+    }
+}
+
+/// Debug logging of request and response bodies, redacted by
+/// `AppState::body_log`. Runs for every request when `body_logging.enabled`
+/// is set outside production, otherwise only for admins sending
+/// [`DEBUG_BODY_LOG_HEADER`]. Sits inside the auth middleware so it can see
+/// who is asking.
+pub struct BodyLog;
+
+impl<S, B> Transform<S, ServiceRequest> for BodyLog
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = BodyLogService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(BodyLogService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct BodyLogService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for BodyLogService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let policy = req.app_data::<web::Data<AppState>>()
+                .map(|state| state.body_log.clone())
+                .filter(|policy| policy.applies_to(&req));
+
+            let Some(policy) = policy else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            let method = req.method().to_string();
+            let path = req.path().to_string();
+
+            let length: Option<u64> = req.headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
+
+            let request_body = if is_textual(req.headers()) && policy.fits(length) {
+                // Read the whole body and hand the handler a copy
+                let mut payload = req.take_payload();
+                let mut buffered = BytesMut::new();
+                while let Some(chunk) = payload.next().await {
+                    buffered.extend_from_slice(&chunk?);
+                }
+                let buffered = buffered.freeze();
+                let rendered = policy.render(req.headers(), &buffered);
+
+                let (_, mut replay) = actix_http::h1::Payload::create(true);
+                replay.unread_data(buffered);
+                req.set_payload(Payload::from(replay));
+                rendered
+            } else if length.unwrap_or(0) == 0 && !req.headers().contains_key(CONTENT_TYPE) {
+                String::new()
+            } else {
+                skipped(req.headers(), length)
+            };
+
+            let res = service.call(req).await?;
+            let status = res.status().as_u16();
+
+            let size = match res.response().body().size() {
+                BodySize::None => Some(0),
+                BodySize::Sized(size) => Some(size),
+                BodySize::Stream => None,
+            };
+            let capture = is_textual(res.headers()) && size.is_some() && policy.fits(size);
+
+            let (res, response_body) = if capture {
+                let (req, res) = res.into_parts();
+                let (res, body) = res.into_parts();
+                let bytes: Bytes = body::to_bytes(body).await.map_err(|e| {
+                    let e: Box<dyn std::error::Error> = e.into();
+                    actix_web::error::ErrorInternalServerError(e.to_string())
+                })?;
+                let rendered = policy.render(res.headers(), &bytes);
+                (ServiceResponse::new(req, res.set_body(BoxBody::new(bytes))), rendered)
+            } else if size == Some(0) {
+                (res.map_into_boxed_body(), String::new())
+            } else {
+                let rendered = skipped(res.headers(), size);
+                (res.map_into_boxed_body(), rendered)
+            };
+
+            tracing::info!(
+                target: "body_log",
+                %method,
+                %path,
+                status,
+                request_body = %request_body,
+                response_body = %response_body,
+                "Request and response bodies"
+            );
+
+            Ok(res)
+        })
+    }
+}
\ No newline at end of file
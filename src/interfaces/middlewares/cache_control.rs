@@ -0,0 +1,144 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::{header::{HeaderValue, CACHE_CONTROL}, Method},
+    web, Error,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, sync::Arc, task::{Context, Poll}};
+
+use crate::{settings::AppConfig, utils::route_pattern::RoutePattern, AppState};
+
+/// Sent instead of shared-cache rules while the soft launch gate is on,
+/// so a CDN never serves gated content to visitors without the passphrase
+const GATED_CACHE_CONTROL: &str = "private, no-cache";
+
+#[derive(Debug)]
+struct CompiledRule {
+    pattern: RoutePattern,
+    value: HeaderValue,
+    cacheable: bool,
+}
+
+/// Cache-Control rules compiled from the `[cache_policy]` config table
+#[derive(Clone, Debug)]
+pub struct CachePolicy {
+    rules: Arc<Vec<CompiledRule>>,
+    default: Option<HeaderValue>,
+}
+
+impl CachePolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let rules = config.cache_policy.rules
+            .iter()
+            .filter_map(|rule| match (RoutePattern::parse(&rule.pattern), HeaderValue::from_str(&rule.cache_control)) {
+                (Some(pattern), Ok(value)) => Some(CompiledRule {
+                    pattern,
+                    cacheable: is_cacheable(&rule.cache_control),
+                    value,
+                }),
+                _ => {
+                    tracing::warn!(pattern = %rule.pattern, "Ignoring cache rule with invalid pattern or Cache-Control value");
+                    None
+                }
+            })
+            .collect();
+
+        let default = config.cache_policy.default
+            .as_deref()
+            .and_then(|value| HeaderValue::from_str(value).ok());
+
+        Self { rules: Arc::new(rules), default }
+    }
+
+    /// Header for `path` and whether it allows caching; first matching rule wins
+    fn lookup(&self, path: &str) -> Option<(&HeaderValue, bool)> {
+        self.rules
+            .iter()
+            .find(|rule| rule.pattern.matches(path))
+            .map(|rule| (&rule.value, rule.cacheable))
+            .or_else(|| self.default.as_ref().map(|value| (value, is_cacheable(value.to_str().unwrap_or_default()))))
+    }
+}
+
+/// Applies `AppState::cache_policy` to responses that did not set their own
+/// Cache-Control. Cacheable rules only apply to successful GET/HEAD responses;
+/// `no-store`/`private` rules apply to every response on the route.
+pub struct CacheControl;
+
+impl<S, B> Transform<S, ServiceRequest> for CacheControl
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = CacheControlService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(CacheControlService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct CacheControlService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for CacheControlService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let state = req.app_data::<web::Data<AppState>>().cloned();
+            let path = req.path().to_string();
+            let readable = matches!(*req.method(), Method::GET | Method::HEAD);
+
+            let mut res = service.call(req).await?;
+
+            let Some(state) = state else {
+                return Ok(res);
+            };
+            if res.headers().contains_key(CACHE_CONTROL) {
+                return Ok(res);
+            }
+
+            let Some((value, cacheable)) = state.cache_policy.lookup(&path) else {
+                return Ok(res);
+            };
+
+            if !cacheable {
+                res.headers_mut().insert(CACHE_CONTROL, value.clone());
+            } else if readable && (res.status().is_success() || res.status().as_u16() == 304) {
+                let value = if state.site_gate.is_some() {
+                    HeaderValue::from_static(GATED_CACHE_CONTROL)
+                } else {
+                    value.clone()
+                };
+                res.headers_mut().insert(CACHE_CONTROL, value);
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+fn is_cacheable(cache_control: &str) -> bool {
+    let lower = cache_control.to_ascii_lowercase();
+    !(lower.contains("no-store") || lower.contains("private"))
+}
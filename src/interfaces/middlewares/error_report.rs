@@ -0,0 +1,96 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::StatusCode,
+    Error, HttpMessage,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+use tracing_actix_web::RequestId;
+
+use crate::{
+    entities::{tenant::Tenant, token::Claims},
+    errors::{AppError, AuthError},
+    reporting::reporter::{report_error, ErrorEvent, ErrorSource, RequestContext},
+};
+
+/// Reports 500 responses to the installed error reporter with the route,
+/// request id, tenant and user. Sits inside the tracing logger so the
+/// request id is already assigned.
+pub struct ErrorReport;
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorReport
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ErrorReportService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ErrorReportService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct ErrorReportService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorReportService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let res = service.call(req).await?;
+
+            if res.status() == StatusCode::INTERNAL_SERVER_ERROR
+                && let Some(error) = res.response().error()
+            {
+                report_error(request_event(&res, error));
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+fn request_event<B>(res: &ServiceResponse<B>, error: &Error) -> ErrorEvent {
+    let req = res.request();
+    let error_type = if error.as_error::<AppError>().is_some() {
+        "InternalError"
+    } else if error.as_error::<AuthError>().is_some() {
+        "AuthError"
+    } else {
+        "Error"
+    };
+    let extensions = req.extensions();
+
+    ErrorEvent {
+        tenant: extensions.get::<Tenant>().map(|tenant| tenant.slug.clone()),
+        request: Some(RequestContext {
+            method: req.method().to_string(),
+            // The pattern keeps ids and slugs out of the report
+            route: req.match_pattern().unwrap_or_else(|| req.path().to_string()),
+            request_id: extensions.get::<RequestId>().map(|id| id.to_string()),
+            user_id: extensions.get::<Claims>().map(|claims| claims.sub.clone()),
+        }),
+        ..ErrorEvent::new(ErrorSource::Request, error_type, error.to_string())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::uri::{PathAndQuery, Uri},
+    Error, HttpMessage,
+};
+use futures_util::future::{ok, Ready};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::utils::public_urls::{normalize_base_path, RequestBasePath, FORWARDED_PREFIX_HEADER};
+
+/// Serves the API under a path prefix, e.g. `/api` behind a reverse proxy.
+///
+/// The prefix is the request's `X-Forwarded-Prefix` when that is a plain
+/// path, else the configured `base_path`. A path that only routes without
+/// either is routed as if it were not there, so proxies that keep the
+/// prefix and proxies that strip it both work, even with a prefix like
+/// `/api` in front of `/api/v1`. The prefix is recorded as
+/// `RequestBasePath` for `PublicUrls::for_request`. Must wrap every other
+/// middleware, since they match on the path.
+pub struct ForwardedPrefix {
+    base_path: Rc<str>,
+}
+
+impl ForwardedPrefix {
+    pub fn new(base_path: &str) -> Self {
+        Self { base_path: normalize_base_path(base_path).unwrap_or_default().into() }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ForwardedPrefix
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ForwardedPrefixService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ForwardedPrefixService {
+            service: Rc::new(service),
+            base_path: Rc::clone(&self.base_path),
+        })
+    }
+}
+
+pub struct ForwardedPrefixService<S> {
+    service: Rc<S>,
+    base_path: Rc<str>,
+}
+
+impl<S, B> Service<ServiceRequest> for ForwardedPrefixService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = S::Future;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let forwarded = req.headers()
+            .get(FORWARDED_PREFIX_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(normalize_base_path);
+
+        let routed = |path: &str| {
+            let path = path.trim_end_matches('/');
+            req.resource_map().has_resource(if path.is_empty() { "/" } else { path })
+        };
+        let stripped = [forwarded.as_deref(), Some(&*self.base_path)]
+            .into_iter()
+            .flatten()
+            .filter(|_| !routed(req.path()))
+            .filter_map(|prefix| strip_base_path(req.path(), prefix))
+            .find(|rest| routed(rest))
+            .map(|rest| match req.query_string() {
+                "" => rest.to_string(),
+                query => format!("{}?{}", rest, query),
+            });
+        if let Some(path_and_query) = stripped.and_then(|p| PathAndQuery::try_from(p).ok()) {
+            let mut parts = req.head().uri.clone().into_parts();
+            parts.path_and_query = Some(path_and_query);
+            if let Ok(uri) = Uri::from_parts(parts) {
+                req.match_info_mut().get_mut().update(&uri);
+                req.head_mut().uri = uri;
+            }
+        }
+
+        let base_path = forwarded.unwrap_or_else(|| self.base_path.to_string());
+        req.extensions_mut().insert(RequestBasePath(base_path));
+
+        self.service.call(req)
+    }
+}
+
+/// `path` without `prefix`, which has to end at a segment boundary
+fn strip_base_path<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return None;
+    }
+
+    match path.strip_prefix(prefix)? {
+        "" => Some("/"),
+        rest if rest.starts_with('/') => Some(rest),
+        _ => None,
+    }
+}
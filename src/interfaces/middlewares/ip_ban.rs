@@ -0,0 +1,76 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::{
+    limiter::headers::{too_many_requests, RateLimitStatus},
+    metrics::METRICS,
+    utils::get_client_ip::get_client_ip,
+    AppState,
+};
+
+/// Rejects requests from IPs banned by `AppState::honeytokens` with a 429
+/// until the ban runs out, reported as a limit of zero requests.
+pub struct IpBanMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for IpBanMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = IpBanMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(IpBanMiddlewareService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct IpBanMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for IpBanMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let remaining = req.app_data::<web::Data<AppState>>()
+                .filter(|state| state.honeytokens.bans.is_enabled())
+                .and_then(|state| {
+                    state.honeytokens.bans.remaining(&get_client_ip(req.request(), &state.trusted_proxies))
+                });
+
+            if let Some(remaining) = remaining {
+                METRICS.incr("ip_ban_rejections_total");
+                let status = RateLimitStatus::exhausted(0, remaining.as_secs());
+                let response = too_many_requests("ip_ban", status, "Too many requests");
+                return Ok(req.into_response(response));
+            }
+
+            let downstream_res = service.call(req).await?;
+            Ok(downstream_res.map_into_boxed_body())
+        })
+    }
+}
\ No newline at end of file
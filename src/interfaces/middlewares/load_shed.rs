@@ -0,0 +1,87 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::{errors::AppError, metrics::METRICS, AppState};
+
+/// Applies the scope's concurrency budget from `AppState::load_shedder`.
+/// Requests that cannot get a slot within the queue timeout are shed with a
+/// 503 and `Retry-After` instead of piling up behind a busy scope.
+pub struct LoadShed {
+    scope: &'static str,
+}
+
+impl LoadShed {
+    pub fn scope(scope: &'static str) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for LoadShed
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = LoadShedService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(LoadShedService {
+            service: Rc::new(service),
+            scope: self.scope,
+        })
+    }
+}
+
+pub struct LoadShedService<S> {
+    service: Rc<S>,
+    scope: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for LoadShedService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let scope = self.scope;
+
+        Box::pin(async move {
+            let Some(shedder) = req.app_data::<web::Data<AppState>>()
+                .map(|state| state.load_shedder.clone())
+            else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            // Held until the downstream response is produced
+            let _permit = match shedder.acquire(scope).await {
+                Ok(permit) => permit,
+                Err(_) => {
+                    METRICS.incr(&format!("http_requests_shed_total{{scope=\"{scope}\"}}"));
+                    let err = AppError::ServiceUnavailable("Server is busy, please retry shortly".into());
+                    return Ok(req.error_response(err).map_into_boxed_body());
+                }
+            };
+
+            METRICS.incr(&format!("http_requests_admitted_total{{scope=\"{scope}\"}}"));
+            let downstream_res = service.call(req).await?;
+            Ok(downstream_res.map_into_boxed_body())
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::{limiter::headers::too_many_requests, utils::get_client_ip::get_client_ip, AppState};
+
+/// Limits each client IP on the route with `AppState::rate_limiter`,
+/// answering 429 once its bucket and window are used up. Every response
+/// carries the client's `RateLimit-*` headers.
+pub struct RateLimit {
+    route: &'static str,
+}
+
+impl RateLimit {
+    pub fn route(route: &'static str) -> Self {
+        Self { route }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitService {
+            service: Rc::new(service),
+            route: self.route,
+        })
+    }
+}
+
+pub struct RateLimitService<S> {
+    service: Rc<S>,
+    route: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let route = self.route;
+
+        Box::pin(async move {
+            let verdict = req.app_data::<web::Data<AppState>>()
+                .map(|state| state.rate_limiter.check(route, &get_client_ip(req.request(), &state.trusted_proxies)));
+
+            let status = match verdict {
+                Some(Err(status)) => {
+                    let response = too_many_requests(&format!("route:{}", route), status, "Too many requests");
+                    return Ok(req.into_response(response));
+                }
+                Some(Ok(status)) => Some(status),
+                None => None,
+            };
+
+            let mut downstream_res = service.call(req).await?;
+            if let Some(status) = status {
+                status.apply(downstream_res.headers_mut());
+            }
+            Ok(downstream_res.map_into_boxed_body())
+        })
+    }
+}
\ No newline at end of file
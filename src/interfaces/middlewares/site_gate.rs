@@ -0,0 +1,116 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::{
+    auth::site_gate::{SITE_GATE_COOKIE, SITE_GATE_HEADER},
+    constants::READYZ_PATH,
+    entities::security_event::is_honeytoken_path,
+    errors::AuthError,
+    middlewares::auth::RoutePolicy,
+    AppState,
+};
+
+const GATE_PATH: &str = "/api/v1/gate";
+/// Called by CI, which never holds a gate token
+const DEPLOY_HOOK_PATH: &str = "/api/v1/admin/deploy-hook";
+
+/// Soft launch gate: when a site passphrase is configured, public content
+/// routes require a gate token. Auth and admin routes are left untouched
+/// since they are already protected by `AuthMiddleware`.
+pub struct SiteGateMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for SiteGateMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = SiteGateMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(SiteGateMiddlewareService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct SiteGateMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for SiteGateMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let locked = req.app_data::<web::Data<AppState>>()
+                .and_then(|state| state.site_gate.as_ref().map(|gate| (gate, &state.route_policy)))
+                .is_some_and(|(gate, route_policy)| {
+                    requires_gate(route_policy, req.path(), req.method().as_str())
+                        && !extract_gate_token(&req).is_some_and(|token| gate.verify(&token))
+                });
+
+            if locked {
+                return Ok(req.error_response(AuthError::SiteLocked).map_into_boxed_body());
+            }
+
+            let downstream_res = service.call(req).await?;
+            Ok(downstream_res.map_into_boxed_body())
+        })
+    }
+}
+
+/// Only public content is gated; the gate endpoint, landing route and auth flow stay open
+fn requires_gate(route_policy: &RoutePolicy, path: &str, method: &str) -> bool {
+    if method.eq_ignore_ascii_case("OPTIONS") || path == "/" || path == GATE_PATH || path == DEPLOY_HOOK_PATH || path == READYZ_PATH {
+        return false;
+    }
+
+    // Scanner hits are recorded whether or not the site is locked
+    if is_honeytoken_path(path) {
+        return false;
+    }
+
+    if path.starts_with("/api/v1/auth/") {
+        return false;
+    }
+
+    // Signed links are handed out by admins and expire on their own
+    if path.starts_with("/api/v1/storage/") {
+        return false;
+    }
+
+    route_policy.is_public(path, method)
+}
+
+fn extract_gate_token(req: &ServiceRequest) -> Option<String> {
+    req.cookie(SITE_GATE_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .or_else(|| {
+            req.headers()
+                .get(SITE_GATE_HEADER)
+                .and_then(|header| header.to_str().ok())
+                .map(|value| value.trim().to_string())
+        })
+        .filter(|token| !token.is_empty())
+}
@@ -0,0 +1,77 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error, HttpMessage,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{rc::Rc, task::{Context, Poll}};
+
+use crate::{constants::READYZ_PATH, errors::AppError, AppState};
+
+/// Resolves the tenant from the Host header and stores it in the request
+/// extensions for `AuthMiddleware` and the `CurrentTenant` extractor.
+/// Unknown hosts get a 404 when strict host matching is enabled, except on
+/// the readiness probe, which load balancers call by IP.
+pub struct TenantMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for TenantMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = TenantMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(TenantMiddlewareService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct TenantMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for TenantMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            if let Some(state) = req.app_data::<web::Data<AppState>>()
+                && req.path() != READYZ_PATH
+            {
+                let host = req.connection_info().host().to_string();
+
+                match state.tenants.resolve(&host) {
+                    Some(tenant) => {
+                        req.extensions_mut().insert(tenant);
+                    }
+                    None => {
+                        tracing::info!(%host, "Request for a host no tenant serves");
+                        let err = AppError::NotFound("Unknown site".into());
+                        return Ok(req.error_response(err).map_into_boxed_body());
+                    }
+                }
+            }
+
+            let downstream_res = service.call(req).await?;
+            Ok(downstream_res.map_into_boxed_body())
+        })
+    }
+}
@@ -0,0 +1,106 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error,
+};
+use futures_util::future::{ok, Ready, LocalBoxFuture};
+use std::{collections::HashMap, rc::Rc, sync::Arc, task::{Context, Poll}, time::Duration};
+
+use crate::{errors::AppError, metrics::METRICS, settings::AppConfig, AppState};
+
+/// Time budget per route scope, resolved once from config
+#[derive(Clone, Debug)]
+pub struct RouteTimeouts {
+    default: Duration,
+    scopes: Arc<HashMap<String, Duration>>,
+}
+
+impl RouteTimeouts {
+    pub fn new(default: Duration, scopes: HashMap<String, Duration>) -> Self {
+        Self { default, scopes: Arc::new(scopes) }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(Duration::from_millis(config.request_timeout_ms), config.route_timeouts())
+    }
+
+    pub fn for_scope(&self, scope: &str) -> Duration {
+        self.scopes.get(scope).copied().unwrap_or(self.default)
+    }
+}
+
+/// Aborts the handler future once the scope's budget is spent and answers 504.
+/// Only the time to produce the response head counts; streamed bodies such as
+/// the CSV export are not cut off.
+pub struct RequestTimeout {
+    scope: &'static str,
+}
+
+impl RequestTimeout {
+    pub fn scope(scope: &'static str) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTimeout
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestTimeoutService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestTimeoutService {
+            service: Rc::new(service),
+            scope: self.scope,
+        })
+    }
+}
+
+pub struct RequestTimeoutService<S> {
+    service: Rc<S>,
+    scope: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTimeoutService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let scope = self.scope;
+
+        Box::pin(async move {
+            let Some(budget) = req.app_data::<web::Data<AppState>>()
+                .map(|state| state.route_timeouts.for_scope(scope))
+            else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            // Dropping the handler future cancels it; in-flight queries are
+            // cut server-side by the session's statement_timeout. The request
+            // must not be cloned here: the scope router needs sole ownership
+            // to record match info, so the 504 is returned as an error.
+            match tokio::time::timeout(budget, service.call(req)).await {
+                Ok(res) => Ok(res?.map_into_boxed_body()),
+                Err(_) => {
+                    METRICS.incr(&format!("http_requests_timed_out_total{{scope=\"{scope}\"}}"));
+                    Err(AppError::Timeout("Request timed out".into()).into())
+                }
+            }
+        })
+    }
+}
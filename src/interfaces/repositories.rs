@@ -3,4 +3,32 @@ pub mod token;
 pub mod about;
 pub mod blog_post;
 pub mod contact_me;
-pub mod sqlx_repo;
\ No newline at end of file
+pub mod feature_flag;
+pub mod app_settings;
+pub mod tenant;
+pub mod hire;
+pub mod uses;
+pub mod changelog;
+pub mod outbound;
+pub mod security_event;
+pub mod outbox;
+pub mod link_check;
+pub mod audit_log;
+pub mod retention;
+pub mod notification_preferences;
+pub mod reading_progress;
+pub mod title_test;
+pub mod sqlx_repo;
+#[cfg(feature = "in-memory")]
+pub mod in_memory;
+
+pub mod api_token;
+pub mod stats_rollup;
+pub mod one_time_token;
+pub mod bookmark;
+pub mod redirect_rule;
+pub mod schema;
+pub mod share_count;
+pub mod config_history;
+pub mod backfill;
+pub mod usage;
\ No newline at end of file
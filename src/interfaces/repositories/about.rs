@@ -1,35 +1,79 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use mockall::automock;
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
 use crate::{
-    entities::about_me::{AboutMe, AboutMeInsert, AboutMeResponse}, 
+    entities::about_me::{AboutMe, AboutMeInsert, AboutMeResponse, UpdateAboutMeRequest}, 
     errors::AppError, repositories::sqlx_repo::SqlxAboutMeRepo,
 
 };
 
+#[automock]
 #[async_trait]
 pub trait AboutRepository: Send + Sync {
     /// Creates the "About Me" content
-    async fn create_about_me(&self, about_insert: &AboutMeInsert) -> Result<Uuid, AppError>;
+    async fn create_about_me(&self, tenant_id: &Uuid, about_insert: &AboutMeInsert) -> Result<Uuid, AppError>;
 
     /// Retrieves the "About Me" content by id
-    async fn get_about_me_by_id(&self, id: &Uuid) -> Result<AboutMe, AppError>;
+    async fn get_about_me_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<AboutMe, AppError>;
 
     /// Retrieves the current "About Me" content
-    async fn get_current_about_me(&self) -> Result<AboutMeResponse, AppError>;
+    async fn get_current_about_me(&self, tenant_id: &Uuid) -> Result<AboutMeResponse, AppError>;
+
+    /// Retrieves the "About Me" content in effect on `date`: the latest
+    /// revision of the latest effective date not after it
+    async fn get_about_me_as_of(&self, tenant_id: &Uuid, date: NaiveDate) -> Result<AboutMeResponse, AppError>;
 
     /// Updates the "About Me" content
-    async fn update_about_me_content(&self, id: &Uuid, content: &str, effective_date: &NaiveDate,) -> Result<AboutMe, AppError>;
+    /// Fields left `Unchanged` in `update` keep their stored value
+    async fn update_about_me_content(&self, tenant_id: &Uuid, id: &Uuid, update: &UpdateAboutMeRequest) -> Result<AboutMe, AppError>;
 
     /// Get the current revision of "About Me" content
-    async fn get_current_revision(&self, effective_date: NaiveDate) -> Result<i32, AppError>;
+    async fn get_current_revision(&self, tenant_id: &Uuid, effective_date: NaiveDate) -> Result<i32, AppError>;
 
     /// Soft delete (recommended for most cases)
-    async fn soft_delete_about_me(&self, id: Uuid) -> Result<(), AppError>;
+    async fn soft_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError>;
 
     /// Hard delete (for compliance/admin use only)
-    async fn hard_delete_about_me(&self, id: Uuid) -> Result<(), AppError>;
+    async fn hard_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: AboutRepository + ?Sized> AboutRepository for Arc<T> {
+    async fn create_about_me(&self, tenant_id: &Uuid, about_insert: &AboutMeInsert) -> Result<Uuid, AppError> {
+        (**self).create_about_me(tenant_id, about_insert).await
+    }
+
+    async fn get_about_me_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<AboutMe, AppError> {
+        (**self).get_about_me_by_id(tenant_id, id).await
+    }
+
+    async fn get_current_about_me(&self, tenant_id: &Uuid) -> Result<AboutMeResponse, AppError> {
+        (**self).get_current_about_me(tenant_id).await
+    }
+
+    async fn get_about_me_as_of(&self, tenant_id: &Uuid, date: NaiveDate) -> Result<AboutMeResponse, AppError> {
+        (**self).get_about_me_as_of(tenant_id, date).await
+    }
+
+    async fn update_about_me_content(&self, tenant_id: &Uuid, id: &Uuid, update: &UpdateAboutMeRequest) -> Result<AboutMe, AppError> {
+        (**self).update_about_me_content(tenant_id, id, update).await
+    }
+
+    async fn get_current_revision(&self, tenant_id: &Uuid, effective_date: NaiveDate) -> Result<i32, AppError> {
+        (**self).get_current_revision(tenant_id, effective_date).await
+    }
+
+    async fn soft_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError> {
+        (**self).soft_delete_about_me(tenant_id, id).await
+    }
+
+    async fn hard_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError> {
+        (**self).hard_delete_about_me(tenant_id, id).await
+    }
 }
 
 impl SqlxAboutMeRepo {
@@ -40,13 +84,14 @@ impl SqlxAboutMeRepo {
 
 #[async_trait]
 impl AboutRepository for SqlxAboutMeRepo {
-    async fn create_about_me(&self, about_insert: &AboutMeInsert) -> Result<Uuid, AppError> {
+    async fn create_about_me(&self, tenant_id: &Uuid, about_insert: &AboutMeInsert) -> Result<Uuid, AppError> {
         let id = sqlx::query_scalar!(
             r#"
-            INSERT INTO about_me (revision, content_markdown, effective_date) 
-            VALUES ($1, $2, $3)
+            INSERT INTO about_me (tenant_id, revision, content_markdown, effective_date) 
+            VALUES ($1, $2, $3, $4)
             RETURNING id
             "#,
+            tenant_id,
             about_insert.revision,
             about_insert.content_markdown,
             about_insert.effective_date,
@@ -57,11 +102,12 @@ impl AboutRepository for SqlxAboutMeRepo {
         Ok(id)
     }
 
-    async fn get_about_me_by_id(&self, id: &Uuid) -> Result<AboutMe, AppError> {
+    async fn get_about_me_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<AboutMe, AppError> {
         let about_me = sqlx::query_as!(
             AboutMe,
-            r#"SELECT * FROM about_me WHERE id = $1 AND deleted_at IS NULL"#,
-            id
+            r#"SELECT * FROM about_me WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL"#,
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -69,16 +115,23 @@ impl AboutRepository for SqlxAboutMeRepo {
         Ok(about_me.into())
     }
 
-    async fn get_current_about_me(&self) -> Result<AboutMeResponse, AppError> {
+    async fn get_current_about_me(&self, tenant_id: &Uuid) -> Result<AboutMeResponse, AppError> {
+        self.get_about_me_as_of(tenant_id, Utc::now().date_naive()).await
+    }
+
+    async fn get_about_me_as_of(&self, tenant_id: &Uuid, date: NaiveDate) -> Result<AboutMeResponse, AppError> {
         let about_me = sqlx::query_as!(
             AboutMe,
             r#"SELECT * 
             FROM about_me 
-            WHERE effective_date <= CURRENT_DATE 
+            WHERE tenant_id = $1
+                AND effective_date <= $2 
                 AND deleted_at IS NULL
             ORDER BY effective_date DESC, revision DESC
             LIMIT 1
-            "#
+            "#,
+            tenant_id,
+            date
         )
         .fetch_optional(&self.pool)
         .await
@@ -91,25 +144,26 @@ impl AboutRepository for SqlxAboutMeRepo {
     }
 
     async fn update_about_me_content(
-        &self, 
+        &self,
+        tenant_id: &Uuid,
         id: &Uuid, 
-        content: &str,
-        effective_date: &NaiveDate,
+        update: &UpdateAboutMeRequest,
     ) -> Result<AboutMe, AppError> {
         let updated = sqlx::query_as!(
             AboutMe,
             r#"
             UPDATE about_me
             SET
-                content_markdown = $1,
-                effective_date = $2,
+                content_markdown = COALESCE($1, content_markdown),
+                effective_date = COALESCE($2, effective_date),
                 updated_at = NOW()
-            WHERE id = $3
+            WHERE id = $3 AND tenant_id = $4
             RETURNING *
             "#,
-            content,
-            effective_date,
-            id
+            update.content_markdown.flatten_str(),
+            update.effective_date.flatten_ref(),
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await
@@ -121,15 +175,17 @@ impl AboutRepository for SqlxAboutMeRepo {
         Ok(updated.map_err(|e| AppError::InternalError(e.to_string()))?)
     }
 
-    async fn get_current_revision(&self, effective_date: NaiveDate) -> Result<i32, AppError> {
+    async fn get_current_revision(&self, tenant_id: &Uuid, effective_date: NaiveDate) -> Result<i32, AppError> {
         let revision = sqlx::query_scalar!(
             r#"
             SELECT MAX(revision)
             FROM about_me
             WHERE effective_date = $1
+            AND tenant_id = $2
             AND deleted_at IS NULL
             "#,
-            effective_date
+            effective_date,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await
@@ -138,14 +194,15 @@ impl AboutRepository for SqlxAboutMeRepo {
         Ok(revision.unwrap_or(0))
     }
 
-    async fn soft_delete_about_me(&self, id: Uuid) -> Result<(), AppError> {
+    async fn soft_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError> {
         sqlx::query!(
             r#"
             UPDATE about_me
             SET deleted_at = NOW()
-            WHERE id = $1 and deleted_at IS NULL
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
             "#,
-            id
+            id,
+            tenant_id
         )
         .execute(&self.pool)
         .await
@@ -158,10 +215,11 @@ impl AboutRepository for SqlxAboutMeRepo {
         })?
     }
 
-    async fn hard_delete_about_me(&self, id: Uuid) -> Result<(), AppError> {
+    async fn hard_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError> {
         sqlx::query!(
-            "DELETE FROM about_me WHERE id = $1",
-            id
+            "DELETE FROM about_me WHERE id = $1 AND tenant_id = $2",
+            id,
+            tenant_id
         )
         .execute(&self.pool)
         .await
@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::api_token::{ApiToken, ApiTokenDailyUsage, ApiTokenInsert, ApiTokenUsage},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxApiTokenRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    /// Every tenant's unrevoked tokens, for the middleware's cache
+    async fn list_active_api_tokens(&self) -> Result<Vec<ApiToken>, AppError>;
+    /// The tenant's tokens, revoked ones included, newest first
+    async fn list_api_tokens(&self, tenant_id: &Uuid) -> Result<Vec<ApiToken>, AppError>;
+    async fn create_api_token(&self, tenant_id: &Uuid, token: &ApiTokenInsert) -> Result<ApiToken, AppError>;
+    /// False when the token does not exist or was already revoked
+    async fn revoke_api_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError>;
+    /// Adds counted usage to the tokens' totals; unknown ids are skipped
+    async fn add_api_token_usage(&self, usage: &[(Uuid, ApiTokenUsage)]) -> Result<(), AppError>;
+    /// Adds counted traffic to the tokens' daily rows; unknown ids are skipped
+    async fn add_api_token_daily_usage(&self, usage: &[ApiTokenDailyUsage]) -> Result<(), AppError>;
+    /// Saves days' totals, never lowering a stored count, so flushes from
+    /// several instances and retries can arrive in any order
+    async fn save_api_token_daily_totals(&self, totals: &[ApiTokenDailyUsage]) -> Result<(), AppError>;
+    /// The token's daily rows from `since` on, oldest first
+    async fn list_api_token_daily_usage(&self, token_id: &Uuid, since: NaiveDate) -> Result<Vec<ApiTokenDailyUsage>, AppError>;
+    /// Requests from `since` on per unrevoked token with a monthly quota
+    async fn count_quota_requests_since(&self, since: NaiveDate) -> Result<Vec<(Uuid, i64)>, AppError>;
+}
+
+#[async_trait]
+impl<T: ApiTokenRepository + ?Sized> ApiTokenRepository for Arc<T> {
+    async fn list_active_api_tokens(&self) -> Result<Vec<ApiToken>, AppError> {
+        (**self).list_active_api_tokens().await
+    }
+
+    async fn list_api_tokens(&self, tenant_id: &Uuid) -> Result<Vec<ApiToken>, AppError> {
+        (**self).list_api_tokens(tenant_id).await
+    }
+
+    async fn create_api_token(&self, tenant_id: &Uuid, token: &ApiTokenInsert) -> Result<ApiToken, AppError> {
+        (**self).create_api_token(tenant_id, token).await
+    }
+
+    async fn revoke_api_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError> {
+        (**self).revoke_api_token(tenant_id, id).await
+    }
+
+    async fn add_api_token_usage(&self, usage: &[(Uuid, ApiTokenUsage)]) -> Result<(), AppError> {
+        (**self).add_api_token_usage(usage).await
+    }
+
+    async fn add_api_token_daily_usage(&self, usage: &[ApiTokenDailyUsage]) -> Result<(), AppError> {
+        (**self).add_api_token_daily_usage(usage).await
+    }
+
+    async fn save_api_token_daily_totals(&self, totals: &[ApiTokenDailyUsage]) -> Result<(), AppError> {
+        (**self).save_api_token_daily_totals(totals).await
+    }
+
+    async fn list_api_token_daily_usage(&self, token_id: &Uuid, since: NaiveDate) -> Result<Vec<ApiTokenDailyUsage>, AppError> {
+        (**self).list_api_token_daily_usage(token_id, since).await
+    }
+
+    async fn count_quota_requests_since(&self, since: NaiveDate) -> Result<Vec<(Uuid, i64)>, AppError> {
+        (**self).count_quota_requests_since(since).await
+    }
+}
+
+impl SqlxApiTokenRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxApiTokenRepo { pool }
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for SqlxApiTokenRepo {
+    async fn list_active_api_tokens(&self) -> Result<Vec<ApiToken>, AppError> {
+        let tokens = sqlx::query_as!(
+            ApiToken,
+            "SELECT * FROM api_tokens WHERE revoked_at IS NULL"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tokens)
+    }
+
+    async fn list_api_tokens(&self, tenant_id: &Uuid) -> Result<Vec<ApiToken>, AppError> {
+        let tokens = sqlx::query_as!(
+            ApiToken,
+            "SELECT * FROM api_tokens WHERE tenant_id = $1 ORDER BY created_at DESC",
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tokens)
+    }
+
+    async fn create_api_token(&self, tenant_id: &Uuid, token: &ApiTokenInsert) -> Result<ApiToken, AppError> {
+        let created = sqlx::query_as!(
+            ApiToken,
+            r#"
+            INSERT INTO api_tokens (tenant_id, name, token_hash, token_prefix, scopes, rate_limit_per_minute, monthly_quota, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+            tenant_id,
+            token.name,
+            token.token_hash,
+            token.token_prefix,
+            &token.scopes,
+            token.rate_limit_per_minute,
+            token.monthly_quota,
+            token.created_by,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn revoke_api_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "UPDATE api_tokens SET revoked_at = NOW() WHERE tenant_id = $1 AND id = $2 AND revoked_at IS NULL",
+            tenant_id,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn add_api_token_usage(&self, usage: &[(Uuid, ApiTokenUsage)]) -> Result<(), AppError> {
+        if usage.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<Uuid> = usage.iter().map(|(id, _)| *id).collect();
+        let requests: Vec<i64> = usage.iter().map(|(_, u)| u.requests as i64).collect();
+        let rejected: Vec<i64> = usage.iter().map(|(_, u)| u.rejected as i64).collect();
+        let last_used: Vec<Option<DateTime<Utc>>> = usage.iter().map(|(_, u)| u.last_used_at).collect();
+
+        sqlx::query!(
+            r#"
+            UPDATE api_tokens t SET
+                request_count = t.request_count + u.requests,
+                rejected_count = t.rejected_count + u.rejected,
+                last_used_at = GREATEST(t.last_used_at, u.last_used_at)
+            FROM UNNEST($1::UUID[], $2::BIGINT[], $3::BIGINT[], $4::TIMESTAMPTZ[])
+                AS u(id, requests, rejected, last_used_at)
+            WHERE t.id = u.id
+            "#,
+            &ids,
+            &requests,
+            &rejected,
+            &last_used as &[Option<DateTime<Utc>>],
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+    async fn add_api_token_daily_usage(&self, usage: &[ApiTokenDailyUsage]) -> Result<(), AppError> {
+        if usage.is_empty() {
+            return Ok(());
+        }
+
+        let (ids, days, requests, bytes, errors) = daily_columns(usage);
+
+        sqlx::query!(
+            r#"
+            INSERT INTO api_token_usage_daily (token_id, day, requests, bytes, errors)
+            SELECT u.token_id, u.day, u.requests, u.bytes, u.errors
+            FROM UNNEST($1::UUID[], $2::DATE[], $3::BIGINT[], $4::BIGINT[], $5::BIGINT[])
+                AS u(token_id, day, requests, bytes, errors)
+            JOIN api_tokens t ON t.id = u.token_id
+            ON CONFLICT (token_id, day) DO UPDATE SET
+                requests = api_token_usage_daily.requests + EXCLUDED.requests,
+                bytes = api_token_usage_daily.bytes + EXCLUDED.bytes,
+                errors = api_token_usage_daily.errors + EXCLUDED.errors
+            "#,
+            &ids,
+            &days,
+            &requests,
+            &bytes,
+            &errors,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn save_api_token_daily_totals(&self, totals: &[ApiTokenDailyUsage]) -> Result<(), AppError> {
+        if totals.is_empty() {
+            return Ok(());
+        }
+
+        let (ids, days, requests, bytes, errors) = daily_columns(totals);
+
+        sqlx::query!(
+            r#"
+            INSERT INTO api_token_usage_daily (token_id, day, requests, bytes, errors)
+            SELECT u.token_id, u.day, u.requests, u.bytes, u.errors
+            FROM UNNEST($1::UUID[], $2::DATE[], $3::BIGINT[], $4::BIGINT[], $5::BIGINT[])
+                AS u(token_id, day, requests, bytes, errors)
+            JOIN api_tokens t ON t.id = u.token_id
+            ON CONFLICT (token_id, day) DO UPDATE SET
+                requests = GREATEST(api_token_usage_daily.requests, EXCLUDED.requests),
+                bytes = GREATEST(api_token_usage_daily.bytes, EXCLUDED.bytes),
+                errors = GREATEST(api_token_usage_daily.errors, EXCLUDED.errors)
+            "#,
+            &ids,
+            &days,
+            &requests,
+            &bytes,
+            &errors,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_api_token_daily_usage(&self, token_id: &Uuid, since: NaiveDate) -> Result<Vec<ApiTokenDailyUsage>, AppError> {
+        let usage = sqlx::query_as!(
+            ApiTokenDailyUsage,
+            "SELECT * FROM api_token_usage_daily WHERE token_id = $1 AND day >= $2 ORDER BY day",
+            token_id,
+            since
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    async fn count_quota_requests_since(&self, since: NaiveDate) -> Result<Vec<(Uuid, i64)>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT u.token_id, SUM(u.requests)::BIGINT AS "requests!"
+            FROM api_token_usage_daily u
+            JOIN api_tokens t ON t.id = u.token_id
+            WHERE u.day >= $1 AND t.monthly_quota IS NOT NULL AND t.revoked_at IS NULL
+            GROUP BY u.token_id
+            "#,
+            since
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.token_id, row.requests)).collect())
+    }
+}
+
+type DailyColumns = (Vec<Uuid>, Vec<NaiveDate>, Vec<i64>, Vec<i64>, Vec<i64>);
+
+fn daily_columns(usage: &[ApiTokenDailyUsage]) -> DailyColumns {
+    (
+        usage.iter().map(|u| u.token_id).collect(),
+        usage.iter().map(|u| u.day).collect(),
+        usage.iter().map(|u| u.requests).collect(),
+        usage.iter().map(|u| u.bytes).collect(),
+        usage.iter().map(|u| u.errors).collect(),
+    )
+}
\ No newline at end of file
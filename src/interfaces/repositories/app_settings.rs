@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+
+use crate::{
+    entities::app_setting::AppSetting,
+    errors::AppError,
+    repositories::sqlx_repo::SqlxAppSettingsRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait AppSettingsRepository: Send + Sync {
+    /// Settings across every tenant, for warming the runtime cache
+    async fn list_all_settings(&self) -> Result<Vec<AppSetting>, AppError>;
+    async fn list_settings(&self, tenant_id: &Uuid) -> Result<Vec<AppSetting>, AppError>;
+    async fn upsert_setting(&self, tenant_id: &Uuid, key: &str, value: &JsonValue) -> Result<AppSetting, AppError>;
+}
+
+#[async_trait]
+impl<T: AppSettingsRepository + ?Sized> AppSettingsRepository for Arc<T> {
+    async fn list_all_settings(&self) -> Result<Vec<AppSetting>, AppError> {
+        (**self).list_all_settings().await
+    }
+
+    async fn list_settings(&self, tenant_id: &Uuid) -> Result<Vec<AppSetting>, AppError> {
+        (**self).list_settings(tenant_id).await
+    }
+
+    async fn upsert_setting(&self, tenant_id: &Uuid, key: &str, value: &JsonValue) -> Result<AppSetting, AppError> {
+        (**self).upsert_setting(tenant_id, key, value).await
+    }
+}
+
+impl SqlxAppSettingsRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxAppSettingsRepo { pool }
+    }
+}
+
+#[async_trait]
+impl AppSettingsRepository for SqlxAppSettingsRepo {
+    async fn list_all_settings(&self) -> Result<Vec<AppSetting>, AppError> {
+        let settings = sqlx::query_as::<_, AppSetting>(
+            "SELECT tenant_id, key, value, updated_at FROM app_settings ORDER BY tenant_id, key"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    async fn list_settings(&self, tenant_id: &Uuid) -> Result<Vec<AppSetting>, AppError> {
+        let settings = sqlx::query_as::<_, AppSetting>(
+            "SELECT tenant_id, key, value, updated_at FROM app_settings WHERE tenant_id = $1 ORDER BY key"
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    async fn upsert_setting(&self, tenant_id: &Uuid, key: &str, value: &JsonValue) -> Result<AppSetting, AppError> {
+        let setting = sqlx::query_as::<_, AppSetting>(
+            r#"
+            INSERT INTO app_settings (tenant_id, key, value)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (tenant_id, key) DO UPDATE SET
+                value = EXCLUDED.value,
+                updated_at = NOW()
+            RETURNING tenant_id, key, value, updated_at
+            "#
+        )
+        .bind(tenant_id)
+        .bind(key)
+        .bind(value)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(setting)
+    }
+}
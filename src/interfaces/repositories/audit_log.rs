@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::{
+    entities::audit::{AuditCursor, AuditEntry, AuditEntryInsert, AuditLogFilter},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxAuditLogRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    async fn record_entry(&self, tenant_id: &Uuid, entry: &AuditEntryInsert) -> Result<(), AppError>;
+    /// Matching entries, newest first
+    async fn search_entries(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError>;
+    async fn count_entries(&self, tenant_id: &Uuid, filter: &AuditLogFilter) -> Result<i64, AppError>;
+    /// Matching entries oldest first, starting after `after`
+    async fn list_entries_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        after: Option<AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError>;
+    async fn delete_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl<T: AuditLogRepository + ?Sized> AuditLogRepository for Arc<T> {
+    async fn record_entry(&self, tenant_id: &Uuid, entry: &AuditEntryInsert) -> Result<(), AppError> {
+        (**self).record_entry(tenant_id, entry).await
+    }
+
+    async fn search_entries(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        (**self).search_entries(tenant_id, filter, limit, offset).await
+    }
+
+    async fn count_entries(&self, tenant_id: &Uuid, filter: &AuditLogFilter) -> Result<i64, AppError> {
+        (**self).count_entries(tenant_id, filter).await
+    }
+
+    async fn list_entries_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        after: Option<AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        (**self).list_entries_chunk(tenant_id, filter, after, limit).await
+    }
+
+    async fn delete_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<u64, AppError> {
+        (**self).delete_entries(tenant_id, ids).await
+    }
+}
+
+impl SqlxAuditLogRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxAuditLogRepo { pool }
+    }
+}
+
+/// Appends the filter's conditions to a query already restricted to a tenant
+fn push_filter(qb: &mut QueryBuilder<'_, Postgres>, filter: &AuditLogFilter) {
+    if let Some(actor) = &filter.actor {
+        qb.push(" AND actor = ").push_bind(actor.clone());
+    }
+    if let Some(action) = &filter.action {
+        qb.push(" AND action = ").push_bind(action.clone());
+    }
+    if let Some(entity_type) = &filter.entity_type {
+        qb.push(" AND entity_type = ").push_bind(entity_type.clone());
+    }
+    if let Some(entity_id) = &filter.entity_id {
+        qb.push(" AND entity_id = ").push_bind(entity_id.clone());
+    }
+    if let Some(result) = filter.result {
+        qb.push(" AND result = ").push_bind(result.as_str());
+    }
+    if let Some(from) = filter.from {
+        qb.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        qb.push(" AND created_at < ").push_bind(to);
+    }
+    if let Some(q) = &filter.q {
+        // Matches idx_audit_log_details_search
+        qb.push(r#" AND jsonb_to_tsvector('simple', details, '["string", "numeric"]') @@ websearch_to_tsquery('simple', "#)
+            .push_bind(q.clone())
+            .push(")");
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for SqlxAuditLogRepo {
+    async fn record_entry(&self, tenant_id: &Uuid, entry: &AuditEntryInsert) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_log (tenant_id, actor, action, entity_type, entity_id, result, details)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            tenant_id,
+            entry.actor,
+            entry.action,
+            entry.entity_type,
+            entry.entity_id,
+            entry.result.as_str(),
+            entry.details,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn search_entries(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT * FROM audit_log WHERE tenant_id = ");
+        qb.push_bind(*tenant_id);
+        push_filter(&mut qb, filter);
+        qb.push(" ORDER BY created_at DESC, id LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let entries = qb
+            .build_query_as::<AuditEntry>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(entries)
+    }
+
+    async fn count_entries(&self, tenant_id: &Uuid, filter: &AuditLogFilter) -> Result<i64, AppError> {
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM audit_log WHERE tenant_id = ");
+        qb.push_bind(*tenant_id);
+        push_filter(&mut qb, filter);
+
+        let count = qb
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn list_entries_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        after: Option<AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT * FROM audit_log WHERE tenant_id = ");
+        qb.push_bind(*tenant_id);
+        push_filter(&mut qb, filter);
+        if let Some((created_at, id)) = after {
+            qb.push(" AND (created_at, id) > (")
+                .push_bind(created_at)
+                .push(", ")
+                .push_bind(id)
+                .push(")");
+        }
+        qb.push(" ORDER BY created_at ASC, id ASC LIMIT ").push_bind(limit);
+
+        let entries = qb
+            .build_query_as::<AuditEntry>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(entries)
+    }
+
+    async fn delete_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM audit_log WHERE tenant_id = $1 AND id = ANY($2)",
+            tenant_id,
+            ids
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
\ No newline at end of file
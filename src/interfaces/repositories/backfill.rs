@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::backfill::{BackfillState, BackfillTask, BACKFILL_COMPLETED, BACKFILL_RUNNING},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxBackfillRepo,
+};
+
+const STATE_COLUMNS: &str =
+    "name, status, checkpoint, processed, total, last_error, started_at, completed_at, updated_at";
+
+#[automock]
+#[async_trait]
+pub trait BackfillRepository: Send + Sync {
+    /// Progress rows of every task that has been started
+    async fn list_states(&self) -> Result<Vec<BackfillState>, AppError>;
+    /// Fills the task's next batch and moves its checkpoint past it, in one
+    /// transaction. `None` when another instance is running the task.
+    async fn run_batch(&self, task: &BackfillTask) -> Result<Option<BackfillState>, AppError>;
+    async fn record_failure(&self, name: &str, error: &str) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: BackfillRepository + ?Sized> BackfillRepository for Arc<T> {
+    async fn list_states(&self) -> Result<Vec<BackfillState>, AppError> {
+        (**self).list_states().await
+    }
+
+    async fn run_batch(&self, task: &BackfillTask) -> Result<Option<BackfillState>, AppError> {
+        (**self).run_batch(task).await
+    }
+
+    async fn record_failure(&self, name: &str, error: &str) -> Result<(), AppError> {
+        (**self).record_failure(name, error).await
+    }
+}
+
+impl SqlxBackfillRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxBackfillRepo { pool }
+    }
+}
+
+#[async_trait]
+impl BackfillRepository for SqlxBackfillRepo {
+    async fn list_states(&self) -> Result<Vec<BackfillState>, AppError> {
+        let states = sqlx::query_as::<_, BackfillState>(&format!("SELECT {STATE_COLUMNS} FROM backfills ORDER BY name"))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(states)
+    }
+
+    async fn run_batch(&self, task: &BackfillTask) -> Result<Option<BackfillState>, AppError> {
+        sqlx::query("INSERT INTO backfills (name) VALUES ($1) ON CONFLICT (name) DO NOTHING")
+            .bind(task.name)
+            .execute(&self.pool)
+            .await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        // The row lock is what keeps two instances off the same task
+        let state = sqlx::query_as::<_, BackfillState>(&format!(
+            "SELECT {STATE_COLUMNS} FROM backfills WHERE name = $1 FOR UPDATE SKIP LOCKED"
+        ))
+        .bind(task.name)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(state) = state else {
+            return Ok(None);
+        };
+        if state.is_completed() {
+            return Ok(Some(state));
+        }
+
+        // Table and conditions come from the task declarations, never from input
+        let total = match state.total {
+            Some(total) => total,
+            None => {
+                sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {} t WHERE {}", task.table, task.pending))
+                    .fetch_one(&mut *tx)
+                    .await?
+            }
+        };
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(&format!(
+            "SELECT t.id FROM {} t WHERE ($1::UUID IS NULL OR t.id > $1) AND {} ORDER BY t.id LIMIT $2",
+            task.table, task.pending
+        ))
+        .bind(state.checkpoint)
+        .bind(task.batch_size)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !ids.is_empty() {
+            sqlx::query(task.fill).bind(&ids).execute(&mut *tx).await?;
+        }
+
+        let done = (ids.len() as i64) < task.batch_size;
+        let state = sqlx::query_as::<_, BackfillState>(&format!(
+            r#"
+            UPDATE backfills SET
+                status = $2,
+                checkpoint = COALESCE($3, checkpoint),
+                processed = processed + $4,
+                total = $5,
+                last_error = NULL,
+                started_at = COALESCE(started_at, NOW()),
+                completed_at = CASE WHEN $2 = '{BACKFILL_COMPLETED}' THEN NOW() END,
+                updated_at = NOW()
+            WHERE name = $1
+            RETURNING {STATE_COLUMNS}
+            "#
+        ))
+        .bind(task.name)
+        .bind(if done { BACKFILL_COMPLETED } else { BACKFILL_RUNNING })
+        .bind(ids.last())
+        .bind(ids.len() as i64)
+        .bind(total)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(state))
+    }
+
+    async fn record_failure(&self, name: &str, error: &str) -> Result<(), AppError> {
+        sqlx::query("UPDATE backfills SET last_error = $2, updated_at = NOW() WHERE name = $1")
+            .bind(name)
+            .bind(error)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
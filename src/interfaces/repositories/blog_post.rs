@@ -1,34 +1,223 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use mockall::automock;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use sqlx::{self, PgPool, QueryBuilder};
 
 use crate::{
-    entities::{blog_post::{BlogPost, BlogPostInsert, UpdateBlogPostRequest}, option_fields::OptionField},
+    entities::{
+        blog_post::{BlogPost, BlogPostInsert, BlogSearchFilter, PostAuthor, PostByline, PostRevision, PostRevisionSummary, PostStatus, UpdateBlogPostRequest},
+        option_fields::OptionField,
+        series::{PostSeries, Series, SeriesInsert, SeriesPostLink, UpdateSeriesRequest},
+    },
     errors::AppError,
     repositories::sqlx_repo::SqlxBlogPostRepo,
 };
 
 /// Helper to compute OFFSET safely from 1-based `page` and `per_page`.
-fn page_offset(page: u32, per_page: u32) -> i64 {
+pub(crate) fn page_offset(page: u32, per_page: u32) -> i64 {
     let page = page.saturating_sub(1);
     (page as i64) * (per_page as i64)
 }
 
+#[automock]
 #[async_trait]
 pub trait BlogPostRepository: Sync + Send {
-    async fn create_blog_post(&self, post: &BlogPostInsert) -> Result<Uuid, AppError>;
-    async fn get_blog_post_by_id(&self, id: &Uuid) -> Result<BlogPost, AppError>;
-    async fn get_blog_post_by_slug(&self, slug: &str) -> Result<BlogPost, AppError>;
-    async fn update_blog_post(&self, id: &Uuid, post: &UpdateBlogPostRequest) -> Result<BlogPost, AppError>;
-    async fn get_all_blog_posts(&self, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError>;
-    async fn publish_blog_post(&self, id: &Uuid) -> Result<BlogPost, AppError>;
-    async fn count_blog_posts(&self, published_only: bool) -> Result<i64, AppError>;
-    async fn get_recent_blog_posts(&self, limit: u32, published_only: bool) -> Result<Vec<BlogPost>, AppError>;
-    async fn search_blog_posts(&self, query: &str) -> Result<Vec<BlogPost>, AppError>;
-    async fn get_blog_posts_by_tag(&self, tag: &str) -> Result<Vec<BlogPost>, AppError>;
-    async fn blog_post_exists_with_slug(&self, slug: &str, exclude_id: Option<Uuid>) -> Result<bool, AppError>;
-    async fn soft_delete_blog_post(&self, id: &Uuid) -> Result<(), AppError>;
-    async fn hard_delete_blog_post(&self, id: &Uuid) -> Result<(), AppError>;
+    async fn create_blog_post(&self, tenant_id: &Uuid, post: &BlogPostInsert) -> Result<Uuid, AppError>;
+    async fn get_blog_post_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError>;
+    async fn get_blog_post_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<BlogPost, AppError>;
+    async fn update_blog_post(&self, tenant_id: &Uuid, id: &Uuid, post: &UpdateBlogPostRequest) -> Result<BlogPost, AppError>;
+    async fn get_all_blog_posts(&self, tenant_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError>;
+    async fn publish_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError>;
+    async fn count_blog_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<i64, AppError>;
+    async fn get_recent_blog_posts(&self, tenant_id: &Uuid, limit: u32, published_only: bool) -> Result<Vec<BlogPost>, AppError>;
+    async fn search_blog_posts(&self, tenant_id: &Uuid, query: &str) -> Result<Vec<BlogPost>, AppError>;
+    /// Admin search over drafts, published and (optionally) trashed posts
+    async fn admin_search_blog_posts(&self, tenant_id: &Uuid, filter: &BlogSearchFilter) -> Result<Vec<BlogPost>, AppError>;
+    async fn get_blog_posts_by_tag(&self, tenant_id: &Uuid, tag: &str) -> Result<Vec<BlogPost>, AppError>;
+    async fn blog_post_exists_with_slug(&self, tenant_id: &Uuid, slug: &str, exclude_id: Option<Uuid>) -> Result<bool, AppError>;
+    async fn soft_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    async fn hard_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    /// Public profiles of the given authors; deleted users are left out
+    async fn get_post_authors(&self, tenant_id: &Uuid, author_ids: &[Uuid]) -> Result<Vec<PostAuthor>, AppError>;
+    /// Case-insensitive. Only users who can write or are credited on a
+    /// published post count as authors; when a username is shared the
+    /// earliest account wins
+    async fn get_author_by_username(&self, tenant_id: &Uuid, username: &str) -> Result<Option<PostAuthor>, AppError>;
+    /// Posts the author is credited on, co-authored ones included
+    async fn get_blog_posts_by_author(&self, tenant_id: &Uuid, author_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError>;
+    /// Bylines of the given posts in credit order; deleted users are left out
+    async fn get_post_bylines(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<Vec<PostByline>, AppError>;
+    /// Replaces a post's bylines with `user_ids`, lead author first
+    async fn set_post_authors(&self, tenant_id: &Uuid, post_id: &Uuid, user_ids: &[Uuid]) -> Result<(), AppError>;
+    async fn create_series(&self, tenant_id: &Uuid, series: &SeriesInsert) -> Result<Series, AppError>;
+    /// `title` and `slug` cannot be cleared; a null leaves them as they are
+    async fn update_series(&self, tenant_id: &Uuid, id: &Uuid, series: &UpdateSeriesRequest) -> Result<Series, AppError>;
+    /// Member posts stay; they only leave the series
+    async fn delete_series(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    async fn list_series(&self, tenant_id: &Uuid) -> Result<Vec<Series>, AppError>;
+    async fn get_series_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<Series>, AppError>;
+    async fn get_series_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<Option<Series>, AppError>;
+    /// Member posts in reading order; trashed posts are left out
+    async fn get_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError>;
+    /// Replaces the series' members with `post_ids`, in reading order
+    async fn set_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError>;
+    async fn get_post_series(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Option<PostSeries>, AppError>;
+    /// Featured posts in homepage order; trashed posts are left out
+    async fn get_featured_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError>;
+    /// Replaces the featured list with `post_ids`, in homepage order
+    async fn set_featured_posts(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError>;
+    /// Untrashed posts whose `published_at`, or last edit when unset, falls
+    /// in `[from, to)`, in that order
+    async fn get_calendar_posts(&self, tenant_id: &Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<BlogPost>, AppError>;
+    /// Posts edited or trashed after `since`, drafts and trashed ones
+    /// included, oldest change first. Without `since`, every published,
+    /// untrashed post.
+    async fn get_posts_changed_since(&self, tenant_id: &Uuid, since: Option<DateTime<Utc>>) -> Result<Vec<BlogPost>, AppError>;
+    /// A post's revisions, newest first; trashed posts keep theirs
+    async fn list_post_revisions(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostRevisionSummary>, AppError>;
+    async fn get_post_revision(&self, tenant_id: &Uuid, post_id: &Uuid, revision: i32) -> Result<Option<PostRevision>, AppError>;
+}
+
+#[async_trait]
+impl<T: BlogPostRepository + ?Sized> BlogPostRepository for Arc<T> {
+    async fn create_blog_post(&self, tenant_id: &Uuid, post: &BlogPostInsert) -> Result<Uuid, AppError> {
+        (**self).create_blog_post(tenant_id, post).await
+    }
+
+    async fn get_blog_post_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError> {
+        (**self).get_blog_post_by_id(tenant_id, id).await
+    }
+
+    async fn get_blog_post_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<BlogPost, AppError> {
+        (**self).get_blog_post_by_slug(tenant_id, slug).await
+    }
+
+    async fn update_blog_post(&self, tenant_id: &Uuid, id: &Uuid, post: &UpdateBlogPostRequest) -> Result<BlogPost, AppError> {
+        (**self).update_blog_post(tenant_id, id, post).await
+    }
+
+    async fn get_all_blog_posts(&self, tenant_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_all_blog_posts(tenant_id, published_only, page, per_page).await
+    }
+
+    async fn publish_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError> {
+        (**self).publish_blog_post(tenant_id, id).await
+    }
+
+    async fn count_blog_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<i64, AppError> {
+        (**self).count_blog_posts(tenant_id, published_only).await
+    }
+
+    async fn get_recent_blog_posts(&self, tenant_id: &Uuid, limit: u32, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_recent_blog_posts(tenant_id, limit, published_only).await
+    }
+
+    async fn search_blog_posts(&self, tenant_id: &Uuid, query: &str) -> Result<Vec<BlogPost>, AppError> {
+        (**self).search_blog_posts(tenant_id, query).await
+    }
+
+    async fn admin_search_blog_posts(&self, tenant_id: &Uuid, filter: &BlogSearchFilter) -> Result<Vec<BlogPost>, AppError> {
+        (**self).admin_search_blog_posts(tenant_id, filter).await
+    }
+
+    async fn get_blog_posts_by_tag(&self, tenant_id: &Uuid, tag: &str) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_blog_posts_by_tag(tenant_id, tag).await
+    }
+
+    async fn blog_post_exists_with_slug(&self, tenant_id: &Uuid, slug: &str, exclude_id: Option<Uuid>) -> Result<bool, AppError> {
+        (**self).blog_post_exists_with_slug(tenant_id, slug, exclude_id).await
+    }
+
+    async fn soft_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).soft_delete_blog_post(tenant_id, id).await
+    }
+
+    async fn hard_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).hard_delete_blog_post(tenant_id, id).await
+    }
+
+    async fn get_post_authors(&self, tenant_id: &Uuid, author_ids: &[Uuid]) -> Result<Vec<PostAuthor>, AppError> {
+        (**self).get_post_authors(tenant_id, author_ids).await
+    }
+
+    async fn get_author_by_username(&self, tenant_id: &Uuid, username: &str) -> Result<Option<PostAuthor>, AppError> {
+        (**self).get_author_by_username(tenant_id, username).await
+    }
+
+    async fn get_blog_posts_by_author(&self, tenant_id: &Uuid, author_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_blog_posts_by_author(tenant_id, author_id, published_only, page, per_page).await
+    }
+
+    async fn get_post_bylines(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<Vec<PostByline>, AppError> {
+        (**self).get_post_bylines(tenant_id, post_ids).await
+    }
+
+    async fn set_post_authors(&self, tenant_id: &Uuid, post_id: &Uuid, user_ids: &[Uuid]) -> Result<(), AppError> {
+        (**self).set_post_authors(tenant_id, post_id, user_ids).await
+    }
+
+    async fn create_series(&self, tenant_id: &Uuid, series: &SeriesInsert) -> Result<Series, AppError> {
+        (**self).create_series(tenant_id, series).await
+    }
+
+    async fn update_series(&self, tenant_id: &Uuid, id: &Uuid, series: &UpdateSeriesRequest) -> Result<Series, AppError> {
+        (**self).update_series(tenant_id, id, series).await
+    }
+
+    async fn delete_series(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).delete_series(tenant_id, id).await
+    }
+
+    async fn list_series(&self, tenant_id: &Uuid) -> Result<Vec<Series>, AppError> {
+        (**self).list_series(tenant_id).await
+    }
+
+    async fn get_series_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<Series>, AppError> {
+        (**self).get_series_by_id(tenant_id, id).await
+    }
+
+    async fn get_series_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<Option<Series>, AppError> {
+        (**self).get_series_by_slug(tenant_id, slug).await
+    }
+
+    async fn get_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_series_posts(tenant_id, series_id, published_only).await
+    }
+
+    async fn set_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError> {
+        (**self).set_series_posts(tenant_id, series_id, post_ids).await
+    }
+
+    async fn get_post_series(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Option<PostSeries>, AppError> {
+        (**self).get_post_series(tenant_id, post_id).await
+    }
+
+    async fn get_featured_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_featured_posts(tenant_id, published_only).await
+    }
+
+    async fn set_featured_posts(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError> {
+        (**self).set_featured_posts(tenant_id, post_ids).await
+    }
+
+    async fn get_calendar_posts(&self, tenant_id: &Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_calendar_posts(tenant_id, from, to).await
+    }
+
+    async fn get_posts_changed_since(&self, tenant_id: &Uuid, since: Option<DateTime<Utc>>) -> Result<Vec<BlogPost>, AppError> {
+        (**self).get_posts_changed_since(tenant_id, since).await
+    }
+
+    async fn list_post_revisions(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostRevisionSummary>, AppError> {
+        (**self).list_post_revisions(tenant_id, post_id).await
+    }
+
+    async fn get_post_revision(&self, tenant_id: &Uuid, post_id: &Uuid, revision: i32) -> Result<Option<PostRevision>, AppError> {
+        (**self).get_post_revision(tenant_id, post_id, revision).await
+    }
 }
 
 impl SqlxBlogPostRepo {
@@ -39,16 +228,19 @@ impl SqlxBlogPostRepo {
 
 #[async_trait]
 impl BlogPostRepository for SqlxBlogPostRepo {
-    async fn create_blog_post(&self, post: &BlogPostInsert) -> Result<Uuid, AppError> {
+    async fn create_blog_post(&self, tenant_id: &Uuid, post: &BlogPostInsert) -> Result<Uuid, AppError> {
+        let mut tx = self.pool.begin().await?;
+
         let id: Uuid = sqlx::query_scalar!(
             r#"
             INSERT INTO blog_posts (
-                title, slug, excerpt, content_markdown, cover_image_url, tags,
-                seo_title, seo_description, published, published_at, created_at, updated_at
+                tenant_id, title, slug, excerpt, content_markdown, cover_image_url, tags,
+                seo_title, seo_description, published, published_at, created_at, updated_at, author_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             RETURNING id
             "#,
+            tenant_id,
             post.title,
             post.slug,
             post.excerpt,
@@ -60,9 +252,10 @@ impl BlogPostRepository for SqlxBlogPostRepo {
             post.published,
             post.published_at,
             post.created_at,
-            post.updated_at
+            post.updated_at,
+            post.author_id
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e {
@@ -73,17 +266,32 @@ impl BlogPostRepository for SqlxBlogPostRepo {
             AppError::from(e)
         })?;
 
+        // The writer is the lead byline until an admin says otherwise
+        if let Some(author_id) = post.author_id {
+            sqlx::query!(
+                "INSERT INTO post_authors (post_id, user_id, tenant_id, position) VALUES ($1, $2, $3, 0)",
+                id,
+                author_id,
+                tenant_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
         Ok(id)
     }
 
-    async fn get_blog_post_by_id(&self, id: &Uuid) -> Result<BlogPost, AppError> {
+    async fn get_blog_post_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError> {
         let post = sqlx::query_as!(
             BlogPost,
             r#"
             SELECT * FROM blog_posts
-            WHERE id = $1 AND deleted_at IS NULL
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
             "#,
-            id
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -91,14 +299,15 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(post)
     }
 
-    async fn get_blog_post_by_slug(&self, slug: &str) -> Result<BlogPost, AppError> {
+    async fn get_blog_post_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<BlogPost, AppError> {
         let post = sqlx::query_as!(
             BlogPost,
             r#"
             SELECT * FROM blog_posts
-            WHERE slug = $1 AND deleted_at IS NULL
+            WHERE slug = $1 AND tenant_id = $2 AND deleted_at IS NULL
             "#,
-            slug
+            slug,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -106,8 +315,8 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(post)
     }
 
-    async fn update_blog_post(&self, id: &Uuid, post: &UpdateBlogPostRequest) -> Result<BlogPost, AppError> {
-        let current = self.get_blog_post_by_id(id).await?;
+    async fn update_blog_post(&self, tenant_id: &Uuid, id: &Uuid, post: &UpdateBlogPostRequest) -> Result<BlogPost, AppError> {
+        let current = self.get_blog_post_by_id(tenant_id, id).await?;
 
         let resolved_slug = resolve_slug_for_update(&post.slug, &post.title, &current.slug);
 
@@ -127,7 +336,7 @@ impl BlogPostRepository for SqlxBlogPostRepo {
                 published = COALESCE($9, published),
                 published_at = COALESCE($10, published_at),
                 updated_at = NOW()
-            WHERE id = $11 AND deleted_at IS NULL
+            WHERE id = $11 AND tenant_id = $12 AND deleted_at IS NULL
             RETURNING *
             "#,
             post.title.flatten_str(),             
@@ -140,7 +349,8 @@ impl BlogPostRepository for SqlxBlogPostRepo {
             post.seo_description.flatten_str(),   
             post.published.flatten_bool(),
             post.published_at.flatten_datetime(),
-            id
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await
@@ -156,7 +366,7 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(updated_post)
     }
 
-    async fn publish_blog_post(&self, id: &Uuid) -> Result<BlogPost, AppError> {
+    async fn publish_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError> {
         let published_post = sqlx::query_as!(
             BlogPost,
             r#"
@@ -164,10 +374,11 @@ impl BlogPostRepository for SqlxBlogPostRepo {
                 published = TRUE,
                 published_at = NOW(),
                 updated_at = NOW()
-            WHERE id = $1 AND deleted_at IS NULL
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
             RETURNING *
             "#,
-            id
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -175,11 +386,12 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(published_post)
     }
 
-    async fn get_all_blog_posts(&self, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
+    async fn get_all_blog_posts(&self, tenant_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
         let limit = per_page as i64;
         let offset = page_offset(page, per_page);
 
-        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL");
+        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL AND tenant_id = ");
+        builder.push_bind(*tenant_id);
 
         if published_only {
             builder.push(" AND published = TRUE");
@@ -200,16 +412,18 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(posts)
     }
 
-    async fn count_blog_posts(&self, published_only: bool) -> Result<i64, AppError> {
+    async fn count_blog_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<i64, AppError> {
         // Single query with the same filter predicate as listing
         let count: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(*)
             FROM blog_posts
-            WHERE deleted_at IS NULL
-              AND ($1::boolean IS FALSE OR published = TRUE)
+            WHERE tenant_id = $1
+              AND deleted_at IS NULL
+              AND ($2::boolean IS FALSE OR published = TRUE)
             "#
         )
+        .bind(tenant_id)
         .bind(published_only)
         .fetch_one(&self.pool)
         .await?;
@@ -218,13 +432,15 @@ impl BlogPostRepository for SqlxBlogPostRepo {
     }
 
     async fn get_recent_blog_posts(
-        &self, 
+        &self,
+        tenant_id: &Uuid,
         limit: u32,
         published_only: bool
     ) -> Result<Vec<BlogPost>, AppError> {
         let mut builder = QueryBuilder::new(
-            "SELECT * FROM blog_posts WHERE deleted_at IS NULL"
+            "SELECT * FROM blog_posts WHERE deleted_at IS NULL AND tenant_id = "
         );
+        builder.push_bind(*tenant_id);
 
         if published_only {
             builder.push(" AND published = TRUE");
@@ -239,8 +455,9 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(posts)
     }
 
-    async fn search_blog_posts(&self, query: &str) -> Result<Vec<BlogPost>, AppError> {
-        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL");
+    async fn search_blog_posts(&self, tenant_id: &Uuid, query: &str) -> Result<Vec<BlogPost>, AppError> {
+        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL AND tenant_id = ");
+        builder.push_bind(*tenant_id);
 
 
         builder.push(" AND (title ILIKE ").push_bind(format!("%{}%", query));
@@ -255,8 +472,49 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(posts)
     }
 
-    async fn get_blog_posts_by_tag(&self, tag: &str) -> Result<Vec<BlogPost>, AppError> {
-        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL");
+    async fn admin_search_blog_posts(&self, tenant_id: &Uuid, filter: &BlogSearchFilter) -> Result<Vec<BlogPost>, AppError> {
+        let pattern = format!("%{}%", escape_like(&filter.query));
+
+        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE tenant_id = ");
+        builder.push_bind(*tenant_id);
+
+        builder.push(" AND (title ILIKE ").push_bind(pattern.clone());
+        builder.push(" OR excerpt ILIKE ").push_bind(pattern.clone());
+        builder.push(" OR content_markdown ILIKE ").push_bind(pattern);
+        builder.push(")");
+
+        match filter.status {
+            Some(PostStatus::Deleted) => { builder.push(" AND deleted_at IS NOT NULL"); }
+            Some(status) => {
+                builder.push(" AND published = ").push_bind(status == PostStatus::Published);
+                if !filter.include_deleted {
+                    builder.push(" AND deleted_at IS NULL");
+                }
+            }
+            None if !filter.include_deleted => { builder.push(" AND deleted_at IS NULL"); }
+            None => {}
+        }
+
+        if let Some(tag) = &filter.tag {
+            builder.push(" AND tags @> ").push_bind(vec![tag.clone()]);
+        }
+        if let Some(from) = filter.created_from {
+            builder.push(" AND created_at >= ").push_bind(from);
+        }
+        if let Some(to) = filter.created_to {
+            builder.push(" AND created_at <= ").push_bind(to);
+        }
+
+        builder.push(" ORDER BY created_at DESC LIMIT ").push_bind(filter.limit);
+
+        let posts: Vec<BlogPost> = builder.build_query_as::<BlogPost>().fetch_all(&self.pool).await?;
+
+        Ok(posts)
+    }
+
+    async fn get_blog_posts_by_tag(&self, tenant_id: &Uuid, tag: &str) -> Result<Vec<BlogPost>, AppError> {
+        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL AND tenant_id = ");
+        builder.push_bind(*tenant_id);
 
 
         builder.push(" AND tags @> ").push_bind(vec![tag]);
@@ -270,7 +528,7 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(posts)
     }
 
-    async fn blog_post_exists_with_slug(&self, slug: &str, exclude_id: Option<Uuid>) -> Result<bool, AppError> {
+    async fn blog_post_exists_with_slug(&self, tenant_id: &Uuid, slug: &str, exclude_id: Option<Uuid>) -> Result<bool, AppError> {
         // Use dynamic query + binds to avoid problematic casts with NULL
         let exists: bool = sqlx::query_scalar(
             r#"
@@ -278,12 +536,14 @@ impl BlogPostRepository for SqlxBlogPostRepo {
                 SELECT 1
                 FROM blog_posts
                 WHERE slug = $1
+                  AND tenant_id = $2
                   AND deleted_at IS NULL
-                  AND ($2 IS NULL OR id <> $2)
+                  AND ($3 IS NULL OR id <> $3)
             )
             "#
         )
         .bind(slug)
+        .bind(tenant_id)
         .bind(exclude_id)
         .fetch_one(&self.pool)
         .await?;
@@ -291,14 +551,15 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(exists)
     }
 
-    async fn soft_delete_blog_post(&self, id: &Uuid) -> Result<(), AppError> {
+    async fn soft_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
         let result = sqlx::query!(
             r#"
             UPDATE blog_posts
             SET deleted_at = NOW()
-            WHERE id = $1 AND deleted_at IS NULL
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
             "#,
-            id
+            id,
+            tenant_id
         )
         .execute(&self.pool)
         .await?;
@@ -310,13 +571,14 @@ impl BlogPostRepository for SqlxBlogPostRepo {
         Ok(())
     }
 
-    async fn hard_delete_blog_post(&self, id: &Uuid) -> Result<(), AppError> {
+    async fn hard_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
         let result = sqlx::query!(
             r#"
             DELETE FROM blog_posts
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = $2
             "#,
-            id
+            id,
+            tenant_id
         )
         .execute(&self.pool)
         .await?;
@@ -327,9 +589,488 @@ impl BlogPostRepository for SqlxBlogPostRepo {
 
         Ok(())
     }
+
+    async fn get_post_authors(&self, tenant_id: &Uuid, author_ids: &[Uuid]) -> Result<Vec<PostAuthor>, AppError> {
+        let authors = sqlx::query_as!(
+            PostAuthor,
+            r#"
+            SELECT id, username, COALESCE(display_name, username) AS name, avatar_url
+            FROM users
+            WHERE tenant_id = $1 AND id = ANY($2) AND deleted_at IS NULL
+            "#,
+            tenant_id,
+            author_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(authors)
+    }
+
+    async fn get_author_by_username(&self, tenant_id: &Uuid, username: &str) -> Result<Option<PostAuthor>, AppError> {
+        let author = sqlx::query_as!(
+            PostAuthor,
+            r#"
+            SELECT u.id, u.username, COALESCE(u.display_name, u.username) AS name, u.avatar_url
+            FROM users u
+            WHERE u.tenant_id = $1
+              AND LOWER(u.username) = LOWER($2)
+              AND u.deleted_at IS NULL
+              AND (
+                u.is_admin OR u.is_editor OR EXISTS (
+                    SELECT 1 FROM post_authors pa
+                    JOIN blog_posts p ON p.id = pa.post_id
+                    WHERE pa.user_id = u.id AND p.published AND p.deleted_at IS NULL
+                )
+              )
+            ORDER BY u.created_at
+            LIMIT 1
+            "#,
+            tenant_id,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(author)
+    }
+
+    async fn get_blog_posts_by_author(&self, tenant_id: &Uuid, author_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
+        let mut builder = QueryBuilder::new("SELECT * FROM blog_posts WHERE deleted_at IS NULL AND tenant_id = ");
+        builder.push_bind(*tenant_id);
+        builder.push(" AND id IN (SELECT post_id FROM post_authors WHERE user_id = ").push_bind(*author_id);
+        builder.push(")");
+
+        if published_only {
+            builder.push(" AND published = TRUE ORDER BY published_at DESC NULLS LAST");
+        } else {
+            builder.push(" ORDER BY created_at DESC");
+        }
+
+        builder.push(" LIMIT ").push_bind(per_page as i64);
+        builder.push(" OFFSET ").push_bind(page_offset(page, per_page));
+
+        let posts: Vec<BlogPost> = builder.build_query_as::<BlogPost>().fetch_all(&self.pool).await?;
+
+        Ok(posts)
+    }
+
+    async fn get_post_bylines(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<Vec<PostByline>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT pa.post_id, u.id, u.username, COALESCE(u.display_name, u.username) AS name, u.avatar_url
+            FROM post_authors pa
+            JOIN users u ON u.id = pa.user_id
+            WHERE pa.tenant_id = $1 AND pa.post_id = ANY($2) AND u.deleted_at IS NULL
+            ORDER BY pa.post_id, pa.position
+            "#,
+            tenant_id,
+            post_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PostByline {
+                post_id: row.post_id,
+                author: PostAuthor { id: row.id, username: row.username, name: row.name, avatar_url: row.avatar_url },
+            })
+            .collect())
+    }
+
+    async fn set_post_authors(&self, tenant_id: &Uuid, post_id: &Uuid, user_ids: &[Uuid]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Locks the post so concurrent byline edits apply one after the other
+        sqlx::query_scalar!(
+            "SELECT id FROM blog_posts WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL FOR UPDATE",
+            post_id,
+            tenant_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Blog post not found".into()))?;
+
+        sqlx::query!("DELETE FROM post_authors WHERE post_id = $1", post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_authors (post_id, user_id, tenant_id, position)
+            SELECT $1, user_id, $2, (ord - 1)::INTEGER
+            FROM UNNEST($3::UUID[]) WITH ORDINALITY AS bylines(user_id, ord)
+            "#,
+            post_id,
+            tenant_id,
+            user_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn create_series(&self, tenant_id: &Uuid, series: &SeriesInsert) -> Result<Series, AppError> {
+        sqlx::query_as!(
+            Series,
+            r#"
+            INSERT INTO blog_series (tenant_id, title, slug, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            tenant_id,
+            series.title,
+            series.slug,
+            series.description
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(series_slug_conflict)
+    }
+
+    async fn update_series(&self, tenant_id: &Uuid, id: &Uuid, series: &UpdateSeriesRequest) -> Result<Series, AppError> {
+        sqlx::query_as!(
+            Series,
+            r#"
+            UPDATE blog_series SET
+                title = COALESCE($3, title),
+                slug = COALESCE($4, slug),
+                description = CASE WHEN $5 THEN $6 ELSE description END,
+                updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2
+            RETURNING *
+            "#,
+            id,
+            tenant_id,
+            series.title.flatten_str(),
+            series.slug.flatten_str(),
+            !series.description.is_unchanged(),
+            series.description.flatten_str()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(series_slug_conflict)?
+        .ok_or_else(|| AppError::NotFound("Series not found".into()))
+    }
+
+    async fn delete_series(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!("DELETE FROM blog_series WHERE id = $1 AND tenant_id = $2", id, tenant_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Series not found".into()));
+        }
+
+        Ok(())
+    }
+
+    async fn list_series(&self, tenant_id: &Uuid) -> Result<Vec<Series>, AppError> {
+        let series = sqlx::query_as!(
+            Series,
+            "SELECT * FROM blog_series WHERE tenant_id = $1 ORDER BY created_at DESC",
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(series)
+    }
+
+    async fn get_series_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<Series>, AppError> {
+        let series = sqlx::query_as!(
+            Series,
+            "SELECT * FROM blog_series WHERE tenant_id = $1 AND id = $2",
+            tenant_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(series)
+    }
+
+    async fn get_series_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<Option<Series>, AppError> {
+        let series = sqlx::query_as!(
+            Series,
+            "SELECT * FROM blog_series WHERE tenant_id = $1 AND slug = LOWER($2)",
+            tenant_id,
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(series)
+    }
+
+    async fn get_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        let posts = sqlx::query_as!(
+            BlogPost,
+            r#"
+            SELECT p.* FROM blog_posts p
+            JOIN series_posts sp ON sp.post_id = p.id
+            WHERE sp.series_id = $1 AND p.tenant_id = $2 AND p.deleted_at IS NULL
+              AND (p.published OR NOT $3)
+            ORDER BY sp.position
+            "#,
+            series_id,
+            tenant_id,
+            published_only
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(posts)
+    }
+
+    async fn set_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Locks the series so concurrent reorders apply one after the other
+        sqlx::query_scalar!(
+            "SELECT id FROM blog_series WHERE id = $1 AND tenant_id = $2 FOR UPDATE",
+            series_id,
+            tenant_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Series not found".into()))?;
+
+        let known = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM blog_posts WHERE tenant_id = $1 AND id = ANY($2) AND deleted_at IS NULL"#,
+            tenant_id,
+            post_ids
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if known != post_ids.len() as i64 {
+            return Err(AppError::InvalidInput("Every post must exist and not be in the trash".into()));
+        }
+
+        sqlx::query!("DELETE FROM series_posts WHERE series_id = $1", series_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO series_posts (series_id, post_id, tenant_id, position)
+            SELECT $1, post_id, $2, (ord - 1)::INTEGER
+            FROM UNNEST($3::UUID[]) WITH ORDINALITY AS members(post_id, ord)
+            "#,
+            series_id,
+            tenant_id,
+            post_ids
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.constraint() == Some("series_posts_post_id_key") => {
+                AppError::Conflict("A post can only belong to one series".into())
+            }
+            _ => AppError::from(e),
+        })?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_post_series(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Option<PostSeries>, AppError> {
+        let series = sqlx::query_as!(
+            Series,
+            r#"
+            SELECT s.* FROM blog_series s
+            JOIN series_posts sp ON sp.series_id = s.id
+            WHERE sp.post_id = $1 AND s.tenant_id = $2
+            "#,
+            post_id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(series) = series else {
+            return Ok(None);
+        };
+
+        let members = sqlx::query_as!(
+            SeriesPostLink,
+            r#"
+            SELECT p.id, p.title, p.slug, p.published FROM blog_posts p
+            JOIN series_posts sp ON sp.post_id = p.id
+            WHERE sp.series_id = $1 AND p.deleted_at IS NULL
+            ORDER BY sp.position
+            "#,
+            series.id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(PostSeries { series, members }))
+    }
+
+    async fn get_featured_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        let posts = sqlx::query_as!(
+            BlogPost,
+            r#"
+            SELECT * FROM blog_posts
+            WHERE tenant_id = $1 AND featured AND deleted_at IS NULL
+              AND (published OR NOT $2)
+            ORDER BY featured_order
+            "#,
+            tenant_id,
+            published_only
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(posts)
+    }
+
+    async fn set_featured_posts(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Locks the tenant so concurrent curations apply one after the other
+        sqlx::query_scalar!("SELECT id FROM tenants WHERE id = $1 FOR UPDATE", tenant_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tenant not found".into()))?;
+
+        let known = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM blog_posts WHERE tenant_id = $1 AND id = ANY($2) AND deleted_at IS NULL"#,
+            tenant_id,
+            post_ids
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if known != post_ids.len() as i64 {
+            return Err(AppError::InvalidInput("Every post must exist and not be in the trash".into()));
+        }
+
+        sqlx::query!(
+            "UPDATE blog_posts SET featured = FALSE, featured_order = NULL WHERE tenant_id = $1 AND featured",
+            tenant_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE blog_posts p
+            SET featured = TRUE, featured_order = (picks.ord - 1)::INTEGER
+            FROM UNNEST($2::UUID[]) WITH ORDINALITY AS picks(post_id, ord)
+            WHERE p.id = picks.post_id AND p.tenant_id = $1
+            "#,
+            tenant_id,
+            post_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_calendar_posts(&self, tenant_id: &Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<BlogPost>, AppError> {
+        let posts = sqlx::query_as!(
+            BlogPost,
+            r#"
+            SELECT * FROM blog_posts
+            WHERE tenant_id = $1 AND deleted_at IS NULL
+              AND COALESCE(published_at, updated_at) >= $2
+              AND COALESCE(published_at, updated_at) < $3
+            ORDER BY COALESCE(published_at, updated_at), id
+            "#,
+            tenant_id,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(posts)
+    }
+
+    async fn get_posts_changed_since(&self, tenant_id: &Uuid, since: Option<DateTime<Utc>>) -> Result<Vec<BlogPost>, AppError> {
+        let posts = sqlx::query_as!(
+            BlogPost,
+            r#"
+            SELECT * FROM blog_posts
+            WHERE tenant_id = $1
+              AND CASE
+                  WHEN $2::TIMESTAMPTZ IS NULL THEN deleted_at IS NULL AND published
+                  ELSE updated_at > $2 OR deleted_at > $2
+              END
+            ORDER BY GREATEST(updated_at, deleted_at), id
+            "#,
+            tenant_id,
+            since
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(posts)
+    }
+
+    async fn list_post_revisions(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostRevisionSummary>, AppError> {
+        let revisions = sqlx::query_as!(
+            PostRevisionSummary,
+            r#"
+            SELECT revision, title, published, created_at FROM post_revisions
+            WHERE tenant_id = $1 AND post_id = $2
+            ORDER BY revision DESC
+            "#,
+            tenant_id,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    async fn get_post_revision(&self, tenant_id: &Uuid, post_id: &Uuid, revision: i32) -> Result<Option<PostRevision>, AppError> {
+        let revision = sqlx::query_as!(
+            PostRevision,
+            "SELECT * FROM post_revisions WHERE tenant_id = $1 AND post_id = $2 AND revision = $3",
+            tenant_id,
+            post_id,
+            revision
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revision)
+    }
+}
+
+fn series_slug_conflict(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db_err) if db_err.constraint() == Some("blog_series_tenant_id_slug_key") => {
+            AppError::Conflict("Series slug already exists".into())
+        }
+        _ => AppError::from(e),
+    }
+}
+
+/// Escapes `%`, `_` and `\` so user input matches literally in ILIKE
+fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
-fn resolve_slug_for_update(
+pub(crate) fn resolve_slug_for_update(
     slug_field: &OptionField<String>,
     title_field: &OptionField<String>,
     current_slug: &str,
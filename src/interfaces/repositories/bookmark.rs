@@ -0,0 +1,320 @@
+use std::{borrow::Cow, sync::Arc};
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::bookmark::{Bookmark, BookmarkInsert, LinkPreview, MAX_PREVIEW_ATTEMPTS, PREVIEW_PENDING},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxBookmarkRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait BookmarkRepository: Send + Sync {
+    async fn create_bookmark(&self, tenant_id: &Uuid, bookmark: &BookmarkInsert) -> Result<Bookmark, AppError>;
+    async fn get_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError>;
+    /// Newest first; `public_only` leaves out bookmarks hidden from the blogroll
+    async fn list_bookmarks(
+        &self,
+        tenant_id: &Uuid,
+        public_only: bool,
+        tag: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Bookmark>, AppError>;
+    async fn count_bookmarks(&self, tenant_id: &Uuid, public_only: bool, tag: Option<String>) -> Result<i64, AppError>;
+    /// Overwrites the editable columns with the values in `bookmark`; the
+    /// preview columns are left alone
+    async fn update_bookmark(&self, tenant_id: &Uuid, bookmark: &Bookmark) -> Result<Bookmark, AppError>;
+    async fn delete_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    /// Bookmarks of every tenant still waiting for a preview, oldest first
+    async fn list_pending_previews(&self, limit: i64) -> Result<Vec<Bookmark>, AppError>;
+    async fn record_preview(&self, id: &Uuid, preview: &LinkPreview) -> Result<(), AppError>;
+    /// Counts a failed fetch; the preview is marked failed once it reaches
+    /// `MAX_PREVIEW_ATTEMPTS`
+    async fn record_preview_failure(&self, id: &Uuid, error: &str) -> Result<(), AppError>;
+    /// Clears the preview and queues the bookmark for a new fetch
+    async fn reset_preview(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError>;
+}
+
+#[async_trait]
+impl<T: BookmarkRepository + ?Sized> BookmarkRepository for Arc<T> {
+    async fn create_bookmark(&self, tenant_id: &Uuid, bookmark: &BookmarkInsert) -> Result<Bookmark, AppError> {
+        (**self).create_bookmark(tenant_id, bookmark).await
+    }
+
+    async fn get_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError> {
+        (**self).get_bookmark(tenant_id, id).await
+    }
+
+    async fn list_bookmarks(
+        &self,
+        tenant_id: &Uuid,
+        public_only: bool,
+        tag: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Bookmark>, AppError> {
+        (**self).list_bookmarks(tenant_id, public_only, tag, limit, offset).await
+    }
+
+    async fn count_bookmarks(&self, tenant_id: &Uuid, public_only: bool, tag: Option<String>) -> Result<i64, AppError> {
+        (**self).count_bookmarks(tenant_id, public_only, tag).await
+    }
+
+    async fn update_bookmark(&self, tenant_id: &Uuid, bookmark: &Bookmark) -> Result<Bookmark, AppError> {
+        (**self).update_bookmark(tenant_id, bookmark).await
+    }
+
+    async fn delete_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).delete_bookmark(tenant_id, id).await
+    }
+
+    async fn list_pending_previews(&self, limit: i64) -> Result<Vec<Bookmark>, AppError> {
+        (**self).list_pending_previews(limit).await
+    }
+
+    async fn record_preview(&self, id: &Uuid, preview: &LinkPreview) -> Result<(), AppError> {
+        (**self).record_preview(id, preview).await
+    }
+
+    async fn record_preview_failure(&self, id: &Uuid, error: &str) -> Result<(), AppError> {
+        (**self).record_preview_failure(id, error).await
+    }
+
+    async fn reset_preview(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError> {
+        (**self).reset_preview(tenant_id, id).await
+    }
+}
+
+impl SqlxBookmarkRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxBookmarkRepo { pool }
+    }
+}
+
+fn map_url_conflict(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(db_err) if db_err.code() == Some(Cow::Borrowed("23505")) => {
+            AppError::Conflict("This link is already bookmarked".to_string())
+        }
+        _ => AppError::from(e),
+    }
+}
+
+#[async_trait]
+impl BookmarkRepository for SqlxBookmarkRepo {
+    async fn create_bookmark(&self, tenant_id: &Uuid, bookmark: &BookmarkInsert) -> Result<Bookmark, AppError> {
+        sqlx::query_as!(
+            Bookmark,
+            r#"
+            INSERT INTO bookmarks (tenant_id, url, title, note, tags, is_public)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+            tenant_id,
+            bookmark.url,
+            bookmark.title,
+            bookmark.note,
+            &bookmark.tags,
+            bookmark.is_public,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_url_conflict)
+    }
+
+    async fn get_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError> {
+        sqlx::query_as!(
+            Bookmark,
+            r#"SELECT * FROM bookmarks WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Bookmark not found".into()))
+    }
+
+    async fn list_bookmarks(
+        &self,
+        tenant_id: &Uuid,
+        public_only: bool,
+        tag: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Bookmark>, AppError> {
+        let bookmarks = sqlx::query_as!(
+            Bookmark,
+            r#"
+            SELECT * FROM bookmarks
+            WHERE tenant_id = $1
+              AND (NOT $2 OR is_public)
+              AND ($3::text IS NULL OR tags @> ARRAY[$3])
+            ORDER BY created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            tenant_id,
+            public_only,
+            tag,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bookmarks)
+    }
+
+    async fn count_bookmarks(&self, tenant_id: &Uuid, public_only: bool, tag: Option<String>) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM bookmarks
+            WHERE tenant_id = $1
+              AND (NOT $2 OR is_public)
+              AND ($3::text IS NULL OR tags @> ARRAY[$3])
+            "#,
+            tenant_id,
+            public_only,
+            tag
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn update_bookmark(&self, tenant_id: &Uuid, bookmark: &Bookmark) -> Result<Bookmark, AppError> {
+        sqlx::query_as!(
+            Bookmark,
+            r#"
+            UPDATE bookmarks SET
+                url = $1,
+                title = $2,
+                note = $3,
+                tags = $4,
+                is_public = $5,
+                updated_at = NOW()
+            WHERE id = $6 AND tenant_id = $7
+            RETURNING *
+            "#,
+            bookmark.url,
+            bookmark.title,
+            bookmark.note,
+            &bookmark.tags,
+            bookmark.is_public,
+            bookmark.id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_url_conflict)?
+        .ok_or_else(|| AppError::NotFound("Bookmark not found".into()))
+    }
+
+    async fn delete_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"DELETE FROM bookmarks WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Bookmark not found".into()));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pending_previews(&self, limit: i64) -> Result<Vec<Bookmark>, AppError> {
+        let bookmarks = sqlx::query_as!(
+            Bookmark,
+            r#"
+            SELECT * FROM bookmarks
+            WHERE preview_status = $1
+            ORDER BY created_at
+            LIMIT $2
+            "#,
+            PREVIEW_PENDING,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bookmarks)
+    }
+
+    async fn record_preview(&self, id: &Uuid, preview: &LinkPreview) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE bookmarks SET
+                preview_status = 'ready',
+                preview_title = $1,
+                preview_description = $2,
+                preview_image_url = $3,
+                preview_site_name = $4,
+                preview_error = NULL,
+                preview_attempts = preview_attempts + 1,
+                preview_fetched_at = NOW()
+            WHERE id = $5
+            "#,
+            preview.title,
+            preview.description,
+            preview.image_url,
+            preview.site_name,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn record_preview_failure(&self, id: &Uuid, error: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE bookmarks SET
+                preview_attempts = preview_attempts + 1,
+                preview_status = CASE WHEN preview_attempts + 1 >= $1 THEN 'failed' ELSE preview_status END,
+                preview_error = $2,
+                preview_fetched_at = NOW()
+            WHERE id = $3
+            "#,
+            MAX_PREVIEW_ATTEMPTS,
+            error,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn reset_preview(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError> {
+        sqlx::query_as!(
+            Bookmark,
+            r#"
+            UPDATE bookmarks SET
+                preview_status = 'pending',
+                preview_title = NULL,
+                preview_description = NULL,
+                preview_image_url = NULL,
+                preview_site_name = NULL,
+                preview_error = NULL,
+                preview_attempts = 0,
+                preview_fetched_at = NULL
+            WHERE id = $1 AND tenant_id = $2
+            RETURNING *
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Bookmark not found".into()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use std::{borrow::Cow, sync::Arc};
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::changelog::{ChangelogEntry, ChangelogEntryInsert},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxChangelogRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait ChangelogRepository: Send + Sync {
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntryInsert) -> Result<ChangelogEntry, AppError>;
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ChangelogEntry, AppError>;
+    async fn get_entry_by_version(&self, tenant_id: &Uuid, version: &str) -> Result<Option<ChangelogEntry>, AppError>;
+    /// Newest release first
+    async fn list_entries(
+        &self,
+        tenant_id: &Uuid,
+        category: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ChangelogEntry>, AppError>;
+    async fn count_entries(&self, tenant_id: &Uuid, category: Option<String>) -> Result<i64, AppError>;
+    /// Overwrites every editable column with the values in `entry`
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntry) -> Result<ChangelogEntry, AppError>;
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: ChangelogRepository + ?Sized> ChangelogRepository for Arc<T> {
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntryInsert) -> Result<ChangelogEntry, AppError> {
+        (**self).create_entry(tenant_id, entry).await
+    }
+
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ChangelogEntry, AppError> {
+        (**self).get_entry(tenant_id, id).await
+    }
+
+    async fn get_entry_by_version(&self, tenant_id: &Uuid, version: &str) -> Result<Option<ChangelogEntry>, AppError> {
+        (**self).get_entry_by_version(tenant_id, version).await
+    }
+
+    async fn list_entries(
+        &self,
+        tenant_id: &Uuid,
+        category: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ChangelogEntry>, AppError> {
+        (**self).list_entries(tenant_id, category, limit, offset).await
+    }
+
+    async fn count_entries(&self, tenant_id: &Uuid, category: Option<String>) -> Result<i64, AppError> {
+        (**self).count_entries(tenant_id, category).await
+    }
+
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntry) -> Result<ChangelogEntry, AppError> {
+        (**self).update_entry(tenant_id, entry).await
+    }
+
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).delete_entry(tenant_id, id).await
+    }
+}
+
+impl SqlxChangelogRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxChangelogRepo { pool }
+    }
+}
+
+fn map_version_conflict(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(db_err) if db_err.code() == Some(Cow::Borrowed("23505")) => {
+            AppError::Conflict("A changelog entry for this version already exists".to_string())
+        }
+        _ => AppError::from(e),
+    }
+}
+
+#[async_trait]
+impl ChangelogRepository for SqlxChangelogRepo {
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntryInsert) -> Result<ChangelogEntry, AppError> {
+        sqlx::query_as!(
+            ChangelogEntry,
+            r#"
+            INSERT INTO changelog_entries (tenant_id, version, released_on, notes_markdown, category)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            tenant_id,
+            entry.version,
+            entry.released_on,
+            entry.notes_markdown,
+            entry.category,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_version_conflict)
+    }
+
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ChangelogEntry, AppError> {
+        sqlx::query_as!(
+            ChangelogEntry,
+            r#"SELECT * FROM changelog_entries WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Changelog entry not found".into()))
+    }
+
+    async fn get_entry_by_version(&self, tenant_id: &Uuid, version: &str) -> Result<Option<ChangelogEntry>, AppError> {
+        let entry = sqlx::query_as!(
+            ChangelogEntry,
+            r#"SELECT * FROM changelog_entries WHERE tenant_id = $1 AND version = $2"#,
+            tenant_id,
+            version
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    async fn list_entries(
+        &self,
+        tenant_id: &Uuid,
+        category: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ChangelogEntry>, AppError> {
+        let entries = sqlx::query_as!(
+            ChangelogEntry,
+            r#"
+            SELECT * FROM changelog_entries
+            WHERE tenant_id = $1 AND ($2::text IS NULL OR category = $2)
+            ORDER BY released_on DESC, created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            tenant_id,
+            category,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn count_entries(&self, tenant_id: &Uuid, category: Option<String>) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM changelog_entries
+            WHERE tenant_id = $1 AND ($2::text IS NULL OR category = $2)
+            "#,
+            tenant_id,
+            category
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntry) -> Result<ChangelogEntry, AppError> {
+        sqlx::query_as!(
+            ChangelogEntry,
+            r#"
+            UPDATE changelog_entries SET
+                version = $1,
+                released_on = $2,
+                notes_markdown = $3,
+                category = $4,
+                updated_at = NOW()
+            WHERE id = $5 AND tenant_id = $6
+            RETURNING *
+            "#,
+            entry.version,
+            entry.released_on,
+            entry.notes_markdown,
+            entry.category,
+            entry.id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_version_conflict)?
+        .ok_or_else(|| AppError::NotFound("Changelog entry not found".into()))
+    }
+
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"DELETE FROM changelog_entries WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Changelog entry not found".into()));
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
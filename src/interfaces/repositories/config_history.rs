@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        config_change::{ConfigChange, ConfigChangeSource, CONFIG_KIND_FEATURE_FLAG, CONFIG_KIND_SETTING},
+        feature_flag::FeatureFlag,
+    },
+    errors::AppError,
+    repositories::sqlx_repo::SqlxConfigHistoryRepo,
+};
+
+const CHANGE_COLUMNS: &str =
+    "id AS version, tenant_id, kind, key, previous_value, new_value, actor_id, rollback_to, changed_at";
+
+/// Versioned writes to a tenant's `app_settings` and `feature_flags`: each
+/// change is applied and recorded with the value it replaced in one
+/// transaction.
+#[automock]
+#[async_trait]
+pub trait ConfigHistoryRepository: Send + Sync {
+    /// Sets the tenant's setting, or removes it when `value` is `None`
+    async fn change_setting(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        value: Option<JsonValue>,
+        source: &ConfigChangeSource,
+    ) -> Result<ConfigChange, AppError>;
+    /// Creates or toggles the flag; `description: None` keeps the stored one
+    async fn change_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+        source: &ConfigChangeSource,
+    ) -> Result<(FeatureFlag, ConfigChange), AppError>;
+    /// Newest first, optionally only versions below `before` and one key
+    async fn list_changes(
+        &self,
+        tenant_id: &Uuid,
+        key: Option<String>,
+        before: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<ConfigChange>, AppError>;
+    async fn get_change(&self, tenant_id: &Uuid, version: i64) -> Result<ConfigChange, AppError>;
+    /// Every change after `version`, oldest first
+    async fn changes_after(&self, tenant_id: &Uuid, version: i64) -> Result<Vec<ConfigChange>, AppError>;
+}
+
+#[async_trait]
+impl<T: ConfigHistoryRepository + ?Sized> ConfigHistoryRepository for Arc<T> {
+    async fn change_setting(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        value: Option<JsonValue>,
+        source: &ConfigChangeSource,
+    ) -> Result<ConfigChange, AppError> {
+        (**self).change_setting(tenant_id, key, value, source).await
+    }
+
+    async fn change_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+        source: &ConfigChangeSource,
+    ) -> Result<(FeatureFlag, ConfigChange), AppError> {
+        (**self).change_feature_flag(tenant_id, key, enabled, description, source).await
+    }
+
+    async fn list_changes(
+        &self,
+        tenant_id: &Uuid,
+        key: Option<String>,
+        before: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<ConfigChange>, AppError> {
+        (**self).list_changes(tenant_id, key, before, limit).await
+    }
+
+    async fn get_change(&self, tenant_id: &Uuid, version: i64) -> Result<ConfigChange, AppError> {
+        (**self).get_change(tenant_id, version).await
+    }
+
+    async fn changes_after(&self, tenant_id: &Uuid, version: i64) -> Result<Vec<ConfigChange>, AppError> {
+        (**self).changes_after(tenant_id, version).await
+    }
+}
+
+impl SqlxConfigHistoryRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxConfigHistoryRepo { pool }
+    }
+}
+
+#[async_trait]
+impl ConfigHistoryRepository for SqlxConfigHistoryRepo {
+    async fn change_setting(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        value: Option<JsonValue>,
+        source: &ConfigChangeSource,
+    ) -> Result<ConfigChange, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Locks the row so the recorded previous value is the one replaced
+        let previous: Option<JsonValue> = sqlx::query_scalar(
+            "SELECT value FROM app_settings WHERE tenant_id = $1 AND key = $2 FOR UPDATE"
+        )
+        .bind(tenant_id)
+        .bind(key)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        match &value {
+            Some(value) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO app_settings (tenant_id, key, value)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (tenant_id, key) DO UPDATE SET
+                        value = EXCLUDED.value,
+                        updated_at = NOW()
+                    "#
+                )
+                .bind(tenant_id)
+                .bind(key)
+                .bind(value)
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM app_settings WHERE tenant_id = $1 AND key = $2")
+                    .bind(tenant_id)
+                    .bind(key)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let change = insert_change(&mut tx, *tenant_id, CONFIG_KIND_SETTING, key, previous, value, source).await?;
+        tx.commit().await?;
+
+        Ok(change)
+    }
+
+    async fn change_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+        source: &ConfigChangeSource,
+    ) -> Result<(FeatureFlag, ConfigChange), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous: Option<bool> = sqlx::query_scalar(
+            "SELECT enabled FROM feature_flags WHERE tenant_id = $1 AND key = $2 FOR UPDATE"
+        )
+        .bind(tenant_id)
+        .bind(key)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let flag = sqlx::query_as::<_, FeatureFlag>(
+            r#"
+            INSERT INTO feature_flags (tenant_id, key, enabled, description)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tenant_id, key) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                description = COALESCE(EXCLUDED.description, feature_flags.description),
+                updated_at = NOW()
+            RETURNING tenant_id, key, enabled, description, created_at, updated_at
+            "#
+        )
+        .bind(tenant_id)
+        .bind(key)
+        .bind(enabled)
+        .bind(description)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let change = insert_change(
+            &mut tx,
+            *tenant_id,
+            CONFIG_KIND_FEATURE_FLAG,
+            key,
+            previous.map(JsonValue::Bool),
+            Some(JsonValue::Bool(enabled)),
+            source,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok((flag, change))
+    }
+
+    async fn list_changes(
+        &self,
+        tenant_id: &Uuid,
+        key: Option<String>,
+        before: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<ConfigChange>, AppError> {
+        let changes = sqlx::query_as::<_, ConfigChange>(&format!(
+            r#"
+            SELECT {CHANGE_COLUMNS} FROM config_changes
+            WHERE tenant_id = $1
+              AND ($2::TEXT IS NULL OR key = $2)
+              AND ($3::BIGINT IS NULL OR id < $3)
+            ORDER BY id DESC
+            LIMIT $4
+            "#
+        ))
+        .bind(tenant_id)
+        .bind(key)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(changes)
+    }
+
+    async fn get_change(&self, tenant_id: &Uuid, version: i64) -> Result<ConfigChange, AppError> {
+        sqlx::query_as::<_, ConfigChange>(&format!(
+            "SELECT {CHANGE_COLUMNS} FROM config_changes WHERE id = $2 AND tenant_id = $1"
+        ))
+        .bind(tenant_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Config version {} not found", version)))
+    }
+
+    async fn changes_after(&self, tenant_id: &Uuid, version: i64) -> Result<Vec<ConfigChange>, AppError> {
+        let changes = sqlx::query_as::<_, ConfigChange>(&format!(
+            r#"
+            SELECT {CHANGE_COLUMNS} FROM config_changes
+            WHERE tenant_id = $1 AND id > $2
+            ORDER BY id
+            "#
+        ))
+        .bind(tenant_id)
+        .bind(version)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(changes)
+    }
+}
+
+async fn insert_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    tenant_id: Uuid,
+    kind: &str,
+    key: &str,
+    previous_value: Option<JsonValue>,
+    new_value: Option<JsonValue>,
+    source: &ConfigChangeSource,
+) -> Result<ConfigChange, AppError> {
+    let change = sqlx::query_as::<_, ConfigChange>(&format!(
+        r#"
+        INSERT INTO config_changes (tenant_id, kind, key, previous_value, new_value, actor_id, rollback_to)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING {CHANGE_COLUMNS}
+        "#
+    ))
+    .bind(tenant_id)
+    .bind(kind)
+    .bind(key)
+    .bind(previous_value)
+    .bind(new_value)
+    .bind(source.actor_id)
+    .bind(source.rollback_to)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(change)
+}
@@ -1,16 +1,108 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
 use uuid::Uuid;
+use sqlx::{Postgres, QueryBuilder};
 
-use crate::{entities::contact_me::{ContactMeFormInsert, ContactMeMessage}, errors::AppError, repositories::sqlx_repo::SqlxContactMeRepo};
+use crate::{
+    entities::{
+        contact_me::{ContactMeCursor, ContactMeExportQuery, ContactMeFormInsert, ContactMeMessage, ContactSecret, ContactSecretRewrite},
+        outbox::{AggregateRef, CONTACT_MESSAGE_RECEIVED},
+    },
+    errors::AppError,
+    repositories::{outbox::enqueue_in_tx, sqlx_repo::SqlxContactMeRepo},
+};
 
+#[automock]
 #[async_trait]
 pub trait ContactMeRepository: Send + Sync {
-    async fn create_contact_message(&self, msg: &ContactMeFormInsert) -> Result<Uuid, AppError>;
-    async fn get_contact_message_by_id(&self, id: &Uuid) -> Result<ContactMeMessage, AppError>;
-    async fn list_contact_messages(&self) -> Result<Vec<ContactMeMessage>, AppError>;
-    async fn count_contact_messages(&self) -> Result<i64, AppError>;
-    async fn soft_delete_contact_message(&self, id: &Uuid) -> Result<(), AppError>;
-    async fn hard_delete_contact_message(&self, id: &Uuid) -> Result<(), AppError>;
+    /// Also enqueues `contact_message.received` in the same transaction
+    async fn create_contact_message(&self, tenant_id: &Uuid, msg: &ContactMeFormInsert) -> Result<Uuid, AppError>;
+    async fn get_contact_message_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ContactMeMessage, AppError>;
+    async fn list_contact_messages(&self, tenant_id: &Uuid) -> Result<Vec<ContactMeMessage>, AppError>;
+    async fn count_contact_messages(&self, tenant_id: &Uuid) -> Result<i64, AppError>;
+    async fn soft_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    async fn hard_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    /// Next chunk of messages after `after`, oldest first, for streaming exports.
+    async fn list_contact_messages_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &ContactMeExportQuery,
+        after: Option<ContactMeCursor>,
+        limit: i64,
+    ) -> Result<Vec<ContactMeMessage>, AppError>;
+    /// Sealed fields of every tenant's messages after `after`, in id order,
+    /// deleted and anonymized ones included
+    async fn list_contact_secrets(&self, after: Option<Uuid>, limit: i64) -> Result<Vec<ContactSecret>, AppError>;
+    /// Writes re-sealed fields back, skipping rows changed since they were
+    /// read. Returns how many messages were rewritten.
+    async fn rewrite_contact_secrets(&self, rewrites: &[ContactSecretRewrite]) -> Result<u64, AppError>;
+    /// Records an auto-reply to `email_hash` at `now` unless one went out
+    /// after `not_before`. `true` when the caller may send it.
+    async fn claim_auto_reply(
+        &self,
+        tenant_id: &Uuid,
+        email_hash: &str,
+        now: DateTime<Utc>,
+        not_before: DateTime<Utc>,
+    ) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+impl<T: ContactMeRepository + ?Sized> ContactMeRepository for Arc<T> {
+    async fn create_contact_message(&self, tenant_id: &Uuid, msg: &ContactMeFormInsert) -> Result<Uuid, AppError> {
+        (**self).create_contact_message(tenant_id, msg).await
+    }
+
+    async fn get_contact_message_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ContactMeMessage, AppError> {
+        (**self).get_contact_message_by_id(tenant_id, id).await
+    }
+
+    async fn list_contact_messages(&self, tenant_id: &Uuid) -> Result<Vec<ContactMeMessage>, AppError> {
+        (**self).list_contact_messages(tenant_id).await
+    }
+
+    async fn count_contact_messages(&self, tenant_id: &Uuid) -> Result<i64, AppError> {
+        (**self).count_contact_messages(tenant_id).await
+    }
+
+    async fn soft_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).soft_delete_contact_message(tenant_id, id).await
+    }
+
+    async fn hard_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).hard_delete_contact_message(tenant_id, id).await
+    }
+
+    async fn list_contact_messages_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &ContactMeExportQuery,
+        after: Option<ContactMeCursor>,
+        limit: i64,
+    ) -> Result<Vec<ContactMeMessage>, AppError> {
+        (**self).list_contact_messages_chunk(tenant_id, filter, after, limit).await
+    }
+
+    async fn list_contact_secrets(&self, after: Option<Uuid>, limit: i64) -> Result<Vec<ContactSecret>, AppError> {
+        (**self).list_contact_secrets(after, limit).await
+    }
+
+    async fn rewrite_contact_secrets(&self, rewrites: &[ContactSecretRewrite]) -> Result<u64, AppError> {
+        (**self).rewrite_contact_secrets(rewrites).await
+    }
+
+    async fn claim_auto_reply(
+        &self,
+        tenant_id: &Uuid,
+        email_hash: &str,
+        now: DateTime<Utc>,
+        not_before: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        (**self).claim_auto_reply(tenant_id, email_hash, now, not_before).await
+    }
 }
 
 impl SqlxContactMeRepo {
@@ -21,29 +113,39 @@ impl SqlxContactMeRepo {
 
 #[async_trait]
 impl ContactMeRepository for SqlxContactMeRepo {
-    async fn create_contact_message(&self, msg: &ContactMeFormInsert) -> Result<Uuid, AppError> {
+    async fn create_contact_message(&self, tenant_id: &Uuid, msg: &ContactMeFormInsert) -> Result<Uuid, AppError> {
+        let mut tx = self.pool.begin().await?;
+
         let id = sqlx::query_scalar!(
             r#"
-            INSERT INTO contact_me_messages (name, email, subject, message) 
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO contact_me_messages (id, tenant_id, name, email, subject, message, country_code, region) 
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id
             "#,
+            msg.id,
+            tenant_id,
             msg.name,
             msg.email,
             msg.subject,
             msg.message,
+            msg.country_code,
+            msg.region,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
-        
+
+        enqueue_in_tx(&mut tx, tenant_id, CONTACT_MESSAGE_RECEIVED, &AggregateRef::payload(id)).await?;
+        tx.commit().await?;
+
         Ok(id)
     }
 
-    async fn get_contact_message_by_id(&self, id: &Uuid) -> Result<ContactMeMessage, AppError> {
+    async fn get_contact_message_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ContactMeMessage, AppError> {
         let contact_msg = sqlx::query_as!(
             ContactMeMessage,
-            r#"SELECT * FROM contact_me_messages WHERE id = $1 AND deleted_at IS NULL"#,
-            id
+            r#"SELECT * FROM contact_me_messages WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL"#,
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -51,10 +153,11 @@ impl ContactMeRepository for SqlxContactMeRepo {
         Ok(contact_msg.into())
     }
 
-    async fn list_contact_messages(&self) -> Result<Vec<ContactMeMessage>, AppError> {
+    async fn list_contact_messages(&self, tenant_id: &Uuid) -> Result<Vec<ContactMeMessage>, AppError> {
         let messages = sqlx::query_as!(
             ContactMeMessage,
-            r#"SELECT * FROM contact_me_messages WHERE deleted_at IS NULL ORDER BY created_at DESC"#
+            r#"SELECT * FROM contact_me_messages WHERE tenant_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC"#,
+            tenant_id
         )
         .fetch_all(&self.pool)
         .await?
@@ -65,9 +168,10 @@ impl ContactMeRepository for SqlxContactMeRepo {
         Ok(messages)
     }
 
-    async fn count_contact_messages(&self) -> Result<i64, AppError> {
+    async fn count_contact_messages(&self, tenant_id: &Uuid) -> Result<i64, AppError> {
         let count = sqlx::query_scalar!(
-            r#"SELECT COUNT(*) as "count!" FROM contact_me_messages WHERE deleted_at IS NULL"#
+            r#"SELECT COUNT(*) as "count!" FROM contact_me_messages WHERE tenant_id = $1 AND deleted_at IS NULL"#,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -75,10 +179,11 @@ impl ContactMeRepository for SqlxContactMeRepo {
         Ok(count)
     }
 
-    async fn soft_delete_contact_message(&self, id: &Uuid) -> Result<(), AppError> {
+    async fn soft_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
         sqlx::query!(
-            r#"UPDATE contact_me_messages SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL"#,
-            id
+            r#"UPDATE contact_me_messages SET deleted_at = NOW() WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL"#,
+            id,
+            tenant_id
         )
         .execute(&self.pool)
         .await
@@ -90,10 +195,11 @@ impl ContactMeRepository for SqlxContactMeRepo {
             }
         })?
     }
-    async fn hard_delete_contact_message(&self, id: &Uuid) -> Result<(), AppError> {
+    async fn hard_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
         sqlx::query!(
-            r#"DELETE FROM contact_me_messages WHERE id = $1"#,
-            id
+            r#"DELETE FROM contact_me_messages WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
         )
         .execute(&self.pool)
         .await
@@ -105,4 +211,149 @@ impl ContactMeRepository for SqlxContactMeRepo {
             }
         })?
     }
+
+    async fn list_contact_messages_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &ContactMeExportQuery,
+        after: Option<ContactMeCursor>,
+        limit: i64,
+    ) -> Result<Vec<ContactMeMessage>, AppError> {
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "SELECT * FROM contact_me_messages WHERE deleted_at IS NULL AND tenant_id = "
+        );
+        qb.push_bind(*tenant_id);
+
+        if let Some(from) = filter.from {
+            qb.push(" AND created_at >= ").push_bind(from);
+        }
+        if let Some(to) = filter.to {
+            qb.push(" AND created_at < ").push_bind(to);
+        }
+        if let Some(spam) = filter.spam {
+            qb.push(" AND is_spam = ").push_bind(spam);
+        }
+        // Keyset pagination keeps each chunk an index range scan regardless of depth
+        if let Some((created_at, id)) = after {
+            qb.push(" AND (created_at, id) > (")
+                .push_bind(created_at)
+                .push(", ")
+                .push_bind(id)
+                .push(")");
+        }
+
+        qb.push(" ORDER BY created_at ASC, id ASC LIMIT ").push_bind(limit);
+
+        let messages = qb
+            .build_query_as::<ContactMeMessage>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(messages)
+    }
+    async fn list_contact_secrets(&self, after: Option<Uuid>, limit: i64) -> Result<Vec<ContactSecret>, AppError> {
+        let secrets = sqlx::query_as!(
+            ContactSecret,
+            r#"
+            SELECT m.id AS message_id, m.email, m.message, i.email AS "identity_email?"
+            FROM contact_me_messages m
+            LEFT JOIN contact_message_identities i ON i.message_id = m.id
+            WHERE $1::uuid IS NULL OR m.id > $1
+            ORDER BY m.id
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(secrets)
+    }
+
+    async fn rewrite_contact_secrets(&self, rewrites: &[ContactSecretRewrite]) -> Result<u64, AppError> {
+        let ids: Vec<Uuid> = rewrites.iter().map(|r| r.before.message_id).collect();
+        let old_emails: Vec<String> = rewrites.iter().map(|r| r.before.email.clone()).collect();
+        let old_messages: Vec<String> = rewrites.iter().map(|r| r.before.message.clone()).collect();
+        let emails: Vec<String> = rewrites.iter().map(|r| r.after.email.clone()).collect();
+        let messages: Vec<String> = rewrites.iter().map(|r| r.after.message.clone()).collect();
+
+        let mut identity_ids = Vec::new();
+        let mut old_identity_emails = Vec::new();
+        let mut identity_emails = Vec::new();
+        for rewrite in rewrites {
+            if let (Some(before), Some(after)) = (&rewrite.before.identity_email, &rewrite.after.identity_email)
+                && before != after
+            {
+                identity_ids.push(rewrite.before.message_id);
+                old_identity_emails.push(before.clone());
+                identity_emails.push(after.clone());
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let rewritten = sqlx::query!(
+            r#"
+            UPDATE contact_me_messages m
+            SET email = u.email, message = u.message
+            FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[], $5::text[])
+                AS u(id, old_email, old_message, email, message)
+            WHERE m.id = u.id AND m.email = u.old_email AND m.message = u.old_message
+            "#,
+            &ids,
+            &old_emails,
+            &old_messages,
+            &emails,
+            &messages
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            UPDATE contact_message_identities i
+            SET email = u.email
+            FROM UNNEST($1::uuid[], $2::text[], $3::text[]) AS u(id, old_email, email)
+            WHERE i.message_id = u.id AND i.email = u.old_email
+            "#,
+            &identity_ids,
+            &old_identity_emails,
+            &identity_emails
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(rewritten)
+    }
+    async fn claim_auto_reply(
+        &self,
+        tenant_id: &Uuid,
+        email_hash: &str,
+        now: DateTime<Utc>,
+        not_before: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        // The conditional upsert claims the slot atomically, so concurrent
+        // deliveries of the same sender's messages send one reply between them
+        let claimed = sqlx::query_scalar!(
+            r#"
+            INSERT INTO contact_auto_replies (tenant_id, email_hash, last_sent_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (tenant_id, email_hash) DO UPDATE SET last_sent_at = EXCLUDED.last_sent_at
+            WHERE contact_auto_replies.last_sent_at <= $4
+            RETURNING tenant_id
+            "#,
+            tenant_id,
+            email_hash,
+            now,
+            not_before
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(claimed.is_some())
+    }
 }
\ No newline at end of file
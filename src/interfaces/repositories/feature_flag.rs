@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::feature_flag::FeatureFlag,
+    errors::AppError,
+    repositories::sqlx_repo::SqlxFeatureFlagRepo,
+};
+
+const FLAG_COLUMNS: &str = "tenant_id, key, enabled, description, created_at, updated_at";
+
+#[automock]
+#[async_trait]
+pub trait FeatureFlagRepository: Send + Sync {
+    async fn list_feature_flags(&self, tenant_id: &Uuid) -> Result<Vec<FeatureFlag>, AppError>;
+    /// Every tenant's flags, for the in-process cache
+    async fn list_all_feature_flags(&self) -> Result<Vec<FeatureFlag>, AppError>;
+    /// Creates the flag if it does not exist yet; `description: None` keeps the stored one.
+    async fn upsert_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+    ) -> Result<FeatureFlag, AppError>;
+}
+
+#[async_trait]
+impl<T: FeatureFlagRepository + ?Sized> FeatureFlagRepository for Arc<T> {
+    async fn list_feature_flags(&self, tenant_id: &Uuid) -> Result<Vec<FeatureFlag>, AppError> {
+        (**self).list_feature_flags(tenant_id).await
+    }
+
+    async fn list_all_feature_flags(&self) -> Result<Vec<FeatureFlag>, AppError> {
+        (**self).list_all_feature_flags().await
+    }
+
+    async fn upsert_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+    ) -> Result<FeatureFlag, AppError> {
+        (**self).upsert_feature_flag(tenant_id, key, enabled, description).await
+    }
+}
+
+impl SqlxFeatureFlagRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxFeatureFlagRepo { pool }
+    }
+}
+
+#[async_trait]
+impl FeatureFlagRepository for SqlxFeatureFlagRepo {
+    async fn list_feature_flags(&self, tenant_id: &Uuid) -> Result<Vec<FeatureFlag>, AppError> {
+        let flags = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "SELECT {FLAG_COLUMNS} FROM feature_flags WHERE tenant_id = $1 ORDER BY key"
+        ))
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(flags)
+    }
+
+    async fn list_all_feature_flags(&self) -> Result<Vec<FeatureFlag>, AppError> {
+        let flags = sqlx::query_as::<_, FeatureFlag>(&format!("SELECT {FLAG_COLUMNS} FROM feature_flags"))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(flags)
+    }
+
+    async fn upsert_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+    ) -> Result<FeatureFlag, AppError> {
+        let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+            r#"
+            INSERT INTO feature_flags (tenant_id, key, enabled, description)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tenant_id, key) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                description = COALESCE(EXCLUDED.description, feature_flags.description),
+                updated_at = NOW()
+            RETURNING {FLAG_COLUMNS}
+            "#
+        ))
+        .bind(tenant_id)
+        .bind(key)
+        .bind(enabled)
+        .bind(description)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(flag)
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        hire::{HireInquiry, HireInquiryInsert},
+        outbox::{AggregateRef, HIRE_INQUIRY_RECEIVED},
+    },
+    errors::AppError,
+    repositories::{outbox::enqueue_in_tx, sqlx_repo::SqlxHireInquiryRepo},
+};
+
+#[automock]
+#[async_trait]
+pub trait HireInquiryRepository: Send + Sync {
+    /// Also enqueues `hire_inquiry.received` in the same transaction
+    async fn create_inquiry(&self, tenant_id: &Uuid, inquiry: &HireInquiryInsert) -> Result<Uuid, AppError>;
+    async fn get_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<HireInquiry, AppError>;
+    async fn list_inquiries(&self, tenant_id: &Uuid) -> Result<Vec<HireInquiry>, AppError>;
+    async fn count_inquiries(&self, tenant_id: &Uuid) -> Result<i64, AppError>;
+    async fn delete_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: HireInquiryRepository + ?Sized> HireInquiryRepository for Arc<T> {
+    async fn create_inquiry(&self, tenant_id: &Uuid, inquiry: &HireInquiryInsert) -> Result<Uuid, AppError> {
+        (**self).create_inquiry(tenant_id, inquiry).await
+    }
+
+    async fn get_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<HireInquiry, AppError> {
+        (**self).get_inquiry(tenant_id, id).await
+    }
+
+    async fn list_inquiries(&self, tenant_id: &Uuid) -> Result<Vec<HireInquiry>, AppError> {
+        (**self).list_inquiries(tenant_id).await
+    }
+
+    async fn count_inquiries(&self, tenant_id: &Uuid) -> Result<i64, AppError> {
+        (**self).count_inquiries(tenant_id).await
+    }
+
+    async fn delete_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).delete_inquiry(tenant_id, id).await
+    }
+}
+
+impl SqlxHireInquiryRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxHireInquiryRepo { pool }
+    }
+}
+
+#[async_trait]
+impl HireInquiryRepository for SqlxHireInquiryRepo {
+    async fn create_inquiry(&self, tenant_id: &Uuid, inquiry: &HireInquiryInsert) -> Result<Uuid, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO hire_inquiries (
+                tenant_id, name, email, company, role, budget, timeline, message, is_spam, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id
+            "#,
+            tenant_id,
+            inquiry.name,
+            inquiry.email,
+            inquiry.company,
+            inquiry.role,
+            inquiry.budget,
+            inquiry.timeline,
+            inquiry.message,
+            inquiry.is_spam,
+            inquiry.created_at,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        enqueue_in_tx(&mut tx, tenant_id, HIRE_INQUIRY_RECEIVED, &AggregateRef::payload(id)).await?;
+        tx.commit().await?;
+
+        Ok(id)
+    }
+
+    async fn get_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<HireInquiry, AppError> {
+        sqlx::query_as!(
+            HireInquiry,
+            r#"SELECT * FROM hire_inquiries WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Hire inquiry not found".into()))
+    }
+
+    async fn list_inquiries(&self, tenant_id: &Uuid) -> Result<Vec<HireInquiry>, AppError> {
+        let inquiries = sqlx::query_as!(
+            HireInquiry,
+            r#"SELECT * FROM hire_inquiries WHERE tenant_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC"#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(inquiries)
+    }
+
+    async fn count_inquiries(&self, tenant_id: &Uuid) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM hire_inquiries WHERE tenant_id = $1 AND deleted_at IS NULL"#,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn delete_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"UPDATE hire_inquiries SET deleted_at = NOW() WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL"#,
+            id,
+            tenant_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Hire inquiry not found".into()));
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
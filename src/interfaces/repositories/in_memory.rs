@@ -0,0 +1,3793 @@
+//! In-memory implementations of every repository trait.
+//!
+//! Compiled only with the `in-memory` feature. They mirror the SQL semantics
+//! closely enough (soft deletes, slug uniqueness, ordering) to boot the API
+//! without Postgres for demos, frontend work and HTTP load tests.
+
+use std::{cmp::Reverse, collections::{BTreeMap, HashMap, HashSet}, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use parking_lot::RwLock;
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        about_me::{AboutMe, AboutMeInsert, AboutMeResponse, UpdateAboutMeRequest},
+        api_token::{ApiToken, ApiTokenDailyUsage, ApiTokenInsert, ApiTokenUsage},
+        blog_post::{BlogPost, BlogPostInsert, BlogSearchFilter, PostAuthor, PostByline, PostRevision, PostRevisionSummary, PostStatus, UpdateBlogPostRequest},
+        bookmark::{Bookmark, BookmarkInsert, LinkPreview, MAX_PREVIEW_ATTEMPTS, PREVIEW_FAILED, PREVIEW_PENDING, PREVIEW_READY},
+        changelog::{ChangelogEntry, ChangelogEntryInsert},
+        config_change::{ConfigChange, ConfigChangeSource, CONFIG_KIND_FEATURE_FLAG, CONFIG_KIND_SETTING},
+        contact_me::{ContactMeCursor, ContactMeExportQuery, ContactMeFormInsert, ContactMeMessage, ContactSecret, ContactSecretRewrite},
+        app_setting::AppSetting,
+        backfill::{BackfillState, BackfillTask, BACKFILL_COMPLETED},
+        usage::{EmailUsage, TableUsage, UsageReport},
+        audit::{AuditCursor, AuditEntry, AuditEntryInsert, AuditLogFilter},
+        feature_flag::FeatureFlag,
+        geo::CountryCount,
+        hire::{HireInquiry, HireInquiryInsert},
+        link_check::{LinkCheck, LinkCheckInsert},
+        notification::NotificationRecipient,
+        one_time_token::{OneTimeToken, OneTimeTokenInsert},
+        outbound::{OutboundClickInsert, OutboundClickSummary, ReferringPage},
+        outbox::{AggregateRef, JobStatus, JobTypeCounts, OutboxEvent, PausedQueue, CONTACT_MESSAGE_RECEIVED, HIRE_INQUIRY_RECEIVED},
+        redirect_rule::{RedirectRule, RedirectRuleInsert},
+        reading::{ReadingDepthCount, ReadingProgressUpdate},
+        stats_rollup::{start_of_day, start_of_month, RollupWindow, COUNTRY_SOURCE_ATTACKS, COUNTRY_SOURCE_CLICKS, ROLLUP_DAY, ROLLUP_MONTH},
+        retention::{RetentionAction, RetentionEntity},
+        schema::AppliedMigration,
+        security_event::{SecurityEventInsert, SecurityEventIpSummary, SecurityEventPathSummary, HONEYTOKEN_HIT},
+        share_count::{PostShareCount, ShareCountInsert},
+        series::{PostSeries, Series, SeriesInsert, SeriesPostLink, UpdateSeriesRequest},
+        tenant::{Tenant, TenantDomain, DEFAULT_TENANT_ID},
+        title_test::{TitleImpression, TitleVariant, TitleVariantInsert, TitleVariantStats},
+        user::{system_user_email, AuthoredContentPolicy, UpdateProfileRequest, User, UserInsert, SYSTEM_USER_NAME},
+        uses::{UsesEntry, UsesEntryInsert},
+    },
+    db::migrations::bundled_migrations,
+    errors::AppError,
+    repositories::{
+        about::AboutRepository,
+        api_token::ApiTokenRepository,
+        app_settings::AppSettingsRepository,
+        audit_log::AuditLogRepository,
+        backfill::BackfillRepository,
+        blog_post::{page_offset, resolve_slug_for_update, BlogPostRepository},
+        bookmark::BookmarkRepository,
+        changelog::ChangelogRepository,
+        config_history::ConfigHistoryRepository,
+        contact_me::ContactMeRepository,
+        feature_flag::FeatureFlagRepository,
+        hire::HireInquiryRepository,
+        link_check::LinkCheckRepository,
+        notification_preferences::NotificationPreferencesRepository,
+        one_time_token::OneTimeTokenRepository,
+        outbound::OutboundClickRepository,
+        outbox::OutboxRepository,
+        reading_progress::ReadingProgressRepository,
+        redirect_rule::RedirectRuleRepository,
+        retention::{unsupported_action, RetentionRepository, ANONYMIZED, ANONYMIZED_EMAIL},
+        schema::SchemaRepository,
+        security_event::SecurityEventRepository,
+        share_count::ShareCountRepository,
+        stats_rollup::{rollup_months, StatsRollupRepository},
+        tenant::TenantRepository,
+        title_test::TitleTestRepository,
+        usage::UsageRepository,
+        user::UserRepository,
+        uses::UsesRepository,
+    },
+};
+
+// ───── Users ─────────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryUserRepo {
+    users: Arc<RwLock<HashMap<Uuid, User>>>,
+    /// Shared with the blog repository so deleting a user can apply the
+    /// authored-content policy, as the SQL repository does in one transaction
+    posts: Arc<RwLock<HashMap<Uuid, BlogPost>>>,
+    bylines: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
+}
+
+impl InMemoryUserRepo {
+    fn modify_user(&self, tenant_id: &Uuid, id: &Uuid, change: impl FnOnce(&mut User)) -> Result<User, AppError> {
+        let mut users = self.users.write();
+        let user = users
+            .get_mut(id)
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        change(user);
+        user.updated_at = Utc::now();
+
+        Ok(user.clone())
+    }
+
+    /// The tenant's system user, created on first use
+    fn system_user_id(users: &mut HashMap<Uuid, User>, tenant_id: &Uuid) -> Uuid {
+        if let Some(user) = users.values().find(|u| u.tenant_id == *tenant_id && u.is_system && u.deleted_at.is_none()) {
+            return user.id;
+        }
+
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        users.insert(id, User {
+            id,
+            tenant_id: *tenant_id,
+            email: system_user_email(tenant_id),
+            username: Some(SYSTEM_USER_NAME.to_string()),
+            password_hash: "!".to_string(),
+            is_admin: false,
+            is_verified: true,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            deleted_by: None,
+            is_editor: false,
+            display_name: None,
+            avatar_url: None,
+            is_system: true,
+        });
+
+        id
+    }
+
+    fn author(user: &User) -> PostAuthor {
+        PostAuthor {
+            id: user.id,
+            username: user.username.clone(),
+            name: user.display_name.clone().or_else(|| user.username.clone()),
+            avatar_url: user.avatar_url.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepo {
+    async fn check_connection(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn user_exists(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError> {
+        Ok(self.users.read().get(id).is_some_and(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none()))
+    }
+
+    async fn count_users(&self, tenant_id: &Uuid) -> Result<u64, AppError> {
+        Ok(self.users
+            .read()
+            .values()
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none() && !u.is_system)
+            .count() as u64)
+    }
+
+    async fn get_user_by_email(&self, tenant_id: &Uuid, email: &str) -> Result<Option<User>, AppError> {
+        Ok(self.users
+            .read()
+            .values()
+            .find(|u| {
+                u.tenant_id == *tenant_id && u.deleted_at.is_none() && !u.is_system && u.email.eq_ignore_ascii_case(email)
+            })
+            .cloned())
+    }
+
+    async fn create_user(&self, tenant_id: &Uuid, user: &UserInsert) -> Result<Uuid, AppError> {
+        let mut users = self.users.write();
+
+        if users.values().any(|u| {
+            u.tenant_id == *tenant_id && u.deleted_at.is_none() && u.email.eq_ignore_ascii_case(&user.email)
+        }) {
+            return Err(AppError::Conflict("User with this email already exists".to_string()));
+        }
+
+        let id = Uuid::new_v4();
+        users.insert(id, User {
+            id,
+            tenant_id: *tenant_id,
+            email: user.email.clone(),
+            username: user.username.clone(),
+            password_hash: user.password_hash.clone(),
+            is_admin: user.is_admin,
+            is_verified: user.is_verified,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            deleted_at: user.deleted_at,
+            deleted_by: user.deleted_by,
+            is_editor: false,
+            display_name: None,
+            avatar_url: None,
+            is_system: false,
+        });
+
+        Ok(id)
+    }
+
+    async fn get_user_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<User>, AppError> {
+        Ok(self.users.read().get(id).filter(|u| u.tenant_id == *tenant_id).cloned())
+    }
+
+    /// Nothing is audited in memory; the policy itself is applied as in SQL
+    async fn delete_user(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        deleted_by: &Uuid,
+        content_policy: AuthoredContentPolicy,
+    ) -> Result<(), AppError> {
+        // Same lock order as the blog repository: posts, bylines, then users
+        let mut posts = self.posts.write();
+        let mut bylines = self.bylines.write();
+        let mut users = self.users.write();
+
+        // Matches the SQL path: soft-deleted users are not found
+        let is_system = users
+            .get(id)
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none())
+            .map(|u| u.is_system)
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        if is_system {
+            return Err(AppError::Conflict("The system user cannot be deleted".to_string()));
+        }
+
+        let content_posts = posts
+            .values()
+            .filter(|p| {
+                p.tenant_id == *tenant_id
+                    && (p.author_id == Some(*id) || bylines.get(&p.id).is_some_and(|ids| ids.contains(id)))
+            })
+            .count();
+
+        if content_posts > 0 {
+            let replacement = match content_policy {
+                AuthoredContentPolicy::Block => {
+                    return Err(AppError::Conflict(format!(
+                        "User owns or is credited on {} post(s); reassign them before deleting the account",
+                        content_posts
+                    )));
+                }
+                AuthoredContentPolicy::Reassign => Some(Self::system_user_id(&mut users, tenant_id)),
+                AuthoredContentPolicy::Anonymize => None,
+            };
+
+            for post in posts.values_mut().filter(|p| p.tenant_id == *tenant_id && p.author_id == Some(*id)) {
+                post.author_id = replacement;
+            }
+            for ids in bylines.values_mut() {
+                let Some(position) = ids.iter().position(|user_id| user_id == id) else {
+                    continue;
+                };
+                match replacement {
+                    Some(system_id) if !ids.contains(&system_id) => ids[position] = system_id,
+                    _ => {
+                        ids.remove(position);
+                    }
+                }
+            }
+        }
+
+        let user = users.get_mut(id).expect("user looked up above");
+        user.deleted_at = Some(Utc::now());
+        user.deleted_by = Some(*deleted_by);
+        user.updated_at = Utc::now();
+
+        Ok(())
+    }
+
+    async fn purge_soft_deleted_users(&self) -> Result<u64, AppError> {
+        let cutoff = Utc::now() - Duration::days(7);
+        let mut users = self.users.write();
+        let before = users.len();
+        users.retain(|_, u| u.deleted_at.is_none_or(|d| d >= cutoff));
+
+        Ok((before - users.len()) as u64)
+    }
+
+    async fn update_profile(&self, tenant_id: &Uuid, id: &Uuid, profile: &UpdateProfileRequest) -> Result<User, AppError> {
+        self.modify_user(tenant_id, id, |user| {
+            if !profile.display_name.is_unchanged() {
+                user.display_name = profile.display_name.flatten_ref().cloned();
+            }
+            if !profile.avatar_url.is_unchanged() {
+                user.avatar_url = profile.avatar_url.flatten_ref().cloned();
+            }
+        })
+    }
+
+    async fn set_editor(&self, tenant_id: &Uuid, id: &Uuid, editor: bool) -> Result<User, AppError> {
+        self.modify_user(tenant_id, id, |user| user.is_editor = editor)
+    }
+
+    async fn set_password(&self, tenant_id: &Uuid, id: &Uuid, password_hash: &str) -> Result<User, AppError> {
+        if self.users.read().get(id).is_some_and(|u| u.is_system) {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+
+        self.modify_user(tenant_id, id, |user| user.password_hash = password_hash.to_string())
+    }
+
+    async fn mark_verified(&self, tenant_id: &Uuid, id: &Uuid) -> Result<User, AppError> {
+        self.modify_user(tenant_id, id, |user| user.is_verified = true)
+    }
+}
+
+// ───── About Me ──────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryAboutMeRepo {
+    entries: Arc<RwLock<HashMap<Uuid, AboutMe>>>,
+}
+
+impl InMemoryAboutMeRepo {
+    fn max_revision(entries: &HashMap<Uuid, AboutMe>, tenant_id: &Uuid, effective_date: NaiveDate) -> Option<i32> {
+        entries
+            .values()
+            .filter(|a| a.tenant_id == *tenant_id && a.deleted_at.is_none() && a.effective_date == effective_date)
+            .map(|a| a.revision)
+            .max()
+    }
+}
+
+#[async_trait]
+impl AboutRepository for InMemoryAboutMeRepo {
+    async fn create_about_me(&self, tenant_id: &Uuid, about_insert: &AboutMeInsert) -> Result<Uuid, AppError> {
+        let mut entries = self.entries.write();
+
+        // Same rule as the `set_about_me_revision` trigger
+        let revision = Self::max_revision(&entries, tenant_id, about_insert.effective_date)
+            .map_or(1, |r| r + 1);
+
+        let id = Uuid::new_v4();
+        entries.insert(id, AboutMe {
+            id,
+            tenant_id: *tenant_id,
+            revision,
+            content_markdown: about_insert.content_markdown.clone(),
+            effective_date: about_insert.effective_date,
+            created_at: about_insert.created_at,
+            updated_at: about_insert.updated_at,
+            deleted_at: None,
+        });
+
+        Ok(id)
+    }
+
+    async fn get_about_me_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<AboutMe, AppError> {
+        self.entries
+            .read()
+            .get(id)
+            .filter(|a| a.tenant_id == *tenant_id && a.deleted_at.is_none())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))
+    }
+
+    async fn get_current_about_me(&self, tenant_id: &Uuid) -> Result<AboutMeResponse, AppError> {
+        self.get_about_me_as_of(tenant_id, Utc::now().date_naive()).await
+    }
+
+    async fn get_about_me_as_of(&self, tenant_id: &Uuid, date: NaiveDate) -> Result<AboutMeResponse, AppError> {
+        self.entries
+            .read()
+            .values()
+            .filter(|a| a.tenant_id == *tenant_id && a.deleted_at.is_none() && a.effective_date <= date)
+            .max_by_key(|a| (a.effective_date, a.revision))
+            .cloned()
+            .map(AboutMeResponse::from)
+            .ok_or_else(|| AppError::NotFound("About me not found".to_string()))
+    }
+
+    async fn update_about_me_content(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        update: &UpdateAboutMeRequest,
+    ) -> Result<AboutMe, AppError> {
+        let mut entries = self.entries.write();
+        let entry = entries
+            .get_mut(id)
+            .filter(|a| a.tenant_id == *tenant_id)
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))?;
+
+        if let Some(content) = update.content_markdown.flatten_str() {
+            entry.content_markdown = content.to_string();
+        }
+        if let Some(effective_date) = update.effective_date.flatten_ref() {
+            entry.effective_date = *effective_date;
+        }
+        entry.updated_at = Utc::now();
+
+        Ok(entry.clone())
+    }
+
+    async fn get_current_revision(&self, tenant_id: &Uuid, effective_date: NaiveDate) -> Result<i32, AppError> {
+        Ok(Self::max_revision(&self.entries.read(), tenant_id, effective_date).unwrap_or(0))
+    }
+
+    async fn soft_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError> {
+        match self.entries.write().get_mut(&id) {
+            Some(entry) if entry.tenant_id == *tenant_id && entry.deleted_at.is_none() => {
+                entry.deleted_at = Some(Utc::now());
+                Ok(())
+            }
+            _ => Err(AppError::NotFound("About me not found".into())),
+        }
+    }
+
+    async fn hard_delete_about_me(&self, tenant_id: &Uuid, id: Uuid) -> Result<(), AppError> {
+        let mut entries = self.entries.write();
+        if entries.get(&id).is_none_or(|a| a.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("About me not found".into()));
+        }
+        entries.remove(&id);
+
+        Ok(())
+    }
+}
+
+// ───── Blog Posts ────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryBlogPostRepo {
+    posts: Arc<RwLock<HashMap<Uuid, BlogPost>>>,
+    /// Post id to credited user ids, lead author first
+    bylines: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
+    series: Arc<RwLock<HashMap<Uuid, Series>>>,
+    /// Series id to member post ids, in reading order
+    series_posts: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
+    /// Post id to its revisions, oldest first
+    revisions: Arc<RwLock<HashMap<Uuid, Vec<PostRevision>>>>,
+    users: InMemoryUserRepo,
+}
+
+impl InMemoryBlogPostRepo {
+    /// Resolves post authors against `users`, as the SQL repository joins `users`
+    pub fn with_users(users: InMemoryUserRepo) -> Self {
+        InMemoryBlogPostRepo {
+            posts: users.posts.clone(),
+            bylines: users.bylines.clone(),
+            users,
+            ..Default::default()
+        }
+    }
+
+    fn slug_taken(posts: &HashMap<Uuid, BlogPost>, tenant_id: &Uuid, slug: &str, exclude_id: Option<Uuid>) -> bool {
+        posts.values().any(|p| {
+            p.tenant_id == *tenant_id
+                && p.deleted_at.is_none()
+                && Some(p.id) != exclude_id
+                && p.slug.eq_ignore_ascii_case(slug)
+        })
+    }
+
+    fn active_posts(&self, tenant_id: &Uuid, published_only: bool) -> Vec<BlogPost> {
+        self.posts
+            .read()
+            .values()
+            .filter(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none() && (!published_only || p.published))
+            .cloned()
+            .collect()
+    }
+
+    fn sort_by_published_desc(posts: &mut [BlogPost]) {
+        // `None` sorts lowest, so a descending sort yields NULLS LAST
+        posts.sort_by_key(|p| Reverse(p.published_at));
+    }
+
+    fn sort_by_created_desc(posts: &mut [BlogPost]) {
+        posts.sort_by_key(|p| Reverse(p.created_at));
+    }
+
+    /// What the `post_revision_trigger` does: a new revision unless nothing
+    /// tracked changed
+    fn record_revision(&self, post: &BlogPost) {
+        let mut revisions = self.revisions.write();
+        let history = revisions.entry(post.id).or_default();
+        let snapshot = PostRevision::of(post, history.len() as i32 + 1);
+        if history.last().is_none_or(|last| !last.same_as(&snapshot)) {
+            history.push(snapshot);
+        }
+    }
+}
+
+#[async_trait]
+impl BlogPostRepository for InMemoryBlogPostRepo {
+    async fn create_blog_post(&self, tenant_id: &Uuid, post: &BlogPostInsert) -> Result<Uuid, AppError> {
+        let mut posts = self.posts.write();
+
+        if Self::slug_taken(&posts, tenant_id, &post.slug, None) {
+            return Err(AppError::Conflict("Slug already exists".into()));
+        }
+
+        let id = Uuid::new_v4();
+        posts.insert(id, BlogPost {
+            id,
+            tenant_id: *tenant_id,
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            excerpt: post.excerpt.clone(),
+            content_markdown: post.content_markdown.clone(),
+            cover_image_url: post.cover_image_url.clone(),
+            tags: post.tags.clone(),
+            seo_title: post.seo_title.clone(),
+            seo_description: post.seo_description.clone(),
+            published: post.published,
+            published_at: post.published_at,
+            updated_at: post.updated_at,
+            created_at: post.created_at,
+            deleted_at: None,
+            author_id: post.author_id,
+            featured: false,
+            featured_order: None,
+        });
+        if let Some(author_id) = post.author_id {
+            self.bylines.write().insert(id, vec![author_id]);
+        }
+        self.record_revision(&posts[&id]);
+
+        Ok(id)
+    }
+
+    async fn get_blog_post_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError> {
+        self.posts
+            .read()
+            .get(id)
+            .filter(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))
+    }
+
+    async fn get_blog_post_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<BlogPost, AppError> {
+        self.posts
+            .read()
+            .values()
+            .find(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none() && p.slug == slug)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))
+    }
+
+    async fn update_blog_post(&self, tenant_id: &Uuid, id: &Uuid, post: &UpdateBlogPostRequest) -> Result<BlogPost, AppError> {
+        let mut posts = self.posts.write();
+
+        let current_slug = posts
+            .get(id)
+            .filter(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none())
+            .map(|p| p.slug.clone())
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))?;
+
+        let resolved_slug = resolve_slug_for_update(&post.slug, &post.title, &current_slug);
+        if Self::slug_taken(&posts, tenant_id, &resolved_slug, Some(*id)) {
+            return Err(AppError::Conflict("Slug already exists".into()));
+        }
+
+        let existing = posts.get_mut(id).expect("checked above");
+
+        // Same COALESCE semantics as the SQL implementation
+        if let Some(title) = post.title.flatten_ref() { existing.title = title.clone(); }
+        existing.slug = resolved_slug;
+        if let Some(excerpt) = post.excerpt.flatten_ref() { existing.excerpt = excerpt.clone(); }
+        if let Some(content) = post.content_markdown.flatten_ref() { existing.content_markdown = content.clone(); }
+        if let Some(url) = post.cover_image_url.flatten_ref() { existing.cover_image_url = Some(url.clone()); }
+        if let Some(tags) = post.tags.flatten_ref() { existing.tags = Some(tags.clone()); }
+        if let Some(seo_title) = post.seo_title.flatten_ref() { existing.seo_title = Some(seo_title.clone()); }
+        if let Some(seo_description) = post.seo_description.flatten_ref() { existing.seo_description = Some(seo_description.clone()); }
+        if let Some(published) = post.published.flatten_bool() { existing.published = published; }
+        if let Some(published_at) = post.published_at.flatten_datetime() { existing.published_at = Some(*published_at); }
+        existing.updated_at = Utc::now();
+        self.record_revision(existing);
+
+        Ok(existing.clone())
+    }
+
+    async fn get_all_blog_posts(&self, tenant_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts = self.active_posts(tenant_id, published_only);
+
+        if published_only {
+            Self::sort_by_published_desc(&mut posts);
+        } else {
+            Self::sort_by_created_desc(&mut posts);
+        }
+
+        Ok(posts
+            .into_iter()
+            .skip(page_offset(page, per_page) as usize)
+            .take(per_page as usize)
+            .collect())
+    }
+
+    async fn publish_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<BlogPost, AppError> {
+        let mut posts = self.posts.write();
+        let post = posts
+            .get_mut(id)
+            .filter(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))?;
+
+        let now = Utc::now();
+        post.published = true;
+        post.published_at = Some(now);
+        post.updated_at = now;
+        self.record_revision(post);
+
+        Ok(post.clone())
+    }
+
+    async fn count_blog_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<i64, AppError> {
+        Ok(self.active_posts(tenant_id, published_only).len() as i64)
+    }
+
+    async fn get_recent_blog_posts(&self, tenant_id: &Uuid, limit: u32, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts = self.active_posts(tenant_id, published_only);
+        Self::sort_by_published_desc(&mut posts);
+        posts.truncate(limit as usize);
+
+        Ok(posts)
+    }
+
+    async fn search_blog_posts(&self, tenant_id: &Uuid, query: &str) -> Result<Vec<BlogPost>, AppError> {
+        let needle = query.to_lowercase();
+        let mut posts: Vec<BlogPost> = self.active_posts(tenant_id, false)
+            .into_iter()
+            .filter(|p| {
+                p.title.to_lowercase().contains(&needle)
+                    || p.content_markdown.to_lowercase().contains(&needle)
+            })
+            .collect();
+        Self::sort_by_created_desc(&mut posts);
+
+        Ok(posts)
+    }
+
+    async fn admin_search_blog_posts(&self, tenant_id: &Uuid, filter: &BlogSearchFilter) -> Result<Vec<BlogPost>, AppError> {
+        let needle = filter.query.to_lowercase();
+        let mut posts: Vec<BlogPost> = self.posts
+            .read()
+            .values()
+            .filter(|p| p.tenant_id == *tenant_id)
+            .filter(|p| match filter.status {
+                Some(status) => PostStatus::of(p) == status
+                    || (filter.include_deleted && p.deleted_at.is_some() && p.published == (status == PostStatus::Published)),
+                None => filter.include_deleted || p.deleted_at.is_none(),
+            })
+            .filter(|p| {
+                p.title.to_lowercase().contains(&needle)
+                    || p.excerpt.to_lowercase().contains(&needle)
+                    || p.content_markdown.to_lowercase().contains(&needle)
+            })
+            .filter(|p| filter.tag.as_ref().is_none_or(|tag| p.tags.as_ref().is_some_and(|tags| tags.contains(tag))))
+            .filter(|p| filter.created_from.is_none_or(|from| p.created_at >= from))
+            .filter(|p| filter.created_to.is_none_or(|to| p.created_at <= to))
+            .cloned()
+            .collect();
+        Self::sort_by_created_desc(&mut posts);
+        posts.truncate(filter.limit as usize);
+
+        Ok(posts)
+    }
+
+    async fn get_blog_posts_by_tag(&self, tenant_id: &Uuid, tag: &str) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts: Vec<BlogPost> = self.active_posts(tenant_id, false)
+            .into_iter()
+            .filter(|p| p.tags.as_ref().is_some_and(|tags| tags.iter().any(|t| t == tag)))
+            .collect();
+        Self::sort_by_created_desc(&mut posts);
+
+        Ok(posts)
+    }
+
+    async fn blog_post_exists_with_slug(&self, tenant_id: &Uuid, slug: &str, exclude_id: Option<Uuid>) -> Result<bool, AppError> {
+        Ok(self.posts.read().values().any(|p| {
+            p.tenant_id == *tenant_id && p.deleted_at.is_none() && p.slug == slug && Some(p.id) != exclude_id
+        }))
+    }
+
+    async fn soft_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        match self.posts.write().get_mut(id) {
+            Some(post) if post.tenant_id == *tenant_id && post.deleted_at.is_none() => {
+                post.deleted_at = Some(Utc::now());
+                Ok(())
+            }
+            _ => Err(AppError::NotFound("Record not found".into())),
+        }
+    }
+
+    async fn hard_delete_blog_post(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut posts = self.posts.write();
+        if posts.get(id).is_none_or(|p| p.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Record not found".into()));
+        }
+        posts.remove(id);
+        self.bylines.write().remove(id);
+        self.revisions.write().remove(id);
+        self.series_posts.write().values_mut().for_each(|members| members.retain(|post_id| post_id != id));
+
+        Ok(())
+    }
+
+    async fn get_post_authors(&self, tenant_id: &Uuid, author_ids: &[Uuid]) -> Result<Vec<PostAuthor>, AppError> {
+        Ok(self.users
+            .users
+            .read()
+            .values()
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none() && author_ids.contains(&u.id))
+            .map(InMemoryUserRepo::author)
+            .collect())
+    }
+
+    async fn get_author_by_username(&self, tenant_id: &Uuid, username: &str) -> Result<Option<PostAuthor>, AppError> {
+        let published_authors: Vec<Uuid> = {
+            let bylines = self.bylines.read();
+            self.active_posts(tenant_id, true)
+                .iter()
+                .filter_map(|p| bylines.get(&p.id))
+                .flatten()
+                .copied()
+                .collect()
+        };
+
+        Ok(self.users
+            .users
+            .read()
+            .values()
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none())
+            .filter(|u| u.username.as_deref().is_some_and(|name| name.eq_ignore_ascii_case(username)))
+            .filter(|u| u.is_admin || u.is_editor || published_authors.contains(&u.id))
+            .min_by_key(|u| u.created_at)
+            .map(InMemoryUserRepo::author))
+    }
+
+    async fn get_blog_posts_by_author(&self, tenant_id: &Uuid, author_id: &Uuid, published_only: bool, page: u32, per_page: u32) -> Result<Vec<BlogPost>, AppError> {
+        let bylines = self.bylines.read().clone();
+        let mut posts: Vec<BlogPost> = self.active_posts(tenant_id, published_only)
+            .into_iter()
+            .filter(|p| bylines.get(&p.id).is_some_and(|ids| ids.contains(author_id)))
+            .collect();
+
+        if published_only {
+            Self::sort_by_published_desc(&mut posts);
+        } else {
+            Self::sort_by_created_desc(&mut posts);
+        }
+
+        Ok(posts
+            .into_iter()
+            .skip(page_offset(page, per_page) as usize)
+            .take(per_page as usize)
+            .collect())
+    }
+
+    async fn get_post_bylines(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<Vec<PostByline>, AppError> {
+        let bylines = self.bylines.read();
+        let users = self.users.users.read();
+
+        Ok(post_ids
+            .iter()
+            .filter_map(|post_id| bylines.get(post_id).map(|ids| (post_id, ids)))
+            .flat_map(|(post_id, ids)| {
+                ids.iter()
+                    .filter_map(|id| users.get(id))
+                    .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none())
+                    .map(|u| PostByline { post_id: *post_id, author: InMemoryUserRepo::author(u) })
+            })
+            .collect())
+    }
+
+    async fn set_post_authors(&self, tenant_id: &Uuid, post_id: &Uuid, user_ids: &[Uuid]) -> Result<(), AppError> {
+        let exists = self.posts
+            .read()
+            .get(post_id)
+            .is_some_and(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none());
+        if !exists {
+            return Err(AppError::NotFound("Blog post not found".into()));
+        }
+
+        self.bylines.write().insert(*post_id, user_ids.to_vec());
+        Ok(())
+    }
+
+    async fn create_series(&self, tenant_id: &Uuid, series: &SeriesInsert) -> Result<Series, AppError> {
+        let mut all = self.series.write();
+        if all.values().any(|s| s.tenant_id == *tenant_id && s.slug == series.slug) {
+            return Err(AppError::Conflict("Series slug already exists".into()));
+        }
+
+        let now = Utc::now();
+        let created = Series {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            title: series.title.clone(),
+            slug: series.slug.clone(),
+            description: series.description.clone(),
+            created_at: now,
+            updated_at: now,
+        };
+        all.insert(created.id, created.clone());
+
+        Ok(created)
+    }
+
+    async fn update_series(&self, tenant_id: &Uuid, id: &Uuid, series: &UpdateSeriesRequest) -> Result<Series, AppError> {
+        let mut all = self.series.write();
+
+        let slug_taken = series.slug
+            .flatten_str()
+            .is_some_and(|slug| all.values().any(|s| s.tenant_id == *tenant_id && s.id != *id && s.slug == slug));
+        if slug_taken {
+            return Err(AppError::Conflict("Series slug already exists".into()));
+        }
+
+        let existing = all
+            .get_mut(id)
+            .filter(|s| s.tenant_id == *tenant_id)
+            .ok_or_else(|| AppError::NotFound("Series not found".into()))?;
+
+        if let Some(title) = series.title.flatten_str() {
+            existing.title = title.to_string();
+        }
+        if let Some(slug) = series.slug.flatten_str() {
+            existing.slug = slug.to_string();
+        }
+        if !series.description.is_unchanged() {
+            existing.description = series.description.flatten_str().map(str::to_string);
+        }
+        existing.updated_at = Utc::now();
+
+        Ok(existing.clone())
+    }
+
+    async fn delete_series(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut all = self.series.write();
+        if all.get(id).is_none_or(|s| s.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Series not found".into()));
+        }
+        all.remove(id);
+        self.series_posts.write().remove(id);
+
+        Ok(())
+    }
+
+    async fn list_series(&self, tenant_id: &Uuid) -> Result<Vec<Series>, AppError> {
+        let mut series: Vec<Series> = self.series
+            .read()
+            .values()
+            .filter(|s| s.tenant_id == *tenant_id)
+            .cloned()
+            .collect();
+        series.sort_by_key(|s| Reverse(s.created_at));
+
+        Ok(series)
+    }
+
+    async fn get_series_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<Series>, AppError> {
+        Ok(self.series.read().get(id).filter(|s| s.tenant_id == *tenant_id).cloned())
+    }
+
+    async fn get_series_by_slug(&self, tenant_id: &Uuid, slug: &str) -> Result<Option<Series>, AppError> {
+        Ok(self.series
+            .read()
+            .values()
+            .find(|s| s.tenant_id == *tenant_id && s.slug.eq_ignore_ascii_case(slug))
+            .cloned())
+    }
+
+    async fn get_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        let member_ids = self.series_posts.read().get(series_id).cloned().unwrap_or_default();
+        let posts = self.posts.read();
+
+        Ok(member_ids
+            .iter()
+            .filter_map(|id| posts.get(id))
+            .filter(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none() && (!published_only || p.published))
+            .cloned()
+            .collect())
+    }
+
+    async fn set_series_posts(&self, tenant_id: &Uuid, series_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError> {
+        if self.series.read().get(series_id).is_none_or(|s| s.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Series not found".into()));
+        }
+
+        let all_known = {
+            let posts = self.posts.read();
+            post_ids.iter().all(|id| posts.get(id).is_some_and(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none()))
+        };
+        if !all_known {
+            return Err(AppError::InvalidInput("Every post must exist and not be in the trash".into()));
+        }
+
+        let mut series_posts = self.series_posts.write();
+        let taken = series_posts
+            .iter()
+            .filter(|(id, _)| *id != series_id)
+            .any(|(_, members)| members.iter().any(|id| post_ids.contains(id)));
+        if taken {
+            return Err(AppError::Conflict("A post can only belong to one series".into()));
+        }
+        series_posts.insert(*series_id, post_ids.to_vec());
+
+        Ok(())
+    }
+
+    async fn get_post_series(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Option<PostSeries>, AppError> {
+        let found = self.series_posts
+            .read()
+            .iter()
+            .find(|(_, members)| members.contains(post_id))
+            .map(|(series_id, members)| (*series_id, members.clone()));
+        let Some((series_id, member_ids)) = found else {
+            return Ok(None);
+        };
+        let Some(series) = self.series.read().get(&series_id).filter(|s| s.tenant_id == *tenant_id).cloned() else {
+            return Ok(None);
+        };
+
+        let posts = self.posts.read();
+        let members = member_ids
+            .iter()
+            .filter_map(|id| posts.get(id))
+            .filter(|p| p.deleted_at.is_none())
+            .map(|p| SeriesPostLink { id: p.id, title: p.title.clone(), slug: p.slug.clone(), published: p.published })
+            .collect();
+
+        Ok(Some(PostSeries { series, members }))
+    }
+
+    async fn get_featured_posts(&self, tenant_id: &Uuid, published_only: bool) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts: Vec<BlogPost> = self.posts
+            .read()
+            .values()
+            .filter(|p| p.tenant_id == *tenant_id && p.featured && p.deleted_at.is_none() && (!published_only || p.published))
+            .cloned()
+            .collect();
+        posts.sort_by_key(|p| p.featured_order);
+
+        Ok(posts)
+    }
+
+    async fn set_featured_posts(&self, tenant_id: &Uuid, post_ids: &[Uuid]) -> Result<(), AppError> {
+        let mut posts = self.posts.write();
+
+        let all_known = post_ids
+            .iter()
+            .all(|id| posts.get(id).is_some_and(|p| p.tenant_id == *tenant_id && p.deleted_at.is_none()));
+        if !all_known {
+            return Err(AppError::InvalidInput("Every post must exist and not be in the trash".into()));
+        }
+
+        for post in posts.values_mut().filter(|p| p.tenant_id == *tenant_id) {
+            post.featured_order = post_ids.iter().position(|id| *id == post.id).map(|i| i as i32);
+            post.featured = post.featured_order.is_some();
+        }
+
+        Ok(())
+    }
+
+    async fn get_calendar_posts(&self, tenant_id: &Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts: Vec<BlogPost> = self.active_posts(tenant_id, false)
+            .into_iter()
+            .filter(|p| (from..to).contains(&p.published_at.unwrap_or(p.updated_at)))
+            .collect();
+        posts.sort_by_key(|p| (p.published_at.unwrap_or(p.updated_at), p.id));
+
+        Ok(posts)
+    }
+
+    async fn get_posts_changed_since(&self, tenant_id: &Uuid, since: Option<DateTime<Utc>>) -> Result<Vec<BlogPost>, AppError> {
+        let mut posts: Vec<BlogPost> = match since {
+            None => self.active_posts(tenant_id, true),
+            Some(since) => self.posts
+                .read()
+                .values()
+                .filter(|p| p.tenant_id == *tenant_id)
+                .filter(|p| p.updated_at > since || p.deleted_at.is_some_and(|d| d > since))
+                .cloned()
+                .collect(),
+        };
+        posts.sort_by_key(|p| (p.deleted_at.map_or(p.updated_at, |d| d.max(p.updated_at)), p.id));
+
+        Ok(posts)
+    }
+
+    async fn list_post_revisions(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostRevisionSummary>, AppError> {
+        Ok(self.revisions
+            .read()
+            .get(post_id)
+            .into_iter()
+            .flatten()
+            .rev()
+            .filter(|r| r.tenant_id == *tenant_id)
+            .map(|r| PostRevisionSummary {
+                revision: r.revision,
+                title: r.title.clone(),
+                published: r.published,
+                created_at: r.created_at,
+            })
+            .collect())
+    }
+
+    async fn get_post_revision(&self, tenant_id: &Uuid, post_id: &Uuid, revision: i32) -> Result<Option<PostRevision>, AppError> {
+        Ok(self.revisions
+            .read()
+            .get(post_id)
+            .into_iter()
+            .flatten()
+            .find(|r| r.tenant_id == *tenant_id && r.revision == revision)
+            .cloned())
+    }
+}
+
+// ───── Contact Messages ──────────────────────────────────────────────
+
+/// When each `(tenant_id, email_hash)` last got an auto-reply
+type AutoReplyLog = HashMap<(Uuid, String), DateTime<Utc>>;
+
+#[derive(Clone, Default)]
+pub struct InMemoryContactMeRepo {
+    messages: Arc<RwLock<HashMap<Uuid, ContactMeMessage>>>,
+    outbox: InMemoryOutboxRepo,
+    auto_replies: Arc<RwLock<AutoReplyLog>>,
+}
+
+impl InMemoryContactMeRepo {
+    /// Enqueues `contact_message.received` on `outbox` for every new message
+    pub fn with_outbox(outbox: InMemoryOutboxRepo) -> Self {
+        InMemoryContactMeRepo { outbox, ..Default::default() }
+    }
+}
+
+#[async_trait]
+impl ContactMeRepository for InMemoryContactMeRepo {
+    async fn create_contact_message(&self, tenant_id: &Uuid, msg: &ContactMeFormInsert) -> Result<Uuid, AppError> {
+        let id = msg.id;
+        self.messages.write().insert(id, ContactMeMessage {
+            id,
+            tenant_id: *tenant_id,
+            name: msg.name.clone(),
+            email: msg.email.clone(),
+            subject: msg.subject.clone(),
+            message: msg.message.clone(),
+            created_at: msg.created_at,
+            deleted_at: None,
+            is_spam: false,
+            anonymized_at: None,
+            country_code: msg.country_code.clone(),
+            region: msg.region.clone(),
+        });
+        self.outbox.enqueue(tenant_id, CONTACT_MESSAGE_RECEIVED, AggregateRef::payload(id));
+
+        Ok(id)
+    }
+
+    async fn get_contact_message_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ContactMeMessage, AppError> {
+        self.messages
+            .read()
+            .get(id)
+            .filter(|m| m.tenant_id == *tenant_id && m.deleted_at.is_none())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Record not found".into()))
+    }
+
+    async fn list_contact_messages(&self, tenant_id: &Uuid) -> Result<Vec<ContactMeMessage>, AppError> {
+        let mut messages: Vec<ContactMeMessage> = self.messages
+            .read()
+            .values()
+            .filter(|m| m.tenant_id == *tenant_id && m.deleted_at.is_none())
+            .cloned()
+            .collect();
+        messages.sort_by_key(|m| Reverse(m.created_at));
+
+        Ok(messages)
+    }
+
+    async fn count_contact_messages(&self, tenant_id: &Uuid) -> Result<i64, AppError> {
+        Ok(self.messages.read().values().filter(|m| m.tenant_id == *tenant_id && m.deleted_at.is_none()).count() as i64)
+    }
+
+    async fn soft_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        match self.messages.write().get_mut(id) {
+            Some(msg) if msg.tenant_id == *tenant_id && msg.deleted_at.is_none() => {
+                msg.deleted_at = Some(Utc::now());
+                Ok(())
+            }
+            _ => Err(AppError::NotFound("Contact me not found".into())),
+        }
+    }
+
+    async fn hard_delete_contact_message(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut messages = self.messages.write();
+        if messages.get(id).is_none_or(|m| m.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Contact me not found".into()));
+        }
+        messages.remove(id);
+
+        Ok(())
+    }
+
+    async fn list_contact_messages_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &ContactMeExportQuery,
+        after: Option<ContactMeCursor>,
+        limit: i64,
+    ) -> Result<Vec<ContactMeMessage>, AppError> {
+        let mut messages: Vec<ContactMeMessage> = self.messages
+            .read()
+            .values()
+            .filter(|m| m.tenant_id == *tenant_id && m.deleted_at.is_none())
+            .filter(|m| filter.from.is_none_or(|from| m.created_at >= from))
+            .filter(|m| filter.to.is_none_or(|to| m.created_at < to))
+            .filter(|m| filter.spam.is_none_or(|spam| m.is_spam == spam))
+            .filter(|m| after.is_none_or(|cursor| (m.created_at, m.id) > cursor))
+            .cloned()
+            .collect();
+        messages.sort_by_key(|m| (m.created_at, m.id));
+        messages.truncate(limit.max(0) as usize);
+
+        Ok(messages)
+    }
+
+    /// Stashed identities live in `InMemoryRetentionRepo` and are not listed
+    async fn list_contact_secrets(&self, after: Option<Uuid>, limit: i64) -> Result<Vec<ContactSecret>, AppError> {
+        let mut secrets: Vec<ContactSecret> = self.messages
+            .read()
+            .values()
+            .filter(|m| after.is_none_or(|after| m.id > after))
+            .map(|m| ContactSecret {
+                message_id: m.id,
+                email: m.email.clone(),
+                message: m.message.clone(),
+                identity_email: None,
+            })
+            .collect();
+        secrets.sort_by_key(|secret| secret.message_id);
+        secrets.truncate(limit.max(0) as usize);
+
+        Ok(secrets)
+    }
+
+    async fn rewrite_contact_secrets(&self, rewrites: &[ContactSecretRewrite]) -> Result<u64, AppError> {
+        let mut messages = self.messages.write();
+        let mut rewritten = 0;
+        for rewrite in rewrites {
+            if let Some(msg) = messages.get_mut(&rewrite.before.message_id)
+                && msg.email == rewrite.before.email
+                && msg.message == rewrite.before.message
+            {
+                msg.email = rewrite.after.email.clone();
+                msg.message = rewrite.after.message.clone();
+                rewritten += 1;
+            }
+        }
+
+        Ok(rewritten)
+    }
+
+    async fn claim_auto_reply(
+        &self,
+        tenant_id: &Uuid,
+        email_hash: &str,
+        now: DateTime<Utc>,
+        not_before: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        let mut replies = self.auto_replies.write();
+        let last_sent = replies.entry((*tenant_id, email_hash.to_string())).or_insert(DateTime::<Utc>::MIN_UTC);
+        if *last_sent > not_before {
+            return Ok(false);
+        }
+
+        *last_sent = now;
+        Ok(true)
+    }
+}
+
+// ───── Hire Inquiries ────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryHireInquiryRepo {
+    inquiries: Arc<RwLock<HashMap<Uuid, HireInquiry>>>,
+    outbox: InMemoryOutboxRepo,
+}
+
+impl InMemoryHireInquiryRepo {
+    /// Enqueues `hire_inquiry.received` on `outbox` for every new inquiry
+    pub fn with_outbox(outbox: InMemoryOutboxRepo) -> Self {
+        InMemoryHireInquiryRepo { outbox, ..Default::default() }
+    }
+}
+
+#[async_trait]
+impl HireInquiryRepository for InMemoryHireInquiryRepo {
+    async fn create_inquiry(&self, tenant_id: &Uuid, inquiry: &HireInquiryInsert) -> Result<Uuid, AppError> {
+        let id = Uuid::new_v4();
+        self.inquiries.write().insert(id, HireInquiry {
+            id,
+            tenant_id: *tenant_id,
+            name: inquiry.name.clone(),
+            email: inquiry.email.clone(),
+            company: inquiry.company.clone(),
+            role: inquiry.role.clone(),
+            budget: inquiry.budget.clone(),
+            timeline: inquiry.timeline.clone(),
+            message: inquiry.message.clone(),
+            is_spam: inquiry.is_spam,
+            created_at: inquiry.created_at,
+            deleted_at: None,
+        });
+        self.outbox.enqueue(tenant_id, HIRE_INQUIRY_RECEIVED, AggregateRef::payload(id));
+
+        Ok(id)
+    }
+
+    async fn get_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<HireInquiry, AppError> {
+        self.inquiries
+            .read()
+            .get(id)
+            .filter(|i| i.tenant_id == *tenant_id && i.deleted_at.is_none())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Hire inquiry not found".into()))
+    }
+
+    async fn list_inquiries(&self, tenant_id: &Uuid) -> Result<Vec<HireInquiry>, AppError> {
+        let mut inquiries: Vec<HireInquiry> = self.inquiries
+            .read()
+            .values()
+            .filter(|i| i.tenant_id == *tenant_id && i.deleted_at.is_none())
+            .cloned()
+            .collect();
+        inquiries.sort_by_key(|i| Reverse(i.created_at));
+
+        Ok(inquiries)
+    }
+
+    async fn count_inquiries(&self, tenant_id: &Uuid) -> Result<i64, AppError> {
+        Ok(self.inquiries.read().values().filter(|i| i.tenant_id == *tenant_id && i.deleted_at.is_none()).count() as i64)
+    }
+
+    async fn delete_inquiry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        match self.inquiries.write().get_mut(id) {
+            Some(inquiry) if inquiry.tenant_id == *tenant_id && inquiry.deleted_at.is_none() => {
+                inquiry.deleted_at = Some(Utc::now());
+                Ok(())
+            }
+            _ => Err(AppError::NotFound("Hire inquiry not found".into())),
+        }
+    }
+}
+
+// ───── Uses ──────────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryUsesRepo {
+    entries: Arc<RwLock<HashMap<Uuid, UsesEntry>>>,
+}
+
+#[async_trait]
+impl UsesRepository for InMemoryUsesRepo {
+    async fn list_entries(&self, tenant_id: &Uuid) -> Result<Vec<UsesEntry>, AppError> {
+        let mut entries: Vec<UsesEntry> = self.entries
+            .read()
+            .values()
+            .filter(|e| e.tenant_id == *tenant_id)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| (e.position, e.created_at));
+
+        Ok(entries)
+    }
+
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<UsesEntry, AppError> {
+        self.entries
+            .read()
+            .get(id)
+            .filter(|e| e.tenant_id == *tenant_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Uses entry not found".into()))
+    }
+
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &UsesEntryInsert) -> Result<UsesEntry, AppError> {
+        let mut entries = self.entries.write();
+        let now = Utc::now();
+
+        let position = entry.position.unwrap_or_else(|| {
+            entries
+                .values()
+                .filter(|e| e.tenant_id == *tenant_id)
+                .map(|e| e.position + 1)
+                .max()
+                .unwrap_or(0)
+        });
+
+        let created = UsesEntry {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            category: entry.category.clone(),
+            name: entry.name.clone(),
+            description_markdown: entry.description_markdown.clone(),
+            link: entry.link.clone(),
+            image_url: entry.image_url.clone(),
+            position,
+            created_at: now,
+            updated_at: now,
+        };
+        entries.insert(created.id, created.clone());
+
+        Ok(created)
+    }
+
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &UsesEntry) -> Result<UsesEntry, AppError> {
+        match self.entries.write().get_mut(&entry.id) {
+            Some(current) if current.tenant_id == *tenant_id => {
+                *current = UsesEntry {
+                    tenant_id: *tenant_id,
+                    created_at: current.created_at,
+                    updated_at: Utc::now(),
+                    ..entry.clone()
+                };
+                Ok(current.clone())
+            }
+            _ => Err(AppError::NotFound("Uses entry not found".into())),
+        }
+    }
+
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut entries = self.entries.write();
+        if entries.get(id).is_none_or(|e| e.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Uses entry not found".into()));
+        }
+        entries.remove(id);
+
+        Ok(())
+    }
+
+    async fn reorder_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<(), AppError> {
+        let mut entries = self.entries.write();
+        let now = Utc::now();
+
+        for (position, id) in ids.iter().enumerate() {
+            if let Some(entry) = entries.get_mut(id).filter(|e| e.tenant_id == *tenant_id) {
+                entry.position = position as i32;
+                entry.updated_at = now;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ───── Changelog ─────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryChangelogRepo {
+    entries: Arc<RwLock<HashMap<Uuid, ChangelogEntry>>>,
+}
+
+impl InMemoryChangelogRepo {
+    fn version_taken(entries: &HashMap<Uuid, ChangelogEntry>, tenant_id: &Uuid, version: &str, exclude: Option<Uuid>) -> bool {
+        entries
+            .values()
+            .any(|e| e.tenant_id == *tenant_id && e.version == version && Some(e.id) != exclude)
+    }
+
+    fn filtered(&self, tenant_id: &Uuid, category: Option<&str>) -> Vec<ChangelogEntry> {
+        let mut entries: Vec<ChangelogEntry> = self.entries
+            .read()
+            .values()
+            .filter(|e| e.tenant_id == *tenant_id && category.is_none_or(|c| e.category == c))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| Reverse((e.released_on, e.created_at)));
+        entries
+    }
+}
+
+#[async_trait]
+impl ChangelogRepository for InMemoryChangelogRepo {
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntryInsert) -> Result<ChangelogEntry, AppError> {
+        let mut entries = self.entries.write();
+        if Self::version_taken(&entries, tenant_id, &entry.version, None) {
+            return Err(AppError::Conflict("A changelog entry for this version already exists".to_string()));
+        }
+
+        let now = Utc::now();
+        let created = ChangelogEntry {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            version: entry.version.clone(),
+            released_on: entry.released_on,
+            notes_markdown: entry.notes_markdown.clone(),
+            category: entry.category.clone(),
+            created_at: now,
+            updated_at: now,
+        };
+        entries.insert(created.id, created.clone());
+
+        Ok(created)
+    }
+
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<ChangelogEntry, AppError> {
+        self.entries
+            .read()
+            .get(id)
+            .filter(|e| e.tenant_id == *tenant_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Changelog entry not found".into()))
+    }
+
+    async fn get_entry_by_version(&self, tenant_id: &Uuid, version: &str) -> Result<Option<ChangelogEntry>, AppError> {
+        Ok(self.entries
+            .read()
+            .values()
+            .find(|e| e.tenant_id == *tenant_id && e.version == version)
+            .cloned())
+    }
+
+    async fn list_entries(
+        &self,
+        tenant_id: &Uuid,
+        category: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ChangelogEntry>, AppError> {
+        Ok(self.filtered(tenant_id, category.as_deref())
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn count_entries(&self, tenant_id: &Uuid, category: Option<String>) -> Result<i64, AppError> {
+        Ok(self.filtered(tenant_id, category.as_deref()).len() as i64)
+    }
+
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &ChangelogEntry) -> Result<ChangelogEntry, AppError> {
+        let mut entries = self.entries.write();
+        if Self::version_taken(&entries, tenant_id, &entry.version, Some(entry.id)) {
+            return Err(AppError::Conflict("A changelog entry for this version already exists".to_string()));
+        }
+
+        match entries.get_mut(&entry.id) {
+            Some(current) if current.tenant_id == *tenant_id => {
+                *current = ChangelogEntry {
+                    tenant_id: *tenant_id,
+                    created_at: current.created_at,
+                    updated_at: Utc::now(),
+                    ..entry.clone()
+                };
+                Ok(current.clone())
+            }
+            _ => Err(AppError::NotFound("Changelog entry not found".into())),
+        }
+    }
+
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut entries = self.entries.write();
+        if entries.get(id).is_none_or(|e| e.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Changelog entry not found".into()));
+        }
+        entries.remove(id);
+
+        Ok(())
+    }
+}
+
+// ───── Bookmarks ─────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryBookmarkRepo {
+    bookmarks: Arc<RwLock<HashMap<Uuid, Bookmark>>>,
+}
+
+impl InMemoryBookmarkRepo {
+    fn url_taken(bookmarks: &HashMap<Uuid, Bookmark>, tenant_id: &Uuid, url: &str, exclude: Option<Uuid>) -> bool {
+        bookmarks
+            .values()
+            .any(|b| b.tenant_id == *tenant_id && b.url == url && Some(b.id) != exclude)
+    }
+
+    fn filtered(&self, tenant_id: &Uuid, public_only: bool, tag: Option<&str>) -> Vec<Bookmark> {
+        let mut bookmarks: Vec<Bookmark> = self.bookmarks
+            .read()
+            .values()
+            .filter(|b| b.tenant_id == *tenant_id && (!public_only || b.is_public))
+            .filter(|b| tag.is_none_or(|t| b.tags.iter().any(|bt| bt == t)))
+            .cloned()
+            .collect();
+        bookmarks.sort_by_key(|b| Reverse(b.created_at));
+        bookmarks
+    }
+
+    fn modify_preview(&self, id: &Uuid, change: impl FnOnce(&mut Bookmark)) {
+        if let Some(bookmark) = self.bookmarks.write().get_mut(id) {
+            change(bookmark);
+            bookmark.preview_attempts += 1;
+            bookmark.preview_fetched_at = Some(Utc::now());
+        }
+    }
+}
+
+#[async_trait]
+impl BookmarkRepository for InMemoryBookmarkRepo {
+    async fn create_bookmark(&self, tenant_id: &Uuid, bookmark: &BookmarkInsert) -> Result<Bookmark, AppError> {
+        let mut bookmarks = self.bookmarks.write();
+        if Self::url_taken(&bookmarks, tenant_id, &bookmark.url, None) {
+            return Err(AppError::Conflict("This link is already bookmarked".to_string()));
+        }
+
+        let now = Utc::now();
+        let created = Bookmark {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            url: bookmark.url.clone(),
+            title: bookmark.title.clone(),
+            note: bookmark.note.clone(),
+            tags: bookmark.tags.clone(),
+            is_public: bookmark.is_public,
+            preview_status: PREVIEW_PENDING.to_string(),
+            preview_title: None,
+            preview_description: None,
+            preview_image_url: None,
+            preview_site_name: None,
+            preview_error: None,
+            preview_attempts: 0,
+            preview_fetched_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        bookmarks.insert(created.id, created.clone());
+
+        Ok(created)
+    }
+
+    async fn get_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError> {
+        self.bookmarks
+            .read()
+            .get(id)
+            .filter(|b| b.tenant_id == *tenant_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Bookmark not found".into()))
+    }
+
+    async fn list_bookmarks(
+        &self,
+        tenant_id: &Uuid,
+        public_only: bool,
+        tag: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Bookmark>, AppError> {
+        Ok(self.filtered(tenant_id, public_only, tag.as_deref())
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn count_bookmarks(&self, tenant_id: &Uuid, public_only: bool, tag: Option<String>) -> Result<i64, AppError> {
+        Ok(self.filtered(tenant_id, public_only, tag.as_deref()).len() as i64)
+    }
+
+    async fn update_bookmark(&self, tenant_id: &Uuid, bookmark: &Bookmark) -> Result<Bookmark, AppError> {
+        let mut bookmarks = self.bookmarks.write();
+        if Self::url_taken(&bookmarks, tenant_id, &bookmark.url, Some(bookmark.id)) {
+            return Err(AppError::Conflict("This link is already bookmarked".to_string()));
+        }
+
+        let stored = bookmarks
+            .get_mut(&bookmark.id)
+            .filter(|b| b.tenant_id == *tenant_id)
+            .ok_or_else(|| AppError::NotFound("Bookmark not found".into()))?;
+        stored.url = bookmark.url.clone();
+        stored.title = bookmark.title.clone();
+        stored.note = bookmark.note.clone();
+        stored.tags = bookmark.tags.clone();
+        stored.is_public = bookmark.is_public;
+        stored.updated_at = Utc::now();
+
+        Ok(stored.clone())
+    }
+
+    async fn delete_bookmark(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut bookmarks = self.bookmarks.write();
+        match bookmarks.get(id) {
+            Some(b) if b.tenant_id == *tenant_id => {
+                bookmarks.remove(id);
+                Ok(())
+            }
+            _ => Err(AppError::NotFound("Bookmark not found".into())),
+        }
+    }
+
+    async fn list_pending_previews(&self, limit: i64) -> Result<Vec<Bookmark>, AppError> {
+        let mut pending: Vec<Bookmark> = self.bookmarks
+            .read()
+            .values()
+            .filter(|b| b.preview_status == PREVIEW_PENDING)
+            .cloned()
+            .collect();
+        pending.sort_by_key(|b| b.created_at);
+        pending.truncate(limit.max(0) as usize);
+
+        Ok(pending)
+    }
+
+    async fn record_preview(&self, id: &Uuid, preview: &LinkPreview) -> Result<(), AppError> {
+        self.modify_preview(id, |bookmark| {
+            bookmark.preview_status = PREVIEW_READY.to_string();
+            bookmark.preview_title = preview.title.clone();
+            bookmark.preview_description = preview.description.clone();
+            bookmark.preview_image_url = preview.image_url.clone();
+            bookmark.preview_site_name = preview.site_name.clone();
+            bookmark.preview_error = None;
+        });
+
+        Ok(())
+    }
+
+    async fn record_preview_failure(&self, id: &Uuid, error: &str) -> Result<(), AppError> {
+        self.modify_preview(id, |bookmark| {
+            if bookmark.preview_attempts + 1 >= MAX_PREVIEW_ATTEMPTS {
+                bookmark.preview_status = PREVIEW_FAILED.to_string();
+            }
+            bookmark.preview_error = Some(error.to_string());
+        });
+
+        Ok(())
+    }
+
+    async fn reset_preview(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Bookmark, AppError> {
+        let mut bookmarks = self.bookmarks.write();
+        let bookmark = bookmarks
+            .get_mut(id)
+            .filter(|b| b.tenant_id == *tenant_id)
+            .ok_or_else(|| AppError::NotFound("Bookmark not found".into()))?;
+        bookmark.preview_status = PREVIEW_PENDING.to_string();
+        bookmark.preview_title = None;
+        bookmark.preview_description = None;
+        bookmark.preview_image_url = None;
+        bookmark.preview_site_name = None;
+        bookmark.preview_error = None;
+        bookmark.preview_attempts = 0;
+        bookmark.preview_fetched_at = None;
+
+        Ok(bookmark.clone())
+    }
+}
+
+// ───── Outbound Clicks ───────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryOutboundClickRepo {
+    clicks: Arc<RwLock<Vec<(Uuid, OutboundClickInsert)>>>,
+}
+
+#[async_trait]
+impl OutboundClickRepository for InMemoryOutboundClickRepo {
+    async fn record_click(&self, tenant_id: &Uuid, click: &OutboundClickInsert) -> Result<(), AppError> {
+        self.clicks.write().push((*tenant_id, click.clone()));
+        Ok(())
+    }
+}
+
+// ───── Reading Progress ──────────────────────────────────────────────
+
+/// One session's furthest point in a post
+#[derive(Clone)]
+struct StoredReading {
+    post_id: Uuid,
+    session_hash: String,
+    max_percent: i16,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Checks posts against the user repository's post store it was built from
+#[derive(Clone, Default)]
+pub struct InMemoryReadingProgressRepo {
+    users: InMemoryUserRepo,
+    progress: Arc<RwLock<Vec<(Uuid, StoredReading)>>>,
+}
+
+impl InMemoryReadingProgressRepo {
+    pub fn new(users: InMemoryUserRepo) -> Self {
+        InMemoryReadingProgressRepo { users, progress: Arc::default() }
+    }
+}
+
+#[async_trait]
+impl ReadingProgressRepository for InMemoryReadingProgressRepo {
+    async fn record_progress(
+        &self,
+        tenant_id: &Uuid,
+        session_hash: &str,
+        updates: &[ReadingProgressUpdate],
+    ) -> Result<u64, AppError> {
+        let posts = self.users.posts.read();
+        let mut progress = self.progress.write();
+        let now = Utc::now();
+        let mut accepted = 0;
+
+        for update in updates {
+            let readable = posts
+                .get(&update.post_id)
+                .is_some_and(|p| p.tenant_id == *tenant_id && p.published && p.deleted_at.is_none());
+            if !readable {
+                continue;
+            }
+
+            let existing = progress
+                .iter_mut()
+                .find(|(_, r)| r.post_id == update.post_id && r.session_hash == session_hash);
+            match existing {
+                Some((_, reading)) => {
+                    reading.max_percent = reading.max_percent.max(update.percent);
+                    reading.updated_at = now;
+                }
+                None => progress.push((
+                    *tenant_id,
+                    StoredReading {
+                        post_id: update.post_id,
+                        session_hash: session_hash.to_string(),
+                        max_percent: update.percent,
+                        started_at: now,
+                        updated_at: now,
+                    },
+                )),
+            }
+            accepted += 1;
+        }
+
+        Ok(accepted)
+    }
+}
+
+// ───── Title Tests ───────────────────────────────────────────────────
+
+/// Counters for one variant and day
+#[derive(Clone)]
+struct StoredTitleStats {
+    post_id: Uuid,
+    variant: i16,
+    day: NaiveDate,
+    impressions: i64,
+    clicks: i64,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryTitleTestRepo {
+    variants: Arc<RwLock<Vec<TitleVariant>>>,
+    stats: Arc<RwLock<Vec<(Uuid, StoredTitleStats)>>>,
+}
+
+impl InMemoryTitleTestRepo {
+    /// Adds `impressions` and `clicks` to the day's counters when the variant exists
+    fn count(&self, tenant_id: &Uuid, event: &TitleImpression, day: NaiveDate, impressions: i64, clicks: i64) -> bool {
+        let exists = self.variants
+            .read()
+            .iter()
+            .any(|v| v.tenant_id == *tenant_id && v.post_id == event.post_id && v.variant == event.variant);
+        if !exists {
+            return false;
+        }
+
+        let mut stats = self.stats.write();
+        match stats.iter_mut().find(|(_, s)| s.post_id == event.post_id && s.variant == event.variant && s.day == day) {
+            Some((_, s)) => {
+                s.impressions += impressions;
+                s.clicks += clicks;
+            }
+            None => stats.push((
+                *tenant_id,
+                StoredTitleStats { post_id: event.post_id, variant: event.variant, day, impressions, clicks },
+            )),
+        }
+
+        true
+    }
+
+    fn remove_test(&self, tenant_id: &Uuid, post_id: &Uuid) -> bool {
+        let mut variants = self.variants.write();
+        let before = variants.len();
+        variants.retain(|v| !(v.tenant_id == *tenant_id && v.post_id == *post_id));
+        self.stats.write().retain(|(t, s)| !(t == tenant_id && s.post_id == *post_id));
+
+        variants.len() < before
+    }
+}
+
+#[async_trait]
+impl TitleTestRepository for InMemoryTitleTestRepo {
+    async fn list_all_title_variants(&self) -> Result<Vec<TitleVariant>, AppError> {
+        let mut variants = self.variants.read().clone();
+        variants.sort_by_key(|v| (v.tenant_id, v.post_id, v.variant));
+        Ok(variants)
+    }
+
+    async fn get_title_variants(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariant>, AppError> {
+        let mut variants: Vec<TitleVariant> = self.variants
+            .read()
+            .iter()
+            .filter(|v| v.tenant_id == *tenant_id && v.post_id == *post_id)
+            .cloned()
+            .collect();
+        variants.sort_by_key(|v| v.variant);
+        Ok(variants)
+    }
+
+    async fn replace_title_test(
+        &self,
+        tenant_id: &Uuid,
+        post_id: &Uuid,
+        variants: &[TitleVariantInsert],
+    ) -> Result<Vec<TitleVariant>, AppError> {
+        self.remove_test(tenant_id, post_id);
+
+        let now = Utc::now();
+        let stored: Vec<TitleVariant> = variants
+            .iter()
+            .map(|v| TitleVariant {
+                post_id: *post_id,
+                variant: v.variant,
+                tenant_id: *tenant_id,
+                title: v.title.clone(),
+                weight: v.weight,
+                created_at: now,
+            })
+            .collect();
+        self.variants.write().extend(stored.iter().cloned());
+
+        Ok(stored)
+    }
+
+    async fn delete_title_test(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<bool, AppError> {
+        Ok(self.remove_test(tenant_id, post_id))
+    }
+
+    async fn record_title_impressions(
+        &self,
+        tenant_id: &Uuid,
+        impressions: &[TitleImpression],
+        day: NaiveDate,
+    ) -> Result<u64, AppError> {
+        let mut counted: HashSet<TitleImpression> = HashSet::new();
+        for impression in impressions {
+            if self.count(tenant_id, impression, day, 1, 0) {
+                counted.insert(*impression);
+            }
+        }
+
+        Ok(counted.len() as u64)
+    }
+
+    async fn record_title_click(&self, tenant_id: &Uuid, click: &TitleImpression, day: NaiveDate) -> Result<bool, AppError> {
+        Ok(self.count(tenant_id, click, day, 0, 1))
+    }
+
+    async fn title_variant_stats(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariantStats>, AppError> {
+        let mut totals: HashMap<i16, (i64, i64)> = HashMap::new();
+        for (_, s) in self.stats.read().iter().filter(|(t, s)| t == tenant_id && s.post_id == *post_id) {
+            let (impressions, clicks) = totals.entry(s.variant).or_default();
+            *impressions += s.impressions;
+            *clicks += s.clicks;
+        }
+
+        let mut stats: Vec<TitleVariantStats> = totals
+            .into_iter()
+            .map(|(variant, (impressions, clicks))| TitleVariantStats { variant, impressions, clicks })
+            .collect();
+        stats.sort_by_key(|s| s.variant);
+        Ok(stats)
+    }
+}
+
+// ───── Link Checks ───────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryLinkCheckRepo {
+    checks: Arc<RwLock<Vec<LinkCheck>>>,
+}
+
+#[async_trait]
+impl LinkCheckRepository for InMemoryLinkCheckRepo {
+    async fn record_link_checks(
+        &self,
+        tenant_id: &Uuid,
+        checks: &[LinkCheckInsert],
+        checked_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut stored = self.checks.write();
+
+        for check in checks {
+            let existing = stored.iter_mut().find(|c| {
+                c.tenant_id == *tenant_id
+                    && c.source_type == check.source_type
+                    && c.source_id == check.source_id
+                    && c.url == check.url
+            });
+            let broken_since = match (check.broken, &existing) {
+                (false, _) => None,
+                (true, Some(previous)) => previous.broken_since.or(Some(checked_at)),
+                (true, None) => Some(checked_at),
+            };
+            let row = LinkCheck {
+                id: existing.as_ref().map_or_else(Uuid::new_v4, |c| c.id),
+                tenant_id: *tenant_id,
+                source_type: check.source_type.to_string(),
+                source_id: check.source_id,
+                source_title: check.source_title.clone(),
+                url: check.url.clone(),
+                status_code: check.status_code,
+                error: check.error.clone(),
+                broken: check.broken,
+                broken_since,
+                checked_at,
+            };
+            match existing {
+                Some(previous) => *previous = row,
+                None => stored.push(row),
+            }
+        }
+
+        stored.retain(|c| c.tenant_id != *tenant_id || c.checked_at >= checked_at);
+        Ok(())
+    }
+
+    async fn list_broken_links(&self, tenant_id: &Uuid) -> Result<Vec<LinkCheck>, AppError> {
+        let mut broken: Vec<LinkCheck> = self.checks
+            .read()
+            .iter()
+            .filter(|c| c.tenant_id == *tenant_id && c.broken)
+            .cloned()
+            .collect();
+        broken.sort_by(|a, b| {
+            (&a.source_type, &a.source_title, a.source_id, &a.url)
+                .cmp(&(&b.source_type, &b.source_title, b.source_id, &b.url))
+        });
+
+        Ok(broken)
+    }
+}
+
+// ───── Security Events ───────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemorySecurityEventRepo {
+    events: Arc<RwLock<Vec<(Uuid, SecurityEventInsert)>>>,
+}
+
+impl InMemorySecurityEventRepo {
+    fn matching(&self, tenant_id: &Uuid, kind: &str, since: DateTime<Utc>) -> Vec<SecurityEventInsert> {
+        self.events
+            .read()
+            .iter()
+            .filter(|(t, e)| t == tenant_id && e.kind == kind && e.occurred_at >= since)
+            .map(|(_, e)| e.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl SecurityEventRepository for InMemorySecurityEventRepo {
+    async fn record_event(&self, tenant_id: &Uuid, event: &SecurityEventInsert) -> Result<(), AppError> {
+        self.events.write().push((*tenant_id, event.clone()));
+        Ok(())
+    }
+
+    async fn ip_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventIpSummary>, AppError> {
+        let mut by_ip: HashMap<String, SecurityEventIpSummary> = HashMap::new();
+        for event in self.matching(tenant_id, &kind, since) {
+            let summary = by_ip.entry(event.ip.clone()).or_insert_with(|| SecurityEventIpSummary {
+                ip: event.ip.clone(),
+                hits: 0,
+                paths: Vec::new(),
+                first_seen: event.occurred_at,
+                last_seen: event.occurred_at,
+            });
+            summary.hits += 1;
+            summary.first_seen = summary.first_seen.min(event.occurred_at);
+            summary.last_seen = summary.last_seen.max(event.occurred_at);
+            if !summary.paths.contains(&event.path) {
+                summary.paths.push(event.path);
+            }
+        }
+
+        let mut summary: Vec<SecurityEventIpSummary> = by_ip.into_values().collect();
+        summary.iter_mut().for_each(|s| s.paths.sort());
+        summary.sort_by_key(|s| Reverse((s.hits, s.last_seen)));
+        summary.truncate(limit.max(0) as usize);
+
+        Ok(summary)
+    }
+
+    async fn path_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventPathSummary>, AppError> {
+        let mut by_path: HashMap<String, i64> = HashMap::new();
+        for event in self.matching(tenant_id, &kind, since) {
+            *by_path.entry(event.path).or_default() += 1;
+        }
+
+        let mut summary: Vec<SecurityEventPathSummary> = by_path
+            .into_iter()
+            .map(|(path, hits)| SecurityEventPathSummary { path, hits })
+            .collect();
+        summary.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.path.cmp(&b.path)));
+        summary.truncate(limit.max(0) as usize);
+
+        Ok(summary)
+    }
+
+    async fn event_totals(&self, tenant_id: &Uuid, kind: String, since: DateTime<Utc>) -> Result<(i64, i64), AppError> {
+        let events = self.matching(tenant_id, &kind, since);
+        let unique_ips: HashSet<&str> = events.iter().map(|e| e.ip.as_str()).collect();
+
+        Ok((events.len() as i64, unique_ips.len() as i64))
+    }
+}
+
+// ───── Outbox ────────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryOutboxRepo {
+    events: Arc<RwLock<HashMap<Uuid, OutboxEvent>>>,
+    paused: Arc<RwLock<HashMap<(Uuid, String), PausedQueue>>>,
+}
+
+impl InMemoryOutboxRepo {
+    /// Counterpart of `outbox::enqueue_in_tx` for the in-memory repositories
+    pub fn enqueue(&self, tenant_id: &Uuid, event_type: &str, payload: JsonValue) {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        self.events.write().insert(id, OutboxEvent {
+            id,
+            tenant_id: *tenant_id,
+            event_type: event_type.to_string(),
+            payload,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+            delivered_at: None,
+            failed_at: None,
+            created_at: now,
+            leased_until: None,
+            cancelled_at: None,
+        });
+    }
+
+    fn jobs(&self, tenant_id: &Uuid, status: Option<JobStatus>, event_type: Option<&str>, now: DateTime<Utc>) -> Vec<OutboxEvent> {
+        let mut events: Vec<OutboxEvent> = self.events
+            .read()
+            .values()
+            .filter(|e| e.tenant_id == *tenant_id)
+            .filter(|e| event_type.is_none_or(|t| e.event_type == t))
+            .filter(|e| status.is_none_or(|s| e.status(now) == s))
+            .cloned()
+            .collect();
+        events.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| a.id.cmp(&b.id)));
+        events
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for InMemoryOutboxRepo {
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        let paused = self.paused.read();
+        let mut events = self.events.write();
+        let mut due: Vec<&mut OutboxEvent> = events
+            .values_mut()
+            .filter(|e| e.delivered_at.is_none() && e.failed_at.is_none() && e.next_attempt_at <= now)
+            .filter(|e| !paused.contains_key(&(e.tenant_id, e.event_type.clone())))
+            .collect();
+        due.sort_by_key(|e| e.next_attempt_at);
+
+        Ok(due
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|e| {
+                e.next_attempt_at = lease_until;
+                e.leased_until = Some(lease_until);
+                e.attempts += 1;
+                e.clone()
+            })
+            .collect())
+    }
+
+    async fn mark_delivered(&self, id: &Uuid) -> Result<(), AppError> {
+        if let Some(event) = self.events.write().get_mut(id) {
+            event.delivered_at = Some(Utc::now());
+            event.last_error = None;
+            event.leased_until = None;
+        }
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: &Uuid, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<(), AppError> {
+        if let Some(event) = self.events.write().get_mut(id) {
+            event.last_error = Some(error.to_string());
+            event.leased_until = None;
+            match retry_at {
+                Some(at) => event.next_attempt_at = at,
+                None => event.failed_at = Some(Utc::now()),
+            }
+        }
+        Ok(())
+    }
+
+    async fn list_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        Ok(self.jobs(tenant_id, status, event_type.as_deref(), now)
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn count_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        Ok(self.jobs(tenant_id, status, event_type.as_deref(), now).len() as i64)
+    }
+
+    async fn get_job(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<OutboxEvent>, AppError> {
+        Ok(self.events.read().get(id).filter(|e| e.tenant_id == *tenant_id).cloned())
+    }
+
+    async fn retry_job(&self, tenant_id: &Uuid, id: &Uuid, now: DateTime<Utc>) -> Result<Option<OutboxEvent>, AppError> {
+        let mut events = self.events.write();
+        let Some(event) = events.get_mut(id).filter(|e| e.tenant_id == *tenant_id) else {
+            return Ok(None);
+        };
+        if !matches!(event.status(now), JobStatus::Queued | JobStatus::Failed | JobStatus::Cancelled) {
+            return Ok(None);
+        }
+
+        if event.failed_at.is_some() {
+            event.attempts = 0;
+        }
+        event.next_attempt_at = now;
+        event.leased_until = None;
+        event.failed_at = None;
+        event.cancelled_at = None;
+
+        Ok(Some(event.clone()))
+    }
+
+    async fn cancel_job(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        reason: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<OutboxEvent>, AppError> {
+        let mut events = self.events.write();
+        let Some(event) = events.get_mut(id).filter(|e| e.tenant_id == *tenant_id) else {
+            return Ok(None);
+        };
+        if event.status(now) != JobStatus::Queued {
+            return Ok(None);
+        }
+
+        event.cancelled_at = Some(now);
+        event.failed_at = Some(now);
+        event.last_error = Some(reason.to_string());
+        event.leased_until = None;
+
+        Ok(Some(event.clone()))
+    }
+
+    async fn job_counts(&self, tenant_id: &Uuid, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<Vec<JobTypeCounts>, AppError> {
+        let mut counts: BTreeMap<String, JobTypeCounts> = BTreeMap::new();
+        for event in self.events.read().values().filter(|e| e.tenant_id == *tenant_id) {
+            let status = event.status(now);
+            let settled_at = event.delivered_at.or(event.failed_at);
+            if settled_at.is_some_and(|at| at < since) {
+                continue;
+            }
+
+            let entry = counts.entry(event.event_type.clone()).or_insert_with(|| JobTypeCounts {
+                event_type: event.event_type.clone(),
+                ..Default::default()
+            });
+            match status {
+                JobStatus::Queued => {
+                    entry.queued += 1;
+                    if event.attempts > 0 {
+                        entry.retrying += 1;
+                    }
+                    entry.oldest_queued_at = Some(entry.oldest_queued_at.map_or(event.created_at, |at| at.min(event.created_at)));
+                }
+                JobStatus::Running => entry.running += 1,
+                JobStatus::Delivered => {
+                    entry.delivered += 1;
+                    entry.delivered_attempts += event.attempts as i64;
+                }
+                JobStatus::Failed => entry.failed += 1,
+                JobStatus::Cancelled => entry.cancelled += 1,
+            }
+        }
+
+        Ok(counts.into_values().collect())
+    }
+
+    async fn list_paused_queues(&self, tenant_id: &Uuid) -> Result<Vec<PausedQueue>, AppError> {
+        let mut queues: Vec<PausedQueue> = self.paused
+            .read()
+            .iter()
+            .filter(|((tid, _), _)| tid == tenant_id)
+            .map(|(_, queue)| queue.clone())
+            .collect();
+        queues.sort_by(|a, b| a.event_type.cmp(&b.event_type));
+
+        Ok(queues)
+    }
+
+    async fn pause_queue(&self, tenant_id: &Uuid, event_type: &str, paused_by: &str) -> Result<PausedQueue, AppError> {
+        Ok(self.paused
+            .write()
+            .entry((*tenant_id, event_type.to_string()))
+            .or_insert_with(|| PausedQueue {
+                event_type: event_type.to_string(),
+                paused_by: Some(paused_by.to_string()),
+                paused_at: Utc::now(),
+            })
+            .clone())
+    }
+
+    async fn resume_queue(&self, tenant_id: &Uuid, event_type: &str) -> Result<bool, AppError> {
+        Ok(self.paused.write().remove(&(*tenant_id, event_type.to_string())).is_some())
+    }
+}
+
+// ───── Feature Flags ─────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryFeatureFlagRepo {
+    flags: Arc<RwLock<HashMap<(Uuid, String), FeatureFlag>>>,
+}
+
+#[async_trait]
+impl FeatureFlagRepository for InMemoryFeatureFlagRepo {
+    async fn list_feature_flags(&self, tenant_id: &Uuid) -> Result<Vec<FeatureFlag>, AppError> {
+        let mut flags: Vec<FeatureFlag> = self.flags
+            .read()
+            .values()
+            .filter(|flag| flag.tenant_id == *tenant_id)
+            .cloned()
+            .collect();
+        flags.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(flags)
+    }
+
+    async fn list_all_feature_flags(&self) -> Result<Vec<FeatureFlag>, AppError> {
+        Ok(self.flags.read().values().cloned().collect())
+    }
+
+    async fn upsert_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+    ) -> Result<FeatureFlag, AppError> {
+        let now = Utc::now();
+        let mut flags = self.flags.write();
+
+        let flag = flags.entry((*tenant_id, key.to_string())).or_insert_with(|| FeatureFlag {
+            tenant_id: *tenant_id,
+            key: key.to_string(),
+            enabled,
+            description: None,
+            created_at: now,
+            updated_at: now,
+        });
+        flag.enabled = enabled;
+        if description.is_some() {
+            flag.description = description;
+        }
+        flag.updated_at = now;
+
+        Ok(flag.clone())
+    }
+}
+
+// ───── Runtime Settings ──────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryAppSettingsRepo {
+    settings: Arc<RwLock<HashMap<(Uuid, String), AppSetting>>>,
+}
+
+#[async_trait]
+impl AppSettingsRepository for InMemoryAppSettingsRepo {
+    async fn list_all_settings(&self) -> Result<Vec<AppSetting>, AppError> {
+        let mut settings: Vec<AppSetting> = self.settings.read().values().cloned().collect();
+        settings.sort_by(|a, b| (a.tenant_id, &a.key).cmp(&(b.tenant_id, &b.key)));
+
+        Ok(settings)
+    }
+
+    async fn list_settings(&self, tenant_id: &Uuid) -> Result<Vec<AppSetting>, AppError> {
+        let mut settings: Vec<AppSetting> = self.settings
+            .read()
+            .values()
+            .filter(|s| s.tenant_id == *tenant_id)
+            .cloned()
+            .collect();
+        settings.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(settings)
+    }
+
+    async fn upsert_setting(&self, tenant_id: &Uuid, key: &str, value: &JsonValue) -> Result<AppSetting, AppError> {
+        let setting = AppSetting {
+            tenant_id: *tenant_id,
+            key: key.to_string(),
+            value: value.clone(),
+            updated_at: Utc::now(),
+        };
+        self.settings.write().insert((*tenant_id, setting.key.clone()), setting.clone());
+
+        Ok(setting)
+    }
+}
+
+// ───── API Tokens ────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryApiTokenRepo {
+    tokens: Arc<RwLock<Vec<ApiToken>>>,
+    daily: Arc<RwLock<HashMap<(Uuid, NaiveDate), ApiTokenDailyUsage>>>,
+}
+
+#[async_trait]
+impl ApiTokenRepository for InMemoryApiTokenRepo {
+    async fn list_active_api_tokens(&self) -> Result<Vec<ApiToken>, AppError> {
+        Ok(self.tokens.read().iter().filter(|t| t.revoked_at.is_none()).cloned().collect())
+    }
+
+    async fn list_api_tokens(&self, tenant_id: &Uuid) -> Result<Vec<ApiToken>, AppError> {
+        let mut tokens: Vec<ApiToken> = self.tokens
+            .read()
+            .iter()
+            .filter(|t| t.tenant_id == *tenant_id)
+            .cloned()
+            .collect();
+        tokens.sort_by_key(|t| Reverse(t.created_at));
+
+        Ok(tokens)
+    }
+
+    async fn create_api_token(&self, tenant_id: &Uuid, token: &ApiTokenInsert) -> Result<ApiToken, AppError> {
+        let created = ApiToken {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            name: token.name.clone(),
+            token_hash: token.token_hash.clone(),
+            token_prefix: token.token_prefix.clone(),
+            scopes: token.scopes.clone(),
+            rate_limit_per_minute: token.rate_limit_per_minute,
+            monthly_quota: token.monthly_quota,
+            request_count: 0,
+            rejected_count: 0,
+            last_used_at: None,
+            created_by: token.created_by,
+            created_at: Utc::now(),
+            revoked_at: None,
+        };
+        self.tokens.write().push(created.clone());
+
+        Ok(created)
+    }
+
+    async fn revoke_api_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError> {
+        let mut tokens = self.tokens.write();
+        let Some(token) = tokens
+            .iter_mut()
+            .find(|t| t.tenant_id == *tenant_id && t.id == *id && t.revoked_at.is_none())
+        else {
+            return Ok(false);
+        };
+        token.revoked_at = Some(Utc::now());
+
+        Ok(true)
+    }
+
+    async fn add_api_token_usage(&self, usage: &[(Uuid, ApiTokenUsage)]) -> Result<(), AppError> {
+        let mut tokens = self.tokens.write();
+        for (id, counted) in usage {
+            if let Some(token) = tokens.iter_mut().find(|t| t.id == *id) {
+                token.request_count += counted.requests as i64;
+                token.rejected_count += counted.rejected as i64;
+                token.last_used_at = token.last_used_at.max(counted.last_used_at);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn add_api_token_daily_usage(&self, usage: &[ApiTokenDailyUsage]) -> Result<(), AppError> {
+        let tokens = self.tokens.read();
+        let mut daily = self.daily.write();
+        for counted in usage.iter().filter(|u| tokens.iter().any(|t| t.id == u.token_id)) {
+            let row = daily
+                .entry((counted.token_id, counted.day))
+                .or_insert_with(|| ApiTokenDailyUsage { requests: 0, bytes: 0, errors: 0, ..counted.clone() });
+            row.requests += counted.requests;
+            row.bytes += counted.bytes;
+            row.errors += counted.errors;
+        }
+
+        Ok(())
+    }
+
+    async fn save_api_token_daily_totals(&self, totals: &[ApiTokenDailyUsage]) -> Result<(), AppError> {
+        let tokens = self.tokens.read();
+        let mut daily = self.daily.write();
+        for total in totals.iter().filter(|u| tokens.iter().any(|t| t.id == u.token_id)) {
+            let row = daily.entry((total.token_id, total.day)).or_insert_with(|| total.clone());
+            row.requests = row.requests.max(total.requests);
+            row.bytes = row.bytes.max(total.bytes);
+            row.errors = row.errors.max(total.errors);
+        }
+
+        Ok(())
+    }
+
+    async fn list_api_token_daily_usage(&self, token_id: &Uuid, since: NaiveDate) -> Result<Vec<ApiTokenDailyUsage>, AppError> {
+        let mut usage: Vec<ApiTokenDailyUsage> = self.daily
+            .read()
+            .values()
+            .filter(|u| u.token_id == *token_id && u.day >= since)
+            .cloned()
+            .collect();
+        usage.sort_by_key(|u| u.day);
+
+        Ok(usage)
+    }
+
+    async fn count_quota_requests_since(&self, since: NaiveDate) -> Result<Vec<(Uuid, i64)>, AppError> {
+        let tokens = self.tokens.read();
+        let mut counts: HashMap<Uuid, i64> = HashMap::new();
+        for usage in self.daily.read().values().filter(|u| u.day >= since) {
+            let limited = tokens
+                .iter()
+                .any(|t| t.id == usage.token_id && t.monthly_quota.is_some() && t.revoked_at.is_none());
+            if limited {
+                *counts.entry(usage.token_id).or_default() += usage.requests;
+            }
+        }
+
+        Ok(counts.into_iter().collect())
+    }
+}
+
+// ───── One-Time Tokens ───────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryOneTimeTokenRepo {
+    tokens: Arc<RwLock<Vec<OneTimeToken>>>,
+}
+
+#[async_trait]
+impl OneTimeTokenRepository for InMemoryOneTimeTokenRepo {
+    async fn create_one_time_token(&self, tenant_id: &Uuid, token: &OneTimeTokenInsert) -> Result<OneTimeToken, AppError> {
+        let now = Utc::now();
+        let mut tokens = self.tokens.write();
+        for earlier in tokens.iter_mut().filter(|t| {
+            t.tenant_id == *tenant_id && t.user_id == token.user_id && t.purpose == token.purpose && t.used_at.is_none()
+        }) {
+            earlier.used_at = Some(now);
+        }
+
+        let created = OneTimeToken {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            user_id: token.user_id,
+            purpose: token.purpose.to_string(),
+            token_hash: token.token_hash.clone(),
+            expires_at: token.expires_at,
+            used_at: None,
+            created_at: now,
+        };
+        tokens.push(created.clone());
+
+        Ok(created)
+    }
+
+    async fn find_one_time_token(&self, tenant_id: &Uuid, token_hash: &str) -> Result<Option<OneTimeToken>, AppError> {
+        Ok(self.tokens
+            .read()
+            .iter()
+            .find(|t| t.tenant_id == *tenant_id && t.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn consume_one_time_token(
+        &self,
+        tenant_id: &Uuid,
+        purpose: &str,
+        token_hash: &str,
+    ) -> Result<Option<OneTimeToken>, AppError> {
+        let now = Utc::now();
+        let mut tokens = self.tokens.write();
+        let Some(token) = tokens
+            .iter_mut()
+            .find(|t| t.tenant_id == *tenant_id && t.token_hash == token_hash && t.is_valid(purpose, now))
+        else {
+            return Ok(None);
+        };
+        token.used_at = Some(now);
+
+        Ok(Some(token.clone()))
+    }
+
+    async fn release_one_time_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        if let Some(token) = self.tokens.write().iter_mut().find(|t| t.tenant_id == *tenant_id && t.id == *id) {
+            token.used_at = None;
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired_one_time_tokens(&self, before: DateTime<Utc>) -> Result<u64, AppError> {
+        let mut tokens = self.tokens.write();
+        let count = tokens.len();
+        tokens.retain(|t| t.expires_at >= before);
+
+        Ok((count - tokens.len()) as u64)
+    }
+}
+
+// ───── Tenants ───────────────────────────────────────────────────────
+
+#[derive(Clone)]
+pub struct InMemoryTenantRepo {
+    tenants: Arc<RwLock<Vec<Tenant>>>,
+    domains: Arc<RwLock<HashMap<String, TenantDomain>>>,
+}
+
+impl Default for InMemoryTenantRepo {
+    /// Seeds the default tenant, matching the migration
+    fn default() -> Self {
+        let tenant = Tenant {
+            id: DEFAULT_TENANT_ID,
+            slug: "default".to_string(),
+            name: "Default".to_string(),
+            hostnames: Vec::new(),
+            created_at: Utc::now(),
+        };
+
+        Self {
+            tenants: Arc::new(RwLock::new(vec![tenant])),
+            domains: Arc::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl TenantRepository for InMemoryTenantRepo {
+    async fn list_tenants(&self) -> Result<Vec<Tenant>, AppError> {
+        let domains = self.domains.read();
+
+        Ok(self.tenants
+            .read()
+            .iter()
+            .cloned()
+            .map(|mut tenant| {
+                tenant.hostnames = domains
+                    .values()
+                    .filter(|d| d.tenant_id == tenant.id && d.verified_at.is_some())
+                    .map(|d| d.hostname.clone())
+                    .collect();
+                tenant.hostnames.sort();
+                tenant
+            })
+            .collect())
+    }
+
+    async fn list_domains(&self, tenant_id: &Uuid) -> Result<Vec<TenantDomain>, AppError> {
+        let mut domains: Vec<TenantDomain> = self.domains
+            .read()
+            .values()
+            .filter(|d| d.tenant_id == *tenant_id)
+            .cloned()
+            .collect();
+        domains.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+
+        Ok(domains)
+    }
+
+    async fn get_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<Option<TenantDomain>, AppError> {
+        Ok(self.domains.read().get(hostname).filter(|d| d.tenant_id == *tenant_id).cloned())
+    }
+
+    async fn create_domain(&self, tenant_id: &Uuid, hostname: &str, verification_token: &str) -> Result<TenantDomain, AppError> {
+        let mut domains = self.domains.write();
+
+        if domains.contains_key(hostname) {
+            return Err(AppError::Conflict("Domain is already registered".to_string()));
+        }
+
+        let domain = TenantDomain {
+            hostname: hostname.to_string(),
+            tenant_id: *tenant_id,
+            verification_token: verification_token.to_string(),
+            verified_at: None,
+            last_checked_at: None,
+            last_error: None,
+            created_at: Utc::now(),
+        };
+        domains.insert(domain.hostname.clone(), domain.clone());
+
+        Ok(domain)
+    }
+
+    async fn delete_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<(), AppError> {
+        let mut domains = self.domains.write();
+        if domains.get(hostname).is_none_or(|d| d.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Domain not found".into()));
+        }
+        domains.remove(hostname);
+
+        Ok(())
+    }
+
+    async fn list_pending_domains(&self, limit: i64) -> Result<Vec<TenantDomain>, AppError> {
+        let mut domains: Vec<TenantDomain> = self.domains
+            .read()
+            .values()
+            .filter(|d| d.verified_at.is_none())
+            .cloned()
+            .collect();
+        // `None` sorts first, matching NULLS FIRST
+        domains.sort_by_key(|d| (d.last_checked_at, d.created_at));
+        domains.truncate(limit.max(0) as usize);
+
+        Ok(domains)
+    }
+
+    async fn record_domain_check(&self, hostname: &str, verified: bool, error: Option<String>) -> Result<TenantDomain, AppError> {
+        let mut domains = self.domains.write();
+        let domain = domains
+            .get_mut(hostname)
+            .ok_or_else(|| AppError::NotFound("Domain not found".into()))?;
+
+        let now = Utc::now();
+        domain.last_checked_at = Some(now);
+        domain.last_error = error;
+        if verified && domain.verified_at.is_none() {
+            domain.verified_at = Some(now);
+        }
+
+        Ok(domain.clone())
+    }
+}
+
+
+// ───── Audit Log ─────────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryAuditLogRepo {
+    entries: Arc<RwLock<Vec<AuditEntry>>>,
+}
+
+/// SQL's full-text search approximated: every word of `q` appears in `details`
+fn audit_filter_matches(filter: &AuditLogFilter, entry: &AuditEntry) -> bool {
+    let text_matches = |wanted: &Option<String>, actual: Option<&str>| wanted.as_deref().is_none_or(|w| actual == Some(w));
+
+    text_matches(&filter.actor, Some(&entry.actor))
+        && text_matches(&filter.action, Some(&entry.action))
+        && text_matches(&filter.entity_type, entry.entity_type.as_deref())
+        && text_matches(&filter.entity_id, entry.entity_id.as_deref())
+        && filter.result.is_none_or(|r| r.as_str() == entry.result)
+        && filter.from.is_none_or(|from| entry.created_at >= from)
+        && filter.to.is_none_or(|to| entry.created_at < to)
+        && filter.q.as_deref().is_none_or(|q| {
+            let details = entry.details.to_string().to_lowercase();
+            q.split_whitespace().all(|word| details.contains(&word.to_lowercase()))
+        })
+}
+
+#[async_trait]
+impl AuditLogRepository for InMemoryAuditLogRepo {
+    async fn record_entry(&self, tenant_id: &Uuid, entry: &AuditEntryInsert) -> Result<(), AppError> {
+        self.entries.write().push(AuditEntry {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            actor: entry.actor.clone(),
+            action: entry.action.clone(),
+            details: entry.details.clone(),
+            created_at: Utc::now(),
+            entity_type: entry.entity_type.clone(),
+            entity_id: entry.entity_id.clone(),
+            result: entry.result.as_str().to_string(),
+        });
+
+        Ok(())
+    }
+
+    async fn search_entries(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut entries: Vec<AuditEntry> = self.entries
+            .read()
+            .iter()
+            .filter(|e| e.tenant_id == *tenant_id && audit_filter_matches(filter, e))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| (Reverse(e.created_at), e.id));
+
+        Ok(entries.into_iter().skip(offset.max(0) as usize).take(limit.max(0) as usize).collect())
+    }
+
+    async fn count_entries(&self, tenant_id: &Uuid, filter: &AuditLogFilter) -> Result<i64, AppError> {
+        let count = self.entries
+            .read()
+            .iter()
+            .filter(|e| e.tenant_id == *tenant_id && audit_filter_matches(filter, e))
+            .count();
+
+        Ok(count as i64)
+    }
+
+    async fn list_entries_chunk(
+        &self,
+        tenant_id: &Uuid,
+        filter: &AuditLogFilter,
+        after: Option<AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut entries: Vec<AuditEntry> = self.entries
+            .read()
+            .iter()
+            .filter(|e| e.tenant_id == *tenant_id && audit_filter_matches(filter, e))
+            .filter(|e| after.is_none_or(|cursor| (e.created_at, e.id) > cursor))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| (e.created_at, e.id));
+        entries.truncate(limit.max(0) as usize);
+
+        Ok(entries)
+    }
+
+    async fn delete_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<u64, AppError> {
+        let mut entries = self.entries.write();
+        let before = entries.len();
+        entries.retain(|e| e.tenant_id != *tenant_id || !ids.contains(&e.id));
+
+        Ok((before - entries.len()) as u64)
+    }
+}
+
+// ───── Retention ─────────────────────────────────────────────────────
+
+/// Name and email of an anonymized contact message
+#[derive(Clone)]
+struct StashedIdentity {
+    tenant_id: Uuid,
+    name: String,
+    email: String,
+    anonymized_at: DateTime<Utc>,
+}
+
+/// Works directly on the stores of the repositories it was built from
+#[derive(Clone, Default)]
+pub struct InMemoryRetentionRepo {
+    contacts: InMemoryContactMeRepo,
+    clicks: InMemoryOutboundClickRepo,
+    reading: InMemoryReadingProgressRepo,
+    titles: InMemoryTitleTestRepo,
+    events: InMemorySecurityEventRepo,
+    audit: InMemoryAuditLogRepo,
+    identities: Arc<RwLock<HashMap<Uuid, StashedIdentity>>>,
+}
+
+impl InMemoryRetentionRepo {
+    pub fn new(
+        contacts: InMemoryContactMeRepo,
+        clicks: InMemoryOutboundClickRepo,
+        reading: InMemoryReadingProgressRepo,
+        titles: InMemoryTitleTestRepo,
+        events: InMemorySecurityEventRepo,
+        audit: InMemoryAuditLogRepo,
+    ) -> Self {
+        InMemoryRetentionRepo { contacts, clicks, reading, titles, events, audit, identities: Arc::default() }
+    }
+}
+
+/// Removes up to `limit` of the tenant's rows matching `expired`
+fn remove_expired<T>(rows: &mut Vec<(Uuid, T)>, tenant_id: &Uuid, limit: i64, expired: impl Fn(&T) -> bool) -> u64 {
+    let mut removed = 0;
+    rows.retain(|(t, row)| {
+        if removed < limit && t == tenant_id && expired(row) {
+            removed += 1;
+            return false;
+        }
+        true
+    });
+
+    removed as u64
+}
+
+#[async_trait]
+impl RetentionRepository for InMemoryRetentionRepo {
+    async fn count_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let count = match (entity, action) {
+            (RetentionEntity::ContactMessages, RetentionAction::Purge | RetentionAction::Anonymize) => self.contacts
+                .messages
+                .read()
+                .values()
+                .filter(|m| m.tenant_id == *tenant_id && m.created_at < cutoff)
+                .filter(|m| action == RetentionAction::Purge || m.anonymized_at.is_none())
+                .count(),
+            (RetentionEntity::AnonymizedIdentities, RetentionAction::Purge) => self.identities
+                .read()
+                .values()
+                .filter(|i| i.tenant_id == *tenant_id && i.anonymized_at < cutoff)
+                .count(),
+            (RetentionEntity::Analytics, RetentionAction::Purge) => {
+                let clicks = self.clicks
+                    .clicks
+                    .read()
+                    .iter()
+                    .filter(|(t, c)| t == tenant_id && c.clicked_at < cutoff)
+                    .count();
+                let reading = self.reading
+                    .progress
+                    .read()
+                    .iter()
+                    .filter(|(t, r)| t == tenant_id && r.updated_at < cutoff)
+                    .count();
+                let titles = self.titles
+                    .stats
+                    .read()
+                    .iter()
+                    .filter(|(t, s)| t == tenant_id && s.day < cutoff.date_naive())
+                    .count();
+                clicks + reading + titles
+            }
+            (RetentionEntity::SecurityEvents, RetentionAction::Purge) => self.events
+                .events
+                .read()
+                .iter()
+                .filter(|(t, e)| t == tenant_id && e.occurred_at < cutoff)
+                .count(),
+            (RetentionEntity::Audit, RetentionAction::Purge | RetentionAction::Archive) => self.audit
+                .entries
+                .read()
+                .iter()
+                .filter(|e| e.tenant_id == *tenant_id && e.created_at < cutoff)
+                .count(),
+            _ => return Err(unsupported_action(entity, action)),
+        };
+
+        Ok(count as i64)
+    }
+
+    async fn apply_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, AppError> {
+        let affected = match (entity, action) {
+            (RetentionEntity::ContactMessages, RetentionAction::Purge) => {
+                let mut messages = self.contacts.messages.write();
+                let expired: Vec<Uuid> = messages
+                    .values()
+                    .filter(|m| m.tenant_id == *tenant_id && m.created_at < cutoff)
+                    .map(|m| m.id)
+                    .take(limit.max(0) as usize)
+                    .collect();
+                let mut identities = self.identities.write();
+                expired.iter().for_each(|id| {
+                    messages.remove(id);
+                    identities.remove(id);
+                });
+                expired.len() as u64
+            }
+            (RetentionEntity::ContactMessages, RetentionAction::Anonymize) => {
+                let now = Utc::now();
+                let mut identities = self.identities.write();
+                let mut anonymized = 0;
+                for message in self.contacts.messages.write().values_mut() {
+                    if anonymized < limit && message.tenant_id == *tenant_id && message.created_at < cutoff
+                        && message.anonymized_at.is_none()
+                    {
+                        let identity = StashedIdentity {
+                            tenant_id: message.tenant_id,
+                            name: std::mem::replace(&mut message.name, ANONYMIZED.to_string()),
+                            email: std::mem::replace(&mut message.email, ANONYMIZED_EMAIL.to_string()),
+                            anonymized_at: now,
+                        };
+                        identities.insert(message.id, identity);
+                        message.anonymized_at = Some(now);
+                        anonymized += 1;
+                    }
+                }
+                anonymized as u64
+            }
+            (RetentionEntity::AnonymizedIdentities, RetentionAction::Purge) => {
+                let mut removed = 0;
+                self.identities.write().retain(|_, i| {
+                    if removed < limit && i.tenant_id == *tenant_id && i.anonymized_at < cutoff {
+                        removed += 1;
+                        return false;
+                    }
+                    true
+                });
+                removed as u64
+            }
+            (RetentionEntity::Analytics, RetentionAction::Purge) => {
+                let clicks = remove_expired(&mut self.clicks.clicks.write(), tenant_id, limit, |c| c.clicked_at < cutoff);
+                let reading = remove_expired(
+                    &mut self.reading.progress.write(),
+                    tenant_id,
+                    limit - clicks as i64,
+                    |r| r.updated_at < cutoff,
+                );
+                let titles = remove_expired(
+                    &mut self.titles.stats.write(),
+                    tenant_id,
+                    limit - (clicks + reading) as i64,
+                    |s| s.day < cutoff.date_naive(),
+                );
+                clicks + reading + titles
+            }
+            (RetentionEntity::SecurityEvents, RetentionAction::Purge) => {
+                remove_expired(&mut self.events.events.write(), tenant_id, limit, |e| e.occurred_at < cutoff)
+            }
+            (RetentionEntity::Audit, RetentionAction::Purge) => {
+                let mut removed = 0;
+                self.audit.entries.write().retain(|e| {
+                    if removed < limit && e.tenant_id == *tenant_id && e.created_at < cutoff {
+                        removed += 1;
+                        return false;
+                    }
+                    true
+                });
+                removed as u64
+            }
+            _ => return Err(unsupported_action(entity, action)),
+        };
+
+        Ok(affected)
+    }
+
+    async fn restore_contact_identity(&self, tenant_id: &Uuid, message_id: &Uuid) -> Result<bool, AppError> {
+        let mut identities = self.identities.write();
+        if identities.get(message_id).is_none_or(|i| i.tenant_id != *tenant_id) {
+            return Ok(false);
+        }
+        let Some(identity) = identities.remove(message_id) else {
+            return Ok(false);
+        };
+
+        let mut messages = self.contacts.messages.write();
+        let Some(message) = messages.get_mut(message_id) else {
+            return Ok(false);
+        };
+        message.name = identity.name;
+        message.email = identity.email;
+        message.anonymized_at = None;
+
+        Ok(true)
+    }
+}
+
+// ───── Stats Rollups ─────────────────────────────────────────────────
+
+/// Identity of a rollup row within its period
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum RollupKey {
+    Reads(Uuid, i16),
+    Clicks(String),
+    Referrer(String),
+    Country(&'static str, Option<String>),
+}
+
+/// One row of one of the four rollup tables
+#[derive(Clone)]
+enum RollupRow {
+    Reads { post_id: Uuid, max_percent: i16, readers: i64 },
+    Clicks { target_url: String, target_host: String, clicks: i64, unique_referrers: i64, last_clicked_at: DateTime<Utc> },
+    Referrer { source_path: String, clicks: i64 },
+    Country { source: &'static str, country_code: Option<String>, count: i64 },
+}
+
+/// Raw table a rollup row is computed from
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum RollupSource {
+    Reading,
+    Clicks,
+    Attacks,
+}
+
+impl RollupRow {
+    fn key(&self) -> RollupKey {
+        match self {
+            RollupRow::Reads { post_id, max_percent, .. } => RollupKey::Reads(*post_id, *max_percent),
+            RollupRow::Clicks { target_url, .. } => RollupKey::Clicks(target_url.clone()),
+            RollupRow::Referrer { source_path, .. } => RollupKey::Referrer(source_path.clone()),
+            RollupRow::Country { source, country_code, .. } => RollupKey::Country(source, country_code.clone()),
+        }
+    }
+
+    fn source(&self) -> RollupSource {
+        match self {
+            RollupRow::Reads { .. } => RollupSource::Reading,
+            RollupRow::Country { source, .. } if *source == COUNTRY_SOURCE_ATTACKS => RollupSource::Attacks,
+            _ => RollupSource::Clicks,
+        }
+    }
+
+    /// Adds the counts of a row with the same key
+    fn add(&mut self, other: &RollupRow) {
+        match (self, other) {
+            (RollupRow::Reads { readers, .. }, RollupRow::Reads { readers: more, .. }) => *readers += more,
+            (
+                RollupRow::Clicks { clicks, unique_referrers, last_clicked_at, .. },
+                RollupRow::Clicks { clicks: more, unique_referrers: more_referrers, last_clicked_at: other_last, .. },
+            ) => {
+                *clicks += more;
+                *unique_referrers += more_referrers;
+                *last_clicked_at = (*last_clicked_at).max(*other_last);
+            }
+            (RollupRow::Referrer { clicks, .. }, RollupRow::Referrer { clicks: more, .. }) => *clicks += more,
+            (RollupRow::Country { count, .. }, RollupRow::Country { count: more, .. }) => *count += more,
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone)]
+struct StoredRollup {
+    tenant_id: Uuid,
+    period: &'static str,
+    period_start: NaiveDate,
+    row: RollupRow,
+}
+
+/// Sums rows sharing a key; the `Option<NaiveDate>` keeps periods apart when given
+fn fold_rollups(rows: impl IntoIterator<Item = (Option<NaiveDate>, RollupRow)>) -> Vec<(Option<NaiveDate>, RollupRow)> {
+    let mut folded: HashMap<(Option<NaiveDate>, RollupKey), RollupRow> = HashMap::new();
+    for (period, row) in rows {
+        match folded.entry((period, row.key())) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => entry.get_mut().add(&row),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(row);
+            }
+        }
+    }
+
+    folded.into_iter().map(|((period, _), row)| (period, row)).collect()
+}
+
+/// Rolls up from the stores of the repositories it was built from
+#[derive(Clone, Default)]
+pub struct InMemoryStatsRollupRepo {
+    clicks: InMemoryOutboundClickRepo,
+    reading: InMemoryReadingProgressRepo,
+    events: InMemorySecurityEventRepo,
+    rollups: Arc<RwLock<Vec<StoredRollup>>>,
+}
+
+impl InMemoryStatsRollupRepo {
+    pub fn new(clicks: InMemoryOutboundClickRepo, reading: InMemoryReadingProgressRepo, events: InMemorySecurityEventRepo) -> Self {
+        InMemoryStatsRollupRepo { clicks, reading, events, rollups: Arc::default() }
+    }
+
+    /// The tenant's raw rows from `from` on, before `to` when given, summed per UTC day
+    fn raw_daily(&self, tenant_id: &Uuid, from: DateTime<Utc>, to: Option<DateTime<Utc>>) -> Vec<(NaiveDate, RollupRow)> {
+        let in_range = |at: DateTime<Utc>| at >= from && to.is_none_or(|to| at < to);
+        let mut rows = Vec::new();
+        let mut referrers: HashMap<(NaiveDate, String), HashSet<String>> = HashMap::new();
+
+        for (_, reading) in self.reading.progress.read().iter().filter(|(t, r)| t == tenant_id && in_range(r.started_at)) {
+            rows.push((
+                Some(reading.started_at.date_naive()),
+                RollupRow::Reads { post_id: reading.post_id, max_percent: reading.max_percent, readers: 1 },
+            ));
+        }
+
+        for (_, click) in self.clicks.clicks.read().iter().filter(|(t, c)| t == tenant_id && in_range(c.clicked_at)) {
+            let day = click.clicked_at.date_naive();
+            rows.push((
+                Some(day),
+                RollupRow::Clicks {
+                    target_url: click.target_url.clone(),
+                    target_host: click.target_host.clone(),
+                    clicks: 1,
+                    unique_referrers: 0,
+                    last_clicked_at: click.clicked_at,
+                },
+            ));
+            if let Some(source_path) = &click.source_path {
+                rows.push((Some(day), RollupRow::Referrer { source_path: source_path.clone(), clicks: 1 }));
+            }
+            rows.push((
+                Some(day),
+                RollupRow::Country { source: COUNTRY_SOURCE_CLICKS, country_code: click.country_code.clone(), count: 1 },
+            ));
+            if let Some(hash) = &click.referrer_hash {
+                referrers.entry((day, click.target_url.clone())).or_default().insert(hash.clone());
+            }
+        }
+
+        for (_, event) in self.events
+            .events
+            .read()
+            .iter()
+            .filter(|(t, e)| t == tenant_id && e.kind == HONEYTOKEN_HIT && in_range(e.occurred_at))
+        {
+            rows.push((
+                Some(event.occurred_at.date_naive()),
+                RollupRow::Country { source: COUNTRY_SOURCE_ATTACKS, country_code: event.country_code.clone(), count: 1 },
+            ));
+        }
+
+        fold_rollups(rows)
+            .into_iter()
+            .filter_map(|(day, mut row)| {
+                let day = day?;
+                if let RollupRow::Clicks { target_url, unique_referrers, .. } = &mut row {
+                    *unique_referrers = referrers.get(&(day, target_url.clone())).map_or(0, |r| r.len() as i64);
+                }
+                Some((day, row))
+            })
+            .collect()
+    }
+
+    /// Rollup rows in the window plus its live raw rows, summed per key
+    fn window_rows(&self, tenant_id: &Uuid, window: &RollupWindow) -> Vec<RollupRow> {
+        let mut rows: Vec<(Option<NaiveDate>, RollupRow)> = self.rollups
+            .read()
+            .iter()
+            .filter(|r| {
+                r.tenant_id == *tenant_id
+                    && r.period == window.period
+                    && r.period_start >= window.from
+                    && r.period_start < window.to
+            })
+            .map(|r| (None, r.row.clone()))
+            .collect();
+        if let Some(live_from) = window.live_from {
+            rows.extend(self.raw_daily(tenant_id, live_from, None).into_iter().map(|(_, row)| (None, row)));
+        }
+
+        fold_rollups(rows).into_iter().map(|(_, row)| row).collect()
+    }
+}
+
+#[async_trait]
+impl StatsRollupRepository for InMemoryStatsRollupRepo {
+    async fn roll_up(&self, tenant_id: &Uuid, from: NaiveDate, to: NaiveDate) -> Result<u64, AppError> {
+        let daily = self.raw_daily(tenant_id, start_of_day(from), Some(start_of_day(to)));
+        let (first_month, end_month) = rollup_months(from, to);
+        let mut rollups = self.rollups.write();
+
+        let replaced: HashSet<(RollupSource, NaiveDate)> = daily.iter().map(|(day, row)| (row.source(), *day)).collect();
+        rollups.retain(|r| {
+            r.tenant_id != *tenant_id
+                || match r.period {
+                    ROLLUP_DAY => !replaced.contains(&(r.row.source(), r.period_start)),
+                    _ => r.period_start < first_month || r.period_start >= end_month,
+                }
+        });
+        let written = daily.len();
+        rollups.extend(daily.into_iter().map(|(period_start, row)| StoredRollup {
+            tenant_id: *tenant_id,
+            period: ROLLUP_DAY,
+            period_start,
+            row,
+        }));
+
+        let monthly = fold_rollups(
+            rollups
+                .iter()
+                .filter(|r| {
+                    r.tenant_id == *tenant_id
+                        && r.period == ROLLUP_DAY
+                        && r.period_start >= first_month
+                        && r.period_start < end_month
+                })
+                .map(|r| (Some(start_of_month(r.period_start)), r.row.clone()))
+                .collect::<Vec<_>>(),
+        );
+        let written = written + monthly.len();
+        rollups.extend(monthly.into_iter().filter_map(|(month, row)| {
+            Some(StoredRollup { tenant_id: *tenant_id, period: ROLLUP_MONTH, period_start: month?, row })
+        }));
+
+        Ok(written as u64)
+    }
+
+    async fn depth_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<ReadingDepthCount>, AppError> {
+        let posts = self.reading.users.posts.read();
+
+        Ok(self
+            .window_rows(tenant_id, window)
+            .into_iter()
+            .filter_map(|row| {
+                let RollupRow::Reads { post_id, max_percent, readers } = row else {
+                    return None;
+                };
+                let post = posts.get(&post_id).filter(|p| p.deleted_at.is_none())?;
+                Some(ReadingDepthCount { post_id, title: post.title.clone(), slug: post.slug.clone(), max_percent, readers })
+            })
+            .collect())
+    }
+
+    async fn click_summary(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<OutboundClickSummary>, AppError> {
+        let mut summary: Vec<OutboundClickSummary> = self
+            .window_rows(tenant_id, window)
+            .into_iter()
+            .filter_map(|row| match row {
+                RollupRow::Clicks { target_url, target_host, clicks, unique_referrers, last_clicked_at } => {
+                    Some(OutboundClickSummary { target_url, target_host, clicks, unique_referrers, last_clicked_at })
+                }
+                _ => None,
+            })
+            .collect();
+        summary.sort_by_key(|s| Reverse((s.clicks, s.last_clicked_at)));
+        summary.truncate(limit.max(0) as usize);
+
+        Ok(summary)
+    }
+
+    async fn count_clicks(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<i64, AppError> {
+        Ok(self
+            .window_rows(tenant_id, window)
+            .iter()
+            .map(|row| match row {
+                RollupRow::Clicks { clicks, .. } => *clicks,
+                _ => 0,
+            })
+            .sum())
+    }
+
+    async fn referring_pages(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<ReferringPage>, AppError> {
+        let mut pages: Vec<ReferringPage> = self
+            .window_rows(tenant_id, window)
+            .into_iter()
+            .filter_map(|row| match row {
+                RollupRow::Referrer { source_path, clicks } => Some(ReferringPage { source_path, clicks }),
+                _ => None,
+            })
+            .collect();
+        pages.sort_by(|a, b| b.clicks.cmp(&a.clicks).then_with(|| a.source_path.cmp(&b.source_path)));
+        pages.truncate(limit.max(0) as usize);
+
+        Ok(pages)
+    }
+
+    async fn country_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<CountryCount>, AppError> {
+        Ok(self
+            .window_rows(tenant_id, window)
+            .into_iter()
+            .filter_map(|row| match row {
+                RollupRow::Country { source, country_code, count } => {
+                    Some(CountryCount { source: source.to_string(), country_code, count })
+                }
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+// ───── Notification Preferences ──────────────────────────────────────
+
+/// Saved preferences, keyed by user
+#[derive(Clone)]
+struct StoredPreferences {
+    webhook_url: Option<String>,
+    channels: JsonValue,
+    updated_at: DateTime<Utc>,
+}
+
+/// Reads admins from the user repository it was built from
+#[derive(Clone, Default)]
+pub struct InMemoryNotificationPreferencesRepo {
+    users: InMemoryUserRepo,
+    preferences: Arc<RwLock<HashMap<Uuid, StoredPreferences>>>,
+}
+
+impl InMemoryNotificationPreferencesRepo {
+    pub fn new(users: InMemoryUserRepo) -> Self {
+        InMemoryNotificationPreferencesRepo { users, preferences: Arc::default() }
+    }
+
+    fn recipient(&self, user: &User) -> NotificationRecipient {
+        let stored = self.preferences.read().get(&user.id).cloned();
+
+        NotificationRecipient {
+            user_id: user.id,
+            email: user.email.clone(),
+            webhook_url: stored.as_ref().and_then(|p| p.webhook_url.clone()),
+            channels: stored.as_ref().map_or_else(|| serde_json::json!({}), |p| p.channels.clone()),
+            updated_at: stored.map(|p| p.updated_at),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for InMemoryNotificationPreferencesRepo {
+    async fn get_recipient(&self, tenant_id: &Uuid, user_id: &Uuid) -> Result<Option<NotificationRecipient>, AppError> {
+        let users = self.users.users.read();
+
+        Ok(users
+            .get(user_id)
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none())
+            .map(|u| self.recipient(u)))
+    }
+
+    async fn list_recipients(&self, tenant_id: &Uuid) -> Result<Vec<NotificationRecipient>, AppError> {
+        let users = self.users.users.read();
+        let mut admins: Vec<&User> = users
+            .values()
+            .filter(|u| u.tenant_id == *tenant_id && u.is_admin && !u.is_system && u.deleted_at.is_none())
+            .collect();
+        admins.sort_by_key(|u| u.created_at);
+
+        Ok(admins.into_iter().map(|u| self.recipient(u)).collect())
+    }
+
+    async fn save_preferences(
+        &self,
+        tenant_id: &Uuid,
+        user_id: &Uuid,
+        webhook_url: Option<String>,
+        channels: &JsonValue,
+    ) -> Result<NotificationRecipient, AppError> {
+        let users = self.users.users.read();
+        let user = users
+            .get(user_id)
+            .filter(|u| u.tenant_id == *tenant_id && u.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.preferences.write().insert(*user_id, StoredPreferences {
+            webhook_url,
+            channels: channels.clone(),
+            updated_at: Utc::now(),
+        });
+
+        Ok(self.recipient(user))
+    }
+}
+
+// ───── Redirect Rules ────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryRedirectRuleRepo {
+    rules: Arc<RwLock<HashMap<Uuid, RedirectRule>>>,
+}
+
+impl InMemoryRedirectRuleRepo {
+    fn source_taken(rules: &HashMap<Uuid, RedirectRule>, tenant_id: &Uuid, rule: &RedirectRuleInsert, exclude: Option<Uuid>) -> bool {
+        rules.values().any(|r| {
+            r.tenant_id == *tenant_id && r.kind == rule.kind && r.source == rule.source && Some(r.id) != exclude
+        })
+    }
+
+    fn sorted(&self, keep: impl Fn(&RedirectRule) -> bool) -> Vec<RedirectRule> {
+        let mut rules: Vec<RedirectRule> = self.rules.read().values().filter(|r| keep(r)).cloned().collect();
+        rules.sort_by_key(|r| (r.created_at, r.id));
+        rules
+    }
+}
+
+#[async_trait]
+impl RedirectRuleRepository for InMemoryRedirectRuleRepo {
+    async fn create_redirect_rule(&self, tenant_id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError> {
+        let mut rules = self.rules.write();
+        if Self::source_taken(&rules, tenant_id, rule, None) {
+            return Err(AppError::Conflict("A redirect rule for this source already exists".to_string()));
+        }
+
+        let now = Utc::now();
+        let created = RedirectRule {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            kind: rule.kind.clone(),
+            source: rule.source.clone(),
+            target: rule.target.clone(),
+            status_code: rule.status_code,
+            hits: 0,
+            last_hit_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        rules.insert(created.id, created.clone());
+
+        Ok(created)
+    }
+
+    async fn get_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<RedirectRule, AppError> {
+        self.rules
+            .read()
+            .get(id)
+            .filter(|r| r.tenant_id == *tenant_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Redirect rule not found".into()))
+    }
+
+    async fn list_redirect_rules(&self, tenant_id: &Uuid) -> Result<Vec<RedirectRule>, AppError> {
+        Ok(self.sorted(|r| r.tenant_id == *tenant_id))
+    }
+
+    async fn list_all_redirect_rules(&self) -> Result<Vec<RedirectRule>, AppError> {
+        Ok(self.sorted(|_| true))
+    }
+
+    async fn update_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError> {
+        let mut rules = self.rules.write();
+        if rules.get(id).is_none_or(|r| r.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Redirect rule not found".into()));
+        }
+        if Self::source_taken(&rules, tenant_id, rule, Some(*id)) {
+            return Err(AppError::Conflict("A redirect rule for this source already exists".to_string()));
+        }
+
+        let stored = rules.get_mut(id).expect("checked above");
+        stored.kind = rule.kind.clone();
+        stored.source = rule.source.clone();
+        stored.target = rule.target.clone();
+        stored.status_code = rule.status_code;
+        stored.updated_at = Utc::now();
+
+        Ok(stored.clone())
+    }
+
+    async fn delete_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let mut rules = self.rules.write();
+        if rules.get(id).is_none_or(|r| r.tenant_id != *tenant_id) {
+            return Err(AppError::NotFound("Redirect rule not found".into()));
+        }
+        rules.remove(id);
+        Ok(())
+    }
+
+    async fn record_redirect_hits(&self, hits: &[(Uuid, i64)], at: DateTime<Utc>) -> Result<(), AppError> {
+        let mut rules = self.rules.write();
+        for (id, count) in hits {
+            if let Some(rule) = rules.get_mut(id) {
+                rule.hits += count;
+                rule.last_hit_at = rule.last_hit_at.max(Some(at));
+            }
+        }
+        Ok(())
+    }
+}
+
+// ───── Schema ────────────────────────────────────────────────────────
+
+/// In-memory stores always have the shape of the latest schema
+#[derive(Clone, Default)]
+pub struct InMemorySchemaRepo;
+
+#[async_trait]
+impl SchemaRepository for InMemorySchemaRepo {
+    async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, AppError> {
+        Ok(bundled_migrations()
+            .into_iter()
+            .map(|m| AppliedMigration { version: m.version, success: true, checksum: m.checksum })
+            .collect())
+    }
+}
+
+// ───── Share Counts ──────────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+pub struct InMemoryShareCountRepo {
+    counts: Arc<RwLock<Vec<PostShareCount>>>,
+}
+
+#[async_trait]
+impl ShareCountRepository for InMemoryShareCountRepo {
+    async fn record_share_counts(
+        &self,
+        tenant_id: &Uuid,
+        counts: &[ShareCountInsert],
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut stored = self.counts.write();
+
+        for count in counts {
+            let row = PostShareCount {
+                tenant_id: *tenant_id,
+                post_id: count.post_id,
+                network: count.network.to_string(),
+                shares: count.shares,
+                fetched_at,
+            };
+            match stored.iter_mut().find(|c| c.post_id == count.post_id && c.network == count.network) {
+                Some(existing) => *existing = row,
+                None => stored.push(row),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_post_share_counts(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostShareCount>, AppError> {
+        let mut counts: Vec<PostShareCount> = self.counts
+            .read()
+            .iter()
+            .filter(|c| c.tenant_id == *tenant_id && c.post_id == *post_id)
+            .cloned()
+            .collect();
+        counts.sort_by(|a, b| a.network.cmp(&b.network));
+
+        Ok(counts)
+    }
+}
+
+// ───── Config History ────────────────────────────────────────────────
+
+/// Writes through to the settings and flag stores it was built with
+#[derive(Clone)]
+pub struct InMemoryConfigHistoryRepo {
+    settings: InMemoryAppSettingsRepo,
+    flags: InMemoryFeatureFlagRepo,
+    changes: Arc<RwLock<Vec<ConfigChange>>>,
+}
+
+impl InMemoryConfigHistoryRepo {
+    pub fn new(settings: InMemoryAppSettingsRepo, flags: InMemoryFeatureFlagRepo) -> Self {
+        InMemoryConfigHistoryRepo { settings, flags, changes: Arc::default() }
+    }
+
+    fn record(
+        &self,
+        tenant_id: Uuid,
+        kind: &str,
+        key: &str,
+        previous_value: Option<JsonValue>,
+        new_value: Option<JsonValue>,
+        source: &ConfigChangeSource,
+    ) -> ConfigChange {
+        let mut changes = self.changes.write();
+        let change = ConfigChange {
+            version: changes.len() as i64 + 1,
+            tenant_id,
+            kind: kind.to_string(),
+            key: key.to_string(),
+            previous_value,
+            new_value,
+            actor_id: Some(source.actor_id),
+            rollback_to: source.rollback_to,
+            changed_at: Utc::now(),
+        };
+        changes.push(change.clone());
+        change
+    }
+
+    fn visible(&self, tenant_id: &Uuid) -> Vec<ConfigChange> {
+        self.changes
+            .read()
+            .iter()
+            .filter(|c| c.tenant_id == *tenant_id)
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ConfigHistoryRepository for InMemoryConfigHistoryRepo {
+    async fn change_setting(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        value: Option<JsonValue>,
+        source: &ConfigChangeSource,
+    ) -> Result<ConfigChange, AppError> {
+        let previous = {
+            let mut settings = self.settings.settings.write();
+            let slot = (*tenant_id, key.to_string());
+            let previous = settings.remove(&slot).map(|s| s.value);
+            if let Some(value) = &value {
+                let setting = AppSetting { tenant_id: *tenant_id, key: key.to_string(), value: value.clone(), updated_at: Utc::now() };
+                settings.insert(slot, setting);
+            }
+            previous
+        };
+
+        Ok(self.record(*tenant_id, CONFIG_KIND_SETTING, key, previous, value, source))
+    }
+
+    async fn change_feature_flag(
+        &self,
+        tenant_id: &Uuid,
+        key: &str,
+        enabled: bool,
+        description: Option<String>,
+        source: &ConfigChangeSource,
+    ) -> Result<(FeatureFlag, ConfigChange), AppError> {
+        let previous = self.flags.flags.read().get(&(*tenant_id, key.to_string())).map(|f| f.enabled);
+        let flag = self.flags.upsert_feature_flag(tenant_id, key, enabled, description).await?;
+
+        let change = self.record(
+            *tenant_id,
+            CONFIG_KIND_FEATURE_FLAG,
+            key,
+            previous.map(JsonValue::Bool),
+            Some(JsonValue::Bool(enabled)),
+            source,
+        );
+        Ok((flag, change))
+    }
+
+    async fn list_changes(
+        &self,
+        tenant_id: &Uuid,
+        key: Option<String>,
+        before: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<ConfigChange>, AppError> {
+        Ok(self.visible(tenant_id)
+            .into_iter()
+            .rev()
+            .filter(|c| key.as_ref().is_none_or(|key| c.key == *key))
+            .filter(|c| before.is_none_or(|before| c.version < before))
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn get_change(&self, tenant_id: &Uuid, version: i64) -> Result<ConfigChange, AppError> {
+        self.visible(tenant_id)
+            .into_iter()
+            .find(|c| c.version == version)
+            .ok_or_else(|| AppError::NotFound(format!("Config version {} not found", version)))
+    }
+
+    async fn changes_after(&self, tenant_id: &Uuid, version: i64) -> Result<Vec<ConfigChange>, AppError> {
+        Ok(self.visible(tenant_id).into_iter().filter(|c| c.version > version).collect())
+    }
+}
+
+// ───── Backfills ────────────────────────────────────────────────────
+
+/// In-memory stores have no derived tables, so every task completes on its
+/// first batch without touching a row
+#[derive(Clone, Default)]
+pub struct InMemoryBackfillRepo {
+    states: Arc<RwLock<BTreeMap<String, BackfillState>>>,
+}
+
+#[async_trait]
+impl BackfillRepository for InMemoryBackfillRepo {
+    async fn list_states(&self) -> Result<Vec<BackfillState>, AppError> {
+        Ok(self.states.read().values().cloned().collect())
+    }
+
+    async fn run_batch(&self, task: &BackfillTask) -> Result<Option<BackfillState>, AppError> {
+        let now = Utc::now();
+        let state = self.states
+            .write()
+            .entry(task.name.to_string())
+            .or_insert_with(|| BackfillState {
+                name: task.name.to_string(),
+                status: BACKFILL_COMPLETED.to_string(),
+                checkpoint: None,
+                processed: 0,
+                total: Some(0),
+                last_error: None,
+                started_at: Some(now),
+                completed_at: Some(now),
+                updated_at: now,
+            })
+            .clone();
+
+        Ok(Some(state))
+    }
+
+    async fn record_failure(&self, name: &str, error: &str) -> Result<(), AppError> {
+        if let Some(state) = self.states.write().get_mut(name) {
+            state.last_error = Some(error.to_string());
+            state.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+}
+
+// ───── Usage ─────────────────────────────────────────────────────────
+
+type QuotaAlertKey = (Uuid, String, NaiveDate, i16);
+
+/// Nothing is stored on disk, so there are no tables to count
+#[derive(Clone, Default)]
+pub struct InMemoryUsageRepo {
+    email_sends: Arc<RwLock<HashMap<NaiveDate, (i64, i64)>>>,
+    reports: Arc<RwLock<HashMap<Uuid, UsageReport>>>,
+    /// Claimed alerts as `(tenant_id, quota, period, threshold)`
+    quota_alerts: Arc<RwLock<HashSet<QuotaAlertKey>>>,
+}
+
+#[async_trait]
+impl UsageRepository for InMemoryUsageRepo {
+    async fn table_usage(&self, _tenant_id: &Uuid) -> Result<Vec<TableUsage>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn database_bytes(&self) -> Result<i64, AppError> {
+        Ok(0)
+    }
+
+    async fn add_email_sends(&self, month: NaiveDate, sent: i64, failed: i64) -> Result<(), AppError> {
+        let mut sends = self.email_sends.write();
+        let counts = sends.entry(month).or_default();
+        counts.0 += sent;
+        counts.1 += failed;
+        Ok(())
+    }
+
+    async fn email_usage(&self, month: NaiveDate) -> Result<EmailUsage, AppError> {
+        let (sent, failed) = self.email_sends.read().get(&month).copied().unwrap_or_default();
+        Ok(EmailUsage { month, sent, failed })
+    }
+
+    async fn save_report(&self, report: &UsageReport) -> Result<(), AppError> {
+        self.reports.write().insert(report.tenant_id, report.clone());
+        Ok(())
+    }
+
+    async fn latest_report(&self, tenant_id: &Uuid) -> Result<Option<UsageReport>, AppError> {
+        Ok(self.reports.read().get(tenant_id).cloned())
+    }
+
+    async fn claim_quota_alerts(&self, tenant_id: &Uuid, quota: &str, period: NaiveDate, thresholds: &[i16]) -> Result<Vec<i16>, AppError> {
+        let mut alerts = self.quota_alerts.write();
+        Ok(thresholds
+            .iter()
+            .copied()
+            .filter(|&threshold| alerts.insert((*tenant_id, quota.to_string(), period, threshold)))
+            .collect())
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::link_check::{LinkCheck, LinkCheckInsert},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxLinkCheckRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait LinkCheckRepository: Send + Sync {
+    /// Stores one run's results as of `checked_at` and drops the tenant's
+    /// older rows, i.e. links that are no longer in the content
+    async fn record_link_checks(
+        &self,
+        tenant_id: &Uuid,
+        checks: &[LinkCheckInsert],
+        checked_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+    /// Broken links grouped by source, then by URL
+    async fn list_broken_links(&self, tenant_id: &Uuid) -> Result<Vec<LinkCheck>, AppError>;
+}
+
+#[async_trait]
+impl<T: LinkCheckRepository + ?Sized> LinkCheckRepository for Arc<T> {
+    async fn record_link_checks(
+        &self,
+        tenant_id: &Uuid,
+        checks: &[LinkCheckInsert],
+        checked_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        (**self).record_link_checks(tenant_id, checks, checked_at).await
+    }
+
+    async fn list_broken_links(&self, tenant_id: &Uuid) -> Result<Vec<LinkCheck>, AppError> {
+        (**self).list_broken_links(tenant_id).await
+    }
+}
+
+impl SqlxLinkCheckRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxLinkCheckRepo { pool }
+    }
+}
+
+#[async_trait]
+impl LinkCheckRepository for SqlxLinkCheckRepo {
+    async fn record_link_checks(
+        &self,
+        tenant_id: &Uuid,
+        checks: &[LinkCheckInsert],
+        checked_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        for check in checks {
+            sqlx::query!(
+                r#"
+                INSERT INTO link_checks (
+                    tenant_id, source_type, source_id, source_title, url,
+                    status_code, error, broken, broken_since, checked_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $8 THEN $9::TIMESTAMPTZ END, $9)
+                ON CONFLICT (tenant_id, source_type, source_id, url) DO UPDATE SET
+                    source_title = EXCLUDED.source_title,
+                    status_code = EXCLUDED.status_code,
+                    error = EXCLUDED.error,
+                    broken = EXCLUDED.broken,
+                    broken_since = CASE
+                        WHEN EXCLUDED.broken THEN COALESCE(link_checks.broken_since, EXCLUDED.checked_at)
+                    END,
+                    checked_at = EXCLUDED.checked_at
+                "#,
+                tenant_id,
+                check.source_type,
+                check.source_id,
+                check.source_title,
+                check.url,
+                check.status_code,
+                check.error,
+                check.broken,
+                checked_at,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            "DELETE FROM link_checks WHERE tenant_id = $1 AND checked_at < $2",
+            tenant_id,
+            checked_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn list_broken_links(&self, tenant_id: &Uuid) -> Result<Vec<LinkCheck>, AppError> {
+        let checks = sqlx::query_as!(
+            LinkCheck,
+            r#"
+            SELECT * FROM link_checks
+            WHERE tenant_id = $1 AND broken
+            ORDER BY source_type, source_title, source_id, url
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(checks)
+    }
+}
\ No newline at end of file
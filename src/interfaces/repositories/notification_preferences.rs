@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use sqlx::types::JsonValue;
+use uuid::Uuid;
+
+use crate::{
+    entities::notification::NotificationRecipient,
+    errors::AppError,
+    repositories::sqlx_repo::SqlxNotificationPreferencesRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait NotificationPreferencesRepository: Send + Sync {
+    /// The user with their preferences, `None` if there is no such user
+    async fn get_recipient(&self, tenant_id: &Uuid, user_id: &Uuid) -> Result<Option<NotificationRecipient>, AppError>;
+    /// Active admins of the tenant, including those who never saved preferences
+    async fn list_recipients(&self, tenant_id: &Uuid) -> Result<Vec<NotificationRecipient>, AppError>;
+    async fn save_preferences(
+        &self,
+        tenant_id: &Uuid,
+        user_id: &Uuid,
+        webhook_url: Option<String>,
+        channels: &JsonValue,
+    ) -> Result<NotificationRecipient, AppError>;
+}
+
+#[async_trait]
+impl<T: NotificationPreferencesRepository + ?Sized> NotificationPreferencesRepository for Arc<T> {
+    async fn get_recipient(&self, tenant_id: &Uuid, user_id: &Uuid) -> Result<Option<NotificationRecipient>, AppError> {
+        (**self).get_recipient(tenant_id, user_id).await
+    }
+
+    async fn list_recipients(&self, tenant_id: &Uuid) -> Result<Vec<NotificationRecipient>, AppError> {
+        (**self).list_recipients(tenant_id).await
+    }
+
+    async fn save_preferences(
+        &self,
+        tenant_id: &Uuid,
+        user_id: &Uuid,
+        webhook_url: Option<String>,
+        channels: &JsonValue,
+    ) -> Result<NotificationRecipient, AppError> {
+        (**self).save_preferences(tenant_id, user_id, webhook_url, channels).await
+    }
+}
+
+impl SqlxNotificationPreferencesRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxNotificationPreferencesRepo { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for SqlxNotificationPreferencesRepo {
+    async fn get_recipient(&self, tenant_id: &Uuid, user_id: &Uuid) -> Result<Option<NotificationRecipient>, AppError> {
+        let recipient = sqlx::query_as!(
+            NotificationRecipient,
+            r#"
+            SELECT
+                u.id AS user_id,
+                u.email,
+                p.webhook_url AS "webhook_url?",
+                COALESCE(p.channels, '{}'::jsonb) AS "channels!",
+                p.updated_at AS "updated_at?"
+            FROM users u
+            LEFT JOIN notification_preferences p ON p.user_id = u.id
+            WHERE u.tenant_id = $1 AND u.id = $2 AND u.deleted_at IS NULL
+            "#,
+            tenant_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(recipient)
+    }
+
+    async fn list_recipients(&self, tenant_id: &Uuid) -> Result<Vec<NotificationRecipient>, AppError> {
+        let recipients = sqlx::query_as!(
+            NotificationRecipient,
+            r#"
+            SELECT
+                u.id AS user_id,
+                u.email,
+                p.webhook_url AS "webhook_url?",
+                COALESCE(p.channels, '{}'::jsonb) AS "channels!",
+                p.updated_at AS "updated_at?"
+            FROM users u
+            LEFT JOIN notification_preferences p ON p.user_id = u.id
+            WHERE u.tenant_id = $1 AND u.is_admin AND NOT u.is_system AND u.deleted_at IS NULL
+            ORDER BY u.created_at
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recipients)
+    }
+
+    async fn save_preferences(
+        &self,
+        tenant_id: &Uuid,
+        user_id: &Uuid,
+        webhook_url: Option<String>,
+        channels: &JsonValue,
+    ) -> Result<NotificationRecipient, AppError> {
+        let recipient = sqlx::query_as!(
+            NotificationRecipient,
+            r#"
+            WITH saved AS (
+                INSERT INTO notification_preferences (user_id, tenant_id, webhook_url, channels, updated_at)
+                VALUES ($2, $1, $3, $4, NOW())
+                ON CONFLICT (user_id) DO UPDATE SET
+                    webhook_url = EXCLUDED.webhook_url,
+                    channels = EXCLUDED.channels,
+                    updated_at = EXCLUDED.updated_at
+                RETURNING *
+            )
+            SELECT
+                u.id AS user_id,
+                u.email,
+                saved.webhook_url,
+                saved.channels,
+                saved.updated_at AS "updated_at?"
+            FROM saved
+            JOIN users u ON u.id = saved.user_id
+            "#,
+            tenant_id,
+            user_id,
+            webhook_url,
+            channels
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(recipient)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::one_time_token::{OneTimeToken, OneTimeTokenInsert},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxOneTimeTokenRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait OneTimeTokenRepository: Send + Sync {
+    /// Stores the token and spends the user's earlier unused tokens for the
+    /// same purpose, so only the newest link works
+    async fn create_one_time_token(&self, tenant_id: &Uuid, token: &OneTimeTokenInsert) -> Result<OneTimeToken, AppError>;
+    async fn find_one_time_token(&self, tenant_id: &Uuid, token_hash: &str) -> Result<Option<OneTimeToken>, AppError>;
+    /// Marks an unused, unexpired token for `purpose` as used and returns
+    /// it; `None` when there is no such token or it was spent concurrently
+    async fn consume_one_time_token(
+        &self,
+        tenant_id: &Uuid,
+        purpose: &str,
+        token_hash: &str,
+    ) -> Result<Option<OneTimeToken>, AppError>;
+    /// Undoes `consume_one_time_token` after the change it guarded failed,
+    /// so the same link can be retried
+    async fn release_one_time_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    /// Deletes tokens that expired before `before`, used or not
+    async fn delete_expired_one_time_tokens(&self, before: DateTime<Utc>) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl<T: OneTimeTokenRepository + ?Sized> OneTimeTokenRepository for Arc<T> {
+    async fn create_one_time_token(&self, tenant_id: &Uuid, token: &OneTimeTokenInsert) -> Result<OneTimeToken, AppError> {
+        (**self).create_one_time_token(tenant_id, token).await
+    }
+
+    async fn find_one_time_token(&self, tenant_id: &Uuid, token_hash: &str) -> Result<Option<OneTimeToken>, AppError> {
+        (**self).find_one_time_token(tenant_id, token_hash).await
+    }
+
+    async fn consume_one_time_token(
+        &self,
+        tenant_id: &Uuid,
+        purpose: &str,
+        token_hash: &str,
+    ) -> Result<Option<OneTimeToken>, AppError> {
+        (**self).consume_one_time_token(tenant_id, purpose, token_hash).await
+    }
+
+    async fn release_one_time_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).release_one_time_token(tenant_id, id).await
+    }
+
+    async fn delete_expired_one_time_tokens(&self, before: DateTime<Utc>) -> Result<u64, AppError> {
+        (**self).delete_expired_one_time_tokens(before).await
+    }
+}
+
+impl SqlxOneTimeTokenRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxOneTimeTokenRepo { pool }
+    }
+}
+
+#[async_trait]
+impl OneTimeTokenRepository for SqlxOneTimeTokenRepo {
+    async fn create_one_time_token(&self, tenant_id: &Uuid, token: &OneTimeTokenInsert) -> Result<OneTimeToken, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE one_time_tokens SET used_at = NOW()
+            WHERE tenant_id = $1 AND user_id = $2 AND purpose = $3 AND used_at IS NULL
+            "#,
+            tenant_id,
+            token.user_id,
+            token.purpose
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let created = sqlx::query_as!(
+            OneTimeToken,
+            r#"
+            INSERT INTO one_time_tokens (tenant_id, user_id, purpose, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            tenant_id,
+            token.user_id,
+            token.purpose,
+            token.token_hash,
+            token.expires_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn find_one_time_token(&self, tenant_id: &Uuid, token_hash: &str) -> Result<Option<OneTimeToken>, AppError> {
+        let token = sqlx::query_as!(
+            OneTimeToken,
+            "SELECT * FROM one_time_tokens WHERE tenant_id = $1 AND token_hash = $2",
+            tenant_id,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn consume_one_time_token(
+        &self,
+        tenant_id: &Uuid,
+        purpose: &str,
+        token_hash: &str,
+    ) -> Result<Option<OneTimeToken>, AppError> {
+        let token = sqlx::query_as!(
+            OneTimeToken,
+            r#"
+            UPDATE one_time_tokens SET used_at = NOW()
+            WHERE tenant_id = $1 AND purpose = $2 AND token_hash = $3
+              AND used_at IS NULL AND expires_at > NOW()
+            RETURNING *
+            "#,
+            tenant_id,
+            purpose,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn release_one_time_token(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE one_time_tokens SET used_at = NULL WHERE tenant_id = $1 AND id = $2",
+            tenant_id,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired_one_time_tokens(&self, before: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query!("DELETE FROM one_time_tokens WHERE expires_at < $1", before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
\ No newline at end of file
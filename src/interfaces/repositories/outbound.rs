@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::outbound::OutboundClickInsert,
+    errors::AppError,
+    repositories::sqlx_repo::SqlxOutboundClickRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait OutboundClickRepository: Send + Sync {
+    async fn record_click(&self, tenant_id: &Uuid, click: &OutboundClickInsert) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: OutboundClickRepository + ?Sized> OutboundClickRepository for Arc<T> {
+    async fn record_click(&self, tenant_id: &Uuid, click: &OutboundClickInsert) -> Result<(), AppError> {
+        (**self).record_click(tenant_id, click).await
+    }
+}
+
+impl SqlxOutboundClickRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxOutboundClickRepo { pool }
+    }
+}
+
+#[async_trait]
+impl OutboundClickRepository for SqlxOutboundClickRepo {
+    async fn record_click(&self, tenant_id: &Uuid, click: &OutboundClickInsert) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO outbound_clicks (
+                tenant_id, target_url, target_host, source_path, referrer_hash, clicked_at, country_code, region
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            tenant_id,
+            click.target_url,
+            click.target_host,
+            click.source_path,
+            click.referrer_hash,
+            click.clicked_at,
+            click.country_code,
+            click.region,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,469 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use sqlx::{types::JsonValue, PgConnection};
+use uuid::Uuid;
+
+use crate::{
+    entities::outbox::{JobStatus, JobTypeCounts, OutboxEvent, PausedQueue},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxOutboxRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// Leases up to `limit` due events until `lease_until` and bumps their
+    /// attempt count. Leased events are invisible to other relays, and come
+    /// back on their own if the lease runs out before they are settled.
+    /// Events of paused queues are skipped.
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError>;
+    async fn mark_delivered(&self, id: &Uuid) -> Result<(), AppError>;
+    /// Records a failed attempt; `retry_at = None` parks the event as failed
+    async fn mark_failed(&self, id: &Uuid, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<(), AppError>;
+
+    /// Newest first; `status` is judged as of `now`
+    async fn list_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError>;
+    async fn count_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError>;
+    async fn get_job(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<OutboxEvent>, AppError>;
+    /// Makes an event that is not running or delivered due at `now`. Parked
+    /// events get their attempts back. `None` when the event was not in a
+    /// retryable state.
+    async fn retry_job(&self, tenant_id: &Uuid, id: &Uuid, now: DateTime<Utc>) -> Result<Option<OutboxEvent>, AppError>;
+    /// Parks a queued event as cancelled; `None` when it was not queued
+    async fn cancel_job(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        reason: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<OutboxEvent>, AppError>;
+    /// Per event type: open events as of `now`, settled ones since `since`
+    async fn job_counts(&self, tenant_id: &Uuid, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<Vec<JobTypeCounts>, AppError>;
+
+    async fn list_paused_queues(&self, tenant_id: &Uuid) -> Result<Vec<PausedQueue>, AppError>;
+    /// Pausing a paused queue keeps its original `paused_by` and `paused_at`
+    async fn pause_queue(&self, tenant_id: &Uuid, event_type: &str, paused_by: &str) -> Result<PausedQueue, AppError>;
+    /// `false` when the queue was not paused
+    async fn resume_queue(&self, tenant_id: &Uuid, event_type: &str) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+impl<T: OutboxRepository + ?Sized> OutboxRepository for Arc<T> {
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        (**self).claim_due(now, lease_until, limit).await
+    }
+
+    async fn mark_delivered(&self, id: &Uuid) -> Result<(), AppError> {
+        (**self).mark_delivered(id).await
+    }
+
+    async fn mark_failed(&self, id: &Uuid, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<(), AppError> {
+        (**self).mark_failed(id, error, retry_at).await
+    }
+
+    async fn list_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        (**self).list_jobs(tenant_id, status, event_type, now, limit, offset).await
+    }
+
+    async fn count_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        (**self).count_jobs(tenant_id, status, event_type, now).await
+    }
+
+    async fn get_job(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<OutboxEvent>, AppError> {
+        (**self).get_job(tenant_id, id).await
+    }
+
+    async fn retry_job(&self, tenant_id: &Uuid, id: &Uuid, now: DateTime<Utc>) -> Result<Option<OutboxEvent>, AppError> {
+        (**self).retry_job(tenant_id, id, now).await
+    }
+
+    async fn cancel_job(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        reason: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<OutboxEvent>, AppError> {
+        (**self).cancel_job(tenant_id, id, reason, now).await
+    }
+
+    async fn job_counts(&self, tenant_id: &Uuid, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<Vec<JobTypeCounts>, AppError> {
+        (**self).job_counts(tenant_id, since, now).await
+    }
+
+    async fn list_paused_queues(&self, tenant_id: &Uuid) -> Result<Vec<PausedQueue>, AppError> {
+        (**self).list_paused_queues(tenant_id).await
+    }
+
+    async fn pause_queue(&self, tenant_id: &Uuid, event_type: &str, paused_by: &str) -> Result<PausedQueue, AppError> {
+        (**self).pause_queue(tenant_id, event_type, paused_by).await
+    }
+
+    async fn resume_queue(&self, tenant_id: &Uuid, event_type: &str) -> Result<bool, AppError> {
+        (**self).resume_queue(tenant_id, event_type).await
+    }
+}
+
+/// Writes an outbox row on the caller's connection. Repositories call this
+/// inside the transaction that makes the domain change, so the event
+/// exists if and only if the change was committed.
+pub(crate) async fn enqueue_in_tx(
+    conn: &mut PgConnection,
+    tenant_id: &Uuid,
+    event_type: &str,
+    payload: &JsonValue,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"INSERT INTO outbox_events (tenant_id, event_type, payload) VALUES ($1, $2, $3)"#,
+        tenant_id,
+        event_type,
+        payload,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+impl SqlxOutboxRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxOutboxRepo { pool }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for SqlxOutboxRepo {
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        // SKIP LOCKED lets several instances relay concurrently without
+        // handing the same event to two of them
+        let events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            UPDATE outbox_events
+            SET next_attempt_at = $2, leased_until = $2, attempts = attempts + 1
+            WHERE id IN (
+                SELECT id FROM outbox_events e
+                WHERE delivered_at IS NULL AND failed_at IS NULL AND next_attempt_at <= $1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM outbox_paused_queues p
+                      WHERE p.tenant_id = e.tenant_id AND p.event_type = e.event_type
+                  )
+                ORDER BY next_attempt_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+            now,
+            lease_until,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    async fn mark_delivered(&self, id: &Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"UPDATE outbox_events SET delivered_at = NOW(), last_error = NULL, leased_until = NULL WHERE id = $1"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: &Uuid, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET last_error = $2,
+                next_attempt_at = COALESCE($3, next_attempt_at),
+                leased_until = NULL,
+                failed_at = CASE WHEN $3::timestamptz IS NULL THEN NOW() ELSE NULL END
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            retry_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+    async fn list_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        let events = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            SELECT * FROM outbox_events
+            WHERE tenant_id = $1
+              AND ($2::text IS NULL OR event_type = $2)
+              AND ($3::text IS NULL OR $3 = CASE
+                  WHEN cancelled_at IS NOT NULL THEN 'cancelled'
+                  WHEN delivered_at IS NOT NULL THEN 'delivered'
+                  WHEN failed_at IS NOT NULL THEN 'failed'
+                  WHEN leased_until > $4 THEN 'running'
+                  ELSE 'queued'
+              END)
+            ORDER BY created_at DESC, id
+            LIMIT $5 OFFSET $6
+            "#,
+            tenant_id,
+            event_type,
+            status.map(|s| s.as_str()),
+            now,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    async fn count_jobs(
+        &self,
+        tenant_id: &Uuid,
+        status: Option<JobStatus>,
+        event_type: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!" FROM outbox_events
+            WHERE tenant_id = $1
+              AND ($2::text IS NULL OR event_type = $2)
+              AND ($3::text IS NULL OR $3 = CASE
+                  WHEN cancelled_at IS NOT NULL THEN 'cancelled'
+                  WHEN delivered_at IS NOT NULL THEN 'delivered'
+                  WHEN failed_at IS NOT NULL THEN 'failed'
+                  WHEN leased_until > $4 THEN 'running'
+                  ELSE 'queued'
+              END)
+            "#,
+            tenant_id,
+            event_type,
+            status.map(|s| s.as_str()),
+            now
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn get_job(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<OutboxEvent>, AppError> {
+        let event = sqlx::query_as!(
+            OutboxEvent,
+            r#"SELECT * FROM outbox_events WHERE tenant_id = $1 AND id = $2"#,
+            tenant_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(event)
+    }
+
+    async fn retry_job(&self, tenant_id: &Uuid, id: &Uuid, now: DateTime<Utc>) -> Result<Option<OutboxEvent>, AppError> {
+        let event = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            UPDATE outbox_events
+            SET attempts = CASE WHEN failed_at IS NULL THEN attempts ELSE 0 END,
+                next_attempt_at = $3,
+                leased_until = NULL,
+                failed_at = NULL,
+                cancelled_at = NULL
+            WHERE tenant_id = $1 AND id = $2
+              AND delivered_at IS NULL
+              AND (leased_until IS NULL OR leased_until <= $3 OR failed_at IS NOT NULL)
+            RETURNING *
+            "#,
+            tenant_id,
+            id,
+            now
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(event)
+    }
+
+    async fn cancel_job(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        reason: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<OutboxEvent>, AppError> {
+        let event = sqlx::query_as!(
+            OutboxEvent,
+            r#"
+            UPDATE outbox_events
+            SET cancelled_at = $4, failed_at = $4, last_error = $3, leased_until = NULL
+            WHERE tenant_id = $1 AND id = $2
+              AND delivered_at IS NULL AND failed_at IS NULL
+              AND (leased_until IS NULL OR leased_until <= $4)
+            RETURNING *
+            "#,
+            tenant_id,
+            id,
+            reason,
+            now
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(event)
+    }
+
+    async fn job_counts(&self, tenant_id: &Uuid, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<Vec<JobTypeCounts>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                event_type,
+                COUNT(*) FILTER (WHERE failed_at IS NULL AND delivered_at IS NULL
+                    AND (leased_until IS NULL OR leased_until <= $3)) AS "queued!",
+                COUNT(*) FILTER (WHERE failed_at IS NULL AND delivered_at IS NULL
+                    AND (leased_until IS NULL OR leased_until <= $3) AND attempts > 0) AS "retrying!",
+                COUNT(*) FILTER (WHERE failed_at IS NULL AND delivered_at IS NULL AND leased_until > $3) AS "running!",
+                COUNT(*) FILTER (WHERE delivered_at IS NOT NULL) AS "delivered!",
+                COUNT(*) FILTER (WHERE failed_at IS NOT NULL AND cancelled_at IS NULL) AS "failed!",
+                COUNT(*) FILTER (WHERE cancelled_at IS NOT NULL) AS "cancelled!",
+                COALESCE(SUM(attempts) FILTER (WHERE delivered_at IS NOT NULL), 0) AS "delivered_attempts!",
+                MIN(created_at) FILTER (WHERE failed_at IS NULL AND delivered_at IS NULL
+                    AND (leased_until IS NULL OR leased_until <= $3)) AS oldest_queued_at
+            FROM outbox_events
+            WHERE tenant_id = $1
+              AND (delivered_at >= $2 OR failed_at >= $2 OR (delivered_at IS NULL AND failed_at IS NULL))
+            GROUP BY event_type
+            ORDER BY event_type
+            "#,
+            tenant_id,
+            since,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| JobTypeCounts {
+                event_type: row.event_type,
+                queued: row.queued,
+                retrying: row.retrying,
+                running: row.running,
+                delivered: row.delivered,
+                failed: row.failed,
+                cancelled: row.cancelled,
+                delivered_attempts: row.delivered_attempts,
+                oldest_queued_at: row.oldest_queued_at,
+            })
+            .collect())
+    }
+
+    async fn list_paused_queues(&self, tenant_id: &Uuid) -> Result<Vec<PausedQueue>, AppError> {
+        let queues = sqlx::query_as!(
+            PausedQueue,
+            r#"
+            SELECT event_type, paused_by, paused_at FROM outbox_paused_queues
+            WHERE tenant_id = $1
+            ORDER BY event_type
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(queues)
+    }
+
+    async fn pause_queue(&self, tenant_id: &Uuid, event_type: &str, paused_by: &str) -> Result<PausedQueue, AppError> {
+        let queue = sqlx::query_as!(
+            PausedQueue,
+            r#"
+            INSERT INTO outbox_paused_queues (tenant_id, event_type, paused_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (tenant_id, event_type) DO UPDATE SET paused_by = outbox_paused_queues.paused_by
+            RETURNING event_type, paused_by, paused_at
+            "#,
+            tenant_id,
+            event_type,
+            paused_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(queue)
+    }
+
+    async fn resume_queue(&self, tenant_id: &Uuid, event_type: &str) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"DELETE FROM outbox_paused_queues WHERE tenant_id = $1 AND event_type = $2"#,
+            tenant_id,
+            event_type
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::reading::ReadingProgressUpdate,
+    errors::AppError,
+    repositories::sqlx_repo::SqlxReadingProgressRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait ReadingProgressRepository: Send + Sync {
+    /// Raises the session's furthest point per post, never lowering it.
+    /// Updates for posts that are not published in the tenant are skipped;
+    /// returns how many were kept.
+    async fn record_progress(
+        &self,
+        tenant_id: &Uuid,
+        session_hash: &str,
+        updates: &[ReadingProgressUpdate],
+    ) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl<T: ReadingProgressRepository + ?Sized> ReadingProgressRepository for Arc<T> {
+    async fn record_progress(
+        &self,
+        tenant_id: &Uuid,
+        session_hash: &str,
+        updates: &[ReadingProgressUpdate],
+    ) -> Result<u64, AppError> {
+        (**self).record_progress(tenant_id, session_hash, updates).await
+    }
+}
+
+impl SqlxReadingProgressRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxReadingProgressRepo { pool }
+    }
+}
+
+#[async_trait]
+impl ReadingProgressRepository for SqlxReadingProgressRepo {
+    async fn record_progress(
+        &self,
+        tenant_id: &Uuid,
+        session_hash: &str,
+        updates: &[ReadingProgressUpdate],
+    ) -> Result<u64, AppError> {
+        let post_ids: Vec<Uuid> = updates.iter().map(|u| u.post_id).collect();
+        let percents: Vec<i16> = updates.iter().map(|u| u.percent).collect();
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO reading_progress (post_id, session_hash, tenant_id, max_percent)
+            SELECT p.id, $2, $1, u.percent
+            FROM UNNEST($3::UUID[], $4::SMALLINT[]) AS u(post_id, percent)
+            JOIN blog_posts p ON p.id = u.post_id
+            WHERE p.tenant_id = $1 AND p.published AND p.deleted_at IS NULL
+            ON CONFLICT (post_id, session_hash) DO UPDATE SET
+                max_percent = GREATEST(reading_progress.max_percent, EXCLUDED.max_percent),
+                updated_at = NOW()
+            "#,
+            tenant_id,
+            session_hash,
+            &post_ids,
+            &percents,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+use std::{borrow::Cow, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::redirect_rule::{RedirectRule, RedirectRuleInsert},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxRedirectRuleRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait RedirectRuleRepository: Send + Sync {
+    async fn create_redirect_rule(&self, tenant_id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError>;
+    async fn get_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<RedirectRule, AppError>;
+    /// Oldest first, the order pattern rules are tried in
+    async fn list_redirect_rules(&self, tenant_id: &Uuid) -> Result<Vec<RedirectRule>, AppError>;
+    /// Rules of every tenant, oldest first
+    async fn list_all_redirect_rules(&self) -> Result<Vec<RedirectRule>, AppError>;
+    async fn update_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError>;
+    async fn delete_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    /// Adds counted hits per rule; rules deleted in the meantime are skipped
+    async fn record_redirect_hits(&self, hits: &[(Uuid, i64)], at: DateTime<Utc>) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: RedirectRuleRepository + ?Sized> RedirectRuleRepository for Arc<T> {
+    async fn create_redirect_rule(&self, tenant_id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError> {
+        (**self).create_redirect_rule(tenant_id, rule).await
+    }
+
+    async fn get_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<RedirectRule, AppError> {
+        (**self).get_redirect_rule(tenant_id, id).await
+    }
+
+    async fn list_redirect_rules(&self, tenant_id: &Uuid) -> Result<Vec<RedirectRule>, AppError> {
+        (**self).list_redirect_rules(tenant_id).await
+    }
+
+    async fn list_all_redirect_rules(&self) -> Result<Vec<RedirectRule>, AppError> {
+        (**self).list_all_redirect_rules().await
+    }
+
+    async fn update_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError> {
+        (**self).update_redirect_rule(tenant_id, id, rule).await
+    }
+
+    async fn delete_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).delete_redirect_rule(tenant_id, id).await
+    }
+
+    async fn record_redirect_hits(&self, hits: &[(Uuid, i64)], at: DateTime<Utc>) -> Result<(), AppError> {
+        (**self).record_redirect_hits(hits, at).await
+    }
+}
+
+impl SqlxRedirectRuleRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxRedirectRuleRepo { pool }
+    }
+}
+
+fn map_source_conflict(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(db_err) if db_err.code() == Some(Cow::Borrowed("23505")) => {
+            AppError::Conflict("A redirect rule for this source already exists".to_string())
+        }
+        _ => AppError::from(e),
+    }
+}
+
+#[async_trait]
+impl RedirectRuleRepository for SqlxRedirectRuleRepo {
+    async fn create_redirect_rule(&self, tenant_id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError> {
+        sqlx::query_as!(
+            RedirectRule,
+            r#"
+            INSERT INTO redirect_rules (tenant_id, kind, source, target, status_code)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            tenant_id,
+            rule.kind,
+            rule.source,
+            rule.target,
+            rule.status_code
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_source_conflict)
+    }
+
+    async fn get_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<RedirectRule, AppError> {
+        sqlx::query_as!(
+            RedirectRule,
+            r#"SELECT * FROM redirect_rules WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Redirect rule not found".into()))
+    }
+
+    async fn list_redirect_rules(&self, tenant_id: &Uuid) -> Result<Vec<RedirectRule>, AppError> {
+        let rules = sqlx::query_as!(
+            RedirectRule,
+            r#"SELECT * FROM redirect_rules WHERE tenant_id = $1 ORDER BY created_at, id"#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    async fn list_all_redirect_rules(&self) -> Result<Vec<RedirectRule>, AppError> {
+        let rules = sqlx::query_as!(
+            RedirectRule,
+            r#"SELECT * FROM redirect_rules ORDER BY created_at, id"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    async fn update_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid, rule: &RedirectRuleInsert) -> Result<RedirectRule, AppError> {
+        sqlx::query_as!(
+            RedirectRule,
+            r#"
+            UPDATE redirect_rules SET
+                kind = $1,
+                source = $2,
+                target = $3,
+                status_code = $4,
+                updated_at = NOW()
+            WHERE id = $5 AND tenant_id = $6
+            RETURNING *
+            "#,
+            rule.kind,
+            rule.source,
+            rule.target,
+            rule.status_code,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_source_conflict)?
+        .ok_or_else(|| AppError::NotFound("Redirect rule not found".into()))
+    }
+
+    async fn delete_redirect_rule(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"DELETE FROM redirect_rules WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Redirect rule not found".into()));
+        }
+
+        Ok(())
+    }
+
+    async fn record_redirect_hits(&self, hits: &[(Uuid, i64)], at: DateTime<Utc>) -> Result<(), AppError> {
+        let (ids, counts): (Vec<Uuid>, Vec<i64>) = hits.iter().copied().unzip();
+
+        sqlx::query!(
+            r#"
+            UPDATE redirect_rules r SET
+                hits = r.hits + h.count,
+                last_hit_at = GREATEST(r.last_hit_at, $3)
+            FROM UNNEST($1::uuid[], $2::bigint[]) AS h(id, count)
+            WHERE r.id = h.id
+            "#,
+            &ids,
+            &counts,
+            at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,318 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::retention::{RetentionAction, RetentionEntity},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxRetentionRepo,
+};
+
+/// Placeholders written over the name and email of anonymized contact messages
+pub const ANONYMIZED: &str = "[anonymized]";
+pub const ANONYMIZED_EMAIL: &str = "anonymized@invalid";
+
+#[automock]
+#[async_trait]
+pub trait RetentionRepository: Send + Sync {
+    /// Rows of `entity` created before `cutoff` that `action` would still change
+    async fn count_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, AppError>;
+    /// Purges or anonymizes up to `limit` expired rows, returning how many changed
+    async fn apply_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, AppError>;
+    /// Puts back the name and email of an anonymized contact message;
+    /// false when the message has none stashed, e.g. after the purge
+    async fn restore_contact_identity(&self, tenant_id: &Uuid, message_id: &Uuid) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+impl<T: RetentionRepository + ?Sized> RetentionRepository for Arc<T> {
+    async fn count_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        (**self).count_expired(tenant_id, entity, action, cutoff).await
+    }
+
+    async fn apply_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, AppError> {
+        (**self).apply_expired(tenant_id, entity, action, cutoff, limit).await
+    }
+
+    async fn restore_contact_identity(&self, tenant_id: &Uuid, message_id: &Uuid) -> Result<bool, AppError> {
+        (**self).restore_contact_identity(tenant_id, message_id).await
+    }
+}
+
+pub fn unsupported_action(entity: RetentionEntity, action: RetentionAction) -> AppError {
+    AppError::InvalidInput(format!("{:?} cannot be applied to {}", action, entity.as_str()))
+}
+
+impl SqlxRetentionRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxRetentionRepo { pool }
+    }
+}
+
+#[async_trait]
+impl RetentionRepository for SqlxRetentionRepo {
+    async fn count_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let count = match (entity, action) {
+            (RetentionEntity::ContactMessages, RetentionAction::Purge) => sqlx::query_scalar!(
+                r#"SELECT COUNT(*) AS "count!" FROM contact_me_messages WHERE tenant_id = $1 AND created_at < $2"#,
+                tenant_id,
+                cutoff
+            )
+            .fetch_one(&self.pool)
+            .await?,
+            (RetentionEntity::ContactMessages, RetentionAction::Anonymize) => sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) AS "count!" FROM contact_me_messages
+                WHERE tenant_id = $1 AND created_at < $2 AND anonymized_at IS NULL
+                "#,
+                tenant_id,
+                cutoff
+            )
+            .fetch_one(&self.pool)
+            .await?,
+            (RetentionEntity::AnonymizedIdentities, RetentionAction::Purge) => sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) AS "count!" FROM contact_message_identities
+                WHERE tenant_id = $1 AND anonymized_at < $2
+                "#,
+                tenant_id,
+                cutoff
+            )
+            .fetch_one(&self.pool)
+            .await?,
+            (RetentionEntity::Analytics, RetentionAction::Purge) => sqlx::query_scalar!(
+                r#"
+                SELECT
+                    (SELECT COUNT(*) FROM outbound_clicks WHERE tenant_id = $1 AND clicked_at < $2)
+                    + (SELECT COUNT(*) FROM reading_progress WHERE tenant_id = $1 AND updated_at < $2)
+                    + (SELECT COUNT(*) FROM title_variant_stats WHERE tenant_id = $1 AND day < ($2 AT TIME ZONE 'UTC')::DATE)
+                    AS "count!"
+                "#,
+                tenant_id,
+                cutoff
+            )
+            .fetch_one(&self.pool)
+            .await?,
+            (RetentionEntity::SecurityEvents, RetentionAction::Purge) => sqlx::query_scalar!(
+                r#"SELECT COUNT(*) AS "count!" FROM security_events WHERE tenant_id = $1 AND occurred_at < $2"#,
+                tenant_id,
+                cutoff
+            )
+            .fetch_one(&self.pool)
+            .await?,
+            (RetentionEntity::Audit, RetentionAction::Purge | RetentionAction::Archive) => sqlx::query_scalar!(
+                r#"SELECT COUNT(*) AS "count!" FROM audit_log WHERE tenant_id = $1 AND created_at < $2"#,
+                tenant_id,
+                cutoff
+            )
+            .fetch_one(&self.pool)
+            .await?,
+            _ => return Err(unsupported_action(entity, action)),
+        };
+
+        Ok(count)
+    }
+
+    async fn apply_expired(
+        &self,
+        tenant_id: &Uuid,
+        entity: RetentionEntity,
+        action: RetentionAction,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, AppError> {
+        let result = match (entity, action) {
+            (RetentionEntity::ContactMessages, RetentionAction::Purge) => sqlx::query!(
+                r#"
+                DELETE FROM contact_me_messages WHERE id IN (
+                    SELECT id FROM contact_me_messages
+                    WHERE tenant_id = $1 AND created_at < $2
+                    LIMIT $3
+                )
+                "#,
+                tenant_id,
+                cutoff,
+                limit
+            )
+            .execute(&self.pool)
+            .await?,
+            (RetentionEntity::ContactMessages, RetentionAction::Anonymize) => sqlx::query!(
+                r#"
+                WITH expired AS (
+                    SELECT id, name, email FROM contact_me_messages
+                    WHERE tenant_id = $1 AND created_at < $2 AND anonymized_at IS NULL
+                    LIMIT $3
+                    FOR UPDATE
+                ),
+                stashed AS (
+                    INSERT INTO contact_message_identities (message_id, tenant_id, name, email)
+                    SELECT id, $1, name, email FROM expired
+                    ON CONFLICT (message_id) DO NOTHING
+                )
+                UPDATE contact_me_messages m
+                SET name = $4, email = $5, anonymized_at = NOW()
+                FROM expired
+                WHERE m.id = expired.id
+                "#,
+                tenant_id,
+                cutoff,
+                limit,
+                ANONYMIZED,
+                ANONYMIZED_EMAIL
+            )
+            .execute(&self.pool)
+            .await?,
+            (RetentionEntity::AnonymizedIdentities, RetentionAction::Purge) => sqlx::query!(
+                r#"
+                DELETE FROM contact_message_identities WHERE message_id IN (
+                    SELECT message_id FROM contact_message_identities
+                    WHERE tenant_id = $1 AND anonymized_at < $2
+                    LIMIT $3
+                )
+                "#,
+                tenant_id,
+                cutoff,
+                limit
+            )
+            .execute(&self.pool)
+            .await?,
+            (RetentionEntity::Analytics, RetentionAction::Purge) => {
+                let clicks = sqlx::query!(
+                    r#"
+                    DELETE FROM outbound_clicks WHERE id IN (
+                        SELECT id FROM outbound_clicks
+                        WHERE tenant_id = $1 AND clicked_at < $2
+                        LIMIT $3
+                    )
+                    "#,
+                    tenant_id,
+                    cutoff,
+                    limit
+                )
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+                let reading = sqlx::query!(
+                    r#"
+                    DELETE FROM reading_progress WHERE (post_id, session_hash) IN (
+                        SELECT post_id, session_hash FROM reading_progress
+                        WHERE tenant_id = $1 AND updated_at < $2
+                        LIMIT $3
+                    )
+                    "#,
+                    tenant_id,
+                    cutoff,
+                    limit - clicks as i64
+                )
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+                let titles = sqlx::query!(
+                    r#"
+                    DELETE FROM title_variant_stats WHERE (post_id, variant, day) IN (
+                        SELECT post_id, variant, day FROM title_variant_stats
+                        WHERE tenant_id = $1 AND day < $2
+                        LIMIT $3
+                    )
+                    "#,
+                    tenant_id,
+                    cutoff.date_naive(),
+                    limit - (clicks + reading) as i64
+                )
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+
+                return Ok(clicks + reading + titles);
+            }
+            (RetentionEntity::SecurityEvents, RetentionAction::Purge) => sqlx::query!(
+                r#"
+                DELETE FROM security_events WHERE id IN (
+                    SELECT id FROM security_events
+                    WHERE tenant_id = $1 AND occurred_at < $2
+                    LIMIT $3
+                )
+                "#,
+                tenant_id,
+                cutoff,
+                limit
+            )
+            .execute(&self.pool)
+            .await?,
+            (RetentionEntity::Audit, RetentionAction::Purge) => sqlx::query!(
+                r#"
+                DELETE FROM audit_log WHERE id IN (
+                    SELECT id FROM audit_log
+                    WHERE tenant_id = $1 AND created_at < $2
+                    LIMIT $3
+                )
+                "#,
+                tenant_id,
+                cutoff,
+                limit
+            )
+            .execute(&self.pool)
+            .await?,
+            _ => return Err(unsupported_action(entity, action)),
+        };
+
+        Ok(result.rows_affected())
+    }
+
+    async fn restore_contact_identity(&self, tenant_id: &Uuid, message_id: &Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            WITH restored AS (
+                DELETE FROM contact_message_identities
+                WHERE tenant_id = $1 AND message_id = $2
+                RETURNING message_id, name, email
+            )
+            UPDATE contact_me_messages m
+            SET name = restored.name, email = restored.email, anonymized_at = NULL
+            FROM restored
+            WHERE m.id = restored.message_id
+            "#,
+            tenant_id,
+            message_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::{
+    entities::schema::AppliedMigration,
+    errors::AppError,
+    repositories::sqlx_repo::SqlxSchemaRepo,
+};
+
+/// Postgres code for an undefined table
+const UNDEFINED_TABLE: &str = "42P01";
+
+#[automock]
+#[async_trait]
+pub trait SchemaRepository: Send + Sync {
+    /// Every row of the migration history; empty when nothing was ever applied
+    async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, AppError>;
+}
+
+#[async_trait]
+impl<T: SchemaRepository + ?Sized> SchemaRepository for Arc<T> {
+    async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, AppError> {
+        (**self).applied_migrations().await
+    }
+}
+
+impl SqlxSchemaRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxSchemaRepo { pool }
+    }
+}
+
+#[async_trait]
+impl SchemaRepository for SqlxSchemaRepo {
+    async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, AppError> {
+        // Not a checked query: the table only exists once a migration has run
+        let rows = sqlx::query_as::<_, AppliedMigration>(
+            "SELECT version, success, checksum FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await;
+
+        match rows {
+            Ok(rows) => Ok(rows),
+            Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some(UNDEFINED_TABLE) => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
\ No newline at end of file
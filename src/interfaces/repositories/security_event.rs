@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::security_event::{SecurityEventInsert, SecurityEventIpSummary, SecurityEventPathSummary},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxSecurityEventRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait SecurityEventRepository: Send + Sync {
+    async fn record_event(&self, tenant_id: &Uuid, event: &SecurityEventInsert) -> Result<(), AppError>;
+    /// Busiest source IPs first
+    async fn ip_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventIpSummary>, AppError>;
+    /// Most probed paths first
+    async fn path_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventPathSummary>, AppError>;
+    /// `(total events, distinct IPs)` since `since`
+    async fn event_totals(&self, tenant_id: &Uuid, kind: String, since: DateTime<Utc>) -> Result<(i64, i64), AppError>;
+}
+
+#[async_trait]
+impl<T: SecurityEventRepository + ?Sized> SecurityEventRepository for Arc<T> {
+    async fn record_event(&self, tenant_id: &Uuid, event: &SecurityEventInsert) -> Result<(), AppError> {
+        (**self).record_event(tenant_id, event).await
+    }
+
+    async fn ip_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventIpSummary>, AppError> {
+        (**self).ip_summary(tenant_id, kind, since, limit).await
+    }
+
+    async fn path_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventPathSummary>, AppError> {
+        (**self).path_summary(tenant_id, kind, since, limit).await
+    }
+
+    async fn event_totals(&self, tenant_id: &Uuid, kind: String, since: DateTime<Utc>) -> Result<(i64, i64), AppError> {
+        (**self).event_totals(tenant_id, kind, since).await
+    }
+}
+
+impl SqlxSecurityEventRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxSecurityEventRepo { pool }
+    }
+}
+
+#[async_trait]
+impl SecurityEventRepository for SqlxSecurityEventRepo {
+    async fn record_event(&self, tenant_id: &Uuid, event: &SecurityEventInsert) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO security_events (tenant_id, kind, ip, user_agent, method, path, occurred_at, country_code, region, details)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            tenant_id,
+            event.kind,
+            event.ip,
+            event.user_agent,
+            event.method,
+            event.path,
+            event.occurred_at,
+            event.country_code,
+            event.region,
+            event.details,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn ip_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventIpSummary>, AppError> {
+        let summary = sqlx::query_as!(
+            SecurityEventIpSummary,
+            r#"
+            SELECT
+                ip,
+                COUNT(*) AS "hits!",
+                ARRAY_AGG(DISTINCT path ORDER BY path) AS "paths!",
+                MIN(occurred_at) AS "first_seen!",
+                MAX(occurred_at) AS "last_seen!"
+            FROM security_events
+            WHERE tenant_id = $1 AND kind = $2 AND occurred_at >= $3
+            GROUP BY ip
+            ORDER BY COUNT(*) DESC, MAX(occurred_at) DESC
+            LIMIT $4
+            "#,
+            tenant_id,
+            kind,
+            since,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
+    async fn path_summary(
+        &self,
+        tenant_id: &Uuid,
+        kind: String,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEventPathSummary>, AppError> {
+        let summary = sqlx::query_as!(
+            SecurityEventPathSummary,
+            r#"
+            SELECT path, COUNT(*) AS "hits!"
+            FROM security_events
+            WHERE tenant_id = $1 AND kind = $2 AND occurred_at >= $3
+            GROUP BY path
+            ORDER BY COUNT(*) DESC, path
+            LIMIT $4
+            "#,
+            tenant_id,
+            kind,
+            since,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
+    async fn event_totals(&self, tenant_id: &Uuid, kind: String, since: DateTime<Utc>) -> Result<(i64, i64), AppError> {
+        let totals = sqlx::query!(
+            r#"
+            SELECT COUNT(*) AS "total!", COUNT(DISTINCT ip) AS "unique_ips!"
+            FROM security_events
+            WHERE tenant_id = $1 AND kind = $2 AND occurred_at >= $3
+            "#,
+            tenant_id,
+            kind,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((totals.total, totals.unique_ips))
+    }
+}
\ No newline at end of file
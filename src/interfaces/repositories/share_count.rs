@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::share_count::{PostShareCount, ShareCountInsert},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxShareCountRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait ShareCountRepository: Send + Sync {
+    /// Replaces the stored count for each post and network in `counts`.
+    /// Networks missing from `counts` keep what they had.
+    async fn record_share_counts(
+        &self,
+        tenant_id: &Uuid,
+        counts: &[ShareCountInsert],
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+    /// The post's counts ordered by network
+    async fn get_post_share_counts(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostShareCount>, AppError>;
+}
+
+#[async_trait]
+impl<T: ShareCountRepository + ?Sized> ShareCountRepository for Arc<T> {
+    async fn record_share_counts(
+        &self,
+        tenant_id: &Uuid,
+        counts: &[ShareCountInsert],
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        (**self).record_share_counts(tenant_id, counts, fetched_at).await
+    }
+
+    async fn get_post_share_counts(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostShareCount>, AppError> {
+        (**self).get_post_share_counts(tenant_id, post_id).await
+    }
+}
+
+impl SqlxShareCountRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxShareCountRepo { pool }
+    }
+}
+
+#[async_trait]
+impl ShareCountRepository for SqlxShareCountRepo {
+    async fn record_share_counts(
+        &self,
+        tenant_id: &Uuid,
+        counts: &[ShareCountInsert],
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        for count in counts {
+            // The join keeps a post deleted mid-run from failing the whole batch
+            sqlx::query!(
+                r#"
+                INSERT INTO post_share_counts (tenant_id, post_id, network, shares, fetched_at)
+                SELECT $1, id, $3, $4, $5 FROM blog_posts WHERE id = $2 AND tenant_id = $1
+                ON CONFLICT (post_id, network) DO UPDATE SET
+                    shares = EXCLUDED.shares,
+                    fetched_at = EXCLUDED.fetched_at
+                "#,
+                tenant_id,
+                count.post_id,
+                count.network,
+                count.shares,
+                fetched_at,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_post_share_counts(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<PostShareCount>, AppError> {
+        let counts = sqlx::query_as!(
+            PostShareCount,
+            r#"
+            SELECT tenant_id, post_id, network, shares, fetched_at
+            FROM post_share_counts
+            WHERE tenant_id = $1 AND post_id = $2
+            ORDER BY network
+            "#,
+            tenant_id,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+}
@@ -18,4 +18,128 @@ pub struct SqlxBlogPostRepo {
 #[derive(Clone)]
 pub struct SqlxContactMeRepo {
     pub pool: PgPool,
-}
\ No newline at end of file
+}
+#[derive(Clone)]
+pub struct SqlxFeatureFlagRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxAppSettingsRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxTenantRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxHireInquiryRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxUsesRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxChangelogRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxOutboundClickRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxSecurityEventRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxOutboxRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxLinkCheckRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxAuditLogRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxRetentionRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxNotificationPreferencesRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxReadingProgressRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxTitleTestRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxApiTokenRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxStatsRollupRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxOneTimeTokenRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxBookmarkRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxRedirectRuleRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxSchemaRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxShareCountRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxConfigHistoryRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxBackfillRepo {
+    pub pool: PgPool,
+}
+
+#[derive(Clone)]
+pub struct SqlxUsageRepo {
+    pub pool: PgPool,
+}
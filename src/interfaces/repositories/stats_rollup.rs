@@ -0,0 +1,591 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Days, Months, NaiveDate};
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        geo::CountryCount,
+        outbound::{OutboundClickSummary, ReferringPage},
+        reading::ReadingDepthCount,
+        security_event::HONEYTOKEN_HIT,
+        stats_rollup::{
+            start_of_day, start_of_month, RollupWindow, COUNTRY_SOURCE_ATTACKS, COUNTRY_SOURCE_CLICKS, ROLLUP_DAY,
+            ROLLUP_MONTH,
+        },
+    },
+    errors::AppError,
+    repositories::sqlx_repo::SqlxStatsRollupRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait StatsRollupRepository: Send + Sync {
+    /// Recomputes the daily rollups of the days in `from..to` that still have
+    /// raw rows, then the monthly rollups of the months those days fall in.
+    /// Returns the rollup rows written.
+    async fn roll_up(&self, tenant_id: &Uuid, from: NaiveDate, to: NaiveDate) -> Result<u64, AppError>;
+    /// Readers per post and furthest depth
+    async fn depth_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<ReadingDepthCount>, AppError>;
+    /// Clicks per outbound link, most clicked first
+    async fn click_summary(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<OutboundClickSummary>, AppError>;
+    async fn count_clicks(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<i64, AppError>;
+    /// Outbound clicks per page of ours, most first
+    async fn referring_pages(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<ReferringPage>, AppError>;
+    /// Outbound clicks and honeytoken hits per country
+    async fn country_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<CountryCount>, AppError>;
+}
+
+#[async_trait]
+impl<T: StatsRollupRepository + ?Sized> StatsRollupRepository for Arc<T> {
+    async fn roll_up(&self, tenant_id: &Uuid, from: NaiveDate, to: NaiveDate) -> Result<u64, AppError> {
+        (**self).roll_up(tenant_id, from, to).await
+    }
+
+    async fn depth_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<ReadingDepthCount>, AppError> {
+        (**self).depth_counts(tenant_id, window).await
+    }
+
+    async fn click_summary(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<OutboundClickSummary>, AppError> {
+        (**self).click_summary(tenant_id, window, limit).await
+    }
+
+    async fn count_clicks(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<i64, AppError> {
+        (**self).count_clicks(tenant_id, window).await
+    }
+
+    async fn referring_pages(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<ReferringPage>, AppError> {
+        (**self).referring_pages(tenant_id, window, limit).await
+    }
+
+    async fn country_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<CountryCount>, AppError> {
+        (**self).country_counts(tenant_id, window).await
+    }
+}
+
+/// First day of every month `from..to` touches, and the first day after the last one
+pub fn rollup_months(from: NaiveDate, to: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let last_day = to - Days::new(1);
+    (start_of_month(from), start_of_month(last_day.max(from)) + Months::new(1))
+}
+
+impl SqlxStatsRollupRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxStatsRollupRepo { pool }
+    }
+}
+
+#[async_trait]
+impl StatsRollupRepository for SqlxStatsRollupRepo {
+    async fn roll_up(&self, tenant_id: &Uuid, from: NaiveDate, to: NaiveDate) -> Result<u64, AppError> {
+        let (from_at, to_at) = (start_of_day(from), start_of_day(to));
+        let (first_month, end_month) = rollup_months(from, to);
+        let mut tx = self.pool.begin().await?;
+        let mut rows = 0;
+
+        // Daily rows, replaced only for days that still have raw rows
+
+        sqlx::query!(
+            r#"
+            DELETE FROM post_read_rollups
+            WHERE tenant_id = $1 AND period = $2 AND period_start IN (
+                SELECT DISTINCT (started_at AT TIME ZONE 'UTC')::DATE
+                FROM reading_progress
+                WHERE tenant_id = $1 AND started_at >= $3 AND started_at < $4
+            )
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO post_read_rollups (tenant_id, period, period_start, post_id, max_percent, readers)
+            SELECT $1, $2, (started_at AT TIME ZONE 'UTC')::DATE, post_id, max_percent, COUNT(*)
+            FROM reading_progress
+            WHERE tenant_id = $1 AND started_at >= $3 AND started_at < $4
+            GROUP BY (started_at AT TIME ZONE 'UTC')::DATE, post_id, max_percent
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM outbound_click_rollups
+            WHERE tenant_id = $1 AND period = $2 AND period_start IN (
+                SELECT DISTINCT (clicked_at AT TIME ZONE 'UTC')::DATE
+                FROM outbound_clicks
+                WHERE tenant_id = $1 AND clicked_at >= $3 AND clicked_at < $4
+            )
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO outbound_click_rollups (
+                tenant_id, period, period_start, target_url, target_host, clicks, unique_referrers, last_clicked_at
+            )
+            SELECT
+                $1, $2, (clicked_at AT TIME ZONE 'UTC')::DATE, target_url, MIN(target_host),
+                COUNT(*), COUNT(DISTINCT referrer_hash), MAX(clicked_at)
+            FROM outbound_clicks
+            WHERE tenant_id = $1 AND clicked_at >= $3 AND clicked_at < $4
+            GROUP BY (clicked_at AT TIME ZONE 'UTC')::DATE, target_url
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM referrer_rollups
+            WHERE tenant_id = $1 AND period = $2 AND period_start IN (
+                SELECT DISTINCT (clicked_at AT TIME ZONE 'UTC')::DATE
+                FROM outbound_clicks
+                WHERE tenant_id = $1 AND clicked_at >= $3 AND clicked_at < $4
+            )
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO referrer_rollups (tenant_id, period, period_start, source_path, clicks)
+            SELECT $1, $2, (clicked_at AT TIME ZONE 'UTC')::DATE, source_path, COUNT(*)
+            FROM outbound_clicks
+            WHERE tenant_id = $1 AND clicked_at >= $3 AND clicked_at < $4 AND source_path IS NOT NULL
+            GROUP BY (clicked_at AT TIME ZONE 'UTC')::DATE, source_path
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM country_rollups
+            WHERE tenant_id = $1 AND period = $2 AND source = $5 AND period_start IN (
+                SELECT DISTINCT (clicked_at AT TIME ZONE 'UTC')::DATE
+                FROM outbound_clicks
+                WHERE tenant_id = $1 AND clicked_at >= $3 AND clicked_at < $4
+            )
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at,
+            COUNTRY_SOURCE_CLICKS
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO country_rollups (tenant_id, period, period_start, source, country_code, count)
+            SELECT $1, $2, (clicked_at AT TIME ZONE 'UTC')::DATE, $5, country_code, COUNT(*)
+            FROM outbound_clicks
+            WHERE tenant_id = $1 AND clicked_at >= $3 AND clicked_at < $4
+            GROUP BY (clicked_at AT TIME ZONE 'UTC')::DATE, country_code
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at,
+            COUNTRY_SOURCE_CLICKS
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM country_rollups
+            WHERE tenant_id = $1 AND period = $2 AND source = $5 AND period_start IN (
+                SELECT DISTINCT (occurred_at AT TIME ZONE 'UTC')::DATE
+                FROM security_events
+                WHERE tenant_id = $1 AND kind = $6 AND occurred_at >= $3 AND occurred_at < $4
+            )
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at,
+            COUNTRY_SOURCE_ATTACKS,
+            HONEYTOKEN_HIT
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO country_rollups (tenant_id, period, period_start, source, country_code, count)
+            SELECT $1, $2, (occurred_at AT TIME ZONE 'UTC')::DATE, $5, country_code, COUNT(*)
+            FROM security_events
+            WHERE tenant_id = $1 AND kind = $6 AND occurred_at >= $3 AND occurred_at < $4
+            GROUP BY (occurred_at AT TIME ZONE 'UTC')::DATE, country_code
+            "#,
+            tenant_id,
+            ROLLUP_DAY,
+            from_at,
+            to_at,
+            COUNTRY_SOURCE_ATTACKS,
+            HONEYTOKEN_HIT
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        // Monthly rows, summed from every daily row of the months touched
+
+        sqlx::query!(
+            "DELETE FROM post_read_rollups WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4",
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO post_read_rollups (tenant_id, period, period_start, post_id, max_percent, readers)
+            SELECT $1, $2, DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, post_id, max_percent, SUM(readers)
+            FROM post_read_rollups
+            WHERE tenant_id = $1 AND period = $5 AND period_start >= $3 AND period_start < $4
+            GROUP BY DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, post_id, max_percent
+            "#,
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month,
+            ROLLUP_DAY
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            "DELETE FROM outbound_click_rollups WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4",
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO outbound_click_rollups (
+                tenant_id, period, period_start, target_url, target_host, clicks, unique_referrers, last_clicked_at
+            )
+            SELECT
+                $1, $2, DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, target_url, MIN(target_host),
+                SUM(clicks), SUM(unique_referrers), MAX(last_clicked_at)
+            FROM outbound_click_rollups
+            WHERE tenant_id = $1 AND period = $5 AND period_start >= $3 AND period_start < $4
+            GROUP BY DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, target_url
+            "#,
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month,
+            ROLLUP_DAY
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            "DELETE FROM referrer_rollups WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4",
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO referrer_rollups (tenant_id, period, period_start, source_path, clicks)
+            SELECT $1, $2, DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, source_path, SUM(clicks)
+            FROM referrer_rollups
+            WHERE tenant_id = $1 AND period = $5 AND period_start >= $3 AND period_start < $4
+            GROUP BY DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, source_path
+            "#,
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month,
+            ROLLUP_DAY
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            "DELETE FROM country_rollups WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4",
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        rows += sqlx::query!(
+            r#"
+            INSERT INTO country_rollups (tenant_id, period, period_start, source, country_code, count)
+            SELECT $1, $2, DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, source, country_code, SUM(count)
+            FROM country_rollups
+            WHERE tenant_id = $1 AND period = $5 AND period_start >= $3 AND period_start < $4
+            GROUP BY DATE_TRUNC('month', period_start::TIMESTAMP)::DATE, source, country_code
+            "#,
+            tenant_id,
+            ROLLUP_MONTH,
+            first_month,
+            end_month,
+            ROLLUP_DAY
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+
+        Ok(rows)
+    }
+
+    async fn depth_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<ReadingDepthCount>, AppError> {
+        let counts = sqlx::query_as!(
+            ReadingDepthCount,
+            r#"
+            WITH counts AS (
+                SELECT post_id, max_percent, readers
+                FROM post_read_rollups
+                WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4
+                UNION ALL
+                SELECT post_id, max_percent, COUNT(*)
+                FROM reading_progress
+                WHERE tenant_id = $1 AND started_at >= $5
+                GROUP BY post_id, max_percent
+            )
+            SELECT
+                c.post_id AS "post_id!",
+                p.title,
+                p.slug,
+                c.max_percent AS "max_percent!",
+                SUM(c.readers)::BIGINT AS "readers!"
+            FROM counts c
+            JOIN blog_posts p ON p.id = c.post_id
+            WHERE p.deleted_at IS NULL
+            GROUP BY c.post_id, p.title, p.slug, c.max_percent
+            "#,
+            tenant_id,
+            window.period,
+            window.from,
+            window.to,
+            window.live_from
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    async fn click_summary(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<OutboundClickSummary>, AppError> {
+        let summary = sqlx::query_as!(
+            OutboundClickSummary,
+            r#"
+            WITH clicks AS (
+                SELECT target_url, target_host, clicks, unique_referrers, last_clicked_at
+                FROM outbound_click_rollups
+                WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4
+                UNION ALL
+                SELECT target_url, MIN(target_host), COUNT(*), COUNT(DISTINCT referrer_hash), MAX(clicked_at)
+                FROM outbound_clicks
+                WHERE tenant_id = $1 AND clicked_at >= $5
+                GROUP BY target_url
+            )
+            SELECT
+                target_url AS "target_url!",
+                MIN(target_host) AS "target_host!",
+                SUM(clicks)::BIGINT AS "clicks!",
+                SUM(unique_referrers)::BIGINT AS "unique_referrers!",
+                MAX(last_clicked_at) AS "last_clicked_at!"
+            FROM clicks
+            GROUP BY target_url
+            ORDER BY SUM(clicks) DESC, MAX(last_clicked_at) DESC
+            LIMIT $6
+            "#,
+            tenant_id,
+            window.period,
+            window.from,
+            window.to,
+            window.live_from,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
+    async fn count_clicks(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT (
+                COALESCE((
+                    SELECT SUM(clicks) FROM outbound_click_rollups
+                    WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4
+                ), 0)
+                + (SELECT COUNT(*) FROM outbound_clicks WHERE tenant_id = $1 AND clicked_at >= $5)
+            )::BIGINT AS "count!"
+            "#,
+            tenant_id,
+            window.period,
+            window.from,
+            window.to,
+            window.live_from
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn referring_pages(
+        &self,
+        tenant_id: &Uuid,
+        window: &RollupWindow,
+        limit: i64,
+    ) -> Result<Vec<ReferringPage>, AppError> {
+        let pages = sqlx::query_as!(
+            ReferringPage,
+            r#"
+            WITH pages AS (
+                SELECT source_path, clicks
+                FROM referrer_rollups
+                WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4
+                UNION ALL
+                SELECT source_path, COUNT(*)
+                FROM outbound_clicks
+                WHERE tenant_id = $1 AND clicked_at >= $5 AND source_path IS NOT NULL
+                GROUP BY source_path
+            )
+            SELECT source_path AS "source_path!", SUM(clicks)::BIGINT AS "clicks!"
+            FROM pages
+            GROUP BY source_path
+            ORDER BY SUM(clicks) DESC, source_path
+            LIMIT $6
+            "#,
+            tenant_id,
+            window.period,
+            window.from,
+            window.to,
+            window.live_from,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(pages)
+    }
+
+    async fn country_counts(&self, tenant_id: &Uuid, window: &RollupWindow) -> Result<Vec<CountryCount>, AppError> {
+        let counts = sqlx::query_as!(
+            CountryCount,
+            r#"
+            WITH counts AS (
+                SELECT source, country_code, count
+                FROM country_rollups
+                WHERE tenant_id = $1 AND period = $2 AND period_start >= $3 AND period_start < $4
+                UNION ALL
+                SELECT $6, country_code, COUNT(*)
+                FROM outbound_clicks
+                WHERE tenant_id = $1 AND clicked_at >= $5
+                GROUP BY country_code
+                UNION ALL
+                SELECT $7, country_code, COUNT(*)
+                FROM security_events
+                WHERE tenant_id = $1 AND kind = $8 AND occurred_at >= $5
+                GROUP BY country_code
+            )
+            SELECT source AS "source!", country_code, SUM(count)::BIGINT AS "count!"
+            FROM counts
+            GROUP BY source, country_code
+            "#,
+            tenant_id,
+            window.period,
+            window.from,
+            window.to,
+            window.live_from,
+            COUNTRY_SOURCE_CLICKS,
+            COUNTRY_SOURCE_ATTACKS,
+            HONEYTOKEN_HIT
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use std::borrow::Cow;
+use uuid::Uuid;
+
+use crate::{
+    entities::tenant::{Tenant, TenantDomain},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxTenantRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait TenantRepository: Send + Sync {
+    /// Every tenant together with its verified host names
+    async fn list_tenants(&self) -> Result<Vec<Tenant>, AppError>;
+    async fn list_domains(&self, tenant_id: &Uuid) -> Result<Vec<TenantDomain>, AppError>;
+    async fn get_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<Option<TenantDomain>, AppError>;
+    async fn create_domain(&self, tenant_id: &Uuid, hostname: &str, verification_token: &str) -> Result<TenantDomain, AppError>;
+    async fn delete_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<(), AppError>;
+    /// Unverified domains across all tenants, least recently checked first
+    async fn list_pending_domains(&self, limit: i64) -> Result<Vec<TenantDomain>, AppError>;
+    /// Stores the outcome of a DNS check; a domain stays verified once verified
+    async fn record_domain_check(&self, hostname: &str, verified: bool, error: Option<String>) -> Result<TenantDomain, AppError>;
+}
+
+#[async_trait]
+impl<T: TenantRepository + ?Sized> TenantRepository for Arc<T> {
+    async fn list_tenants(&self) -> Result<Vec<Tenant>, AppError> {
+        (**self).list_tenants().await
+    }
+
+    async fn list_domains(&self, tenant_id: &Uuid) -> Result<Vec<TenantDomain>, AppError> {
+        (**self).list_domains(tenant_id).await
+    }
+
+    async fn get_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<Option<TenantDomain>, AppError> {
+        (**self).get_domain(tenant_id, hostname).await
+    }
+
+    async fn create_domain(&self, tenant_id: &Uuid, hostname: &str, verification_token: &str) -> Result<TenantDomain, AppError> {
+        (**self).create_domain(tenant_id, hostname, verification_token).await
+    }
+
+    async fn delete_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<(), AppError> {
+        (**self).delete_domain(tenant_id, hostname).await
+    }
+
+    async fn list_pending_domains(&self, limit: i64) -> Result<Vec<TenantDomain>, AppError> {
+        (**self).list_pending_domains(limit).await
+    }
+
+    async fn record_domain_check(&self, hostname: &str, verified: bool, error: Option<String>) -> Result<TenantDomain, AppError> {
+        (**self).record_domain_check(hostname, verified, error).await
+    }
+}
+
+impl SqlxTenantRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxTenantRepo { pool }
+    }
+}
+
+#[async_trait]
+impl TenantRepository for SqlxTenantRepo {
+    async fn list_tenants(&self) -> Result<Vec<Tenant>, AppError> {
+        let tenants = sqlx::query_as::<_, Tenant>(
+            r#"
+            SELECT
+                t.id,
+                t.slug,
+                t.name,
+                COALESCE(
+                    ARRAY_AGG(d.hostname ORDER BY d.hostname) FILTER (WHERE d.hostname IS NOT NULL),
+                    '{}'
+                ) AS hostnames,
+                t.created_at
+            FROM tenants t
+            LEFT JOIN tenant_domains d ON d.tenant_id = t.id AND d.verified_at IS NOT NULL
+            GROUP BY t.id
+            ORDER BY t.slug
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tenants)
+    }
+
+    async fn list_domains(&self, tenant_id: &Uuid) -> Result<Vec<TenantDomain>, AppError> {
+        let domains = sqlx::query_as::<_, TenantDomain>(
+            "SELECT * FROM tenant_domains WHERE tenant_id = $1 ORDER BY hostname"
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(domains)
+    }
+
+    async fn get_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<Option<TenantDomain>, AppError> {
+        let domain = sqlx::query_as::<_, TenantDomain>(
+            "SELECT * FROM tenant_domains WHERE tenant_id = $1 AND hostname = $2"
+        )
+        .bind(tenant_id)
+        .bind(hostname)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(domain)
+    }
+
+    async fn create_domain(&self, tenant_id: &Uuid, hostname: &str, verification_token: &str) -> Result<TenantDomain, AppError> {
+        sqlx::query_as::<_, TenantDomain>(
+            r#"
+            INSERT INTO tenant_domains (hostname, tenant_id, verification_token)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#
+        )
+        .bind(hostname)
+        .bind(tenant_id)
+        .bind(verification_token)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.code() == Some(Cow::Borrowed("23505")) => {
+                AppError::Conflict("Domain is already registered".to_string())
+            }
+            _ => AppError::from(e),
+        })
+    }
+
+    async fn delete_domain(&self, tenant_id: &Uuid, hostname: &str) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM tenant_domains WHERE tenant_id = $1 AND hostname = $2")
+            .bind(tenant_id)
+            .bind(hostname)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Domain not found".into()));
+        }
+
+        Ok(())
+    }
+
+    async fn list_pending_domains(&self, limit: i64) -> Result<Vec<TenantDomain>, AppError> {
+        let domains = sqlx::query_as::<_, TenantDomain>(
+            r#"
+            SELECT * FROM tenant_domains
+            WHERE verified_at IS NULL
+            ORDER BY last_checked_at ASC NULLS FIRST, created_at ASC
+            LIMIT $1
+            "#
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(domains)
+    }
+
+    async fn record_domain_check(&self, hostname: &str, verified: bool, error: Option<String>) -> Result<TenantDomain, AppError> {
+        sqlx::query_as::<_, TenantDomain>(
+            r#"
+            UPDATE tenant_domains SET
+                last_checked_at = NOW(),
+                last_error = $3,
+                verified_at = CASE WHEN $2 THEN COALESCE(verified_at, NOW()) ELSE verified_at END
+            WHERE hostname = $1
+            RETURNING *
+            "#
+        )
+        .bind(hostname)
+        .bind(verified)
+        .bind(error)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Domain not found".into()))
+    }
+}
\ No newline at end of file
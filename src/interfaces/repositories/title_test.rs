@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::title_test::{TitleImpression, TitleVariant, TitleVariantInsert, TitleVariantStats},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxTitleTestRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait TitleTestRepository: Send + Sync {
+    /// Every tenant's running tests, for the serving cache
+    async fn list_all_title_variants(&self) -> Result<Vec<TitleVariant>, AppError>;
+    /// The post's variants in variant order; empty when no test is running
+    async fn get_title_variants(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariant>, AppError>;
+    /// Replaces the post's test, dropping the old one's numbers
+    async fn replace_title_test(
+        &self,
+        tenant_id: &Uuid,
+        post_id: &Uuid,
+        variants: &[TitleVariantInsert],
+    ) -> Result<Vec<TitleVariant>, AppError>;
+    /// Ends the post's test along with its numbers; false when none was running
+    async fn delete_title_test(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<bool, AppError>;
+    /// Counts impressions on `day`, skipping variants that no longer exist
+    async fn record_title_impressions(
+        &self,
+        tenant_id: &Uuid,
+        impressions: &[TitleImpression],
+        day: NaiveDate,
+    ) -> Result<u64, AppError>;
+    /// Counts a click on `day`; false when the variant does not exist
+    async fn record_title_click(&self, tenant_id: &Uuid, click: &TitleImpression, day: NaiveDate) -> Result<bool, AppError>;
+    /// Impressions and clicks per variant since the test started
+    async fn title_variant_stats(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariantStats>, AppError>;
+}
+
+#[async_trait]
+impl<T: TitleTestRepository + ?Sized> TitleTestRepository for Arc<T> {
+    async fn list_all_title_variants(&self) -> Result<Vec<TitleVariant>, AppError> {
+        (**self).list_all_title_variants().await
+    }
+
+    async fn get_title_variants(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariant>, AppError> {
+        (**self).get_title_variants(tenant_id, post_id).await
+    }
+
+    async fn replace_title_test(
+        &self,
+        tenant_id: &Uuid,
+        post_id: &Uuid,
+        variants: &[TitleVariantInsert],
+    ) -> Result<Vec<TitleVariant>, AppError> {
+        (**self).replace_title_test(tenant_id, post_id, variants).await
+    }
+
+    async fn delete_title_test(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<bool, AppError> {
+        (**self).delete_title_test(tenant_id, post_id).await
+    }
+
+    async fn record_title_impressions(
+        &self,
+        tenant_id: &Uuid,
+        impressions: &[TitleImpression],
+        day: NaiveDate,
+    ) -> Result<u64, AppError> {
+        (**self).record_title_impressions(tenant_id, impressions, day).await
+    }
+
+    async fn record_title_click(&self, tenant_id: &Uuid, click: &TitleImpression, day: NaiveDate) -> Result<bool, AppError> {
+        (**self).record_title_click(tenant_id, click, day).await
+    }
+
+    async fn title_variant_stats(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariantStats>, AppError> {
+        (**self).title_variant_stats(tenant_id, post_id).await
+    }
+}
+
+impl SqlxTitleTestRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxTitleTestRepo { pool }
+    }
+}
+
+#[async_trait]
+impl TitleTestRepository for SqlxTitleTestRepo {
+    async fn list_all_title_variants(&self) -> Result<Vec<TitleVariant>, AppError> {
+        let variants = sqlx::query_as!(
+            TitleVariant,
+            "SELECT * FROM post_title_variants ORDER BY tenant_id, post_id, variant"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(variants)
+    }
+
+    async fn get_title_variants(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariant>, AppError> {
+        let variants = sqlx::query_as!(
+            TitleVariant,
+            "SELECT * FROM post_title_variants WHERE tenant_id = $1 AND post_id = $2 ORDER BY variant",
+            tenant_id,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(variants)
+    }
+
+    async fn replace_title_test(
+        &self,
+        tenant_id: &Uuid,
+        post_id: &Uuid,
+        variants: &[TitleVariantInsert],
+    ) -> Result<Vec<TitleVariant>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Stats rows go with their variants
+        sqlx::query!(
+            "DELETE FROM post_title_variants WHERE tenant_id = $1 AND post_id = $2",
+            tenant_id,
+            post_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let mut stored = Vec::with_capacity(variants.len());
+        for variant in variants {
+            let row = sqlx::query_as!(
+                TitleVariant,
+                r#"
+                INSERT INTO post_title_variants (post_id, variant, tenant_id, title, weight)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING *
+                "#,
+                post_id,
+                variant.variant,
+                tenant_id,
+                variant.title,
+                variant.weight,
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            stored.push(row);
+        }
+
+        tx.commit().await?;
+
+        Ok(stored)
+    }
+
+    async fn delete_title_test(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM post_title_variants WHERE tenant_id = $1 AND post_id = $2",
+            tenant_id,
+            post_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_title_impressions(
+        &self,
+        tenant_id: &Uuid,
+        impressions: &[TitleImpression],
+        day: NaiveDate,
+    ) -> Result<u64, AppError> {
+        let post_ids: Vec<Uuid> = impressions.iter().map(|i| i.post_id).collect();
+        let variants: Vec<i16> = impressions.iter().map(|i| i.variant).collect();
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO title_variant_stats (post_id, variant, day, tenant_id, impressions)
+            SELECT v.post_id, v.variant, $4, $1, COUNT(*)
+            FROM UNNEST($2::UUID[], $3::SMALLINT[]) AS i(post_id, variant)
+            JOIN post_title_variants v ON v.post_id = i.post_id AND v.variant = i.variant
+            WHERE v.tenant_id = $1
+            GROUP BY v.post_id, v.variant
+            ON CONFLICT (post_id, variant, day) DO UPDATE SET
+                impressions = title_variant_stats.impressions + EXCLUDED.impressions
+            "#,
+            tenant_id,
+            &post_ids,
+            &variants,
+            day,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn record_title_click(&self, tenant_id: &Uuid, click: &TitleImpression, day: NaiveDate) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO title_variant_stats (post_id, variant, day, tenant_id, clicks)
+            SELECT post_id, variant, $4, $1, 1
+            FROM post_title_variants
+            WHERE tenant_id = $1 AND post_id = $2 AND variant = $3
+            ON CONFLICT (post_id, variant, day) DO UPDATE SET
+                clicks = title_variant_stats.clicks + 1
+            "#,
+            tenant_id,
+            click.post_id,
+            click.variant,
+            day,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn title_variant_stats(&self, tenant_id: &Uuid, post_id: &Uuid) -> Result<Vec<TitleVariantStats>, AppError> {
+        let stats = sqlx::query_as!(
+            TitleVariantStats,
+            r#"
+            SELECT variant, SUM(impressions)::BIGINT AS "impressions!", SUM(clicks)::BIGINT AS "clicks!"
+            FROM title_variant_stats
+            WHERE tenant_id = $1 AND post_id = $2
+            GROUP BY variant
+            ORDER BY variant
+            "#,
+            tenant_id,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stats)
+    }
+}
\ No newline at end of file
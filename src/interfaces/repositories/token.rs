@@ -1,15 +1,17 @@
 use async_trait::async_trait;
+use mockall::automock;
 use jsonwebtoken::TokenData;
 use uuid::Uuid;
 use crate::{entities::{token::{Claims, RefreshClaims}, user::User}, errors::AuthError, AppState};
 
+#[automock]
 #[async_trait]
 pub trait TokenServiceRepository: Send + Sync {
-    /// Creates a new JWT for the user
-    fn create_jwt(&self, user: &User) -> Result<String, AuthError>;
+    /// Creates a new JWT for the user at their current claims version
+    fn create_jwt(&self, user: &User, claims_version: u64) -> Result<String, AuthError>;
 
-    /// Creates a new refresh JWT for the user
-    fn create_refresh_jwt(&self, user_id: &Uuid) -> Result<String, AuthError>;
+    /// Creates a new refresh JWT for the user at their current claims version
+    fn create_refresh_jwt(&self, user_id: &Uuid, claims_version: u64) -> Result<String, AuthError>;
 
     /// Decodes a JWT and returns the claims
     fn decode_jwt(&self, token: &str) -> Result<TokenData<Claims>, AuthError>;
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use mockall::automock;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::{
+    entities::usage::{EmailUsage, TableUsage, UsageReport},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxUsageRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait UsageRepository: Send + Sync {
+    /// Rows the site has in every table with a `tenant_id`, empty tables included
+    async fn table_usage(&self, tenant_id: &Uuid) -> Result<Vec<TableUsage>, AppError>;
+    /// On-disk size of the whole database
+    async fn database_bytes(&self) -> Result<i64, AppError>;
+    /// Adds to the month's email counts
+    async fn add_email_sends(&self, month: NaiveDate, sent: i64, failed: i64) -> Result<(), AppError>;
+    /// Zero counts for a month nothing was sent in
+    async fn email_usage(&self, month: NaiveDate) -> Result<EmailUsage, AppError>;
+    /// Replaces the site's stored report
+    async fn save_report(&self, report: &UsageReport) -> Result<(), AppError>;
+    async fn latest_report(&self, tenant_id: &Uuid) -> Result<Option<UsageReport>, AppError>;
+    /// Records alerts at `thresholds` percent of the quota for the period,
+    /// returning the thresholds that had not been recorded yet
+    async fn claim_quota_alerts(&self, tenant_id: &Uuid, quota: &str, period: NaiveDate, thresholds: &[i16]) -> Result<Vec<i16>, AppError>;
+}
+
+#[async_trait]
+impl<T: UsageRepository + ?Sized> UsageRepository for Arc<T> {
+    async fn table_usage(&self, tenant_id: &Uuid) -> Result<Vec<TableUsage>, AppError> {
+        (**self).table_usage(tenant_id).await
+    }
+
+    async fn database_bytes(&self) -> Result<i64, AppError> {
+        (**self).database_bytes().await
+    }
+
+    async fn add_email_sends(&self, month: NaiveDate, sent: i64, failed: i64) -> Result<(), AppError> {
+        (**self).add_email_sends(month, sent, failed).await
+    }
+
+    async fn email_usage(&self, month: NaiveDate) -> Result<EmailUsage, AppError> {
+        (**self).email_usage(month).await
+    }
+
+    async fn save_report(&self, report: &UsageReport) -> Result<(), AppError> {
+        (**self).save_report(report).await
+    }
+
+    async fn latest_report(&self, tenant_id: &Uuid) -> Result<Option<UsageReport>, AppError> {
+        (**self).latest_report(tenant_id).await
+    }
+
+    async fn claim_quota_alerts(&self, tenant_id: &Uuid, quota: &str, period: NaiveDate, thresholds: &[i16]) -> Result<Vec<i16>, AppError> {
+        (**self).claim_quota_alerts(tenant_id, quota, period, thresholds).await
+    }
+}
+
+impl SqlxUsageRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxUsageRepo { pool }
+    }
+}
+
+#[async_trait]
+impl UsageRepository for SqlxUsageRepo {
+    async fn table_usage(&self, tenant_id: &Uuid) -> Result<Vec<TableUsage>, AppError> {
+        // Read from the catalog, so tables added later are counted without a change here
+        let tables: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT c.table_name::TEXT
+            FROM information_schema.columns c
+            JOIN information_schema.tables t USING (table_schema, table_name)
+            WHERE c.table_schema = current_schema()
+              AND c.column_name = 'tenant_id'
+              AND t.table_type = 'BASE TABLE'
+            ORDER BY c.table_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut usage = Vec::with_capacity(tables.len());
+        for table in tables {
+            let quoted = format!("\"{}\"", table.replace('"', "\"\""));
+            let (rows, table_bytes): (i64, i64) = sqlx::query_as(&format!(
+                "SELECT (SELECT COUNT(*) FROM {quoted} WHERE tenant_id = $1), pg_total_relation_size($2::regclass)"
+            ))
+            .bind(tenant_id)
+            .bind(&quoted)
+            .fetch_one(&self.pool)
+            .await?;
+
+            usage.push(TableUsage { table, rows, table_bytes });
+        }
+
+        Ok(usage)
+    }
+
+    async fn database_bytes(&self) -> Result<i64, AppError> {
+        let bytes = sqlx::query_scalar("SELECT pg_database_size(current_database())")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(bytes)
+    }
+
+    async fn add_email_sends(&self, month: NaiveDate, sent: i64, failed: i64) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO email_send_counts (month, sent, failed)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (month) DO UPDATE SET
+                sent = email_send_counts.sent + EXCLUDED.sent,
+                failed = email_send_counts.failed + EXCLUDED.failed,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(month)
+        .bind(sent)
+        .bind(failed)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn email_usage(&self, month: NaiveDate) -> Result<EmailUsage, AppError> {
+        let usage = sqlx::query_as::<_, EmailUsage>("SELECT month, sent, failed FROM email_send_counts WHERE month = $1")
+            .bind(month)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(usage.unwrap_or(EmailUsage { month, sent: 0, failed: 0 }))
+    }
+
+    async fn save_report(&self, report: &UsageReport) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO usage_reports (tenant_id, report, collected_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (tenant_id) DO UPDATE SET report = EXCLUDED.report, collected_at = EXCLUDED.collected_at
+            "#,
+        )
+        .bind(report.tenant_id)
+        .bind(Json(report))
+        .bind(report.collected_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest_report(&self, tenant_id: &Uuid) -> Result<Option<UsageReport>, AppError> {
+        let report: Option<Json<UsageReport>> = sqlx::query_scalar("SELECT report FROM usage_reports WHERE tenant_id = $1")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(report.map(|Json(report)| report))
+    }
+
+    async fn claim_quota_alerts(&self, tenant_id: &Uuid, quota: &str, period: NaiveDate, thresholds: &[i16]) -> Result<Vec<i16>, AppError> {
+        let claimed = sqlx::query_scalar(
+            r#"
+            INSERT INTO quota_alerts (tenant_id, quota, period, threshold)
+            SELECT $1, $2, $3, UNNEST($4::SMALLINT[])
+            ON CONFLICT DO NOTHING
+            RETURNING threshold
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(quota)
+        .bind(period)
+        .bind(thresholds)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(claimed)
+    }
+}
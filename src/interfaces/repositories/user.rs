@@ -1,24 +1,101 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use mockall::automock;
 use uuid::Uuid;
 use std::borrow::Cow;
+use sqlx::PgConnection;
 
 use crate::{
-    entities::user::{User, UserInsert}, 
+    entities::user::{
+        system_user_email, AuthoredContentPolicy, UpdateProfileRequest, User, UserInsert, SYSTEM_USER_NAME,
+        USER_AUDIT_DELETED, USER_AUDIT_DELETE_BLOCKED,
+    },
     errors::AppError, 
     repositories::sqlx_repo::SqlxUserRepo,
 };
 
 
+#[automock]
 #[async_trait]
 pub trait UserRepository: Send + Sync {
     async fn check_connection(&self) -> Result<(), AppError>;
-    async fn user_exists(&self, id: &Uuid) -> Result<bool, AppError>;
-    async fn count_users(&self) -> Result<u64, AppError>;
-    async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
-    async fn create_user(&self, user: &UserInsert) -> Result<Uuid, AppError>;
-    async fn get_user_by_id(&self, id: &Uuid) -> Result<Option<User>, AppError>;
-    async fn delete_user(&self, id: &Uuid, deleted_by: &Uuid) -> Result<(), AppError>;
+    async fn user_exists(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError>;
+    async fn count_users(&self, tenant_id: &Uuid) -> Result<u64, AppError>;
+    async fn get_user_by_email(&self, tenant_id: &Uuid, email: &str) -> Result<Option<User>, AppError>;
+    async fn create_user(&self, tenant_id: &Uuid, user: &UserInsert) -> Result<Uuid, AppError>;
+    async fn get_user_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<User>, AppError>;
+    /// Soft-deletes the user, first applying `content_policy` to the posts
+    /// they own or are credited on. Both outcomes are written to `user_audit`.
+    async fn delete_user(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        deleted_by: &Uuid,
+        content_policy: AuthoredContentPolicy,
+    ) -> Result<(), AppError>;
     async fn purge_soft_deleted_users(&self) -> Result<u64, AppError>;
+    async fn update_profile(&self, tenant_id: &Uuid, id: &Uuid, profile: &UpdateProfileRequest) -> Result<User, AppError>;
+    async fn set_editor(&self, tenant_id: &Uuid, id: &Uuid, editor: bool) -> Result<User, AppError>;
+    async fn set_password(&self, tenant_id: &Uuid, id: &Uuid, password_hash: &str) -> Result<User, AppError>;
+    async fn mark_verified(&self, tenant_id: &Uuid, id: &Uuid) -> Result<User, AppError>;
+}
+
+#[async_trait]
+impl<T: UserRepository + ?Sized> UserRepository for Arc<T> {
+    async fn check_connection(&self) -> Result<(), AppError> {
+        (**self).check_connection().await
+    }
+
+    async fn user_exists(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError> {
+        (**self).user_exists(tenant_id, id).await
+    }
+
+    async fn count_users(&self, tenant_id: &Uuid) -> Result<u64, AppError> {
+        (**self).count_users(tenant_id).await
+    }
+
+    async fn get_user_by_email(&self, tenant_id: &Uuid, email: &str) -> Result<Option<User>, AppError> {
+        (**self).get_user_by_email(tenant_id, email).await
+    }
+
+    async fn create_user(&self, tenant_id: &Uuid, user: &UserInsert) -> Result<Uuid, AppError> {
+        (**self).create_user(tenant_id, user).await
+    }
+
+    async fn get_user_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<User>, AppError> {
+        (**self).get_user_by_id(tenant_id, id).await
+    }
+
+    async fn delete_user(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        deleted_by: &Uuid,
+        content_policy: AuthoredContentPolicy,
+    ) -> Result<(), AppError> {
+        (**self).delete_user(tenant_id, id, deleted_by, content_policy).await
+    }
+
+    async fn purge_soft_deleted_users(&self) -> Result<u64, AppError> {
+        (**self).purge_soft_deleted_users().await
+    }
+
+    async fn update_profile(&self, tenant_id: &Uuid, id: &Uuid, profile: &UpdateProfileRequest) -> Result<User, AppError> {
+        (**self).update_profile(tenant_id, id, profile).await
+    }
+
+    async fn set_editor(&self, tenant_id: &Uuid, id: &Uuid, editor: bool) -> Result<User, AppError> {
+        (**self).set_editor(tenant_id, id, editor).await
+    }
+
+    async fn set_password(&self, tenant_id: &Uuid, id: &Uuid, password_hash: &str) -> Result<User, AppError> {
+        (**self).set_password(tenant_id, id, password_hash).await
+    }
+
+    async fn mark_verified(&self, tenant_id: &Uuid, id: &Uuid) -> Result<User, AppError> {
+        (**self).mark_verified(tenant_id, id).await
+    }
 }
 
 impl SqlxUserRepo {
@@ -37,10 +114,11 @@ impl UserRepository for SqlxUserRepo {
             .map_err(AppError::from)
     }
 
-    async fn user_exists(&self, id: &Uuid) -> Result<bool, AppError> {
+    async fn user_exists(&self, tenant_id: &Uuid, id: &Uuid) -> Result<bool, AppError> {
         let exists: Option<bool> = sqlx::query_scalar!(
-            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND deleted_at IS NULL)",
-            id
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL)",
+            id,
+            tenant_id
         )
         .fetch_one(&self.pool)
         .await
@@ -51,8 +129,11 @@ impl UserRepository for SqlxUserRepo {
         Ok(exists)
     }
 
-    async fn count_users(&self) -> Result<u64, AppError> {
-        let count: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM users WHERE deleted_at IS NULL")
+    async fn count_users(&self, tenant_id: &Uuid) -> Result<u64, AppError> {
+        let count: i64 = sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM users WHERE tenant_id = $1 AND deleted_at IS NULL AND NOT is_system",
+            tenant_id
+        )
             .fetch_one(&self.pool)
             .await
             .map_err(AppError::from)?
@@ -61,11 +142,12 @@ impl UserRepository for SqlxUserRepo {
         Ok(count as u64)
     }
 
-    async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+    async fn get_user_by_email(&self, tenant_id: &Uuid, email: &str) -> Result<Option<User>, AppError> {
         let user = sqlx::query_as!(
             User,
-            "SELECT * FROM users WHERE email = $1 AND deleted_at IS NULL",
-            email
+            "SELECT * FROM users WHERE email = $1 AND tenant_id = $2 AND deleted_at IS NULL AND NOT is_system",
+            email,
+            tenant_id
         )
         .fetch_optional(&self.pool)
         .await
@@ -74,9 +156,10 @@ impl UserRepository for SqlxUserRepo {
         Ok(user)
     }
 
-    async fn create_user(&self, user: &UserInsert) -> Result<Uuid, AppError> {
+    async fn create_user(&self, tenant_id: &Uuid, user: &UserInsert) -> Result<Uuid, AppError> {
         let row = sqlx::query!(
             r#"INSERT INTO users (
+                tenant_id,
                 email, 
                 username,
                 password_hash,
@@ -87,8 +170,9 @@ impl UserRepository for SqlxUserRepo {
                 deleted_at,
                 deleted_by
             ) 
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING id
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id
             "#,
+            tenant_id,
             user.email,
             user.username,
             user.password_hash,
@@ -113,26 +197,124 @@ impl UserRepository for SqlxUserRepo {
         Ok(row.id)
     }
 
-    async fn get_user_by_id(&self, id: &Uuid) -> Result<Option<User>, AppError> {
-        sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1", id)
+    async fn get_user_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<Option<User>, AppError> {
+        sqlx::query_as!(
+            User,
+            "SELECT * FROM users WHERE id = $1 AND tenant_id = $2",
+            id,
+            tenant_id
+        )
             .fetch_optional(&self.pool)
             .await
             .map_err(AppError::from)
     }
 
-    async fn delete_user(&self, id: &Uuid, deleted_by: &Uuid) -> Result<(), AppError> {
-        let result = sqlx::query!(
+    async fn delete_user(
+        &self,
+        tenant_id: &Uuid,
+        id: &Uuid,
+        deleted_by: &Uuid,
+        content_policy: AuthoredContentPolicy,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let is_system = sqlx::query_scalar!(
+            "SELECT is_system FROM users WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL FOR UPDATE",
+            id,
+            tenant_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        if is_system {
+            return Err(AppError::Conflict("The system user cannot be deleted".to_string()));
+        }
+
+        // Trashed posts count too: they still name the user until purged
+        let content_posts = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!" FROM blog_posts p
+            WHERE p.tenant_id = $1
+              AND (p.author_id = $2 OR EXISTS (
+                  SELECT 1 FROM post_authors pa WHERE pa.post_id = p.id AND pa.user_id = $2
+              ))
+            "#,
+            tenant_id,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if content_posts > 0 {
+            match content_policy {
+                AuthoredContentPolicy::Block => {
+                    record_audit(&mut tx, id, USER_AUDIT_DELETE_BLOCKED, deleted_by, content_policy, content_posts).await?;
+                    tx.commit().await?;
+
+                    return Err(AppError::Conflict(format!(
+                        "User owns or is credited on {} post(s); reassign them before deleting the account",
+                        content_posts
+                    )));
+                }
+                AuthoredContentPolicy::Reassign => {
+                    let system_id = system_user_id(&mut tx, tenant_id).await?;
+
+                    sqlx::query!(
+                        "UPDATE blog_posts SET author_id = $3 WHERE tenant_id = $1 AND author_id = $2",
+                        tenant_id,
+                        id,
+                        system_id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+
+                    // A post can credit the system user only once
+                    sqlx::query!(
+                        r#"
+                        DELETE FROM post_authors
+                        WHERE user_id = $1
+                          AND post_id IN (SELECT post_id FROM post_authors WHERE user_id = $2)
+                        "#,
+                        id,
+                        system_id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+
+                    sqlx::query!("UPDATE post_authors SET user_id = $2 WHERE user_id = $1", id, system_id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                AuthoredContentPolicy::Anonymize => {
+                    sqlx::query!(
+                        "UPDATE blog_posts SET author_id = NULL WHERE tenant_id = $1 AND author_id = $2",
+                        tenant_id,
+                        id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+
+                    sqlx::query!("DELETE FROM post_authors WHERE user_id = $1", id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+
+        sqlx::query!(
             r#"
             UPDATE users
             SET 
                 deleted_at = NOW(),
                 deleted_by = $2
-            WHERE id = $1 AND deleted_at IS NULL
+            WHERE id = $1 AND tenant_id = $3
             "#,
             id,
-            deleted_by
+            deleted_by,
+            tenant_id
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| {
             match e {
@@ -143,13 +325,8 @@ impl UserRepository for SqlxUserRepo {
             }
         })?;
 
-        if result.rows_affected() == 0 {
-            return if self.user_exists(id).await? {
-                Err(AppError::Conflict("User is already deleted".to_string()))
-            } else {
-                Err(AppError::NotFound("User not found".to_string()))
-            };
-        }
+        record_audit(&mut tx, id, USER_AUDIT_DELETED, deleted_by, content_policy, content_posts).await?;
+        tx.commit().await?;
 
         Ok(())
     }
@@ -163,4 +340,129 @@ impl UserRepository for SqlxUserRepo {
 
         Ok(result.rows_affected())
     }
+
+    async fn update_profile(&self, tenant_id: &Uuid, id: &Uuid, profile: &UpdateProfileRequest) -> Result<User, AppError> {
+        // A field left out keeps its value; an explicit null clears it
+        sqlx::query_as!(
+            User,
+            r#"
+            UPDATE users SET
+                display_name = CASE WHEN $3 THEN $4 ELSE display_name END,
+                avatar_url = CASE WHEN $5 THEN $6 ELSE avatar_url END,
+                updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            id,
+            tenant_id,
+            !profile.display_name.is_unchanged(),
+            profile.display_name.flatten_str(),
+            !profile.avatar_url.is_unchanged(),
+            profile.avatar_url.flatten_str()
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    async fn set_editor(&self, tenant_id: &Uuid, id: &Uuid, editor: bool) -> Result<User, AppError> {
+        sqlx::query_as!(
+            User,
+            r#"
+            UPDATE users SET is_editor = $3, updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            id,
+            tenant_id,
+            editor
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    async fn set_password(&self, tenant_id: &Uuid, id: &Uuid, password_hash: &str) -> Result<User, AppError> {
+        sqlx::query_as!(
+            User,
+            r#"
+            UPDATE users SET password_hash = $3, updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL AND NOT is_system
+            RETURNING *
+            "#,
+            id,
+            tenant_id,
+            password_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    async fn mark_verified(&self, tenant_id: &Uuid, id: &Uuid) -> Result<User, AppError> {
+        sqlx::query_as!(
+            User,
+            r#"
+            UPDATE users SET is_verified = TRUE, updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+}
+
+/// The tenant's system user, created on first use. The password hash is not
+/// a valid PHC string, so no password ever verifies against it.
+async fn system_user_id(conn: &mut PgConnection, tenant_id: &Uuid) -> Result<Uuid, AppError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO users (tenant_id, email, username, password_hash, is_verified, is_system)
+        VALUES ($1, $2, $3, '!', TRUE, TRUE)
+        ON CONFLICT (tenant_id) WHERE is_system AND deleted_at IS NULL DO NOTHING
+        "#,
+        tenant_id,
+        system_user_email(tenant_id),
+        SYSTEM_USER_NAME
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let id = sqlx::query_scalar!(
+        "SELECT id FROM users WHERE tenant_id = $1 AND is_system AND deleted_at IS NULL",
+        tenant_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(id)
+}
+
+async fn record_audit(
+    conn: &mut PgConnection,
+    user_id: &Uuid,
+    action: &str,
+    performed_by: &Uuid,
+    content_policy: AuthoredContentPolicy,
+    content_posts: i64,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO user_audit (user_id, action, performed_by, content_policy, content_posts)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        user_id,
+        action,
+        performed_by,
+        content_policy.as_str(),
+        content_posts as i32
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::{
+    entities::uses::{UsesEntry, UsesEntryInsert},
+    errors::AppError,
+    repositories::sqlx_repo::SqlxUsesRepo,
+};
+
+#[automock]
+#[async_trait]
+pub trait UsesRepository: Send + Sync {
+    /// Every entry for the tenant, in page order
+    async fn list_entries(&self, tenant_id: &Uuid) -> Result<Vec<UsesEntry>, AppError>;
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<UsesEntry, AppError>;
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &UsesEntryInsert) -> Result<UsesEntry, AppError>;
+    /// Overwrites every editable column with the values in `entry`
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &UsesEntry) -> Result<UsesEntry, AppError>;
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError>;
+    /// Sets each entry's position to its index in `ids`
+    async fn reorder_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: UsesRepository + ?Sized> UsesRepository for Arc<T> {
+    async fn list_entries(&self, tenant_id: &Uuid) -> Result<Vec<UsesEntry>, AppError> {
+        (**self).list_entries(tenant_id).await
+    }
+
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<UsesEntry, AppError> {
+        (**self).get_entry(tenant_id, id).await
+    }
+
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &UsesEntryInsert) -> Result<UsesEntry, AppError> {
+        (**self).create_entry(tenant_id, entry).await
+    }
+
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &UsesEntry) -> Result<UsesEntry, AppError> {
+        (**self).update_entry(tenant_id, entry).await
+    }
+
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        (**self).delete_entry(tenant_id, id).await
+    }
+
+    async fn reorder_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<(), AppError> {
+        (**self).reorder_entries(tenant_id, ids).await
+    }
+}
+
+impl SqlxUsesRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        SqlxUsesRepo { pool }
+    }
+}
+
+#[async_trait]
+impl UsesRepository for SqlxUsesRepo {
+    async fn list_entries(&self, tenant_id: &Uuid) -> Result<Vec<UsesEntry>, AppError> {
+        let entries = sqlx::query_as!(
+            UsesEntry,
+            r#"SELECT * FROM uses_entries WHERE tenant_id = $1 ORDER BY position, created_at"#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn get_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<UsesEntry, AppError> {
+        sqlx::query_as!(
+            UsesEntry,
+            r#"SELECT * FROM uses_entries WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Uses entry not found".into()))
+    }
+
+    async fn create_entry(&self, tenant_id: &Uuid, entry: &UsesEntryInsert) -> Result<UsesEntry, AppError> {
+        let created = sqlx::query_as!(
+            UsesEntry,
+            r#"
+            INSERT INTO uses_entries (tenant_id, category, name, description_markdown, link, image_url, position)
+            VALUES (
+                $1, $2, $3, $4, $5, $6,
+                COALESCE($7, (SELECT COALESCE(MAX(position) + 1, 0) FROM uses_entries WHERE tenant_id = $1))
+            )
+            RETURNING *
+            "#,
+            tenant_id,
+            entry.category,
+            entry.name,
+            entry.description_markdown,
+            entry.link,
+            entry.image_url,
+            entry.position,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn update_entry(&self, tenant_id: &Uuid, entry: &UsesEntry) -> Result<UsesEntry, AppError> {
+        sqlx::query_as!(
+            UsesEntry,
+            r#"
+            UPDATE uses_entries SET
+                category = $1,
+                name = $2,
+                description_markdown = $3,
+                link = $4,
+                image_url = $5,
+                position = $6,
+                updated_at = NOW()
+            WHERE id = $7 AND tenant_id = $8
+            RETURNING *
+            "#,
+            entry.category,
+            entry.name,
+            entry.description_markdown,
+            entry.link,
+            entry.image_url,
+            entry.position,
+            entry.id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Uses entry not found".into()))
+    }
+
+    async fn delete_entry(&self, tenant_id: &Uuid, id: &Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            r#"DELETE FROM uses_entries WHERE id = $1 AND tenant_id = $2"#,
+            id,
+            tenant_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Uses entry not found".into()));
+        }
+
+        Ok(())
+    }
+
+    async fn reorder_entries(&self, tenant_id: &Uuid, ids: &[Uuid]) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE uses_entries AS u SET
+                position = o.ord - 1,
+                updated_at = NOW()
+            FROM UNNEST($1::uuid[]) WITH ORDINALITY AS o(id, ord)
+            WHERE u.id = o.id AND u.tenant_id = $2
+            "#,
+            ids,
+            tenant_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
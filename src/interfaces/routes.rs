@@ -1,6 +1,9 @@
 use actix_web::web;
 
-use crate::handlers::home::home;
+use crate::{
+    constants::READYZ_PATH,
+    handlers::{fallback, home::home, system::readiness},
+};
 
 mod auth;
 mod admin;
@@ -8,9 +11,27 @@ mod users;
 mod json_error;
 mod about_me;
 mod blog;
+mod gate;
+mod contact;
+mod hire;
+mod uses;
+mod changelog;
+mod bookmarks;
+mod outbound;
+mod presence;
+mod reading;
+mod storage;
+mod honeytoken;
+mod visitor;
+mod sync;
+mod pages;
+mod public_api;
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(home);
+    cfg.service(web::resource(READYZ_PATH).route(web::get().to(readiness)));
+    cfg.configure(honeytoken::config_routes);
+    cfg.configure(pages::config_routes);
 
     cfg.service(
         web::scope("/api/v1")
@@ -19,7 +40,22 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             .configure(users::config_routes)
             .configure(about_me::config_routes)
             .configure(blog::config_routes)
+            .configure(gate::config_routes)
+            .configure(contact::config_routes)
+            .configure(hire::config_routes)
+            .configure(uses::config_routes)
+            .configure(changelog::config_routes)
+            .configure(bookmarks::config_routes)
+            .configure(outbound::config_routes)
+            .configure(presence::config_routes)
+            .configure(reading::config_routes)
+            .configure(storage::config_routes)
+            .configure(visitor::config_routes)
+            .configure(sync::config_routes)
+            .configure(public_api::config_routes)
     );
 
     cfg.configure(json_error::config_routes);
+
+    cfg.default_service(web::route().to(fallback::not_found));
 }
\ No newline at end of file
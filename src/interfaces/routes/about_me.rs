@@ -1,11 +1,14 @@
 use actix_web::web;
 
 use crate::handlers::about_me;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
 
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/about-me")
+            .wrap(RequestTimeout::scope("about-me"))
+            .wrap(LoadShed::scope("about-me"))
             .service(
                 web::resource("")
                     .route(web::post().to(about_me::create_about_me))
@@ -16,6 +19,7 @@ pub fn config_routes(cfg: &mut web::ServiceConfig) {
             )
             .service(
                 web::resource("/{about_me_id}")
+                    .route(web::patch().to(about_me::update_about_me))
                     .route(web::delete().to(about_me::delete_about_me))
             )
     );
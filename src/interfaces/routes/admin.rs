@@ -1,11 +1,264 @@
 use actix_web::web;
 
-use crate::handlers::{auth, system::admin_health_check};
+use crate::entities::content_fixture::MAX_FIXTURE_BYTES;
+use crate::entities::redirect_rule::MAX_REDIRECT_IMPORT_BYTES;
+use crate::entities::user_import::MAX_USER_IMPORT_BYTES;
+use crate::handlers::{api_tokens, audit, auth, bookmarks, changelog, contact_me, domains, email_templates, feature_flags, fixtures, geo, hire, honeytoken, jobs, link_checks, outbound, presence, rate_limits, reading, rebuild, redirects, retention, schedule_preview, settings, static_export, stats, system::{admin_health_check, admin_metrics}, usage, users};
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/admin")
+            .wrap(RequestTimeout::scope("admin"))
+            .wrap(LoadShed::scope("admin"))
             .service(admin_health_check)
+            .service(admin_metrics)
             .service(auth::admin_dashboard)
+            .service(
+                web::resource("/api-tokens")
+                    .route(web::get().to(api_tokens::list_api_tokens))
+                    .route(web::post().to(api_tokens::issue_api_token))
+            )
+            .service(
+                web::resource("/api-tokens/{id}")
+                    .route(web::delete().to(api_tokens::revoke_api_token))
+            )
+            .service(
+                web::resource("/api-tokens/{id}/usage")
+                    .route(web::get().to(api_tokens::api_token_usage))
+            )
+            .service(
+                web::resource("/audit-log")
+                    .route(web::get().to(audit::list_audit_log))
+            )
+            .service(
+                web::resource("/audit-log/export")
+                    .route(web::get().to(audit::export_audit_log))
+            )
+            .service(
+                web::resource("/bookmarks")
+                    .route(web::get().to(bookmarks::list_all_bookmarks))
+            )
+            .service(
+                web::resource("/contact-messages/export.csv")
+                    .route(web::get().to(contact_me::export_contact_messages_csv))
+            )
+            .service(
+                web::resource("/contact-messages/anonymize")
+                    .route(web::post().to(retention::anonymize_contact_messages))
+            )
+            .service(
+                web::resource("/contact-messages/{id}/restore")
+                    .route(web::post().to(retention::restore_contact_message))
+            )
+            .service(
+                web::resource("/deploy-hook")
+                    .route(web::post().to(changelog::deploy_hook))
+            )
+            .service(
+                web::resource("/domains")
+                    .route(web::get().to(domains::list_domains))
+                    .route(web::post().to(domains::add_domain))
+            )
+            .service(
+                web::resource("/domains/{hostname}")
+                    .route(web::get().to(domains::get_domain))
+                    .route(web::delete().to(domains::delete_domain))
+            )
+            .service(
+                web::resource("/domains/{hostname}/verify")
+                    .route(web::post().to(domains::verify_domain))
+            )
+            .service(
+                web::resource("/email-templates")
+                    .route(web::get().to(email_templates::list_email_templates))
+            )
+            .service(
+                web::resource("/email-templates/{name}/preview")
+                    .route(web::get().to(email_templates::preview_email_template))
+            )
+            .service(
+                web::resource("/feature-flags")
+                    .route(web::get().to(feature_flags::list_feature_flags))
+            )
+            .service(
+                web::resource("/feature-flags/refresh")
+                    .route(web::post().to(feature_flags::refresh_feature_flags))
+            )
+            .service(
+                web::resource("/feature-flags/{key}")
+                    .route(web::put().to(feature_flags::update_feature_flag))
+            )
+            .service(
+                web::resource("/fixtures/export")
+                    .route(web::get().to(fixtures::export_content_fixture))
+            )
+            .service(
+                web::resource("/fixtures/load")
+                    .app_data(web::PayloadConfig::new(MAX_FIXTURE_BYTES))
+                    .route(web::post().to(fixtures::load_content_fixture))
+            )
+            .service(
+                web::resource("/fixtures/snapshots")
+                    .route(web::post().to(fixtures::snapshot_content_fixture))
+            )
+            .service(
+                web::resource("/geo")
+                    .route(web::get().to(geo::geo_report))
+            )
+            .service(
+                web::resource("/geo/reload")
+                    .route(web::post().to(geo::reload_geo_database))
+            )
+            .service(
+                web::resource("/hire-inquiries")
+                    .route(web::get().to(hire::list_hire_inquiries))
+            )
+            .service(
+                web::resource("/hire-inquiries/{id}")
+                    .route(web::delete().to(hire::delete_hire_inquiry))
+            )
+            .service(
+                web::resource("/jobs")
+                    .route(web::get().to(jobs::list_jobs))
+            )
+            .service(
+                web::resource("/jobs/stats")
+                    .route(web::get().to(jobs::job_stats))
+            )
+            .service(
+                web::resource("/jobs/queues/{event_type}/pause")
+                    .route(web::post().to(jobs::pause_job_queue))
+            )
+            .service(
+                web::resource("/jobs/queues/{event_type}/resume")
+                    .route(web::post().to(jobs::resume_job_queue))
+            )
+            .service(
+                web::resource("/jobs/{id}/retry")
+                    .route(web::post().to(jobs::retry_job))
+            )
+            .service(
+                web::resource("/jobs/{id}/cancel")
+                    .route(web::post().to(jobs::cancel_job))
+            )
+            .service(
+                web::resource("/backfills")
+                    .route(web::get().to(jobs::backfill_progress))
+            )
+            .service(
+                web::resource("/link-checks")
+                    .route(web::get().to(link_checks::link_check_report))
+            )
+            .service(
+                web::resource("/link-checks/run")
+                    .route(web::post().to(link_checks::run_link_check))
+            )
+            .service(
+                web::resource("/outbound-clicks")
+                    .route(web::get().to(outbound::outbound_click_report))
+            )
+            .service(
+                web::resource("/presence")
+                    .route(web::get().to(presence::admin_presence))
+            )
+            .service(
+                web::resource("/reading-stats")
+                    .route(web::get().to(reading::reading_stats_report))
+            )
+            .service(
+                web::resource("/preview-at")
+                    .route(web::get().to(schedule_preview::preview_at))
+            )
+            .service(
+                web::resource("/rate-limits")
+                    .route(web::get().to(rate_limits::list_rate_limits))
+            )
+            .service(
+                web::resource("/rate-limits/{key}")
+                    .route(web::delete().to(rate_limits::reset_rate_limit))
+            )
+            .service(
+                web::resource("/rebuild")
+                    .route(web::get().to(rebuild::rebuild_status))
+                    .route(web::post().to(rebuild::start_rebuild))
+            )
+            .service(
+                web::resource("/redirects")
+                    .route(web::get().to(redirects::list_redirect_rules))
+                    .route(web::post().to(redirects::create_redirect_rule))
+            )
+            .service(
+                web::resource("/redirects/import")
+                    .app_data(web::PayloadConfig::new(MAX_REDIRECT_IMPORT_BYTES))
+                    .route(web::post().to(redirects::import_redirect_rules))
+            )
+            .service(
+                web::resource("/redirects/{id}")
+                    .route(web::patch().to(redirects::update_redirect_rule))
+                    .route(web::delete().to(redirects::delete_redirect_rule))
+            )
+            .service(
+                web::resource("/retention")
+                    .route(web::get().to(retention::retention_report))
+            )
+            .service(
+                web::resource("/retention/run")
+                    .route(web::post().to(retention::run_retention))
+            )
+            .service(
+                web::resource("/stats/monthly")
+                    .route(web::get().to(stats::monthly_stats))
+            )
+            .service(
+                web::resource("/stats/rollups/run")
+                    .route(web::post().to(stats::run_stats_rollup))
+            )
+            .service(
+                web::resource("/usage")
+                    .route(web::get().to(usage::usage_report))
+            )
+            .service(
+                web::resource("/usage/refresh")
+                    .route(web::post().to(usage::refresh_usage))
+            )
+            .service(
+                web::resource("/static-export")
+                    .route(web::get().to(static_export::static_export_status))
+                    .route(web::post().to(static_export::start_static_export))
+            )
+            .service(
+                web::resource("/security/honeytokens")
+                    .route(web::get().to(honeytoken::honeytoken_report))
+            )
+            .service(
+                web::resource("/settings")
+                    .route(web::get().to(settings::list_settings))
+            )
+            .service(
+                web::resource("/settings/history")
+                    .route(web::get().to(settings::settings_history))
+            )
+            .service(
+                web::resource("/settings/history/{version}/rollback")
+                    .route(web::post().to(settings::rollback_settings))
+            )
+            .service(
+                web::resource("/settings/{key}")
+                    .route(web::put().to(settings::update_setting))
+            )
+            .service(
+                web::resource("/users/import")
+                    .app_data(web::PayloadConfig::new(MAX_USER_IMPORT_BYTES))
+                    .route(web::post().to(users::import_users))
+            )
+            .service(
+                web::resource("/users/{user_id}/editor")
+                    .route(web::put().to(users::set_editor))
+            )
+            .service(
+                web::resource("/users/{user_id}/claims-version")
+                    .route(web::post().to(users::bump_claims_version))
+            )
     );
 }
\ No newline at end of file
@@ -1,13 +1,22 @@
 use actix_web::web;
 
 use crate::handlers::auth;
+use crate::middlewares::{load_shed::LoadShed, rate_limit::RateLimit, timeout::RequestTimeout};
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/auth")
+            .wrap(RequestTimeout::scope("auth"))
+            .wrap(LoadShed::scope("auth"))
+            .wrap(RateLimit::route("auth"))
             .service(auth::register)
             .service(auth::login)
             .service(auth::refresh_token)
             .service(auth::logout)
+            .service(auth::request_password_reset)
+            .service(auth::check_password_reset)
+            .service(auth::confirm_password_reset)
+            .service(auth::send_verification_email)
+            .service(auth::confirm_email_verification)
     );
 }
\ No newline at end of file
@@ -1,9 +1,12 @@
 use actix_web::web;
-use crate::handlers::blog_posts;
+use crate::handlers::{blog_posts, series};
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/blog")
+            .wrap(RequestTimeout::scope("blog"))
+            .wrap(LoadShed::scope("blog"))
             .service(
                 web::resource("/posts")
                     .route(web::get().to(blog_posts::get_all_blog_posts))
@@ -13,12 +16,28 @@ pub fn config_routes(cfg: &mut web::ServiceConfig) {
                 web::resource("/posts/recent/{limit}")
                     .route(web::get().to(blog_posts::get_recent_blog_posts))
             )
+            .service(
+                web::resource("/posts/featured")
+                    .route(web::get().to(blog_posts::get_featured_posts))
+            )
             .service(
                 web::resource("/posts/{post_id}")
                     .route(web::get().to(blog_posts::get_blog_post_by_id))
                     .route(web::patch().to(blog_posts::update_blog_post))
                     .route(web::delete().to(blog_posts::delete_blog_post))
             )
+            .service(
+                web::resource("/posts/{post_id}/shares")
+                    .route(web::get().to(blog_posts::get_post_share_counts))
+            )
+            .service(
+                web::resource("/authors/{username}/posts")
+                    .route(web::get().to(blog_posts::get_author_posts))
+            )
+            .service(
+                web::resource("/series/{slug}")
+                    .route(web::get().to(series::get_series))
+            )
             .service(
                 web::resource("/posts/{post_id}/publish")
                     .route(web::post().to(blog_posts::publish_blog_post))
@@ -31,5 +50,85 @@ pub fn config_routes(cfg: &mut web::ServiceConfig) {
                 web::resource("/admin/posts/recent/{limit}")
                     .route(web::get().to(blog_posts::admin_get_recent_blog_posts))
             )
+            .service(
+                web::resource("/admin/posts/{post_id}/authors")
+                    .route(web::post().to(blog_posts::admin_add_post_author))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/authors/{user_id}")
+                    .route(web::delete().to(blog_posts::admin_remove_post_author))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/revisions")
+                    .route(web::get().to(blog_posts::admin_list_post_revisions))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/revisions/{a}/diff/{b}")
+                    .route(web::get().to(blog_posts::admin_diff_post_revisions))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/lock")
+                    .route(web::get().to(blog_posts::admin_get_edit_lock))
+                    .route(web::post().to(blog_posts::admin_acquire_edit_lock))
+                    .route(web::delete().to(blog_posts::admin_release_edit_lock))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/bundle.zip")
+                    .route(web::get().to(blog_posts::admin_download_post_bundle))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/title-test")
+                    .route(web::get().to(blog_posts::admin_get_title_test))
+                    .route(web::put().to(blog_posts::admin_set_title_test))
+                    .route(web::delete().to(blog_posts::admin_end_title_test))
+            )
+            .service(
+                web::resource("/admin/posts/{post_id}/title-test/promote")
+                    .route(web::post().to(blog_posts::admin_promote_title))
+            )
+            .service(
+                web::resource("/admin/calendar")
+                    .route(web::get().to(blog_posts::admin_get_calendar))
+            )
+            .service(
+                web::resource("/admin/calendar/{post_id}")
+                    .route(web::patch().to(blog_posts::admin_reschedule_post))
+            )
+            .service(
+                web::resource("/admin/featured")
+                    .route(web::get().to(blog_posts::admin_get_featured_posts))
+                    .route(web::put().to(blog_posts::admin_set_featured_posts))
+            )
+            .service(
+                web::resource("/admin/series")
+                    .route(web::get().to(series::admin_list_series))
+                    .route(web::post().to(series::admin_create_series))
+            )
+            .service(
+                web::resource("/admin/series/{series_id}")
+                    .route(web::patch().to(series::admin_update_series))
+                    .route(web::delete().to(series::admin_delete_series))
+            )
+            .service(
+                web::resource("/admin/series/{series_id}/posts")
+                    .route(web::get().to(series::admin_get_series_posts))
+                    .route(web::put().to(series::admin_set_series_posts))
+            )
+            .service(
+                web::resource("/admin/search")
+                    .route(web::get().to(blog_posts::admin_search_blog_posts))
+            )
+            .service(
+                web::resource("/admin/slug-check")
+                    .route(web::get().to(blog_posts::admin_check_slug))
+            )
+            .service(
+                web::resource("/admin/suggest-tags")
+                    .route(web::post().to(blog_posts::admin_suggest_tags))
+            )
+            .service(
+                web::resource("/admin/posts/scan")
+                    .route(web::post().to(blog_posts::admin_scan_content))
+            )
     );
 }
@@ -0,0 +1,26 @@
+use actix_web::web;
+
+use crate::handlers::bookmarks;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/bookmarks")
+            .wrap(RequestTimeout::scope("bookmarks"))
+            .wrap(LoadShed::scope("bookmarks"))
+            .service(
+                web::resource("")
+                    .route(web::get().to(bookmarks::list_bookmarks))
+                    .route(web::post().to(bookmarks::create_bookmark))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::patch().to(bookmarks::update_bookmark))
+                    .route(web::delete().to(bookmarks::delete_bookmark))
+            )
+            .service(
+                web::resource("/{id}/preview")
+                    .route(web::post().to(bookmarks::refresh_bookmark_preview))
+            )
+    );
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use actix_web::web;
+
+use crate::handlers::{changelog, feed};
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/changelog")
+            .wrap(RequestTimeout::scope("changelog"))
+            .wrap(LoadShed::scope("changelog"))
+            .service(
+                web::resource("")
+                    .route(web::get().to(changelog::list_changelog))
+                    .route(web::post().to(changelog::create_changelog_entry))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::patch().to(changelog::update_changelog_entry))
+                    .route(web::delete().to(changelog::delete_changelog_entry))
+            )
+    );
+
+    cfg.service(
+        web::scope("/feed")
+            .wrap(RequestTimeout::scope("feed"))
+            .wrap(LoadShed::scope("feed"))
+            .route("/rss.xml", web::get().to(feed::rss_feed))
+    );
+}
\ No newline at end of file
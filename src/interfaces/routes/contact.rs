@@ -0,0 +1,13 @@
+use actix_web::web;
+
+use crate::handlers::contact_me;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/contact-me")
+            .wrap(RequestTimeout::scope("contact"))
+            .wrap(LoadShed::scope("contact"))
+            .route(web::post().to(contact_me::create_contact_me))
+    );
+}
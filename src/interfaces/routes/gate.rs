@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::handlers::gate;
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(gate::unlock_site);
+}
@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::hire;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/hire")
+            .wrap(RequestTimeout::scope("hire"))
+            .wrap(LoadShed::scope("hire"))
+            .route("", web::get().to(hire::get_availability))
+            .route("/inquiries", web::post().to(hire::create_hire_inquiry))
+    );
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use actix_web::web;
+
+use crate::entities::security_event::HONEYTOKEN_PATHS;
+use crate::handlers::honeytoken;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+/// Decoys live at the site root, outside `/api/v1`, where scanners look for them
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    for path in HONEYTOKEN_PATHS {
+        cfg.service(
+            web::resource(*path)
+                .wrap(RequestTimeout::scope("honeytoken"))
+                .wrap(LoadShed::scope("honeytoken"))
+                .route(web::route().to(honeytoken::decoy))
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::outbound;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/out")
+            .wrap(RequestTimeout::scope("out"))
+            .wrap(LoadShed::scope("out"))
+            .route("", web::get().to(outbound::redirect_outbound))
+            .route("/qr.png", web::get().to(outbound::outbound_qr_code))
+    );
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use actix_web::web;
+
+use crate::handlers::pages;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+/// Server-rendered pages, outside `/api/v1`; they share the limits of the
+/// endpoints they render
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/about.html")
+            .wrap(RequestTimeout::scope("about-me"))
+            .wrap(LoadShed::scope("about-me"))
+            .route(web::get().to(pages::about_page))
+    );
+}
\ No newline at end of file
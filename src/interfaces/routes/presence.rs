@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::presence;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/presence")
+            .wrap(RequestTimeout::scope("presence"))
+            .wrap(LoadShed::scope("presence"))
+            .route("", web::get().to(presence::current_presence))
+            .route("/beat", web::post().to(presence::presence_beat))
+    );
+}
\ No newline at end of file
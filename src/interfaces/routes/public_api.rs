@@ -0,0 +1,20 @@
+use actix_web::web;
+
+use crate::entities::public_api::{PUBLIC_API_LIMIT_ROUTE, PUBLIC_API_VERSION};
+use crate::handlers::public_api;
+use crate::middlewares::{api_version::ApiVersion, load_shed::LoadShed, rate_limit::RateLimit, timeout::RequestTimeout};
+
+/// Read-only API for third parties, limited apart from browser traffic
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/public")
+            .wrap(RequestTimeout::scope("public-api"))
+            .wrap(LoadShed::scope("public-api"))
+            .wrap(RateLimit::route(PUBLIC_API_LIMIT_ROUTE))
+            .wrap(ApiVersion::new(PUBLIC_API_VERSION))
+            .route("", web::get().to(public_api::public_api_index))
+            .route("/posts", web::get().to(public_api::public_posts))
+            .route("/posts/{slug}", web::get().to(public_api::public_post))
+            .route("/about", web::get().to(public_api::public_about))
+    );
+}
\ No newline at end of file
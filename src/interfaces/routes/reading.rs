@@ -0,0 +1,16 @@
+use actix_web::web;
+
+use crate::entities::api_token::API_SCOPE_ANALYTICS;
+use crate::handlers::reading;
+use crate::middlewares::{api_token::RequireApiToken, load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/reading")
+            .wrap(RequestTimeout::scope("reading"))
+            .wrap(LoadShed::scope("reading"))
+            .wrap(RequireApiToken::scope(API_SCOPE_ANALYTICS))
+            .route("/progress", web::post().to(reading::record_reading_progress))
+            .route("/title-click", web::post().to(reading::record_title_click))
+    );
+}
\ No newline at end of file
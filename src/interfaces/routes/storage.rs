@@ -0,0 +1,13 @@
+use actix_web::web;
+
+use crate::handlers::storage;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/storage")
+            .wrap(RequestTimeout::scope("storage"))
+            .wrap(LoadShed::scope("storage"))
+            .route("/{key:.+}", web::get().to(storage::download_object))
+    );
+}
\ No newline at end of file
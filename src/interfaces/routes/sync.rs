@@ -0,0 +1,13 @@
+use actix_web::web;
+
+use crate::handlers::sync;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/sync")
+            .wrap(RequestTimeout::scope("sync"))
+            .wrap(LoadShed::scope("sync"))
+            .route(web::get().to(sync::sync_changes))
+    );
+}
\ No newline at end of file
@@ -1,12 +1,25 @@
 use actix_web::web;
 use crate::handlers::users;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/users")
+            .wrap(RequestTimeout::scope("users"))
+            .wrap(LoadShed::scope("users"))
             .service(
                 web::resource("/me")
                     .route(web::get().to(users::me))
+                    .route(web::patch().to(users::update_profile))
+            )
+            .service(
+                web::resource("/me/notifications")
+                    .route(web::get().to(users::notification_preferences))
+                    .route(web::patch().to(users::update_notification_preferences))
+            )
+            .service(
+                web::resource("/me/notifications/stream")
+                    .route(web::get().to(users::notification_stream))
             )
             .service(
                 web::resource("/{user_id}")
@@ -0,0 +1,30 @@
+use actix_web::web;
+
+use crate::handlers::uses;
+use crate::middlewares::{load_shed::LoadShed, timeout::RequestTimeout};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/uses")
+            .wrap(RequestTimeout::scope("uses"))
+            .wrap(LoadShed::scope("uses"))
+            .service(
+                web::resource("")
+                    .route(web::get().to(uses::get_uses))
+                    .route(web::post().to(uses::create_uses_entry))
+            )
+            .service(
+                web::resource("/export.md")
+                    .route(web::get().to(uses::export_uses_markdown))
+            )
+            .service(
+                web::resource("/order")
+                    .route(web::put().to(uses::reorder_uses_entries))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::patch().to(uses::update_uses_entry))
+                    .route(web::delete().to(uses::delete_uses_entry))
+            )
+    );
+}
\ No newline at end of file
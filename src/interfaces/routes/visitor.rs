@@ -0,0 +1,10 @@
+use actix_web::web;
+
+use crate::handlers::visitor;
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/visitor-token")
+            .route(web::get().to(visitor::visitor_token))
+    );
+}
\ No newline at end of file
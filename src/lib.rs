@@ -1,8 +1,4 @@
 use async_trait::async_trait;
-use deadpool_redis::{
-    Pool as RedisPool, 
-    Runtime,
-};
 use redis::AsyncCommands;
 
 mod domain;
@@ -14,119 +10,425 @@ pub mod settings;
 pub mod constants;
 pub mod graceful_shutdown;
 pub mod background_task;
+pub mod doctor;
 pub mod shared_repos;
 
 pub use domain::{entities, use_cases};
 pub use interfaces::{handlers, repositories, middlewares, routes};
-pub use infrastructure::{auth, db, utils, limiter};
+pub use infrastructure::{auth, db, utils, limiter, mailer, metrics, dns, cache, cdn, storage, links, geo, captcha, reporting, crypto};
+
+use std::{sync::Arc, time::Duration};
 
-use auth::jwt::JwtService;
+use auth::{jwt::JwtService, password::PasswordHasherPool, site_gate::SiteGate, visitor::VisitorTokens};
 use use_cases::auth::AuthHandler;
 
 use crate::{
-    domain::use_cases::{about::AboutHandler, blog::BlogPostHandler, contact::ContactMeHandler}, 
+    domain::use_cases::{
+        about::AboutHandler, account_emails::AccountEmails, api_tokens::ApiTokens, audit::AuditTrail, backfills::Backfills, captcha::CaptchaGuard, blog::BlogPostHandler, config_history::ConfigHistory, content_safety::ContentSafety, bookmarks::BookmarkHandler, changelog::ChangelogHandler, contact::ContactMeHandler,
+        dispatcher::NotificationDispatcher, domains::DomainVerifier, edit_locks::PostEditLocks, feed::FeedHandler,
+        feature_flags::FeatureFlags, fixtures::ContentFixtures, geo::GeoInsights, hire::HireHandler, ingest::ContactIngestQueue, honeytoken::HoneytokenMonitor, jobs::JobDashboard, link_checks::LinkChecker, notifications::ContactNotifier,
+        one_time_tokens::OneTimeTokens, outbound::OutboundLinks, pages::SitePages, outbox::OutboxRelay, post_bundle::PostBundler, prewarm::ContentPrewarmer, presence::PresenceTracker, public_api::PublicApi, quota_alerts::QuotaAlerts, reading::ReadingAnalytics, rebuild::ContentRebuilder, redirects::RedirectRules, retention::DataRetention, schedule_preview::SchedulePreview, schema::SchemaMigrations, series::SeriesHandler, share_counts::ShareCounts, static_export::StaticSiteExporter, stats_rollups::StatsRollups, sync::ContentSync, usage::UsageReports, settings::RuntimeSettings, tag_suggestions::TagSuggester, tenants::TenantResolver, title_tests::TitleTests, user_import::UserImport, uses::UsesHandler,
+    }, 
+    cache::{api_token_usage::api_token_usage_counter_from_pool, claims_version::claims_version_store_from_pool, edit_locks::edit_lock_store_from_pool, one_time_tokens::one_time_token_cache_from_pool, page_cache::page_store_from_pool, presence::presence_store_from_pool, rate_limit_state::rate_limit_state_store_from_pool, usage::redis_usage_probe_from_pool, redis_pool::{RedisConnection, RedisRunError, SupervisedPool}},
+    captcha::verifier::captcha_verifier_from_config,
+    crypto::{field_cipher::FieldCipher, keys::contact_key_provider_from_config},
+    cdn::purge::cdn_purger_from_config,
+    db::{backfills::bundled_backfills, migrations::bundled_migrations},
+    dns::txt::txt_resolver_from_config,
+    links::{preview::link_preview_fetcher_from_config, probe::link_prober_from_config, shares::share_providers_from_config},
     errors::AuthError, 
-    interfaces::repositories::sqlx_repo::{SqlxAboutMeRepo, SqlxBlogPostRepo, SqlxContactMeRepo, SqlxUserRepo}, 
-    shared_repos::SharedRepositories
+    geo::geoip::GeoLocator,
+    limiter::{ip_ban::IpBanList, load_shedder::LoadShedder, rate_limiter::RateHybridLimiterStore},
+    mailer::email::{mailer_from_config, CountingMailer, EmailSendCounter},
+    middlewares::{auth::RoutePolicy, body_log::BodyLogPolicy, cache_control::CachePolicy, timeout::RouteTimeouts},
+    storage::{links::StorageLinks, object::{storage_from_config, ObjectStorage}},
+    utils::{get_client_ip::TrustedProxies, public_urls::PublicUrls},
+    shared_repos::{
+        DynAboutRepo, DynApiTokenRepo, DynAppSettingsRepo, DynAuditLogRepo, DynBackfillRepo, DynBlogPostRepo, DynBookmarkRepo, DynChangelogRepo, DynConfigHistoryRepo, DynContactRepo, DynFeatureFlagRepo,
+        DynHireInquiryRepo, DynLinkCheckRepo, DynNotificationPreferencesRepo, DynOneTimeTokenRepo, DynOutboundClickRepo, DynOutboxRepo, DynReadingProgressRepo, DynRedirectRuleRepo, DynRetentionRepo, DynSchemaRepo, DynSecurityEventRepo, DynShareCountRepo, DynStatsRollupRepo, DynTenantRepo, DynTitleTestRepo, DynUsageRepo, DynUserRepo, DynUsesRepo,
+        SharedRepositories,
+    }
 };
 
 pub struct AppState {
     pub auth_handler: AppAuthHandler,
-    pub about_handler: AboutHandler<SqlxAboutMeRepo>,
-    pub blog_handler: BlogPostHandler<SqlxBlogPostRepo>,
-    pub contact_handler: ContactMeHandler<SqlxContactMeRepo>,
-    pub redis_pool: Option<RedisPool>,
+    pub about_handler: AboutHandler<DynAboutRepo>,
+    pub blog_handler: BlogPostHandler<DynBlogPostRepo>,
+    pub series_handler: SeriesHandler<DynBlogPostRepo>,
+    pub contact_handler: ContactMeHandler<DynContactRepo>,
+    pub uses_handler: UsesHandler<DynUsesRepo>,
+    pub bookmark_handler: BookmarkHandler<DynBookmarkRepo>,
+    pub changelog_handler: ChangelogHandler<DynChangelogRepo>,
+    pub feed_handler: FeedHandler<DynBlogPostRepo, DynChangelogRepo>,
+    pub prewarmer: ContentPrewarmer<DynBlogPostRepo, DynChangelogRepo>,
+    pub rebuilder: ContentRebuilder<DynBlogPostRepo, DynChangelogRepo>,
+    pub schedule_preview: SchedulePreview<DynBlogPostRepo, DynAboutRepo>,
+    pub user_import: UserImport<DynUserRepo>,
+    pub redirects: RedirectRules<DynRedirectRuleRepo>,
+    pub public_api: PublicApi<DynBlogPostRepo, DynAboutRepo>,
+    pub schema: SchemaMigrations<DynSchemaRepo>,
+    pub backfills: Backfills<DynBackfillRepo>,
+    pub site_pages: SitePages<DynAboutRepo, DynAppSettingsRepo>,
+    pub static_exporter: StaticSiteExporter<DynBlogPostRepo, DynChangelogRepo, DynAboutRepo, DynUsesRepo>,
+    pub fixtures: ContentFixtures<DynBlogPostRepo, DynAboutRepo>,
+    pub post_bundler: PostBundler<DynBlogPostRepo>,
+    pub edit_locks: PostEditLocks<DynBlogPostRepo>,
+    pub presence: PresenceTracker,
+    pub feature_flags: FeatureFlags<DynFeatureFlagRepo>,
+    pub captcha: CaptchaGuard<DynFeatureFlagRepo>,
+    pub site_gate: Option<SiteGate>,
+    pub visitor_tokens: VisitorTokens,
+    pub settings: RuntimeSettings<DynAppSettingsRepo>,
+    pub config_history: ConfigHistory<DynConfigHistoryRepo, DynAppSettingsRepo, DynFeatureFlagRepo>,
+    pub contact_notifier: ContactNotifier<DynContactRepo, DynAppSettingsRepo>,
+    pub notifications: NotificationDispatcher<DynNotificationPreferencesRepo>,
+    pub hire_handler: HireHandler<DynHireInquiryRepo, DynAppSettingsRepo>,
+    pub outbound: OutboundLinks<DynOutboundClickRepo, DynAppSettingsRepo, DynStatsRollupRepo>,
+    pub reading: ReadingAnalytics<DynReadingProgressRepo, DynStatsRollupRepo>,
+    pub honeytokens: HoneytokenMonitor<DynSecurityEventRepo>,
+    pub content_safety: ContentSafety<DynSecurityEventRepo>,
+    pub geo: GeoInsights<DynStatsRollupRepo>,
+    pub stats_rollups: StatsRollups<DynStatsRollupRepo>,
+    pub usage: UsageReports<DynUsageRepo>,
+    pub quota_alerts: QuotaAlerts<DynUsageRepo>,
+    pub retention: DataRetention<DynRetentionRepo, DynAuditLogRepo, DynAppSettingsRepo>,
+    pub audit: AuditTrail<DynAuditLogRepo>,
+    pub outbox_relay: OutboxRelay<DynOutboxRepo, DynTenantRepo>,
+    pub jobs: JobDashboard<DynOutboxRepo>,
+    pub load_shedder: LoadShedder,
+    pub rate_limiter: RateHybridLimiterStore,
+    pub api_tokens: ApiTokens<DynApiTokenRepo>,
+    pub account_emails: AccountEmails<DynUserRepo, DynOneTimeTokenRepo, DynAppSettingsRepo>,
+    pub route_timeouts: RouteTimeouts,
+    pub cache_policy: CachePolicy,
+    pub route_policy: RoutePolicy,
+    pub body_log: BodyLogPolicy,
+    pub tenants: TenantResolver<DynTenantRepo>,
+    pub domains: DomainVerifier<DynTenantRepo>,
+    pub link_checker: LinkChecker<DynLinkCheckRepo, DynBlogPostRepo, DynAboutRepo>,
+    pub share_counts: ShareCounts<DynShareCountRepo, DynBlogPostRepo>,
+    pub content_sync: ContentSync<DynBlogPostRepo, DynAboutRepo>,
+    pub tag_suggester: TagSuggester<DynBlogPostRepo>,
+    pub title_tests: TitleTests<DynTitleTestRepo, DynBlogPostRepo>,
+    pub redis_pool: Option<SupervisedPool>,
+    pub storage: Option<Arc<dyn ObjectStorage>>,
+    pub storage_links: StorageLinks,
+    pub public_urls: PublicUrls,
+    pub trusted_proxies: TrustedProxies,
 }
 
-pub type AppAuthHandler = AuthHandler<SqlxUserRepo, JwtService>;
+pub type AppAuthHandler = AuthHandler<DynUserRepo, JwtService>;
 
 impl AppState {
     pub fn new(
         config: &settings::AppConfig, 
         pool: sqlx::PgPool
     ) -> Self {
-        let shared_repos = SharedRepositories::new(pool);
+        Self::from_repositories(config, SharedRepositories::new(pool))
+    }
+
+    /// Builds the state from an explicit repository set.
+    /// Used by `new` for Postgres and by handler tests with mock repositories.
+    pub fn from_repositories(
+        config: &settings::AppConfig,
+        shared_repos: SharedRepositories
+    ) -> Self {
         let jwt_service = JwtService::new(config);
 
-        let auth_handler = AuthHandler::new(shared_repos.user_repo, jwt_service);
-        let about_handler = AboutHandler::new(shared_repos.about_repo);
-        let blog_handler = BlogPostHandler::new(shared_repos.blog_post_repo);
-        let contact_handler = ContactMeHandler::new(shared_repos.contact_repo);
-        
-        let redis_pool = config.redis_url.as_ref().and_then(|url| {
-            let cfg = deadpool_redis::Config::from_url(url);
-            cfg.create_pool(Some(Runtime::Tokio1))
-                .map_err(|e| {
-                    tracing::error!("Redis pool creation error: {}", e)
-                })
-                .ok()
-        });
+        let redis_pool = SupervisedPool::from_config(config);
+        let password_hasher = PasswordHasherPool::new(
+            config.password_hash_concurrency,
+            Duration::from_millis(config.password_hash_queue_timeout_ms),
+        );
+
+        let auth_handler = AuthHandler::new(
+            shared_repos.user_repo.clone(),
+            jwt_service,
+            password_hasher.clone(),
+            config.user_content_policy,
+            claims_version_store_from_pool(redis_pool.as_ref()),
+        );
+        let storage = storage_from_config(config);
+        let storage_links = StorageLinks::new(config);
+        let fixtures = ContentFixtures::new(
+            shared_repos.blog_post_repo.clone(),
+            shared_repos.about_repo.clone(),
+            storage.clone(),
+            config,
+        );
+        let post_bundler = PostBundler::new(shared_repos.blog_post_repo.clone(), storage.clone());
+        let edit_locks = PostEditLocks::new(
+            shared_repos.blog_post_repo.clone(),
+            edit_lock_store_from_pool(redis_pool.as_ref()),
+            config,
+        );
+        let link_checker = LinkChecker::new(
+            shared_repos.link_check_repo,
+            shared_repos.blog_post_repo.clone(),
+            shared_repos.about_repo.clone(),
+            link_prober_from_config(config),
+            config,
+        );
+        let share_counts = ShareCounts::new(
+            shared_repos.share_count_repo,
+            shared_repos.blog_post_repo.clone(),
+            share_providers_from_config(config),
+            config,
+        );
+        let content_sync = ContentSync::new(shared_repos.blog_post_repo.clone(), shared_repos.about_repo.clone());
+        let tag_suggester = TagSuggester::new(shared_repos.blog_post_repo.clone(), config);
+        let title_tests = TitleTests::new(shared_repos.title_test_repo, shared_repos.blog_post_repo.clone());
+
+        let prewarmer = ContentPrewarmer::new(
+            shared_repos.blog_post_repo.clone(),
+            shared_repos.changelog_repo.clone(),
+            page_store_from_pool(redis_pool.as_ref()),
+            cdn_purger_from_config(config),
+            config,
+        );
+        let rebuilder = ContentRebuilder::new(prewarmer.clone());
+        let presence = PresenceTracker::new(
+            presence_store_from_pool(redis_pool.as_ref(), config.presence_ttl_secs),
+            config,
+        );
+        let feed_handler = FeedHandler::new(
+            shared_repos.blog_post_repo.clone(),
+            shared_repos.changelog_repo.clone(),
+            config,
+        );
+        let static_exporter = StaticSiteExporter::new(
+            shared_repos.blog_post_repo.clone(),
+            shared_repos.about_repo.clone(),
+            feed_handler.clone(),
+            UsesHandler::new(shared_repos.uses_repo.clone()),
+            storage.clone(),
+            config,
+        );
+        let about_repo = shared_repos.about_repo;
+        let about_handler = AboutHandler::new(about_repo.clone());
+        let schedule_preview = SchedulePreview::new(shared_repos.blog_post_repo.clone(), about_repo.clone());
+        let public_api = PublicApi::new(shared_repos.blog_post_repo.clone(), about_repo.clone(), config);
+        let user_import = UserImport::new(shared_repos.user_repo.clone());
+        let redirects = RedirectRules::new(shared_repos.redirect_rule_repo);
+        let schema = SchemaMigrations::new(shared_repos.schema_repo, bundled_migrations());
+        let backfills = Backfills::new(shared_repos.backfill_repo, bundled_backfills());
+        let series_handler = SeriesHandler::new(shared_repos.blog_post_repo.clone());
+        let blog_handler = BlogPostHandler::new(shared_repos.blog_post_repo, config);
+        let changelog_handler = ChangelogHandler::new(shared_repos.changelog_repo, config);
+        let settings = RuntimeSettings::new(shared_repos.settings_repo);
+        let site_pages = SitePages::new(about_repo, settings.clone());
+        let email_sends = EmailSendCounter::default();
+        let mailer = CountingMailer::wrap(mailer_from_config(config), email_sends.clone());
+        let notifications = NotificationDispatcher::new(shared_repos.notification_preferences_repo, mailer.clone(), config);
+        let contact_cipher = FieldCipher::new(contact_key_provider_from_config(config));
+        let contact_notifier = ContactNotifier::new(
+            shared_repos.contact_repo.clone(),
+            settings.clone(),
+            mailer.clone(),
+            Arc::new(notifications.clone()),
+            contact_cipher.clone(),
+            config,
+        );
+        let audit = AuditTrail::new(shared_repos.audit_repo.clone());
+        let retention = DataRetention::new(
+            shared_repos.retention_repo,
+            shared_repos.audit_repo,
+            settings.clone(),
+            storage.clone(),
+        );
+        let account_emails = AccountEmails::new(
+            shared_repos.user_repo,
+            OneTimeTokens::new(shared_repos.one_time_token_repo, one_time_token_cache_from_pool(redis_pool.as_ref())),
+            settings.clone(),
+            password_hasher,
+            mailer.clone(),
+            claims_version_store_from_pool(redis_pool.as_ref()),
+            config,
+        );
+        let hire_handler = HireHandler::new(shared_repos.hire_repo, settings.clone(), mailer, config);
+        let geo_locator = GeoLocator::from_config(config);
+        let geo = GeoInsights::new(shared_repos.stats_rollup_repo.clone(), geo_locator.clone());
+        let outbound = OutboundLinks::new(
+            shared_repos.outbound_repo,
+            settings.clone(),
+            shared_repos.stats_rollup_repo.clone(),
+            geo_locator.clone(),
+            storage.clone(),
+        );
+        let reading = ReadingAnalytics::new(shared_repos.reading_progress_repo, shared_repos.stats_rollup_repo.clone());
+        let stats_rollups = StatsRollups::new(shared_repos.stats_rollup_repo, config);
+        let quota_alerts = QuotaAlerts::new(shared_repos.usage_repo.clone(), Arc::new(notifications.clone()), config);
+        let usage = UsageReports::new(
+            shared_repos.usage_repo,
+            storage.clone(),
+            redis_usage_probe_from_pool(redis_pool.as_ref()),
+            email_sends,
+        );
+        let content_safety = ContentSafety::new(shared_repos.security_event_repo.clone(), geo_locator.clone(), config);
+        let honeytokens = HoneytokenMonitor::new(
+            shared_repos.security_event_repo,
+            IpBanList::new(Duration::from_secs(config.honeytoken_ban_secs)),
+            geo_locator.clone(),
+        );
+        let contact_handler = ContactMeHandler::new(
+            shared_repos.contact_repo,
+            ContactIngestQueue::new(config.contact_queue_capacity),
+            geo_locator,
+            contact_cipher,
+        );
+        let uses_handler = UsesHandler::new(shared_repos.uses_repo);
+        let bookmark_handler = BookmarkHandler::new(shared_repos.bookmark_repo, link_preview_fetcher_from_config(config));
+        let feature_flags = FeatureFlags::new(shared_repos.feature_flag_repo);
+        let config_history = ConfigHistory::new(shared_repos.config_history_repo, settings.clone(), feature_flags.clone());
+        let captcha = CaptchaGuard::new(captcha_verifier_from_config(config), feature_flags.clone());
+        let site_gate = SiteGate::from_config(config);
+        let visitor_tokens = VisitorTokens::from_config(config);
+        let load_shedder = LoadShedder::new(
+            config.scope_concurrency_limits(),
+            Duration::from_millis(config.scope_queue_timeout_ms),
+        );
+        let rate_limiter = RateHybridLimiterStore::from_config(
+            config,
+            rate_limit_state_store_from_pool(redis_pool.as_ref()),
+        );
+        let api_tokens = ApiTokens::new(
+            shared_repos.api_token_repo,
+            api_token_usage_counter_from_pool(redis_pool.as_ref()),
+            config,
+        );
+        let route_timeouts = RouteTimeouts::from_config(config);
+        let cache_policy = CachePolicy::from_config(config);
+        let route_policy = RoutePolicy::from_config(config);
+        let body_log = BodyLogPolicy::from_config(config);
+        let tenants = TenantResolver::new(shared_repos.tenant_repo, config.strict_tenant_hosts);
+        let domains = DomainVerifier::new(tenants.clone(), txt_resolver_from_config(config));
+        let jobs = JobDashboard::new(shared_repos.outbox_repo.clone());
+        let outbox_relay = OutboxRelay::new(shared_repos.outbox_repo, tenants.clone())
+            .subscribe(Arc::new(contact_notifier.clone()))
+            .subscribe(Arc::new(hire_handler.clone()))
+            .with_alerts(Arc::new(notifications.clone()));
 
         AppState { 
             auth_handler,
             about_handler,
             blog_handler,
+            series_handler,
             contact_handler,
-            redis_pool 
+            uses_handler,
+            bookmark_handler,
+            changelog_handler,
+            feed_handler,
+            prewarmer,
+            rebuilder,
+            static_exporter,
+            site_pages,
+            schedule_preview,
+            user_import,
+            redirects,
+            public_api,
+            schema,
+            backfills,
+            fixtures,
+            post_bundler,
+            edit_locks,
+            presence,
+            feature_flags,
+            captcha,
+            site_gate,
+            visitor_tokens,
+            settings,
+            config_history,
+            contact_notifier,
+            notifications,
+            hire_handler,
+            outbound,
+            reading,
+            honeytokens,
+            content_safety,
+            geo,
+            stats_rollups,
+            usage,
+            quota_alerts,
+            retention,
+            audit,
+            outbox_relay,
+            jobs,
+            load_shedder,
+            rate_limiter,
+            api_tokens,
+            account_emails,
+            route_timeouts,
+            cache_policy,
+            route_policy,
+            body_log,
+            tenants,
+            domains,
+            link_checker,
+            share_counts,
+            content_sync,
+            tag_suggester,
+            title_tests,
+            redis_pool,
+            storage,
+            storage_links,
+            public_urls: PublicUrls::from_config(config),
+            trusted_proxies: TrustedProxies::from_config(config),
         }
     }
 
-    /// Helper method to access Redis
+    /// Runs `op` on a pooled Redis connection, whatever the topology,
+    /// retrying it on a fresh connection when a failover made it fail
     pub async fn with_redis<F, Fut, T>(&self, op: F) -> Result<T, AuthError>
-    where 
-        F: FnOnce(deadpool_redis::Connection) -> Fut,
-        Fut: Future<Output = Result<T, AuthError>>,
+    where
+        F: Fn(RedisConnection) -> Fut,
+        Fut: Future<Output = redis::RedisResult<T>>,
     {
-        if let Some(pool) = &self.redis_pool {
-            let conn = pool.get().await
-                .map_err(|e| AuthError::RedisConnection(e.to_string()))?;
-            op(conn).await
-        } else {
-            Err(AuthError::RedisNotConfigured)
-        }
+        let pool = self.redis_pool.as_ref().ok_or(AuthError::RedisNotConfigured)?;
+        pool.run(op).await.map_err(|e| match e {
+            RedisRunError::Connection(e) => AuthError::RedisConnection(e.to_string()),
+            RedisRunError::Command(e) => AuthError::RedisOperation(e.to_string()),
+        })
     }
 
-    /// Automatically increments a Redis counter with TTL
+    /// Automatically increments a Redis counter with TTL. Returns the count
+    /// and the seconds until the counter expires.
     pub async fn redis_incr_with_ttl(
         &self,
         key: &str,
         ttl_secs: usize,
-    ) -> Result<u32, AuthError> {
-        self.with_redis(|mut conn| async move {
-            let current = self.incr_with_ttl(&mut conn, key, ttl_secs)
-                .await
-                .map_err(|e| AuthError::RedisOperation(e.to_string()))?;
-            Ok(current)
-        })
-        .await
+    ) -> Result<(u32, u64), AuthError> {
+        self.with_redis(|mut conn| async move { self.incr_with_ttl(&mut conn, key, ttl_secs).await }).await
     }
 
-    /// Atomically increments a counter and sets TTL if it's the first increment.
-    /// Returns the current counter value after increment.
+    /// Atomically increments a counter and sets TTL if it's the first increment,
+    /// or if the counter somehow lost it. Returns the counter value after
+    /// increment and its remaining TTL.
     pub async fn incr_with_ttl<C>(
         &self,
         conn: &mut C,
         key: &str,
         ttl_secs: usize,
-    ) -> redis::RedisResult<u32>
+    ) -> redis::RedisResult<(u32, u64)>
     where
         C: AsyncCommands + Send,
     {
         let script = r#"
             local current = redis.call("INCR", KEYS[1])
-            if current == 1 then
+            local ttl = redis.call("TTL", KEYS[1])
+            if current == 1 or ttl < 0 then
                 redis.call("EXPIRE", KEYS[1], ARGV[1])
+                ttl = tonumber(ARGV[1])
             end
-            return current
+            return {current, ttl}
         "#;
 
-        let cur: i64 = redis::Script::new(script)
+        let (cur, ttl): (i64, i64) = redis::Script::new(script)
             .key(key)
             .arg(ttl_secs)
             .invoke_async(conn)
             .await?;
 
-        Ok(cur as u32)
+        Ok((cur as u32, ttl.max(0) as u64))
     }
 
 
@@ -162,25 +464,12 @@ impl RedisService for AppState {
         }
         
         self.with_redis(|mut conn| async move {
-            conn.set_ex::<_, _, ()>(
-                format!("{}:{}", prefix, token),
-                "1",
-                ttl_seconds as u64
-            ).await
-            .map_err(|e| AuthError::RedisOperation(e.to_string()))?;
-        
-            Ok(())
+            conn.set_ex::<_, _, ()>(format!("{}:{}", prefix, token), "1", ttl_seconds as u64).await
         }).await
     }
 
     async fn is_token_revoked(&self, prefix: &str, token: &str) -> Result<bool, AuthError> {
-        self.with_redis(|mut conn| async move {
-            let exists: bool = conn
-                .exists(format!("{}:{}", prefix, token))
-                .await
-                .map_err(|e| AuthError::RedisOperation(e.to_string()))?;
-            Ok(exists)
-        }).await
+        self.with_redis(|mut conn| async move { conn.exists::<_, bool>(format!("{}:{}", prefix, token)).await }).await
     }
 }
 
@@ -191,14 +480,11 @@ pub enum TokenCheckMode {
 }
 
 pub async fn is_token_invalid(
-    redis_pool: &RedisPool,
+    redis_pool: &SupervisedPool,
     key: &str,
     mode: TokenCheckMode,
 ) -> Result<bool, AuthError> {
-    let mut conn = redis_pool.get().await
-        .map_err(|e| AuthError::RedisOperation(e.to_string()))?;
-
-    let exists = conn.exists(key).await
+    let exists: bool = redis_pool.run(|mut conn| async move { conn.exists(key).await }).await
         .map_err(|e| AuthError::RedisOperation(e.to_string()))?;
 
     Ok(match mode {
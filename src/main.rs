@@ -1,18 +1,460 @@
-use std::env;
+use std::{env, path::{Path, PathBuf}, sync::atomic::{AtomicUsize, Ordering}, time::{Duration, Instant}};
 
-use actix_web::{middleware::NormalizePath, web, App, HttpServer};
+use actix_web::{http::{KeepAlive, StatusCode}, middleware::{ErrorHandlers, NormalizePath}, web, App, HttpServer};
+use sqlx::PgPool;
 use tracing_actix_web::TracingLogger;
 use tracing_subscriber::{fmt, EnvFilter, prelude::*};
 use portfolio_backend::{
-    background_task::start_purge_task, 
-    db::postgres::create_pool, 
+    background_task::{
+        start_api_token_refresh_task, start_bookmark_preview_task, start_contact_digest_task, start_feature_flag_refresh_task, start_link_check_task, start_one_time_token_cleanup_task, start_purge_task, start_redirect_refresh_task, start_share_count_task, start_tag_index_task,
+        start_backfill_task, start_contact_ingest_task, start_domain_verification_task, start_error_report_task, start_outbox_relay_task, start_redis_supervisor_task, start_retention_task, start_stats_rollup_task, start_tenant_refresh_task, start_usage_collector_task, start_title_test_refresh_task,
+    }, 
+    db::{migrations::run_migrations, postgres::{create_pool, without_statement_timeout}},
+    doctor::{self, Database, Depth, DoctorReport},
+    entities::{
+        content_fixture::ContentFixture, stats_rollup::DEFAULT_BACKFILL_DAYS, tenant::{Tenant, DEFAULT_TENANT_ID},
+        user_import::UserImportFormat,
+    },
     graceful_shutdown::shutdown_signal, 
-    middlewares::auth::AuthMiddleware, 
+    reporting::{reporter::{install_error_reporter, install_panic_hook}, sentry::error_reporter_from_config},
+    handlers::fallback::method_not_allowed,
+    middlewares::{
+        auth::AuthMiddleware, body_log::BodyLog, cache_control::CacheControl, error_report::ErrorReport, forwarded_prefix::ForwardedPrefix, ip_ban::IpBanMiddleware, site_gate::SiteGateMiddleware,
+        tenant::TenantMiddleware,
+    }, 
     routes::configure_routes, 
     settings::AppConfig, 
+    shared_repos::SharedRepositories,
+    use_cases::{
+        contact::REKEY_BATCH_SIZE,
+        schema::{DEFAULT_MIGRATION_WAIT, MIGRATION_POLL_INTERVAL},
+        user_import::parse_user_import,
+    },
+    utils::redact::{RedactingMakeWriter, Redactor},
     AppState
 };
 
+/// Storage backend selected with `--storage=<postgres|memory>`.
+#[derive(Debug, PartialEq)]
+enum Storage {
+    Postgres,
+    Memory,
+}
+
+fn storage_from_args() -> Storage {
+    let value = env::args()
+        .find_map(|arg| arg.strip_prefix("--storage=").map(str::to_owned))
+        .unwrap_or_else(|| "postgres".to_string());
+
+    match value.as_str() {
+        "postgres" => Storage::Postgres,
+        "memory" => Storage::Memory,
+        other => {
+            eprintln!("Unknown storage backend '{}', expected 'postgres' or 'memory'", other);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// `--doctor` prints a text report, `--doctor=json` a JSON one
+#[derive(Debug, PartialEq)]
+enum DoctorFormat {
+    Text,
+    Json,
+}
+
+fn doctor_from_args() -> Option<DoctorFormat> {
+    env::args().find_map(|arg| match arg.as_str() {
+        "--doctor" | "--doctor=text" => Some(DoctorFormat::Text),
+        "--doctor=json" => Some(DoctorFormat::Json),
+        _ => None,
+    })
+}
+
+/// Prints the report and exits: 0 when every check passed, 1 otherwise
+fn finish_doctor(report: &DoctorReport, format: DoctorFormat) -> ! {
+    match format {
+        DoctorFormat::Text => print!("{}", report.to_text()),
+        DoctorFormat::Json => println!("{}", serde_json::to_string_pretty(report).unwrap_or_default()),
+    }
+
+    std::process::exit(if report.passed { 0 } else { 1 });
+}
+
+/// One-shot content fixture commands; both run against `--tenant=<slug>`,
+/// or the default tenant, and exit instead of starting the server
+#[derive(Debug, PartialEq)]
+enum FixtureCommand {
+    /// `--export-fixtures[=<path>]`
+    Export(Option<PathBuf>),
+    /// `--load-fixtures=<path>`, with `--dry-run` to only report changes
+    Load { path: PathBuf, dry_run: bool },
+}
+
+fn fixture_command_from_args() -> Option<FixtureCommand> {
+    let dry_run = env::args().any(|arg| arg == "--dry-run");
+
+    env::args().find_map(|arg| {
+        if arg == "--export-fixtures" {
+            return Some(FixtureCommand::Export(None));
+        }
+        if let Some(path) = arg.strip_prefix("--export-fixtures=") {
+            return Some(FixtureCommand::Export(Some(PathBuf::from(path))));
+        }
+        arg.strip_prefix("--load-fixtures=")
+            .map(|path| FixtureCommand::Load { path: PathBuf::from(path), dry_run })
+    })
+}
+
+/// Tenant named with `--tenant=<slug>`, or the default one
+fn tenant_from_args(state: &AppState) -> Tenant {
+    let slug = env::args().find_map(|arg| arg.strip_prefix("--tenant=").map(str::to_owned));
+
+    let tenant = match &slug {
+        Some(slug) => state.tenants.all().into_iter().find(|t| &t.slug == slug),
+        None => state.tenants.get(&DEFAULT_TENANT_ID),
+    };
+
+    tenant.unwrap_or_else(|| {
+        eprintln!("Unknown tenant '{}'", slug.as_deref().unwrap_or("default"));
+        std::process::exit(2);
+    })
+}
+
+/// Runs a fixture command and exits: 0 on success, 1 otherwise
+async fn run_fixture_command(state: &AppState, command: FixtureCommand) -> ! {
+    let tenant = tenant_from_args(state);
+
+    let result = match command {
+        FixtureCommand::Export(path) => export_fixtures(state, &tenant, path).await,
+        FixtureCommand::Load { path, dry_run } => load_fixtures(state, &tenant, &path, dry_run).await,
+    };
+
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn export_fixtures(state: &AppState, tenant: &Tenant, path: Option<PathBuf>) -> Result<(), String> {
+    let fixture = state.fixtures.export(tenant).await.map_err(|e| e.to_string())?;
+    let path = path.unwrap_or_else(|| {
+        PathBuf::from("fixtures").join(format!(
+            "content-{}-{}.json",
+            tenant.slug,
+            fixture.exported_at.format("%Y%m%d%H%M%S")
+        ))
+    });
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    }
+
+    let json = serde_json::to_string_pretty(&fixture).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+
+    println!("Exported {} post(s) from '{}' to {}", fixture.posts.len(), tenant.slug, path.display());
+    Ok(())
+}
+
+async fn load_fixtures(state: &AppState, tenant: &Tenant, path: &Path, dry_run: bool) -> Result<(), String> {
+    let raw = std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let fixture: ContentFixture = serde_json::from_slice(&raw)
+        .map_err(|e| format!("{} is not a content fixture: {}", path.display(), e))?;
+
+    let report = state.fixtures.load(tenant.id, &fixture, dry_run).await.map_err(|e| e.to_string())?;
+    println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    Ok(())
+}
+
+/// `--rebuild` regenerates cached post pages, list pages and feeds for
+/// `--tenant=<slug>`, or every tenant, instead of starting the server
+fn rebuild_from_args() -> bool {
+    env::args().any(|arg| arg == "--rebuild")
+}
+
+/// Rebuilds each tenant in turn, reporting every batch on stderr, and
+/// exits: 0 when all of them succeeded, 1 otherwise
+async fn run_rebuild(state: &AppState) -> ! {
+    let tenants = if env::args().any(|arg| arg.starts_with("--tenant=")) {
+        vec![tenant_from_args(state)]
+    } else {
+        state.tenants.all()
+    };
+
+    if !state.prewarmer.page_cache_enabled() {
+        eprintln!("No Redis configured: no pages are cached, only CDN purges will be sent");
+    }
+
+    let mut failed = false;
+    for tenant in &tenants {
+        let result = match state.rebuilder.begin(tenant) {
+            Ok(_) => {
+                state.rebuilder
+                    .run(tenant, |progress| {
+                        eprintln!(
+                            "[{}] {} batch(es), {} post(s), {} page(s) stored",
+                            progress.tenant, progress.batches, progress.posts, progress.pages
+                        );
+                    })
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(done) => println!(
+                "Rebuilt '{}': {} post(s) in {} batch(es), {} page(s) stored",
+                done.tenant, done.posts, done.batches, done.pages
+            ),
+            Err(e) => {
+                eprintln!("Rebuild of '{}' failed: {}", tenant.slug, e);
+                failed = true;
+            }
+        }
+    }
+
+    std::process::exit(if failed { 1 } else { 0 });
+}
+
+/// Renders every tenant's listings and newest posts into the page cache
+/// before the server binds. Failures and the timeout only leave pages
+/// cold until the next publish or rebuild; startup goes on either way.
+async fn warm_page_cache(state: &AppState, timeout: Duration) {
+    if !state.prewarmer.page_cache_enabled() {
+        tracing::info!("Startup warmup skipped: no Redis configured");
+        return;
+    }
+
+    let started = Instant::now();
+    let stored = AtomicUsize::new(0);
+    let warm = async {
+        for tenant in state.tenants.all() {
+            match state.prewarmer.warm_startup(&tenant).await {
+                Ok(pages) => {
+                    stored.fetch_add(pages, Ordering::Relaxed);
+                }
+                Err(e) => tracing::warn!(tenant = %tenant.slug, "Startup warmup failed: {}", e),
+            }
+        }
+    };
+
+    if tokio::time::timeout(timeout, warm).await.is_err() {
+        tracing::warn!("Startup warmup stopped after {}s, starting with a partly cold cache", timeout.as_secs());
+    }
+    tracing::info!("Warmed {} page(s) in {} ms", stored.load(Ordering::Relaxed), started.elapsed().as_millis());
+}
+
+/// `--backfill-stats[=<days>]` rolls up the last `days` days of analytics
+/// (`DEFAULT_BACKFILL_DAYS` when no count is given) for `--tenant=<slug>`,
+/// or every tenant, instead of starting the server
+fn backfill_stats_from_args() -> Option<u32> {
+    env::args().find_map(|arg| {
+        if arg == "--backfill-stats" {
+            return Some(DEFAULT_BACKFILL_DAYS);
+        }
+
+        let days = arg.strip_prefix("--backfill-stats=")?;
+        match days.parse() {
+            Ok(days) if (1..=3650).contains(&days) => Some(days),
+            _ => {
+                eprintln!("--backfill-stats expects a number of days between 1 and 3650");
+                std::process::exit(2);
+            }
+        }
+    })
+}
+
+/// Backfills each tenant in turn and exits: 0 when all of them
+/// succeeded, 1 otherwise
+async fn run_backfill_stats(state: &AppState, days: u32) -> ! {
+    let tenants = if env::args().any(|arg| arg.starts_with("--tenant=")) {
+        vec![tenant_from_args(state)]
+    } else {
+        state.tenants.all()
+    };
+
+    let mut failed = false;
+    for tenant in &tenants {
+        match state.stats_rollups.roll_up_days(tenant.id, days).await {
+            Ok(run) => println!(
+                "Rolled up '{}' from {} to {}: {} row(s) written",
+                tenant.slug, run.from, run.to, run.rows
+            ),
+            Err(e) => {
+                eprintln!("Stats backfill of '{}' failed: {}", tenant.slug, e);
+                failed = true;
+            }
+        }
+    }
+
+    std::process::exit(if failed { 1 } else { 0 });
+}
+
+/// `--import-users=<path>` creates the accounts in a CSV or JSON user export
+/// (told apart by the file extension) in `--tenant=<slug>`, or the default
+/// tenant; `--dry-run` only reports what would happen
+fn import_users_from_args() -> Option<(PathBuf, bool)> {
+    let dry_run = env::args().any(|arg| arg == "--dry-run");
+    env::args().find_map(|arg| arg.strip_prefix("--import-users=").map(|path| (PathBuf::from(path), dry_run)))
+}
+
+/// Runs a user import, prints its report and exits: 0 on success, 1 otherwise
+async fn run_user_import(state: &AppState, path: &Path, dry_run: bool) -> ! {
+    let tenant = tenant_from_args(state);
+
+    match import_users(state, &tenant, path, dry_run).await {
+        Ok(()) => std::process::exit(0),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn import_users(state: &AppState, tenant: &Tenant, path: &Path, dry_run: bool) -> Result<(), String> {
+    let raw = std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let rows = parse_user_import(UserImportFormat::from_path(path), &raw)
+        .map_err(|e| format!("{} is not a user export: {}", path.display(), e))?;
+
+    let report = state.user_import.import(tenant.id, rows, dry_run).await.map_err(|e| e.to_string())?;
+    println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    Ok(())
+}
+
+/// `--rekey-contact-messages` seals every contact message, on every tenant,
+/// with the primary contact encryption key instead of starting the server
+fn rekey_contact_messages_from_args() -> bool {
+    env::args().any(|arg| arg == "--rekey-contact-messages")
+}
+
+/// Re-seals contact messages, prints the report and exits: 0 when every
+/// row is sealed with the primary key, 1 otherwise
+async fn run_rekey_contact_messages(state: &AppState) -> ! {
+    match state.contact_handler.rekey_contact_messages(REKEY_BATCH_SIZE).await {
+        Ok(report) => {
+            println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+            std::process::exit(if report.failed.is_empty() { 0 } else { 1 });
+        }
+        Err(e) => {
+            eprintln!("Contact message rekey failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--migrate-only` applies pending migrations and exits instead of starting
+/// the server, for a deploy step that runs before new instances boot
+fn migrate_only_from_args() -> bool {
+    env::args().any(|arg| arg == "--migrate-only")
+}
+
+/// `--wait-for-migrations[=<secs>]` holds startup until another instance or
+/// a `--migrate-only` job has applied every bundled migration
+/// (`DEFAULT_MIGRATION_WAIT` when no timeout is given)
+fn wait_for_migrations_from_args() -> Option<Duration> {
+    env::args().find_map(|arg| {
+        if arg == "--wait-for-migrations" {
+            return Some(DEFAULT_MIGRATION_WAIT);
+        }
+
+        let secs = arg.strip_prefix("--wait-for-migrations=")?;
+        match secs.parse() {
+            Ok(secs) if (1..=86_400).contains(&secs) => Some(Duration::from_secs(secs)),
+            _ => {
+                eprintln!("--wait-for-migrations expects a number of seconds between 1 and 86400");
+                std::process::exit(2);
+            }
+        }
+    })
+}
+
+/// `--allow-drift` boots even when the database holds migrations this build
+/// does not know or applied ones that have changed since. Meant for the old
+/// release during a blue/green deploy, once the new one has migrated.
+fn allow_drift_from_args() -> bool {
+    env::args().any(|arg| arg == "--allow-drift")
+}
+
+/// Applies pending migrations, prints the resulting schema status and
+/// exits: 0 when the schema is up to date, 1 otherwise
+async fn run_migrate_only(state: &AppState, pool: Option<&PgPool>, allow_drift: bool) -> ! {
+    let Some(pool) = pool else {
+        eprintln!("--migrate-only requires --storage=postgres");
+        std::process::exit(2);
+    };
+
+    match state.schema.status().await {
+        Ok(status) if status.has_drift() && !allow_drift => {
+            eprintln!("Refusing to migrate a drifted schema ({}), pass --allow-drift to proceed", status.summary());
+            std::process::exit(1);
+        }
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Cannot read the migration history: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    if let Err(e) = run_migrations(pool, allow_drift).await {
+        eprintln!("Migration failed: {}", e);
+        std::process::exit(1);
+    }
+
+    match state.schema.status().await {
+        Ok(status) => {
+            println!("{}", serde_json::to_string_pretty(&status).unwrap_or_default());
+            std::process::exit(if status.is_up_to_date() { 0 } else { 1 });
+        }
+        Err(e) => {
+            eprintln!("Cannot read the migration history: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Waits for pending migrations when asked to, then refuses to continue on
+/// a drifted schema unless `--allow-drift` was given. Pending migrations
+/// are left to the self-check, which fails on them.
+async fn check_schema(state: &AppState, wait: Option<Duration>, allow_drift: bool) {
+    let status = match wait {
+        Some(timeout) => state.schema.wait_until_up_to_date(timeout, MIGRATION_POLL_INTERVAL).await,
+        None => state.schema.status().await,
+    };
+
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            tracing::error!("Schema check failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if !status.has_drift() {
+        return;
+    }
+    if !allow_drift {
+        tracing::error!("Schema drift detected ({}), pass --allow-drift to start anyway", status.summary());
+        std::process::exit(1);
+    }
+    tracing::warn!("Starting despite schema drift: {}", status.summary());
+}
+
+#[cfg(feature = "in-memory")]
+fn in_memory_repositories() -> SharedRepositories {
+    tracing::warn!("Using in-memory storage; all data is lost on shutdown");
+    SharedRepositories::in_memory()
+}
+
+#[cfg(not(feature = "in-memory"))]
+fn in_memory_repositories() -> SharedRepositories {
+    tracing::error!("--storage=memory requires building with `--features in-memory`");
+    std::process::exit(1);
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv::dotenv().ok();
@@ -20,14 +462,46 @@ async fn main() -> std::io::Result<()> {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,actix_web=info,portfolio_backend=debug"));
 
-    if std::env::var("RUST_LOG_JSON").is_ok() {
+    let storage = storage_from_args();
+    let doctor_format = doctor_from_args();
+    let fixture_command = fixture_command_from_args();
+    let rebuild = rebuild_from_args();
+    let backfill_stats = backfill_stats_from_args();
+    let user_import = import_users_from_args();
+    let rekey_contact_messages = rekey_contact_messages_from_args();
+    let migrate_only = migrate_only_from_args();
+    let wait_for_migrations = wait_for_migrations_from_args();
+    let allow_drift = allow_drift_from_args();
+
+    if doctor_format.is_some()
+        || fixture_command.is_some()
+        || rebuild
+        || backfill_stats.is_some()
+        || user_import.is_some()
+        || rekey_contact_messages
+        || migrate_only
+    {
+        // Reports go to stdout, so keep logs out of their way
+        fmt()
+            .with_env_filter(env_filter)
+            .with_writer(RedactingMakeWriter::new(std::io::stderr, Redactor::with_defaults()))
+            .with_target(false)
+            .compact()
+            .init();
+    } else if std::env::var("RUST_LOG_JSON").is_ok() {
         tracing_subscriber::registry()
             .with(env_filter)
-            .with(fmt::layer().json().flatten_event(true))
+            .with(
+                fmt::layer()
+                    .json()
+                    .flatten_event(true)
+                    .with_writer(RedactingMakeWriter::new(std::io::stdout, Redactor::with_defaults())),
+            )
             .init();
     } else {
         fmt()
             .with_env_filter(env_filter)
+            .with_writer(RedactingMakeWriter::new(std::io::stdout, Redactor::with_defaults()))
             .with_target(false)
             .compact()
             .init();
@@ -39,19 +513,126 @@ async fn main() -> std::io::Result<()> {
             cfg
         },
         Err(e) => {
+            if let Some(format) = doctor_format {
+                finish_doctor(&DoctorReport::config_error(e), format);
+            }
             tracing::error!("Configuration error: {}", e);
             std::process::exit(1);
         }
     };
 
-    let pool = create_pool(&config.database_url)
-        .await
-        .expect("Failed to create database connection pool");
+    let error_transport = error_reporter_from_config(&config).map(|(reporter, transport)| {
+        install_error_reporter(reporter);
+        tracing::info!("Error reporting enabled");
+        transport
+    });
+    install_panic_hook();
+
+    if let Some(format) = doctor_format {
+        let report = doctor::run_full(&config, storage == Storage::Postgres).await;
+        finish_doctor(&report, format);
+    }
+
+    let db_pool = match storage {
+        Storage::Postgres => Some(
+            create_pool(&config.database_url, Duration::from_millis(config.db_statement_timeout_ms))
+                .await
+                .expect("Failed to create database connection pool")
+        ),
+        Storage::Memory => None,
+    };
+
+    let shared_repos = match &db_pool {
+        Some(pool) => SharedRepositories::new(pool.clone()),
+        None => in_memory_repositories(),
+    };
 
     let app_state = web::Data::new(
-        AppState::new(&config, pool.clone())
+        AppState::from_repositories(&config, shared_repos)
     );
 
+    if migrate_only {
+        run_migrate_only(&app_state, db_pool.as_ref(), allow_drift).await;
+    }
+
+    check_schema(&app_state, wait_for_migrations, allow_drift).await;
+
+    // Abbreviated `--doctor` run: refuse to start on a broken schema or keys
+    // rather than failing on the first request
+    let database = db_pool.as_ref().map_or(Database::InMemory, Database::Postgres);
+    let report = doctor::run(
+        &config,
+        database,
+        app_state.redis_pool.as_ref(),
+        app_state.storage.as_deref(),
+        Depth::Quick,
+    ).await;
+    report.log();
+    if !report.passed {
+        tracing::error!("Startup self-check failed, run with --doctor for the full report");
+        std::process::exit(1);
+    }
+
+    if app_state.site_gate.is_some() {
+        tracing::info!("Soft launch mode enabled: public content requires the site passphrase");
+    }
+
+    if let Err(e) = app_state.feature_flags.refresh().await {
+        tracing::warn!("Initial feature flag load failed, all flags disabled: {}", e);
+    }
+
+    match app_state.tenants.refresh().await {
+        Ok(count) => tracing::info!("Serving {} tenant(s)", count),
+        Err(e) => tracing::warn!("Initial tenant load failed, every host is unresolved: {}", e),
+    }
+
+    if let Err(e) = app_state.settings.refresh().await {
+        tracing::warn!("Initial runtime settings load failed, using config defaults: {}", e);
+    }
+
+    if let Err(e) = app_state.title_tests.refresh().await {
+        tracing::warn!("Initial title test load failed, posts show their own titles: {}", e);
+    }
+
+    match app_state.api_tokens.refresh().await {
+        Ok(count) => tracing::info!("Loaded {} issued API token(s)", count),
+        Err(e) => tracing::warn!("Initial API token load failed, only configured tokens are accepted: {}", e),
+    }
+
+    match app_state.redirects.refresh().await {
+        Ok(count) => tracing::info!("Loaded {} redirect rule(s)", count),
+        Err(e) => tracing::warn!("Initial redirect rule load failed, unknown paths return 404: {}", e),
+    }
+
+    let restored = app_state.rate_limiter.restore().await;
+    if restored > 0 {
+        tracing::info!("Restored {} rate limit bucket(s) from Redis", restored);
+    }
+
+    if let Some(command) = fixture_command {
+        without_statement_timeout(run_fixture_command(&app_state, command)).await;
+    }
+
+    if rebuild {
+        without_statement_timeout(run_rebuild(&app_state)).await;
+    }
+
+    if let Some(days) = backfill_stats {
+        without_statement_timeout(run_backfill_stats(&app_state, days)).await;
+    }
+
+    if let Some((path, dry_run)) = user_import {
+        without_statement_timeout(run_user_import(&app_state, &path, dry_run)).await;
+    }
+
+    if rekey_contact_messages {
+        without_statement_timeout(run_rekey_contact_messages(&app_state)).await;
+    }
+
+    if config.startup_warmup {
+        warm_page_cache(&app_state, Duration::from_secs(config.startup_warmup_timeout_secs)).await;
+    }
+
     let server_addr = format!("{}:{}", config.host, config.port);
     
     tracing::info!(
@@ -59,17 +640,44 @@ async fn main() -> std::io::Result<()> {
         env!("CARGO_PKG_VERSION"),
         server_addr
     );
+    tracing::info!(
+        workers = config.worker_count,
+        backlog = config.server_backlog,
+        keep_alive_secs = config.server_keep_alive_secs,
+        client_request_timeout_ms = config.server_client_request_timeout_ms,
+        max_connections = config.server_max_connections,
+        "HTTP server tuning"
+    );
+
+    let keep_alive = match config.server_keep_alive_secs {
+        0 => KeepAlive::Disabled,
+        secs => KeepAlive::Timeout(Duration::from_secs(secs)),
+    };
     
     let app_state_clone = app_state.clone();
 
     let server = HttpServer::new(move || {
         App::new()
             .app_data(app_state.clone())
+            .wrap(ErrorHandlers::new().handler(StatusCode::METHOD_NOT_ALLOWED, method_not_allowed))
+            .wrap(ErrorReport)
+            .wrap(BodyLog)
             .wrap(TracingLogger::default())
             .wrap(NormalizePath::trim())
             .wrap(AuthMiddleware)
+            .wrap(SiteGateMiddleware)
+            .wrap(TenantMiddleware)
+            .wrap(CacheControl)
+            .wrap(IpBanMiddleware)
+            .wrap(ForwardedPrefix::new(app_state.public_urls.base_path()))
             .configure(configure_routes)
     })
+    .workers(config.worker_count)
+    .backlog(config.server_backlog)
+    .keep_alive(keep_alive)
+    // Zero disables the limit in actix as well
+    .client_request_timeout(Duration::from_millis(config.server_client_request_timeout_ms))
+    .max_connections(config.server_max_connections)
     .bind(server_addr)?
     .run();
 
@@ -80,10 +688,130 @@ async fn main() -> std::io::Result<()> {
     let server_handle = server.handle();
 
     // Keep a JoinHandle so we can await the task on shutdown
-    let purge_handle = tokio::spawn(start_purge_task(
+    let purge_handle = tokio::spawn(without_statement_timeout(start_purge_task(
         app_state_clone.auth_handler.user_repo.clone(),
         shutdown_receiver,
-    ));
+    )));
+
+    let flag_refresh_handle = tokio::spawn(without_statement_timeout(start_feature_flag_refresh_task(
+        app_state_clone.feature_flags.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let title_test_refresh_handle = tokio::spawn(without_statement_timeout(start_title_test_refresh_task(
+        app_state_clone.title_tests.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let api_token_refresh_handle = tokio::spawn(without_statement_timeout(start_api_token_refresh_task(
+        app_state_clone.api_tokens.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let redirect_refresh_handle = tokio::spawn(without_statement_timeout(start_redirect_refresh_task(
+        app_state_clone.redirects.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let tenant_refresh_handle = tokio::spawn(without_statement_timeout(start_tenant_refresh_task(
+        app_state_clone.tenants.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let domain_verification_handle = tokio::spawn(without_statement_timeout(start_domain_verification_task(
+        app_state_clone.domains.clone(),
+        Duration::from_secs(config.domain_verification_interval_secs.max(1)),
+        shutdown_sender.subscribe(),
+    )));
+
+    let digest_handle = tokio::spawn(without_statement_timeout(start_contact_digest_task(
+        app_state_clone.contact_notifier.clone(),
+        app_state_clone.tenants.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let outbox_handle = tokio::spawn(without_statement_timeout(start_outbox_relay_task(
+        app_state_clone.outbox_relay.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let contact_ingest_handle = tokio::spawn(without_statement_timeout(start_contact_ingest_task(
+        app_state_clone.contact_handler.clone(),
+        app_state_clone.outbox_relay.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let link_check_handle = tokio::spawn(without_statement_timeout(start_link_check_task(
+        app_state_clone.link_checker.clone(),
+        app_state_clone.tenants.clone(),
+        Duration::from_secs(config.link_check_interval_secs),
+        shutdown_sender.subscribe(),
+    )));
+
+    let share_count_handle = tokio::spawn(without_statement_timeout(start_share_count_task(
+        app_state_clone.share_counts.clone(),
+        app_state_clone.tenants.clone(),
+        Duration::from_secs(config.share_count_interval_secs),
+        shutdown_sender.subscribe(),
+    )));
+
+    let tag_index_handle = tokio::spawn(without_statement_timeout(start_tag_index_task(
+        app_state_clone.tag_suggester.clone(),
+        app_state_clone.tenants.clone(),
+        Duration::from_secs(config.tag_index_refresh_secs),
+        shutdown_sender.subscribe(),
+    )));
+
+    let retention_handle = tokio::spawn(without_statement_timeout(start_retention_task(
+        app_state_clone.retention.clone(),
+        app_state_clone.tenants.clone(),
+        Duration::from_secs(config.retention_interval_secs),
+        shutdown_sender.subscribe(),
+    )));
+
+    let stats_rollup_handle = tokio::spawn(without_statement_timeout(start_stats_rollup_task(
+        app_state_clone.stats_rollups.clone(),
+        app_state_clone.tenants.clone(),
+        Duration::from_secs(config.stats_rollup_interval_secs),
+        shutdown_sender.subscribe(),
+    )));
+
+    let usage_collector_handle = tokio::spawn(without_statement_timeout(start_usage_collector_task(
+        app_state_clone.usage.clone(),
+        app_state_clone.quota_alerts.clone(),
+        app_state_clone.api_tokens.clone(),
+        app_state_clone.tenants.clone(),
+        Duration::from_secs(config.usage_collect_interval_secs),
+        shutdown_sender.subscribe(),
+    )));
+
+    let backfill_handle = tokio::spawn(without_statement_timeout(start_backfill_task(
+        app_state_clone.backfills.clone(),
+        Duration::from_millis(config.backfill_pause_ms),
+        shutdown_sender.subscribe(),
+    )));
+
+    let one_time_token_cleanup_handle = tokio::spawn(without_statement_timeout(start_one_time_token_cleanup_task(
+        app_state_clone.account_emails.tokens.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let bookmark_preview_handle = tokio::spawn(without_statement_timeout(start_bookmark_preview_task(
+        app_state_clone.bookmark_handler.clone(),
+        shutdown_sender.subscribe(),
+    )));
+
+    let error_report_handle = error_transport.map(|transport| {
+        tokio::spawn(start_error_report_task(transport, shutdown_sender.subscribe()))
+    });
+
+    let redis_supervisor_handle = app_state_clone.redis_pool.clone().map(|pool| {
+        tokio::spawn(start_redis_supervisor_task(
+            pool,
+            Duration::from_secs(config.redis_health_check_secs),
+            shutdown_sender.subscribe(),
+        ))
+    });
 
     let res = tokio::select! {
         res = server => res,
@@ -94,7 +822,36 @@ async fn main() -> std::io::Result<()> {
         },
     };
 
+    let saved = app_state_clone.rate_limiter.persist().await;
+    if saved > 0 {
+        tracing::info!("Saved {} rate limit bucket(s) to Redis", saved);
+    }
+
     let _ = purge_handle.await;
+    let _ = flag_refresh_handle.await;
+    let _ = title_test_refresh_handle.await;
+    let _ = api_token_refresh_handle.await;
+    let _ = redirect_refresh_handle.await;
+    let _ = tenant_refresh_handle.await;
+    let _ = domain_verification_handle.await;
+    let _ = digest_handle.await;
+    let _ = outbox_handle.await;
+    let _ = contact_ingest_handle.await;
+    let _ = link_check_handle.await;
+    let _ = share_count_handle.await;
+    let _ = tag_index_handle.await;
+    let _ = retention_handle.await;
+    let _ = stats_rollup_handle.await;
+    let _ = usage_collector_handle.await;
+    let _ = backfill_handle.await;
+    let _ = one_time_token_cleanup_handle.await;
+    let _ = bookmark_preview_handle.await;
+    if let Some(handle) = error_report_handle {
+        let _ = handle.await;
+    }
+    if let Some(handle) = redis_supervisor_handle {
+        let _ = handle.await;
+    }
 
     res
 }
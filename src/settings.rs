@@ -1,9 +1,20 @@
 use config::{Config, ConfigError, Environment, File};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use sysinfo::System;
 use dotenv::dotenv;
 use jsonwebtoken::{DecodingKey, EncodingKey};
-use std::{env, fmt, str::FromStr};
+use ipnet::IpNet;
+use std::{collections::HashMap, env, fmt, net::IpAddr, str::FromStr, time::Duration};
 use zeroize::Zeroizing;
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::entities::{
+    app_setting::NotificationPolicy,
+    share_count::{SHARE_NETWORKS, SHARE_NETWORK_FACEBOOK},
+    user::AuthoredContentPolicy,
+};
+use crate::reporting::sentry::SentryDsn;
+use crate::utils::{public_urls::normalize_base_path, route_pattern::RoutePattern};
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -26,6 +37,116 @@ impl FromStr for AppEnvironment {
     }
 }
 
+/// Where uploaded files and generated exports are kept
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Local,
+    S3,
+}
+
+impl FromStr for StorageBackend {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "local" => Ok(StorageBackend::Local),
+            "s3" => Ok(StorageBackend::S3),
+            _ => Err(ConfigError::Message(format!("Invalid STORAGE_BACKEND value: {}, expected 'local' or 's3'", s))),
+        }
+    }
+}
+
+/// How Redis is deployed; decides how `redis_url` is read
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisTopology {
+    Standalone,
+    Sentinel,
+    Cluster,
+}
+
+impl RedisTopology {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedisTopology::Standalone => "standalone",
+            RedisTopology::Sentinel => "sentinel",
+            RedisTopology::Cluster => "cluster",
+        }
+    }
+}
+
+impl FromStr for RedisTopology {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "standalone" => Ok(RedisTopology::Standalone),
+            "sentinel" => Ok(RedisTopology::Sentinel),
+            "cluster" => Ok(RedisTopology::Cluster),
+            _ => Err(ConfigError::Message(format!(
+                "Invalid REDIS_TOPOLOGY value: {}, expected 'standalone', 'sentinel' or 'cluster'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Which service checks captcha tokens on public forms
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptchaProvider {
+    Off,
+    HCaptcha,
+    Turnstile,
+    /// Accepts every token; development and tests only
+    Test,
+}
+
+impl FromStr for CaptchaProvider {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "" => Ok(CaptchaProvider::Off),
+            "hcaptcha" => Ok(CaptchaProvider::HCaptcha),
+            "turnstile" => Ok(CaptchaProvider::Turnstile),
+            "test" => Ok(CaptchaProvider::Test),
+            _ => Err(ConfigError::Message(format!(
+                "Invalid CAPTCHA_PROVIDER value: {}, expected 'off', 'hcaptcha', 'turnstile' or 'test'",
+                s
+            ))),
+        }
+    }
+}
+
+/// How a route's rate limiter buckets survive a restart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitPersistence {
+    /// Buckets live in process memory only
+    Off,
+    /// Saved to Redis on shutdown and restored on startup
+    Snapshot,
+    /// Also saved after every request, so a crash loses nothing
+    WriteThrough,
+}
+
+impl FromStr for RateLimitPersistence {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" => Ok(RateLimitPersistence::Off),
+            "snapshot" => Ok(RateLimitPersistence::Snapshot),
+            "write-through" => Ok(RateLimitPersistence::WriteThrough),
+            _ => Err(ConfigError::Message(format!(
+                "Invalid rate limit persistence: {}, expected 'off', 'snapshot' or 'write-through'",
+                s
+            ))),
+        }
+    }
+}
+
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct AppConfig {
@@ -41,15 +162,56 @@ pub struct AppConfig {
     #[serde(default = "default_host")]
     pub host: String,
 
-    #[serde(default = "default_worker_count")]
+    /// HTTP workers; `auto` in the config file or `APP_WORKER_COUNT` sizes
+    /// it from the CPUs and available memory
+    #[serde(default = "default_worker_count", deserialize_with = "deserialize_worker_count")]
     pub worker_count: usize,
 
+    /// Pending connections the listener queues before refusing new ones
+    #[serde(default = "default_server_backlog")]
+    pub server_backlog: u32,
+
+    /// Idle time an HTTP/1.1 connection is kept open; 0 disables keep-alive
+    #[serde(default = "default_server_keep_alive_secs")]
+    pub server_keep_alive_secs: u64,
+
+    /// Time a client has to send the request head; 0 disables the limit
+    #[serde(default = "default_server_client_request_timeout_ms")]
+    pub server_client_request_timeout_ms: u64,
+
+    /// Concurrent connections per worker before accepting pauses
+    #[serde(default = "default_server_max_connections")]
+    pub server_max_connections: usize,
+
     #[serde(default)]
     pub database_url: String,
 
+    /// One URL, or with the sentinel and cluster topologies a
+    /// comma-separated list of sentinel or seed node URLs
     #[serde(default)]
     pub redis_url: Option<String>,
 
+    #[serde(default = "default_redis_topology")]
+    pub redis_topology: RedisTopology,
+
+    /// Master name the sentinels monitor; required with the sentinel topology
+    #[serde(default)]
+    pub redis_sentinel_master: Option<String>,
+
+    /// Times a command that failed because of a failover (a demoted master,
+    /// a cluster moving slots, a dropped connection) is retried on a fresh
+    /// connection
+    #[serde(default = "default_redis_failover_retries")]
+    pub redis_failover_retries: u32,
+
+    /// How often pooled Redis connections are validated
+    #[serde(default = "default_redis_health_check_secs")]
+    pub redis_health_check_secs: u64,
+
+    /// Failed health checks in a row before the Redis pool is rebuilt
+    #[serde(default = "default_redis_rebuild_after_failures")]
+    pub redis_rebuild_after_failures: u32,
+
     #[serde(default = "default_cors_origins")]
     pub cors_allowed_origins: Vec<String>,
 
@@ -59,11 +221,435 @@ pub struct AppConfig {
     #[serde(default = "default_jwt_expiration")]
     pub jwt_expiration_minutes: i64,
 
+    /// `iss` written into new access and refresh tokens
+    #[serde(default)]
+    pub jwt_issuer: Option<String>,
+
+    /// `aud` written into new access and refresh tokens
+    #[serde(default)]
+    pub jwt_audience: Option<String>,
+
+    /// Reject tokens without the configured `iss`/`aud`. Off by default so
+    /// tokens minted before the claims existed keep working until they
+    /// expire; a mismatching value is rejected either way.
+    #[serde(default)]
+    pub jwt_enforce_issuer_audience: bool,
+
     #[serde(default)]
     pub refresh_token_secret: String,
 
     #[serde(default = "default_refresh_expiration")]
     pub refresh_token_exp_days: i64,
+
+    #[serde(default = "default_password_hash_concurrency")]
+    pub password_hash_concurrency: usize,
+
+    #[serde(default = "default_password_hash_queue_timeout_ms")]
+    pub password_hash_queue_timeout_ms: u64,
+
+    /// When set, public content requires this passphrase (soft launch mode)
+    #[serde(default)]
+    pub site_gate_passphrase: Option<String>,
+
+    #[serde(default = "default_site_gate_ttl_hours")]
+    pub site_gate_ttl_hours: i64,
+
+    /// Lifetime of anonymous visitor tokens; renewed whenever one is requested
+    #[serde(default = "default_visitor_token_ttl_days")]
+    pub visitor_token_ttl_days: i64,
+
+    /// Where admin notifications go; notifications are skipped when unset
+    #[serde(default)]
+    pub notification_email: Option<String>,
+
+    #[serde(default = "default_mail_from")]
+    pub mail_from: String,
+
+    /// HTTP mail relay endpoint; emails are only logged when unset
+    #[serde(default)]
+    pub mail_relay_url: Option<String>,
+
+    #[serde(default)]
+    pub mail_relay_token: Option<String>,
+
+    /// Absolute base used for links in emails, e.g. `https://example.com`
+    #[serde(default)]
+    pub public_base_url: Option<String>,
+
+    /// Path a reverse proxy serves the API under, e.g. `/api`. Requests may
+    /// arrive with or without it, and links back to the API include it;
+    /// `X-Forwarded-Prefix` overrides it per request.
+    #[serde(default)]
+    pub base_path: Option<String>,
+
+    /// Reverse proxies in front of the API, as IPs or CIDR ranges, e.g.
+    /// `10.0.0.0/8,127.0.0.1`. `X-Forwarded-For` is only believed from
+    /// these peers; with none listed the peer address is the client.
+    #[serde(default)]
+    pub trusted_proxies: Option<String>,
+
+    /// Default contact notification policy until overridden at runtime
+    #[serde(default = "default_contact_notification_policy")]
+    pub contact_notification_policy: NotificationPolicy,
+
+    /// Per-scope concurrency overrides, e.g. `blog=64,auth=32`
+    #[serde(default)]
+    pub scope_concurrency_limits: Option<String>,
+
+    /// How long a request may wait for a scope slot before it is shed with a 503
+    #[serde(default = "default_scope_queue_timeout_ms")]
+    pub scope_queue_timeout_ms: u64,
+
+    /// Default time budget for a request before it is aborted with a 504
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+
+    /// Per-scope timeout overrides in milliseconds, e.g. `admin=30000`
+    #[serde(default)]
+    pub route_timeouts: Option<String>,
+
+    /// Postgres `statement_timeout` (migrations and background jobs run
+    /// without one); keep it below the request timeout so the database gives
+    /// up before the client does
+    #[serde(default = "default_db_statement_timeout_ms")]
+    pub db_statement_timeout_ms: u64,
+
+    /// `[cache_policy]` table: Cache-Control rules by route pattern
+    #[serde(default)]
+    pub cache_policy: CachePolicyConfig,
+
+    /// `[body_logging]` table: debug logging of request and response bodies
+    #[serde(default)]
+    pub body_logging: BodyLoggingConfig,
+
+    /// `[route_policy]` table: who may call which routes, checked by the
+    /// auth middleware before the built-in rules
+    #[serde(default)]
+    pub route_policy: RoutePolicyConfig,
+
+    /// Reject requests whose host no tenant claims instead of serving the default tenant
+    #[serde(default)]
+    pub strict_tenant_hosts: bool,
+
+    /// DNS-over-HTTPS JSON endpoint used to check custom domain TXT records
+    #[serde(default = "default_dns_over_https_url")]
+    pub dns_over_https_url: String,
+
+    /// How often pending custom domains are re-checked
+    #[serde(default = "default_domain_verification_interval_secs")]
+    pub domain_verification_interval_secs: u64,
+
+    /// Shared secret CI sends in `X-Deploy-Token`; the deploy hook is off when unset
+    #[serde(default)]
+    pub deploy_hook_token: Option<String>,
+
+    /// How long an IP that requests a honeytoken path is banned; 0 disables auto-ban
+    #[serde(default)]
+    pub honeytoken_ban_secs: u64,
+
+    /// Domains posts and contact messages may not link to, e.g.
+    /// `evil.example,*.phish.example`; subdomains are blocked too
+    #[serde(default)]
+    pub blocked_content_domains: Option<String>,
+
+    /// GeoLite2 City database used to tag clicks, security events and
+    /// contact messages with country/region; lookups are off when unset
+    #[serde(default)]
+    pub geoip_db_path: Option<String>,
+
+    /// TTL of pre-warmed public pages in Redis
+    #[serde(default = "default_page_cache_ttl_secs")]
+    pub page_cache_ttl_secs: u64,
+
+    /// Fill the page cache for every tenant before the server starts listening
+    #[serde(default)]
+    pub startup_warmup: bool,
+
+    /// Longest the startup warmup may hold the server back
+    #[serde(default = "default_startup_warmup_timeout_secs")]
+    pub startup_warmup_timeout_secs: u64,
+
+    /// How long a presence heartbeat keeps a visitor counted
+    #[serde(default = "default_presence_ttl_secs")]
+    pub presence_ttl_secs: u64,
+
+    /// How long a post edit lock lasts after the editor's last heartbeat
+    #[serde(default = "default_post_edit_lock_ttl_secs")]
+    pub post_edit_lock_ttl_secs: u64,
+
+    /// Requests a client can make at once on rate limited routes
+    #[serde(default = "default_rate_limit_burst")]
+    pub rate_limit_burst: u64,
+
+    /// Sustained requests per minute per client on rate limited routes
+    #[serde(default = "default_rate_limit_per_minute")]
+    pub rate_limit_per_minute: u64,
+
+    /// Per-route bucket persistence in Redis, e.g. `auth=snapshot`
+    #[serde(default)]
+    pub rate_limit_persistence: Option<String>,
+
+    /// API tokens valid on every site and scope as `name=token` pairs, e.g.
+    /// for the sites' own frontends; admins issue narrower ones per site
+    #[serde(default)]
+    pub public_api_tokens: Option<String>,
+
+    /// Requests an API token can make at once on token protected routes
+    #[serde(default = "default_api_token_burst")]
+    pub api_token_burst: u64,
+
+    /// Sustained requests per minute per API token, unless it sets its own
+    #[serde(default = "default_api_token_per_minute")]
+    pub api_token_per_minute: u64,
+
+    /// Requests a client can make at once on the public API under `/api/v1/public`
+    #[serde(default = "default_public_api_burst")]
+    pub public_api_burst: u64,
+
+    /// Sustained requests per minute per client on the public API
+    #[serde(default = "default_public_api_per_minute")]
+    pub public_api_per_minute: u64,
+
+    /// Contact submissions that can wait for the database writer; more are shed with 429
+    #[serde(default = "default_contact_queue_capacity")]
+    pub contact_queue_capacity: usize,
+
+    /// Keys sealing contact message emails and bodies at rest, as
+    /// `id=<base64 of 32 bytes>` pairs. Keep retired keys listed until
+    /// `--rekey-contact-messages` has moved every row off them.
+    #[serde(default)]
+    pub contact_encryption_keys: Option<String>,
+
+    /// Id of the key new contact messages are sealed with; may be left out
+    /// when only one key is listed
+    #[serde(default)]
+    pub contact_encryption_key_id: Option<String>,
+
+    /// Most posts the homepage can feature at once
+    #[serde(default = "default_featured_posts_max")]
+    pub featured_posts_max: usize,
+
+    /// How often external links in published content are re-checked
+    #[serde(default = "default_link_check_interval_secs")]
+    pub link_check_interval_secs: u64,
+
+    /// How often expired rows are purged or anonymized per the retention policies
+    #[serde(default = "default_retention_interval_secs")]
+    pub retention_interval_secs: u64,
+
+    /// How often the analytics rollups are refreshed
+    #[serde(default = "default_stats_rollup_interval_secs")]
+    pub stats_rollup_interval_secs: u64,
+
+    /// Days each rollup run re-aggregates, today included
+    #[serde(default = "default_stats_rollup_days")]
+    pub stats_rollup_days: u32,
+
+    /// How often the usage report of every site is collected
+    #[serde(default = "default_usage_collect_interval_secs")]
+    pub usage_collect_interval_secs: u64,
+
+    /// Emails the mail relay allows per calendar month; 0 disables the alert
+    #[serde(default)]
+    pub email_monthly_quota: u64,
+
+    /// Size of the object storage plan in bytes; 0 disables the alert
+    #[serde(default)]
+    pub storage_quota_bytes: u64,
+
+    /// Percentages of a quota at which the admins are alerted, e.g. `80,95`
+    #[serde(default)]
+    pub quota_alert_thresholds: Option<String>,
+
+    /// Pause between derived-data backfill batches, so they don't crowd out requests
+    #[serde(default = "default_backfill_pause_ms")]
+    pub backfill_pause_ms: u64,
+
+    /// Links requested at the same time during a check run
+    #[serde(default = "default_link_check_concurrency")]
+    pub link_check_concurrency: usize,
+
+    /// Per-link request timeout
+    #[serde(default = "default_link_check_timeout_secs")]
+    pub link_check_timeout_secs: u64,
+
+    /// How often the tag suggestion index is rebuilt from the posts
+    #[serde(default = "default_tag_index_refresh_secs")]
+    pub tag_index_refresh_secs: u64,
+
+    /// Words never suggested as tags, on top of the built-in English list,
+    /// e.g. `post,today,thing`
+    #[serde(default)]
+    pub tag_stopwords: Option<String>,
+
+    /// Networks share counts are collected from for published posts, e.g.
+    /// `hackernews,reddit`; collection is off when none are listed
+    #[serde(default)]
+    pub share_count_networks: Option<String>,
+
+    /// How often every published post's share counts are refreshed
+    #[serde(default = "default_share_count_interval_secs")]
+    pub share_count_interval_secs: u64,
+
+    /// Pause between two requests to the same network, to stay under its rate limit
+    #[serde(default = "default_share_count_request_spacing_ms")]
+    pub share_count_request_spacing_ms: u64,
+
+    /// Graph API access token, required when `facebook` is listed
+    #[serde(default)]
+    pub share_count_facebook_token: Option<String>,
+
+    /// What deleting a user does to the posts they wrote
+    #[serde(default = "default_user_content_policy")]
+    pub user_content_policy: AuthoredContentPolicy,
+
+    #[serde(default = "default_storage_backend")]
+    pub storage_backend: StorageBackend,
+
+    /// Directory the local backend keeps objects in
+    #[serde(default = "default_storage_local_root")]
+    pub storage_local_root: String,
+
+    #[serde(default)]
+    pub storage_s3_bucket: Option<String>,
+
+    #[serde(default = "default_storage_s3_region")]
+    pub storage_s3_region: String,
+
+    /// Custom endpoint for S3-compatible services such as MinIO; switches to
+    /// path-style requests
+    #[serde(default)]
+    pub storage_s3_endpoint: Option<String>,
+
+    /// Static credentials; when unset the usual `AWS_*` variables and the
+    /// instance role are used
+    #[serde(default)]
+    pub storage_s3_access_key_id: Option<String>,
+
+    #[serde(default)]
+    pub storage_s3_secret_access_key: Option<String>,
+
+    /// Cloudflare zone purged after a publish; needs `cloudflare_api_token` too
+    #[serde(default)]
+    pub cloudflare_zone_id: Option<String>,
+
+    /// API token with the Cache Purge permission for `cloudflare_zone_id`
+    #[serde(default)]
+    pub cloudflare_api_token: Option<String>,
+
+    /// Checks captcha tokens on register and contact when their
+    /// `captcha_*` feature flag is on; off by default
+    #[serde(default = "default_captcha_provider")]
+    pub captcha_provider: CaptchaProvider,
+
+    /// Secret key for hCaptcha or Turnstile
+    #[serde(default)]
+    pub captcha_secret: Option<String>,
+
+    /// Sentry DSN; panics, 500 responses and failed background jobs are
+    /// reported there when set
+    #[serde(default)]
+    pub error_reporting_dsn: Option<String>,
+
+    /// Share of 500 responses reported, from 0.0 to 1.0
+    #[serde(default = "default_error_reporting_sample_rate")]
+    pub error_reporting_sample_rate: f64,
+
+    /// Share of failed background job runs reported, from 0.0 to 1.0
+    #[serde(default = "default_error_reporting_sample_rate")]
+    pub error_reporting_job_sample_rate: f64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CachePolicyConfig {
+    /// Applied to responses no rule matches; leave unset to send no header
+    #[serde(default)]
+    pub default: Option<String>,
+
+    /// Checked in order, first match wins
+    #[serde(default = "default_cache_rules")]
+    pub rules: Vec<CacheRule>,
+}
+
+impl Default for CachePolicyConfig {
+    fn default() -> Self {
+        Self {
+            default: None,
+            rules: default_cache_rules(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BodyLoggingConfig {
+    /// Log every body; refused in production, where only admins can opt in
+    /// per request with `X-Debug-Body-Log: 1`
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Larger bodies are noted by size but not buffered or logged
+    #[serde(default = "default_body_log_max_bytes")]
+    pub max_body_bytes: usize,
+
+    /// JSON keys and form fields whose values are dropped. Matched
+    /// case-insensitively as substrings, so `token` covers `refresh_token`
+    #[serde(default = "default_redact_fields")]
+    pub redact_fields: Vec<String>,
+
+    /// Extra regular expressions masked in every logged body; email
+    /// addresses are always masked
+    #[serde(default)]
+    pub redact_patterns: Vec<String>,
+}
+
+impl Default for BodyLoggingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_body_bytes: default_body_log_max_bytes(),
+            redact_fields: default_redact_fields(),
+            redact_patterns: Vec::new(),
+        }
+    }
+}
+
+/// `pattern` is a path where `*` matches one segment and `**` any remainder,
+/// e.g. `/api/v1/blog/posts/*` or `/api/v1/admin/**`
+#[derive(Debug, Deserialize, Clone)]
+pub struct CacheRule {
+    pub pattern: String,
+    pub cache_control: String,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct RoutePolicyConfig {
+    /// Checked in order before the built-in rules, first match wins
+    #[serde(default)]
+    pub rules: Vec<RouteRule>,
+}
+
+/// Who may call the routes matching `pattern`, written like a cache rule's
+/// pattern or with named segments, e.g. `/api/v1/blog/posts/{post_id}`
+#[derive(Debug, Deserialize, Clone)]
+pub struct RouteRule {
+    pub pattern: String,
+    /// Methods the rule applies to; empty for all
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub access: RouteAccess,
+}
+
+/// Least a caller needs to pass the auth middleware
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteAccess {
+    /// No token needed
+    Public,
+    /// Any signed-in user
+    User,
+    /// Editors and admins
+    Editor,
+    Admin,
 }
 
 fn default_env() -> AppEnvironment {
@@ -81,6 +667,18 @@ fn default_host() -> String {
 fn default_worker_count() -> usize {
     num_cpus::get()
 }
+fn default_server_backlog() -> u32 {
+    2048
+}
+fn default_server_keep_alive_secs() -> u64 {
+    5
+}
+fn default_server_client_request_timeout_ms() -> u64 {
+    5000
+}
+fn default_server_max_connections() -> usize {
+    25_000
+}
 fn default_cors_origins() -> Vec<String> {
     vec!["*".to_string()]
 }
@@ -90,6 +688,245 @@ fn default_jwt_expiration() -> i64 {
 fn default_refresh_expiration() -> i64 {
     7
 }
+fn default_password_hash_concurrency() -> usize {
+    num_cpus::get()
+}
+fn default_password_hash_queue_timeout_ms() -> u64 {
+    250
+}
+fn default_site_gate_ttl_hours() -> i64 {
+    72
+}
+fn default_visitor_token_ttl_days() -> i64 {
+    365
+}
+fn default_mail_from() -> String {
+    "no-reply@localhost".to_string()
+}
+fn default_contact_notification_policy() -> NotificationPolicy {
+    NotificationPolicy::Immediate
+}
+fn default_dns_over_https_url() -> String {
+    "https://cloudflare-dns.com/dns-query".to_string()
+}
+fn default_domain_verification_interval_secs() -> u64 {
+    300
+}
+fn default_redis_topology() -> RedisTopology {
+    RedisTopology::Standalone
+}
+fn default_redis_failover_retries() -> u32 {
+    2
+}
+fn default_redis_health_check_secs() -> u64 {
+    10
+}
+fn default_redis_rebuild_after_failures() -> u32 {
+    3
+}
+fn default_page_cache_ttl_secs() -> u64 {
+    300
+}
+fn default_startup_warmup_timeout_secs() -> u64 {
+    30
+}
+fn default_user_content_policy() -> AuthoredContentPolicy {
+    AuthoredContentPolicy::Anonymize
+}
+fn default_captcha_provider() -> CaptchaProvider {
+    CaptchaProvider::Off
+}
+fn default_error_reporting_sample_rate() -> f64 {
+    1.0
+}
+fn default_storage_backend() -> StorageBackend {
+    StorageBackend::Local
+}
+fn default_storage_local_root() -> String {
+    "storage".to_string()
+}
+fn default_storage_s3_region() -> String {
+    "us-east-1".to_string()
+}
+fn default_presence_ttl_secs() -> u64 {
+    60
+}
+fn default_post_edit_lock_ttl_secs() -> u64 {
+    90
+}
+fn default_rate_limit_burst() -> u64 {
+    10
+}
+fn default_rate_limit_per_minute() -> u64 {
+    30
+}
+fn default_public_api_burst() -> u64 {
+    20
+}
+fn default_public_api_per_minute() -> u64 {
+    60
+}
+fn default_api_token_burst() -> u64 {
+    100
+}
+fn default_api_token_per_minute() -> u64 {
+    600
+}
+fn default_contact_queue_capacity() -> usize {
+    256
+}
+fn default_featured_posts_max() -> usize {
+    6
+}
+fn default_link_check_interval_secs() -> u64 {
+    24 * 60 * 60
+}
+fn default_retention_interval_secs() -> u64 {
+    24 * 60 * 60
+}
+fn default_stats_rollup_interval_secs() -> u64 {
+    60 * 60
+}
+fn default_stats_rollup_days() -> u32 {
+    2
+}
+fn default_usage_collect_interval_secs() -> u64 {
+    60 * 60
+}
+fn default_backfill_pause_ms() -> u64 {
+    250
+}
+fn default_link_check_concurrency() -> usize {
+    8
+}
+fn default_link_check_timeout_secs() -> u64 {
+    10
+}
+fn default_tag_index_refresh_secs() -> u64 {
+    60 * 60
+}
+fn default_share_count_interval_secs() -> u64 {
+    6 * 60 * 60
+}
+fn default_share_count_request_spacing_ms() -> u64 {
+    2_000
+}
+fn default_scope_queue_timeout_ms() -> u64 {
+    100
+}
+fn default_request_timeout_ms() -> u64 {
+    10_000
+}
+fn default_db_statement_timeout_ms() -> u64 {
+    8_000
+}
+fn default_body_log_max_bytes() -> usize {
+    64 * 1024
+}
+fn default_redact_fields() -> Vec<String> {
+    ["password", "passphrase", "token", "secret", "authorization", "api_key", "cookie"]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+fn default_cache_rules() -> Vec<CacheRule> {
+    [
+        ("/api/v1/admin/**", "no-store"),
+        ("/api/v1/blog/admin/**", "no-store"),
+        ("/api/v1/auth/**", "no-store"),
+        ("/api/v1/users/**", "private, no-store"),
+        ("/api/v1/gate", "no-store"),
+        ("/api/v1/feed/**", "public, max-age=900, s-maxage=3600"),
+        ("/api/v1/images/**", "public, max-age=86400, s-maxage=604800"),
+        ("/api/v1/blog/posts", "public, max-age=30, s-maxage=60"),
+        ("/api/v1/blog/posts/recent/*", "public, max-age=30, s-maxage=60"),
+        ("/api/v1/blog/posts/*", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/blog/posts/*/shares", "public, max-age=300, s-maxage=900"),
+        ("/api/v1/blog/authors/*/posts", "public, max-age=60, s-maxage=300"),
+        ("/api/v1/blog/series/*", "public, max-age=60, s-maxage=300"),
+        ("/api/v1/about-me/introduction", "public, max-age=300, s-maxage=3600"),
+        ("/about.html", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/hire", "public, max-age=60, s-maxage=300"),
+        ("/api/v1/uses", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/uses/export.md", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/changelog", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/bookmarks", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/out", "no-store"),
+        ("/api/v1/out/qr.png", "public, max-age=86400"),
+        ("/api/v1/presence", "public, max-age=10, s-maxage=10"),
+        ("/api/v1/presence/beat", "no-store"),
+        ("/api/v1/reading/**", "no-store"),
+        ("/api/v1/sync", "public, max-age=30, s-maxage=60"),
+        ("/api/v1/visitor-token", "private, no-store"),
+        ("/api/v1/storage/**", "private, no-store"),
+        ("/api/v1/public", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/public/posts", "public, max-age=60, s-maxage=300"),
+        ("/api/v1/public/posts/*", "public, max-age=300, s-maxage=3600"),
+        ("/api/v1/public/about", "public, max-age=300, s-maxage=3600"),
+    ]
+    .into_iter()
+    .map(|(pattern, cache_control)| CacheRule {
+        pattern: pattern.to_string(),
+        cache_control: cache_control.to_string(),
+    })
+    .collect()
+}
+
+/// Upper bound for `worker_count`, explicit or automatic
+const MAX_WORKER_COUNT: usize = 256;
+/// Memory set aside per worker when `worker_count` is `auto`
+const WORKER_MEMORY_BUDGET_BYTES: u64 = 128 * 1024 * 1024;
+
+/// A number of workers, or `auto`
+fn parse_worker_count(raw: &str) -> Result<usize, ConfigError> {
+    let raw = raw.trim();
+    if raw.eq_ignore_ascii_case("auto") {
+        return Ok(auto_worker_count());
+    }
+
+    raw.parse()
+        .map_err(|_| ConfigError::Message(format!("Invalid WORKER_COUNT value: {}, expected a number or 'auto'", raw)))
+}
+
+fn deserialize_worker_count<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum WorkerCount {
+        Fixed(usize),
+        Named(String),
+    }
+
+    match WorkerCount::deserialize(deserializer)? {
+        WorkerCount::Fixed(count) => Ok(count),
+        WorkerCount::Named(raw) => parse_worker_count(&raw).map_err(serde::de::Error::custom),
+    }
+}
+
+/// One worker per CPU, but only as many as the available memory holds at
+/// `WORKER_MEMORY_BUDGET_BYTES` each. Inside a container the cgroup's free
+/// memory counts, not the host's.
+fn auto_worker_count() -> usize {
+    let cpus = num_cpus::get();
+
+    let mut sys = System::new();
+    sys.refresh_memory();
+    let available = sys.cgroup_limits().map_or_else(|| sys.available_memory(), |limits| limits.free_memory);
+
+    // 0 means the platform does not report memory; size by CPUs alone
+    let workers = match available {
+        0 => cpus,
+        bytes => cpus.min((bytes / WORKER_MEMORY_BUDGET_BYTES) as usize),
+    }
+    .clamp(1, MAX_WORKER_COUNT);
+
+    tracing::info!(
+        "WORKER_COUNT=auto: {} worker(s) for {} CPU(s) and {} MiB available",
+        workers,
+        cpus,
+        available / (1024 * 1024)
+    );
+    workers
+}
 
 impl AppConfig {
     pub fn new() -> Result<Self, ConfigError> {
@@ -116,6 +953,359 @@ impl AppConfig {
          if config.redis_url.is_none() {
             config.redis_url = env::var("APP_REDIS_URL").ok();
         }
+
+        if let Ok(topology) = env::var("APP_REDIS_TOPOLOGY") {
+            config.redis_topology = topology.parse()?;
+        }
+
+        if config.redis_sentinel_master.is_none() {
+            config.redis_sentinel_master = env::var("APP_REDIS_SENTINEL_MASTER").ok().filter(|m| !m.trim().is_empty());
+        }
+
+        if let Ok(retries) = env::var("APP_REDIS_FAILOVER_RETRIES") {
+            config.redis_failover_retries = retries.trim().parse()
+                .map_err(|_| ConfigError::Message("REDIS_FAILOVER_RETRIES must be a whole number".into()))?;
+        }
+
+        if let Ok(workers) = env::var("APP_WORKER_COUNT") {
+            config.worker_count = parse_worker_count(&workers)?;
+        }
+
+        if let Ok(backlog) = env::var("APP_SERVER_BACKLOG") {
+            config.server_backlog = backlog.trim().parse()
+                .map_err(|_| ConfigError::Message("SERVER_BACKLOG must be a whole number".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_SERVER_KEEP_ALIVE_SECS") {
+            config.server_keep_alive_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("SERVER_KEEP_ALIVE_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(ms) = env::var("APP_SERVER_CLIENT_REQUEST_TIMEOUT_MS") {
+            config.server_client_request_timeout_ms = ms.trim().parse()
+                .map_err(|_| ConfigError::Message("SERVER_CLIENT_REQUEST_TIMEOUT_MS must be a whole number of milliseconds".into()))?;
+        }
+
+        if let Ok(max) = env::var("APP_SERVER_MAX_CONNECTIONS") {
+            config.server_max_connections = max.trim().parse()
+                .map_err(|_| ConfigError::Message("SERVER_MAX_CONNECTIONS must be a whole number".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_REDIS_HEALTH_CHECK_SECS") {
+            config.redis_health_check_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("REDIS_HEALTH_CHECK_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(failures) = env::var("APP_REDIS_REBUILD_AFTER_FAILURES") {
+            config.redis_rebuild_after_failures = failures.trim().parse()
+                .map_err(|_| ConfigError::Message("REDIS_REBUILD_AFTER_FAILURES must be a whole number".into()))?;
+        }
+
+        if config.scope_concurrency_limits.is_none() {
+            config.scope_concurrency_limits = env::var("APP_SCOPE_CONCURRENCY_LIMITS").ok();
+        }
+
+        if config.route_timeouts.is_none() {
+            config.route_timeouts = env::var("APP_ROUTE_TIMEOUTS").ok();
+        }
+
+        if config.rate_limit_persistence.is_none() {
+            config.rate_limit_persistence = env::var("APP_RATE_LIMIT_PERSISTENCE").ok();
+        }
+
+        if config.public_api_tokens.is_none() {
+            config.public_api_tokens = env::var("APP_PUBLIC_API_TOKENS")
+                .ok()
+                .filter(|t| !t.trim().is_empty());
+        }
+
+        if config.geoip_db_path.is_none() {
+            config.geoip_db_path = env::var("APP_GEOIP_DB_PATH")
+                .ok()
+                .filter(|p| !p.trim().is_empty());
+        }
+
+        if config.site_gate_passphrase.is_none() {
+            config.site_gate_passphrase = env::var("APP_SITE_GATE_PASSPHRASE")
+                .ok()
+                .filter(|p| !p.trim().is_empty());
+        }
+
+        if let Ok(days) = env::var("APP_VISITOR_TOKEN_TTL_DAYS") {
+            config.visitor_token_ttl_days = days.trim().parse()
+                .map_err(|_| ConfigError::Message("VISITOR_TOKEN_TTL_DAYS must be a whole number of days".into()))?;
+        }
+
+        if config.deploy_hook_token.is_none() {
+            config.deploy_hook_token = env::var("APP_DEPLOY_HOOK_TOKEN")
+                .ok()
+                .filter(|t| !t.trim().is_empty());
+        }
+
+        if let Ok(secs) = env::var("APP_PAGE_CACHE_TTL_SECS") {
+            config.page_cache_ttl_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("PAGE_CACHE_TTL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(enabled) = env::var("APP_STARTUP_WARMUP") {
+            config.startup_warmup = enabled.trim().eq_ignore_ascii_case("true");
+        }
+
+        if let Ok(secs) = env::var("APP_STARTUP_WARMUP_TIMEOUT_SECS") {
+            config.startup_warmup_timeout_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("STARTUP_WARMUP_TIMEOUT_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(capacity) = env::var("APP_CONTACT_QUEUE_CAPACITY") {
+            config.contact_queue_capacity = capacity.trim().parse()
+                .map_err(|_| ConfigError::Message("CONTACT_QUEUE_CAPACITY must be a whole number".into()))?;
+        }
+
+        if config.contact_encryption_keys.is_none() {
+            config.contact_encryption_keys = env::var("APP_CONTACT_ENCRYPTION_KEYS")
+                .ok()
+                .filter(|keys| !keys.trim().is_empty());
+        }
+
+        if config.contact_encryption_key_id.is_none() {
+            config.contact_encryption_key_id = env::var("APP_CONTACT_ENCRYPTION_KEY_ID")
+                .ok()
+                .filter(|id| !id.trim().is_empty());
+        }
+
+        if let Ok(max) = env::var("APP_FEATURED_POSTS_MAX") {
+            config.featured_posts_max = max.trim().parse()
+                .map_err(|_| ConfigError::Message("FEATURED_POSTS_MAX must be a whole number".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_LINK_CHECK_INTERVAL_SECS") {
+            config.link_check_interval_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("LINK_CHECK_INTERVAL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_RETENTION_INTERVAL_SECS") {
+            config.retention_interval_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("RETENTION_INTERVAL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_STATS_ROLLUP_INTERVAL_SECS") {
+            config.stats_rollup_interval_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("STATS_ROLLUP_INTERVAL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(days) = env::var("APP_STATS_ROLLUP_DAYS") {
+            config.stats_rollup_days = days.trim().parse()
+                .map_err(|_| ConfigError::Message("STATS_ROLLUP_DAYS must be a whole number of days".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_USAGE_COLLECT_INTERVAL_SECS") {
+            config.usage_collect_interval_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("USAGE_COLLECT_INTERVAL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(quota) = env::var("APP_EMAIL_MONTHLY_QUOTA") {
+            config.email_monthly_quota = quota.trim().parse()
+                .map_err(|_| ConfigError::Message("EMAIL_MONTHLY_QUOTA must be a whole number of emails".into()))?;
+        }
+
+        if let Ok(bytes) = env::var("APP_STORAGE_QUOTA_BYTES") {
+            config.storage_quota_bytes = bytes.trim().parse()
+                .map_err(|_| ConfigError::Message("STORAGE_QUOTA_BYTES must be a whole number of bytes".into()))?;
+        }
+
+        if config.quota_alert_thresholds.is_none() {
+            config.quota_alert_thresholds = env::var("APP_QUOTA_ALERT_THRESHOLDS").ok();
+        }
+
+        if let Ok(ms) = env::var("APP_BACKFILL_PAUSE_MS") {
+            config.backfill_pause_ms = ms.trim().parse()
+                .map_err(|_| ConfigError::Message("BACKFILL_PAUSE_MS must be a whole number of milliseconds".into()))?;
+        }
+
+        if let Ok(concurrency) = env::var("APP_LINK_CHECK_CONCURRENCY") {
+            config.link_check_concurrency = concurrency.trim().parse()
+                .map_err(|_| ConfigError::Message("LINK_CHECK_CONCURRENCY must be a whole number".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_LINK_CHECK_TIMEOUT_SECS") {
+            config.link_check_timeout_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("LINK_CHECK_TIMEOUT_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_TAG_INDEX_REFRESH_SECS") {
+            config.tag_index_refresh_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("TAG_INDEX_REFRESH_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if config.base_path.is_none() {
+            config.base_path = env::var("APP_BASE_PATH").ok();
+        }
+
+        if config.trusted_proxies.is_none() {
+            config.trusted_proxies = env::var("APP_TRUSTED_PROXIES").ok();
+        }
+
+        if config.share_count_networks.is_none() {
+            config.share_count_networks = env::var("APP_SHARE_COUNT_NETWORKS").ok();
+        }
+
+        if let Ok(secs) = env::var("APP_SHARE_COUNT_INTERVAL_SECS") {
+            config.share_count_interval_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("SHARE_COUNT_INTERVAL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(ms) = env::var("APP_SHARE_COUNT_REQUEST_SPACING_MS") {
+            config.share_count_request_spacing_ms = ms.trim().parse()
+                .map_err(|_| ConfigError::Message("SHARE_COUNT_REQUEST_SPACING_MS must be a whole number of milliseconds".into()))?;
+        }
+
+        if config.share_count_facebook_token.is_none() {
+            config.share_count_facebook_token = env::var("APP_SHARE_COUNT_FACEBOOK_TOKEN")
+                .ok()
+                .filter(|t| !t.trim().is_empty());
+        }
+
+        if config.tag_stopwords.is_none() {
+            config.tag_stopwords = env::var("APP_TAG_STOPWORDS").ok();
+        }
+
+        if config.blocked_content_domains.is_none() {
+            config.blocked_content_domains = env::var("APP_BLOCKED_CONTENT_DOMAINS").ok();
+        }
+
+        if let Ok(policy) = env::var("APP_USER_CONTENT_POLICY") {
+            config.user_content_policy = policy.parse().map_err(ConfigError::Message)?;
+        }
+
+        if let Ok(secs) = env::var("APP_PRESENCE_TTL_SECS") {
+            config.presence_ttl_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("PRESENCE_TTL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(secs) = env::var("APP_POST_EDIT_LOCK_TTL_SECS") {
+            config.post_edit_lock_ttl_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("POST_EDIT_LOCK_TTL_SECS must be a whole number of seconds".into()))?;
+        }
+
+        if let Ok(burst) = env::var("APP_RATE_LIMIT_BURST") {
+            config.rate_limit_burst = burst.trim().parse()
+                .map_err(|_| ConfigError::Message("RATE_LIMIT_BURST must be a whole number".into()))?;
+        }
+
+        if let Ok(per_minute) = env::var("APP_RATE_LIMIT_PER_MINUTE") {
+            config.rate_limit_per_minute = per_minute.trim().parse()
+                .map_err(|_| ConfigError::Message("RATE_LIMIT_PER_MINUTE must be a whole number".into()))?;
+        }
+
+        if let Ok(burst) = env::var("APP_PUBLIC_API_BURST") {
+            config.public_api_burst = burst.trim().parse()
+                .map_err(|_| ConfigError::Message("PUBLIC_API_BURST must be a whole number".into()))?;
+        }
+
+        if let Ok(per_minute) = env::var("APP_PUBLIC_API_PER_MINUTE") {
+            config.public_api_per_minute = per_minute.trim().parse()
+                .map_err(|_| ConfigError::Message("PUBLIC_API_PER_MINUTE must be a whole number".into()))?;
+        }
+
+        if let Ok(burst) = env::var("APP_API_TOKEN_BURST") {
+            config.api_token_burst = burst.trim().parse()
+                .map_err(|_| ConfigError::Message("API_TOKEN_BURST must be a whole number".into()))?;
+        }
+
+        if let Ok(per_minute) = env::var("APP_API_TOKEN_PER_MINUTE") {
+            config.api_token_per_minute = per_minute.trim().parse()
+                .map_err(|_| ConfigError::Message("API_TOKEN_PER_MINUTE must be a whole number".into()))?;
+        }
+
+        if let Ok(backend) = env::var("APP_STORAGE_BACKEND") {
+            config.storage_backend = backend.parse()?;
+        }
+
+        if let Some(root) = env::var("APP_STORAGE_LOCAL_ROOT").ok().map(|r| r.trim().to_string()).filter(|r| !r.is_empty()) {
+            config.storage_local_root = root;
+        }
+
+        if let Some(region) = env::var("APP_STORAGE_S3_REGION").ok().map(|r| r.trim().to_string()).filter(|r| !r.is_empty()) {
+            config.storage_s3_region = region;
+        }
+
+        for (field, key) in [
+            (&mut config.storage_s3_bucket, "APP_STORAGE_S3_BUCKET"),
+            (&mut config.storage_s3_endpoint, "APP_STORAGE_S3_ENDPOINT"),
+            (&mut config.storage_s3_access_key_id, "APP_STORAGE_S3_ACCESS_KEY_ID"),
+            (&mut config.storage_s3_secret_access_key, "APP_STORAGE_S3_SECRET_ACCESS_KEY"),
+        ] {
+            if field.is_none() {
+                *field = env::var(key).ok().filter(|v| !v.trim().is_empty());
+            }
+        }
+
+        if config.cloudflare_zone_id.is_none() {
+            config.cloudflare_zone_id = env::var("APP_CLOUDFLARE_ZONE_ID")
+                .ok()
+                .filter(|z| !z.trim().is_empty());
+        }
+
+        if config.cloudflare_api_token.is_none() {
+            config.cloudflare_api_token = env::var("APP_CLOUDFLARE_API_TOKEN")
+                .ok()
+                .filter(|t| !t.trim().is_empty());
+        }
+
+        if let Ok(provider) = env::var("APP_CAPTCHA_PROVIDER") {
+            config.captcha_provider = provider.parse()?;
+        }
+
+        if config.captcha_secret.is_none() {
+            config.captcha_secret = env::var("APP_CAPTCHA_SECRET")
+                .ok()
+                .filter(|s| !s.trim().is_empty());
+        }
+
+        if config.error_reporting_dsn.is_none() {
+            config.error_reporting_dsn = env::var("APP_ERROR_REPORTING_DSN")
+                .ok()
+                .filter(|d| !d.trim().is_empty());
+        }
+
+        for (field, key, name) in [
+            (&mut config.error_reporting_sample_rate, "APP_ERROR_REPORTING_SAMPLE_RATE", "ERROR_REPORTING_SAMPLE_RATE"),
+            (&mut config.error_reporting_job_sample_rate, "APP_ERROR_REPORTING_JOB_SAMPLE_RATE", "ERROR_REPORTING_JOB_SAMPLE_RATE"),
+        ] {
+            if let Ok(rate) = env::var(key) {
+                *field = rate.trim().parse()
+                    .map_err(|_| ConfigError::Message(format!("{} must be a number between 0 and 1", name)))?;
+            }
+        }
+
+        if config.jwt_issuer.is_none() {
+            config.jwt_issuer = env::var("APP_JWT_ISSUER")
+                .ok()
+                .filter(|i| !i.trim().is_empty());
+        }
+
+        if config.jwt_audience.is_none() {
+            config.jwt_audience = env::var("APP_JWT_AUDIENCE")
+                .ok()
+                .filter(|a| !a.trim().is_empty());
+        }
+
+        if let Ok(enforce) = env::var("APP_JWT_ENFORCE_ISSUER_AUDIENCE") {
+            config.jwt_enforce_issuer_audience = enforce.trim().eq_ignore_ascii_case("true");
+        }
+
+        if let Ok(enabled) = env::var("APP_BODY_LOGGING") {
+            config.body_logging.enabled = enabled.trim().eq_ignore_ascii_case("true");
+        }
+
+        if let Ok(strict) = env::var("APP_STRICT_TENANT_HOSTS") {
+            config.strict_tenant_hosts = strict.trim().eq_ignore_ascii_case("true");
+        }
+
+        if let Ok(secs) = env::var("APP_HONEYTOKEN_BAN_SECS") {
+            config.honeytoken_ban_secs = secs.trim().parse()
+                .map_err(|_| ConfigError::Message("HONEYTOKEN_BAN_SECS must be a whole number of seconds".into()))?;
+        }
         
 
         config.validate()?;
@@ -131,12 +1321,184 @@ impl AppConfig {
         if self.jwt_secret.len() < 32 {
             errors.push("JWT_SECRET must be at least 32 characters");
         }
+        if self.jwt_enforce_issuer_audience && self.jwt_issuer.is_none() && self.jwt_audience.is_none() {
+            errors.push("JWT_ENFORCE_ISSUER_AUDIENCE requires JWT_ISSUER or JWT_AUDIENCE");
+        }
         if self.refresh_token_secret.len() < 32 {
             errors.push("REFRESH_TOKEN_SECRET must be at least 32 characters");
         }
+        if self.site_gate_passphrase.as_ref().is_some_and(|p| p.len() < 8) {
+            errors.push("SITE_GATE_PASSPHRASE must be at least 8 characters");
+        }
+        if self.visitor_token_ttl_days <= 0 {
+            errors.push("VISITOR_TOKEN_TTL_DAYS must be greater than 0");
+        }
+        if self.deploy_hook_token.as_ref().is_some_and(|t| t.len() < 32) {
+            errors.push("DEPLOY_HOOK_TOKEN must be at least 32 characters");
+        }
+        if !(1..=MAX_WORKER_COUNT).contains(&self.worker_count) {
+            errors.push("WORKER_COUNT must be between 1 and 256, or auto");
+        }
+        if self.server_backlog == 0 {
+            errors.push("SERVER_BACKLOG must be greater than 0");
+        }
+        if self.server_keep_alive_secs > 3600 {
+            errors.push("SERVER_KEEP_ALIVE_SECS must be at most 3600");
+        }
+        if self.server_client_request_timeout_ms > 300_000 {
+            errors.push("SERVER_CLIENT_REQUEST_TIMEOUT_MS must be at most 300000");
+        }
+        if self.server_max_connections == 0 {
+            errors.push("SERVER_MAX_CONNECTIONS must be greater than 0");
+        }
+        if self.redis_health_check_secs == 0 {
+            errors.push("REDIS_HEALTH_CHECK_SECS must be greater than 0");
+        }
+        if self.redis_rebuild_after_failures == 0 {
+            errors.push("REDIS_REBUILD_AFTER_FAILURES must be greater than 0");
+        }
+        if self.redis_failover_retries > 10 {
+            errors.push("REDIS_FAILOVER_RETRIES must be at most 10");
+        }
+        if self.redis_topology == RedisTopology::Sentinel && self.redis_sentinel_master.is_none() {
+            errors.push("REDIS_SENTINEL_MASTER must be set when REDIS_TOPOLOGY is sentinel");
+        }
+        if self.redis_topology == RedisTopology::Standalone && self.redis_urls().len() > 1 {
+            errors.push("REDIS_URL lists several nodes; set REDIS_TOPOLOGY to sentinel or cluster");
+        }
+        if !(10..=3600).contains(&self.presence_ttl_secs) {
+            errors.push("PRESENCE_TTL_SECS must be between 10 and 3600");
+        }
+        if !(15..=900).contains(&self.post_edit_lock_ttl_secs) {
+            errors.push("POST_EDIT_LOCK_TTL_SECS must be between 15 and 900");
+        }
+        if !(1..=600).contains(&self.startup_warmup_timeout_secs) {
+            errors.push("STARTUP_WARMUP_TIMEOUT_SECS must be between 1 and 600");
+        }
+        if self.rate_limit_burst == 0 {
+            errors.push("RATE_LIMIT_BURST must be greater than 0");
+        }
+        if self.rate_limit_per_minute == 0 {
+            errors.push("RATE_LIMIT_PER_MINUTE must be greater than 0");
+        }
+        if self.public_api_burst == 0 {
+            errors.push("PUBLIC_API_BURST must be greater than 0");
+        }
+        if self.public_api_per_minute == 0 {
+            errors.push("PUBLIC_API_PER_MINUTE must be greater than 0");
+        }
+        if self.api_token_burst == 0 {
+            errors.push("API_TOKEN_BURST must be greater than 0");
+        }
+        if self.api_token_per_minute == 0 {
+            errors.push("API_TOKEN_PER_MINUTE must be greater than 0");
+        }
+        if !(1..=100_000).contains(&self.contact_queue_capacity) {
+            errors.push("CONTACT_QUEUE_CAPACITY must be between 1 and 100000");
+        }
+        if self.base_path.as_deref().is_some_and(|path| normalize_base_path(path).is_none()) {
+            errors.push("BASE_PATH must be a plain path such as /api");
+        }
+        if self.trusted_proxies().is_err() {
+            errors.push("TRUSTED_PROXIES must list IP addresses or CIDR ranges");
+        }
+        match self.contact_encryption_keys() {
+            Err(e) => errors.push(e),
+            Ok(keys) if !keys.is_empty() && self.contact_encryption_key_id().is_none() => {
+                errors.push("CONTACT_ENCRYPTION_KEY_ID must name one of CONTACT_ENCRYPTION_KEYS, and is required when several are listed");
+            }
+            Ok(_) => {}
+        }
+        if !(1..=24).contains(&self.featured_posts_max) {
+            errors.push("FEATURED_POSTS_MAX must be between 1 and 24");
+        }
+        if self.link_check_interval_secs < 60 {
+            errors.push("LINK_CHECK_INTERVAL_SECS must be at least 60");
+        }
+        if self.retention_interval_secs < 60 {
+            errors.push("RETENTION_INTERVAL_SECS must be at least 60");
+        }
+        if self.stats_rollup_interval_secs < 60 {
+            errors.push("STATS_ROLLUP_INTERVAL_SECS must be at least 60");
+        }
+        if !(1..=31).contains(&self.stats_rollup_days) {
+            errors.push("STATS_ROLLUP_DAYS must be between 1 and 31");
+        }
+        if self.usage_collect_interval_secs < 60 {
+            errors.push("USAGE_COLLECT_INTERVAL_SECS must be at least 60");
+        }
+        if self.quota_alert_thresholds().is_err() {
+            errors.push("QUOTA_ALERT_THRESHOLDS must list percentages between 1 and 100");
+        }
+        if self.backfill_pause_ms > 60_000 {
+            errors.push("BACKFILL_PAUSE_MS must be at most 60000");
+        }
+        if !(1..=64).contains(&self.link_check_concurrency) {
+            errors.push("LINK_CHECK_CONCURRENCY must be between 1 and 64");
+        }
+        if !(1..=60).contains(&self.link_check_timeout_secs) {
+            errors.push("LINK_CHECK_TIMEOUT_SECS must be between 1 and 60");
+        }
+        if self.tag_index_refresh_secs < 60 {
+            errors.push("TAG_INDEX_REFRESH_SECS must be at least 60");
+        }
+        let share_networks = self.share_count_networks();
+        if share_networks.iter().any(|network| !SHARE_NETWORKS.contains(&network.as_str())) {
+            errors.push("SHARE_COUNT_NETWORKS may only list hackernews, reddit and facebook");
+        }
+        if share_networks.iter().any(|network| network == SHARE_NETWORK_FACEBOOK) && self.share_count_facebook_token.is_none() {
+            errors.push("SHARE_COUNT_FACEBOOK_TOKEN is required when SHARE_COUNT_NETWORKS lists facebook");
+        }
+        if self.share_count_interval_secs < 300 {
+            errors.push("SHARE_COUNT_INTERVAL_SECS must be at least 300");
+        }
+        if self.share_count_request_spacing_ms > 60_000 {
+            errors.push("SHARE_COUNT_REQUEST_SPACING_MS must be at most 60000");
+        }
+        if self.storage_backend == StorageBackend::S3 && self.storage_s3_bucket.is_none() {
+            errors.push("STORAGE_S3_BUCKET must be set when STORAGE_BACKEND is s3");
+        }
+        if self.storage_s3_access_key_id.is_some() != self.storage_s3_secret_access_key.is_some() {
+            errors.push("STORAGE_S3_ACCESS_KEY_ID and STORAGE_S3_SECRET_ACCESS_KEY must be set together");
+        }
+        if self.storage_s3_endpoint.as_ref().is_some_and(|e| url::Url::parse(e).is_err()) {
+            errors.push("STORAGE_S3_ENDPOINT must be an absolute URL");
+        }
+        if self.cloudflare_zone_id.is_some() != self.cloudflare_api_token.is_some() {
+            errors.push("CLOUDFLARE_ZONE_ID and CLOUDFLARE_API_TOKEN must be set together");
+        }
+        if matches!(self.captcha_provider, CaptchaProvider::HCaptcha | CaptchaProvider::Turnstile)
+            && self.captcha_secret.is_none()
+        {
+            errors.push("CAPTCHA_SECRET must be set when CAPTCHA_PROVIDER is hcaptcha or turnstile");
+        }
+        if self.is_production() && self.captcha_provider == CaptchaProvider::Test {
+            errors.push("CAPTCHA_PROVIDER=test is not allowed in production");
+        }
+        if self.error_reporting_dsn.as_deref().is_some_and(|dsn| dsn.parse::<SentryDsn>().is_err()) {
+            errors.push("ERROR_REPORTING_DSN must look like https://<key>@<host>/<project id>");
+        }
+        if !(0.0..=1.0).contains(&self.error_reporting_sample_rate) {
+            errors.push("ERROR_REPORTING_SAMPLE_RATE must be between 0 and 1");
+        }
+        if !(0.0..=1.0).contains(&self.error_reporting_job_sample_rate) {
+            errors.push("ERROR_REPORTING_JOB_SAMPLE_RATE must be between 0 and 1");
+        }
         if self.is_production() && self.cors_origins().iter().any(|o| o == "*") {
             errors.push("Wildcard CORS (*) is not allowed in production");
         }
+        if self.is_production() && self.body_logging.enabled {
+            errors.push("BODY_LOGGING cannot be enabled in production; use the X-Debug-Body-Log header");
+        }
+        if self.body_logging.redact_patterns.iter().any(|p| regex::Regex::new(p).is_err()) {
+            errors.push("body_logging.redact_patterns contains an invalid regular expression");
+        }
+        if self.route_policy.rules.iter().any(|rule| {
+            RoutePattern::parse(&rule.pattern).is_none()
+                || rule.methods.iter().any(|m| m.is_empty() || !m.chars().all(|c| c.is_ascii_alphabetic()))
+        }) {
+            errors.push("route_policy.rules contains an invalid pattern or method");
+        }
 
         if errors.is_empty() {
             Ok(())
@@ -149,6 +1511,178 @@ impl AppConfig {
         self.env == AppEnvironment::Production
     }
 
+    /// Concurrency budget per route scope: built-in defaults merged with
+    /// `scope_concurrency_limits` overrides. Malformed entries are ignored.
+    pub fn scope_concurrency_limits(&self) -> HashMap<String, usize> {
+        let mut limits: HashMap<String, usize> = [
+            ("blog", 64),
+            ("about-me", 32),
+            ("auth", 32),
+            ("users", 16),
+            ("admin", 16),
+            ("contact", 16),
+            ("hire", 16),
+            ("uses", 32),
+            ("changelog", 32),
+            ("bookmarks", 32),
+            ("feed", 32),
+            ("out", 64),
+            ("presence", 64),
+            ("reading", 32),
+            ("sync", 16),
+            ("storage", 16),
+            ("public-api", 16),
+            ("honeytoken", 8),
+        ]
+        .into_iter()
+        .map(|(scope, limit)| (scope.to_string(), limit))
+        .collect();
+
+        limits.extend(parse_scope_overrides(self.scope_concurrency_limits.as_deref()));
+        limits
+    }
+
+    /// Per-scope request timeouts from `route_timeouts`; scopes not listed
+    /// fall back to `request_timeout_ms`
+    pub fn route_timeouts(&self) -> HashMap<String, Duration> {
+        parse_scope_overrides::<u64>(self.route_timeouts.as_deref())
+            .into_iter()
+            .map(|(scope, ms)| (scope, Duration::from_millis(ms)))
+            .collect()
+    }
+
+    /// Routes whose limiter buckets are kept in Redis across restarts;
+    /// routes not listed, and malformed entries, stay in memory only
+    pub fn rate_limit_persistence(&self) -> HashMap<String, RateLimitPersistence> {
+        parse_scope_overrides(self.rate_limit_persistence.as_deref())
+    }
+
+    /// Tokens from `public_api_tokens` by name; malformed entries are ignored
+    pub fn public_api_tokens(&self) -> HashMap<String, String> {
+        parse_scope_overrides::<String>(self.public_api_tokens.as_deref())
+            .into_iter()
+            .filter(|(name, token)| !name.is_empty() && !token.is_empty())
+            .collect()
+    }
+
+    /// Keys from `contact_encryption_keys` by id. Unlike other pair lists a
+    /// malformed entry is an error, as it would leave messages unreadable.
+    pub fn contact_encryption_keys(&self) -> Result<HashMap<String, Zeroizing<[u8; 32]>>, &'static str> {
+        let Some(raw) = self.contact_encryption_keys.as_deref() else {
+            return Ok(HashMap::new());
+        };
+
+        raw.split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                let (id, key) = entry.split_once('=').ok_or(CONTACT_KEY_FORMAT)?;
+                let id = id.trim();
+                let id_valid = !id.is_empty()
+                    && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                let key = Zeroizing::new(BASE64_STANDARD.decode(key.trim()).map_err(|_| CONTACT_KEY_FORMAT)?);
+                match <[u8; 32]>::try_from(key.as_slice()) {
+                    Ok(key) if id_valid => Ok((id.to_string(), Zeroizing::new(key))),
+                    _ => Err(CONTACT_KEY_FORMAT),
+                }
+            })
+            .collect()
+    }
+
+    /// Key new contact messages are sealed with: the configured id when it
+    /// is listed, else the only key listed
+    pub fn contact_encryption_key_id(&self) -> Option<String> {
+        let keys = self.contact_encryption_keys().ok()?;
+        match self.contact_encryption_key_id.as_deref().map(str::trim) {
+            Some(id) => keys.contains_key(id).then(|| id.to_string()),
+            None if keys.len() == 1 => keys.into_keys().next(),
+            None => None,
+        }
+    }
+
+    /// The URLs in `redis_url`, in the order given
+    pub fn redis_urls(&self) -> Vec<String> {
+        self.redis_url
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Networks listed in `share_count_networks`, lowercased and without repeats
+    pub fn share_count_networks(&self) -> Vec<String> {
+        let mut networks: Vec<String> = Vec::new();
+        for network in self.share_count_networks.as_deref().unwrap_or_default().split(',') {
+            let network = network.trim().to_lowercase();
+            if !network.is_empty() && !networks.contains(&network) {
+                networks.push(network);
+            }
+        }
+        networks
+    }
+
+    /// Domains listed in `blocked_content_domains`, lowercased, without
+    /// wildcards or repeats
+    pub fn blocked_content_domains(&self) -> Vec<String> {
+        let mut domains: Vec<String> = Vec::new();
+        for domain in self.blocked_content_domains.as_deref().unwrap_or_default().split(',') {
+            let domain = domain.trim().trim_start_matches("*.").trim_matches('.').to_lowercase();
+            if !domain.is_empty() && !domains.contains(&domain) {
+                domains.push(domain);
+            }
+        }
+        domains
+    }
+
+    /// Percentages in `quota_alert_thresholds`, ascending and without
+    /// repeats; 80 and 95 when unset
+    pub fn quota_alert_thresholds(&self) -> Result<Vec<u8>, &'static str> {
+        let Some(raw) = self.quota_alert_thresholds.as_deref() else {
+            return Ok(vec![80, 95]);
+        };
+
+        let mut thresholds = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| match s.trim_end_matches('%').parse::<u8>() {
+                Ok(percent) if (1..=100).contains(&percent) => Ok(percent),
+                _ => Err("not a percentage between 1 and 100"),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        Ok(thresholds)
+    }
+
+    /// Ranges listed in `trusted_proxies`; a bare address is a range of one
+    pub fn trusted_proxies(&self) -> Result<Vec<IpNet>, &'static str> {
+        self.trusted_proxies
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse::<IpNet>()
+                    .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| "not an IP address or CIDR range")
+            })
+            .collect()
+    }
+
+    /// Extra stopwords from `tag_stopwords`, lowercased
+    pub fn tag_stopwords(&self) -> Vec<String> {
+        self.tag_stopwords
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
     pub fn cors_origins(&self) -> Vec<String> {
         self.cors_allowed_origins
             .iter()
@@ -159,6 +1693,21 @@ impl AppConfig {
     }
 }
 
+/// Parses `scope=value` pairs separated by commas, skipping malformed entries
+const CONTACT_KEY_FORMAT: &str =
+    "CONTACT_ENCRYPTION_KEYS entries must be id=<base64 of 32 bytes>, ids made of letters, digits, '-' and '_'";
+
+fn parse_scope_overrides<T: FromStr>(raw: Option<&str>) -> HashMap<String, T> {
+    raw.unwrap_or_default()
+        .split(',')
+        .filter_map(|entry| {
+            let (scope, value) = entry.split_once('=')?;
+            let value = value.trim().parse::<T>().ok()?;
+            Some((scope.trim().to_string(), value))
+        })
+        .collect()
+}
+
 fn fill_or_env(current: String, env_key: &str) -> Result<String, ConfigError> {
     if current.trim().is_empty() {
         env::var(env_key).map_err(|_| ConfigError::Message(format!("{env_key} must be set")))
@@ -208,12 +1757,101 @@ impl fmt::Debug for AppConfig {
             .field("port", &self.port)
             .field("host", &self.host)
             .field("worker_count", &self.worker_count)
+            .field("server_backlog", &self.server_backlog)
+            .field("server_keep_alive_secs", &self.server_keep_alive_secs)
+            .field("server_client_request_timeout_ms", &self.server_client_request_timeout_ms)
+            .field("server_max_connections", &self.server_max_connections)
             .field("database_url", &self.database_url.redact())
+            .field("redis_topology", &self.redis_topology)
+            .field("redis_sentinel_master", &self.redis_sentinel_master)
+            .field("redis_failover_retries", &self.redis_failover_retries)
+            .field("redis_health_check_secs", &self.redis_health_check_secs)
+            .field("redis_rebuild_after_failures", &self.redis_rebuild_after_failures)
             .field("cors_allowed_origins", &self.cors_allowed_origins)
             .field("jwt_secret", &self.jwt_secret.redact())
             .field("jwt_expiration_minutes", &self.jwt_expiration_minutes)
+            .field("jwt_issuer", &self.jwt_issuer)
+            .field("jwt_audience", &self.jwt_audience)
+            .field("jwt_enforce_issuer_audience", &self.jwt_enforce_issuer_audience)
             .field("refresh_token_secret", &self.refresh_token_secret.redact())
             .field("refresh_token_exp_days", &self.refresh_token_exp_days)
+            .field("password_hash_concurrency", &self.password_hash_concurrency)
+            .field("password_hash_queue_timeout_ms", &self.password_hash_queue_timeout_ms)
+            .field("site_gate_passphrase", &self.site_gate_passphrase.as_ref().map(|_| "[REDACTED]"))
+            .field("site_gate_ttl_hours", &self.site_gate_ttl_hours)
+            .field("visitor_token_ttl_days", &self.visitor_token_ttl_days)
+            .field("notification_email", &self.notification_email)
+            .field("mail_from", &self.mail_from)
+            .field("mail_relay_url", &self.mail_relay_url)
+            .field("mail_relay_token", &self.mail_relay_token.as_ref().map(|_| "[REDACTED]"))
+            .field("public_base_url", &self.public_base_url)
+            .field("base_path", &self.base_path)
+            .field("trusted_proxies", &self.trusted_proxies)
+            .field("contact_notification_policy", &self.contact_notification_policy)
+            .field("scope_concurrency_limits", &self.scope_concurrency_limits)
+            .field("scope_queue_timeout_ms", &self.scope_queue_timeout_ms)
+            .field("request_timeout_ms", &self.request_timeout_ms)
+            .field("route_timeouts", &self.route_timeouts)
+            .field("db_statement_timeout_ms", &self.db_statement_timeout_ms)
+            .field("cache_policy", &self.cache_policy)
+            .field("body_logging", &self.body_logging)
+            .field("route_policy", &self.route_policy)
+            .field("strict_tenant_hosts", &self.strict_tenant_hosts)
+            .field("dns_over_https_url", &self.dns_over_https_url)
+            .field("domain_verification_interval_secs", &self.domain_verification_interval_secs)
+            .field("deploy_hook_token", &self.deploy_hook_token.as_ref().map(|_| "[REDACTED]"))
+            .field("honeytoken_ban_secs", &self.honeytoken_ban_secs)
+            .field("blocked_content_domains", &self.blocked_content_domains)
+            .field("geoip_db_path", &self.geoip_db_path)
+            .field("page_cache_ttl_secs", &self.page_cache_ttl_secs)
+            .field("startup_warmup", &self.startup_warmup)
+            .field("startup_warmup_timeout_secs", &self.startup_warmup_timeout_secs)
+            .field("presence_ttl_secs", &self.presence_ttl_secs)
+            .field("post_edit_lock_ttl_secs", &self.post_edit_lock_ttl_secs)
+            .field("rate_limit_burst", &self.rate_limit_burst)
+            .field("rate_limit_per_minute", &self.rate_limit_per_minute)
+            .field("rate_limit_persistence", &self.rate_limit_persistence)
+            .field("public_api_tokens", &self.public_api_tokens.as_ref().map(|_| "[REDACTED]"))
+            .field("public_api_burst", &self.public_api_burst)
+            .field("public_api_per_minute", &self.public_api_per_minute)
+            .field("api_token_burst", &self.api_token_burst)
+            .field("api_token_per_minute", &self.api_token_per_minute)
+            .field("contact_queue_capacity", &self.contact_queue_capacity)
+            .field("contact_encryption_keys", &self.contact_encryption_keys.as_ref().map(|_| "[REDACTED]"))
+            .field("contact_encryption_key_id", &self.contact_encryption_key_id)
+            .field("featured_posts_max", &self.featured_posts_max)
+            .field("link_check_interval_secs", &self.link_check_interval_secs)
+            .field("retention_interval_secs", &self.retention_interval_secs)
+            .field("stats_rollup_interval_secs", &self.stats_rollup_interval_secs)
+            .field("stats_rollup_days", &self.stats_rollup_days)
+            .field("usage_collect_interval_secs", &self.usage_collect_interval_secs)
+            .field("email_monthly_quota", &self.email_monthly_quota)
+            .field("storage_quota_bytes", &self.storage_quota_bytes)
+            .field("quota_alert_thresholds", &self.quota_alert_thresholds)
+            .field("backfill_pause_ms", &self.backfill_pause_ms)
+            .field("link_check_concurrency", &self.link_check_concurrency)
+            .field("link_check_timeout_secs", &self.link_check_timeout_secs)
+            .field("tag_index_refresh_secs", &self.tag_index_refresh_secs)
+            .field("tag_stopwords", &self.tag_stopwords)
+            .field("share_count_networks", &self.share_count_networks)
+            .field("share_count_interval_secs", &self.share_count_interval_secs)
+            .field("share_count_request_spacing_ms", &self.share_count_request_spacing_ms)
+            .field("share_count_facebook_token", &self.share_count_facebook_token.as_ref().map(|_| "[REDACTED]"))
+            .field("user_content_policy", &self.user_content_policy)
+            .field("storage_backend", &self.storage_backend)
+            .field("storage_local_root", &self.storage_local_root)
+            .field("storage_s3_bucket", &self.storage_s3_bucket)
+            .field("storage_s3_region", &self.storage_s3_region)
+            .field("storage_s3_endpoint", &self.storage_s3_endpoint)
+            .field("storage_s3_access_key_id", &self.storage_s3_access_key_id)
+            .field("storage_s3_secret_access_key", &self.storage_s3_secret_access_key.as_ref().map(|_| "[REDACTED]"))
+            .field("cloudflare_zone_id", &self.cloudflare_zone_id)
+            .field("cloudflare_api_token", &self.cloudflare_api_token.as_ref().map(|_| "[REDACTED]"))
+            .field("captcha_provider", &self.captcha_provider)
+            .field("captcha_secret", &self.captcha_secret.as_ref().map(|_| "[REDACTED]"))
+            .field("error_reporting_dsn", &self.error_reporting_dsn.as_ref().map(|_| "[REDACTED]"))
+            .field("error_reporting_sample_rate", &self.error_reporting_sample_rate)
+            .field("error_reporting_job_sample_rate", &self.error_reporting_job_sample_rate)
             .finish()
     }
 }
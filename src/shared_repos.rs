@@ -1,26 +1,239 @@
-use crate::{repositories::sqlx_repo::{SqlxAboutMeRepo, SqlxBlogPostRepo, SqlxContactMeRepo, SqlxUserRepo}};
+use std::sync::Arc;
 
+use crate::repositories::{
+    about::AboutRepository,
+    api_token::ApiTokenRepository,
+    app_settings::AppSettingsRepository,
+    audit_log::AuditLogRepository,
+    backfill::BackfillRepository,
+    blog_post::BlogPostRepository,
+    bookmark::BookmarkRepository,
+    changelog::ChangelogRepository,
+    config_history::ConfigHistoryRepository,
+    contact_me::ContactMeRepository,
+    feature_flag::FeatureFlagRepository,
+    hire::HireInquiryRepository,
+    link_check::LinkCheckRepository,
+    notification_preferences::NotificationPreferencesRepository,
+    one_time_token::OneTimeTokenRepository,
+    outbound::OutboundClickRepository,
+    outbox::OutboxRepository,
+    reading_progress::ReadingProgressRepository,
+    redirect_rule::RedirectRuleRepository,
+    retention::RetentionRepository,
+    schema::SchemaRepository,
+    security_event::SecurityEventRepository,
+    share_count::ShareCountRepository,
+    stats_rollup::StatsRollupRepository,
+    sqlx_repo::{
+        SqlxAboutMeRepo, SqlxApiTokenRepo, SqlxAppSettingsRepo, SqlxAuditLogRepo, SqlxBackfillRepo, SqlxBlogPostRepo, SqlxBookmarkRepo, SqlxChangelogRepo, SqlxConfigHistoryRepo, SqlxContactMeRepo, SqlxFeatureFlagRepo,
+        SqlxHireInquiryRepo, SqlxLinkCheckRepo, SqlxNotificationPreferencesRepo, SqlxOneTimeTokenRepo, SqlxOutboundClickRepo, SqlxOutboxRepo, SqlxReadingProgressRepo, SqlxRedirectRuleRepo, SqlxRetentionRepo, SqlxSchemaRepo, SqlxSecurityEventRepo, SqlxShareCountRepo, SqlxStatsRollupRepo, SqlxTenantRepo, SqlxTitleTestRepo, SqlxUsageRepo, SqlxUserRepo,
+        SqlxUsesRepo,
+    },
+    tenant::TenantRepository,
+    title_test::TitleTestRepository,
+    user::UserRepository,
+    usage::UsageRepository,
+    uses::UsesRepository,
+};
 
+pub type DynUserRepo = Arc<dyn UserRepository>;
+pub type DynAboutRepo = Arc<dyn AboutRepository>;
+pub type DynBlogPostRepo = Arc<dyn BlogPostRepository>;
+pub type DynContactRepo = Arc<dyn ContactMeRepository>;
+pub type DynFeatureFlagRepo = Arc<dyn FeatureFlagRepository>;
+pub type DynAppSettingsRepo = Arc<dyn AppSettingsRepository>;
+pub type DynTenantRepo = Arc<dyn TenantRepository>;
+pub type DynHireInquiryRepo = Arc<dyn HireInquiryRepository>;
+pub type DynUsesRepo = Arc<dyn UsesRepository>;
+pub type DynChangelogRepo = Arc<dyn ChangelogRepository>;
+pub type DynOutboundClickRepo = Arc<dyn OutboundClickRepository>;
+pub type DynSecurityEventRepo = Arc<dyn SecurityEventRepository>;
+pub type DynOutboxRepo = Arc<dyn OutboxRepository>;
+pub type DynLinkCheckRepo = Arc<dyn LinkCheckRepository>;
+pub type DynAuditLogRepo = Arc<dyn AuditLogRepository>;
+pub type DynRetentionRepo = Arc<dyn RetentionRepository>;
+pub type DynNotificationPreferencesRepo = Arc<dyn NotificationPreferencesRepository>;
+pub type DynReadingProgressRepo = Arc<dyn ReadingProgressRepository>;
+pub type DynTitleTestRepo = Arc<dyn TitleTestRepository>;
+pub type DynApiTokenRepo = Arc<dyn ApiTokenRepository>;
+pub type DynStatsRollupRepo = Arc<dyn StatsRollupRepository>;
+pub type DynOneTimeTokenRepo = Arc<dyn OneTimeTokenRepository>;
+pub type DynBookmarkRepo = Arc<dyn BookmarkRepository>;
+pub type DynRedirectRuleRepo = Arc<dyn RedirectRuleRepository>;
+pub type DynSchemaRepo = Arc<dyn SchemaRepository>;
+pub type DynShareCountRepo = Arc<dyn ShareCountRepository>;
+pub type DynConfigHistoryRepo = Arc<dyn ConfigHistoryRepository>;
+pub type DynBackfillRepo = Arc<dyn BackfillRepository>;
+pub type DynUsageRepo = Arc<dyn UsageRepository>;
+
+/// Repository set backing `AppState`.
+///
+/// Fields are trait objects so handlers can be exercised against mocks
+/// (see `MockUserRepository` and friends) without a running Postgres.
 #[derive(Clone)]
 pub struct SharedRepositories {
-    pub user_repo: SqlxUserRepo,
-    pub about_repo: SqlxAboutMeRepo,
-    pub blog_post_repo: SqlxBlogPostRepo,
-    pub contact_repo: SqlxContactMeRepo,
+    pub user_repo: DynUserRepo,
+    pub about_repo: DynAboutRepo,
+    pub blog_post_repo: DynBlogPostRepo,
+    pub contact_repo: DynContactRepo,
+    pub feature_flag_repo: DynFeatureFlagRepo,
+    pub settings_repo: DynAppSettingsRepo,
+    pub tenant_repo: DynTenantRepo,
+    pub hire_repo: DynHireInquiryRepo,
+    pub uses_repo: DynUsesRepo,
+    pub changelog_repo: DynChangelogRepo,
+    pub outbound_repo: DynOutboundClickRepo,
+    pub security_event_repo: DynSecurityEventRepo,
+    pub outbox_repo: DynOutboxRepo,
+    pub link_check_repo: DynLinkCheckRepo,
+    pub audit_repo: DynAuditLogRepo,
+    pub retention_repo: DynRetentionRepo,
+    pub notification_preferences_repo: DynNotificationPreferencesRepo,
+    pub reading_progress_repo: DynReadingProgressRepo,
+    pub title_test_repo: DynTitleTestRepo,
+    pub api_token_repo: DynApiTokenRepo,
+    pub stats_rollup_repo: DynStatsRollupRepo,
+    pub one_time_token_repo: DynOneTimeTokenRepo,
+    pub bookmark_repo: DynBookmarkRepo,
+    pub redirect_rule_repo: DynRedirectRuleRepo,
+    pub schema_repo: DynSchemaRepo,
+    pub share_count_repo: DynShareCountRepo,
+    pub config_history_repo: DynConfigHistoryRepo,
+    pub backfill_repo: DynBackfillRepo,
+    pub usage_repo: DynUsageRepo,
 }
 
 impl SharedRepositories {
     pub fn new(pool: sqlx::PgPool) -> Self {
-        let user_repo = SqlxUserRepo::new(pool.clone());
-        let about_repo = SqlxAboutMeRepo::new(pool.clone());
-        let blog_post_repo = SqlxBlogPostRepo::new(pool.clone());
-        let contact_repo = SqlxContactMeRepo::new(pool.clone());
+        let user_repo = Arc::new(SqlxUserRepo::new(pool.clone()));
+        let about_repo = Arc::new(SqlxAboutMeRepo::new(pool.clone()));
+        let blog_post_repo = Arc::new(SqlxBlogPostRepo::new(pool.clone()));
+        let contact_repo = Arc::new(SqlxContactMeRepo::new(pool.clone()));
+        let feature_flag_repo = Arc::new(SqlxFeatureFlagRepo::new(pool.clone()));
+        let settings_repo = Arc::new(SqlxAppSettingsRepo::new(pool.clone()));
+        let tenant_repo = Arc::new(SqlxTenantRepo::new(pool.clone()));
+        let hire_repo = Arc::new(SqlxHireInquiryRepo::new(pool.clone()));
+        let uses_repo = Arc::new(SqlxUsesRepo::new(pool.clone()));
+        let changelog_repo = Arc::new(SqlxChangelogRepo::new(pool.clone()));
+        let outbound_repo = Arc::new(SqlxOutboundClickRepo::new(pool.clone()));
+        let security_event_repo = Arc::new(SqlxSecurityEventRepo::new(pool.clone()));
+        let outbox_repo = Arc::new(SqlxOutboxRepo::new(pool.clone()));
+        let link_check_repo = Arc::new(SqlxLinkCheckRepo::new(pool.clone()));
+        let audit_repo = Arc::new(SqlxAuditLogRepo::new(pool.clone()));
+        let retention_repo = Arc::new(SqlxRetentionRepo::new(pool.clone()));
+        let notification_preferences_repo = Arc::new(SqlxNotificationPreferencesRepo::new(pool.clone()));
+        let reading_progress_repo = Arc::new(SqlxReadingProgressRepo::new(pool.clone()));
+        let title_test_repo = Arc::new(SqlxTitleTestRepo::new(pool.clone()));
+        let api_token_repo = Arc::new(SqlxApiTokenRepo::new(pool.clone()));
+        let stats_rollup_repo = Arc::new(SqlxStatsRollupRepo::new(pool.clone()));
+        let one_time_token_repo = Arc::new(SqlxOneTimeTokenRepo::new(pool.clone()));
+        let bookmark_repo = Arc::new(SqlxBookmarkRepo::new(pool.clone()));
+        let redirect_rule_repo = Arc::new(SqlxRedirectRuleRepo::new(pool.clone()));
+        let schema_repo = Arc::new(SqlxSchemaRepo::new(pool.clone()));
+        let share_count_repo = Arc::new(SqlxShareCountRepo::new(pool.clone()));
+        let config_history_repo = Arc::new(SqlxConfigHistoryRepo::new(pool.clone()));
+        let backfill_repo = Arc::new(SqlxBackfillRepo::new(pool.clone()));
+        let usage_repo = Arc::new(SqlxUsageRepo::new(pool.clone()));
         
         SharedRepositories {
             user_repo,
             about_repo,
             blog_post_repo,
             contact_repo,
+            feature_flag_repo,
+            settings_repo,
+            tenant_repo,
+            hire_repo,
+            uses_repo,
+            changelog_repo,
+            outbound_repo,
+            security_event_repo,
+            outbox_repo,
+            link_check_repo,
+            audit_repo,
+            retention_repo,
+            notification_preferences_repo,
+            reading_progress_repo,
+            title_test_repo,
+            api_token_repo,
+            stats_rollup_repo,
+            one_time_token_repo,
+            bookmark_repo,
+            redirect_rule_repo,
+            schema_repo,
+            share_count_repo,
+            config_history_repo,
+            backfill_repo,
+            usage_repo,
         }
     }
-}
\ No newline at end of file
+
+    /// Repositories held entirely in process memory; nothing is persisted.
+    #[cfg(feature = "in-memory")]
+    pub fn in_memory() -> Self {
+        use crate::repositories::in_memory::{
+            InMemoryAboutMeRepo, InMemoryApiTokenRepo, InMemoryAppSettingsRepo, InMemoryAuditLogRepo, InMemoryBackfillRepo, InMemoryBlogPostRepo, InMemoryBookmarkRepo, InMemoryChangelogRepo, InMemoryConfigHistoryRepo, InMemoryContactMeRepo,
+            InMemoryFeatureFlagRepo, InMemoryHireInquiryRepo, InMemoryLinkCheckRepo, InMemoryNotificationPreferencesRepo, InMemoryOneTimeTokenRepo, InMemoryOutboundClickRepo, InMemoryOutboxRepo,
+            InMemoryReadingProgressRepo, InMemoryRedirectRuleRepo, InMemoryRetentionRepo, InMemorySchemaRepo, InMemorySecurityEventRepo, InMemoryShareCountRepo, InMemoryStatsRollupRepo, InMemoryTenantRepo, InMemoryTitleTestRepo, InMemoryUsageRepo, InMemoryUserRepo, InMemoryUsesRepo,
+        };
+
+        // Shared so creates enqueue onto the same outbox the relay drains
+        let outbox = InMemoryOutboxRepo::default();
+        let users = InMemoryUserRepo::default();
+        let contacts = InMemoryContactMeRepo::with_outbox(outbox.clone());
+        let clicks = InMemoryOutboundClickRepo::default();
+        let events = InMemorySecurityEventRepo::default();
+        let audit = InMemoryAuditLogRepo::default();
+        let reading = InMemoryReadingProgressRepo::new(users.clone());
+        let titles = InMemoryTitleTestRepo::default();
+        // Retention and rollups work on the same stores the other repositories write to
+        let retention = InMemoryRetentionRepo::new(
+            contacts.clone(),
+            clicks.clone(),
+            reading.clone(),
+            titles.clone(),
+            events.clone(),
+            audit.clone(),
+        );
+        let rollups = InMemoryStatsRollupRepo::new(clicks.clone(), reading.clone(), events.clone());
+
+        let notification_preferences = InMemoryNotificationPreferencesRepo::new(users.clone());
+        // Versioned changes write to the same settings and flags the services read
+        let settings = InMemoryAppSettingsRepo::default();
+        let flags = InMemoryFeatureFlagRepo::default();
+        let config_history = InMemoryConfigHistoryRepo::new(settings.clone(), flags.clone());
+
+        SharedRepositories {
+            user_repo: Arc::new(users.clone()),
+            about_repo: Arc::new(InMemoryAboutMeRepo::default()),
+            blog_post_repo: Arc::new(InMemoryBlogPostRepo::with_users(users)),
+            contact_repo: Arc::new(contacts),
+            feature_flag_repo: Arc::new(flags),
+            settings_repo: Arc::new(settings),
+            tenant_repo: Arc::new(InMemoryTenantRepo::default()),
+            hire_repo: Arc::new(InMemoryHireInquiryRepo::with_outbox(outbox.clone())),
+            uses_repo: Arc::new(InMemoryUsesRepo::default()),
+            changelog_repo: Arc::new(InMemoryChangelogRepo::default()),
+            outbound_repo: Arc::new(clicks),
+            security_event_repo: Arc::new(events),
+            outbox_repo: Arc::new(outbox),
+            link_check_repo: Arc::new(InMemoryLinkCheckRepo::default()),
+            audit_repo: Arc::new(audit),
+            retention_repo: Arc::new(retention),
+            notification_preferences_repo: Arc::new(notification_preferences),
+            reading_progress_repo: Arc::new(reading),
+            title_test_repo: Arc::new(titles),
+            api_token_repo: Arc::new(InMemoryApiTokenRepo::default()),
+            stats_rollup_repo: Arc::new(rollups),
+            one_time_token_repo: Arc::new(InMemoryOneTimeTokenRepo::default()),
+            bookmark_repo: Arc::new(InMemoryBookmarkRepo::default()),
+            redirect_rule_repo: Arc::new(InMemoryRedirectRuleRepo::default()),
+            schema_repo: Arc::new(InMemorySchemaRepo),
+            share_count_repo: Arc::new(InMemoryShareCountRepo::default()),
+            config_history_repo: Arc::new(config_history),
+            backfill_repo: Arc::new(InMemoryBackfillRepo::default()),
+            usage_repo: Arc::new(InMemoryUsageRepo::default()),
+        }
+    }
+}
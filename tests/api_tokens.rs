@@ -0,0 +1,236 @@
+mod common;
+
+use std::sync::Arc;
+
+use chrono::{Datelike, Months, Utc};
+use common::test_config;
+use portfolio_backend::{
+    cache::api_token_usage::MockApiTokenUsageCounter,
+    entities::api_token::{hash_api_token, ApiToken, ApiTokenDailyUsage, ApiTokenUsageQuery, CreateApiTokenRequest, API_SCOPE_ANALYTICS},
+    errors::{AppError, AuthError},
+    repositories::api_token::MockApiTokenRepository,
+    use_cases::api_tokens::{ApiTokenKey, ApiTokens},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn stored(tenant_id: Uuid, token: &str) -> ApiToken {
+    ApiToken {
+        id: Uuid::new_v4(),
+        tenant_id,
+        name: "partner".to_string(),
+        token_hash: hash_api_token(token),
+        token_prefix: token[..11].to_string(),
+        scopes: vec![API_SCOPE_ANALYTICS.to_string()],
+        rate_limit_per_minute: Some(120),
+        monthly_quota: None,
+        request_count: 0,
+        rejected_count: 0,
+        last_used_at: None,
+        created_by: None,
+        created_at: Utc::now(),
+        revoked_at: None,
+    }
+}
+
+#[test]
+fn configured_tokens_work_on_every_site_but_only_for_known_scopes() {
+    let config = test_config(json!({ "public_api_tokens": "frontend=secret-one, broken, =nameless" }));
+    let tokens = ApiTokens::new(MockApiTokenRepository::new(), None, &config);
+
+    for tenant_id in [Uuid::new_v4(), Uuid::new_v4()] {
+        let grant = tokens.authorize(&tenant_id, "secret-one", API_SCOPE_ANALYTICS).expect("accepted");
+        assert_eq!(grant.key, ApiTokenKey::Config("frontend".to_string()));
+        assert_eq!(grant.per_minute, config.api_token_per_minute);
+    }
+
+    assert!(matches!(
+        tokens.authorize(&Uuid::new_v4(), "secret-one", "search"),
+        Err(AuthError::Forbidden(_))
+    ));
+    assert!(matches!(
+        tokens.authorize(&Uuid::new_v4(), "secret-two", API_SCOPE_ANALYTICS),
+        Err(AuthError::InvalidToken)
+    ));
+}
+
+#[tokio::test]
+async fn issued_tokens_are_bound_to_their_site_until_revoked() {
+    let tenant_id = Uuid::new_v4();
+    let mut repo = MockApiTokenRepository::new();
+    repo.expect_create_api_token().returning(|tenant_id, insert| {
+        let mut token = stored(*tenant_id, "pk_0123456789");
+        token.token_hash = insert.token_hash.clone();
+        token.token_prefix = insert.token_prefix.clone();
+        Ok(token)
+    });
+    repo.expect_revoke_api_token().returning(|_, _| Ok(true));
+    let tokens = ApiTokens::new(repo, None, &test_config(json!({})));
+
+    let request = CreateApiTokenRequest {
+        name: "partner".to_string(),
+        scopes: vec![API_SCOPE_ANALYTICS.to_string()],
+        rate_limit_per_minute: None,
+        monthly_quota: None,
+    };
+    let issued = tokens.issue(tenant_id, None, request).await.expect("issued");
+    assert!(issued.token.starts_with("pk_"));
+    assert!(issued.token.starts_with(issued.summary.token_prefix.as_deref().unwrap()));
+
+    let grant = tokens.authorize(&tenant_id, &issued.token, API_SCOPE_ANALYTICS).expect("accepted");
+    assert_eq!(grant.per_minute, 120);
+    assert!(tokens.authorize(&Uuid::new_v4(), &issued.token, API_SCOPE_ANALYTICS).is_err());
+
+    tokens.revoke(tenant_id, issued.summary.id.unwrap()).await.expect("revoked");
+    assert!(matches!(
+        tokens.authorize(&tenant_id, &issued.token, API_SCOPE_ANALYTICS),
+        Err(AuthError::InvalidToken)
+    ));
+}
+
+#[tokio::test]
+async fn usage_of_issued_tokens_is_flushed_and_kept_when_the_flush_fails() {
+    let issued_id = Uuid::new_v4();
+    let mut repo = MockApiTokenRepository::new();
+    let mut attempts = 0;
+    repo.expect_add_api_token_usage().times(2).returning(move |usage| {
+        attempts += 1;
+        assert_eq!(usage.len(), 1);
+        let (id, counted) = usage[0];
+        assert_eq!(id, issued_id);
+        if attempts == 1 {
+            assert_eq!((counted.requests, counted.rejected), (2, 1));
+            return Err(AppError::InternalError("database down".to_string()));
+        }
+        assert_eq!((counted.requests, counted.rejected), (3, 1));
+        Ok(())
+    });
+    repo.expect_list_api_tokens().returning(|_| Ok(Vec::new()));
+    let tokens = ApiTokens::new(repo, None, &test_config(json!({ "public_api_tokens": "frontend=secret-one" })));
+    let configured = ApiTokenKey::Config("frontend".to_string());
+    let issued = ApiTokenKey::Issued(issued_id);
+
+    tokens.record_use(&issued, true);
+    tokens.record_use(&issued, false);
+    tokens.record_use(&configured, true);
+    assert!(tokens.flush_usage().await.is_err());
+
+    tokens.record_use(&issued, true);
+    tokens.flush_usage().await.expect("flushed");
+
+    let report = tokens.report(&Uuid::new_v4()).await.expect("report");
+    assert_eq!(report.total, 1);
+    assert_eq!(report.tokens[0].name, "frontend");
+    assert_eq!(report.tokens[0].request_count, 1);
+}
+
+#[tokio::test]
+async fn monthly_quota_counts_stored_and_unflushed_requests() {
+    let tenant_id = Uuid::new_v4();
+    let mut token = stored(tenant_id, "pk_quota123456");
+    token.monthly_quota = Some(3);
+    let token_id = token.id;
+
+    let mut repo = MockApiTokenRepository::new();
+    repo.expect_add_api_token_usage().returning(|_| Ok(()));
+    repo.expect_list_active_api_tokens().returning(move || Ok(vec![token.clone()]));
+    repo.expect_count_quota_requests_since().returning(move |since| {
+        assert_eq!(since.day(), 1);
+        Ok(vec![(token_id, 2)])
+    });
+    let tokens = ApiTokens::new(repo, None, &test_config(json!({})));
+    tokens.refresh().await.expect("refreshed");
+
+    let grant = tokens.authorize(&tenant_id, "pk_quota123456", API_SCOPE_ANALYTICS).expect("accepted");
+    assert_eq!(grant.monthly_quota, Some(3));
+    assert!(tokens.check_quota(&grant).is_ok());
+
+    tokens.record_traffic(&grant.key, 512, false);
+    let retry_after = tokens.check_quota(&grant).expect_err("quota used up");
+    assert!(retry_after > 0 && retry_after <= 31 * 24 * 60 * 60);
+}
+
+#[tokio::test]
+async fn daily_traffic_goes_through_redis_and_unsaved_totals_are_retried() {
+    let token_id = Uuid::new_v4();
+    let mut counter = MockApiTokenUsageCounter::new();
+    let mut requests = 0;
+    counter.expect_add_usage().times(2).returning(move |usage| {
+        assert_eq!(usage.len(), 1);
+        requests += usage[0].requests;
+        Ok(vec![ApiTokenDailyUsage { requests, ..usage[0].clone() }])
+    });
+
+    let mut repo = MockApiTokenRepository::new();
+    repo.expect_add_api_token_usage().returning(|_| Ok(()));
+    repo.expect_add_api_token_daily_usage().never();
+    let mut attempts = 0;
+    repo.expect_save_api_token_daily_totals().times(2).returning(move |totals| {
+        attempts += 1;
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].token_id, token_id);
+        if attempts == 1 {
+            assert_eq!((totals[0].requests, totals[0].bytes, totals[0].errors), (2, 300, 1));
+            return Err(AppError::InternalError("database down".to_string()));
+        }
+        // The newer total replaces the one that was not saved
+        assert_eq!(totals[0].requests, 3);
+        Ok(())
+    });
+    let tokens = ApiTokens::new(repo, Some(Arc::new(counter)), &test_config(json!({})));
+    let key = ApiTokenKey::Issued(token_id);
+
+    tokens.record_traffic(&key, 100, false);
+    tokens.record_traffic(&key, 200, true);
+    tokens.record_traffic(&ApiTokenKey::Config("frontend".to_string()), 100, false);
+    assert!(tokens.flush_usage().await.is_err());
+
+    tokens.record_traffic(&key, 50, false);
+    tokens.flush_usage().await.expect("flushed");
+}
+
+#[tokio::test]
+async fn usage_report_lists_months_newest_first_with_unflushed_traffic() {
+    let tenant_id = Uuid::new_v4();
+    let mut token = stored(tenant_id, "pk_report12345");
+    token.monthly_quota = Some(100);
+    let token_id = token.id;
+    let today = Utc::now().date_naive();
+    let last_month = today.with_day(1).unwrap() - Months::new(1);
+
+    let mut repo = MockApiTokenRepository::new();
+    repo.expect_list_api_tokens().returning(move |_| Ok(vec![token.clone()]));
+    repo.expect_list_api_token_daily_usage().returning(move |id, since| {
+        assert_eq!(*id, token_id);
+        assert_eq!(since, last_month);
+        Ok(vec![
+            ApiTokenDailyUsage { token_id, day: last_month, requests: 40, bytes: 4000, errors: 2 },
+            ApiTokenDailyUsage { token_id, day: today, requests: 5, bytes: 500, errors: 0 },
+        ])
+    });
+    let tokens = ApiTokens::new(repo, None, &test_config(json!({})));
+    tokens.record_traffic(&ApiTokenKey::Issued(token_id), 100, true);
+
+    let report = tokens
+        .usage(&tenant_id, token_id, ApiTokenUsageQuery { months: Some(2) })
+        .await
+        .expect("report");
+    assert_eq!(report.monthly_quota, Some(100));
+    assert_eq!(report.months.len(), 2);
+
+    let current = &report.months[0];
+    assert_eq!(current.month, today.with_day(1).unwrap());
+    assert_eq!((current.requests, current.bytes, current.errors), (6, 600, 1));
+    assert_eq!(current.quota_remaining, Some(94));
+    assert_eq!(current.days.len(), 1);
+
+    let previous = &report.months[1];
+    assert_eq!(previous.month, last_month);
+    assert_eq!((previous.requests, previous.quota_remaining), (40, Some(60)));
+
+    assert!(matches!(
+        tokens.usage(&tenant_id, Uuid::new_v4(), ApiTokenUsageQuery { months: None }).await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(tokens.usage(&tenant_id, token_id, ApiTokenUsageQuery { months: Some(0) }).await.is_err());
+}
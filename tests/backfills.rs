@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+use portfolio_backend::{
+    entities::backfill::{BackfillState, BackfillTask, BACKFILL_COMPLETED, BACKFILL_RUNNING},
+    errors::AppError,
+    repositories::backfill::MockBackfillRepository,
+    use_cases::backfills::Backfills,
+};
+
+const fn task(name: &'static str) -> BackfillTask {
+    BackfillTask {
+        name,
+        description: "Test fill",
+        table: "blog_posts",
+        pending: "TRUE",
+        fill: "SELECT $1",
+        batch_size: 10,
+    }
+}
+
+fn state(name: &str, status: &str, processed: i64, total: i64) -> BackfillState {
+    BackfillState {
+        name: name.to_string(),
+        status: status.to_string(),
+        checkpoint: None,
+        processed,
+        total: Some(total),
+        last_error: None,
+        started_at: Some(Utc::now()),
+        completed_at: None,
+        updated_at: Utc::now(),
+    }
+}
+
+/// Progress rows as the table holds them; each batch fills up to 10 of
+/// `rows` pending rows, and tasks named in `failing` error instead
+type States = Arc<Mutex<HashMap<String, BackfillState>>>;
+
+fn backfills(states: &States, rows: i64, failing: &'static [&'static str], tasks: Vec<BackfillTask>) -> Backfills<MockBackfillRepository> {
+    let mut repo = MockBackfillRepository::new();
+
+    let stored = states.clone();
+    repo.expect_list_states()
+        .returning(move || Ok(stored.lock().unwrap().values().cloned().collect()));
+    let stored = states.clone();
+    repo.expect_run_batch().returning(move |task| {
+        if failing.contains(&task.name) {
+            return Err(AppError::InternalError("Batch failed".to_string()));
+        }
+        let mut states = stored.lock().unwrap();
+        let state = states
+            .entry(task.name.to_string())
+            .or_insert_with(|| state(task.name, BACKFILL_RUNNING, 0, rows));
+        state.processed = (state.processed + task.batch_size).min(rows);
+        if state.processed == rows {
+            state.status = BACKFILL_COMPLETED.to_string();
+        }
+        Ok(Some(state.clone()))
+    });
+    let stored = states.clone();
+    repo.expect_record_failure().returning(move |name, error| {
+        stored.lock().unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| state(name, BACKFILL_RUNNING, 0, rows))
+            .last_error = Some(error.to_string());
+        Ok(())
+    });
+
+    Backfills::new(repo, tasks)
+}
+
+#[tokio::test]
+async fn passes_resume_from_stored_progress_and_skip_completed_tasks() {
+    let states = States::default();
+    states.lock().unwrap().insert("search".to_string(), state("search", BACKFILL_RUNNING, 20, 25));
+    states.lock().unwrap().insert("reading".to_string(), state("reading", BACKFILL_COMPLETED, 25, 25));
+    let backfills = backfills(&states, 25, &[], vec![task("search"), task("reading")]);
+
+    // A restart picks up at 20 of 25, so one batch finishes it
+    let pass = backfills.run_pass().await.unwrap();
+    assert_eq!((pass.batches, pass.remaining), (1, 0));
+    assert_eq!(states.lock().unwrap()["search"].processed, 25);
+    assert_eq!(states.lock().unwrap()["reading"].processed, 25, "completed tasks are not run again");
+
+    let pass = backfills.run_pass().await.unwrap();
+    assert_eq!((pass.batches, pass.remaining), (0, 0));
+}
+
+#[tokio::test]
+async fn a_failing_task_is_recorded_and_retried_without_holding_up_others() {
+    let states = States::default();
+    let backfills = backfills(&states, 15, &["search"], vec![task("search"), task("reading")]);
+
+    let pass = backfills.run_pass().await.unwrap();
+    assert_eq!((pass.batches, pass.remaining), (1, 2));
+    assert_eq!(pass.failures.len(), 1);
+    assert_eq!(pass.failures[0].0, "search");
+
+    let pass = backfills.run_pass().await.unwrap();
+    assert_eq!((pass.batches, pass.remaining), (1, 1), "reading completes, search stays due");
+
+    let progress = backfills.progress().await.unwrap();
+    let search = progress.backfills.iter().find(|b| b.name == "search").unwrap();
+    assert!(search.last_error.as_deref().is_some_and(|e| e.contains("Batch failed")));
+    assert!(progress.running);
+}
+
+#[tokio::test]
+async fn progress_lists_every_declared_task_with_a_percentage() {
+    let states = States::default();
+    let backfills = backfills(&states, 40, &[], vec![task("search"), task("reading")]);
+
+    backfills.run_pass().await.unwrap();
+    // Rows inserted after the count can take `processed` past `total`
+    states.lock().unwrap().get_mut("reading").unwrap().processed = 55;
+
+    let progress = backfills.progress().await.unwrap();
+    let percents: Vec<_> = progress.backfills.iter().map(|b| (b.name.as_str(), b.percent)).collect();
+    assert_eq!(percents, vec![("search", Some(25)), ("reading", Some(99))]);
+    assert!(progress.running);
+
+    while backfills.run_pass().await.unwrap().remaining > 0 {}
+    let progress = backfills.progress().await.unwrap();
+    assert!(progress.backfills.iter().all(|b| b.percent == Some(100) && b.status == BACKFILL_COMPLETED));
+    assert!(!progress.running);
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use portfolio_backend::{
+    entities::{
+        bookmark::{normalize_tags, Bookmark, BookmarkQuery, LinkPreview, PREVIEW_PENDING},
+        view::Viewed,
+    },
+    errors::AppError,
+    links::preview::{parse_link_preview, MockLinkPreviewFetcher},
+    repositories::bookmark::MockBookmarkRepository,
+    use_cases::bookmarks::BookmarkHandler,
+};
+use url::Url;
+use uuid::Uuid;
+
+fn pending(url: &str) -> Bookmark {
+    Bookmark {
+        id: Uuid::new_v4(),
+        tenant_id: Uuid::new_v4(),
+        url: url.to_string(),
+        title: None,
+        note: None,
+        tags: vec!["rust".to_string()],
+        is_public: true,
+        preview_status: PREVIEW_PENDING.to_string(),
+        preview_title: None,
+        preview_description: None,
+        preview_image_url: None,
+        preview_site_name: None,
+        preview_error: None,
+        preview_attempts: 0,
+        preview_fetched_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn open_graph_tags_win_over_title_and_description() {
+    let base = Url::parse("https://example.com/posts/one").unwrap();
+    let html = r#"
+        <html><head>
+          <title>Plain   title</title>
+          <meta name="description" content="Plain description">
+          <meta property="og:title" content="Rust &amp; You">
+          <meta content='An OG description' property='og:description' />
+          <meta property="og:image" content="/img/cover.png">
+          <meta property="og:site_name" content="Example">
+        </head><body></body></html>
+    "#;
+
+    assert_eq!(parse_link_preview(html, &base), LinkPreview {
+        title: Some("Rust & You".to_string()),
+        description: Some("An OG description".to_string()),
+        image_url: Some("https://example.com/img/cover.png".to_string()),
+        site_name: Some("Example".to_string()),
+    });
+}
+
+#[test]
+fn plain_title_and_description_are_used_without_open_graph() {
+    let base = Url::parse("https://example.com/").unwrap();
+    let html = "<head><TITLE>\n  A   page\n</TITLE><meta name=\"Description\" content=\"It&#39;s short\"></head>";
+
+    let preview = parse_link_preview(html, &base);
+    assert_eq!(preview.title.as_deref(), Some("A page"));
+    assert_eq!(preview.description.as_deref(), Some("It's short"));
+    assert_eq!(preview.image_url, None);
+}
+
+#[test]
+fn images_on_private_addresses_are_dropped() {
+    let base = Url::parse("https://example.com/").unwrap();
+    let html = r#"<meta property="og:image" content="http://169.254.169.254/latest/meta-data">"#;
+
+    assert_eq!(parse_link_preview(html, &base).image_url, None);
+}
+
+#[test]
+fn long_titles_are_truncated() {
+    let base = Url::parse("https://example.com/").unwrap();
+    let html = format!("<title>{}</title>", "a".repeat(1000));
+
+    let title = parse_link_preview(&html, &base).title.unwrap();
+    assert!(title.chars().count() <= 301);
+    assert!(title.ends_with('…'));
+}
+
+#[test]
+fn tags_are_lowercased_and_deduplicated_in_order() {
+    let tags = vec![" Rust ".to_string(), "web".to_string(), "rust".to_string(), "".to_string()];
+
+    assert_eq!(normalize_tags(tags), vec!["rust", "web"]);
+}
+
+#[test]
+fn the_public_view_hides_preview_bookkeeping() {
+    let bookmark = pending("https://example.com/");
+
+    let public = serde_json::to_value(Viewed::public(&bookmark)).unwrap();
+    assert!(public.get("preview_status").is_none());
+    assert!(public.get("is_public").is_none());
+    assert!(public.get("tenant_id").is_none());
+    assert_eq!(public["url"], "https://example.com/");
+
+    let admin = serde_json::to_value(Viewed::admin(&bookmark)).unwrap();
+    assert_eq!(admin["preview_status"], PREVIEW_PENDING);
+}
+
+#[tokio::test]
+async fn pending_previews_are_recorded_as_ready_or_failed() {
+    let ok = pending("https://ok.example/");
+    let broken = pending("https://broken.example/");
+    let (ok_id, broken_id) = (ok.id, broken.id);
+
+    let mut repo = MockBookmarkRepository::new();
+    let batch = vec![ok, broken];
+    repo.expect_list_pending_previews().times(1).returning(move |_| Ok(batch.clone()));
+    repo.expect_record_preview().times(1).returning(move |id, preview| {
+        assert_eq!(*id, ok_id);
+        assert_eq!(preview.title.as_deref(), Some("Ok"));
+        Ok(())
+    });
+    repo.expect_record_preview_failure().times(1).returning(move |id, error| {
+        assert_eq!(*id, broken_id);
+        assert!(error.contains("Connection failed"));
+        Ok(())
+    });
+
+    let mut fetcher = MockLinkPreviewFetcher::new();
+    fetcher.expect_fetch().times(2).returning(|url| {
+        if url.contains("broken") {
+            Err(AppError::ServiceUnavailable("Connection failed".to_string()))
+        } else {
+            Ok(LinkPreview { title: Some("Ok".to_string()), ..Default::default() })
+        }
+    });
+
+    let handler = BookmarkHandler::new(Arc::new(repo), Arc::new(fetcher));
+    let run = handler.fetch_pending_previews(10).await.unwrap();
+
+    assert_eq!((run.ready, run.failed), (1, 1));
+}
+
+#[tokio::test]
+async fn the_blogroll_only_lists_public_bookmarks() {
+    let mut repo = MockBookmarkRepository::new();
+    repo.expect_list_bookmarks().times(1).returning(|_, public_only, tag, limit, offset| {
+        assert!(public_only);
+        assert_eq!(tag.as_deref(), Some("rust"));
+        assert_eq!((limit, offset), (10, 10));
+        Ok(vec![])
+    });
+    repo.expect_count_bookmarks().times(1).returning(|_, _, _| Ok(12));
+
+    let handler = BookmarkHandler::new(repo, Arc::new(MockLinkPreviewFetcher::new()));
+    let query = BookmarkQuery { page: Some(2), per_page: Some(10), tag: Some(" Rust ".to_string()) };
+    let list = handler.list_bookmarks(Uuid::new_v4(), query, true).await.unwrap();
+
+    assert_eq!((list.total, list.page, list.per_page), (12, 2, 10));
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use portfolio_backend::{
+    captcha::verifier::{CaptchaVerifier, MockCaptchaVerifier},
+    entities::{feature_flag::FeatureFlag, tenant::DEFAULT_TENANT_ID},
+    errors::AppError,
+    repositories::feature_flag::MockFeatureFlagRepository,
+    settings::CaptchaProvider,
+    use_cases::{
+        captcha::{CaptchaForm, CaptchaGuard},
+        feature_flags::FeatureFlags,
+    },
+};
+use uuid::Uuid;
+
+async fn flags(enabled: &[CaptchaForm]) -> FeatureFlags<MockFeatureFlagRepository> {
+    let stored: Vec<FeatureFlag> = enabled
+        .iter()
+        .map(|form| FeatureFlag {
+            tenant_id: DEFAULT_TENANT_ID,
+            key: form.flag_key().to_string(),
+            enabled: true,
+            description: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .collect();
+
+    let mut repo = MockFeatureFlagRepository::new();
+    repo.expect_list_all_feature_flags().returning(move || Ok(stored.clone()));
+
+    let flags = FeatureFlags::new(repo);
+    flags.refresh().await.expect("flags loaded");
+    flags
+}
+
+fn verifier(accepts: &'static str) -> Option<Arc<dyn CaptchaVerifier>> {
+    let mut verifier = MockCaptchaVerifier::new();
+    verifier.expect_verify().returning(move |token, _| Ok(token == accepts));
+    Some(Arc::new(verifier))
+}
+
+#[tokio::test]
+async fn only_flagged_forms_need_a_captcha_and_only_with_a_provider() {
+    let site = DEFAULT_TENANT_ID;
+    let guard = CaptchaGuard::new(verifier("ok"), flags(&[CaptchaForm::Contact]).await);
+    assert!(guard.is_required(&site, CaptchaForm::Contact));
+    // Flags are per tenant
+    assert!(!guard.is_required(&Uuid::new_v4(), CaptchaForm::Contact));
+    assert!(!guard.is_required(&site, CaptchaForm::Register));
+    assert!(guard.check(&site, CaptchaForm::Register, None, "203.0.113.7").await.is_ok());
+
+    let unconfigured = CaptchaGuard::new(None, flags(&[CaptchaForm::Contact]).await);
+    assert!(!unconfigured.is_required(&site, CaptchaForm::Contact));
+    assert!(unconfigured.check(&site, CaptchaForm::Contact, None, "203.0.113.7").await.is_ok());
+}
+
+#[tokio::test]
+async fn missing_and_rejected_tokens_are_refused() {
+    let site = DEFAULT_TENANT_ID;
+    let guard = CaptchaGuard::new(verifier("ok"), flags(&[CaptchaForm::Register]).await);
+
+    for token in [None, Some(""), Some("  "), Some("forged")] {
+        assert!(matches!(
+            guard.check(&site, CaptchaForm::Register, token, "203.0.113.7").await,
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+    assert!(guard.check(&site, CaptchaForm::Register, Some("ok"), "203.0.113.7").await.is_ok());
+}
+
+#[tokio::test]
+async fn an_unreachable_provider_fails_closed() {
+    let mut verifier = MockCaptchaVerifier::new();
+    verifier.expect_verify()
+        .returning(|_, _| Err(AppError::ServiceUnavailable("Turnstile unreachable".to_string())));
+    let site = DEFAULT_TENANT_ID;
+    let guard = CaptchaGuard::new(Some(Arc::new(verifier)), flags(&[CaptchaForm::Guestbook]).await);
+
+    assert!(matches!(
+        guard.check(&site, CaptchaForm::Guestbook, Some("token"), "203.0.113.7").await,
+        Err(AppError::ServiceUnavailable(_))
+    ));
+}
+
+#[test]
+fn providers_parse_from_config_values() {
+    assert_eq!("hCaptcha".parse::<CaptchaProvider>().unwrap(), CaptchaProvider::HCaptcha);
+    assert_eq!(" turnstile ".parse::<CaptchaProvider>().unwrap(), CaptchaProvider::Turnstile);
+    assert_eq!("off".parse::<CaptchaProvider>().unwrap(), CaptchaProvider::Off);
+    assert!("recaptcha".parse::<CaptchaProvider>().is_err());
+}
@@ -0,0 +1,115 @@
+//! Fixtures shared by the integration tests. Each test binary uses only
+//! some of them.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use portfolio_backend::{
+    entities::{blog_post::BlogPost, tenant::Tenant},
+    repositories::{
+        about::MockAboutRepository, api_token::MockApiTokenRepository, app_settings::MockAppSettingsRepository,
+        audit_log::MockAuditLogRepository, backfill::MockBackfillRepository, blog_post::MockBlogPostRepository,
+        bookmark::MockBookmarkRepository, changelog::MockChangelogRepository,
+        config_history::MockConfigHistoryRepository, contact_me::MockContactMeRepository,
+        feature_flag::MockFeatureFlagRepository, hire::MockHireInquiryRepository,
+        link_check::MockLinkCheckRepository, notification_preferences::MockNotificationPreferencesRepository,
+        one_time_token::MockOneTimeTokenRepository, outbound::MockOutboundClickRepository,
+        outbox::MockOutboxRepository, reading_progress::MockReadingProgressRepository,
+        redirect_rule::MockRedirectRuleRepository, retention::MockRetentionRepository,
+        schema::MockSchemaRepository, security_event::MockSecurityEventRepository,
+        share_count::MockShareCountRepository, stats_rollup::MockStatsRollupRepository,
+        tenant::MockTenantRepository, title_test::MockTitleTestRepository, usage::MockUsageRepository,
+        user::MockUserRepository, uses::MockUsesRepository,
+    },
+    settings::AppConfig,
+    shared_repos::SharedRepositories,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+pub const JWT_SECRET: &str = "integration-test-secret-that-is-long-enough-00";
+pub const REFRESH_TOKEN_SECRET: &str = "integration-test-refresh-secret-long-enough-0";
+
+/// A valid config with test secrets; `overrides` replaces or adds settings,
+/// e.g. `test_config(json!({ "honeytoken_ban_secs": 60 }))`
+pub fn test_config(overrides: serde_json::Value) -> AppConfig {
+    let mut base = json!({
+        "jwt_secret": JWT_SECRET,
+        "refresh_token_secret": REFRESH_TOKEN_SECRET,
+    });
+    base.as_object_mut().unwrap().extend(overrides.as_object().unwrap().clone());
+    serde_json::from_value(base).expect("test config")
+}
+
+/// Ada's site at `ada.dev`, with a fresh id
+pub fn tenant() -> Tenant {
+    Tenant {
+        id: Uuid::new_v4(),
+        slug: "ada".to_string(),
+        name: "Ada Lovelace".to_string(),
+        hostnames: vec!["ada.dev".to_string()],
+        created_at: Utc::now(),
+    }
+}
+
+/// A post titled after its slug, published now or a draft
+pub fn post(tenant_id: Uuid, slug: &str, published: bool) -> BlogPost {
+    BlogPost {
+        id: Uuid::new_v4(),
+        tenant_id,
+        author_id: None,
+        title: slug.to_string(),
+        slug: slug.to_string(),
+        excerpt: String::new(),
+        content_markdown: "Body".to_string(),
+        cover_image_url: None,
+        tags: None,
+        seo_title: None,
+        seo_description: None,
+        published,
+        published_at: published.then(Utc::now),
+        updated_at: Utc::now(),
+        created_at: Utc::now(),
+        deleted_at: None,
+        featured: false,
+        featured_order: None,
+    }
+}
+
+/// Mocks without expectations for every repository, so any call a test did
+/// not set up fails it. Override the ones a test needs:
+/// `SharedRepositories { user_repo: Arc::new(users), ..mock_repositories() }`
+pub fn mock_repositories() -> SharedRepositories {
+    SharedRepositories {
+        user_repo: Arc::new(MockUserRepository::new()),
+        about_repo: Arc::new(MockAboutRepository::new()),
+        blog_post_repo: Arc::new(MockBlogPostRepository::new()),
+        contact_repo: Arc::new(MockContactMeRepository::new()),
+        feature_flag_repo: Arc::new(MockFeatureFlagRepository::new()),
+        settings_repo: Arc::new(MockAppSettingsRepository::new()),
+        tenant_repo: Arc::new(MockTenantRepository::new()),
+        hire_repo: Arc::new(MockHireInquiryRepository::new()),
+        uses_repo: Arc::new(MockUsesRepository::new()),
+        changelog_repo: Arc::new(MockChangelogRepository::new()),
+        outbound_repo: Arc::new(MockOutboundClickRepository::new()),
+        security_event_repo: Arc::new(MockSecurityEventRepository::new()),
+        outbox_repo: Arc::new(MockOutboxRepository::new()),
+        link_check_repo: Arc::new(MockLinkCheckRepository::new()),
+        audit_repo: Arc::new(MockAuditLogRepository::new()),
+        retention_repo: Arc::new(MockRetentionRepository::new()),
+        notification_preferences_repo: Arc::new(MockNotificationPreferencesRepository::new()),
+        reading_progress_repo: Arc::new(MockReadingProgressRepository::new()),
+        title_test_repo: Arc::new(MockTitleTestRepository::new()),
+        api_token_repo: Arc::new(MockApiTokenRepository::new()),
+        stats_rollup_repo: Arc::new(MockStatsRollupRepository::new()),
+        one_time_token_repo: Arc::new(MockOneTimeTokenRepository::new()),
+        bookmark_repo: Arc::new(MockBookmarkRepository::new()),
+        redirect_rule_repo: Arc::new(MockRedirectRuleRepository::new()),
+        schema_repo: Arc::new(MockSchemaRepository::new()),
+        share_count_repo: Arc::new(MockShareCountRepository::new()),
+        config_history_repo: Arc::new(MockConfigHistoryRepository::new()),
+        backfill_repo: Arc::new(MockBackfillRepository::new()),
+        usage_repo: Arc::new(MockUsageRepository::new()),
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use portfolio_backend::{
+    entities::{
+        app_setting::{CONTACT_NOTIFICATION_POLICY, EMAIL_LOCALE},
+        config_change::{ConfigChange, ConfigHistoryQuery, CONFIG_KIND_FEATURE_FLAG, CONFIG_KIND_SETTING},
+        feature_flag::{FeatureFlag, UpdateFeatureFlagRequest},
+    },
+    errors::AppError,
+    repositories::{
+        app_settings::MockAppSettingsRepository, config_history::MockConfigHistoryRepository,
+        feature_flag::MockFeatureFlagRepository,
+    },
+    use_cases::{config_history::ConfigHistory, feature_flags::FeatureFlags, settings::RuntimeSettings},
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+type Settings = RuntimeSettings<Arc<MockAppSettingsRepository>>;
+type Flags = FeatureFlags<Arc<MockFeatureFlagRepository>>;
+type History = ConfigHistory<MockConfigHistoryRepository, Arc<MockAppSettingsRepository>, Arc<MockFeatureFlagRepository>>;
+
+/// Recorded changes, as the table holds them; the current value of a key
+/// is the `new_value` of its last change
+type Changes = Arc<Mutex<Vec<ConfigChange>>>;
+
+fn record(changes: &Changes, tenant_id: Uuid, kind: &str, key: &str, value: Option<Value>, rollback_to: Option<i64>) -> ConfigChange {
+    let mut changes = changes.lock().unwrap();
+    let previous_value = changes.iter().rev()
+        .find(|c| c.tenant_id == tenant_id && c.kind == kind && c.key == key)
+        .and_then(|c| c.new_value.clone());
+    let change = ConfigChange {
+        version: changes.len() as i64 + 1,
+        tenant_id,
+        kind: kind.to_string(),
+        key: key.to_string(),
+        previous_value,
+        new_value: value,
+        actor_id: None,
+        rollback_to,
+        changed_at: Utc::now(),
+    };
+    changes.push(change.clone());
+    change
+}
+
+/// The service caches are returned for checking what readers see
+fn history(changes: &Changes) -> (History, Settings, Flags) {
+    let mut repo = MockConfigHistoryRepository::new();
+
+    let stored = changes.clone();
+    repo.expect_change_setting().returning(move |tenant_id, key, value, source| {
+        Ok(record(&stored, *tenant_id, CONFIG_KIND_SETTING, key, value, source.rollback_to))
+    });
+    let stored = changes.clone();
+    repo.expect_change_feature_flag().returning(move |tenant_id, key, enabled, _, source| {
+        let change = record(&stored, *tenant_id, CONFIG_KIND_FEATURE_FLAG, key, Some(json!(enabled)), source.rollback_to);
+        let flag = FeatureFlag {
+            tenant_id: *tenant_id,
+            key: key.to_string(),
+            enabled,
+            description: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        Ok((flag, change))
+    });
+    let stored = changes.clone();
+    repo.expect_list_changes().returning(move |tenant_id, key, before, limit| {
+        Ok(stored.lock().unwrap().iter().rev()
+            .filter(|c| c.tenant_id == *tenant_id)
+            .filter(|c| key.as_ref().is_none_or(|key| c.key == *key) && before.is_none_or(|before| c.version < before))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    });
+    let stored = changes.clone();
+    repo.expect_get_change().returning(move |tenant_id, version| {
+        stored.lock().unwrap().iter().find(|c| c.tenant_id == *tenant_id && c.version == version).cloned()
+            .ok_or_else(|| AppError::NotFound("Config version not found".to_string()))
+    });
+    let stored = changes.clone();
+    repo.expect_changes_after().returning(move |tenant_id, version| {
+        Ok(stored.lock().unwrap().iter().filter(|c| c.tenant_id == *tenant_id && c.version > version).cloned().collect())
+    });
+
+    let settings = RuntimeSettings::new(Arc::new(MockAppSettingsRepository::new()));
+    let flags = FeatureFlags::new(Arc::new(MockFeatureFlagRepository::new()));
+    (ConfigHistory::new(repo, settings.clone(), flags.clone()), settings, flags)
+}
+
+fn toggle(enabled: bool) -> UpdateFeatureFlagRequest {
+    UpdateFeatureFlagRequest { enabled, description: None }
+}
+
+#[tokio::test]
+async fn rollback_restores_settings_and_flags_as_they_were_at_a_version() {
+    let changes = Changes::default();
+    let (history, settings, flags) = history(&changes);
+    let (tenant, admin) = (Uuid::new_v4(), Uuid::new_v4());
+
+    history.update_setting(tenant, admin, CONTACT_NOTIFICATION_POLICY, json!("hourly")).await.unwrap();
+    history.set_flag(tenant, admin, "comments", toggle(false)).await.unwrap();
+    let snapshot = changes.lock().unwrap().len() as i64;
+
+    history.update_setting(tenant, admin, CONTACT_NOTIFICATION_POLICY, json!("daily")).await.unwrap();
+    history.update_setting(tenant, admin, CONTACT_NOTIFICATION_POLICY, json!("immediate")).await.unwrap();
+    history.update_setting(tenant, admin, EMAIL_LOCALE, json!("fr")).await.unwrap();
+    history.set_flag(tenant, admin, "comments", toggle(true)).await.unwrap();
+    history.set_flag(tenant, admin, "webmentions", toggle(true)).await.unwrap();
+    assert!(flags.is_enabled(&tenant, "comments"));
+
+    // Another site's changes since are its own
+    let (other, other_admin) = (Uuid::new_v4(), Uuid::new_v4());
+    history.set_flag(other, other_admin, "comments", toggle(true)).await.unwrap();
+    history.update_setting(other, other_admin, EMAIL_LOCALE, json!("en")).await.unwrap();
+
+    let rollback = history.rollback(tenant, admin, snapshot).await.unwrap();
+    assert_eq!(rollback.changes.len(), 4, "one change per key touched since, not per change");
+    assert!(rollback.changes.iter().all(|c| c.rollback_to == Some(snapshot)));
+
+    assert_eq!(settings.get::<String>(&tenant, CONTACT_NOTIFICATION_POLICY).as_deref(), Some("hourly"));
+    // Set after the snapshot, so removed again
+    assert_eq!(settings.get::<String>(&tenant, EMAIL_LOCALE), None);
+    assert!(!flags.is_enabled(&tenant, "comments"));
+    assert!(!flags.is_enabled(&tenant, "webmentions"));
+    assert!(flags.is_enabled(&other, "comments"));
+    assert_eq!(settings.get::<String>(&other, EMAIL_LOCALE).as_deref(), Some("en"));
+
+    // Nothing changed since, so a second rollback has nothing to do
+    assert!(history.rollback(tenant, admin, snapshot).await.unwrap().changes.is_empty());
+    // ...and the rollback itself can be undone
+    let undo = history.rollback(tenant, admin, snapshot + 5).await.unwrap();
+    assert_eq!(undo.changes.len(), 4);
+    assert_eq!(settings.get::<String>(&tenant, EMAIL_LOCALE).as_deref(), Some("fr"));
+}
+
+#[tokio::test]
+async fn history_pages_back_from_the_newest_change() {
+    let changes = Changes::default();
+    let (history, _, _) = history(&changes);
+    let (tenant, admin) = (Uuid::new_v4(), Uuid::new_v4());
+
+    for policy in ["hourly", "daily", "immediate"] {
+        history.update_setting(tenant, admin, CONTACT_NOTIFICATION_POLICY, json!(policy)).await.unwrap();
+    }
+
+    let first = history.history(tenant, ConfigHistoryQuery { limit: Some(2), ..Default::default() }).await.unwrap();
+    assert_eq!(first.changes.iter().map(|c| c.version).collect::<Vec<_>>(), vec![3, 2]);
+    assert_eq!(first.changes[0].previous_value, Some(json!("daily")));
+    assert_eq!(first.next_before, Some(2));
+
+    let query = ConfigHistoryQuery { limit: Some(2), before: first.next_before, key: None };
+    let last = history.history(tenant, query).await.unwrap();
+    assert_eq!(last.changes.len(), 1);
+    assert_eq!(last.changes[0].previous_value, None);
+    assert_eq!(last.next_before, None);
+}
+
+#[tokio::test]
+async fn rejected_changes_and_unknown_versions_leave_no_trace() {
+    let changes = Changes::default();
+    let (history, _, _) = history(&changes);
+    let (tenant, admin) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let bad = history.update_setting(tenant, admin, CONTACT_NOTIFICATION_POLICY, json!("weekly")).await;
+    assert!(matches!(bad, Err(AppError::InvalidInput(_))));
+    let bad = history.set_flag(tenant, admin, "Not A Key", toggle(true)).await;
+    assert!(matches!(bad, Err(AppError::InvalidInput(_))));
+    assert!(matches!(history.rollback(tenant, admin, 42).await, Err(AppError::NotFound(_))));
+
+    assert!(changes.lock().unwrap().is_empty());
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{tenant, test_config};
+use portfolio_backend::{
+    crypto::field_cipher::FieldCipher,
+    entities::{
+        app_setting::{AppSetting, CONTACT_AUTO_REPLY},
+        contact_me::{ContactAutoReply, ContactMeMessage},
+        notification::Notification,
+        tenant::{Tenant, DEFAULT_TENANT_ID},
+    },
+    mailer::email::{EmailMessage, MockMailer},
+    repositories::{app_settings::MockAppSettingsRepository, contact_me::MockContactMeRepository},
+    use_cases::{
+        dispatcher::NotificationSink,
+        notifications::{is_automated_address, ContactNotifier},
+        settings::RuntimeSettings,
+    },
+};
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+struct NoAlerts;
+
+#[async_trait]
+impl NotificationSink for NoAlerts {
+    async fn notify(&self, _tenant_id: Uuid, _notification: Notification) {}
+}
+
+type Outbox = Arc<Mutex<Vec<EmailMessage>>>;
+
+/// The default tenant, so the configured `notification_email` is its own address
+/// Ada's site as the default tenant, whose owner is `notification_email`
+fn default_site() -> Tenant {
+    Tenant { id: DEFAULT_TENANT_ID, ..tenant() }
+}
+
+fn message(tenant_id: Uuid, email: &str) -> ContactMeMessage {
+    ContactMeMessage {
+        id: Uuid::new_v4(),
+        tenant_id,
+        name: "Grace".to_string(),
+        email: email.to_string(),
+        subject: Some("Project inquiry".to_string()),
+        message: "Are you available next month?".to_string(),
+        created_at: Utc::now(),
+        deleted_at: None,
+        is_spam: false,
+        country_code: None,
+        region: None,
+        anonymized_at: None,
+    }
+}
+
+async fn contact_notifier(tenant_id: Uuid, auto_reply: serde_json::Value, sent: &Outbox) -> ContactNotifier<MockContactMeRepository, MockAppSettingsRepository> {
+    let mut settings_repo = MockAppSettingsRepository::new();
+    settings_repo.expect_list_all_settings().returning(move || {
+        Ok(vec![AppSetting {
+            tenant_id,
+            key: CONTACT_AUTO_REPLY.to_string(),
+            value: auto_reply.clone(),
+            updated_at: Utc::now(),
+        }])
+    });
+    let settings = RuntimeSettings::new(settings_repo);
+    settings.refresh().await.expect("settings loaded");
+
+    // Same semantics as the conditional upsert: one slot per address per window
+    let replies = Arc::new(Mutex::new(Vec::<(String, DateTime<Utc>)>::new()));
+    let mut contact_repo = MockContactMeRepository::new();
+    contact_repo.expect_claim_auto_reply().returning(move |_, key, now, not_before| {
+        let mut replies = replies.lock().unwrap();
+        match replies.iter_mut().find(|(k, _)| k == key) {
+            Some((_, last)) if *last > not_before => Ok(false),
+            Some((_, last)) => {
+                *last = now;
+                Ok(true)
+            }
+            None => {
+                replies.push((key.to_string(), now));
+                Ok(true)
+            }
+        }
+    });
+
+    let outbox = sent.clone();
+    let mut mailer = MockMailer::new();
+    mailer.expect_send().returning(move |email| {
+        outbox.lock().unwrap().push(email.clone());
+        Ok(())
+    });
+
+    let config = test_config(json!({ "mail_from": "hello@ada.dev", "notification_email": "owner@ada.dev" }));
+
+    ContactNotifier::new(contact_repo, settings, Arc::new(mailer), Arc::new(NoAlerts), FieldCipher::disabled(), &config)
+}
+
+#[tokio::test]
+async fn senders_get_one_acknowledgment_per_throttle_window() {
+    let site = default_site();
+    let sent = Outbox::default();
+    let notifier = contact_notifier(site.id, json!({ "enabled": true, "response_time": "within two business days" }), &sent).await;
+
+    assert!(notifier.send_auto_reply(&site, &message(site.id, "grace@example.com")).await.unwrap());
+    assert!(!notifier.send_auto_reply(&site, &message(site.id, " Grace@Example.com")).await.unwrap());
+    assert!(notifier.send_auto_reply(&site, &message(site.id, "ada@example.org")).await.unwrap());
+
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    let reply = &sent[0];
+    assert_eq!((reply.from.as_str(), reply.to.as_str()), ("hello@ada.dev", "grace@example.com"));
+    assert_eq!(reply.subject, "Thanks for getting in touch");
+    assert!(reply.text.contains("Hi Grace,"));
+    assert!(reply.text.contains("I usually reply within two business days."));
+    assert!(reply.text.contains("Your message: Project inquiry"));
+    assert!(reply.html.as_deref().unwrap().contains("within two business days"));
+    assert_eq!(reply.headers.get("Auto-Submitted").map(String::as_str), Some("auto-replied"));
+}
+
+#[tokio::test]
+async fn custom_text_fills_in_the_name_and_response_time() {
+    let site = default_site();
+    let sent = Outbox::default();
+    let auto_reply = json!({
+        "enabled": true,
+        "response_time": "by Friday",
+        "subject": "Got it!",
+        "message": "Thanks {name}, I'll answer {response_time}.",
+    });
+    let notifier = contact_notifier(site.id, auto_reply, &sent).await;
+
+    assert!(notifier.send_auto_reply(&site, &message(site.id, "grace@example.com")).await.unwrap());
+
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent[0].subject, "Got it!");
+    assert!(sent[0].text.contains("Thanks Grace, I'll answer by Friday."));
+    assert!(!sent[0].text.contains("I usually reply"));
+}
+
+#[tokio::test]
+async fn spam_loops_and_disabled_replies_send_nothing() {
+    let site = default_site();
+    let sent = Outbox::default();
+    let notifier = contact_notifier(site.id, json!({ "enabled": true, "response_time": "soon" }), &sent).await;
+
+    let mut spam = message(site.id, "grace@example.com");
+    spam.is_spam = true;
+    assert!(!notifier.send_auto_reply(&site, &spam).await.unwrap());
+    for own_or_automated in ["hello@ada.dev", "Owner@ada.dev", "no-reply@example.com", "bounce+123@mail.example.com"] {
+        assert!(!notifier.send_auto_reply(&site, &message(site.id, own_or_automated)).await.unwrap());
+    }
+
+    let disabled = contact_notifier(site.id, json!({ "enabled": false, "response_time": "soon" }), &sent).await;
+    assert!(!disabled.send_auto_reply(&site, &message(site.id, "grace@example.com")).await.unwrap());
+
+    assert!(sent.lock().unwrap().is_empty());
+}
+
+#[test]
+fn settings_are_validated_and_default_to_a_daily_throttle() {
+    let auto_reply: ContactAutoReply = serde_json::from_value(json!({ "enabled": true, "response_time": "soon" })).unwrap();
+    assert_eq!(auto_reply.throttle_hours, 24);
+    assert!(auto_reply.validate().is_ok());
+
+    let never: ContactAutoReply = serde_json::from_value(json!({ "enabled": true, "response_time": "", "throttle_hours": 0 })).unwrap();
+    assert!(never.validate().is_err());
+
+    assert!(is_automated_address("MAILER-DAEMON@example.com"));
+    assert!(!is_automated_address("noreen@example.com"));
+}
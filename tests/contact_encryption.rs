@@ -0,0 +1,222 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use portfolio_backend::{
+    crypto::{
+        field_cipher::FieldCipher,
+        keys::{DataKey, StaticKeyProvider},
+    },
+    entities::contact_me::{ContactMeFormInsert, ContactMeMessage, ContactSecret},
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    repositories::contact_me::MockContactMeRepository,
+    settings::AppConfig,
+    use_cases::{
+        contact::{open_contact_message, seal_contact_message, ContactMeHandler, UNREADABLE_FIELD},
+        ingest::ContactIngestQueue,
+    },
+};
+use serde_json::json;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+type Store = Arc<Mutex<Vec<ContactSecret>>>;
+
+fn key(id: &str, byte: u8) -> DataKey {
+    DataKey { id: id.to_string(), material: Zeroizing::new([byte; 32]) }
+}
+
+/// A cipher holding the keys `(id, byte)`, sealing with `primary`
+fn cipher(keys: &[(&str, u8)], primary: &str) -> FieldCipher {
+    let keys = keys.iter().map(|(id, byte)| key(id, *byte)).collect();
+    FieldCipher::new(Arc::new(StaticKeyProvider::new(keys, Some(primary))))
+}
+
+fn form(id: Uuid) -> ContactMeFormInsert {
+    ContactMeFormInsert {
+        id,
+        name: "Ada".to_string(),
+        email: "ada@example.com".to_string(),
+        subject: Some("Hello".to_string()),
+        message: "Are you available for a project?".to_string(),
+        created_at: Utc::now(),
+        country_code: None,
+        region: None,
+    }
+}
+
+fn stored_message(tenant_id: Uuid, insert: &ContactMeFormInsert) -> ContactMeMessage {
+    ContactMeMessage {
+        id: insert.id,
+        tenant_id,
+        name: insert.name.clone(),
+        email: insert.email.clone(),
+        subject: insert.subject.clone(),
+        message: insert.message.clone(),
+        created_at: insert.created_at,
+        deleted_at: None,
+        is_spam: false,
+        country_code: None,
+        region: None,
+        anonymized_at: None,
+    }
+}
+
+/// Mock repository paging and compare-and-set rewriting a shared list of
+/// secrets, ordered by id
+fn stored_repo(store: &Store) -> MockContactMeRepository {
+    let mut repo = MockContactMeRepository::new();
+
+    let secrets = store.clone();
+    repo.expect_list_contact_secrets().returning(move |after, limit| {
+        let mut rows: Vec<_> = secrets.lock().unwrap().iter()
+            .filter(|s| after.is_none_or(|after| s.message_id > after))
+            .cloned()
+            .collect();
+        rows.sort_by_key(|s| s.message_id);
+        rows.truncate(limit as usize);
+        Ok(rows)
+    });
+
+    let secrets = store.clone();
+    repo.expect_rewrite_contact_secrets().returning(move |rewrites| {
+        let mut secrets = secrets.lock().unwrap();
+        let mut rewritten = 0;
+        for rewrite in rewrites {
+            if let Some(secret) = secrets.iter_mut().find(|s| **s == rewrite.before) {
+                *secret = rewrite.after.clone();
+                rewritten += 1;
+            }
+        }
+        Ok(rewritten)
+    });
+
+    repo
+}
+
+fn handler(repo: MockContactMeRepository, cipher: FieldCipher) -> ContactMeHandler<MockContactMeRepository> {
+    ContactMeHandler::new(repo, ContactIngestQueue::new(8), GeoLocator::default(), cipher)
+}
+
+#[test]
+fn sealed_fields_open_only_for_their_own_message_and_column() {
+    let cipher = cipher(&[("k1", 1)], "k1");
+    let sealed = cipher.seal("ada@example.com", "contact:1:email").unwrap();
+
+    assert!(sealed.starts_with("enc:v1:k1:"));
+    assert!(!sealed.contains("ada@example.com"));
+    assert_ne!(sealed, cipher.seal("ada@example.com", "contact:1:email").unwrap());
+    assert_eq!(cipher.open(&sealed, "contact:1:email").unwrap(), "ada@example.com");
+    assert!(cipher.is_current(&sealed));
+
+    assert!(matches!(cipher.open(&sealed, "contact:2:email"), Err(AppError::InternalError(_))));
+    assert!(matches!(cipher.open(&sealed, "contact:1:message"), Err(AppError::InternalError(_))));
+
+    // Rows written before encryption was enabled still read
+    assert_eq!(cipher.open("plain@example.com", "contact:1:email").unwrap(), "plain@example.com");
+    assert!(!cipher.is_current("plain@example.com"));
+
+    let disabled = FieldCipher::disabled();
+    assert!(!disabled.is_enabled());
+    assert_eq!(disabled.seal("ada@example.com", "contact:1:email").unwrap(), "ada@example.com");
+    assert!(disabled.open(&sealed, "contact:1:email").is_err());
+}
+
+#[test]
+fn messages_sealed_before_a_rotation_still_open() {
+    let id = Uuid::new_v4();
+    let tenant_id = Uuid::new_v4();
+    let before = cipher(&[("2025", 1)], "2025");
+    let after = cipher(&[("2025", 1), ("2026", 2)], "2026");
+
+    let mut insert = form(id);
+    seal_contact_message(&before, &mut insert).unwrap();
+    assert!(insert.email.starts_with("enc:v1:2025:"));
+    assert_eq!(insert.name, "Ada");
+    assert_eq!(insert.subject.as_deref(), Some("Hello"));
+
+    let opened = open_contact_message(&after, stored_message(tenant_id, &insert));
+    assert_eq!(opened.email, "ada@example.com");
+    assert_eq!(opened.message, "Are you available for a project?");
+    assert!(!after.is_current(&insert.email));
+
+    // Once the old key is dropped the row reads as unreadable, not as an error
+    let dropped = cipher(&[("2026", 2)], "2026");
+    let opened = open_contact_message(&dropped, stored_message(tenant_id, &insert));
+    assert_eq!((opened.email.as_str(), opened.message.as_str()), (UNREADABLE_FIELD, UNREADABLE_FIELD));
+    assert_eq!(opened.name, "Ada");
+}
+
+#[test]
+fn keys_are_parsed_from_config_and_the_primary_must_be_listed() {
+    let key_a = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+    let key_b = "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=";
+    let config = |keys: &str, id: Option<&str>| -> AppConfig {
+        serde_json::from_value(json!({
+            "contact_encryption_keys": keys,
+            "contact_encryption_key_id": id,
+        }))
+        .unwrap()
+    };
+
+    let single = config(&format!("2025={key_a}"), None);
+    assert_eq!(single.contact_encryption_keys().unwrap()["2025"].as_slice(), &[1u8; 32]);
+    assert_eq!(single.contact_encryption_key_id().as_deref(), Some("2025"));
+
+    let rotated = config(&format!("2025={key_a}, 2026={key_b}"), Some("2026"));
+    assert_eq!(rotated.contact_encryption_keys().unwrap().len(), 2);
+    assert_eq!(rotated.contact_encryption_key_id().as_deref(), Some("2026"));
+
+    assert_eq!(config(&format!("2025={key_a},2026={key_b}"), None).contact_encryption_key_id(), None);
+    assert_eq!(config(&format!("2025={key_a}"), Some("2024")).contact_encryption_key_id(), None);
+
+    assert!(config("2025=AQID", None).contact_encryption_keys().is_err());
+    assert!(config(&format!("20:25={key_a}"), None).contact_encryption_keys().is_err());
+    assert!(config(key_a, None).contact_encryption_keys().is_err());
+}
+
+#[tokio::test]
+async fn rekey_reseals_old_and_plaintext_rows_and_reports_unreadable_ones() {
+    let old = cipher(&[("2025", 1)], "2025");
+    let lost = cipher(&[("lost", 9)], "lost");
+    let current = cipher(&[("2025", 1), ("2026", 2)], "2026");
+
+    let secret = |cipher: &FieldCipher, identity: bool| {
+        let id = Uuid::new_v4();
+        let email = cipher.seal("ada@example.com", &format!("contact:{id}:email")).unwrap();
+        ContactSecret {
+            message_id: id,
+            identity_email: identity.then(|| email.clone()),
+            message: cipher.seal("Hello there", &format!("contact:{id}:message")).unwrap(),
+            email,
+        }
+    };
+    let store = Store::new(Mutex::new(vec![
+        secret(&old, true),
+        secret(&old, false),
+        secret(&FieldCipher::disabled(), false),
+        secret(&current, false),
+        secret(&lost, false),
+    ]));
+    let lost_id = store.lock().unwrap()[4].message_id;
+
+    let report = handler(stored_repo(&store), current.clone()).rekey_contact_messages(2).await.unwrap();
+    assert_eq!((report.scanned, report.rewritten), (5, 3));
+    assert_eq!(report.failed, vec![lost_id]);
+
+    for secret in store.lock().unwrap().iter().filter(|s| s.message_id != lost_id) {
+        let context = |field| format!("contact:{}:{}", secret.message_id, field);
+        assert!(current.is_current(&secret.email) && current.is_current(&secret.message));
+        assert_eq!(current.open(&secret.email, &context("email")).unwrap(), "ada@example.com");
+        assert_eq!(current.open(&secret.message, &context("message")).unwrap(), "Hello there");
+        if let Some(identity) = &secret.identity_email {
+            assert_eq!(current.open(identity, &context("email")).unwrap(), "ada@example.com");
+        }
+    }
+
+    let again = handler(stored_repo(&store), current).rekey_contact_messages(2).await.unwrap();
+    assert_eq!((again.scanned, again.rewritten, again.failed.len()), (5, 0, 1));
+
+    let unkeyed = handler(MockContactMeRepository::new(), FieldCipher::disabled()).rekey_contact_messages(2).await;
+    assert!(matches!(unkeyed, Err(AppError::InvalidInput(_))));
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::test_config;
+use portfolio_backend::{
+    entities::security_event::{SecurityEventInsert, CONTENT_BLOCKED, CONTENT_FLAGGED},
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    repositories::security_event::MockSecurityEventRepository,
+    use_cases::content_safety::{ContentOrigin, ContentSafety},
+    utils::content_scan::{ContentScanner, ScanSeverity},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn origin() -> ContentOrigin {
+    ContentOrigin {
+        ip: "203.0.113.9".to_string(),
+        user_agent: Some("curl/8.0".to_string()),
+        method: "POST".to_string(),
+        path: "/blog/posts".to_string(),
+    }
+}
+
+/// Keeps every recorded event for inspection
+fn content_safety(events: &Arc<Mutex<Vec<SecurityEventInsert>>>, blocked_domains: &str) -> ContentSafety<MockSecurityEventRepository> {
+    let mut repo = MockSecurityEventRepository::new();
+    let recorded = events.clone();
+    repo.expect_record_event().returning(move |_, event| {
+        recorded.lock().unwrap().push(event.clone());
+        Ok(())
+    });
+    ContentSafety::new(repo, GeoLocator::default(), &test_config(json!({ "blocked_content_domains": blocked_domains })))
+}
+
+#[test]
+fn scanner_grades_findings_by_what_they_can_do() {
+    let scanner = ContentScanner::new(vec!["evil.example".to_string()]);
+    let rules = |text: &str| {
+        scanner.scan(text).into_iter().map(|f| (f.rule, f.severity)).collect::<Vec<_>>()
+    };
+
+    assert!(rules("Plain prose with a [link](https://example.com) and `data: 1`.").is_empty());
+    assert_eq!(rules("![dot](data:image/png;base64,iVBORw0KGgo=)"), vec![("data_uri", ScanSeverity::Low)]);
+    assert_eq!(rules("![x](data:image/svg+xml;base64,PHN2Zz4=)"), vec![("data_uri", ScanSeverity::High)]);
+    assert_eq!(rules("[click](javascript:alert(1))"), vec![("script_url", ScanSeverity::High)]);
+    assert_eq!(rules(r#"<img src=x onerror="alert(1)">"#), vec![("event_handler", ScanSeverity::Medium)]);
+    assert_eq!(rules("See http://cdn.EVIL.example/x.js"), vec![("blocked_domain", ScanSeverity::High)]);
+    assert!(rules("See https://notevil.example/").is_empty());
+
+    // One embed is a pasted video; a handful is an injection attempt
+    let one = r#"<iframe src="https://www.youtube.com/embed/x"></iframe>"#;
+    assert_eq!(rules(one), vec![("embed_tag", ScanSeverity::Medium)]);
+    let many = "<script></script>".repeat(4);
+    assert!(rules(&many).iter().all(|(rule, severity)| *rule == "embed_tag" && *severity == ScanSeverity::High));
+}
+
+#[tokio::test]
+async fn high_severity_findings_refuse_the_write_and_are_recorded() {
+    let events = Arc::default();
+    let safety = content_safety(&events, "*.Evil.Example., bad.test");
+
+    let fields = [("title", "Fine title"), ("content_markdown", "Read [this](https://evil.example/payload)")];
+    let refused = safety.check(Uuid::new_v4(), &origin(), &fields).await;
+    let Err(AppError::ValidationError(errors)) = refused else { panic!("blocked domain not refused: {:?}", refused) };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field, "content_markdown");
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, CONTENT_BLOCKED);
+    assert_eq!(
+        events[0].details,
+        Some(json!({ "findings": [{ "field": "content_markdown", "rule": "blocked_domain", "severity": "high" }] })),
+        "details name the rules but never quote the content"
+    );
+}
+
+#[tokio::test]
+async fn lesser_findings_come_back_as_warnings() {
+    let events = Arc::default();
+    let safety = content_safety(&events, "");
+
+    let fields = [("content_markdown", "![dot](data:image/png;base64,iVBORw0KGgo=)")];
+    let warnings = safety.check(Uuid::new_v4(), &origin(), &fields).await.unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].field, "content_markdown");
+    assert_eq!(warnings[0].finding.rule, "data_uri");
+    assert_eq!(events.lock().unwrap()[0].kind, CONTENT_FLAGGED);
+
+    // Clean content records nothing
+    let clean = safety.check(Uuid::new_v4(), &origin(), &[("content_markdown", "Hello")]).await.unwrap();
+    assert!(clean.is_empty());
+    assert_eq!(events.lock().unwrap().len(), 1);
+}
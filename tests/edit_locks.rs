@@ -0,0 +1,136 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{post, tenant, test_config};
+use portfolio_backend::{
+    cache::edit_locks::MockEditLockStore,
+    entities::{
+        blog_post::{BlogPost, PostEditor},
+        edit_lock::PostEditLock,
+    },
+    errors::AppError,
+    repositories::blog_post::MockBlogPostRepository,
+    use_cases::edit_locks::{EditLockOutcome, PostEditLocks},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+type Locks = PostEditLocks<MockBlogPostRepository>;
+
+/// Ada's draft, written by `author_id`
+fn draft(tenant_id: Uuid, author_id: Uuid) -> BlogPost {
+    BlogPost { author_id: Some(author_id), ..post(tenant_id, "draft", false) }
+}
+
+fn editor(user_id: Uuid) -> PostEditor {
+    PostEditor { user_id, admin: false }
+}
+
+/// Same semantics as the Redis scripts, for a single lock
+fn edit_locks(post: &BlogPost) -> Locks {
+    let mut repo = MockBlogPostRepository::new();
+    let current = post.clone();
+    repo.expect_get_blog_post_by_id().returning(move |_, _| Ok(current.clone()));
+
+    let held: Arc<Mutex<Option<PostEditLock>>> = Arc::default();
+    let mut store = MockEditLockStore::new();
+    let lock = held.clone();
+    store.expect_acquire().returning(move |_, wanted, _, force| {
+        let mut lock = lock.lock().unwrap();
+        match lock.as_mut() {
+            Some(current) if current.holder_id != wanted.holder_id && !force => {}
+            Some(current) if current.holder_id == wanted.holder_id => current.expires_at = wanted.expires_at,
+            _ => *lock = Some(wanted.clone()),
+        }
+        Ok(lock.clone().unwrap())
+    });
+    let lock = held.clone();
+    store.expect_current().returning(move |_| Ok(lock.lock().unwrap().clone()));
+    store.expect_release().returning(move |_, holder_id| {
+        let mut lock = held.lock().unwrap();
+        let holds = lock.as_ref().is_some_and(|l| l.holder_id == *holder_id);
+        if holds {
+            *lock = None;
+        }
+        Ok(holds)
+    });
+
+    PostEditLocks::new(repo, Some(Arc::new(store)), &test_config(json!({})))
+}
+
+#[tokio::test]
+async fn second_editor_sees_the_holder_until_the_lock_is_released() {
+    let site = tenant();
+    let (ada, grace) = (Uuid::new_v4(), Uuid::new_v4());
+    let post = draft(site.id, ada);
+    let admin_grace = PostEditor { user_id: grace, admin: true };
+    let admin_ada = PostEditor { user_id: ada, admin: true };
+    let locks = edit_locks(&post);
+    let id = post.id.to_string();
+
+    let first = locks.acquire(&site, admin_ada, "ada@ada.dev", &id, false).await.unwrap();
+    let EditLockOutcome::Acquired(first) = first else { panic!("lock not acquired") };
+    assert_eq!(first.holder_email, "ada@ada.dev");
+
+    let blocked = locks.acquire(&site, admin_grace, "grace@ada.dev", &id, false).await.unwrap();
+    assert!(matches!(&blocked, EditLockOutcome::HeldByOther(lock) if lock.holder_id == ada));
+    assert_eq!(locks.held_by_other(&site, admin_grace, &post.id).await.map(|l| l.holder_id), Some(ada));
+    assert!(locks.held_by_other(&site, admin_ada, &post.id).await.is_none());
+
+    // A heartbeat keeps the original acquisition time
+    let EditLockOutcome::Acquired(beat) = locks.acquire(&site, admin_ada, "ada@ada.dev", &id, false).await.unwrap() else {
+        panic!("heartbeat refused")
+    };
+    assert_eq!(beat.acquired_at, first.acquired_at);
+    assert!(beat.expires_at >= first.expires_at);
+
+    // Someone else's release leaves the lock alone
+    locks.release(&site, admin_grace, &id).await.unwrap();
+    assert!(locks.status(&site, &id).await.unwrap().lock.is_some());
+
+    locks.release(&site, admin_ada, &id).await.unwrap();
+    assert!(locks.status(&site, &id).await.unwrap().lock.is_none());
+    assert!(matches!(
+        locks.acquire(&site, admin_grace, "grace@ada.dev", &id, false).await.unwrap(),
+        EditLockOutcome::Acquired(_)
+    ));
+}
+
+#[tokio::test]
+async fn only_admins_can_take_over_a_lock() {
+    let site = tenant();
+    let (ada, grace) = (Uuid::new_v4(), Uuid::new_v4());
+    let post = draft(site.id, ada);
+    let locks = edit_locks(&post);
+    let id = post.id.to_string();
+
+    locks.acquire(&site, editor(ada), "ada@ada.dev", &id, false).await.unwrap();
+
+    let forced = locks.acquire(&site, editor(ada), "ada@ada.dev", &id, true).await;
+    assert!(matches!(forced, Err(AppError::ForbiddenAccess)));
+    // Editors cannot lock other authors' posts at all
+    let foreign = locks.acquire(&site, editor(grace), "grace@ada.dev", &id, false).await;
+    assert!(matches!(foreign, Err(AppError::ForbiddenAccess)));
+
+    let admin = PostEditor { user_id: grace, admin: true };
+    let taken = locks.acquire(&site, admin, "grace@ada.dev", &id, true).await.unwrap();
+    assert!(matches!(taken, EditLockOutcome::Acquired(lock) if lock.holder_id == grace));
+    assert_eq!(locks.held_by_other(&site, editor(ada), &post.id).await.map(|l| l.holder_id), Some(grace));
+
+    // The old holder's heartbeat does not win the lock back
+    let beat = locks.acquire(&site, editor(ada), "ada@ada.dev", &id, false).await.unwrap();
+    assert!(matches!(beat, EditLockOutcome::HeldByOther(_)));
+}
+
+#[tokio::test]
+async fn without_redis_locks_are_unavailable_but_never_block_saves() {
+    let site = tenant();
+    let ada = Uuid::new_v4();
+    let post = draft(site.id, ada);
+    let locks = PostEditLocks::new(MockBlogPostRepository::new(), None, &test_config(json!({})));
+
+    let result = locks.acquire(&site, editor(ada), "ada@ada.dev", &post.id.to_string(), false).await;
+    assert!(matches!(result, Err(AppError::ServiceUnavailable(_))));
+    assert!(locks.held_by_other(&site, editor(ada), &post.id).await.is_none());
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::test_config;
+use portfolio_backend::{
+    reporting::{
+        reporter::{ErrorEvent, ErrorSource, RequestContext},
+        sentry::{error_reporter_from_config, sentry_event, SentryDsn, SentryOptions},
+    },
+};
+use serde_json::json;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::broadcast,
+};
+
+fn request_event(message: &str) -> ErrorEvent {
+    ErrorEvent {
+        tenant: Some("default".to_string()),
+        request: Some(RequestContext {
+            method: "POST".to_string(),
+            route: "/api/v1/blog/posts/{id}".to_string(),
+            request_id: Some("req-1".to_string()),
+            user_id: Some("user-1".to_string()),
+        }),
+        ..ErrorEvent::new(ErrorSource::Request, "InternalError", message)
+    }
+}
+
+#[test]
+fn dsns_resolve_to_the_store_endpoint() {
+    let dsn: SentryDsn = "https://abc123@o1.ingest.sentry.io/42".parse().unwrap();
+    assert_eq!(dsn.public_key, "abc123");
+    assert_eq!(dsn.store_url.as_str(), "https://o1.ingest.sentry.io/api/42/store/");
+
+    let prefixed: SentryDsn = "http://key@sentry.internal:9000/sentry/7".parse().unwrap();
+    assert_eq!(prefixed.store_url.as_str(), "http://sentry.internal:9000/sentry/api/7/store/");
+
+    assert!("https://o1.ingest.sentry.io/42".parse::<SentryDsn>().is_err());
+    assert!("https://abc@o1.ingest.sentry.io/".parse::<SentryDsn>().is_err());
+    assert!("not a dsn".parse::<SentryDsn>().is_err());
+}
+
+#[test]
+fn events_carry_context_and_are_scrubbed() {
+    let options = SentryOptions::from_config(&test_config(json!({
+        "body_logging": { "redact_patterns": ["sk_live_[a-z0-9]+"] },
+    })));
+    let payload = sentry_event(&request_event("mail to ada@example.com failed with sk_live_abc123"), &options);
+
+    let message = payload["message"]["formatted"].as_str().unwrap();
+    assert!(!message.contains("ada@example.com"));
+    assert!(!message.contains("sk_live_abc123"));
+    assert_eq!(payload["exception"]["values"][0]["value"], message);
+    assert_eq!(payload["exception"]["values"][0]["type"], "InternalError");
+
+    assert_eq!(payload["level"], "error");
+    assert_eq!(payload["environment"], "development");
+    assert!(payload["release"].as_str().unwrap().starts_with("portfolio_backend@"));
+    assert_eq!(payload["transaction"], "POST /api/v1/blog/posts/{id}");
+    assert_eq!(payload["tags"]["tenant"], "default");
+    assert_eq!(payload["tags"]["request_id"], "req-1");
+    assert_eq!(payload["user"]["id"], "user-1");
+}
+
+#[test]
+fn panics_are_fatal_and_jobs_are_tagged() {
+    let options = SentryOptions::from_config(&test_config(json!({})));
+
+    let panic = sentry_event(&ErrorEvent::new(ErrorSource::Panic, "panic", "boom"), &options);
+    assert_eq!(panic["level"], "fatal");
+
+    let job = ErrorEvent { job: Some("retention".to_string()), ..ErrorEvent::new(ErrorSource::Job, "JobFailed", "db down") };
+    let payload = sentry_event(&job, &options);
+    assert_eq!(payload["tags"]["job"], "retention");
+    assert_eq!(payload["transaction"], "retention");
+    assert!(payload.get("request").is_none());
+}
+
+#[tokio::test]
+async fn reported_events_are_posted_with_the_dsn_key() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let config = test_config(json!({
+        "error_reporting_dsn": format!("http://testkey@127.0.0.1:{}/5", port),
+        // Requests are sampled out entirely; panics are always sent
+        "error_reporting_sample_rate": 0.0,
+    }));
+
+    let (reporter, transport) = error_reporter_from_config(&config).expect("reporter");
+    reporter.report(request_event("sampled out"));
+    reporter.report(ErrorEvent::new(ErrorSource::Panic, "panic", "worker died"));
+
+    let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let task = tokio::spawn(transport.run(shutdown_rx));
+
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut received = Vec::new();
+    let mut buf = [0u8; 4096];
+    while !String::from_utf8_lossy(&received).contains("worker died") {
+        let n = socket.read(&mut buf).await.unwrap();
+        assert!(n > 0, "connection closed before the event arrived");
+        received.extend_from_slice(&buf[..n]);
+    }
+    socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+
+    let request = String::from_utf8_lossy(&received).to_lowercase();
+    assert!(request.starts_with("post /api/5/store/ http/1.1"));
+    assert!(request.contains("sentry_key=testkey"));
+    assert!(!request.contains("sampled out"));
+
+    shutdown_tx.send(()).unwrap();
+    task.await.unwrap();
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, read_body_json, TestRequest},
+    web, App, HttpResponse,
+};
+use chrono::Utc;
+use common::{mock_repositories, test_config};
+use portfolio_backend::{
+    auth::jwt::JwtService,
+    entities::{config_change::ConfigChange, feature_flag::FeatureFlag, tenant::Tenant, user::User},
+    middlewares::{auth::AuthMiddleware, tenant::TenantMiddleware},
+    repositories::{
+        config_history::MockConfigHistoryRepository, feature_flag::MockFeatureFlagRepository,
+        tenant::MockTenantRepository,
+    },
+    routes::configure_routes,
+    shared_repos::SharedRepositories,
+    use_cases::feature_flags::RequireFeature,
+    AppState,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Whether each `(tenant_id, key)` flag is on, as the table holds them
+type Flags = Arc<Mutex<HashMap<(Uuid, String), bool>>>;
+
+fn tenant(slug: &str) -> Tenant {
+    Tenant {
+        slug: slug.to_string(),
+        name: slug.to_string(),
+        hostnames: vec![format!("{slug}.dev")],
+        ..common::tenant()
+    }
+}
+
+fn flag(tenant_id: Uuid, key: &str, enabled: bool) -> FeatureFlag {
+    FeatureFlag {
+        tenant_id,
+        key: key.to_string(),
+        enabled,
+        description: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn admin_token(tenant: &Tenant) -> String {
+    let now = Utc::now();
+    let admin = User {
+        id: Uuid::new_v4(),
+        tenant_id: tenant.id,
+        email: format!("admin@{}.dev", tenant.slug),
+        username: None,
+        password_hash: String::new(),
+        is_admin: true,
+        is_verified: true,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        deleted_by: None,
+        is_editor: false,
+        display_name: None,
+        avatar_url: None,
+        is_system: false,
+    };
+    JwtService::new(&test_config(json!({}))).create_jwt(&admin, 0).unwrap()
+}
+
+/// Flag reads and writes go to `flags`, the way the Postgres repositories scope them
+async fn state(tenants: Vec<Tenant>, flags: &Flags) -> web::Data<AppState> {
+    let mut tenant_repo = MockTenantRepository::new();
+    tenant_repo.expect_list_tenants().returning(move || Ok(tenants.clone()));
+
+    let mut flag_repo = MockFeatureFlagRepository::new();
+    let stored = flags.clone();
+    flag_repo.expect_list_feature_flags().returning(move |tenant_id| {
+        let mut listed: Vec<FeatureFlag> = stored.lock().unwrap().iter()
+            .filter(|((id, _), _)| id == tenant_id)
+            .map(|((id, key), enabled)| flag(*id, key, *enabled))
+            .collect();
+        listed.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(listed)
+    });
+
+    let mut history_repo = MockConfigHistoryRepository::new();
+    let stored = flags.clone();
+    history_repo.expect_change_feature_flag().returning(move |tenant_id, key, enabled, _, source| {
+        let previous = stored.lock().unwrap().insert((*tenant_id, key.to_string()), enabled);
+        let change = ConfigChange {
+            version: 1,
+            tenant_id: *tenant_id,
+            kind: "feature_flag".to_string(),
+            key: key.to_string(),
+            previous_value: previous.map(Value::Bool),
+            new_value: Some(Value::Bool(enabled)),
+            actor_id: Some(source.actor_id),
+            rollback_to: None,
+            changed_at: Utc::now(),
+        };
+        Ok((flag(*tenant_id, key, enabled), change))
+    });
+
+    let repos = SharedRepositories {
+        tenant_repo: Arc::new(tenant_repo),
+        feature_flag_repo: Arc::new(flag_repo),
+        config_history_repo: Arc::new(history_repo),
+        ..mock_repositories()
+    };
+    let state = AppState::from_repositories(&test_config(json!({})), repos);
+    state.tenants.refresh().await.unwrap();
+    web::Data::new(state)
+}
+
+#[actix_web::test]
+async fn an_admin_only_reads_and_changes_their_own_sites_flags() {
+    let (ada, grace) = (tenant("ada"), tenant("grace"));
+    let flags = Flags::default();
+    flags.lock().unwrap().extend([
+        ((ada.id, "comments".to_string()), false),
+        ((grace.id, "comments".to_string()), false),
+        ((grace.id, "guestbook".to_string()), true),
+    ]);
+    let state = state(vec![ada.clone(), grace.clone()], &flags).await;
+    let app = init_service(
+        App::new()
+            .app_data(state.clone())
+            .configure(configure_routes)
+            .wrap(AuthMiddleware)
+            .wrap(TenantMiddleware),
+    )
+    .await;
+    let token = format!("Bearer {}", admin_token(&ada));
+
+    let req = TestRequest::put()
+        .uri("/api/v1/admin/feature-flags/comments")
+        .insert_header(("Host", "ada.dev"))
+        .insert_header(("Authorization", token.as_str()))
+        .set_json(json!({ "enabled": true }))
+        .to_request();
+    assert_eq!(call_service(&app, req).await.status(), StatusCode::OK);
+    assert!(flags.lock().unwrap()[&(ada.id, "comments".to_string())]);
+    assert!(!flags.lock().unwrap()[&(grace.id, "comments".to_string())]);
+    assert!(state.feature_flags.is_enabled(&ada.id, "comments"));
+    assert!(!state.feature_flags.is_enabled(&grace.id, "comments"));
+
+    let req = TestRequest::get()
+        .uri("/api/v1/admin/feature-flags")
+        .insert_header(("Host", "ada.dev"))
+        .insert_header(("Authorization", token.as_str()))
+        .to_request();
+    let listed: Vec<Value> = read_body_json(call_service(&app, req).await).await;
+    assert_eq!(listed, vec![json!({
+        "key": "comments",
+        "enabled": true,
+        "description": null,
+        "created_at": listed[0]["created_at"],
+        "updated_at": listed[0]["updated_at"],
+    })]);
+
+    // The same token on the other site's host is refused before any handler runs
+    let read = TestRequest::get().uri("/api/v1/admin/feature-flags");
+    let write = TestRequest::put().uri("/api/v1/admin/feature-flags/guestbook").set_json(json!({ "enabled": false }));
+    for req in [read, write] {
+        let req = req.insert_header(("Host", "grace.dev")).insert_header(("Authorization", token.as_str()));
+        assert_eq!(call_service(&app, req.to_request()).await.status(), StatusCode::UNAUTHORIZED);
+    }
+    assert!(flags.lock().unwrap()[&(grace.id, "guestbook".to_string())]);
+}
+
+#[actix_web::test]
+async fn a_feature_guarded_route_only_resolves_for_the_site_that_turned_it_on() {
+    let (ada, grace) = (tenant("ada"), tenant("grace"));
+    let flags = Flags::default();
+    let state = state(vec![ada.clone(), grace.clone()], &flags).await;
+    let site = init_service(
+        App::new()
+            .app_data(state.clone())
+            .service(web::resource("/x").guard(RequireFeature("x")).to(HttpResponse::Ok))
+            .wrap(TenantMiddleware),
+    )
+    .await;
+    let admin = init_service(
+        App::new()
+            .app_data(state.clone())
+            .configure(configure_routes)
+            .wrap(AuthMiddleware)
+            .wrap(TenantMiddleware),
+    )
+    .await;
+    let get_x = |host: &str| TestRequest::get().uri("/x").insert_header(("Host", host)).to_request();
+
+    assert_eq!(call_service(&site, get_x("ada.dev")).await.status(), StatusCode::NOT_FOUND);
+
+    let req = TestRequest::put()
+        .uri("/api/v1/admin/feature-flags/x")
+        .insert_header(("Host", "ada.dev"))
+        .insert_header(("Authorization", format!("Bearer {}", admin_token(&ada))))
+        .set_json(json!({ "enabled": true }))
+        .to_request();
+    assert_eq!(call_service(&admin, req).await.status(), StatusCode::OK);
+
+    assert_eq!(call_service(&site, get_x("ada.dev")).await.status(), StatusCode::OK);
+    assert_eq!(call_service(&site, get_x("grace.dev")).await.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, TestRequest},
+    web, App, HttpMessage,
+};
+use common::{mock_repositories, tenant, test_config};
+use portfolio_backend::{
+    entities::{
+        tenant::Tenant,
+        token::{Claims, TokenType},
+    },
+    repositories::user::MockUserRepository,
+    routes::configure_routes,
+    shared_repos::SharedRepositories,
+    AppState,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn claims(tenant: &Tenant, admin: bool) -> Claims {
+    Claims {
+        sub: Uuid::new_v4().to_string(),
+        email: "ada@example.com".to_string(),
+        admin,
+        editor: false,
+        verified: true,
+        exp: 0,
+        token_type: TokenType::Access,
+        iat: 0,
+        tid: tenant.id,
+        claims_version: 0,
+        iss: None,
+        aud: None,
+    }
+}
+
+/// The real route tree over `repos`, with the tenant and claims the
+/// middleware would have resolved
+macro_rules! app {
+    ($repos:expr, $tenant:expr, $claims:expr) => {{
+        let (tenant, claims): (Tenant, Option<Claims>) = ($tenant, $claims);
+        init_service(
+            App::new()
+                .app_data(web::Data::new(AppState::from_repositories(&test_config(json!({})), $repos)))
+                .configure(configure_routes)
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(tenant.clone());
+                    if let Some(claims) = &claims {
+                        req.extensions_mut().insert(claims.clone());
+                    }
+                    actix_web::dev::Service::call(srv, req)
+                }),
+        )
+        .await
+    }};
+}
+
+#[actix_web::test]
+async fn login_looks_the_user_up_in_the_request_tenant() {
+    let site = tenant();
+    let mut users = MockUserRepository::new();
+    let tenant_id = site.id;
+    users.expect_get_user_by_email()
+        .withf(move |tid, email| *tid == tenant_id && email == "grace@example.com")
+        .times(1)
+        .returning(|_, _| Ok(None));
+    let app = app!(SharedRepositories { user_repo: Arc::new(users), ..mock_repositories() }, site, None);
+
+    let req = TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .set_json(json!({ "email": "grace@example.com", "password": "correct horse battery" }))
+        .to_request();
+    assert_eq!(call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn admin_routes_refuse_other_users_before_touching_a_repository() {
+    let site = tenant();
+    // No expectations: any repository call fails the test
+    let app = app!(mock_repositories(), site.clone(), Some(claims(&site, false)));
+
+    for uri in ["/api/v1/admin/feature-flags", "/api/v1/admin/audit-log"] {
+        let res = call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN, "{uri}");
+    }
+}
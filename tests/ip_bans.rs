@@ -0,0 +1,127 @@
+mod common;
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use actix_web::{
+    http::{header::RETRY_AFTER, StatusCode},
+    test::{call_service, init_service, read_body_json, TestRequest},
+    web, App, HttpMessage,
+};
+use common::{mock_repositories, tenant, test_config};
+use portfolio_backend::{
+    geo::geoip::GeoLocator,
+    limiter::{headers::RATELIMIT_RESET, ip_ban::IpBanList},
+    middlewares::ip_ban::IpBanMiddleware,
+    repositories::security_event::MockSecurityEventRepository,
+    routes::configure_routes,
+    settings::AppConfig,
+    shared_repos::SharedRepositories,
+    use_cases::honeytoken::HoneytokenMonitor,
+    utils::get_client_ip::{get_client_ip, TrustedProxies},
+    AppState,
+};
+use serde_json::{json, Value};
+
+/// Behind proxies at 10.0.0.0/8 and 192.0.2.1, banning for a minute
+fn proxied_config() -> AppConfig {
+    test_config(json!({ "honeytoken_ban_secs": 60, "trusted_proxies": "10.0.0.0/8, 192.0.2.1" }))
+}
+
+fn peer(ip: &str) -> SocketAddr {
+    SocketAddr::new(ip.parse().unwrap(), 443)
+}
+
+fn client_ip(from: &str, forwarded_for: Option<&str>) -> String {
+    let mut req = TestRequest::get().peer_addr(peer(from));
+    if let Some(forwarded_for) = forwarded_for {
+        req = req.insert_header(("X-Forwarded-For", forwarded_for));
+    }
+    get_client_ip(&req.to_http_request(), &TrustedProxies::from_config(&proxied_config()))
+}
+
+#[test]
+fn forwarded_for_is_only_believed_from_trusted_proxies() {
+    // Straight from a client, the header is whatever it chose to send
+    assert_eq!(client_ip("203.0.113.9", Some("198.51.100.2")), "203.0.113.9");
+    assert_eq!(client_ip("10.1.2.3", None), "10.1.2.3");
+
+    // Through the proxies, the last hop they did not add is the client
+    assert_eq!(client_ip("10.1.2.3", Some("203.0.113.9")), "203.0.113.9");
+    assert_eq!(client_ip("10.1.2.3", Some("198.51.100.2, 203.0.113.9, 192.0.2.1")), "203.0.113.9");
+    assert_eq!(client_ip("192.0.2.1", Some("not-an-ip, 203.0.113.9")), "203.0.113.9");
+    assert_eq!(client_ip("10.1.2.3", Some("garbage")), "10.1.2.3");
+}
+
+#[test]
+fn trusted_proxies_must_be_addresses_or_ranges() {
+    assert_eq!(proxied_config().trusted_proxies().unwrap().len(), 2);
+
+    let config = test_config(json!({ "trusted_proxies": "10.0.0.0/8,proxy.internal" }));
+    assert!(config.trusted_proxies().is_err());
+}
+
+#[test]
+fn one_honeytoken_hit_bans_the_ip_until_the_ban_runs_out() {
+    let monitor = HoneytokenMonitor::new(
+        MockSecurityEventRepository::new(),
+        IpBanList::new(Duration::from_millis(50)),
+        GeoLocator::default(),
+    );
+
+    let (event, banned) = monitor.hit("203.0.113.9", None, "GET", "/wp-login.php");
+    assert!(banned);
+    assert_eq!(event.ip, "203.0.113.9");
+    assert!(monitor.bans.remaining("203.0.113.9").is_some());
+    assert_eq!(monitor.bans.remaining("198.51.100.2"), None);
+
+    std::thread::sleep(Duration::from_millis(80));
+    assert_eq!(monitor.bans.remaining("203.0.113.9"), None);
+
+    // With no ban duration hits are still recorded, nobody is banned
+    let disabled = HoneytokenMonitor::new(MockSecurityEventRepository::new(), IpBanList::new(Duration::ZERO), GeoLocator::default());
+    assert!(!disabled.hit("203.0.113.9", None, "GET", "/wp-login.php").1);
+    assert_eq!(disabled.bans.remaining("203.0.113.9"), None);
+}
+
+#[actix_web::test]
+async fn banned_clients_get_429s_everywhere_with_the_time_left() {
+    let tenant = tenant();
+    let mut events = MockSecurityEventRepository::new();
+    events.expect_record_event().returning(|_, _| Ok(()));
+    let repos = SharedRepositories { security_event_repo: Arc::new(events), ..mock_repositories() };
+    let app = init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::from_repositories(&proxied_config(), repos)))
+            .configure(configure_routes)
+            .wrap_fn(move |req, srv| {
+                req.extensions_mut().insert(tenant.clone());
+                actix_web::dev::Service::call(srv, req)
+            })
+            .wrap(IpBanMiddleware),
+    )
+    .await;
+    let via_proxy = |path: &str, client: &str| {
+        TestRequest::get()
+            .uri(path)
+            .peer_addr(peer("10.1.2.3"))
+            .insert_header(("X-Forwarded-For", client))
+            .to_request()
+    };
+
+    let res = call_service(&app, via_proxy("/wp-login.php", "203.0.113.9")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND, "decoys look like any unknown path");
+
+    let res = call_service(&app, via_proxy("/no-such-page", "203.0.113.9")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = res.headers().get(RETRY_AFTER).unwrap().to_str().unwrap().parse().unwrap();
+    assert!(retry_after > 0 && retry_after <= 60, "{retry_after}");
+    assert_eq!(res.headers().get(RATELIMIT_RESET), res.headers().get(RETRY_AFTER));
+    let body: Value = read_body_json(res).await;
+    assert_eq!((body["scope"].as_str(), body["code"].as_u64()), (Some("ip_ban"), Some(429)));
+
+    // Other clients behind the same proxy, and the proxy itself, are not banned
+    let res = call_service(&app, via_proxy("/no-such-page", "198.51.100.2")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let direct = TestRequest::get().uri("/no-such-page").peer_addr(peer("10.1.2.3")).to_request();
+    assert_eq!(call_service(&app, direct).await.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Duration, Utc};
+use portfolio_backend::{
+    entities::outbox::{
+        JobListQuery, JobStatsQuery, JobStatus, JobTypeCounts, OutboxEvent, PausedQueue, CONTACT_MESSAGE_RECEIVED,
+        HIRE_INQUIRY_RECEIVED,
+    },
+    errors::AppError,
+    repositories::outbox::MockOutboxRepository,
+    use_cases::jobs::{summarize_payload, JobDashboard},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn event(tenant_id: Uuid, payload: serde_json::Value) -> OutboxEvent {
+    let now = Utc::now();
+    OutboxEvent {
+        id: Uuid::new_v4(),
+        tenant_id,
+        event_type: CONTACT_MESSAGE_RECEIVED.to_string(),
+        payload,
+        attempts: 0,
+        last_error: None,
+        next_attempt_at: now,
+        delivered_at: None,
+        failed_at: None,
+        created_at: now,
+        leased_until: None,
+        cancelled_at: None,
+    }
+}
+
+fn at(offset_secs: i64) -> Option<DateTime<Utc>> {
+    Some(Utc::now() + Duration::seconds(offset_secs))
+}
+
+#[test]
+fn status_follows_leases_and_settlement() {
+    let now = Utc::now();
+    let mut job = event(Uuid::new_v4(), json!({}));
+    assert_eq!(job.status(now), JobStatus::Queued);
+
+    job.leased_until = at(300);
+    assert_eq!(job.status(now), JobStatus::Running);
+
+    // A relay that died mid-delivery leaves an expired lease behind
+    job.leased_until = at(-5);
+    assert_eq!(job.status(now), JobStatus::Queued);
+
+    job.failed_at = at(0);
+    assert_eq!(job.status(now), JobStatus::Failed);
+
+    job.cancelled_at = at(0);
+    assert_eq!(job.status(now), JobStatus::Cancelled);
+}
+
+#[test]
+fn payload_summaries_reduce_nested_values() {
+    assert_eq!(summarize_payload(&json!({ "id": 7, "tags": ["a", "b"], "meta": { "x": 1 } })), "id=7, meta={1 fields}, tags=[2 items]");
+    assert_eq!(summarize_payload(&json!("plain")), "plain");
+
+    let long = "x".repeat(60);
+    assert_eq!(summarize_payload(&json!({ "note": long })), format!("note={}…", "x".repeat(40)));
+}
+
+#[tokio::test]
+async fn listed_jobs_have_personal_data_redacted() {
+    let tenant_id = Uuid::new_v4();
+    let mut failing = event(tenant_id, json!({ "id": "42", "email": "ada@example.com" }));
+    failing.attempts = 2;
+    failing.last_error = Some("Relay rejected ada@example.com".to_string());
+    failing.next_attempt_at = Utc::now() + Duration::seconds(60);
+
+    let mut repo = MockOutboxRepository::new();
+    repo.expect_list_jobs().returning(move |tid, status, event_type, _, limit, offset| {
+        assert_eq!((status, event_type.as_deref(), limit, offset), (Some(JobStatus::Queued), None, 20, 20));
+        Ok(vec![OutboxEvent { tenant_id: *tid, ..failing.clone() }])
+    });
+    repo.expect_count_jobs().returning(|_, _, _, _| Ok(21));
+
+    let query = JobListQuery { status: Some(JobStatus::Queued), event_type: Some(" ".to_string()), page: Some(2), per_page: None };
+    let page = JobDashboard::new(repo).list(tenant_id, query).await.unwrap();
+
+    assert_eq!((page.total, page.page, page.per_page), (21, 2, 20));
+    let job = &page.jobs[0];
+    assert_eq!(job.status, JobStatus::Queued);
+    assert_eq!(job.payload_summary, "email=[REDACTED], id=42");
+    assert_eq!(job.last_error.as_deref(), Some("Relay rejected [REDACTED]"));
+    assert!(job.next_attempt_at.is_some());
+}
+
+#[tokio::test]
+async fn retry_and_cancel_explain_why_they_did_not_apply() {
+    let tenant_id = Uuid::new_v4();
+    let mut delivered = event(tenant_id, json!({}));
+    delivered.delivered_at = at(0);
+    let delivered_id = delivered.id;
+
+    let mut repo = MockOutboxRepository::new();
+    repo.expect_retry_job().returning(|_, _, _| Ok(None));
+    repo.expect_cancel_job().returning(|_, _, _, _| Ok(None));
+    repo.expect_get_job().returning(move |_, id| Ok((*id == delivered_id).then(|| delivered.clone())));
+
+    let jobs = JobDashboard::new(repo);
+    assert!(matches!(jobs.retry(tenant_id, &delivered_id.to_string()).await, Err(AppError::Conflict(m)) if m.contains("delivered")));
+    assert!(matches!(jobs.cancel(tenant_id, &delivered_id.to_string(), "admin").await, Err(AppError::Conflict(_))));
+    assert!(matches!(jobs.retry(tenant_id, &Uuid::new_v4().to_string()).await, Err(AppError::NotFound(_))));
+    assert!(matches!(jobs.retry(tenant_id, "not-a-uuid").await, Err(AppError::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn cancelled_jobs_record_the_admin() {
+    let tenant_id = Uuid::new_v4();
+    let queued = event(tenant_id, json!({}));
+    let id = queued.id;
+
+    let mut repo = MockOutboxRepository::new();
+    repo.expect_cancel_job().times(1).returning(move |_, _, reason, now| {
+        Ok(Some(OutboxEvent {
+            cancelled_at: Some(now),
+            failed_at: Some(now),
+            last_error: Some(reason.to_string()),
+            ..queued.clone()
+        }))
+    });
+
+    let job = JobDashboard::new(repo).cancel(tenant_id, &id.to_string(), "admin-1").await.unwrap();
+    assert_eq!(job.status, JobStatus::Cancelled);
+    assert_eq!(job.last_error.as_deref(), Some("Cancelled by admin admin-1"));
+    assert!(job.failed_at.is_none());
+    assert!(job.cancelled_at.is_some());
+}
+
+#[tokio::test]
+async fn stats_cover_every_queue_with_rates_and_pauses() {
+    let tenant_id = Uuid::new_v4();
+    let mut repo = MockOutboxRepository::new();
+    repo.expect_job_counts().returning(|_, since, now| {
+        assert_eq!((now - since).num_days(), 30);
+        Ok(vec![JobTypeCounts {
+            event_type: CONTACT_MESSAGE_RECEIVED.to_string(),
+            queued: 2,
+            retrying: 1,
+            delivered: 3,
+            failed: 1,
+            delivered_attempts: 6,
+            ..Default::default()
+        }])
+    });
+    repo.expect_list_paused_queues().returning(|_| {
+        Ok(vec![PausedQueue {
+            event_type: HIRE_INQUIRY_RECEIVED.to_string(),
+            paused_by: Some("admin-1".to_string()),
+            paused_at: Utc::now(),
+        }])
+    });
+
+    let stats = JobDashboard::new(repo).stats(tenant_id, JobStatsQuery { days: Some(30) }).await.unwrap();
+
+    let types: Vec<_> = stats.job_types.iter().map(|t| t.event_type.as_str()).collect();
+    assert_eq!(types, vec![CONTACT_MESSAGE_RECEIVED, HIRE_INQUIRY_RECEIVED]);
+
+    let contact = &stats.job_types[0];
+    assert_eq!(contact.success_rate, Some(0.75));
+    assert_eq!(contact.failure_rate, Some(0.25));
+    assert_eq!(contact.avg_attempts, Some(2.0));
+    assert!(contact.paused.is_none());
+
+    let hire = &stats.job_types[1];
+    assert_eq!((hire.queued, hire.success_rate), (0, None));
+    assert_eq!(hire.paused.as_ref().and_then(|p| p.paused_by.as_deref()), Some("admin-1"));
+}
+
+#[tokio::test]
+async fn only_known_queues_can_be_paused() {
+    let mut repo = MockOutboxRepository::new();
+    repo.expect_pause_queue().times(1).returning(|_, event_type, admin| {
+        Ok(PausedQueue { event_type: event_type.to_string(), paused_by: Some(admin.to_string()), paused_at: Utc::now() })
+    });
+    repo.expect_resume_queue().never();
+
+    let jobs = JobDashboard::new(repo);
+    let queue = jobs.pause(Uuid::new_v4(), HIRE_INQUIRY_RECEIVED, "admin-1").await.unwrap();
+    assert_eq!(queue.event_type, HIRE_INQUIRY_RECEIVED);
+
+    assert!(matches!(jobs.pause(Uuid::new_v4(), "report.generated", "admin-1").await, Err(AppError::NotFound(_))));
+    assert!(matches!(jobs.resume(Uuid::new_v4(), "report.generated").await, Err(AppError::NotFound(_))));
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use chrono::Utc;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use common::{test_config, JWT_SECRET, REFRESH_TOKEN_SECRET};
+use portfolio_backend::{
+    auth::jwt::JwtService,
+    entities::{tenant::DEFAULT_TENANT_ID, token::TokenType, user::User},
+    errors::AuthError,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+const ISSUER: &str = "https://api.example.com";
+const AUDIENCE: &str = "portfolio";
+
+fn test_user() -> User {
+    let now = Utc::now();
+    User {
+        id: Uuid::new_v4(),
+        tenant_id: Uuid::new_v4(),
+        email: "ada@example.com".to_string(),
+        username: None,
+        password_hash: String::new(),
+        is_admin: false,
+        is_verified: true,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        deleted_by: None,
+        is_editor: false,
+        display_name: None,
+        avatar_url: None,
+        is_system: false,
+    }
+}
+
+/// Access token shaped like the ones issued before `iss`/`aud` existed
+fn legacy_access_token(user: &User, extra: serde_json::Value) -> String {
+    let now = Utc::now().timestamp();
+    let mut claims = json!({
+        "sub": user.id.to_string(),
+        "email": user.email,
+        "admin": user.is_admin,
+        "verified": user.is_verified,
+        "exp": now + 600,
+        "token_type": "access",
+        "iat": now,
+        "tid": user.tenant_id,
+    });
+    // A null in `extra` drops the claim
+    let claims = claims.as_object_mut().unwrap();
+    claims.extend(extra.as_object().unwrap().clone());
+    claims.retain(|_, value| !value.is_null());
+    encode(&Header::new(Algorithm::HS512), &claims, &EncodingKey::from_secret(JWT_SECRET.as_bytes())).unwrap()
+}
+
+fn legacy_refresh_token(user: &User) -> String {
+    let now = Utc::now().timestamp();
+    let claims = json!({
+        "sub": user.id.to_string(),
+        "exp": now + 600,
+        "token_type": "refresh",
+        "iat": now,
+    });
+    encode(&Header::new(Algorithm::HS512), &claims, &EncodingKey::from_secret(REFRESH_TOKEN_SECRET.as_bytes())).unwrap()
+}
+
+#[test]
+fn new_tokens_carry_configured_issuer_and_audience() {
+    let service = JwtService::new(&test_config(json!({
+        "jwt_issuer": ISSUER,
+        "jwt_audience": AUDIENCE,
+        "jwt_enforce_issuer_audience": true,
+    })));
+    let user = test_user();
+
+    let access = service.decode_jwt(&service.create_jwt(&user, 0).unwrap()).expect("access token");
+    assert_eq!(access.claims.iss.as_deref(), Some(ISSUER));
+    assert_eq!(access.claims.aud.as_deref(), Some(AUDIENCE));
+    assert_eq!(access.claims.token_type, TokenType::Access);
+
+    let refresh = service.decode_refresh_jwt(&service.create_refresh_jwt(&user.id, 0).unwrap()).expect("refresh token");
+    assert_eq!(refresh.claims.iss.as_deref(), Some(ISSUER));
+    assert_eq!(refresh.claims.aud.as_deref(), Some(AUDIENCE));
+}
+
+#[test]
+fn tokens_minted_before_the_change_are_accepted_unless_enforced() {
+    let user = test_user();
+    let token = legacy_access_token(&user, json!({}));
+
+    let lenient = JwtService::new(&test_config(json!({ "jwt_issuer": ISSUER, "jwt_audience": AUDIENCE })));
+    let claims = lenient.decode_jwt(&token).expect("legacy token accepted").claims;
+    assert_eq!(claims.sub, user.id.to_string());
+    assert!(claims.iss.is_none() && claims.aud.is_none());
+    assert!(lenient.decode_refresh_jwt(&legacy_refresh_token(&user)).is_ok());
+
+    let strict = JwtService::new(&test_config(json!({
+        "jwt_issuer": ISSUER,
+        "jwt_audience": AUDIENCE,
+        "jwt_enforce_issuer_audience": true,
+    })));
+    assert!(matches!(strict.decode_jwt(&token), Err(AuthError::InvalidToken)));
+    assert!(matches!(strict.decode_refresh_jwt(&legacy_refresh_token(&user)), Err(AuthError::InvalidToken)));
+}
+
+#[test]
+fn mismatched_issuer_or_audience_is_always_rejected() {
+    let service = JwtService::new(&test_config(json!({ "jwt_issuer": ISSUER, "jwt_audience": AUDIENCE })));
+    let user = test_user();
+
+    let wrong_issuer = legacy_access_token(&user, json!({ "iss": "https://elsewhere.example.com", "aud": AUDIENCE }));
+    assert!(matches!(service.decode_jwt(&wrong_issuer), Err(AuthError::InvalidToken)));
+
+    let wrong_audience = legacy_access_token(&user, json!({ "iss": ISSUER, "aud": "another-app" }));
+    assert!(matches!(service.decode_jwt(&wrong_audience), Err(AuthError::InvalidToken)));
+}
+
+#[test]
+fn tokens_are_accepted_when_no_issuer_or_audience_is_configured() {
+    let service = JwtService::new(&test_config(json!({ "jwt_issuer": null, "jwt_audience": null })));
+    let user = test_user();
+
+    let token = service.create_jwt(&user, 0).unwrap();
+    let claims = service.decode_jwt(&token).expect("access token").claims;
+    assert!(claims.iss.is_none() && claims.aud.is_none());
+
+    let with_audience = legacy_access_token(&user, json!({ "aud": AUDIENCE }));
+    assert!(service.decode_jwt(&with_audience).is_ok());
+}
+
+#[test]
+fn claims_version_round_trips_and_defaults_to_zero_for_older_tokens() {
+    let service = JwtService::new(&test_config(json!({ "jwt_issuer": ISSUER, "jwt_audience": AUDIENCE })));
+    let user = test_user();
+
+    let access = service.decode_jwt(&service.create_jwt(&user, 3).unwrap()).expect("access token");
+    assert_eq!(access.claims.claims_version, 3);
+
+    let refresh = service.decode_refresh_jwt(&service.create_refresh_jwt(&user.id, 3).unwrap()).expect("refresh token");
+    assert_eq!(refresh.claims.claims_version, 3);
+
+    let legacy = service.decode_jwt(&legacy_access_token(&user, json!({}))).expect("legacy token");
+    assert_eq!(legacy.claims.claims_version, 0);
+}
+#[test]
+fn tokens_minted_before_tenants_belong_to_the_default_tenant() {
+    let service = JwtService::new(&test_config(json!({ "jwt_issuer": ISSUER, "jwt_audience": AUDIENCE })));
+    let user = test_user();
+
+    let legacy = service.decode_jwt(&legacy_access_token(&user, json!({ "tid": null }))).expect("pre-tenant token");
+    assert_eq!(legacy.claims.tid, DEFAULT_TENANT_ID);
+
+    let current = service.decode_jwt(&service.create_jwt(&user, 0).unwrap()).expect("access token");
+    assert_eq!(current.claims.tid, user.tenant_id);
+}
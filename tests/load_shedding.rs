@@ -0,0 +1,77 @@
+mod common;
+
+use std::{collections::HashMap, time::Duration};
+
+use actix_web::{
+    http::{header::RETRY_AFTER, StatusCode},
+    test::{call_service, init_service, TestRequest},
+    web, App, HttpResponse,
+};
+use common::{mock_repositories, test_config};
+use portfolio_backend::{limiter::load_shedder::LoadShedder, middlewares::load_shed::LoadShed, AppState};
+use serde_json::json;
+
+fn shedder(limits: &[(&str, usize)], queue_timeout_ms: u64) -> LoadShedder {
+    let limits: HashMap<String, usize> = limits.iter().map(|(scope, limit)| (scope.to_string(), *limit)).collect();
+    LoadShedder::new(limits, Duration::from_millis(queue_timeout_ms))
+}
+
+#[tokio::test]
+async fn a_full_scope_sheds_after_the_queue_timeout_without_touching_others() {
+    let shedder = shedder(&[("blog", 1), ("auth", 1)], 20);
+
+    let held = shedder.acquire("blog").await.unwrap();
+    assert!(held.is_some());
+    assert_eq!(shedder.available("blog"), Some(0));
+    assert!(shedder.acquire("blog").await.is_err());
+
+    // A busy blog does not starve auth, and scopes without a budget are not limited
+    assert!(shedder.acquire("auth").await.unwrap().is_some());
+    assert!(shedder.acquire("unlisted").await.unwrap().is_none());
+    assert_eq!(shedder.available("unlisted"), None);
+
+    drop(held);
+    assert_eq!(shedder.available("blog"), Some(1));
+    assert!(shedder.acquire("blog").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn queued_requests_get_a_slot_freed_within_the_timeout() {
+    let blog = shedder(&[("blog", 1)], 500);
+    let held = blog.acquire("blog").await.unwrap();
+
+    let waiting = tokio::spawn({
+        let blog = blog.clone();
+        async move { blog.acquire("blog").await.map(|permit| permit.is_some()) }
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    drop(held);
+
+    assert!(matches!(waiting.await.unwrap(), Ok(true)));
+
+    // A zero budget still lets one request through at a time
+    let minimum = shedder(&[("blog", 0)], 20);
+    assert_eq!(minimum.available("blog"), Some(1));
+}
+
+#[actix_web::test]
+async fn shed_requests_get_a_503_with_retry_after() {
+    let config = test_config(json!({ "scope_concurrency_limits": "blog=1", "scope_queue_timeout_ms": 20 }));
+    let state = web::Data::new(AppState::from_repositories(&config, mock_repositories()));
+    let app = init_service(
+        App::new()
+            .app_data(state.clone())
+            .service(web::resource("/blog").wrap(LoadShed::scope("blog")).to(HttpResponse::Ok)),
+    )
+    .await;
+
+    let in_flight = state.load_shedder.acquire("blog").await.unwrap();
+    let res = call_service(&app, TestRequest::get().uri("/blog").to_request()).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(res.headers().contains_key(RETRY_AFTER));
+
+    drop(in_flight);
+    let res = call_service(&app, TestRequest::get().uri("/blog").to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(state.load_shedder.available("blog"), Some(1), "the slot is given back after the response");
+}
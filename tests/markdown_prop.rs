@@ -0,0 +1,52 @@
+use once_cell::sync::Lazy;
+use portfolio_backend::utils::markdown::{first_paragraph_excerpt, safe_markdown_to_html};
+use proptest::prelude::*;
+use regex::Regex;
+
+static EVENT_HANDLER_ATTR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<[a-z][^>]*\son[a-z]+\s*=").unwrap());
+static JS_URL_ATTR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)(href|src)\s*=\s*["']?\s*javascript:"#).unwrap());
+
+fn hostile_fragment() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("<script>alert(1)</script>".to_string()),
+        Just("<SCRIPT SRC=//evil.example/x.js></SCRIPT>".to_string()),
+        Just("<img src=x onerror=alert(1)>".to_string()),
+        Just("<a href=\"javascript:alert(1)\">x</a>".to_string()),
+        Just("[x](javascript:alert(1))".to_string()),
+        Just("<svg onload=alert(1)>".to_string()),
+        Just("<div onmouseover='x()'>hover</div>".to_string()),
+        Just("<iframe src=\"https://evil.example\"></iframe>".to_string()),
+        "\\PC{0,40}",
+    ]
+}
+
+fn assert_safe(html: &str) -> Result<(), TestCaseError> {
+    prop_assert!(!html.to_ascii_lowercase().contains("<script"), "script tag survived: {}", html);
+    prop_assert!(!html.to_ascii_lowercase().contains("<iframe"), "iframe survived: {}", html);
+    prop_assert!(!EVENT_HANDLER_ATTR.is_match(html), "event handler survived: {}", html);
+    prop_assert!(!JS_URL_ATTR.is_match(html), "javascript: url survived: {}", html);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_input_never_yields_active_content(input in "\\PC{0,400}") {
+        assert_safe(&safe_markdown_to_html(&input))?;
+    }
+
+    #[test]
+    fn hostile_fragments_are_neutralised(parts in prop::collection::vec(hostile_fragment(), 1..8)) {
+        let input = parts.join("\n\n");
+        assert_safe(&safe_markdown_to_html(&input))?;
+    }
+
+    #[test]
+    fn generated_excerpts_fit_and_are_trimmed(input in "\\PC{0,600}", max_chars in 1usize..300) {
+        if let Some(excerpt) = first_paragraph_excerpt(&input, max_chars) {
+            prop_assert!(excerpt.chars().count() <= max_chars, "excerpt too long: {:?}", excerpt);
+            prop_assert!(!excerpt.is_empty() && excerpt.trim() == excerpt, "untrimmed excerpt: {:?}", excerpt);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use portfolio_backend::{
+    cache::one_time_tokens::MockOneTimeTokenCache,
+    entities::one_time_token::{hash_one_time_token, OneTimeToken, PURPOSE_EMAIL_VERIFICATION, PURPOSE_PASSWORD_RESET},
+    errors::AppError,
+    repositories::one_time_token::MockOneTimeTokenRepository,
+    use_cases::one_time_tokens::OneTimeTokens,
+};
+use uuid::Uuid;
+
+fn stored(tenant_id: Uuid, token: &str, purpose: &str) -> OneTimeToken {
+    OneTimeToken {
+        id: Uuid::new_v4(),
+        tenant_id,
+        user_id: Uuid::new_v4(),
+        purpose: purpose.to_string(),
+        token_hash: hash_one_time_token(token),
+        expires_at: Utc::now() + Duration::minutes(30),
+        used_at: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn tokens_are_only_valid_for_their_purpose_until_used_or_expired() {
+    let now = Utc::now();
+    let token = stored(Uuid::new_v4(), "reset", PURPOSE_PASSWORD_RESET);
+
+    assert!(token.is_valid(PURPOSE_PASSWORD_RESET, now));
+    assert!(!token.is_valid(PURPOSE_EMAIL_VERIFICATION, now));
+    assert!(!token.is_valid(PURPOSE_PASSWORD_RESET, token.expires_at));
+    assert!(!OneTimeToken { used_at: Some(now), ..token }.is_valid(PURPOSE_PASSWORD_RESET, now));
+}
+
+#[tokio::test]
+async fn issued_tokens_are_stored_hashed_and_cached() {
+    let tenant_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+
+    let mut repo = MockOneTimeTokenRepository::new();
+    repo.expect_create_one_time_token().times(1).returning(move |tenant_id, insert| {
+        assert_eq!(insert.user_id, user_id);
+        assert_eq!(insert.purpose, PURPOSE_EMAIL_VERIFICATION);
+        Ok(OneTimeToken {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            user_id: insert.user_id,
+            purpose: insert.purpose.to_string(),
+            token_hash: insert.token_hash.clone(),
+            expires_at: insert.expires_at,
+            used_at: None,
+            created_at: Utc::now(),
+        })
+    });
+    let mut cache = MockOneTimeTokenCache::new();
+    cache.expect_put_token().times(1).returning(|_| Ok(()));
+
+    let tokens = OneTimeTokens::new(repo, Some(Arc::new(cache)));
+    let token = tokens
+        .issue(&tenant_id, user_id, PURPOSE_EMAIL_VERIFICATION, Duration::hours(24))
+        .await
+        .expect("issued");
+
+    assert_eq!(token.len(), 64);
+    assert_ne!(token, hash_one_time_token(&token));
+}
+
+#[tokio::test]
+async fn lookups_fall_back_to_postgres_when_redis_fails() {
+    let tenant_id = Uuid::new_v4();
+    let token = stored(tenant_id, "reset", PURPOSE_PASSWORD_RESET);
+
+    let mut repo = MockOneTimeTokenRepository::new();
+    let found = token.clone();
+    repo.expect_find_one_time_token()
+        .times(1)
+        .returning(move |_, hash| Ok((hash == found.token_hash).then(|| found.clone())));
+    let mut cache = MockOneTimeTokenCache::new();
+    cache.expect_get_token()
+        .returning(|_| Err(AppError::ServiceUnavailable("Redis unavailable".to_string())));
+    cache.expect_put_token().returning(|_| Ok(()));
+
+    let tokens = OneTimeTokens::new(repo, Some(Arc::new(cache)));
+    let peeked = tokens.peek(&tenant_id, PURPOSE_PASSWORD_RESET, "reset").await.expect("peeked");
+
+    assert_eq!(peeked.map(|t| t.id), Some(token.id));
+}
+
+#[tokio::test]
+async fn a_released_token_can_be_consumed_again() {
+    let tenant_id = Uuid::new_v4();
+    let token = stored(tenant_id, "verify", PURPOSE_EMAIL_VERIFICATION);
+
+    let mut repo = MockOneTimeTokenRepository::new();
+    let consumed = token.clone();
+    repo.expect_consume_one_time_token()
+        .times(2)
+        .returning(move |_, _, _| Ok(Some(OneTimeToken { used_at: Some(Utc::now()), ..consumed.clone() })));
+    let id = token.id;
+    repo.expect_release_one_time_token()
+        .times(1)
+        .withf(move |_, released| *released == id)
+        .returning(|_, _| Ok(()));
+
+    let tokens = OneTimeTokens::new(repo, None);
+    let first = tokens
+        .consume(&tenant_id, PURPOSE_EMAIL_VERIFICATION, "verify")
+        .await
+        .expect("consumed")
+        .expect("valid");
+    tokens.release(&first).await.expect("released");
+
+    let second = tokens.consume(&tenant_id, PURPOSE_EMAIL_VERIFICATION, "verify").await.expect("consumed");
+    assert_eq!(second.map(|t| t.id), Some(token.id));
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use portfolio_backend::entities::{about_me::UpdateAboutMeRequest, option_fields::OptionField};
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+fn option_field<T: Arbitrary + Clone + 'static>() -> impl Strategy<Value = OptionField<T>> {
+    prop_oneof![
+        Just(OptionField::Unchanged),
+        Just(OptionField::SetToNull),
+        any::<T>().prop_map(OptionField::SetToValue),
+    ]
+}
+
+/// A PATCH body with one field, declared the way request structs declare theirs
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+struct Patch<T> {
+    #[serde(default, skip_serializing_if = "OptionField::is_unchanged")]
+    field: OptionField<T>,
+}
+
+fn round_trip<T>(value: &OptionField<T>) -> OptionField<T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let json = serde_json::to_string(&Patch { field: value.clone() }).expect("serialize");
+    serde_json::from_str::<Patch<T>>(&json).expect("deserialize").field
+}
+
+fn patch<T: DeserializeOwned>(json: serde_json::Value) -> OptionField<T> {
+    serde_json::from_value::<Patch<T>>(json).expect("deserialize").field
+}
+
+proptest! {
+    #[test]
+    fn string_fields_round_trip(field in option_field::<String>()) {
+        prop_assert_eq!(round_trip(&field), field);
+    }
+
+    #[test]
+    fn integer_fields_round_trip(field in option_field::<i64>()) {
+        prop_assert_eq!(round_trip(&field), field);
+    }
+
+    #[test]
+    fn bool_fields_round_trip(field in option_field::<bool>()) {
+        prop_assert_eq!(round_trip(&field), field);
+    }
+
+    #[test]
+    fn vec_fields_round_trip(field in option_field::<Vec<String>>()) {
+        prop_assert_eq!(round_trip(&field), field);
+    }
+
+    #[test]
+    fn set_values_serialize_as_plain_json(value in any::<i64>()) {
+        let json = serde_json::to_value(Patch { field: OptionField::SetToValue(value) }).expect("serialize");
+        prop_assert_eq!(json, serde_json::json!({ "field": value }));
+    }
+}
+
+#[test]
+fn missing_field_is_unchanged() {
+    assert_eq!(patch::<String>(serde_json::json!({})), OptionField::Unchanged);
+}
+
+#[test]
+fn null_is_set_to_null() {
+    assert_eq!(patch::<String>(serde_json::json!({ "field": null })), OptionField::SetToNull);
+    assert_eq!(patch::<i32>(serde_json::json!({ "field": null })), OptionField::SetToNull);
+}
+
+#[test]
+fn plain_values_are_set_to_value() {
+    assert_eq!(patch::<bool>(serde_json::json!({ "field": false })), OptionField::SetToValue(false));
+    assert_eq!(patch::<i32>(serde_json::json!({ "field": 3 })), OptionField::SetToValue(3));
+    assert_eq!(
+        patch::<Vec<String>>(serde_json::json!({ "field": ["rust", "web"] })),
+        OptionField::SetToValue(vec!["rust".to_string(), "web".to_string()])
+    );
+    assert_eq!(
+        patch::<DateTime<Utc>>(serde_json::json!({ "field": "2024-03-01T09:30:00Z" })),
+        OptionField::SetToValue(Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap())
+    );
+}
+
+#[test]
+fn variant_names_are_ordinary_strings() {
+    assert_eq!(
+        patch::<String>(serde_json::json!({ "field": "SetToNull" })),
+        OptionField::SetToValue("SetToNull".to_string())
+    );
+}
+
+#[test]
+fn mistyped_values_are_rejected() {
+    assert!(serde_json::from_value::<Patch<i32>>(serde_json::json!({ "field": "three" })).is_err());
+}
+
+#[test]
+fn unchanged_fields_are_left_out_when_serialized() {
+    let json = serde_json::to_value(Patch::<String> { field: OptionField::Unchanged }).expect("serialize");
+    assert_eq!(json, serde_json::json!({}));
+
+    let json = serde_json::to_value(Patch::<String> { field: OptionField::SetToNull }).expect("serialize");
+    assert_eq!(json, serde_json::json!({ "field": null }));
+}
+
+
+fn about_update(json: serde_json::Value) -> UpdateAboutMeRequest {
+    serde_json::from_value(json).expect("deserialize")
+}
+
+#[test]
+fn missing_about_fields_are_unchanged() {
+    let update = about_update(serde_json::json!({ "expected_revision": 2 }));
+
+    assert_eq!(update.content_markdown, OptionField::Unchanged);
+    assert_eq!(update.effective_date, OptionField::Unchanged);
+    assert_eq!(update.expected_revision, 2);
+}
+
+#[test]
+fn explicit_null_about_fields_are_set_to_null() {
+    let update = about_update(serde_json::json!({
+        "content_markdown": null,
+        "effective_date": null,
+        "expected_revision": 0,
+    }));
+
+    assert_eq!(update.content_markdown, OptionField::SetToNull);
+    assert_eq!(update.effective_date, OptionField::SetToNull);
+}
+
+#[test]
+fn about_values_are_set_to_value() {
+    let update = about_update(serde_json::json!({
+        "effective_date": "2024-03-01",
+        "expected_revision": 0,
+    }));
+
+    assert_eq!(update.content_markdown, OptionField::Unchanged);
+    assert_eq!(update.effective_date, OptionField::SetToValue(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()));
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, read_body, TestRequest},
+    web, App, HttpMessage,
+};
+use bytes::Bytes;
+use chrono::Utc;
+use common::{mock_repositories, tenant, test_config};
+use futures::StreamExt;
+use portfolio_backend::{
+    entities::{
+        app_setting::{AppSetting, OUTBOUND_ALLOWED_DOMAINS},
+        outbound::{OutboundQrQuery, QrErrorCorrection},
+        tenant::Tenant,
+        token::{Claims, TokenType},
+    },
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    middlewares::forwarded_prefix::ForwardedPrefix,
+    repositories::{
+        app_settings::MockAppSettingsRepository, outbound::MockOutboundClickRepository,
+        stats_rollup::MockStatsRollupRepository,
+    },
+    routes::configure_routes,
+    shared_repos::SharedRepositories,
+    storage::object::{MockObjectStorage, ObjectBody},
+    use_cases::{outbound::OutboundLinks, settings::RuntimeSettings},
+    utils::{public_urls::PublicUrls, qr_code::qr_png},
+    AppState,
+};
+use qrcode::EcLevel;
+use serde_json::json;
+use uuid::Uuid;
+
+type Links = OutboundLinks<MockOutboundClickRepository, MockAppSettingsRepository, MockStatsRollupRepository>;
+type Stored = Arc<Mutex<Vec<(String, Bytes)>>>;
+
+/// Links to github.com are allowed for the tenant
+fn settings_repo(tenant_id: Uuid) -> MockAppSettingsRepository {
+    let mut settings_repo = MockAppSettingsRepository::new();
+    settings_repo.expect_list_all_settings().returning(move || {
+        Ok(vec![AppSetting {
+            tenant_id,
+            key: OUTBOUND_ALLOWED_DOMAINS.to_string(),
+            value: json!(["github.com"]),
+            updated_at: Utc::now(),
+        }])
+    });
+    settings_repo
+}
+
+async fn outbound_links(tenant_id: Uuid, stored: &Stored) -> Links {
+    let settings = RuntimeSettings::new(settings_repo(tenant_id));
+    settings.refresh().await.expect("settings loaded");
+
+    let mut storage = MockObjectStorage::new();
+    let objects = stored.clone();
+    storage.expect_put().returning(move |key, body, _| {
+        objects.lock().unwrap().push((key.to_string(), body));
+        Ok(())
+    });
+    let objects = stored.clone();
+    storage.expect_get_stream().returning(move |key| {
+        let objects = objects.lock().unwrap();
+        let (_, body) = objects
+            .iter()
+            .find(|(k, _)| k == key)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Object not found".to_string()))?;
+        Ok(ObjectBody { size: body.len() as u64, stream: futures::stream::iter([Ok(body)]).boxed() })
+    });
+
+    OutboundLinks::new(
+        MockOutboundClickRepository::new(),
+        settings,
+        MockStatsRollupRepository::new(),
+        GeoLocator::default(),
+        Some(Arc::new(storage)),
+    )
+}
+
+fn claims(tenant: &Tenant, admin: bool) -> Claims {
+    Claims {
+        sub: Uuid::new_v4().to_string(),
+        email: "ada@example.com".to_string(),
+        admin,
+        editor: false,
+        verified: true,
+        exp: 0,
+        token_type: TokenType::Access,
+        iat: 0,
+        tid: tenant.id,
+        claims_version: 0,
+        iss: None,
+        aud: None,
+    }
+}
+
+fn query(url: &str, size: Option<u32>, ecc: QrErrorCorrection) -> OutboundQrQuery {
+    OutboundQrQuery { url: url.to_string(), size, ecc }
+}
+
+fn png_width(png: &[u8]) -> u32 {
+    let decoder = png::Decoder::new(png);
+    decoder.read_info().expect("valid PNG").info().width
+}
+
+#[tokio::test]
+async fn qr_codes_are_rendered_once_then_served_from_storage() {
+    let site = tenant();
+    let stored = Stored::default();
+    let links = outbound_links(site.id, &stored).await;
+
+    let first = links
+        .qr_code(&site, &PublicUrls::default(), query("https://github.com/ada", None, QrErrorCorrection::M))
+        .await
+        .unwrap();
+    let again = links
+        .qr_code(&site, &PublicUrls::default(), query("https://github.com/ada", None, QrErrorCorrection::M))
+        .await
+        .unwrap();
+    assert_eq!(first, again);
+
+    let larger = links
+        .qr_code(&site, &PublicUrls::default(), query("https://github.com/ada", Some(1024), QrErrorCorrection::H))
+        .await
+        .unwrap();
+
+    let stored = stored.lock().unwrap();
+    assert_eq!(stored.len(), 2);
+    assert!(stored.iter().all(|(key, _)| key.starts_with("qr/ada/") && key.ends_with(".png")));
+    assert!(png_width(&first) <= 512 && png_width(&first) > 400);
+    assert!(png_width(&larger) <= 1024 && png_width(&larger) > 900);
+}
+
+#[tokio::test]
+async fn qr_codes_only_cover_allowed_targets_and_sizes() {
+    let site = tenant();
+    let stored = Stored::default();
+    let links = outbound_links(site.id, &stored).await;
+
+    let elsewhere = links
+        .qr_code(&site, &PublicUrls::default(), query("https://evil.example/phish", None, QrErrorCorrection::M))
+        .await;
+    assert!(matches!(elsewhere, Err(AppError::InvalidInput(_))));
+
+    let huge = links
+        .qr_code(&site, &PublicUrls::default(), query("https://github.com/ada", Some(10_000), QrErrorCorrection::M))
+        .await;
+    assert!(huge.is_err());
+    assert!(stored.lock().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn qr_codes_are_for_admins_and_link_back_under_the_forwarded_prefix() {
+    let site = tenant();
+    let config = test_config(json!({}));
+    let repos = SharedRepositories { settings_repo: Arc::new(settings_repo(site.id)), ..mock_repositories() };
+    let state = AppState::from_repositories(&config, repos);
+    state.settings.refresh().await.unwrap();
+
+    let resolved = site.clone();
+    let app = init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .configure(configure_routes)
+            .wrap_fn(move |req, srv| {
+                req.extensions_mut().insert(resolved.clone());
+                // Stands in for AuthMiddleware: the role comes from the test header
+                if let Some(admin) = req.headers().get("X-Test-Admin").map(|value| value == "true") {
+                    req.extensions_mut().insert(claims(&resolved, admin));
+                }
+                actix_web::dev::Service::call(srv, req)
+            })
+            .wrap(ForwardedPrefix::new("")),
+    )
+    .await;
+    let uri = "/sites/ada/api/v1/out/qr.png?url=https%3A%2F%2Fgithub.com%2Fada&size=512&ecc=m";
+
+    let user = TestRequest::get()
+        .uri(uri)
+        .insert_header(("X-Forwarded-Prefix", "/sites/ada"))
+        .insert_header(("X-Test-Admin", "false"))
+        .to_request();
+    assert_eq!(call_service(&app, user).await.status(), StatusCode::FORBIDDEN);
+
+    let req = TestRequest::get()
+        .uri(uri)
+        .insert_header(("X-Forwarded-Prefix", "/sites/ada"))
+        .insert_header(("X-Test-Admin", "true"))
+        .to_request();
+    let png = read_body(call_service(&app, req).await).await;
+
+    let link = "https://ada.dev/sites/ada/api/v1/out?url=https%3A%2F%2Fgithub.com%2Fada";
+    assert!(png == qr_png(link, 512, EcLevel::M).unwrap(), "QR code of {link}");
+}
+
+#[test]
+fn qr_png_keeps_whole_pixel_modules_and_a_quiet_zone() {
+    let png = portfolio_backend::utils::qr_code::qr_png("https://ada.dev/api/v1/out?url=x", 10, EcLevel::L).unwrap();
+    let decoder = png::Decoder::new(png.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut pixels).unwrap();
+
+    // Too small to scale: one pixel per module, 4 light modules on each side
+    assert_eq!(frame.width, frame.height);
+    assert!(frame.width > 10);
+    assert!(pixels[..frame.width as usize * 4].iter().all(|&p| p == u8::MAX));
+    assert_eq!(pixels[4 * frame.width as usize + 4], 0, "finder pattern corner is dark");
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use common::tenant;
+use portfolio_backend::{
+    entities::{
+        notification::{Notification, NotificationEvent},
+        outbox::{AggregateRef, OutboxEvent, CONTACT_MESSAGE_RECEIVED, HIRE_INQUIRY_RECEIVED, OUTBOX_MAX_ATTEMPTS},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    repositories::{outbox::MockOutboxRepository, tenant::MockTenantRepository},
+    use_cases::{
+        dispatcher::NotificationSink,
+        outbox::{OutboxRelay, OutboxSubscriber, RelayOutcome},
+        tenants::TenantResolver,
+    },
+};
+use uuid::Uuid;
+
+/// Delivers contact messages, failing with `error` when one is set
+struct StubSubscriber {
+    error: Option<&'static str>,
+}
+
+#[async_trait]
+impl OutboxSubscriber for StubSubscriber {
+    fn event_types(&self) -> &'static [&'static str] {
+        &[CONTACT_MESSAGE_RECEIVED]
+    }
+
+    async fn deliver(&self, _tenant: &Tenant, _event: &OutboxEvent) -> Result<(), AppError> {
+        match self.error {
+            Some(error) => Err(AppError::ServiceUnavailable(error.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct RecordedAlerts(Mutex<Vec<Notification>>);
+
+#[async_trait]
+impl NotificationSink for RecordedAlerts {
+    async fn notify(&self, _tenant_id: Uuid, notification: Notification) {
+        self.0.lock().unwrap().push(notification);
+    }
+}
+
+type MarkedFailed = Arc<Mutex<Vec<(Uuid, Option<DateTime<Utc>>)>>>;
+
+fn event(tenant_id: Uuid, event_type: &str, attempts: i32) -> OutboxEvent {
+    let id = Uuid::new_v4();
+    OutboxEvent {
+        id,
+        tenant_id,
+        event_type: event_type.to_string(),
+        payload: AggregateRef::payload(id),
+        attempts,
+        last_error: None,
+        next_attempt_at: Utc::now(),
+        delivered_at: None,
+        failed_at: None,
+        created_at: Utc::now(),
+        leased_until: Some(Utc::now() + Duration::minutes(5)),
+        cancelled_at: None,
+    }
+}
+
+fn outbox(events: Vec<OutboxEvent>, failed: &MarkedFailed) -> MockOutboxRepository {
+    let mut outbox = MockOutboxRepository::new();
+    outbox.expect_claim_due().times(1).returning(move |_, _, _| Ok(events.clone()));
+    let failed = failed.clone();
+    outbox.expect_mark_failed().returning(move |id, _, retry_at| {
+        failed.lock().unwrap().push((*id, retry_at));
+        Ok(())
+    });
+    outbox
+}
+
+async fn relay(outbox: MockOutboxRepository, site: Tenant, subscriber: StubSubscriber) -> OutboxRelay<MockOutboxRepository, MockTenantRepository> {
+    let mut tenant_repo = MockTenantRepository::new();
+    tenant_repo.expect_list_tenants().returning(move || Ok(vec![site.clone()]));
+    let tenants = TenantResolver::new(tenant_repo, false);
+    tenants.refresh().await.expect("tenants loaded");
+
+    OutboxRelay::new(outbox, tenants).subscribe(Arc::new(subscriber))
+}
+
+#[tokio::test]
+async fn delivered_events_are_marked_even_when_one_mark_fails() {
+    let site = tenant();
+    let first = event(site.id, CONTACT_MESSAGE_RECEIVED, 1);
+    let second = event(site.id, CONTACT_MESSAGE_RECEIVED, 1);
+    let first_id = first.id;
+
+    let failed = MarkedFailed::default();
+    let mut outbox = outbox(vec![first, second], &failed);
+    let delivered = Arc::new(Mutex::new(Vec::new()));
+    let marked = delivered.clone();
+    outbox.expect_mark_delivered().times(2).returning(move |id| {
+        marked.lock().unwrap().push(*id);
+        if *id == first_id {
+            Err(AppError::InternalError("connection reset".to_string()))
+        } else {
+            Ok(())
+        }
+    });
+
+    let relay = relay(outbox, site, StubSubscriber { error: None }).await;
+    let outcome = relay.relay_due().await.expect("batch relayed");
+
+    assert_eq!(outcome, RelayOutcome { delivered: 2, retrying: 0, failed: 0 });
+    assert_eq!(delivered.lock().unwrap().len(), 2);
+    assert!(failed.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn failed_deliveries_back_off_until_the_last_attempt_which_alerts() {
+    let site = tenant();
+    let first_try = event(site.id, CONTACT_MESSAGE_RECEIVED, 1);
+    let seventh_try = event(site.id, CONTACT_MESSAGE_RECEIVED, OUTBOX_MAX_ATTEMPTS - 1);
+    let last_try = event(site.id, CONTACT_MESSAGE_RECEIVED, OUTBOX_MAX_ATTEMPTS);
+    let ids = [first_try.id, seventh_try.id, last_try.id];
+
+    let failed = MarkedFailed::default();
+    let outbox = outbox(vec![first_try, seventh_try, last_try], &failed);
+    let alerts = Arc::new(RecordedAlerts::default());
+    let relay = relay(outbox, site, StubSubscriber { error: Some("SMTP 421") })
+        .await
+        .with_alerts(alerts.clone());
+
+    let before = Utc::now();
+    let outcome = relay.relay_due().await.expect("batch relayed");
+    let after = Utc::now();
+
+    assert_eq!(outcome, RelayOutcome { delivered: 0, retrying: 2, failed: 1 });
+
+    let failed = failed.lock().unwrap();
+    assert_eq!(failed.iter().map(|(id, _)| *id).collect::<Vec<_>>(), ids);
+    let retry_at = |i: usize| failed[i].1.expect("retried");
+    assert!(retry_at(0) >= before + Duration::seconds(30) && retry_at(0) <= after + Duration::seconds(30));
+    // 30s doubled six times, still under the one hour cap
+    assert!(retry_at(1) >= before + Duration::seconds(1920) && retry_at(1) <= after + Duration::seconds(1920));
+    assert_eq!(failed[2].1, None);
+
+    let alerts = alerts.0.lock().unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].event, NotificationEvent::JobFailed);
+    assert!(alerts[0].body.contains(&ids[2].to_string()));
+    assert!(alerts[0].body.contains("SMTP 421"));
+}
+
+#[tokio::test]
+async fn events_without_a_subscriber_are_retried_not_delivered() {
+    let site = tenant();
+    let inquiry = event(site.id, HIRE_INQUIRY_RECEIVED, 1);
+    let inquiry_id = inquiry.id;
+
+    let failed = MarkedFailed::default();
+    let mut outbox = outbox(vec![inquiry], &failed);
+    outbox.expect_mark_delivered().never();
+
+    let relay = relay(outbox, site, StubSubscriber { error: None }).await;
+    let outcome = relay.relay_due().await.expect("batch relayed");
+
+    assert_eq!(outcome, RelayOutcome { delivered: 0, retrying: 1, failed: 0 });
+    let failed = failed.lock().unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0, inquiry_id);
+    assert!(failed[0].1.is_some());
+}
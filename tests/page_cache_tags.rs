@@ -0,0 +1,193 @@
+mod common;
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use common::{post, tenant, test_config};
+use portfolio_backend::{
+    cache::page_cache::{CachedPage, MockPageStore},
+    cdn::purge::{CdnPurger, MockCdnPurger},
+    entities::blog_post::{BlogPost, PostAuthor, PostByline},
+    errors::AppError,
+    repositories::{blog_post::MockBlogPostRepository, changelog::MockChangelogRepository},
+    use_cases::prewarm::{featured_posts_key, post_detail_key, post_list_key, rss_feed_key, ContentPrewarmer, DEFAULT_POSTS_PER_PAGE},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+type Prewarmer = ContentPrewarmer<Arc<MockBlogPostRepository>, MockChangelogRepository>;
+
+/// Pages and tag sets as Redis holds them, plus the keys written since the last `take_puts`
+#[derive(Default)]
+struct FakeCache {
+    pages: HashMap<String, CachedPage>,
+    tags: HashMap<String, HashSet<String>>,
+    puts: Vec<String>,
+}
+
+impl FakeCache {
+    fn take_puts(&mut self) -> HashSet<String> {
+        self.puts.drain(..).collect()
+    }
+}
+
+fn author(id: Uuid) -> PostAuthor {
+    PostAuthor { id, username: None, name: Some("Grace".to_string()), avatar_url: None }
+}
+
+fn blog_repo(posts: &Arc<Mutex<Vec<BlogPost>>>, bylines: Vec<PostByline>) -> MockBlogPostRepository {
+    let mut repo = MockBlogPostRepository::new();
+
+    let all = posts.clone();
+    repo.expect_get_blog_post_by_id().returning(move |_, id| {
+        all.lock().unwrap().iter().find(|p| p.id == *id).cloned()
+            .ok_or_else(|| AppError::NotFound("Blog post not found".to_string()))
+    });
+    let all = posts.clone();
+    repo.expect_get_all_blog_posts().returning(move |_, _, page, _| {
+        let published = all.lock().unwrap().iter().filter(|p| p.published).cloned().collect();
+        Ok(if page == 1 { published } else { Vec::new() })
+    });
+    let all = posts.clone();
+    repo.expect_get_recent_blog_posts()
+        .returning(move |_, _, _| Ok(all.lock().unwrap().iter().filter(|p| p.published).cloned().collect()));
+    repo.expect_get_featured_posts().returning(|_, _| Ok(Vec::new()));
+    repo.expect_get_post_series().returning(|_, _| Ok(None));
+    repo.expect_get_post_bylines().returning(move |_, ids| {
+        Ok(bylines.iter().filter(|b| ids.contains(&b.post_id)).map(|b| PostByline { post_id: b.post_id, author: b.author.clone() }).collect())
+    });
+    repo
+}
+
+fn prewarmer(
+    posts: &Arc<Mutex<Vec<BlogPost>>>,
+    bylines: Vec<PostByline>,
+    cache: &Arc<Mutex<FakeCache>>,
+    purger: Option<Arc<dyn CdnPurger>>,
+) -> Prewarmer {
+    let mut changelog = MockChangelogRepository::new();
+    changelog.expect_list_entries().returning(|_, _, _, _| Ok(Vec::new()));
+
+    let mut pages = MockPageStore::new();
+    let state = cache.clone();
+    pages.expect_put_page().returning(move |key, page, tags, _| {
+        let mut cache = state.lock().unwrap();
+        cache.pages.insert(key.to_string(), page.clone());
+        for tag in tags {
+            cache.tags.entry(tag.clone()).or_default().insert(key.to_string());
+        }
+        cache.puts.push(key.to_string());
+        Ok(())
+    });
+    let state = cache.clone();
+    pages.expect_invalidate_tags().returning(move |tags| {
+        let mut cache = state.lock().unwrap();
+        let keys: HashSet<String> = tags.iter().filter_map(|tag| cache.tags.remove(tag)).flatten().collect();
+        for key in &keys {
+            cache.pages.remove(key);
+        }
+        Ok(keys.into_iter().collect())
+    });
+
+    ContentPrewarmer::new(Arc::new(blog_repo(posts, bylines)), changelog, Some(Arc::new(pages)), purger, &test_config(json!({})))
+}
+
+#[tokio::test]
+async fn editing_a_post_renders_again_only_the_pages_showing_it() {
+    let site = tenant();
+    let (first, second, draft) = (post(site.id, "first", true), post(site.id, "second", true), post(site.id, "draft", false));
+    let posts = Arc::new(Mutex::new(vec![first.clone(), second.clone(), draft.clone()]));
+    let cache = Arc::new(Mutex::new(FakeCache::default()));
+    let prewarmer = prewarmer(&posts, Vec::new(), &cache, None);
+
+    let snapshot = posts.lock().unwrap().clone();
+    prewarmer.warm_posts(&site, snapshot).await.unwrap();
+    prewarmer.warm_listings(&site).await.unwrap();
+    cache.lock().unwrap().take_puts();
+
+    let list = post_list_key(&site, 1, DEFAULT_POSTS_PER_PAGE);
+    prewarmer.refresh_post(&site, second.id).await.unwrap();
+    assert_eq!(
+        cache.lock().unwrap().take_puts(),
+        HashSet::from([post_detail_key(&site, &second.id), list.clone(), rss_feed_key(&site)]),
+        "the featured list and the other post's page do not show it"
+    );
+
+    // A draft is on no public page
+    prewarmer.refresh_post(&site, draft.id).await.unwrap();
+    assert!(cache.lock().unwrap().take_puts().is_empty());
+    assert_eq!(cache.lock().unwrap().pages.len(), 5);
+
+    // Unpublished, the post leaves its own page and the ones listing it
+    posts.lock().unwrap()[1].published = false;
+    prewarmer.refresh_post(&site, second.id).await.unwrap();
+    let cache = cache.lock().unwrap();
+    assert!(!cache.pages.contains_key(&post_detail_key(&site, &second.id)));
+    assert!(!cache.pages[&list].body.contains(&second.id.to_string()));
+    assert!(cache.pages[&list].body.contains(&first.id.to_string()));
+}
+
+#[tokio::test]
+async fn author_and_changelog_changes_touch_their_own_pages() {
+    let site = tenant();
+    let grace = Uuid::new_v4();
+    let (first, second) = (post(site.id, "first", true), post(site.id, "second", true));
+    let bylines = vec![PostByline { post_id: second.id, author: author(grace) }];
+    let posts = Arc::new(Mutex::new(vec![first.clone(), second.clone()]));
+    let cache = Arc::new(Mutex::new(FakeCache::default()));
+    let prewarmer = prewarmer(&posts, bylines, &cache, None);
+
+    let snapshot = posts.lock().unwrap().clone();
+    prewarmer.warm_posts(&site, snapshot).await.unwrap();
+    prewarmer.warm_listings(&site).await.unwrap();
+    cache.lock().unwrap().take_puts();
+
+    // The feed shows no bylines
+    prewarmer.refresh_author(&site, grace).await.unwrap();
+    assert_eq!(
+        cache.lock().unwrap().take_puts(),
+        HashSet::from([post_detail_key(&site, &second.id), post_list_key(&site, 1, DEFAULT_POSTS_PER_PAGE)])
+    );
+
+    prewarmer.refresh_changelog(&site).await.unwrap();
+    assert_eq!(cache.lock().unwrap().take_puts(), HashSet::from([rss_feed_key(&site)]));
+}
+
+#[tokio::test]
+async fn publishing_renders_the_lists_and_feed_even_when_cold() {
+    let site = tenant();
+    let fresh = post(site.id, "fresh", true);
+    let posts = Arc::new(Mutex::new(vec![fresh.clone()]));
+    let cache = Arc::new(Mutex::new(FakeCache::default()));
+    let prewarmer = prewarmer(&posts, Vec::new(), &cache, None);
+
+    assert_eq!(prewarmer.warm_post(&site, fresh.id).await.unwrap(), 3);
+    assert_eq!(
+        cache.lock().unwrap().take_puts(),
+        HashSet::from([post_detail_key(&site, &fresh.id), post_list_key(&site, 1, DEFAULT_POSTS_PER_PAGE), rss_feed_key(&site)])
+    );
+}
+
+#[tokio::test]
+async fn startup_warmup_fills_the_cache_without_purging_the_cdn() {
+    let site = tenant();
+    let (first, second, draft) = (post(site.id, "first", true), post(site.id, "second", true), post(site.id, "draft", false));
+    let posts = Arc::new(Mutex::new(vec![first.clone(), second.clone(), draft]));
+    let cache = Arc::new(Mutex::new(FakeCache::default()));
+    // Any purge would fail the test: the mock expects none
+    let prewarmer = prewarmer(&posts, Vec::new(), &cache, Some(Arc::new(MockCdnPurger::new())));
+
+    assert_eq!(prewarmer.warm_startup(&site).await.unwrap(), 5);
+    assert_eq!(
+        cache.lock().unwrap().take_puts(),
+        HashSet::from([
+            post_detail_key(&site, &first.id),
+            post_detail_key(&site, &second.id),
+            post_list_key(&site, 1, DEFAULT_POSTS_PER_PAGE),
+            featured_posts_key(&site),
+            rss_feed_key(&site),
+        ])
+    );
+}
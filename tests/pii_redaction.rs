@@ -0,0 +1,243 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+use futures::TryStreamExt;
+use portfolio_backend::{
+    crypto::field_cipher::FieldCipher,
+    entities::{
+        contact_me::{ContactMeExportQuery, ContactMeMessage},
+        pii::{pii_field_names, Pii, PII_REGISTRY, REDACTED},
+        user::User,
+    },
+    errors::AppError,
+    geo::geoip::GeoLocator,
+    repositories::contact_me::MockContactMeRepository,
+    use_cases::{contact::ContactMeHandler, ingest::ContactIngestQueue},
+    utils::redact::{RedactingMakeWriter, Redactor},
+};
+use serde_json::json;
+use tracing::Level;
+use tracing_subscriber::fmt::MakeWriter;
+use uuid::Uuid;
+
+const EMAIL: &str = "ada.lovelace@example.com";
+const USERNAME: &str = "countess_ada";
+const DISPLAY_NAME: &str = "Augusta Ada King";
+const SENDER: &str = "Charles Babbage";
+const BODY: &str = "Could the analytical engine compose music?";
+const IP: &str = "203.0.113.77";
+const USER_AGENT: &str = "Mozilla/5.0 (AdaBrowser)";
+
+/// Every value below that must never reach a log line
+const PII_VALUES: &[&str] = &[EMAIL, USERNAME, DISPLAY_NAME, SENDER, BODY, IP, USER_AGENT];
+
+/// Collects everything a subscriber writes
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Capture {
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Capture {
+    type Writer = Capture;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn user() -> User {
+    let now = Utc::now();
+    User {
+        id: Uuid::new_v4(),
+        tenant_id: Uuid::new_v4(),
+        email: EMAIL.to_string(),
+        username: Some(USERNAME.to_string()),
+        password_hash: String::new(),
+        is_admin: false,
+        is_verified: true,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        deleted_by: None,
+        is_editor: false,
+        display_name: Some(DISPLAY_NAME.to_string()),
+        avatar_url: None,
+        is_system: false,
+    }
+}
+
+fn contact_message(tenant_id: Uuid, email: &str) -> ContactMeMessage {
+    ContactMeMessage {
+        id: Uuid::new_v4(),
+        tenant_id,
+        name: SENDER.to_string(),
+        email: email.to_string(),
+        subject: Some("Music".to_string()),
+        message: BODY.to_string(),
+        created_at: Utc::now(),
+        deleted_at: None,
+        is_spam: false,
+        country_code: Some("GB".to_string()),
+        region: None,
+        anonymized_at: None,
+    }
+}
+
+/// Logs personal data every way the codebase does: as event fields, inside
+/// `Debug` output and inside error messages, then reads messages back
+/// through a handler whose key cannot open them
+async fn log_pii_events() {
+    let user = user();
+    let contact = contact_message(user.tenant_id, EMAIL);
+
+    tracing::info!(user_id = %user.id, email = %user.email, username = ?user.username, "User signed in");
+    tracing::warn!(ip = IP, user_agent = USER_AGENT, path = "/wp-login.php", "Honeytoken path requested");
+    tracing::debug!(?user, "Loaded user");
+    tracing::debug!(?contact, "Contact message stored");
+    tracing::info!(name = %contact.name, "Contact message from a new sender");
+    let error = AppError::Conflict(format!("{} is already registered", user.email));
+    tracing::error!("Registration failed: {}", error);
+
+    let mut repo = MockContactMeRepository::new();
+    let sealed = contact_message(user.tenant_id, "enc:v1:lost:AAAA");
+    repo.expect_list_contact_messages().returning(move |_| Ok(vec![sealed.clone()]));
+    repo.expect_count_contact_messages().returning(|_| Ok(1));
+    let handler = ContactMeHandler::new(repo, ContactIngestQueue::new(8), GeoLocator::default(), FieldCipher::disabled());
+    let listed = handler.list_contact_messages(user.tenant_id).await.unwrap();
+    tracing::debug!(messages = ?listed.messages, "Listed contact messages");
+}
+
+/// Runs `log_pii_events` under `subscriber` and returns what it wrote
+async fn captured<S>(capture: &Capture, subscriber: S) -> String
+where
+    S: tracing::Subscriber + Send + Sync + 'static,
+{
+    let _guard = tracing::subscriber::set_default(subscriber);
+    log_pii_events().await;
+    capture.text()
+}
+
+#[tokio::test]
+async fn no_tagged_field_appears_in_captured_logs() {
+    let text = Capture::default();
+    let ansi = Capture::default();
+    let json = Capture::default();
+    let writer = |capture: &Capture| RedactingMakeWriter::new(capture.clone(), Redactor::with_defaults());
+
+    let logs = [
+        captured(&text, tracing_subscriber::fmt().with_max_level(Level::TRACE).with_ansi(false).with_writer(writer(&text)).finish()).await,
+        captured(&ansi, tracing_subscriber::fmt().with_max_level(Level::TRACE).with_ansi(true).compact().with_writer(writer(&ansi)).finish()).await,
+        captured(&json, tracing_subscriber::fmt().with_max_level(Level::TRACE).json().flatten_event(true).with_writer(writer(&json)).finish()).await,
+    ];
+
+    for log in &logs {
+        assert!(log.contains("Honeytoken path requested") && log.contains("/wp-login.php"), "{log}");
+        assert!(log.contains("Contact message field did not open"), "{log}");
+        assert!(log.contains(REDACTED), "{log}");
+        for value in PII_VALUES {
+            assert!(!log.contains(value), "{value} leaked into:\n{log}");
+        }
+    }
+
+    for line in logs[2].lines() {
+        let event: serde_json::Value = serde_json::from_str(line).expect("JSON log lines stay JSON");
+        assert!(event["message"].is_string());
+    }
+}
+
+#[test]
+fn tagged_entities_are_registered_and_blanked_field_by_field() {
+    assert!(PII_REGISTRY.iter().all(|(_, fields)| !fields.is_empty()));
+    let contact = PII_REGISTRY.iter().find(|(name, _)| *name == "ContactMeMessage").unwrap();
+    assert_eq!(contact.1, ContactMeMessage::PII_FIELDS);
+    for field in ["email", "username", "display_name", "name", "message", "ip", "user_agent"] {
+        assert!(pii_field_names().contains(&field), "{field}");
+    }
+
+    let mut user = user();
+    user.display_name = None;
+    let id = user.id;
+    user.redact_pii();
+    assert_eq!((user.email.as_str(), user.username.as_deref()), (REDACTED, Some(REDACTED)));
+    assert_eq!(user.display_name, None);
+    assert_eq!(user.id, id);
+
+    let mut contact = contact_message(Uuid::new_v4(), EMAIL);
+    contact.redact_pii();
+    assert_eq!([contact.name.as_str(), &contact.email, &contact.message], [REDACTED; 3]);
+    assert_eq!(contact.subject.as_deref(), Some("Music"));
+}
+
+#[test]
+fn redactor_drops_tagged_keys_and_keeps_the_rest() {
+    let redactor = Redactor::with_defaults();
+
+    let mut body = json!({
+        "email": EMAIL,
+        "username": USERNAME,
+        "new_password": "hunter2",
+        "subject": "Hello",
+        "nested": [{ "ip": IP, "note": format!("reach me at {EMAIL}") }],
+    });
+    redactor.redact_json(&mut body);
+    assert_eq!(body, json!({
+        "email": REDACTED,
+        "username": REDACTED,
+        "new_password": REDACTED,
+        "subject": "Hello",
+        "nested": [{ "ip": REDACTED, "note": format!("reach me at {REDACTED}") }],
+    }));
+
+    let text = redactor.redact_text(&format!(r#"ip={IP} path=/about name="{SENDER}" User {{ username: Some("{USERNAME}") }}"#));
+    assert_eq!(text, format!(r#"ip={REDACTED} path=/about name={REDACTED} User {{ username: Some("{REDACTED}") }}"#));
+
+    // Prose that merely mentions a tagged field is left readable
+    let prose = "Failed to send message: connection refused";
+    assert_eq!(redactor.redact_text(prose), prose);
+}
+
+#[tokio::test]
+async fn contact_exports_redact_personal_data_unless_asked_not_to() {
+    let tenant_id = Uuid::new_v4();
+    let mut repo = MockContactMeRepository::new();
+    let message = contact_message(tenant_id, EMAIL);
+    repo.expect_list_contact_messages_chunk()
+        .returning(move |_, _, _, _| Ok(vec![message.clone()]));
+    let handler = ContactMeHandler::new(
+        Arc::new(repo),
+        ContactIngestQueue::new(8),
+        GeoLocator::default(),
+        FieldCipher::disabled(),
+    );
+
+    let export = async |include_pii| {
+        let filter = ContactMeExportQuery { include_pii, ..Default::default() };
+        let chunks: Vec<_> = handler.export_contact_messages_csv(tenant_id, filter).unwrap().try_collect().await.unwrap();
+        String::from_utf8(chunks.concat()).unwrap()
+    };
+
+    let redacted = export(false).await;
+    assert!(redacted.contains(REDACTED) && redacted.contains("Music"));
+    assert!(PII_VALUES.iter().all(|value| !redacted.contains(value)), "{redacted}");
+
+    let full = export(true).await;
+    assert!(full.contains(EMAIL) && full.contains(SENDER) && full.contains(BODY));
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use std::{
+    io::{Cursor, Read},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use portfolio_backend::{
+    entities::blog_post::{BlogPost, PostRevision, PostRevisionSummary},
+    errors::AppError,
+    repositories::blog_post::MockBlogPostRepository,
+    storage::object::{MockObjectStorage, ObjectBody},
+    use_cases::post_bundle::PostBundler,
+};
+use uuid::Uuid;
+use zip::ZipArchive;
+
+fn post(content_markdown: &str) -> BlogPost {
+    BlogPost {
+        title: "Shipping a \"tiny\" CMS".to_string(),
+        excerpt: "How the blog is built".to_string(),
+        content_markdown: content_markdown.to_string(),
+        cover_image_url: Some("https://ada.dev/api/v1/storage/media/cover.png?token=abc".to_string()),
+        tags: Some(vec!["rust".to_string()]),
+        ..common::post(Uuid::new_v4(), "tiny-cms", false)
+    }
+}
+
+fn bundler(post: &BlogPost) -> PostBundler<MockBlogPostRepository> {
+    let mut repo = MockBlogPostRepository::new();
+    let current = post.clone();
+    repo.expect_get_blog_post_by_id().returning(move |_, _| Ok(current.clone()));
+
+    let mut revisions = Vec::new();
+    for (revision, text) in [(1, "First draft"), (2, "Second draft")] {
+        let mut snapshot = PostRevision::of(post, revision);
+        snapshot.content_markdown = text.to_string();
+        revisions.push(snapshot);
+    }
+    let listed = revisions.clone();
+    repo.expect_list_post_revisions().returning(move |_, _| {
+        Ok(listed
+            .iter()
+            .rev()
+            .map(|r| PostRevisionSummary { revision: r.revision, title: r.title.clone(), published: r.published, created_at: r.created_at })
+            .collect())
+    });
+    repo.expect_get_post_revision()
+        .returning(move |_, _, revision| Ok(revisions.iter().find(|r| r.revision == revision).cloned()));
+
+    let mut storage = MockObjectStorage::new();
+    storage.expect_get_stream().returning(|key| match key {
+        "media/cover.png" | "media/diagram.png" => Ok(ObjectBody {
+            size: 4,
+            stream: futures::stream::iter([Ok(Bytes::from_static(b"PNG!"))]).boxed(),
+        }),
+        _ => Err(AppError::NotFound("Object not found".to_string())),
+    });
+
+    PostBundler::new(repo, Some(Arc::new(storage)))
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+    let mut text = String::new();
+    archive.by_name(name).expect(name).read_to_string(&mut text).unwrap();
+    text
+}
+
+#[tokio::test]
+async fn bundle_holds_markdown_revisions_and_stored_images() {
+    let post = post(
+        "Intro\n\n![Diagram](/api/v1/storage/media/diagram.png)\n\n![Gone](media/missing.png)\n\n![Elsewhere](https://example.com/pic.png)",
+    );
+    let bundle = bundler(&post).bundle(post.tenant_id, &post.id.to_string()).await.unwrap();
+    assert_eq!(bundle.filename, "tiny-cms.zip");
+
+    let mut archive = ZipArchive::new(Cursor::new(bundle.body.to_vec())).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort();
+    assert_eq!(names, [
+        "images/01-cover.png",
+        "images/02-diagram.png",
+        "manifest.json",
+        "post.md",
+        "revisions/0001.md",
+        "revisions/0002.md",
+    ]);
+
+    let post_md = read_entry(&mut archive, "post.md");
+    assert!(post_md.starts_with("---\ntitle: \"Shipping a \\\"tiny\\\" CMS\"\nslug: \"tiny-cms\"\n"));
+    assert!(post_md.contains("published: false\n"));
+    assert!(post_md.contains("tags: [\"rust\"]\n"));
+    assert!(post_md.contains("cover_image: \"images/01-cover.png\"\n"));
+    assert!(post_md.contains("![Diagram](images/02-diagram.png)"));
+    assert!(post_md.contains("![Gone](media/missing.png)"));
+    assert!(post_md.contains("![Elsewhere](https://example.com/pic.png)"));
+
+    assert!(read_entry(&mut archive, "revisions/0002.md").contains("revision: 2\n"));
+    assert!(read_entry(&mut archive, "revisions/0001.md").ends_with("\n\nFirst draft\n"));
+    assert_eq!(read_entry(&mut archive, "images/02-diagram.png"), "PNG!");
+
+    let manifest: serde_json::Value = serde_json::from_str(&read_entry(&mut archive, "manifest.json")).unwrap();
+    assert_eq!(manifest["revisions"], 2);
+    assert_eq!(manifest["missing_images"], serde_json::json!(["media/missing.png"]));
+}
+
+#[tokio::test]
+async fn bundle_rejects_a_malformed_id() {
+    let post = post("Body");
+    let result = bundler(&post).bundle(post.tenant_id, "not-a-uuid").await;
+    assert!(matches!(result, Err(AppError::InvalidInput(_))));
+}
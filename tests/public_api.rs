@@ -0,0 +1,183 @@
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use common::test_config;
+use portfolio_backend::{
+    entities::{
+        about_me::AboutMeResponse,
+        blog_post::{BlogPost, PostAuthor, PostByline},
+        public_api::{PublicPostsQuery, API_VERSION_HEADER, PUBLIC_API_LIMIT_ROUTE, PUBLIC_API_VERSION},
+        tenant::Tenant,
+    },
+    errors::AppError,
+    limiter::rate_limiter::RateHybridLimiterStore,
+    middlewares::api_version::ApiVersion,
+    repositories::{about::MockAboutRepository, blog_post::MockBlogPostRepository},
+    use_cases::public_api::PublicApi,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn tenant(hostnames: &[&str]) -> Tenant {
+    Tenant {
+        slug: "site".to_string(),
+        name: "Site".to_string(),
+        hostnames: hostnames.iter().map(|h| h.to_string()).collect(),
+        ..common::tenant()
+    }
+}
+
+fn post(slug: &str, published: bool) -> BlogPost {
+    BlogPost {
+        author_id: Some(Uuid::new_v4()),
+        title: slug.replace('-', " "),
+        excerpt: "Excerpt".to_string(),
+        content_markdown: "# Heading\n\nBody <script>alert(1)</script>".to_string(),
+        tags: Some(vec!["rust".to_string()]),
+        seo_title: Some("SEO title".to_string()),
+        featured: true,
+        featured_order: Some(1),
+        ..common::post(Uuid::new_v4(), slug, published)
+    }
+}
+
+fn bylines(posts: &[Uuid]) -> Vec<PostByline> {
+    posts.iter().map(|post_id| PostByline {
+        post_id: *post_id,
+        author: PostAuthor {
+            id: Uuid::new_v4(),
+            username: Some("ada".to_string()),
+            name: Some("Ada".to_string()),
+            avatar_url: None,
+        },
+    }).collect()
+}
+
+#[tokio::test]
+async fn posts_are_served_as_stable_dtos_linking_to_the_site() {
+    let mut blog_repo = MockBlogPostRepository::new();
+    blog_repo.expect_get_all_blog_posts().returning(|_, published_only, page, per_page| {
+        assert!(published_only);
+        assert_eq!((page, per_page), (2, 50));
+        Ok(vec![post("hello-world", true), post("second-post", true)])
+    });
+    blog_repo.expect_get_post_bylines().returning(|_, ids| Ok(bylines(ids)));
+
+    let api = PublicApi::new(blog_repo, MockAboutRepository::new(), &test_config(json!({})));
+    let query = PublicPostsQuery { page: Some(2), per_page: Some(500) };
+    let list = api.posts(&tenant(&["blog.example.com"]), &query).await.unwrap();
+
+    assert_eq!((list.page, list.per_page), (2, 50));
+    assert_eq!(list.posts[0].url, "https://blog.example.com/blog/hello-world");
+    assert_eq!(list.posts[0].authors[0].name.as_deref(), Some("Ada"));
+
+    // Only the documented fields leave the server
+    let body = serde_json::to_value(&list.posts[0]).unwrap();
+    let mut fields: Vec<&str> = body.as_object().unwrap().keys().map(String::as_str).collect();
+    fields.sort_unstable();
+    assert_eq!(fields, vec![
+        "authors", "cover_image_url", "excerpt", "published_at", "slug", "tags", "title", "updated_at", "url",
+    ]);
+    assert_eq!(body["authors"][0], json!({ "name": "Ada", "username": "ada", "avatar_url": null }));
+}
+
+#[tokio::test]
+async fn drafts_and_missing_posts_are_not_found() {
+    let mut blog_repo = MockBlogPostRepository::new();
+    blog_repo.expect_get_blog_post_by_slug().returning(|_, slug| match slug {
+        "draft" => Ok(post("draft", false)),
+        "hello-world" => Ok(post("hello-world", true)),
+        _ => Err(AppError::NotFound("Blog post not found".to_string())),
+    });
+    blog_repo.expect_get_post_bylines().returning(|_, ids| Ok(bylines(ids)));
+
+    let config = test_config(json!({ "public_base_url": "https://fallback.example/" }));
+    let api = PublicApi::new(blog_repo, MockAboutRepository::new(), &config);
+    let site = tenant(&[]);
+
+    let found = api.post(&site, "hello-world").await.unwrap();
+    assert_eq!(found.summary.url, "https://fallback.example/blog/hello-world");
+    assert!(found.content_html.contains("<h1>Heading</h1>"));
+    assert!(!found.content_html.contains("<script>"));
+
+    assert!(matches!(api.post(&site, "draft").await, Err(AppError::NotFound(_))));
+    assert!(matches!(api.post(&site, "missing").await, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn about_keeps_only_the_rendered_page() {
+    let mut about_repo = MockAboutRepository::new();
+    about_repo.expect_get_current_about_me().returning(|_| Ok(AboutMeResponse {
+        id: Uuid::new_v4(),
+        revision: 3,
+        content_markdown: "Hi".to_string(),
+        content_html: "<p>Hi</p>".to_string(),
+        effective_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        deleted_at: None,
+    }));
+
+    let api = PublicApi::new(MockBlogPostRepository::new(), about_repo, &test_config(json!({})));
+    let about = serde_json::to_value(api.about(&tenant(&[])).await.unwrap()).unwrap();
+
+    assert_eq!(about["content_html"], "<p>Hi</p>");
+    assert_eq!(about["effective_date"], "2025-01-01");
+    assert!(about.get("revision").is_none());
+    assert!(about.get("content_markdown").is_none());
+}
+
+#[tokio::test]
+async fn public_api_clients_get_their_own_budget() {
+    let config = test_config(json!({
+        "rate_limit_burst": 1,
+        "rate_limit_per_minute": 1,
+        "public_api_burst": 3,
+        "public_api_per_minute": 3,
+    }));
+    let limiter = RateHybridLimiterStore::from_config(&config, None);
+
+    assert_eq!(limiter.route_size(PUBLIC_API_LIMIT_ROUTE), (3, 3));
+    assert_eq!(limiter.route_size("auth"), (1, 1));
+
+    // The burst, then the per-minute window once the bucket is empty
+    let allowed = |route| (0..10).take_while(|_| limiter.check(route, "203.0.113.7").is_ok()).count();
+    assert_eq!(allowed(PUBLIC_API_LIMIT_ROUTE), 6);
+
+    // Browser routes keep the defaults and are unaffected
+    assert_eq!(allowed("auth"), 2);
+}
+
+#[actix_web::test]
+async fn responses_carry_the_api_version_and_other_versions_are_refused() {
+    let app = test::init_service(
+        App::new().service(
+            web::scope("/public")
+                .wrap(ApiVersion::new(PUBLIC_API_VERSION))
+                .route("/ok", web::get().to(HttpResponse::Ok))
+                .route("/fail", web::get().to(HttpResponse::NotFound)),
+        ),
+    )
+    .await;
+
+    for (path, status) in [("/public/ok", 200), ("/public/fail", 404)] {
+        let res = test::call_service(&app, test::TestRequest::get().uri(path).to_request()).await;
+        assert_eq!(res.status().as_u16(), status);
+        assert_eq!(res.headers().get(API_VERSION_HEADER).unwrap(), PUBLIC_API_VERSION);
+    }
+
+    let pinned = test::TestRequest::get()
+        .uri("/public/ok")
+        .insert_header((API_VERSION_HEADER, PUBLIC_API_VERSION))
+        .to_request();
+    assert_eq!(test::call_service(&app, pinned).await.status().as_u16(), 200);
+
+    let future = test::TestRequest::get()
+        .uri("/public/ok")
+        .insert_header((API_VERSION_HEADER, "2"))
+        .to_request();
+    let res = test::call_service(&app, future).await;
+    assert_eq!(res.status().as_u16(), 400);
+    assert_eq!(res.headers().get(API_VERSION_HEADER).unwrap(), PUBLIC_API_VERSION);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use actix_web::{
+    test::{call_service, init_service, read_body, TestRequest},
+    web, App, HttpRequest, HttpResponse,
+};
+use portfolio_backend::{
+    entities::tenant::Tenant,
+    middlewares::forwarded_prefix::ForwardedPrefix,
+    utils::public_urls::{normalize_base_path, PublicUrls},
+};
+
+fn tenant(hostnames: &[&str]) -> Tenant {
+    Tenant { hostnames: hostnames.iter().map(|host| host.to_string()).collect(), ..common::tenant() }
+}
+
+/// Answers with the link to itself the request would be handed
+async fn self_link(req: HttpRequest) -> HttpResponse {
+    let urls = PublicUrls::new("https://example.com", "/api").for_request(&req);
+    HttpResponse::Ok().body(urls.api_root(req.path()))
+}
+
+#[test]
+fn site_links_skip_the_base_path_and_api_links_keep_it() {
+    let urls = PublicUrls::new("https://example.com/", "/api/");
+    let hosted = tenant(&["ada.dev"]);
+    let unhosted = tenant(&[]);
+
+    assert_eq!(urls.site(&hosted, "/blog/hello"), "https://ada.dev/blog/hello");
+    assert_eq!(urls.site(&unhosted, "/admin/blog/posts/hello"), "https://example.com/admin/blog/posts/hello");
+    assert_eq!(urls.api(&hosted, "/about.html"), "https://ada.dev/api/about.html");
+    assert_eq!(urls.api_root("/api/v1/storage/a.png"), "https://example.com/api/api/v1/storage/a.png");
+
+    // Nothing configured keeps the links relative, as before
+    assert_eq!(PublicUrls::default().site(&unhosted, "/blog/hello"), "/blog/hello");
+    assert_eq!(PublicUrls::default().api(&unhosted, "/about.html"), "/about.html");
+}
+
+#[test]
+fn base_paths_must_be_plain_paths() {
+    assert_eq!(normalize_base_path("/api/").as_deref(), Some("/api"));
+    assert_eq!(normalize_base_path("/sites/ada-v2").as_deref(), Some("/sites/ada-v2"));
+    assert_eq!(normalize_base_path(" / ").as_deref(), Some(""));
+
+    for invalid in ["api", "//evil.example", "/api//v1", "/../admin", "/api?x=1", "/api#top", "/a b"] {
+        assert_eq!(normalize_base_path(invalid), None, "{invalid}");
+    }
+}
+
+#[actix_web::test]
+async fn prefixed_requests_are_routed_and_link_back_under_their_prefix() {
+    let app = init_service(
+        App::new()
+            .wrap(ForwardedPrefix::new("/api"))
+            .route("/api/v1/ping", web::get().to(self_link))
+            .route("/about.html", web::get().to(self_link)),
+    )
+    .await;
+
+    let call = |uri: &'static str, prefix: Option<&'static str>| {
+        let app = &app;
+        async move {
+            let mut req = TestRequest::get().uri(uri);
+            if let Some(prefix) = prefix {
+                req = req.insert_header(("X-Forwarded-Prefix", prefix));
+            }
+            let res = call_service(app, req.to_request()).await;
+            (res.status().as_u16(), String::from_utf8(read_body(res).await.to_vec()).unwrap())
+        }
+    };
+
+    // The proxy kept the configured prefix
+    assert_eq!(call("/api/api/v1/ping", None).await, (200, "https://example.com/api/api/v1/ping".to_string()));
+    assert_eq!(call("/api/about.html?x=1", None).await, (200, "https://example.com/api/about.html".to_string()));
+    // ...or stripped it, and `/api` of `/api/v1` is not mistaken for it
+    assert_eq!(call("/api/v1/ping", None).await, (200, "https://example.com/api/api/v1/ping".to_string()));
+
+    // A forwarded prefix wins over the configured one, unless it is not a plain path
+    assert_eq!(
+        call("/sites/ada/about.html", Some("/sites/ada/")).await,
+        (200, "https://example.com/sites/ada/about.html".to_string())
+    );
+    assert_eq!(call("/about.html", Some("//evil.example")).await, (200, "https://example.com/api/about.html".to_string()));
+
+    assert_eq!(call("/elsewhere/about.html", None).await.0, 404);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use common::test_config;
+use portfolio_backend::{
+    entities::{
+        api_token::{hash_api_token, ApiToken, API_SCOPE_ANALYTICS},
+        notification::{Notification, NotificationEvent},
+        tenant::DEFAULT_TENANT_ID,
+        usage::{EmailUsage, StorageUsage, UsageReport},
+    },
+    repositories::{api_token::MockApiTokenRepository, usage::MockUsageRepository},
+    use_cases::{api_tokens::ApiTokens, dispatcher::NotificationSink, quota_alerts::QuotaAlerts},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone, Default)]
+struct Alerts(Arc<Mutex<Vec<(Uuid, Notification)>>>);
+
+#[async_trait]
+impl NotificationSink for Alerts {
+    async fn notify(&self, tenant_id: Uuid, notification: Notification) {
+        self.0.lock().unwrap().push((tenant_id, notification));
+    }
+}
+
+impl Alerts {
+    fn take(&self) -> Vec<(Uuid, Notification)> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// Claims kept like the `quota_alerts` table keeps them
+fn usage_repo() -> MockUsageRepository {
+    let claimed: Arc<Mutex<BTreeSet<(Uuid, String, i16)>>> = Arc::default();
+    let mut repo = MockUsageRepository::new();
+    repo.expect_claim_quota_alerts().returning(move |tenant_id, quota, _, thresholds| {
+        let mut claimed = claimed.lock().unwrap();
+        Ok(thresholds
+            .iter()
+            .copied()
+            .filter(|&threshold| claimed.insert((*tenant_id, quota.to_string(), threshold)))
+            .collect())
+    });
+    repo
+}
+
+fn report(sent: i64, stored_bytes: u64) -> UsageReport {
+    UsageReport {
+        tenant_id: DEFAULT_TENANT_ID,
+        collected_at: Utc::now(),
+        storage: Some(StorageUsage {
+            backend: "s3://sites".to_string(),
+            objects: 1,
+            bytes: 10,
+            by_prefix: Vec::new(),
+            total_objects: 10,
+            total_bytes: stored_bytes,
+        }),
+        database: None,
+        redis: None,
+        email: Some(EmailUsage { month: Utc::now().date_naive(), sent, failed: 0 }),
+        unavailable: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn each_threshold_is_raised_once_per_period() {
+    let alerts = Alerts::default();
+    let quotas = QuotaAlerts::new(
+        usage_repo(),
+        Arc::new(alerts.clone()),
+        &test_config(json!({ "email_monthly_quota": 1_000, "quota_alert_thresholds": "80,95" })),
+    );
+
+    assert_eq!(quotas.check(&[report(700, 0)], &[]).await.unwrap(), 0);
+
+    assert_eq!(quotas.check(&[report(850, 0)], &[]).await.unwrap(), 1);
+    let raised = alerts.take();
+    assert_eq!(raised[0].0, DEFAULT_TENANT_ID, "instance quotas go to the default site");
+    assert_eq!(raised[0].1.event, NotificationEvent::QuotaWarning);
+    assert_eq!(raised[0].1.title, "Emails sent this month at 85% of the quota");
+
+    // Still past 80%, but that was already raised this month
+    assert_eq!(quotas.check(&[report(900, 0)], &[]).await.unwrap(), 0);
+
+    assert_eq!(quotas.check(&[report(960, 0)], &[]).await.unwrap(), 1);
+    assert!(alerts.take()[0].1.body.contains("960 of 1000 (96%), past the 95% alert threshold"));
+}
+
+#[tokio::test]
+async fn a_jump_past_several_thresholds_raises_only_the_highest() {
+    let alerts = Alerts::default();
+    let quotas = QuotaAlerts::new(
+        usage_repo(),
+        Arc::new(alerts.clone()),
+        // Storage is not watched without a quota
+        &test_config(json!({ "storage_quota_bytes": 2_000, "quota_alert_thresholds": "95, 80%,80" })),
+    );
+
+    assert_eq!(quotas.instance_quotas(&[report(5_000, 1_950)]).len(), 1);
+    assert_eq!(quotas.check(&[report(5_000, 1_950)], &[]).await.unwrap(), 1);
+    let raised = alerts.take();
+    assert!(raised[0].1.title.starts_with("Bytes stored in s3://sites at 97%"));
+    assert!(raised[0].1.body.contains("past the 95% alert"));
+
+    // 80% was recorded with 95%, so dropping back under 95% raises nothing
+    assert_eq!(quotas.check(&[report(5_000, 1_700)], &[]).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn api_token_quotas_alert_the_tokens_site() {
+    let tenant_id = Uuid::new_v4();
+    let token = ApiToken {
+        id: Uuid::new_v4(),
+        tenant_id,
+        name: "partner".to_string(),
+        token_hash: hash_api_token("pk_alerts12345"),
+        token_prefix: "pk_alerts12".to_string(),
+        scopes: vec![API_SCOPE_ANALYTICS.to_string()],
+        rate_limit_per_minute: None,
+        monthly_quota: Some(100),
+        request_count: 0,
+        rejected_count: 0,
+        last_used_at: None,
+        created_by: None,
+        created_at: Utc::now(),
+        revoked_at: None,
+    };
+    let token_id = token.id;
+
+    let mut repo = MockApiTokenRepository::new();
+    repo.expect_add_api_token_usage().returning(|_| Ok(()));
+    repo.expect_list_active_api_tokens().returning(move || Ok(vec![token.clone()]));
+    repo.expect_count_quota_requests_since().returning(move |_| Ok(vec![(token_id, 90)]));
+    let tokens = ApiTokens::new(repo, None, &test_config(json!({})));
+    tokens.refresh().await.expect("refreshed");
+
+    let usage = tokens.quota_usage();
+    assert_eq!(usage.len(), 1);
+    assert_eq!((usage[0].0, usage[0].1.used, usage[0].1.limit), (tenant_id, 90, 100));
+
+    let alerts = Alerts::default();
+    let quotas = QuotaAlerts::new(usage_repo(), Arc::new(alerts.clone()), &test_config(json!({})));
+    assert_eq!(quotas.check(&[], &usage).await.unwrap(), 1);
+
+    let raised = alerts.take();
+    assert_eq!(raised[0].0, tenant_id);
+    assert_eq!(raised[0].1.title, "Requests of API token 'partner' this month at 90% of the quota");
+}
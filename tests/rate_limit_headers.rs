@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use actix_web::{
+    body::to_bytes,
+    http::header::{HeaderMap, RETRY_AFTER},
+};
+use portfolio_backend::{
+    entities::contact_me::{ContactMeFormInsert, NewContactMeForm},
+    errors::AppError,
+    limiter::{
+        headers::{too_many_requests, RateLimitStatus, RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET},
+        rate_limiter::RateHybridLimiterStore,
+    },
+    use_cases::ingest::ContactIngestQueue,
+};
+use uuid::Uuid;
+
+fn header(headers: &HeaderMap, name: impl actix_web::http::header::AsHeaderName) -> u64 {
+    headers.get(name).unwrap().to_str().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn limiter_counts_down_and_reports_when_to_retry() {
+    // A burst of 2, then 2 more through the minute window
+    let limiter = RateHybridLimiterStore::new(2.0, 2.0 / 60.0, Duration::from_secs(60), 2, Duration::from_secs(600));
+
+    let first = limiter.check("auth", "203.0.113.7").unwrap();
+    assert_eq!((first.limit, first.remaining), (4, 3));
+    assert!(first.reset_secs > 0 && first.reset_secs <= 30, "one token to refill: {}", first.reset_secs);
+
+    let remaining: Vec<u64> = (0..3).map(|_| limiter.check("auth", "203.0.113.7").unwrap().remaining).collect();
+    assert_eq!(remaining, vec![2, 1, 0]);
+
+    let refused = limiter.check("auth", "203.0.113.7").unwrap_err();
+    assert_eq!((refused.limit, refused.remaining), (4, 0));
+    assert!(refused.reset_secs >= 1);
+
+    // Other clients have their own budget
+    assert_eq!(limiter.check("auth", "198.51.100.2").unwrap().remaining, 3);
+}
+
+#[actix_web::test]
+async fn refusals_carry_ratelimit_headers_retry_after_and_the_scope() {
+    let status = RateLimitStatus::fixed_window(2, 3, 1_800);
+    let response = too_many_requests("contact:email", status, "Too many messages from this email address.");
+
+    assert_eq!(response.status().as_u16(), 429);
+    let headers = response.headers();
+    assert_eq!(header(headers, RATELIMIT_LIMIT), 2);
+    assert_eq!(header(headers, RATELIMIT_REMAINING), 0);
+    assert_eq!(header(headers, RATELIMIT_RESET), 1_800);
+    assert_eq!(header(headers, RETRY_AFTER), 1_800);
+
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "code": 429,
+            "error": "Too many requests",
+            "message": "Too many messages from this email address.",
+            "scope": "contact:email",
+            "limit": 2,
+            "remaining": 0,
+            "reset": 1_800,
+            "retry_after": 1_800,
+        })
+    );
+}
+
+#[actix_web::test]
+async fn allowed_responses_get_the_headers_and_refusals_never_say_retry_now() {
+    let mut headers = HeaderMap::new();
+    RateLimitStatus::fixed_window(5, 2, 600).apply(&mut headers);
+    assert_eq!(
+        (header(&headers, RATELIMIT_LIMIT), header(&headers, RATELIMIT_REMAINING), header(&headers, RATELIMIT_RESET)),
+        (5, 3, 600)
+    );
+    assert!(headers.get(RETRY_AFTER).is_none());
+
+    // A ban or counter about to expire still asks for at least a second
+    let response = too_many_requests("ip_ban", RateLimitStatus::exhausted(0, 0), "Too many requests");
+    assert_eq!(header(response.headers(), RETRY_AFTER), 1);
+    assert_eq!(header(response.headers(), RATELIMIT_RESET), 1);
+    assert_eq!(header(response.headers(), RATELIMIT_LIMIT), 0);
+}
+
+#[actix_web::test]
+async fn a_full_contact_queue_is_refused_like_any_other_limit() {
+    let message = || -> ContactMeFormInsert {
+        NewContactMeForm {
+            name: "Grace".to_string(),
+            email: "grace@example.com".to_string(),
+            subject: None,
+            message: "Are you available next month?".to_string(),
+        }
+        .try_into()
+        .unwrap()
+    };
+    let queue = ContactIngestQueue::new(2);
+    queue.push(Uuid::new_v4(), message()).unwrap();
+    queue.push(Uuid::new_v4(), message()).unwrap();
+
+    let Err(AppError::TooManyRequests(reason)) = queue.push(Uuid::new_v4(), message()) else {
+        panic!("a third message fits in a queue of two");
+    };
+    let response = too_many_requests("contact:queue", queue.shed_status(), &reason);
+
+    let headers = response.headers();
+    assert_eq!(header(headers, RATELIMIT_LIMIT), 2);
+    assert_eq!(header(headers, RATELIMIT_REMAINING), 0);
+    assert_eq!(header(headers, RETRY_AFTER), 1);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+    assert_eq!((body["scope"].as_str(), body["message"].as_str()), (Some("contact:queue"), Some(reason.as_str())));
+}
@@ -0,0 +1,54 @@
+use portfolio_backend::entities::reading::{
+    bucket_percent, PostReadingStats, ReadingDepthCount, ReadingProgressBatch, ReadingProgressEvent,
+};
+use uuid::Uuid;
+
+fn count(post_id: Uuid, max_percent: i16, readers: i64) -> ReadingDepthCount {
+    ReadingDepthCount { post_id, title: "Post".to_string(), slug: "post".to_string(), max_percent, readers }
+}
+
+#[test]
+fn percent_rounds_down_to_its_bucket() {
+    assert_eq!(bucket_percent(0), 0);
+    assert_eq!(bucket_percent(9), 0);
+    assert_eq!(bucket_percent(95), 90);
+    assert_eq!(bucket_percent(100), 100);
+    assert_eq!(bucket_percent(250), 100);
+}
+
+#[test]
+fn batch_keeps_the_furthest_point_per_post() {
+    let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+    let batch = ReadingProgressBatch {
+        session_id: "0123456789abcdef".to_string(),
+        events: vec![
+            ReadingProgressEvent { post_id: a, percent: 45 },
+            ReadingProgressEvent { post_id: b, percent: 10 },
+            ReadingProgressEvent { post_id: a, percent: 20 },
+        ],
+    };
+
+    let updates = batch.furthest_per_post();
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates.iter().find(|u| u.post_id == a).map(|u| u.percent), Some(40));
+    assert_eq!(updates.iter().find(|u| u.post_id == b).map(|u| u.percent), Some(10));
+}
+
+#[test]
+fn depth_counts_fold_into_completion_and_drop_off() {
+    let post = Uuid::new_v4();
+    let stats = PostReadingStats::from_counts(vec![count(post, 30, 2), count(post, 90, 1), count(post, 100, 1)]);
+
+    assert_eq!(stats.len(), 1);
+    let stats = &stats[0];
+    assert_eq!(stats.readers, 4);
+    assert_eq!(stats.completions, 2);
+    assert_eq!(stats.completion_rate, 0.5);
+    assert_eq!(stats.average_depth, 62.5);
+
+    let reached: Vec<(i16, i64)> = stats.depth.iter().map(|d| (d.percent, d.readers)).collect();
+    assert_eq!(reached.first(), Some(&(10, 4)));
+    assert_eq!(reached[2], (30, 4));
+    assert_eq!(reached[3], (40, 2));
+    assert_eq!(reached.last(), Some(&(100, 1)));
+}
@@ -0,0 +1,225 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use portfolio_backend::{
+    entities::redirect_rule::{
+        NewRedirectRuleRequest, RedirectRule, UpdateRedirectRuleRequest, REDIRECT_EXACT, REDIRECT_PATTERN,
+    },
+    errors::AppError,
+    repositories::redirect_rule::MockRedirectRuleRepository,
+    use_cases::redirects::RedirectRules,
+};
+use uuid::Uuid;
+
+type Store = Arc<Mutex<Vec<RedirectRule>>>;
+
+/// Mock repository over a shared list of rules, oldest first
+fn stored_repo(store: &Store) -> MockRedirectRuleRepository {
+    let mut repo = MockRedirectRuleRepository::new();
+
+    let rules = store.clone();
+    repo.expect_create_redirect_rule().returning(move |tenant_id, insert| {
+        let mut rules = rules.lock().unwrap();
+        if rules.iter().any(|r| r.tenant_id == *tenant_id && r.kind == insert.kind && r.source == insert.source) {
+            return Err(AppError::Conflict("A redirect rule for this source already exists".to_string()));
+        }
+        let now = Utc::now() + Duration::milliseconds(rules.len() as i64);
+        let rule = RedirectRule {
+            id: Uuid::new_v4(),
+            tenant_id: *tenant_id,
+            kind: insert.kind.clone(),
+            source: insert.source.clone(),
+            target: insert.target.clone(),
+            status_code: insert.status_code,
+            hits: 0,
+            last_hit_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        rules.push(rule.clone());
+        Ok(rule)
+    });
+
+    let rules = store.clone();
+    repo.expect_get_redirect_rule().returning(move |tenant_id, id| {
+        rules.lock().unwrap().iter()
+            .find(|r| r.tenant_id == *tenant_id && r.id == *id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Redirect rule not found".into()))
+    });
+
+    let rules = store.clone();
+    repo.expect_list_redirect_rules().returning(move |tenant_id| {
+        Ok(rules.lock().unwrap().iter().filter(|r| r.tenant_id == *tenant_id).cloned().collect())
+    });
+
+    let rules = store.clone();
+    repo.expect_list_all_redirect_rules().returning(move || Ok(rules.lock().unwrap().clone()));
+
+    let rules = store.clone();
+    repo.expect_update_redirect_rule().returning(move |tenant_id, id, insert| {
+        let mut rules = rules.lock().unwrap();
+        let rule = rules.iter_mut()
+            .find(|r| r.tenant_id == *tenant_id && r.id == *id)
+            .ok_or_else(|| AppError::NotFound("Redirect rule not found".into()))?;
+        rule.kind = insert.kind.clone();
+        rule.source = insert.source.clone();
+        rule.target = insert.target.clone();
+        rule.status_code = insert.status_code;
+        Ok(rule.clone())
+    });
+
+    let rules = store.clone();
+    repo.expect_delete_redirect_rule().returning(move |tenant_id, id| {
+        rules.lock().unwrap().retain(|r| !(r.tenant_id == *tenant_id && r.id == *id));
+        Ok(())
+    });
+
+    let rules = store.clone();
+    repo.expect_record_redirect_hits().returning(move |hits, at| {
+        for rule in rules.lock().unwrap().iter_mut() {
+            if let Some((_, count)) = hits.iter().find(|(id, _)| *id == rule.id) {
+                rule.hits += count;
+                rule.last_hit_at = Some(at);
+            }
+        }
+        Ok(())
+    });
+
+    repo
+}
+
+fn stored(store: &Store, id: Uuid) -> RedirectRule {
+    store.lock().unwrap().iter().find(|r| r.id == id).cloned().unwrap()
+}
+
+fn rule(kind: &str, source: &str, target: &str, status_code: Option<u16>) -> NewRedirectRuleRequest {
+    NewRedirectRuleRequest {
+        kind: Some(kind.to_string()),
+        source: source.to_string(),
+        target: target.to_string(),
+        status_code,
+    }
+}
+
+#[test]
+fn rules_are_validated_and_sources_lose_their_trailing_slash() {
+    let insert = rule(REDIRECT_EXACT, "/2019/05/hello-world/", "/blog/hello-world", None).to_insert().unwrap();
+    assert_eq!(insert.source, "/2019/05/hello-world");
+    assert_eq!(insert.status_code, 301);
+
+    let pattern = rule(REDIRECT_PATTERN, r"/\d{4}/\d{2}/([^/]+)/", "/blog/$1", Some(308)).to_insert().unwrap();
+    assert_eq!(pattern.source, r"/\d{4}/\d{2}/([^/]+)");
+
+    assert!(rule(REDIRECT_EXACT, "old-page", "/new", None).to_insert().is_err());
+    assert!(rule(REDIRECT_EXACT, "/old", "javascript:alert(1)", None).to_insert().is_err());
+    assert!(rule(REDIRECT_EXACT, "/old", "//evil.example", None).to_insert().is_err());
+    assert!(rule(REDIRECT_EXACT, "/old", "/old", None).to_insert().is_err());
+    assert!(rule(REDIRECT_EXACT, "/old", "/new", Some(200)).to_insert().is_err());
+    assert!(rule(REDIRECT_PATTERN, "/(unclosed", "/new", None).to_insert().is_err());
+    assert!(rule("glob", "/old", "/new", None).to_insert().is_err());
+    assert!(rule(REDIRECT_EXACT, "/old", "https://example.com/new", None).to_insert().is_ok());
+}
+
+#[tokio::test]
+async fn exact_rules_win_patterns_expand_captures_and_hits_are_counted() {
+    let tenant_id = Uuid::new_v4();
+    let store = Store::default();
+    let redirects = RedirectRules::new(stored_repo(&store));
+
+    let pattern = redirects
+        .create(tenant_id, rule(REDIRECT_PATTERN, r"/\d{4}/\d{2}/(?P<slug>[^/]+)", "/blog/${slug}", None))
+        .await
+        .unwrap();
+    let exact = redirects
+        .create(tenant_id, rule(REDIRECT_EXACT, "/2019/05/about-me", "/about.html", Some(302)))
+        .await
+        .unwrap();
+    redirects
+        .create(tenant_id, rule(REDIRECT_PATTERN, "/blog/(.*)", "/blog/$1", None))
+        .await
+        .unwrap();
+
+    let hit = redirects.resolve(&tenant_id, "/2019/05/about-me", "").unwrap();
+    assert_eq!((hit.location.as_str(), hit.status_code, hit.rule_id), ("/about.html", 302, exact.id));
+
+    let hit = redirects.resolve(&tenant_id, "/2020/11/rust-tips", "utm_source=old").unwrap();
+    assert_eq!((hit.location.as_str(), hit.status_code), ("/blog/rust-tips?utm_source=old", 301));
+    assert_eq!(hit.rule_id, pattern.id);
+
+    // Rewriting a path to itself would loop
+    assert!(redirects.resolve(&tenant_id, "/blog/rust-tips", "").is_none());
+    assert!(redirects.resolve(&tenant_id, "/2020/11/rust-tips/extra", "").is_none());
+    assert!(redirects.resolve(&Uuid::new_v4(), "/2019/05/about-me", "").is_none());
+
+    redirects.resolve(&tenant_id, "/2021/01/another", "");
+    assert_eq!(redirects.flush_hits().await.unwrap(), 2);
+    assert_eq!(redirects.flush_hits().await.unwrap(), 0);
+
+    let counted = stored(&store, pattern.id);
+    assert_eq!(counted.hits, 2);
+    assert!(counted.last_hit_at.is_some());
+    assert_eq!(stored(&store, exact.id).hits, 1);
+}
+
+#[tokio::test]
+async fn changes_apply_immediately_and_other_instances_catch_up_on_refresh() {
+    let tenant_id = Uuid::new_v4();
+    let store = Store::default();
+    let redirects = RedirectRules::new(stored_repo(&store));
+    let other_instance = RedirectRules::new(stored_repo(&store));
+
+    let created = redirects.create(tenant_id, rule(REDIRECT_EXACT, "/old", "/new", None)).await.unwrap();
+    assert!(redirects.resolve(&tenant_id, "/old", "").is_some());
+    assert!(other_instance.resolve(&tenant_id, "/old", "").is_none());
+    assert_eq!(other_instance.refresh().await.unwrap(), 1);
+    assert!(other_instance.resolve(&tenant_id, "/old", "").is_some());
+
+    let update = UpdateRedirectRuleRequest { target: Some("/newer".to_string()), ..Default::default() };
+    redirects.update(tenant_id, &created.id.to_string(), update).await.unwrap();
+    assert_eq!(redirects.resolve(&tenant_id, "/old", "").unwrap().location, "/newer");
+
+    let duplicate = redirects.create(tenant_id, rule(REDIRECT_EXACT, "/old/", "/elsewhere", None)).await;
+    assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+
+    redirects.delete(tenant_id, &created.id.to_string()).await.unwrap();
+    assert!(redirects.resolve(&tenant_id, "/old", "").is_none());
+}
+
+#[tokio::test]
+async fn csv_import_reports_conflicts_and_skipped_rows() {
+    let tenant_id = Uuid::new_v4();
+    let store = Store::default();
+    let redirects = RedirectRules::new(stored_repo(&store));
+    redirects.create(tenant_id, rule(REDIRECT_EXACT, "/existing", "/somewhere", None)).await.unwrap();
+
+    let csv = "source,target,status_code,kind\n\
+               /2019/05/hello-world/,/blog/hello-world,,\n\
+               /existing,/elsewhere,301,exact\n\
+               /2019/05/hello-world,/blog/duplicate,,\n\
+               /category/(.*),/blog?tag=$1,302,pattern\n\
+               /bad-status,/blog,200,\n\
+               not-a-path,/blog,,\n";
+
+    let dry_run = redirects.import(tenant_id, csv.as_bytes(), true).await.unwrap();
+    assert_eq!((dry_run.total_rows, dry_run.created), (6, 2));
+    assert_eq!(store.lock().unwrap().len(), 1);
+
+    let report = redirects.import(tenant_id, csv.as_bytes(), false).await.unwrap();
+    assert_eq!(report.created, 2);
+    let conflicts: Vec<_> = report.conflicts.iter().map(|c| c.row).collect();
+    assert_eq!(conflicts, vec![2, 3]);
+    let skipped: Vec<_> = report.skipped.iter().map(|s| (s.row, s.source.as_deref())).collect();
+    assert_eq!(skipped, vec![(5, Some("/bad-status")), (6, Some("not-a-path"))]);
+
+    assert_eq!(redirects.resolve(&tenant_id, "/2019/05/hello-world", "").unwrap().location, "/blog/hello-world");
+    assert_eq!(redirects.resolve(&tenant_id, "/category/rust", "").unwrap().location, "/blog?tag=rust");
+
+    let again = redirects.import(tenant_id, csv.as_bytes(), false).await.unwrap();
+    assert_eq!((again.created, again.conflicts.len()), (0, 4));
+
+    assert!(matches!(
+        redirects.import(tenant_id, b"from,to\n/a,/b\n", false).await,
+        Err(AppError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::io;
+
+use common::test_config;
+use portfolio_backend::{
+    cache::redis_pool::{is_failover_error, RedisEndpoints},
+    settings::RedisTopology,
+};
+use redis::{ErrorKind, RedisError};
+use serde_json::json;
+
+#[test]
+fn endpoints_follow_the_configured_topology() {
+    assert!(RedisEndpoints::from_config(&test_config(json!({}))).is_none());
+
+    let standalone = RedisEndpoints::from_config(&test_config(json!({ "redis_url": " redis://cache:6379 " }))).unwrap();
+    assert!(matches!(&standalone, RedisEndpoints::Standalone(url) if url == "redis://cache:6379"));
+    assert_eq!(standalone.topology(), RedisTopology::Standalone);
+
+    let sentinel = RedisEndpoints::from_config(&test_config(json!({
+        "redis_url": "redis://s1:26379, redis://s2:26379,,redis://s3:26379",
+        "redis_topology": "sentinel",
+        "redis_sentinel_master": "mymaster",
+    })))
+    .unwrap();
+    match sentinel {
+        RedisEndpoints::Sentinel { urls, master } => {
+            assert_eq!(urls, vec!["redis://s1:26379", "redis://s2:26379", "redis://s3:26379"]);
+            assert_eq!(master, "mymaster");
+        }
+        other => panic!("expected sentinel endpoints, got {:?}", other),
+    }
+
+    let cluster = RedisEndpoints::from_config(&test_config(json!({
+        "redis_url": "redis://n1:6379,redis://n2:6379",
+        "redis_topology": "cluster",
+    })))
+    .unwrap();
+    assert!(matches!(&cluster, RedisEndpoints::Cluster(urls) if urls.len() == 2));
+    assert_eq!(cluster.topology().as_str(), "cluster");
+}
+
+#[test]
+fn topology_names_parse_case_insensitively() {
+    assert_eq!(" Sentinel ".parse::<RedisTopology>().unwrap(), RedisTopology::Sentinel);
+    assert_eq!("CLUSTER".parse::<RedisTopology>().unwrap(), RedisTopology::Cluster);
+    assert!("replicated".parse::<RedisTopology>().is_err());
+}
+
+#[test]
+fn only_errors_a_failover_explains_are_retried() {
+    for kind in [ErrorKind::ReadOnly, ErrorKind::TryAgain, ErrorKind::ClusterDown, ErrorKind::MasterDown, ErrorKind::BusyLoadingError] {
+        assert!(is_failover_error(&RedisError::from((kind, "failover"))), "{:?}", kind);
+    }
+    assert!(is_failover_error(&RedisError::from(io::Error::from(io::ErrorKind::ConnectionRefused))));
+    assert!(is_failover_error(&RedisError::from(io::Error::from(io::ErrorKind::BrokenPipe))));
+
+    assert!(!is_failover_error(&RedisError::from((ErrorKind::TypeError, "WRONGTYPE"))));
+    assert!(!is_failover_error(&RedisError::from((ErrorKind::NoScriptError, "NOSCRIPT"))));
+    assert!(!is_failover_error(&RedisError::from((ErrorKind::AuthenticationFailed, "WRONGPASS"))));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, read_body, try_call_service, TestRequest},
+    web, App, HttpResponse,
+};
+use bytes::Bytes;
+use common::{mock_repositories, test_config};
+use futures::StreamExt;
+use portfolio_backend::{
+    db::postgres::{create_pool, without_statement_timeout},
+    middlewares::timeout::{RequestTimeout, RouteTimeouts},
+    AppState,
+};
+use serde_json::json;
+
+/// Admin gets a generous budget, blog a tight one, everything else the default
+fn state() -> web::Data<AppState> {
+    let config = test_config(json!({ "request_timeout_ms": 200, "route_timeouts": "admin=1000,blog=30" }));
+    web::Data::new(AppState::from_repositories(&config, mock_repositories()))
+}
+
+#[test]
+fn scopes_fall_back_to_the_default_budget() {
+    let config = test_config(json!({ "request_timeout_ms": 200, "route_timeouts": "admin=1000, blog=30, feed=soon" }));
+    let timeouts = RouteTimeouts::from_config(&config);
+
+    assert_eq!(timeouts.for_scope("admin"), Duration::from_millis(1000));
+    assert_eq!(timeouts.for_scope("blog"), Duration::from_millis(30));
+    assert_eq!(timeouts.for_scope("feed"), Duration::from_millis(200), "malformed entries are ignored");
+    assert_eq!(timeouts.for_scope("uses"), Duration::from_millis(200));
+}
+
+#[actix_web::test]
+async fn slow_handlers_are_cancelled_with_a_504() {
+    let finished = Arc::new(AtomicBool::new(false));
+    let slow = {
+        let finished = finished.clone();
+        move || {
+            let finished = finished.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                finished.store(true, Ordering::SeqCst);
+                HttpResponse::Ok().finish()
+            }
+        }
+    };
+    let app = init_service(
+        App::new()
+            .app_data(state())
+            .service(web::resource("/blog").wrap(RequestTimeout::scope("blog")).to(slow.clone()))
+            .service(web::resource("/admin").wrap(RequestTimeout::scope("admin")).to(slow)),
+    )
+    .await;
+
+    let err = try_call_service(&app, TestRequest::get().uri("/blog").to_request()).await.unwrap_err();
+    assert_eq!(err.as_response_error().status_code(), StatusCode::GATEWAY_TIMEOUT);
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(!finished.load(Ordering::SeqCst), "the handler was dropped, not left running");
+
+    // The same work fits in a scope with a larger budget
+    let res = call_service(&app, TestRequest::get().uri("/admin").to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(finished.load(Ordering::SeqCst));
+}
+
+#[actix_web::test]
+async fn streamed_bodies_are_not_cut_off() {
+    let app = init_service(App::new().app_data(state()).service(
+        web::resource("/blog").wrap(RequestTimeout::scope("blog")).to(|| async {
+            let chunks = futures::stream::iter(["a", "b", "c"]).then(|chunk| async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok::<_, actix_web::Error>(Bytes::from(chunk))
+            });
+            HttpResponse::Ok().streaming(chunks)
+        }),
+    ))
+    .await;
+
+    // Three 20 ms chunks outlast the 30 ms budget; only the head counts
+    let res = call_service(&app, TestRequest::get().uri("/blog").to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(read_body(res).await, Bytes::from_static(b"abc"));
+}
+
+#[tokio::test]
+#[ignore = "needs the migrated Postgres at DATABASE_URL (cargo test -- --ignored)"]
+async fn statement_timeout_spares_background_work_on_the_same_pool() {
+    let pool = create_pool(&std::env::var("DATABASE_URL").expect("DATABASE_URL"), Duration::from_millis(50))
+        .await
+        .unwrap();
+    let sleep = || sqlx::query("SELECT pg_sleep(0.2)").execute(&pool);
+
+    let err = sleep().await.unwrap_err();
+    assert_eq!(err.as_database_error().and_then(|e| e.code()).as_deref(), Some("57014"), "query_canceled");
+
+    assert!(without_statement_timeout(sleep()).await.is_ok());
+
+    // The connection the job used is bounded again for the next request
+    assert!(sleep().await.is_err());
+}
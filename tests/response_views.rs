@@ -0,0 +1,79 @@
+use chrono::Utc;
+use portfolio_backend::entities::{
+    blog_post::{AuthoredPost, BlogPost, PostAuthor},
+    contact_me::ContactMeResponse,
+    view::{ResponseView, Viewed},
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn authored_post() -> AuthoredPost {
+    let author = PostAuthor {
+        id: Uuid::new_v4(),
+        username: Some("ada".to_string()),
+        name: Some("Ada".to_string()),
+        avatar_url: None,
+    };
+    let post = BlogPost {
+        id: Uuid::new_v4(),
+        tenant_id: Uuid::new_v4(),
+        author_id: Some(author.id),
+        title: "Hello".to_string(),
+        slug: "hello".to_string(),
+        excerpt: "Hi".to_string(),
+        content_markdown: "Hi there".to_string(),
+        cover_image_url: None,
+        tags: None,
+        seo_title: None,
+        seo_description: None,
+        published: true,
+        published_at: Some(Utc::now()),
+        updated_at: Utc::now(),
+        created_at: Utc::now(),
+        deleted_at: None,
+        featured: false,
+        featured_order: None,
+    };
+
+    AuthoredPost { post, author: Some(author.clone()), authors: vec![author], series: None, title_variant: None }
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+#[test]
+fn public_view_leaves_out_admin_only_fields() {
+    let json = to_value(&Viewed::public(vec![authored_post()]));
+    let post = &json[0];
+
+    assert_eq!(post["slug"], "hello");
+    assert!(post.get("deleted_at").is_none());
+    assert!(post.get("author_id").is_none());
+    assert!(post["author"].get("id").is_none());
+    assert_eq!(post["authors"][0]["username"], "ada");
+    assert!(post.get("tenant_id").is_none());
+}
+
+#[test]
+fn admin_view_and_plain_serialization_keep_every_field() {
+    let post = authored_post();
+
+    for json in [to_value(&Viewed::admin(&post)), to_value(&post)] {
+        assert!(json["deleted_at"].is_null());
+        assert_eq!(json["author_id"], post.post.author_id.unwrap().to_string());
+        assert!(json["author"]["id"].is_string());
+    }
+}
+
+#[test]
+fn views_nest_and_restore_the_enclosing_one() {
+    let response = ContactMeResponse { message: "Thanks".to_string(), id: Uuid::new_v4() };
+
+    let nested = to_value(&Viewed::public((Viewed::admin(&response), &response)));
+    assert!(nested[0]["id"].is_string());
+    assert!(nested[1].get("id").is_none());
+
+    assert_eq!(ResponseView::for_admin(false), ResponseView::Public);
+    assert!(to_value(&response)["id"].is_string());
+}
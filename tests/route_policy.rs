@@ -0,0 +1,125 @@
+mod common;
+
+use actix_web::{
+    test::{call_service, init_service, read_body, TestRequest},
+    App, HttpResponse,
+};
+use futures_util::future::ready;
+use common::test_config;
+use portfolio_backend::{
+    entities::token::{Claims, TokenType},
+    middlewares::auth::{is_authorized, RoutePolicy},
+    routes::configure_routes,
+    settings::RouteAccess,
+    utils::route_pattern::RoutePattern,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn claims(admin: bool, editor: bool) -> Claims {
+    Claims {
+        sub: Uuid::new_v4().to_string(),
+        email: "ada@example.com".to_string(),
+        admin,
+        editor,
+        verified: true,
+        exp: 0,
+        token_type: TokenType::Access,
+        iat: 0,
+        tid: Uuid::new_v4(),
+        claims_version: 0,
+        iss: None,
+        aud: None,
+    }
+}
+
+#[actix_web::test]
+async fn built_in_rules_cover_the_real_route_tree() {
+    // Answers whether the app routes the path, without running any handler
+    let app = init_service(App::new().configure(configure_routes).wrap_fn(|req, _| {
+        let routed = req.resource_map().has_resource(req.path());
+        ready(Ok(req.into_response(HttpResponse::Ok().body(routed.to_string()))))
+    }))
+    .await;
+    let policy = RoutePolicy::from_config(&test_config(json!({})));
+    let id = Uuid::new_v4();
+
+    let cases = [
+        ("GET", "/".to_string(), RouteAccess::Public),
+        ("GET", "/about.html".to_string(), RouteAccess::Public),
+        ("POST", "/api/v1/auth/login".to_string(), RouteAccess::Public),
+        ("POST", "/api/v1/auth/refresh-token".to_string(), RouteAccess::Public),
+        ("GET", "/api/v1/auth/password-reset".to_string(), RouteAccess::Public),
+        ("POST", "/api/v1/auth/logout".to_string(), RouteAccess::User),
+        ("POST", "/api/v1/gate".to_string(), RouteAccess::Public),
+        ("GET", "/api/v1/blog/posts".to_string(), RouteAccess::Public),
+        ("POST", "/api/v1/blog/posts".to_string(), RouteAccess::User),
+        ("GET", "/api/v1/blog/posts/recent/5".to_string(), RouteAccess::Public),
+        ("GET", format!("/api/v1/blog/posts/{id}/shares"), RouteAccess::Public),
+        ("PATCH", format!("/api/v1/blog/posts/{id}"), RouteAccess::User),
+        ("GET", "/api/v1/blog/authors/ada/posts".to_string(), RouteAccess::Public),
+        ("GET", "/api/v1/blog/series/engines".to_string(), RouteAccess::Public),
+        ("POST", format!("/api/v1/blog/admin/posts/{id}/lock"), RouteAccess::Editor),
+        ("POST", "/api/v1/blog/admin/posts/scan".to_string(), RouteAccess::Editor),
+        ("GET", "/api/v1/blog/admin/calendar".to_string(), RouteAccess::Admin),
+        ("DELETE", format!("/api/v1/blog/admin/posts/{id}/authors/{id}"), RouteAccess::Admin),
+        ("GET", "/api/v1/users/me".to_string(), RouteAccess::User),
+        ("POST", "/api/v1/bookmarks".to_string(), RouteAccess::User),
+        ("GET", "/api/v1/out".to_string(), RouteAccess::Public),
+        ("GET", "/api/v1/out/qr.png".to_string(), RouteAccess::Admin),
+        ("GET", "/api/v1/public/posts/hello".to_string(), RouteAccess::Public),
+        ("GET", "/api/v1/storage/exports/ada/manifest.json".to_string(), RouteAccess::Public),
+        ("POST", "/api/v1/admin/deploy-hook".to_string(), RouteAccess::Public),
+        ("GET", "/api/v1/admin/audit-log".to_string(), RouteAccess::Admin),
+        ("PUT", format!("/api/v1/admin/users/{id}/editor"), RouteAccess::Admin),
+    ];
+
+    for (method, path, access) in cases {
+        let res = call_service(&app, TestRequest::default().uri(&path).to_request()).await;
+        assert_eq!(read_body(res).await, "true", "{path} is not a route");
+        assert_eq!(policy.access(&path, method), access, "{method} {path}");
+    }
+}
+
+#[test]
+fn configured_rules_come_first_and_patterns_take_parameters() {
+    let policy = RoutePolicy::from_config(&test_config(json!({
+        "route_policy": { "rules": [
+            { "pattern": "/api/v1/blog/posts/{post_id}/shares", "methods": ["get"], "access": "user" },
+            { "pattern": "/api/v1/auth/register", "access": "admin" },
+        ]},
+    })));
+
+    assert_eq!(policy.access("/api/v1/blog/posts/42/shares", "GET"), RouteAccess::User);
+    assert_eq!(policy.access("/api/v1/blog/posts/42", "GET"), RouteAccess::Public);
+    assert_eq!(policy.access("/api/v1/auth/register", "POST"), RouteAccess::Admin);
+
+    // Preflights and decoys pass whatever the rules say
+    assert_eq!(policy.access("/api/v1/auth/register", "OPTIONS"), RouteAccess::Public);
+    assert_eq!(policy.access("/wp-login.php", "POST"), RouteAccess::Public);
+
+    // Unlisted API routes need a user; reads elsewhere reach the redirect fallback
+    assert_eq!(policy.access("/api/v2/anything", "GET"), RouteAccess::User);
+    assert_eq!(policy.access("/old/blog/url", "GET"), RouteAccess::Public);
+    assert_eq!(policy.access("/old/blog/url", "POST"), RouteAccess::User);
+
+    let pattern = RoutePattern::parse("/api/v1/blog/{section}/posts/**").unwrap();
+    assert!(pattern.matches("/api/v1/blog/admin/posts"));
+    assert!(pattern.matches("/api/v1/blog/admin/posts/1/authors/2"));
+    assert!(!pattern.matches("/api/v1/blog/posts"));
+    for invalid in ["api/v1", "/api/{", "/api/{}", "/api/v{1}", "/api/{a}b"] {
+        assert_eq!(RoutePattern::parse(invalid), None, "{invalid}");
+    }
+}
+
+#[test]
+fn roles_are_checked_against_the_required_access() {
+    let (user, editor, admin) = (claims(false, false), claims(false, true), claims(true, false));
+
+    assert!(is_authorized(RouteAccess::User, &user));
+    assert!(!is_authorized(RouteAccess::Editor, &user));
+    assert!(is_authorized(RouteAccess::Editor, &editor));
+    assert!(is_authorized(RouteAccess::Editor, &admin));
+    assert!(!is_authorized(RouteAccess::Admin, &editor));
+    assert!(is_authorized(RouteAccess::Admin, &admin));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use portfolio_backend::{
+    entities::{
+        about_me::AboutMeResponse,
+        blog_post::{BlogPost, CalendarStatus},
+        schedule_preview::PreviewAtQuery,
+    },
+    errors::AppError,
+    repositories::{about::MockAboutRepository, blog_post::MockBlogPostRepository},
+    use_cases::schedule_preview::SchedulePreview,
+};
+use uuid::Uuid;
+
+fn post(title: &str, published: bool, published_at: Option<DateTime<Utc>>) -> BlogPost {
+    BlogPost {
+        title: title.to_string(),
+        content_markdown: String::new(),
+        published_at,
+        ..common::post(Uuid::new_v4(), &title.to_lowercase(), published)
+    }
+}
+
+fn about(revision: i32, effective_date: NaiveDate) -> AboutMeResponse {
+    AboutMeResponse {
+        id: Uuid::from_u128(revision as u128),
+        revision,
+        content_markdown: format!("Revision {}", revision),
+        content_html: format!("<p>Revision {}</p>", revision),
+        effective_date,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        deleted_at: None,
+    }
+}
+
+#[tokio::test]
+async fn preview_lists_what_goes_live_by_then() {
+    let now = Utc::now();
+    let at = now + Duration::days(10);
+    let switch_date = now.date_naive() + Duration::days(5);
+
+    let posts = vec![
+        post("Live", true, Some(now - Duration::days(3))),
+        post("Soon", true, Some(now + Duration::days(2))),
+        post("Later", true, Some(now + Duration::days(20))),
+        post("Draft", false, None),
+    ];
+    let mut blog_repo = MockBlogPostRepository::new();
+    blog_repo.expect_get_all_blog_posts().returning(move |_, published_only, page, _| {
+        assert!(!published_only);
+        Ok(if page == 1 { posts.clone() } else { Vec::new() })
+    });
+
+    let mut about_repo = MockAboutRepository::new();
+    about_repo.expect_get_about_me_as_of().returning(move |_, date| {
+        Ok(if date >= switch_date { about(2, switch_date) } else { about(1, switch_date - Duration::days(35)) })
+    });
+
+    let previewer = SchedulePreview::new(blog_repo, about_repo);
+    let preview = previewer
+        .preview_at(Uuid::new_v4(), PreviewAtQuery { timestamp: at })
+        .await
+        .expect("preview");
+
+    let about = preview.about.expect("about in effect");
+    assert_eq!(about.about.revision, 2);
+    assert!(about.changed);
+
+    let live: Vec<&str> = preview.live_posts.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(live, ["Soon", "Live"]);
+    assert_eq!(preview.live_posts[0].status_now, CalendarStatus::Scheduled);
+    assert_eq!(preview.newly_live, 1);
+
+    let waiting: Vec<&str> = preview.scheduled_posts.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(waiting, ["Later"]);
+
+    let tomorrow = previewer
+        .preview_at(Uuid::new_v4(), PreviewAtQuery { timestamp: now + Duration::days(1) })
+        .await
+        .expect("preview");
+    let about = tomorrow.about.expect("about in effect");
+    assert_eq!(about.about.revision, 1);
+    assert!(!about.changed);
+    assert_eq!(tomorrow.newly_live, 0);
+}
+
+#[tokio::test]
+async fn preview_without_about_content_or_in_the_past() {
+    let mut blog_repo = MockBlogPostRepository::new();
+    blog_repo.expect_get_all_blog_posts().returning(|_, _, _, _| Ok(Vec::new()));
+    let mut about_repo = MockAboutRepository::new();
+    about_repo
+        .expect_get_about_me_as_of()
+        .returning(|_, _| Err(AppError::NotFound("About me not found".to_string())));
+    let preview = SchedulePreview::new(blog_repo, about_repo);
+
+    let empty = preview
+        .preview_at(Uuid::new_v4(), PreviewAtQuery { timestamp: Utc::now() + Duration::hours(1) })
+        .await
+        .expect("preview");
+    assert!(empty.about.is_none());
+    assert!(empty.live_posts.is_empty());
+
+    assert!(matches!(
+        preview.preview_at(Uuid::new_v4(), PreviewAtQuery { timestamp: Utc::now() - Duration::hours(1) }).await,
+        Err(AppError::InvalidInput(_))
+    ));
+}
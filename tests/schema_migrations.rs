@@ -0,0 +1,102 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use portfolio_backend::{
+    db::migrations::bundled_migrations,
+    entities::schema::{AppliedMigration, BundledMigration, SchemaStatus},
+    errors::AppError,
+    repositories::schema::MockSchemaRepository,
+    use_cases::schema::SchemaMigrations,
+};
+
+fn bundled(versions: &[i64]) -> Vec<BundledMigration> {
+    versions
+        .iter()
+        .map(|&version| BundledMigration {
+            version,
+            description: format!("migration {}", version),
+            checksum: version.to_be_bytes().to_vec(),
+        })
+        .collect()
+}
+
+fn applied(version: i64) -> AppliedMigration {
+    AppliedMigration { version, success: true, checksum: version.to_be_bytes().to_vec() }
+}
+
+#[test]
+fn bundled_migrations_are_the_up_migrations_in_order() {
+    let migrations = bundled_migrations();
+    assert!(!migrations.is_empty());
+    assert!(migrations.windows(2).all(|pair| pair[0].version < pair[1].version));
+}
+
+#[test]
+fn comparison_separates_pending_failed_modified_and_unknown_migrations() {
+    let status = SchemaStatus::compare(&bundled(&[1, 2, 3]), &[applied(1), applied(2), applied(3)]);
+    assert!(status.is_up_to_date() && !status.has_drift());
+    assert_eq!((status.version, status.bundled_version), (Some(3), Some(3)));
+
+    let status = SchemaStatus::compare(&bundled(&[1, 2, 3, 4]), &[applied(1), applied(2)]);
+    assert_eq!(status.pending, vec![3, 4]);
+    assert!(!status.is_up_to_date() && !status.has_drift());
+    assert_eq!(status.version, Some(2));
+
+    let mut broken = applied(3);
+    broken.success = false;
+    let mut edited = applied(2);
+    edited.checksum = b"edited".to_vec();
+    let status = SchemaStatus::compare(&bundled(&[1, 2, 3]), &[applied(1), edited, broken]);
+    assert_eq!((status.failed.clone(), status.modified.clone()), (vec![3], vec![2]));
+    assert!(!status.is_up_to_date() && status.has_drift());
+    assert_eq!(status.version, Some(2));
+
+    // The old release during a blue/green deploy: up to date, but behind
+    let status = SchemaStatus::compare(&bundled(&[1, 2]), &[applied(1), applied(2), applied(3)]);
+    assert_eq!(status.unknown, vec![3]);
+    assert!(status.is_up_to_date() && status.has_drift());
+    assert!(status.summary().contains("does not know"));
+
+    let status = SchemaStatus::compare(&bundled(&[1]), &[]);
+    assert_eq!((status.version, status.pending.clone()), (None, vec![1]));
+}
+
+#[tokio::test]
+async fn waiting_returns_once_another_instance_has_migrated() {
+    let reads = Arc::new(AtomicUsize::new(0));
+    let counter = reads.clone();
+    let mut repo = MockSchemaRepository::new();
+    repo.expect_applied_migrations().returning(move || match counter.fetch_add(1, Ordering::SeqCst) {
+        0 => Err(AppError::ServiceUnavailable("Database unavailable".into())),
+        1 => Ok(vec![applied(1)]),
+        _ => Ok(vec![applied(1), applied(2)]),
+    });
+
+    let schema = SchemaMigrations::new(repo, bundled(&[1, 2]));
+    let status = schema
+        .wait_until_up_to_date(Duration::from_secs(5), Duration::from_millis(10))
+        .await
+        .unwrap();
+
+    assert_eq!(status.version, Some(2));
+    assert_eq!(reads.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn waiting_gives_up_after_the_timeout() {
+    let mut repo = MockSchemaRepository::new();
+    repo.expect_applied_migrations().returning(|| Ok(vec![applied(1)]));
+
+    let schema = SchemaMigrations::new(repo, bundled(&[1, 2]));
+    let result = schema.wait_until_up_to_date(Duration::from_millis(50), Duration::from_millis(10)).await;
+
+    match result {
+        Err(AppError::ServiceUnavailable(msg)) => assert!(msg.contains("pending")),
+        other => panic!("expected a timeout, got {:?}", other.map(|s| s.summary())),
+    }
+}
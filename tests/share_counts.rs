@@ -0,0 +1,149 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::Utc;
+use common::{post, tenant, test_config};
+use portfolio_backend::{
+    entities::{
+        blog_post::BlogPost,
+        share_count::{PostShareCount, ShareCountInsert, SHARE_NETWORK_HACKERNEWS, SHARE_NETWORK_REDDIT},
+    },
+    errors::AppError,
+    links::shares::{MockShareCountProvider, ShareCountProvider, ShareFetchError},
+    repositories::{blog_post::MockBlogPostRepository, share_count::MockShareCountRepository},
+    use_cases::share_counts::ShareCounts,
+};
+use serde_json::json;
+
+type Recorded = Arc<Mutex<Vec<ShareCountInsert>>>;
+
+fn blog_repo(posts: Vec<BlogPost>) -> MockBlogPostRepository {
+    let mut repo = MockBlogPostRepository::new();
+    let published: Vec<BlogPost> = posts.iter().filter(|p| p.published).cloned().collect();
+    repo.expect_get_all_blog_posts()
+        .returning(move |_, _, _, _| Ok(published.clone()));
+    repo.expect_get_blog_post_by_id().returning(move |_, id| {
+        posts
+            .iter()
+            .find(|p| p.id == *id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Blog post not found".to_string()))
+    });
+    repo
+}
+
+fn recording_repo(recorded: &Recorded) -> MockShareCountRepository {
+    let mut repo = MockShareCountRepository::new();
+    let recorded = recorded.clone();
+    repo.expect_record_share_counts().returning(move |_, counts, _| {
+        recorded.lock().unwrap().extend_from_slice(counts);
+        Ok(())
+    });
+    repo
+}
+
+fn provider(
+    network: &'static str,
+    calls: &Arc<Mutex<Vec<String>>>,
+    answer: impl Fn(&str) -> Result<u64, ShareFetchError> + Send + Sync + 'static,
+) -> Arc<dyn ShareCountProvider> {
+    let mut provider = MockShareCountProvider::new();
+    provider.expect_network().return_const(network);
+    let calls = calls.clone();
+    provider.expect_share_count().returning(move |url| {
+        calls.lock().unwrap().push(url.to_string());
+        answer(url)
+    });
+    Arc::new(provider)
+}
+
+#[tokio::test]
+async fn refresh_counts_published_posts_and_pauses_a_rate_limited_network() {
+    let site = tenant();
+    let posts = vec![post(site.id, "first", true), post(site.id, "second", true), post(site.id, "draft", false)];
+    let recorded = Recorded::default();
+    let hn_calls = Arc::new(Mutex::new(Vec::new()));
+    let reddit_calls = Arc::new(Mutex::new(Vec::new()));
+
+    let providers = vec![
+        provider(SHARE_NETWORK_HACKERNEWS, &hn_calls, |url| Ok(if url.ends_with("/first") { 12 } else { 3 })),
+        provider(SHARE_NETWORK_REDDIT, &reddit_calls, |_| {
+            Err(ShareFetchError::RateLimited { retry_after: Some(Duration::from_secs(600)) })
+        }),
+    ];
+    let share_counts = ShareCounts::new(recording_repo(&recorded), blog_repo(posts.clone()), providers, &test_config(json!({ "share_count_request_spacing_ms": 0 })));
+
+    let run = share_counts.refresh_tenant(&site).await.unwrap();
+    assert_eq!(run.posts, 2);
+    assert_eq!(run.fetched, 2);
+    assert_eq!(run.skipped_networks, [SHARE_NETWORK_REDDIT]);
+    assert_eq!(*hn_calls.lock().unwrap(), ["https://ada.dev/blog/first", "https://ada.dev/blog/second"]);
+    assert_eq!(recorded.lock().unwrap().as_slice(), [
+        ShareCountInsert { post_id: posts[0].id, network: SHARE_NETWORK_HACKERNEWS, shares: 12 },
+        ShareCountInsert { post_id: posts[1].id, network: SHARE_NETWORK_HACKERNEWS, shares: 3 },
+    ]);
+
+    // Reddit is left alone until its retry-after is over
+    share_counts.refresh_tenant(&site).await.unwrap();
+    assert_eq!(reddit_calls.lock().unwrap().len(), 1);
+    assert_eq!(hn_calls.lock().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn unavailable_network_keeps_its_previous_counts() {
+    let site = tenant();
+    let posts = vec![post(site.id, "first", true), post(site.id, "second", true)];
+    let recorded = Recorded::default();
+    let calls = Arc::new(Mutex::new(Vec::new()));
+
+    let providers = vec![provider(SHARE_NETWORK_REDDIT, &calls, |_| {
+        Err(ShareFetchError::Unavailable("Connection failed".to_string()))
+    })];
+    let share_counts = ShareCounts::new(recording_repo(&recorded), blog_repo(posts), providers, &test_config(json!({ "share_count_request_spacing_ms": 0 })));
+
+    let run = share_counts.refresh_tenant(&site).await.unwrap();
+    assert_eq!(run.fetched, 0);
+    assert_eq!(run.skipped_networks, [SHARE_NETWORK_REDDIT]);
+    assert_eq!(calls.lock().unwrap().len(), 1, "a network that is down is not asked about every post");
+    assert!(recorded.lock().unwrap().is_empty());
+
+    // Unlike a rate limit, the next run tries again
+    share_counts.refresh_tenant(&site).await.unwrap();
+    assert_eq!(calls.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn post_share_counts_are_summed_for_published_posts_only() {
+    let site = tenant();
+    let published = post(site.id, "first", true);
+    let draft = post(site.id, "draft", false);
+
+    let mut repo = MockShareCountRepository::new();
+    let (tenant_id, post_id) = (site.id, published.id);
+    repo.expect_get_post_share_counts().returning(move |_, _| {
+        Ok([(SHARE_NETWORK_HACKERNEWS, 12), (SHARE_NETWORK_REDDIT, 5)]
+            .into_iter()
+            .map(|(network, shares)| PostShareCount {
+                tenant_id,
+                post_id,
+                network: network.to_string(),
+                shares,
+                fetched_at: Utc::now(),
+            })
+            .collect())
+    });
+    let share_counts = ShareCounts::new(repo, blog_repo(vec![published.clone(), draft.clone()]), Vec::new(), &test_config(json!({ "share_count_request_spacing_ms": 0 })));
+
+    let counts = share_counts.post_share_counts(site.id, &published.id.to_string()).await.unwrap();
+    assert_eq!(counts.total, 17);
+    assert_eq!(counts.networks.len(), 2);
+
+    let hidden = share_counts.post_share_counts(site.id, &draft.id.to_string()).await;
+    assert!(matches!(hidden, Err(AppError::NotFound(_))));
+    let malformed = share_counts.post_share_counts(site.id, "not-a-uuid").await;
+    assert!(matches!(malformed, Err(AppError::InvalidInput(_))));
+}
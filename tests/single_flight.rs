@@ -0,0 +1,64 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use portfolio_backend::cache::single_flight::SingleFlight;
+use tokio::sync::Notify;
+
+#[tokio::test]
+async fn concurrent_calls_share_one_computation() {
+    let flights: SingleFlight<Result<u32, String>> = SingleFlight::new();
+    let runs = Arc::new(AtomicUsize::new(0));
+    let release = Arc::new(Notify::new());
+
+    let calls: Vec<_> = (0..8)
+        .map(|_| {
+            let (flights, runs, release) = (flights.clone(), Arc::clone(&runs), Arc::clone(&release));
+            tokio::spawn(async move {
+                flights
+                    .run("page:blog:posts:1:10", || async move {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        release.notified().await;
+                        Ok(42)
+                    })
+                    .await
+            })
+        })
+        .collect();
+
+    while flights.in_flight() == 0 || runs.load(Ordering::SeqCst) == 0 {
+        tokio::task::yield_now().await;
+    }
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    release.notify_one();
+
+    for call in calls {
+        assert_eq!(call.await.unwrap(), Ok(42));
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert_eq!(flights.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn finished_results_are_not_kept() {
+    let flights: SingleFlight<Result<u32, String>> = SingleFlight::new();
+
+    assert_eq!(flights.run("feed", || async { Err("database down".to_string()) }).await, Err("database down".to_string()));
+    assert_eq!(flights.run("feed", || async { Ok(7) }).await, Ok(7));
+    assert_eq!(flights.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn waiter_takes_over_when_the_first_caller_goes_away() {
+    let flights: SingleFlight<u32> = SingleFlight::new();
+
+    let abandoned = flights.run("post", std::future::pending::<u32>);
+    assert!(tokio::time::timeout(Duration::from_millis(10), abandoned).await.is_err());
+
+    assert_eq!(flights.run("post", || async { 3 }).await, 3);
+    assert_eq!(flights.in_flight(), 0);
+}
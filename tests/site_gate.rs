@@ -0,0 +1,95 @@
+mod common;
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, TestRequest},
+    web, App, HttpResponse,
+};
+use common::{mock_repositories, test_config};
+use portfolio_backend::{
+    auth::site_gate::{SITE_GATE_COOKIE, SITE_GATE_HEADER},
+    constants::READYZ_PATH,
+    middlewares::site_gate::SiteGateMiddleware,
+    AppState,
+};
+use serde_json::json;
+
+/// Every request reaches a stub handler unless the gate turns it away
+macro_rules! gated_app {
+    ($state:expr) => {
+        init_service(
+            App::new()
+                .app_data($state)
+                .default_service(web::to(HttpResponse::Ok))
+                .wrap(SiteGateMiddleware),
+        )
+        .await
+    };
+}
+
+fn locked_state() -> web::Data<AppState> {
+    let config = test_config(json!({ "site_gate_passphrase": "open sesame" }));
+    web::Data::new(AppState::from_repositories(&config, mock_repositories()))
+}
+
+#[actix_web::test]
+async fn public_content_needs_a_gate_token_while_the_site_is_locked() {
+    let app = gated_app!(locked_state());
+
+    let gated = [
+        ("GET", "/api/v1/blog/posts/hello-world"),
+        ("GET", "/api/v1/uses"),
+        ("POST", "/api/v1/contact-me"),
+        ("GET", "/about"),
+    ];
+    for (method, path) in gated {
+        let req = TestRequest::default().method(method.parse().unwrap()).uri(path).to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED, "{method} {path}");
+    }
+}
+
+#[actix_web::test]
+async fn the_gate_itself_auth_and_machine_routes_stay_open() {
+    let app = gated_app!(locked_state());
+
+    let open = [
+        ("GET", "/"),
+        ("POST", "/api/v1/gate"),
+        ("POST", "/api/v1/auth/login"),
+        ("POST", "/api/v1/admin/deploy-hook"),
+        ("GET", READYZ_PATH),
+        ("GET", "/wp-login.php"),
+        ("GET", "/api/v1/storage/exports/a.json"),
+        ("OPTIONS", "/api/v1/blog/posts/hello-world"),
+        // Signed-in routes are left to AuthMiddleware
+        ("GET", "/api/v1/admin/feature-flags"),
+        ("PUT", "/api/v1/blog/admin/posts/1"),
+    ];
+    for (method, path) in open {
+        let req = TestRequest::default().method(method.parse().unwrap()).uri(path).to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::OK, "{method} {path}");
+    }
+}
+
+#[actix_web::test]
+async fn a_valid_gate_token_unlocks_public_content() {
+    let state = locked_state();
+    let token = state.site_gate.as_ref().unwrap().unlock("open sesame").unwrap();
+    let app = gated_app!(state);
+
+    let by_cookie = TestRequest::get()
+        .uri("/api/v1/uses")
+        .cookie(actix_web::cookie::Cookie::new(SITE_GATE_COOKIE, token.clone()))
+        .to_request();
+    assert_eq!(call_service(&app, by_cookie).await.status(), StatusCode::OK);
+    let by_header = TestRequest::get().uri("/api/v1/uses").insert_header((SITE_GATE_HEADER, token)).to_request();
+    assert_eq!(call_service(&app, by_header).await.status(), StatusCode::OK);
+
+    let forged = TestRequest::get().uri("/api/v1/uses").insert_header((SITE_GATE_HEADER, "not-a-token")).to_request();
+    assert_eq!(call_service(&app, forged).await.status(), StatusCode::UNAUTHORIZED);
+
+    // Without a passphrase the site is public
+    let open = gated_app!(web::Data::new(AppState::from_repositories(&test_config(json!({})), mock_repositories())));
+    let req = TestRequest::get().uri("/api/v1/uses").to_request();
+    assert_eq!(call_service(&open, req).await.status(), StatusCode::OK);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use chrono::{NaiveDate, Utc};
+use portfolio_backend::{
+    entities::{about_me::AboutMeResponse, app_setting::{AppSetting, SITE_PAGES}, tenant::Tenant},
+    errors::AppError,
+    repositories::{about::MockAboutRepository, app_settings::MockAppSettingsRepository},
+    use_cases::{pages::SitePages, settings::RuntimeSettings},
+    utils::public_urls::PublicUrls,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn tenant(hostnames: Vec<String>) -> Tenant {
+    Tenant { name: "Ada <Lovelace>".to_string(), hostnames, ..common::tenant() }
+}
+
+fn about() -> AboutMeResponse {
+    AboutMeResponse {
+        id: Uuid::new_v4(),
+        revision: 2,
+        content_markdown: "I write *engines*.".to_string(),
+        content_html: "<p>I write <em>engines</em>.</p>".to_string(),
+        effective_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+        created_at: Utc::now(),
+        updated_at: "2026-03-04T10:00:00Z".parse().unwrap(),
+        deleted_at: None,
+    }
+}
+
+async fn settings_with(tenant_id: Uuid, value: Option<serde_json::Value>) -> RuntimeSettings<MockAppSettingsRepository> {
+    let mut repo = MockAppSettingsRepository::new();
+    repo.expect_list_all_settings().returning(move || {
+        Ok(value.iter().map(|value| AppSetting {
+            tenant_id,
+            key: SITE_PAGES.to_string(),
+            value: value.clone(),
+            updated_at: Utc::now(),
+        }).collect())
+    });
+    let settings = RuntimeSettings::new(repo);
+    settings.refresh().await.expect("settings loaded");
+    settings
+}
+
+#[tokio::test]
+async fn about_page_uses_the_tenant_name_and_default_nav() {
+    let tenant = tenant(vec!["ada.dev".to_string()]);
+    let mut about_repo = MockAboutRepository::new();
+    about_repo.expect_get_current_about_me().returning(|_| Ok(about()));
+    let pages = SitePages::new(about_repo, settings_with(tenant.id, None).await);
+
+    let html = pages.about(&tenant, &PublicUrls::default()).await.expect("rendered");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>About &middot; Ada &#60;Lovelace&#62;</title>"));
+    assert!(html.contains("<p>I write <em>engines</em>.</p>"));
+    assert!(html.contains(r#"<link rel="canonical" href="https://ada.dev/about.html">"#));
+    assert!(html.contains(r#"<a href="/about.html">About</a>"#));
+    assert!(html.contains("Last updated 2026-03-04"));
+}
+
+#[tokio::test]
+async fn about_page_title_and_nav_come_from_site_settings() {
+    let tenant = tenant(Vec::new());
+    let mut about_repo = MockAboutRepository::new();
+    about_repo.expect_get_current_about_me().returning(|_| Ok(about()));
+    let settings = settings_with(tenant.id, Some(json!({
+        "title": "Ada's notes",
+        "nav": [{ "label": "Projects", "url": "https://github.com/ada" }],
+    })))
+    .await;
+    let pages = SitePages::new(about_repo, settings);
+
+    let html = pages.about(&tenant, &PublicUrls::new("https://example.com/", "")).await.expect("rendered");
+    assert!(html.contains("<strong>Ada&#39;s notes</strong>"));
+    assert!(html.contains(r#"<a href="https://github.com/ada">Projects</a>"#));
+    assert!(!html.contains(r#"href="/about.html""#));
+    assert!(html.contains(r#"href="https://example.com/about.html""#));
+}
+
+#[tokio::test]
+async fn about_page_is_not_found_until_published() {
+    let tenant = tenant(Vec::new());
+    let mut about_repo = MockAboutRepository::new();
+    about_repo
+        .expect_get_current_about_me()
+        .returning(|_| Err(AppError::NotFound("no rows".to_string())));
+    let pages = SitePages::new(about_repo, settings_with(tenant.id, None).await);
+
+    assert!(matches!(pages.about(&tenant, &PublicUrls::default()).await, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn site_pages_setting_rejects_unsafe_nav_links() {
+    let settings = settings_with(Uuid::new_v4(), None).await;
+
+    for value in [
+        json!({ "nav": [{ "label": "x", "url": "javascript:alert(1)" }] }),
+        json!({ "nav": [{ "label": "x", "url": "//evil.example" }] }),
+        json!({ "title": "" }),
+        json!({ "nav": "home" }),
+    ] {
+        assert!(settings.update_setting(Uuid::new_v4(), SITE_PAGES, value).await.is_err());
+    }
+}
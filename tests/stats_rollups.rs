@@ -0,0 +1,143 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use portfolio_backend::{
+    entities::{
+        geo::{CountryCount, GeoCountrySummary},
+        stats_rollup::{RollupWindow, COUNTRY_SOURCE_ATTACKS, COUNTRY_SOURCE_CLICKS, ROLLUP_DAY},
+    },
+    repositories::{
+        sqlx_repo::SqlxStatsRollupRepo,
+        stats_rollup::{rollup_months, StatsRollupRepository},
+    },
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn count(source: &str, country_code: Option<&str>, count: i64) -> CountryCount {
+    CountryCount { source: source.to_string(), country_code: country_code.map(str::to_string), count }
+}
+
+#[test]
+fn last_days_reads_closed_days_from_rollups_and_today_live() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 10, 15, 30, 0).unwrap();
+    let window = RollupWindow::last_days(7, now);
+
+    assert_eq!(window.period, ROLLUP_DAY);
+    assert_eq!((window.from, window.to), (date(2025, 3, 3), date(2025, 3, 10)));
+    assert_eq!(window.live_from, Some(Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap()));
+    assert_eq!(window.since(), Utc.with_ymd_and_hms(2025, 3, 3, 0, 0, 0).unwrap());
+}
+
+#[test]
+fn rolled_up_days_cover_whole_months() {
+    assert_eq!(rollup_months(date(2025, 1, 30), date(2025, 2, 2)), (date(2025, 1, 1), date(2025, 3, 1)));
+    assert_eq!(rollup_months(date(2025, 2, 1), date(2025, 3, 1)), (date(2025, 2, 1), date(2025, 3, 1)));
+    assert_eq!(rollup_months(date(2024, 12, 31), date(2025, 1, 1)), (date(2024, 12, 1), date(2025, 1, 1)));
+}
+
+#[test]
+fn country_counts_fold_busiest_first_and_unknown_last() {
+    let countries = GeoCountrySummary::from_counts(vec![
+        count(COUNTRY_SOURCE_CLICKS, Some("NG"), 3),
+        count(COUNTRY_SOURCE_ATTACKS, None, 50),
+        count(COUNTRY_SOURCE_CLICKS, Some("DE"), 2),
+        count(COUNTRY_SOURCE_ATTACKS, Some("DE"), 4),
+        count(COUNTRY_SOURCE_CLICKS, Some("NG"), 1),
+    ]);
+
+    let rows: Vec<(Option<&str>, i64, i64)> = countries
+        .iter()
+        .map(|c| (c.country_code.as_deref(), c.clicks, c.attacks))
+        .collect();
+    assert_eq!(rows, vec![(Some("DE"), 2, 4), (Some("NG"), 4, 0), (None, 0, 50)]);
+}
+
+async fn click(pool: &PgPool, tenant_id: Uuid, target_url: &str, source_path: &str, country: &str, at: DateTime<Utc>) {
+    let host = target_url.split('/').nth(2).unwrap();
+    sqlx::query(
+        "INSERT INTO outbound_clicks (tenant_id, target_url, target_host, source_path, country_code, clicked_at)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(tenant_id)
+    .bind(target_url)
+    .bind(host)
+    .bind(source_path)
+    .bind(country)
+    .bind(at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Every rollup row of the tenant, in a stable order
+async fn rollups(pool: &PgPool, tenant_id: Uuid) -> Vec<(String, NaiveDate, String, i64)> {
+    sqlx::query_as(
+        "SELECT period, period_start, target_url, clicks FROM outbound_click_rollups WHERE tenant_id = $1
+         UNION ALL
+         SELECT period, period_start, source_path, clicks FROM referrer_rollups WHERE tenant_id = $1
+         UNION ALL
+         SELECT period, period_start, source || ':' || country_code, count FROM country_rollups WHERE tenant_id = $1
+         ORDER BY 1, 2, 3",
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "needs the migrated Postgres at DATABASE_URL (cargo test -- --ignored)"]
+async fn backfills_are_idempotent_and_keep_days_whose_raw_rows_were_purged() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").expect("DATABASE_URL")).await.unwrap();
+    let tenant_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tenants (id, slug, name) VALUES ($1, $2, 'Rollups')")
+        .bind(tenant_id)
+        .bind(format!("rollups-{}", tenant_id.simple()))
+        .execute(&pool)
+        .await
+        .unwrap();
+    let repo = SqlxStatsRollupRepo::new(pool.clone());
+    let at = |d: u32| Utc.with_ymd_and_hms(2025, 1, d, 12, 0, 0).unwrap();
+
+    click(&pool, tenant_id, "https://github.com/ada", "/blog/engines", "NG", at(10)).await;
+    click(&pool, tenant_id, "https://github.com/ada", "/blog/engines", "NG", at(10)).await;
+    click(&pool, tenant_id, "https://mastodon.social/@ada", "/about", "DE", at(10)).await;
+    click(&pool, tenant_id, "https://github.com/ada", "/blog/engines", "NG", at(11)).await;
+
+    let written = repo.roll_up(&tenant_id, date(2025, 1, 1), date(2025, 2, 1)).await.unwrap();
+    let first = rollups(&pool, tenant_id).await;
+    assert!(written > 0);
+
+    // Running the backfill again replaces rows instead of adding to them
+    assert_eq!(repo.roll_up(&tenant_id, date(2025, 1, 1), date(2025, 2, 1)).await.unwrap(), written);
+    assert_eq!(rollups(&pool, tenant_id).await, first);
+
+    // Retention purges the 10th; a later run only touches the days still in the raw table
+    sqlx::query("DELETE FROM outbound_clicks WHERE tenant_id = $1 AND clicked_at < $2")
+        .bind(tenant_id)
+        .bind(at(11))
+        .execute(&pool)
+        .await
+        .unwrap();
+    click(&pool, tenant_id, "https://github.com/ada", "/about", "DE", at(11)).await;
+    repo.roll_up(&tenant_id, date(2025, 1, 1), date(2025, 2, 1)).await.unwrap();
+
+    let january = RollupWindow::month(date(2025, 1, 1));
+    assert_eq!(repo.count_clicks(&tenant_id, &january).await.unwrap(), 5);
+    let pages: Vec<(String, i64)> = repo
+        .referring_pages(&tenant_id, &january, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|page| (page.source_path, page.clicks))
+        .collect();
+    assert_eq!(pages, vec![("/blog/engines".to_string(), 3), ("/about".to_string(), 2)]);
+
+    let tenth = RollupWindow { period: ROLLUP_DAY, from: date(2025, 1, 10), to: date(2025, 1, 11), live_from: None };
+    assert_eq!(repo.count_clicks(&tenant_id, &tenth).await.unwrap(), 3);
+
+    sqlx::query("DELETE FROM tenants WHERE id = $1").bind(tenant_id).execute(&pool).await.unwrap();
+}
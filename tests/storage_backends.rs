@@ -0,0 +1,159 @@
+mod common;
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use common::test_config;
+use portfolio_backend::{
+    errors::AppError,
+    storage::{
+        links::StorageLinks,
+        local::LocalStorage,
+        object::{validate_key, ObjectStorage, StoredObject},
+        s3::S3Storage,
+    },
+};
+use serde_json::json;
+use testcontainers_modules::{
+    minio::MinIO,
+    testcontainers::{
+        core::{CmdWaitFor, ExecCommand},
+        runners::AsyncRunner,
+    },
+};
+
+const BUCKET: &str = "portfolio-test";
+
+async fn read_all(storage: &dyn ObjectStorage, key: &str) -> Vec<u8> {
+    let object = storage.get_stream(key).await.expect("get_stream");
+    let chunks: Vec<Bytes> = object.stream.try_collect().await.expect("stream");
+    let body = chunks.concat();
+    assert_eq!(body.len() as u64, object.size);
+    body
+}
+
+/// The contract every backend must meet
+async fn exercise(storage: &dyn ObjectStorage) {
+    let key = "exports/default/content-test.json";
+
+    assert!(!storage.exists(key).await.unwrap());
+    assert!(matches!(storage.get_stream(key).await, Err(AppError::NotFound(_))));
+
+    storage.put(key, Bytes::from_static(b"{\"v\":1}"), "application/json").await.unwrap();
+    assert!(storage.exists(key).await.unwrap());
+    assert_eq!(read_all(storage, key).await, b"{\"v\":1}");
+
+    // Overwrites replace the whole object
+    storage.put(key, Bytes::from_static(b"{}"), "application/json").await.unwrap();
+    assert_eq!(read_all(storage, key).await, b"{}");
+
+    // Large enough to come back in more than one chunk
+    let big = vec![7u8; 200 * 1024];
+    storage.put("exports/big.bin", Bytes::from(big.clone()), "application/octet-stream").await.unwrap();
+    assert_eq!(read_all(storage, "exports/big.bin").await, big);
+
+    let mut listed = storage.list("exports").await.unwrap();
+    listed.sort_by(|a, b| a.key.cmp(&b.key));
+    assert_eq!(
+        listed,
+        vec![
+            StoredObject { key: "exports/big.bin".to_string(), size: big.len() as u64 },
+            StoredObject { key: key.to_string(), size: 2 },
+        ]
+    );
+    assert_eq!(storage.list("").await.unwrap().len(), 2);
+    assert!(storage.list("exports/missing").await.unwrap().is_empty());
+
+    assert!(storage.presign(key, Duration::from_secs(60)).await.unwrap().contains(key));
+
+    storage.delete(key).await.unwrap();
+    assert!(!storage.exists(key).await.unwrap());
+    storage.delete(key).await.expect("deleting a missing object is not an error");
+
+    for bad in ["", "/abs", "../escape", "a/../../b", "a//b", "spaces here", "a\\b"] {
+        assert!(storage.put(bad, Bytes::new(), "text/plain").await.is_err(), "accepted {:?}", bad);
+    }
+}
+
+#[test]
+fn keys_cannot_leave_the_storage_root() {
+    assert!(validate_key("exports/tenant-1/content_2025.json").is_ok());
+    for bad in ["", ".", "..", "a/./b", "a/..", "/a", "a/", "ä", "a?b", "a#b"] {
+        assert!(validate_key(bad).is_err(), "accepted {:?}", bad);
+    }
+}
+
+#[tokio::test]
+async fn local_backend_round_trips_objects() {
+    let root = std::env::temp_dir().join(format!("portfolio-storage-{}", uuid::Uuid::new_v4()));
+    let config = test_config(json!({}));
+    let storage = LocalStorage::new(&root, StorageLinks::new(&config));
+
+    exercise(&storage).await;
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[tokio::test]
+async fn local_links_only_open_their_own_key() {
+    let config = test_config(json!({ "public_base_url": "https://example.com/" }));
+    let links = StorageLinks::new(&config);
+
+    let url = links.sign("exports/a.json", Duration::from_secs(60)).unwrap();
+    assert!(url.starts_with("https://example.com/api/v1/storage/exports/a.json?token="));
+
+    let token = url.split("token=").nth(1).unwrap();
+    assert!(links.verify("exports/a.json", token));
+    assert!(!links.verify("exports/b.json", token));
+    assert!(!links.verify("exports/a.json", &format!("{}x", token)));
+
+    let other = StorageLinks::new(&test_config(json!({ "jwt_secret": "a-completely-different-secret-of-enough-length" })));
+    assert!(!other.verify("exports/a.json", token));
+}
+
+#[tokio::test]
+#[ignore = "starts a MinIO container; needs Docker (cargo test -- --ignored)"]
+async fn s3_backend_round_trips_objects_on_minio() {
+    let minio = MinIO::default().start().await.expect("MinIO container");
+    let port = minio.get_host_port_ipv4(9000).await.unwrap();
+    let endpoint = format!("http://{}:{}", minio.get_host().await.unwrap(), port);
+
+    // object_store cannot create buckets, so use the MinIO client in the image
+    minio
+        .exec(
+            ExecCommand::new([
+                "sh",
+                "-c",
+                &format!(
+                    "mc alias set local http://127.0.0.1:9000 minioadmin minioadmin && mc mb local/{}",
+                    BUCKET
+                ),
+            ])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await
+        .expect("create bucket");
+
+    let config = test_config(json!({
+        "storage_backend": "s3",
+        "storage_s3_bucket": BUCKET,
+        "storage_s3_endpoint": endpoint,
+        "storage_s3_access_key_id": "minioadmin",
+        "storage_s3_secret_access_key": "minioadmin",
+    }));
+    let storage = S3Storage::from_config(&config).expect("S3 storage");
+
+    exercise(&storage).await;
+
+    // Presigned URLs work without credentials
+    storage.put("exports/shared.txt", Bytes::from_static(b"hello"), "text/plain").await.unwrap();
+    let url = storage.presign("exports/shared.txt", Duration::from_secs(60)).await.unwrap();
+    let response = reqwest::get(&url).await.unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(
+        response.headers().get("content-type").and_then(|v| v.to_str().ok()),
+        Some("text/plain")
+    );
+    assert_eq!(response.text().await.unwrap(), "hello");
+}
@@ -0,0 +1,49 @@
+use portfolio_backend::utils::tag_index::{TagIndex, Tokenizer};
+
+fn corpus() -> Vec<(String, Vec<String>)> {
+    [
+        ("Ownership and borrowing in Rust keep memory safe without a garbage collector.", vec!["rust"]),
+        ("Async Rust with tokio: futures, executors and the borrow checker.", vec!["rust", "async"]),
+        ("Styling components with CSS grid and flexbox layouts.", vec!["css"]),
+        ("Deploying a Postgres database with backups and replicas.", vec!["postgres"]),
+    ]
+    .into_iter()
+    .map(|(text, tags)| (text.to_string(), tags.into_iter().map(String::from).collect()))
+    .collect()
+}
+
+#[test]
+fn related_existing_tags_rank_first() {
+    let index = TagIndex::build(Tokenizer::new(&[]), &corpus());
+    let suggestions = index.suggest("Writing an async web server in Rust on top of tokio and hyper.", 5);
+
+    let mut top: Vec<&str> = suggestions[..2].iter().map(|s| s.tag.as_str()).collect();
+    top.sort_unstable();
+    assert_eq!(top, ["async", "rust"]);
+    assert!(suggestions[..2].iter().all(|s| s.existing));
+    assert_eq!(suggestions.iter().find(|s| s.tag == "rust").map(|s| s.uses), Some(2));
+    assert!(!suggestions.iter().any(|s| s.tag == "css"));
+}
+
+#[test]
+fn unseen_terms_become_new_tags_and_stopwords_never_do() {
+    let index = TagIndex::build(Tokenizer::new(&["hyper".to_string()]), &corpus());
+    let suggestions = index.suggest("# Kubernetes\n\nThe kubernetes operator was used with hyper.\n\n```\nfn main() {}\n```", 10);
+
+    let kubernetes = suggestions.iter().find(|s| s.tag == "kubernetes").expect("kubernetes suggested");
+    assert!(!kubernetes.existing);
+    assert_eq!(kubernetes.uses, 0);
+    for excluded in ["the", "was", "with", "used", "hyper", "main"] {
+        assert!(!suggestions.iter().any(|s| s.tag == excluded), "{} suggested", excluded);
+    }
+}
+
+#[test]
+fn empty_corpus_still_suggests_from_the_draft() {
+    let index = TagIndex::build(Tokenizer::new(&[]), &[]);
+    let suggestions = index.suggest("Benchmarks of benchmarks", 3);
+
+    assert_eq!(index.documents(), 0);
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].tag, "benchmarks");
+}
@@ -0,0 +1,77 @@
+use portfolio_backend::utils::text_diff::unified_diff;
+use proptest::prelude::*;
+
+/// Few distinct lines, so the two sides share plenty and the diff has work to do
+fn document() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop_oneof![Just("a"), Just("b"), Just("c"), Just(""), Just("# d")], 0..30)
+        .prop_map(|lines| lines.join("\n"))
+}
+
+fn lines_of(text: &str) -> Vec<String> {
+    text.lines().map(String::from).collect()
+}
+
+/// The two sides read back from a diff's body lines
+fn sides(text: &str) -> (Vec<String>, Vec<String>) {
+    let (mut old, mut new) = (Vec::new(), Vec::new());
+    for line in text.lines().skip(2).filter(|l| !l.starts_with("@@")) {
+        let (prefix, rest) = line.split_at(1);
+        match prefix {
+            " " => {
+                old.push(rest.to_string());
+                new.push(rest.to_string());
+            }
+            "-" => old.push(rest.to_string()),
+            "+" => new.push(rest.to_string()),
+            other => panic!("unexpected line prefix {:?}", other),
+        }
+    }
+    (old, new)
+}
+
+fn lcs_len(a: &[String], b: &[String]) -> usize {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in 0..a.len() {
+        for j in 0..b.len() {
+            table[i + 1][j + 1] = if a[i] == b[j] { table[i][j] + 1 } else { table[i][j + 1].max(table[i + 1][j]) };
+        }
+    }
+    table[a.len()][b.len()]
+}
+
+proptest! {
+    #[test]
+    fn full_context_diff_rebuilds_both_sides(old in document(), new in document()) {
+        let diff = unified_diff(&old, &new, "a", "b", usize::MAX);
+        let (old, new) = (lines_of(&old), lines_of(&new));
+
+        if old == new {
+            prop_assert!(diff.text.is_empty());
+        } else {
+            let (rebuilt_old, rebuilt_new) = sides(&diff.text);
+            prop_assert_eq!(rebuilt_old, old);
+            prop_assert_eq!(rebuilt_new, new);
+        }
+    }
+
+    #[test]
+    fn edit_script_is_minimal(old in document(), new in document()) {
+        let diff = unified_diff(&old, &new, "a", "b", 3);
+        let (old, new) = (lines_of(&old), lines_of(&new));
+        let common = lcs_len(&old, &new);
+
+        prop_assert_eq!(diff.deletions, old.len() - common);
+        prop_assert_eq!(diff.additions, new.len() - common);
+    }
+}
+
+#[test]
+fn hunk_headers_follow_unified_format() {
+    let diff = unified_diff("one\ntwo\nthree\n", "one\n2\nthree\nfour\n", "revision 1", "revision 2", 1);
+
+    assert_eq!(
+        diff.text,
+        "--- revision 1\n+++ revision 2\n@@ -1,3 +1,4 @@\n one\n-two\n+2\n three\n+four\n"
+    );
+    assert_eq!((diff.additions, diff.deletions), (2, 1));
+}
@@ -0,0 +1,70 @@
+use chrono::Utc;
+use portfolio_backend::entities::title_test::{
+    pick_variant, total_weight, SetTitleTestRequest, TitleTestReport, TitleVariant, TitleVariantRequest, TitleVariantStats,
+};
+use uuid::Uuid;
+
+fn variant(post_id: Uuid, variant: i16, title: Option<&str>, weight: i16) -> TitleVariant {
+    TitleVariant {
+        post_id,
+        variant,
+        tenant_id: Uuid::nil(),
+        title: title.map(str::to_string),
+        weight,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn rolls_land_on_variants_in_proportion_to_weight() {
+    let post = Uuid::new_v4();
+    let variants = [variant(post, 0, None, 0), variant(post, 1, Some("A"), 30), variant(post, 2, Some("B"), 70)];
+    assert_eq!(total_weight(&variants), 100);
+
+    let landed: Vec<i16> = (0..total_weight(&variants))
+        .map(|roll| pick_variant(&variants, roll).map(|v| v.variant).unwrap())
+        .collect();
+    assert_eq!(landed.iter().filter(|&&v| v == 0).count(), 0);
+    assert_eq!(landed.iter().filter(|&&v| v == 1).count(), 30);
+    assert_eq!(landed.iter().filter(|&&v| v == 2).count(), 70);
+    assert!(pick_variant(&variants, 100).is_none());
+}
+
+#[test]
+fn request_numbers_the_original_title_zero() {
+    let request = SetTitleTestRequest {
+        original_weight: None,
+        variants: vec![
+            TitleVariantRequest { title: "First take".to_string(), weight: 25 },
+            TitleVariantRequest { title: "Second take".to_string(), weight: 25 },
+        ],
+    };
+
+    let inserts = request.to_inserts();
+    let numbered: Vec<(i16, Option<&str>, i16)> =
+        inserts.iter().map(|v| (v.variant, v.title.as_deref(), v.weight)).collect();
+    assert_eq!(
+        numbered,
+        [(0, None, 50), (1, Some("First take"), 25), (2, Some("Second take"), 25)]
+    );
+}
+
+#[test]
+fn report_fills_in_the_current_title_and_picks_the_best_ctr() {
+    let post = Uuid::new_v4();
+    let variants = [variant(post, 0, None, 50), variant(post, 1, Some("Alternative"), 50)];
+    let stats = [
+        TitleVariantStats { variant: 0, impressions: 100, clicks: 5 },
+        TitleVariantStats { variant: 1, impressions: 50, clicks: 5 },
+    ];
+
+    let report = TitleTestReport::build("Original", &variants, &stats).unwrap();
+    assert_eq!(report.variants[0].title, "Original");
+    assert_eq!(report.variants[0].ctr, 0.05);
+    assert_eq!(report.variants[1].ctr, 0.1);
+    assert_eq!(report.leader, Some(1));
+
+    let fresh = TitleTestReport::build("Original", &variants, &[]).unwrap();
+    assert_eq!(fresh.leader, None);
+    assert!(TitleTestReport::build("Original", &[], &[]).is_none());
+}
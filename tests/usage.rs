@@ -0,0 +1,182 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use common::tenant;
+use portfolio_backend::{
+    cache::usage::MockRedisUsageProbe,
+    entities::usage::{EmailUsage, RedisUsage, TableUsage, UsageReport},
+    errors::AppError,
+    mailer::email::{CountingMailer, EmailMessage, EmailSendCounter, MockMailer},
+    repositories::usage::MockUsageRepository,
+    storage::object::{MockObjectStorage, StoredObject},
+    use_cases::usage::UsageReports,
+};
+use uuid::Uuid;
+
+fn object(key: &str, size: u64) -> StoredObject {
+    StoredObject { key: key.to_string(), size }
+}
+
+fn table(table: &str, rows: i64) -> TableUsage {
+    TableUsage { table: table.to_string(), rows, table_bytes: 8192 }
+}
+
+/// Stored reports and email counts, as the tables hold them
+#[derive(Clone, Default)]
+struct Stored {
+    reports: Arc<Mutex<BTreeMap<Uuid, UsageReport>>>,
+    sent: Arc<Mutex<(i64, i64)>>,
+}
+
+fn usage_repo(stored: &Stored, email_store_up: bool) -> MockUsageRepository {
+    let mut repo = MockUsageRepository::new();
+    repo.expect_table_usage()
+        .returning(|_| Ok(vec![table("blog_posts", 12), table("api_tokens", 0), table("audit_log", 40)]));
+    repo.expect_database_bytes().returning(|| Ok(9_000_000));
+
+    let sent = stored.sent.clone();
+    repo.expect_add_email_sends().returning(move |_, s, f| {
+        if !email_store_up {
+            return Err(AppError::ServiceUnavailable("Database unavailable".to_string()));
+        }
+        let mut sent = sent.lock().unwrap();
+        *sent = (sent.0 + s, sent.1 + f);
+        Ok(())
+    });
+    let sent = stored.sent.clone();
+    repo.expect_email_usage().returning(move |month| {
+        let (sent, failed) = *sent.lock().unwrap();
+        Ok(EmailUsage { month, sent, failed })
+    });
+
+    let reports = stored.reports.clone();
+    repo.expect_save_report().returning(move |report| {
+        reports.lock().unwrap().insert(report.tenant_id, report.clone());
+        Ok(())
+    });
+    let reports = stored.reports.clone();
+    repo.expect_latest_report().returning(move |id| Ok(reports.lock().unwrap().get(id).cloned()));
+
+    repo
+}
+
+#[tokio::test]
+async fn reports_attribute_objects_rows_and_redis_keys_to_the_site() {
+    let ada = tenant();
+    let stored = Stored::default();
+
+    let mut storage = MockObjectStorage::new();
+    let ada_id = ada.id;
+    storage.expect_list().returning(move |_| {
+        Ok(vec![
+            object("exports/ada/content-20250101.json", 300),
+            object("exports/ada/site-20250101/index.html", 200),
+            object(&format!("audit-archive/{}/20250101.jsonl", ada_id), 1_000),
+            object("qr/ada/abc.png", 50),
+            object("exports/grace/content-20250101.json", 700),
+            object("doctor-probe.txt", 2),
+        ])
+    });
+    storage.expect_describe().returning(|| "s3://sites".to_string());
+
+    let mut redis = MockRedisUsageProbe::new();
+    redis.expect_namespace_usage().returning(|pattern, _| {
+        Ok(RedisUsage { namespace: pattern.to_string(), keys: 3, bytes: 4096, used_memory: Some(1 << 20), truncated: false })
+    });
+
+    let usage = UsageReports::new(
+        usage_repo(&stored, true),
+        Some(Arc::new(storage)),
+        Some(Arc::new(redis)),
+        EmailSendCounter::default(),
+    );
+    let report = usage.refresh(&ada).await.unwrap();
+
+    let storage = report.storage.as_ref().unwrap();
+    assert_eq!((storage.objects, storage.bytes), (4, 1_550));
+    assert_eq!((storage.total_objects, storage.total_bytes), (6, 2_252));
+    let prefixes: Vec<_> = storage.by_prefix.iter().map(|p| (p.prefix.as_str(), p.objects, p.bytes)).collect();
+    assert_eq!(prefixes, vec![("audit-archive", 1, 1_000), ("exports", 2, 500), ("qr", 1, 50)]);
+
+    let database = report.database.as_ref().unwrap();
+    let tables: Vec<_> = database.tables.iter().map(|t| (t.table.as_str(), t.rows)).collect();
+    assert_eq!(tables, vec![("audit_log", 40), ("blog_posts", 12)], "largest first, empty tables left out");
+    assert_eq!(database.rows, 52);
+
+    assert_eq!(report.redis.as_ref().unwrap().namespace, "t:ada:*");
+    assert!(report.unavailable.is_empty());
+    assert_eq!(stored.reports.lock().unwrap().get(&ada.id), Some(&report));
+}
+
+#[tokio::test]
+async fn emails_sent_through_the_mailer_are_counted_once_and_kept_when_not_stored() {
+    let ada = tenant();
+    let counter = EmailSendCounter::default();
+
+    let mut inner = MockMailer::new();
+    inner.expect_send().returning(|message| match message.to.as_str() {
+        "bounce@example.com" => Err(AppError::ServiceUnavailable("Mail relay rejected message".to_string())),
+        _ => Ok(()),
+    });
+    let mailer = CountingMailer::wrap(Arc::new(inner), counter.clone());
+    for to in ["a@example.com", "b@example.com", "bounce@example.com"] {
+        let message = EmailMessage {
+            from: "site@ada.dev".to_string(),
+            to: to.to_string(),
+            subject: "Hello".to_string(),
+            text: "Hi".to_string(),
+            html: None,
+            headers: BTreeMap::new(),
+        };
+        let _ = mailer.send(&message).await;
+    }
+
+    // The store is down: the counts stay in memory for the next run
+    let stored = Stored::default();
+    let usage = UsageReports::new(usage_repo(&stored, false), None, None, counter.clone());
+    let report = usage.refresh(&ada).await.unwrap();
+    assert_eq!(report.email, None);
+    assert!(report.unavailable.iter().any(|u| u.starts_with("email:")));
+
+    let usage = UsageReports::new(usage_repo(&stored, true), None, None, counter.clone());
+    let email = usage.refresh(&ada).await.unwrap().email.unwrap();
+    assert_eq!((email.sent, email.failed), (2, 1));
+
+    // Already stored, so not added again
+    let email = usage.refresh(&ada).await.unwrap().email.unwrap();
+    assert_eq!((email.sent, email.failed), (2, 1));
+}
+
+#[tokio::test]
+async fn unmeasurable_parts_are_reported_and_the_stored_report_is_served() {
+    let ada = tenant();
+    let stored = Stored::default();
+
+    let mut storage = MockObjectStorage::new();
+    storage.expect_list().returning(|_| Err(AppError::ServiceUnavailable("Object storage unavailable".to_string())));
+    storage.expect_describe().returning(|| "s3://sites".to_string());
+    let mut redis = MockRedisUsageProbe::new();
+    redis.expect_namespace_usage()
+        .times(1)
+        .returning(|_, _| Err(AppError::ServiceUnavailable("Namespace usage is not measured on a Redis Cluster".to_string())));
+
+    let usage = UsageReports::new(
+        usage_repo(&stored, true),
+        Some(Arc::new(storage)),
+        Some(Arc::new(redis)),
+        EmailSendCounter::default(),
+    );
+
+    // Nothing stored yet, so the first read measures
+    let report = usage.report(&ada).await.unwrap();
+    assert_eq!((report.storage.as_ref(), report.redis.as_ref()), (None, None));
+    assert!(report.database.is_some());
+    assert_eq!(report.unavailable.len(), 2);
+
+    // Later reads are served from the store without measuring again
+    assert_eq!(usage.report(&ada).await.unwrap(), report);
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::Utc;
+use common::test_config;
+use portfolio_backend::{
+    auth::{
+        jwt::JwtService,
+        password::{check_hash_format, hash_password, needs_rehash, verify_password, PasswordHasherPool},
+    },
+    entities::{
+        user::{AuthoredContentPolicy, LoginUser, PasswordHashAlgorithm, User},
+        user_import::{UserImportFormat, UserRole},
+    },
+    errors::AppError,
+    repositories::user::MockUserRepository,
+    use_cases::{
+        auth::AuthHandler,
+        user_import::{parse_user_import, UserImport},
+    },
+};
+use serde_json::json;
+use uuid::Uuid;
+
+const PASSWORD: &str = "Imported#Secret42";
+
+fn bcrypt_hash(password: &str) -> String {
+    bcrypt::hash(password, 4).expect("bcrypt hash")
+}
+
+fn existing_user(tenant_id: Uuid, email: &str, password_hash: String) -> User {
+    let now = Utc::now();
+    User {
+        id: Uuid::new_v4(),
+        tenant_id,
+        email: email.to_string(),
+        username: None,
+        password_hash,
+        is_admin: false,
+        is_verified: true,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        deleted_by: None,
+        is_editor: false,
+        display_name: None,
+        avatar_url: None,
+        is_system: false,
+    }
+}
+
+#[test]
+fn bcrypt_hashes_verify_and_are_marked_for_rehash() {
+    let bcrypt = bcrypt_hash(PASSWORD);
+    let argon2 = hash_password(PASSWORD).unwrap();
+
+    assert_eq!(PasswordHashAlgorithm::detect(&bcrypt), Some(PasswordHashAlgorithm::Bcrypt));
+    assert!(verify_password(PASSWORD, &bcrypt).unwrap());
+    assert!(!verify_password("wrong", &bcrypt).unwrap());
+    assert!(needs_rehash(&bcrypt));
+
+    assert!(verify_password(PASSWORD, &argon2).unwrap());
+    assert!(!needs_rehash(&argon2));
+
+    assert!(check_hash_format(PasswordHashAlgorithm::Bcrypt, &bcrypt).is_ok());
+    assert!(check_hash_format(PasswordHashAlgorithm::Argon2, &bcrypt).is_err());
+    assert!(check_hash_format(PasswordHashAlgorithm::Bcrypt, "$2b$12$short").is_err());
+}
+
+#[tokio::test]
+async fn csv_import_reports_created_conflicting_and_skipped_rows() {
+    let tenant_id = Uuid::new_v4();
+    let hash = bcrypt_hash(PASSWORD);
+    let csv = format!(
+        "email,username,password_hash,hash_algorithm,roles,verified\n\
+         ada@example.com,ada,{hash},bcrypt,admin;editor,\n\
+         taken@example.com,,{hash},bcrypt,,\n\
+         ADA@example.com,,{hash},bcrypt,,\n\
+         bob@example.com,,{hash},argon2,,\n\
+         carol@example.com,,{hash},bcrypt,owner,\n\
+         not-an-email,,{hash},bcrypt,,\n\
+         dan@example.com,,{hash},md5,,\n\
+         erin@example.com,erin,{hash},bcrypt,User,false\n"
+    );
+
+    let rows = parse_user_import(UserImportFormat::Csv, csv.as_bytes()).unwrap();
+
+    let created = Arc::new(Mutex::new(Vec::new()));
+    let mut repo = MockUserRepository::new();
+    repo.expect_get_user_by_email().returning(move |tid, email| {
+        Ok((email == "taken@example.com").then(|| existing_user(*tid, email, String::new())))
+    });
+    let store = created.clone();
+    repo.expect_create_user().returning(move |_, insert| {
+        store.lock().unwrap().push((insert.email.clone(), insert.password_hash.clone(), insert.is_admin, insert.is_verified));
+        Ok(Uuid::new_v4())
+    });
+    repo.expect_set_editor().times(1).returning(|tid, id, editor| {
+        assert!(editor);
+        let mut user = existing_user(*tid, "ada@example.com", String::new());
+        user.id = *id;
+        user.is_editor = true;
+        Ok(user)
+    });
+
+    let report = UserImport::new(repo).import(tenant_id, rows, false).await.unwrap();
+
+    assert_eq!(report.total_rows, 8);
+    let imported: Vec<_> = report.imported.iter().map(|u| (u.row, u.email.as_str())).collect();
+    assert_eq!(imported, vec![(1, "ada@example.com"), (8, "erin@example.com")]);
+    assert!(report.imported.iter().all(|u| u.id.is_some()));
+    assert_eq!(report.imported[0].roles, vec![UserRole::Admin, UserRole::Editor]);
+
+    let conflicts: Vec<_> = report.conflicts.iter().map(|c| c.row).collect();
+    assert_eq!(conflicts, vec![2, 3]);
+
+    let skipped: Vec<_> = report.skipped.iter().map(|s| (s.row, s.email.as_deref())).collect();
+    assert_eq!(skipped, vec![
+        (4, Some("bob@example.com")),
+        (5, Some("carol@example.com")),
+        (6, Some("not-an-email")),
+        (7, Some("dan@example.com")),
+    ]);
+
+    let created = created.lock().unwrap();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0], ("ada@example.com".to_string(), hash.clone(), true, true));
+    assert_eq!(created[1], ("erin@example.com".to_string(), hash, false, false));
+}
+
+#[tokio::test]
+async fn json_dry_run_writes_nothing() {
+    let body = json!([
+        { "email": "ada@example.com", "password_hash": bcrypt_hash(PASSWORD), "hash_algorithm": "bcrypt", "roles": ["editor"] },
+        { "email": "bob@example.com", "password_hash": hash_password(PASSWORD).unwrap(), "hash_algorithm": "argon2" },
+        { "email": "carol@example.com", "hash_algorithm": "bcrypt" },
+    ]);
+
+    let rows = parse_user_import(UserImportFormat::Json, body.to_string().as_bytes()).unwrap();
+
+    let mut repo = MockUserRepository::new();
+    repo.expect_get_user_by_email().returning(|_, _| Ok(None));
+    repo.expect_create_user().never();
+    repo.expect_set_editor().never();
+
+    let report = UserImport::new(repo).import(Uuid::new_v4(), rows, true).await.unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.imported.len(), 2);
+    assert!(report.imported.iter().all(|u| u.id.is_none()));
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].email.as_deref(), Some("carol@example.com"));
+    assert!(report.skipped[0].reason.contains("password_hash"));
+}
+
+#[test]
+fn unreadable_exports_are_rejected_whole() {
+    assert!(matches!(
+        parse_user_import(UserImportFormat::Json, br#"{"users": []}"#),
+        Err(AppError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        parse_user_import(UserImportFormat::Csv, b"mail,hash\nada@example.com,x\n"),
+        Err(AppError::InvalidInput(_))
+    ));
+}
+
+#[tokio::test]
+async fn first_login_with_an_imported_hash_rehashes_to_argon2id() {
+    let tenant_id = Uuid::new_v4();
+    let user = existing_user(tenant_id, "ada@example.com", bcrypt_hash(PASSWORD));
+    let user_id = user.id;
+
+    let saved = Arc::new(Mutex::new(None));
+    let mut repo = MockUserRepository::new();
+    repo.expect_get_user_by_email().returning(move |_, _| Ok(Some(user.clone())));
+    let store = saved.clone();
+    repo.expect_set_password().times(1).returning(move |tid, id, hash| {
+        assert_eq!(*id, user_id);
+        *store.lock().unwrap() = Some(hash.to_string());
+        Ok(existing_user(*tid, "ada@example.com", hash.to_string()))
+    });
+
+    let config = test_config(json!({}));
+    let auth = AuthHandler::new(
+        repo,
+        JwtService::new(&config),
+        PasswordHasherPool::new(1, Duration::from_secs(5)),
+        AuthoredContentPolicy::Reassign,
+        None,
+    );
+
+    let login = LoginUser { email: "ada@example.com".to_string(), password: PASSWORD.to_string() };
+    auth.login(tenant_id, login).await.expect("bcrypt password accepted");
+
+    let rehashed = saved.lock().unwrap().clone().expect("hash replaced");
+    assert!(rehashed.starts_with("$argon2id$"));
+    assert!(verify_password(PASSWORD, &rehashed).unwrap());
+}
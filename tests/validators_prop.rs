@@ -0,0 +1,57 @@
+use portfolio_backend::entities::blog_post::{slug_from_title, validate_slug, validate_tags};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn well_formed_slugs_are_accepted(slug in "[a-z0-9]{1,10}(-[a-z0-9]{1,10}){0,5}") {
+        prop_assert!(validate_slug(&slug).is_ok());
+    }
+
+    #[test]
+    fn slugs_with_uppercase_are_rejected(prefix in "[a-z0-9]{0,10}", upper in "[A-Z]", suffix in "[a-z0-9]{0,10}") {
+        let slug = format!("{prefix}{upper}{suffix}");
+        prop_assert!(validate_slug(&slug).is_err());
+    }
+
+    #[test]
+    fn slugs_with_edge_or_double_hyphens_are_rejected(body in "[a-z0-9]{1,10}") {
+        let leading = format!("-{body}");
+        let trailing = format!("{body}-");
+        let doubled = format!("{body}--{body}");
+        prop_assert!(validate_slug(&leading).is_err());
+        prop_assert!(validate_slug(&trailing).is_err());
+        prop_assert!(validate_slug(&doubled).is_err());
+    }
+
+    #[test]
+    fn generated_slugs_always_validate(title in "\\PC{0,120}") {
+        if let Some(slug) = slug_from_title(&title) {
+            prop_assert!(validate_slug(&slug).is_ok(), "generated slug {:?} rejected", slug);
+        }
+    }
+
+    #[test]
+    fn bounded_alphanumeric_tags_are_accepted(tags in prop::collection::vec("[a-zA-Z0-9-]{1,30}", 0..=10)) {
+        prop_assert!(validate_tags(&tags).is_ok());
+    }
+
+    #[test]
+    fn more_than_ten_tags_are_rejected(tags in prop::collection::vec("[a-z]{1,5}", 11..20)) {
+        prop_assert!(validate_tags(&tags).is_err());
+    }
+
+    #[test]
+    fn tags_with_forbidden_chars_are_rejected(
+        head in "[a-z]{0,10}",
+        bad in "[ _./#<>]",
+        tail in "[a-z]{0,10}"
+    ) {
+        let tags = vec![format!("{head}{bad}{tail}")];
+        prop_assert!(validate_tags(&tags).is_err());
+    }
+
+    #[test]
+    fn overlong_tags_are_rejected(tag in "[a-z]{31,60}") {
+        prop_assert!(validate_tags(&[tag]).is_err());
+    }
+}
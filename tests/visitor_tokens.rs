@@ -0,0 +1,85 @@
+mod common;
+
+use chrono::Utc;
+use common::{test_config, JWT_SECRET};
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use portfolio_backend::{auth::{jwt::JwtService, visitor::VisitorTokens}, entities::user::User};
+use serde_json::json;
+use sha2::Sha512;
+use uuid::Uuid;
+
+fn tokens() -> VisitorTokens {
+    VisitorTokens::from_config(&test_config(json!({})))
+}
+
+fn signed(key: &[u8], claims: serde_json::Value) -> String {
+    encode(&Header::new(Algorithm::HS512), &claims, &EncodingKey::from_secret(key)).unwrap()
+}
+
+fn visitor_key() -> Vec<u8> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(JWT_SECRET.as_bytes()).unwrap();
+    mac.update(b"visitor");
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[test]
+fn issued_tokens_verify_for_their_tenant_only() {
+    let tokens = tokens();
+    let tenant_id = Uuid::new_v4();
+    let token = tokens.issue(tenant_id, "visitor-1").unwrap();
+
+    assert_eq!(tokens.verify(tenant_id, &token).as_deref(), Some("visitor-1"));
+    assert_eq!(tokens.verify(Uuid::new_v4(), &token), None);
+    assert_eq!(tokens.verify(tenant_id, "not-a-token"), None);
+}
+
+#[test]
+fn access_tokens_are_not_visitor_tokens() {
+    let config = test_config(json!({}));
+    let now = Utc::now();
+    let user = User {
+        id: Uuid::new_v4(),
+        tenant_id: Uuid::new_v4(),
+        email: "ada@example.com".to_string(),
+        username: None,
+        password_hash: String::new(),
+        is_admin: true,
+        is_verified: true,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        deleted_by: None,
+        is_editor: false,
+        display_name: None,
+        avatar_url: None,
+        is_system: false,
+    };
+    let access = JwtService::new(&config).create_jwt(&user, 0).unwrap();
+
+    assert_eq!(tokens().verify(user.tenant_id, &access), None);
+
+    // Even shaped like a visitor token, the access token key does not sign one
+    let forged = signed(
+        JWT_SECRET.as_bytes(),
+        json!({ "sub": user.id, "tid": user.tenant_id, "aud": "visitor", "exp": now.timestamp() + 600, "iat": now.timestamp() }),
+    );
+    assert_eq!(tokens().verify(user.tenant_id, &forged), None);
+}
+
+#[test]
+fn tokens_without_the_visitor_audience_are_rejected() {
+    let tenant_id = Uuid::new_v4();
+    let now = Utc::now().timestamp();
+    let claims = |aud: Option<&str>| {
+        let mut claims = json!({ "sub": "visitor-1", "tid": tenant_id, "exp": now + 600, "iat": now });
+        if let Some(aud) = aud {
+            claims["aud"] = json!(aud);
+        }
+        claims
+    };
+
+    assert_eq!(tokens().verify(tenant_id, &signed(&visitor_key(), claims(Some("visitor")))).as_deref(), Some("visitor-1"));
+    assert_eq!(tokens().verify(tenant_id, &signed(&visitor_key(), claims(None))), None);
+    assert_eq!(tokens().verify(tenant_id, &signed(&visitor_key(), claims(Some("portfolio")))), None);
+}